@@ -0,0 +1,291 @@
+//! Combined action plan for `analyze --mode all`
+//!
+//! Runs of the top, dominators, dead and monos analyses often flag the same
+//! thing (a large data segment shows up in both top and dominators), and the
+//! asset and dependency analyses report in their own units. The plan merges
+//! everything into one list of actions, de-duplicated by rule and subject
+//! (or description, for recommendations without a rule id), and sorted by
+//! estimated savings so the most valuable fix comes first.
+
+use super::asset_metrics::ScanResults;
+use super::asset_types::AssetPriority;
+use super::deps_types::DependencyReport;
+use super::report_utils::SizedEntry;
+use super::twiggy::AnalysisResults;
+use crate::fmt::format_bytes;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// One entry of the action plan
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Action {
+    /// Priority: P0 (Critical), P1 (High), P2 (Medium), P3 (Low)
+    pub priority: String,
+    /// What to do
+    pub description: String,
+    /// Estimated savings in bytes
+    pub estimated_savings_bytes: u64,
+    /// Analyses that reported it (e.g. `top`, `deps`)
+    pub sources: Vec<String>,
+}
+
+impl SizedEntry for Action {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.estimated_savings_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.description
+    }
+
+    fn matches(&self, pattern: &Regex) -> bool {
+        pattern.is_match(&self.description) || self.sources.iter().any(|s| pattern.is_match(s))
+    }
+}
+
+/// Prioritized actions from every analysis
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ActionPlan {
+    /// Size of the analyzed WASM file
+    pub total_size_bytes: u64,
+    /// Actions, largest estimated savings first
+    pub actions: Vec<Action>,
+    /// Analyses that could not run, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl ActionPlan {
+    /// Sum of the estimated savings of every action
+    ///
+    /// Savings overlap (externalizing an asset also shrinks its data
+    /// segment), so this is an upper bound.
+    pub fn total_savings_bytes(&self) -> u64 {
+        self.actions.iter().map(|a| a.estimated_savings_bytes).sum()
+    }
+}
+
+/// Collects findings and builds an [`ActionPlan`]
+#[derive(Debug, Default)]
+pub struct ActionPlanBuilder {
+    total_size_bytes: u64,
+    /// Dedup key -> action
+    actions: BTreeMap<String, Action>,
+    warnings: Vec<String>,
+}
+
+impl ActionPlanBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the recommendations of a twiggy analysis
+    ///
+    /// Low priority notes without savings ("bundle is well-optimized") are
+    /// not actions and are left out.
+    pub fn add_analysis(&mut self, results: &AnalysisResults) {
+        self.total_size_bytes = self.total_size_bytes.max(results.total_size_bytes);
+        for rec in &results.recommendations {
+            if rec.priority == "P3" && rec.estimated_savings_bytes == 0 {
+                continue;
+            }
+            let key = if rec.rule.is_empty() {
+                format!("description:{}", rec.description)
+            } else {
+                format!(
+                    "{}:{}",
+                    rec.rule,
+                    rec.subject.as_deref().unwrap_or_default()
+                )
+            };
+            self.add(
+                key,
+                &rec.priority,
+                &rec.description,
+                rec.estimated_savings_bytes,
+                &results.mode,
+            );
+        }
+    }
+
+    /// Add an externalization action for every critical and high priority asset
+    pub fn add_assets(&mut self, results: &ScanResults) {
+        for priority in [AssetPriority::Critical, AssetPriority::High] {
+            for asset in results
+                .assets_by_priority
+                .get(&priority)
+                .into_iter()
+                .flatten()
+            {
+                let description = format!(
+                    "Externalize {} ({}) embedded at {}",
+                    asset.file_path,
+                    format_bytes(asset.size_bytes),
+                    asset.source_location
+                );
+                self.add(
+                    format!("asset:{}", asset.file_path),
+                    asset_priority(priority),
+                    &description,
+                    asset.size_bytes,
+                    "assets",
+                );
+            }
+        }
+    }
+
+    /// Add an action for every dependency issue
+    ///
+    /// Savings are the middle of the estimated size impact range.
+    pub fn add_dependencies(&mut self, report: &DependencyReport) {
+        for issue in &report.issues {
+            let savings_bytes = issue
+                .size_impact_kb
+                .map_or(0, |(low, high)| (low as u64 + high as u64) / 2 * 1024);
+            self.add(
+                format!("dependency:{}", issue.package),
+                &issue.severity.to_string(),
+                &format!("{}: {} {}", issue.package, issue.issue, issue.suggestion),
+                savings_bytes,
+                "deps",
+            );
+        }
+    }
+
+    /// Note an analysis that could not run
+    pub fn add_warning(&mut self, warning: impl Into<String>) {
+        self.warnings.push(warning.into());
+    }
+
+    /// Build the plan, largest savings first (then by priority)
+    pub fn build(self) -> ActionPlan {
+        let mut actions: Vec<Action> = self.actions.into_values().collect();
+        actions.sort_by(|a, b| {
+            b.estimated_savings_bytes
+                .cmp(&a.estimated_savings_bytes)
+                .then_with(|| a.priority.cmp(&b.priority))
+                .then_with(|| a.description.cmp(&b.description))
+        });
+        ActionPlan {
+            total_size_bytes: self.total_size_bytes,
+            actions,
+            warnings: self.warnings,
+        }
+    }
+
+    /// Add an action, merging it into an earlier one with the same key
+    ///
+    /// A merged action keeps the higher priority and savings estimate.
+    fn add(&mut self, key: String, priority: &str, description: &str, savings: u64, source: &str) {
+        let action = self.actions.entry(key).or_insert_with(|| Action {
+            priority: priority.to_string(),
+            description: description.to_string(),
+            estimated_savings_bytes: savings,
+            sources: Vec::new(),
+        });
+        if priority < action.priority.as_str() {
+            action.priority = priority.to_string();
+        }
+        if savings > action.estimated_savings_bytes {
+            action.estimated_savings_bytes = savings;
+            action.description = description.to_string();
+        }
+        if !action.sources.iter().any(|s| s == source) {
+            action.sources.push(source.to_string());
+        }
+    }
+}
+
+/// Recommendation priority for an asset priority
+fn asset_priority(priority: AssetPriority) -> &'static str {
+    match priority {
+        AssetPriority::Critical => "P0",
+        AssetPriority::High => "P1",
+        AssetPriority::Medium => "P2",
+        AssetPriority::Low => "P3",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::deps_types::{DependencyIssue, IssueSeverity};
+    use crate::analyzer::twiggy::Recommendation;
+
+    fn recommendation(priority: &str, rule: &str, subject: &str, savings: u64) -> Recommendation {
+        Recommendation {
+            priority: priority.to_string(),
+            description: format!("{} {}", rule, subject),
+            estimated_savings_bytes: savings,
+            estimated_savings_percent: 0.0,
+            rule: rule.to_string(),
+            subject: Some(subject.to_string()),
+        }
+    }
+
+    fn analysis(mode: &str, recommendations: Vec<Recommendation>) -> AnalysisResults {
+        AnalysisResults {
+            total_size_bytes: 1_000_000,
+            mode: mode.to_string(),
+            items: Vec::new(),
+            limit: None,
+            recommendations,
+            mono_groups: None,
+        }
+    }
+
+    #[test]
+    fn test_build_merges_overlapping_findings_and_sorts_by_savings() {
+        let mut builder = ActionPlanBuilder::new();
+        builder.add_analysis(&analysis(
+            "top",
+            vec![recommendation(
+                "P2",
+                "twiggy.large_data_segment",
+                "data[0]",
+                50_000,
+            )],
+        ));
+        builder.add_analysis(&analysis(
+            "dominators",
+            vec![
+                recommendation("P1", "twiggy.large_data_segment", "data[0]", 40_000),
+                recommendation("P1", "twiggy.symbol_dominates", "main", 30_000),
+                recommendation("P3", "twiggy.mono_minimal", "", 0),
+            ],
+        ));
+        builder.add_dependencies(&DependencyReport {
+            total_deps: 1,
+            direct_deps: 1,
+            issues: vec![DependencyIssue {
+                package: "regex".to_string(),
+                version: "1".to_string(),
+                severity: IssueSeverity::Critical,
+                issue: "Unicode tables".to_string(),
+                suggestion: "Use regex-lite".to_string(),
+                size_impact_kb: Some((100, 200)),
+                savings_percent: None,
+                verify_id: None,
+            }],
+            duplicates: Default::default(),
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
+        });
+        builder.add_warning("assets: no Cargo.toml");
+
+        let plan = builder.build();
+
+        assert_eq!(plan.actions.len(), 3);
+        assert_eq!(plan.actions[0].sources, ["deps"]);
+        assert_eq!(plan.actions[0].estimated_savings_bytes, 150 * 1024);
+        let segment = &plan.actions[1];
+        assert_eq!(segment.priority, "P1");
+        assert_eq!(segment.estimated_savings_bytes, 50_000);
+        assert_eq!(segment.sources, ["top", "dominators"]);
+        assert_eq!(plan.total_savings_bytes(), 150 * 1024 + 80_000);
+        assert_eq!(plan.warnings, ["assets: no Cargo.toml"]);
+    }
+}
@@ -0,0 +1,115 @@
+//! Action plan report formatting
+
+use super::action_plan::ActionPlan;
+use super::report_utils::{format_bytes, omitted_footer, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of actions listed by default
+const DEFAULT_TOP_ACTIONS: usize = 20;
+
+/// Format the action plan for console output
+pub fn format_console_report(plan: &ActionPlan) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(plan, ItemLimit::Default)
+}
+
+/// Format the action plan, limiting the actions listed
+pub fn format_console_report_with_limit(
+    plan: &ActionPlan,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("Action Plan").bold().underlined())?;
+    for warning in &plan.warnings {
+        writeln!(output, "   {} {}", style("⚠").yellow(), warning)?;
+    }
+    if plan.actions.is_empty() {
+        writeln!(
+            output,
+            "\n   {} Nothing to do: no analysis found a size reduction",
+            style("✓").green()
+        )?;
+        return Ok(output);
+    }
+
+    writeln!(
+        output,
+        "\n   {} actions for a {} module, up to {} to save (estimates overlap)",
+        style(plan.actions.len()).cyan().bold(),
+        format_bytes(plan.total_size_bytes),
+        style(format_bytes(plan.total_savings_bytes()))
+            .yellow()
+            .bold()
+    )?;
+
+    let shown = limit.visible(plan.actions.len(), Some(DEFAULT_TOP_ACTIONS));
+    for (index, action) in plan.actions.iter().take(shown).enumerate() {
+        let priority_color = match action.priority.as_str() {
+            "P0" => console::Color::Red,
+            "P1" => console::Color::Yellow,
+            "P2" => console::Color::Blue,
+            _ => console::Color::White,
+        };
+        writeln!(
+            output,
+            "\n  {:>2}. {} {}",
+            index + 1,
+            style(format!("[{}]", action.priority))
+                .fg(priority_color)
+                .bold(),
+            action.description
+        )?;
+        let savings = if action.estimated_savings_bytes > 0 {
+            format!("~{}", format_bytes(action.estimated_savings_bytes))
+        } else {
+            "unknown".to_string()
+        };
+        writeln!(
+            output,
+            "      {}",
+            style(format!(
+                "Savings: {}  ·  from {}",
+                savings,
+                action.sources.join(", ")
+            ))
+            .dim()
+        )?;
+    }
+    if let Some(footer) = omitted_footer(plan.actions.len(), shown, "actions") {
+        writeln!(output, "\n  {}", style(footer).dim())?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::action_plan::Action;
+
+    #[test]
+    fn test_format_console_report_numbers_actions_with_sources() {
+        let action = |priority: &str, savings, sources: &[&str]| Action {
+            priority: priority.to_string(),
+            description: format!("fix {}", priority),
+            estimated_savings_bytes: savings,
+            sources: sources.iter().map(|s| s.to_string()).collect(),
+        };
+        let plan = ActionPlan {
+            total_size_bytes: 2048,
+            actions: vec![
+                action("P0", 1024, &["top", "dominators"]),
+                action("P3", 0, &["deps"]),
+            ],
+            warnings: vec!["assets: scan failed".to_string()],
+        };
+
+        let text = format_console_report_with_limit(&plan, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("assets: scan failed"));
+        assert!(text.contains("2 actions for a 2.00 KiB module, up to 1.00 KiB to save"));
+        assert!(text.contains("1. [P0] fix P0"));
+        assert!(text.contains("~1.00 KiB  ·  from top, dominators"));
+        assert!(text.contains("1 more actions"));
+    }
+}
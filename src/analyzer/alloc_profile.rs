@@ -0,0 +1,533 @@
+//! Allocation counting for evidence-based allocator recommendations
+//!
+//! The heuristics in [`super::allocator`] only look at crate names. This module
+//! measures what the code actually does: it generates a small harness crate that
+//! depends on the project, installs a counting `#[global_allocator]`, runs the
+//! configured smoke-test entrypoints in a debug build and reports how many heap
+//! allocations (and bytes) each entrypoint performed.
+//!
+//! # Configuration
+//!
+//! ```toml
+//! [allocator]
+//! smoke-entrypoints = ["my_crate::smoke::render", "parse_fixture"]
+//! ```
+//!
+//! Entrypoints must be public zero-argument functions. Paths without a crate
+//! prefix are resolved against the project's library crate. The library must
+//! build as an `rlib` (add `"rlib"` next to `"cdylib"` in `crate-type`).
+
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use crate::profiling::{self, ProfilePhase};
+use cargo_metadata::{MetadataCommand, TargetKind};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Directory (relative to the project root) where the harness crate is generated
+pub const HARNESS_DIR: &str = "alloc-harness";
+
+/// Prefix of the harness output lines carrying measurements
+const REPORT_PREFIX: &str = "wasm-slim-alloc";
+
+/// Average allocations per entrypoint above which a small allocator's slower
+/// allocation path is likely to cost more than its size savings are worth
+pub const HEAVY_ALLOCATION_THRESHOLD: u64 = 10_000;
+
+/// Errors that can occur while profiling allocations
+#[derive(Error, Debug)]
+pub enum AllocationProfileError {
+    /// Failed to run cargo metadata
+    #[error("Failed to run cargo metadata: {0}")]
+    Metadata(#[from] cargo_metadata::Error),
+
+    /// Project has no library target to link the harness against
+    #[error("No library target found in project; allocation profiling requires a lib crate")]
+    NoLibraryTarget,
+
+    /// No smoke entrypoints configured
+    #[error(
+        "No smoke entrypoints configured (set [allocator] smoke-entrypoints in .wasm-slim.toml)"
+    )]
+    NoEntrypoints,
+
+    /// The harness failed to build or run
+    #[error("Allocation harness failed: {0}")]
+    HarnessFailed(String),
+
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Allocation counts measured for a single entrypoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EntrypointAllocations {
+    /// Entrypoint path as configured
+    pub entrypoint: String,
+    /// Number of allocation calls (including reallocations)
+    pub allocations: u64,
+    /// Total bytes requested
+    pub bytes: u64,
+}
+
+/// Measured allocation profile across all smoke entrypoints
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AllocationProfile {
+    /// Per-entrypoint measurements, in configuration order
+    pub entrypoints: Vec<EntrypointAllocations>,
+    /// Sum of allocations over all entrypoints
+    pub total_allocations: u64,
+    /// Sum of bytes over all entrypoints
+    pub total_bytes: u64,
+}
+
+/// Allocator recommendation backed by measured allocation counts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "verdict", content = "reason", rename_all = "snake_case")]
+pub enum AllocatorAssessment {
+    /// A small allocator such as lol_alloc is a good fit
+    Recommended(String),
+    /// The workload allocates too much for a small allocator to pay off
+    NotRecommended(String),
+}
+
+impl AllocationProfile {
+    /// Build a profile from per-entrypoint measurements
+    pub fn from_entrypoints(entrypoints: Vec<EntrypointAllocations>) -> Self {
+        let total_allocations = entrypoints.iter().map(|e| e.allocations).sum();
+        let total_bytes = entrypoints.iter().map(|e| e.bytes).sum();
+        Self {
+            entrypoints,
+            total_allocations,
+            total_bytes,
+        }
+    }
+
+    /// Average number of allocations per entrypoint
+    pub fn average_allocations(&self) -> u64 {
+        if self.entrypoints.is_empty() {
+            0
+        } else {
+            self.total_allocations / self.entrypoints.len() as u64
+        }
+    }
+
+    /// Decide whether a size-optimized allocator is worth it for this workload
+    pub fn assess(&self) -> AllocatorAssessment {
+        let average = self.average_allocations();
+        if average > HEAVY_ALLOCATION_THRESHOLD {
+            AllocatorAssessment::NotRecommended(format!(
+                "Smoke entrypoints average {} allocations; a small allocator's slower allocation \
+                 path would likely cost more than its 2-5% size savings",
+                average
+            ))
+        } else {
+            AllocatorAssessment::Recommended(format!(
+                "Smoke entrypoints average {} allocations (<= {}); a size-optimized allocator \
+                 such as lol_alloc is unlikely to hurt runtime performance",
+                average, HEAVY_ALLOCATION_THRESHOLD
+            ))
+        }
+    }
+}
+
+/// Runs smoke entrypoints under a counting allocator
+///
+/// # Examples
+///
+/// ```no_run
+/// use wasm_slim::analyzer::alloc_profile::AllocationProfiler;
+///
+/// let profiler = AllocationProfiler::new(".");
+/// let profile = profiler.profile(&["my_crate::smoke".to_string()])?;
+/// println!("{} allocations", profile.total_allocations);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct AllocationProfiler<
+    FS: FileSystem = RealFileSystem,
+    CE: CommandExecutor = RealCommandExecutor,
+> {
+    project_root: PathBuf,
+    fs: FS,
+    cmd_executor: CE,
+}
+
+impl AllocationProfiler {
+    /// Create a profiler for the given project root
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self::with_executors(project_root, RealFileSystem, RealCommandExecutor)
+    }
+}
+
+impl<FS: FileSystem, CE: CommandExecutor> AllocationProfiler<FS, CE> {
+    /// Create a profiler with custom filesystem and command executors
+    pub fn with_executors(project_root: impl AsRef<Path>, fs: FS, cmd_executor: CE) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+            fs,
+            cmd_executor,
+        }
+    }
+
+    /// Profile the given entrypoints
+    ///
+    /// Resolves the project's library crate name via `cargo metadata`, then
+    /// generates and runs the harness.
+    pub fn profile(
+        &self,
+        entrypoints: &[String],
+    ) -> Result<AllocationProfile, AllocationProfileError> {
+        if entrypoints.is_empty() {
+            return Err(AllocationProfileError::NoEntrypoints);
+        }
+
+        let metadata = {
+            let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+            MetadataCommand::new()
+                .current_dir(&self.project_root)
+                .no_deps()
+                .exec()?
+        };
+        let lib_name = metadata
+            .root_package()
+            .and_then(|pkg| {
+                pkg.targets
+                    .iter()
+                    .find(|t| {
+                        t.kind
+                            .iter()
+                            .any(|k| matches!(k, TargetKind::Lib | TargetKind::RLib))
+                    })
+                    .map(|t| (pkg.name.to_string(), t.name.replace('-', "_")))
+            })
+            .ok_or(AllocationProfileError::NoLibraryTarget)?;
+
+        self.profile_crate(&lib_name.0, &lib_name.1, entrypoints)
+    }
+
+    /// Generate the harness for a known package/library name and run it
+    pub(crate) fn profile_crate(
+        &self,
+        package_name: &str,
+        lib_name: &str,
+        entrypoints: &[String],
+    ) -> Result<AllocationProfile, AllocationProfileError> {
+        let harness_dir = crate::state::scratch_dir(&self.project_root).join(HARNESS_DIR);
+        self.fs.create_dir_all(&harness_dir.join("src"))?;
+        self.fs.write(
+            &harness_dir.join("Cargo.toml"),
+            harness_manifest(package_name, &self.project_root),
+        )?;
+        self.fs.write(
+            &harness_dir.join("src").join("main.rs"),
+            harness_main(lib_name, entrypoints),
+        )?;
+
+        let manifest = harness_dir.join("Cargo.toml");
+        let output = self.cmd_executor.execute(
+            |cmd| {
+                cmd.arg("run")
+                    .arg("--quiet")
+                    .arg("--manifest-path")
+                    .arg(&manifest)
+                    .current_dir(&self.project_root)
+            },
+            "cargo",
+        )?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AllocationProfileError::HarnessFailed(
+                stderr.trim().to_string(),
+            ));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(AllocationProfile::from_entrypoints(parse_harness_output(
+            &stdout,
+        )))
+    }
+}
+
+/// Generate the harness `Cargo.toml`
+///
+/// The harness declares its own empty `[workspace]` so it never gets pulled
+/// into (or rejected by) the project's workspace.
+pub(crate) fn harness_manifest(package_name: &str, project_root: &Path) -> String {
+    format!(
+        "[package]\n\
+         name = \"wasm-slim-alloc-harness\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"2021\"\n\
+         publish = false\n\
+         \n\
+         [dependencies]\n\
+         {} = {{ path = {:?} }}\n\
+         \n\
+         [workspace]\n",
+        package_name,
+        project_root.display().to_string()
+    )
+}
+
+/// Generate the harness `main.rs` with a counting global allocator
+pub(crate) fn harness_main(lib_name: &str, entrypoints: &[String]) -> String {
+    let calls: String = entrypoints
+        .iter()
+        .map(|entry| {
+            let path = qualify_entrypoint(lib_name, entry);
+            format!("    measure({:?}, || {{ let _ = {}(); }});\n", entry, path)
+        })
+        .collect();
+
+    format!(
+        r#"use std::alloc::{{GlobalAlloc, Layout, System}};
+use std::sync::atomic::{{AtomicU64, Ordering}};
+
+struct Counting;
+
+static ALLOCS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for Counting {{
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {{
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }}
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {{
+        System.dealloc(ptr, layout)
+    }}
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {{
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }}
+}}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn measure(name: &str, f: impl FnOnce()) {{
+    ALLOCS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    f();
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    println!("{prefix} entry={{}} allocs={{}} bytes={{}}", name, allocs, bytes);
+}}
+
+fn main() {{
+{calls}}}
+"#,
+        prefix = REPORT_PREFIX,
+        calls = calls
+    )
+}
+
+/// Prefix an entrypoint with the library crate name unless already qualified
+fn qualify_entrypoint(lib_name: &str, entry: &str) -> String {
+    let prefix = format!("{}::", lib_name);
+    if entry.starts_with(&prefix) {
+        entry.to_string()
+    } else {
+        format!("{}{}", prefix, entry)
+    }
+}
+
+/// Parse measurement lines printed by the harness
+///
+/// Lines look like `wasm-slim-alloc entry=<path> allocs=<n> bytes=<n>`; anything
+/// else (e.g. output of the entrypoints themselves) is ignored.
+pub(crate) fn parse_harness_output(output: &str) -> Vec<EntrypointAllocations> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix(REPORT_PREFIX)?;
+            let mut entrypoint = None;
+            let mut allocations = None;
+            let mut bytes = None;
+            for field in rest.split_whitespace() {
+                match field.split_once('=') {
+                    Some(("entry", v)) => entrypoint = Some(v.to_string()),
+                    Some(("allocs", v)) => allocations = v.parse().ok(),
+                    Some(("bytes", v)) => bytes = v.parse().ok(),
+                    _ => {}
+                }
+            }
+            Some(EntrypointAllocations {
+                entrypoint: entrypoint?,
+                allocations: allocations?,
+                bytes: bytes?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::mock_exit_status;
+    use std::io;
+    use std::process::{Command, Output};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct MockFs {
+        written: Arc<Mutex<Vec<(PathBuf, String)>>>,
+    }
+
+    impl FileSystem for MockFs {
+        fn copy(&self, _from: &Path, _to: &Path) -> io::Result<u64> {
+            Ok(0)
+        }
+        fn create_dir_all(&self, _path: &Path) -> io::Result<()> {
+            Ok(())
+        }
+        fn read_dir(&self, path: &Path) -> io::Result<std::fs::ReadDir> {
+            std::fs::read_dir(path)
+        }
+        fn metadata(&self, path: &Path) -> io::Result<std::fs::Metadata> {
+            std::fs::metadata(path)
+        }
+        fn read_to_string(&self, _path: &Path) -> io::Result<String> {
+            Ok(String::new())
+        }
+        fn write(&self, path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+            self.written.lock().unwrap().push((
+                path.to_path_buf(),
+                String::from_utf8_lossy(contents.as_ref()).to_string(),
+            ));
+            Ok(())
+        }
+    }
+
+    struct MockExecutor {
+        code: i32,
+        stdout: String,
+    }
+
+    impl CommandExecutor for MockExecutor {
+        fn status(&self, _cmd: &mut Command) -> io::Result<std::process::ExitStatus> {
+            Ok(mock_exit_status(self.code))
+        }
+        fn output(&self, _cmd: &mut Command) -> io::Result<Output> {
+            Ok(Output {
+                status: mock_exit_status(self.code),
+                stdout: self.stdout.clone().into_bytes(),
+                stderr: b"error[E0425]: cannot find function".to_vec(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_harness_output_extracts_measurements() {
+        let output = "hello from smoke\n\
+                      wasm-slim-alloc entry=render allocs=42 bytes=4096\n\
+                      wasm-slim-alloc entry=my_crate::parse allocs=7 bytes=128\n";
+        let parsed = parse_harness_output(output);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].entrypoint, "render");
+        assert_eq!(parsed[0].allocations, 42);
+        assert_eq!(parsed[0].bytes, 4096);
+        assert_eq!(parsed[1].entrypoint, "my_crate::parse");
+    }
+
+    #[test]
+    fn test_parse_harness_output_skips_malformed_lines() {
+        let output = "wasm-slim-alloc entry=render allocs=abc bytes=1\nwasm-slim-alloc allocs=1";
+        assert!(parse_harness_output(output).is_empty());
+    }
+
+    #[test]
+    fn test_harness_main_qualifies_entrypoints() {
+        let main = harness_main(
+            "my_crate",
+            &["smoke".to_string(), "my_crate::x::y".to_string()],
+        );
+        assert!(main.contains("#[global_allocator]"));
+        assert!(main.contains("my_crate::smoke()"));
+        assert!(main.contains("my_crate::x::y()"));
+        assert!(!main.contains("my_crate::my_crate::"));
+    }
+
+    #[test]
+    fn test_harness_manifest_depends_on_project_by_path() {
+        let manifest = harness_manifest("my-crate", Path::new("/work/my-crate"));
+        assert!(manifest.contains("my-crate = { path = \"/work/my-crate\" }"));
+        assert!(manifest.contains("[workspace]"));
+    }
+
+    #[test]
+    fn test_allocation_profile_assess_recommends_for_light_workloads() {
+        let profile = AllocationProfile::from_entrypoints(vec![EntrypointAllocations {
+            entrypoint: "render".to_string(),
+            allocations: 120,
+            bytes: 8192,
+        }]);
+        assert_eq!(profile.total_allocations, 120);
+        assert!(matches!(
+            profile.assess(),
+            AllocatorAssessment::Recommended(_)
+        ));
+    }
+
+    #[test]
+    fn test_allocation_profile_assess_rejects_heavy_workloads() {
+        let profile = AllocationProfile::from_entrypoints(vec![EntrypointAllocations {
+            entrypoint: "parse".to_string(),
+            allocations: HEAVY_ALLOCATION_THRESHOLD * 5,
+            bytes: 1 << 20,
+        }]);
+        assert!(matches!(
+            profile.assess(),
+            AllocatorAssessment::NotRecommended(_)
+        ));
+    }
+
+    #[test]
+    fn test_profile_crate_writes_harness_and_parses_output() {
+        let fs = MockFs::default();
+        let executor = MockExecutor {
+            code: 0,
+            stdout: "wasm-slim-alloc entry=smoke allocs=3 bytes=96\n".to_string(),
+        };
+        let profiler = AllocationProfiler::with_executors("/proj", fs.clone(), executor);
+
+        let profile = profiler
+            .profile_crate("my-crate", "my_crate", &["smoke".to_string()])
+            .unwrap();
+
+        assert_eq!(profile.total_allocations, 3);
+        assert_eq!(profile.total_bytes, 96);
+        let written = fs.written.lock().unwrap();
+        assert!(written.iter().any(|(p, _)| p.ends_with("Cargo.toml")));
+        assert!(written.iter().any(|(p, _)| p.ends_with("main.rs")));
+    }
+
+    #[test]
+    fn test_profile_crate_reports_harness_failure() {
+        let executor = MockExecutor {
+            code: 101,
+            stdout: String::new(),
+        };
+        let profiler = AllocationProfiler::with_executors("/proj", MockFs::default(), executor);
+
+        let err = profiler
+            .profile_crate("my-crate", "my_crate", &["smoke".to_string()])
+            .unwrap_err();
+        assert!(matches!(err, AllocationProfileError::HarnessFailed(_)));
+    }
+
+    #[test]
+    fn test_profile_without_entrypoints_returns_error() {
+        let profiler = AllocationProfiler::new(".");
+        assert!(matches!(
+            profiler.profile(&[]),
+            Err(AllocationProfileError::NoEntrypoints)
+        ));
+    }
+}
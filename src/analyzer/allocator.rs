@@ -5,13 +5,19 @@
 //! # Overview
 //!
 //! WASM bundles using default system allocator carry overhead. Custom allocators
-//! like `wee_alloc` trade allocation speed for size (2-5% reduction).
+//! like `lol_alloc` trade allocation speed for size (2-5% reduction). The
+//! unmaintained `wee_alloc` (RUSTSEC-2022-0054) is never recommended.
 //!
 //! This module:
-//! - Detects existing custom allocators (wee_alloc, lol_alloc, dlmalloc)
+//! - Detects existing custom allocators (wee_alloc, lol_alloc, talc, dlmalloc)
 //! - Counts allocation-heavy dependencies
 //! - Recommends allocator when ≥5 allocation-heavy deps present
 //!
+//! `analyze --mode allocator --fix` applies the recommendation with
+//! [`SuggestionApplicator::apply_allocator`](super::SuggestionApplicator::apply_allocator).
+//! `--compare` replaces the size estimate with trial builds per allocator
+//! (see [`super::allocator_trials`]).
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -24,6 +30,7 @@
 //! }
 //! ```
 
+use crate::profiling::{self, ProfilePhase};
 use cargo_metadata::MetadataCommand;
 use std::path::Path;
 
@@ -53,7 +60,7 @@ impl AllocatorDetector {
     /// - Project has ≥5 allocation-heavy dependencies
     ///
     /// Returns `None` if:
-    /// - Custom allocator already present (wee_alloc, lol_alloc, dlmalloc)
+    /// - Custom allocator already present (wee_alloc, lol_alloc, talc, dlmalloc)
     /// - Too few allocation-heavy dependencies (<5)
     ///
     /// # Examples
@@ -71,9 +78,12 @@ impl AllocatorDetector {
     pub fn check_allocator_optimization(
         &self,
     ) -> Result<Option<DependencyIssue>, DependencyAnalysisError> {
-        let metadata = MetadataCommand::new()
-            .current_dir(&self.project_root)
-            .exec()?;
+        let metadata = {
+            let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+            MetadataCommand::new()
+                .current_dir(&self.project_root)
+                .exec()?
+        };
 
         self.check_allocator_optimization_with_metadata(&metadata)
     }
@@ -92,10 +102,12 @@ impl AllocatorDetector {
         metadata: &cargo_metadata::Metadata,
     ) -> Result<Option<DependencyIssue>, DependencyAnalysisError> {
         // Check if custom allocator already present
-        let has_custom_allocator = metadata
-            .packages
-            .iter()
-            .any(|p| matches!(p.name.as_str(), "wee_alloc" | "lol_alloc" | "dlmalloc"));
+        let has_custom_allocator = metadata.packages.iter().any(|p| {
+            matches!(
+                p.name.as_str(),
+                "wee_alloc" | "lol_alloc" | "talc" | "dlmalloc"
+            )
+        });
 
         if has_custom_allocator {
             return Ok(None); // Already optimized
@@ -116,9 +128,10 @@ impl AllocatorDetector {
                     "No custom allocator detected. Project has {} allocation-heavy dependencies using default system allocator.",
                     heavy_alloc_deps
                 ),
-                suggestion: "Add wee_alloc for 2-5% size reduction:\n\n   [dependencies]\n   wee_alloc = \"0.4.5\"\n\n   And configure global allocator in lib.rs:\n   #[cfg(target_arch = \"wasm32\")]\n   #[global_allocator]\n   static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;\n\n   Note: wee_alloc trades smaller size for slightly slower allocation performance.".to_string(),
+                suggestion: "Add lol_alloc for 2-5% size reduction:\n\n   [target.'cfg(target_arch = \"wasm32\")'.dependencies]\n   lol_alloc = \"0.4\"\n\n   And configure global allocator in lib.rs:\n   #[cfg(all(target_arch = \"wasm32\", not(target_feature = \"atomics\")))]\n   #[global_allocator]\n   static ALLOC: lol_alloc::AssumeSingleThreaded<lol_alloc::FreeListAllocator> =\n       unsafe { lol_alloc::AssumeSingleThreaded::new(lol_alloc::FreeListAllocator::new()) };\n\n   Note: lol_alloc trades smaller size for slower allocation; talc is faster, and\n   measuring with --compare may show the default allocator is fine. Avoid the\n   unmaintained wee_alloc (RUSTSEC-2022-0054).".to_string(),
                 size_impact_kb: Some(estimated_savings_kb),
                 savings_percent: Some(3), // Conservative 3% estimate
+                verify_id: None,
             }))
         } else {
             Ok(None) // Not enough heap usage to warrant custom allocator
@@ -420,7 +433,8 @@ mod tests {
         let issue = result.unwrap();
         assert_eq!(issue.package, "wasm-allocator");
         assert_eq!(issue.severity, IssueSeverity::Medium);
-        assert!(issue.suggestion.contains("wee_alloc"));
+        assert!(issue.suggestion.contains("lol_alloc"));
+        assert!(issue.suggestion.contains("RUSTSEC-2022-0054"));
         assert!(issue.size_impact_kb.is_some());
         assert_eq!(issue.savings_percent, Some(3));
     }
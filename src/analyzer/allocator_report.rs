@@ -0,0 +1,231 @@
+//! Allocator analysis report formatting
+//!
+//! Combines the crate-name heuristic from [`super::allocator`] with measured
+//! allocation counts from [`super::alloc_profile`] and the trial builds of
+//! [`super::allocator_trials`] for console output.
+
+use super::alloc_profile::{AllocationProfile, AllocatorAssessment};
+use super::allocator_trials::AllocatorComparison;
+use super::deps::DependencyIssue;
+use super::report_utils::format_delta;
+use crate::fmt::format_bytes;
+use console::style;
+
+/// Print allocator analysis report to console
+///
+/// # Arguments
+///
+/// * `heuristic` - Recommendation from dependency heuristics (if any)
+/// * `profile` - Measured allocation profile (if smoke entrypoints were run)
+pub fn print_allocator_report(
+    heuristic: Option<&DependencyIssue>,
+    profile: Option<&AllocationProfile>,
+) {
+    println!();
+    println!("{}", style("Allocator Analysis").bold().underlined());
+    println!();
+
+    println!("{}", style("HEURISTIC (dependency names):").bold());
+    match heuristic {
+        Some(issue) => {
+            println!("  {} {}", style("→").dim(), issue.issue);
+            if let Some((min, max)) = issue.size_impact_kb {
+                println!(
+                    "  {} Estimated savings: {}-{} KB",
+                    style("→").dim(),
+                    style(min).green(),
+                    style(max).green()
+                );
+            }
+        }
+        None => println!(
+            "  {} No allocator change suggested by dependency heuristics",
+            style("→").dim()
+        ),
+    }
+    println!();
+
+    let Some(profile) = profile else {
+        println!(
+            "{}",
+            style(
+                "💡 Tip: Configure [allocator] smoke-entrypoints in .wasm-slim.toml to measure actual allocation counts"
+            )
+            .dim()
+        );
+        println!();
+        return;
+    };
+
+    println!(
+        "{}",
+        style("MEASURED (counting allocator, debug build):").bold()
+    );
+    println!("{}", style("─".repeat(70)).dim());
+    for entry in &profile.entrypoints {
+        println!(
+            "  {:>10} allocs {:>12}  {}",
+            style(entry.allocations).yellow().bold(),
+            format_bytes(entry.bytes),
+            style(&entry.entrypoint).cyan()
+        );
+    }
+    println!("{}", style("─".repeat(70)).dim());
+    println!(
+        "  {:>10} allocs {:>12}  {}",
+        style(profile.total_allocations).bold(),
+        format_bytes(profile.total_bytes),
+        style("total").bold()
+    );
+    println!();
+
+    match profile.assess() {
+        AllocatorAssessment::Recommended(reason) => {
+            println!("  {} {}", style("✅").bold(), style(reason).green());
+            if let Some(issue) = heuristic {
+                println!();
+                println!("  {}", issue.suggestion);
+            }
+        }
+        AllocatorAssessment::NotRecommended(reason) => {
+            println!("  {} {}", style("⚠️").bold(), style(reason).yellow());
+        }
+    }
+    println!();
+}
+
+/// Print the measured allocator comparison to console
+///
+/// Sizes are relative to the project's current allocator; benchmark times
+/// to std's default allocator.
+pub fn print_allocator_comparison(comparison: &AllocatorComparison) {
+    println!(
+        "{} (baseline {})",
+        style("MEASURED (trial builds per allocator):").bold(),
+        style(format_bytes(comparison.baseline_bytes)).cyan()
+    );
+    println!("{}", style("─".repeat(70)).dim());
+    for trial in &comparison.trials {
+        let name = trial.allocator.crate_name();
+        let (Some(bytes), Some(delta)) = (trial.trial_bytes, trial.delta_bytes) else {
+            println!(
+                "  {:<10} {}",
+                name,
+                style(format!(
+                    "not measured: {}",
+                    trial.skipped.as_deref().unwrap_or("unknown")
+                ))
+                .dim()
+            );
+            continue;
+        };
+        let change = format_delta(delta);
+        let change = if delta < 0 {
+            style(change).green()
+        } else {
+            style(change).yellow()
+        };
+        let mut speed = match (trial.bench_nanos, comparison.baseline_bench_nanos) {
+            (Some(nanos), Some(base)) if base > 0 => {
+                format!("{:.2}x alloc time", nanos as f64 / base as f64)
+            }
+            _ => String::new(),
+        };
+        if trial.allocator.is_unmaintained() {
+            speed.push_str("  unmaintained (RUSTSEC-2022-0054), never applied");
+        }
+        println!(
+            "  {:<10} {:>12} {:>12}  {}",
+            name,
+            format_bytes(bytes),
+            change,
+            style(speed).dim()
+        );
+    }
+    println!("{}", style("─".repeat(70)).dim());
+    if let Some(reason) = &comparison.bench_skipped {
+        println!(
+            "  {} Micro-benchmark skipped: {}",
+            style("→").dim(),
+            style(reason).dim()
+        );
+    }
+    match comparison.smallest() {
+        Some(best) => println!(
+            "  {} Smallest build: {} ({})",
+            style("✅").bold(),
+            style(best.allocator.crate_name()).green(),
+            format_delta(best.delta_bytes.unwrap_or_default())
+        ),
+        None => println!(
+            "  {} No allocator measured smaller than the current one",
+            style("→").dim()
+        ),
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::alloc_profile::EntrypointAllocations;
+    use crate::analyzer::deps::IssueSeverity;
+
+    #[test]
+    fn test_print_allocator_report_handles_all_combinations() {
+        let issue = DependencyIssue {
+            package: "wasm-allocator".to_string(),
+            version: "n/a".to_string(),
+            severity: IssueSeverity::Medium,
+            issue: "No custom allocator detected".to_string(),
+            suggestion: "Add lol_alloc".to_string(),
+            size_impact_kb: Some((10, 20)),
+            savings_percent: Some(3),
+            verify_id: None,
+        };
+        let profile = AllocationProfile::from_entrypoints(vec![EntrypointAllocations {
+            entrypoint: "smoke".to_string(),
+            allocations: 10,
+            bytes: 1024,
+        }]);
+
+        print_allocator_report(None, None);
+        print_allocator_report(Some(&issue), None);
+        print_allocator_report(Some(&issue), Some(&profile));
+        print_allocator_report(None, Some(&profile));
+    }
+
+    #[test]
+    fn test_print_allocator_comparison_handles_skipped_trials() {
+        use crate::analyzer::allocator_trials::{AllocatorCandidate, AllocatorTrial};
+
+        let trial = |allocator, delta: Option<i64>| AllocatorTrial {
+            allocator,
+            trial_bytes: delta.map(|d| (1000 + d) as u64),
+            delta_bytes: delta,
+            skipped: delta.is_none().then(|| "build failed".to_string()),
+            bench_nanos: Some(500),
+        };
+        let mut comparison = AllocatorComparison {
+            baseline_bytes: 1000,
+            baseline_bench_nanos: Some(250),
+            bench_skipped: None,
+            trials: vec![
+                trial(AllocatorCandidate::LolAlloc, Some(-120)),
+                trial(AllocatorCandidate::Talc, None),
+                trial(AllocatorCandidate::WeeAlloc, Some(-200)),
+            ],
+        };
+        assert_eq!(
+            comparison.smallest().unwrap().allocator,
+            AllocatorCandidate::LolAlloc
+        );
+
+        print_allocator_comparison(&comparison);
+        comparison.baseline_bench_nanos = None;
+        comparison.bench_skipped = Some("wasmtime not installed".to_string());
+        comparison.trials.truncate(1);
+        comparison.trials[0].delta_bytes = Some(40);
+        print_allocator_comparison(&comparison);
+    }
+}
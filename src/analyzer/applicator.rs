@@ -1,23 +1,34 @@
 //! Automatic application of dependency optimization suggestions
 //!
-//! Takes DependencyReport and applies fixes to Cargo.toml automatically.
+//! Takes DependencyReport and applies fixes to Cargo.toml automatically,
+//! including the dedupe fixes for duplicated crate versions (requirement
+//! edits in Cargo.toml and `cargo update --precise` lockfile moves).
 
-use crate::infra::{FileSystem, RealFileSystem};
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
 use crate::optimizer::BackupManager;
 use anyhow::{Context, Result};
 use console::style;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::Path;
 use toml_edit::{value, DocumentMut, Item, Table};
 
-use super::deps::{DependencyIssue, DependencyReport};
-use super::heavy_deps::{get_heavy_dependency_info, AlternativeType};
+use super::allocator_trials::AllocatorCandidate;
+use super::deps::{DedupeFix, DependencyIssue, DependencyReport};
+use super::heavy_deps::{AlternativeType, HeavyDepsDatabase};
+
+/// Target table the allocator dependency is added under
+const WASM32_CFG: &str = "cfg(target_arch = \"wasm32\")";
 
 /// Applies dependency optimization suggestions to Cargo.toml
-pub struct SuggestionApplicator<FS: FileSystem = RealFileSystem> {
+pub struct SuggestionApplicator<
+    FS: FileSystem = RealFileSystem,
+    CE: CommandExecutor = RealCommandExecutor,
+> {
     project_root: std::path::PathBuf,
     fs: FS,
+    cmd_executor: CE,
     backup_manager: BackupManager<FS>,
+    heavy_deps: HeavyDepsDatabase,
 }
 
 impl SuggestionApplicator<RealFileSystem> {
@@ -43,21 +54,38 @@ impl SuggestionApplicator<RealFileSystem> {
 impl<FS: FileSystem + Clone> SuggestionApplicator<FS> {
     /// Create a new suggestion applicator with a custom filesystem implementation
     pub fn with_fs(project_root: impl AsRef<Path>, fs: FS) -> Self {
+        SuggestionApplicator::with_executors(project_root, fs, RealCommandExecutor)
+    }
+}
+
+impl<FS: FileSystem + Clone, CE: CommandExecutor> SuggestionApplicator<FS, CE> {
+    /// Create a new suggestion applicator with custom filesystem and command executors
+    pub fn with_executors(project_root: impl AsRef<Path>, fs: FS, cmd_executor: CE) -> Self {
         let project_root = project_root.as_ref().to_path_buf();
         let backup_manager = BackupManager::with_fs(&project_root, fs.clone());
         Self {
             project_root,
             fs,
+            cmd_executor,
             backup_manager,
+            heavy_deps: HeavyDepsDatabase::default(),
         }
     }
 
+    /// Pick alternatives from `database` instead of the built-in one
+    pub fn with_heavy_deps(mut self, database: HeavyDepsDatabase) -> Self {
+        self.heavy_deps = database;
+        self
+    }
+
     /// Apply suggestions from a dependency report
     ///
     /// Automatically fixes detected dependency issues by:
     /// - Disabling problematic default features
     /// - Replacing heavy dependencies with lighter alternatives
     /// - Optimizing feature flags for WASM targets
+    /// - Raising requirements and running `cargo update --precise` to unify
+    ///   duplicated crate versions
     ///
     /// # Arguments
     /// * `report` - Dependency analysis report with issues
@@ -102,7 +130,7 @@ impl<FS: FileSystem + Clone> SuggestionApplicator<FS> {
         let mut fixes_applied = 0;
 
         // Group issues by package
-        let mut issues_by_package: HashMap<String, Vec<&DependencyIssue>> = HashMap::new();
+        let mut issues_by_package: BTreeMap<String, Vec<&DependencyIssue>> = BTreeMap::new();
         for issue in &report.issues {
             issues_by_package
                 .entry(issue.package.clone())
@@ -113,7 +141,7 @@ impl<FS: FileSystem + Clone> SuggestionApplicator<FS> {
         // Apply fixes for each package
         for package_name in issues_by_package.keys() {
             // Get the heavy dependency info
-            if let Some(heavy_info) = get_heavy_dependency_info(package_name) {
+            if let Some(heavy_info) = self.heavy_deps.get(package_name) {
                 // Find the best alternative (highest savings)
                 let best_alternative = heavy_info
                     .alternatives
@@ -144,6 +172,28 @@ impl<FS: FileSystem + Clone> SuggestionApplicator<FS> {
             }
         }
 
+        // Unify duplicated versions: requirement edits go into this write,
+        // lockfile moves run once Cargo.toml is final
+        let dedupe_fixes: Vec<&DedupeFix> = report
+            .duplicate_versions
+            .iter()
+            .flat_map(|d| &d.fixes)
+            .collect();
+        for fix in &dedupe_fixes {
+            if let DedupeFix::UnifyRequirement {
+                member,
+                dependency,
+                to,
+                ..
+            } = fix
+            {
+                if Self::raise_requirement(&mut doc, member, dependency, to) {
+                    fixes_applied += 1;
+                    Self::print_dedupe_fix(fix, dry_run);
+                }
+            }
+        }
+
         if !dry_run && fixes_applied > 0 {
             // Create backup before writing
             let backup_path = self
@@ -162,9 +212,208 @@ impl<FS: FileSystem + Clone> SuggestionApplicator<FS> {
                 .context("Failed to write Cargo.toml")?;
         }
 
+        for fix in dedupe_fixes {
+            let DedupeFix::CargoUpdate { package, from, to } = fix else {
+                continue;
+            };
+            if dry_run || self.cargo_update(package, from, to)? {
+                fixes_applied += 1;
+                Self::print_dedupe_fix(fix, dry_run);
+            } else {
+                println!(
+                    "   {} `{}` failed; another requirement still needs {} {}",
+                    style("⚠").yellow(),
+                    fix,
+                    package,
+                    from
+                );
+            }
+        }
+
         Ok(fixes_applied)
     }
 
+    /// Apply the custom allocator recommendation
+    ///
+    /// Adds `allocator` as a wasm32-only dependency in Cargo.toml and injects
+    /// its [`global_allocator`](AllocatorCandidate::global_allocator) block
+    /// into the library root (`src/lib.rs` or `[lib] path`), after its inner
+    /// attributes. Both files are backed up first; steps that are already
+    /// done are skipped. A library root that already declares a different
+    /// `#[global_allocator]` is left alone, and an unmaintained allocator is
+    /// refused.
+    ///
+    /// # Returns
+    /// Number of files changed (or, with `dry_run`, that would change)
+    ///
+    /// # Errors
+    /// Returns error if Cargo.toml or the library root is missing, cannot be
+    /// parsed, or a write fails
+    pub fn apply_allocator(&self, allocator: AllocatorCandidate, dry_run: bool) -> Result<usize> {
+        if allocator.is_unmaintained() {
+            anyhow::bail!(
+                "{} is unmaintained (RUSTSEC-2022-0054) and is never applied",
+                allocator.crate_name()
+            );
+        }
+        let cargo_toml_path = self.project_root.join("Cargo.toml");
+        if !cargo_toml_path.exists() {
+            anyhow::bail!("Cargo.toml not found in {}", self.project_root.display());
+        }
+        let content = self
+            .fs
+            .read_to_string(&cargo_toml_path)
+            .context("Failed to read Cargo.toml")?;
+        let mut doc = content
+            .parse::<DocumentMut>()
+            .context("Failed to parse Cargo.toml")?;
+
+        let lib_path = doc
+            .get("lib")
+            .and_then(|lib| lib.get("path"))
+            .and_then(|path| path.as_str())
+            .unwrap_or("src/lib.rs")
+            .to_string();
+        let lib_rs_path = self.project_root.join(&lib_path);
+        let lib_rs = self.fs.read_to_string(&lib_rs_path).with_context(|| {
+            format!(
+                "Failed to read {} (the allocator is set in the library root)",
+                lib_path
+            )
+        })?;
+
+        let injected = inject_global_allocator(&lib_rs, allocator.global_allocator());
+        if injected.is_none() && !lib_rs.contains(allocator.global_allocator()) {
+            anyhow::bail!(
+                "{} already declares a #[global_allocator]; replace it with {} by hand",
+                lib_path,
+                allocator.crate_name()
+            );
+        }
+
+        let mut changed = 0;
+        let verb = if dry_run { "Would add" } else { "Added" };
+
+        let crate_name = allocator.crate_name();
+        if add_wasm32_dependency(&mut doc, crate_name, allocator.dependency()) {
+            changed += 1;
+            println!(
+                "   {} {} {} for wasm32 to Cargo.toml",
+                style("✓").green(),
+                verb,
+                crate_name
+            );
+            if !dry_run {
+                self.backup_and_write(&cargo_toml_path, &doc.to_string())?;
+            }
+        }
+
+        if let Some(injected) = injected {
+            changed += 1;
+            println!(
+                "   {} {} #[global_allocator] to {}",
+                style("✓").green(),
+                verb,
+                lib_path
+            );
+            if !dry_run {
+                self.backup_and_write(&lib_rs_path, &injected)?;
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// Back up `path`, then overwrite it with `content`
+    fn backup_and_write(&self, path: &Path, content: &str) -> Result<()> {
+        let backup_path = self
+            .backup_manager
+            .create_backup(path)
+            .context("Failed to create backup")?;
+        println!(
+            "   {} Backup created: {}",
+            style("💾").dim(),
+            backup_path.display()
+        );
+        self.fs
+            .write(path, content.as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Print an applied (or, in dry-run mode, pending) dedupe fix
+    fn print_dedupe_fix(fix: &DedupeFix, dry_run: bool) {
+        if dry_run {
+            println!("   {} Would {}", style("•").yellow(), fix);
+        } else {
+            println!("   {} Unified: {}", style("✓").green(), fix);
+        }
+    }
+
+    /// Set the requirement of `dependency` in the root package's `[dependencies]`
+    ///
+    /// Only applies when the root package is `member`; dependencies without a
+    /// version requirement (path or git) are left alone.
+    fn raise_requirement(
+        doc: &mut DocumentMut,
+        member: &str,
+        dependency: &str,
+        requirement: &str,
+    ) -> bool {
+        let is_member = doc
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+            == Some(member);
+        if !is_member {
+            return false;
+        }
+        let Some(dep_item) =
+            Self::get_dependencies_table(doc).and_then(|deps| deps.get_mut(dependency))
+        else {
+            return false;
+        };
+
+        match dep_item {
+            Item::Value(val) if val.is_str() => {
+                *dep_item = value(requirement);
+                true
+            }
+            Item::Value(val) => match val.as_inline_table_mut() {
+                Some(table) if table.contains_key("version") => {
+                    table.insert("version", requirement.into());
+                    true
+                }
+                _ => false,
+            },
+            Item::Table(table) if table.contains_key("version") => {
+                table.insert("version", value(requirement));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Move one locked version of a package to another with `cargo update --precise`
+    fn cargo_update(&self, package: &str, from: &str, to: &str) -> Result<bool> {
+        let spec = format!("{}@{}", package, from);
+        let status = self
+            .cmd_executor
+            .run(
+                |cmd| {
+                    cmd.current_dir(&self.project_root).args([
+                        "update",
+                        "-p",
+                        &spec,
+                        "--precise",
+                        to,
+                    ])
+                },
+                "cargo",
+            )
+            .context("Failed to run cargo update")?;
+        Ok(status.success())
+    }
+
     /// Apply a specific fix based on alternative type
     fn apply_fix(
         &self,
@@ -316,20 +565,18 @@ impl<FS: FileSystem + Clone> SuggestionApplicator<FS> {
                         }
                     }
                 }
-                Item::Table(table) => {
+                Item::Table(table) if !table.contains_key("default-features") => {
                     // Already a table, just set default-features = false
-                    if !table.contains_key("default-features") {
-                        table.insert("default-features", value(false));
-
-                        // Add minimal features if not present
-                        if !table.contains_key("features") {
-                            if let Some(features) = self.get_minimal_features(package_name) {
-                                let features_array = Self::create_features_array(&features);
-                                table.insert("features", value(features_array));
-                            }
+                    table.insert("default-features", value(false));
+
+                    // Add minimal features if not present
+                    if !table.contains_key("features") {
+                        if let Some(features) = self.get_minimal_features(package_name) {
+                            let features_array = Self::create_features_array(&features);
+                            table.insert("features", value(features_array));
                         }
-                        return Ok(true);
                     }
+                    return Ok(true);
                 }
                 _ => {}
             }
@@ -462,14 +709,201 @@ impl<FS: FileSystem + Clone> SuggestionApplicator<FS> {
     }
 }
 
+/// Add `name` to the wasm32 target dependencies, unless any dependency
+/// table already has it
+pub(crate) fn add_wasm32_dependency(doc: &mut DocumentMut, name: &str, dependency: Item) -> bool {
+    let has_dependency = |table: &dyn toml_edit::TableLike| {
+        table
+            .get("dependencies")
+            .and_then(|deps| deps.as_table_like())
+            .is_some_and(|deps| deps.contains_key(name))
+    };
+    let in_targets = doc
+        .get("target")
+        .and_then(|t| t.as_table_like())
+        .is_some_and(|targets| {
+            targets
+                .iter()
+                .filter_map(|(_, target)| target.as_table_like())
+                .any(has_dependency)
+        });
+    if has_dependency(doc.as_table()) || in_targets {
+        return false;
+    }
+
+    let targets = doc
+        .entry("target")
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut();
+    let Some(targets) = targets else {
+        return false;
+    };
+    let wasm = targets
+        .entry(WASM32_CFG)
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut();
+    let Some(wasm) = wasm else {
+        return false;
+    };
+    let Some(deps) = wasm
+        .entry("dependencies")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_mut()
+    else {
+        return false;
+    };
+    deps.insert(name, dependency);
+    true
+}
+
+/// `lib.rs` with a `#[global_allocator]` block after the leading inner doc
+/// comments and attributes, or `None` if one is already declared
+pub(crate) fn inject_global_allocator(lib_rs: &str, block: &str) -> Option<String> {
+    if lib_rs.contains("#[global_allocator]") {
+        return None;
+    }
+    let lines: Vec<&str> = lib_rs.lines().collect();
+    let header = lines
+        .iter()
+        .take_while(|line| {
+            let line = line.trim_start();
+            line.is_empty() || line.starts_with("//!") || line.starts_with("#![")
+        })
+        .count();
+    // Blank lines between the header and the code are replaced by one
+    // on each side of the block
+    let header = lines[..header]
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |i| i + 1);
+
+    let mut injected = String::new();
+    for line in &lines[..header] {
+        injected.push_str(line);
+        injected.push('\n');
+    }
+    if header > 0 {
+        injected.push('\n');
+    }
+    injected.push_str(block);
+    let body = lines[header..]
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .map_or(lines.len(), |i| header + i);
+    if body < lines.len() {
+        injected.push('\n');
+    }
+    for line in &lines[body..] {
+        injected.push_str(line);
+        injected.push('\n');
+    }
+    Some(injected)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
 
+    /// Records `cargo` invocations instead of running them
+    #[derive(Clone, Default)]
+    struct RecordingExecutor(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl CommandExecutor for RecordingExecutor {
+        fn status(
+            &self,
+            cmd: &mut std::process::Command,
+        ) -> std::io::Result<std::process::ExitStatus> {
+            let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy()).collect();
+            self.0.lock().unwrap().push(args.join(" "));
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(
+            &self,
+            _cmd: &mut std::process::Command,
+        ) -> std::io::Result<std::process::Output> {
+            unimplemented!("output not needed for these tests")
+        }
+    }
+
     // ============ Happy Path Tests ============
 
+    #[test]
+    fn test_apply_suggestions_unifies_duplicate_versions() {
+        use crate::analyzer::deps::DuplicateVersion;
+
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &cargo_toml,
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nsyn = { version = \"1.0\", features = [\"full\"] }\n",
+        )
+        .unwrap();
+        let duplicate = |package: &str, fix| DuplicateVersion {
+            package: package.to_string(),
+            versions: Vec::new(),
+            estimated_cost_bytes: 0,
+            fixes: vec![fix],
+        };
+        let report = DependencyReport {
+            total_deps: 4,
+            direct_deps: 1,
+            issues: vec![],
+            duplicates: std::collections::BTreeMap::new(),
+            duplicate_versions: vec![
+                duplicate(
+                    "syn",
+                    DedupeFix::UnifyRequirement {
+                        member: "app".to_string(),
+                        dependency: "syn".to_string(),
+                        from: "^1.0".to_string(),
+                        to: "2.0".to_string(),
+                    },
+                ),
+                duplicate(
+                    "itoa",
+                    DedupeFix::CargoUpdate {
+                        package: "itoa".to_string(),
+                        from: "1.0.0".to_string(),
+                        to: "1.0.11".to_string(),
+                    },
+                ),
+                duplicate(
+                    "base64",
+                    DedupeFix::UpgradeDependent {
+                        dependent: "legacy".to_string(),
+                        version: "0.3.0".to_string(),
+                        package: "base64".to_string(),
+                        requires: "^0.13".to_string(),
+                    },
+                ),
+            ],
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
+        };
+        let executor = RecordingExecutor::default();
+        let applicator =
+            SuggestionApplicator::with_executors(temp_dir.path(), RealFileSystem, executor.clone());
+
+        assert_eq!(applicator.apply_suggestions(&report, false).unwrap(), 2);
+        let updated = fs::read_to_string(&cargo_toml).unwrap();
+        assert!(updated.contains("syn = { version = \"2.0\", features = [\"full\"] }"));
+        assert_eq!(
+            *executor.0.lock().unwrap(),
+            vec!["update -p itoa@1.0.0 --precise 1.0.11"]
+        );
+    }
+
     #[test]
     fn test_apply_feature_minimization_adds_default_features_false() {
         let temp_dir = TempDir::new().unwrap();
@@ -537,7 +971,10 @@ getrandom = "0.3"
             total_deps: 0,
             direct_deps: 0,
             issues: vec![],
-            duplicates: std::collections::HashMap::new(),
+            duplicates: std::collections::BTreeMap::new(),
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
         };
 
         let result = applicator.apply_suggestions(&report, false);
@@ -561,7 +998,10 @@ getrandom = "0.3"
             total_deps: 0,
             direct_deps: 0,
             issues: vec![],
-            duplicates: std::collections::HashMap::new(),
+            duplicates: std::collections::BTreeMap::new(),
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
         };
 
         let result = applicator.apply_suggestions(&report, false);
@@ -683,6 +1123,89 @@ getrandom = { version = "0.3", features = ["wasm_js"] }
         assert_eq!(count, 1);
     }
 
+    #[test]
+    fn test_apply_allocator_adds_dependency_and_global_allocator() {
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+        let lib_rs = temp_dir.path().join("src/lib.rs");
+        fs::create_dir(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            &cargo_toml,
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1\"\n",
+        )
+        .unwrap();
+        fs::write(&lib_rs, "//! App\n#![no_std]\n\npub fn f() {}\n").unwrap();
+        let applicator = SuggestionApplicator::new(temp_dir.path());
+
+        assert_eq!(
+            applicator
+                .apply_allocator(AllocatorCandidate::LolAlloc, true)
+                .unwrap(),
+            2
+        );
+        assert!(!fs::read_to_string(&lib_rs)
+            .unwrap()
+            .contains("global_allocator"));
+
+        assert_eq!(
+            applicator
+                .apply_allocator(AllocatorCandidate::LolAlloc, false)
+                .unwrap(),
+            2
+        );
+        let manifest = fs::read_to_string(&cargo_toml).unwrap();
+        assert!(manifest.contains(
+            "[target.'cfg(target_arch = \"wasm32\")'.dependencies]\nlol_alloc = \"0.4\"\n"
+        ));
+        assert_eq!(
+            fs::read_to_string(&lib_rs).unwrap(),
+            format!(
+                "//! App\n#![no_std]\n\n{}\npub fn f() {{}}\n",
+                AllocatorCandidate::LolAlloc.global_allocator()
+            )
+        );
+        let backups = fs::read_dir(crate::state::state_dir(temp_dir.path()).join("backups"))
+            .unwrap()
+            .count();
+        assert_eq!(backups, 2);
+
+        // Already applied: nothing left to do
+        assert_eq!(
+            applicator
+                .apply_allocator(AllocatorCandidate::LolAlloc, false)
+                .unwrap(),
+            0
+        );
+
+        // Another allocator is never stacked on top of the declared one
+        let err = applicator
+            .apply_allocator(AllocatorCandidate::Talc, false)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("already declares a #[global_allocator]"));
+        assert!(!fs::read_to_string(&cargo_toml).unwrap().contains("talc"));
+    }
+
+    #[test]
+    fn test_apply_allocator_refuses_unmaintained_wee_alloc() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = "[package]\nname = \"app\"\nversion = \"0.1.0\"\n";
+        fs::create_dir(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("Cargo.toml"), manifest).unwrap();
+        fs::write(temp_dir.path().join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+
+        let err = SuggestionApplicator::new(temp_dir.path())
+            .apply_allocator(AllocatorCandidate::WeeAlloc, false)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("wee_alloc is unmaintained"));
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap(),
+            manifest
+        );
+    }
+
     #[test]
     fn test_apply_suggestions_with_dry_run_does_not_modify_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -704,7 +1227,10 @@ regex = "1.10"
             total_deps: 1,
             direct_deps: 1,
             issues: vec![],
-            duplicates: std::collections::HashMap::new(),
+            duplicates: std::collections::BTreeMap::new(),
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
         };
 
         let result = applicator.apply_suggestions(&report, true); // dry_run = true
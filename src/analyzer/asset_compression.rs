@@ -0,0 +1,360 @@
+//! Compression opportunities for embedded assets
+//!
+//! Embedded assets are often stored in a heavier format than needed: TTF/OTF
+//! fonts instead of WOFF2, PNG/JPEG images instead of WebP, pretty-printed
+//! JSON. The savings for JSON are exact (the file is minified in memory);
+//! fonts and images use typical conversion ratios. `--compress` performs the
+//! conversions with `woff2_compress` and `cwebp` when they are installed,
+//! writing the converted file next to the original so the embedding site
+//! only has to point at it.
+
+use super::asset_metrics::DetectedAsset;
+use super::asset_types::AssetType;
+use super::report_utils::SizedEntry;
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Typical WOFF2 size relative to TTF/OTF
+const WOFF2_RATIO: f64 = 0.5;
+
+/// Typical lossless WebP size relative to PNG
+const WEBP_FROM_PNG_RATIO: f64 = 0.74;
+
+/// Typical WebP (quality 80) size relative to JPEG
+const WEBP_FROM_JPEG_RATIO: f64 = 0.7;
+
+/// Assets smaller than this are not worth converting
+const MIN_ASSET_BYTES: u64 = 1024;
+
+/// Conversion that shrinks an asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompressionKind {
+    /// TTF/OTF font to WOFF2
+    Woff2,
+    /// PNG (lossless) or JPEG (quality 80) to WebP
+    Webp,
+    /// Pretty-printed JSON to minified JSON
+    MinifiedJson,
+}
+
+impl CompressionKind {
+    /// Short description of the conversion
+    pub fn description(&self) -> &'static str {
+        match self {
+            CompressionKind::Woff2 => "convert to WOFF2",
+            CompressionKind::Webp => "convert to WebP",
+            CompressionKind::MinifiedJson => "minify JSON",
+        }
+    }
+
+    /// Tool performing the conversion, if an external one is needed
+    pub fn tool(&self) -> Option<&'static str> {
+        match self {
+            CompressionKind::Woff2 => Some("woff2_compress"),
+            CompressionKind::Webp => Some("cwebp"),
+            CompressionKind::MinifiedJson => None,
+        }
+    }
+
+    /// Whether the tool the conversion needs is on PATH
+    pub fn tool_available(&self) -> bool {
+        self.tool().is_none_or(|tool| which::which(tool).is_ok())
+    }
+
+    /// Where the converted file is written
+    pub fn output_path(&self, path: &Path) -> PathBuf {
+        match self {
+            CompressionKind::Woff2 => path.with_extension("woff2"),
+            CompressionKind::Webp => path.with_extension("webp"),
+            CompressionKind::MinifiedJson => path.with_extension("min.json"),
+        }
+    }
+}
+
+/// An embedded asset that could be stored more compactly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionOpportunity {
+    /// Asset path as written at the embedding site
+    pub file_path: String,
+    /// Asset location on disk
+    pub path: PathBuf,
+    /// Suggested conversion
+    pub kind: CompressionKind,
+    /// Current size in bytes
+    pub current_bytes: u64,
+    /// Size after conversion in bytes
+    pub estimated_bytes: u64,
+    /// Whether `estimated_bytes` is exact rather than a typical ratio
+    pub exact: bool,
+}
+
+impl SizedEntry for CompressionOpportunity {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.current_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.file_path
+    }
+}
+
+impl CompressionOpportunity {
+    /// Bytes saved by the conversion
+    pub fn savings_bytes(&self) -> u64 {
+        self.current_bytes.saturating_sub(self.estimated_bytes)
+    }
+}
+
+/// Find compression opportunities among detected single-file assets
+pub fn compression_opportunities<FS: FileSystem>(
+    assets: &[DetectedAsset],
+    fs: &FS,
+) -> Vec<CompressionOpportunity> {
+    let mut seen = BTreeSet::new();
+    let mut opportunities = Vec::new();
+    for asset in assets {
+        if asset.asset_type == AssetType::Directory || asset.size_bytes < MIN_ASSET_BYTES {
+            continue;
+        }
+        let path = asset.resolved_path();
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        let estimate = |kind, ratio: f64| {
+            Some((
+                kind,
+                (asset.size_bytes as f64 * ratio).round() as u64,
+                false,
+            ))
+        };
+        let found = match extension.as_deref() {
+            Some("ttf") | Some("otf") => estimate(CompressionKind::Woff2, WOFF2_RATIO),
+            Some("png") => estimate(CompressionKind::Webp, WEBP_FROM_PNG_RATIO),
+            Some("jpg") | Some("jpeg") => estimate(CompressionKind::Webp, WEBP_FROM_JPEG_RATIO),
+            Some("json") => minified_json(&path, fs)
+                .map(|minified| minified.len() as u64)
+                .filter(|&bytes| bytes < asset.size_bytes)
+                .map(|bytes| (CompressionKind::MinifiedJson, bytes, true)),
+            _ => None,
+        };
+        if let Some((kind, estimated_bytes, exact)) = found {
+            opportunities.push(CompressionOpportunity {
+                file_path: asset.file_path.clone(),
+                path,
+                kind,
+                current_bytes: asset.size_bytes,
+                estimated_bytes,
+                exact,
+            });
+        }
+    }
+    opportunities.sort_by_key(|o| std::cmp::Reverse(o.savings_bytes()));
+    opportunities
+}
+
+/// Minified form of a JSON file, if it parses
+fn minified_json<FS: FileSystem>(path: &Path, fs: &FS) -> Option<String> {
+    let content = fs.read_to_string(path).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    serde_json::to_string(&value).ok()
+}
+
+/// Outcome of one conversion
+#[derive(Debug, Clone, Serialize)]
+pub struct CompressedAsset {
+    /// Original asset
+    pub source: PathBuf,
+    /// Converted file
+    pub output: PathBuf,
+    /// Size before conversion
+    pub before_bytes: u64,
+    /// Measured size after conversion
+    pub after_bytes: u64,
+}
+
+/// Performs asset conversions
+pub struct AssetCompressor<
+    FS: FileSystem = RealFileSystem,
+    CE: CommandExecutor = RealCommandExecutor,
+> {
+    fs: FS,
+    cmd_executor: CE,
+}
+
+impl AssetCompressor {
+    /// Create a compressor using the real filesystem and tools
+    pub fn new() -> Self {
+        Self::with_executors(RealFileSystem, RealCommandExecutor)
+    }
+}
+
+impl Default for AssetCompressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<FS: FileSystem, CE: CommandExecutor> AssetCompressor<FS, CE> {
+    /// Create a compressor with custom executors
+    pub fn with_executors(fs: FS, cmd_executor: CE) -> Self {
+        Self { fs, cmd_executor }
+    }
+
+    /// Convert one asset, writing the result next to the original
+    pub fn compress(&self, opportunity: &CompressionOpportunity) -> Result<CompressedAsset> {
+        let source = &opportunity.path;
+        let output = opportunity.kind.output_path(source);
+
+        match opportunity.kind {
+            CompressionKind::MinifiedJson => {
+                let minified = minified_json(source, &self.fs)
+                    .with_context(|| format!("{} is not valid JSON", source.display()))?;
+                self.fs
+                    .write(&output, minified)
+                    .with_context(|| format!("Failed to write {}", output.display()))?;
+            }
+            CompressionKind::Woff2 | CompressionKind::Webp => {
+                let tool = opportunity.kind.tool().unwrap_or_default();
+                let lossless = source
+                    .extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("png"));
+                let result = self
+                    .cmd_executor
+                    .execute(
+                        |cmd| {
+                            if opportunity.kind == CompressionKind::Webp {
+                                if lossless {
+                                    cmd.arg("-lossless");
+                                } else {
+                                    cmd.arg("-q").arg("80");
+                                }
+                                cmd.arg(source).arg("-o").arg(&output)
+                            } else {
+                                cmd.arg(source)
+                            }
+                        },
+                        tool,
+                    )
+                    .with_context(|| format!("Failed to run {} (is it installed?)", tool))?;
+                if !result.status.success() {
+                    anyhow::bail!(
+                        "{} failed on {}: {}",
+                        tool,
+                        source.display(),
+                        String::from_utf8_lossy(&result.stderr).trim()
+                    );
+                }
+            }
+        }
+
+        let after_bytes = self
+            .fs
+            .metadata(&output)
+            .with_context(|| format!("{} was not written", output.display()))?
+            .len();
+        Ok(CompressedAsset {
+            source: source.clone(),
+            output,
+            before_bytes: opportunity.current_bytes,
+            after_bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::process::{Command, ExitStatus, Output};
+
+    fn asset(dir: &Path, name: &str, size_bytes: u64, asset_type: AssetType) -> DetectedAsset {
+        DetectedAsset {
+            file_path: name.to_string(),
+            size_bytes,
+            asset_type,
+            source_location: format!("{}:0", dir.join("lib.rs").display()),
+            detection_method: "include_bytes!".to_string(),
+        }
+    }
+
+    /// Writes half of the input to the `-o` path, like a very good cwebp
+    struct FakeCwebp;
+
+    impl CommandExecutor for FakeCwebp {
+        fn status(&self, _cmd: &mut Command) -> io::Result<ExitStatus> {
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, cmd: &mut Command) -> io::Result<Output> {
+            let args: Vec<_> = cmd.get_args().collect();
+            let input = std::fs::read(args[1])?;
+            std::fs::write(args[3], &input[..input.len() / 2])?;
+            Ok(Output {
+                status: crate::infra::mock_exit_status(0),
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_compression_opportunities_estimate_and_measure() {
+        let temp = tempfile::tempdir().unwrap();
+        let pretty = serde_json::to_string_pretty(&serde_json::json!({
+            "items": (0..300).collect::<Vec<_>>()
+        }))
+        .unwrap();
+        let minified_len = pretty.split_whitespace().collect::<String>().len() as u64;
+        std::fs::write(temp.path().join("data.json"), &pretty).unwrap();
+        let assets = vec![
+            asset(temp.path(), "font.ttf", 100_000, AssetType::Font),
+            asset(
+                temp.path(),
+                "data.json",
+                pretty.len() as u64,
+                AssetType::Data,
+            ),
+            asset(temp.path(), "icon.woff2", 50_000, AssetType::Font),
+            asset(temp.path(), "tiny.png", 200, AssetType::Image),
+        ];
+
+        let found = compression_opportunities(&assets, &RealFileSystem);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].kind, CompressionKind::Woff2);
+        assert_eq!(found[0].estimated_bytes, 50_000);
+        assert!(!found[0].exact);
+        assert_eq!(found[1].kind, CompressionKind::MinifiedJson);
+        assert_eq!(found[1].estimated_bytes, minified_len);
+        assert!(found[1].exact);
+    }
+
+    #[test]
+    fn test_compress_writes_converted_file_next_to_original() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("logo.png"), vec![7u8; 4096]).unwrap();
+        std::fs::write(temp.path().join("data.json"), "{\n  \"a\": 1\n}\n").unwrap();
+        let opportunities = compression_opportunities(
+            &[
+                asset(temp.path(), "logo.png", 4096, AssetType::Image),
+                asset(temp.path(), "data.json", 1100, AssetType::Data),
+            ],
+            &RealFileSystem,
+        );
+        let compressor = AssetCompressor::with_executors(RealFileSystem, FakeCwebp);
+
+        let webp = compressor.compress(&opportunities[1]).unwrap();
+        assert_eq!(webp.output, temp.path().join("logo.webp"));
+        assert_eq!(webp.after_bytes, 2048);
+        let json = compressor.compress(&opportunities[0]).unwrap();
+        assert_eq!(std::fs::read_to_string(json.output).unwrap(), "{\"a\":1}");
+    }
+}
@@ -0,0 +1,287 @@
+//! Externalization scaffolding for embedded assets
+//!
+//! `analyze --mode assets --apply` takes the critical and high priority assets
+//! embedded with `include_bytes!`/`include_str!`, copies them into
+//! `public/assets/` so the web server can serve them, and writes a patch that
+//! replaces each embedding site with a note on how to fetch the asset at
+//! runtime. Sources are not edited in place: an embedding site usually sits in
+//! a `const` or `static` that cannot become an `async` fetch without reworking
+//! its callers, so the patch is applied (and finished) by hand. The originals
+//! stay where they are until then, so the project keeps building.
+//!
+//! Embedded directories (rust-embed, `include_dir!`) are left alone.
+
+use super::asset_metrics::{DetectedAsset, ScanResults};
+use super::asset_types::{AssetPriority, AssetType};
+use super::source_patch::unified_diff;
+use crate::infra::{FileSystem, RealFileSystem};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Where externalized assets are copied, relative to the project root
+pub const PUBLIC_ASSETS_DIR: &str = "public/assets";
+
+/// URL prefix the assets are served under
+pub const ASSETS_URL_PREFIX: &str = "/assets";
+
+/// Patch with the rewritten embedding sites, inside the state directory
+pub const EXTERNALIZE_PATCH_FILE: &str = "externalize-assets.patch";
+
+/// One asset moved out of the binary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AssetMove {
+    /// Current location
+    pub from: PathBuf,
+    /// Location under `public/assets/`
+    pub to: PathBuf,
+    /// URL to fetch it from at runtime
+    pub url: String,
+    /// Size in bytes
+    pub size_bytes: u64,
+}
+
+/// What `--apply` does
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExternalizationPlan {
+    /// Assets to copy into `public/assets/`
+    pub moves: Vec<AssetMove>,
+    /// Unified diff replacing the embedding sites (empty when nothing matched)
+    pub patch: String,
+}
+
+impl ExternalizationPlan {
+    /// Bytes removed from the binary once the patch is applied
+    pub fn total_bytes(&self) -> u64 {
+        self.moves.iter().map(|m| m.size_bytes).sum()
+    }
+}
+
+/// Plans and applies asset externalization
+pub struct AssetExternalizer<FS: FileSystem = RealFileSystem> {
+    project_root: PathBuf,
+    fs: FS,
+}
+
+impl AssetExternalizer<RealFileSystem> {
+    /// Create an externalizer for the given project root
+    pub fn new(project_root: impl Into<PathBuf>) -> Self {
+        Self::with_fs(project_root, RealFileSystem)
+    }
+}
+
+impl<FS: FileSystem> AssetExternalizer<FS> {
+    /// Create an externalizer with a custom filesystem implementation
+    pub fn with_fs(project_root: impl Into<PathBuf>, fs: FS) -> Self {
+        Self {
+            project_root: project_root.into(),
+            fs,
+        }
+    }
+
+    /// Plan the externalization of the critical and high priority assets
+    pub fn plan(&self, results: &ScanResults) -> Result<ExternalizationPlan> {
+        let selected: Vec<&DetectedAsset> = [AssetPriority::Critical, AssetPriority::High]
+            .iter()
+            .filter_map(|p| results.assets_by_priority.get(p))
+            .flatten()
+            .filter(|a| a.asset_type != AssetType::Directory)
+            .collect();
+
+        // The same asset may be embedded from several sites (and detected
+        // twice per site); it is copied once
+        let mut moves: BTreeMap<PathBuf, AssetMove> = BTreeMap::new();
+        let mut taken = BTreeSet::new();
+        // Per source file: asset path as written -> URL
+        let mut sites: BTreeMap<PathBuf, BTreeMap<String, String>> = BTreeMap::new();
+        for asset in selected {
+            let source_file = asset.source_file();
+            let from = asset.resolved_path();
+            let from = from.canonicalize().unwrap_or(from);
+            let url = match moves.get(&from) {
+                Some(existing) => existing.url.clone(),
+                None => {
+                    let name = unique_name(&from, &mut taken);
+                    let asset_move = AssetMove {
+                        to: self.project_root.join(PUBLIC_ASSETS_DIR).join(&name),
+                        url: format!("{}/{}", ASSETS_URL_PREFIX, name),
+                        from: from.clone(),
+                        size_bytes: asset.size_bytes,
+                    };
+                    let url = asset_move.url.clone();
+                    moves.insert(from, asset_move);
+                    url
+                }
+            };
+            sites
+                .entry(source_file)
+                .or_default()
+                .insert(asset.file_path.clone(), url);
+        }
+
+        let mut patch = String::new();
+        for (source_file, assets) in &sites {
+            let content = self
+                .fs
+                .read_to_string(source_file)
+                .with_context(|| format!("Failed to read {}", source_file.display()))?;
+            let display = source_file
+                .strip_prefix(&self.project_root)
+                .unwrap_or(source_file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            patch.push_str(&file_patch(&display, &content, assets));
+        }
+
+        Ok(ExternalizationPlan {
+            moves: moves.into_values().collect(),
+            patch,
+        })
+    }
+
+    /// Copy the assets into `public/assets/` and write the patch
+    ///
+    /// Returns where the patch was written.
+    pub fn apply(&self, plan: &ExternalizationPlan) -> Result<PathBuf> {
+        for asset_move in &plan.moves {
+            if let Some(parent) = asset_move.to.parent() {
+                self.fs
+                    .create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            self.fs
+                .copy(&asset_move.from, &asset_move.to)
+                .with_context(|| {
+                    format!(
+                        "Failed to copy {} to {}",
+                        asset_move.from.display(),
+                        asset_move.to.display()
+                    )
+                })?;
+        }
+
+        let patch_path = crate::state::state_dir(&self.project_root).join(EXTERNALIZE_PATCH_FILE);
+        if let Some(parent) = patch_path.parent() {
+            self.fs
+                .create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        self.fs
+            .write(&patch_path, &plan.patch)
+            .with_context(|| format!("Failed to write {}", patch_path.display()))?;
+        Ok(patch_path)
+    }
+}
+
+/// File name under `public/assets/`, numbered when two assets share a name
+fn unique_name(from: &Path, taken: &mut BTreeSet<String>) -> String {
+    let name = from
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "asset".to_string());
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem.to_string(), format!(".{}", ext)),
+        _ => (name.clone(), String::new()),
+    };
+    let mut candidate = name;
+    let mut n = 2;
+    while !taken.insert(candidate.clone()) {
+        candidate = format!("{}-{}{}", stem, n, ext);
+        n += 1;
+    }
+    candidate
+}
+
+/// Unified diff for one source file, replacing every line that embeds one of
+/// `assets` (path as written -> URL) with a runtime loading note
+fn file_patch(display: &str, content: &str, assets: &BTreeMap<String, String>) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let replacements: BTreeMap<usize, Vec<String>> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let url = assets.iter().find_map(|(path, url)| {
+                let literal = format!("\"{}\"", path);
+                let embeds = (line.contains("include_bytes!") || line.contains("include_str!"))
+                    && line.contains(&literal);
+                embeds.then_some(url)
+            })?;
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let loader = if line.contains("include_str!") {
+                "load_asset_text"
+            } else {
+                "load_asset"
+            };
+            Some((
+                i,
+                vec![
+                    format!(
+                        "{}// Externalized by wasm-slim: served from {}",
+                        indent, url
+                    ),
+                    format!("{}// let bytes = {}(\"{}\").await?;", indent, loader, url),
+                    format!("{}// {}", indent, line.trim_start()),
+                ],
+            ))
+        })
+        .collect();
+    unified_diff(display, &lines, &replacements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::assets::AssetDetector;
+
+    #[test]
+    fn test_file_patch_replaces_embedding_lines_with_context() {
+        let content = "use std::io;\n\nconst FONT: &[u8] = include_bytes!(\"../fonts/a.woff2\");\nconst CSS: &str = include_str!(\"site.css\");\n\nfn main() {}\n";
+        let assets = BTreeMap::from([
+            (
+                "../fonts/a.woff2".to_string(),
+                "/assets/a.woff2".to_string(),
+            ),
+            ("site.css".to_string(), "/assets/site.css".to_string()),
+        ]);
+
+        let patch = file_patch("src/lib.rs", content, &assets);
+
+        assert!(patch.starts_with("--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,6 +1,10 @@\n"));
+        assert!(patch.contains("-const FONT: &[u8] = include_bytes!(\"../fonts/a.woff2\");\n"));
+        assert!(patch.contains("+// let bytes = load_asset(\"/assets/a.woff2\").await?;\n"));
+        assert!(patch.contains("+// let bytes = load_asset_text(\"/assets/site.css\").await?;\n"));
+        assert!(patch.ends_with(" fn main() {}\n"));
+        assert_eq!(file_patch("src/lib.rs", "fn main() {}\n", &assets), "");
+    }
+
+    #[test]
+    fn test_plan_and_apply_copy_large_assets_and_write_patch() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let src = root.join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("big.bin"), vec![0u8; 600 * 1024]).unwrap();
+        std::fs::write(src.join("small.txt"), "tiny").unwrap();
+        std::fs::write(
+            src.join("lib.rs"),
+            "static BIG: &[u8] = include_bytes!(\"big.bin\");\nstatic SMALL: &str = include_str!(\"small.txt\");\n",
+        )
+        .unwrap();
+        let results = AssetDetector::new(&root).scan_project().unwrap();
+        let externalizer = AssetExternalizer::new(&root);
+
+        let plan = externalizer.plan(&results).unwrap();
+        assert_eq!(plan.moves.len(), 1);
+        assert_eq!(plan.moves[0].url, "/assets/big.bin");
+        assert_eq!(plan.total_bytes(), 600 * 1024);
+        assert!(plan
+            .patch
+            .contains(" static SMALL: &str = include_str!(\"small.txt\");\n"));
+
+        let patch_path = externalizer.apply(&plan).unwrap();
+        assert!(root.join(PUBLIC_ASSETS_DIR).join("big.bin").is_file());
+        assert!(src.join("big.bin").is_file());
+        assert_eq!(std::fs::read_to_string(patch_path).unwrap(), plan.patch);
+    }
+}
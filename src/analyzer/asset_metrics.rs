@@ -1,9 +1,13 @@
 //! Asset scan results and metrics
 
+use super::report_utils::SizedEntry;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
+use super::asset_compression::CompressionOpportunity;
 use super::asset_types::AssetPriority;
+use super::thresholds::RecommendationThresholds;
 
 /// A detected embedded asset
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,34 +24,72 @@ pub struct DetectedAsset {
     pub detection_method: String,
 }
 
+impl SizedEntry for DetectedAsset {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.file_path
+    }
+}
+
+impl DetectedAsset {
+    /// Source file the asset was detected in
+    pub fn source_file(&self) -> PathBuf {
+        let path = self
+            .source_location
+            .rsplit_once(':')
+            .map_or(self.source_location.as_str(), |(path, _)| path);
+        PathBuf::from(path)
+    }
+
+    /// Location of a single-file asset on disk
+    pub fn resolved_path(&self) -> PathBuf {
+        self.source_file()
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(&self.file_path)
+    }
+}
+
 /// Complete scan results with all detected assets
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ScanResults {
     /// Total number of detected assets
     pub total_assets: usize,
-    /// Total size of all assets in KB
-    pub total_size_kb: u64,
-    /// Total bundle size in KB
-    pub bundle_size_kb: u64,
+    /// Total size of all assets in bytes
+    pub total_size_bytes: u64,
+    /// Total bundle size in bytes (0 if no bundle was built)
+    pub bundle_size_bytes: u64,
     /// Percentage of bundle occupied by assets
     pub bundle_percentage: f64,
     /// All detected assets
     pub assets: Vec<DetectedAsset>,
     /// Assets grouped by priority level
-    pub assets_by_priority: HashMap<AssetPriority, Vec<DetectedAsset>>,
+    pub assets_by_priority: BTreeMap<AssetPriority, Vec<DetectedAsset>>,
     /// Estimated savings from externalization
     pub estimated_savings: EstimatedSavings,
+    /// Assets that could be stored in a more compact format
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compression: Vec<CompressionOpportunity>,
+    /// Files that were skipped or could only be read partially
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Thresholds the priorities were assigned with
+    #[serde(skip)]
+    pub thresholds: RecommendationThresholds,
 }
 
 /// Estimated savings from externalizing assets
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EstimatedSavings {
-    /// Savings from externalizing critical priority assets only (KB)
-    pub critical_only_kb: u64,
-    /// Savings from externalizing high and critical priority assets (KB)
-    pub high_and_critical_kb: u64,
-    /// Savings from externalizing all assets (KB)
-    pub all_assets_kb: u64,
+    /// Savings from externalizing critical priority assets only (bytes)
+    pub critical_only_bytes: u64,
+    /// Savings from externalizing high and critical priority assets (bytes)
+    pub high_and_critical_bytes: u64,
+    /// Savings from externalizing all assets (bytes)
+    pub all_assets_bytes: u64,
     /// Savings from critical assets as percentage of bundle
     pub critical_only_percent: f64,
     /// Savings from high+critical assets as percentage of bundle
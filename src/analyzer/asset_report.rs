@@ -2,40 +2,64 @@
 //!
 //! Provides console and JSON output formatters for asset detection results.
 
+use crate::analyzer::asset_compression::{CompressedAsset, CompressionOpportunity};
 use crate::analyzer::asset_display::AssetPriorityDisplay;
+use crate::analyzer::asset_externalize::ExternalizationPlan;
 use crate::analyzer::assets::{AssetPriority, ScanResults};
+use crate::analyzer::report_utils::{format_bytes, omitted_footer, ItemLimit};
+use crate::fmt::percent_of;
 use console::style;
+use std::path::Path;
 
 /// Print asset detection report to console
 pub fn print_asset_report(results: &ScanResults) {
+    print_asset_report_with_limit(results, ItemLimit::Default);
+}
+
+/// Print asset detection report, limiting the assets listed per priority
+pub fn print_asset_report_with_limit(results: &ScanResults, limit: ItemLimit) {
     println!();
     println!("{}", style("Asset Detection Report").bold());
     println!("{}", style("━".repeat(50)).dim());
     println!();
 
     // Summary
-    let total_mb = results.total_size_kb as f64 / 1024.0;
     println!(
         "📦 {} {}",
         style("Embedded Assets Found:").bold(),
         style(format!(
-            "{} ({:.2} MB total, {:.1}% of bundle)",
-            results.total_assets, total_mb, results.bundle_percentage
+            "{} ({} total, {:.1}% of bundle)",
+            results.total_assets,
+            format_bytes(results.total_size_bytes),
+            results.bundle_percentage
         ))
         .cyan()
     );
     println!();
 
+    // Files the scan skipped or read lossily
+    if !results.warnings.is_empty() {
+        println!("{}", style("Warnings:").bold().yellow());
+        for warning in &results.warnings {
+            println!("   {} {}", style("⚠️").bold(), style(warning).yellow());
+        }
+        println!();
+    }
+
     if results.total_assets == 0 {
         println!("✨ No embedded assets detected. Bundle is asset-free!");
         return;
     }
 
     // Group by priority and display
-    print_priority_group(results, AssetPriority::Critical, ">10% or >500KB");
-    print_priority_group(results, AssetPriority::High, "5-10% or 200-500KB");
-    print_priority_group(results, AssetPriority::Medium, "2-5% or 100-200KB");
-    print_priority_group(results, AssetPriority::Low, "<2% or <100KB");
+    for priority in [
+        AssetPriority::Critical,
+        AssetPriority::High,
+        AssetPriority::Medium,
+        AssetPriority::Low,
+    ] {
+        print_priority_group(results, priority, limit);
+    }
 
     println!();
     println!("{}", style("━".repeat(50)).dim());
@@ -43,6 +67,7 @@ pub fn print_asset_report(results: &ScanResults) {
 
     // Estimated savings
     print_savings_estimate(results);
+    print_compression_opportunities(results, limit);
 
     println!();
     println!(
@@ -52,7 +77,7 @@ pub fn print_asset_report(results: &ScanResults) {
     println!();
 }
 
-fn print_priority_group(results: &ScanResults, priority: AssetPriority, threshold: &str) {
+fn print_priority_group(results: &ScanResults, priority: AssetPriority, limit: ItemLimit) {
     if let Some(assets) = results.assets_by_priority.get(&priority) {
         if assets.is_empty() {
             return;
@@ -63,21 +88,17 @@ fn print_priority_group(results: &ScanResults, priority: AssetPriority, threshol
             "{} {} ({}):",
             style(&priority_name).fg(priority.color()).bold(),
             style("Priority").fg(priority.color()),
-            style(threshold).dim()
+            style(priority.threshold_label(&results.thresholds)).dim()
         );
 
-        for asset in assets {
-            let size_kb = asset.size_bytes / 1024;
-            let size_str = if size_kb >= 1024 {
-                format!("{:.2} MB", size_kb as f64 / 1024.0)
-            } else {
-                format!("{} KB", size_kb)
-            };
+        let shown = limit.visible(assets.len(), None);
+        for asset in assets.iter().take(shown) {
+            let size_str = format_bytes(asset.size_bytes);
 
-            let percentage = if results.bundle_size_kb > 0 {
+            let percentage = if results.bundle_size_bytes > 0 {
                 format!(
                     "({:.1}%)",
-                    (size_kb as f64 / results.bundle_size_kb as f64) * 100.0
+                    percent_of(asset.size_bytes, results.bundle_size_bytes)
                 )
             } else {
                 String::new()
@@ -96,6 +117,9 @@ fn print_priority_group(results: &ScanResults, priority: AssetPriority, threshol
                 style(&asset.source_location).dim()
             );
         }
+        if let Some(footer) = omitted_footer(assets.len(), shown, "assets") {
+            println!("  {}", style(footer).dim());
+        }
         println!();
     }
 }
@@ -103,31 +127,123 @@ fn print_priority_group(results: &ScanResults, priority: AssetPriority, threshol
 fn print_savings_estimate(results: &ScanResults) {
     println!("{}", style("💡 Externalization Impact Estimate:").bold());
 
-    if results.estimated_savings.critical_only_kb > 0 {
+    if results.estimated_savings.critical_only_bytes > 0 {
         println!(
-            "   - Externalizing Critical assets: ~{} KB saved ({:.1}%)",
-            results.estimated_savings.critical_only_kb,
+            "   - Externalizing Critical assets: ~{} saved ({:.1}%)",
+            format_bytes(results.estimated_savings.critical_only_bytes),
             results.estimated_savings.critical_only_percent
         );
     }
 
-    if results.estimated_savings.high_and_critical_kb > 0 {
+    if results.estimated_savings.high_and_critical_bytes > 0 {
         println!(
-            "   - Externalizing High+Critical: ~{} KB saved ({:.1}%)",
-            results.estimated_savings.high_and_critical_kb,
+            "   - Externalizing High+Critical: ~{} saved ({:.1}%)",
+            format_bytes(results.estimated_savings.high_and_critical_bytes),
             results.estimated_savings.high_and_critical_percent
         );
     }
 
     println!(
-        "   - Externalizing all assets: ~{} KB saved ({:.1}%)",
-        results.estimated_savings.all_assets_kb, results.estimated_savings.all_assets_percent
+        "   - Externalizing all assets: ~{} saved ({:.1}%)",
+        format_bytes(results.estimated_savings.all_assets_bytes),
+        results.estimated_savings.all_assets_percent
     );
 }
 
+fn print_compression_opportunities(results: &ScanResults, limit: ItemLimit) {
+    if results.compression.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{}", style("🗜️  Compression Opportunities:").bold());
+    let shown = limit.visible(results.compression.len(), None);
+    for opportunity in results.compression.iter().take(shown) {
+        let approx = if opportunity.exact { "" } else { "~" };
+        println!(
+            "   - {}: {} ({} → {}{}, {}{} saved)",
+            style(&opportunity.file_path).cyan(),
+            opportunity.kind.description(),
+            format_bytes(opportunity.current_bytes),
+            approx,
+            format_bytes(opportunity.estimated_bytes),
+            approx,
+            style(format_bytes(opportunity.savings_bytes())).green()
+        );
+    }
+    if let Some(footer) = omitted_footer(results.compression.len(), shown, "assets") {
+        println!("     {}", style(footer).dim());
+    }
+    let total: u64 = results.compression.iter().map(|o| o.savings_bytes()).sum();
+    println!(
+        "   {} ~{} in total; convert with wasm-slim analyze --mode assets --compress",
+        style("→").dim(),
+        format_bytes(total)
+    );
+}
+
+/// Print what `--compress` did (or would do) for each opportunity
+///
+/// An outcome of `None` means the conversion was not attempted: a dry run,
+/// or the tool it needs is not installed.
+pub fn print_compression(
+    outcomes: &[(
+        &CompressionOpportunity,
+        Option<Result<CompressedAsset, String>>,
+    )],
+    dry_run: bool,
+) {
+    println!();
+    println!("{}", style("🗜️  Asset Compression").bold().underlined());
+    println!();
+
+    if outcomes.is_empty() {
+        println!("✨ No fonts, images or JSON files to compress.");
+        println!();
+        return;
+    }
+
+    for (opportunity, outcome) in outcomes {
+        let output = opportunity.kind.output_path(&opportunity.path);
+        match outcome {
+            Some(Ok(compressed)) => println!(
+                "  {} {} → {} ({} → {})",
+                style("✓").green(),
+                style(compressed.source.display()).cyan(),
+                style(compressed.output.display()).green(),
+                format_bytes(compressed.before_bytes),
+                style(format_bytes(compressed.after_bytes)).bold()
+            ),
+            Some(Err(e)) => println!(
+                "  {} {}: {}",
+                style("✗").red(),
+                style(opportunity.path.display()).cyan(),
+                style(e).red()
+            ),
+            None if dry_run => println!(
+                "  Would write {} → {}",
+                style(opportunity.path.display()).cyan(),
+                style(output.display()).green()
+            ),
+            None => println!(
+                "  {} {}: {} not found; install it to {}",
+                style("⚠️").yellow(),
+                style(opportunity.path.display()).cyan(),
+                opportunity.kind.tool().unwrap_or_default(),
+                opportunity.kind.description()
+            ),
+        }
+    }
+    if outcomes.iter().any(|(_, o)| matches!(o, Some(Ok(_)))) {
+        println!();
+        println!("   Point the include_bytes!/include_str! sites at the new files and delete the originals.");
+    }
+    println!();
+}
+
 /// Print JSON output of scan results
 pub fn print_json_output(results: &ScanResults) -> anyhow::Result<()> {
-    let json = serde_json::to_string_pretty(results)?;
+    let json = crate::redact::to_json_pretty(results)?;
     println!("{}", json);
     Ok(())
 }
@@ -170,6 +286,99 @@ pub fn show_externalization_guide(results: &ScanResults) {
     println!();
 }
 
+/// Print what `--apply` did (or would do) and the loader code to finish it
+///
+/// `patch_path` is where the patch was written; without it (dry run) the
+/// patch is printed instead.
+pub fn print_externalization(plan: &ExternalizationPlan, patch_path: Option<&Path>) {
+    println!();
+    println!("{}", style("📦 Asset Externalization").bold().underlined());
+    println!();
+
+    if plan.moves.is_empty() {
+        println!("✨ No critical or high priority assets to externalize.");
+        println!();
+        return;
+    }
+
+    let verb = if patch_path.is_some() {
+        "Copied"
+    } else {
+        "Would copy"
+    };
+    for asset_move in &plan.moves {
+        println!(
+            "  {} {} → {} ({})",
+            verb,
+            style(asset_move.from.display()).cyan(),
+            style(asset_move.to.display()).green(),
+            format_bytes(asset_move.size_bytes)
+        );
+    }
+    println!(
+        "  {} {} leaves the binary once the patch is applied",
+        style("→").dim(),
+        style(format_bytes(plan.total_bytes())).green().bold()
+    );
+    println!();
+
+    match patch_path {
+        Some(path) => {
+            println!(
+                "{} Embedding sites rewritten in {}",
+                style("📝").bold(),
+                style(path.display()).cyan()
+            );
+            println!(
+                "   Review and apply it with {}, then replace each use with the",
+                style(format!("git apply {}", path.display())).bold()
+            );
+            println!("   loader call noted in the patch and delete the original files.");
+        }
+        None => {
+            println!("{}", style("📝 Patch (dry run, not written):").bold());
+            print!("{}", plan.patch);
+        }
+    }
+    println!();
+
+    println!(
+        "{}",
+        style("🦀 Rust loader (web-sys, wasm-bindgen-futures, js-sys):").bold()
+    );
+    println!("{}", style(RUST_LOADER).dim());
+    println!("{}", style("🌐 Preload the assets in index.html:").bold());
+    for asset_move in &plan.moves {
+        println!(
+            "   <link rel=\"preload\" href=\"{}\" as=\"fetch\" crossorigin>",
+            asset_move.url
+        );
+    }
+    println!();
+}
+
+/// Runtime loader printed by `--apply`
+const RUST_LOADER: &str = r#"   use wasm_bindgen::{JsCast, JsValue};
+   use wasm_bindgen_futures::JsFuture;
+
+   /// Fetch an externalized asset
+   pub async fn load_asset(url: &str) -> Result<Vec<u8>, JsValue> {
+       let window = web_sys::window().ok_or("no window")?;
+       let response: web_sys::Response =
+           JsFuture::from(window.fetch_with_str(url)).await?.dyn_into()?;
+       if !response.ok() {
+           return Err(format!("{} returned {}", url, response.status()).into());
+       }
+       let buffer = JsFuture::from(response.array_buffer()?).await?;
+       Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+   }
+
+   /// Fetch an externalized text asset
+   pub async fn load_asset_text(url: &str) -> Result<String, JsValue> {
+       String::from_utf8(load_asset(url).await?).map_err(|e| e.to_string().into())
+   }
+"#;
+
 fn show_font_guide() {
     println!("{}", style("🔤 Externalizing Fonts:").bold());
     println!();
@@ -234,7 +443,7 @@ fn show_general_guide() {
 mod tests {
     use super::*;
     use crate::analyzer::assets::{AssetType, DetectedAsset, EstimatedSavings};
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
 
     // Helper function to create test scan results
     fn create_test_results(
@@ -242,27 +451,28 @@ mod tests {
         total_size_kb: u64,
         bundle_size_kb: u64,
     ) -> ScanResults {
-        let bundle_percentage = if bundle_size_kb > 0 {
-            (total_size_kb as f64 / bundle_size_kb as f64) * 100.0
-        } else {
-            0.0
-        };
+        let total_size_bytes = total_size_kb * 1024;
+        let bundle_size_bytes = bundle_size_kb * 1024;
+        let bundle_percentage = percent_of(total_size_bytes, bundle_size_bytes);
 
         ScanResults {
             total_assets,
-            total_size_kb,
-            bundle_size_kb,
+            total_size_bytes,
+            bundle_size_bytes,
             bundle_percentage,
             assets: vec![],
-            assets_by_priority: HashMap::new(),
+            assets_by_priority: BTreeMap::new(),
             estimated_savings: EstimatedSavings {
-                critical_only_kb: 0,
-                high_and_critical_kb: 0,
-                all_assets_kb: total_size_kb,
+                critical_only_bytes: 0,
+                high_and_critical_bytes: 0,
+                all_assets_bytes: total_size_bytes,
                 critical_only_percent: 0.0,
                 high_and_critical_percent: 0.0,
                 all_assets_percent: bundle_percentage,
             },
+            compression: Vec::new(),
+            warnings: Vec::new(),
+            thresholds: Default::default(),
         }
     }
 
@@ -340,19 +550,22 @@ mod tests {
         // Test savings estimate with no critical/high assets
         let results = ScanResults {
             total_assets: 1,
-            total_size_kb: 50,
-            bundle_size_kb: 1000,
+            total_size_bytes: 50 * 1024,
+            bundle_size_bytes: 1000 * 1024,
             bundle_percentage: 5.0,
             assets: vec![],
-            assets_by_priority: HashMap::new(),
+            assets_by_priority: BTreeMap::new(),
             estimated_savings: EstimatedSavings {
-                critical_only_kb: 0,
-                high_and_critical_kb: 0,
-                all_assets_kb: 50,
+                critical_only_bytes: 0,
+                high_and_critical_bytes: 0,
+                all_assets_bytes: 50 * 1024,
                 critical_only_percent: 0.0,
                 high_and_critical_percent: 0.0,
                 all_assets_percent: 5.0,
             },
+            compression: Vec::new(),
+            warnings: Vec::new(),
+            thresholds: Default::default(),
         };
 
         print_savings_estimate(&results);
@@ -364,19 +577,22 @@ mod tests {
         // Test savings estimate with critical assets
         let results = ScanResults {
             total_assets: 3,
-            total_size_kb: 800,
-            bundle_size_kb: 1000,
+            total_size_bytes: 800 * 1024,
+            bundle_size_bytes: 1000 * 1024,
             bundle_percentage: 80.0,
             assets: vec![],
-            assets_by_priority: HashMap::new(),
+            assets_by_priority: BTreeMap::new(),
             estimated_savings: EstimatedSavings {
-                critical_only_kb: 600,
-                high_and_critical_kb: 750,
-                all_assets_kb: 800,
+                critical_only_bytes: 600 * 1024,
+                high_and_critical_bytes: 750 * 1024,
+                all_assets_bytes: 800 * 1024,
                 critical_only_percent: 60.0,
                 high_and_critical_percent: 75.0,
                 all_assets_percent: 80.0,
             },
+            compression: Vec::new(),
+            warnings: Vec::new(),
+            thresholds: Default::default(),
         };
 
         print_savings_estimate(&results);
@@ -436,7 +652,7 @@ mod tests {
     fn test_print_priority_group_empty_list_no_output() {
         // Test priority group with no assets of that priority
         let results = create_test_results(0, 0, 1000);
-        print_priority_group(&results, AssetPriority::Critical, ">10%");
+        print_priority_group(&results, AssetPriority::Critical, ItemLimit::Default);
         // No panic = success
     }
 
@@ -453,7 +669,7 @@ mod tests {
             .assets_by_priority
             .insert(AssetPriority::Critical, critical_assets);
 
-        print_priority_group(&results, AssetPriority::Critical, ">10% or >500KB");
+        print_priority_group(&results, AssetPriority::Critical, ItemLimit::Default);
         // No panic = success
     }
 
@@ -473,8 +689,8 @@ mod tests {
             "JSON should contain total_assets"
         );
         assert!(
-            json_str.contains("total_size_kb"),
-            "JSON should contain total_size_kb"
+            json_str.contains("total_size_bytes"),
+            "JSON should contain total_size_bytes"
         );
         assert!(
             json_str.contains("bundle_percentage"),
@@ -1,5 +1,6 @@
 //! Asset type definitions
 
+use super::thresholds::RecommendationThresholds;
 use serde::{Deserialize, Serialize};
 
 /// Type of detected asset
@@ -12,6 +13,8 @@ pub enum AssetType {
     Image,
     /// Generic data file
     Data,
+    /// Whole directory embedded with rust-embed or `include_dir!`
+    Directory,
     /// Unknown or unrecognized asset type
     Unknown,
 }
@@ -21,31 +24,34 @@ pub enum AssetType {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AssetPriority {
-    /// <2% of bundle or <100KB
+    /// <2% of bundle or <100 KiB
     Low,
-    /// 2-5% of bundle or 100-200KB
+    /// 2-5% of bundle or 100-200 KiB
     Medium,
-    /// 5-10% of bundle or 200-500KB
+    /// 5-10% of bundle or 200-500 KiB
     High,
-    /// >10% of bundle or >500KB
+    /// >10% of bundle or >500 KiB
     Critical,
 }
 
 impl AssetPriority {
     /// Priority thresholds
     pub const CRITICAL_PERCENTAGE: f64 = 10.0;
-    /// Critical size threshold in KB
+    /// Critical size threshold in KiB
     pub const CRITICAL_SIZE_KB: u64 = 500;
     /// High priority percentage threshold
     pub const HIGH_PERCENTAGE: f64 = 5.0;
-    /// High size threshold in KB
+    /// High size threshold in KiB
     pub const HIGH_SIZE_KB: u64 = 200;
     /// Medium priority percentage threshold
     pub const MEDIUM_PERCENTAGE: f64 = 2.0;
-    /// Medium size threshold in KB
+    /// Medium size threshold in KiB
     pub const MEDIUM_SIZE_KB: u64 = 100;
 
-    /// Calculate priority based on asset size and bundle size
+    /// Calculate priority from sizes in whole KiB
+    ///
+    /// Prefer [`AssetPriority::from_bytes`]; whole KiB drop the remainder of
+    /// each size, which matters close to the thresholds.
     ///
     /// Priority is determined by either percentage of bundle or absolute size:
     /// - **Critical**: >10% of bundle OR >500KB
@@ -81,20 +87,89 @@ impl AssetPriority {
     /// assert_eq!(priority, AssetPriority::High);
     /// ```
     pub fn from_size(asset_kb: u64, bundle_kb: u64) -> Self {
-        let percentage = if bundle_kb > 0 {
-            (asset_kb as f64 / bundle_kb as f64) * 100.0
-        } else {
-            0.0
-        };
+        Self::from_bytes(
+            asset_kb.saturating_mul(1024),
+            bundle_kb.saturating_mul(1024),
+        )
+    }
 
-        if percentage > Self::CRITICAL_PERCENTAGE || asset_kb > Self::CRITICAL_SIZE_KB {
+    /// Calculate priority based on asset size and bundle size in bytes
+    ///
+    /// Same thresholds as [`AssetPriority::from_size`], compared exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_slim::analyzer::asset_types::AssetPriority;
+    ///
+    /// // Just over 100 KiB is medium, even if it rounds down to 100 KiB
+    /// assert_eq!(AssetPriority::from_bytes(100 * 1024 + 1, 0), AssetPriority::Medium);
+    /// assert_eq!(AssetPriority::from_bytes(100 * 1024, 0), AssetPriority::Low);
+    /// ```
+    pub fn from_bytes(asset_bytes: u64, bundle_bytes: u64) -> Self {
+        Self::from_bytes_with(
+            asset_bytes,
+            bundle_bytes,
+            &RecommendationThresholds::default(),
+        )
+    }
+
+    /// Calculate priority in bytes against configured thresholds
+    /// (`[analysis.thresholds]`)
+    pub fn from_bytes_with(
+        asset_bytes: u64,
+        bundle_bytes: u64,
+        thresholds: &RecommendationThresholds,
+    ) -> Self {
+        let percentage = crate::fmt::percent_of(asset_bytes, bundle_bytes);
+
+        if percentage > thresholds.asset_critical_percent
+            || asset_bytes > thresholds.asset_critical_bytes
+        {
             AssetPriority::Critical
-        } else if percentage > Self::HIGH_PERCENTAGE || asset_kb > Self::HIGH_SIZE_KB {
+        } else if percentage > thresholds.asset_high_percent
+            || asset_bytes > thresholds.asset_high_bytes
+        {
             AssetPriority::High
-        } else if percentage > Self::MEDIUM_PERCENTAGE || asset_kb > Self::MEDIUM_SIZE_KB {
+        } else if percentage > thresholds.asset_medium_percent
+            || asset_bytes > thresholds.asset_medium_bytes
+        {
             AssetPriority::Medium
         } else {
             AssetPriority::Low
         }
     }
+
+    /// Range of this priority under the given thresholds, e.g.
+    /// `5-10% or 200-500 KiB`
+    pub fn threshold_label(&self, thresholds: &RecommendationThresholds) -> String {
+        let t = thresholds;
+        let kib = |bytes: u64| bytes / 1024;
+        match self {
+            AssetPriority::Critical => format!(
+                ">{}% or >{} KiB",
+                t.asset_critical_percent,
+                kib(t.asset_critical_bytes)
+            ),
+            AssetPriority::High => format!(
+                "{}-{}% or {}-{} KiB",
+                t.asset_high_percent,
+                t.asset_critical_percent,
+                kib(t.asset_high_bytes),
+                kib(t.asset_critical_bytes)
+            ),
+            AssetPriority::Medium => format!(
+                "{}-{}% or {}-{} KiB",
+                t.asset_medium_percent,
+                t.asset_high_percent,
+                kib(t.asset_medium_bytes),
+                kib(t.asset_high_bytes)
+            ),
+            AssetPriority::Low => format!(
+                "<{}% or <{} KiB",
+                t.asset_medium_percent,
+                kib(t.asset_medium_bytes)
+            ),
+        }
+    }
 }
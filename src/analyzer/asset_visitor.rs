@@ -1,7 +1,9 @@
 //! AST visitor implementation for asset detection
 //!
 //! Provides a `syn::Visit` implementation to traverse Rust AST and detect
-//! embedded asset inclusions (`include_bytes!`, `include_str!`).
+//! embedded asset inclusions (`include_bytes!`, `include_str!`) and embedded
+//! directories (`#[derive(RustEmbed)]` with `#[folder = "..."]`,
+//! `include_dir!`, `embed_dir!`).
 //!
 //! # Overview
 //!
@@ -16,6 +18,8 @@
 //! 2. Checks for macro expressions
 //! 3. Identifies `include_bytes!` and `include_str!` macros
 //! 4. Extracts the file path from macro arguments
+//! 5. Records the folders of rust-embed structs and `include_dir!`/`embed_dir!`
+//!    invocations separately, since those embed every file below them
 //!
 //! # Examples
 //!
@@ -38,7 +42,7 @@
 
 use std::path::Path;
 use syn::visit::{self, Visit};
-use syn::{Expr, Item};
+use syn::{Expr, Item, ItemStruct, Lit, Meta};
 
 /// Visitor for traversing the AST to find asset inclusions
 ///
@@ -46,6 +50,7 @@ use syn::{Expr, Item};
 /// macro invocations, collecting file paths and detection metadata.
 pub struct AssetVisitor<'a> {
     assets: Vec<(String, usize, String)>, // (path, line, method)
+    directories: Vec<(String, usize, String)>, // (folder, line, method)
     _phantom: std::marker::PhantomData<&'a ()>,
 }
 
@@ -58,6 +63,7 @@ impl<'a> AssetVisitor<'a> {
     pub fn new(_source_file: &'a Path) -> Self {
         Self {
             assets: Vec::new(),
+            directories: Vec::new(),
             _phantom: std::marker::PhantomData,
         }
     }
@@ -84,6 +90,16 @@ impl<'a> AssetVisitor<'a> {
         &self.assets
     }
 
+    /// Get the collected embedded directories
+    ///
+    /// Same layout as [`assets`](Self::assets); the method is
+    /// `#[derive(RustEmbed)]`, `include_dir!` or `embed_dir!`. Folders are
+    /// written relative to the crate root and may start with
+    /// `$CARGO_MANIFEST_DIR`.
+    pub fn directories(&self) -> &[(String, usize, String)] {
+        &self.directories
+    }
+
     /// Check if a macro is an asset inclusion macro
     ///
     /// Examines the macro path to determine if it's `include_bytes!` or `include_str!`,
@@ -91,6 +107,14 @@ impl<'a> AssetVisitor<'a> {
     fn check_macro(&mut self, mac: &syn::Macro) {
         let path_str = quote::quote!(#mac.path).to_string();
 
+        let last = mac.path.segments.last().map(|s| s.ident.to_string());
+        if let Some(name @ ("include_dir" | "embed_dir")) = last.as_deref() {
+            if let Some(folder) = Self::extract_string_literal(&mac.tokens.to_string()) {
+                self.directories.push((folder, 0, format!("{}!", name)));
+            }
+            return;
+        }
+
         if path_str.contains("include_bytes") || path_str.contains("include_str") {
             // Extract the string literal from macro tokens
             let tokens = mac.tokens.to_string();
@@ -160,6 +184,39 @@ impl<'a> Visit<'a> for AssetVisitor<'a> {
         visit::visit_expr(self, expr);
     }
 
+    /// Visit a struct definition
+    ///
+    /// Records the `#[folder = "..."]` of structs deriving rust-embed's
+    /// `RustEmbed` (or `Embed`).
+    fn visit_item_struct(&mut self, item: &'a ItemStruct) {
+        let derives_embed = item.attrs.iter().any(|attr| {
+            attr.path().is_ident("derive") && {
+                // Matches `RustEmbed` and rust-embed 8's `Embed`
+                quote::quote!(#attr).to_string().contains("Embed")
+            }
+        });
+        if derives_embed {
+            for attr in &item.attrs {
+                let Meta::NameValue(meta) = &attr.meta else {
+                    continue;
+                };
+                if !meta.path.is_ident("folder") {
+                    continue;
+                }
+                if let Expr::Lit(lit) = &meta.value {
+                    if let Lit::Str(folder) = &lit.lit {
+                        self.directories.push((
+                            folder.value(),
+                            0,
+                            "#[derive(RustEmbed)]".to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+        visit::visit_item_struct(self, item);
+    }
+
     /// Visit an item node
     ///
     /// Continues traversal to nested items within the AST.
@@ -185,6 +242,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_visitor_collects_embedded_directories() {
+        let source = r#"
+            #[derive(rust_embed::RustEmbed)]
+            #[folder = "$CARGO_MANIFEST_DIR/static/"]
+            struct Static;
+
+            #[derive(Clone)]
+            #[folder = "ignored"]
+            struct NotEmbedded;
+
+            static TEMPLATES: Dir = include_dir!("templates");
+            const LOGO: &[u8] = include_bytes!("logo.png");
+        "#;
+        let syntax_tree: syn::File = syn::parse_str(source).unwrap();
+        let mut visitor = AssetVisitor::new(Path::new("src/lib.rs"));
+        visitor.visit_file(&syntax_tree);
+
+        assert_eq!(
+            visitor.directories(),
+            &[
+                (
+                    "$CARGO_MANIFEST_DIR/static/".to_string(),
+                    0,
+                    "#[derive(RustEmbed)]".to_string()
+                ),
+                ("templates".to_string(), 0, "include_dir!".to_string()),
+            ]
+        );
+        assert_eq!(visitor.assets().len(), 1);
+    }
+
     #[test]
     fn test_extract_string_literal_invalid_formats_returns_none() {
         assert_eq!(AssetVisitor::extract_string_literal(""), None);
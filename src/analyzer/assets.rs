@@ -2,12 +2,18 @@
 //!
 //! Detects embedded assets (fonts, images, data files) that could be externalized
 //! to reduce WASM bundle size. Based on Warp.dev's 10MB savings through asset
-//! externalization.
-
-use crate::infra::{FileSystem, RealFileSystem};
+//! externalization. Besides single files (`include_bytes!`, `include_str!`),
+//! whole directories embedded with rust-embed, `include_dir!` or `embed_dir!`
+//! are detected and counted with the total size of their files.
+
+use super::asset_compression::compression_opportunities;
+use super::thresholds::RecommendationThresholds;
+use crate::fmt::percent_of;
+use crate::infra::{FileSystem, RealFileSystem, WalkOptions};
+use crate::profiling::{self, ProfilePhase};
 use rayon::prelude::*;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use syn::File;
@@ -36,6 +42,7 @@ pub enum AssetDetectionError {
 /// Compiled regex patterns for asset detection (cached for performance)
 static INCLUDE_BYTES_RE: OnceLock<Regex> = OnceLock::new();
 static INCLUDE_STR_RE: OnceLock<Regex> = OnceLock::new();
+static EMBED_DIR_RE: OnceLock<Regex> = OnceLock::new();
 
 /// Main asset detector that scans Rust source files for embedded assets
 ///
@@ -65,20 +72,24 @@ static INCLUDE_STR_RE: OnceLock<Regex> = OnceLock::new();
 /// ```no_run
 /// # use wasm_slim::analyzer::AssetDetector;
 /// # use std::path::Path;
+/// # use wasm_slim::fmt::format_bytes;
 /// let detector = AssetDetector::new(Path::new("."));
 /// let results = detector.scan_project()?;
 ///
 /// let savings = &results.estimated_savings;
 /// println!("Potential savings:");
-/// println!("  Critical assets only: {} KB ({:.1}%)",
-///          savings.critical_only_kb, savings.critical_only_percent);
-/// println!("  High + critical: {} KB ({:.1}%)",
-///          savings.high_and_critical_kb, savings.high_and_critical_percent);
+/// println!("  Critical assets only: {} ({:.1}%)",
+///          format_bytes(savings.critical_only_bytes), savings.critical_only_percent);
+/// println!("  High + critical: {} ({:.1}%)",
+///          format_bytes(savings.high_and_critical_bytes), savings.high_and_critical_percent);
 /// # Ok::<(), anyhow::Error>(())
 /// ```
 pub struct AssetDetector<FS: FileSystem = RealFileSystem> {
     project_root: PathBuf,
     fs: FS,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    thresholds: RecommendationThresholds,
 }
 
 // Core detector logic
@@ -117,9 +128,28 @@ impl<FS: FileSystem + Sync> AssetDetector<FS> {
         Self {
             project_root: project_root.into(),
             fs,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            thresholds: RecommendationThresholds::default(),
         }
     }
 
+    /// Limit the scan with gitignore-style globs relative to the project root
+    ///
+    /// With `include` set, only matching source files are scanned (even if
+    /// `.gitignore` excludes them); files matching `exclude` are skipped.
+    pub fn with_globs(mut self, include: Vec<String>, exclude: Vec<String>) -> Self {
+        self.include = include;
+        self.exclude = exclude;
+        self
+    }
+
+    /// Use custom priority thresholds (`[analysis.thresholds]`)
+    pub fn with_thresholds(mut self, thresholds: RecommendationThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
     /// Scan the entire project for embedded assets
     ///
     /// # Examples
@@ -134,43 +164,69 @@ impl<FS: FileSystem + Sync> AssetDetector<FS> {
     /// ```
     #[must_use = "Scan results contain important asset information"]
     pub fn scan_project(&self) -> Result<ScanResults, AssetDetectionError> {
-        // Find all Rust source files
-        let rust_files = self.find_rust_files(&self.project_root)?;
+        let _span = profiling::span(ProfilePhase::Scanning, "asset scan");
 
-        // Parallel scan of all files
-        let all_assets: Vec<DetectedAsset> = rust_files
+        // Find all Rust source files, sorted so results don't depend on
+        // directory iteration order
+        let mut warnings = Vec::new();
+        let mut rust_files = self.find_rust_files(&mut warnings);
+        rust_files.sort();
+
+        // Parallel scan of all files (collect preserves file order); a file
+        // that cannot be scanned is reported instead of failing the scan
+        let scanned: Vec<_> = rust_files
             .par_iter()
-            .flat_map(|source_file| {
-                self.scan_file(source_file).unwrap_or_else(|e| {
-                    eprintln!("Warning: Failed to scan {}: {}", source_file.display(), e);
-                    Vec::new()
-                })
-            })
+            .map(|source_file| (source_file, self.scan_file(source_file)))
             .collect();
+        let mut all_assets = Vec::new();
+        for (source_file, scan) in scanned {
+            let display = self.display_path(source_file);
+            match scan {
+                Ok((assets, lossy)) => {
+                    if lossy {
+                        warnings.push(format!(
+                            "{} is not valid UTF-8; scanned with invalid bytes replaced",
+                            display
+                        ));
+                    }
+                    all_assets.extend(assets);
+                }
+                Err(e) => warnings.push(format!("Skipped {}: {}", display, e)),
+            }
+        }
 
         // Get bundle size (if available)
-        let bundle_size_kb = self.estimate_bundle_size()?;
+        let bundle_size_bytes = self.estimate_bundle_size()?;
 
         // Build results
-        self.build_results(all_assets, bundle_size_kb)
+        let mut results = self.build_results(all_assets, bundle_size_bytes)?;
+        results.compression = compression_opportunities(&results.assets, &self.fs);
+        results.warnings = warnings;
+        Ok(results)
     }
 
-    /// Scan a single source file for assets
-    fn scan_file(&self, source_file: &Path) -> Result<Vec<DetectedAsset>, AssetDetectionError> {
-        let content = self.fs.read_to_string(source_file)?;
+    /// Scan a single source file for assets, also returning whether it was
+    /// not valid UTF-8
+    fn scan_file(
+        &self,
+        source_file: &Path,
+    ) -> Result<(Vec<DetectedAsset>, bool), AssetDetectionError> {
+        let (content, lossy) = self.fs.read_to_string_lossy(source_file)?;
 
         let mut assets = Vec::new();
 
-        // Try AST parsing first (most reliable)
-        if let Ok(parsed_assets) = self.scan_with_ast(&content, source_file) {
-            assets.extend(parsed_assets);
+        // Try AST parsing first (most reliable); embedded directories are
+        // only looked for with regexes when the file does not parse
+        match self.scan_with_ast(&content, source_file) {
+            Ok(parsed_assets) => assets.extend(parsed_assets),
+            Err(_) => assets.extend(self.scan_directories_with_regex(&content, source_file)),
         }
 
         // Add regex fallback for patterns AST might miss
         let regex_assets = self.scan_with_regex(&content, source_file)?;
         assets.extend(regex_assets);
 
-        Ok(assets)
+        Ok((assets, lossy))
     }
 }
 
@@ -195,10 +251,33 @@ impl<FS: FileSystem + Sync> AssetDetector<FS> {
                 assets.push(asset);
             }
         }
+        for (folder, line, method) in visitor.directories() {
+            if let Some(asset) = self.create_directory_asset(folder, source_file, *line, method) {
+                assets.push(asset);
+            }
+        }
 
         Ok(assets)
     }
 
+    /// Find embedded directories with regexes, for files syn cannot parse
+    fn scan_directories_with_regex(&self, content: &str, source_file: &Path) -> Vec<DetectedAsset> {
+        let embed_dir_re = EMBED_DIR_RE.get_or_init(|| {
+            Regex::new(r#"(?:#\[\s*(folder)\s*=|\b(include_dir|embed_dir)!\s*\()\s*"([^"]+)""#)
+                .expect("embedded directory regex is valid")
+        });
+        embed_dir_re
+            .captures_iter(content)
+            .filter_map(|cap| {
+                let method = match cap.get(2) {
+                    Some(name) => format!("{}!", name.as_str()),
+                    None => "#[derive(RustEmbed)]".to_string(),
+                };
+                self.create_directory_asset(&cap[3], source_file, 0, &method)
+            })
+            .collect()
+    }
+
     /// Scan using regex patterns (fallback)
     fn scan_with_regex(
         &self,
@@ -280,25 +359,81 @@ impl<FS: FileSystem + Sync> AssetDetector<FS> {
         })
     }
 
+    /// Create a DetectedAsset for an embedded directory
+    ///
+    /// Folders are relative to the crate root (the nearest directory above
+    /// the source file with a Cargo.toml), optionally spelled with a leading
+    /// `$CARGO_MANIFEST_DIR`. The size is the total of every file below it.
+    fn create_directory_asset(
+        &self,
+        folder: &str,
+        source_file: &Path,
+        line: usize,
+        method: &str,
+    ) -> Option<DetectedAsset> {
+        let crate_root = source_file
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.project_root))
+            .find(|dir| dir.join("Cargo.toml").is_file())
+            .unwrap_or(&self.project_root);
+        let relative = folder
+            .strip_prefix("$CARGO_MANIFEST_DIR")
+            .or_else(|| folder.strip_prefix("${CARGO_MANIFEST_DIR}"))
+            .map(|rest| rest.trim_start_matches('/'))
+            .unwrap_or(folder);
+        let resolved = crate_root.join(relative);
+        if !resolved.is_dir() {
+            return None;
+        }
+
+        let (files, size_bytes) = self.directory_size(&resolved);
+        Some(DetectedAsset {
+            file_path: folder.to_string(),
+            size_bytes,
+            asset_type: AssetType::Directory,
+            source_location: format!("{}:{}", source_file.display(), line),
+            detection_method: format!("{} ({} files)", method, files),
+        })
+    }
+
+    /// Number of files below `dir` and their total size
+    fn directory_size(&self, dir: &Path) -> (usize, u64) {
+        let Ok(entries) = self.fs.read_dir(dir) else {
+            return (0, 0);
+        };
+        entries
+            .flatten()
+            .map(|entry| {
+                let path = entry.path();
+                if path.is_dir() {
+                    self.directory_size(&path)
+                } else {
+                    (1, self.fs.metadata(&path).map(|m| m.len()).unwrap_or(0))
+                }
+            })
+            .fold((0, 0), |(files, bytes), (f, b)| (files + f, bytes + b))
+    }
+
     /// Build final ScanResults from detected assets
     fn build_results(
         &self,
         assets: Vec<DetectedAsset>,
-        bundle_size_kb: u64,
+        bundle_size_bytes: u64,
     ) -> Result<ScanResults, AssetDetectionError> {
-        let total_size_kb: u64 = assets.iter().map(|a| a.size_bytes / 1024).sum();
-
-        let bundle_percentage = if bundle_size_kb > 0 {
-            (total_size_kb as f64 / bundle_size_kb as f64) * 100.0
-        } else {
-            0.0
-        };
+        // Sums and percentages use exact byte counts; units are only
+        // applied when displayed
+        let total_size_bytes: u64 = assets.iter().map(|a| a.size_bytes).sum();
+        let bundle_percentage = percent_of(total_size_bytes, bundle_size_bytes);
 
         // Group by priority
-        let mut assets_by_priority: HashMap<AssetPriority, Vec<DetectedAsset>> = HashMap::new();
+        let mut assets_by_priority: BTreeMap<AssetPriority, Vec<DetectedAsset>> = BTreeMap::new();
         for asset in &assets {
-            let asset_kb = asset.size_bytes / 1024;
-            let priority = AssetPriority::from_size(asset_kb, bundle_size_kb);
+            let priority = AssetPriority::from_bytes_with(
+                asset.size_bytes,
+                bundle_size_bytes,
+                &self.thresholds,
+            );
             assets_by_priority
                 .entry(priority)
                 .or_default()
@@ -306,80 +441,172 @@ impl<FS: FileSystem + Sync> AssetDetector<FS> {
         }
 
         // Calculate estimated savings
-        let critical_kb: u64 = assets_by_priority
-            .get(&AssetPriority::Critical)
-            .map(|a| a.iter().map(|x| x.size_bytes / 1024).sum())
-            .unwrap_or(0);
-
-        let high_kb: u64 = assets_by_priority
-            .get(&AssetPriority::High)
-            .map(|a| a.iter().map(|x| x.size_bytes / 1024).sum())
-            .unwrap_or(0);
+        let priority_bytes = |priority: AssetPriority| -> u64 {
+            assets_by_priority
+                .get(&priority)
+                .map(|a| a.iter().map(|x| x.size_bytes).sum())
+                .unwrap_or(0)
+        };
+        let critical_bytes = priority_bytes(AssetPriority::Critical);
+        let high_and_critical_bytes = critical_bytes + priority_bytes(AssetPriority::High);
 
         let estimated_savings = EstimatedSavings {
-            critical_only_kb: critical_kb,
-            high_and_critical_kb: critical_kb + high_kb,
-            all_assets_kb: total_size_kb,
-            critical_only_percent: if bundle_size_kb > 0 {
-                (critical_kb as f64 / bundle_size_kb as f64) * 100.0
-            } else {
-                0.0
-            },
-            high_and_critical_percent: if bundle_size_kb > 0 {
-                ((critical_kb + high_kb) as f64 / bundle_size_kb as f64) * 100.0
-            } else {
-                0.0
-            },
+            critical_only_bytes: critical_bytes,
+            high_and_critical_bytes,
+            all_assets_bytes: total_size_bytes,
+            critical_only_percent: percent_of(critical_bytes, bundle_size_bytes),
+            high_and_critical_percent: percent_of(high_and_critical_bytes, bundle_size_bytes),
             all_assets_percent: bundle_percentage,
         };
 
         Ok(ScanResults {
             total_assets: assets.len(),
-            total_size_kb,
-            bundle_size_kb,
+            total_size_bytes,
+            bundle_size_bytes,
             bundle_percentage,
             assets,
             assets_by_priority,
             estimated_savings,
+            compression: Vec::new(),
+            warnings: Vec::new(),
+            thresholds: self.thresholds,
         })
     }
 }
 
 // Utility functions
 impl<FS: FileSystem + Sync> AssetDetector<FS> {
-    /// Find all Rust source files in the project
-    fn find_rust_files(&self, dir: &Path) -> Result<Vec<PathBuf>, AssetDetectionError> {
-        let mut rust_files = Vec::new();
+    /// Find the Rust source files to scan
+    ///
+    /// Walks the project honoring `.gitignore` and the configured globs. In a
+    /// virtual workspace only the member directories are walked, and
+    /// directories listed in `[workspace] exclude` are skipped.
+    fn find_rust_files(&self, warnings: &mut Vec<String>) -> Vec<PathBuf> {
+        if self.project_root.is_file() {
+            let is_rust = self.project_root.extension().and_then(|e| e.to_str()) == Some("rs");
+            return if is_rust {
+                vec![self.project_root.clone()]
+            } else {
+                Vec::new()
+            };
+        }
 
-        if dir.is_file() {
-            if dir.extension().and_then(|e| e.to_str()) == Some("rs") {
-                rust_files.push(dir.to_path_buf());
+        let (roots, workspace_exclude) = self.workspace_roots();
+        let options = WalkOptions {
+            roots,
+            extension: Some("rs".to_string()),
+            include: self.include.clone(),
+            exclude: workspace_exclude
+                .into_iter()
+                .chain(self.exclude.iter().cloned())
+                .collect(),
+        };
+        let mut rust_files = Vec::new();
+        for entry in self.fs.walk_files(&self.project_root, &options) {
+            match entry {
+                Ok(path) => rust_files.push(path),
+                Err(e) => warnings.push(format!("Skipped part of the source tree: {}", e)),
             }
-            return Ok(rust_files);
         }
+        rust_files.dedup();
+        rust_files
+    }
 
-        for entry in self.fs.read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
+    /// Directories to walk and globs to skip, from the root Cargo.toml
+    ///
+    /// A project without `[workspace]` (or with a root package) is walked
+    /// from the root; a virtual workspace from each member directory.
+    fn workspace_roots(&self) -> (Vec<PathBuf>, Vec<String>) {
+        let manifest = self
+            .fs
+            .read_to_string(&self.project_root.join("Cargo.toml"))
+            .ok()
+            .and_then(|content| content.parse::<toml_edit::DocumentMut>().ok());
+        let Some(workspace) = manifest
+            .as_ref()
+            .and_then(|doc| doc.get("workspace"))
+            .and_then(|w| w.as_table_like())
+        else {
+            return (Vec::new(), Vec::new());
+        };
+        let strings = |key: &str| -> Vec<String> {
+            workspace
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|v| v.as_str())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let excluded_dirs: Vec<PathBuf> = strings("exclude")
+            .iter()
+            .map(|dir| self.project_root.join(dir.trim_matches('/')))
+            .collect();
+        let exclude: Vec<String> = strings("exclude")
+            .iter()
+            .map(|dir| format!("/{}/", dir.trim_matches('/')))
+            .collect();
 
-            // Skip target directory and hidden directories
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name == "target" || name.starts_with('.') {
-                    continue;
-                }
-            }
+        let has_package = manifest
+            .as_ref()
+            .is_some_and(|doc| doc.contains_key("package"));
+        if has_package {
+            return (Vec::new(), exclude);
+        }
+        let mut roots: Vec<PathBuf> = strings("members")
+            .iter()
+            .flat_map(|member| self.expand_member(member))
+            .filter(|dir| {
+                !excluded_dirs
+                    .iter()
+                    .any(|excluded| dir.starts_with(excluded))
+            })
+            .filter(|dir| self.fs.metadata(&dir.join("Cargo.toml")).is_ok())
+            .collect();
+        roots.sort();
+        roots.dedup();
+        if roots.is_empty() {
+            // Members could not be resolved; fall back to the whole tree
+            return (Vec::new(), exclude);
+        }
+        (roots, exclude)
+    }
 
-            if path.is_dir() {
-                rust_files.extend(self.find_rust_files(&path)?);
-            } else if path.extension().and_then(|e| e.to_str()) == Some("rs") {
-                rust_files.push(path);
+    /// Directories matching a `members` entry such as `crates/*`
+    fn expand_member(&self, member: &str) -> Vec<PathBuf> {
+        let mut dirs = vec![self.project_root.clone()];
+        for component in member.split('/').filter(|c| !c.is_empty() && *c != ".") {
+            if !component.contains(['*', '?', '[']) {
+                dirs = dirs.into_iter().map(|d| d.join(component)).collect();
+                continue;
             }
+            let Ok(glob) = globset::Glob::new(component) else {
+                return Vec::new();
+            };
+            let matcher = glob.compile_matcher();
+            dirs = dirs
+                .iter()
+                .filter_map(|dir| self.fs.read_dir(dir).ok())
+                .flat_map(|entries| entries.flatten())
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .filter(|entry| matcher.is_match(entry.file_name()))
+                .map(|entry| entry.path())
+                .collect();
         }
+        dirs
+    }
 
-        Ok(rust_files)
+    /// Path relative to the project root, for warnings
+    fn display_path<'a>(&self, path: &'a Path) -> std::path::Display<'a> {
+        path.strip_prefix(&self.project_root)
+            .unwrap_or(path)
+            .display()
     }
 
-    /// Estimate WASM bundle size (if available)
+    /// Estimate WASM bundle size in bytes (if available)
     fn estimate_bundle_size(&self) -> Result<u64, AssetDetectionError> {
         // Look for compiled WASM files in target directory
         let target_dir = self
@@ -403,7 +630,7 @@ impl<FS: FileSystem + Sync> AssetDetector<FS> {
             }
         }
 
-        Ok(total_size / 1024) // Convert to KB
+        Ok(total_size)
     }
 }
 
@@ -645,6 +872,30 @@ mod tests {
         assert_eq!(AssetPriority::from_size(101, 5000), AssetPriority::Medium); // >2%
     }
 
+    #[test]
+    fn test_from_bytes_with_configured_thresholds() {
+        let thresholds = RecommendationThresholds {
+            asset_critical_bytes: 64 * 1024,
+            asset_high_bytes: 32 * 1024,
+            asset_medium_bytes: 8 * 1024,
+            ..Default::default()
+        };
+        let priority = |kb: u64| AssetPriority::from_bytes_with(kb * 1024, 0, &thresholds);
+
+        assert_eq!(priority(65), AssetPriority::Critical);
+        assert_eq!(priority(40), AssetPriority::High);
+        assert_eq!(priority(9), AssetPriority::Medium);
+        assert_eq!(priority(8), AssetPriority::Low);
+        assert_eq!(
+            AssetPriority::High.threshold_label(&thresholds),
+            "5-10% or 32-64 KiB"
+        );
+        assert_eq!(
+            AssetPriority::Low.threshold_label(&RecommendationThresholds::default()),
+            "<2% or <100 KiB"
+        );
+    }
+
     #[test]
     fn test_from_size_zero_bundle_size_returns_low_priority() {
         // When bundle size is zero, priority is based solely on asset size
@@ -661,6 +912,132 @@ mod tests {
         assert_eq!(AssetPriority::from_size(0, 0), AssetPriority::Low);
     }
 
+    #[test]
+    fn test_build_results_sums_sub_kib_assets_exactly() {
+        let asset = |path: &str, size_bytes: u64| DetectedAsset {
+            file_path: path.to_string(),
+            size_bytes,
+            asset_type: AssetType::Image,
+            source_location: "src/lib.rs:1".to_string(),
+            detection_method: "include_bytes!".to_string(),
+        };
+        let assets: Vec<DetectedAsset> = (0..40)
+            .map(|i| asset(&format!("icon{}.svg", i), 900))
+            .chain(std::iter::once(asset("font.woff2", 100 * 1024 + 1)))
+            .collect();
+
+        let results = AssetDetector::new(".")
+            .build_results(assets, 400 * 1024)
+            .unwrap();
+
+        // 40 icons below 1 KiB each used to count as 0
+        assert_eq!(results.total_size_bytes, 40 * 900 + 100 * 1024 + 1);
+        assert!((results.bundle_percentage - 33.79).abs() < 0.01);
+        // Just over 100 KiB and 25% of the bundle
+        assert!(results
+            .assets_by_priority
+            .get(&AssetPriority::Critical)
+            .is_some_and(|a| a[0].file_path == "font.woff2"));
+        assert_eq!(
+            results.estimated_savings.critical_only_bytes,
+            100 * 1024 + 1
+        );
+    }
+
+    #[test]
+    fn test_scan_project_reads_non_utf8_files_lossily() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("font.ttf"), vec![0u8; 2048]).unwrap();
+        std::fs::write(
+            src.join("lib.rs"),
+            b"// \xff\xfe\nstatic FONT: &[u8] = include_bytes!(\"font.ttf\");\n",
+        )
+        .unwrap();
+
+        let results = AssetDetector::new(temp.path()).scan_project().unwrap();
+
+        assert!(results.assets.iter().any(|a| a.file_path == "font.ttf"));
+        assert_eq!(
+            results.warnings,
+            vec!["src/lib.rs is not valid UTF-8; scanned with invalid bytes replaced"]
+        );
+    }
+
+    #[test]
+    fn test_scan_project_sums_embedded_directories() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        std::fs::create_dir_all(temp.path().join("static/css")).unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n",
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("static/index.html"), vec![b'x'; 1000]).unwrap();
+        std::fs::write(temp.path().join("static/css/site.css"), vec![b'x'; 500]).unwrap();
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(
+            src.join("lib.rs"),
+            "#[derive(rust_embed::RustEmbed)]\n#[folder = \"$CARGO_MANIFEST_DIR/static/\"]\nstruct Static;\n\nstatic SAME: Dir = include_dir!(\"static\");\n",
+        )
+        .unwrap();
+
+        let results = AssetDetector::new(temp.path()).scan_project().unwrap();
+
+        assert_eq!(results.total_assets, 2);
+        assert_eq!(results.total_size_bytes, 3000);
+        let rust_embed = &results.assets[0];
+        assert_eq!(rust_embed.asset_type, AssetType::Directory);
+        assert_eq!(rust_embed.size_bytes, 1500);
+        assert_eq!(
+            rust_embed.detection_method,
+            "#[derive(RustEmbed)] (2 files)"
+        );
+        assert_eq!(results.assets[1].detection_method, "include_dir! (2 files)");
+    }
+
+    #[test]
+    fn test_scan_project_walks_workspace_members_only() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n",
+        )
+        .unwrap();
+        let embed = "static DATA: &[u8] = include_bytes!(\"data.bin\");\n";
+        for dir in ["crates/app", "crates/old", "crates/app/gen", "tools"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("data.bin"), vec![0u8; 1024]).unwrap();
+            std::fs::write(root.join(dir).join("lib.rs"), embed).unwrap();
+        }
+        for member in ["crates/app", "crates/old"] {
+            std::fs::write(
+                root.join(member).join("Cargo.toml"),
+                "[package]\nname = \"x\"\n",
+            )
+            .unwrap();
+        }
+        std::fs::write(root.join(".gitignore"), "gen/\n").unwrap();
+
+        let results = AssetDetector::new(root).scan_project().unwrap();
+        let sources: Vec<&str> = results
+            .assets
+            .iter()
+            .map(|a| a.source_location.as_str())
+            .collect();
+        assert!(!sources.is_empty());
+        assert!(sources.iter().all(|s| s.contains("crates/app/lib.rs")));
+
+        let results = AssetDetector::new(root)
+            .with_globs(Vec::new(), vec!["crates/app/lib.rs".to_string()])
+            .scan_project()
+            .unwrap();
+        assert_eq!(results.total_assets, 0);
+    }
+
     #[test]
     fn test_emoji_all_priorities_return_valid_emojis() {
         // Test emoji representations
@@ -0,0 +1,364 @@
+//! Batch analysis of many WASM artifacts
+//!
+//! Reads a list of artifacts (one path per line) and analyzes them on a
+//! bounded worker pool, producing one combined report. Each artifact gets its
+//! file size, gzip size and twiggy's top contributors with recommendations.
+//! An artifact that cannot be analyzed is reported with its error instead of
+//! failing the whole batch.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::batch::{parse_artifact_list, BatchAnalyzer};
+//! use std::path::Path;
+//!
+//! let list = std::fs::read_to_string("artifacts.txt")?;
+//! let artifacts = parse_artifact_list(&list, Path::new("."));
+//! let report = BatchAnalyzer::new(4).analyze(&artifacts);
+//! std::fs::write("batch.html", report.to_html())?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use super::report_utils::{escape_html, format_bytes};
+use super::thresholds::RecommendationThresholds;
+use super::twiggy::{AnalysisItem, AnalysisMode, Recommendation, TwiggyAnalyzer};
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use crate::summary;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of top contributors kept per artifact
+pub const TOP_ITEMS_PER_ARTIFACT: usize = 10;
+
+/// Parse an artifact list: one path per line, `#` starts a comment
+///
+/// Relative paths are resolved against `base_dir` (the list's directory).
+pub fn parse_artifact_list(contents: &str, base_dir: &Path) -> Vec<PathBuf> {
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| base_dir.join(line))
+        .collect()
+}
+
+/// Fixed-size pool of worker threads draining a queue of jobs
+#[derive(Debug, Clone, Copy)]
+pub struct JobQueue {
+    workers: usize,
+}
+
+impl JobQueue {
+    /// Create a queue served by `workers` threads (at least one)
+    pub fn new(workers: usize) -> Self {
+        Self {
+            workers: workers.max(1),
+        }
+    }
+
+    /// Number of worker threads
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Run `job` on every item; results keep the order of `items`
+    pub fn run<T, R, F>(&self, items: &[T], job: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, R)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.workers.min(items.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(item) = items.get(index) else {
+                                break;
+                            };
+                            done.push((index, job(item)));
+                        }
+                        done
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("batch worker panicked"))
+                .collect()
+        });
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+/// Analysis of one artifact in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactReport {
+    /// Path as listed
+    pub path: String,
+    /// File size in bytes
+    pub size_bytes: u64,
+    /// Size after gzip at level 9
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gzip_bytes: Option<u64>,
+    /// Largest items by shallow size
+    pub top_items: Vec<AnalysisItem>,
+    /// Recommendations for this artifact
+    pub recommendations: Vec<Recommendation>,
+    /// Why the artifact could not be (fully) analyzed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Combined report for a batch of artifacts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchReport {
+    /// Per-artifact results, in list order
+    pub artifacts: Vec<ArtifactReport>,
+    /// Sum of all artifact sizes in bytes
+    pub total_size_bytes: u64,
+    /// Number of artifacts that could not be analyzed
+    pub failed: usize,
+    /// Worker threads used
+    pub workers: usize,
+}
+
+impl BatchReport {
+    /// Render as a standalone HTML page
+    pub fn to_html(&self) -> String {
+        let mut rows = String::new();
+        for artifact in &self.artifacts {
+            let gzip = artifact
+                .gzip_bytes
+                .map(format_bytes)
+                .unwrap_or_else(|| "-".to_string());
+            let largest = artifact
+                .top_items
+                .first()
+                .map(|item| format!("{} ({})", item.name, format_bytes(item.size_bytes)))
+                .unwrap_or_else(|| "-".to_string());
+            let notes = match &artifact.error {
+                Some(error) => format!("<span class=\"error\">{}</span>", escape_html(error)),
+                None => artifact
+                    .recommendations
+                    .iter()
+                    .map(|rec| {
+                        format!(
+                            "{} {}",
+                            escape_html(&rec.priority),
+                            escape_html(&rec.description)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("<br>"),
+            };
+            rows.push_str(&format!(
+                "    <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&artifact.path),
+                format_bytes(artifact.size_bytes),
+                gzip,
+                escape_html(&largest),
+                notes
+            ));
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n  <meta charset=\"utf-8\">\n  <title>wasm-slim batch report</title>\n  <style>body{{font-family:sans-serif}}th,td{{text-align:left;padding:0.2em 1em 0.2em 0;vertical-align:top}}.error{{color:#b00}}</style>\n</head>\n<body>\n  <h1>wasm-slim batch report</h1>\n  <p>{} artifacts, {} total, {} failed</p>\n  <table>\n    <tr><th>Artifact</th><th>Size</th><th>Gzip</th><th>Largest item</th><th>Recommendations</th></tr>\n{}  </table>\n</body>\n</html>\n",
+            self.artifacts.len(),
+            format_bytes(self.total_size_bytes),
+            self.failed,
+            rows
+        )
+    }
+}
+
+/// Analyzes a list of artifacts on a bounded worker pool
+pub struct BatchAnalyzer<CE: CommandExecutor = RealCommandExecutor> {
+    queue: JobQueue,
+    thresholds: RecommendationThresholds,
+    cmd_executor: CE,
+}
+
+impl BatchAnalyzer {
+    /// Create an analyzer using `workers` threads
+    pub fn new(workers: usize) -> Self {
+        Self::with_executor(workers, RealCommandExecutor)
+    }
+}
+
+impl<CE: CommandExecutor + Clone + Sync> BatchAnalyzer<CE> {
+    /// Create an analyzer with a custom command executor
+    pub fn with_executor(workers: usize, cmd_executor: CE) -> Self {
+        Self {
+            queue: JobQueue::new(workers),
+            thresholds: RecommendationThresholds::default(),
+            cmd_executor,
+        }
+    }
+
+    /// Use custom recommendation thresholds (scaled to each artifact)
+    pub fn with_thresholds(mut self, thresholds: RecommendationThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Analyze all artifacts
+    pub fn analyze(&self, artifacts: &[PathBuf]) -> BatchReport {
+        let reports = self.queue.run(artifacts, |path| self.analyze_one(path));
+
+        BatchReport {
+            total_size_bytes: reports.iter().map(|r| r.size_bytes).sum(),
+            failed: reports.iter().filter(|r| r.error.is_some()).count(),
+            workers: self.queue.workers(),
+            artifacts: reports,
+        }
+    }
+
+    fn analyze_one(&self, path: &Path) -> ArtifactReport {
+        let mut report = ArtifactReport {
+            path: path.display().to_string(),
+            size_bytes: 0,
+            gzip_bytes: None,
+            top_items: Vec::new(),
+            recommendations: Vec::new(),
+            error: None,
+        };
+
+        match RealFileSystem.metadata(path) {
+            Ok(metadata) => report.size_bytes = metadata.len(),
+            Err(e) => {
+                report.error = Some(e.to_string());
+                return report;
+            }
+        }
+        report.gzip_bytes = summary::gzip_size(path);
+
+        let analyzer =
+            TwiggyAnalyzer::with_executors(path, RealFileSystem, self.cmd_executor.clone())
+                .with_thresholds(self.thresholds);
+        match analyzer.analyze(AnalysisMode::Top) {
+            Ok(results) => {
+                report.top_items = results
+                    .items
+                    .into_iter()
+                    .take(TOP_ITEMS_PER_ARTIFACT)
+                    .collect();
+                report.recommendations = results.recommendations;
+            }
+            Err(e) => report.error = Some(e.to_string()),
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::{Command, ExitStatus, Output};
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    /// Executor answering `twiggy top`
+    #[derive(Clone)]
+    struct FakeTools;
+
+    impl CommandExecutor for FakeTools {
+        fn status(&self, _cmd: &mut Command) -> std::io::Result<ExitStatus> {
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, cmd: &mut Command) -> std::io::Result<Output> {
+            let stdout = match cmd.get_program().to_str() {
+                Some("twiggy") => b" Shallow Bytes | Shallow % | Item\n---\n 600 | 60.00% | code[0]\n 300 | 30.00% | data[0]\n"
+                    .to_vec(),
+                _ => Vec::new(),
+            };
+            Ok(Output {
+                status: crate::infra::mock_exit_status(0),
+                stdout,
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_artifact_list_skips_comments_and_blank_lines() {
+        let list = "# nightly audit\napps/shell.wasm\n\n  /abs/cart.wasm  # checkout\n";
+        assert_eq!(
+            parse_artifact_list(list, Path::new("/ci")),
+            vec![
+                PathBuf::from("/ci/apps/shell.wasm"),
+                PathBuf::from("/abs/cart.wasm")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_job_queue_keeps_order_and_bounds_concurrency() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items: Vec<u32> = (0..12).collect();
+
+        let results = JobQueue::new(3).run(&items, |n| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+            n * 2
+        });
+
+        assert_eq!(results, (0..12).map(|n| n * 2).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert_eq!(JobQueue::new(0).workers(), 1);
+    }
+
+    #[test]
+    fn test_analyze_reports_each_artifact_and_keeps_going_on_errors() {
+        let temp = TempDir::new().unwrap();
+        let shell = temp.path().join("shell.wasm");
+        std::fs::write(&shell, vec![0u8; 1000]).unwrap();
+        let missing = temp.path().join("missing.wasm");
+
+        let report = BatchAnalyzer::with_executor(2, FakeTools).analyze(&[shell, missing]);
+
+        assert_eq!(report.artifacts.len(), 2);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.total_size_bytes, 1000);
+
+        let first = &report.artifacts[0];
+        assert!(first.gzip_bytes.is_some_and(|gzip| gzip < 1000));
+        assert_eq!(first.top_items[0].name, "code[0]");
+        assert!(first.error.is_none());
+        assert!(report.artifacts[1].error.is_some());
+    }
+
+    #[test]
+    fn test_to_html_escapes_and_lists_errors() {
+        let report = BatchReport {
+            artifacts: vec![ArtifactReport {
+                path: "<app>.wasm".to_string(),
+                size_bytes: 2048,
+                gzip_bytes: None,
+                top_items: Vec::new(),
+                recommendations: Vec::new(),
+                error: Some("twiggy not installed".to_string()),
+            }],
+            total_size_bytes: 2048,
+            failed: 1,
+            workers: 1,
+        };
+
+        let html = report.to_html();
+        assert!(html.contains("&lt;app&gt;.wasm"));
+        assert!(html.contains("<span class=\"error\">twiggy not installed</span>"));
+        assert!(html.contains("1 artifacts, 2.00 KiB total, 1 failed"));
+    }
+}
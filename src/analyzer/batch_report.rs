@@ -0,0 +1,123 @@
+//! Batch analysis report formatting
+
+use super::batch::BatchReport;
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of artifacts listed by default
+const DEFAULT_TOP_ARTIFACTS: usize = 50;
+
+/// Format the batch report for console output
+pub fn format_console_report(report: &BatchReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the batch report, limiting the artifacts listed
+pub fn format_console_report_with_limit(
+    report: &BatchReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{} Batch Analysis", style("📦").bold())?;
+    writeln!(
+        output,
+        "   {} artifacts, {} total ({} workers)\n",
+        report.artifacts.len(),
+        style(format_bytes(report.total_size_bytes)).cyan(),
+        report.workers
+    )?;
+
+    writeln!(
+        output,
+        "   {:<40} {:>10} {:>10}  Largest item",
+        "Artifact", "Size", "Gzip"
+    )?;
+    writeln!(output, "   {}", "─".repeat(90))?;
+
+    let shown = limit.visible(report.artifacts.len(), Some(DEFAULT_TOP_ARTIFACTS));
+    for artifact in report.artifacts.iter().take(shown) {
+        let detail = match (&artifact.error, artifact.top_items.first()) {
+            (Some(error), _) => style(truncate_str(error, 40)).red(),
+            (None, Some(item)) => style(format!(
+                "{} ({})",
+                truncate_str(&item.name, 28),
+                format_bytes(item.size_bytes)
+            ))
+            .dim(),
+            (None, None) => style("-".to_string()).dim(),
+        };
+        writeln!(
+            output,
+            "   {:<40} {:>10} {:>10}  {}",
+            truncate_str(&artifact.path, 40),
+            format_bytes(artifact.size_bytes),
+            artifact
+                .gzip_bytes
+                .map(format_bytes)
+                .unwrap_or_else(|| "-".to_string()),
+            detail
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.artifacts.len(), shown, "artifacts") {
+        writeln!(output, "   {}", style(footer).dim())?;
+    }
+
+    if report.failed > 0 {
+        writeln!(
+            output,
+            "\n{} {} artifact(s) could not be analyzed",
+            style("⚠️").yellow(),
+            report.failed
+        )?;
+    }
+
+    writeln!(
+        output,
+        "\n{}",
+        style("💡 Tip: Use --format json or --format html for the full per-artifact report").dim()
+    )?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::batch::ArtifactReport;
+
+    fn artifact(path: &str, error: Option<&str>) -> ArtifactReport {
+        ArtifactReport {
+            path: path.to_string(),
+            size_bytes: 2048,
+            gzip_bytes: Some(1024),
+            top_items: Vec::new(),
+            recommendations: Vec::new(),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_format_console_report_lists_artifacts_and_failures() {
+        let report = BatchReport {
+            artifacts: vec![
+                artifact("shell.wasm", None),
+                artifact("cart.wasm", Some("WASM file not found")),
+                artifact("search.wasm", None),
+            ],
+            total_size_bytes: 6144,
+            failed: 1,
+            workers: 2,
+        };
+
+        let text = format_console_report_with_limit(&report, ItemLimit::Max(2)).unwrap();
+
+        assert!(text.contains("3 artifacts"));
+        assert!(text.contains("shell.wasm"));
+        assert!(text.contains("WASM file not found"));
+        assert!(!text.contains("search.wasm"));
+        assert!(text.contains("1 more artifacts omitted"));
+        assert!(text.contains("1 artifact(s) could not be analyzed"));
+    }
+}
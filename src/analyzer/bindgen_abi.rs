@@ -0,0 +1,771 @@
+//! wasm-bindgen ABI cost analysis
+//!
+//! Every value that crosses the JS/WASM boundary goes through glue generated
+//! by wasm-bindgen. Some signatures are much more expensive than they look:
+//! strings are re-encoded and copied in each direction, `Vec<T>` is copied
+//! into or out of linear memory, `&mut [T]` is copied in and back out, and
+//! structs routed through `JsValue` with serde pay for a full serialization.
+//!
+//! This module inspects the `#[wasm_bindgen]` export signatures in the Rust
+//! sources and the generated JS glue (`pkg/*.js`), flags the costly patterns
+//! and estimates what the cheaper alternative saves in size and copies.
+
+use super::report_utils::SizedEntry;
+use crate::infra::{FileSystem, RealFileSystem};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use syn::visit::Visit;
+use thiserror::Error;
+
+/// Directory wasm-pack writes the generated glue to
+const GLUE_DIR: &str = "pkg";
+
+/// Structs with at least this many fields are called out as large
+const LARGE_STRUCT_FIELDS: usize = 6;
+
+/// Errors that can occur during ABI analysis
+#[derive(Error, Debug)]
+pub enum BindgenAbiError {
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Regex compilation error
+    #[error("Regex error: {0}")]
+    Regex(#[from] regex::Error),
+}
+
+/// Costly pattern at the JS/WASM boundary
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AbiPattern {
+    /// Exported struct passed by value, consuming the JS handle
+    StructByValue,
+    /// Value serialized through `JsValue` with serde
+    SerdeJsValue,
+    /// String passed in and a String returned by the same export
+    StringRoundTrip,
+    /// `Vec<T>` taken by value
+    VecParam,
+    /// `Vec<T>` returned to JS
+    VecReturn,
+    /// `&mut [T]` copied in and back out
+    MutSliceParam,
+}
+
+impl AbiPattern {
+    /// Get human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            AbiPattern::StructByValue => "struct by value",
+            AbiPattern::SerdeJsValue => "serde through JsValue",
+            AbiPattern::StringRoundTrip => "String round-trip",
+            AbiPattern::VecParam => "Vec<T> parameter",
+            AbiPattern::VecReturn => "Vec<T> return",
+            AbiPattern::MutSliceParam => "&mut [T] parameter",
+        }
+    }
+
+    /// Get recommended alternative
+    pub fn alternative(&self) -> &'static str {
+        match self {
+            AbiPattern::StructByValue => "take &T so the JS object stays valid",
+            AbiPattern::SerdeJsValue => {
+                "export the struct with #[wasm_bindgen] or pass typed arrays"
+            }
+            AbiPattern::StringRoundTrip => {
+                "keep the string on one side (return a handle or use js_sys::JsString)"
+            }
+            AbiPattern::VecParam => "take &[T] and let the glue free the buffer",
+            AbiPattern::VecReturn => {
+                "fill a caller-provided &mut [T] or expose a view of linear memory"
+            }
+            AbiPattern::MutSliceParam => "keep the buffer in WASM memory between calls",
+        }
+    }
+
+    /// Estimated glue and helper code saved per occurrence (bytes)
+    pub fn size_per_occurrence(&self) -> u64 {
+        match self {
+            AbiPattern::StructByValue => 150,
+            AbiPattern::SerdeJsValue => 4000, // Serializer code per type
+            AbiPattern::StringRoundTrip => 400,
+            AbiPattern::VecParam => 150,
+            AbiPattern::VecReturn => 250,
+            AbiPattern::MutSliceParam => 200,
+        }
+    }
+
+    /// Buffer copies the alternative avoids per call
+    pub fn copies_saved_per_call(&self) -> usize {
+        match self {
+            AbiPattern::StructByValue | AbiPattern::VecParam => 0,
+            AbiPattern::SerdeJsValue
+            | AbiPattern::StringRoundTrip
+            | AbiPattern::VecReturn
+            | AbiPattern::MutSliceParam => 1,
+        }
+    }
+}
+
+/// A costly pattern found in an export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbiIssue {
+    /// Pattern detected
+    pub pattern: AbiPattern,
+    /// Exported function or method
+    pub function: String,
+    /// Source file
+    pub file: PathBuf,
+    /// Line number (0 if unknown)
+    pub line: usize,
+    /// What was detected (type name, parameter)
+    pub detail: String,
+}
+
+impl SizedEntry for AbiIssue {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        &self.function
+    }
+}
+
+/// Copy helpers used by one generated glue file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlueStats {
+    /// Glue file
+    pub file: PathBuf,
+    /// Glue size in bytes
+    pub size_bytes: u64,
+    /// Call sites copying data into WASM memory, by helper
+    pub copies_in: BTreeMap<String, usize>,
+    /// Call sites copying data out of WASM memory, by helper
+    pub copies_out: BTreeMap<String, usize>,
+}
+
+impl SizedEntry for GlueStats {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        self.file.to_str().unwrap_or_default()
+    }
+}
+
+impl GlueStats {
+    /// Total copy call sites in both directions
+    pub fn total_copies(&self) -> usize {
+        self.copies_in.values().sum::<usize>() + self.copies_out.values().sum::<usize>()
+    }
+}
+
+/// Complete ABI analysis results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AbiResults {
+    /// Number of exported functions and methods inspected
+    pub exports_scanned: usize,
+    /// Issues by pattern
+    pub by_pattern: Vec<(AbiPattern, usize)>,
+    /// All issues
+    pub issues: Vec<AbiIssue>,
+    /// Generated glue statistics (empty if no glue was found)
+    pub glue: Vec<GlueStats>,
+    /// Estimated size saved by applying every alternative (bytes)
+    pub estimated_savings_bytes: u64,
+    /// Buffer copies saved per call if every export were called once
+    pub copies_saved_per_call: usize,
+    /// Recommendations
+    pub recommendations: Vec<String>,
+}
+
+/// Shape of a type at the boundary
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AbiType {
+    String,
+    Str,
+    Vec(String),
+    MutSlice(String),
+    Named(String),
+    Other,
+}
+
+/// An exported function or method signature
+#[derive(Debug, Clone)]
+struct ExportFn {
+    name: String,
+    params: Vec<(String, AbiType)>,
+    ret: AbiType,
+    uses_serde: bool,
+}
+
+/// wasm-bindgen ABI analyzer
+pub struct BindgenAbiAnalyzer<FS: FileSystem = RealFileSystem> {
+    project_root: PathBuf,
+    fs: FS,
+}
+
+impl BindgenAbiAnalyzer<RealFileSystem> {
+    /// Create a new ABI analyzer with the real filesystem
+    pub fn new(project_root: impl Into<PathBuf>) -> Self {
+        Self::with_fs(project_root, RealFileSystem)
+    }
+}
+
+impl<FS: FileSystem> BindgenAbiAnalyzer<FS> {
+    /// Create a new ABI analyzer with a custom filesystem implementation
+    pub fn with_fs(project_root: impl Into<PathBuf>, fs: FS) -> Self {
+        Self {
+            project_root: project_root.into(),
+            fs,
+        }
+    }
+
+    /// Scan export signatures and generated glue
+    pub fn analyze(&self) -> Result<AbiResults, BindgenAbiError> {
+        let mut files = Vec::new();
+        let src = self.project_root.join("src");
+        if src.exists() {
+            self.collect_files(&src, "rs", &mut files)?;
+        }
+        files.sort();
+
+        // Exported struct names must be known before any signature is classified
+        let mut structs = BTreeMap::new();
+        let mut exports = Vec::new();
+        for file in &files {
+            let content = self.fs.read_to_string(file)?;
+            let Ok(syntax) = syn::parse_file(&content) else {
+                eprintln!("Warning: Failed to parse {}", file.display());
+                continue;
+            };
+            let mut visitor = ExportVisitor::default();
+            visitor.visit_file(&syntax);
+            structs.extend(visitor.structs);
+            for export in visitor.exports {
+                let line = find_fn_line(&content, &export.name);
+                exports.push((file, line, export));
+            }
+        }
+
+        let mut issues = Vec::new();
+        for (file, line, export) in &exports {
+            for (pattern, detail) in classify(export, &structs) {
+                issues.push(AbiIssue {
+                    pattern,
+                    function: export.name.clone(),
+                    file: file.to_path_buf(),
+                    line: *line,
+                    detail,
+                });
+            }
+        }
+
+        let glue = self.scan_glue()?;
+        Ok(build_results(exports.len(), issues, glue))
+    }
+
+    /// Scan the generated JS glue for copy helpers
+    fn scan_glue(&self) -> Result<Vec<GlueStats>, BindgenAbiError> {
+        let dir = self.project_root.join(GLUE_DIR);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut files = Vec::new();
+        self.collect_files(&dir, "js", &mut files)?;
+        files.sort();
+
+        let mut stats = Vec::new();
+        for file in files {
+            let content = self.fs.read_to_string(&file)?;
+            let glue = scan_glue_source(&file, &content)?;
+            if glue.total_copies() > 0 {
+                stats.push(glue);
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Recursively collect files with the given extension
+    fn collect_files(
+        &self,
+        dir: &Path,
+        extension: &str,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<(), BindgenAbiError> {
+        for entry in self.fs.read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.collect_files(&path, extension, files)?;
+            } else if path.extension().and_then(|s| s.to_str()) == Some(extension) {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Count copy helper call sites in one glue file
+///
+/// Helper definitions (`function passStringToWasm0(`) are not call sites.
+pub(crate) fn scan_glue_source(file: &Path, content: &str) -> Result<GlueStats, BindgenAbiError> {
+    let helper_re = Regex::new(
+        r"(function\s+)?\b(passStringToWasm0|passArray\w*ToWasm0|getStringFromWasm0|getArray\w*FromWasm0)\s*\(",
+    )?;
+
+    let mut copies_in = BTreeMap::new();
+    let mut copies_out = BTreeMap::new();
+    for caps in helper_re.captures_iter(content) {
+        if caps.get(1).is_some() {
+            continue;
+        }
+        let helper = caps[2].to_string();
+        let target = if helper.starts_with("pass") {
+            &mut copies_in
+        } else {
+            &mut copies_out
+        };
+        *target.entry(helper).or_insert(0) += 1;
+    }
+
+    Ok(GlueStats {
+        file: file.to_path_buf(),
+        size_bytes: content.len() as u64,
+        copies_in,
+        copies_out,
+    })
+}
+
+/// Detect the costly patterns in one export
+fn classify(export: &ExportFn, structs: &BTreeMap<String, usize>) -> Vec<(AbiPattern, String)> {
+    let mut found = Vec::new();
+
+    let takes_string = export
+        .params
+        .iter()
+        .any(|(_, ty)| matches!(ty, AbiType::String | AbiType::Str));
+    if takes_string && export.ret == AbiType::String {
+        found.push((
+            AbiPattern::StringRoundTrip,
+            "string in, String out".to_string(),
+        ));
+    }
+
+    for (param, ty) in &export.params {
+        match ty {
+            AbiType::Vec(inner) => {
+                found.push((AbiPattern::VecParam, format!("{}: Vec<{}>", param, inner)))
+            }
+            AbiType::MutSlice(inner) => found.push((
+                AbiPattern::MutSliceParam,
+                format!("{}: &mut [{}]", param, inner),
+            )),
+            AbiType::Named(name) => {
+                if let Some(&fields) = structs.get(name) {
+                    let size = if fields >= LARGE_STRUCT_FIELDS {
+                        format!(" ({} fields)", fields)
+                    } else {
+                        String::new()
+                    };
+                    found.push((
+                        AbiPattern::StructByValue,
+                        format!("{}: {}{}", param, name, size),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let AbiType::Vec(inner) = &export.ret {
+        found.push((AbiPattern::VecReturn, format!("-> Vec<{}>", inner)));
+    }
+
+    if export.uses_serde {
+        found.push((
+            AbiPattern::SerdeJsValue,
+            "serde conversion through JsValue".to_string(),
+        ));
+    }
+
+    found
+}
+
+/// Summarize issues into results with recommendations
+fn build_results(
+    exports_scanned: usize,
+    issues: Vec<AbiIssue>,
+    glue: Vec<GlueStats>,
+) -> AbiResults {
+    let mut counts: BTreeMap<AbiPattern, usize> = BTreeMap::new();
+    for issue in &issues {
+        *counts.entry(issue.pattern).or_insert(0) += 1;
+    }
+
+    let estimated_savings_bytes = issues.iter().map(|i| i.pattern.size_per_occurrence()).sum();
+    let copies_saved_per_call = issues
+        .iter()
+        .map(|i| i.pattern.copies_saved_per_call())
+        .sum();
+
+    let mut by_pattern: Vec<(AbiPattern, usize)> = counts.into_iter().collect();
+    by_pattern.sort_by(|a, b| {
+        let savings = |(p, n): &(AbiPattern, usize)| p.size_per_occurrence() * *n as u64;
+        savings(b).cmp(&savings(a)).then(a.0.cmp(&b.0))
+    });
+
+    let recommendations = generate_recommendations(&by_pattern, &glue);
+
+    AbiResults {
+        exports_scanned,
+        by_pattern,
+        issues,
+        glue,
+        estimated_savings_bytes,
+        copies_saved_per_call,
+        recommendations,
+    }
+}
+
+/// Generate recommendations, largest savings first
+fn generate_recommendations(by_pattern: &[(AbiPattern, usize)], glue: &[GlueStats]) -> Vec<String> {
+    let mut recs = Vec::new();
+    for (pattern, count) in by_pattern {
+        let priority = match pattern {
+            AbiPattern::SerdeJsValue => "[P1]",
+            AbiPattern::StringRoundTrip | AbiPattern::VecReturn | AbiPattern::MutSliceParam => {
+                "[P2]"
+            }
+            AbiPattern::StructByValue | AbiPattern::VecParam => "[P3]",
+        };
+        let copies = pattern.copies_saved_per_call() * count;
+        let runtime = if copies > 0 {
+            format!(", {} fewer copies per call", copies)
+        } else {
+            String::new()
+        };
+        recs.push(format!(
+            "{} {} {}: {} (save ~{} B{})",
+            priority,
+            count,
+            pattern.name(),
+            pattern.alternative(),
+            pattern.size_per_occurrence() * *count as u64,
+            runtime
+        ));
+    }
+
+    let strings: usize = glue
+        .iter()
+        .flat_map(|g| g.copies_in.iter().chain(&g.copies_out))
+        .filter(|(helper, _)| helper.contains("String"))
+        .map(|(_, n)| n)
+        .sum();
+    if strings > 10 {
+        recs.push(format!(
+            "[P2] Generated glue has {} string copy sites; prefer numeric IDs or handles for hot calls",
+            strings
+        ));
+    }
+
+    recs
+}
+
+/// First line declaring `fn name` (0 if not found)
+fn find_fn_line(content: &str, name: &str) -> usize {
+    let needle = format!("fn {}", name);
+    content
+        .lines()
+        .position(|line| {
+            line.find(&needle).is_some_and(|i| {
+                !line[i + needle.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+            })
+        })
+        .map(|i| i + 1)
+        .unwrap_or(0)
+}
+
+/// True if the attributes include `#[wasm_bindgen]` (with or without arguments)
+fn has_wasm_bindgen(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|a| {
+        a.path()
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "wasm_bindgen")
+    })
+}
+
+/// Classify a type as seen by the wasm-bindgen glue
+fn abi_type(ty: &syn::Type) -> AbiType {
+    match ty {
+        syn::Type::Reference(r) => match &*r.elem {
+            syn::Type::Path(p) if last_ident(p).as_deref() == Some("str") => AbiType::Str,
+            syn::Type::Slice(s) if r.mutability.is_some() => AbiType::MutSlice(type_name(&s.elem)),
+            _ => AbiType::Other,
+        },
+        syn::Type::Path(p) => {
+            let Some(segment) = p.path.segments.last() else {
+                return AbiType::Other;
+            };
+            match segment.ident.to_string().as_str() {
+                "String" => AbiType::String,
+                "Vec" => AbiType::Vec(first_generic(segment).map_or_else(String::new, type_name)),
+                // The glue unwraps these before crossing the boundary
+                "Option" | "Result" => first_generic(segment).map_or(AbiType::Other, abi_type),
+                name => AbiType::Named(name.to_string()),
+            }
+        }
+        _ => AbiType::Other,
+    }
+}
+
+fn last_ident(p: &syn::TypePath) -> Option<String> {
+    p.path.segments.last().map(|s| s.ident.to_string())
+}
+
+fn first_generic(segment: &syn::PathSegment) -> Option<&syn::Type> {
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|a| match a {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn type_name(ty: &syn::Type) -> String {
+    match ty {
+        syn::Type::Path(p) => last_ident(p).unwrap_or_default(),
+        _ => "_".to_string(),
+    }
+}
+
+/// Collects `#[wasm_bindgen]` exports and exported structs from one file
+#[derive(Default)]
+struct ExportVisitor {
+    /// Exported struct name -> field count
+    structs: BTreeMap<String, usize>,
+    exports: Vec<ExportFn>,
+}
+
+impl ExportVisitor {
+    fn push_export(&mut self, sig: &syn::Signature, block: &syn::Block) {
+        let params = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                syn::FnArg::Typed(pat) => {
+                    let name = match &*pat.pat {
+                        syn::Pat::Ident(ident) => ident.ident.to_string(),
+                        _ => "_".to_string(),
+                    };
+                    Some((name, abi_type(&pat.ty)))
+                }
+                syn::FnArg::Receiver(_) => None,
+            })
+            .collect();
+        let ret = match &sig.output {
+            syn::ReturnType::Type(_, ty) => abi_type(ty),
+            syn::ReturnType::Default => AbiType::Other,
+        };
+
+        let mut serde = SerdeVisitor::default();
+        serde.visit_block(block);
+
+        self.exports.push(ExportFn {
+            name: sig.ident.to_string(),
+            params,
+            ret,
+            uses_serde: serde.found,
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for ExportVisitor {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        if has_wasm_bindgen(&node.attrs) {
+            self.structs
+                .insert(node.ident.to_string(), node.fields.len());
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if has_wasm_bindgen(&node.attrs) {
+            self.push_export(&node.sig, &node.block);
+        }
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        if !has_wasm_bindgen(&node.attrs) {
+            return;
+        }
+        for item in &node.items {
+            if let syn::ImplItem::Fn(method) = item {
+                if matches!(method.vis, syn::Visibility::Public(_)) {
+                    self.push_export(&method.sig, &method.block);
+                }
+            }
+        }
+    }
+}
+
+/// Finds serde conversions through `JsValue` in a function body
+#[derive(Default)]
+struct SerdeVisitor {
+    found: bool,
+}
+
+/// Functions and methods that serialize through `JsValue`
+const SERDE_BRIDGES: &[&str] = &["from_serde", "into_serde", "to_value", "from_value"];
+
+impl<'ast> Visit<'ast> for SerdeVisitor {
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        if SERDE_BRIDGES.contains(&node.method.to_string().as_str()) {
+            self.found = true;
+        }
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        let segments: BTreeSet<String> = node
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        let bridge = node
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| SERDE_BRIDGES.contains(&s.ident.to_string().as_str()));
+        if bridge && (segments.contains("JsValue") || segments.contains("serde_wasm_bindgen")) {
+            self.found = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exports_of(code: &str) -> (BTreeMap<String, usize>, Vec<ExportFn>) {
+        let mut visitor = ExportVisitor::default();
+        visitor.visit_file(&syn::parse_file(code).unwrap());
+        (visitor.structs, visitor.exports)
+    }
+
+    fn patterns_of(code: &str) -> Vec<(String, AbiPattern)> {
+        let (structs, exports) = exports_of(code);
+        exports
+            .iter()
+            .flat_map(|e| {
+                classify(e, &structs)
+                    .into_iter()
+                    .map(|(p, _)| (e.name.clone(), p))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_classify_detects_costly_signatures() {
+        let code = r#"
+            #[wasm_bindgen]
+            pub struct Scene { a: u32, b: u32, c: u32, d: u32, e: u32, f: u32 }
+
+            #[wasm_bindgen]
+            pub fn render(scene: Scene, pixels: &mut [u8]) {}
+
+            #[wasm_bindgen]
+            pub fn translate(text: &str) -> Result<String, JsValue> { todo!() }
+
+            #[wasm_bindgen]
+            pub fn encode(data: Vec<u8>) -> Vec<u8> { data }
+
+            #[wasm_bindgen]
+            pub fn load(value: JsValue) -> Result<(), JsValue> {
+                let config: Config = serde_wasm_bindgen::from_value(value)?;
+                Ok(())
+            }
+
+            pub fn internal(text: String) -> String { text }
+        "#;
+
+        let found = patterns_of(code);
+        let has = |f: &str, p: AbiPattern| found.contains(&(f.to_string(), p));
+        assert!(has("render", AbiPattern::StructByValue));
+        assert!(has("render", AbiPattern::MutSliceParam));
+        assert!(has("translate", AbiPattern::StringRoundTrip));
+        assert!(has("encode", AbiPattern::VecParam));
+        assert!(has("encode", AbiPattern::VecReturn));
+        assert!(has("load", AbiPattern::SerdeJsValue));
+        assert!(!found.iter().any(|(f, _)| f == "internal"));
+    }
+
+    #[test]
+    fn test_classify_accepts_cheap_signatures() {
+        let code = r#"
+            #[wasm_bindgen]
+            pub struct Scene { width: u32 }
+
+            #[wasm_bindgen]
+            impl Scene {
+                pub fn draw(&self, other: &Scene, pixels: &[u8]) -> u32 { 0 }
+                pub fn name(&self) -> String { String::new() }
+                fn private(self, text: String) -> String { text }
+            }
+        "#;
+
+        let (structs, exports) = exports_of(code);
+        assert_eq!(structs.get("Scene"), Some(&1));
+        assert_eq!(exports.len(), 2);
+        assert!(patterns_of(code).is_empty());
+    }
+
+    #[test]
+    fn test_scan_glue_source_counts_call_sites_only() {
+        let glue = r#"
+            function passStringToWasm0(arg, malloc, realloc) { }
+            function getStringFromWasm0(ptr, len) { }
+            export function translate(text) {
+                const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc);
+                return getStringFromWasm0(r0, r1);
+            }
+            export function encode(data) {
+                const ptr0 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
+                return getArrayU8FromWasm0(r0, r1).slice();
+            }
+        "#;
+
+        let stats = scan_glue_source(Path::new("pkg/app.js"), glue).unwrap();
+        assert_eq!(stats.copies_in.get("passStringToWasm0"), Some(&1));
+        assert_eq!(stats.copies_in.get("passArray8ToWasm0"), Some(&1));
+        assert_eq!(stats.copies_out.get("getStringFromWasm0"), Some(&1));
+        assert_eq!(stats.copies_out.get("getArrayU8FromWasm0"), Some(&1));
+        assert_eq!(stats.total_copies(), 4);
+    }
+
+    #[test]
+    fn test_analyze_reports_savings_and_line_numbers() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("src")).unwrap();
+        std::fs::write(
+            temp.path().join("src/lib.rs"),
+            "use wasm_bindgen::prelude::*;\n\n#[wasm_bindgen]\npub fn shout(text: String) -> String {\n    text.to_uppercase()\n}\n",
+        )
+        .unwrap();
+
+        let results = BindgenAbiAnalyzer::new(temp.path()).analyze().unwrap();
+        assert_eq!(results.exports_scanned, 1);
+        assert_eq!(results.issues.len(), 1);
+        assert_eq!(results.issues[0].line, 4);
+        assert_eq!(results.by_pattern, vec![(AbiPattern::StringRoundTrip, 1)]);
+        assert_eq!(results.estimated_savings_bytes, 400);
+        assert_eq!(results.copies_saved_per_call, 1);
+        assert!(results.glue.is_empty());
+        assert!(results.recommendations[0].contains("String round-trip"));
+    }
+}
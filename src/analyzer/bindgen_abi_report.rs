@@ -0,0 +1,152 @@
+//! wasm-bindgen ABI report formatting
+
+use super::bindgen_abi::AbiResults;
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of issues listed by default
+const DEFAULT_TOP_ISSUES: usize = 20;
+
+/// Format the ABI report for console output
+pub fn format_console_report(results: &AbiResults) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(results, ItemLimit::Default)
+}
+
+/// Format the ABI report, limiting the issues listed
+pub fn format_console_report_with_limit(
+    results: &AbiResults,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("wasm-bindgen ABI Analysis").bold().underlined()
+    )?;
+    writeln!(
+        output,
+        "\n   {} exports scanned, {} costly patterns",
+        results.exports_scanned,
+        style(results.issues.len()).cyan().bold()
+    )?;
+    writeln!(
+        output,
+        "   Estimated savings: ~{} of glue, {} fewer copies per call",
+        style(format_bytes(results.estimated_savings_bytes)).yellow(),
+        style(results.copies_saved_per_call).yellow()
+    )?;
+
+    if results.issues.is_empty() {
+        writeln!(
+            output,
+            "\n{}",
+            style("✨ No costly boundary patterns in the exported signatures").green()
+        )?;
+    } else {
+        writeln!(output, "\n{}", style("ISSUES:").bold())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+
+        let shown = limit.visible(results.issues.len(), Some(DEFAULT_TOP_ISSUES));
+        for issue in results.issues.iter().take(shown) {
+            writeln!(
+                output,
+                "  {} {} {}",
+                style(format!("{:22}", issue.pattern.name())).cyan(),
+                style(&issue.function).bold(),
+                style(truncate_str(&issue.detail, 40)).dim()
+            )?;
+            writeln!(
+                output,
+                "       {} {}:{}",
+                style("at").dim(),
+                issue.file.display(),
+                issue.line
+            )?;
+            writeln!(
+                output,
+                "       {} {}",
+                style("→").dim(),
+                style(issue.pattern.alternative()).green()
+            )?;
+        }
+        if let Some(footer) = omitted_footer(results.issues.len(), shown, "issues") {
+            writeln!(output, "\n      {}", style(footer).dim())?;
+        }
+    }
+
+    if !results.glue.is_empty() {
+        writeln!(output, "\n{}", style("GENERATED GLUE:").bold())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        for glue in &results.glue {
+            writeln!(
+                output,
+                "  {} ({}): {} copies in, {} copies out",
+                glue.file.display(),
+                format_bytes(glue.size_bytes),
+                glue.copies_in.values().sum::<usize>(),
+                glue.copies_out.values().sum::<usize>()
+            )?;
+        }
+    }
+
+    if !results.recommendations.is_empty() {
+        writeln!(output, "\n{}", style("RECOMMENDATIONS:").bold().yellow())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        for rec in &results.recommendations {
+            let styled = if rec.starts_with("[P1]") {
+                style(rec).yellow().bold()
+            } else if rec.starts_with("[P2]") {
+                style(rec).blue()
+            } else {
+                style(rec).green()
+            };
+            writeln!(output, "  {}", styled)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::bindgen_abi::{AbiIssue, AbiPattern, GlueStats};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_console_report_lists_issues_and_glue() {
+        let issue = |function: &str| AbiIssue {
+            pattern: AbiPattern::StringRoundTrip,
+            function: function.to_string(),
+            file: PathBuf::from("src/lib.rs"),
+            line: 12,
+            detail: "string in, String out".to_string(),
+        };
+        let results = AbiResults {
+            exports_scanned: 5,
+            by_pattern: vec![(AbiPattern::StringRoundTrip, 2)],
+            issues: vec![issue("shout"), issue("whisper")],
+            glue: vec![GlueStats {
+                file: PathBuf::from("pkg/app.js"),
+                size_bytes: 2048,
+                copies_in: BTreeMap::from([("passStringToWasm0".to_string(), 2)]),
+                copies_out: BTreeMap::from([("getStringFromWasm0".to_string(), 2)]),
+            }],
+            estimated_savings_bytes: 800,
+            copies_saved_per_call: 2,
+            recommendations: vec!["[P2] 2 String round-trip: ...".to_string()],
+        };
+
+        let text = format_console_report_with_limit(&results, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("5 exports scanned"));
+        assert!(text.contains("shout"));
+        assert!(text.contains("src/lib.rs:12"));
+        assert!(!text.contains("whisper"));
+        assert!(text.contains("1 more issues omitted"));
+        assert!(text.contains("pkg/app.js (2.00 KiB): 2 copies in, 2 copies out"));
+        assert!(text.contains("[P2] 2 String round-trip"));
+    }
+}
@@ -2,16 +2,36 @@
 //!
 //! Provides insights into which functions and data structures take up the most space
 //! in the compiled binary. Complements twiggy by analyzing the Rust binary before WASM conversion.
-
+//!
+//! cargo-bloat is asked for `--message-format json` first; releases without
+//! it fall back to parsing the human-readable table. Without cargo-bloat at
+//! all, [`BloatAnalyzer::analyze_wasm`] sizes the function bodies of the
+//! release artifact and names them from its `name` section. That view is
+//! degraded: data and symbols the linker merged are not attributed, and
+//! crates are guessed from the leading path segment.
+
+use super::report_utils::SizedEntry;
+use super::thresholds::RecommendationThresholds;
+use super::twiggy::native::ItemGraph;
+use crate::fmt::percent_of;
+use crate::i18n;
 use crate::infra::{CommandExecutor, RealCommandExecutor};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::process::Output;
+
+/// Symbols kept in [`BloatResults::items`]
+const TOP_ITEMS: usize = 50;
+
+/// Crate name cargo-bloat uses for symbols it cannot attribute
+const UNKNOWN_CRATE: &str = "[Unknown]";
 
 /// Main cargo-bloat analyzer
 pub struct BloatAnalyzer<CE: CommandExecutor = RealCommandExecutor> {
     project_root: std::path::PathBuf,
     cmd_executor: CE,
+    thresholds: RecommendationThresholds,
 }
 
 /// Bloat analysis item
@@ -27,6 +47,20 @@ pub struct BloatItem {
     pub crate_name: Option<String>,
 }
 
+impl SizedEntry for BloatItem {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.percentage)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
 /// Complete bloat analysis results
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BloatResults {
@@ -36,10 +70,69 @@ pub struct BloatResults {
     pub text_size_bytes: u64,
     /// Top items by size
     pub items: Vec<BloatItem>,
+    /// Code size per crate, largest first
+    ///
+    /// Covers every symbol when cargo-bloat emits JSON, only the top items
+    /// when the text table had to be parsed.
+    #[serde(default)]
+    pub crates: Vec<CrateSize>,
     /// Recommendations based on findings
     pub recommendations: Vec<Recommendation>,
 }
 
+/// Size attributed to one crate by `cargo bloat --crates`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrateSize {
+    /// Crate name as it appears in symbols (underscores, not dashes)
+    pub crate_name: String,
+    /// Code size in bytes
+    pub size_bytes: u64,
+    /// Percentage of the .text section
+    pub percentage: f64,
+}
+
+impl SizedEntry for CrateSize {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.percentage)
+    }
+
+    fn name(&self) -> &str {
+        &self.crate_name
+    }
+}
+
+/// `cargo bloat --message-format json` output
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct BloatJson {
+    file_size: u64,
+    text_section_size: u64,
+    /// Present without `--crates`
+    #[serde(default)]
+    functions: Vec<BloatJsonFunction>,
+    /// Present with `--crates`
+    #[serde(default)]
+    crates: Vec<BloatJsonCrate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BloatJsonFunction {
+    #[serde(rename = "crate")]
+    crate_name: Option<String>,
+    name: String,
+    size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BloatJsonCrate {
+    name: String,
+    size: u64,
+}
+
 /// Actionable recommendation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Recommendation {
@@ -47,10 +140,16 @@ pub struct Recommendation {
     pub priority: String,
     /// Description of the recommendation
     pub description: String,
-    /// Estimated savings in KB
-    pub estimated_savings_kb: u64,
+    /// Estimated savings in bytes
+    pub estimated_savings_bytes: u64,
     /// Estimated savings as percentage
     pub estimated_savings_percent: f64,
+    /// Rule that produced it (e.g. `bloat.heavy_crate`), matched by `.wasm-slim-ignore`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub rule: String,
+    /// Symbol or crate it is about, matched by `.wasm-slim-ignore`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
 }
 
 impl BloatAnalyzer {
@@ -100,9 +199,16 @@ impl<CE: CommandExecutor> BloatAnalyzer<CE> {
         Self {
             project_root: project_root.as_ref().to_path_buf(),
             cmd_executor,
+            thresholds: RecommendationThresholds::default(),
         }
     }
 
+    /// Use custom recommendation thresholds (`[analysis.thresholds]`)
+    pub fn with_thresholds(mut self, thresholds: RecommendationThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
     /// Check if cargo-bloat is installed using a custom command executor
     pub fn check_installation_with_executor<E: CommandExecutor>(executor: &E) -> Result<bool> {
         let output = executor.execute(|cmd| cmd.arg("bloat").arg("--version"), "cargo");
@@ -124,32 +230,160 @@ impl<CE: CommandExecutor> BloatAnalyzer<CE> {
         // Build the project first
         self.build_release()?;
 
-        // Run cargo bloat with JSON output
-        let output = self
-            .cmd_executor
+        // All symbols, so crate totals and recommendations see the whole binary
+        if let Some(stdout) = self.run_bloat_json(&["-n", "0"])? {
+            if let Ok(results) = self.parse_json_output(&stdout) {
+                return Ok(results);
+            }
+        }
+
+        let output = self.run_bloat(&["-n", "50"])?; // Top 50 items
+        self.parse_output(&output)
+    }
+
+    /// Attribute code size to crates with `cargo bloat --crates`
+    pub fn analyze_crates(&self) -> Result<Vec<CrateSize>> {
+        if !Self::check_installation_with_executor(&self.cmd_executor)? {
+            anyhow::bail!("cargo-bloat is not installed. Install with: cargo install cargo-bloat");
+        }
+
+        self.build_release()?;
+
+        if let Some(stdout) = self.run_bloat_json(&["--crates", "-n", "0"])? {
+            if let Ok(crates) = BloatAnalyzer::parse_crates_json(&stdout) {
+                return Ok(crates);
+            }
+        }
+
+        let output = self.run_bloat(&["--crates", "-n", "0"])?; // All crates
+        Ok(BloatAnalyzer::parse_crates_output(&output))
+    }
+
+    /// Run `cargo bloat --message-format json`
+    ///
+    /// Returns `None` when cargo-bloat rejects the flag or fails otherwise,
+    /// so the caller can fall back to the text table.
+    fn run_bloat_json(&self, args: &[&str]) -> Result<Option<String>> {
+        let mut json_args = args.to_vec();
+        json_args.extend(["--message-format", "json"]);
+        let output = self.execute_bloat(&json_args)?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(String::from_utf8(output.stdout).ok())
+    }
+
+    /// Run cargo bloat and return its text output
+    fn run_bloat(&self, args: &[&str]) -> Result<String> {
+        let output = self.execute_bloat(args)?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("cargo bloat failed: {}", stderr);
+        }
+
+        String::from_utf8(output.stdout).context("Failed to parse cargo bloat output as UTF-8")
+    }
+
+    fn execute_bloat(&self, args: &[&str]) -> Result<Output> {
+        self.cmd_executor
             .execute(
                 |cmd| {
                     cmd.arg("bloat")
                         .arg("--release")
                         .arg("--target")
                         .arg("wasm32-unknown-unknown")
-                        .arg("-n")
-                        .arg("50") // Top 50 items
+                        .args(args)
                         .current_dir(&self.project_root)
                 },
                 "cargo",
             )
-            .context("Failed to run cargo bloat")?;
+            .with_context(|| format!("Failed to run cargo bloat {}", args.join(" ")))
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("cargo bloat failed: {}", stderr);
+    /// Built-in analysis for when cargo-bloat is not installed
+    ///
+    /// Analyzes `wasm_file`, or builds the project and analyzes the newest
+    /// release artifact.
+    pub fn analyze_builtin(&self, wasm_file: Option<&Path>) -> Result<BloatResults> {
+        match wasm_file {
+            Some(wasm_file) => self.analyze_wasm(wasm_file),
+            None => {
+                self.build_release()?;
+                let wasm_file = self.find_release_wasm()?;
+                self.analyze_wasm(&wasm_file)
+            }
         }
+    }
+
+    /// Size the functions of a WASM file from its code and `name` sections
+    ///
+    /// Function bodies make up the code size; items and crate totals follow
+    /// the cargo-bloat layout so the same reports apply.
+    pub fn analyze_wasm(&self, wasm_file: &Path) -> Result<BloatResults> {
+        let total_size_bytes = std::fs::metadata(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?
+            .len();
+        let graph = ItemGraph::read(wasm_file)
+            .with_context(|| format!("Failed to parse {}", wasm_file.display()))?;
+        Ok(self.analyze_graph(&graph, total_size_bytes))
+    }
+
+    fn analyze_graph(&self, graph: &ItemGraph, total_size_bytes: u64) -> BloatResults {
+        let functions: Vec<usize> = (0..graph.item_count())
+            .filter(|&id| graph.is_code(id) && graph.size_bytes(id) > 0)
+            .collect();
+        let text_size_bytes: u64 = functions.iter().map(|&id| graph.size_bytes(id)).sum();
+
+        let mut items: Vec<BloatItem> = functions
+            .into_iter()
+            .map(|id| {
+                let name = graph.name(id).to_string();
+                BloatItem {
+                    size_bytes: graph.size_bytes(id),
+                    percentage: percent_of(graph.size_bytes(id), text_size_bytes),
+                    // Unnamed bodies keep twiggy's `code[N]` name
+                    crate_name: graph
+                        .symbol(id)
+                        .and_then(|_| BloatAnalyzer::crate_of(&name)),
+                    name,
+                }
+            })
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.size_bytes));
+
+        let recommendations = self.generate_recommendations(&items, total_size_bytes);
+        let crates = BloatAnalyzer::aggregate_crates(&items, text_size_bytes);
+        items.truncate(TOP_ITEMS);
 
-        let stdout = String::from_utf8(output.stdout)
-            .context("Failed to parse cargo bloat output as UTF-8")?;
+        BloatResults {
+            total_size_bytes,
+            text_size_bytes,
+            items,
+            crates,
+            recommendations,
+        }
+    }
 
-        self.parse_output(&stdout)
+    /// Newest `.wasm` in the release output directory
+    fn find_release_wasm(&self) -> Result<std::path::PathBuf> {
+        let release_dir = self
+            .project_root
+            .join("target/wasm32-unknown-unknown/release");
+        let entries = std::fs::read_dir(&release_dir)
+            .with_context(|| format!("Failed to read {}", release_dir.display()))?;
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("wasm"))
+            .filter_map(|path| {
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                Some((modified, path))
+            })
+            .max()
+            .map(|(_, path)| path)
+            .with_context(|| format!("No .wasm file found in {}", release_dir.display()))
     }
 
     /// Build the release binary
@@ -216,11 +450,47 @@ impl<CE: CommandExecutor> BloatAnalyzer<CE> {
         }
 
         let recommendations = self.generate_recommendations(&items, total_size_bytes);
+        let crates = BloatAnalyzer::aggregate_crates(&items, text_size_bytes);
 
         Ok(BloatResults {
             total_size_bytes,
             text_size_bytes,
             items,
+            crates,
+            recommendations,
+        })
+    }
+
+    /// Parse `cargo bloat --message-format json` output
+    ///
+    /// Crate totals and recommendations cover every symbol; only the
+    /// largest [`TOP_ITEMS`] are kept as items.
+    fn parse_json_output(&self, output: &str) -> Result<BloatResults> {
+        let parsed: BloatJson =
+            serde_json::from_str(output).context("Failed to parse cargo bloat JSON output")?;
+        let text_size_bytes = parsed.text_section_size;
+
+        let mut items: Vec<BloatItem> = parsed
+            .functions
+            .into_iter()
+            .map(|function| BloatItem {
+                size_bytes: function.size,
+                percentage: percent_of(function.size, text_size_bytes),
+                name: function.name,
+                crate_name: function.crate_name,
+            })
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.size_bytes));
+
+        let recommendations = self.generate_recommendations(&items, parsed.file_size);
+        let crates = BloatAnalyzer::aggregate_crates(&items, text_size_bytes);
+        items.truncate(TOP_ITEMS);
+
+        Ok(BloatResults {
+            total_size_bytes: parsed.file_size,
+            text_size_bytes,
+            items,
+            crates,
             recommendations,
         })
     }
@@ -255,6 +525,89 @@ impl BloatAnalyzer {
         })
     }
 
+    /// Parse `cargo bloat --crates` output
+    ///
+    /// Rows look like ` 5.3%  65.5%  97.5KiB std`: file share, .text share,
+    /// size and crate name. Summary and note lines are skipped.
+    pub(crate) fn parse_crates_output(output: &str) -> Vec<CrateSize> {
+        output
+            .lines()
+            .filter(|line| !line.contains("section size") && !line.contains("File  .text"))
+            .filter_map(|line| {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                let size_index = parts.iter().position(|p| !p.ends_with('%'))?;
+                let size_bytes = BloatAnalyzer::parse_size(parts[size_index]).ok()?;
+                let crate_name = parts.get(size_index + 1..)?.join(" ");
+                if crate_name.is_empty() {
+                    return None;
+                }
+                let percentage = parts[..size_index]
+                    .last()
+                    .and_then(|p| p.trim_end_matches('%').parse::<f64>().ok())
+                    .unwrap_or(0.0);
+                Some(CrateSize {
+                    crate_name,
+                    size_bytes,
+                    percentage,
+                })
+            })
+            .collect()
+    }
+
+    /// Parse `cargo bloat --crates --message-format json` output
+    pub(crate) fn parse_crates_json(output: &str) -> Result<Vec<CrateSize>> {
+        let parsed: BloatJson =
+            serde_json::from_str(output).context("Failed to parse cargo bloat JSON output")?;
+        Ok(parsed
+            .crates
+            .into_iter()
+            .map(|krate| CrateSize {
+                percentage: percent_of(krate.size, parsed.text_section_size),
+                crate_name: krate.name,
+                size_bytes: krate.size,
+            })
+            .collect())
+    }
+
+    /// Sum symbol sizes per crate, largest first
+    ///
+    /// Symbols without a crate are counted as `[Unknown]`, as cargo-bloat does.
+    pub fn aggregate_crates(items: &[BloatItem], text_size_bytes: u64) -> Vec<CrateSize> {
+        let mut sizes: std::collections::BTreeMap<&str, u64> = std::collections::BTreeMap::new();
+        for item in items {
+            let crate_name = item.crate_name.as_deref().unwrap_or(UNKNOWN_CRATE);
+            *sizes.entry(crate_name).or_insert(0) += item.size_bytes;
+        }
+
+        let mut crates: Vec<CrateSize> = sizes
+            .into_iter()
+            .map(|(crate_name, size_bytes)| CrateSize {
+                crate_name: crate_name.to_string(),
+                size_bytes,
+                percentage: percent_of(size_bytes, text_size_bytes),
+            })
+            .collect();
+        // Stable sort keeps ties in name order
+        crates.sort_by_key(|krate| std::cmp::Reverse(krate.size_bytes));
+        crates
+    }
+
+    /// Crate of a demangled symbol: its leading path segment
+    ///
+    /// `<T as Trait>::method` impls are attributed to the crate of `T`.
+    fn crate_of(name: &str) -> Option<String> {
+        let path = name.trim_start_matches(['<', '&', '*']);
+        let (crate_name, _) = path.split_once("::")?;
+        let crate_name = crate_name
+            .trim_start_matches("mut ")
+            .trim_start_matches("const ");
+        let is_ident = !crate_name.is_empty()
+            && crate_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        is_ident.then(|| crate_name.to_string())
+    }
+
     /// Parse size string (e.g., "12.3KiB", "1.5MiB") to bytes
     fn parse_size(size_str: &str) -> Result<u64> {
         let size_str = size_str.trim();
@@ -289,48 +642,54 @@ impl<CE: CommandExecutor> BloatAnalyzer<CE> {
         total_size: u64,
     ) -> Vec<Recommendation> {
         let mut recommendations = Vec::new();
+        let thresholds = &self.thresholds;
 
-        // Check for large individual functions (>50KB or >5%)
+        // Check for large individual functions (>50KB or >5% by default);
+        // twice the share is critical
         for item in items {
-            if item.size_bytes > 50 * 1024 || item.percentage > 5.0 {
-                let savings_kb = item.size_bytes / 1024;
+            if item.size_bytes > thresholds.large_function_bytes
+                || item.percentage > thresholds.large_function_percent
+            {
                 recommendations.push(Recommendation {
-                    priority: if item.percentage > 10.0 {
+                    priority: if item.percentage > thresholds.large_function_percent * 2.0 {
                         "P0".to_string()
                     } else {
                         "P1".to_string()
                     },
-                    description: format!(
-                        "Large function '{}' ({:.1}% of binary). Consider splitting or optimizing.",
-                        item.name, item.percentage
+                    description: i18n::tf(
+                        "bloat.large_function",
+                        &[
+                            ("name", &item.name),
+                            ("percent", &format!("{:.1}", item.percentage)),
+                        ],
                     ),
-                    estimated_savings_kb: savings_kb / 2, // Conservative estimate
+                    estimated_savings_bytes: item.size_bytes / 2, // Conservative estimate
                     estimated_savings_percent: item.percentage / 2.0,
+                    rule: "bloat.large_function".to_string(),
+                    subject: Some(item.name.clone()),
                 });
             }
         }
 
-        // Group by crate and find heavy crates
-        let mut crate_sizes: std::collections::HashMap<String, u64> =
-            std::collections::HashMap::new();
-        for item in items {
-            if let Some(ref crate_name) = item.crate_name {
-                *crate_sizes.entry(crate_name.clone()).or_insert(0) += item.size_bytes;
-            }
-        }
-
-        // Check for crates contributing >20% of binary
-        for (crate_name, size) in crate_sizes {
-            let percentage = (size as f64 / total_size as f64) * 100.0;
-            if percentage > 20.0 {
+        // Check for crates contributing >20% of binary (by default)
+        let crates = BloatAnalyzer::aggregate_crates(items, total_size);
+        for krate in crates.iter().filter(|c| c.crate_name != UNKNOWN_CRATE) {
+            let (crate_name, size, percentage) =
+                (&krate.crate_name, krate.size_bytes, krate.percentage);
+            if percentage > thresholds.heavy_crate_percent {
                 recommendations.push(Recommendation {
                     priority: "P0".to_string(),
-                    description: format!(
-                        "Crate '{}' contributes {:.1}% of binary. Consider lighter alternatives or feature minimization.",
-                        crate_name, percentage
+                    description: i18n::tf(
+                        "bloat.heavy_crate",
+                        &[
+                            ("name", crate_name),
+                            ("percent", &format!("{:.1}", percentage)),
+                        ],
                     ),
-                    estimated_savings_kb: ((size / 2) / 1024),
+                    estimated_savings_bytes: size / 2,
                     estimated_savings_percent: percentage / 2.0,
+                    rule: "bloat.heavy_crate".to_string(),
+                    subject: Some(crate_name.clone()),
                 });
             }
         }
@@ -346,17 +705,18 @@ impl<CE: CommandExecutor> BloatAnalyzer<CE> {
             .map(|item| item.size_bytes)
             .sum();
 
-        if formatting_size > total_size / 20 {
-            // >5%
-            let percentage = (formatting_size as f64 / total_size as f64) * 100.0;
+        let percentage = percent_of(formatting_size, total_size);
+        if percentage > thresholds.formatting_percent {
             recommendations.push(Recommendation {
                 priority: "P2".to_string(),
-                description: format!(
-                    "Formatting code takes up {:.1}% of binary. Consider using panic='abort' or removing Debug derives.",
-                    percentage
+                description: i18n::tf(
+                    "bloat.formatting_code",
+                    &[("percent", &format!("{:.1}", percentage))],
                 ),
-                estimated_savings_kb: ((formatting_size / 3) / 1024),
+                estimated_savings_bytes: formatting_size / 3,
                 estimated_savings_percent: percentage / 3.0,
+                rule: "bloat.formatting_code".to_string(),
+                subject: None,
             });
         }
 
@@ -367,6 +727,7 @@ impl<CE: CommandExecutor> BloatAnalyzer<CE> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_wasm::{leb, section, string, HEADER};
 
     #[test]
     fn test_parse_size_kib_units_converts_to_bytes() {
@@ -547,6 +908,160 @@ File  .text     Size Crate Name
         assert_eq!(results.total_size_bytes, 0); // Missing File size
     }
 
+    #[test]
+    fn test_parse_crates_output_extracts_crate_rows() {
+        let output = " File  .text     Size Crate\n 5.3%  65.5%  97.5KiB std\n 1.2%  14.0%  20.8KiB serde_json\n 0.1%   1.0%    1520B [Unknown]\n 8.1% 100.0% 148.9KiB .text section size, the file size is 1.8MiB\n\nNote: numbers above are a result of guesswork.\n";
+
+        let crates = BloatAnalyzer::parse_crates_output(output);
+
+        assert_eq!(crates.len(), 3);
+        assert_eq!(crates[0].crate_name, "std");
+        assert_eq!(crates[0].size_bytes, (97.5 * 1024.0) as u64);
+        assert_eq!(crates[0].percentage, 65.5);
+        assert_eq!(crates[1].crate_name, "serde_json");
+        assert_eq!(crates[2].crate_name, "[Unknown]");
+        assert_eq!(crates[2].size_bytes, 1520);
+    }
+
+    #[test]
+    fn test_parse_json_output_aggregates_all_symbols_by_crate() {
+        let analyzer = BloatAnalyzer::new(".");
+        let mut functions: Vec<String> = (0..60)
+            .map(|i| format!(r#"{{"crate":"std","name":"std::f{}","size":100}}"#, i))
+            .collect();
+        functions.push(
+            r#"{"crate":"serde_json","name":"serde_json::de::parse","size":9000}"#.to_string(),
+        );
+        functions.push(r#"{"name":"__wasm_call_ctors","size":1000}"#.to_string());
+        let output = format!(
+            r#"{{"file-size":100000,"text-section-size":16000,"functions":[{}]}}"#,
+            functions.join(",")
+        );
+
+        let results = analyzer.parse_json_output(&output).unwrap();
+
+        assert_eq!(results.total_size_bytes, 100000);
+        assert_eq!(results.text_size_bytes, 16000);
+        assert_eq!(results.items.len(), TOP_ITEMS);
+        assert_eq!(results.items[0].name, "serde_json::de::parse");
+        assert_eq!(results.items[0].percentage, 56.25);
+        let crates: Vec<(&str, u64)> = results
+            .crates
+            .iter()
+            .map(|c| (c.crate_name.as_str(), c.size_bytes))
+            .collect();
+        assert_eq!(
+            crates,
+            [("serde_json", 9000), ("std", 6000), ("[Unknown]", 1000)]
+        );
+        assert!(analyzer.parse_json_output("File  .text  Size").is_err());
+    }
+
+    #[test]
+    fn test_parse_crates_json_extracts_crates() {
+        let output = r#"{"file-size":200000,"text-section-size":100000,"crates":[{"name":"std","size":65500},{"name":"serde_json","size":12500}]}"#;
+
+        let crates = BloatAnalyzer::parse_crates_json(output).unwrap();
+
+        assert_eq!(
+            crates,
+            [
+                CrateSize {
+                    crate_name: "std".to_string(),
+                    size_bytes: 65500,
+                    percentage: 65.5,
+                },
+                CrateSize {
+                    crate_name: "serde_json".to_string(),
+                    size_bytes: 12500,
+                    percentage: 12.5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crate_of_takes_leading_path_segment() {
+        assert_eq!(
+            BloatAnalyzer::crate_of("serde_json::de::parse"),
+            Some("serde_json".to_string())
+        );
+        assert_eq!(
+            BloatAnalyzer::crate_of("<app::Config as core::fmt::Debug>::fmt"),
+            Some("app".to_string())
+        );
+        assert_eq!(
+            BloatAnalyzer::crate_of("<&mut T as core::fmt::Write>::write_str"),
+            None
+        );
+        assert_eq!(BloatAnalyzer::crate_of("__wasm_call_ctors"), None);
+    }
+
+    /// Module of functions with the given body padding; names are optional
+    fn module(functions: &[(Option<&str>, usize)]) -> Vec<u8> {
+        let mut wasm = HEADER.to_vec();
+        wasm.extend(section(1, &[1, 0x60, 0, 0]));
+        let mut declared = leb(functions.len());
+        declared.extend(std::iter::repeat_n(0, functions.len()));
+        wasm.extend(section(3, &declared));
+
+        let mut code = leb(functions.len());
+        for &(_, padding) in functions {
+            let mut body = vec![0];
+            body.extend(std::iter::repeat_n(0x01, padding));
+            body.push(0x0b);
+            code.extend(leb(body.len()));
+            code.extend(body);
+        }
+        wasm.extend(section(10, &code));
+
+        let named: Vec<(usize, &str)> = functions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (name, _))| name.map(|name| (index, name)))
+            .collect();
+        let mut names = leb(named.len());
+        for (index, name) in named {
+            names.extend(leb(index));
+            names.extend(string(name));
+        }
+        let mut name_section = string("name");
+        name_section.extend(section(1, &names));
+        wasm.extend(section(0, &name_section));
+        wasm
+    }
+
+    #[test]
+    fn test_analyze_graph_sizes_named_functions_without_cargo_bloat() {
+        let wasm = module(&[
+            (Some("_ZN10serde_json2de5parse17h0123456789abcdefE"), 98),
+            (Some("app::run"), 48),
+            (None, 18),
+        ]);
+        let graph = ItemGraph::parse(&wasm).unwrap();
+
+        let results = BloatAnalyzer::new(".").analyze_graph(&graph, wasm.len() as u64);
+
+        assert_eq!(results.total_size_bytes, wasm.len() as u64);
+        // Bodies are padding plus the locals count and `end`
+        assert_eq!(results.text_size_bytes, 100 + 50 + 20);
+        let items: Vec<(&str, Option<&str>, u64)> = results
+            .items
+            .iter()
+            .map(|i| (i.name.as_str(), i.crate_name.as_deref(), i.size_bytes))
+            .collect();
+        assert_eq!(
+            items,
+            [
+                ("serde_json::de::parse", Some("serde_json"), 100),
+                ("app::run", Some("app"), 50),
+                ("code[2]", None, 20),
+            ]
+        );
+        assert_eq!(results.crates[0].crate_name, "serde_json");
+        assert_eq!(results.crates[2].crate_name, "[Unknown]");
+    }
+
     #[test]
     fn test_generate_recommendations_large_function_creates_p0_priority() {
         let analyzer = BloatAnalyzer::new(".");
@@ -623,6 +1138,30 @@ File  .text     Size Crate Name
         assert!(recommendations.is_empty() || recommendations.iter().all(|r| r.priority != "P0"));
     }
 
+    #[test]
+    fn test_generate_recommendations_respects_configured_thresholds() {
+        let items = vec![BloatItem {
+            size_bytes: 20 * 1024,
+            percentage: 2.0,
+            name: "app::render".to_string(),
+            crate_name: Some("app".to_string()),
+        }];
+        let thresholds = RecommendationThresholds {
+            large_function_bytes: 16 * 1024,
+            heavy_crate_percent: 1.5,
+            ..Default::default()
+        };
+        let analyzer = BloatAnalyzer::new(".").with_thresholds(thresholds);
+
+        let recommendations = analyzer.generate_recommendations(&items, 1024 * 1024);
+
+        assert_eq!(recommendations.len(), 2);
+        assert_eq!(recommendations[0].priority, "P1");
+        assert!(BloatAnalyzer::new(".")
+            .generate_recommendations(&items, 1024 * 1024)
+            .is_empty());
+    }
+
     // P2-CODE-GLOBAL-004: Property-based tests for parse_size
     mod proptest_parse_size {
         use super::*;
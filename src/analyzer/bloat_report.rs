@@ -1,33 +1,56 @@
 //! Bloat analysis report formatting
 
 use super::bloat::BloatResults;
-use super::report_utils;
+use super::report_utils::{self, omitted_footer, ItemLimit};
+use crate::i18n::t;
 use console::style;
 use std::fmt::{self, Write as _};
 
+/// Number of top contributors listed by default
+const DEFAULT_TOP_ITEMS: usize = 20;
+
+/// Number of crates listed by default
+const DEFAULT_TOP_CRATES: usize = 10;
+
 /// Format bloat analysis results for console output
 pub fn format_console_report(results: &BloatResults) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(results, ItemLimit::Default)
+}
+
+/// Format bloat analysis results, limiting the contributors listed
+pub fn format_console_report_with_limit(
+    results: &BloatResults,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
     let mut output = String::new();
 
     // Header
-    writeln!(output, "\n{} Binary Size Analysis", style("📊").bold())?;
+    writeln!(output, "\n{} {}", style("📊").bold(), t("bloat.title"))?;
     writeln!(
         output,
-        "   Total Size: {}",
+        "   {} {}",
+        t("bloat.total_size"),
         style(format_bytes(results.total_size_bytes)).cyan()
     )?;
     writeln!(
         output,
-        "   Code (.text): {}\n",
+        "   {} {}\n",
+        t("bloat.code_size"),
         style(format_bytes(results.text_size_bytes)).cyan()
     )?;
 
     // Top contributors
-    writeln!(output, "{} Top Contributors by Size", style("🔍").bold())?;
+    writeln!(
+        output,
+        "{} {}",
+        style("🔍").bold(),
+        t("bloat.top_contributors")
+    )?;
     writeln!(output, "   {:<12} {:<8} Symbol", "Size", "Percent")?;
     output.push_str("   ─────────────────────────────────────────────────────────\n");
 
-    for (i, item) in results.items.iter().take(20).enumerate() {
+    let shown = limit.visible(results.items.len(), Some(DEFAULT_TOP_ITEMS));
+    for (i, item) in results.items.iter().take(shown).enumerate() {
         let size_str = format_bytes(item.size_bytes);
         let percent_str = format!("{:.1}%", item.percentage);
         let name = truncate_symbol(&item.name, 60);
@@ -46,13 +69,39 @@ pub fn format_console_report(results: &BloatResults) -> Result<String, fmt::Erro
             style(name).dim()
         )?;
     }
+    if let Some(footer) = omitted_footer(results.items.len(), shown, "symbols") {
+        writeln!(output, "   {}", style(footer).dim())?;
+    }
+
+    // Crate totals
+    if !results.crates.is_empty() {
+        writeln!(output, "\n{} {}", style("📦").bold(), t("bloat.top_crates"))?;
+        writeln!(output, "   {:<12} {:<8} Crate", "Size", "Percent")?;
+        output.push_str("   ─────────────────────────────────────────────────────────\n");
+
+        let shown = limit.visible(results.crates.len(), Some(DEFAULT_TOP_CRATES));
+        for krate in results.crates.iter().take(shown) {
+            let percent_str = format!("{:.1}%", krate.percentage);
+            writeln!(
+                output,
+                "   {:<12} {:<8} {}",
+                format_bytes(krate.size_bytes),
+                percent_str,
+                krate.crate_name
+            )?;
+        }
+        if let Some(footer) = omitted_footer(results.crates.len(), shown, "crates") {
+            writeln!(output, "   {}", style(footer).dim())?;
+        }
+    }
 
     // Recommendations
     if !results.recommendations.is_empty() {
         writeln!(
             output,
-            "\n{} Optimization Opportunities",
-            style("💡").bold()
+            "\n{} {}",
+            style("💡").bold(),
+            t("bloat.opportunities")
         )?;
 
         for rec in &results.recommendations {
@@ -65,10 +114,10 @@ pub fn format_console_report(results: &BloatResults) -> Result<String, fmt::Erro
 
             writeln!(
                 output,
-                "   {} {} (save ~{}KB / {:.1}%)",
+                "   {} {} (save ~{} / {:.1}%)",
                 priority_str,
                 rec.description,
-                rec.estimated_savings_kb,
+                format_bytes(rec.estimated_savings_bytes),
                 rec.estimated_savings_percent
             )?;
         }
@@ -80,7 +129,7 @@ pub fn format_console_report(results: &BloatResults) -> Result<String, fmt::Erro
 
 /// Format bloat results as JSON
 pub fn format_json_report(results: &BloatResults) -> Result<String, serde_json::Error> {
-    serde_json::to_string_pretty(results)
+    crate::redact::to_json_pretty(results)
 }
 
 // Use shared formatting utilities from report_utils
@@ -101,8 +150,8 @@ mod tests {
     #[test]
     fn test_format_bytes_converts_to_readable_units() {
         assert_eq!(format_bytes(500), "500 B");
-        assert_eq!(format_bytes(1536), "1.50 KB");
-        assert_eq!(format_bytes(1572864), "1.50 MB");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(1572864), "1.50 MiB");
     }
 
     #[test]
@@ -120,6 +169,7 @@ mod tests {
             total_size_bytes: 1024000,
             text_size_bytes: 512000,
             items: vec![],
+            crates: Vec::new(),
             recommendations: vec![],
         };
         let json = format_json_report(&results).expect("Failed to serialize bloat results to JSON");
@@ -132,13 +182,14 @@ mod tests {
             total_size_bytes: 1024,
             text_size_bytes: 512,
             items: vec![],
+            crates: Vec::new(),
             recommendations: vec![],
         };
         let output = format_console_report(&results);
         assert!(output.is_ok());
         let text = output.unwrap();
         assert!(text.contains("Binary Size Analysis"));
-        assert!(text.contains("1.00 KB")); // total size
+        assert!(text.contains("1.00 KiB")); // total size
     }
 
     #[test]
@@ -162,11 +213,14 @@ mod tests {
                     crate_name: None,
                 },
             ],
+            crates: Vec::new(),
             recommendations: vec![Recommendation {
                 priority: "P0".to_string(),
                 description: "Optimize large function".to_string(),
-                estimated_savings_kb: 10,
+                estimated_savings_bytes: 10 * 1024,
                 estimated_savings_percent: 2.0,
+                rule: String::new(),
+                subject: None,
             }],
         };
 
@@ -180,6 +234,61 @@ mod tests {
         assert!(text.contains("P0"));
     }
 
+    #[test]
+    fn test_format_console_report_lists_crate_totals() {
+        use super::super::bloat::CrateSize;
+
+        let results = BloatResults {
+            total_size_bytes: 100000,
+            text_size_bytes: 50000,
+            items: vec![],
+            crates: vec![CrateSize {
+                crate_name: "serde_json".to_string(),
+                size_bytes: 20480,
+                percentage: 41.0,
+            }],
+            recommendations: vec![],
+        };
+
+        let text = format_console_report(&results).unwrap();
+        assert!(text.contains("Code Size by Crate"));
+        assert!(text.contains("20.00 KiB"));
+        assert!(text.contains("41.0%"));
+        assert!(text.contains("serde_json"));
+    }
+
+    #[test]
+    fn test_format_console_report_with_limit_adds_omitted_footer() {
+        let items = (0..30)
+            .map(|i| BloatItem {
+                name: format!("func_{}", i),
+                size_bytes: 1000,
+                percentage: 1.0,
+                crate_name: None,
+            })
+            .collect();
+        let results = BloatResults {
+            total_size_bytes: 100000,
+            text_size_bytes: 50000,
+            items,
+            crates: Vec::new(),
+            recommendations: vec![],
+        };
+
+        let text = format_console_report(&results).unwrap();
+        assert!(text.contains("func_19"));
+        assert!(!text.contains("func_20"));
+        assert!(text.contains("10 more symbols omitted, use --all"));
+
+        let text = format_console_report_with_limit(&results, ItemLimit::Max(5)).unwrap();
+        assert!(!text.contains("func_5"));
+        assert!(text.contains("25 more symbols omitted"));
+
+        let text = format_console_report_with_limit(&results, ItemLimit::All).unwrap();
+        assert!(text.contains("func_29"));
+        assert!(!text.contains("omitted"));
+    }
+
     #[test]
     fn test_format_console_report_with_many_items() {
         let mut items = vec![];
@@ -196,6 +305,7 @@ mod tests {
             total_size_bytes: 100000,
             text_size_bytes: 50000,
             items,
+            crates: Vec::new(),
             recommendations: vec![],
         };
 
@@ -215,30 +325,39 @@ mod tests {
             total_size_bytes: 100000,
             text_size_bytes: 50000,
             items: vec![],
+            crates: Vec::new(),
             recommendations: vec![
                 Recommendation {
                     priority: "P0".to_string(),
                     description: "Critical issue".to_string(),
-                    estimated_savings_kb: 50,
+                    estimated_savings_bytes: 50 * 1024,
                     estimated_savings_percent: 10.0,
+                    rule: String::new(),
+                    subject: None,
                 },
                 Recommendation {
                     priority: "P1".to_string(),
                     description: "High priority".to_string(),
-                    estimated_savings_kb: 20,
+                    estimated_savings_bytes: 20 * 1024,
                     estimated_savings_percent: 5.0,
+                    rule: String::new(),
+                    subject: None,
                 },
                 Recommendation {
                     priority: "P2".to_string(),
                     description: "Medium priority".to_string(),
-                    estimated_savings_kb: 10,
+                    estimated_savings_bytes: 10 * 1024,
                     estimated_savings_percent: 2.0,
+                    rule: String::new(),
+                    subject: None,
                 },
                 Recommendation {
                     priority: "P3".to_string(),
                     description: "Low priority".to_string(),
-                    estimated_savings_kb: 5,
+                    estimated_savings_bytes: 5 * 1024,
                     estimated_savings_percent: 1.0,
+                    rule: String::new(),
+                    subject: None,
                 },
             ],
         };
@@ -265,11 +384,14 @@ mod tests {
                 percentage: 1.0,
                 crate_name: None,
             }],
+            crates: Vec::new(),
             recommendations: vec![Recommendation {
                 priority: "P0".to_string(),
                 description: "Test".to_string(),
-                estimated_savings_kb: 10,
+                estimated_savings_bytes: 10 * 1024,
                 estimated_savings_percent: 2.0,
+                rule: String::new(),
+                subject: None,
             }],
         };
 
@@ -0,0 +1,422 @@
+//! Cross-check a built artifact against the active configuration
+//!
+//! A build that silently ignores the configuration looks fine until someone
+//! inspects the bundle: the profile was never picked up because a workspace
+//! root overrides it, wasm-opt was skipped because it is not on `PATH`, or
+//! the analyzed file is the debug build. The custom sections of the artifact
+//! give most of this away: `strip = true` removes the `name` section and
+//! DWARF, wasm-opt drops both unless run with `-g`, the `--strip-*` flags
+//! remove their sections, and LLVM records the target features it compiled
+//! with in `target_features`.
+
+use super::report_utils::SizedEntry;
+use crate::config::Template;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+use wasmparser::{BinaryReader, Parser, Payload};
+
+/// wasm-opt `--enable-*` flags and the LLVM target feature they correspond to
+const FEATURE_FLAGS: &[(&str, &str)] = &[
+    ("--enable-bulk-memory", "bulk-memory"),
+    ("--enable-exception-handling", "exception-handling"),
+    ("--enable-multivalue", "multivalue"),
+    ("--enable-mutable-globals", "mutable-globals"),
+    ("--enable-nontrapping-float-to-int", "nontrapping-fptoint"),
+    ("--enable-reference-types", "reference-types"),
+    ("--enable-sign-ext", "sign-ext"),
+    ("--enable-simd", "simd128"),
+    ("--enable-tail-call", "tail-call"),
+];
+
+/// Errors that can occur while checking an artifact
+#[derive(Error, Debug)]
+pub enum ConfigCheckError {
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Artifact is not a valid WASM module
+    #[error("WASM parsing failed: {0}")]
+    WasmParse(#[from] wasmparser::BinaryReaderError),
+}
+
+/// Pipeline stage that produced the artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactStage {
+    /// Raw `cargo build` output under `target/`; wasm-opt has not run yet
+    Cargo,
+    /// Post-processed output (e.g. `pkg/`), after wasm-bindgen and wasm-opt
+    Final,
+}
+
+/// Setting the artifact contradicts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MismatchKind {
+    /// Artifact comes from a debug build, so `[profile.release]` never applied
+    DebugProfile,
+    /// `name` section present although `strip = true`
+    NamesNotStripped,
+    /// DWARF sections present although `strip = true`
+    DebugInfoNotStripped,
+    /// wasm-opt configured, but the artifact still has names or DWARF
+    WasmOptNotApplied,
+    /// `--strip-debug`/`--strip-dwarf`/`--strip-producers` had no effect
+    StripFlagIgnored,
+    /// wasm-opt enables a feature the code was not compiled with
+    FeatureNotCompiled,
+}
+
+/// One way the artifact contradicts the configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigMismatch {
+    /// Kind of mismatch
+    pub kind: MismatchKind,
+    /// Configured setting, e.g. `profile.strip = true`
+    pub setting: String,
+    /// What the artifact shows instead
+    pub finding: String,
+    /// How to make the build apply the setting
+    pub hint: String,
+}
+
+impl SizedEntry for ConfigMismatch {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        &self.setting
+    }
+}
+
+/// Result of checking one artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigCheckResults {
+    /// Checked artifact
+    pub artifact: PathBuf,
+    /// Pipeline stage the artifact comes from
+    pub stage: ArtifactStage,
+    /// Template the configuration resolves to
+    pub template: String,
+    /// Custom sections found in the artifact
+    pub custom_sections: Vec<String>,
+    /// Settings the artifact contradicts
+    pub mismatches: Vec<ConfigMismatch>,
+}
+
+/// Custom sections and target features of a module
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArtifactSections {
+    /// Custom section names, in order of first appearance
+    pub custom: Vec<String>,
+    /// Features from the `target_features` section (`None` if absent)
+    pub target_features: Option<BTreeSet<String>>,
+}
+
+impl ArtifactSections {
+    /// Read the custom sections of a WASM module
+    pub fn parse(bytes: &[u8]) -> Result<Self, ConfigCheckError> {
+        let mut sections = Self::default();
+        for payload in Parser::new(0).parse_all(bytes) {
+            if let Payload::CustomSection(reader) = payload? {
+                let name = reader.name().to_string();
+                if name == "target_features" {
+                    sections.target_features =
+                        Some(parse_target_features(reader.data(), reader.data_offset())?);
+                }
+                if !sections.custom.contains(&name) {
+                    sections.custom.push(name);
+                }
+            }
+        }
+        Ok(sections)
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.custom.iter().any(|s| s == name)
+    }
+
+    fn has_dwarf(&self) -> bool {
+        self.custom.iter().any(|s| s.starts_with(".debug_"))
+    }
+}
+
+/// Enabled (`+`) and required (`=`) features of a `target_features` section
+fn parse_target_features(data: &[u8], offset: usize) -> Result<BTreeSet<String>, ConfigCheckError> {
+    let mut reader = BinaryReader::new(data, offset);
+    let mut features = BTreeSet::new();
+    for _ in 0..reader.read_var_u32()? {
+        let prefix = reader.read_u8()?;
+        let name = reader.read_string()?;
+        if prefix == b'+' || prefix == b'=' {
+            features.insert(name.to_string());
+        }
+    }
+    Ok(features)
+}
+
+/// Checks artifacts against a resolved template
+pub struct ConfigChecker {
+    template: Template,
+}
+
+impl ConfigChecker {
+    /// Create a checker for the resolved configuration
+    pub fn new(template: Template) -> Self {
+        Self { template }
+    }
+
+    /// Check a WASM artifact
+    pub fn check(&self, wasm_file: &Path) -> Result<ConfigCheckResults, ConfigCheckError> {
+        let bytes = std::fs::read(wasm_file)?;
+        let sections = ArtifactSections::parse(&bytes)?;
+        let stage = artifact_stage(wasm_file);
+
+        let mut mismatches = Vec::new();
+        if is_debug_build(wasm_file) {
+            mismatches.push(ConfigMismatch {
+                kind: MismatchKind::DebugProfile,
+                setting: "[profile.release]".to_string(),
+                finding: "artifact comes from a debug build".to_string(),
+                hint: "Analyze the release artifact (cargo build --release or wasm-slim build)"
+                    .to_string(),
+            });
+        }
+        mismatches.extend(self.check_sections(&sections, stage));
+
+        Ok(ConfigCheckResults {
+            artifact: wasm_file.to_path_buf(),
+            stage,
+            template: self.template.name.clone(),
+            custom_sections: sections.custom,
+            mismatches,
+        })
+    }
+
+    /// Compare the sections of an artifact with the configuration
+    pub fn check_sections(
+        &self,
+        sections: &ArtifactSections,
+        stage: ArtifactStage,
+    ) -> Vec<ConfigMismatch> {
+        let profile = &self.template.profile;
+        let flags = &self.template.wasm_opt.flags;
+        let has_flag = |flag: &str| flags.iter().any(|f| f == flag);
+        let mut mismatches = Vec::new();
+
+        if profile.strip && sections.has("name") {
+            mismatches.push(ConfigMismatch {
+                kind: MismatchKind::NamesNotStripped,
+                setting: "profile.strip = true".to_string(),
+                finding: "`name` section is present".to_string(),
+                hint: "Check that [profile.release] is set in the workspace root Cargo.toml"
+                    .to_string(),
+            });
+        }
+        if profile.strip && sections.has_dwarf() {
+            mismatches.push(ConfigMismatch {
+                kind: MismatchKind::DebugInfoNotStripped,
+                setting: "profile.strip = true".to_string(),
+                finding: "DWARF `.debug_*` sections are present".to_string(),
+                hint: "Check that `debug = true` is not set for the release profile".to_string(),
+            });
+        }
+
+        // wasm-opt only runs after cargo, so its settings say nothing about
+        // the raw cargo output
+        if stage == ArtifactStage::Final {
+            let keeps_debuginfo = has_flag("-g") || has_flag("--debuginfo");
+            if !flags.is_empty()
+                && !keeps_debuginfo
+                && (sections.has("name") || sections.has_dwarf())
+            {
+                mismatches.push(ConfigMismatch {
+                    kind: MismatchKind::WasmOptNotApplied,
+                    setting: format!("wasm-opt flags = [{}]", flags.join(", ")),
+                    finding: "names or DWARF survive, which wasm-opt drops without -g".to_string(),
+                    hint: "Check that wasm-opt is installed and ran (wasm-slim build --verbose)"
+                        .to_string(),
+                });
+            }
+
+            let strip_debug = ["--strip-debug", "--strip-dwarf"]
+                .into_iter()
+                .find(|flag| has_flag(flag));
+            if let Some(flag) = strip_debug.filter(|_| sections.has_dwarf()) {
+                mismatches.push(ConfigMismatch {
+                    kind: MismatchKind::StripFlagIgnored,
+                    setting: format!("wasm-opt {}", flag),
+                    finding: "DWARF `.debug_*` sections are present".to_string(),
+                    hint: "Run wasm-opt on the final artifact, after wasm-bindgen".to_string(),
+                });
+            }
+            if has_flag("--strip-producers") && sections.has("producers") {
+                mismatches.push(ConfigMismatch {
+                    kind: MismatchKind::StripFlagIgnored,
+                    setting: "wasm-opt --strip-producers".to_string(),
+                    finding: "`producers` section is present".to_string(),
+                    hint: "Run wasm-opt on the final artifact, after wasm-bindgen".to_string(),
+                });
+            }
+        }
+
+        // Only LLVM writes target_features; binaryen drops it by default
+        if let Some(ref compiled) = sections.target_features {
+            for (flag, feature) in FEATURE_FLAGS {
+                if has_flag(flag) && !compiled.contains(*feature) {
+                    mismatches.push(ConfigMismatch {
+                        kind: MismatchKind::FeatureNotCompiled,
+                        setting: format!("wasm-opt {}", flag),
+                        finding: format!("code was compiled without `{}`", feature),
+                        hint: format!(
+                            "Add `-C target-feature=+{}` to RUSTFLAGS, or drop {}",
+                            feature, flag
+                        ),
+                    });
+                }
+            }
+        }
+
+        mismatches
+    }
+}
+
+/// Artifacts under a `target` directory are raw cargo output
+fn artifact_stage(wasm_file: &Path) -> ArtifactStage {
+    let in_target = wasm_file
+        .components()
+        .any(|c| matches!(c, Component::Normal(name) if name == "target"));
+    if in_target {
+        ArtifactStage::Cargo
+    } else {
+        ArtifactStage::Final
+    }
+}
+
+/// `target/<triple>/debug/...` or `target/debug/...`
+fn is_debug_build(wasm_file: &Path) -> bool {
+    let components: Vec<&std::ffi::OsStr> = wasm_file
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name),
+            _ => None,
+        })
+        .collect();
+    components
+        .iter()
+        .position(|c| *c == "target")
+        .is_some_and(|target| {
+            components[target + 1..]
+                .iter()
+                .take(2)
+                .any(|c| *c == "debug")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{custom_section, HEADER};
+
+    fn module(sections: &[Vec<u8>]) -> Vec<u8> {
+        let mut wasm = HEADER.to_vec();
+        for section in sections {
+            wasm.extend(section);
+        }
+        wasm
+    }
+
+    fn checker() -> ConfigChecker {
+        ConfigChecker::new(Template::get("balanced").unwrap())
+    }
+
+    fn kinds(mismatches: &[ConfigMismatch]) -> Vec<MismatchKind> {
+        mismatches.iter().map(|m| m.kind).collect()
+    }
+
+    #[test]
+    fn test_parse_reads_custom_sections_and_target_features() {
+        let features = [
+            2, b'+', 4, b's', b'i', b'g', b'n', b'-', 4, b'a', b't', b'o', b'm',
+        ];
+        let wasm = module(&[
+            custom_section("name", &[]),
+            custom_section("target_features", &features),
+        ]);
+
+        let sections = ArtifactSections::parse(&wasm).unwrap();
+        assert_eq!(sections.custom, vec!["name", "target_features"]);
+        assert_eq!(
+            sections.target_features,
+            Some(BTreeSet::from(["sign".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_final_artifact_with_names_flags_strip_and_wasm_opt() {
+        let sections = ArtifactSections {
+            custom: vec!["name".to_string(), "producers".to_string()],
+            target_features: None,
+        };
+
+        let mismatches = checker().check_sections(&sections, ArtifactStage::Final);
+        assert_eq!(
+            kinds(&mismatches),
+            vec![
+                MismatchKind::NamesNotStripped,
+                MismatchKind::WasmOptNotApplied,
+                MismatchKind::StripFlagIgnored,
+            ]
+        );
+        assert!(mismatches[2].setting.contains("--strip-producers"));
+    }
+
+    #[test]
+    fn test_cargo_artifact_skips_wasm_opt_checks_but_checks_features() {
+        let sections = ArtifactSections {
+            custom: vec![".debug_info".to_string(), "producers".to_string()],
+            target_features: Some(BTreeSet::from([
+                "mutable-globals".to_string(),
+                "sign-ext".to_string(),
+                "nontrapping-fptoint".to_string(),
+            ])),
+        };
+
+        let mismatches = checker().check_sections(&sections, ArtifactStage::Cargo);
+        assert_eq!(
+            kinds(&mismatches),
+            vec![
+                MismatchKind::DebugInfoNotStripped,
+                MismatchKind::FeatureNotCompiled,
+            ]
+        );
+        assert!(mismatches[1].hint.contains("+bulk-memory"));
+    }
+
+    #[test]
+    fn test_clean_artifact_has_no_mismatches() {
+        let sections = ArtifactSections::default();
+        assert!(checker()
+            .check_sections(&sections, ArtifactStage::Final)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_artifact_stage_and_debug_build_from_path() {
+        let debug = Path::new("target/wasm32-unknown-unknown/debug/app.wasm");
+        assert_eq!(artifact_stage(debug), ArtifactStage::Cargo);
+        assert!(is_debug_build(debug));
+
+        let release = Path::new("target/wasm32-unknown-unknown/release/app.wasm");
+        assert!(!is_debug_build(release));
+        assert_eq!(
+            artifact_stage(Path::new("pkg/app_bg.wasm")),
+            ArtifactStage::Final
+        );
+        assert!(!is_debug_build(Path::new("debug/app.wasm")));
+    }
+}
@@ -0,0 +1,96 @@
+//! Config cross-check report formatting
+
+use super::config_check::{ArtifactStage, ConfigCheckResults};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Format the config cross-check for console output
+pub fn format_console_report(results: &ConfigCheckResults) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Config Cross-Check").bold().underlined()
+    )?;
+    let stage = match results.stage {
+        ArtifactStage::Cargo => "cargo output",
+        ArtifactStage::Final => "final artifact",
+    };
+    writeln!(
+        output,
+        "\n   {} ({}) against template '{}'",
+        results.artifact.display(),
+        stage,
+        results.template
+    )?;
+
+    if results.mismatches.is_empty() {
+        writeln!(
+            output,
+            "\n{}",
+            style("✓ The artifact matches the configured build settings").green()
+        )?;
+        return Ok(output);
+    }
+
+    writeln!(
+        output,
+        "\n{}",
+        style(format!(
+            "⚠ Your build didn't apply your config ({} mismatches):",
+            results.mismatches.len()
+        ))
+        .yellow()
+        .bold()
+    )?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    for mismatch in &results.mismatches {
+        writeln!(
+            output,
+            "  {} but {}",
+            style(&mismatch.setting).cyan(),
+            mismatch.finding
+        )?;
+        writeln!(
+            output,
+            "       {} {}",
+            style("→").dim(),
+            style(&mismatch.hint).green()
+        )?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::config_check::{ConfigMismatch, MismatchKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_console_report_lists_mismatches() {
+        let mut results = ConfigCheckResults {
+            artifact: PathBuf::from("pkg/app_bg.wasm"),
+            stage: ArtifactStage::Final,
+            template: "balanced".to_string(),
+            custom_sections: vec!["name".to_string()],
+            mismatches: vec![ConfigMismatch {
+                kind: MismatchKind::NamesNotStripped,
+                setting: "profile.strip = true".to_string(),
+                finding: "`name` section is present".to_string(),
+                hint: "Check the workspace root".to_string(),
+            }],
+        };
+
+        let text = format_console_report(&results).unwrap();
+        assert!(text.contains("pkg/app_bg.wasm (final artifact) against template 'balanced'"));
+        assert!(text.contains("1 mismatches"));
+        assert!(text.contains("but `name` section is present"));
+
+        results.mismatches.clear();
+        let text = format_console_report(&results).unwrap();
+        assert!(text.contains("matches the configured build settings"));
+    }
+}
@@ -0,0 +1,328 @@
+//! Per-crate size attribution
+//!
+//! Maps every sized item of a WASM module to the crate it comes from, using
+//! the demangled symbol path, then matches crates to packages in the
+//! dependency graph cargo resolves for the WASM target. Data segments,
+//! imports and other items without a Rust path are reported as
+//! unattributed.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::CrateSizeAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = CrateSizeAnalyzer::new(".").analyze(Path::new("pkg/app_bg.wasm"))?;
+//! for entry in &report.crates {
+//!     println!("{} {} bytes ({:.1}%)", entry.crate_name, entry.size_bytes, entry.percentage);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use super::symbol_blame::crate_of_symbol;
+use super::twiggy::{AnalysisItem, TwiggyAnalyzer};
+use crate::profiling::{self, ProfilePhase};
+use anyhow::Result;
+use cargo_metadata::{MetadataCommand, TargetKind};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Target the dependency graph is resolved for
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Where a crate comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrateOrigin {
+    /// Workspace member
+    Workspace,
+    /// Dependency in the resolved graph
+    Dependency,
+    /// Shipped with the Rust toolchain (`std`, `core`, `alloc`, ...)
+    Toolchain,
+    /// Not found in the dependency graph
+    Unknown,
+}
+
+/// Package a crate name resolves to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CratePackage {
+    /// Package name in `Cargo.toml` (may use dashes)
+    pub name: String,
+    /// Resolved version
+    pub version: String,
+    /// Whether the package is a workspace member
+    pub workspace_member: bool,
+}
+
+/// Size attributed to one crate
+#[derive(Debug, Clone, Serialize)]
+pub struct CrateSize {
+    /// Crate name as it appears in symbol paths
+    pub crate_name: String,
+    /// Package name, when the crate is in the dependency graph
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// Package version, when the crate is in the dependency graph
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Where the crate comes from
+    pub origin: CrateOrigin,
+    /// Sum of the crate's item sizes
+    pub size_bytes: u64,
+    /// Share of the module size
+    pub percentage: f64,
+    /// Number of items attributed to the crate
+    pub items: usize,
+}
+
+impl SizedEntry for CrateSize {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.percentage)
+    }
+
+    fn name(&self) -> &str {
+        &self.crate_name
+    }
+}
+
+/// Crate-level size breakdown of a module
+#[derive(Debug, Clone, Serialize)]
+pub struct CrateSizeReport {
+    /// Sum of all item sizes
+    pub total_bytes: u64,
+    /// Crates, largest first
+    pub crates: Vec<CrateSize>,
+    /// Size of items without a Rust path (data, imports, custom sections, ...)
+    pub unattributed_bytes: u64,
+    /// Share of the module size that is unattributed
+    pub unattributed_percentage: f64,
+    /// Caveats about how complete the attribution is
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Attributes module size to crates
+pub struct CrateSizeAnalyzer {
+    project_root: PathBuf,
+}
+
+impl CrateSizeAnalyzer {
+    /// Create an analyzer for the project that built the module
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Break a module's size down per crate
+    pub fn analyze(&self, wasm_file: &Path) -> Result<CrateSizeReport> {
+        let items = TwiggyAnalyzer::new(wasm_file).sized_items()?;
+        let mut warnings = Vec::new();
+        let packages = match self.packages() {
+            Ok(packages) => packages,
+            Err(e) => {
+                warnings.push(format!(
+                    "cargo metadata failed ({}); crates were not matched to packages",
+                    e
+                ));
+                HashMap::new()
+            }
+        };
+
+        let mut report = attribute(&items, &packages);
+        report.warnings.extend(warnings);
+        Ok(report)
+    }
+
+    /// Packages in the WASM dependency graph, by the crate name symbols use
+    ///
+    /// Falls back to the workspace members alone when the graph cannot be
+    /// resolved (e.g., offline with a cold registry index).
+    fn packages(&self) -> Result<HashMap<String, CratePackage>> {
+        let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+        let metadata = MetadataCommand::new()
+            .current_dir(&self.project_root)
+            .other_options(vec![
+                "--filter-platform".to_string(),
+                WASM_TARGET.to_string(),
+            ])
+            .exec()
+            .or_else(|_| {
+                MetadataCommand::new()
+                    .current_dir(&self.project_root)
+                    .no_deps()
+                    .exec()
+            })?;
+
+        let members: HashSet<_> = metadata.workspace_members.iter().collect();
+        let lib_kinds = [
+            TargetKind::Lib,
+            TargetKind::RLib,
+            TargetKind::CDyLib,
+            TargetKind::DyLib,
+            TargetKind::StaticLib,
+        ];
+
+        let mut packages = HashMap::new();
+        for package in &metadata.packages {
+            let info = CratePackage {
+                name: package.name.to_string(),
+                version: package.version.to_string(),
+                workspace_member: members.contains(&package.id),
+            };
+            // Symbols use the library target name, which may differ from the package
+            let lib_name = package
+                .targets
+                .iter()
+                .find(|t| lib_kinds.iter().any(|k| t.is_kind(k.clone())))
+                .map(|t| t.name.replace('-', "_"))
+                .unwrap_or_else(|| package.name.replace('-', "_"));
+            packages.insert(lib_name, info);
+        }
+        Ok(packages)
+    }
+}
+
+/// Group items by the crate of their symbol
+///
+/// `packages` maps crate names, as they appear in symbol paths, to packages.
+pub fn attribute(
+    items: &[AnalysisItem],
+    packages: &HashMap<String, CratePackage>,
+) -> CrateSizeReport {
+    let mut by_crate: HashMap<String, (u64, f64, usize)> = HashMap::new();
+    let mut unattributed_bytes = 0;
+    let mut unattributed_percentage = 0.0;
+    for item in items {
+        match crate_of_symbol(&item.name) {
+            Some(crate_name) => {
+                let entry = by_crate.entry(crate_name).or_insert((0, 0.0, 0));
+                entry.0 += item.size_bytes;
+                entry.1 += item.percentage;
+                entry.2 += 1;
+            }
+            None => {
+                unattributed_bytes += item.size_bytes;
+                unattributed_percentage += item.percentage;
+            }
+        }
+    }
+
+    let mut crates: Vec<CrateSize> = by_crate
+        .into_iter()
+        .map(|(crate_name, (size_bytes, percentage, items))| {
+            let package = packages.get(&crate_name);
+            let origin = match package {
+                Some(p) if p.workspace_member => CrateOrigin::Workspace,
+                Some(_) => CrateOrigin::Dependency,
+                None if is_toolchain_crate(&crate_name) => CrateOrigin::Toolchain,
+                None => CrateOrigin::Unknown,
+            };
+            CrateSize {
+                package: package.map(|p| p.name.clone()),
+                version: package.map(|p| p.version.clone()),
+                crate_name,
+                origin,
+                size_bytes,
+                percentage,
+                items,
+            }
+        })
+        .collect();
+    crates.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.crate_name.cmp(&b.crate_name))
+    });
+
+    CrateSizeReport {
+        total_bytes: items.iter().map(|i| i.size_bytes).sum(),
+        crates,
+        unattributed_bytes,
+        unattributed_percentage,
+        warnings: Vec::new(),
+    }
+}
+
+/// Crates shipped with the Rust toolchain
+fn is_toolchain_crate(crate_name: &str) -> bool {
+    matches!(
+        crate_name,
+        "std"
+            | "core"
+            | "alloc"
+            | "compiler_builtins"
+            | "panic_abort"
+            | "panic_unwind"
+            | "dlmalloc"
+            | "hashbrown"
+            | "rustc_demangle"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, size_bytes: u64) -> AnalysisItem {
+        AnalysisItem {
+            size_bytes,
+            percentage: size_bytes as f64 / 10.0,
+            name: name.to_string(),
+        }
+    }
+
+    fn package(name: &str, workspace_member: bool) -> CratePackage {
+        CratePackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            workspace_member,
+        }
+    }
+
+    #[test]
+    fn test_attribute_groups_items_by_owning_crate() {
+        let items = vec![
+            item("serde_json::de::from_str", 300),
+            item("<my_app::Model as serde::ser::Serialize>::serialize", 200),
+            item("core::fmt::write", 150),
+            item("serde_json::ser::to_string", 100),
+            item("mystery::thing", 50),
+            item("data[0]", 40),
+            item("import env::log", 10),
+        ];
+        let packages = HashMap::from([
+            ("serde_json".to_string(), package("serde_json", false)),
+            ("my_app".to_string(), package("my-app", true)),
+        ]);
+
+        let report = attribute(&items, &packages);
+        assert_eq!(report.total_bytes, 850);
+        assert_eq!(report.unattributed_bytes, 50);
+        assert!((report.unattributed_percentage - 5.0).abs() < f64::EPSILON);
+
+        let crates: Vec<(&str, u64, CrateOrigin)> = report
+            .crates
+            .iter()
+            .map(|c| (c.crate_name.as_str(), c.size_bytes, c.origin))
+            .collect();
+        assert_eq!(
+            crates,
+            vec![
+                ("serde_json", 400, CrateOrigin::Dependency),
+                ("my_app", 200, CrateOrigin::Workspace),
+                ("core", 150, CrateOrigin::Toolchain),
+                ("mystery", 50, CrateOrigin::Unknown),
+            ]
+        );
+        assert_eq!(report.crates[0].items, 2);
+        assert_eq!(report.crates[1].package.as_deref(), Some("my-app"));
+    }
+}
@@ -0,0 +1,119 @@
+//! Per-crate size report formatting
+
+use super::crate_sizes::{CrateOrigin, CrateSizeReport};
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of crates listed by default
+const DEFAULT_TOP_CRATES: usize = 25;
+
+/// Format the crate breakdown for console output
+pub fn format_console_report(report: &CrateSizeReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the crate breakdown, limiting the crates listed
+pub fn format_console_report_with_limit(
+    report: &CrateSizeReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("Size by Crate").bold().underlined())?;
+    writeln!(
+        output,
+        "\n   {} in {} crates",
+        style(format_bytes(report.total_bytes)).yellow(),
+        style(report.crates.len()).cyan().bold()
+    )?;
+    for warning in &report.warnings {
+        writeln!(
+            output,
+            "   {} {}",
+            style("⚠").yellow(),
+            style(warning).yellow()
+        )?;
+    }
+
+    writeln!(
+        output,
+        "\n  {:>10}  {:>6}  {:<32} Origin",
+        "Size", "%", "Crate"
+    )?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+
+    let shown = limit.visible(report.crates.len(), Some(DEFAULT_TOP_CRATES));
+    for entry in report.crates.iter().take(shown) {
+        let package = match (&entry.package, &entry.version) {
+            (Some(name), Some(version)) => format!("{} {}", name, version),
+            _ => String::new(),
+        };
+        let origin = match entry.origin {
+            CrateOrigin::Workspace => style("workspace").green(),
+            CrateOrigin::Dependency => style("dependency").cyan(),
+            CrateOrigin::Toolchain => style("toolchain").dim(),
+            CrateOrigin::Unknown => style("unknown").yellow(),
+        };
+        writeln!(
+            output,
+            "  {:>10}  {:>5.1}%  {:<32} {} {}",
+            format_bytes(entry.size_bytes),
+            entry.percentage,
+            style(truncate_str(&entry.crate_name, 32)).bold(),
+            origin,
+            style(package).dim()
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.crates.len(), shown, "crates") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+
+    if report.unattributed_bytes > 0 {
+        writeln!(
+            output,
+            "  {:>10}  {:>5.1}%  {}",
+            format_bytes(report.unattributed_bytes),
+            report.unattributed_percentage,
+            style("(data, imports and other non-Rust items)").dim()
+        )?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::crate_sizes::CrateSize;
+
+    fn entry(crate_name: &str, size_bytes: u64) -> CrateSize {
+        CrateSize {
+            crate_name: crate_name.to_string(),
+            package: Some(crate_name.replace('_', "-")),
+            version: Some("1.0.0".to_string()),
+            origin: CrateOrigin::Dependency,
+            size_bytes,
+            percentage: 10.0,
+            items: 1,
+        }
+    }
+
+    #[test]
+    fn test_format_console_report_lists_crates_and_unattributed() {
+        let report = CrateSizeReport {
+            total_bytes: 4096,
+            crates: vec![entry("serde_json", 2048), entry("regex_syntax", 1024)],
+            unattributed_bytes: 1024,
+            unattributed_percentage: 25.0,
+            warnings: Vec::new(),
+        };
+
+        let text = format_console_report_with_limit(&report, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("4.00 KiB in 2 crates"));
+        assert!(text.contains("serde-json 1.0.0"));
+        assert!(!text.contains("regex_syntax"));
+        assert!(text.contains("1 more crates"));
+        assert!(text.contains("25.0%"));
+    }
+}
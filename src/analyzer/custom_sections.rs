@@ -0,0 +1,431 @@
+//! Custom section analysis
+//!
+//! Lists the custom sections of a WASM module with their sizes. None of the
+//! well-known ones are needed to run the module: `name` maps function
+//! indices to symbols for stack traces, `producers` records the toolchain,
+//! `.debug_*` sections hold DWARF and `target_features` is only read by
+//! later tooling. Each one present becomes a strip recommendation, and
+//! [`CustomSectionAnalyzer::strip`] applies them with `wasm-opt` (or
+//! `wasm-strip` when wasm-opt is not installed).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::CustomSectionAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = CustomSectionAnalyzer::new().analyze(Path::new("pkg/app_bg.wasm"))?;
+//! for recommendation in &report.recommendations {
+//!     println!("{} saves {} bytes", recommendation.description, recommendation.savings_bytes);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use crate::infra::{CommandExecutor, RealCommandExecutor};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+use wasmparser::{Parser, Payload};
+
+/// Kind of custom section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CustomSectionKind {
+    /// `name`: function, local and global names
+    Names,
+    /// `producers`: language and tool versions
+    Producers,
+    /// `.debug_*`: DWARF debug info
+    Debug,
+    /// `target_features`: features the module was compiled with
+    TargetFeatures,
+    /// Any other custom section (kept by `--fix`)
+    Other,
+}
+
+impl CustomSectionKind {
+    /// Kind of a custom section, by name
+    pub fn of(name: &str) -> Self {
+        match name {
+            "name" => CustomSectionKind::Names,
+            "producers" => CustomSectionKind::Producers,
+            "target_features" => CustomSectionKind::TargetFeatures,
+            name if name.starts_with(".debug_") => CustomSectionKind::Debug,
+            _ => CustomSectionKind::Other,
+        }
+    }
+
+    /// wasm-opt flag that strips this kind, if it can be stripped
+    pub fn wasm_opt_flag(self) -> Option<&'static str> {
+        match self {
+            // `--strip-debug` removes the name section along with DWARF
+            CustomSectionKind::Names => Some("--strip-debug"),
+            CustomSectionKind::Producers => Some("--strip-producers"),
+            CustomSectionKind::Debug => Some("--strip-dwarf"),
+            CustomSectionKind::TargetFeatures => Some("--strip-target-features"),
+            CustomSectionKind::Other => None,
+        }
+    }
+
+    /// What stripping this kind costs
+    fn tradeoff(self) -> &'static str {
+        match self {
+            CustomSectionKind::Names => {
+                "browser stack traces show function indices instead of names"
+            }
+            CustomSectionKind::Producers => {
+                "toolchain versions are no longer recorded in the binary"
+            }
+            CustomSectionKind::Debug => "DevTools can no longer map code to Rust source lines",
+            CustomSectionKind::TargetFeatures => {
+                "later tools can no longer read the enabled features"
+            }
+            CustomSectionKind::Other => "",
+        }
+    }
+}
+
+/// One custom section
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomSection {
+    /// Section name
+    pub name: String,
+    /// Kind of section
+    pub kind: CustomSectionKind,
+    /// Size including the section header
+    pub size_bytes: u64,
+    /// Share of the module size
+    pub percentage: f64,
+}
+
+impl SizedEntry for CustomSection {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.percentage)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Recommendation to strip one kind of custom section
+#[derive(Debug, Clone, Serialize)]
+pub struct StripRecommendation {
+    /// Kind of section to strip
+    pub kind: CustomSectionKind,
+    /// Bytes saved
+    pub savings_bytes: u64,
+    /// What to strip and the flag that strips it
+    pub description: String,
+    /// What is lost by stripping
+    pub tradeoff: String,
+}
+
+/// Custom sections of a module
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomSectionReport {
+    /// Module size
+    pub module_bytes: u64,
+    /// Sum of the custom section sizes
+    pub custom_bytes: u64,
+    /// Sections, largest first
+    pub sections: Vec<CustomSection>,
+    /// Strip recommendations, largest saving first
+    pub recommendations: Vec<StripRecommendation>,
+}
+
+impl CustomSectionReport {
+    /// Total saving of all recommendations
+    pub fn strippable_bytes(&self) -> u64 {
+        self.recommendations.iter().map(|r| r.savings_bytes).sum()
+    }
+}
+
+/// How `strip` removed the sections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StripOutcome {
+    /// Tool that was (or would be) run
+    pub tool: &'static str,
+    /// Arguments passed to it
+    pub args: Vec<String>,
+    /// Module size before stripping
+    pub before_bytes: u64,
+    /// Module size after stripping (`None` in dry-run mode)
+    pub after_bytes: Option<u64>,
+}
+
+/// Lists custom sections and strips the removable ones
+pub struct CustomSectionAnalyzer<CE: CommandExecutor = RealCommandExecutor> {
+    cmd_executor: CE,
+}
+
+impl CustomSectionAnalyzer {
+    /// Create an analyzer that runs the real strip tools
+    pub fn new() -> Self {
+        Self::with_executor(RealCommandExecutor)
+    }
+}
+
+impl Default for CustomSectionAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CE: CommandExecutor> CustomSectionAnalyzer<CE> {
+    /// Create an analyzer with a custom command executor
+    pub fn with_executor(cmd_executor: CE) -> Self {
+        Self { cmd_executor }
+    }
+
+    /// List the custom sections of a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<CustomSectionReport> {
+        let module = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        analyze_module(&module).with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+
+    /// Strip the recommended sections from `wasm_file` in place
+    ///
+    /// Uses `wasm-opt` with one flag per recommendation. Without wasm-opt,
+    /// falls back to `wasm-strip`, which removes every custom section and is
+    /// therefore only used when all of them are recommended for stripping
+    /// (callers may drop recommendations to keep those sections).
+    pub fn strip(
+        &self,
+        wasm_file: &Path,
+        report: &CustomSectionReport,
+        dry_run: bool,
+    ) -> Result<StripOutcome> {
+        let mut args = vec![wasm_file.display().to_string()];
+        let mut flags: Vec<&str> = report
+            .recommendations
+            .iter()
+            .filter_map(|r| r.kind.wasm_opt_flag())
+            .collect();
+        flags.sort_unstable();
+        flags.dedup();
+        args.extend(flags.iter().map(|f| f.to_string()));
+        // Parse any feature the module uses; nothing is optimized
+        args.extend(["--all-features", "-o"].map(String::from));
+        args.push(wasm_file.display().to_string());
+
+        let mut outcome = StripOutcome {
+            tool: "wasm-opt",
+            args,
+            before_bytes: report.module_bytes,
+            after_bytes: None,
+        };
+        if dry_run || report.recommendations.is_empty() {
+            return Ok(outcome);
+        }
+
+        let output = match self
+            .cmd_executor
+            .execute(|cmd| cmd.args(&outcome.args), "wasm-opt")
+        {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let recommended = |kind| report.recommendations.iter().any(|r| r.kind == kind);
+                if report.sections.iter().any(|s| !recommended(s.kind)) {
+                    anyhow::bail!(
+                        "wasm-opt is not installed, and wasm-strip would also remove sections that are not recommended for stripping; install binaryen"
+                    );
+                }
+                outcome.tool = "wasm-strip";
+                outcome.args = vec![wasm_file.display().to_string()];
+                self.cmd_executor
+                    .execute(|cmd| cmd.args(&outcome.args), "wasm-strip")
+                    .context("Neither wasm-opt nor wasm-strip is installed")?
+            }
+            result => result.context("Failed to run wasm-opt")?,
+        };
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} failed: {}",
+                outcome.tool,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        outcome.after_bytes = Some(
+            std::fs::metadata(wasm_file)
+                .with_context(|| format!("Failed to read {}", wasm_file.display()))?
+                .len(),
+        );
+        Ok(outcome)
+    }
+}
+
+/// List the custom sections of a module and what stripping them saves
+pub fn analyze_module(module: &[u8]) -> Result<CustomSectionReport> {
+    let module_bytes = module.len() as u64;
+    let percentage = |bytes: u64| {
+        if module_bytes == 0 {
+            0.0
+        } else {
+            bytes as f64 / module_bytes as f64 * 100.0
+        }
+    };
+
+    let mut sections = Vec::new();
+    for payload in Parser::new(0).parse_all(module) {
+        if let Payload::CustomSection(reader) = payload? {
+            // The payload range excludes the section id and size prefix
+            let size_bytes = (reader.range().len() + 1 + leb128_len(reader.range().len())) as u64;
+            sections.push(CustomSection {
+                name: reader.name().to_string(),
+                kind: CustomSectionKind::of(reader.name()),
+                size_bytes,
+                percentage: percentage(size_bytes),
+            });
+        }
+    }
+    sections.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut by_kind: Vec<(CustomSectionKind, u64)> = Vec::new();
+    for section in sections
+        .iter()
+        .filter(|s| s.kind != CustomSectionKind::Other)
+    {
+        match by_kind.iter_mut().find(|(kind, _)| *kind == section.kind) {
+            Some((_, bytes)) => *bytes += section.size_bytes,
+            None => by_kind.push((section.kind, section.size_bytes)),
+        }
+    }
+    let mut recommendations: Vec<StripRecommendation> = by_kind
+        .into_iter()
+        .map(|(kind, savings_bytes)| {
+            let what = match kind {
+                CustomSectionKind::Names => "the name section",
+                CustomSectionKind::Producers => "the producers section",
+                CustomSectionKind::Debug => "DWARF debug sections",
+                CustomSectionKind::TargetFeatures => "the target_features section",
+                CustomSectionKind::Other => unreachable!("other sections are never stripped"),
+            };
+            StripRecommendation {
+                kind,
+                savings_bytes,
+                description: format!(
+                    "Strip {} ({})",
+                    what,
+                    kind.wasm_opt_flag().unwrap_or_default()
+                ),
+                tradeoff: kind.tradeoff().to_string(),
+            }
+        })
+        .collect();
+    recommendations.sort_by(|a, b| {
+        b.savings_bytes
+            .cmp(&a.savings_bytes)
+            .then_with(|| a.kind.cmp(&b.kind))
+    });
+
+    Ok(CustomSectionReport {
+        module_bytes,
+        custom_bytes: sections.iter().map(|s| s.size_bytes).sum(),
+        sections,
+        recommendations,
+    })
+}
+
+/// Encoded length of a LEB128 `u32`
+fn leb128_len(value: usize) -> usize {
+    let mut len = 1;
+    let mut value = value >> 7;
+    while value > 0 {
+        len += 1;
+        value >>= 7;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{custom_section, HEADER};
+    use std::process::{Command, ExitStatus, Output};
+
+    fn module() -> Vec<u8> {
+        let mut module = HEADER.to_vec();
+        module.extend(custom_section("name", &[0; 40]));
+        module.extend(custom_section(".debug_info", &[0; 60]));
+        module.extend(custom_section(".debug_line", &[0; 20]));
+        module.extend(custom_section("producers", &[0; 10]));
+        module.extend(custom_section("my_metadata", &[0; 5]));
+        module
+    }
+
+    #[test]
+    fn test_analyze_groups_recommendations_by_kind() {
+        let report = analyze_module(&module()).unwrap();
+
+        assert_eq!(report.sections.len(), 5);
+        assert_eq!(report.sections[0].name, ".debug_info");
+        assert_eq!(report.sections[0].size_bytes, 2 + 12 + 60);
+        let recommended: Vec<(CustomSectionKind, u64)> = report
+            .recommendations
+            .iter()
+            .map(|r| (r.kind, r.savings_bytes))
+            .collect();
+        assert_eq!(
+            recommended,
+            vec![
+                (CustomSectionKind::Debug, (2 + 12 + 60) + (2 + 12 + 20)),
+                (CustomSectionKind::Names, 2 + 5 + 40),
+                (CustomSectionKind::Producers, 2 + 10 + 10),
+            ]
+        );
+        assert_eq!(
+            report.custom_bytes,
+            report.strippable_bytes() + (2 + 12 + 5)
+        );
+    }
+
+    /// Fails every command as if the tool were not installed
+    struct NoTools;
+
+    impl CommandExecutor for NoTools {
+        fn status(&self, _cmd: &mut Command) -> std::io::Result<ExitStatus> {
+            Err(std::io::ErrorKind::NotFound.into())
+        }
+
+        fn output(&self, _cmd: &mut Command) -> std::io::Result<Output> {
+            Err(std::io::ErrorKind::NotFound.into())
+        }
+    }
+
+    #[test]
+    fn test_strip_builds_wasm_opt_flags_and_keeps_unknown_sections() {
+        let report = analyze_module(&module()).unwrap();
+        let analyzer = CustomSectionAnalyzer::with_executor(NoTools);
+        let path = Path::new("app_bg.wasm");
+
+        let outcome = analyzer.strip(path, &report, true).unwrap();
+        assert_eq!(
+            outcome.args,
+            vec![
+                "app_bg.wasm",
+                "--strip-debug",
+                "--strip-dwarf",
+                "--strip-producers",
+                "--all-features",
+                "-o",
+                "app_bg.wasm"
+            ]
+        );
+        assert_eq!(outcome.after_bytes, None);
+
+        // wasm-strip would drop `my_metadata` too, so it is not used
+        let error = analyzer.strip(path, &report, false).unwrap_err();
+        assert!(error.to_string().contains("install binaryen"));
+    }
+}
@@ -0,0 +1,120 @@
+//! Custom section report formatting
+
+use super::custom_sections::{CustomSectionKind, CustomSectionReport};
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of sections listed by default
+const DEFAULT_TOP_SECTIONS: usize = 20;
+
+/// Format the custom section report for console output
+pub fn format_console_report(report: &CustomSectionReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the custom section report, limiting the sections listed
+pub fn format_console_report_with_limit(
+    report: &CustomSectionReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("Custom Sections").bold().underlined())?;
+    if report.sections.is_empty() {
+        writeln!(
+            output,
+            "\n   {} No custom sections; nothing to strip",
+            style("✓").green()
+        )?;
+        return Ok(output);
+    }
+    writeln!(
+        output,
+        "\n   {} in {} sections ({:.1}% of {})",
+        style(format_bytes(report.custom_bytes)).yellow(),
+        style(report.sections.len()).cyan().bold(),
+        if report.module_bytes == 0 {
+            0.0
+        } else {
+            report.custom_bytes as f64 / report.module_bytes as f64 * 100.0
+        },
+        format_bytes(report.module_bytes)
+    )?;
+
+    writeln!(
+        output,
+        "\n  {:>10}  {:>6}  {:<32} Kind",
+        "Size", "%", "Section"
+    )?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    let shown = limit.visible(report.sections.len(), Some(DEFAULT_TOP_SECTIONS));
+    for section in report.sections.iter().take(shown) {
+        let kind = match section.kind {
+            CustomSectionKind::Names => style("names"),
+            CustomSectionKind::Producers => style("producers"),
+            CustomSectionKind::Debug => style("debug"),
+            CustomSectionKind::TargetFeatures => style("target-features"),
+            CustomSectionKind::Other => style("other").dim(),
+        };
+        writeln!(
+            output,
+            "  {:>10}  {:>5.1}%  {:<32} {}",
+            format_bytes(section.size_bytes),
+            section.percentage,
+            style(truncate_str(&section.name, 32)).bold(),
+            kind
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.sections.len(), shown, "sections") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+
+    if !report.recommendations.is_empty() {
+        writeln!(output, "\n{}", style("Recommendations").bold())?;
+        for recommendation in &report.recommendations {
+            writeln!(
+                output,
+                "  {} {} saves {}",
+                style("→").cyan(),
+                recommendation.description,
+                style(format_bytes(recommendation.savings_bytes))
+                    .green()
+                    .bold()
+            )?;
+            writeln!(
+                output,
+                "    {}",
+                style(format!("Trade-off: {}", recommendation.tradeoff)).dim()
+            )?;
+        }
+        writeln!(
+            output,
+            "\n  {} Run with --fix to strip {} in place",
+            style("💡").dim(),
+            format_bytes(report.strippable_bytes())
+        )?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::custom_sections::analyze_module;
+
+    #[test]
+    fn test_format_console_report_lists_sections_and_savings() {
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        module.extend([0, 8, 4, b'n', b'a', b'm', b'e', 1, 2, 3]);
+        module.extend([0, 4, 3, b'a', b'b', b'c']);
+        let report = analyze_module(&module).unwrap();
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("16 B in 2 sections"));
+        assert!(text.contains("Strip the name section (--strip-debug) saves 10 B"));
+        assert!(text.contains("Trade-off: browser stack traces"));
+        assert!(text.contains("Run with --fix to strip 10 B in place"));
+    }
+}
@@ -0,0 +1,584 @@
+//! Data segment content inspection
+//!
+//! Size tools report `data[0]` as one opaque item, although rustc merges
+//! every string literal, lookup table and `include_bytes!` file into it.
+//! This pass decodes the data segments and samples what they hold: embedded
+//! files recognized by their magic bytes (fonts, images, compressed blobs),
+//! JSON documents, SVG markup and runs of UTF-8 text. Files the
+//! [`AssetDetector`](super::AssetDetector) finds in `include_bytes!` and
+//! `include_str!` calls are matched against the segment contents byte for
+//! byte to name the source of each blob.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::DataSegmentAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = DataSegmentAnalyzer::new(".").analyze(Path::new("pkg/app_bg.wasm"))?;
+//! for segment in &report.segments {
+//!     println!("data[{}] {} bytes, {} findings", segment.index, segment.size_bytes, segment.findings.len());
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::assets::AssetDetector;
+use super::report_utils::SizedEntry;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use wasmparser::{DataKind, Operator, Parser, Payload};
+
+/// Shortest JSON document reported
+const MIN_JSON_BYTES: usize = 32;
+
+/// Shortest run of text counted as a string
+const MIN_STRING_BYTES: usize = 16;
+
+/// Longest strings sampled per segment
+const STRING_SAMPLES: usize = 5;
+
+/// Characters kept from each sampled string
+const SAMPLE_CHARS: usize = 80;
+
+/// Bytes of an asset searched for before comparing the whole file
+const ASSET_PREFIX_BYTES: usize = 64;
+
+/// What an embedded blob was recognized as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataContentKind {
+    /// JSON document
+    Json,
+    /// SVG markup
+    Svg,
+    /// PNG image
+    Png,
+    /// JPEG image
+    Jpeg,
+    /// GIF image
+    Gif,
+    /// WebP image
+    Webp,
+    /// WOFF font
+    Woff,
+    /// WOFF2 font
+    Woff2,
+    /// TrueType or OpenType font
+    Font,
+    /// gzip stream
+    Gzip,
+    /// zstd frame
+    Zstd,
+    /// File matched by content only
+    Asset,
+}
+
+impl DataContentKind {
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            DataContentKind::Json => "JSON",
+            DataContentKind::Svg => "SVG",
+            DataContentKind::Png => "PNG image",
+            DataContentKind::Jpeg => "JPEG image",
+            DataContentKind::Gif => "GIF image",
+            DataContentKind::Webp => "WebP image",
+            DataContentKind::Woff => "WOFF font",
+            DataContentKind::Woff2 => "WOFF2 font",
+            DataContentKind::Font => "TrueType/OpenType font",
+            DataContentKind::Gzip => "gzip stream",
+            DataContentKind::Zstd => "zstd frame",
+            DataContentKind::Asset => "embedded file",
+        }
+    }
+}
+
+/// A blob found inside a data segment
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DataFinding {
+    /// What the blob is
+    pub kind: DataContentKind,
+    /// Offset within the segment
+    pub offset: u64,
+    /// Size, when the format records it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Embedded file the blob matches, with the `include_*!` location
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Content breakdown of one data segment
+#[derive(Debug, Clone, Serialize)]
+pub struct DataSegmentContent {
+    /// Segment index (`data[N]`)
+    pub index: usize,
+    /// Linear-memory address of an active segment with a constant offset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_offset: Option<u64>,
+    /// Segment size
+    pub size_bytes: u64,
+    /// Share of the module size
+    pub percentage: f64,
+    /// Bytes in runs of UTF-8 text
+    pub text_bytes: u64,
+    /// Zero bytes
+    pub zero_bytes: u64,
+    /// Recognized blobs, by offset
+    pub findings: Vec<DataFinding>,
+    /// Longest strings, truncated
+    pub strings: Vec<String>,
+}
+
+impl SizedEntry for DataSegmentContent {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.percentage)
+    }
+
+    fn name(&self) -> &str {
+        self.strings.first().map_or("", String::as_str)
+    }
+
+    fn matches(&self, pattern: &Regex) -> bool {
+        self.strings.iter().any(|s| pattern.is_match(s))
+    }
+}
+
+/// Contents of a module's data segments
+#[derive(Debug, Clone, Serialize)]
+pub struct DataReport {
+    /// Module size
+    pub module_bytes: u64,
+    /// Sum of the segment sizes
+    pub data_bytes: u64,
+    /// Segments, largest first
+    pub segments: Vec<DataSegmentContent>,
+    /// Caveats about the source matching
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// A file embedded with `include_bytes!` or `include_str!`
+pub struct KnownAsset {
+    /// Path and `include_*!` location shown for matches
+    pub label: String,
+    /// File contents
+    pub bytes: Vec<u8>,
+}
+
+/// Inspects what a module's data segments contain
+pub struct DataSegmentAnalyzer {
+    project_root: PathBuf,
+}
+
+impl DataSegmentAnalyzer {
+    /// Create an analyzer; embedded files are looked up in `project_root`
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Inspect the data segments of a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<DataReport> {
+        let module = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+
+        let mut warnings = Vec::new();
+        let assets = match self.known_assets() {
+            Ok(assets) => assets,
+            Err(e) => {
+                warnings.push(format!(
+                    "Asset scan failed ({}); blobs were not matched to source files",
+                    e
+                ));
+                Vec::new()
+            }
+        };
+
+        let mut report = inspect_module(&module, &assets)
+            .with_context(|| format!("Failed to parse {}", wasm_file.display()))?;
+        report.warnings.extend(warnings);
+        Ok(report)
+    }
+
+    /// Files the asset detector finds embedded in the project's sources
+    fn known_assets(&self) -> Result<Vec<KnownAsset>> {
+        let scan = AssetDetector::new(&self.project_root).scan_project()?;
+        let mut assets = Vec::new();
+        for asset in scan.assets {
+            // `source_location` is `<source file>:<line>`; the asset path is
+            // relative to the source file's directory
+            let Some((source_file, _)) = asset.source_location.rsplit_once(':') else {
+                continue;
+            };
+            let path = Path::new(source_file)
+                .parent()
+                .unwrap_or(Path::new(""))
+                .join(&asset.file_path);
+            if let Ok(bytes) = std::fs::read(&path) {
+                assets.push(KnownAsset {
+                    label: format!("{} ({})", asset.file_path, asset.source_location),
+                    bytes,
+                });
+            }
+        }
+        Ok(assets)
+    }
+}
+
+/// Inspect the data segments of a module, matching blobs against `assets`
+pub fn inspect_module(module: &[u8], assets: &[KnownAsset]) -> Result<DataReport> {
+    let mut segments = Vec::new();
+    for payload in Parser::new(0).parse_all(module) {
+        if let Payload::DataSection(reader) = payload? {
+            for (index, data) in reader.into_iter().enumerate() {
+                let data = data?;
+                let memory_offset = match data.kind {
+                    DataKind::Active { offset_expr, .. } => {
+                        match offset_expr.get_operators_reader().read()? {
+                            Operator::I32Const { value } => Some(u64::from(value as u32)),
+                            Operator::I64Const { value } => Some(value as u64),
+                            _ => None,
+                        }
+                    }
+                    DataKind::Passive => None,
+                };
+                segments.push(inspect_segment(index, memory_offset, data.data, assets));
+            }
+        }
+    }
+
+    let module_bytes = module.len() as u64;
+    for segment in &mut segments {
+        segment.percentage = if module_bytes == 0 {
+            0.0
+        } else {
+            segment.size_bytes as f64 / module_bytes as f64 * 100.0
+        };
+    }
+    segments.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.index.cmp(&b.index))
+    });
+
+    Ok(DataReport {
+        module_bytes,
+        data_bytes: segments.iter().map(|s| s.size_bytes).sum(),
+        segments,
+        warnings: Vec::new(),
+    })
+}
+
+fn inspect_segment(
+    index: usize,
+    memory_offset: Option<u64>,
+    data: &[u8],
+    assets: &[KnownAsset],
+) -> DataSegmentContent {
+    let mut findings = sniff_blobs(data);
+
+    for asset in assets.iter().filter(|a| !a.bytes.is_empty()) {
+        let Some(offset) = find_bytes(data, &asset.bytes) else {
+            continue;
+        };
+        let end = offset + asset.bytes.len();
+        let existing = findings.iter_mut().find(|f| {
+            let start = f.offset as usize;
+            start >= offset && start < end
+        });
+        match existing {
+            Some(finding) => {
+                finding.source = Some(asset.label.clone());
+                finding.size_bytes.get_or_insert(asset.bytes.len() as u64);
+            }
+            None => findings.push(DataFinding {
+                kind: DataContentKind::Asset,
+                offset: offset as u64,
+                size_bytes: Some(asset.bytes.len() as u64),
+                source: Some(asset.label.clone()),
+            }),
+        }
+    }
+    findings.sort_by_key(|f| f.offset);
+
+    let mut strings = text_runs(data, MIN_STRING_BYTES);
+    let text_bytes = strings.iter().map(|s| s.len() as u64).sum();
+    strings.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    strings.truncate(STRING_SAMPLES);
+
+    DataSegmentContent {
+        index,
+        memory_offset,
+        size_bytes: data.len() as u64,
+        percentage: 0.0,
+        text_bytes,
+        zero_bytes: data.iter().filter(|&&b| b == 0).count() as u64,
+        findings,
+        strings: strings
+            .into_iter()
+            .map(|s| {
+                if s.chars().count() > SAMPLE_CHARS {
+                    format!("{}…", s.chars().take(SAMPLE_CHARS).collect::<String>())
+                } else {
+                    s.to_string()
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Scan for embedded files by magic bytes, and for JSON and SVG documents
+///
+/// Scanning resumes after blobs of known size, so their contents are not
+/// sniffed again.
+fn sniff_blobs(data: &[u8]) -> Vec<DataFinding> {
+    let mut findings = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let rest = &data[pos..];
+        let Some((kind, size)) = sniff_at(rest) else {
+            pos += 1;
+            continue;
+        };
+        findings.push(DataFinding {
+            kind,
+            offset: pos as u64,
+            size_bytes: size.map(|s| s as u64),
+            source: None,
+        });
+        pos += size.unwrap_or(1).max(1);
+    }
+    findings
+}
+
+/// Recognize a blob starting at the beginning of `bytes`
+fn sniff_at(bytes: &[u8]) -> Option<(DataContentKind, Option<usize>)> {
+    let u32_be = |at: usize| -> Option<usize> {
+        Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?) as usize)
+    };
+    let within = |size: usize| (size <= bytes.len()).then_some(size);
+
+    match bytes {
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', ..] => {
+            Some((DataContentKind::Png, png_size(bytes)))
+        }
+        [0xff, 0xd8, 0xff, ..] => {
+            let end = find_bytes(&bytes[3..], &[0xff, 0xd9]).map(|end| end + 5);
+            Some((DataContentKind::Jpeg, end))
+        }
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some((DataContentKind::Gif, None)),
+        [b'R', b'I', b'F', b'F', a, b, c, d, b'W', b'E', b'B', b'P', ..] => {
+            let size = u32::from_le_bytes([*a, *b, *c, *d]) as usize + 8;
+            Some((DataContentKind::Webp, within(size)))
+        }
+        [b'w', b'O', b'F', b'F', ..] => Some((DataContentKind::Woff, u32_be(8).and_then(within))),
+        [b'w', b'O', b'F', b'2', ..] => Some((DataContentKind::Woff2, u32_be(8).and_then(within))),
+        [0, 1, 0, 0, ..] | [b'O', b'T', b'T', b'O', ..] | [b't', b'r', b'u', b'e', ..] => {
+            sfnt_size(bytes).map(|size| (DataContentKind::Font, Some(size)))
+        }
+        [0x1f, 0x8b, 0x08, ..] => Some((DataContentKind::Gzip, None)),
+        [0x28, 0xb5, 0x2f, 0xfd, ..] => Some((DataContentKind::Zstd, None)),
+        [b'<', b's', b'v', b'g', b' ' | b'>' | b'\n', ..] => {
+            let end = find_bytes(bytes, b"</svg>").map(|end| end + b"</svg>".len());
+            Some((DataContentKind::Svg, end))
+        }
+        [b'{', b'"', ..] | [b'[', b'{' | b'"', ..] => json_size(bytes)
+            .filter(|&size| size >= MIN_JSON_BYTES)
+            .map(|size| (DataContentKind::Json, Some(size))),
+        _ => None,
+    }
+}
+
+/// Length of a PNG, walking its chunks up to `IEND`
+fn png_size(bytes: &[u8]) -> Option<usize> {
+    let mut pos = 8;
+    loop {
+        let length = u32::from_be_bytes(bytes.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        let kind = bytes.get(pos + 4..pos + 8)?;
+        pos = pos.checked_add(12)?.checked_add(length)?;
+        if pos > bytes.len() {
+            return None;
+        }
+        if kind == b"IEND" {
+            return Some(pos);
+        }
+    }
+}
+
+/// Length of a TrueType/OpenType font, from the end of its furthest table
+///
+/// Returns `None` unless the table directory looks valid, since the
+/// TrueType version tag `00 01 00 00` also occurs in ordinary data.
+fn sfnt_size(bytes: &[u8]) -> Option<usize> {
+    let num_tables = u16::from_be_bytes(bytes.get(4..6)?.try_into().ok()?) as usize;
+    if !(1..=64).contains(&num_tables) {
+        return None;
+    }
+    let mut end = 12 + num_tables * 16;
+    for table in 0..num_tables {
+        let record = bytes.get(12 + table * 16..12 + (table + 1) * 16)?;
+        if !record[..4]
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || *b == b' ')
+        {
+            return None;
+        }
+        let offset = u32::from_be_bytes(record[8..12].try_into().ok()?) as usize;
+        let length = u32::from_be_bytes(record[12..16].try_into().ok()?) as usize;
+        end = end.max(offset.checked_add(length)?);
+    }
+    (end <= bytes.len()).then_some(end)
+}
+
+/// Length of the JSON value at the start of `bytes`, if it parses
+fn json_size(bytes: &[u8]) -> Option<usize> {
+    let mut values =
+        serde_json::Deserializer::from_slice(bytes).into_iter::<serde::de::IgnoredAny>();
+    match values.next() {
+        Some(Ok(_)) => Some(values.byte_offset()),
+        _ => None,
+    }
+}
+
+/// Runs of UTF-8 text at least `min_bytes` long
+pub(super) fn text_runs(data: &[u8], min_bytes: usize) -> Vec<&str> {
+    let mut runs = Vec::new();
+    let is_break = |b: &u8| (*b < 0x20 && !matches!(*b, b'\t' | b'\n' | b'\r')) || *b == 0x7f;
+    for piece in data.split(is_break) {
+        let mut rest = piece;
+        while !rest.is_empty() {
+            let (text, skip) = match std::str::from_utf8(rest) {
+                Ok(text) => (text, rest.len()),
+                Err(e) => {
+                    let valid = e.valid_up_to();
+                    // Everything before `valid_up_to` is valid UTF-8
+                    let text = std::str::from_utf8(&rest[..valid]).unwrap_or_default();
+                    (text, valid + e.error_len().unwrap_or(rest.len() - valid))
+                }
+            };
+            if text.len() >= min_bytes {
+                runs.push(text);
+            }
+            rest = &rest[skip.min(rest.len())..];
+        }
+    }
+    runs
+}
+
+/// Offset of the first occurrence of `needle` in `haystack`
+///
+/// Long needles are located by their prefix first, then compared in full.
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return None;
+    }
+    let prefix = &needle[..needle.len().min(ASSET_PREFIX_BYTES)];
+    let last_start = haystack.len() - needle.len();
+    (0..=last_start).find(|&start| {
+        haystack[start..].starts_with(prefix) && haystack[start..].starts_with(needle)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{leb, section, HEADER};
+
+    /// Module with one active data segment at address 1024
+    fn module(data: &[u8]) -> Vec<u8> {
+        let mut module = HEADER.to_vec();
+        module.extend([5, 3, 1, 0, 1]);
+        let mut segments = vec![1, 0, 0x41, 0x80, 0x08, 0x0b];
+        segments.extend(leb(data.len()));
+        segments.extend_from_slice(data);
+        module.extend(section(11, &segments));
+        module
+    }
+
+    fn png() -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend([0, 0, 0, 2, b'I', b'H', b'D', b'R', 7, 7, 0, 0, 0, 0]);
+        png.extend([0, 0, 0, 0, b'I', b'E', b'N', b'D', 0, 0, 0, 0]);
+        png
+    }
+
+    #[test]
+    fn test_inspect_finds_blobs_strings_and_asset_sources() {
+        let json = br#"{"locale":"en","messages":{"hello":"Hello, world"}}"#;
+        let mut data = vec![0; 8];
+        data.extend_from_slice(b"called `Option::unwrap()` on a `None` value");
+        data.push(0);
+        data.extend_from_slice(json);
+        data.extend([0xff, 0x00]);
+        data.extend(png());
+        data.extend([0; 4]);
+
+        let assets = vec![KnownAsset {
+            label: "logo.png (src/lib.rs:3)".to_string(),
+            bytes: png(),
+        }];
+        let report = inspect_module(&module(&data), &assets).unwrap();
+
+        assert_eq!(report.data_bytes, data.len() as u64);
+        let segment = &report.segments[0];
+        assert_eq!(segment.memory_offset, Some(1024));
+        let findings: Vec<(DataContentKind, u64, Option<u64>)> = segment
+            .findings
+            .iter()
+            .map(|f| (f.kind, f.offset, f.size_bytes))
+            .collect();
+        let json_offset = 8 + 43 + 1;
+        assert_eq!(
+            findings,
+            vec![
+                (DataContentKind::Json, json_offset, Some(json.len() as u64)),
+                (
+                    DataContentKind::Png,
+                    json_offset + json.len() as u64 + 2,
+                    Some(png().len() as u64)
+                ),
+            ]
+        );
+        assert_eq!(
+            segment.findings[1].source.as_deref(),
+            Some("logo.png (src/lib.rs:3)")
+        );
+        assert_eq!(
+            segment.strings[0],
+            json.iter().map(|&b| b as char).collect::<String>()
+        );
+        assert!(segment
+            .strings
+            .contains(&"called `Option::unwrap()` on a `None` value".to_string()));
+    }
+
+    #[test]
+    fn test_unrecognized_asset_is_reported_by_content() {
+        let asset = b"\x01\x02\x03 binary lookup table \x04\x05".to_vec();
+        let mut data = vec![9; 10];
+        data.extend_from_slice(&asset);
+
+        let assets = vec![KnownAsset {
+            label: "table.bin".to_string(),
+            bytes: asset.clone(),
+        }];
+        let report = inspect_module(&module(&data), &assets).unwrap();
+        assert_eq!(
+            report.segments[0].findings,
+            vec![DataFinding {
+                kind: DataContentKind::Asset,
+                offset: 10,
+                size_bytes: Some(asset.len() as u64),
+                source: Some("table.bin".to_string()),
+            }]
+        );
+    }
+}
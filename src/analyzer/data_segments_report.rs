@@ -0,0 +1,158 @@
+//! Data segment content report formatting
+
+use super::data_segments::DataReport;
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of segments listed by default
+const DEFAULT_TOP_SEGMENTS: usize = 10;
+
+/// Findings listed per segment by default
+const DEFAULT_FINDINGS_PER_SEGMENT: usize = 10;
+
+/// Format the data segment report for console output
+pub fn format_console_report(report: &DataReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the data segment report, limiting the segments and findings listed
+pub fn format_console_report_with_limit(
+    report: &DataReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Data Segment Contents").bold().underlined()
+    )?;
+    writeln!(
+        output,
+        "\n   {} in {} segments ({:.1}% of the module)",
+        style(format_bytes(report.data_bytes)).yellow(),
+        style(report.segments.len()).cyan().bold(),
+        if report.module_bytes == 0 {
+            0.0
+        } else {
+            report.data_bytes as f64 / report.module_bytes as f64 * 100.0
+        }
+    )?;
+    for warning in &report.warnings {
+        writeln!(
+            output,
+            "   {} {}",
+            style("⚠").yellow(),
+            style(warning).yellow()
+        )?;
+    }
+
+    let shown = limit.visible(report.segments.len(), Some(DEFAULT_TOP_SEGMENTS));
+    for segment in report.segments.iter().take(shown) {
+        let address = segment
+            .memory_offset
+            .map(|offset| format!(" @ {:#x}", offset))
+            .unwrap_or_default();
+        writeln!(
+            output,
+            "\n  {}{}  {} ({:.1}%)",
+            style(format!("data[{}]", segment.index)).cyan().bold(),
+            style(address).dim(),
+            format_bytes(segment.size_bytes),
+            segment.percentage
+        )?;
+        writeln!(
+            output,
+            "    {}",
+            style(format!(
+                "text {} · zeros {}",
+                format_bytes(segment.text_bytes),
+                format_bytes(segment.zero_bytes)
+            ))
+            .dim()
+        )?;
+
+        let findings = limit.visible(segment.findings.len(), Some(DEFAULT_FINDINGS_PER_SEGMENT));
+        for finding in segment.findings.iter().take(findings) {
+            let size = finding
+                .size_bytes
+                .map_or_else(|| "size unknown".to_string(), format_bytes);
+            let source = finding
+                .source
+                .as_deref()
+                .map(|source| format!(" ← {}", source))
+                .unwrap_or_default();
+            writeln!(
+                output,
+                "    {:>12}  {:<24} {}{}",
+                format!("+{:#x}", finding.offset),
+                style(finding.kind.label()).bold(),
+                size,
+                style(source).green()
+            )?;
+        }
+        if let Some(footer) = omitted_footer(segment.findings.len(), findings, "findings") {
+            writeln!(output, "    {}", style(footer).dim())?;
+        }
+
+        for string in &segment.strings {
+            writeln!(
+                output,
+                "    {} {}",
+                style("\"").dim(),
+                style(truncate_str(&string.escape_debug().to_string(), 90)).dim()
+            )?;
+        }
+    }
+    if let Some(footer) = omitted_footer(report.segments.len(), shown, "segments") {
+        writeln!(output, "\n  {}", style(footer).dim())?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::data_segments::{DataContentKind, DataFinding, DataSegmentContent};
+
+    #[test]
+    fn test_format_console_report_lists_findings_with_sources() {
+        let report = DataReport {
+            module_bytes: 1000,
+            data_bytes: 500,
+            segments: vec![DataSegmentContent {
+                index: 0,
+                memory_offset: Some(1024),
+                size_bytes: 500,
+                percentage: 50.0,
+                text_bytes: 100,
+                zero_bytes: 20,
+                findings: vec![
+                    DataFinding {
+                        kind: DataContentKind::Woff2,
+                        offset: 16,
+                        size_bytes: Some(300),
+                        source: Some("fonts/inter.woff2 (src/lib.rs:4)".to_string()),
+                    },
+                    DataFinding {
+                        kind: DataContentKind::Gzip,
+                        offset: 400,
+                        size_bytes: None,
+                        source: None,
+                    },
+                ],
+                strings: vec!["index out of bounds".to_string()],
+            }],
+            warnings: Vec::new(),
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("in 1 segments (50.0% of the module)"));
+        assert!(text.contains("@ 0x400"));
+        assert!(text.contains("← fonts/inter.woff2 (src/lib.rs:4)"));
+        assert!(text.contains("size unknown"));
+        assert!(text.contains("index out of bounds"));
+    }
+}
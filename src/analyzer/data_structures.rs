@@ -0,0 +1,490 @@
+//! Size-costly data structure detection
+//!
+//! Combines the function symbols of a WASM module with its data segments to
+//! find data structures that cost more than they need to:
+//!
+//! - Large static lookup tables: named static data (when the linker kept
+//!   per-symbol data segments) and long runs of sorted 32-bit words in the
+//!   merged `.rodata`
+//! - Statics built lazily at runtime (`lazy_static`, `once_cell`,
+//!   `LazyLock`), whose initializers compile to long runs of inline inserts
+//! - B-tree code: every key/value type pair instantiates its own copy of
+//!   `alloc::collections::btree`
+//! - Large derived `Debug` impls, typically on enums with many variants
+//!
+//! Each pattern comes with targeted advice (phf, compact tables, fewer map
+//! types, cfg-gated `Debug` derives).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::DataStructureAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = DataStructureAnalyzer::new().analyze(Path::new("pkg/app_bg.wasm"))?;
+//! for finding in &report.findings {
+//!     println!("{}: {} ({} bytes)", finding.pattern.label(), finding.subject, finding.size_bytes);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use super::symbol_blame::{crate_of_symbol, is_toolchain_crate};
+use super::twiggy::native::{self, demangle};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use wasmparser::{KnownCustom, Name, Parser, Payload, TypeRef};
+
+/// Static data at least this large is reported as a lookup table
+const LOOKUP_TABLE_MIN_BYTES: u64 = 4096;
+
+/// Sorted 32-bit words in a row that make an unnamed lookup table
+const SORTED_TABLE_MIN_ENTRIES: usize = 1024;
+
+/// Lazy static initializers at least this large are reported
+const LAZY_INIT_MIN_BYTES: u64 = 1024;
+
+/// B-tree code at least this large is reported
+const BTREE_MIN_BYTES: u64 = 8192;
+
+/// `Debug` impls at least this large are reported
+const DEBUG_FMT_MIN_BYTES: u64 = 1024;
+
+/// Names the linker gives merged data segments
+const MERGED_SEGMENT_NAMES: &[&str] = &[".rodata", ".data", ".bss", ".tdata"];
+
+/// Kind of size-costly pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DataStructurePattern {
+    /// Large static table in the data segments
+    LookupTable,
+    /// Static collection built at runtime by a lazy initializer
+    LazyStatic,
+    /// Monomorphized B-tree code
+    BTreeMap,
+    /// Large derived `Debug` impl
+    DebugDerive,
+}
+
+impl DataStructurePattern {
+    /// Short label for reports
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::LookupTable => "Static lookup tables",
+            Self::LazyStatic => "Lazily built statics",
+            Self::BTreeMap => "BTreeMap-heavy code",
+            Self::DebugDerive => "Large Debug impls",
+        }
+    }
+
+    /// What to do about the pattern
+    pub fn advice(self) -> &'static str {
+        match self {
+            Self::LookupTable => {
+                "Store ranges or bitsets instead of one entry per key, generate the table in \
+                 build.rs in its most compact form, or put rarely used tables behind a feature"
+            }
+            Self::LazyStatic => {
+                "Build fixed maps at compile time with phf (phf_map!): the data lands in \
+                 .rodata and the insert-per-entry initializer disappears"
+            }
+            Self::BTreeMap => {
+                "Each key/value type pair instantiates the whole B-tree; use fewer map types, \
+                 a sorted Vec with binary_search for small or read-mostly maps, or phf for \
+                 fixed ones"
+            }
+            Self::DebugDerive => {
+                "Derive Debug only in debug builds (#[cfg_attr(debug_assertions, derive(Debug))]) \
+                 or write a manual impl that prints the discriminant; the variant names live in \
+                 .rodata as well"
+            }
+        }
+    }
+}
+
+/// One instance of a pattern
+#[derive(Debug, Clone, Serialize)]
+pub struct DataStructureFinding {
+    /// Pattern found
+    pub pattern: DataStructurePattern,
+    /// Static, type or code the finding is about
+    pub subject: String,
+    /// Bytes attributed to it
+    pub size_bytes: u64,
+    /// Functions (code patterns) or table entries (lookup tables)
+    pub count: usize,
+    /// Where the data lives (`data[N] +0xOFFSET`), for lookup tables
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl SizedEntry for DataStructureFinding {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.subject
+    }
+}
+
+/// Size-costly data structures found in a module
+#[derive(Debug, Clone, Serialize)]
+pub struct DataStructureReport {
+    /// Module size
+    pub module_bytes: u64,
+    /// Size of the code section
+    pub code_bytes: u64,
+    /// Size of the data segments
+    pub data_bytes: u64,
+    /// Findings, grouped by pattern and largest first within a pattern
+    pub findings: Vec<DataStructureFinding>,
+    /// Caveats about what could be detected
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl DataStructureReport {
+    /// Findings of one pattern
+    pub fn of(&self, pattern: DataStructurePattern) -> impl Iterator<Item = &DataStructureFinding> {
+        self.findings.iter().filter(move |f| f.pattern == pattern)
+    }
+
+    /// Total bytes attributed to one pattern
+    pub fn pattern_bytes(&self, pattern: DataStructurePattern) -> u64 {
+        self.of(pattern).map(|f| f.size_bytes).sum()
+    }
+}
+
+/// Detects size-costly data structure patterns
+#[derive(Debug, Default)]
+pub struct DataStructureAnalyzer;
+
+impl DataStructureAnalyzer {
+    /// Create an analyzer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Look for costly data structures in a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<DataStructureReport> {
+        let bytes = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        analyze_module(&bytes).with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+}
+
+/// A data segment's index and contents
+struct Segment<'a> {
+    index: u32,
+    bytes: &'a [u8],
+}
+
+/// Look for costly data structures in a module
+pub fn analyze_module(module: &[u8]) -> Result<DataStructureReport> {
+    let mut imported_functions = 0;
+    let mut defined = 0;
+    let mut sizes: Vec<(u32, u64)> = Vec::new();
+    let mut segments = Vec::new();
+    let mut function_names = HashMap::new();
+    let mut data_names = HashMap::new();
+    let mut code_bytes = 0;
+    let mut has_names = false;
+    for payload in Parser::new(0).parse_all(module) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader.into_imports() {
+                    if matches!(import?.ty, TypeRef::Func(_) | TypeRef::FuncExact(_)) {
+                        imported_functions += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionStart { range, .. } => code_bytes = range.len() as u64,
+            Payload::CodeSectionEntry(body) => {
+                sizes.push((imported_functions + defined, body.range().len() as u64));
+                defined += 1;
+            }
+            Payload::DataSection(reader) => {
+                for (index, data) in reader.into_iter().enumerate() {
+                    segments.push(Segment {
+                        index: index as u32,
+                        bytes: data?.data,
+                    });
+                }
+            }
+            Payload::CustomSection(reader) => {
+                let Some(functions) = native::function_names(&reader) else {
+                    continue;
+                };
+                has_names = true;
+                for (index, name) in functions {
+                    function_names.insert(index, demangle(name));
+                }
+                if let KnownCustom::Name(section) = reader.as_known() {
+                    for name in section.into_iter().map_while(Result::ok) {
+                        if let Name::Data(map) = name {
+                            for naming in map.into_iter().map_while(Result::ok) {
+                                data_names.insert(naming.index, naming.name.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut warnings = Vec::new();
+    if !has_names {
+        warnings.push(
+            "No name section: only unnamed lookup tables can be detected (build without \
+             stripping symbols, e.g. strip = false, for the code patterns)"
+                .to_string(),
+        );
+    }
+
+    let functions: Vec<(&str, u64)> = sizes
+        .iter()
+        .filter_map(|(index, size)| Some((function_names.get(index)?.as_str(), *size)))
+        .collect();
+
+    let mut findings = lookup_tables(&segments, &data_names);
+    findings.extend(lazy_statics(&functions));
+    findings.extend(btree_code(&functions));
+    findings.extend(debug_impls(&functions));
+    for pattern in [
+        DataStructurePattern::LookupTable,
+        DataStructurePattern::LazyStatic,
+        DataStructurePattern::DebugDerive,
+    ] {
+        sort_pattern(&mut findings, pattern);
+    }
+
+    Ok(DataStructureReport {
+        module_bytes: module.len() as u64,
+        code_bytes,
+        data_bytes: segments.iter().map(|s| s.bytes.len() as u64).sum(),
+        findings,
+        warnings,
+    })
+}
+
+/// Largest first within one pattern, keeping the pattern order
+fn sort_pattern(findings: &mut [DataStructureFinding], pattern: DataStructurePattern) {
+    let start = findings.iter().position(|f| f.pattern == pattern);
+    let end = findings.iter().rposition(|f| f.pattern == pattern);
+    if let (Some(start), Some(end)) = (start, end) {
+        findings[start..=end].sort_by_key(|f| std::cmp::Reverse(f.size_bytes));
+    }
+}
+
+/// Named static data segments and sorted word runs in merged ones
+fn lookup_tables(
+    segments: &[Segment<'_>],
+    data_names: &HashMap<u32, String>,
+) -> Vec<DataStructureFinding> {
+    let mut findings = Vec::new();
+    for segment in segments {
+        let name = data_names
+            .get(&segment.index)
+            .map(String::as_str)
+            .filter(|name| !MERGED_SEGMENT_NAMES.contains(name));
+        if let Some(name) = name {
+            let size_bytes = segment.bytes.len() as u64;
+            if size_bytes >= LOOKUP_TABLE_MIN_BYTES {
+                let symbol = [".rodata.", ".data."]
+                    .iter()
+                    .find_map(|prefix| name.strip_prefix(prefix))
+                    .unwrap_or(name);
+                findings.push(DataStructureFinding {
+                    pattern: DataStructurePattern::LookupTable,
+                    subject: demangle(symbol),
+                    size_bytes,
+                    count: 0,
+                    location: Some(format!("data[{}]", segment.index)),
+                });
+            }
+            continue;
+        }
+
+        for (offset, entries) in sorted_word_runs(segment.bytes) {
+            findings.push(DataStructureFinding {
+                pattern: DataStructurePattern::LookupTable,
+                subject: format!("sorted table of {} u32 entries", entries),
+                size_bytes: entries as u64 * 4,
+                count: entries,
+                location: Some(format!("data[{}] +{:#x}", segment.index, offset)),
+            });
+        }
+    }
+    findings
+}
+
+/// Runs of strictly increasing little-endian u32 words, as (offset, entries)
+fn sorted_word_runs(bytes: &[u8]) -> Vec<(usize, usize)> {
+    let words: Vec<u32> = bytes
+        .chunks_exact(4)
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        .collect();
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=words.len() {
+        if i < words.len() && words[i] > words[i - 1] {
+            continue;
+        }
+        if i - start >= SORTED_TABLE_MIN_ENTRIES {
+            runs.push((start * 4, i - start));
+        }
+        start = i;
+    }
+    runs
+}
+
+/// Static a lazy initializer belongs to, if `name` is one
+///
+/// Matches `lazy_static!`'s `__static_ref_initialize` and closures defined in
+/// a static's initializer (`app::MAP::{{closure}}`, as with `LazyLock::new`
+/// and `once_cell::sync::Lazy::new`).
+fn lazy_static_of(name: &str) -> Option<String> {
+    if let Some(inner) = name.strip_prefix('<') {
+        if name.contains("__static_ref_initialize") {
+            let (static_path, _) = inner.split_once(" as ")?;
+            return Some(static_path.to_string());
+        }
+        return None;
+    }
+    let (path, _) = name.split_once("::{{closure}}")?;
+    let last = path.rsplit("::").next()?;
+    let is_static = last.len() > 1
+        && last.chars().any(|c| c.is_ascii_uppercase())
+        && last
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    is_static.then(|| path.to_string())
+}
+
+/// Lazy static initializers over the size threshold, per static
+fn lazy_statics(functions: &[(&str, u64)]) -> Vec<DataStructureFinding> {
+    let mut by_static: HashMap<String, (u64, usize)> = HashMap::new();
+    for &(name, size) in functions {
+        if let Some(path) = lazy_static_of(name) {
+            let entry = by_static.entry(path).or_default();
+            entry.0 += size;
+            entry.1 += 1;
+        }
+    }
+    by_static
+        .into_iter()
+        .filter(|&(_, (size, _))| size >= LAZY_INIT_MIN_BYTES)
+        .map(|(subject, (size_bytes, count))| DataStructureFinding {
+            pattern: DataStructurePattern::LazyStatic,
+            subject,
+            size_bytes,
+            count,
+            location: None,
+        })
+        .collect()
+}
+
+/// B-tree code, if there is enough of it to matter
+fn btree_code(functions: &[(&str, u64)]) -> Option<DataStructureFinding> {
+    let btree: Vec<u64> = functions
+        .iter()
+        .filter(|(name, _)| name.contains("alloc::collections::btree::"))
+        .map(|&(_, size)| size)
+        .collect();
+    let size_bytes: u64 = btree.iter().sum();
+    (size_bytes >= BTREE_MIN_BYTES).then(|| DataStructureFinding {
+        pattern: DataStructurePattern::BTreeMap,
+        subject: "alloc::collections::btree".to_string(),
+        size_bytes,
+        count: btree.len(),
+        location: None,
+    })
+}
+
+/// Type a `Debug::fmt` impl is for, if `name` is one outside the toolchain
+fn debug_impl_of(name: &str) -> Option<&str> {
+    let inner = name.strip_prefix('<')?;
+    let (self_type, rest) = inner.split_once(" as ")?;
+    if !rest.starts_with("core::fmt::Debug>::fmt") {
+        return None;
+    }
+    let from_toolchain = crate_of_symbol(self_type).is_none_or(|c| is_toolchain_crate(&c));
+    (!from_toolchain).then_some(self_type)
+}
+
+/// `Debug` impls over the size threshold
+fn debug_impls(functions: &[(&str, u64)]) -> Vec<DataStructureFinding> {
+    functions
+        .iter()
+        .filter(|&&(_, size)| size >= DEBUG_FMT_MIN_BYTES)
+        .filter_map(|&(name, size_bytes)| {
+            Some(DataStructureFinding {
+                pattern: DataStructurePattern::DebugDerive,
+                subject: debug_impl_of(name)?.to_string(),
+                size_bytes,
+                count: 1,
+                location: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_patterns_are_recognized() {
+        assert_eq!(
+            lazy_static_of("app::COUNTRY_CODES::{{closure}}").as_deref(),
+            Some("app::COUNTRY_CODES")
+        );
+        assert_eq!(
+            lazy_static_of("<app::MAP as core::ops::deref::Deref>::deref::__static_ref_initialize")
+                .as_deref(),
+            Some("app::MAP")
+        );
+        assert!(lazy_static_of("app::render::{{closure}}").is_none());
+
+        assert_eq!(
+            debug_impl_of("<app::Opcode as core::fmt::Debug>::fmt"),
+            Some("app::Opcode")
+        );
+        assert!(debug_impl_of("<core::option::Option<T> as core::fmt::Debug>::fmt").is_none());
+        assert!(debug_impl_of("<app::Opcode as core::fmt::Display>::fmt").is_none());
+
+        let functions = [
+            ("<app::Opcode as core::fmt::Debug>::fmt", 4000),
+            ("<app::Small as core::fmt::Debug>::fmt", 100),
+            (
+                "alloc::collections::btree::map::BTreeMap<K,V,A>::insert",
+                6000,
+            ),
+            ("alloc::collections::btree::node::Handle::split", 3000),
+        ];
+        assert_eq!(debug_impls(&functions).len(), 1);
+        let btree = btree_code(&functions).unwrap();
+        assert_eq!((btree.size_bytes, btree.count), (9000, 2));
+    }
+
+    #[test]
+    fn test_sorted_word_runs_find_tables() {
+        let mut bytes = vec![0xff; 12];
+        for i in 0..SORTED_TABLE_MIN_ENTRIES as u32 {
+            bytes.extend((i * 3).to_le_bytes());
+        }
+        bytes.extend([0; 16]);
+        let runs = sorted_word_runs(&bytes);
+        // The leading 0xffffffff words are not part of the run
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].1, SORTED_TABLE_MIN_ENTRIES);
+        assert_eq!(runs[0].0, 12);
+
+        assert!(sorted_word_runs(&[0; 8192]).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+//! Data structure pattern report formatting
+
+use super::data_structures::{DataStructurePattern, DataStructureReport};
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Findings listed per pattern by default
+const DEFAULT_FINDINGS_PER_PATTERN: usize = 10;
+
+/// Patterns in report order
+const PATTERNS: [DataStructurePattern; 4] = [
+    DataStructurePattern::LookupTable,
+    DataStructurePattern::LazyStatic,
+    DataStructurePattern::BTreeMap,
+    DataStructurePattern::DebugDerive,
+];
+
+/// Format the data structure report for console output
+pub fn format_console_report(report: &DataStructureReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the data structure report, limiting the findings listed per pattern
+pub fn format_console_report_with_limit(
+    report: &DataStructureReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Costly Data Structures").bold().underlined()
+    )?;
+    writeln!(
+        output,
+        "\n   Code {} · data {} · module {}",
+        format_bytes(report.code_bytes),
+        format_bytes(report.data_bytes),
+        format_bytes(report.module_bytes)
+    )?;
+    for warning in &report.warnings {
+        writeln!(
+            output,
+            "   {} {}",
+            style("⚠").yellow(),
+            style(warning).yellow()
+        )?;
+    }
+    if report.findings.is_empty() {
+        writeln!(
+            output,
+            "\n   {} No size-costly data structure patterns found",
+            style("✓").green()
+        )?;
+        return Ok(output);
+    }
+
+    for pattern in PATTERNS {
+        let findings: Vec<_> = report.of(pattern).collect();
+        if findings.is_empty() {
+            continue;
+        }
+        writeln!(
+            output,
+            "\n{} {}",
+            style(pattern.label()).bold(),
+            style(format!("({})", format_bytes(report.pattern_bytes(pattern)))).yellow()
+        )?;
+
+        let shown = limit.visible(findings.len(), Some(DEFAULT_FINDINGS_PER_PATTERN));
+        for finding in findings.iter().take(shown) {
+            let detail = match (&finding.location, pattern) {
+                (Some(location), _) => location.clone(),
+                (None, DataStructurePattern::DebugDerive) => String::new(),
+                (None, _) => format!("{} functions", finding.count),
+            };
+            writeln!(
+                output,
+                "  {:>10}  {:<56} {}",
+                format_bytes(finding.size_bytes),
+                truncate_str(&finding.subject, 56),
+                style(detail).dim()
+            )?;
+        }
+        if let Some(footer) = omitted_footer(findings.len(), shown, "findings") {
+            writeln!(output, "  {}", style(footer).dim())?;
+        }
+        writeln!(output, "  {} {}", style("→").cyan(), pattern.advice())?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::data_structures::DataStructureFinding;
+
+    #[test]
+    fn test_format_console_report_groups_findings_with_advice() {
+        let report = DataStructureReport {
+            module_bytes: 100_000,
+            code_bytes: 60_000,
+            data_bytes: 30_000,
+            findings: vec![
+                DataStructureFinding {
+                    pattern: DataStructurePattern::LazyStatic,
+                    subject: "app::COUNTRY_CODES".to_string(),
+                    size_bytes: 5000,
+                    count: 2,
+                    location: None,
+                },
+                DataStructureFinding {
+                    pattern: DataStructurePattern::DebugDerive,
+                    subject: "app::Opcode".to_string(),
+                    size_bytes: 3000,
+                    count: 1,
+                    location: None,
+                },
+            ],
+            warnings: Vec::new(),
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("Lazily built statics"));
+        assert!(text.contains("app::COUNTRY_CODES"));
+        assert!(text.contains("phf_map!"));
+        assert!(text.contains("cfg_attr(debug_assertions, derive(Debug))"));
+        assert!(!text.contains("BTreeMap-heavy code"));
+    }
+}
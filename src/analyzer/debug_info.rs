@@ -0,0 +1,276 @@
+//! Leftover debug info detection
+//!
+//! A release artifact should carry no DWARF (`.debug_*` sections), no
+//! `name` section and no linker symbol tables (`linking`, `reloc.*`), yet a
+//! `debug = true` profile, `strip = false`, a wasm-opt run with `-g` or a
+//! custom pipeline step easily leaves them in. This check lists each one
+//! with its exact size. DWARF and names are stripped in place through
+//! [`CustomSectionAnalyzer::strip`]; symbol tables only appear when the
+//! linker emitted relocations and need a relink instead.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::DebugInfoChecker;
+//! use std::path::Path;
+//!
+//! let report = DebugInfoChecker::new().check(Path::new("pkg/app_bg.wasm"))?;
+//! if !report.is_clean() {
+//!     println!("{} bytes of debug info left", report.leftover_bytes);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::custom_sections::{
+    self, CustomSectionAnalyzer, CustomSectionKind, CustomSectionReport, StripOutcome,
+};
+use super::report_utils::SizedEntry;
+use crate::infra::{CommandExecutor, RealCommandExecutor};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// Kind of leftover debug info
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LeftoverKind {
+    /// `.debug_*`: DWARF debug info
+    Dwarf,
+    /// `name`: function, local and global names
+    Names,
+    /// `linking` and `reloc.*`: linker symbol tables
+    Symbols,
+}
+
+impl LeftoverKind {
+    /// Kind of a custom section, if it is debug info
+    pub fn of(name: &str) -> Option<Self> {
+        match CustomSectionKind::of(name) {
+            CustomSectionKind::Debug => Some(LeftoverKind::Dwarf),
+            CustomSectionKind::Names => Some(LeftoverKind::Names),
+            _ if name == "linking" || name.starts_with("reloc.") => Some(LeftoverKind::Symbols),
+            _ => None,
+        }
+    }
+
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            LeftoverKind::Dwarf => "DWARF",
+            LeftoverKind::Names => "name section",
+            LeftoverKind::Symbols => "symbol table",
+        }
+    }
+
+    /// Whether [`DebugInfoChecker::strip`] removes this kind
+    pub fn strippable(self) -> bool {
+        !matches!(self, LeftoverKind::Symbols)
+    }
+}
+
+/// One leftover debug info section
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeftoverSection {
+    /// Section name
+    pub name: String,
+    /// Kind of debug info
+    pub kind: LeftoverKind,
+    /// Size including the section header
+    pub size_bytes: u64,
+}
+
+impl SizedEntry for LeftoverSection {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Debug info left in a module
+#[derive(Debug, Clone, Serialize)]
+pub struct DebugInfoReport {
+    /// Module size
+    pub module_bytes: u64,
+    /// Leftover sections, largest first
+    pub sections: Vec<LeftoverSection>,
+    /// Sum of the leftover section sizes
+    pub leftover_bytes: u64,
+    /// Part of `leftover_bytes` that `--fix` strips
+    pub strippable_bytes: u64,
+    /// Custom sections the check was derived from
+    #[serde(skip)]
+    custom_sections: CustomSectionReport,
+}
+
+impl DebugInfoReport {
+    /// Whether the module carries no debug info
+    pub fn is_clean(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Total size per kind, in [`LeftoverKind`] order
+    pub fn bytes_by_kind(&self) -> Vec<(LeftoverKind, u64)> {
+        let mut totals: Vec<(LeftoverKind, u64)> = Vec::new();
+        for section in &self.sections {
+            match totals.iter_mut().find(|(kind, _)| *kind == section.kind) {
+                Some((_, bytes)) => *bytes += section.size_bytes,
+                None => totals.push((section.kind, section.size_bytes)),
+            }
+        }
+        totals.sort();
+        totals
+    }
+}
+
+/// Finds and strips debug info left in an artifact
+pub struct DebugInfoChecker<CE: CommandExecutor = RealCommandExecutor> {
+    sections: CustomSectionAnalyzer<CE>,
+}
+
+impl DebugInfoChecker {
+    /// Create a checker that runs the real strip tools
+    pub fn new() -> Self {
+        Self::with_executor(RealCommandExecutor)
+    }
+}
+
+impl Default for DebugInfoChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CE: CommandExecutor> DebugInfoChecker<CE> {
+    /// Create a checker with a custom command executor
+    pub fn with_executor(cmd_executor: CE) -> Self {
+        Self {
+            sections: CustomSectionAnalyzer::with_executor(cmd_executor),
+        }
+    }
+
+    /// Look for debug info in a WASM file
+    pub fn check(&self, wasm_file: &Path) -> Result<DebugInfoReport> {
+        let module = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        check_module(&module).with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+
+    /// Strip the DWARF and name sections from `wasm_file` in place
+    ///
+    /// Other custom sections, `producers` included, are kept.
+    pub fn strip(
+        &self,
+        wasm_file: &Path,
+        report: &DebugInfoReport,
+        dry_run: bool,
+    ) -> Result<StripOutcome> {
+        let mut custom = report.custom_sections.clone();
+        custom
+            .recommendations
+            .retain(|r| matches!(r.kind, CustomSectionKind::Debug | CustomSectionKind::Names));
+        self.sections.strip(wasm_file, &custom, dry_run)
+    }
+}
+
+/// Look for debug info in a module
+pub fn check_module(module: &[u8]) -> Result<DebugInfoReport> {
+    let custom_sections = custom_sections::analyze_module(module)?;
+    // Custom sections come largest first
+    let sections: Vec<LeftoverSection> = custom_sections
+        .sections
+        .iter()
+        .filter_map(|section| {
+            LeftoverKind::of(&section.name).map(|kind| LeftoverSection {
+                name: section.name.clone(),
+                kind,
+                size_bytes: section.size_bytes,
+            })
+        })
+        .collect();
+    Ok(DebugInfoReport {
+        module_bytes: custom_sections.module_bytes,
+        leftover_bytes: sections.iter().map(|s| s.size_bytes).sum(),
+        strippable_bytes: sections
+            .iter()
+            .filter(|s| s.kind.strippable())
+            .map(|s| s.size_bytes)
+            .sum(),
+        sections,
+        custom_sections,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{custom_section, HEADER};
+    use std::process::{Command, ExitStatus, Output};
+
+    fn module() -> Vec<u8> {
+        let mut module = HEADER.to_vec();
+        module.extend(custom_section(".debug_info", &[0; 60]));
+        module.extend(custom_section("name", &[0; 40]));
+        module.extend(custom_section("reloc.CODE", &[0; 10]));
+        module.extend(custom_section("producers", &[0; 10]));
+        module
+    }
+
+    #[test]
+    fn test_check_module_lists_leftovers_with_exact_sizes() {
+        let report = check_module(&module()).unwrap();
+
+        let sections: Vec<(&str, LeftoverKind, u64)> = report
+            .sections
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.size_bytes))
+            .collect();
+        assert_eq!(
+            sections,
+            [
+                (".debug_info", LeftoverKind::Dwarf, 2 + 12 + 60),
+                ("name", LeftoverKind::Names, 2 + 5 + 40),
+                ("reloc.CODE", LeftoverKind::Symbols, 2 + 11 + 10),
+            ]
+        );
+        assert_eq!(report.leftover_bytes, 74 + 47 + 23);
+        assert_eq!(report.strippable_bytes, 74 + 47);
+        assert!(check_module(b"\0asm\x01\0\0\0").unwrap().is_clean());
+    }
+
+    /// Fails every command as if the tool were not installed
+    struct NoTools;
+
+    impl CommandExecutor for NoTools {
+        fn status(&self, _cmd: &mut Command) -> std::io::Result<ExitStatus> {
+            Err(std::io::ErrorKind::NotFound.into())
+        }
+
+        fn output(&self, _cmd: &mut Command) -> std::io::Result<Output> {
+            Err(std::io::ErrorKind::NotFound.into())
+        }
+    }
+
+    #[test]
+    fn test_strip_keeps_producers() {
+        let report = check_module(&module()).unwrap();
+        let checker = DebugInfoChecker::with_executor(NoTools);
+
+        let outcome = checker
+            .strip(Path::new("app_bg.wasm"), &report, true)
+            .unwrap();
+        assert_eq!(
+            outcome.args,
+            [
+                "app_bg.wasm",
+                "--strip-debug",
+                "--strip-dwarf",
+                "--all-features",
+                "-o",
+                "app_bg.wasm"
+            ]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Leftover debug info report formatting
+
+use super::debug_info::{DebugInfoReport, LeftoverKind};
+use super::report_utils::format_bytes;
+use crate::fmt::percent_of;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Format the leftover debug info report for console output
+pub fn format_console_report(report: &DebugInfoReport) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Leftover Debug Info").bold().underlined()
+    )?;
+    if report.is_clean() {
+        writeln!(
+            output,
+            "\n{}",
+            style("✨ No DWARF, name section or symbol table in the module").green()
+        )?;
+        return Ok(output);
+    }
+
+    writeln!(
+        output,
+        "\n   {} of {} ({:.1}%) is debug info",
+        style(format_bytes(report.leftover_bytes)).yellow().bold(),
+        format_bytes(report.module_bytes),
+        percent_of(report.leftover_bytes, report.module_bytes)
+    )?;
+
+    writeln!(output, "\n{}", style("SECTIONS:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    for section in &report.sections {
+        writeln!(
+            output,
+            "  {:<24} {:<14} {:>10} bytes",
+            section.name,
+            section.kind.label(),
+            section.size_bytes
+        )?;
+    }
+
+    writeln!(output, "\n{}", style("FIX:").bold().yellow())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    if report.strippable_bytes > 0 {
+        writeln!(
+            output,
+            "  Run with --fix to strip {} of DWARF and names in place",
+            style(format_bytes(report.strippable_bytes)).green().bold()
+        )?;
+        writeln!(
+            output,
+            "  To keep them out of future builds, set strip = true in [profile.release]"
+        )?;
+    }
+    if report
+        .bytes_by_kind()
+        .iter()
+        .any(|(kind, _)| *kind == LeftoverKind::Symbols)
+    {
+        writeln!(
+            output,
+            "  Symbol tables come from a relocatable link; relink without --emit-relocs"
+        )?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::debug_info::check_module;
+
+    #[test]
+    fn test_format_console_report_lists_sections_and_fix() {
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        module.extend([0, 8, 7]);
+        module.extend(b".debug_");
+        let report = check_module(&module).unwrap();
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains(".debug_"));
+        assert!(text.contains("10 bytes"));
+        assert!(text.contains("Run with --fix to strip 10 B"));
+        assert!(!text.contains("relink"));
+
+        let clean = check_module(b"\0asm\x01\0\0\0").unwrap();
+        assert!(format_console_report(&clean)
+            .unwrap()
+            .contains("No DWARF, name section or symbol table"));
+    }
+}
@@ -0,0 +1,516 @@
+//! Dependency graph export with per-crate size attribution
+//!
+//! Lists tell which crates are large; a graph tells which dependency edge
+//! to cut. This resolves the dependency graph cargo builds for the WASM
+//! target (normal dependencies, proc-macros not followed) and annotates each
+//! package with its size contribution: measured from the symbols of a module
+//! when one is given, otherwise estimated from the heavy dependency
+//! database. Each package also gets the size of its whole subtree and of the
+//! part only it pulls in, which is what removing it would save. The graph is
+//! written as Graphviz DOT or JSON.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::DepGraphAnalyzer;
+//! use std::path::Path;
+//!
+//! let graph = DepGraphAnalyzer::new(".").analyze(Some(Path::new("pkg/app_bg.wasm")))?;
+//! std::fs::write("deps.dot", graph.to_dot())?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::heavy_deps::HeavyDepsDatabase;
+use super::symbol_blame::crate_of_symbol;
+use super::twiggy::TwiggyAnalyzer;
+use crate::fmt::format_bytes;
+use crate::profiling::{self, ProfilePhase};
+use anyhow::{Context, Result};
+use cargo_metadata::{DependencyKind, MetadataCommand, TargetKind};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Target the dependency graph is resolved for
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// How a package's size was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeSource {
+    /// Summed from the module's symbols
+    Measured,
+    /// Taken from the heavy dependency database
+    Estimated,
+}
+
+/// Package in the graph
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DepNode {
+    /// `name version`
+    pub id: String,
+    /// Package name
+    pub name: String,
+    /// Resolved version
+    pub version: String,
+    /// Whether the package is the project's own crate
+    pub root: bool,
+    /// Size of the package's own code and data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Where `size_bytes` comes from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_source: Option<SizeSource>,
+    /// Size of the package and everything it depends on
+    pub subtree_bytes: u64,
+    /// Size of the package and the dependencies only it pulls in
+    pub exclusive_bytes: u64,
+    /// Library crate name, as symbols spell it
+    #[serde(skip)]
+    crate_name: String,
+}
+
+/// Dependency edge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DepEdge {
+    /// Id of the dependent package
+    pub from: String,
+    /// Id of the dependency
+    pub to: String,
+}
+
+/// Dependency graph annotated with sizes
+#[derive(Debug, Clone, Serialize)]
+pub struct DepSizeGraph {
+    /// Packages reachable from the project, largest exclusive size first
+    pub nodes: Vec<DepNode>,
+    /// Normal dependency edges between them
+    pub edges: Vec<DepEdge>,
+    /// Measured bytes of crates outside the graph (the standard library)
+    /// and of items without a crate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unmatched_bytes: Option<u64>,
+    /// Caveats about the attribution
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Builds the size-annotated dependency graph of a project
+pub struct DepGraphAnalyzer {
+    project_root: PathBuf,
+    heavy_deps: HeavyDepsDatabase,
+    offline: bool,
+}
+
+impl DepGraphAnalyzer {
+    /// Create an analyzer for the project at `project_root`
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+            heavy_deps: HeavyDepsDatabase::default(),
+            offline: false,
+        }
+    }
+
+    /// Resolve the graph without network access
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Use a project-specific database for the size estimates
+    pub fn with_heavy_deps(mut self, database: HeavyDepsDatabase) -> Self {
+        self.heavy_deps = database;
+        self
+    }
+
+    /// Build the graph, measuring sizes in `wasm_file` if given
+    pub fn analyze(&self, wasm_file: Option<&Path>) -> Result<DepSizeGraph> {
+        let mut graph = self.graph()?;
+        match wasm_file {
+            Some(wasm_file) => {
+                let mut sizes: HashMap<String, u64> = HashMap::new();
+                let mut unattributed = 0;
+                for item in TwiggyAnalyzer::new(wasm_file).sized_items()? {
+                    match crate_of_symbol(&item.name) {
+                        Some(crate_name) => {
+                            *sizes.entry(crate_name).or_insert(0) += item.size_bytes
+                        }
+                        None => unattributed += item.size_bytes,
+                    }
+                }
+                graph.attach_measured(&sizes, unattributed);
+            }
+            None => graph.attach_estimates(&self.heavy_deps),
+        }
+        graph.update_totals();
+        Ok(graph)
+    }
+
+    /// Resolved graph for the WASM target, reachable packages only
+    fn graph(&self) -> Result<DepSizeGraph> {
+        let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+        let mut options = vec!["--filter-platform".to_string(), WASM_TARGET.to_string()];
+        if self.offline {
+            options.push("--offline".to_string());
+        }
+        let metadata = MetadataCommand::new()
+            .current_dir(&self.project_root)
+            .other_options(options)
+            .exec()
+            .context("Failed to resolve the WASM dependency graph")?;
+        let resolve = metadata
+            .resolve
+            .as_ref()
+            .context("No dependency resolution found in cargo metadata")?;
+
+        let packages: HashMap<_, _> = metadata.packages.iter().map(|p| (&p.id, p)).collect();
+        let deps: HashMap<_, Vec<_>> = resolve
+            .nodes
+            .iter()
+            .map(|node| {
+                let normal = node
+                    .deps
+                    .iter()
+                    .filter(|dep| {
+                        dep.dep_kinds
+                            .iter()
+                            .any(|k| matches!(k.kind, DependencyKind::Normal))
+                    })
+                    .map(|dep| &dep.pkg)
+                    .collect();
+                (&node.id, normal)
+            })
+            .collect();
+        let roots: Vec<_> = match metadata.root_package() {
+            Some(root) => vec![&root.id],
+            None => metadata.workspace_members.iter().collect(),
+        };
+
+        // Breadth-first from the roots; proc-macros run on the host
+        let mut seen: HashSet<_> = roots.iter().copied().collect();
+        let mut queue: VecDeque<_> = roots.iter().copied().collect();
+        let mut graph = DepSizeGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            unmatched_bytes: None,
+            warnings: Vec::new(),
+        };
+        while let Some(id) = queue.pop_front() {
+            let Some(package) = packages.get(id) else {
+                continue;
+            };
+            let lib = package.targets.iter().find(|t| {
+                t.is_kind(TargetKind::Lib)
+                    || t.is_kind(TargetKind::RLib)
+                    || t.is_kind(TargetKind::CDyLib)
+            });
+            graph.nodes.push(DepNode {
+                id: label(&package.name, &package.version.to_string()),
+                name: package.name.to_string(),
+                version: package.version.to_string(),
+                root: roots.contains(&id),
+                size_bytes: None,
+                size_source: None,
+                subtree_bytes: 0,
+                exclusive_bytes: 0,
+                crate_name: lib
+                    .map(|t| t.name.as_str())
+                    .unwrap_or(package.name.as_str())
+                    .replace('-', "_"),
+            });
+            if package
+                .targets
+                .iter()
+                .any(|t| t.is_kind(TargetKind::ProcMacro))
+            {
+                continue;
+            }
+            for dep in deps.get(id).into_iter().flatten() {
+                let Some(dep_package) = packages.get(dep) else {
+                    continue;
+                };
+                graph.edges.push(DepEdge {
+                    from: label(&package.name, &package.version.to_string()),
+                    to: label(&dep_package.name, &dep_package.version.to_string()),
+                });
+                if seen.insert(*dep) {
+                    queue.push_back(*dep);
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
+
+fn label(name: &str, version: &str) -> String {
+    format!("{} {}", name, version)
+}
+
+impl DepSizeGraph {
+    /// Attribute measured per-crate sizes to packages
+    ///
+    /// `sizes` maps crate names, as they appear in symbol paths, to bytes.
+    /// A crate resolved at several versions cannot be told apart by symbol
+    /// path; its bytes go to the first version reached from the project.
+    pub fn attach_measured(&mut self, sizes: &HashMap<String, u64>, unattributed_bytes: u64) {
+        let mut unmatched = unattributed_bytes;
+        for (crate_name, &bytes) in sizes {
+            let matching: Vec<usize> = (0..self.nodes.len())
+                .filter(|&i| &self.nodes[i].crate_name == crate_name)
+                .collect();
+            let Some(&first) = matching.first() else {
+                unmatched += bytes;
+                continue;
+            };
+            let node = &mut self.nodes[first];
+            node.size_bytes = Some(bytes);
+            node.size_source = Some(SizeSource::Measured);
+            if matching.len() > 1 {
+                self.warnings.push(format!(
+                    "{} is in the graph at several versions; its {} are attributed to {}",
+                    crate_name,
+                    format_bytes(bytes),
+                    self.nodes[first].id
+                ));
+            }
+        }
+        for node in self.nodes.iter_mut().filter(|n| n.size_bytes.is_none()) {
+            node.size_bytes = Some(0);
+            node.size_source = Some(SizeSource::Measured);
+        }
+        self.warnings.sort();
+        self.unmatched_bytes = Some(unmatched);
+    }
+
+    /// Fill in database estimates (the midpoint of the range) where known
+    pub fn attach_estimates(&mut self, database: &HeavyDepsDatabase) {
+        for node in self.nodes.iter_mut().filter(|n| !n.root) {
+            if let Some((min, max)) = database.size_kb(&node.name) {
+                node.size_bytes = Some(u64::from(min + max) / 2 * 1024);
+                node.size_source = Some(SizeSource::Estimated);
+            }
+        }
+        self.warnings.push(
+            "No WASM file given: sizes are estimates for known heavy crates only".to_string(),
+        );
+    }
+
+    /// Recompute subtree and exclusive sizes and order the nodes
+    pub fn update_totals(&mut self) {
+        let index: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+        for edge in &self.edges {
+            if let (Some(&from), Some(&to)) =
+                (index.get(edge.from.as_str()), index.get(edge.to.as_str()))
+            {
+                adjacency[from].push(to);
+            }
+        }
+        let roots: Vec<usize> = (0..self.nodes.len())
+            .filter(|&i| self.nodes[i].root)
+            .collect();
+        let sizes: Vec<u64> = self
+            .nodes
+            .iter()
+            .map(|n| n.size_bytes.unwrap_or(0))
+            .collect();
+
+        let mut totals = Vec::with_capacity(self.nodes.len());
+        for i in 0..self.nodes.len() {
+            let subtree = reachable(&adjacency, &[i], None);
+            // What the roots still reach once this package is gone
+            let without = reachable(&adjacency, &roots, Some(i));
+            let mut subtree_bytes = 0;
+            let mut exclusive_bytes = 0;
+            for j in (0..sizes.len()).filter(|&j| subtree[j]) {
+                subtree_bytes += sizes[j];
+                if !without[j] {
+                    exclusive_bytes += sizes[j];
+                }
+            }
+            totals.push((subtree_bytes, exclusive_bytes));
+        }
+        for (node, (subtree, exclusive)) in self.nodes.iter_mut().zip(totals) {
+            node.subtree_bytes = subtree;
+            node.exclusive_bytes = exclusive;
+        }
+        self.nodes.sort_by(|a, b| {
+            b.root
+                .cmp(&a.root)
+                .then(b.exclusive_bytes.cmp(&a.exclusive_bytes))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+    }
+
+    /// Render the graph in Graphviz DOT
+    ///
+    /// Packages are shaded by how much removing them would save.
+    pub fn to_dot(&self) -> String {
+        let largest = self
+            .nodes
+            .iter()
+            .filter(|n| !n.root)
+            .map(|n| n.exclusive_bytes)
+            .max()
+            .unwrap_or(0);
+        let mut dot = String::from("digraph dependencies {\n");
+        dot.push_str("  rankdir=LR;\n");
+        dot.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+        for node in &self.nodes {
+            let size = match (node.size_bytes, node.size_source) {
+                (Some(bytes), Some(SizeSource::Estimated)) => format!("~{}", format_bytes(bytes)),
+                (Some(bytes), _) => format_bytes(bytes),
+                (None, _) => "size unknown".to_string(),
+            };
+            let color = if node.root {
+                "#dbe9f6"
+            } else if largest == 0 || node.exclusive_bytes == 0 {
+                "#f5f5f5"
+            } else if node.exclusive_bytes * 2 >= largest {
+                "#fc8d59"
+            } else if node.exclusive_bytes * 5 >= largest {
+                "#fdcc8a"
+            } else {
+                "#fef0d9"
+            };
+            let _ = writeln!(
+                dot,
+                "  {} [label=\"{}\\n{}\\nsubtree {}, exclusive {}\", fillcolor=\"{}\"];",
+                quote(&node.id),
+                node.id,
+                size,
+                format_bytes(node.subtree_bytes),
+                format_bytes(node.exclusive_bytes),
+                color
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(dot, "  {} -> {};", quote(&edge.from), quote(&edge.to));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Nodes reachable from `start`, never entering `skip`
+fn reachable(adjacency: &[Vec<usize>], start: &[usize], skip: Option<usize>) -> Vec<bool> {
+    let mut seen = vec![false; adjacency.len()];
+    let mut stack: Vec<usize> = start.iter().copied().filter(|&i| Some(i) != skip).collect();
+    for &i in &stack {
+        seen[i] = true;
+    }
+    while let Some(i) = stack.pop() {
+        for &j in &adjacency[i] {
+            if !seen[j] && Some(j) != skip {
+                seen[j] = true;
+                stack.push(j);
+            }
+        }
+    }
+    seen
+}
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, root: bool) -> DepNode {
+        DepNode {
+            id: label(name, "1.0.0"),
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            root,
+            size_bytes: None,
+            size_source: None,
+            subtree_bytes: 0,
+            exclusive_bytes: 0,
+            crate_name: name.replace('-', "_"),
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> DepEdge {
+        DepEdge {
+            from: label(from, "1.0.0"),
+            to: label(to, "1.0.0"),
+        }
+    }
+
+    /// app → serde-json → serde, app → toml → serde, app → regex
+    fn graph() -> DepSizeGraph {
+        DepSizeGraph {
+            nodes: ["app", "serde-json", "toml", "serde", "regex"]
+                .iter()
+                .map(|name| node(name, *name == "app"))
+                .collect(),
+            edges: vec![
+                edge("app", "serde-json"),
+                edge("app", "toml"),
+                edge("app", "regex"),
+                edge("serde-json", "serde"),
+                edge("toml", "serde"),
+            ],
+            unmatched_bytes: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_measured_sizes_give_subtree_and_exclusive_totals() {
+        let mut graph = graph();
+        let sizes = HashMap::from([
+            ("app".to_string(), 1000),
+            ("serde_json".to_string(), 3000),
+            ("toml".to_string(), 2000),
+            ("serde".to_string(), 4000),
+            ("regex".to_string(), 5000),
+            ("core".to_string(), 700),
+        ]);
+        graph.attach_measured(&sizes, 300);
+        graph.update_totals();
+
+        let totals: Vec<(&str, u64, u64)> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.name.as_str(), n.subtree_bytes, n.exclusive_bytes))
+            .collect();
+        assert_eq!(
+            totals,
+            [
+                ("app", 15000, 15000),
+                ("regex", 5000, 5000),
+                ("serde", 4000, 4000),
+                // serde stays through toml, so only serde-json itself goes
+                ("serde-json", 7000, 3000),
+                ("toml", 6000, 2000),
+            ]
+        );
+        assert_eq!(graph.unmatched_bytes, Some(1000));
+    }
+
+    #[test]
+    fn test_to_dot_labels_and_shades_nodes() {
+        let mut graph = graph();
+        let sizes = HashMap::from([("regex".to_string(), 5000), ("serde".to_string(), 100)]);
+        graph.attach_measured(&sizes, 0);
+        graph.update_totals();
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph dependencies {\n"));
+        assert!(dot.contains(
+            "\"regex 1.0.0\" [label=\"regex 1.0.0\\n4.88 KiB\\nsubtree 4.88 KiB, exclusive 4.88 KiB\", fillcolor=\"#fc8d59\"];"
+        ));
+        assert!(dot.contains("\"serde 1.0.0\" [label=\"serde 1.0.0\\n100 B"));
+        assert!(dot.contains("\"app 1.0.0\" -> \"serde-json 1.0.0\";"));
+    }
+}
@@ -2,16 +2,22 @@
 //!
 //! Uses `cargo metadata` to analyze dependencies and provide size optimization recommendations.
 
+use crate::config::CrateRules;
+use crate::profiling::{self, ProfilePhase};
 use cargo_metadata::{DependencyKind, MetadataCommand};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 use thiserror::Error;
 
 use super::allocator::AllocatorDetector;
-use super::heavy_deps::{get_heavy_dependency_info, AlternativeType};
+use super::heavy_deps::{AlternativeType, HeavyDepsDatabase};
+use super::proposals::ProposalAdvisor;
 
 // Re-export types for backward compatibility
-pub use super::deps_types::{DependencyIssue, DependencyReport, IssueSeverity};
+pub use super::deps_types::{
+    DedupeFix, DependencyIssue, DependencyReport, DuplicateVersion, IssueSeverity, RuleKind,
+    RuleViolation,
+};
 
 /// Errors that can occur during dependency analysis
 #[derive(Error, Debug)]
@@ -28,6 +34,10 @@ pub enum DependencyAnalysisError {
     #[error("No root package found in cargo metadata")]
     NoRootPackage,
 
+    /// `[crates]` rules could not be parsed
+    #[error("Invalid dependency rules: {0}")]
+    InvalidRules(String),
+
     /// I/O error during analysis
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -37,9 +47,11 @@ pub enum DependencyAnalysisError {
 ///
 /// This analyzer scans your project's dependencies to identify:
 /// - Heavy dependencies that significantly impact WASM bundle size
-/// - Duplicate dependencies with different versions
+/// - Duplicate dependencies with different versions, their estimated cost and
+///   how to unify them
 /// - Opportunities to switch to lighter alternatives
 /// - Allocator optimization opportunities
+/// - Newer Wasm proposals (tail calls, reference types) worth evaluating
 ///
 /// # Examples
 ///
@@ -61,8 +73,17 @@ pub enum DependencyAnalysisError {
 /// ```
 pub struct DependencyAnalyzer {
     project_root: std::path::PathBuf,
+    offline: bool,
+    rules: CrateRules,
+    heavy_deps: HeavyDepsDatabase,
 }
 
+/// Target the resolved dependency graph is filtered to
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Warning attached to reports built from declared dependencies only
+const OFFLINE_DEGRADED_WARNING: &str = "Offline: the registry index is unavailable, so only direct dependencies declared in Cargo.toml were analyzed (transitive dependencies and duplicate versions were skipped)";
+
 impl DependencyAnalyzer {
     /// Create a new dependency analyzer for the given project root
     ///
@@ -84,9 +105,78 @@ impl DependencyAnalyzer {
     pub fn new(project_root: impl AsRef<Path>) -> Self {
         Self {
             project_root: project_root.as_ref().to_path_buf(),
+            offline: false,
+            rules: CrateRules::default(),
+            heavy_deps: HeavyDepsDatabase::default(),
         }
     }
 
+    /// Run `cargo metadata` with `--offline`
+    ///
+    /// If the dependency graph cannot be resolved without the network,
+    /// analysis falls back to the direct dependencies declared in
+    /// `Cargo.toml` and the report carries a warning.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use wasm_slim::analyzer::DependencyAnalyzer;
+    ///
+    /// let report = DependencyAnalyzer::new(".").with_offline(true).analyze()?;
+    /// for warning in &report.warnings {
+    ///     eprintln!("{}", warning);
+    /// }
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Check the dependency graph against banned and discouraged crates
+    ///
+    /// Matches are listed in [`DependencyReport::rule_violations`]; use
+    /// [`DependencyReport::has_banned`] to fail on banned crates.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use wasm_slim::analyzer::DependencyAnalyzer;
+    /// use wasm_slim::config::CrateRules;
+    ///
+    /// let rules = CrateRules {
+    ///     banned: vec!["openssl".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// let report = DependencyAnalyzer::new(".").with_crate_rules(rules).analyze()?;
+    /// assert!(!report.has_banned());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn with_crate_rules(mut self, rules: CrateRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Look heavy dependencies up in `database` instead of the built-in one
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use wasm_slim::analyzer::heavy_deps::HeavyDepsDatabase;
+    /// use wasm_slim::analyzer::DependencyAnalyzer;
+    /// use wasm_slim::config::ConfigLoader;
+    /// use std::path::Path;
+    ///
+    /// let config = ConfigLoader::load(Path::new("."))?;
+    /// let database = HeavyDepsDatabase::for_project(Path::new("."), &config)?;
+    /// let report = DependencyAnalyzer::new(".").with_heavy_deps(database).analyze()?;
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn with_heavy_deps(mut self, database: HeavyDepsDatabase) -> Self {
+        self.heavy_deps = database;
+        self
+    }
+
     /// Run full dependency analysis
     ///
     /// # Examples
@@ -101,12 +191,48 @@ impl DependencyAnalyzer {
     /// ```
     #[must_use = "Analysis results should be used or printed"]
     pub fn analyze(&self) -> Result<DependencyReport, DependencyAnalysisError> {
-        let metadata = MetadataCommand::new()
-            .current_dir(&self.project_root)
-            .exec()?;
+        match self.fetch_metadata(false) {
+            Ok(metadata) => self.analyze_with_metadata(&metadata),
+            Err(err) if self.offline => {
+                // Workspace manifests can still be read without the index
+                let metadata = self.fetch_metadata(true).map_err(|_| err)?;
+                self.analyze_declared_with_metadata(&metadata)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Run `cargo metadata`, optionally without resolving dependencies
+    ///
+    /// The resolved graph is filtered to what cargo builds for
+    /// `wasm32-unknown-unknown`, so native-only dependencies are not reported.
+    fn fetch_metadata(
+        &self,
+        no_deps: bool,
+    ) -> Result<cargo_metadata::Metadata, DependencyAnalysisError> {
+        let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+        let mut command = MetadataCommand::new();
+        command.current_dir(&self.project_root);
+        let mut options = Vec::new();
+        if self.offline {
+            options.push("--offline".to_string());
+        }
+        if no_deps {
+            command.no_deps();
+        } else {
+            options.extend(["--filter-platform".to_string(), WASM_TARGET.to_string()]);
+        }
+        command.other_options(options);
+        Ok(command.exec()?)
+    }
 
+    /// Analyze a fully resolved dependency graph
+    pub(crate) fn analyze_with_metadata(
+        &self,
+        metadata: &cargo_metadata::Metadata,
+    ) -> Result<DependencyReport, DependencyAnalysisError> {
         let mut issues = Vec::new();
-        let mut duplicates: HashMap<String, Vec<String>> = HashMap::new();
+        let mut duplicates: BTreeMap<String, Vec<String>> = BTreeMap::new();
 
         // Get workspace members
         let workspace_members: HashSet<_> = metadata.workspace_members.iter().collect();
@@ -119,25 +245,8 @@ impl DependencyAnalyzer {
             }
 
             // Check for heavy dependencies
-            if let Some(heavy_info) = get_heavy_dependency_info(&package.name) {
-                let severity = self.determine_severity(heavy_info.bundle_percent);
-
-                for alternative in &heavy_info.alternatives {
-                    issues.push(DependencyIssue {
-                        package: package.name.to_string(),
-                        version: package.version.to_string(),
-                        severity,
-                        issue: heavy_info.reason.to_string(),
-                        suggestion: self.format_suggestion(alternative),
-                        size_impact_kb: alternative.size_kb.map(|(current_min, current_max)| {
-                            let saved_min = heavy_info.size_kb.0.saturating_sub(current_max);
-                            let saved_max = heavy_info.size_kb.1.saturating_sub(current_min);
-                            (saved_min, saved_max)
-                        }),
-                        savings_percent: Some(alternative.savings_percent),
-                    });
-                }
-            }
+            issues
+                .extend(self.heavy_dependency_issues(&package.name, &package.version.to_string()));
 
             // Track duplicate versions
             duplicates
@@ -156,11 +265,16 @@ impl DependencyAnalyzer {
         // Check for allocator optimization opportunity
         let allocator_detector = AllocatorDetector::new(&self.project_root);
         if let Ok(Some(allocator_issue)) =
-            allocator_detector.check_allocator_optimization_with_metadata(&metadata)
+            allocator_detector.check_allocator_optimization_with_metadata(metadata)
         {
             issues.push(allocator_issue);
         }
 
+        // Forward-looking guidance on newer Wasm proposals
+        issues.extend(
+            ProposalAdvisor::new(&self.project_root).check_proposals_with_metadata(metadata),
+        );
+
         // Count dependencies
         let resolve = metadata
             .resolve
@@ -168,6 +282,23 @@ impl DependencyAnalyzer {
             .ok_or(DependencyAnalysisError::NoResolution)?;
         let total_deps = resolve.nodes.len();
 
+        // Rules apply to every crate in the graph, with its resolved features
+        let packages: HashMap<_, _> = metadata.packages.iter().map(|p| (&p.id, p)).collect();
+        let graph = resolve
+            .nodes
+            .iter()
+            .filter(|node| !workspace_members.contains(&node.id))
+            .filter_map(|node| {
+                let package = packages.get(&node.id)?;
+                let default_enabled = node.features.iter().any(|f| f.as_str() == "default");
+                Some((
+                    package.name.to_string(),
+                    package.version.to_string(),
+                    default_enabled,
+                ))
+            });
+        let rule_violations = self.rule_violations(graph)?;
+
         // Count direct dependencies
         let root_package = metadata
             .root_package()
@@ -183,9 +314,127 @@ impl DependencyAnalyzer {
             direct_deps,
             issues,
             duplicates,
+            duplicate_versions: super::version_dedupe::duplicate_versions(
+                metadata,
+                &self.heavy_deps,
+            ),
+            warnings: Vec::new(),
+            rule_violations,
+        })
+    }
+
+    /// Analyze only the dependencies declared by the root package
+    ///
+    /// Used offline when `cargo metadata` cannot resolve the graph; versions
+    /// are the requirement strings from `Cargo.toml`.
+    pub(crate) fn analyze_declared_with_metadata(
+        &self,
+        metadata: &cargo_metadata::Metadata,
+    ) -> Result<DependencyReport, DependencyAnalysisError> {
+        let root_package = metadata
+            .root_package()
+            .ok_or(DependencyAnalysisError::NoRootPackage)?;
+
+        let declared: Vec<_> = root_package
+            .dependencies
+            .iter()
+            .filter(|d| matches!(d.kind, DependencyKind::Normal))
+            .collect();
+
+        let issues = declared
+            .iter()
+            .flat_map(|dep| self.heavy_dependency_issues(&dep.name, &dep.req.to_string()))
+            .collect();
+        let rule_violations = self.rule_violations(declared.iter().map(|dep| {
+            (
+                dep.name.clone(),
+                dep.req.to_string(),
+                dep.uses_default_features,
+            )
+        }))?;
+
+        Ok(DependencyReport {
+            total_deps: declared.len(),
+            direct_deps: declared.len(),
+            issues,
+            duplicates: BTreeMap::new(),
+            duplicate_versions: Vec::new(),
+            warnings: vec![OFFLINE_DEGRADED_WARNING.to_string()],
+            rule_violations,
         })
     }
 
+    /// Match `(name, version, default features enabled)` crates against the rules
+    fn rule_violations(
+        &self,
+        crates: impl Iterator<Item = (String, String, bool)>,
+    ) -> Result<Vec<RuleViolation>, DependencyAnalysisError> {
+        let banned = self
+            .rules
+            .banned_crates()
+            .map_err(|e| DependencyAnalysisError::InvalidRules(e.to_string()))?;
+
+        let mut violations = Vec::new();
+        for (name, version, default_enabled) in crates {
+            let matched = banned.iter().zip(&self.rules.banned).find(|(ban, _)| {
+                ban.name == name && (default_enabled || !ban.default_features_only)
+            });
+            if let Some((_, rule)) = matched {
+                violations.push(RuleViolation {
+                    package: name,
+                    version,
+                    kind: RuleKind::Banned,
+                    rule: rule.clone(),
+                    hint: None,
+                });
+            } else if let Some(hint) = self.rules.discouraged.get(&name) {
+                violations.push(RuleViolation {
+                    rule: name.clone(),
+                    package: name,
+                    version,
+                    kind: RuleKind::Discouraged,
+                    hint: Some(hint.clone()),
+                });
+            }
+        }
+        // Banned crates first, then by name
+        violations.sort_by(|a, b| {
+            (a.kind != RuleKind::Banned, &a.package).cmp(&(b.kind != RuleKind::Banned, &b.package))
+        });
+        Ok(violations)
+    }
+
+    /// Issues for a dependency listed in the heavy-dependency database
+    fn heavy_dependency_issues(&self, name: &str, version: &str) -> Vec<DependencyIssue> {
+        let Some(heavy_info) = self.heavy_deps.get(name) else {
+            return Vec::new();
+        };
+        let severity = self.determine_severity(heavy_info.bundle_percent);
+        let (size_min, size_max) = self.heavy_deps.size_kb(name).unwrap_or(heavy_info.size_kb);
+
+        heavy_info
+            .alternatives
+            .iter()
+            .map(|alternative| DependencyIssue {
+                package: name.to_string(),
+                version: version.to_string(),
+                severity,
+                issue: heavy_info.reason.to_string(),
+                suggestion: self.format_suggestion(alternative),
+                size_impact_kb: self.heavy_deps.alternative_size_kb(alternative).map(
+                    |(current_min, current_max)| {
+                        let saved_min = size_min.saturating_sub(current_max);
+                        let saved_max = size_max.saturating_sub(current_min);
+                        (saved_min, saved_max)
+                    },
+                ),
+                savings_percent: Some(alternative.savings_percent),
+                verify_id: (alternative.alt_type == AlternativeType::FeatureMinimization)
+                    .then(|| format!("default-features:{}", name)),
+            })
+            .collect()
+    }
+
     fn determine_severity(&self, bundle_percent: Option<(u8, u8)>) -> IssueSeverity {
         match bundle_percent {
             Some((_, max)) if max >= 30 => IssueSeverity::Critical,
@@ -197,13 +446,15 @@ impl DependencyAnalyzer {
 
     fn format_suggestion(&self, alternative: &super::heavy_deps::DependencyAlternative) -> String {
         match alternative.alt_type {
-            AlternativeType::Replacement => {
-                format!(
-                    "Replace with {} ({})",
-                    alternative.crate_name.unwrap_or("alternative"),
+            AlternativeType::Replacement => match &alternative.crate_name {
+                Some(crate_name) => {
+                    format!("Replace with {} ({})", crate_name, alternative.description)
+                }
+                None => format!(
+                    "Replace with a custom implementation ({})",
                     alternative.description
-                )
-            }
+                ),
+            },
             AlternativeType::FeatureMinimization => {
                 format!("Use default-features = false ({})", alternative.description)
             }
@@ -290,4 +541,128 @@ mod tests {
         );
         assert_eq!(analyzer.determine_severity(None), IssueSeverity::Low);
     }
+
+    /// Metadata as produced by `cargo metadata --no-deps` for a single crate
+    fn unresolved_metadata(deps: &[(&str, &str)]) -> cargo_metadata::Metadata {
+        let dependencies: Vec<_> = deps
+            .iter()
+            .map(|(name, req)| {
+                serde_json::json!({
+                    "name": name,
+                    "source": "registry+https://github.com/rust-lang/crates.io-index",
+                    "req": req,
+                    "kind": null,
+                    "rename": null,
+                    "optional": false,
+                    "uses_default_features": true,
+                    "features": [],
+                    "target": null,
+                    "registry": null
+                })
+            })
+            .collect();
+
+        serde_json::from_value(serde_json::json!({
+            "packages": [{
+                "name": "app",
+                "version": "0.1.0",
+                "id": "app 0.1.0",
+                "license": null,
+                "license_file": null,
+                "description": null,
+                "source": null,
+                "dependencies": dependencies,
+                "targets": [],
+                "features": {},
+                "manifest_path": "/fake/Cargo.toml",
+                "metadata": null,
+                "publish": null,
+                "authors": [],
+                "categories": [],
+                "keywords": [],
+                "readme": null,
+                "repository": null,
+                "homepage": null,
+                "documentation": null,
+                "edition": "2021",
+                "links": null,
+                "default_run": null,
+                "rust_version": null
+            }],
+            "workspace_members": ["app 0.1.0"],
+            "workspace_default_members": ["app 0.1.0"],
+            "resolve": null,
+            "target_directory": "/fake/target",
+            "version": 1,
+            "workspace_root": "/fake",
+            "metadata": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_analyze_declared_with_metadata_checks_direct_dependencies() {
+        let analyzer = DependencyAnalyzer::new(".").with_offline(true);
+        let metadata = unresolved_metadata(&[("swc_core", "^0.90"), ("log", "^0.4")]);
+
+        let report = analyzer.analyze_declared_with_metadata(&metadata).unwrap();
+
+        assert_eq!(report.direct_deps, 2);
+        assert!(report.duplicates.is_empty());
+        assert!(!report.issues.is_empty());
+        assert!(report.issues.iter().all(|i| i.package == "swc_core"));
+        assert_eq!(report.issues[0].version, "^0.90");
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].starts_with("Offline"));
+    }
+
+    #[test]
+    fn test_rule_violations_match_banned_and_discouraged_crates() {
+        let rules = CrateRules {
+            banned: vec![
+                "openssl".to_string(),
+                "reqwest(default-features)".to_string(),
+            ],
+            discouraged: [("chrono".to_string(), "Use `time`".to_string())].into(),
+        };
+        let analyzer = DependencyAnalyzer::new(".").with_crate_rules(rules);
+        let graph = [
+            ("chrono", "0.4.38", true),
+            ("reqwest", "0.12.0", false),
+            ("openssl", "0.10.0", false),
+            ("serde", "1.0.0", true),
+        ]
+        .map(|(name, version, default)| (name.to_string(), version.to_string(), default));
+
+        let violations = analyzer.rule_violations(graph.into_iter()).unwrap();
+        let found: Vec<(&str, RuleKind)> = violations
+            .iter()
+            .map(|v| (v.package.as_str(), v.kind))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("openssl", RuleKind::Banned),
+                ("chrono", RuleKind::Discouraged)
+            ]
+        );
+        assert_eq!(violations[1].hint.as_deref(), Some("Use `time`"));
+
+        // Declared dependencies use default features in the fixture
+        let metadata = unresolved_metadata(&[("reqwest", "^0.12")]);
+        let report = analyzer.analyze_declared_with_metadata(&metadata).unwrap();
+        assert!(report.has_banned());
+        assert_eq!(report.rule_violations[0].rule, "reqwest(default-features)");
+    }
+
+    #[test]
+    fn test_analyze_with_metadata_requires_resolution() {
+        let analyzer = DependencyAnalyzer::new(".");
+        let metadata = unresolved_metadata(&[("swc_core", "^0.90")]);
+
+        assert!(matches!(
+            analyzer.analyze_with_metadata(&metadata),
+            Err(DependencyAnalysisError::NoResolution)
+        ));
+    }
 }
@@ -9,7 +9,7 @@
 //! - Colored terminal output
 //! - Issue grouping by severity
 //! - Size impact formatting
-//! - Duplicate version detection display
+//! - Duplicate version detection display, with estimated cost and fixes
 //!
 //! # Examples
 //!
@@ -22,9 +22,13 @@
 //! print_dependency_report(&report);
 //! ```
 
+use crate::i18n::t;
 use console::style;
 
-use super::deps::{DependencyIssue, DependencyReport, IssueSeverity};
+use super::deps::{
+    DedupeFix, DependencyIssue, DependencyReport, DuplicateVersion, IssueSeverity, RuleKind,
+};
+use super::report_utils::{format_bytes, omitted_footer, ItemLimit};
 
 /// Print formatted dependency analysis report to console
 ///
@@ -49,34 +53,73 @@ use super::deps::{DependencyIssue, DependencyReport, IssueSeverity};
 /// print_dependency_report(&report);
 /// ```
 pub fn print_dependency_report(report: &DependencyReport) {
+    print_dependency_report_with_limit(report, ItemLimit::Default);
+}
+
+/// Print dependency analysis report, limiting the issues listed per severity
+/// and the duplicate crates listed
+pub fn print_dependency_report_with_limit(report: &DependencyReport, limit: ItemLimit) {
     println!(
-        "\n{} {} Dependency Analysis Report",
+        "\n{} {} {}",
         style("📦").bold(),
-        style("WASM").cyan().bold()
+        style("WASM").cyan().bold(),
+        t("deps.report_title")
     );
     println!(
-        "   {} Total dependencies: {}",
+        "   {} {} {}",
         style("→").dim(),
+        t("deps.total"),
         style(report.total_deps).yellow()
     );
     println!(
-        "   {} Direct dependencies: {}",
+        "   {} {} {}",
         style("→").dim(),
+        t("deps.direct"),
         style(report.direct_deps).yellow()
     );
+    for warning in &report.warnings {
+        println!("   {} {}", style("⚠️").bold(), style(warning).yellow());
+    }
+
+    // Banned and discouraged crates, never truncated
+    if !report.rule_violations.is_empty() {
+        println!("\n{} {}", style("🚫").bold(), t("deps.rule_violations"));
+        for violation in &report.rule_violations {
+            let (label, hint) = match violation.kind {
+                RuleKind::Banned => (
+                    style("banned").red().bold(),
+                    format!("matches rule \"{}\"", violation.rule),
+                ),
+                RuleKind::Discouraged => (
+                    style("discouraged").yellow(),
+                    violation.hint.clone().unwrap_or_default(),
+                ),
+            };
+            println!(
+                "   {} {} {} ({}): {}",
+                style("→").dim(),
+                label,
+                style(&violation.package).cyan(),
+                violation.version,
+                hint
+            );
+        }
+    }
 
     if !report.issues.is_empty() {
         println!(
-            "\n{} {} Optimization Opportunities Found",
+            "\n{} {} {}",
             style("🔍").bold(),
-            style(report.issues.len()).yellow().bold()
+            style(report.issues.len()).yellow().bold(),
+            t("deps.opportunities_found")
         );
 
         let (min_kb, max_kb) = report.total_estimated_savings_kb();
         if max_kb > 0 {
             println!(
-                "   {} Estimated savings: {}-{} KB",
+                "   {} {} {}-{} KB",
                 style("→").dim(),
+                t("deps.estimated_savings"),
                 style(min_kb).green().bold(),
                 style(max_kb).green().bold()
             );
@@ -103,37 +146,100 @@ pub fn print_dependency_report(report: &DependencyReport) {
                     severity_issues.len()
                 );
 
-                for issue in severity_issues {
+                let shown = limit.visible(severity_issues.len(), None);
+                for issue in severity_issues.iter().take(shown) {
                     print_issue(issue);
                 }
+                if let Some(footer) = omitted_footer(severity_issues.len(), shown, "issues") {
+                    println!("\n   {}", style(footer).dim());
+                }
             }
         }
     } else {
         println!(
-            "\n{} {} All dependencies optimized!",
+            "\n{} {} {}",
             style("✅").bold(),
-            style("Great!").green().bold()
+            style("Great!").green().bold(),
+            t("deps.all_optimized")
         );
     }
 
     // Duplicate versions
     if !report.duplicates.is_empty() {
         println!(
-            "\n{} {} Duplicate Versions Detected",
+            "\n{} {} {}",
             style("⚠️").bold(),
-            style("Warning").yellow().bold()
+            style("Warning").yellow().bold(),
+            t("deps.duplicates")
         );
-        for (name, versions) in &report.duplicates {
-            println!(
-                "   {} {}: {}",
-                style("→").dim(),
-                style(name).yellow(),
-                versions.join(", ")
-            );
+        if report.duplicate_versions.is_empty() {
+            let shown = limit.visible(report.duplicates.len(), None);
+            for (name, versions) in report.duplicates.iter().take(shown) {
+                println!(
+                    "   {} {}: {}",
+                    style("→").dim(),
+                    style(name).yellow(),
+                    versions.join(", ")
+                );
+            }
+            if let Some(footer) = omitted_footer(report.duplicates.len(), shown, "crates") {
+                println!("   {}", style(footer).dim());
+            }
+        } else {
+            print_duplicate_versions(&report.duplicate_versions, limit);
         }
     }
 }
 
+/// Print duplicated crates with their estimated cost and fixes, costliest first
+fn print_duplicate_versions(duplicates: &[DuplicateVersion], limit: ItemLimit) {
+    let total: u64 = duplicates.iter().map(|d| d.estimated_cost_bytes).sum();
+    if total > 0 {
+        println!(
+            "   {} Extra copies cost an estimated {}",
+            style("→").dim(),
+            style(format_bytes(total)).yellow().bold()
+        );
+    }
+
+    let shown = limit.visible(duplicates.len(), None);
+    for duplicate in duplicates.iter().take(shown) {
+        let cost = if duplicate.estimated_cost_bytes > 0 {
+            format!(" (~{})", format_bytes(duplicate.estimated_cost_bytes))
+        } else {
+            String::new()
+        };
+        println!(
+            "   {} {}: {}{}",
+            style("→").dim(),
+            style(&duplicate.package).yellow(),
+            duplicate.versions.join(", "),
+            style(cost).dim()
+        );
+        for fix in &duplicate.fixes {
+            let marker = if fix.is_applicable() {
+                style("fix").green()
+            } else {
+                style("manual").dim()
+            };
+            println!("       {} {}", marker, fix);
+        }
+    }
+    if let Some(footer) = omitted_footer(duplicates.len(), shown, "crates") {
+        println!("   {}", style(footer).dim());
+    }
+    if duplicates
+        .iter()
+        .flat_map(|d| &d.fixes)
+        .any(DedupeFix::is_applicable)
+    {
+        println!(
+            "   {} Run with --fix to apply the marked fixes",
+            style("💡").bold()
+        );
+    }
+}
+
 /// Print a single dependency issue with formatting
 ///
 /// Displays:
@@ -167,4 +273,11 @@ fn print_issue(issue: &DependencyIssue) {
         style("Fix:").dim(),
         style(&issue.suggestion).green()
     );
+    if let Some(id) = &issue.verify_id {
+        println!(
+            "   {} wasm-slim verify-recommendation {}",
+            style("Verify:").dim(),
+            style(id).cyan()
+        );
+    }
 }
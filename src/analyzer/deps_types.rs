@@ -3,7 +3,8 @@
 //! Extracted from deps.rs to follow Single Responsibility Principle.
 //! This module contains only the data structures, while deps.rs handles the analysis logic.
 
-use std::collections::HashMap;
+use super::report_utils::SizedEntry;
+use std::collections::BTreeMap;
 
 /// Issue severity levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
@@ -47,6 +48,143 @@ pub struct DependencyIssue {
     pub size_impact_kb: Option<(u32, u32)>,
     /// Estimated savings percentage
     pub savings_percent: Option<u8>,
+    /// Verification ID for `wasm-slim verify-recommendation` (if measurable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify_id: Option<String>,
+}
+
+impl SizedEntry for DependencyIssue {
+    fn size_bytes(&self) -> Option<u64> {
+        self.size_impact_kb
+            .map(|(_, high_kb)| u64::from(high_kb) * 1024)
+    }
+
+    fn name(&self) -> &str {
+        &self.package
+    }
+}
+
+/// Whether a dependency rule forbids a crate or only advises against it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleKind {
+    /// Listed in `[crates] banned`; fails analysis and builds
+    Banned,
+    /// Listed in `[crates.discouraged]`; produces a warning
+    Discouraged,
+}
+
+/// A banned or discouraged crate found in the dependency graph
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RuleViolation {
+    /// Package name
+    pub package: String,
+    /// Version (requirement string when only declared dependencies were analyzed)
+    pub version: String,
+    /// Rule kind
+    pub kind: RuleKind,
+    /// The rule as written in the config
+    pub rule: String,
+    /// Replacement hint for discouraged crates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// A step that moves a duplicated crate onto a single version
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DedupeFix {
+    /// Move a semver-compatible copy onto the newest version in Cargo.lock
+    CargoUpdate {
+        /// Duplicated package
+        package: String,
+        /// Version to replace
+        from: String,
+        /// Version to move to
+        to: String,
+    },
+    /// Raise a workspace member's requirement so it accepts the newest version
+    UnifyRequirement {
+        /// Workspace member declaring the dependency
+        member: String,
+        /// Dependency key in `[dependencies]`
+        dependency: String,
+        /// Current requirement
+        from: String,
+        /// Requirement matching the newest version
+        to: String,
+    },
+    /// Upgrade a crate that pins an older, incompatible version (manual)
+    UpgradeDependent {
+        /// Crate holding the old version in place
+        dependent: String,
+        /// Its resolved version
+        version: String,
+        /// Duplicated package
+        package: String,
+        /// Requirement it declares on the duplicated package
+        requires: String,
+    },
+}
+
+impl DedupeFix {
+    /// Whether `SuggestionApplicator` can apply this fix
+    pub fn is_applicable(&self) -> bool {
+        !matches!(self, DedupeFix::UpgradeDependent { .. })
+    }
+}
+
+impl std::fmt::Display for DedupeFix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DedupeFix::CargoUpdate { package, from, to } => {
+                write!(f, "cargo update -p {}@{} --precise {}", package, from, to)
+            }
+            DedupeFix::UnifyRequirement {
+                member,
+                dependency,
+                from,
+                to,
+            } => write!(
+                f,
+                "set {} = \"{}\" in {}'s Cargo.toml (currently {})",
+                dependency, to, member, from
+            ),
+            DedupeFix::UpgradeDependent {
+                dependent,
+                version,
+                package,
+                requires,
+            } => write!(
+                f,
+                "upgrade {} {}, which requires {} {}",
+                dependent, version, package, requires
+            ),
+        }
+    }
+}
+
+/// A crate resolved at more than one version, with its cost and remediation
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DuplicateVersion {
+    /// Package name
+    pub package: String,
+    /// Resolved versions, oldest first
+    pub versions: Vec<String>,
+    /// Estimated size of the copies beyond the newest one
+    pub estimated_cost_bytes: u64,
+    /// Steps that would unify the copies on the newest version
+    pub fixes: Vec<DedupeFix>,
+}
+
+impl SizedEntry for DuplicateVersion {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.estimated_cost_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.package
+    }
 }
 
 /// Full dependency analysis report
@@ -59,7 +197,16 @@ pub struct DependencyReport {
     /// Detected issues
     pub issues: Vec<DependencyIssue>,
     /// Duplicate versions
-    pub duplicates: HashMap<String, Vec<String>>,
+    pub duplicates: BTreeMap<String, Vec<String>>,
+    /// Estimated cost and fixes for each duplicated crate, costliest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub duplicate_versions: Vec<DuplicateVersion>,
+    /// Caveats about how complete the analysis is (e.g., offline fallback)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Banned and discouraged crates from `[crates]` rules
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rule_violations: Vec<RuleViolation>,
 }
 
 impl DependencyReport {
@@ -128,6 +275,13 @@ impl DependencyReport {
         (min, max)
     }
 
+    /// Whether the graph contains a banned crate
+    pub fn has_banned(&self) -> bool {
+        self.rule_violations
+            .iter()
+            .any(|v| v.kind == RuleKind::Banned)
+    }
+
     /// Print formatted dependency report to console
     ///
     /// Delegates to [`crate::analyzer::deps_report::print_dependency_report`]
@@ -144,6 +298,11 @@ impl DependencyReport {
     pub fn print_report(&self) {
         super::deps_report::print_dependency_report(self);
     }
+
+    /// Print the report, limiting how many items each list shows
+    pub fn print_report_with_limit(&self, limit: super::report_utils::ItemLimit) {
+        super::deps_report::print_dependency_report_with_limit(self, limit);
+    }
 }
 
 #[cfg(test)]
@@ -180,6 +339,7 @@ mod tests {
                     suggestion: "test".to_string(),
                     size_impact_kb: None,
                     savings_percent: None,
+                    verify_id: None,
                 },
                 DependencyIssue {
                     package: "test2".to_string(),
@@ -189,9 +349,13 @@ mod tests {
                     suggestion: "test".to_string(),
                     size_impact_kb: None,
                     savings_percent: None,
+                    verify_id: None,
                 },
             ],
-            duplicates: HashMap::new(),
+            duplicates: BTreeMap::new(),
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
         };
 
         let critical = report.issues_by_severity(IssueSeverity::Critical);
@@ -213,6 +377,7 @@ mod tests {
                     suggestion: "test".to_string(),
                     size_impact_kb: Some((100, 200)),
                     savings_percent: Some(50),
+                    verify_id: None,
                 },
                 DependencyIssue {
                     package: "test2".to_string(),
@@ -222,13 +387,44 @@ mod tests {
                     suggestion: "test".to_string(),
                     size_impact_kb: Some((50, 100)),
                     savings_percent: Some(20),
+                    verify_id: None,
                 },
             ],
-            duplicates: HashMap::new(),
+            duplicates: BTreeMap::new(),
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
         };
 
         let (min, max) = report.total_estimated_savings_kb();
         assert_eq!(min, 150);
         assert_eq!(max, 300);
     }
+
+    #[test]
+    fn test_dependency_report_duplicates_serialize_in_name_order() {
+        let mut duplicates = BTreeMap::new();
+        duplicates.insert(
+            "syn".to_string(),
+            vec!["1.0".to_string(), "2.0".to_string()],
+        );
+        duplicates.insert(
+            "bitflags".to_string(),
+            vec!["1.3".to_string(), "2.4".to_string()],
+        );
+        let report = DependencyReport {
+            total_deps: 10,
+            direct_deps: 5,
+            issues: Vec::new(),
+            duplicates,
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
+        };
+
+        let json = serde_json::to_string(&report).unwrap();
+        let bitflags = json.find("\"bitflags\"").unwrap();
+        let syn = json.find("\"syn\"").unwrap();
+        assert!(bitflags < syn);
+    }
 }
@@ -0,0 +1,323 @@
+//! Duplicate function body detection
+//!
+//! Groups the functions of a WASM module whose bodies are byte-identical,
+//! or identical apart from their immediates (call targets, constants, local
+//! and global indices). Generic code instantiated with types that share a
+//! layout often compiles to such copies. Identical bodies can be folded
+//! into one function; similar ones can be merged into one function that
+//! takes the differing immediates as parameters, behind small thunks.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::DuplicateFunctionAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = DuplicateFunctionAnalyzer::new().analyze(Path::new("pkg/app_bg.wasm"))?;
+//! for group in &report.groups {
+//!     println!("{} copies, {} bytes to save", group.functions.len(), group.potential_savings_bytes);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use super::twiggy::native::{demangle, function_names};
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::mem::{discriminant, Discriminant};
+use std::path::Path;
+use wasmparser::{Operator, Parser, Payload, TypeRef, ValType};
+
+/// Estimated size of the thunk that forwards to a merged function
+///
+/// Covers the function entry, a `local.get` per parameter, the extra
+/// immediates passed as constants and the `call`.
+const MERGE_THUNK_BYTES: u64 = 12;
+
+/// How closely the functions of a group match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateKind {
+    /// Byte-identical bodies
+    Identical,
+    /// Same instructions, different immediates
+    Similar,
+}
+
+/// A function in a duplicate group
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateFunction {
+    /// Function index (imports included)
+    pub index: u32,
+    /// Demangled name, or `code[N]` without a name section
+    pub name: String,
+    /// Body size in bytes
+    pub size_bytes: u64,
+}
+
+/// Functions that could be merged into one
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateGroup {
+    /// How closely the bodies match
+    pub kind: DuplicateKind,
+    /// Functions in the group, by index
+    pub functions: Vec<DuplicateFunction>,
+    /// Sum of the body sizes
+    pub total_bytes: u64,
+    /// Estimated saving from keeping one body
+    pub potential_savings_bytes: u64,
+}
+
+impl SizedEntry for DuplicateGroup {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.potential_savings_bytes)
+    }
+
+    fn name(&self) -> &str {
+        self.functions.first().map_or("", |f| f.name.as_str())
+    }
+
+    fn matches(&self, pattern: &Regex) -> bool {
+        self.functions.iter().any(|f| pattern.is_match(&f.name))
+    }
+}
+
+/// Duplicate bodies found in a module
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateReport {
+    /// Size of the code section
+    pub code_bytes: u64,
+    /// Number of function bodies compared
+    pub functions_scanned: usize,
+    /// Groups, largest saving first
+    pub groups: Vec<DuplicateGroup>,
+}
+
+impl DuplicateReport {
+    /// Total estimated saving of the groups of one kind
+    pub fn savings_bytes(&self, kind: DuplicateKind) -> u64 {
+        self.groups
+            .iter()
+            .filter(|g| g.kind == kind)
+            .map(|g| g.potential_savings_bytes)
+            .sum()
+    }
+}
+
+/// One function body, reduced to what grouping compares
+struct Body<'a> {
+    function: DuplicateFunction,
+    type_index: u32,
+    bytes: &'a [u8],
+    /// Locals and instruction sequence without immediates
+    shape: (Vec<(u32, ValType)>, Vec<Discriminant<Operator<'a>>>),
+}
+
+/// Finds identical and near-identical function bodies
+#[derive(Debug, Default)]
+pub struct DuplicateFunctionAnalyzer;
+
+impl DuplicateFunctionAnalyzer {
+    /// Create an analyzer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Group the duplicate function bodies of a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<DuplicateReport> {
+        let bytes = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        analyze_module(&bytes).with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+}
+
+/// Group the duplicate function bodies of a module
+pub fn analyze_module(module: &[u8]) -> Result<DuplicateReport> {
+    let mut imported_functions = 0;
+    let mut type_indices = Vec::new();
+    let mut bodies = Vec::new();
+    let mut names = HashMap::new();
+    let mut code_bytes = 0;
+    for payload in Parser::new(0).parse_all(module) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader.into_imports() {
+                    if matches!(import?.ty, TypeRef::Func(_) | TypeRef::FuncExact(_)) {
+                        imported_functions += 1;
+                    }
+                }
+            }
+            Payload::FunctionSection(reader) => {
+                type_indices = reader.into_iter().collect::<Result<_, _>>()?;
+            }
+            Payload::CodeSectionStart { range, .. } => code_bytes = range.len() as u64,
+            Payload::CodeSectionEntry(body) => {
+                let defined = bodies.len();
+                let locals = body
+                    .get_locals_reader()?
+                    .into_iter()
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut operators = Vec::new();
+                let mut reader = body.get_operators_reader()?;
+                while !reader.eof() {
+                    operators.push(discriminant(&reader.read()?));
+                }
+                let range = body.range();
+                bodies.push(Body {
+                    function: DuplicateFunction {
+                        index: imported_functions + defined as u32,
+                        name: format!("code[{}]", defined),
+                        size_bytes: range.len() as u64,
+                    },
+                    type_index: type_indices.get(defined).copied().unwrap_or(u32::MAX),
+                    bytes: &module[range],
+                    shape: (locals, operators),
+                });
+            }
+            Payload::CustomSection(reader) => {
+                for (index, name) in function_names(&reader).into_iter().flatten() {
+                    names.insert(index, demangle(name));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for body in &mut bodies {
+        if let Some(name) = names.remove(&body.function.index) {
+            body.function.name = name;
+        }
+    }
+
+    Ok(DuplicateReport {
+        code_bytes,
+        functions_scanned: bodies.len(),
+        groups: group_bodies(&bodies),
+    })
+}
+
+/// Group bodies by type and shape, then tell identical groups from similar ones
+///
+/// A shape group whose bodies are all byte-identical is reported as
+/// identical; otherwise the whole group is reported as similar. Similar
+/// groups whose thunks would cost more than the merge saves are dropped.
+fn group_bodies(bodies: &[Body<'_>]) -> Vec<DuplicateGroup> {
+    let mut by_shape: HashMap<_, Vec<&Body<'_>>> = HashMap::new();
+    for body in bodies {
+        by_shape
+            .entry((body.type_index, &body.shape))
+            .or_default()
+            .push(body);
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_shape
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .filter_map(|members| {
+            let identical = members.iter().all(|b| b.bytes == members[0].bytes);
+            let total_bytes: u64 = members.iter().map(|b| b.function.size_bytes).sum();
+            let largest = members
+                .iter()
+                .map(|b| b.function.size_bytes)
+                .max()
+                .unwrap_or(0);
+            let (kind, potential_savings_bytes) = if identical {
+                (DuplicateKind::Identical, total_bytes - largest)
+            } else {
+                let thunks = members.len() as u64 * MERGE_THUNK_BYTES;
+                (
+                    DuplicateKind::Similar,
+                    (total_bytes - largest).saturating_sub(thunks),
+                )
+            };
+            (potential_savings_bytes > 0).then(|| DuplicateGroup {
+                kind,
+                functions: members.iter().map(|b| b.function.clone()).collect(),
+                total_bytes,
+                potential_savings_bytes,
+            })
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.potential_savings_bytes
+            .cmp(&a.potential_savings_bytes)
+            .then_with(|| a.functions[0].index.cmp(&b.functions[0].index))
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Module with one `(i32) -> i32` type, one imported function and the
+    /// given bodies (without their size prefix)
+    fn module(bodies: &[&[u8]]) -> Vec<u8> {
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        module.extend([1, 6, 1, 0x60, 1, 0x7f, 1, 0x7f]);
+        module.extend([2, 7, 1, 1, b'e', 1, b'f', 0, 0]);
+        let mut functions = vec![bodies.len() as u8];
+        functions.extend(std::iter::repeat_n(0, bodies.len()));
+        module.push(3);
+        module.push(functions.len() as u8);
+        module.extend(functions);
+        let mut code = vec![bodies.len() as u8];
+        for body in bodies {
+            code.push(body.len() as u8);
+            code.extend_from_slice(body);
+        }
+        module.push(10);
+        module.push(code.len() as u8);
+        module.extend(code);
+        module
+    }
+
+    /// `local.get 0; i32.const N; i32.add; call F; end`, padded with `nop`s
+    fn body(constant: u8, callee: u8) -> Vec<u8> {
+        let mut body = vec![0, 0x20, 0, 0x41, constant, 0x6a, 0x10, callee];
+        body.extend([0x01; 16]);
+        body.push(0x0b);
+        body
+    }
+
+    #[test]
+    fn test_groups_identical_and_similar_bodies() {
+        let bodies = [
+            body(1, 0),
+            body(1, 0),
+            body(2, 0),
+            body(3, 1),
+            // Different shape
+            vec![0, 0x20, 0, 0x0b],
+        ];
+        let bodies: Vec<&[u8]> = bodies.iter().map(Vec::as_slice).collect();
+
+        let report = analyze_module(&module(&bodies)).unwrap();
+        assert_eq!(report.functions_scanned, 5);
+        assert_eq!(report.groups.len(), 1);
+        let group = &report.groups[0];
+        assert_eq!(group.kind, DuplicateKind::Similar);
+        let indices: Vec<u32> = group.functions.iter().map(|f| f.index).collect();
+        assert_eq!(indices, vec![1, 2, 3, 4]);
+        assert_eq!(group.functions[0].name, "code[0]");
+        assert_eq!(group.total_bytes, 4 * 25);
+        assert_eq!(
+            group.potential_savings_bytes,
+            3 * 25 - 4 * MERGE_THUNK_BYTES
+        );
+    }
+
+    #[test]
+    fn test_identical_bodies_save_all_but_one_copy() {
+        let bodies = [body(7, 0), body(7, 0), body(7, 0)];
+        let bodies: Vec<&[u8]> = bodies.iter().map(Vec::as_slice).collect();
+
+        let report = analyze_module(&module(&bodies)).unwrap();
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.groups[0].kind, DuplicateKind::Identical);
+        assert_eq!(report.savings_bytes(DuplicateKind::Identical), 2 * 25);
+        assert_eq!(report.savings_bytes(DuplicateKind::Similar), 0);
+    }
+}
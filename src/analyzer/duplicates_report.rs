@@ -0,0 +1,140 @@
+//! Duplicate function report formatting
+
+use super::duplicates::{DuplicateKind, DuplicateReport};
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of groups listed by default
+const DEFAULT_TOP_GROUPS: usize = 15;
+
+/// Functions listed per group
+const FUNCTIONS_PER_GROUP: usize = 4;
+
+/// Format the duplicate function report for console output
+pub fn format_console_report(report: &DuplicateReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the duplicate function report, limiting the groups listed
+pub fn format_console_report_with_limit(
+    report: &DuplicateReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Duplicate Function Bodies").bold().underlined()
+    )?;
+    if report.groups.is_empty() {
+        writeln!(
+            output,
+            "\n   {} No duplicate bodies among {} functions",
+            style("✓").green(),
+            report.functions_scanned
+        )?;
+        return Ok(output);
+    }
+
+    let identical = report.savings_bytes(DuplicateKind::Identical);
+    let similar = report.savings_bytes(DuplicateKind::Similar);
+    writeln!(
+        output,
+        "\n   {} groups among {} functions ({} of code)",
+        style(report.groups.len()).cyan().bold(),
+        report.functions_scanned,
+        format_bytes(report.code_bytes)
+    )?;
+    writeln!(
+        output,
+        "   Identical bodies: {} to save by folding (wasm-opt --duplicate-function-elimination)",
+        style(format_bytes(identical)).yellow().bold()
+    )?;
+    writeln!(
+        output,
+        "   Similar bodies:   ~{} to save by merging (wasm-opt --merge-similar-functions)",
+        style(format_bytes(similar)).yellow()
+    )?;
+
+    let shown = limit.visible(report.groups.len(), Some(DEFAULT_TOP_GROUPS));
+    for group in report.groups.iter().take(shown) {
+        let kind = match group.kind {
+            DuplicateKind::Identical => style("identical").red(),
+            DuplicateKind::Similar => style("similar").yellow(),
+        };
+        writeln!(
+            output,
+            "\n  {} {} × {} — save {}",
+            kind,
+            group.functions.len(),
+            format_bytes(group.total_bytes / group.functions.len() as u64),
+            style(format_bytes(group.potential_savings_bytes)).bold()
+        )?;
+        for function in group.functions.iter().take(FUNCTIONS_PER_GROUP) {
+            writeln!(
+                output,
+                "    {}",
+                style(truncate_str(&function.name, 90)).dim()
+            )?;
+        }
+        if let Some(footer) =
+            omitted_footer(group.functions.len(), FUNCTIONS_PER_GROUP, "functions")
+        {
+            writeln!(output, "    {}", style(footer).dim())?;
+        }
+    }
+    if let Some(footer) = omitted_footer(report.groups.len(), shown, "groups") {
+        writeln!(output, "\n  {}", style(footer).dim())?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::duplicates::{DuplicateFunction, DuplicateGroup};
+
+    fn group(kind: DuplicateKind, names: &[&str], savings: u64) -> DuplicateGroup {
+        DuplicateGroup {
+            kind,
+            functions: names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| DuplicateFunction {
+                    index: index as u32,
+                    name: name.to_string(),
+                    size_bytes: 100,
+                })
+                .collect(),
+            total_bytes: 100 * names.len() as u64,
+            potential_savings_bytes: savings,
+        }
+    }
+
+    #[test]
+    fn test_format_console_report_summarizes_savings_per_kind() {
+        let report = DuplicateReport {
+            code_bytes: 10_000,
+            functions_scanned: 40,
+            groups: vec![
+                group(
+                    DuplicateKind::Identical,
+                    &["a::f", "b::f", "c::f", "d::f", "e::f"],
+                    400,
+                ),
+                group(DuplicateKind::Similar, &["x::g", "y::g"], 76),
+            ],
+        };
+
+        let text = format_console_report_with_limit(&report, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("2 groups among 40 functions"));
+        assert!(text.contains("400 B to save by folding"));
+        assert!(text.contains("~76 B to save by merging"));
+        assert!(text.contains("1 more functions"));
+        assert!(!text.contains("x::g"));
+        assert!(text.contains("1 more groups"));
+    }
+}
@@ -0,0 +1,219 @@
+//! Measured size of individual features via trial builds
+//!
+//! `analyze --mode features --measure` replaces the estimated impact of each
+//! flagged feature with a measurement: the crate is built once as-is and
+//! once per feature with that feature turned off in a copy of Cargo.toml,
+//! all through the regular build pipeline in scratch target directories under
+//! `.wasm-slim/scratch/feature-trials/`. The project itself is never modified.
+//!
+//! Only features the project switches on itself can be toggled: `default`
+//! (via `default-features = false`) and features listed on a direct
+//! dependency. Anything else is reported as skipped with the reason.
+
+use super::features::UnusedFeature;
+use super::verify::{Experiment, RecommendationVerifier, VerificationError};
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Scratch directory (relative to the project root) for trial builds
+pub const FEATURE_TRIALS_DIR: &str = "feature-trials";
+
+/// Trial build results for the flagged features
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureMeasurements {
+    /// Size of the unmodified build
+    pub baseline_bytes: u64,
+    /// One entry per flagged feature
+    pub features: Vec<FeatureMeasurement>,
+}
+
+/// Trial build result for one feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureMeasurement {
+    /// Dependency the feature belongs to
+    pub package: String,
+    /// Feature turned off
+    pub feature: String,
+    /// Size of the build without the feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial_bytes: Option<u64>,
+    /// Measured size difference (negative = smaller)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta_bytes: Option<i64>,
+    /// Why the feature was not measured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+/// Measures flagged features with trial builds
+pub struct FeatureMeasurer<
+    FS: FileSystem + Clone = RealFileSystem,
+    CE: CommandExecutor + Clone = RealCommandExecutor,
+> {
+    project_root: PathBuf,
+    verifier: RecommendationVerifier<FS, CE>,
+}
+
+impl FeatureMeasurer {
+    /// Create a measurer for the given project root
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self::with_executors(project_root, RealFileSystem, RealCommandExecutor)
+    }
+}
+
+impl<FS: FileSystem + Clone, CE: CommandExecutor + Clone> FeatureMeasurer<FS, CE> {
+    /// Create a measurer with custom filesystem and command executors
+    pub fn with_executors(project_root: impl AsRef<Path>, fs: FS, cmd_executor: CE) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+            verifier: RecommendationVerifier::with_executors(project_root, fs, cmd_executor),
+        }
+    }
+
+    /// Build the project without each of `features`, one at a time
+    ///
+    /// Fails only when the baseline cannot be built.
+    pub fn measure(
+        &self,
+        features: &[UnusedFeature],
+    ) -> Result<FeatureMeasurements, VerificationError> {
+        let mut candidates: Vec<(&str, &str)> = features
+            .iter()
+            .map(|f| (f.package.as_str(), f.feature.as_str()))
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let experiments: Vec<Experiment> = candidates
+            .iter()
+            .map(|(package, feature)| trial_experiment(package, feature))
+            .collect();
+        let scratch_dir = crate::state::scratch_dir(&self.project_root).join(FEATURE_TRIALS_DIR);
+        let (baseline_bytes, results) = self.verifier.verify_all(&experiments, &scratch_dir)?;
+
+        let features = candidates
+            .iter()
+            .zip(results)
+            .map(|((package, feature), result)| {
+                let mut measurement = FeatureMeasurement {
+                    package: package.to_string(),
+                    feature: feature.to_string(),
+                    trial_bytes: None,
+                    delta_bytes: None,
+                    skipped: None,
+                };
+                match result {
+                    Ok(result) => {
+                        measurement.trial_bytes = Some(result.experiment_bytes);
+                        measurement.delta_bytes = Some(result.delta_bytes);
+                    }
+                    Err(e) => measurement.skipped = Some(e.to_string()),
+                }
+                measurement
+            })
+            .collect();
+
+        Ok(FeatureMeasurements {
+            baseline_bytes,
+            features,
+        })
+    }
+}
+
+/// Experiment turning off one feature of a dependency
+fn trial_experiment(package: &str, feature: &str) -> Experiment {
+    if feature == "default" {
+        Experiment::DisableDefaultFeatures {
+            package: package.to_string(),
+        }
+    } else {
+        Experiment::DisableFeature {
+            package: package.to_string(),
+            feature: feature.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::process::{Command, ExitStatus, Output};
+
+    /// Pretends to build, writing a module whose size depends on the
+    /// features left in the copied Cargo.toml
+    #[derive(Clone)]
+    struct FakeCargo;
+
+    impl CommandExecutor for FakeCargo {
+        fn status(&self, cmd: &mut Command) -> io::Result<ExitStatus> {
+            let dir = cmd.get_current_dir().unwrap().to_path_buf();
+            let args: Vec<_> = cmd.get_args().map(|a| a.to_owned()).collect();
+            let target = args
+                .iter()
+                .position(|a| a == "--target-dir")
+                .map(|i| PathBuf::from(&args[i + 1]))
+                .unwrap();
+            let manifest = std::fs::read_to_string(dir.join("Cargo.toml"))?;
+            let size = if manifest.contains("\"unicode\"") {
+                1000
+            } else {
+                700
+            };
+            let out = target.join("wasm32-unknown-unknown/release");
+            std::fs::create_dir_all(&out)?;
+            std::fs::write(out.join("app.wasm"), vec![0u8; size])?;
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, cmd: &mut Command) -> io::Result<Output> {
+            Ok(Output {
+                status: self.status(cmd)?,
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    fn flagged(package: &str, feature: &str) -> UnusedFeature {
+        UnusedFeature {
+            package: package.to_string(),
+            feature: feature.to_string(),
+            enabled_by: "explicit".to_string(),
+            estimated_impact_kb: 30,
+            confidence: "Low".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_measure_reports_delta_and_skips_unlisted_features() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[dependencies]\nregex = { version = \"1\", features = [\"std\", \"unicode\"] }\n",
+        )
+        .unwrap();
+        let measurer = FeatureMeasurer::with_executors(temp.path(), RealFileSystem, FakeCargo);
+
+        let measurements = measurer
+            .measure(&[
+                flagged("regex", "unicode"),
+                flagged("regex", "perf"),
+                flagged("regex", "unicode"),
+            ])
+            .unwrap();
+
+        assert_eq!(measurements.baseline_bytes, 1000);
+        assert_eq!(measurements.features.len(), 2);
+        let perf = &measurements.features[0];
+        assert_eq!(perf.feature, "perf");
+        assert!(perf.skipped.as_deref().unwrap().contains("not listed"));
+        let unicode = &measurements.features[1];
+        assert_eq!(unicode.trial_bytes, Some(700));
+        assert_eq!(unicode.delta_bytes, Some(-300));
+        assert!(std::fs::read_to_string(temp.path().join("Cargo.toml"))
+            .unwrap()
+            .contains("\"unicode\""));
+    }
+}
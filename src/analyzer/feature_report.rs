@@ -1,12 +1,20 @@
 //! Feature analysis report formatting
 
 use super::features::FeatureAnalysisResults;
-use super::report_utils;
+use super::report_utils::{self, format_bytes, format_delta, omitted_footer, ItemLimit};
 use console::style;
 use std::fmt::{self, Write as _};
 
 /// Format feature analysis results for console output
 pub fn format_console_report(results: &FeatureAnalysisResults) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(results, ItemLimit::Default)
+}
+
+/// Format feature analysis results, limiting the features listed
+pub fn format_console_report_with_limit(
+    results: &FeatureAnalysisResults,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
     let mut output = String::new();
 
     // Header
@@ -37,7 +45,8 @@ pub fn format_console_report(results: &FeatureAnalysisResults) -> Result<String,
         )?;
         output.push_str("   ─────────────────────────────────────────────────────────────────\n");
 
-        for feature in &results.unused_features {
+        let shown = limit.visible(results.unused_features.len(), None);
+        for feature in results.unused_features.iter().take(shown) {
             let confidence_str = match feature.confidence.as_str() {
                 "High" => style(&feature.confidence).green(),
                 "Medium" => style(&feature.confidence).yellow(),
@@ -54,6 +63,66 @@ pub fn format_console_report(results: &FeatureAnalysisResults) -> Result<String,
                 confidence_str
             )?;
         }
+        if let Some(footer) = omitted_footer(results.unused_features.len(), shown, "features") {
+            writeln!(output, "   {}", style(footer).dim())?;
+        }
+    }
+
+    if let Some(measurements) = &results.measurements {
+        writeln!(
+            output,
+            "\n{} Measured by Trial Builds (baseline {})",
+            style("📏").bold(),
+            style(format_bytes(measurements.baseline_bytes)).cyan()
+        )?;
+        for measurement in &measurements.features {
+            let name = format!("{}/{}", measurement.package, measurement.feature);
+            match (measurement.delta_bytes, &measurement.skipped) {
+                (Some(delta), _) => {
+                    let change = format_delta(delta);
+                    let change = if delta < 0 {
+                        style(change).green()
+                    } else {
+                        style(change).yellow()
+                    };
+                    writeln!(output, "   {:<36} {}", truncate(&name, 36), change)?;
+                }
+                (None, skipped) => writeln!(
+                    output,
+                    "   {:<36} {}",
+                    truncate(&name, 36),
+                    style(format!(
+                        "not measured: {}",
+                        skipped.as_deref().unwrap_or("unknown")
+                    ))
+                    .dim()
+                )?,
+            }
+        }
+    }
+
+    if !results.unified_features.is_empty() {
+        writeln!(
+            output,
+            "\n{} Disabled Features Re-enabled by Other Crates",
+            style("🔗").bold()
+        )?;
+        for unified in &results.unified_features {
+            writeln!(
+                output,
+                "   {}/{} disabled by {}, enabled by {}",
+                style(&unified.package).bold(),
+                style(&unified.feature).yellow(),
+                unified.member,
+                style(&unified.enabled_by).cyan()
+            )?;
+            writeln!(output, "      {}", style(unified.path.join(" → ")).dim())?;
+        }
+        writeln!(
+            output,
+            "   {} Disable the feature on the last edge of each path, or the savings never materialize\n",
+            style("→").cyan()
+        )?;
     }
 
     // Recommendations
@@ -79,7 +148,7 @@ pub fn format_console_report(results: &FeatureAnalysisResults) -> Result<String,
 
 /// Format feature analysis results as JSON
 pub fn format_json_report(results: &FeatureAnalysisResults) -> Result<String, serde_json::Error> {
-    serde_json::to_string_pretty(results)
+    crate::redact::to_json_pretty(results)
 }
 
 // Use shared formatting utilities from report_utils
@@ -104,6 +173,8 @@ mod tests {
             unused_features: vec![],
             estimated_savings_kb: 0,
             recommendations: vec![],
+            unified_features: Vec::new(),
+            measurements: None,
         };
         let json = format_json_report(&results).unwrap();
         assert!(json.contains("total_features"));
@@ -116,6 +187,8 @@ mod tests {
             unused_features: vec![],
             estimated_savings_kb: 0,
             recommendations: vec![],
+            unified_features: Vec::new(),
+            measurements: None,
         };
 
         let output = format_console_report(&results);
@@ -153,6 +226,8 @@ mod tests {
             ],
             estimated_savings_kb: 150,
             recommendations: vec![],
+            unified_features: Vec::new(),
+            measurements: None,
         };
 
         let output = format_console_report(&results);
@@ -176,6 +251,8 @@ mod tests {
                 "Consider splitting dependencies".to_string(),
                 "Review feature usage".to_string(),
             ],
+            unified_features: Vec::new(),
+            measurements: None,
         };
 
         let output = format_console_report(&results);
@@ -186,6 +263,66 @@ mod tests {
         assert!(text.contains("splitting dependencies"));
     }
 
+    #[test]
+    fn test_format_console_report_shows_measured_features() {
+        use crate::analyzer::feature_measure::{FeatureMeasurement, FeatureMeasurements};
+        let measurement =
+            |feature: &str, delta: Option<i64>, skipped: Option<&str>| FeatureMeasurement {
+                package: "regex".to_string(),
+                feature: feature.to_string(),
+                trial_bytes: delta.map(|d| (300_000 + d) as u64),
+                delta_bytes: delta,
+                skipped: skipped.map(str::to_string),
+            };
+        let results = FeatureAnalysisResults {
+            total_features: 2,
+            unused_features: vec![],
+            estimated_savings_kb: 0,
+            recommendations: vec![],
+            unified_features: Vec::new(),
+            measurements: Some(FeatureMeasurements {
+                baseline_bytes: 300_000,
+                features: vec![
+                    measurement("unicode", Some(-102_400), None),
+                    measurement("perf", None, Some("Feature 'perf' is not listed")),
+                ],
+            }),
+        };
+
+        let text = format_console_report(&results).unwrap();
+        assert!(text.contains("Measured by Trial Builds"));
+        assert!(text.contains("regex/unicode"));
+        assert!(text.contains("-100.00 KiB"));
+        assert!(text.contains("not measured: Feature 'perf' is not listed"));
+    }
+
+    #[test]
+    fn test_format_console_report_lists_unified_features_with_path() {
+        let results = FeatureAnalysisResults {
+            total_features: 4,
+            unused_features: vec![],
+            estimated_savings_kb: 0,
+            recommendations: vec![],
+            unified_features: vec![crate::analyzer::feature_unification::UnifiedFeature {
+                package: "regex".to_string(),
+                feature: "default".to_string(),
+                member: "app".to_string(),
+                enabled_by: "cookies".to_string(),
+                path: vec![
+                    "app".to_string(),
+                    "http-client".to_string(),
+                    "cookies".to_string(),
+                ],
+            }],
+            measurements: None,
+        };
+
+        let text = format_console_report(&results).unwrap();
+        assert!(text.contains("Re-enabled by Other Crates"));
+        assert!(text.contains("disabled by app, enabled by"));
+        assert!(text.contains("app → http-client → cookies"));
+    }
+
     #[test]
     fn test_format_console_report_with_all_confidence_levels() {
         use super::super::features::UnusedFeature;
@@ -217,6 +354,8 @@ mod tests {
             ],
             estimated_savings_kb: 35,
             recommendations: vec![],
+            unified_features: Vec::new(),
+            measurements: None,
         };
 
         let output = format_console_report(&results);
@@ -242,6 +381,8 @@ mod tests {
             }],
             estimated_savings_kb: 25,
             recommendations: vec!["Test recommendation".to_string()],
+            unified_features: Vec::new(),
+            measurements: None,
         };
 
         let json = format_json_report(&results).unwrap();
@@ -0,0 +1,289 @@
+//! Features re-enabled by feature unification
+//!
+//! Cargo builds each crate once with the union of the features every
+//! dependent asks for. Turning off `default-features` on a direct
+//! dependency therefore saves nothing while another crate in the graph still
+//! asks for them. For every feature a workspace member switched off, this
+//! finds the crates that switch it back on and the dependency path from the
+//! member to each of them, so the edge to fix is obvious.
+
+use super::report_utils::SizedEntry;
+use cargo_metadata::{DependencyKind, Metadata, Package, PackageId};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// A feature a workspace member disabled that another crate enables
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnifiedFeature {
+    /// Dependency the feature belongs to
+    pub package: String,
+    /// Feature re-enabled (`default` for the default set)
+    pub feature: String,
+    /// Workspace member that disabled it
+    pub member: String,
+    /// Crate whose dependency declaration enables it
+    pub enabled_by: String,
+    /// Dependency path from the member to `enabled_by`
+    pub path: Vec<String>,
+}
+
+impl SizedEntry for UnifiedFeature {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        &self.package
+    }
+
+    fn matches(&self, pattern: &Regex) -> bool {
+        pattern.is_match(&self.package) || pattern.is_match(&self.feature)
+    }
+}
+
+/// Features disabled by workspace members but enabled elsewhere in the graph
+pub(crate) fn unified_features(metadata: &Metadata) -> Vec<UnifiedFeature> {
+    let Some(resolve) = &metadata.resolve else {
+        return Vec::new();
+    };
+    let packages: HashMap<&PackageId, &Package> =
+        metadata.packages.iter().map(|p| (&p.id, p)).collect();
+    let nodes: HashMap<&PackageId, _> = resolve.nodes.iter().map(|n| (&n.id, n)).collect();
+    let normal_deps = |id: &PackageId| -> Vec<&PackageId> {
+        nodes.get(id).map_or_else(Vec::new, |node| {
+            node.deps
+                .iter()
+                .filter(|d| d.dep_kinds.iter().any(|k| k.kind == DependencyKind::Normal))
+                .map(|d| &d.pkg)
+                .collect()
+        })
+    };
+
+    let mut found = Vec::new();
+    for member_id in &metadata.workspace_members {
+        let Some(member) = packages.get(member_id) else {
+            continue;
+        };
+        for dep_id in normal_deps(member_id) {
+            let (Some(dep), Some(dep_node)) = (packages.get(dep_id), nodes.get(dep_id)) else {
+                continue;
+            };
+            let Some(declared) = member.dependencies.iter().find(|d| {
+                d.kind == DependencyKind::Normal
+                    && d.name == dep.name.as_str()
+                    && !d.uses_default_features
+                    && d.req.matches(&dep.version)
+            }) else {
+                continue;
+            };
+
+            let kept = feature_closure(dep, declared.features.iter().map(String::as_str));
+            let resolved: HashSet<&str> = dep_node.features.iter().map(|f| f.as_str()).collect();
+            let mut disabled = feature_closure(dep, ["default"]);
+            disabled.retain(|f| !kept.contains(f) && resolved.contains(f.as_str()));
+            if disabled.is_empty() {
+                continue;
+            }
+
+            for (enabler, features) in enablers(metadata, &nodes, dep_id, dep) {
+                if enabler.id == member.id {
+                    continue;
+                }
+                let path = dependency_path(member_id, &enabler.id, &normal_deps, &packages);
+                for feature in features.into_iter().filter(|f| disabled.contains(f)) {
+                    found.push(UnifiedFeature {
+                        package: dep.name.to_string(),
+                        feature,
+                        member: member.name.to_string(),
+                        enabled_by: enabler.name.to_string(),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+    }
+    found.sort_by(|a, b| {
+        (&a.package, &a.feature, &a.enabled_by).cmp(&(&b.package, &b.feature, &b.enabled_by))
+    });
+    found.dedup();
+    found
+}
+
+/// Features of `package` turned on by enabling `roots`, including `roots`
+fn feature_closure<'a>(
+    package: &Package,
+    roots: impl IntoIterator<Item = &'a str>,
+) -> BTreeSet<String> {
+    let mut enabled = BTreeSet::new();
+    let mut pending: Vec<String> = roots.into_iter().map(str::to_string).collect();
+    while let Some(feature) = pending.pop() {
+        if !enabled.insert(feature.clone()) {
+            continue;
+        }
+        for entry in package.features.get(&feature).into_iter().flatten() {
+            if !entry.contains('/') && !entry.starts_with("dep:") {
+                pending.push(entry.clone());
+            }
+        }
+    }
+    enabled.retain(|f| f == "default" || package.features.contains_key(f));
+    enabled
+}
+
+/// Crates depending on `dep_id` and the features of it each one asks for
+fn enablers<'a>(
+    metadata: &'a Metadata,
+    nodes: &HashMap<&PackageId, &cargo_metadata::Node>,
+    dep_id: &PackageId,
+    dep: &Package,
+) -> Vec<(&'a Package, BTreeSet<String>)> {
+    let mut result = Vec::new();
+    for parent in &metadata.packages {
+        let Some(parent_node) = nodes.get(&parent.id) else {
+            continue;
+        };
+        if !parent_node.deps.iter().any(|d| &d.pkg == dep_id) {
+            continue;
+        }
+        let Some(declared) = parent.dependencies.iter().find(|d| {
+            d.kind == DependencyKind::Normal
+                && d.name == dep.name.as_str()
+                && d.req.matches(&dep.version)
+        }) else {
+            continue;
+        };
+
+        let mut requested: BTreeSet<String> = declared.features.iter().cloned().collect();
+        if declared.uses_default_features {
+            requested.insert("default".to_string());
+        }
+        // `dep/feature` and `dep?/feature` entries of the parent's own enabled features
+        let local_name = declared.rename.as_deref().unwrap_or(&declared.name);
+        for feature in &parent_node.features {
+            for entry in parent.features.get(feature.as_str()).into_iter().flatten() {
+                let Some((name, dep_feature)) = entry.split_once('/') else {
+                    continue;
+                };
+                if name.trim_end_matches('?') == local_name {
+                    requested.insert(dep_feature.to_string());
+                }
+            }
+        }
+        result.push((parent, requested));
+    }
+    result
+}
+
+/// Crate names on the shortest dependency path from `from` to `to`
+fn dependency_path<'a>(
+    from: &'a PackageId,
+    to: &'a PackageId,
+    normal_deps: &impl Fn(&PackageId) -> Vec<&'a PackageId>,
+    packages: &HashMap<&PackageId, &Package>,
+) -> Vec<String> {
+    let mut previous: HashMap<&PackageId, &PackageId> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    let mut seen = HashSet::from([from]);
+    while let Some(id) = queue.pop_front() {
+        if id == to {
+            break;
+        }
+        for next in normal_deps(id) {
+            if seen.insert(next) {
+                previous.insert(next, id);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let mut path = vec![to];
+    while let Some(prev) = previous.get(path[path.len() - 1]) {
+        path.push(prev);
+    }
+    path.iter()
+        .rev()
+        .filter_map(|id| packages.get(id).map(|p| p.name.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn dep(name: &str, default_features: bool, features: &[&str]) -> Value {
+        json!({
+            "name": name, "source": null, "req": "*", "kind": null,
+            "optional": false, "uses_default_features": default_features,
+            "features": features, "target": null, "rename": null,
+            "registry": null, "path": null
+        })
+    }
+
+    fn package(name: &str, deps: Vec<Value>, features: Value) -> Value {
+        json!({
+            "name": name, "version": "1.0.0", "id": format!("{} 1.0.0", name),
+            "license": null, "license_file": null, "description": null,
+            "source": null, "dependencies": deps, "targets": [], "features": features,
+            "manifest_path": format!("/fake/{}/Cargo.toml", name),
+            "metadata": null, "publish": null, "authors": [], "categories": [],
+            "keywords": [], "readme": null, "repository": null, "homepage": null,
+            "documentation": null, "edition": "2021", "links": null,
+            "default_run": null, "rust_version": null
+        })
+    }
+
+    fn node(name: &str, deps: &[&str], features: &[&str]) -> Value {
+        json!({
+            "id": format!("{} 1.0.0", name),
+            "dependencies": deps.iter().map(|d| format!("{} 1.0.0", d)).collect::<Vec<_>>(),
+            "deps": deps.iter().map(|d| json!({
+                "name": d, "pkg": format!("{} 1.0.0", d),
+                "dep_kinds": [{"kind": null, "target": null}]
+            })).collect::<Vec<_>>(),
+            "features": features
+        })
+    }
+
+    #[test]
+    fn test_unified_features_reports_enabler_and_path() {
+        let metadata: Metadata = serde_json::from_value(json!({
+            "packages": [
+                package("app", vec![dep("regex", false, &["std"]), dep("http-client", true, &[])], json!({})),
+                package("http-client", vec![dep("cookies", true, &[])], json!({})),
+                package("cookies", vec![dep("regex", true, &[])], json!({})),
+                package("regex", vec![], json!({
+                    "default": ["std", "unicode"], "std": [], "unicode": ["unicode-perl"],
+                    "unicode-perl": [], "perf": []
+                })),
+            ],
+            "workspace_members": ["app 1.0.0"],
+            "workspace_default_members": ["app 1.0.0"],
+            "resolve": {
+                "nodes": [
+                    node("app", &["regex", "http-client"], &[]),
+                    node("http-client", &["cookies"], &[]),
+                    node("cookies", &["regex"], &[]),
+                    node("regex", &[], &["default", "std", "unicode", "unicode-perl"]),
+                ],
+                "root": "app 1.0.0"
+            },
+            "target_directory": "/fake/target",
+            "version": 1,
+            "workspace_root": "/fake",
+            "metadata": null
+        }))
+        .unwrap();
+
+        let found = unified_features(&metadata);
+
+        assert_eq!(found.len(), 1);
+        let unified = &found[0];
+        assert_eq!(unified.package, "regex");
+        assert_eq!(unified.feature, "default");
+        assert_eq!(unified.member, "app");
+        assert_eq!(unified.enabled_by, "cookies");
+        assert_eq!(unified.path, vec!["app", "http-client", "cookies"]);
+    }
+}
@@ -1,8 +1,11 @@
 //! Feature flag analyzer for detecting unused features
 //!
 //! Analyzes Cargo.toml dependencies to find enabled features that might not be used,
-//! potentially reducing binary size by 10-30%.
+//! potentially reducing binary size by 10-30%, and features switched off on a
+//! direct dependency that feature unification turns back on.
 
+use super::report_utils::SizedEntry;
+use regex::Regex;
 use thiserror::Error;
 
 /// Errors that can occur during feature analysis
@@ -24,10 +27,13 @@ pub enum FeatureAnalysisError {
     #[error("cargo tree failed")]
     CargoTreeFailed,
 }
+use super::feature_measure::FeatureMeasurements;
+use super::feature_unification::{unified_features, UnifiedFeature};
 use crate::infra::{CommandExecutor, RealCommandExecutor};
+use crate::profiling::{self, ProfilePhase};
 use cargo_metadata::MetadataCommand;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
 /// Feature flag analyzer
@@ -72,6 +78,20 @@ pub struct UnusedFeature {
     pub confidence: String,
 }
 
+impl SizedEntry for UnusedFeature {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.estimated_impact_kb * 1024)
+    }
+
+    fn name(&self) -> &str {
+        &self.package
+    }
+
+    fn matches(&self, pattern: &Regex) -> bool {
+        pattern.is_match(&self.package) || pattern.is_match(&self.feature)
+    }
+}
+
 /// Complete feature analysis results
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FeatureAnalysisResults {
@@ -83,6 +103,12 @@ pub struct FeatureAnalysisResults {
     pub estimated_savings_kb: u64,
     /// Recommendations
     pub recommendations: Vec<String>,
+    /// Features a workspace member disabled that other crates re-enable
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unified_features: Vec<UnifiedFeature>,
+    /// Trial build measurements of the unused features (`--measure`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurements: Option<FeatureMeasurements>,
 }
 
 impl FeatureAnalyzer<RealCommandExecutor> {
@@ -105,9 +131,12 @@ impl<CE: CommandExecutor> FeatureAnalyzer<CE> {
     #[must_use = "Analysis results should be used or printed"]
     pub fn analyze(&self) -> Result<FeatureAnalysisResults, FeatureAnalysisError> {
         // Get cargo metadata
-        let metadata = MetadataCommand::new()
-            .current_dir(&self.project_root)
-            .exec()?;
+        let metadata = {
+            let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+            MetadataCommand::new()
+                .current_dir(&self.project_root)
+                .exec()?
+        };
 
         // Get feature tree
         let feature_tree = self.get_feature_tree()?;
@@ -171,11 +200,13 @@ impl<CE: CommandExecutor> FeatureAnalyzer<CE> {
             unused_features,
             estimated_savings_kb,
             recommendations,
+            unified_features: unified_features(&metadata),
+            measurements: None,
         })
     }
 
     /// Get feature tree using cargo tree
-    fn get_feature_tree(&self) -> Result<HashMap<String, HashSet<String>>, FeatureAnalysisError> {
+    fn get_feature_tree(&self) -> Result<BTreeMap<String, BTreeSet<String>>, FeatureAnalysisError> {
         let output = self.cmd_executor.execute(
             |cmd| {
                 cmd.arg("tree")
@@ -193,7 +224,7 @@ impl<CE: CommandExecutor> FeatureAnalyzer<CE> {
         }
 
         let stdout = String::from_utf8(output.stdout)?;
-        let mut feature_map = HashMap::new();
+        let mut feature_map = BTreeMap::new();
 
         for line in stdout.lines() {
             // Parse format: "package_name v0.1.0 feature1,feature2"
@@ -204,7 +235,7 @@ impl<CE: CommandExecutor> FeatureAnalyzer<CE> {
                 // Extract features from the rest
                 let features_parts: Vec<&str> = parts.iter().skip(1).copied().collect();
                 let features_str = features_parts.join(" ");
-                let features: HashSet<String> = features_str
+                let features: BTreeSet<String> = features_str
                     .split(',')
                     .filter(|s| !s.is_empty() && !s.starts_with('v'))
                     .map(|s| String::from(s.trim()))
@@ -213,7 +244,7 @@ impl<CE: CommandExecutor> FeatureAnalyzer<CE> {
                 if !features.is_empty() {
                     feature_map
                         .entry(String::from(package_name))
-                        .or_insert_with(HashSet::new)
+                        .or_insert_with(BTreeSet::new)
                         .extend(features);
                 }
             }
@@ -292,7 +323,7 @@ impl<CE: CommandExecutor> FeatureAnalyzer<CE> {
         }
 
         // Group by package
-        let mut by_package: HashMap<String, Vec<&UnusedFeature>> = HashMap::new();
+        let mut by_package: BTreeMap<String, Vec<&UnusedFeature>> = BTreeMap::new();
         for feature in unused_features {
             by_package
                 .entry(feature.package.clone())
@@ -402,6 +433,8 @@ mod tests {
             unused_features: vec![],
             estimated_savings_kb: 0,
             recommendations: vec!["test".to_string()],
+            unified_features: Vec::new(),
+            measurements: None,
         };
 
         assert_eq!(results.total_features, 10);
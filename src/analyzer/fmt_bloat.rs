@@ -0,0 +1,467 @@
+//! `core::fmt` bloat attribution
+//!
+//! The bloat analyzer sums every symbol with `fmt::` in its name, which
+//! misses code that only formatting needs (float printing in
+//! `core::num::flt2dec`, `memchr` behind padding, unicode tables behind
+//! `{:?}` escaping) and counts the user's own impls as machinery. This pass
+//! works on the item graph instead:
+//!
+//! - the formatting machinery is every `core`/`alloc`/`std` item under
+//!   `fmt::`, plus standard library impls of the fmt traits
+//! - treated as one node, the machinery dominates everything that becomes
+//!   unreachable once it is removed; that set is the formatting cost
+//! - entry points are the items outside that set that reference the
+//!   machinery directly: `Display`/`Debug` impls of the user's types,
+//!   `format!` call sites (callers of `alloc::fmt::format`) and other
+//!   callers. For each, the dominator tree gives the formatting code it
+//!   alone keeps alive.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::FmtBloatAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = FmtBloatAnalyzer::new().analyze(Path::new("pkg/app_bg.wasm"))?;
+//! for entry in &report.entry_points {
+//!     println!("{} keeps {} bytes of fmt code", entry.name, entry.exclusive_bytes);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use super::twiggy::native::ItemGraph;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// Path prefixes of the formatting machinery
+const FMT_PATHS: [&str; 3] = ["core::fmt::", "alloc::fmt::", "std::fmt::"];
+
+/// Crates whose types count as standard library types
+const STD_CRATES: [&str; 3] = ["core", "alloc", "std"];
+
+/// What connects an entry point to the formatting machinery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FmtEntryKind {
+    /// `Display` impl of a user type
+    DisplayImpl,
+    /// `Debug` impl of a user type
+    DebugImpl,
+    /// Other fmt trait impl of a user type (`LowerHex`, `fmt::Write`, …)
+    OtherImpl,
+    /// Function calling `alloc::fmt::format` (`format!`, `to_string()`)
+    FormatCall,
+    /// Other user function calling into fmt (`write!`, `println!`, …)
+    Caller,
+    /// Standard library function (panic handler, `unwrap` messages, …)
+    StdLib,
+}
+
+impl FmtEntryKind {
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            FmtEntryKind::DisplayImpl => "Display impl",
+            FmtEntryKind::DebugImpl => "Debug impl",
+            FmtEntryKind::OtherImpl => "fmt impl",
+            FmtEntryKind::FormatCall => "format! call",
+            FmtEntryKind::Caller => "fmt caller",
+            FmtEntryKind::StdLib => "std",
+        }
+    }
+}
+
+/// An item outside the formatting code that references the machinery
+#[derive(Debug, Clone, Serialize)]
+pub struct FmtEntryPoint {
+    /// Item name
+    pub name: String,
+    /// How it uses formatting
+    pub kind: FmtEntryKind,
+    /// Shallow size of the item itself
+    pub size_bytes: u64,
+    /// Machinery items it references directly
+    pub fmt_references: usize,
+    /// Formatting code only this item keeps alive
+    pub exclusive_bytes: u64,
+}
+
+/// An item reachable only through the formatting machinery
+#[derive(Debug, Clone, Serialize)]
+pub struct FmtItem {
+    /// Item name
+    pub name: String,
+    /// Shallow size
+    pub size_bytes: u64,
+    /// Whether the item is machinery itself rather than code it needs
+    pub machinery: bool,
+}
+
+impl SizedEntry for FmtItem {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Formatting code of a module and what pulls it in
+#[derive(Debug, Clone, Serialize)]
+pub struct FmtBloatReport {
+    /// Module size
+    pub module_bytes: u64,
+    /// Code reachable only through the machinery, machinery included
+    pub fmt_bytes: u64,
+    /// Part of `fmt_bytes` outside `fmt::` paths
+    pub helper_bytes: u64,
+    /// What the name heuristic of the bloat analyzer counts
+    pub name_matched_bytes: u64,
+    /// Sum of the user impls of fmt traits
+    pub impl_bytes: u64,
+    /// Items of `fmt_bytes`, largest first
+    pub items: Vec<FmtItem>,
+    /// Entry points, largest exclusive cost first
+    pub entry_points: Vec<FmtEntryPoint>,
+    /// Suggested changes
+    pub recommendations: Vec<String>,
+}
+
+/// Attributes formatting code to the impls and call sites that need it
+#[derive(Debug, Default)]
+pub struct FmtBloatAnalyzer;
+
+impl FmtBloatAnalyzer {
+    /// Create an analyzer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Attribute the formatting code of a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<FmtBloatReport> {
+        let module_bytes = std::fs::metadata(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?
+            .len();
+        let graph = ItemGraph::read(wasm_file)
+            .with_context(|| format!("Failed to parse {}", wasm_file.display()))?;
+        Ok(analyze_graph(&graph, module_bytes))
+    }
+}
+
+/// Attribute the formatting code of a parsed module
+fn analyze_graph(graph: &ItemGraph, module_bytes: u64) -> FmtBloatReport {
+    let count = graph.item_count();
+    let machinery: Vec<bool> = (0..count).map(|id| is_machinery(graph.name(id))).collect();
+    let reachable = graph.reachable_avoiding(&[]);
+    let without_fmt = graph.reachable_avoiding(&machinery);
+    let in_fmt: Vec<bool> = (0..count)
+        .map(|id| reachable[id] && !without_fmt[id])
+        .collect();
+
+    let mut items: Vec<FmtItem> = (0..count)
+        .filter(|&id| in_fmt[id] && graph.size_bytes(id) > 0)
+        .map(|id| FmtItem {
+            name: graph.name(id).to_string(),
+            size_bytes: graph.size_bytes(id),
+            machinery: machinery[id],
+        })
+        .collect();
+    items.sort_by_key(|i| std::cmp::Reverse(i.size_bytes));
+
+    let dominated = graph.dominator_children();
+    let mut entry_points: Vec<FmtEntryPoint> = (0..count)
+        .filter(|&id| reachable[id] && !in_fmt[id] && graph.is_code(id))
+        .filter_map(|id| {
+            let fmt_references = graph
+                .references(id)
+                .iter()
+                .filter(|&&to| machinery[to])
+                .count();
+            let kind = entry_kind(graph, id);
+            if fmt_references == 0 && !is_user_impl(kind) {
+                return None;
+            }
+            Some(FmtEntryPoint {
+                name: graph.name(id).to_string(),
+                kind,
+                size_bytes: graph.size_bytes(id),
+                fmt_references,
+                exclusive_bytes: dominated_bytes(graph, &dominated, id, &in_fmt),
+            })
+        })
+        .collect();
+    entry_points.sort_by(|a, b| {
+        b.exclusive_bytes
+            .cmp(&a.exclusive_bytes)
+            .then_with(|| b.size_bytes.cmp(&a.size_bytes))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut report = FmtBloatReport {
+        module_bytes,
+        fmt_bytes: items.iter().map(|i| i.size_bytes).sum(),
+        helper_bytes: items
+            .iter()
+            .filter(|i| !i.machinery)
+            .map(|i| i.size_bytes)
+            .sum(),
+        name_matched_bytes: (0..count)
+            .filter(|&id| graph.name(id).contains("fmt::"))
+            .map(|id| graph.size_bytes(id))
+            .sum(),
+        impl_bytes: entry_points
+            .iter()
+            .filter(|e| is_user_impl(e.kind))
+            .map(|e| e.size_bytes)
+            .sum(),
+        items,
+        entry_points,
+        recommendations: Vec::new(),
+    };
+    report.recommendations = recommendations(&report);
+    report
+}
+
+/// Sum of the `in_fmt` items in the dominator subtree of `id`
+fn dominated_bytes(graph: &ItemGraph, dominated: &[Vec<usize>], id: usize, in_fmt: &[bool]) -> u64 {
+    let mut bytes = 0;
+    let mut stack: Vec<usize> = dominated[id].clone();
+    while let Some(next) = stack.pop() {
+        if in_fmt[next] {
+            bytes += graph.size_bytes(next);
+        }
+        stack.extend(&dominated[next]);
+    }
+    bytes
+}
+
+/// Whether an item belongs to the standard formatting machinery
+fn is_machinery(name: &str) -> bool {
+    if FMT_PATHS.iter().any(|p| name.starts_with(p)) {
+        return true;
+    }
+    fmt_impl(name).is_some_and(|(ty, _)| !is_user_type(ty))
+}
+
+/// Kind of an entry point function
+fn entry_kind(graph: &ItemGraph, id: usize) -> FmtEntryKind {
+    let name = graph.name(id);
+    if let Some((ty, trait_name)) = fmt_impl(name) {
+        if is_user_type(ty) {
+            return match trait_name {
+                "Display" => FmtEntryKind::DisplayImpl,
+                "Debug" => FmtEntryKind::DebugImpl,
+                _ => FmtEntryKind::OtherImpl,
+            };
+        }
+    }
+    let crate_name = name
+        .trim_start_matches('<')
+        .split("::")
+        .next()
+        .unwrap_or("");
+    if STD_CRATES.contains(&crate_name) {
+        return FmtEntryKind::StdLib;
+    }
+    let calls_format = graph
+        .references(id)
+        .iter()
+        .any(|&to| graph.name(to).starts_with("alloc::fmt::format"));
+    if calls_format {
+        FmtEntryKind::FormatCall
+    } else {
+        FmtEntryKind::Caller
+    }
+}
+
+fn is_user_impl(kind: FmtEntryKind) -> bool {
+    matches!(
+        kind,
+        FmtEntryKind::DisplayImpl | FmtEntryKind::DebugImpl | FmtEntryKind::OtherImpl
+    )
+}
+
+/// Self type and trait name of `<Type as core::fmt::Trait>::method`
+fn fmt_impl(name: &str) -> Option<(&str, &str)> {
+    let rest = name.strip_prefix('<')?;
+    let inner = &rest[..rest.rfind(">::")?];
+    let (ty, trait_path) = inner.rsplit_once(" as ")?;
+    let trait_path = trait_path.split('<').next().unwrap_or(trait_path);
+    let trait_name = FMT_PATHS.iter().find_map(|p| trait_path.strip_prefix(p))?;
+    Some((ty, trait_name))
+}
+
+/// Whether a type path belongs to the user's crates
+///
+/// References are looked through; primitives, generic parameters
+/// (legacy mangling keeps them as `T`) and standard library types are not
+/// user types.
+fn is_user_type(ty: &str) -> bool {
+    let ty = ty.trim_start_matches('&').trim_start_matches("mut ");
+    match ty.split_once("::") {
+        Some((crate_name, _)) => !STD_CRATES.contains(&crate_name),
+        None => false,
+    }
+}
+
+/// Suggested changes for the largest kinds of entry points
+fn recommendations(report: &FmtBloatReport) -> Vec<String> {
+    let mut recs = Vec::new();
+    if report.fmt_bytes == 0 {
+        return recs;
+    }
+    let of_kind = |kind: FmtEntryKind| report.entry_points.iter().filter(move |e| e.kind == kind);
+
+    let debug_impls = of_kind(FmtEntryKind::DebugImpl).count();
+    if debug_impls > 0 {
+        recs.push(format!(
+            "{} Debug impls are in the release build; derive them only for debug builds with #[cfg_attr(debug_assertions, derive(Debug))]",
+            debug_impls
+        ));
+    }
+    let format_calls = of_kind(FmtEntryKind::FormatCall).count();
+    if format_calls > 0 {
+        recs.push(format!(
+            "{} functions call format!/to_string(); build strings with push_str and itoa/ryu for numbers",
+            format_calls
+        ));
+    }
+    if of_kind(FmtEntryKind::StdLib).any(|e| e.name.contains("panic")) {
+        recs.push(
+            "Panic messages are formatted by the panic handler; panic_immediate_abort removes it"
+                .to_string(),
+        );
+    }
+    if report.helper_bytes > 0 {
+        recs.push(format!(
+            "{} bytes outside fmt:: (float and integer printing, escaping) are only needed by formatting",
+            report.helper_bytes
+        ));
+    }
+    recs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{leb, section, string, HEADER};
+
+    /// Module of named functions with the indices they call; function 0 is
+    /// exported
+    fn module(functions: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut wasm = HEADER.to_vec();
+        wasm.extend(section(1, &[1, 0x60, 0, 0]));
+        let mut declared = leb(functions.len());
+        declared.extend(std::iter::repeat_n(0, functions.len()));
+        wasm.extend(section(3, &declared));
+
+        let mut exports = vec![1];
+        exports.extend(string("main"));
+        exports.extend([0, 0]);
+        wasm.extend(section(7, &exports));
+
+        let mut code = leb(functions.len());
+        for (_, calls) in functions {
+            let mut body = vec![0];
+            for &callee in *calls {
+                body.extend([0x10, callee]);
+            }
+            // Padding so every function has a distinct, non-trivial size
+            body.extend(std::iter::repeat_n(0x01, 10));
+            body.push(0x0b);
+            code.extend(leb(body.len()));
+            code.extend(body);
+        }
+        wasm.extend(section(10, &code));
+
+        let mut names = leb(functions.len());
+        for (index, (name, _)) in functions.iter().enumerate() {
+            names.extend(leb(index));
+            names.extend(string(name));
+        }
+        let mut name_section = string("name");
+        name_section.extend(section(1, &names));
+        wasm.extend(section(0, &name_section));
+        wasm
+    }
+
+    #[test]
+    fn test_classify_machinery_and_user_impls() {
+        assert!(is_machinery("core::fmt::write"));
+        assert!(is_machinery("<&T as core::fmt::Debug>::fmt"));
+        assert!(is_machinery(
+            "<alloc::string::String as core::fmt::Write>::write_str"
+        ));
+        assert!(!is_machinery("<app::Point as core::fmt::Display>::fmt"));
+        assert!(!is_machinery("core::num::flt2dec::to_shortest_str"));
+        assert_eq!(
+            fmt_impl("<app::Wrapper<T> as core::fmt::Debug>::fmt"),
+            Some(("app::Wrapper<T>", "Debug"))
+        );
+    }
+
+    #[test]
+    fn test_attributes_fmt_only_code_to_entry_points() {
+        // 0 main -> 1 render, 2 <Point as Display>::fmt, 5 add
+        // 1 render -> 3 alloc::fmt::format -> 4 core::fmt::write -> 6 flt2dec
+        // 2 impl -> 7 core::fmt::Formatter::pad -> 6 flt2dec
+        let wasm = module(&[
+            ("main", &[1, 2, 5]),
+            ("app::render", &[3]),
+            ("<app::Point as core::fmt::Display>::fmt", &[7]),
+            ("alloc::fmt::format::format_inner", &[4]),
+            ("core::fmt::write", &[6]),
+            ("app::add", &[]),
+            ("core::num::flt2dec::to_shortest_str", &[]),
+            ("core::fmt::Formatter::pad", &[6]),
+        ]);
+        let graph = ItemGraph::parse(&wasm).unwrap();
+        let report = analyze_graph(&graph, wasm.len() as u64);
+
+        let mut fmt_names: Vec<&str> = report.items.iter().map(|i| i.name.as_str()).collect();
+        fmt_names.sort();
+        assert_eq!(
+            fmt_names,
+            vec![
+                "alloc::fmt::format::format_inner",
+                "core::fmt::Formatter::pad",
+                "core::fmt::write",
+                "core::num::flt2dec::to_shortest_str",
+            ]
+        );
+        let flt2dec = report.items.iter().find(|i| !i.machinery).unwrap();
+        assert_eq!(report.helper_bytes, flt2dec.size_bytes);
+
+        let render = report
+            .entry_points
+            .iter()
+            .find(|e| e.name == "app::render")
+            .unwrap();
+        assert_eq!(render.kind, FmtEntryKind::FormatCall);
+        let display = report
+            .entry_points
+            .iter()
+            .find(|e| e.kind == FmtEntryKind::DisplayImpl)
+            .unwrap();
+        assert_eq!(report.impl_bytes, display.size_bytes);
+        // flt2dec is shared, so each entry only owns its own call chain
+        let size = |name: &str| {
+            report
+                .items
+                .iter()
+                .find(|i| i.name == name)
+                .unwrap()
+                .size_bytes
+        };
+        assert_eq!(
+            render.exclusive_bytes,
+            size("alloc::fmt::format::format_inner") + size("core::fmt::write")
+        );
+        assert_eq!(display.exclusive_bytes, size("core::fmt::Formatter::pad"));
+        assert!(!report.entry_points.iter().any(|e| e.name == "app::add"));
+        assert!(report.recommendations.iter().any(|r| r.contains("format!")));
+    }
+}
@@ -0,0 +1,146 @@
+//! Formatting bloat report formatting
+
+use super::fmt_bloat::FmtBloatReport;
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use crate::fmt::percent_of;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of entry points listed by default
+const DEFAULT_TOP_ENTRIES: usize = 20;
+
+/// Number of retained items listed by default
+const DEFAULT_TOP_ITEMS: usize = 10;
+
+/// Format the formatting bloat report for console output
+pub fn format_console_report(report: &FmtBloatReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the formatting bloat report, limiting the entries listed
+pub fn format_console_report_with_limit(
+    report: &FmtBloatReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("core::fmt Code").bold().underlined())?;
+    if report.fmt_bytes == 0 {
+        writeln!(
+            output,
+            "\n   {} No code is reachable only through core::fmt",
+            style("✓").green()
+        )?;
+        return Ok(output);
+    }
+    writeln!(
+        output,
+        "\n   {} ({:.1}% of {}) only reachable through formatting",
+        style(format_bytes(report.fmt_bytes)).yellow().bold(),
+        percent_of(report.fmt_bytes, report.module_bytes),
+        format_bytes(report.module_bytes)
+    )?;
+    writeln!(
+        output,
+        "   {} of it outside fmt:: paths; name matching finds {}",
+        format_bytes(report.helper_bytes),
+        format_bytes(report.name_matched_bytes)
+    )?;
+    if report.impl_bytes > 0 {
+        writeln!(
+            output,
+            "   Your fmt trait impls add {}",
+            format_bytes(report.impl_bytes)
+        )?;
+    }
+
+    writeln!(output, "\n{}", style("Largest Retained Items").bold())?;
+    let shown = limit.visible(report.items.len(), Some(DEFAULT_TOP_ITEMS));
+    for item in report.items.iter().take(shown) {
+        let name = truncate_str(&item.name, 70);
+        writeln!(
+            output,
+            "  {:>10}  {}",
+            format_bytes(item.size_bytes),
+            if item.machinery {
+                style(name)
+            } else {
+                style(name).cyan()
+            }
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.items.len(), shown, "items") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+
+    if !report.entry_points.is_empty() {
+        writeln!(output, "\n{}", style("What Pulls It In").bold())?;
+        writeln!(
+            output,
+            "  {:>10} {:>10} {:>5}  {:<14} Function",
+            "Exclusive", "Own", "Refs", "Kind"
+        )?;
+        writeln!(output, "{}", style("─".repeat(100)).dim())?;
+        let shown = limit.visible(report.entry_points.len(), Some(DEFAULT_TOP_ENTRIES));
+        for entry in report.entry_points.iter().take(shown) {
+            writeln!(
+                output,
+                "  {:>10} {:>10} {:>5}  {:<14} {}",
+                format_bytes(entry.exclusive_bytes),
+                format_bytes(entry.size_bytes),
+                entry.fmt_references,
+                entry.kind.label(),
+                style(truncate_str(&entry.name, 56)).bold()
+            )?;
+        }
+        if let Some(footer) = omitted_footer(report.entry_points.len(), shown, "entry points") {
+            writeln!(output, "  {}", style(footer).dim())?;
+        }
+    }
+
+    if !report.recommendations.is_empty() {
+        writeln!(output, "\n{}", style("Recommendations").bold())?;
+        for rec in &report.recommendations {
+            writeln!(output, "  {} {}", style("→").cyan(), rec)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::fmt_bloat::{FmtEntryKind, FmtEntryPoint, FmtItem};
+
+    #[test]
+    fn test_format_console_report_lists_items_and_entry_points() {
+        let report = FmtBloatReport {
+            module_bytes: 100_000,
+            fmt_bytes: 12_000,
+            helper_bytes: 4_000,
+            name_matched_bytes: 9_000,
+            impl_bytes: 300,
+            items: vec![FmtItem {
+                name: "core::num::flt2dec::to_shortest_str".to_string(),
+                size_bytes: 4_000,
+                machinery: false,
+            }],
+            entry_points: vec![FmtEntryPoint {
+                name: "<app::Point as core::fmt::Debug>::fmt".to_string(),
+                kind: FmtEntryKind::DebugImpl,
+                size_bytes: 300,
+                fmt_references: 2,
+                exclusive_bytes: 1_500,
+            }],
+            recommendations: vec!["1 Debug impls are in the release build".to_string()],
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("(12.0% of"));
+        assert!(text.contains("flt2dec"));
+        assert!(text.contains("Debug impl"));
+        assert!(text.contains("app::Point"));
+        assert!(text.contains("1 Debug impls are in the release build"));
+    }
+}
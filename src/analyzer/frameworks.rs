@@ -0,0 +1,444 @@
+//! Reactive framework double-inclusion detection
+//!
+//! Two UI frameworks in one artifact (yew pulled in by a shared component
+//! library next to the app's own leptos, say), or two versions of the same
+//! framework, ship two runtimes where one would do. This walks the
+//! dependency graph cargo resolves for the WASM target, groups crates into
+//! known framework families, and traces the dependency edges that bring
+//! each family in. Given a module, the size of each family is measured from
+//! its symbols.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::FrameworkAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = FrameworkAnalyzer::new(".").analyze(Some(Path::new("pkg/app_bg.wasm")))?;
+//! if report.has_double_inclusion() {
+//!     for framework in &report.frameworks {
+//!         println!("{} {:?}", framework.name, framework.versions);
+//!     }
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use super::symbol_blame::crate_of_symbol;
+use super::twiggy::TwiggyAnalyzer;
+use crate::profiling::{self, ProfilePhase};
+use anyhow::{Context, Result};
+use cargo_metadata::{DependencyKind, MetadataCommand, TargetKind};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Target the dependency graph is resolved for
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Known frameworks and the crate name prefixes of their families
+///
+/// A crate belongs to a family when its name (with `_` for `-`) equals a
+/// prefix or starts with the prefix followed by `_`.
+const FRAMEWORKS: &[(&str, &[&str])] = &[
+    ("yew", &["yew"]),
+    ("leptos", &["leptos", "reactive_graph", "tachys"]),
+    ("dioxus", &["dioxus"]),
+    ("sycamore", &["sycamore"]),
+    ("seed", &["seed"]),
+    ("percy", &["percy"]),
+    ("dominator", &["dominator"]),
+    ("sauron", &["sauron"]),
+    ("mogwai", &["mogwai"]),
+];
+
+/// Package in the resolved dependency graph
+#[derive(Debug, Clone)]
+pub struct GraphPackage {
+    /// Package name
+    pub name: String,
+    /// Resolved version
+    pub version: String,
+    /// Whether the package is a root of the graph (the project's own crate)
+    pub root: bool,
+    /// Whether the package is a proc-macro, which never reaches the artifact
+    pub proc_macro: bool,
+    /// Indices of the packages it depends on (normal dependencies only)
+    pub deps: Vec<usize>,
+}
+
+impl GraphPackage {
+    fn label(&self) -> String {
+        format!("{} {}", self.name, self.version)
+    }
+}
+
+/// A dependency edge into a framework family
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FrameworkEntry {
+    /// Package that depends on the framework (`name version`)
+    pub from: String,
+    /// Framework package it depends on (`name version`)
+    pub to: String,
+    /// Shortest chain from the project to `to`, both ends included
+    pub path: Vec<String>,
+}
+
+/// One framework family found in the graph
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameworkInclusion {
+    /// Framework name
+    pub name: String,
+    /// Distinct versions of the framework's main crate
+    pub versions: Vec<String>,
+    /// Family crates in the graph (`name version`)
+    pub crates: Vec<String>,
+    /// Size of the family's symbols, when a module was measured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Edges through which the family enters the graph
+    pub entries: Vec<FrameworkEntry>,
+}
+
+impl SizedEntry for FrameworkInclusion {
+    fn size_bytes(&self) -> Option<u64> {
+        self.size_bytes
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, pattern: &Regex) -> bool {
+        pattern.is_match(&self.name) || self.crates.iter().any(|c| pattern.is_match(c))
+    }
+}
+
+/// Frameworks in a project's WASM dependency graph
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameworkReport {
+    /// Frameworks found, largest first (by name without a module)
+    pub frameworks: Vec<FrameworkInclusion>,
+    /// Size of every framework but the largest, when several were measured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicated_bytes: Option<u64>,
+    /// Caveats about the measurement
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl FrameworkReport {
+    /// Whether two frameworks, or two versions of one, are included
+    pub fn has_double_inclusion(&self) -> bool {
+        self.frameworks.len() > 1 || self.frameworks.iter().any(|f| f.versions.len() > 1)
+    }
+}
+
+/// Detects frameworks included more than once
+pub struct FrameworkAnalyzer {
+    project_root: PathBuf,
+}
+
+impl FrameworkAnalyzer {
+    /// Create an analyzer for the project at `project_root`
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Find the frameworks in the graph, measuring them in `wasm_file` if given
+    pub fn analyze(&self, wasm_file: Option<&Path>) -> Result<FrameworkReport> {
+        let graph = self.graph()?;
+        let crate_sizes = match wasm_file {
+            Some(wasm_file) => {
+                let mut sizes = HashMap::new();
+                for item in TwiggyAnalyzer::new(wasm_file).sized_items()? {
+                    if let Some(crate_name) = crate_of_symbol(&item.name) {
+                        *sizes.entry(crate_name).or_insert(0) += item.size_bytes;
+                    }
+                }
+                Some(sizes)
+            }
+            None => None,
+        };
+        Ok(detect(&graph, crate_sizes.as_ref()))
+    }
+
+    /// Resolved graph for the WASM target, normal dependencies only
+    fn graph(&self) -> Result<Vec<GraphPackage>> {
+        let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+        let metadata = MetadataCommand::new()
+            .current_dir(&self.project_root)
+            .other_options(vec![
+                "--filter-platform".to_string(),
+                WASM_TARGET.to_string(),
+            ])
+            .exec()
+            .context("Failed to resolve the WASM dependency graph")?;
+        let resolve = metadata
+            .resolve
+            .as_ref()
+            .context("No dependency resolution found in cargo metadata")?;
+
+        let index: HashMap<_, _> = metadata
+            .packages
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (&p.id, i))
+            .collect();
+        let roots: HashSet<_> = match metadata.root_package() {
+            Some(root) => HashSet::from([&root.id]),
+            None => metadata.workspace_members.iter().collect(),
+        };
+
+        let mut graph: Vec<GraphPackage> = metadata
+            .packages
+            .iter()
+            .map(|p| GraphPackage {
+                name: p.name.to_string(),
+                version: p.version.to_string(),
+                root: roots.contains(&p.id),
+                proc_macro: p.targets.iter().any(|t| t.is_kind(TargetKind::ProcMacro)),
+                deps: Vec::new(),
+            })
+            .collect();
+        for node in &resolve.nodes {
+            let Some(&from) = index.get(&node.id) else {
+                continue;
+            };
+            graph[from].deps = node
+                .deps
+                .iter()
+                .filter(|dep| {
+                    dep.dep_kinds
+                        .iter()
+                        .any(|k| matches!(k.kind, DependencyKind::Normal))
+                })
+                .filter_map(|dep| index.get(&dep.pkg).copied())
+                .collect();
+        }
+        Ok(graph)
+    }
+}
+
+/// Framework family a crate belongs to
+fn framework_of(package: &str) -> Option<&'static str> {
+    let name = package.replace('-', "_");
+    FRAMEWORKS
+        .iter()
+        .find(|(_, prefixes)| {
+            prefixes.iter().any(|prefix| {
+                name == *prefix
+                    || name
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.starts_with('_'))
+            })
+        })
+        .map(|(framework, _)| *framework)
+}
+
+/// Group the graph's crates into framework families
+///
+/// Only crates reachable from the roots over normal dependencies count, and
+/// proc-macros are not followed: their dependencies are built for the host.
+/// `crate_sizes` maps crate names, as they appear in symbol paths, to sizes.
+pub fn detect(
+    graph: &[GraphPackage],
+    crate_sizes: Option<&HashMap<String, u64>>,
+) -> FrameworkReport {
+    // Breadth-first from the roots; `parent` doubles as the visited set
+    let mut parent: HashMap<usize, Option<usize>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for i in (0..graph.len()).filter(|&i| graph[i].root) {
+        parent.insert(i, None);
+        queue.push_back(i);
+    }
+    while let Some(i) = queue.pop_front() {
+        if graph[i].proc_macro {
+            continue;
+        }
+        for &dep in &graph[i].deps {
+            if let Entry::Vacant(e) = parent.entry(dep) {
+                e.insert(Some(i));
+                queue.push_back(dep);
+            }
+        }
+    }
+    let path_to = |mut i: usize| {
+        let mut path = vec![graph[i].label()];
+        while let Some(Some(p)) = parent.get(&i) {
+            path.push(graph[*p].label());
+            i = *p;
+        }
+        path.reverse();
+        path
+    };
+
+    let mut families: HashMap<&str, Vec<usize>> = HashMap::new();
+    for &i in parent.keys() {
+        let package = &graph[i];
+        if package.root || package.proc_macro {
+            continue;
+        }
+        if let Some(framework) = framework_of(&package.name) {
+            families.entry(framework).or_default().push(i);
+        }
+    }
+
+    let mut frameworks: Vec<FrameworkInclusion> = families
+        .into_iter()
+        .map(|(name, members)| {
+            let member_set: HashSet<usize> = members.iter().copied().collect();
+            let main: Vec<usize> = members
+                .iter()
+                .copied()
+                .filter(|&i| graph[i].name.replace('-', "_") == name)
+                .collect();
+            let versioned = if main.is_empty() { &members } else { &main };
+            let versions: BTreeSet<String> = versioned
+                .iter()
+                .map(|&i| graph[i].version.clone())
+                .collect();
+            let crates: BTreeSet<String> = members.iter().map(|&i| graph[i].label()).collect();
+
+            // Edges from outside the family into it, from reachable packages
+            let mut entries = Vec::new();
+            for (from, package) in graph.iter().enumerate() {
+                if member_set.contains(&from) || !parent.contains_key(&from) || package.proc_macro {
+                    continue;
+                }
+                for &to in package.deps.iter().filter(|to| member_set.contains(to)) {
+                    let mut path = path_to(from);
+                    path.push(graph[to].label());
+                    entries.push(FrameworkEntry {
+                        from: package.label(),
+                        to: graph[to].label(),
+                        path,
+                    });
+                }
+            }
+            entries.sort_by(|a, b| a.path.len().cmp(&b.path.len()).then(a.from.cmp(&b.from)));
+            entries.dedup();
+
+            let size_bytes = crate_sizes.map(|sizes| {
+                sizes
+                    .iter()
+                    .filter(|(crate_name, _)| framework_of(crate_name) == Some(name))
+                    .map(|(_, bytes)| bytes)
+                    .sum()
+            });
+            FrameworkInclusion {
+                name: name.to_string(),
+                versions: versions.into_iter().collect(),
+                crates: crates.into_iter().collect(),
+                size_bytes,
+                entries,
+            }
+        })
+        .collect();
+    frameworks.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut warnings = Vec::new();
+    let duplicated_bytes = crate_sizes.and_then(|_| {
+        (frameworks.len() > 1).then(|| frameworks.iter().skip(1).filter_map(|f| f.size_bytes).sum())
+    });
+    if crate_sizes.is_some() && frameworks.iter().any(|f| f.versions.len() > 1) {
+        warnings.push(
+            "Versions of one framework share crate names in symbols, so their sizes are combined"
+                .to_string(),
+        );
+    }
+
+    FrameworkReport {
+        frameworks,
+        duplicated_bytes,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, version: &str, deps: &[usize]) -> GraphPackage {
+        GraphPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            root: false,
+            proc_macro: false,
+            deps: deps.to_vec(),
+        }
+    }
+
+    /// app -> leptos 0.7, app -> shared-ui -> yew 0.21 -> yew-macro (proc) -> yew 0.20
+    fn graph() -> Vec<GraphPackage> {
+        let mut app = package("app", "0.1.0", &[1, 3]);
+        app.root = true;
+        let mut yew_macro = package("yew-macro", "0.21.0", &[6]);
+        yew_macro.proc_macro = true;
+        vec![
+            app,
+            package("leptos", "0.7.0", &[2]),
+            package("reactive_graph", "0.1.0", &[]),
+            package("shared-ui", "0.3.0", &[4]),
+            package("yew", "0.21.0", &[5]),
+            yew_macro,
+            package("yew", "0.20.0", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_detect_finds_frameworks_and_entry_edges() {
+        let report = detect(&graph(), None);
+        assert!(report.has_double_inclusion());
+        assert_eq!(report.duplicated_bytes, None);
+
+        let names: Vec<&str> = report.frameworks.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["leptos", "yew"]);
+
+        let yew = &report.frameworks[1];
+        // The proc-macro's dependencies are built for the host, not the artifact
+        assert_eq!(yew.versions, vec!["0.21.0"]);
+        assert_eq!(yew.crates, vec!["yew 0.21.0"]);
+        assert_eq!(
+            yew.entries,
+            vec![FrameworkEntry {
+                from: "shared-ui 0.3.0".to_string(),
+                to: "yew 0.21.0".to_string(),
+                path: vec![
+                    "app 0.1.0".to_string(),
+                    "shared-ui 0.3.0".to_string(),
+                    "yew 0.21.0".to_string()
+                ],
+            }]
+        );
+        assert_eq!(report.frameworks[0].crates.len(), 2);
+    }
+
+    #[test]
+    fn test_detect_attributes_duplicated_size() {
+        let sizes = HashMap::from([
+            ("leptos".to_string(), 30_000),
+            ("reactive_graph".to_string(), 20_000),
+            ("yew".to_string(), 40_000),
+            ("serde".to_string(), 9_000),
+        ]);
+        let report = detect(&graph(), Some(&sizes));
+
+        let sizes: Vec<(&str, Option<u64>)> = report
+            .frameworks
+            .iter()
+            .map(|f| (f.name.as_str(), f.size_bytes))
+            .collect();
+        assert_eq!(sizes, vec![("leptos", Some(50_000)), ("yew", Some(40_000))]);
+        assert_eq!(report.duplicated_bytes, Some(40_000));
+        assert!(report.warnings.is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! Framework double-inclusion report formatting
+
+use super::frameworks::FrameworkReport;
+use super::report_utils::{format_bytes, omitted_footer, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Entry edges listed per framework by default
+const DEFAULT_ENTRIES_PER_FRAMEWORK: usize = 5;
+
+/// Format the framework report for console output
+pub fn format_console_report(report: &FrameworkReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the framework report, limiting the entry edges listed per framework
+pub fn format_console_report_with_limit(
+    report: &FrameworkReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("UI Frameworks").bold().underlined())?;
+    if report.frameworks.is_empty() {
+        writeln!(
+            output,
+            "\n   {} No reactive framework in the WASM dependency graph",
+            style("✓").green()
+        )?;
+        return Ok(output);
+    }
+
+    if report.has_double_inclusion() {
+        writeln!(
+            output,
+            "\n   {} {}",
+            style("⚠").yellow(),
+            style("More than one framework runtime ships in the artifact").yellow()
+        )?;
+        if let Some(bytes) = report.duplicated_bytes {
+            writeln!(
+                output,
+                "   Consolidating on the largest framework would remove about {}",
+                style(format_bytes(bytes)).yellow().bold()
+            )?;
+        }
+    } else {
+        writeln!(
+            output,
+            "\n   {} One framework, one version",
+            style("✓").green()
+        )?;
+    }
+    for warning in &report.warnings {
+        writeln!(output, "   {}", style(warning).dim())?;
+    }
+
+    for framework in &report.frameworks {
+        let size = framework
+            .size_bytes
+            .map(|bytes| format!(" ({})", format_bytes(bytes)))
+            .unwrap_or_default();
+        let versions = if framework.versions.len() > 1 {
+            style(framework.versions.join(", ")).red().bold()
+        } else {
+            style(framework.versions.join(", ")).dim()
+        };
+        writeln!(
+            output,
+            "\n  {} {}{}",
+            style(&framework.name).cyan().bold(),
+            versions,
+            size
+        )?;
+        writeln!(
+            output,
+            "    {}",
+            style(format!("crates: {}", framework.crates.join(", "))).dim()
+        )?;
+
+        let shown = limit.visible(framework.entries.len(), Some(DEFAULT_ENTRIES_PER_FRAMEWORK));
+        for entry in framework.entries.iter().take(shown) {
+            writeln!(output, "    {}", entry.path.join(" → "))?;
+        }
+        if let Some(footer) = omitted_footer(framework.entries.len(), shown, "edges") {
+            writeln!(output, "    {}", style(footer).dim())?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::frameworks::{FrameworkEntry, FrameworkInclusion};
+
+    #[test]
+    fn test_format_console_report_shows_entry_paths_and_duplicated_size() {
+        let inclusion = |name: &str, versions: &[&str], size_bytes| FrameworkInclusion {
+            name: name.to_string(),
+            versions: versions.iter().map(|v| v.to_string()).collect(),
+            crates: vec![format!("{} {}", name, versions[0])],
+            size_bytes: Some(size_bytes),
+            entries: vec![FrameworkEntry {
+                from: "shared-ui 0.3.0".to_string(),
+                to: format!("{} {}", name, versions[0]),
+                path: vec![
+                    "app 0.1.0".to_string(),
+                    "shared-ui 0.3.0".to_string(),
+                    format!("{} {}", name, versions[0]),
+                ],
+            }],
+        };
+        let report = FrameworkReport {
+            frameworks: vec![
+                inclusion("leptos", &["0.7.0"], 51_200),
+                inclusion("yew", &["0.20.0", "0.21.0"], 40_960),
+            ],
+            duplicated_bytes: Some(40_960),
+            warnings: Vec::new(),
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("More than one framework runtime"));
+        assert!(text.contains("remove about 40.00 KiB"));
+        assert!(text.contains("0.20.0, 0.21.0"));
+        assert!(text.contains("app 0.1.0 → shared-ui 0.3.0 → yew 0.20.0"));
+    }
+}
@@ -0,0 +1,331 @@
+//! Aggregate size health score for `analyze --mode score`
+//!
+//! Each analysis reports in its own terms, which makes progress hard to
+//! track. The score condenses the dead code, monomorphization, embedded
+//! asset, heavy dependency and formatting findings into one number from 0
+//! to 100. Every category is scored on its own (100 at or below a healthy
+//! level, 0 at or beyond a clearly bloated one, linear in between) and the
+//! overall score is their weighted average. Categories whose analysis could
+//! not run are left out and the remaining weights are rescaled.
+
+use super::asset_metrics::ScanResults;
+use super::deps_types::{DependencyReport, IssueSeverity};
+use super::fmt_bloat::FmtBloatReport;
+use super::report_utils::SizedEntry;
+use super::twiggy::AnalysisResults;
+use crate::fmt::percent_of;
+use serde::Serialize;
+
+/// A scored aspect of the module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCategory {
+    /// Unreachable code and data
+    DeadCode,
+    /// Duplicate generic instantiations
+    Monomorphization,
+    /// Files embedded with `include_bytes!`/`include_str!`
+    Assets,
+    /// Critical and high severity dependency issues
+    HeavyDependencies,
+    /// Code only the formatting machinery needs
+    Formatting,
+}
+
+impl HealthCategory {
+    /// Get human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            HealthCategory::DeadCode => "Dead code",
+            HealthCategory::Monomorphization => "Monomorphization",
+            HealthCategory::Assets => "Embedded assets",
+            HealthCategory::HeavyDependencies => "Heavy dependencies",
+            HealthCategory::Formatting => "Formatting",
+        }
+    }
+
+    /// Share of the overall score
+    pub fn weight(&self) -> u32 {
+        match self {
+            HealthCategory::DeadCode => 25,
+            HealthCategory::Monomorphization => 25,
+            HealthCategory::Assets => 20,
+            HealthCategory::HeavyDependencies => 15,
+            HealthCategory::Formatting => 15,
+        }
+    }
+
+    /// Metric values scoring 100 and 0
+    fn bounds(&self) -> (f64, f64) {
+        match self {
+            HealthCategory::DeadCode => (1.0, 10.0),
+            HealthCategory::Monomorphization => (5.0, 30.0),
+            HealthCategory::Assets => (5.0, 50.0),
+            HealthCategory::HeavyDependencies => (0.0, 5.0),
+            HealthCategory::Formatting => (3.0, 20.0),
+        }
+    }
+}
+
+/// Score of one category
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryScore {
+    /// Scored category
+    pub category: HealthCategory,
+    /// Measured value: percent of the module, or a count for dependencies
+    pub metric: f64,
+    /// The measured value in words
+    pub detail: String,
+    /// Category score, 0 to 100
+    pub score: u8,
+    /// Share of the overall score
+    pub weight: u32,
+}
+
+impl SizedEntry for CategoryScore {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        self.category.name()
+    }
+}
+
+/// Overall size health of a module
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HealthScore {
+    /// Size of the analyzed WASM file
+    pub total_size_bytes: u64,
+    /// Weighted score, 0 to 100
+    pub score: u8,
+    /// Scores per category, in [`HealthCategory`] order
+    pub categories: Vec<CategoryScore>,
+    /// Analyses that could not run, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Collects analysis results and builds a [`HealthScore`]
+#[derive(Debug, Default)]
+pub struct HealthScoreBuilder {
+    total_size_bytes: u64,
+    categories: Vec<CategoryScore>,
+    warnings: Vec<String>,
+}
+
+impl HealthScoreBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Score dead code from a dead analysis, or monomorphization from a
+    /// monos analysis; other modes are ignored
+    ///
+    /// Dead analyses list only the largest unreachable items, so the dead
+    /// code share is a lower bound.
+    pub fn add_analysis(&mut self, results: &AnalysisResults) {
+        self.total_size_bytes = self.total_size_bytes.max(results.total_size_bytes);
+        let total = results.total_size_bytes;
+        match results.mode.as_str() {
+            "dead" => {
+                let bytes: u64 = results.items.iter().map(|i| i.size_bytes).sum();
+                let percent = percent_of(bytes, total);
+                self.add(
+                    HealthCategory::DeadCode,
+                    percent,
+                    format!("{:.1}% of the module is unreachable", percent),
+                );
+            }
+            "monos" => {
+                let bytes: u64 = results
+                    .mono_groups
+                    .iter()
+                    .flatten()
+                    .map(|g| g.potential_savings_bytes)
+                    .sum();
+                let percent = percent_of(bytes, total);
+                self.add(
+                    HealthCategory::Monomorphization,
+                    percent,
+                    format!("{:.1}% of the module is duplicate instantiations", percent),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Score the embedded assets against the module size
+    pub fn add_assets(&mut self, results: &ScanResults) {
+        let percent = percent_of(results.total_size_bytes, self.total_size_bytes).min(100.0);
+        self.add(
+            HealthCategory::Assets,
+            percent,
+            format!(
+                "{} embedded assets, {:.1}% of the module",
+                results.total_assets, percent
+            ),
+        );
+    }
+
+    /// Score the critical and high severity dependency issues
+    pub fn add_dependencies(&mut self, report: &DependencyReport) {
+        let heavy = report
+            .issues
+            .iter()
+            .filter(|i| matches!(i.severity, IssueSeverity::Critical | IssueSeverity::High))
+            .count();
+        self.add(
+            HealthCategory::HeavyDependencies,
+            heavy as f64,
+            format!("{} critical or high severity dependencies", heavy),
+        );
+    }
+
+    /// Score the code only formatting needs
+    pub fn add_fmt(&mut self, report: &FmtBloatReport) {
+        let percent = percent_of(report.fmt_bytes, report.module_bytes);
+        self.add(
+            HealthCategory::Formatting,
+            percent,
+            format!("{:.1}% of the module is formatting code", percent),
+        );
+    }
+
+    /// Record an analysis that could not run
+    pub fn add_warning(&mut self, warning: impl Into<String>) {
+        self.warnings.push(warning.into());
+    }
+
+    fn add(&mut self, category: HealthCategory, metric: f64, detail: String) {
+        let (good, bad) = category.bounds();
+        let score = (100.0 * (bad - metric) / (bad - good)).clamp(0.0, 100.0);
+        self.categories.retain(|c| c.category != category);
+        self.categories.push(CategoryScore {
+            category,
+            metric,
+            detail,
+            score: score.round() as u8,
+            weight: category.weight(),
+        });
+    }
+
+    /// Weighted overall score
+    pub fn build(mut self) -> HealthScore {
+        self.categories.sort_by_key(|c| c.category);
+        let weights: u32 = self.categories.iter().map(|c| c.weight).sum();
+        let weighted: u32 = self
+            .categories
+            .iter()
+            .map(|c| u32::from(c.score) * c.weight)
+            .sum();
+        let score = if weights == 0 {
+            0
+        } else {
+            (f64::from(weighted) / f64::from(weights)).round() as u8
+        };
+        HealthScore {
+            total_size_bytes: self.total_size_bytes,
+            score,
+            categories: self.categories,
+            warnings: self.warnings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::twiggy::{AnalysisItem, MonomorphizationGroup};
+
+    fn results(mode: &str, items: Vec<AnalysisItem>) -> AnalysisResults {
+        AnalysisResults {
+            total_size_bytes: 100_000,
+            mode: mode.to_string(),
+            items,
+            limit: None,
+            recommendations: Vec::new(),
+            mono_groups: None,
+        }
+    }
+
+    #[test]
+    fn test_build_weights_category_scores() {
+        let mut builder = HealthScoreBuilder::new();
+        // 5.5% dead: halfway between 1% and 10%
+        builder.add_analysis(&results(
+            "dead",
+            vec![AnalysisItem {
+                size_bytes: 5_500,
+                percentage: 5.5,
+                name: "code[7]".to_string(),
+            }],
+        ));
+        let mut monos = results("monos", Vec::new());
+        monos.mono_groups = Some(vec![MonomorphizationGroup {
+            function_name: "app::encode".to_string(),
+            instantiation_count: 2,
+            total_size_bytes: 4_000,
+            avg_size_bytes: 2_000,
+            instantiations: Vec::new(),
+            potential_savings_bytes: 2_000,
+            type_arguments: Vec::new(),
+            refactor: None,
+        }]);
+        builder.add_analysis(&monos);
+        builder.add_analysis(&results("top", Vec::new()));
+        builder.add_warning("deps: cargo metadata failed");
+
+        let health = builder.build();
+
+        let scores: Vec<(HealthCategory, u8)> = health
+            .categories
+            .iter()
+            .map(|c| (c.category, c.score))
+            .collect();
+        assert_eq!(
+            scores,
+            [
+                (HealthCategory::DeadCode, 50),
+                (HealthCategory::Monomorphization, 100)
+            ]
+        );
+        assert_eq!(health.score, 75);
+        assert_eq!(health.warnings, ["deps: cargo metadata failed"]);
+    }
+
+    #[test]
+    fn test_add_dependencies_counts_heavy_issues() {
+        let issue = |severity| crate::analyzer::deps_types::DependencyIssue {
+            package: "regex".to_string(),
+            version: "1.0.0".to_string(),
+            severity,
+            issue: String::new(),
+            suggestion: String::new(),
+            size_impact_kb: None,
+            savings_percent: None,
+            verify_id: None,
+        };
+        let report = DependencyReport {
+            total_deps: 3,
+            direct_deps: 3,
+            issues: vec![
+                issue(IssueSeverity::Critical),
+                issue(IssueSeverity::High),
+                issue(IssueSeverity::Low),
+            ],
+            duplicates: Default::default(),
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+            rule_violations: Vec::new(),
+        };
+        let mut builder = HealthScoreBuilder::new();
+        builder.add_dependencies(&report);
+
+        let health = builder.build();
+        assert_eq!(health.categories[0].metric, 2.0);
+        assert_eq!(health.categories[0].score, 60);
+        assert_eq!(health.score, 60);
+    }
+}
@@ -0,0 +1,77 @@
+//! Size health score report formatting
+
+use super::health_score::HealthScore;
+use super::report_utils::format_bytes;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Format the health score for console output
+pub fn format_console_report(health: &HealthScore) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Size Health Score").bold().underlined()
+    )?;
+    for warning in &health.warnings {
+        writeln!(output, "   {} {}", style("⚠").yellow(), warning)?;
+    }
+    writeln!(
+        output,
+        "\n   {} / 100 for a {} module",
+        styled_score(health.score),
+        format_bytes(health.total_size_bytes)
+    )?;
+
+    writeln!(output, "\n{}", style("BREAKDOWN:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    for category in &health.categories {
+        writeln!(
+            output,
+            "  {:<20} {:>3}  (weight {:>2})  {}",
+            category.category.name(),
+            styled_score(category.score),
+            category.weight,
+            style(&category.detail).dim()
+        )?;
+    }
+
+    Ok(output)
+}
+
+fn styled_score(score: u8) -> console::StyledObject<u8> {
+    match score {
+        80.. => style(score).green().bold(),
+        50..80 => style(score).yellow().bold(),
+        _ => style(score).red().bold(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::health_score::{CategoryScore, HealthCategory};
+
+    #[test]
+    fn test_format_console_report_lists_categories_and_warnings() {
+        let health = HealthScore {
+            total_size_bytes: 200_000,
+            score: 72,
+            categories: vec![CategoryScore {
+                category: HealthCategory::DeadCode,
+                metric: 3.5,
+                detail: "3.5% of the module is unreachable".to_string(),
+                score: 72,
+                weight: 25,
+            }],
+            warnings: vec!["assets: scan failed".to_string()],
+        };
+
+        let text = format_console_report(&health).unwrap();
+        assert!(text.contains("72 / 100 for a 195.31 KiB module"));
+        assert!(text.contains("Dead code"));
+        assert!(text.contains("3.5% of the module is unreachable"));
+        assert!(text.contains("assets: scan failed"));
+    }
+}
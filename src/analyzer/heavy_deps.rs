@@ -1,10 +1,22 @@
 //! Database of known heavy dependencies and their lighter alternatives
 //!
-//! Based on real-world WASM optimization case studies (Warp.dev).
-
-use std::collections::HashMap;
+//! Based on real-world WASM optimization case studies (Warp.dev). Projects
+//! extend or override it with `[heavy-deps.<crate>]` tables in
+//! `.wasm-slim.toml` or a `heavy-deps.toml` file; see [`HeavyDepsDatabase`].
+
+use super::size_db::SizeDatabase;
+use crate::config::file::{ConfigFile, HeavyDepSettings};
+use crate::infra::{FileSystem, RealFileSystem};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 use std::sync::OnceLock;
 
+/// Project heavy-dependency file, next to `.wasm-slim.toml`
+pub const HEAVY_DEPS_FILE_NAME: &str = "heavy-deps.toml";
+
 /// A known heavy dependency with metadata
 #[derive(Debug, Clone)]
 pub struct HeavyDependency {
@@ -13,7 +25,7 @@ pub struct HeavyDependency {
     /// Percentage of typical bundle
     pub bundle_percent: Option<(u8, u8)>,
     /// Description of why it's heavy
-    pub reason: &'static str,
+    pub reason: Cow<'static, str>,
     /// Recommended alternatives
     pub alternatives: Vec<DependencyAlternative>,
 }
@@ -24,17 +36,18 @@ pub struct DependencyAlternative {
     /// Type of alternative
     pub alt_type: AlternativeType,
     /// Crate name (if replacement)
-    pub crate_name: Option<&'static str>,
+    pub crate_name: Option<Cow<'static, str>>,
     /// Expected size in KB
     pub size_kb: Option<(u32, u32)>,
     /// Estimated savings percentage
     pub savings_percent: u8,
     /// Description
-    pub description: &'static str,
+    pub description: Cow<'static, str>,
 }
 
 /// Type of dependency alternative recommendation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum AlternativeType {
     /// Replace with different crate
     Replacement,
@@ -61,13 +74,13 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (1000, 1500),
             bundle_percent: Some((30, 45)),
-            reason: "Full compiler suite with all features",
+            reason: "Full compiler suite with all features".into(),
             alternatives: vec![DependencyAlternative {
                 alt_type: AlternativeType::Split,
-                crate_name: Some("swc_ecma_parser"),
+                crate_name: Some("swc_ecma_parser".into()),
                 size_kb: Some((400, 600)),
                 savings_percent: 60,
-                description: "Split into minimal components (parser + AST only)",
+                description: "Split into minimal components (parser + AST only)".into(),
             }],
         },
     );
@@ -78,21 +91,21 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (800, 1000),
             bundle_percent: Some((15, 25)),
-            reason: "Full-featured PDF library with many formats",
+            reason: "Full-featured PDF library with many formats".into(),
             alternatives: vec![
                 DependencyAlternative {
                     alt_type: AlternativeType::Replacement,
-                    crate_name: Some("pdf-writer"),
+                    crate_name: Some("pdf-writer".into()),
                     size_kb: Some((200, 400)),
                     savings_percent: 50,
-                    description: "Lightweight PDF writer for simple use cases",
+                    description: "Lightweight PDF writer for simple use cases".into(),
                 },
                 DependencyAlternative {
                     alt_type: AlternativeType::Replacement,
-                    crate_name: Some("lopdf"),
+                    crate_name: Some("lopdf".into()),
                     size_kb: Some((400, 600)),
                     savings_percent: 30,
-                    description: "PDF library with minimal features (no rayon)",
+                    description: "PDF library with minimal features (no rayon)".into(),
                 },
             ],
         },
@@ -104,13 +117,13 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (400, 800),
             bundle_percent: Some((10, 20)),
-            reason: "Default includes rayon (doesn't work in WASM)",
+            reason: "Default includes rayon (doesn't work in WASM)".into(),
             alternatives: vec![DependencyAlternative {
                 alt_type: AlternativeType::FeatureMinimization,
                 crate_name: None,
                 size_kb: Some((400, 600)),
                 savings_percent: 25,
-                description: "Disable rayon and unused features",
+                description: "Disable rayon and unused features".into(),
             }],
         },
     );
@@ -121,13 +134,13 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (500, 1000),
             bundle_percent: Some((15, 30)),
-            reason: "Async runtime doesn't work in WASM",
+            reason: "Async runtime doesn't work in WASM".into(),
             alternatives: vec![DependencyAlternative {
                 alt_type: AlternativeType::WasmFix,
-                crate_name: Some("wasm-bindgen-futures"),
+                crate_name: Some("wasm-bindgen-futures".into()),
                 size_kb: Some((50, 100)),
                 savings_percent: 80,
-                description: "WASM-specific async runtime",
+                description: "WASM-specific async runtime".into(),
             }],
         },
     );
@@ -138,13 +151,13 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (400, 800),
             bundle_percent: Some((10, 25)),
-            reason: "Async runtime doesn't work in WASM",
+            reason: "Async runtime doesn't work in WASM".into(),
             alternatives: vec![DependencyAlternative {
                 alt_type: AlternativeType::WasmFix,
-                crate_name: Some("wasm-bindgen-futures"),
+                crate_name: Some("wasm-bindgen-futures".into()),
                 size_kb: Some((50, 100)),
                 savings_percent: 80,
-                description: "WASM-specific async runtime",
+                description: "WASM-specific async runtime".into(),
             }],
         },
     );
@@ -155,13 +168,13 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (400, 600),
             bundle_percent: Some((10, 15)),
-            reason: "Full text shaping library",
+            reason: "Full text shaping library".into(),
             alternatives: vec![DependencyAlternative {
                 alt_type: AlternativeType::Optional,
                 crate_name: None,
                 size_kb: Some((0, 0)),
                 savings_percent: 100,
-                description: "Make optional via feature flag (removes from default build)",
+                description: "Make optional via feature flag (removes from default build)".into(),
             }],
         },
     );
@@ -172,21 +185,21 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (300, 500),
             bundle_percent: Some((8, 15)),
-            reason: "Feature-rich date/time library",
+            reason: "Feature-rich date/time library".into(),
             alternatives: vec![
                 DependencyAlternative {
                     alt_type: AlternativeType::Replacement,
-                    crate_name: Some("time"),
+                    crate_name: Some("time".into()),
                     size_kb: Some((150, 250)),
                     savings_percent: 40,
-                    description: "Lighter date/time library",
+                    description: "Lighter date/time library".into(),
                 },
                 DependencyAlternative {
                     alt_type: AlternativeType::WasmFix,
-                    crate_name: Some("js-sys"),
+                    crate_name: Some("js-sys".into()),
                     size_kb: Some((50, 100)),
                     savings_percent: 70,
-                    description: "Use browser Date API (WASM only)",
+                    description: "Use browser Date API (WASM only)".into(),
                 },
             ],
         },
@@ -198,13 +211,13 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (300, 600),
             bundle_percent: Some((8, 15)),
-            reason: "Large unicode tables included by default",
+            reason: "Large unicode tables included by default".into(),
             alternatives: vec![DependencyAlternative {
                 alt_type: AlternativeType::FeatureMinimization,
                 crate_name: None,
                 size_kb: Some((100, 200)),
                 savings_percent: 60,
-                description: "Disable unicode support if not needed",
+                description: "Disable unicode support if not needed".into(),
             }],
         },
     );
@@ -215,30 +228,67 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
         HeavyDependency {
             size_kb: (400, 800),
             bundle_percent: Some((10, 20)),
-            reason: "Includes all image format codecs by default",
+            reason: "Includes all image format codecs by default".into(),
             alternatives: vec![DependencyAlternative {
                 alt_type: AlternativeType::FeatureMinimization,
                 crate_name: None,
                 size_kb: Some((150, 300)),
                 savings_percent: 50,
-                description: "Enable only needed image formats",
+                description: "Enable only needed image formats".into(),
             }],
         },
     );
 
+    // serde_json
+    db.insert(
+        "serde_json",
+        HeavyDependency {
+            size_kb: (50, 250),
+            bundle_percent: Some((5, 15)),
+            reason:
+                "Generic serializer and deserializer code is instantiated for every derived type"
+                    .into(),
+            alternatives: vec![
+                DependencyAlternative {
+                    alt_type: AlternativeType::Replacement,
+                    crate_name: Some("miniserde".into()),
+                    size_kb: Some((20, 60)),
+                    savings_percent: 60,
+                    description: "JSON-only derives that avoid monomorphized visitors".into(),
+                },
+                DependencyAlternative {
+                    alt_type: AlternativeType::Replacement,
+                    crate_name: Some("serde-lite".into()),
+                    size_kb: Some((30, 80)),
+                    savings_percent: 40,
+                    description: "Derives through an intermediate value instead of per-format code"
+                        .into(),
+                },
+                DependencyAlternative {
+                    alt_type: AlternativeType::Replacement,
+                    crate_name: None,
+                    size_kb: None,
+                    savings_percent: 30,
+                    description: "Hand-written impls for the few types that cross the JS boundary"
+                        .into(),
+                },
+            ],
+        },
+    );
+
     // getrandom
     db.insert(
         "getrandom",
         HeavyDependency {
             size_kb: (50, 100),
             bundle_percent: None,
-            reason: "Default doesn't work in WASM (missing OS entropy)",
+            reason: "Default doesn't work in WASM (missing OS entropy)".into(),
             alternatives: vec![DependencyAlternative {
                 alt_type: AlternativeType::WasmFix,
                 crate_name: None,
                 size_kb: Some((50, 100)),
                 savings_percent: 0,
-                description: "Enable WASM support (required for functionality, not size)",
+                description: "Enable WASM support (required for functionality, not size)".into(),
             }],
         },
     );
@@ -247,12 +297,124 @@ fn init_heavy_deps_database() -> HashMap<&'static str, HeavyDependency> {
 }
 
 /// Get information about a heavy dependency
-pub fn get_heavy_dependency_info(name: &str) -> Option<&HeavyDependency> {
+pub fn get_heavy_dependency_info(name: &str) -> Option<&'static HeavyDependency> {
     HEAVY_DEPS_DATABASE
         .get_or_init(init_heavy_deps_database)
         .get(name)
 }
 
+/// The built-in database with a project's own entries layered over it
+///
+/// Size estimates come from the project entries, then from measured sizes
+/// (see [`SizeDatabase`]), then from the built-in ranges.
+#[derive(Debug, Clone, Default)]
+pub struct HeavyDepsDatabase {
+    custom: HashMap<String, HeavyDependency>,
+    measurements: SizeDatabase,
+}
+
+impl HeavyDepsDatabase {
+    /// Built-in entries plus `entries`, which win for the same crate
+    pub fn with_entries(entries: &BTreeMap<String, HeavyDepSettings>) -> Self {
+        Self {
+            custom: entries
+                .iter()
+                .map(|(name, entry)| (name.clone(), HeavyDependency::from(entry)))
+                .collect(),
+            measurements: SizeDatabase::default(),
+        }
+    }
+
+    /// Use `measurements` instead of the bundled size database
+    pub fn with_measurements(mut self, measurements: SizeDatabase) -> Self {
+        self.measurements = measurements;
+        self
+    }
+
+    /// Built-in entries plus the project's `heavy-deps.toml` and `[heavy-deps]` tables
+    ///
+    /// `.wasm-slim.toml` wins over `heavy-deps.toml` for the same crate.
+    /// Measured sizes come from the project's downloaded size database when
+    /// there is one.
+    pub fn for_project(project_root: &Path, config: &ConfigFile) -> Result<Self> {
+        Self::for_project_with_fs(project_root, config, &RealFileSystem)
+    }
+
+    /// Load the project entries with a custom filesystem implementation
+    pub fn for_project_with_fs<FS: FileSystem>(
+        project_root: &Path,
+        config: &ConfigFile,
+        fs: &FS,
+    ) -> Result<Self> {
+        let path = project_root.join(HEAVY_DEPS_FILE_NAME);
+        let mut entries: BTreeMap<String, HeavyDepSettings> = match fs.read_to_string(&path) {
+            Ok(contents) => toml_edit::de::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", HEAVY_DEPS_FILE_NAME))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        for (name, entry) in &entries {
+            entry
+                .validate()
+                .with_context(|| format!("Invalid entry '{}' in {}", name, HEAVY_DEPS_FILE_NAME))?;
+        }
+        entries.extend(config.heavy_deps.clone());
+        Ok(Self::with_entries(&entries)
+            .with_measurements(SizeDatabase::load_with_fs(project_root, fs)))
+    }
+
+    /// Information about a heavy dependency, preferring project entries
+    pub fn get(&self, name: &str) -> Option<&HeavyDependency> {
+        self.custom
+            .get(name)
+            .or_else(|| get_heavy_dependency_info(name))
+    }
+
+    /// Size range of a crate in KB: configured, measured or built-in
+    pub fn size_kb(&self, name: &str) -> Option<(u32, u32)> {
+        if let Some(entry) = self.custom.get(name) {
+            return Some(entry.size_kb);
+        }
+        self.measurements
+            .wasm_kb(name)
+            .map(|kb| (kb, kb))
+            .or_else(|| get_heavy_dependency_info(name).map(|info| info.size_kb))
+    }
+
+    /// Expected size of an alternative in KB, measured when it names a crate
+    pub fn alternative_size_kb(&self, alternative: &DependencyAlternative) -> Option<(u32, u32)> {
+        alternative
+            .crate_name
+            .as_deref()
+            .and_then(|name| self.measurements.wasm_kb(name))
+            .map(|kb| (kb, kb))
+            .or(alternative.size_kb)
+    }
+}
+
+impl From<&HeavyDepSettings> for HeavyDependency {
+    fn from(entry: &HeavyDepSettings) -> Self {
+        Self {
+            size_kb: entry.size_kb,
+            bundle_percent: entry.bundle_percent,
+            reason: entry.reason.clone().into(),
+            alternatives: entry
+                .alternatives
+                .iter()
+                .map(|alt| DependencyAlternative {
+                    alt_type: alt.alt_type,
+                    crate_name: alt.crate_name.clone().map(Cow::Owned),
+                    size_kb: alt.size_kb,
+                    savings_percent: alt.savings_percent,
+                    description: alt.description.clone().into(),
+                })
+                .collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,7 +440,7 @@ mod tests {
         assert!(printpdf
             .alternatives
             .iter()
-            .any(|a| a.crate_name == Some("pdf-writer")));
+            .any(|a| a.crate_name.as_deref() == Some("pdf-writer")));
     }
 
     #[test]
@@ -303,7 +465,7 @@ mod tests {
         assert!(tokio
             .alternatives
             .iter()
-            .any(|a| a.crate_name == Some("wasm-bindgen-futures")));
+            .any(|a| a.crate_name.as_deref() == Some("wasm-bindgen-futures")));
     }
 
     #[test]
@@ -317,7 +479,7 @@ mod tests {
         assert!(async_std
             .alternatives
             .iter()
-            .any(|a| a.crate_name == Some("wasm-bindgen-futures")));
+            .any(|a| a.crate_name.as_deref() == Some("wasm-bindgen-futures")));
     }
 
     #[test]
@@ -466,6 +628,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_project_entries_extend_and_override_builtin_database() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join(HEAVY_DEPS_FILE_NAME),
+            r#"
+[acme-telemetry]
+size-kb = [300, 450]
+reason = "Bundles the full protobuf runtime"
+
+[tokio]
+size-kb = [100, 200]
+reason = "Internal runtime shim"
+
+[[tokio.alternatives]]
+type = "replacement"
+crate = "acme-rt"
+savings-percent = 70
+description = "Browser runtime"
+"#,
+        )
+        .unwrap();
+        let config: ConfigFile = toml_edit::de::from_str(
+            r#"
+[heavy-deps.acme-telemetry]
+size-kb = [400, 500]
+reason = "Configured in .wasm-slim.toml"
+"#,
+        )
+        .unwrap();
+
+        let database = HeavyDepsDatabase::for_project(temp.path(), &config).unwrap();
+
+        let acme = database.get("acme-telemetry").unwrap();
+        assert_eq!(acme.size_kb, (400, 500));
+        assert_eq!(acme.reason, "Configured in .wasm-slim.toml");
+        let tokio = database.get("tokio").unwrap();
+        assert_eq!(tokio.alternatives.len(), 1);
+        assert_eq!(tokio.alternatives[0].crate_name.as_deref(), Some("acme-rt"));
+        assert_eq!(tokio.alternatives[0].alt_type, AlternativeType::Replacement);
+        assert!(database.get("regex").is_some());
+    }
+
+    #[test]
+    fn test_size_estimates_prefer_entries_then_measurements() {
+        let measurements = SizeDatabase::parse(
+            r#"{"schema": 1, "updated": "2026-10-01", "crates": {
+                "tokio": {"version": "1.40", "wasm_kb": 610},
+                "wasm-bindgen-futures": {"version": "0.4", "wasm_kb": 12},
+                "regex": {"version": "1.11", "wasm_kb": 420}
+            }}"#,
+        )
+        .unwrap();
+        let entries: BTreeMap<String, HeavyDepSettings> =
+            toml_edit::de::from_str("[regex]\nsize-kb = [100, 150]\nreason = \"Pinned\"\n")
+                .unwrap();
+        let database = HeavyDepsDatabase::with_entries(&entries).with_measurements(measurements);
+
+        assert_eq!(database.size_kb("regex"), Some((100, 150)));
+        assert_eq!(database.size_kb("tokio"), Some((610, 610)));
+        assert_eq!(database.size_kb("chrono"), Some((300, 500)));
+        let futures = get_heavy_dependency_info("tokio")
+            .unwrap()
+            .alternatives
+            .iter()
+            .find(|a| a.crate_name.as_deref() == Some("wasm-bindgen-futures"))
+            .unwrap();
+        assert_eq!(database.alternative_size_kb(futures), Some((12, 12)));
+    }
+
     #[test]
     fn test_database_initialization_has_entries() {
         // Ensure database actually has entries
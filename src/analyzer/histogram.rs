@@ -0,0 +1,282 @@
+//! Function size distribution
+//!
+//! Buckets the function bodies of a WASM module by size and lists the
+//! outliers, to tell bloat spread over many small shims apart from bloat
+//! concentrated in a few giant functions.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::FunctionSizeAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = FunctionSizeAnalyzer::new().analyze(Path::new("pkg/app_bg.wasm"))?;
+//! for bucket in &report.buckets {
+//!     println!("{}: {} functions, {} bytes", bucket.label, bucket.functions, bucket.total_bytes);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use super::twiggy::native::{demangle, function_names};
+use crate::fmt::format_bytes;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use wasmparser::{Parser, Payload, TypeRef};
+
+/// Upper bounds (exclusive) of the buckets; the last bucket is open-ended
+const BUCKET_BOUNDS: [u64; 4] = [256, 1024, 10 * 1024, 50 * 1024];
+
+/// A function is an outlier at this many times the median size
+const OUTLIER_MEDIAN_FACTOR: u64 = 20;
+
+/// Smallest size reported as an outlier
+const OUTLIER_MIN_BYTES: u64 = 4096;
+
+/// Functions below this size count as small shims
+const SMALL_FUNCTION_BYTES: u64 = 1024;
+
+/// Functions at or above this size count as giant
+const LARGE_FUNCTION_BYTES: u64 = 10 * 1024;
+
+/// Functions in one size range
+#[derive(Debug, Clone, Serialize)]
+pub struct SizeBucket {
+    /// Range label (e.g., `1.00 KiB–10.00 KiB`)
+    pub label: String,
+    /// Inclusive lower bound
+    pub min_bytes: u64,
+    /// Exclusive upper bound (`None` for the last bucket)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    /// Number of functions in the range
+    pub functions: usize,
+    /// Sum of their body sizes
+    pub total_bytes: u64,
+}
+
+/// A function far larger than typical
+#[derive(Debug, Clone, Serialize)]
+pub struct SizeOutlier {
+    /// Function index (imports included)
+    pub index: u32,
+    /// Demangled name, or `code[N]` without a name section
+    pub name: String,
+    /// Body size in bytes
+    pub size_bytes: u64,
+    /// Size relative to the median function
+    pub times_median: f64,
+}
+
+impl SizedEntry for SizeOutlier {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Where most of the code size sits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SizeProfile {
+    /// Most bytes are in functions under 1 KiB
+    ManySmall,
+    /// Most bytes are in functions of 10 KiB or more
+    FewLarge,
+    /// Neither dominates
+    Mixed,
+}
+
+/// Function size distribution of a module
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionSizeReport {
+    /// Number of function bodies
+    pub functions: usize,
+    /// Sum of the body sizes
+    pub total_bytes: u64,
+    /// Median body size
+    pub median_bytes: u64,
+    /// 90th percentile body size
+    pub p90_bytes: u64,
+    /// Largest body size
+    pub max_bytes: u64,
+    /// Size ranges, smallest first
+    pub buckets: Vec<SizeBucket>,
+    /// Functions far above the median, largest first
+    pub outliers: Vec<SizeOutlier>,
+    /// Where most of the code size sits
+    pub profile: SizeProfile,
+}
+
+/// Computes the function size distribution of a module
+#[derive(Debug, Default)]
+pub struct FunctionSizeAnalyzer;
+
+impl FunctionSizeAnalyzer {
+    /// Create an analyzer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Bucket the function sizes of a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<FunctionSizeReport> {
+        let bytes = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        analyze_module(&bytes).with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+}
+
+/// Bucket the function sizes of a module
+pub fn analyze_module(module: &[u8]) -> Result<FunctionSizeReport> {
+    let mut imported_functions = 0;
+    let mut sizes: Vec<(u32, u64)> = Vec::new();
+    let mut names = HashMap::new();
+    for payload in Parser::new(0).parse_all(module) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader.into_imports() {
+                    if matches!(import?.ty, TypeRef::Func(_) | TypeRef::FuncExact(_)) {
+                        imported_functions += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let index = imported_functions + sizes.len() as u32;
+                sizes.push((index, body.range().len() as u64));
+            }
+            Payload::CustomSection(reader) => {
+                for (index, name) in function_names(&reader).into_iter().flatten() {
+                    names.insert(index, demangle(name));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut sorted: Vec<u64> = sizes.iter().map(|&(_, size)| size).collect();
+    sorted.sort_unstable();
+    let percentile = |p: usize| {
+        sorted
+            .get((sorted.len() * p / 100).min(sorted.len().saturating_sub(1)))
+            .copied()
+            .unwrap_or(0)
+    };
+    let median_bytes = percentile(50);
+    let total_bytes: u64 = sorted.iter().sum();
+
+    let buckets = bucket(&sorted);
+
+    let threshold = (median_bytes * OUTLIER_MEDIAN_FACTOR).max(OUTLIER_MIN_BYTES);
+    let mut outliers: Vec<SizeOutlier> = sizes
+        .iter()
+        .filter(|&&(_, size)| size >= threshold)
+        .map(|&(index, size_bytes)| SizeOutlier {
+            index,
+            name: names
+                .remove(&index)
+                .unwrap_or_else(|| format!("code[{}]", index - imported_functions)),
+            size_bytes,
+            times_median: size_bytes as f64 / median_bytes.max(1) as f64,
+        })
+        .collect();
+    outliers.sort_by_key(|o| std::cmp::Reverse(o.size_bytes));
+
+    let small: u64 = sorted.iter().filter(|&&s| s < SMALL_FUNCTION_BYTES).sum();
+    let large: u64 = sorted.iter().filter(|&&s| s >= LARGE_FUNCTION_BYTES).sum();
+    let profile = if small * 2 > total_bytes {
+        SizeProfile::ManySmall
+    } else if large * 2 > total_bytes {
+        SizeProfile::FewLarge
+    } else {
+        SizeProfile::Mixed
+    };
+
+    Ok(FunctionSizeReport {
+        functions: sorted.len(),
+        total_bytes,
+        median_bytes,
+        p90_bytes: percentile(90),
+        max_bytes: sorted.last().copied().unwrap_or(0),
+        buckets,
+        outliers,
+        profile,
+    })
+}
+
+/// Count function sizes per bucket
+fn bucket(sizes: &[u64]) -> Vec<SizeBucket> {
+    let mut lower = 0;
+    let mut buckets = Vec::with_capacity(BUCKET_BOUNDS.len() + 1);
+    for upper in BUCKET_BOUNDS.iter().copied().map(Some).chain([None]) {
+        let in_range: Vec<u64> = sizes
+            .iter()
+            .copied()
+            .filter(|&s| s >= lower && upper.is_none_or(|upper| s < upper))
+            .collect();
+        let label = match upper {
+            Some(upper) => format!("{}–{}", format_bytes(lower), format_bytes(upper)),
+            None => format!("≥ {}", format_bytes(lower)),
+        };
+        buckets.push(SizeBucket {
+            label,
+            min_bytes: lower,
+            max_bytes: upper,
+            functions: in_range.len(),
+            total_bytes: in_range.iter().sum(),
+        });
+        lower = upper.unwrap_or(lower);
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{leb, section, HEADER};
+
+    fn module(body_sizes: &[usize]) -> Vec<u8> {
+        let mut module = HEADER.to_vec();
+        module.extend(section(1, &[1, 0x60, 0, 0]));
+        let mut functions = leb(body_sizes.len());
+        functions.extend(std::iter::repeat_n(0, body_sizes.len()));
+        module.extend(section(3, &functions));
+        let mut code = leb(body_sizes.len());
+        for &size in body_sizes {
+            // No locals, nops, end
+            code.extend(leb(size));
+            code.push(0);
+            code.extend(std::iter::repeat_n(0x01, size - 2));
+            code.push(0x0b);
+        }
+        module.extend(section(10, &code));
+        module
+    }
+
+    #[test]
+    fn test_analyze_buckets_sizes_and_flags_outliers() {
+        let report = analyze_module(&module(&[10, 20, 30, 40, 2000, 60_000])).unwrap();
+        assert_eq!(report.functions, 6);
+        let counts: Vec<usize> = report.buckets.iter().map(|b| b.functions).collect();
+        assert_eq!(counts, vec![4, 0, 1, 0, 1]);
+        assert_eq!(report.buckets[4].total_bytes, 60_000);
+        assert_eq!(report.buckets[2].label, "1.00 KiB–10.00 KiB");
+        assert_eq!(report.max_bytes, 60_000);
+
+        assert_eq!(report.outliers.len(), 1);
+        assert_eq!(report.outliers[0].name, "code[5]");
+        assert_eq!(report.profile, SizeProfile::FewLarge);
+    }
+
+    #[test]
+    fn test_analyze_many_small_functions() {
+        let report = analyze_module(&module(&[100; 50])).unwrap();
+        assert!(report.outliers.is_empty());
+        assert_eq!(report.median_bytes, 100);
+        assert_eq!(report.profile, SizeProfile::ManySmall);
+    }
+}
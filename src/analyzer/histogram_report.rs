@@ -0,0 +1,125 @@
+//! Function size histogram report formatting
+
+use super::histogram::{FunctionSizeReport, SizeProfile};
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of outliers listed by default
+const DEFAULT_TOP_OUTLIERS: usize = 15;
+
+/// Width of the longest histogram bar
+const BAR_WIDTH: usize = 30;
+
+/// Format the function size histogram for console output
+pub fn format_console_report(report: &FunctionSizeReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the function size histogram, limiting the outliers listed
+pub fn format_console_report_with_limit(
+    report: &FunctionSizeReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Function Size Distribution").bold().underlined()
+    )?;
+    writeln!(
+        output,
+        "\n   {} functions, {} · median {} · p90 {} · largest {}",
+        style(report.functions).cyan().bold(),
+        format_bytes(report.total_bytes),
+        format_bytes(report.median_bytes),
+        format_bytes(report.p90_bytes),
+        format_bytes(report.max_bytes)
+    )?;
+
+    writeln!(
+        output,
+        "\n  {:<20} {:>9} {:>12} {:>6}",
+        "Size", "Functions", "Bytes", "%"
+    )?;
+    writeln!(output, "{}", style("─".repeat(45 + BAR_WIDTH)).dim())?;
+    let widest = report
+        .buckets
+        .iter()
+        .map(|b| b.total_bytes)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for bucket in &report.buckets {
+        let share = if report.total_bytes == 0 {
+            0.0
+        } else {
+            bucket.total_bytes as f64 / report.total_bytes as f64 * 100.0
+        };
+        let bar = (bucket.total_bytes as usize * BAR_WIDTH).div_ceil(widest as usize);
+        writeln!(
+            output,
+            "  {:<20} {:>9} {:>12} {:>5.1}% {}",
+            bucket.label,
+            bucket.functions,
+            format_bytes(bucket.total_bytes),
+            share,
+            style("█".repeat(bar)).cyan()
+        )?;
+    }
+
+    let verdict = match report.profile {
+        SizeProfile::ManySmall => {
+            "Most code is in functions under 1 KiB: look for generic instantiations \
+             and shims (--mode monos, --mode duplicates)"
+        }
+        SizeProfile::FewLarge => {
+            "Most code is in functions of 10 KiB or more: start with the outliers below \
+             (--mode dominators shows what they pull in)"
+        }
+        SizeProfile::Mixed => "Code is spread across small and large functions",
+    };
+    writeln!(output, "\n  {} {}", style("→").cyan(), verdict)?;
+
+    if !report.outliers.is_empty() {
+        writeln!(output, "\n{}", style("Outliers").bold())?;
+        let shown = limit.visible(report.outliers.len(), Some(DEFAULT_TOP_OUTLIERS));
+        for outlier in report.outliers.iter().take(shown) {
+            writeln!(
+                output,
+                "  {:>10}  {:>7}  {}",
+                format_bytes(outlier.size_bytes),
+                style(format!("{:.0}×", outlier.times_median)).yellow(),
+                truncate_str(&outlier.name, 80)
+            )?;
+        }
+        if let Some(footer) = omitted_footer(report.outliers.len(), shown, "outliers") {
+            writeln!(output, "  {}", style(footer).dim())?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::histogram::analyze_module;
+
+    #[test]
+    fn test_format_console_report_draws_buckets_and_verdict() {
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        module.extend([1, 4, 1, 0x60, 0, 0]);
+        module.extend([3, 3, 2, 0, 0]);
+        module.extend([10, 7, 2, 2, 0, 0x0b, 2, 0, 0x0b]);
+        let report = analyze_module(&module).unwrap();
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("2 functions"));
+        assert!(text.contains("0 B–256 B"));
+        assert!(text.contains("100.0%"));
+        assert!(text.contains("functions under 1 KiB"));
+        assert!(!text.contains("Outliers"));
+    }
+}
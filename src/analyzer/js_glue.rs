@@ -0,0 +1,390 @@
+//! wasm-bindgen JS glue analysis
+//!
+//! The JS that wasm-bindgen generates next to the module (`pkg/*.js`, split
+//! into `name.js` and `name_bg.js` for the bundler target) is shipped on every
+//! page load, and for DOM-heavy crates it easily adds 50–150 KB that no WASM
+//! size report shows. This module measures the glue (raw and gzipped), counts
+//! what drives its size — exported wrappers, imported `__wbg_*` shims and
+//! closure wrappers — and recommends build options or API surface cuts.
+
+use super::report_utils::SizedEntry;
+use crate::fmt::format_bytes;
+use crate::infra::{FileSystem, RealFileSystem};
+use crate::summary;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Directory wasm-pack writes the generated glue to
+const GLUE_DIR: &str = "pkg";
+
+/// Glue larger than this is worth minifying
+const LARGE_GLUE_BYTES: u64 = 50 * 1024;
+
+/// More imported shims than this point at a broad web-sys/js-sys surface
+const MANY_IMPORT_SHIMS: usize = 50;
+
+/// More closure wrappers than this are worth consolidating
+const MANY_CLOSURES: usize = 10;
+
+/// More exported wrappers than this are worth trimming
+const MANY_EXPORTS: usize = 50;
+
+/// Errors that can occur during glue analysis
+#[derive(Error, Debug)]
+pub enum JsGlueError {
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Regex compilation error
+    #[error("Regex error: {0}")]
+    Regex(#[from] regex::Error),
+
+    /// No glue file at the given location
+    #[error("No wasm-bindgen JS glue found in {0} (run wasm-pack build first or pass --file)")]
+    NoGlue(PathBuf),
+}
+
+/// Statistics for one generated glue file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlueFile {
+    /// Glue file
+    pub file: PathBuf,
+    /// Size in bytes
+    pub size_bytes: u64,
+    /// Size after gzip at level 9
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gzip_bytes: Option<u64>,
+    /// Exported function wrappers
+    pub exports: Vec<String>,
+    /// Exported classes
+    pub classes: Vec<String>,
+    /// Imported JS shims (`__wbg_*`), one per web-sys/js-sys item used
+    pub import_shims: usize,
+    /// Closure wrappers (`__wbindgen_closure_wrapper*`)
+    pub closures: usize,
+    /// JS values go through the heap slab (`addHeapObject`) instead of externref
+    pub heap_objects: bool,
+    /// Generated by a debug build (`_assertNum` checks)
+    pub debug_assertions: bool,
+}
+
+impl SizedEntry for GlueFile {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        self.file.to_str().unwrap_or_default()
+    }
+}
+
+/// Complete glue analysis results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsGlueResults {
+    /// Glue files, largest first
+    pub files: Vec<GlueFile>,
+    /// Total size of all glue files in bytes
+    pub total_size_bytes: u64,
+    /// Total gzipped size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_gzip_bytes: Option<u64>,
+    /// Recommendations
+    pub recommendations: Vec<String>,
+}
+
+impl JsGlueResults {
+    /// Exported function wrappers across all files
+    pub fn export_count(&self) -> usize {
+        self.files.iter().map(|f| f.exports.len()).sum()
+    }
+
+    /// Exported classes across all files
+    pub fn class_count(&self) -> usize {
+        self.files.iter().map(|f| f.classes.len()).sum()
+    }
+
+    /// Imported shims across all files
+    pub fn import_shim_count(&self) -> usize {
+        self.files.iter().map(|f| f.import_shims).sum()
+    }
+
+    /// Closure wrappers across all files
+    pub fn closure_count(&self) -> usize {
+        self.files.iter().map(|f| f.closures).sum()
+    }
+}
+
+/// wasm-bindgen JS glue analyzer
+pub struct JsGlueAnalyzer<FS: FileSystem = RealFileSystem> {
+    path: PathBuf,
+    fs: FS,
+}
+
+impl JsGlueAnalyzer {
+    /// Create an analyzer for a glue file, a directory of glue or a WASM
+    /// file (whose directory holds the glue)
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self::with_fs(path, RealFileSystem)
+    }
+
+    /// Create an analyzer for the project's `pkg/` directory
+    pub fn for_project(project_root: &Path) -> Self {
+        Self::new(project_root.join(GLUE_DIR))
+    }
+}
+
+impl<FS: FileSystem> JsGlueAnalyzer<FS> {
+    /// Create an analyzer with a custom filesystem
+    pub fn with_fs(path: impl Into<PathBuf>, fs: FS) -> Self {
+        Self {
+            path: path.into(),
+            fs,
+        }
+    }
+
+    /// Measure and scan every glue file
+    pub fn analyze(&self) -> Result<JsGlueResults, JsGlueError> {
+        let files = self.glue_files()?;
+        if files.is_empty() {
+            return Err(JsGlueError::NoGlue(self.path.clone()));
+        }
+
+        let mut glue = Vec::new();
+        for file in files {
+            let content = self.fs.read_to_string(&file)?;
+            let mut stats = scan_glue_source(&file, &content)?;
+            stats.gzip_bytes = summary::gzip_size(&file);
+            glue.push(stats);
+        }
+        glue.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.file.cmp(&b.file)));
+
+        Ok(build_results(glue))
+    }
+
+    /// Glue files at the configured path
+    ///
+    /// Only the top level of a directory is scanned: `snippets/` holds the
+    /// crate's own JS, not generated glue.
+    fn glue_files(&self) -> Result<Vec<PathBuf>, JsGlueError> {
+        let dir = if is_js(&self.path) {
+            return Ok(vec![self.path.clone()]);
+        } else if self.path.extension().is_some_and(|e| e == "wasm") {
+            self.path.parent().unwrap_or(Path::new(".")).to_path_buf()
+        } else {
+            self.path.clone()
+        };
+        if !dir.is_dir() {
+            return Err(JsGlueError::NoGlue(dir));
+        }
+
+        let mut files = Vec::new();
+        for entry in self.fs.read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_file() && is_js(&path) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// True for `.js` and `.mjs` files
+fn is_js(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e == "js" || e == "mjs")
+}
+
+/// Count the wrappers and shims in one glue file
+///
+/// Covers the ES module targets (`export function`) and the nodejs target
+/// (`module.exports.name =`). Imported shims carry a hash suffix
+/// (`__wbg_log_1d3ae027…`), which keeps init helpers like `__wbg_init` out.
+pub(crate) fn scan_glue_source(file: &Path, content: &str) -> Result<GlueFile, JsGlueError> {
+    let export_re = Regex::new(
+        r"(?m)^(?:export\s+function\s+|(?:module\.)?exports\.)([A-Za-z_$][\w$]*)\s*(?:\(|=)",
+    )?;
+    let class_re = Regex::new(r"(?m)^export\s+class\s+([A-Za-z_$][\w$]*)")?;
+    let shim_re = Regex::new(r"(?:function\s+|\.)__wbg_\w+?_[0-9a-f]{8,}\s*(?:\(|=)")?;
+    let closure_re = Regex::new(r"(?:function\s+|\.)__wbindgen_closure_wrapper\d+\s*(?:\(|=)")?;
+
+    let exports = export_re
+        .captures_iter(content)
+        .map(|caps| caps[1].to_string())
+        .filter(|name| !name.starts_with("__wbg") && !name.starts_with("__wbindgen"))
+        .collect();
+    let classes = class_re
+        .captures_iter(content)
+        .map(|caps| caps[1].to_string())
+        .collect();
+
+    Ok(GlueFile {
+        file: file.to_path_buf(),
+        size_bytes: content.len() as u64,
+        gzip_bytes: None,
+        exports,
+        classes,
+        import_shims: shim_re.find_iter(content).count(),
+        closures: closure_re.find_iter(content).count(),
+        heap_objects: content.contains("function addHeapObject("),
+        debug_assertions: content.contains("function _assertNum("),
+    })
+}
+
+/// Summarize glue files into results with recommendations
+fn build_results(files: Vec<GlueFile>) -> JsGlueResults {
+    let total_size_bytes = files.iter().map(|f| f.size_bytes).sum();
+    let total_gzip_bytes = files.iter().map(|f| f.gzip_bytes).sum();
+    let mut results = JsGlueResults {
+        files,
+        total_size_bytes,
+        total_gzip_bytes,
+        recommendations: Vec::new(),
+    };
+    results.recommendations = generate_recommendations(&results);
+    results
+}
+
+/// Generate recommendations, most impactful first
+fn generate_recommendations(results: &JsGlueResults) -> Vec<String> {
+    let mut recs = Vec::new();
+    let files = &results.files;
+
+    if files.iter().any(|f| f.debug_assertions) {
+        recs.push(
+            "[P1] Glue was generated by a debug build (_assertNum checks); build with `wasm-pack build --release`"
+                .to_string(),
+        );
+    }
+
+    if results.total_size_bytes > LARGE_GLUE_BYTES {
+        let gzip = results
+            .total_gzip_bytes
+            .map(|bytes| format!(", {} gzipped", format_bytes(bytes)))
+            .unwrap_or_default();
+        recs.push(format!(
+            "[P2] Glue is {}{} and wasm-bindgen does not minify it; run it through your bundler's minifier (esbuild --minify, terser)",
+            format_bytes(results.total_size_bytes),
+            gzip
+        ));
+    }
+
+    let shims = results.import_shim_count();
+    if shims > MANY_IMPORT_SHIMS {
+        recs.push(format!(
+            "[P2] {} imported JS shims (__wbg_*): each web-sys/js-sys method used adds one; trim web-sys features and move repeated DOM work into a JS snippet (#[wasm_bindgen(module = ...)])",
+            shims
+        ));
+    }
+
+    let closures = results.closure_count();
+    if closures > MANY_CLOSURES {
+        recs.push(format!(
+            "[P2] {} closure wrappers: each Closure signature adds a wrapper and destructor; reuse one callback type or dispatch from a single closure",
+            closures
+        ));
+    }
+
+    let exports = results.export_count();
+    if exports > MANY_EXPORTS {
+        recs.push(format!(
+            "[P3] {} exported functions each get a JS wrapper; keep internal helpers out of #[wasm_bindgen] or group them in an exported struct",
+            exports
+        ));
+    }
+
+    if files.iter().any(|f| f.heap_objects) {
+        recs.push(
+            "[P3] JS values go through the heap-slab glue (addHeapObject/dropObject); enable reference types (RUSTFLAGS=\"-C target-feature=+reference-types\") so wasm-bindgen passes externref"
+                .to_string(),
+        );
+    }
+
+    let bundler_split = files.iter().any(|f| {
+        f.file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|s| s.ends_with("_bg"))
+    });
+    if bundler_split {
+        recs.push(
+            "[P3] Bundler target splits the glue into name.js and name_bg.js; `wasm-pack build --target web` emits a single ES module"
+                .to_string(),
+        );
+    }
+
+    recs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLER_GLUE: &str = r#"let wasm;
+export function __wbg_set_wasm(val) { wasm = val; }
+function addHeapObject(obj) { return 0; }
+export function greet(name) { wasm.greet(name); }
+export function render() { wasm.render(); }
+export class Counter { free() {} }
+export function __wbg_log_1d3ae0273d8f4f8a(arg0) { console.log(arg0); }
+export function __wbg_createElement_8bae7856a4bb7411(arg0, arg1) { return 0; }
+export function __wbindgen_closure_wrapper123(arg0, arg1, arg2) { return 0; }
+"#;
+
+    #[test]
+    fn test_scan_glue_source_counts_exports_shims_and_closures() {
+        let glue = scan_glue_source(Path::new("app_bg.js"), BUNDLER_GLUE).unwrap();
+
+        assert_eq!(glue.exports, ["greet", "render"]);
+        assert_eq!(glue.classes, ["Counter"]);
+        assert_eq!(glue.import_shims, 2);
+        assert_eq!(glue.closures, 1);
+        assert!(glue.heap_objects);
+        assert!(!glue.debug_assertions);
+
+        let web = "const imports = {};\nimports.wbg = {};\nimports.wbg.__wbg_log_1d3ae0273d8f4f8a = function(arg0) {};\nasync function __wbg_init(input) {}\n";
+        assert_eq!(
+            scan_glue_source(Path::new("app.js"), web)
+                .unwrap()
+                .import_shims,
+            1
+        );
+    }
+
+    #[test]
+    fn test_analyze_wasm_path_scans_sibling_glue_and_recommends() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("app_bg.js"), BUNDLER_GLUE).unwrap();
+        std::fs::write(
+            temp.path().join("app.js"),
+            "export * from \"./app_bg.js\";\n",
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("app_bg.wasm"), b"\0asm").unwrap();
+        std::fs::create_dir(temp.path().join("snippets")).unwrap();
+
+        let results = JsGlueAnalyzer::new(temp.path().join("app_bg.wasm"))
+            .analyze()
+            .unwrap();
+
+        assert_eq!(results.files.len(), 2);
+        assert!(results.files[0].file.ends_with("app_bg.js"));
+        assert!(results.total_gzip_bytes.is_some());
+        assert_eq!(results.export_count(), 2);
+        assert!(results
+            .recommendations
+            .iter()
+            .any(|r| r.contains("reference types")));
+        assert!(results
+            .recommendations
+            .iter()
+            .any(|r| r.contains("--target web")));
+
+        let missing = JsGlueAnalyzer::new(temp.path().join("snippets")).analyze();
+        assert!(matches!(missing, Err(JsGlueError::NoGlue(_))));
+    }
+}
@@ -0,0 +1,141 @@
+//! wasm-bindgen JS glue report formatting
+
+use super::js_glue::JsGlueResults;
+use super::report_utils::{format_bytes, omitted_footer, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of exported wrappers listed by default
+const DEFAULT_TOP_EXPORTS: usize = 20;
+
+/// Format the glue report for console output
+pub fn format_console_report(results: &JsGlueResults) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(results, ItemLimit::Default)
+}
+
+/// Format the glue report, limiting the exports listed
+pub fn format_console_report_with_limit(
+    results: &JsGlueResults,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("wasm-bindgen JS Glue Analysis").bold().underlined()
+    )?;
+    let gzip = results
+        .total_gzip_bytes
+        .map(|bytes| format!(" ({} gzipped)", format_bytes(bytes)))
+        .unwrap_or_default();
+    writeln!(
+        output,
+        "\n   Glue size: {}{}",
+        style(format_bytes(results.total_size_bytes)).cyan().bold(),
+        gzip
+    )?;
+    writeln!(
+        output,
+        "   {} exported functions, {} classes, {} imported shims, {} closure wrappers",
+        results.export_count(),
+        results.class_count(),
+        results.import_shim_count(),
+        results.closure_count()
+    )?;
+
+    writeln!(output, "\n{}", style("FILES:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    for file in &results.files {
+        let gzip = file
+            .gzip_bytes
+            .map(|bytes| format!(", {} gzipped", format_bytes(bytes)))
+            .unwrap_or_default();
+        writeln!(
+            output,
+            "  {} ({}{}): {} exports, {} shims, {} closures",
+            file.file.display(),
+            format_bytes(file.size_bytes),
+            gzip,
+            file.exports.len(),
+            file.import_shims,
+            file.closures
+        )?;
+    }
+
+    let exports: Vec<&String> = results
+        .files
+        .iter()
+        .flat_map(|f| f.classes.iter().chain(&f.exports))
+        .collect();
+    if !exports.is_empty() {
+        writeln!(output, "\n{}", style("EXPORTED API:").bold())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        let shown = limit.visible(exports.len(), Some(DEFAULT_TOP_EXPORTS));
+        for name in exports.iter().take(shown) {
+            writeln!(output, "  {}", name)?;
+        }
+        if let Some(footer) = omitted_footer(exports.len(), shown, "exports") {
+            writeln!(output, "\n  {}", style(footer).dim())?;
+        }
+    }
+
+    if results.recommendations.is_empty() {
+        writeln!(
+            output,
+            "\n{}",
+            style("✨ Glue is small and built for release").green()
+        )?;
+    } else {
+        writeln!(output, "\n{}", style("RECOMMENDATIONS:").bold().yellow())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        for rec in &results.recommendations {
+            let styled = if rec.starts_with("[P1]") {
+                style(rec).yellow().bold()
+            } else if rec.starts_with("[P2]") {
+                style(rec).blue()
+            } else {
+                style(rec).green()
+            };
+            writeln!(output, "  {}", styled)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::js_glue::GlueFile;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_console_report_shows_sizes_counts_and_exports() {
+        let results = JsGlueResults {
+            files: vec![GlueFile {
+                file: PathBuf::from("pkg/app_bg.js"),
+                size_bytes: 4096,
+                gzip_bytes: Some(1024),
+                exports: vec!["greet".to_string(), "render".to_string()],
+                classes: vec!["Counter".to_string()],
+                import_shims: 7,
+                closures: 2,
+                heap_objects: false,
+                debug_assertions: false,
+            }],
+            total_size_bytes: 4096,
+            total_gzip_bytes: Some(1024),
+            recommendations: vec!["[P1] build with --release".to_string()],
+        };
+
+        let text = format_console_report_with_limit(&results, ItemLimit::Max(2)).unwrap();
+        assert!(text.contains("Glue size: 4.00 KiB (1.00 KiB gzipped)"));
+        assert!(
+            text.contains("2 exported functions, 1 classes, 7 imported shims, 2 closure wrappers")
+        );
+        assert!(text.contains("  Counter\n  greet\n"));
+        assert!(text.contains("1 more exports"));
+        assert!(text.contains("[P1] build with --release"));
+    }
+}
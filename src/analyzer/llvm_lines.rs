@@ -0,0 +1,440 @@
+//! Monomorphization analysis from `cargo llvm-lines`
+//!
+//! `cargo llvm-lines` counts the LLVM IR lines each generic function
+//! produces across all of its copies, before any optimization. That points at
+//! generic bloat after a quick debug build instead of an optimized WASM
+//! build. When a WASM file is given too, each function is matched against the
+//! twiggy monomorphization groups to show which copies survived into the
+//! binary and how large they are.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::LlvmLinesAnalyzer;
+//!
+//! let report = LlvmLinesAnalyzer::new(".")
+//!     .with_wasm_file("pkg/app_bg.wasm")
+//!     .analyze()?;
+//! for function in report.functions.iter().take(5) {
+//!     println!("{}: {} lines in {} copies", function.name, function.lines, function.copies);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::format_bytes;
+use super::report_utils::SizedEntry;
+use super::twiggy::{AnalysisMode, MonomorphizationGroup, TwiggyAnalyzer};
+use crate::fmt::percent_of;
+use crate::infra::{CommandExecutor, RealCommandExecutor};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Target the LLVM IR is generated for
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Functions turned into recommendations
+const MAX_RECOMMENDATIONS: usize = 3;
+
+/// Share of all LLVM lines a function needs to be recommended, in percent
+const MIN_RECOMMENDED_PERCENT: f64 = 1.0;
+
+/// Copies of a function found in the WASM file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WasmCopies {
+    /// Instantiations in the monomorphization groups
+    pub instantiations: usize,
+    /// Their total size
+    pub size_bytes: u64,
+}
+
+/// One generic function from `cargo llvm-lines`
+#[derive(Debug, Clone, Serialize)]
+pub struct LlvmLinesFunction {
+    /// Function name as printed by `cargo llvm-lines`
+    pub name: String,
+    /// LLVM IR lines across all copies
+    pub lines: u64,
+    /// Number of monomorphized copies
+    pub copies: u64,
+    /// Share of all LLVM lines
+    pub lines_percentage: f64,
+    /// Matching copies in the WASM file, if one was analyzed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm: Option<WasmCopies>,
+}
+
+impl SizedEntry for LlvmLinesFunction {
+    fn size_bytes(&self) -> Option<u64> {
+        self.wasm.as_ref().map(|wasm| wasm.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.lines_percentage)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// `cargo llvm-lines` output, optionally correlated with a WASM file
+#[derive(Debug, Clone, Serialize)]
+pub struct LlvmLinesReport {
+    /// LLVM IR lines of the whole crate
+    pub total_lines: u64,
+    /// Monomorphized copies of all functions
+    pub total_copies: u64,
+    /// Functions, most lines first
+    pub functions: Vec<LlvmLinesFunction>,
+    /// WASM file the functions were matched against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm_file: Option<PathBuf>,
+    /// Size of the WASM copies matched to a function
+    pub matched_wasm_bytes: u64,
+    /// Suggested changes
+    pub recommendations: Vec<String>,
+}
+
+/// Runs `cargo llvm-lines` for the WASM target and summarizes generic bloat
+pub struct LlvmLinesAnalyzer<CE: CommandExecutor = RealCommandExecutor> {
+    project_root: PathBuf,
+    wasm_file: Option<PathBuf>,
+    cmd_executor: CE,
+}
+
+impl LlvmLinesAnalyzer<RealCommandExecutor> {
+    /// Create an analyzer for the project at `project_root`
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self::with_executor(project_root, RealCommandExecutor)
+    }
+}
+
+impl<CE: CommandExecutor> LlvmLinesAnalyzer<CE> {
+    /// Create an analyzer with a custom command executor
+    pub fn with_executor(project_root: impl AsRef<Path>, cmd_executor: CE) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+            wasm_file: None,
+            cmd_executor,
+        }
+    }
+
+    /// Match functions against the monomorphizations of a WASM file
+    pub fn with_wasm_file(mut self, wasm_file: impl Into<PathBuf>) -> Self {
+        self.wasm_file = Some(wasm_file.into());
+        self
+    }
+
+    /// Run `cargo llvm-lines` and, with a WASM file, correlate the results
+    pub fn analyze(&self) -> Result<LlvmLinesReport> {
+        let output = self.run_llvm_lines()?;
+        let groups = match &self.wasm_file {
+            Some(wasm_file) => TwiggyAnalyzer::new(wasm_file)
+                .analyze(AnalysisMode::Monos)?
+                .mono_groups
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        let mut report = parse_output(&output)?;
+        if let Some(wasm_file) = &self.wasm_file {
+            correlate(&mut report, &groups);
+            report.wasm_file = Some(wasm_file.clone());
+        }
+        report.recommendations = recommendations(&report);
+        Ok(report)
+    }
+
+    fn run_llvm_lines(&self) -> Result<String> {
+        // Packages with a binary too would make cargo ask which target to use
+        let lib_only = self.project_root.join("src").join("lib.rs").is_file();
+        let output = self
+            .cmd_executor
+            .execute(
+                |cmd| {
+                    cmd.current_dir(&self.project_root).args([
+                        "llvm-lines",
+                        "--target",
+                        WASM_TARGET,
+                    ]);
+                    if lib_only {
+                        cmd.arg("--lib");
+                    }
+                    cmd
+                },
+                "cargo",
+            )
+            .context("Failed to run cargo llvm-lines")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("no such command") {
+                anyhow::bail!(
+                    "cargo-llvm-lines is not installed; run `cargo install cargo-llvm-lines`"
+                );
+            }
+            anyhow::bail!(
+                "cargo llvm-lines failed: {}",
+                stderr.lines().last().unwrap_or("no error output")
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Parse the table printed by `cargo llvm-lines`
+///
+/// ```text
+///   Lines          Copies        Function name
+///   -----          ------        -------------
+///   30737          1107          (TOTAL)
+///    1395 (4.5%)     21 (1.9%)   alloc::raw_vec::RawVec<T,A>::grow_amortized
+/// ```
+///
+/// Percentage columns may also carry a cumulative value (`(4.5%,  4.5%)`).
+fn parse_output(output: &str) -> Result<LlvmLinesReport> {
+    let mut total = None;
+    let mut functions = Vec::new();
+    for line in output.lines() {
+        let Some((lines, copies, name)) = parse_row(line) else {
+            continue;
+        };
+        if name == "(TOTAL)" {
+            total = Some((lines, copies));
+        } else {
+            functions.push(LlvmLinesFunction {
+                name: name.to_string(),
+                lines,
+                copies,
+                lines_percentage: 0.0,
+                wasm: None,
+            });
+        }
+    }
+
+    let (total_lines, total_copies) =
+        total.context("Unexpected cargo llvm-lines output: no (TOTAL) row")?;
+    for function in &mut functions {
+        function.lines_percentage = percent_of(function.lines, total_lines);
+    }
+
+    Ok(LlvmLinesReport {
+        total_lines,
+        total_copies,
+        functions,
+        wasm_file: None,
+        matched_wasm_bytes: 0,
+        recommendations: Vec::new(),
+    })
+}
+
+/// Split a table row into lines, copies and function name
+fn parse_row(line: &str) -> Option<(u64, u64, &str)> {
+    let (lines, rest) = split_number(line.trim_start())?;
+    let (copies, rest) = split_number(skip_percentages(rest.trim_start()).trim_start())?;
+    let name = skip_percentages(rest.trim_start()).trim();
+    (!name.is_empty()).then_some((lines, copies, name))
+}
+
+fn split_number(text: &str) -> Option<(u64, &str)> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    Some((text[..end].parse().ok()?, &text[end..]))
+}
+
+fn skip_percentages(text: &str) -> &str {
+    match text.find(')') {
+        Some(end) if text.starts_with('(') && text[..end].contains('%') => &text[end + 1..],
+        _ => text,
+    }
+}
+
+/// Attach the WASM copies of each function from the monomorphization groups
+fn correlate(report: &mut LlvmLinesReport, groups: &[MonomorphizationGroup]) {
+    let mut copies: HashMap<String, WasmCopies> = HashMap::new();
+    for item in groups.iter().flat_map(|g| &g.instantiations) {
+        let entry = copies.entry(generic_key(&item.name)).or_default();
+        entry.instantiations += 1;
+        entry.size_bytes += item.size_bytes;
+    }
+
+    for function in &mut report.functions {
+        // Take the match so two spellings of one function are not counted twice
+        let wasm = copies
+            .remove(&generic_key(&function.name))
+            .unwrap_or_default();
+        report.matched_wasm_bytes += wasm.size_bytes;
+        function.wasm = Some(wasm);
+    }
+}
+
+/// Function path with generic arguments and the symbol hash removed
+///
+/// `cargo llvm-lines` prints parameter names (`Vec<T,A>::push`) while v0
+/// symbols in the WASM file carry concrete types (`Vec<u8>::push`); both
+/// reduce to `alloc::vec::Vec::push`. The angle brackets of qualified paths
+/// (`<Vec<T> as Drop>::drop`) are kept.
+fn generic_key(name: &str) -> String {
+    let name = strip_hash(name.trim());
+    let mut key = String::with_capacity(name.len());
+    let mut depth = 0usize;
+    let mut prev = ' ';
+    for c in name.chars() {
+        match c {
+            '>' if prev == '-' => {
+                if depth == 0 {
+                    key.push(c);
+                }
+            }
+            '<' if depth > 0 => depth += 1,
+            '<' if prev.is_alphanumeric() || prev == '_' => depth = 1,
+            '<' if key.ends_with("::") => {
+                // Turbofish: `drop_in_place::<T>`
+                key.truncate(key.len() - 2);
+                depth = 1;
+            }
+            '>' if depth > 0 => depth -= 1,
+            _ if depth > 0 => {}
+            _ => key.push(c),
+        }
+        prev = c;
+    }
+    key
+}
+
+/// Remove the `::h0123456789abcdef` suffix of legacy symbols
+fn strip_hash(name: &str) -> &str {
+    match name.rsplit_once("::h") {
+        Some((path, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+            path
+        }
+        _ => name,
+    }
+}
+
+/// Suggestions for a report
+fn recommendations(report: &LlvmLinesReport) -> Vec<String> {
+    let mut recs: Vec<String> = report
+        .functions
+        .iter()
+        .filter(|f| f.copies > 1 && f.lines_percentage >= MIN_RECOMMENDED_PERCENT)
+        .take(MAX_RECOMMENDATIONS)
+        .map(|f| {
+            let mut rec = format!(
+                "{} has {} copies ({} LLVM lines, {:.1}%); move the type-independent body into a non-generic inner function or take &dyn Trait",
+                f.name, f.copies, f.lines, f.lines_percentage
+            );
+            if let Some(wasm) = f.wasm.as_ref().filter(|w| w.instantiations > 0) {
+                rec.push_str(&format!(
+                    " ({} copies, {} in the WASM file)",
+                    wasm.instantiations,
+                    format_bytes(wasm.size_bytes)
+                ));
+            }
+            rec
+        })
+        .collect();
+
+    if report.wasm_file.is_some() {
+        let optimized_away = report
+            .functions
+            .iter()
+            .filter(|f| f.copies > 1 && f.wasm.as_ref().is_some_and(|w| w.instantiations == 0))
+            .count();
+        if optimized_away > 0 {
+            recs.push(format!(
+                "{} generic functions left no separate copies in the WASM file (inlined or removed); their lines cost compile time, not size",
+                optimized_away
+            ));
+        }
+    }
+    recs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::twiggy::AnalysisItem;
+
+    const OUTPUT: &str = "  Lines                 Copies               Function name
+  -----                 ------               -------------
+  30737                 1107                 (TOTAL)
+   1395 (4.5%,  4.5%)     21 (1.9%,  1.9%)   alloc::raw_vec::RawVec<T,A>::grow_amortized
+   1036 (3.4%,  7.9%)     14 (1.3%,  3.2%)   <alloc::vec::Vec<T,A> as core::ops::drop::Drop>::drop
+    212 (0.7%,  8.6%)      1 (0.1%,  3.3%)   app::render
+";
+
+    #[test]
+    fn test_parse_output_reads_totals_and_functions() {
+        let report = parse_output(OUTPUT).unwrap();
+        assert_eq!(report.total_lines, 30737);
+        assert_eq!(report.total_copies, 1107);
+        assert_eq!(report.functions.len(), 3);
+        assert_eq!(
+            report.functions[1].name,
+            "<alloc::vec::Vec<T,A> as core::ops::drop::Drop>::drop"
+        );
+        assert_eq!(report.functions[1].copies, 14);
+        assert!((report.functions[0].lines_percentage - 4.54).abs() < 0.01);
+        assert!(parse_output("error: could not compile").is_err());
+    }
+
+    #[test]
+    fn test_correlate_matches_wasm_instantiations_by_generic_path() {
+        let item = |name: &str, size_bytes| AnalysisItem {
+            size_bytes,
+            percentage: 0.0,
+            name: name.to_string(),
+        };
+        let groups = vec![MonomorphizationGroup {
+            function_name: "drop".to_string(),
+            instantiation_count: 2,
+            total_size_bytes: 700,
+            avg_size_bytes: 350,
+            instantiations: vec![
+                item("<alloc::vec::Vec<u8> as core::ops::drop::Drop>::drop", 400),
+                item(
+                    "<alloc::vec::Vec<app::Node, alloc::alloc::Global> as core::ops::drop::Drop>::drop",
+                    300,
+                ),
+            ],
+            potential_savings_bytes: 300,
+            type_arguments: Vec::new(),
+            refactor: None,
+        }];
+
+        let mut report = parse_output(OUTPUT).unwrap();
+        report.wasm_file = Some(PathBuf::from("app.wasm"));
+        correlate(&mut report, &groups);
+        report.recommendations = recommendations(&report);
+
+        let drop = report.functions[1].wasm.as_ref().unwrap();
+        assert_eq!((drop.instantiations, drop.size_bytes), (2, 700));
+        assert_eq!(report.matched_wasm_bytes, 700);
+        assert!(report.recommendations[0]
+            .starts_with("alloc::raw_vec::RawVec<T,A>::grow_amortized has 21 copies"));
+        assert!(report.recommendations[1].ends_with("(2 copies, 700 B in the WASM file)"));
+        assert!(
+            report.recommendations[2].starts_with("1 generic functions left no separate copies")
+        );
+    }
+
+    #[test]
+    fn test_generic_key_strips_arguments_hashes_and_turbofish() {
+        assert_eq!(
+            generic_key("alloc::raw_vec::RawVec<T,A>::grow_amortized"),
+            "alloc::raw_vec::RawVec::grow_amortized"
+        );
+        assert_eq!(
+            generic_key("core::ptr::drop_in_place::<alloc::string::String>"),
+            generic_key("core::ptr::drop_in_place<T>")
+        );
+        assert_eq!(
+            generic_key("<F as core::ops::Fn<()>>::call::h0123456789abcdef"),
+            "<F as core::ops::Fn>::call"
+        );
+    }
+}
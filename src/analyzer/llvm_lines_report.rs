@@ -0,0 +1,134 @@
+//! cargo llvm-lines report formatting
+
+use super::llvm_lines::LlvmLinesReport;
+use super::report_utils::{format_bytes, format_number, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of functions listed by default
+const DEFAULT_TOP_FUNCTIONS: usize = 20;
+
+/// Format the llvm-lines report for console output
+pub fn format_console_report(report: &LlvmLinesReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the llvm-lines report, limiting the functions listed
+pub fn format_console_report_with_limit(
+    report: &LlvmLinesReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("LLVM IR Lines").bold().underlined())?;
+    writeln!(
+        output,
+        "\n   {} lines in {} monomorphized copies",
+        style(format_number(report.total_lines)).yellow().bold(),
+        format_number(report.total_copies)
+    )?;
+    if let Some(wasm_file) = &report.wasm_file {
+        writeln!(
+            output,
+            "   {} of {} matched to these functions",
+            format_bytes(report.matched_wasm_bytes),
+            wasm_file.display()
+        )?;
+    }
+
+    if !report.functions.is_empty() {
+        writeln!(output, "\n{}", style("Functions by LLVM Lines").bold())?;
+        if report.wasm_file.is_some() {
+            writeln!(
+                output,
+                "  {:>8} {:>6} {:>6} {:>6} {:>10}  Function",
+                "Lines", "%", "Copies", "WASM", "WASM size"
+            )?;
+        } else {
+            writeln!(
+                output,
+                "  {:>8} {:>6} {:>6}  Function",
+                "Lines", "%", "Copies"
+            )?;
+        }
+        writeln!(output, "{}", style("─".repeat(100)).dim())?;
+
+        let shown = limit.visible(report.functions.len(), Some(DEFAULT_TOP_FUNCTIONS));
+        for function in report.functions.iter().take(shown) {
+            let wasm = match &function.wasm {
+                Some(wasm) if wasm.instantiations > 0 => format!(
+                    " {:>6} {:>10}",
+                    wasm.instantiations,
+                    format_bytes(wasm.size_bytes)
+                ),
+                Some(_) => format!(" {:>6} {:>10}", "-", "-"),
+                None => String::new(),
+            };
+            writeln!(
+                output,
+                "  {:>8} {:>5.1}% {:>6}{}  {}",
+                function.lines,
+                function.lines_percentage,
+                function.copies,
+                wasm,
+                truncate_str(&function.name, 70)
+            )?;
+        }
+        if let Some(footer) = omitted_footer(report.functions.len(), shown, "functions") {
+            writeln!(output, "  {}", style(footer).dim())?;
+        }
+    }
+
+    if !report.recommendations.is_empty() {
+        writeln!(output, "\n{}", style("Recommendations").bold())?;
+        for rec in &report.recommendations {
+            writeln!(output, "  {} {}", style("→").cyan(), rec)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::llvm_lines::{LlvmLinesFunction, WasmCopies};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_console_report_lists_functions_with_wasm_copies() {
+        let report = LlvmLinesReport {
+            total_lines: 30_737,
+            total_copies: 1_107,
+            functions: vec![
+                LlvmLinesFunction {
+                    name: "alloc::raw_vec::RawVec<T,A>::grow_amortized".to_string(),
+                    lines: 1_395,
+                    copies: 21,
+                    lines_percentage: 4.5,
+                    wasm: Some(WasmCopies {
+                        instantiations: 6,
+                        size_bytes: 2_048,
+                    }),
+                },
+                LlvmLinesFunction {
+                    name: "app::render".to_string(),
+                    lines: 212,
+                    copies: 1,
+                    lines_percentage: 0.7,
+                    wasm: Some(WasmCopies::default()),
+                },
+            ],
+            wasm_file: Some(PathBuf::from("app.wasm")),
+            matched_wasm_bytes: 2_048,
+            recommendations: vec!["Split grow_amortized".to_string()],
+        };
+
+        let text = format_console_report_with_limit(&report, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("30,737"));
+        assert!(text.contains("2.00 KiB of app.wasm matched"));
+        assert!(text.contains("RawVec<T,A>::grow_amortized"));
+        assert!(!text.contains("app::render"));
+        assert!(text.contains("Split grow_amortized"));
+    }
+}
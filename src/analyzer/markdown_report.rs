@@ -0,0 +1,370 @@
+//! Markdown reports for pull request comments
+//!
+//! `analyze --format markdown` renders the bloat and twiggy modes as tables a
+//! bot can post as-is: the size (against the last recorded build when there
+//! is one), the largest items and the recommendations. `compare --format
+//! markdown` (or `--pr-comment`) adds the recommendations for the after build
+//! to the comparison comment; with three or more builds it renders the size
+//! table of every build instead.
+
+use super::report_utils::{format_bytes, format_delta, truncate_str, ItemLimit};
+use super::twiggy::AnalysisResults;
+use super::{bloat, twiggy};
+use crate::fmt::percent_of;
+
+/// Items listed by default
+const DEFAULT_TOP_ITEMS: usize = 10;
+
+/// Recommendations listed by default
+const DEFAULT_RECOMMENDATIONS: usize = 10;
+
+/// Longest symbol name kept in a table cell
+const MAX_NAME_CHARS: usize = 80;
+
+/// One row of the recommendations table
+struct RecommendationRow<'a> {
+    priority: &'a str,
+    description: &'a str,
+    savings_bytes: u64,
+    savings_percent: f64,
+}
+
+impl<'a> From<&'a bloat::Recommendation> for RecommendationRow<'a> {
+    fn from(rec: &'a bloat::Recommendation) -> Self {
+        Self {
+            priority: &rec.priority,
+            description: &rec.description,
+            savings_bytes: rec.estimated_savings_bytes,
+            savings_percent: rec.estimated_savings_percent,
+        }
+    }
+}
+
+impl<'a> From<&'a twiggy::Recommendation> for RecommendationRow<'a> {
+    fn from(rec: &'a twiggy::Recommendation) -> Self {
+        Self {
+            priority: &rec.priority,
+            description: &rec.description,
+            savings_bytes: rec.estimated_savings_bytes,
+            savings_percent: rec.estimated_savings_percent,
+        }
+    }
+}
+
+/// Format a twiggy analysis as a Markdown comment
+///
+/// `previous_bytes` is the size of the last recorded build, shown as the
+/// before column when given.
+pub fn format_analysis_markdown(
+    results: &AnalysisResults,
+    previous_bytes: Option<u64>,
+    limit: ItemLimit,
+) -> String {
+    let mut md = format!("### wasm-slim analysis ({})\n\n", results.mode);
+    md.push_str(&size_table(results.total_size_bytes, previous_bytes));
+    md.push_str(&items_table(
+        "Largest items",
+        results
+            .items
+            .iter()
+            .map(|item| (item.name.as_str(), item.size_bytes, item.percentage)),
+        results.items.len(),
+        limit,
+    ));
+    md.push_str(&recommendations_section(
+        results.recommendations.iter().map(RecommendationRow::from),
+        results.recommendations.len(),
+        limit,
+    ));
+    md
+}
+
+/// Format a cargo-bloat analysis as a Markdown comment
+pub fn format_bloat_markdown(results: &bloat::BloatResults, limit: ItemLimit) -> String {
+    let mut md = String::from("### wasm-slim bloat analysis\n\n");
+    md.push_str("| | Size |\n|---|---|\n");
+    md.push_str(&format!(
+        "| Binary | {} |\n| Code (.text) | {} ({:.1}%) |\n",
+        format_bytes(results.total_size_bytes),
+        format_bytes(results.text_size_bytes),
+        percent_of(results.text_size_bytes, results.total_size_bytes)
+    ));
+    md.push_str(&items_table(
+        "Largest functions",
+        results
+            .items
+            .iter()
+            .map(|item| (item.name.as_str(), item.size_bytes, item.percentage)),
+        results.items.len(),
+        limit,
+    ));
+    md.push_str(&recommendations_section(
+        results.recommendations.iter().map(RecommendationRow::from),
+        results.recommendations.len(),
+        limit,
+    ));
+    md
+}
+
+/// Format twiggy recommendations as a Markdown section
+///
+/// `compare --format markdown` appends this for the after build.
+pub fn format_recommendations_markdown(
+    recommendations: &[twiggy::Recommendation],
+    limit: ItemLimit,
+) -> String {
+    recommendations_section(
+        recommendations.iter().map(RecommendationRow::from),
+        recommendations.len(),
+        limit,
+    )
+}
+
+/// Format an N-way comparison as a Markdown comment
+///
+/// Lists every build with its change from the previous and the first one,
+/// followed by the largest symbol changes of each step.
+pub fn format_multi_comparison_markdown(
+    results: &twiggy::MultiComparison,
+    limit: ItemLimit,
+) -> String {
+    let delta_cell = |delta: Option<twiggy::SizeDelta>| match delta {
+        Some(delta) => format!("{} ({:+.1}%)", format_delta(delta.bytes), delta.percent),
+        None => "—".to_string(),
+    };
+    let mut md = format!(
+        "### wasm-slim size report ({} builds)\n\n",
+        results.builds.len()
+    );
+    md.push_str("| Build | Size | vs previous | vs first |\n|---|---:|---:|---:|\n");
+    for build in &results.builds {
+        md.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            markdown_code(&build.file),
+            format_bytes(build.size_bytes),
+            delta_cell(build.from_previous),
+            delta_cell(build.from_previous.map(|_| build.from_first))
+        ));
+    }
+
+    for (step, pair) in results.steps.iter().zip(results.builds.windows(2)) {
+        if step.top_changes.is_empty() {
+            continue;
+        }
+        let total = step.top_changes.len();
+        let shown = limit.visible(total, Some(DEFAULT_TOP_ITEMS));
+        md.push_str(&format!(
+            "\n<details><summary>Top symbol changes: {} → {}</summary>\n\n",
+            markdown_cell(&pair[0].file),
+            markdown_cell(&pair[1].file)
+        ));
+        md.push_str("| Delta | Symbol |\n|---:|---|\n");
+        for change in step.top_changes.iter().take(shown) {
+            md.push_str(&format!(
+                "| {} | `{}` |\n",
+                format_delta(change.delta_bytes),
+                markdown_code(&truncate_str(&change.name, MAX_NAME_CHARS))
+            ));
+        }
+        if shown < total {
+            md.push_str(&format!("\n_{} more not shown_\n", total - shown));
+        }
+        md.push_str("\n</details>\n");
+    }
+    md
+}
+
+/// Size table, with the change since `previous_bytes` when given
+fn size_table(size_bytes: u64, previous_bytes: Option<u64>) -> String {
+    let mut md = String::from("| | Size |\n|---|---|\n");
+    match previous_bytes {
+        Some(previous) => {
+            let delta = size_bytes as i64 - previous as i64;
+            md.push_str(&format!(
+                "| Before (last recorded build) | {} |\n| After | {} |\n| Change | {} ({:+.1}%) |\n",
+                format_bytes(previous),
+                format_bytes(size_bytes),
+                format_delta(delta),
+                if previous == 0 {
+                    0.0
+                } else {
+                    delta as f64 / previous as f64 * 100.0
+                }
+            ));
+        }
+        None => md.push_str(&format!("| Total | {} |\n", format_bytes(size_bytes))),
+    }
+    md
+}
+
+/// Collapsible table of the largest items
+fn items_table<'a>(
+    title: &str,
+    items: impl Iterator<Item = (&'a str, u64, f64)>,
+    total: usize,
+    limit: ItemLimit,
+) -> String {
+    if total == 0 {
+        return String::new();
+    }
+    let shown = limit.visible(total, Some(DEFAULT_TOP_ITEMS));
+    let mut md = format!("\n<details><summary>{}</summary>\n\n", title);
+    md.push_str("| Size | % | Item |\n|---:|---:|---|\n");
+    for (name, size_bytes, percentage) in items.take(shown) {
+        md.push_str(&format!(
+            "| {} | {:.1}% | `{}` |\n",
+            format_bytes(size_bytes),
+            percentage,
+            markdown_code(&truncate_str(name, MAX_NAME_CHARS))
+        ));
+    }
+    if shown < total {
+        md.push_str(&format!("\n_{} more not shown_\n", total - shown));
+    }
+    md.push_str("\n</details>\n");
+    md
+}
+
+/// Recommendations table, or a note that there are none
+fn recommendations_section<'a>(
+    rows: impl Iterator<Item = RecommendationRow<'a>>,
+    total: usize,
+    limit: ItemLimit,
+) -> String {
+    let mut md = String::from("\n#### Recommendations\n\n");
+    if total == 0 {
+        md.push_str("No recommendations.\n");
+        return md;
+    }
+    let shown = limit.visible(total, Some(DEFAULT_RECOMMENDATIONS));
+    md.push_str("| Priority | Recommendation | Savings |\n|---|---|---:|\n");
+    for row in rows.take(shown) {
+        md.push_str(&format!(
+            "| {} | {} | {} ({:.1}%) |\n",
+            row.priority,
+            markdown_cell(row.description),
+            format_bytes(row.savings_bytes),
+            row.savings_percent
+        ));
+    }
+    if shown < total {
+        md.push_str(&format!("\n_{} more not shown_\n", total - shown));
+    }
+    md
+}
+
+/// Keep text from breaking a Markdown table cell
+pub(crate) fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Keep text from breaking an inline code span inside a table
+pub(crate) fn markdown_code(text: &str) -> String {
+    markdown_cell(&text.replace('`', "'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::twiggy::AnalysisItem;
+
+    fn recommendation(description: &str) -> twiggy::Recommendation {
+        twiggy::Recommendation {
+            priority: "P1".to_string(),
+            description: description.to_string(),
+            estimated_savings_bytes: 4096,
+            estimated_savings_percent: 4.0,
+            rule: String::new(),
+            subject: None,
+        }
+    }
+
+    #[test]
+    fn test_analysis_markdown_has_size_change_items_and_recommendations() {
+        let results = AnalysisResults {
+            total_size_bytes: 110 * 1024,
+            mode: "top".to_string(),
+            items: (0..12)
+                .map(|i| AnalysisItem {
+                    size_bytes: 1024,
+                    percentage: 1.0,
+                    name: format!("fn_{}<`T`|U>", i),
+                })
+                .collect(),
+            limit: None,
+            recommendations: vec![recommendation("Use a | pipe")],
+            mono_groups: None,
+        };
+
+        let md = format_analysis_markdown(&results, Some(100 * 1024), ItemLimit::Default);
+
+        assert!(md.starts_with("### wasm-slim analysis (top)\n"));
+        assert!(md.contains("| Change | +10.00 KiB (+10.0%) |"));
+        assert!(md.contains("| `fn_0<'T'\\|U>` |"));
+        assert!(md.contains("_2 more not shown_"));
+        assert!(md.contains("| P1 | Use a \\| pipe | 4.00 KiB (4.0%) |"));
+    }
+
+    #[test]
+    fn test_bloat_markdown_without_recommendations() {
+        let results = bloat::BloatResults {
+            total_size_bytes: 2000,
+            text_size_bytes: 1000,
+            items: Vec::new(),
+            crates: Vec::new(),
+            recommendations: Vec::new(),
+        };
+
+        let md = format_bloat_markdown(&results, ItemLimit::All);
+
+        assert!(md.contains("| Code (.text) | 1000 B (50.0%) |"));
+        assert!(!md.contains("<details>"));
+        assert!(md.ends_with("No recommendations.\n"));
+    }
+
+    #[test]
+    fn test_multi_comparison_markdown_lists_builds_and_step_changes() {
+        let step = |before: u64, after: u64, top_changes: Vec<twiggy::ChangeItem>| {
+            twiggy::ComparisonResults {
+                before_size_bytes: before,
+                after_size_bytes: after,
+                delta_bytes: after as i64 - before as i64,
+                delta_percent: 0.0,
+                top_changes,
+                symbols: Default::default(),
+                before_compressed: Default::default(),
+                after_compressed: Default::default(),
+                compressed_delta: Default::default(),
+            }
+        };
+        let files: Vec<String> = ["v1.wasm", "v2.wasm", "v3.wasm"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let results = twiggy::MultiComparison::from_steps(
+            &files,
+            vec![
+                step(
+                    4096,
+                    2048,
+                    vec![twiggy::ChangeItem {
+                        delta_bytes: -2048,
+                        name: "app::render".to_string(),
+                        before_bytes: None,
+                        after_bytes: None,
+                    }],
+                ),
+                step(2048, 3072, Vec::new()),
+            ],
+        );
+
+        let md = format_multi_comparison_markdown(&results, ItemLimit::Default);
+
+        assert!(md.starts_with("### wasm-slim size report (3 builds)\n"));
+        assert!(md.contains("| `v1.wasm` | 4.00 KiB | — | — |"));
+        assert!(md.contains("| `v3.wasm` | 3.00 KiB | +1.00 KiB (+50.0%) | -1.00 KiB (-25.0%) |"));
+        assert!(md.contains("Top symbol changes: v1.wasm → v2.wasm"));
+        assert!(md.contains("| -2.00 KiB | `app::render` |"));
+        assert!(!md.contains("v2.wasm → v3.wasm"));
+    }
+}
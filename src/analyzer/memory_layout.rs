@@ -0,0 +1,528 @@
+//! Memory and table section analysis
+//!
+//! The linker sizes the initial memory from a fixed budget rather than from
+//! what the module needs: rustc reserves a 1 MiB stack, and an explicit
+//! `--initial-memory` or a wasm-bindgen default often asks for far more
+//! pages than the static data, stack and first heap allocations occupy.
+//! Tables get slots for every function whose address is taken. This module
+//! reads the declared memories and tables, finds where the static layout
+//! ends (`__heap_base`, or the stack pointer and the end of the data
+//! segments), and measures the runs of zeros shipped in active data, which
+//! fresh memory already holds.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::MemoryLayoutAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = MemoryLayoutAnalyzer::new().analyze(Path::new("pkg/app_bg.wasm"))?;
+//! println!("static layout ends at {} bytes", report.static_end_bytes);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use crate::fmt::format_bytes;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use wasmparser::{
+    ConstExpr, DataKind, ElementItems, ElementKind, ExternalKind, MemoryType, Operator, Parser,
+    Payload, TypeRef,
+};
+
+/// WASM page size
+const PAGE_BYTES: u64 = 64 * 1024;
+
+/// Unused initial memory beyond the static layout worth trimming
+const SLACK_MEMORY_BYTES: u64 = 1024 * 1024;
+
+/// Shortest run of zeros wasm-opt splits out of a segment
+const MIN_ZERO_RUN_BYTES: u64 = 32;
+
+/// Encoding cost of the extra segment a split introduces
+const SEGMENT_OVERHEAD_BYTES: u64 = 8;
+
+/// Packable zeros worth a recommendation
+const ZERO_RUN_REPORT_BYTES: u64 = 1024;
+
+/// Table slots where indirect call targets start to add up
+const LARGE_TABLE_ENTRIES: u64 = 1000;
+
+/// A linear memory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemoryInfo {
+    /// Memory index (`memory[N]`)
+    pub index: u32,
+    /// `module.name` the memory is imported from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import: Option<String>,
+    /// Name the memory is exported as
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export: Option<String>,
+    /// Declared initial size
+    pub initial_bytes: u64,
+    /// Declared maximum size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum_bytes: Option<u64>,
+    /// Shared between threads
+    pub shared: bool,
+}
+
+impl SizedEntry for MemoryInfo {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.initial_bytes)
+    }
+
+    fn name(&self) -> &str {
+        self.export
+            .as_deref()
+            .or(self.import.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// A table
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TableInfo {
+    /// Table index (`table[N]`)
+    pub index: u32,
+    /// `module.name` the table is imported from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import: Option<String>,
+    /// Name the table is exported as
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export: Option<String>,
+    /// Declared initial slots
+    pub initial: u64,
+    /// Declared maximum slots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<u64>,
+    /// Slots up to the end of the last active element segment
+    pub populated: u64,
+}
+
+impl SizedEntry for TableInfo {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        self.export
+            .as_deref()
+            .or(self.import.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Declared memories and tables of a module
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryLayoutReport {
+    /// Module size
+    pub module_bytes: u64,
+    /// Memories, imported first
+    pub memories: Vec<MemoryInfo>,
+    /// Tables, imported first
+    pub tables: Vec<TableInfo>,
+    /// End of the active data segments in memory 0
+    pub data_end_bytes: u64,
+    /// Initial value of the stack pointer, if the module has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_pointer_bytes: Option<u64>,
+    /// Where the heap starts: `__heap_base`, or the end of the stack and data
+    pub static_end_bytes: u64,
+    /// Runs of zeros in active data long enough to split out
+    pub zero_runs: usize,
+    /// Bytes those runs take up in the binary
+    pub zero_run_bytes: u64,
+    /// Binary bytes saved by splitting them out, net of segment headers
+    pub packable_zero_bytes: u64,
+    /// Suggestions, most impactful first
+    pub recommendations: Vec<String>,
+}
+
+impl MemoryLayoutReport {
+    /// Smallest initial memory holding the static layout, in whole pages
+    pub fn required_memory_bytes(&self) -> u64 {
+        self.static_end_bytes.div_ceil(PAGE_BYTES) * PAGE_BYTES
+    }
+}
+
+/// Reads the memory and table sections of a module
+pub struct MemoryLayoutAnalyzer;
+
+impl MemoryLayoutAnalyzer {
+    /// Create an analyzer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Analyze the memories and tables of a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<MemoryLayoutReport> {
+        let module = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        inspect_module(&module).with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+}
+
+impl Default for MemoryLayoutAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Analyze the memories and tables of a module
+pub fn inspect_module(module: &[u8]) -> Result<MemoryLayoutReport> {
+    let mut memories = Vec::new();
+    let mut tables = Vec::new();
+    let mut imported_globals = 0u32;
+    // Initial value of the first defined mutable global
+    let mut stack_pointer_bytes = None;
+    let mut seen_mutable_global = false;
+    let mut global_values: HashMap<u32, u64> = HashMap::new();
+    let mut global_exports: HashMap<String, u32> = HashMap::new();
+    let mut populated: HashMap<u32, u64> = HashMap::new();
+    let mut data_end_bytes = 0;
+    let mut zero_runs = 0;
+    let mut zero_run_bytes = 0;
+    let mut packable_zero_bytes = 0;
+
+    for payload in Parser::new(0).parse_all(module) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for entry in reader.into_imports_with_offsets() {
+                    let (_, import) = entry?;
+                    let source = Some(format!("{}.{}", import.module, import.name));
+                    match import.ty {
+                        TypeRef::Memory(ty) => {
+                            memories.push(memory_info(memories.len() as u32, source, &ty))
+                        }
+                        TypeRef::Table(ty) => tables.push(TableInfo {
+                            index: tables.len() as u32,
+                            import: source,
+                            export: None,
+                            initial: ty.initial,
+                            maximum: ty.maximum,
+                            populated: 0,
+                        }),
+                        TypeRef::Global(_) => imported_globals += 1,
+                        _ => {}
+                    }
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for ty in reader {
+                    let ty = ty?;
+                    memories.push(memory_info(memories.len() as u32, None, &ty));
+                }
+            }
+            Payload::TableSection(reader) => {
+                for table in reader {
+                    let ty = table?.ty;
+                    tables.push(TableInfo {
+                        index: tables.len() as u32,
+                        import: None,
+                        export: None,
+                        initial: ty.initial,
+                        maximum: ty.maximum,
+                        populated: 0,
+                    });
+                }
+            }
+            Payload::GlobalSection(reader) => {
+                for (offset, global) in reader.into_iter().enumerate() {
+                    let global = global?;
+                    let index = imported_globals + offset as u32;
+                    let value = const_value(&global.init_expr);
+                    if let Some(value) = value {
+                        global_values.insert(index, value);
+                    }
+                    // rustc's linker puts the stack pointer in the first one
+                    if global.ty.mutable && !seen_mutable_global {
+                        seen_mutable_global = true;
+                        stack_pointer_bytes = value;
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    let index = export.index as usize;
+                    match export.kind {
+                        ExternalKind::Memory => {
+                            if let Some(memory) = memories.get_mut(index) {
+                                memory.export.get_or_insert(export.name.to_string());
+                            }
+                        }
+                        ExternalKind::Table => {
+                            if let Some(table) = tables.get_mut(index) {
+                                table.export.get_or_insert(export.name.to_string());
+                            }
+                        }
+                        ExternalKind::Global => {
+                            global_exports.insert(export.name.to_string(), export.index);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Payload::ElementSection(reader) => {
+                for element in reader {
+                    let element = element?;
+                    let ElementKind::Active {
+                        table_index,
+                        offset_expr,
+                    } = element.kind
+                    else {
+                        continue;
+                    };
+                    let count = match element.items {
+                        ElementItems::Functions(reader) => reader.count(),
+                        ElementItems::Expressions(_, reader) => reader.count(),
+                    };
+                    if let Some(offset) = const_value(&offset_expr) {
+                        let end = populated.entry(table_index.unwrap_or(0)).or_insert(0);
+                        *end = (*end).max(offset + u64::from(count));
+                    }
+                }
+            }
+            Payload::DataSection(reader) => {
+                for segment in reader {
+                    let segment = segment?;
+                    let DataKind::Active {
+                        memory_index: 0,
+                        offset_expr,
+                    } = segment.kind
+                    else {
+                        continue;
+                    };
+                    if let Some(offset) = const_value(&offset_expr) {
+                        data_end_bytes = data_end_bytes.max(offset + segment.data.len() as u64);
+                    }
+                    for run in zero_run_lengths(segment.data) {
+                        zero_runs += 1;
+                        zero_run_bytes += run.bytes;
+                        packable_zero_bytes += if run.at_edge {
+                            run.bytes
+                        } else {
+                            run.bytes - SEGMENT_OVERHEAD_BYTES
+                        };
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for table in &mut tables {
+        table.populated = populated.get(&table.index).copied().unwrap_or(0);
+    }
+    let heap_base = global_exports
+        .get("__heap_base")
+        .and_then(|index| global_values.get(index))
+        .copied();
+    let static_end_bytes =
+        heap_base.unwrap_or_else(|| data_end_bytes.max(stack_pointer_bytes.unwrap_or(0)));
+
+    let mut report = MemoryLayoutReport {
+        module_bytes: module.len() as u64,
+        memories,
+        tables,
+        data_end_bytes,
+        stack_pointer_bytes,
+        static_end_bytes,
+        zero_runs,
+        zero_run_bytes,
+        packable_zero_bytes,
+        recommendations: Vec::new(),
+    };
+    report.recommendations = recommendations(&report);
+    Ok(report)
+}
+
+fn memory_info(index: u32, import: Option<String>, ty: &MemoryType) -> MemoryInfo {
+    let page = ty.page_size_log2.map_or(PAGE_BYTES, |log2| 1 << log2);
+    MemoryInfo {
+        index,
+        import,
+        export: None,
+        initial_bytes: ty.initial * page,
+        maximum_bytes: ty.maximum.map(|pages| pages * page),
+        shared: ty.shared,
+    }
+}
+
+/// Value of a constant `i32.const`/`i64.const` expression
+fn const_value(expr: &ConstExpr) -> Option<u64> {
+    let mut reader = expr.get_operators_reader();
+    match reader.read().ok()? {
+        Operator::I32Const { value } => Some(u64::from(value as u32)),
+        Operator::I64Const { value } => Some(value as u64),
+        _ => None,
+    }
+}
+
+/// A run of zeros in a data segment
+struct ZeroRun {
+    bytes: u64,
+    /// Touches the start or end of the segment, so trimming needs no new segment
+    at_edge: bool,
+}
+
+/// Runs of zeros of at least [`MIN_ZERO_RUN_BYTES`] in a segment
+fn zero_run_lengths(data: &[u8]) -> Vec<ZeroRun> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < data.len() {
+        if data[start] != 0 {
+            start += 1;
+            continue;
+        }
+        let end = data[start..]
+            .iter()
+            .position(|&b| b != 0)
+            .map_or(data.len(), |len| start + len);
+        let bytes = (end - start) as u64;
+        if bytes >= MIN_ZERO_RUN_BYTES {
+            runs.push(ZeroRun {
+                bytes,
+                at_edge: start == 0 || end == data.len(),
+            });
+        }
+        start = end;
+    }
+    runs
+}
+
+/// Suggestions for the declared sizes and the shipped zeros
+fn recommendations(report: &MemoryLayoutReport) -> Vec<String> {
+    let mut recs = Vec::new();
+    if report.packable_zero_bytes > ZERO_RUN_REPORT_BYTES {
+        recs.push(format!(
+            "[P1] {} runs of zeros ship {} in active data that fresh memory already holds; \
+             run wasm-opt with --memory-packing (on at -O levels) to save {}, \
+             or initialize large zeroed statics at runtime",
+            report.zero_runs,
+            format_bytes(report.zero_run_bytes),
+            format_bytes(report.packable_zero_bytes)
+        ));
+    }
+
+    let required = report.required_memory_bytes();
+    if let Some(memory) = report.memories.first() {
+        if required > 0 && memory.initial_bytes > required + SLACK_MEMORY_BYTES {
+            let pages = memory.initial_bytes / PAGE_BYTES;
+            if memory.import.is_some() {
+                recs.push(format!(
+                    "[P2] Imported memory starts at {} pages ({}) but the static layout \
+                     ends at {}; create it with initial: {} in the host",
+                    pages,
+                    format_bytes(memory.initial_bytes),
+                    format_bytes(report.static_end_bytes),
+                    required / PAGE_BYTES
+                ));
+            } else {
+                recs.push(format!(
+                    "[P2] Memory starts at {} pages ({}) but the static layout ends at \
+                     {}; link with -C link-arg=--initial-memory={} and let the heap grow \
+                     on demand, or -C link-arg=--import-memory to size it in the host",
+                    pages,
+                    format_bytes(memory.initial_bytes),
+                    format_bytes(report.static_end_bytes),
+                    required
+                ));
+            }
+        }
+    }
+
+    for table in &report.tables {
+        if table.import.is_none() && table.export.is_none() && table.initial > table.populated {
+            recs.push(format!(
+                "[P3] table[{}] declares {} slots but element segments fill {}; \
+                 the rest are allocated empty at instantiation, so drop any link arg \
+                 that raises the initial table size",
+                table.index, table.initial, table.populated
+            ));
+        }
+        if table.populated > LARGE_TABLE_ENTRIES {
+            recs.push(format!(
+                "[P3] {} functions sit in table[{}] as indirect call targets and cannot be \
+                 removed as dead code; replace dyn Trait and function pointers on cold paths \
+                 with enums or generics (see --mode reachability)",
+                table.populated, table.index
+            ));
+        }
+    }
+    recs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{leb, section, HEADER};
+
+    /// A memory of `pages` pages, a 4-slot table filled at offset 1 with
+    /// two functions, a stack pointer at 64 KiB and one data segment at
+    /// 64 KiB holding `data`
+    fn module(pages: u8, data: &[u8]) -> Vec<u8> {
+        let mut wasm = HEADER.to_vec();
+        wasm.extend(section(1, &[1, 0x60, 0, 0]));
+        wasm.extend(section(3, &[2, 0, 0]));
+        wasm.extend(section(4, &[1, 0x70, 0, 4]));
+        wasm.extend(section(5, &[1, 0, pages]));
+        // global 0: mut i32 = 65536
+        wasm.extend(section(6, &[1, 0x7f, 1, 0x41, 0x80, 0x80, 0x04, 0x0b]));
+        wasm.extend(section(
+            7,
+            &[1, 6, b'm', b'e', b'm', b'o', b'r', b'y', 2, 0],
+        ));
+        wasm.extend(section(9, &[1, 0, 0x41, 1, 0x0b, 2, 0, 1]));
+        wasm.extend(section(10, &[2, 2, 0, 0x0b, 2, 0, 0x0b]));
+
+        let mut segments = vec![1, 0, 0x41, 0x80, 0x80, 0x04, 0x0b];
+        segments.extend(leb(data.len()));
+        segments.extend(data);
+        wasm.extend(section(11, &segments));
+        wasm
+    }
+
+    #[test]
+    fn test_inspect_module_reads_memory_table_and_layout() {
+        let report = inspect_module(&module(2, b"hello")).unwrap();
+
+        assert_eq!(
+            report.memories,
+            [MemoryInfo {
+                index: 0,
+                import: None,
+                export: Some("memory".to_string()),
+                initial_bytes: 2 * PAGE_BYTES,
+                maximum_bytes: None,
+                shared: false,
+            }]
+        );
+        assert_eq!(report.tables[0].initial, 4);
+        assert_eq!(report.tables[0].populated, 3);
+        assert_eq!(report.stack_pointer_bytes, Some(PAGE_BYTES));
+        assert_eq!(report.data_end_bytes, PAGE_BYTES + 5);
+        assert_eq!(report.static_end_bytes, PAGE_BYTES + 5);
+        assert_eq!(report.required_memory_bytes(), 2 * PAGE_BYTES);
+        assert_eq!(report.recommendations.len(), 1);
+        assert!(report.recommendations[0].contains("table[0] declares 4 slots"));
+    }
+
+    #[test]
+    fn test_oversized_memory_and_zero_runs_are_recommended() {
+        let mut data = b"head".to_vec();
+        data.extend(vec![0; 4096]);
+        data.extend(b"tail");
+        let report = inspect_module(&module(64, &data)).unwrap();
+
+        assert_eq!(report.zero_runs, 1);
+        assert_eq!(report.zero_run_bytes, 4096);
+        assert_eq!(report.packable_zero_bytes, 4096 - SEGMENT_OVERHEAD_BYTES);
+        assert!(report.recommendations[0].starts_with("[P1] 1 runs of zeros"));
+        assert!(report.recommendations[1].contains(&format!("--initial-memory={}", 2 * PAGE_BYTES)));
+    }
+}
@@ -0,0 +1,149 @@
+//! Memory and table report formatting
+
+use super::memory_layout::MemoryLayoutReport;
+use super::report_utils::format_bytes;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Format the memory and table report for console output
+pub fn format_console_report(report: &MemoryLayoutReport) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Memory and Table Analysis").bold().underlined()
+    )?;
+
+    writeln!(output, "\n{}", style("MEMORIES:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    if report.memories.is_empty() {
+        writeln!(output, "  No linear memory")?;
+    }
+    for memory in &report.memories {
+        let maximum = memory
+            .maximum_bytes
+            .map_or_else(|| "unbounded".to_string(), format_bytes);
+        writeln!(
+            output,
+            "  memory[{}]  initial {}, maximum {}{}",
+            memory.index,
+            style(format_bytes(memory.initial_bytes)).cyan().bold(),
+            maximum,
+            if memory.shared { ", shared" } else { "" }
+        )?;
+        if let Some(import) = &memory.import {
+            writeln!(output, "    imported from {}", import)?;
+        }
+        if let Some(export) = &memory.export {
+            writeln!(output, "    exported as \"{}\"", export)?;
+        }
+    }
+
+    writeln!(output, "\n{}", style("STATIC LAYOUT:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    writeln!(
+        output,
+        "  Data ends at:     {}",
+        format_bytes(report.data_end_bytes)
+    )?;
+    if let Some(stack) = report.stack_pointer_bytes {
+        writeln!(output, "  Stack pointer at: {}", format_bytes(stack))?;
+    }
+    writeln!(
+        output,
+        "  Heap starts at:   {} (needs {} of initial memory)",
+        format_bytes(report.static_end_bytes),
+        style(format_bytes(report.required_memory_bytes()))
+            .cyan()
+            .bold()
+    )?;
+    writeln!(
+        output,
+        "  Zero runs:        {} in {} runs, {} packable",
+        format_bytes(report.zero_run_bytes),
+        report.zero_runs,
+        format_bytes(report.packable_zero_bytes)
+    )?;
+
+    if !report.tables.is_empty() {
+        writeln!(output, "\n{}", style("TABLES:").bold())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        for table in &report.tables {
+            let maximum = table
+                .maximum
+                .map_or_else(|| "unbounded".to_string(), |max| max.to_string());
+            writeln!(
+                output,
+                "  table[{}]  {} slots ({} populated), maximum {}",
+                table.index, table.initial, table.populated, maximum
+            )?;
+        }
+    }
+
+    if report.recommendations.is_empty() {
+        writeln!(
+            output,
+            "\n{}",
+            style("✨ Memory and tables are sized to what the module needs").green()
+        )?;
+    } else {
+        writeln!(output, "\n{}", style("RECOMMENDATIONS:").bold().yellow())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        for rec in &report.recommendations {
+            let styled = if rec.starts_with("[P1]") {
+                style(rec).yellow().bold()
+            } else if rec.starts_with("[P2]") {
+                style(rec).blue()
+            } else {
+                style(rec).green()
+            };
+            writeln!(output, "  {}", styled)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::memory_layout::{MemoryInfo, TableInfo};
+
+    #[test]
+    fn test_format_console_report_shows_memory_layout_and_tables() {
+        let report = MemoryLayoutReport {
+            module_bytes: 8192,
+            memories: vec![MemoryInfo {
+                index: 0,
+                import: None,
+                export: Some("memory".to_string()),
+                initial_bytes: 17 * 1024 * 1024,
+                maximum_bytes: None,
+                shared: false,
+            }],
+            tables: vec![TableInfo {
+                index: 0,
+                import: None,
+                export: None,
+                initial: 12,
+                maximum: Some(12),
+                populated: 12,
+            }],
+            data_end_bytes: 1024 * 1024 + 512,
+            stack_pointer_bytes: Some(1024 * 1024),
+            static_end_bytes: 1024 * 1024 + 512,
+            zero_runs: 0,
+            zero_run_bytes: 0,
+            packable_zero_bytes: 0,
+            recommendations: vec!["[P2] Memory starts at 272 pages".to_string()],
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("memory[0]  initial 17.00 MiB, maximum unbounded"));
+        assert!(text.contains("exported as \"memory\""));
+        assert!(text.contains("needs 1.06 MiB of initial memory"));
+        assert!(text.contains("table[0]  12 slots (12 populated), maximum 12"));
+        assert!(text.contains("[P2] Memory starts at 272 pages"));
+    }
+}
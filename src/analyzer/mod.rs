@@ -6,46 +6,271 @@
 //! - Heavy dependency identification
 //! - Size estimation and reporting
 
+pub mod action_plan;
+pub mod action_plan_report;
+pub mod alloc_profile;
 pub mod allocator;
+pub mod allocator_report;
+pub mod allocator_trials;
 pub mod applicator;
+pub mod asset_compression;
 pub mod asset_display;
+pub mod asset_externalize;
 pub mod asset_metrics;
 pub mod asset_report;
 pub mod asset_types;
 pub mod asset_visitor;
 pub mod assets;
+pub mod batch;
+pub mod batch_report;
+pub mod bindgen_abi;
+pub mod bindgen_abi_report;
 pub mod bloat;
 pub mod bloat_report;
+pub mod config_check;
+pub mod config_check_report;
+pub mod crate_sizes;
+pub mod crate_sizes_report;
+pub mod custom_sections;
+pub mod custom_sections_report;
+pub mod data_segments;
+pub mod data_segments_report;
+pub mod data_structures;
+pub mod data_structures_report;
+pub mod debug_info;
+pub mod debug_info_report;
+pub mod dep_graph;
 pub mod deps;
 pub mod deps_report;
 pub mod deps_types;
+pub mod duplicates;
+pub mod duplicates_report;
+pub mod feature_measure;
 pub mod feature_report;
+pub mod feature_unification;
 pub mod features;
+pub mod fmt_bloat;
+pub mod fmt_bloat_report;
+pub mod frameworks;
+pub mod frameworks_report;
+pub mod health_score;
+pub mod health_score_report;
 pub mod heavy_deps;
+pub mod histogram;
+pub mod histogram_report;
+pub mod js_glue;
+pub mod js_glue_report;
+pub mod llvm_lines;
+pub mod llvm_lines_report;
+pub mod markdown_report;
+pub mod memory_layout;
+pub mod memory_layout_report;
+pub mod no_std;
+pub mod no_std_report;
+pub mod package;
+pub mod package_report;
 pub mod panic_advisor;
+pub mod panic_fixes;
 pub mod panic_report;
 pub mod panics;
+pub mod proposals;
+pub mod reachability;
+pub mod reachability_report;
 pub mod report_utils;
+pub mod rules;
+pub mod sarif;
+pub mod serde_report;
+pub mod serde_usage;
+pub mod size_db;
+pub mod snip;
+pub mod snip_report;
+pub mod source_map;
+pub mod source_map_report;
+pub mod source_patch;
+pub mod startup;
+pub mod startup_report;
+pub mod strings;
+pub mod strings_report;
+pub mod suppressions;
+pub mod symbol_blame;
+pub mod symbol_blame_report;
+pub mod symbol_budget;
+pub mod symbol_budget_report;
+pub mod thresholds;
+pub mod treemap_report;
 pub mod twiggy;
 pub mod twiggy_report;
+pub mod unused_deps;
+pub mod unused_deps_report;
+pub mod vendor_review;
+pub mod vendor_review_report;
+pub mod verify;
+pub mod version_dedupe;
 
 // Public exports for common analyzer types
+pub use alloc_profile::AllocationProfiler;
+pub use allocator::AllocatorDetector;
+pub use allocator_report::{print_allocator_comparison, print_allocator_report};
 pub use applicator::SuggestionApplicator;
-pub use asset_report::{print_asset_report, print_json_output, show_externalization_guide};
+pub use asset_report::{
+    print_asset_report, print_asset_report_with_limit, print_compression, print_externalization,
+    print_json_output, show_externalization_guide,
+};
 pub use assets::AssetDetector;
+pub use batch::BatchAnalyzer;
+pub use batch_report::{
+    format_console_report as format_batch_console,
+    format_console_report_with_limit as format_batch_console_with_limit,
+};
+pub use bindgen_abi::BindgenAbiAnalyzer;
+pub use bindgen_abi_report::{
+    format_console_report as format_bindgen_abi_console,
+    format_console_report_with_limit as format_bindgen_abi_console_with_limit,
+};
 pub use bloat::BloatAnalyzer;
 pub use bloat_report::{
-    format_console_report as format_bloat_console, format_json_report as format_bloat_json,
+    format_console_report as format_bloat_console,
+    format_console_report_with_limit as format_bloat_console_with_limit,
+    format_json_report as format_bloat_json,
+};
+pub use config_check::ConfigChecker;
+pub use config_check_report::format_console_report as format_config_check_console;
+pub use crate_sizes::CrateSizeAnalyzer;
+pub use crate_sizes_report::{
+    format_console_report as format_crate_sizes_console,
+    format_console_report_with_limit as format_crate_sizes_console_with_limit,
+};
+pub use custom_sections::{CustomSectionAnalyzer, CustomSectionReport};
+pub use custom_sections_report::{
+    format_console_report as format_custom_sections_console,
+    format_console_report_with_limit as format_custom_sections_console_with_limit,
+};
+pub use data_segments::{DataReport, DataSegmentAnalyzer};
+pub use data_segments_report::{
+    format_console_report as format_data_segments_console,
+    format_console_report_with_limit as format_data_segments_console_with_limit,
+};
+pub use data_structures::{DataStructureAnalyzer, DataStructureReport};
+pub use data_structures_report::{
+    format_console_report as format_data_structures_console,
+    format_console_report_with_limit as format_data_structures_console_with_limit,
 };
+pub use debug_info::{DebugInfoChecker, DebugInfoReport};
+pub use debug_info_report::format_console_report as format_debug_info_console;
+pub use dep_graph::{DepGraphAnalyzer, DepSizeGraph};
 pub use deps::DependencyAnalyzer;
+pub use duplicates::{DuplicateFunctionAnalyzer, DuplicateReport};
+pub use duplicates_report::{
+    format_console_report as format_duplicates_console,
+    format_console_report_with_limit as format_duplicates_console_with_limit,
+};
 pub use feature_report::{
-    format_console_report as format_feature_console, format_json_report as format_feature_json,
+    format_console_report as format_feature_console,
+    format_console_report_with_limit as format_feature_console_with_limit,
+    format_json_report as format_feature_json,
 };
 pub use features::FeatureAnalyzer;
-pub use panic_report::{print_json_report as print_panic_json, print_panic_report};
+pub use fmt_bloat::{FmtBloatAnalyzer, FmtBloatReport};
+pub use fmt_bloat_report::{
+    format_console_report as format_fmt_bloat_console,
+    format_console_report_with_limit as format_fmt_bloat_console_with_limit,
+};
+pub use frameworks::{FrameworkAnalyzer, FrameworkReport};
+pub use frameworks_report::{
+    format_console_report as format_frameworks_console,
+    format_console_report_with_limit as format_frameworks_console_with_limit,
+};
+pub use histogram::{FunctionSizeAnalyzer, FunctionSizeReport};
+pub use histogram_report::{
+    format_console_report as format_histogram_console,
+    format_console_report_with_limit as format_histogram_console_with_limit,
+};
+pub use js_glue::JsGlueAnalyzer;
+pub use js_glue_report::{
+    format_console_report as format_js_glue_console,
+    format_console_report_with_limit as format_js_glue_console_with_limit,
+};
+pub use llvm_lines::{LlvmLinesAnalyzer, LlvmLinesReport};
+pub use llvm_lines_report::{
+    format_console_report as format_llvm_lines_console,
+    format_console_report_with_limit as format_llvm_lines_console_with_limit,
+};
+pub use memory_layout::{MemoryLayoutAnalyzer, MemoryLayoutReport};
+pub use memory_layout_report::format_console_report as format_memory_layout_console;
+pub use no_std::{NoStdAnalyzer, NoStdReport};
+pub use no_std_report::{
+    format_console_report as format_no_std_console,
+    format_console_report_with_limit as format_no_std_console_with_limit,
+};
+pub use package::{PackageAnalyzer, PackageReport};
+pub use package_report::{
+    format_console_report as format_package_console,
+    format_console_report_with_limit as format_package_console_with_limit,
+};
+pub use panic_report::{
+    print_json_report as print_panic_json, print_panic_fixes, print_panic_report,
+    print_panic_report_with_limit, print_panic_sites,
+};
 pub use panics::PanicDetector;
-pub use twiggy::{AnalysisMode, MonomorphizationGroup, TwiggyAnalyzer};
-pub use twiggy_report::{print_analysis_report, print_comparison_report};
+pub use proposals::ProposalAdvisor;
+pub use reachability::{ReachabilityAnalyzer, ReachabilityReport};
+pub use reachability_report::{
+    format_console_report as format_reachability_console,
+    format_console_report_with_limit as format_reachability_console_with_limit,
+};
+pub use report_utils::{ItemFilter, ItemLimit, SizedEntry, SortOrder};
+pub use serde_report::{
+    format_console_report as format_serde_console,
+    format_console_report_with_limit as format_serde_console_with_limit,
+};
+pub use serde_usage::{SerdeAnalyzer, SerdeReport};
+pub use snip::{SnipPlan, SnipPlanner};
+pub use snip_report::{
+    format_console_report as format_snip_console,
+    format_console_report_with_limit as format_snip_console_with_limit,
+};
+pub use source_map::SourceMapAnalyzer;
+pub use source_map_report::{
+    format_console_report as format_source_map_console,
+    format_console_report_with_limit as format_source_map_console_with_limit,
+};
+pub use startup::{StartupAnalyzer, StartupReport};
+pub use startup_report::format_console_report as format_startup_console;
+pub use strings::{StringAnalyzer, StringReport};
+pub use strings_report::{
+    format_console_report as format_strings_console,
+    format_console_report_with_limit as format_strings_console_with_limit,
+};
+pub use symbol_blame::SymbolBlamer;
+pub use symbol_blame_report::{
+    format_console_report as format_symbol_blame_console,
+    format_console_report_with_limit as format_symbol_blame_console_with_limit, format_pr_comment,
+};
+pub use symbol_budget::{SymbolBaseline, SymbolBudgetReport};
+pub use symbol_budget_report::{
+    format_console_report as format_symbol_budget_console,
+    format_console_report_with_limit as format_symbol_budget_console_with_limit,
+};
+pub use treemap_report::{build_treemap, format_html_report as format_treemap_html, TreemapNode};
+pub use twiggy::{
+    AnalysisBackend, AnalysisMode, FoldedStacks, MonomorphizationGroup, TwiggyAnalyzer,
+};
+pub use twiggy_report::{
+    print_analysis_report, print_analysis_report_with_limit, print_comparison_report,
+    print_comparison_report_with_limit, print_multi_comparison_report,
+    print_multi_comparison_report_with_limit,
+};
+pub use unused_deps::{UnusedDepsAnalyzer, UnusedDepsReport};
+pub use unused_deps_report::{
+    format_console_report as format_unused_deps_console,
+    format_console_report_with_limit as format_unused_deps_console_with_limit,
+};
+pub use vendor_review::VendorReviewer;
+pub use vendor_review_report::{
+    format_console_report as format_vendor_review_console,
+    format_console_report_with_limit as format_vendor_review_console_with_limit,
+};
+pub use verify::RecommendationVerifier;
 
 #[cfg(test)]
 mod tests {
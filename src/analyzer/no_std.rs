@@ -0,0 +1,610 @@
+//! `no_std` feasibility analysis
+//!
+//! Dropping `std` for `core` + `alloc` removes the std runtime pieces a
+//! wasm32 build still links (the panic machinery's formatting, `HashMap`'s
+//! `RandomState`, I/O stubs). Whether that is possible depends on two
+//! things: what the crate itself uses from `std`, and whether every direct
+//! dependency builds without it. This module scans the crate's sources for
+//! `std` paths that have no `core`/`alloc` counterpart and checks each
+//! direct dependency's crate root for `no_std` support, naming the
+//! `Cargo.toml` change that turns `std` off where one exists. Given a
+//! built module, the code attributed to `std::` is reported as the
+//! expected savings.
+
+use super::report_utils::SizedEntry;
+use super::serde_usage::collect_rust_files;
+use super::twiggy::native::ItemGraph;
+use crate::profiling::{self, ProfilePhase};
+use anyhow::{Context, Result};
+use cargo_metadata::{Dependency, DependencyKind, Metadata, MetadataCommand, Package, TargetKind};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+
+/// Overall verdict of the analysis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoStdVerdict {
+    /// The crate is already `#![no_std]`
+    AlreadyNoStd,
+    /// Nothing blocks a `no_std + alloc` build
+    Feasible,
+    /// Every blocker has a known fix
+    FeasibleWithChanges,
+    /// Some blocker has no known fix
+    Blocked,
+}
+
+impl NoStdVerdict {
+    /// Get human-readable description
+    pub fn description(&self) -> &'static str {
+        match self {
+            NoStdVerdict::AlreadyNoStd => "already no_std",
+            NoStdVerdict::Feasible => "feasible",
+            NoStdVerdict::FeasibleWithChanges => "feasible with changes",
+            NoStdVerdict::Blocked => "blocked",
+        }
+    }
+}
+
+/// Something that requires `std`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NoStdBlocker {
+    /// `std` path or macro used by the crate, or a dependency name
+    pub subject: String,
+    /// Why it needs `std`
+    pub reason: String,
+    /// Change that removes the blocker, if one is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl SizedEntry for NoStdBlocker {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        &self.subject
+    }
+}
+
+/// Code the module links from `std`
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SavingsEstimate {
+    /// Bytes of functions attributed to `std::`
+    pub std_code_bytes: u64,
+    /// Module size in bytes
+    pub module_bytes: u64,
+}
+
+/// Result of the `no_std` feasibility analysis
+#[derive(Debug, Clone, Serialize)]
+pub struct NoStdReport {
+    /// Analyzed package
+    pub package: String,
+    /// Overall verdict
+    pub verdict: NoStdVerdict,
+    /// Rust files scanned
+    pub files_scanned: usize,
+    /// Direct dependencies checked
+    pub dependencies_checked: usize,
+    /// Crate sources using `std`-only APIs
+    pub source_blockers: Vec<NoStdBlocker>,
+    /// Dependencies building with `std`
+    pub dependency_blockers: Vec<NoStdBlocker>,
+    /// Expected savings, when a WASM file was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub savings: Option<SavingsEstimate>,
+    /// Problems that limited the analysis
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Checks whether a crate can build as `no_std + alloc`
+pub struct NoStdAnalyzer {
+    project_root: PathBuf,
+}
+
+impl NoStdAnalyzer {
+    /// Create an analyzer for the project at `project_root`
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Check the root package, estimating savings from `wasm_file` if given
+    pub fn analyze(&self, wasm_file: Option<&Path>) -> Result<NoStdReport> {
+        let metadata = {
+            let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+            MetadataCommand::new()
+                .current_dir(&self.project_root)
+                .exec()
+                .context("Failed to run cargo metadata")?
+        };
+        let package = metadata.root_package().context(
+            "no-std needs a package manifest; run it in a crate, not a virtual workspace",
+        )?;
+
+        let mut warnings = Vec::new();
+        let already_no_std = package
+            .targets
+            .iter()
+            .find(|t| t.is_kind(TargetKind::Lib) || t.is_kind(TargetKind::CDyLib))
+            .and_then(|t| std::fs::read_to_string(&t.src_path).ok())
+            .is_some_and(|source| no_std_support(&source) == NoStdSupport::Always);
+
+        let mut uses = BTreeMap::new();
+        let mut files = Vec::new();
+        collect_rust_files(&self.project_root.join("src"), &mut files)?;
+        for file in &files {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let relative = file.strip_prefix(&self.project_root).unwrap_or(file);
+            if let Err(e) = scan_source(&content, &relative.display().to_string(), &mut uses) {
+                warnings.push(format!(
+                    "Could not parse {} ({}); its std usage was not checked",
+                    relative.display(),
+                    e
+                ));
+            }
+        }
+
+        let (dependencies_checked, dependency_blockers) =
+            check_dependencies(&metadata, package, &mut warnings);
+
+        let savings = match wasm_file {
+            Some(wasm_file) => Some(estimate_savings(wasm_file)?),
+            None => None,
+        };
+
+        let source_blockers: Vec<NoStdBlocker> = uses.into_values().collect();
+        Ok(NoStdReport {
+            package: package.name.to_string(),
+            verdict: verdict(already_no_std, &source_blockers, &dependency_blockers),
+            files_scanned: files.len(),
+            dependencies_checked,
+            source_blockers,
+            dependency_blockers,
+            savings,
+            warnings,
+        })
+    }
+}
+
+/// Combine the blockers into a verdict
+fn verdict(
+    already_no_std: bool,
+    source_blockers: &[NoStdBlocker],
+    dependency_blockers: &[NoStdBlocker],
+) -> NoStdVerdict {
+    let mut blockers = source_blockers.iter().chain(dependency_blockers);
+    if already_no_std {
+        NoStdVerdict::AlreadyNoStd
+    } else if source_blockers.is_empty() && dependency_blockers.is_empty() {
+        NoStdVerdict::Feasible
+    } else if blockers.all(|b| b.fix.is_some()) {
+        NoStdVerdict::FeasibleWithChanges
+    } else {
+        NoStdVerdict::Blocked
+    }
+}
+
+/// How a crate root supports `no_std`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NoStdSupport {
+    /// `#![no_std]`
+    Always,
+    /// `#![cfg_attr(..., no_std)]`, usually behind a `std` feature
+    Conditional,
+    /// Always links `std`
+    Never,
+}
+
+/// Read the `no_std` attribute of a crate root
+fn no_std_support(source: &str) -> NoStdSupport {
+    let Ok(file) = syn::parse_file(source) else {
+        // Fall back to a textual check
+        return if source.contains("#![no_std]") {
+            NoStdSupport::Always
+        } else if source.contains("no_std)]") {
+            NoStdSupport::Conditional
+        } else {
+            NoStdSupport::Never
+        };
+    };
+    let mut support = NoStdSupport::Never;
+    for attr in &file.attrs {
+        if attr.path().is_ident("no_std") {
+            return NoStdSupport::Always;
+        }
+        if attr.path().is_ident("cfg_attr") {
+            if let syn::Meta::List(list) = &attr.meta {
+                let tokens = list.tokens.to_string();
+                if tokens.ends_with(", no_std") || tokens.contains("no_std ,") {
+                    support = NoStdSupport::Conditional;
+                }
+            }
+        }
+    }
+    support
+}
+
+/// Check the direct normal dependencies of `package`
+fn check_dependencies(
+    metadata: &Metadata,
+    package: &Package,
+    warnings: &mut Vec<String>,
+) -> (usize, Vec<NoStdBlocker>) {
+    let mut checked = 0;
+    let mut blockers = Vec::new();
+    for dep in &package.dependencies {
+        if dep.kind != DependencyKind::Normal {
+            continue;
+        }
+        let Some(resolved) = metadata
+            .packages
+            .iter()
+            .find(|p| p.name.as_str() == dep.name && dep.req.matches(&p.version))
+        else {
+            continue;
+        };
+        let Some(lib) = resolved
+            .targets
+            .iter()
+            .find(|t| t.is_kind(TargetKind::Lib) || t.is_kind(TargetKind::RLib))
+        else {
+            // Proc macros run on the host and never reach the module
+            continue;
+        };
+        checked += 1;
+
+        let support = match std::fs::read_to_string(&lib.src_path) {
+            Ok(source) => no_std_support(&source),
+            Err(e) => {
+                warnings.push(format!(
+                    "Could not read {} ({}); assuming it needs std",
+                    lib.src_path, e
+                ));
+                NoStdSupport::Never
+            }
+        };
+        if let Some(blocker) = dependency_blocker(dep, resolved, support) {
+            blockers.push(blocker);
+        }
+    }
+    blockers.sort_by(|a, b| {
+        a.fix
+            .is_some()
+            .cmp(&b.fix.is_some())
+            .then(a.subject.cmp(&b.subject))
+    });
+    (checked, blockers)
+}
+
+/// Whether the declaration of `dep` keeps `std` on
+fn dependency_blocker(
+    dep: &Dependency,
+    resolved: &Package,
+    support: NoStdSupport,
+) -> Option<NoStdBlocker> {
+    let name = dep.rename.clone().unwrap_or_else(|| dep.name.clone());
+    match support {
+        NoStdSupport::Always => None,
+        NoStdSupport::Never => Some(NoStdBlocker {
+            subject: name,
+            reason: "crate root is not no_std".to_string(),
+            fix: None,
+        }),
+        NoStdSupport::Conditional => {
+            let default_std = dep.uses_default_features
+                && resolved
+                    .features
+                    .get("default")
+                    .is_some_and(|default| default.iter().any(|f| f == "std"));
+            let explicit_std = dep.features.iter().any(|f| f == "std");
+            if !default_std && !explicit_std {
+                return None;
+            }
+            let mut features: Vec<&str> = dep
+                .features
+                .iter()
+                .map(String::as_str)
+                .filter(|f| *f != "std")
+                .collect();
+            if resolved.features.contains_key("alloc") && !features.contains(&"alloc") {
+                features.push("alloc");
+            }
+            let features = features
+                .iter()
+                .map(|f| format!("\"{}\"", f))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Some(NoStdBlocker {
+                reason: if explicit_std {
+                    "\"std\" feature enabled".to_string()
+                } else {
+                    "\"std\" is a default feature".to_string()
+                },
+                fix: Some(format!(
+                    "{} = {{ version = \"{}\", default-features = false, features = [{}] }}",
+                    name, dep.req, features
+                )),
+                subject: name,
+            })
+        }
+    }
+}
+
+/// What a `std` path needs
+enum StdUse {
+    /// Available from `core` or `alloc` under the same path
+    Portable,
+    /// Needs `std`, with a `no_std` replacement
+    Replaceable(&'static str),
+    /// Needs `std`, no drop-in replacement
+    StdOnly,
+}
+
+/// Classify a path below `std` (segments after `std`)
+fn classify(segments: &[String]) -> StdUse {
+    let module = segments.first().map(String::as_str).unwrap_or("");
+    let item = segments.get(1).map(String::as_str).unwrap_or("");
+    match (module, item) {
+        ("collections", "HashMap" | "HashSet" | "hash_map" | "hash_set") => {
+            StdUse::Replaceable("use hashbrown (or alloc::collections::BTreeMap/BTreeSet)")
+        }
+        ("sync", "Mutex" | "RwLock" | "Once" | "OnceLock" | "LazyLock" | "mpsc") => {
+            StdUse::Replaceable("use spin or once_cell's race/critical-section types")
+        }
+        ("time", "Instant" | "SystemTime") => {
+            StdUse::Replaceable("read time from the host (js_sys::Date::now)")
+        }
+        ("error", _) => StdUse::Portable,
+        ("io", _) => StdUse::Replaceable("use core::fmt::Write or embedded-io"),
+        ("fs" | "net" | "thread" | "process" | "env" | "path" | "os", _) => StdUse::StdOnly,
+        ("ffi", "OsString" | "OsStr") => StdUse::StdOnly,
+        _ => StdUse::Portable,
+    }
+}
+
+/// Collect the `std`-only paths and macros one source file uses
+fn scan_source(
+    content: &str,
+    file_label: &str,
+    uses: &mut BTreeMap<String, NoStdBlocker>,
+) -> syn::Result<()> {
+    let file = syn::parse_file(content)?;
+    let mut visitor = StdVisitor {
+        file_label,
+        uses,
+        prefix: Vec::new(),
+    };
+    visitor.visit_file(&file);
+    Ok(())
+}
+
+struct StdVisitor<'a> {
+    file_label: &'a str,
+    uses: &'a mut BTreeMap<String, NoStdBlocker>,
+    /// Segments of the enclosing `use` groups
+    prefix: Vec<String>,
+}
+
+impl StdVisitor<'_> {
+    fn record(&mut self, segments: &[String], line: usize) {
+        if segments.first().map(String::as_str) != Some("std") {
+            return;
+        }
+        let rest = &segments[1..];
+        let fix = match classify(rest) {
+            StdUse::Portable => return,
+            StdUse::Replaceable(fix) => Some(fix.to_string()),
+            StdUse::StdOnly => None,
+        };
+        // Name the item where only some items of the module need std
+        let depth = match rest[0].as_str() {
+            "collections" | "sync" | "time" | "ffi" => 2,
+            _ => 1,
+        };
+        let subject = format!("std::{}", rest[..depth.min(rest.len())].join("::"));
+        self.add(subject, line, fix);
+    }
+
+    fn add(&mut self, subject: String, line: usize, fix: Option<String>) {
+        let location = format!("{}:{}", self.file_label, line);
+        self.uses
+            .entry(subject.clone())
+            .or_insert_with(|| NoStdBlocker {
+                subject,
+                reason: format!("used at {}", location),
+                fix,
+            });
+    }
+
+    fn visit_use_tree_segments(&mut self, tree: &syn::UseTree) {
+        match tree {
+            syn::UseTree::Path(p) => {
+                self.prefix.push(p.ident.to_string());
+                self.visit_use_tree_segments(&p.tree);
+                self.prefix.pop();
+            }
+            syn::UseTree::Name(n) => self.record_use(&n.ident),
+            syn::UseTree::Rename(r) => self.record_use(&r.ident),
+            syn::UseTree::Group(g) => g.items.iter().for_each(|t| self.visit_use_tree_segments(t)),
+            syn::UseTree::Glob(g) => {
+                let segments = self.prefix.clone();
+                self.record(&segments, g.star_token.span().start().line);
+            }
+        }
+    }
+
+    fn record_use(&mut self, ident: &syn::Ident) {
+        let mut segments = self.prefix.clone();
+        segments.push(ident.to_string());
+        self.record(&segments, ident.span().start().line);
+    }
+}
+
+impl<'ast> Visit<'ast> for StdVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &'ast syn::ItemMod) {
+        // Tests build with std regardless
+        let cfg_test = item.attrs.iter().any(|attr| {
+            attr.path().is_ident("cfg")
+                && attr
+                    .parse_args::<syn::Ident>()
+                    .is_ok_and(|ident| ident == "test")
+        });
+        if !cfg_test {
+            visit::visit_item_mod(self, item);
+        }
+    }
+
+    fn visit_item_use(&mut self, item: &'ast syn::ItemUse) {
+        self.prefix.clear();
+        self.visit_use_tree_segments(&item.tree);
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        self.record(&segments, path.span().start().line);
+        visit::visit_path(self, path);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if let Some(name) = mac.path.get_ident().map(|i| i.to_string()) {
+            if matches!(
+                name.as_str(),
+                "println" | "eprintln" | "print" | "eprint" | "dbg"
+            ) {
+                let line = mac.path.span().start().line;
+                self.add(
+                    format!("{}!", name),
+                    line,
+                    Some("log through web_sys::console or a log backend".to_string()),
+                );
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Sum the code the module attributes to `std`
+fn estimate_savings(wasm_file: &Path) -> Result<SavingsEstimate> {
+    let graph = ItemGraph::read(wasm_file)
+        .with_context(|| format!("Failed to parse {}", wasm_file.display()))?;
+    let std_code_bytes = (0..graph.item_count())
+        .filter(|&id| graph.is_code(id))
+        .filter(|&id| {
+            let name = graph.name(id);
+            name.starts_with("std::") || name.starts_with("<std::")
+        })
+        .map(|id| graph.size_bytes(id))
+        .sum();
+    Ok(SavingsEstimate {
+        std_code_bytes,
+        module_bytes: std::fs::metadata(wasm_file)?.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_source_reports_std_only_apis() {
+        let mut uses = BTreeMap::new();
+        scan_source(
+            r#"
+            use std::collections::{BTreeMap, HashMap};
+            use std::fmt::Write;
+            use std::{fs, sync::Arc};
+
+            fn run() -> std::io::Result<()> {
+                let _ = std::vec::Vec::<u8>::new();
+                println!("{}", fs::read_to_string("x")?);
+                Ok(())
+            }
+
+            #[cfg(test)]
+            mod tests {
+                use std::thread;
+            }
+            "#,
+            "src/lib.rs",
+            &mut uses,
+        )
+        .unwrap();
+
+        let subjects: Vec<&str> = uses.keys().map(String::as_str).collect();
+        assert_eq!(
+            subjects,
+            [
+                "println!",
+                "std::collections::HashMap",
+                "std::fs",
+                "std::io"
+            ]
+        );
+        assert_eq!(uses["std::fs"].fix, None);
+        assert_eq!(uses["std::fs"].reason, "used at src/lib.rs:4");
+        assert!(uses["std::collections::HashMap"]
+            .fix
+            .as_deref()
+            .unwrap()
+            .contains("hashbrown"));
+    }
+
+    #[test]
+    fn test_no_std_support_reads_crate_attributes() {
+        assert_eq!(
+            no_std_support("#![no_std]\npub fn f() {}"),
+            NoStdSupport::Always
+        );
+        assert_eq!(
+            no_std_support("#![cfg_attr(not(feature = \"std\"), no_std)]\npub fn f() {}"),
+            NoStdSupport::Conditional
+        );
+        assert_eq!(no_std_support("pub fn f() {}"), NoStdSupport::Never);
+    }
+
+    #[test]
+    fn test_dependency_blocker_suggests_disabling_default_std() {
+        let dep: Dependency = serde_json::from_value(serde_json::json!({
+            "name": "serde", "source": null, "req": "^1.0", "kind": null,
+            "optional": false, "uses_default_features": true,
+            "features": ["derive"], "target": null, "rename": null,
+            "registry": null, "path": null
+        }))
+        .unwrap();
+        let resolved: Package = serde_json::from_value(serde_json::json!({
+            "name": "serde", "version": "1.0.0", "id": "serde 1.0.0",
+            "license": null, "license_file": null, "description": null,
+            "source": null, "dependencies": [], "targets": [],
+            "features": {"default": ["std"], "std": [], "alloc": [], "derive": []},
+            "manifest_path": "/fake/serde/Cargo.toml",
+            "metadata": null, "publish": null, "authors": [], "categories": [],
+            "keywords": [], "readme": null, "repository": null, "homepage": null,
+            "documentation": null, "edition": "2021", "links": null,
+            "default_run": null, "rust_version": null
+        }))
+        .unwrap();
+
+        let blocker = dependency_blocker(&dep, &resolved, NoStdSupport::Conditional).unwrap();
+        assert_eq!(blocker.reason, "\"std\" is a default feature");
+        assert_eq!(
+            blocker.fix.as_deref(),
+            Some(
+                "serde = { version = \"^1.0\", default-features = false, features = [\"derive\", \"alloc\"] }"
+            )
+        );
+        assert!(dependency_blocker(&dep, &resolved, NoStdSupport::Always).is_none());
+        assert!(dependency_blocker(&dep, &resolved, NoStdSupport::Never)
+            .unwrap()
+            .fix
+            .is_none());
+    }
+}
@@ -0,0 +1,160 @@
+//! `no_std` feasibility report formatting
+
+use super::no_std::{NoStdBlocker, NoStdReport, NoStdVerdict};
+use super::report_utils::{format_bytes, omitted_footer, ItemLimit};
+use crate::fmt::percent_of;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of blockers listed per group by default
+const DEFAULT_TOP_BLOCKERS: usize = 20;
+
+/// Format the `no_std` report for console output
+pub fn format_console_report(report: &NoStdReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the `no_std` report, limiting the blockers listed
+pub fn format_console_report_with_limit(
+    report: &NoStdReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("no_std Feasibility").bold().underlined()
+    )?;
+    let verdict = match report.verdict {
+        NoStdVerdict::Blocked => style(report.verdict.description()).red().bold(),
+        NoStdVerdict::FeasibleWithChanges => style(report.verdict.description()).yellow().bold(),
+        _ => style(report.verdict.description()).green().bold(),
+    };
+    writeln!(
+        output,
+        "\n   {}: {} ({} source files, {} dependencies checked)",
+        report.package, verdict, report.files_scanned, report.dependencies_checked
+    )?;
+    for warning in &report.warnings {
+        writeln!(
+            output,
+            "   {} {}",
+            style("⚠").yellow(),
+            style(warning).yellow()
+        )?;
+    }
+
+    write_blockers(
+        &mut output,
+        "CRATE SOURCES:",
+        &report.source_blockers,
+        limit,
+    )?;
+    write_blockers(
+        &mut output,
+        "DEPENDENCIES:",
+        &report.dependency_blockers,
+        limit,
+    )?;
+
+    if let Some(savings) = report.savings {
+        writeln!(
+            output,
+            "\n   Estimated savings: ~{} of std code ({:.1}% of {})",
+            style(format_bytes(savings.std_code_bytes)).green().bold(),
+            percent_of(savings.std_code_bytes, savings.module_bytes),
+            format_bytes(savings.module_bytes)
+        )?;
+    }
+
+    if matches!(
+        report.verdict,
+        NoStdVerdict::Feasible | NoStdVerdict::FeasibleWithChanges
+    ) {
+        writeln!(
+            output,
+            "\n   {} Add #![no_std] and extern crate alloc, then provide a #[global_allocator] \
+             (e.g. dlmalloc or talc) and a #[panic_handler] for wasm32-unknown-unknown",
+            style("→").cyan()
+        )?;
+    }
+
+    Ok(output)
+}
+
+/// Write one group of blockers
+fn write_blockers(
+    output: &mut String,
+    title: &str,
+    blockers: &[NoStdBlocker],
+    limit: ItemLimit,
+) -> fmt::Result {
+    if blockers.is_empty() {
+        return Ok(());
+    }
+    writeln!(output, "\n{}", style(title).bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    let shown = limit.visible(blockers.len(), Some(DEFAULT_TOP_BLOCKERS));
+    for blocker in blockers.iter().take(shown) {
+        let mark = if blocker.fix.is_some() {
+            style("•").yellow()
+        } else {
+            style("✗").red()
+        };
+        writeln!(
+            output,
+            "  {} {} {}",
+            mark,
+            style(&blocker.subject).bold(),
+            style(format!("({})", blocker.reason)).dim()
+        )?;
+        if let Some(fix) = &blocker.fix {
+            writeln!(output, "      {} {}", style("→").cyan(), fix)?;
+        }
+    }
+    if let Some(footer) = omitted_footer(blockers.len(), shown, "blockers") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::no_std::SavingsEstimate;
+
+    #[test]
+    fn test_format_console_report_shows_verdict_blockers_and_savings() {
+        let blocker = |subject: &str, fix: Option<&str>| NoStdBlocker {
+            subject: subject.to_string(),
+            reason: "used at src/lib.rs:3".to_string(),
+            fix: fix.map(str::to_string),
+        };
+        let report = NoStdReport {
+            package: "app".to_string(),
+            verdict: NoStdVerdict::Blocked,
+            files_scanned: 4,
+            dependencies_checked: 2,
+            source_blockers: vec![
+                blocker("std::fs", None),
+                blocker("std::collections::HashMap", Some("use hashbrown")),
+            ],
+            dependency_blockers: vec![],
+            savings: Some(SavingsEstimate {
+                std_code_bytes: 1024,
+                module_bytes: 4096,
+            }),
+            warnings: vec![],
+        };
+
+        let text = format_console_report_with_limit(&report, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("(4 source files, 2 dependencies checked)"));
+        assert!(text.contains("std::fs"));
+        assert!(!text.contains("use hashbrown"));
+        assert!(text.contains("1 more blockers omitted"));
+        assert!(!text.contains("DEPENDENCIES:"));
+        assert!(text.contains("~1.00 KiB of std code (25.0% of 4.00 KiB)"));
+        assert!(!text.contains("#[global_allocator]"));
+    }
+}
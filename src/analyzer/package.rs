@@ -0,0 +1,268 @@
+//! Whole-package size analysis
+//!
+//! What a page downloads is not just the `.wasm`: wasm-pack's `pkg/` also
+//! ships the JS glue, the `.d.ts` typings and any JS snippets the crate
+//! pulls in with `#[wasm_bindgen(module = ...)]`. This module totals those
+//! files, raw and gzipped, so the deliverable as a whole can be tracked and
+//! budgeted (`[package_budget]`). Package metadata (`package.json`, README,
+//! LICENSE) is not shipped to browsers and is left out.
+
+use super::report_utils::SizedEntry;
+use crate::infra::{FileSystem, RealFileSystem};
+use crate::summary;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Directory wasm-bindgen copies JS snippets to
+const SNIPPETS_DIR: &str = "snippets";
+
+/// Errors that can occur during package analysis
+#[derive(Error, Debug)]
+pub enum PackageError {
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The directory holds no WASM module
+    #[error("No .wasm file in {0}; is it a wasm-pack output directory?")]
+    NoWasm(PathBuf),
+}
+
+/// Kind of deliverable file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageFileKind {
+    /// The WASM module
+    Wasm,
+    /// Generated JS glue
+    Glue,
+    /// TypeScript declarations (`.d.ts`)
+    Typings,
+    /// JS snippets copied from the crate (`snippets/`)
+    Snippet,
+}
+
+impl PackageFileKind {
+    /// Get human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            PackageFileKind::Wasm => "WASM",
+            PackageFileKind::Glue => "JS glue",
+            PackageFileKind::Typings => "Typings (.d.ts)",
+            PackageFileKind::Snippet => "Snippets",
+        }
+    }
+
+    /// Kind of a file at `relative` inside the package, if it is shipped
+    fn of(relative: &Path) -> Option<Self> {
+        let name = relative.file_name()?.to_str()?;
+        if name.ends_with(".wasm") {
+            Some(PackageFileKind::Wasm)
+        } else if name.ends_with(".d.ts") {
+            Some(PackageFileKind::Typings)
+        } else if relative.starts_with(SNIPPETS_DIR) {
+            Some(PackageFileKind::Snippet)
+        } else if name.ends_with(".js") || name.ends_with(".mjs") || name.ends_with(".cjs") {
+            Some(PackageFileKind::Glue)
+        } else {
+            None
+        }
+    }
+}
+
+/// One deliverable file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageFile {
+    /// Path relative to the package directory
+    pub path: PathBuf,
+    /// Kind of file
+    pub kind: PackageFileKind,
+    /// Size in bytes
+    pub size_bytes: u64,
+    /// Size after gzip at level 9
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gzip_bytes: Option<u64>,
+}
+
+impl SizedEntry for PackageFile {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        self.path.to_str().unwrap_or_default()
+    }
+}
+
+/// Sizes of all files of one kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KindTotal {
+    /// Kind of file
+    pub kind: PackageFileKind,
+    /// Number of files
+    pub files: usize,
+    /// Total size in bytes
+    pub size_bytes: u64,
+    /// Total gzipped size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gzip_bytes: Option<u64>,
+}
+
+/// Complete package analysis results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageReport {
+    /// Package directory
+    pub dir: PathBuf,
+    /// Totals per kind, in [`PackageFileKind`] order
+    pub by_kind: Vec<KindTotal>,
+    /// Deliverable files, largest first
+    pub files: Vec<PackageFile>,
+    /// Total deliverable size in bytes
+    pub total_size_bytes: u64,
+    /// Total gzipped size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_gzip_bytes: Option<u64>,
+}
+
+/// Analyzer for a wasm-pack output directory
+pub struct PackageAnalyzer<FS: FileSystem = RealFileSystem> {
+    dir: PathBuf,
+    fs: FS,
+}
+
+impl PackageAnalyzer {
+    /// Create an analyzer for a package directory (e.g. `pkg/`)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self::with_fs(dir, RealFileSystem)
+    }
+}
+
+impl<FS: FileSystem> PackageAnalyzer<FS> {
+    /// Create an analyzer with a custom filesystem
+    pub fn with_fs(dir: impl Into<PathBuf>, fs: FS) -> Self {
+        Self {
+            dir: dir.into(),
+            fs,
+        }
+    }
+
+    /// Measure every deliverable file in the package
+    pub fn analyze(&self) -> Result<PackageReport, PackageError> {
+        let mut paths = Vec::new();
+        self.collect_files(&self.dir, &mut paths)?;
+
+        let mut files = Vec::new();
+        for path in paths {
+            let relative = path.strip_prefix(&self.dir).unwrap_or(&path).to_path_buf();
+            let Some(kind) = PackageFileKind::of(&relative) else {
+                continue;
+            };
+            files.push(PackageFile {
+                kind,
+                size_bytes: self.fs.metadata(&path)?.len(),
+                gzip_bytes: summary::gzip_size(&path),
+                path: relative,
+            });
+        }
+        if !files.iter().any(|f| f.kind == PackageFileKind::Wasm) {
+            return Err(PackageError::NoWasm(self.dir.clone()));
+        }
+        files.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.path.cmp(&b.path)));
+
+        Ok(build_report(self.dir.clone(), files))
+    }
+
+    /// Recursively collect files
+    fn collect_files(&self, dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), PackageError> {
+        for entry in self.fs.read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.collect_files(&path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Total the files per kind and overall
+fn build_report(dir: PathBuf, files: Vec<PackageFile>) -> PackageReport {
+    let mut by_kind: Vec<KindTotal> = Vec::new();
+    for file in &files {
+        match by_kind.iter_mut().find(|k| k.kind == file.kind) {
+            Some(total) => {
+                total.files += 1;
+                total.size_bytes += file.size_bytes;
+                total.gzip_bytes = total.gzip_bytes.zip(file.gzip_bytes).map(|(a, b)| a + b);
+            }
+            None => by_kind.push(KindTotal {
+                kind: file.kind,
+                files: 1,
+                size_bytes: file.size_bytes,
+                gzip_bytes: file.gzip_bytes,
+            }),
+        }
+    }
+    by_kind.sort_by_key(|k| k.kind);
+
+    PackageReport {
+        dir,
+        total_size_bytes: by_kind.iter().map(|k| k.size_bytes).sum(),
+        total_gzip_bytes: by_kind.iter().map(|k| k.gzip_bytes).sum(),
+        by_kind,
+        files,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_totals_deliverables_by_kind() {
+        let temp = tempfile::tempdir().unwrap();
+        let pkg = temp.path();
+        std::fs::write(pkg.join("app_bg.wasm"), vec![0; 1000]).unwrap();
+        std::fs::write(pkg.join("app.js"), vec![b' '; 300]).unwrap();
+        std::fs::write(pkg.join("app_bg.js"), vec![b' '; 200]).unwrap();
+        std::fs::write(pkg.join("app.d.ts"), vec![b' '; 50]).unwrap();
+        std::fs::write(pkg.join("package.json"), "{}").unwrap();
+        std::fs::write(pkg.join("README.md"), "# app").unwrap();
+        let snippet_dir = pkg.join("snippets").join("app-1234").join("src");
+        std::fs::create_dir_all(&snippet_dir).unwrap();
+        std::fs::write(snippet_dir.join("dom.js"), vec![b' '; 40]).unwrap();
+
+        let report = PackageAnalyzer::new(pkg).analyze().unwrap();
+
+        assert_eq!(report.files.len(), 5);
+        assert_eq!(report.files[0].path, PathBuf::from("app_bg.wasm"));
+        assert_eq!(report.total_size_bytes, 1590);
+        assert!(report.total_gzip_bytes.is_some_and(|gzip| gzip < 1590));
+        let kinds: Vec<_> = report
+            .by_kind
+            .iter()
+            .map(|k| (k.kind, k.files, k.size_bytes))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (PackageFileKind::Wasm, 1, 1000),
+                (PackageFileKind::Glue, 2, 500),
+                (PackageFileKind::Typings, 1, 50),
+                (PackageFileKind::Snippet, 1, 40),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_analyze_without_wasm_fails() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("index.js"), "").unwrap();
+
+        let result = PackageAnalyzer::new(temp.path()).analyze();
+
+        assert!(matches!(result, Err(PackageError::NoWasm(_))));
+    }
+}
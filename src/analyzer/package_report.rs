@@ -0,0 +1,122 @@
+//! Package analysis report formatting
+
+use super::package::PackageReport;
+use super::report_utils::{format_bytes, omitted_footer, ItemLimit};
+use crate::fmt::percent_of;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of files listed by default
+const DEFAULT_TOP_FILES: usize = 20;
+
+/// Format the package report for console output
+pub fn format_console_report(report: &PackageReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the package report, limiting the files listed
+pub fn format_console_report_with_limit(
+    report: &PackageReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Package Analysis").bold().underlined()
+    )?;
+    let gzip = report
+        .total_gzip_bytes
+        .map(|bytes| format!(" ({} gzipped)", format_bytes(bytes)))
+        .unwrap_or_default();
+    writeln!(
+        output,
+        "\n   {}: {}{} in {} files",
+        report.dir.display(),
+        style(format_bytes(report.total_size_bytes)).cyan().bold(),
+        gzip,
+        report.files.len()
+    )?;
+
+    writeln!(output, "\n{}", style("BY KIND:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    for kind in &report.by_kind {
+        let gzip = kind
+            .gzip_bytes
+            .map(|bytes| format!("{} gzipped", format_bytes(bytes)))
+            .unwrap_or_default();
+        writeln!(
+            output,
+            "  {:16} {:>12} {:>6.1}%  {}",
+            kind.kind.name(),
+            format_bytes(kind.size_bytes),
+            percent_of(kind.size_bytes, report.total_size_bytes),
+            style(gzip).dim()
+        )?;
+    }
+
+    writeln!(output, "\n{}", style("FILES:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    let shown = limit.visible(report.files.len(), Some(DEFAULT_TOP_FILES));
+    for file in report.files.iter().take(shown) {
+        writeln!(
+            output,
+            "  {:>12}  {}",
+            format_bytes(file.size_bytes),
+            file.path.display()
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.files.len(), shown, "files") {
+        writeln!(output, "\n  {}", style(footer).dim())?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::package::{KindTotal, PackageFile, PackageFileKind};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_console_report_shows_kinds_and_files() {
+        let file = |path: &str, kind, size_bytes| PackageFile {
+            path: PathBuf::from(path),
+            kind,
+            size_bytes,
+            gzip_bytes: None,
+        };
+        let report = PackageReport {
+            dir: PathBuf::from("pkg"),
+            by_kind: vec![
+                KindTotal {
+                    kind: PackageFileKind::Wasm,
+                    files: 1,
+                    size_bytes: 3072,
+                    gzip_bytes: Some(1024),
+                },
+                KindTotal {
+                    kind: PackageFileKind::Glue,
+                    files: 1,
+                    size_bytes: 1024,
+                    gzip_bytes: None,
+                },
+            ],
+            files: vec![
+                file("app_bg.wasm", PackageFileKind::Wasm, 3072),
+                file("app.js", PackageFileKind::Glue, 1024),
+            ],
+            total_size_bytes: 4096,
+            total_gzip_bytes: None,
+        };
+
+        let text = format_console_report_with_limit(&report, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("pkg: 4.00 KiB in 2 files"));
+        assert!(text.contains("75.0%"));
+        assert!(text.contains("1.00 KiB gzipped"));
+        assert!(text.contains("app_bg.wasm"));
+        assert!(text.contains("1 more files omitted"));
+    }
+}
@@ -1,35 +1,37 @@
 //! Recommendation engine for panic pattern optimization
 
-use super::panics::{PanicPattern, PanicResults};
+use super::panics::{DetectedPanic, FilePanics, PanicPattern, PanicResults};
+use crate::fmt::format_bytes;
 
 /// Generate actionable recommendations based on panic detection results
 pub fn generate_recommendations(
     total: usize,
     by_pattern: &[(PanicPattern, usize)],
-    size_kb: u64,
+    size_bytes: u64,
 ) -> Vec<String> {
+    let size = format_bytes(size_bytes);
     let mut recs = Vec::new();
 
     // Overall assessment
     if total > 100 {
         recs.push(format!(
-            "[P0] Critical: {} panic sites detected (~{} KB). Significant WASM bloat.",
-            total, size_kb
+            "[P0] Critical: {} panic sites detected (~{}). Significant WASM bloat.",
+            total, size
         ));
     } else if total > 50 {
         recs.push(format!(
-            "[P1] High: {} panic sites detected (~{} KB). Consider refactoring hot paths.",
-            total, size_kb
+            "[P1] High: {} panic sites detected (~{}). Consider refactoring hot paths.",
+            total, size
         ));
     } else if total > 10 {
         recs.push(format!(
-            "[P2] Moderate: {} panic sites detected (~{} KB). Optimize critical sections.",
-            total, size_kb
+            "[P2] Moderate: {} panic sites detected (~{}). Optimize critical sections.",
+            total, size
         ));
     } else {
         recs.push(format!(
-            "[P3] Low: {} panic sites detected (~{} KB). Well optimized!",
-            total, size_kb
+            "[P3] Low: {} panic sites detected (~{}). Well optimized!",
+            total, size
         ));
         return recs; // No need for detailed recommendations
     }
@@ -37,13 +39,13 @@ pub fn generate_recommendations(
     // Top offenders
     for (pattern, count) in by_pattern.iter().take(3) {
         if *count > 10 {
-            let savings = (pattern.size_per_occurrence() * (*count as u64)) / 1024;
+            let savings = pattern.size_per_occurrence() * (*count as u64);
             recs.push(format!(
-                "  → Replace {} {} calls with {} (save ~{} KB)",
+                "  → Replace {} {} calls with {} (save ~{})",
                 count,
                 pattern.name(),
                 pattern.alternative(),
-                savings
+                format_bytes(savings)
             ));
         }
     }
@@ -69,14 +71,44 @@ pub fn generate_recommendations(
     recs
 }
 
+/// Recommendations for the panic strings found in a WASM binary
+///
+/// Takes the bytes of panic messages and of panic source paths found by the
+/// string analysis of the data segments.
+pub fn string_recommendations(message_bytes: u64, path_bytes: u64) -> Vec<String> {
+    let total = message_bytes + path_bytes;
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let mut recs = Vec::new();
+    let priority = if total >= 10 * 1024 { "P1" } else { "P2" };
+    recs.push(format!(
+        "[{}] ~{} of panic messages and source paths embedded in the data segments",
+        priority,
+        format_bytes(total)
+    ));
+    if path_bytes > 0 {
+        recs.push(format!(
+            "  → Build with -Zlocation-detail=none (nightly) to drop panic source paths (save ~{})",
+            format_bytes(path_bytes)
+        ));
+    }
+    if message_bytes > 0 {
+        recs.push(format!(
+            "  → Use panic = \"abort\" with -Zbuild-std-features=panic_immediate_abort to drop panic messages (save ~{})",
+            format_bytes(message_bytes)
+        ));
+    }
+    recs
+}
+
 /// Build complete panic results with recommendations
-pub fn build_results(
-    panic_sites: Vec<super::panics::DetectedPanic>,
-) -> super::panics::PanicResults {
+pub fn build_results(panic_sites: Vec<DetectedPanic>) -> PanicResults {
     let total_panics = panic_sites.len();
 
     // Count by pattern type
-    let mut pattern_counts = std::collections::HashMap::new();
+    let mut pattern_counts = std::collections::BTreeMap::new();
     let mut total_size: u64 = 0;
 
     for panic in &panic_sites {
@@ -85,18 +117,40 @@ pub fn build_results(
     }
 
     let mut by_pattern: Vec<(PanicPattern, usize)> = pattern_counts.into_iter().collect();
-    by_pattern.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by count descending
-
-    let estimated_size_kb = total_size / 1024;
+    // Sort by count descending; the stable sort keeps pattern order for ties
+    by_pattern.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
 
     // Generate recommendations
-    let recommendations = generate_recommendations(total_panics, &by_pattern, estimated_size_kb);
+    let recommendations = generate_recommendations(total_panics, &by_pattern, total_size);
+    let by_file = file_summaries(&panic_sites);
 
     PanicResults {
         total_panics,
         by_pattern,
         panic_sites,
-        estimated_size_kb,
+        by_file,
+        overflow_checks: false,
+        estimated_size_bytes: total_size,
         recommendations,
+        warnings: Vec::new(),
+    }
+}
+
+/// Panic site count and estimated size per file, most costly first
+fn file_summaries(panic_sites: &[DetectedPanic]) -> Vec<FilePanics> {
+    let mut files: std::collections::BTreeMap<&std::path::Path, FilePanics> =
+        std::collections::BTreeMap::new();
+    for panic in panic_sites {
+        let summary = files.entry(&panic.file).or_insert_with(|| FilePanics {
+            file: panic.file.clone(),
+            count: 0,
+            estimated_size_bytes: 0,
+        });
+        summary.count += 1;
+        summary.estimated_size_bytes += panic.pattern.size_per_occurrence();
     }
+    let mut files: Vec<FilePanics> = files.into_values().collect();
+    // Stable sort: files with equal sizes stay in path order
+    files.sort_by_key(|f| std::cmp::Reverse(f.estimated_size_bytes));
+    files
 }
@@ -0,0 +1,481 @@
+//! Machine-applicable fixes for common panic sites
+//!
+//! `analyze --mode panics --patch` turns the `unwrap()` calls whose rewrite
+//! can be inferred from the surrounding code into a patch:
+//!
+//! - `.unwrap()` becomes `?` when the enclosing function returns `Option`
+//!   and the receiver is an `Option`-returning call (`get`, `first`, `pop`,
+//!   `strip_prefix`, ...), or it returns `Result` and the receiver is a
+//!   `Result`-returning call (`parse`, `try_into`, `fs::read_to_string`, ...).
+//! - `.unwrap()` becomes `.unwrap_or_default()` when the value has a type
+//!   known to implement `Default`: a typed `let` (`let n: u32 = ...`) or a
+//!   turbofish (`parse::<u32>()`).
+//!
+//! There is no type checker behind this, only names, so the patch is written
+//! to the state directory for review rather than applied. Tests, closures
+//! and async blocks are left alone.
+
+use super::panics::{PanicPattern, PanicResults};
+use super::source_patch::unified_diff;
+use crate::infra::{FileSystem, RealFileSystem};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{Expr, ExprMethodCall, GenericArgument, ReturnType, Type};
+
+/// Patch with the panic fixes, inside the state directory
+pub const PANIC_FIXES_PATCH_FILE: &str = "panic-fixes.patch";
+
+/// Methods returning `Option`
+const OPTION_METHODS: &[&str] = &[
+    "get",
+    "get_mut",
+    "first",
+    "last",
+    "pop",
+    "next",
+    "next_back",
+    "nth",
+    "find",
+    "find_map",
+    "position",
+    "max",
+    "min",
+    "max_by_key",
+    "min_by_key",
+    "checked_add",
+    "checked_sub",
+    "checked_mul",
+    "checked_div",
+    "strip_prefix",
+    "strip_suffix",
+    "split_once",
+    "rsplit_once",
+    "parent",
+    "file_name",
+    "file_stem",
+    "extension",
+    "to_str",
+];
+
+/// Methods and functions returning `Result`
+const RESULT_METHODS: &[&str] = &[
+    "parse",
+    "try_into",
+    "try_from",
+    "from_str",
+    "from_utf8",
+    "read_to_string",
+    "read_to_end",
+    "write_all",
+    "flush",
+    "metadata",
+    "canonicalize",
+    "create_dir_all",
+    "remove_file",
+];
+
+/// Types implementing `Default`
+const DEFAULT_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32",
+    "f64", "bool", "char", "String", "Vec", "VecDeque", "HashMap", "HashSet", "BTreeMap",
+    "BTreeSet", "Option",
+];
+
+/// How a panic site is rewritten
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PanicFixKind {
+    /// `.unwrap()` → `?`
+    Propagate,
+    /// `.unwrap()` → `.unwrap_or_default()`
+    UnwrapOrDefault,
+}
+
+impl PanicFixKind {
+    /// Replacement for `.unwrap()`
+    fn replacement(&self) -> &'static str {
+        match self {
+            PanicFixKind::Propagate => "?",
+            PanicFixKind::UnwrapOrDefault => ".unwrap_or_default()",
+        }
+    }
+
+    /// Short description
+    pub fn description(&self) -> &'static str {
+        match self {
+            PanicFixKind::Propagate => "unwrap() → ?",
+            PanicFixKind::UnwrapOrDefault => "unwrap() → unwrap_or_default()",
+        }
+    }
+}
+
+/// One rewritten panic site
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PanicFix {
+    /// Source file
+    pub file: PathBuf,
+    /// Line (1-based)
+    pub line: usize,
+    /// Rewrite applied
+    pub kind: PanicFixKind,
+}
+
+/// What `--patch` writes
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PanicFixPlan {
+    /// Sites the patch rewrites
+    pub fixes: Vec<PanicFix>,
+    /// Unified diff with the rewrites (empty when nothing was fixable)
+    pub patch: String,
+}
+
+impl PanicFixPlan {
+    /// Panic infrastructure removed once the patch is applied, in bytes
+    pub fn estimated_savings_bytes(&self) -> u64 {
+        self.fixes.len() as u64 * PanicPattern::Unwrap.size_per_occurrence()
+    }
+}
+
+/// Plans and writes panic fixes
+pub struct PanicFixer<FS: FileSystem = RealFileSystem> {
+    project_root: PathBuf,
+    fs: FS,
+}
+
+impl PanicFixer<RealFileSystem> {
+    /// Create a fixer for the given project root
+    pub fn new(project_root: impl Into<PathBuf>) -> Self {
+        Self::with_fs(project_root, RealFileSystem)
+    }
+}
+
+impl<FS: FileSystem> PanicFixer<FS> {
+    /// Create a fixer with a custom filesystem implementation
+    pub fn with_fs(project_root: impl Into<PathBuf>, fs: FS) -> Self {
+        Self {
+            project_root: project_root.into(),
+            fs,
+        }
+    }
+
+    /// Find the fixable `unwrap()` sites among the detected panics
+    ///
+    /// Files that no longer parse are skipped.
+    pub fn plan(&self, results: &PanicResults) -> Result<PanicFixPlan> {
+        let files: BTreeSet<&Path> = results
+            .panic_sites
+            .iter()
+            .filter(|p| p.pattern == PanicPattern::Unwrap)
+            .map(|p| p.file.as_path())
+            .collect();
+
+        let mut plan = PanicFixPlan::default();
+        for file in files {
+            let content = self
+                .fs
+                .read_to_string(file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let Ok(syntax_tree) = syn::parse_file(&content) else {
+                continue;
+            };
+            let mut visitor = FixVisitor::default();
+            visitor.visit_file(&syntax_tree);
+
+            let lines: Vec<&str> = content.lines().collect();
+            let mut edits: BTreeMap<usize, Vec<(usize, PanicFixKind)>> = BTreeMap::new();
+            for (line, column, kind) in visitor.fixes {
+                edits.entry(line - 1).or_default().push((column, kind));
+            }
+            let mut replacements = BTreeMap::new();
+            for (index, mut line_edits) in edits {
+                let Some(line) = lines.get(index) else {
+                    continue;
+                };
+                // Rewrite right to left so earlier columns stay valid
+                line_edits.sort_by_key(|(column, _)| std::cmp::Reverse(*column));
+                let mut chars: Vec<char> = line.chars().collect();
+                for (column, kind) in line_edits {
+                    let end = column + ".unwrap()".len();
+                    if chars.get(column..end).map(|c| c.iter().collect::<String>())
+                        != Some(".unwrap()".to_string())
+                    {
+                        continue;
+                    }
+                    chars.splice(column..end, kind.replacement().chars());
+                    plan.fixes.push(PanicFix {
+                        file: file.to_path_buf(),
+                        line: index + 1,
+                        kind,
+                    });
+                }
+                let rewritten: String = chars.into_iter().collect();
+                if rewritten != *line {
+                    replacements.insert(index, vec![rewritten]);
+                }
+            }
+
+            let display = file
+                .strip_prefix(&self.project_root)
+                .unwrap_or(file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            plan.patch
+                .push_str(&unified_diff(&display, &lines, &replacements));
+        }
+        plan.fixes
+            .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        Ok(plan)
+    }
+
+    /// Write the patch into the state directory, returning its path
+    pub fn write(&self, plan: &PanicFixPlan) -> Result<PathBuf> {
+        let patch_path = crate::state::state_dir(&self.project_root).join(PANIC_FIXES_PATCH_FILE);
+        if let Some(parent) = patch_path.parent() {
+            self.fs
+                .create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        self.fs
+            .write(&patch_path, &plan.patch)
+            .with_context(|| format!("Failed to write {}", patch_path.display()))?;
+        Ok(patch_path)
+    }
+}
+
+/// What the enclosing function returns
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Returns {
+    Option,
+    Result,
+    Other,
+}
+
+/// Collects fixable `.unwrap()` calls as (line, char column of the `.`, kind)
+#[derive(Default)]
+struct FixVisitor {
+    returns: Vec<Returns>,
+    /// Positions of unwraps whose value is bound with a `Default` type
+    typed_default: HashSet<(usize, usize)>,
+    fixes: Vec<(usize, usize, PanicFixKind)>,
+}
+
+impl FixVisitor {
+    fn visit_fn_body(&mut self, output: &ReturnType, visit: impl FnOnce(&mut Self)) {
+        let returns = match output {
+            ReturnType::Type(_, ty) => match last_segment(ty).as_deref() {
+                Some("Option") => Returns::Option,
+                Some("Result") => Returns::Result,
+                _ => Returns::Other,
+            },
+            ReturnType::Default => Returns::Other,
+        };
+        self.returns.push(returns);
+        visit(self);
+        self.returns.pop();
+    }
+}
+
+impl<'ast> Visit<'ast> for FixVisitor {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if is_test(&node.attrs) {
+            return;
+        }
+        self.visit_fn_body(&node.sig.output, |v| syn::visit::visit_item_fn(v, node));
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        if is_test(&node.attrs) {
+            return;
+        }
+        self.visit_fn_body(&node.sig.output, |v| {
+            syn::visit::visit_impl_item_fn(v, node)
+        });
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if !is_test(&node.attrs) {
+            syn::visit::visit_item_mod(self, node);
+        }
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast syn::ExprClosure) {
+        self.returns.push(Returns::Other);
+        syn::visit::visit_expr_closure(self, node);
+        self.returns.pop();
+    }
+
+    fn visit_expr_async(&mut self, node: &'ast syn::ExprAsync) {
+        self.returns.push(Returns::Other);
+        syn::visit::visit_expr_async(self, node);
+        self.returns.pop();
+    }
+
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        if let (syn::Pat::Type(pat), Some(init)) = (&node.pat, &node.init) {
+            if let Expr::MethodCall(call) = init.expr.as_ref() {
+                if call.method == "unwrap" && is_default_type(&pat.ty) {
+                    let start = call.dot_token.span.start();
+                    self.typed_default.insert((start.line, start.column));
+                }
+            }
+        }
+        syn::visit::visit_local(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        if node.method == "unwrap" && node.args.is_empty() {
+            let start = node.dot_token.span.start();
+            let end = node.paren_token.span.close().end();
+            let returns = self.returns.last().copied().unwrap_or(Returns::Other);
+            let receiver = receiver_returns(&node.receiver);
+            let kind = if returns != Returns::Other && receiver == returns {
+                Some(PanicFixKind::Propagate)
+            } else if self.typed_default.contains(&(start.line, start.column))
+                || parses_into_default(&node.receiver)
+            {
+                Some(PanicFixKind::UnwrapOrDefault)
+            } else {
+                None
+            };
+            if let Some(kind) = kind {
+                if start.line == end.line && node.span().end().line == end.line {
+                    self.fixes.push((start.line, start.column, kind));
+                }
+            }
+        }
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
+
+/// Whether the attributes mark test-only code
+fn is_test(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("test")
+            || (attr.path().is_ident("cfg")
+                && attr
+                    .parse_args::<syn::Ident>()
+                    .is_ok_and(|ident| ident == "test"))
+    })
+}
+
+/// What the call producing an unwrapped value returns, judged by its name
+fn receiver_returns(expr: &Expr) -> Returns {
+    let name = match expr {
+        Expr::MethodCall(call) => call.method.to_string(),
+        Expr::Call(call) => match call.func.as_ref() {
+            Expr::Path(path) => match path.path.segments.last() {
+                Some(segment) => segment.ident.to_string(),
+                None => return Returns::Other,
+            },
+            _ => return Returns::Other,
+        },
+        _ => return Returns::Other,
+    };
+    if OPTION_METHODS.contains(&name.as_str()) {
+        Returns::Option
+    } else if RESULT_METHODS.contains(&name.as_str()) {
+        Returns::Result
+    } else {
+        Returns::Other
+    }
+}
+
+/// `.parse::<T>()` with `T: Default`
+fn parses_into_default(expr: &Expr) -> bool {
+    let Expr::MethodCall(call) = expr else {
+        return false;
+    };
+    call.method == "parse"
+        && call.turbofish.as_ref().is_some_and(|turbofish| {
+            turbofish.args.iter().any(|arg| match arg {
+                GenericArgument::Type(ty) => is_default_type(ty),
+                _ => false,
+            })
+        })
+}
+
+/// Whether a type is one of the known `Default` types
+fn is_default_type(ty: &Type) -> bool {
+    last_segment(ty).is_some_and(|name| DEFAULT_TYPES.contains(&name.as_str()))
+}
+
+/// Last path segment of a type (`Result` for `io::Result<T>`)
+fn last_segment(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::PanicDetector;
+
+    #[test]
+    fn test_plan_rewrites_inferable_unwraps_only() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(
+            src.join("lib.rs"),
+            r#"fn first(v: &[u8]) -> Option<u8> {
+    let x = *v.first().unwrap();
+    Some(x)
+}
+
+fn port(s: &str) -> Result<u16, std::num::ParseIntError> {
+    let p = s.parse::<u16>().unwrap();
+    let unknown = lookup(s).unwrap();
+    let cb = || s.parse::<u8>().unwrap();
+    Ok(p)
+}
+
+fn count(s: &str) -> u32 {
+    let n: u32 = s.trim().parse().unwrap();
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    fn t() -> Option<u8> {
+        [1u8].first().copied().unwrap();
+        None
+    }
+}
+"#,
+        )
+        .unwrap();
+        let results = PanicDetector::new(temp.path()).scan_project().unwrap();
+
+        let plan = PanicFixer::new(temp.path()).plan(&results).unwrap();
+
+        let kinds: Vec<(usize, PanicFixKind)> =
+            plan.fixes.iter().map(|f| (f.line, f.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (2, PanicFixKind::Propagate),
+                (7, PanicFixKind::Propagate),
+                (9, PanicFixKind::UnwrapOrDefault),
+                (14, PanicFixKind::UnwrapOrDefault),
+            ]
+        );
+        assert!(plan
+            .patch
+            .starts_with("--- a/src/lib.rs\n+++ b/src/lib.rs\n"));
+        assert!(plan.patch.contains("+    let x = *v.first()?;\n"));
+        assert!(plan.patch.contains("+    let p = s.parse::<u16>()?;\n"));
+        assert!(plan
+            .patch
+            .contains("+    let n: u32 = s.trim().parse().unwrap_or_default();\n"));
+        assert!(plan
+            .patch
+            .contains("     let unknown = lookup(s).unwrap();\n"));
+        assert_eq!(plan.estimated_savings_bytes(), 4 * 800);
+    }
+}
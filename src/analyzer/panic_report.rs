@@ -2,12 +2,23 @@
 //!
 //! Provides console output formatters for panic detection results.
 
-use crate::analyzer::panics::PanicResults;
+use crate::analyzer::panic_fixes::PanicFixPlan;
+use crate::analyzer::panics::{DetectedPanic, PanicResults};
+use crate::analyzer::report_utils::{format_bytes, omitted_footer, ItemLimit};
 use console::style;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Number of files listed by default under "top files"
+const DEFAULT_TOP_FILES: usize = 10;
 
 /// Print panic analysis report to console
 pub fn print_panic_report(results: &PanicResults) {
+    print_panic_report_with_limit(results, ItemLimit::Default);
+}
+
+/// Print panic analysis report, limiting the files listed
+pub fn print_panic_report_with_limit(results: &PanicResults, limit: ItemLimit) {
     println!();
     println!("{}", style("Panic Pattern Analysis").bold().underlined());
     println!();
@@ -19,9 +30,11 @@ pub fn print_panic_report(results: &PanicResults) {
         style(results.total_panics).cyan().bold()
     );
     println!(
-        "📦 {} ~{} KB (estimated impact on WASM size)",
+        "📦 {} ~{} (estimated impact on WASM size)",
         style("Size Impact:").bold(),
-        style(results.estimated_size_kb).yellow().bold()
+        style(format_bytes(results.estimated_size_bytes))
+            .yellow()
+            .bold()
     );
     println!();
 
@@ -32,13 +45,13 @@ pub fn print_panic_report(results: &PanicResults) {
         println!();
 
         for (pattern, count) in &results.by_pattern {
-            let size_kb = (pattern.size_per_occurrence() * (*count as u64)) / 1024;
+            let size_bytes = pattern.size_per_occurrence() * (*count as u64);
 
             println!(
-                "  {} {:>4} occurrences (~{} KB)",
+                "  {} {:>4} occurrences (~{})",
                 style(format!("{:20}", pattern.name())).cyan(),
                 style(count).bold(),
-                style(size_kb).yellow()
+                style(format_bytes(size_bytes)).yellow()
             );
             println!(
                 "       {} Use {}",
@@ -59,9 +72,11 @@ pub fn print_panic_report(results: &PanicResults) {
         println!("{}", style("─".repeat(70)).dim());
 
         let mut sorted_files: Vec<_> = files_with_panics.iter().collect();
+        // Stable sort: files with equal counts stay in path order
         sorted_files.sort_by(|a, b| b.1.cmp(a.1));
 
-        for (file, count) in sorted_files.iter().take(10) {
+        let shown = limit.visible(sorted_files.len(), Some(DEFAULT_TOP_FILES));
+        for (file, count) in sorted_files.iter().take(shown) {
             println!(
                 "  {:>4}  {}",
                 style(count).yellow().bold(),
@@ -69,12 +84,8 @@ pub fn print_panic_report(results: &PanicResults) {
             );
         }
 
-        if files_with_panics.len() > 10 {
-            println!(
-                "\n      {} {} more files...",
-                style("...").dim(),
-                files_with_panics.len() - 10
-            );
+        if let Some(footer) = omitted_footer(sorted_files.len(), shown, "files") {
+            println!("\n      {}", style(footer).dim());
         }
 
         println!();
@@ -119,6 +130,16 @@ pub fn print_panic_report(results: &PanicResults) {
         println!();
     }
 
+    // Files the scan skipped or read lossily
+    if !results.warnings.is_empty() {
+        println!("{}", style("WARNINGS:").bold().yellow());
+        println!("{}", style("─".repeat(70)).dim());
+        for warning in &results.warnings {
+            println!("  {} {}", style("⚠️").bold(), style(warning).yellow());
+        }
+        println!();
+    }
+
     // Footer
     println!(
         "{}",
@@ -127,18 +148,119 @@ pub fn print_panic_report(results: &PanicResults) {
     println!();
 }
 
+/// Print every panic site, grouped by file (most costly file first)
+///
+/// `limit` caps the number of files listed; all sites of a listed file are
+/// shown.
+pub fn print_panic_sites(results: &PanicResults, limit: ItemLimit) {
+    println!("{}", style("PANIC SITES BY FILE:").bold());
+    println!("{}", style("─".repeat(70)).dim());
+    if !results.overflow_checks {
+        println!(
+            "  {}",
+            style("overflow-checks is off in the release profile; arithmetic is not counted").dim()
+        );
+    }
+    println!();
+
+    let mut sites: BTreeMap<&Path, Vec<&DetectedPanic>> = BTreeMap::new();
+    for panic in &results.panic_sites {
+        sites.entry(&panic.file).or_default().push(panic);
+    }
+
+    let shown = limit.visible(results.by_file.len(), Some(DEFAULT_TOP_FILES));
+    for summary in results.by_file.iter().take(shown) {
+        println!(
+            "  {}  {} sites (~{})",
+            style(summary.file.display()).cyan().bold(),
+            style(summary.count).bold(),
+            style(format_bytes(summary.estimated_size_bytes)).yellow()
+        );
+        let mut file_sites = sites.remove(summary.file.as_path()).unwrap_or_default();
+        file_sites.sort_by_key(|p| p.line);
+        for panic in file_sites {
+            let line = if panic.line > 0 {
+                panic.line.to_string()
+            } else {
+                "?".to_string()
+            };
+            println!(
+                "    {:>5}  {:20} {}",
+                style(line).dim(),
+                panic.pattern.name(),
+                style(panic.snippet.as_deref().unwrap_or("")).dim()
+            );
+        }
+        println!();
+    }
+    if let Some(footer) = omitted_footer(results.by_file.len(), shown, "files") {
+        println!("  {}", style(footer).dim());
+        println!();
+    }
+    println!("{}", style("─".repeat(70)).dim());
+    println!();
+}
+
+/// Print what `--patch` wrote (or would write)
+///
+/// `patch_path` is where the patch was written; without it (dry run) the
+/// patch is printed instead.
+pub fn print_panic_fixes(plan: &PanicFixPlan, patch_path: Option<&Path>) {
+    println!("{}", style("MACHINE-APPLICABLE FIXES:").bold());
+    println!("{}", style("─".repeat(70)).dim());
+
+    if plan.fixes.is_empty() {
+        println!("  No unwrap() call has a rewrite that can be inferred from its context.");
+        println!();
+        return;
+    }
+
+    let mut by_kind = BTreeMap::new();
+    for fix in &plan.fixes {
+        *by_kind.entry(fix.kind).or_insert(0usize) += 1;
+    }
+    for (kind, count) in by_kind {
+        println!(
+            "  {:>4}  {}",
+            style(count).yellow().bold(),
+            style(kind.description()).cyan()
+        );
+    }
+    println!(
+        "  {} ~{} of panic infrastructure once applied",
+        style("→").dim(),
+        style(format_bytes(plan.estimated_savings_bytes()))
+            .green()
+            .bold()
+    );
+    println!();
+
+    match patch_path {
+        Some(path) => {
+            println!("  Patch written to {}", style(path.display()).cyan());
+            println!(
+                "  Review it (the rewrites are inferred from names, not types), then run {}",
+                style(format!("git apply {}", path.display())).bold()
+            );
+        }
+        None => {
+            println!("{}", style("  Patch (dry run, not written):").bold());
+            print!("{}", plan.patch);
+        }
+    }
+    println!();
+}
+
 /// Print panic analysis report as JSON
 pub fn print_json_report(results: &PanicResults) -> anyhow::Result<()> {
-    let json = serde_json::to_string_pretty(results)?;
+    let json = crate::redact::to_json_pretty(results)?;
     println!("{}", json);
     Ok(())
 }
 
 /// Group panic sites by file
-fn group_by_file(
-    panics: &[crate::analyzer::panics::DetectedPanic],
-) -> HashMap<std::path::PathBuf, usize> {
-    let mut map = HashMap::new();
+fn group_by_file(panics: &[DetectedPanic]) -> BTreeMap<std::path::PathBuf, usize> {
+    let mut map = BTreeMap::new();
 
     for panic in panics {
         *map.entry(panic.file.clone()).or_insert(0) += 1;
@@ -150,7 +272,7 @@ fn group_by_file(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::analyzer::panics::{DetectedPanic, PanicPattern};
+    use crate::analyzer::panics::PanicPattern;
     use std::path::PathBuf;
 
     #[test]
@@ -5,12 +5,15 @@
 //!
 //! Based on [Rust WASM book](https://rustwasm.github.io/docs/book/reference/code-size.html#avoid-panicking)
 
+use super::report_utils::SizedEntry;
 use crate::infra::{FileSystem, RealFileSystem};
+use crate::profiling::{self, ProfilePhase};
 use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use syn::{visit::Visit, BinOp, ExprBinary, ExprIndex, ExprMethodCall};
+use syn::spanned::Spanned;
+use syn::{visit::Visit, BinOp, Expr, ExprBinary, ExprIndex, ExprLit, ExprMethodCall};
 use thiserror::Error;
 
 /// Errors that can occur during panic detection
@@ -30,7 +33,7 @@ pub enum PanicDetectionError {
 }
 
 /// Type of panic pattern detected
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PanicPattern {
     /// .unwrap() call
     Unwrap,
@@ -44,6 +47,8 @@ pub enum PanicPattern {
     PanicMacro,
     /// assert!() macro (in release builds)
     AssertMacro,
+    /// Arithmetic + - * (only with `overflow-checks` in the release profile)
+    Overflow,
 }
 
 impl PanicPattern {
@@ -56,6 +61,7 @@ impl PanicPattern {
             PanicPattern::Division => "division operator",
             PanicPattern::PanicMacro => "panic!()",
             PanicPattern::AssertMacro => "assert!()",
+            PanicPattern::Overflow => "arithmetic overflow",
         }
     }
 
@@ -67,6 +73,7 @@ impl PanicPattern {
             PanicPattern::Division => ".checked_div() or .checked_rem()",
             PanicPattern::PanicMacro => "Result<T, E> or Option<T>",
             PanicPattern::AssertMacro => "debug_assert!() or runtime checks",
+            PanicPattern::Overflow => ".wrapping_*(), .checked_*() or .saturating_*()",
         }
     }
 
@@ -79,6 +86,7 @@ impl PanicPattern {
             PanicPattern::Division => 600,
             PanicPattern::PanicMacro => 1500,
             PanicPattern::AssertMacro => 1000,
+            PanicPattern::Overflow => 300, // Shares the panic machinery already linked in
         }
     }
 }
@@ -96,6 +104,16 @@ pub struct DetectedPanic {
     pub snippet: Option<String>,
 }
 
+impl SizedEntry for DetectedPanic {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        self.file.to_str().unwrap_or_default()
+    }
+}
+
 /// Complete panic analysis results
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PanicResults {
@@ -105,10 +123,40 @@ pub struct PanicResults {
     pub by_pattern: Vec<(PanicPattern, usize)>,
     /// All detected panic sites
     pub panic_sites: Vec<DetectedPanic>,
-    /// Estimated size impact in KB
-    pub estimated_size_kb: u64,
+    /// Panic sites grouped by file, most costly first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub by_file: Vec<FilePanics>,
+    /// Whether the release profile enables `overflow-checks` (arithmetic sites are counted)
+    #[serde(default)]
+    pub overflow_checks: bool,
+    /// Estimated size impact in bytes
+    pub estimated_size_bytes: u64,
     /// Recommendations
     pub recommendations: Vec<String>,
+    /// Files that were skipped or could only be read partially
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Panic sites in one source file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilePanics {
+    /// File path
+    pub file: PathBuf,
+    /// Number of panic sites
+    pub count: usize,
+    /// Estimated panic infrastructure the sites retain, in bytes
+    pub estimated_size_bytes: u64,
+}
+
+impl SizedEntry for FilePanics {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.estimated_size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        self.file.to_str().unwrap_or_default()
+    }
 }
 
 /// Panic pattern detector
@@ -135,32 +183,78 @@ impl<FS: FileSystem + Sync + Send> PanicDetector<FS> {
 
     /// Scan the project for panic patterns
     pub fn scan_project(&self) -> Result<PanicResults, PanicDetectionError> {
-        // Find all Rust source files
-        let rust_files = self.find_rust_files()?;
-
-        // Parallel scan of all files
-        let all_panics: Vec<DetectedPanic> = rust_files
+        let _span = profiling::span(ProfilePhase::Scanning, "panic scan");
+
+        // Find all Rust source files, sorted so results don't depend on
+        // directory iteration order
+        let mut warnings = Vec::new();
+        let mut rust_files = self.find_rust_files(&mut warnings);
+        rust_files.sort();
+        let overflow_checks = self.release_overflow_checks();
+
+        // Parallel scan of all files (collect preserves file order); a file
+        // that cannot be scanned is reported instead of failing the scan
+        let scanned: Vec<_> = rust_files
             .par_iter()
-            .flat_map(|source_file| {
-                self.scan_file(source_file).unwrap_or_else(|e| {
-                    eprintln!("Warning: Failed to scan {}: {}", source_file.display(), e);
-                    Vec::new()
-                })
-            })
+            .map(|source_file| (source_file, self.scan_file(source_file, overflow_checks)))
             .collect();
+        let mut all_panics = Vec::new();
+        for (source_file, scan) in scanned {
+            let display = source_file
+                .strip_prefix(&self.project_root)
+                .unwrap_or(source_file)
+                .display();
+            match scan {
+                Ok((panics, lossy)) => {
+                    if lossy {
+                        warnings.push(format!(
+                            "{} is not valid UTF-8; scanned with invalid bytes replaced",
+                            display
+                        ));
+                    }
+                    all_panics.extend(panics);
+                }
+                Err(e) => warnings.push(format!("Skipped {}: {}", display, e)),
+            }
+        }
 
         // Build results
-        self.build_results(all_panics)
+        let mut results = self.build_results(all_panics)?;
+        results.overflow_checks = overflow_checks;
+        results.warnings = warnings;
+        Ok(results)
     }
 
-    /// Scan a single source file
-    fn scan_file(&self, source_file: &Path) -> Result<Vec<DetectedPanic>, PanicDetectionError> {
-        let content = self.fs.read_to_string(source_file)?;
+    /// Whether `[profile.release]` turns on `overflow-checks`
+    ///
+    /// Without them, release arithmetic wraps silently and retains no panic
+    /// code, so `+`, `-` and `*` are only counted when they are on.
+    fn release_overflow_checks(&self) -> bool {
+        self.fs
+            .read_to_string(&self.project_root.join("Cargo.toml"))
+            .ok()
+            .and_then(|content| content.parse::<toml_edit::DocumentMut>().ok())
+            .and_then(|doc| {
+                doc.get("profile")?
+                    .get("release")?
+                    .get("overflow-checks")?
+                    .as_bool()
+            })
+            .unwrap_or(false)
+    }
+
+    /// Scan a single source file, also returning whether it was not valid UTF-8
+    fn scan_file(
+        &self,
+        source_file: &Path,
+        overflow_checks: bool,
+    ) -> Result<(Vec<DetectedPanic>, bool), PanicDetectionError> {
+        let (content, lossy) = self.fs.read_to_string_lossy(source_file)?;
 
         let mut panics = Vec::new();
 
         // Try AST parsing first (most reliable)
-        if let Ok(ast_panics) = self.scan_with_ast(&content, source_file) {
+        if let Ok(ast_panics) = self.scan_with_ast(&content, source_file, overflow_checks) {
             panics.extend(ast_panics);
         }
 
@@ -168,7 +262,7 @@ impl<FS: FileSystem + Sync + Send> PanicDetector<FS> {
         let regex_panics = self.scan_with_regex(&content, source_file)?;
         panics.extend(regex_panics);
 
-        Ok(panics)
+        Ok((panics, lossy))
     }
 
     /// Scan using AST parsing
@@ -176,15 +270,26 @@ impl<FS: FileSystem + Sync + Send> PanicDetector<FS> {
         &self,
         content: &str,
         source_file: &Path,
+        overflow_checks: bool,
     ) -> Result<Vec<DetectedPanic>, PanicDetectionError> {
         let syntax_tree: syn::File = syn::parse_str(content).map_err(|e| {
             PanicDetectionError::ParseError(source_file.to_path_buf(), e.to_string())
         })?;
 
-        let mut visitor = PanicVisitor::new(source_file);
+        let mut visitor = PanicVisitor::new(source_file, overflow_checks);
         visitor.visit_file(&syntax_tree);
 
-        Ok(visitor.panics)
+        // Line numbers come from spans; snippets from the source line
+        let lines: Vec<&str> = content.lines().collect();
+        let mut panics = visitor.panics;
+        for panic in &mut panics {
+            panic.snippet = panic
+                .line
+                .checked_sub(1)
+                .and_then(|i| lines.get(i))
+                .map(|line| line.trim().to_string());
+        }
+        Ok(panics)
     }
 
     /// Scan using regex patterns (fallback)
@@ -225,39 +330,44 @@ impl<FS: FileSystem + Sync + Send> PanicDetector<FS> {
     }
 
     /// Find all Rust source files in the project
-    fn find_rust_files(&self) -> Result<Vec<PathBuf>, PanicDetectionError> {
+    ///
+    /// Directories that cannot be read are skipped and noted in `warnings`.
+    fn find_rust_files(&self, warnings: &mut Vec<String>) -> Vec<PathBuf> {
         let mut rust_files = Vec::new();
 
         // Search in src/ and tests/ directories
         for dir_name in &["src", "tests", "benches", "examples"] {
             let dir_path = self.project_root.join(dir_name);
             if dir_path.exists() {
-                Self::collect_rust_files(&dir_path, &mut rust_files, &self.fs)?;
+                self.collect_rust_files(&dir_path, &mut rust_files, warnings);
             }
         }
 
-        Ok(rust_files)
+        rust_files
     }
 
     /// Recursively collect .rs files
-    fn collect_rust_files(
-        dir: &Path,
-        files: &mut Vec<PathBuf>,
-        fs: &FS,
-    ) -> Result<(), PanicDetectionError> {
-        if dir.is_dir() {
-            for entry in fs.read_dir(dir)? {
-                let entry = entry?;
-                let path = entry.path();
-
-                if path.is_dir() {
-                    Self::collect_rust_files(&path, files, fs)?;
-                } else if path.extension().and_then(|s| s.to_str()) == Some("rs") {
-                    files.push(path);
-                }
+    fn collect_rust_files(&self, dir: &Path, files: &mut Vec<PathBuf>, warnings: &mut Vec<String>) {
+        if !dir.is_dir() {
+            return;
+        }
+        let entries = match self.fs.read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                let display = dir.strip_prefix(&self.project_root).unwrap_or(dir);
+                warnings.push(format!("Skipped {}: {}", display.display(), e));
+                return;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.is_dir() {
+                self.collect_rust_files(&path, files, warnings);
+            } else if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+                files.push(path);
             }
         }
-        Ok(())
     }
 
     /// Build final results with statistics and recommendations
@@ -272,13 +382,15 @@ impl<FS: FileSystem + Sync + Send> PanicDetector<FS> {
 /// AST visitor for detecting panic patterns
 struct PanicVisitor<'a> {
     source_file: &'a Path,
+    overflow_checks: bool,
     panics: Vec<DetectedPanic>,
 }
 
 impl<'a> PanicVisitor<'a> {
-    fn new(source_file: &'a Path) -> Self {
+    fn new(source_file: &'a Path, overflow_checks: bool) -> Self {
         Self {
             source_file,
+            overflow_checks,
             panics: Vec::new(),
         }
     }
@@ -300,10 +412,10 @@ impl<'a> Visit<'a> for PanicVisitor<'a> {
 
         match method_name.as_str() {
             "unwrap" => {
-                self.add_panic(PanicPattern::Unwrap, 0);
+                self.add_panic(PanicPattern::Unwrap, node.method.span().start().line);
             }
             "expect" => {
-                self.add_panic(PanicPattern::Expect, 0);
+                self.add_panic(PanicPattern::Expect, node.method.span().start().line);
             }
             _ => {}
         }
@@ -314,9 +426,20 @@ impl<'a> Visit<'a> for PanicVisitor<'a> {
 
     /// Visit binary operations to detect division
     fn visit_expr_binary(&mut self, node: &'a ExprBinary) {
+        let line = node.op.span().start().line;
         match node.op {
             BinOp::Div(_) | BinOp::Rem(_) => {
-                self.add_panic(PanicPattern::Division, 0);
+                self.add_panic(PanicPattern::Division, line);
+            }
+            BinOp::Add(_)
+            | BinOp::Sub(_)
+            | BinOp::Mul(_)
+            | BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+                if self.overflow_checks && !(is_literal(&node.left) && is_literal(&node.right)) =>
+            {
+                self.add_panic(PanicPattern::Overflow, line);
             }
             _ => {}
         }
@@ -327,13 +450,21 @@ impl<'a> Visit<'a> for PanicVisitor<'a> {
 
     /// Visit index expressions to detect arr\[i\]
     fn visit_expr_index(&mut self, node: &'a ExprIndex) {
-        self.add_panic(PanicPattern::Index, 0);
+        self.add_panic(
+            PanicPattern::Index,
+            node.bracket_token.span.open().start().line,
+        );
 
         // Continue visiting children
         syn::visit::visit_expr_index(self, node);
     }
 }
 
+/// Whether an operand is a literal (constant arithmetic cannot overflow at runtime)
+fn is_literal(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(ExprLit { .. }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,7 +506,7 @@ mod tests {
             }
         "#;
 
-        let result = detector.scan_with_ast(code, Path::new("test.rs"));
+        let result = detector.scan_with_ast(code, Path::new("test.rs"), false);
         assert!(result.is_ok());
 
         let panics = result.unwrap();
@@ -393,7 +524,7 @@ mod tests {
             }
         "#;
 
-        let result = detector.scan_with_ast(code, Path::new("test.rs"));
+        let result = detector.scan_with_ast(code, Path::new("test.rs"), false);
         assert!(result.is_ok());
 
         let panics = result.unwrap();
@@ -411,7 +542,7 @@ mod tests {
             }
         "#;
 
-        let result = detector.scan_with_ast(code, Path::new("test.rs"));
+        let result = detector.scan_with_ast(code, Path::new("test.rs"), false);
         assert!(result.is_ok());
 
         let panics = result.unwrap();
@@ -429,7 +560,7 @@ mod tests {
             }
         "#;
 
-        let result = detector.scan_with_ast(code, Path::new("test.rs"));
+        let result = detector.scan_with_ast(code, Path::new("test.rs"), false);
         assert!(result.is_ok());
 
         let panics = result.unwrap();
@@ -479,7 +610,7 @@ mod tests {
         use crate::analyzer::panic_advisor::generate_recommendations;
         let by_pattern = vec![(PanicPattern::Unwrap, 150)];
 
-        let recs = generate_recommendations(150, &by_pattern, 120);
+        let recs = generate_recommendations(150, &by_pattern, 120 * 1024);
 
         assert!(!recs.is_empty());
         assert!(recs[0].contains("[P0]"));
@@ -491,10 +622,99 @@ mod tests {
         use crate::analyzer::panic_advisor::generate_recommendations;
         let by_pattern = vec![(PanicPattern::Unwrap, 5)];
 
-        let recs = generate_recommendations(5, &by_pattern, 4);
+        let recs = generate_recommendations(5, &by_pattern, 4 * 1024);
 
         assert!(!recs.is_empty());
         assert!(recs[0].contains("[P3]"));
         assert!(recs[0].contains("Low"));
     }
+
+    #[test]
+    fn test_scan_project_reads_non_utf8_files_lossily() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("lib.rs"), "fn ok() {}\n").unwrap();
+        std::fs::write(
+            src.join("fixture.rs"),
+            b"// caf\xe9\nfn f() { panic!(\"boom\"); }\n",
+        )
+        .unwrap();
+
+        let results = PanicDetector::new(temp.path()).scan_project().unwrap();
+
+        assert!(results
+            .panic_sites
+            .iter()
+            .any(|p| p.pattern == PanicPattern::PanicMacro));
+        assert_eq!(results.warnings.len(), 1);
+        assert!(results.warnings[0].starts_with("src/fixture.rs is not valid UTF-8"));
+    }
+
+    #[test]
+    fn test_scan_project_records_lines_and_overflow_sites() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("a.rs"), "fn a(v: &[u8]) -> u8 {\n    v[0]\n}\n").unwrap();
+        std::fs::write(
+            src.join("b.rs"),
+            "fn b(x: Option<u32>, y: u32) -> u32 {\n    let z = x.unwrap();\n    z + y + 1 + 2\n}\n",
+        )
+        .unwrap();
+
+        let results = PanicDetector::new(temp.path()).scan_project().unwrap();
+        assert!(!results.overflow_checks);
+        assert_eq!(results.total_panics, 2);
+        assert_eq!(results.by_file[0].file, src.join("a.rs"));
+        assert_eq!(results.by_file[0].estimated_size_bytes, 1000);
+        let unwrap = &results.panic_sites[1];
+        assert_eq!(unwrap.line, 2);
+        assert_eq!(unwrap.snippet.as_deref(), Some("let z = x.unwrap();"));
+
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"x\"\n\n[profile.release]\noverflow-checks = true\n",
+        )
+        .unwrap();
+        let results = PanicDetector::new(temp.path()).scan_project().unwrap();
+        assert!(results.overflow_checks);
+        let overflow: Vec<usize> = results
+            .panic_sites
+            .iter()
+            .filter(|p| p.pattern == PanicPattern::Overflow)
+            .map(|p| p.line)
+            .collect();
+        assert_eq!(overflow, vec![3, 3, 3]);
+    }
+
+    #[test]
+    fn test_build_results_orders_ties_by_pattern() {
+        use crate::analyzer::panic_advisor::build_results;
+        let site = |pattern: PanicPattern| DetectedPanic {
+            file: PathBuf::from("src/lib.rs"),
+            line: 1,
+            pattern,
+            snippet: None,
+        };
+        let sites = vec![
+            site(PanicPattern::Division),
+            site(PanicPattern::Index),
+            site(PanicPattern::Unwrap),
+            site(PanicPattern::Index),
+            site(PanicPattern::Expect),
+        ];
+
+        let results = build_results(sites);
+
+        assert_eq!(
+            results.by_pattern,
+            vec![
+                (PanicPattern::Index, 2),
+                (PanicPattern::Unwrap, 1),
+                (PanicPattern::Expect, 1),
+                (PanicPattern::Division, 1),
+            ]
+        );
+    }
 }
@@ -0,0 +1,247 @@
+//! Readiness checks for newer WebAssembly proposals
+//!
+//! Forward-looking guidance on codegen options that are not enabled by default
+//! but may shrink the binary for a given dependency profile.
+//!
+//! # Overview
+//!
+//! - **Tail calls**: `return_call` replaces call + return sequences in
+//!   recursion-heavy code (parsers, interpreters). Gains are usually small
+//!   (≈1-2%) and the output needs a runtime with tail-call support.
+//! - **Reference types / GC**: rustc compiles to linear memory, so the Wasm GC
+//!   proposal does not apply to Rust code. The nearest win for Rust projects is
+//!   wasm-bindgen's `--reference-types` mode, which replaces the JS heap-slab
+//!   glue with `externref` tables.
+//!
+//! Options already enabled in `.cargo/config.toml` are not reported again.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::proposals::ProposalAdvisor;
+//! use std::path::Path;
+//!
+//! let advisor = ProposalAdvisor::new(Path::new("."));
+//! for issue in advisor.check_proposals()? {
+//!     println!("{}: {}", issue.package, issue.suggestion);
+//! }
+//! # Ok::<(), wasm_slim::analyzer::deps::DependencyAnalysisError>(())
+//! ```
+
+use crate::profiling::{self, ProfilePhase};
+use cargo_metadata::MetadataCommand;
+use std::path::{Path, PathBuf};
+
+use super::deps::{DependencyAnalysisError, DependencyIssue, IssueSeverity};
+
+/// Minimum number of recursion-heavy dependencies before suggesting tail calls
+const TAIL_CALL_MIN_DEPS: usize = 2;
+
+/// Suggests newer Wasm proposals that fit the project's dependency profile
+pub struct ProposalAdvisor {
+    project_root: PathBuf,
+}
+
+impl ProposalAdvisor {
+    /// Create a new proposal advisor for the given project
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Check which proposals could benefit this project
+    pub fn check_proposals(&self) -> Result<Vec<DependencyIssue>, DependencyAnalysisError> {
+        let metadata = {
+            let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+            MetadataCommand::new()
+                .current_dir(&self.project_root)
+                .exec()?
+        };
+
+        Ok(self.check_proposals_with_metadata(&metadata))
+    }
+
+    /// Check proposals with provided metadata (for testing)
+    pub(crate) fn check_proposals_with_metadata(
+        &self,
+        metadata: &cargo_metadata::Metadata,
+    ) -> Vec<DependencyIssue> {
+        let cargo_config = self.read_cargo_config();
+        let mut issues = Vec::new();
+
+        let recursive: Vec<&str> = metadata
+            .packages
+            .iter()
+            .map(|p| p.name.as_str())
+            .filter(|name| Self::is_recursion_heavy(name))
+            .collect();
+
+        if recursive.len() >= TAIL_CALL_MIN_DEPS && !cargo_config.contains("+tail-call") {
+            issues.push(DependencyIssue {
+                package: "wasm-tail-call".to_string(),
+                version: "proposal".to_string(),
+                severity: IssueSeverity::Low,
+                issue: format!(
+                    "{} recursion-heavy dependencies ({}) could use tail calls instead of call + return sequences.",
+                    recursive.len(),
+                    recursive.join(", ")
+                ),
+                suggestion: "Forward-looking: enable the tail-call proposal:\n\n   # .cargo/config.toml\n   [target.wasm32-unknown-unknown]\n   rustflags = [\"-C\", \"target-feature=+tail-call\"]\n\n   and pass --enable-tail-call to wasm-opt.\n   Toolchain: a rustc that lists `tail-call` in\n   `rustc --print target-features --target wasm32-unknown-unknown`.\n   Runtime: Chrome 112+, Firefox 121+, Safari 18.2+.\n   Measure before adopting; typical gains are small.".to_string(),
+                size_impact_kb: None,
+                savings_percent: Some(1),
+                verify_id: None,
+            });
+        }
+
+        let uses_bindgen = metadata
+            .packages
+            .iter()
+            .any(|p| p.name.as_str() == "wasm-bindgen");
+        if uses_bindgen && !cargo_config.contains("reference-types") {
+            issues.push(DependencyIssue {
+                package: "wasm-reference-types".to_string(),
+                version: "proposal".to_string(),
+                severity: IssueSeverity::Low,
+                issue: "wasm-bindgen keeps JS objects in a heap slab with generated glue; the Wasm GC proposal does not apply to rustc's linear-memory codegen.".to_string(),
+                suggestion: "Forward-looking: use reference types instead of GC:\n\n   wasm-bindgen --reference-types ...\n\n   This stores JS values in externref tables and drops the heap-slab glue.\n   Toolchain: Rust 1.82+ (reference-types enabled by default for wasm32)\n   and a recent wasm-bindgen-cli.\n   Full Wasm GC only helps GC-targeting toolchains (e.g., Kotlin/Wasm, Dart).".to_string(),
+                size_impact_kb: None,
+                savings_percent: Some(1),
+                verify_id: None,
+            });
+        }
+
+        issues
+    }
+
+    /// Contents of the project's cargo config (empty if absent)
+    fn read_cargo_config(&self) -> String {
+        ["config.toml", "config"]
+            .iter()
+            .filter_map(|name| {
+                std::fs::read_to_string(self.project_root.join(".cargo").join(name)).ok()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Check if a crate is dominated by deep recursion or dispatch loops
+    ///
+    /// Parser combinators, recursive-descent parsers and interpreters make
+    /// heavy use of calls in tail position.
+    pub(crate) fn is_recursion_heavy(crate_name: &str) -> bool {
+        matches!(
+            crate_name,
+            // Parser combinators
+            "nom" | "winnow" | "combine" | "chumsky" |
+            // Grammar-driven parsers
+            "pest" | "lalrpop-util" | "tree-sitter" |
+            // Recursive-descent data formats
+            "serde_json" | "toml" | "toml_edit" | "ron" | "syn" |
+            // Interpreters and VMs
+            "rhai" | "boa_engine" | "wasmi" | "rlua"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cargo_metadata::Metadata;
+    use tempfile::TempDir;
+
+    fn metadata_with(names: &[&str]) -> Metadata {
+        let packages: Vec<_> = names
+            .iter()
+            .map(|name| {
+                serde_json::json!({
+                    "name": name,
+                    "version": "1.0.0",
+                    "id": format!("{} 1.0.0", name),
+                    "license": null,
+                    "license_file": null,
+                    "description": null,
+                    "source": null,
+                    "dependencies": [],
+                    "targets": [],
+                    "features": {},
+                    "manifest_path": "/fake/Cargo.toml",
+                    "metadata": null,
+                    "publish": null,
+                    "authors": [],
+                    "categories": [],
+                    "keywords": [],
+                    "readme": null,
+                    "repository": null,
+                    "homepage": null,
+                    "documentation": null,
+                    "edition": "2021",
+                    "links": null,
+                    "default_run": null,
+                    "rust_version": null
+                })
+            })
+            .collect();
+
+        serde_json::from_value(serde_json::json!({
+            "packages": packages,
+            "workspace_members": [],
+            "workspace_default_members": [],
+            "resolve": null,
+            "target_directory": "/fake/target",
+            "version": 1,
+            "workspace_root": "/fake",
+            "metadata": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_is_recursion_heavy_detects_known_crates() {
+        assert!(ProposalAdvisor::is_recursion_heavy("nom"));
+        assert!(ProposalAdvisor::is_recursion_heavy("serde_json"));
+        assert!(!ProposalAdvisor::is_recursion_heavy("base64"));
+    }
+
+    #[test]
+    fn test_suggests_tail_calls_for_recursion_heavy_profile() {
+        let temp = TempDir::new().unwrap();
+        let advisor = ProposalAdvisor::new(temp.path());
+
+        let issues = advisor.check_proposals_with_metadata(&metadata_with(&["nom", "serde_json"]));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].package, "wasm-tail-call");
+        assert!(issues[0].suggestion.contains("target-feature=+tail-call"));
+        assert_eq!(issues[0].severity, IssueSeverity::Low);
+
+        let issues = advisor.check_proposals_with_metadata(&metadata_with(&["nom", "base64"]));
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_suggests_reference_types_for_wasm_bindgen() {
+        let temp = TempDir::new().unwrap();
+        let advisor = ProposalAdvisor::new(temp.path());
+
+        let issues = advisor.check_proposals_with_metadata(&metadata_with(&["wasm-bindgen"]));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].package, "wasm-reference-types");
+        assert!(issues[0].suggestion.contains("--reference-types"));
+    }
+
+    #[test]
+    fn test_skips_options_already_enabled_in_cargo_config() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".cargo")).unwrap();
+        std::fs::write(
+            temp.path().join(".cargo/config.toml"),
+            "[target.wasm32-unknown-unknown]\nrustflags = [\"-C\", \"target-feature=+tail-call,+reference-types\"]\n",
+        )
+        .unwrap();
+        let advisor = ProposalAdvisor::new(temp.path());
+
+        let issues =
+            advisor.check_proposals_with_metadata(&metadata_with(&["nom", "syn", "wasm-bindgen"]));
+        assert!(issues.is_empty());
+    }
+}
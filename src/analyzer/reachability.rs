@@ -0,0 +1,396 @@
+//! Call-graph reachability from exports
+//!
+//! Walks the item graph of a WASM module (the one behind the native twiggy
+//! analysis) with its exports as roots. Given the exports the JavaScript side actually calls, every other
+//! exported function is dead, along with whatever only it reaches; those
+//! functions are reported as wasm-snip candidates and a wasm-metadce graph
+//! is derived that keeps just the live exports.
+//!
+//! Without a list of live exports, every export counts as live and the
+//! report shows, per export, the code only that export keeps alive.
+//!
+//! Edges are direct calls, `return_call`, `ref.func` and global accesses. An
+//! indirect call through a table reaches every function placed in that table
+//! by an active element segment. The start function, passive element
+//! segments, non-function exports (so functions in exported tables) and
+//! wasm-bindgen's own `__wbindgen_*`/`__wbg_*` exports, which the generated
+//! glue calls on the caller's behalf, are always roots.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::ReachabilityAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = ReachabilityAnalyzer::new()
+//!     .with_live_exports(vec!["greet".to_string()])
+//!     .analyze(Path::new("pkg/app_bg.wasm"))?;
+//! println!("{} bytes only reachable from unused exports", report.candidate_bytes);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use super::twiggy::native::{Export, ItemGraph};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// Export name prefixes of wasm-bindgen's glue, always treated as live
+const GLUE_EXPORT_PREFIXES: &[&str] = &["__wbindgen_", "__wbg_"];
+
+/// A function export and the code it alone keeps alive
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReachability {
+    /// Export name
+    pub name: String,
+    /// Index of the exported function
+    pub function_index: u32,
+    /// Whether the export is called (listed, wasm-bindgen glue, or no list given)
+    pub live: bool,
+    /// Functions reachable from no other export
+    pub exclusive_functions: usize,
+    /// Body size of those functions
+    pub exclusive_bytes: u64,
+}
+
+impl SizedEntry for ExportReachability {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.exclusive_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A function reachable only from exports that are never called
+#[derive(Debug, Clone, Serialize)]
+pub struct SnipCandidate {
+    /// Function index (imports included)
+    pub index: u32,
+    /// Demangled name, or `code[N]` without a name section
+    pub name: String,
+    /// Name as it appears in the name section, which wasm-snip matches against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Body size in bytes
+    pub size_bytes: u64,
+    /// Dead exports that reach the function
+    pub exports: Vec<String>,
+}
+
+impl SizedEntry for SnipCandidate {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// An export as wasm-metadce sees it
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadceExport {
+    /// Export name
+    pub name: String,
+    /// Whether it is kept
+    pub root: bool,
+}
+
+/// Reachability of a module's functions from its exports
+#[derive(Debug, Clone, Serialize)]
+pub struct ReachabilityReport {
+    /// Size of the code section
+    pub code_bytes: u64,
+    /// Number of defined functions
+    pub functions: usize,
+    /// Whether a list of live exports was given
+    pub live_exports_given: bool,
+    /// Function exports, largest exclusive size first
+    pub exports: Vec<ExportReachability>,
+    /// Listed live exports the module does not export
+    pub unknown_live_exports: Vec<String>,
+    /// Functions only reachable from dead exports, largest first
+    pub candidates: Vec<SnipCandidate>,
+    /// Total size of the candidates
+    pub candidate_bytes: u64,
+    /// Defined functions not reachable from any export or root
+    pub unreachable_functions: usize,
+    /// Body size of those functions
+    pub unreachable_bytes: u64,
+    /// Every export of the module, for the wasm-metadce graph
+    pub metadce_exports: Vec<MetadceExport>,
+}
+
+impl ReachabilityReport {
+    /// Exports that are never called
+    pub fn dead_exports(&self) -> impl Iterator<Item = &ExportReachability> {
+        self.exports.iter().filter(|export| !export.live)
+    }
+
+    /// wasm-snip arguments that snip every named candidate
+    ///
+    /// Candidates without a name section entry cannot be addressed by
+    /// wasm-snip; wasm-metadce removes them regardless.
+    pub fn wasm_snip_args(&self, input: &str, output: &str) -> Vec<String> {
+        let mut args = vec![input.to_string(), "-o".to_string(), output.to_string()];
+        args.extend(
+            self.candidates
+                .iter()
+                .filter_map(|candidate| candidate.symbol.clone()),
+        );
+        args
+    }
+
+    /// Graph for `wasm-metadce --graph-file` keeping only the live exports
+    pub fn metadce_graph(&self) -> serde_json::Value {
+        let nodes: Vec<serde_json::Value> = self
+            .metadce_exports
+            .iter()
+            .map(|export| {
+                let mut node = serde_json::json!({
+                    "name": format!("export:{}", export.name),
+                    "export": export.name,
+                });
+                if export.root {
+                    node["root"] = serde_json::Value::Bool(true);
+                }
+                node
+            })
+            .collect();
+        serde_json::Value::Array(nodes)
+    }
+}
+
+/// Finds code kept alive only by exports that are never called
+#[derive(Debug, Default)]
+pub struct ReachabilityAnalyzer {
+    live_exports: Option<Vec<String>>,
+}
+
+impl ReachabilityAnalyzer {
+    /// Create an analyzer that treats every export as live
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat only these exports (plus wasm-bindgen glue) as called
+    pub fn with_live_exports(mut self, live_exports: Vec<String>) -> Self {
+        self.live_exports = Some(live_exports);
+        self
+    }
+
+    /// Analyze the reachability of a WASM file's functions
+    pub fn analyze(&self, wasm_file: &Path) -> Result<ReachabilityReport> {
+        let bytes = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        analyze_module(&bytes, self.live_exports.as_deref())
+            .with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+}
+
+/// Whether an export belongs to wasm-bindgen's generated glue
+fn is_glue_export(name: &str) -> bool {
+    GLUE_EXPORT_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// Mask blocking the given items; blocking an export item removes it as a root
+fn blocked(graph: &ItemGraph, items: impl IntoIterator<Item = usize>) -> Vec<bool> {
+    let mut blocked = vec![false; graph.item_count()];
+    for id in items {
+        blocked[id] = true;
+    }
+    blocked
+}
+
+/// Analyze a module's reachability, treating `live_exports` (if given) as the
+/// only exports that are called
+pub fn analyze_module(
+    module: &[u8],
+    live_exports: Option<&[String]>,
+) -> Result<ReachabilityReport> {
+    let graph = ItemGraph::parse(module)?;
+    let is_live = |name: &str| {
+        live_exports.is_none_or(|live| live.iter().any(|l| l == name)) || is_glue_export(name)
+    };
+
+    let func_exports: Vec<&Export> = graph
+        .exports()
+        .iter()
+        .filter(|export| export.function.is_some())
+        .collect();
+    let defined: Vec<(u32, usize)> = graph
+        .functions()
+        .iter()
+        .enumerate()
+        .skip(graph.imported_functions())
+        .map(|(index, &id)| (index as u32, id))
+        .collect();
+
+    let all = graph.reachable_avoiding(&[]);
+    let live = graph.reachable_avoiding(&blocked(
+        &graph,
+        func_exports
+            .iter()
+            .filter(|export| !is_live(&export.name))
+            .map(|export| export.item),
+    ));
+
+    let mut exports: Vec<ExportReachability> = func_exports
+        .iter()
+        .map(|export| {
+            let without = graph.reachable_avoiding(&blocked(&graph, [export.item]));
+            let exclusive: Vec<usize> = defined
+                .iter()
+                .map(|&(_, id)| id)
+                .filter(|&id| all[id] && !without[id])
+                .collect();
+            ExportReachability {
+                name: export.name.clone(),
+                function_index: export.function.unwrap_or_default(),
+                live: is_live(&export.name),
+                exclusive_functions: exclusive.len(),
+                exclusive_bytes: exclusive.iter().map(|&id| graph.size_bytes(id)).sum(),
+            }
+        })
+        .collect();
+    exports.sort_by(|a, b| {
+        b.exclusive_bytes
+            .cmp(&a.exclusive_bytes)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let dead_reach: Vec<(&str, Vec<bool>)> = func_exports
+        .iter()
+        .filter(|export| !is_live(&export.name))
+        .map(|export| {
+            (
+                export.name.as_str(),
+                graph.reachable_from([export.item], &[]),
+            )
+        })
+        .collect();
+    let mut candidates: Vec<SnipCandidate> = defined
+        .iter()
+        .filter(|&&(_, id)| all[id] && !live[id])
+        .map(|&(index, id)| SnipCandidate {
+            index,
+            name: graph.name(id).to_string(),
+            symbol: graph.symbol(id).map(str::to_string),
+            size_bytes: graph.size_bytes(id),
+            exports: dead_reach
+                .iter()
+                .filter(|(_, reached)| reached[id])
+                .map(|(name, _)| name.to_string())
+                .collect(),
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.index.cmp(&b.index))
+    });
+
+    let unreachable: Vec<usize> = defined
+        .iter()
+        .map(|&(_, id)| id)
+        .filter(|&id| !all[id])
+        .collect();
+
+    let unknown_live_exports = live_exports
+        .unwrap_or_default()
+        .iter()
+        .filter(|name| !graph.exports().iter().any(|export| export.name == **name))
+        .cloned()
+        .collect();
+
+    Ok(ReachabilityReport {
+        code_bytes: graph.code_section_bytes(),
+        functions: defined.len(),
+        live_exports_given: live_exports.is_some(),
+        candidate_bytes: candidates.iter().map(|c| c.size_bytes).sum(),
+        exports,
+        unknown_live_exports,
+        candidates,
+        unreachable_functions: unreachable.len(),
+        unreachable_bytes: unreachable.iter().map(|&id| graph.size_bytes(id)).sum(),
+        metadce_exports: graph
+            .exports()
+            .iter()
+            .map(|export| MetadceExport {
+                name: export.name.clone(),
+                root: export.function.is_none() || is_live(&export.name),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{section, string, HEADER};
+
+    /// f0 "used" → f2 (shared); f1 "unused" → f2, f3; f4 unreachable
+    fn module() -> Vec<u8> {
+        let mut module = HEADER.to_vec();
+        module.extend(section(1, &[1, 0x60, 0, 0]));
+        module.extend(section(3, &[5, 0, 0, 0, 0, 0]));
+        let mut exports = vec![3];
+        for (name, index) in [("used", 0), ("unused", 1), ("__wbindgen_free", 4)] {
+            exports.extend(string(name));
+            exports.extend([0, index]);
+        }
+        module.extend(section(7, &exports));
+        let mut code = vec![5];
+        for body in [
+            vec![0x10, 2],
+            vec![0x10, 2, 0x10, 3],
+            vec![],
+            vec![0x01; 6],
+            vec![],
+        ] {
+            code.push(body.len() as u8 + 2);
+            code.push(0);
+            code.extend(body);
+            code.push(0x0b);
+        }
+        module.extend(section(10, &code));
+        module
+    }
+
+    #[test]
+    fn test_unused_export_candidates_exclude_shared_callees() {
+        let live = vec!["used".to_string(), "missing".to_string()];
+        let report = analyze_module(&module(), Some(&live)).unwrap();
+
+        let indices: Vec<u32> = report.candidates.iter().map(|c| c.index).collect();
+        assert_eq!(indices, vec![3, 1]);
+        assert_eq!(report.candidates[0].exports, vec!["unused".to_string()]);
+        assert_eq!(report.candidate_bytes, 8 + 6);
+        assert_eq!(report.unknown_live_exports, vec!["missing".to_string()]);
+        // wasm-bindgen glue is live without being listed
+        let dead: Vec<&str> = report.dead_exports().map(|e| e.name.as_str()).collect();
+        assert_eq!(dead, vec!["unused"]);
+
+        let graph = report.metadce_graph();
+        assert_eq!(graph[0]["root"], serde_json::Value::Bool(true));
+        assert!(graph[1].get("root").is_none());
+    }
+
+    #[test]
+    fn test_without_live_list_reports_exclusive_size_per_export() {
+        let report = analyze_module(&module(), None).unwrap();
+        assert!(report.candidates.is_empty());
+        assert!(report.dead_exports().next().is_none());
+
+        let unused = report.exports.iter().find(|e| e.name == "unused").unwrap();
+        assert_eq!(unused.exclusive_functions, 2);
+        let used = report.exports.iter().find(|e| e.name == "used").unwrap();
+        // f2 is shared with "unused"
+        assert_eq!(used.exclusive_functions, 1);
+        assert_eq!(report.unreachable_functions, 0);
+    }
+}
@@ -0,0 +1,169 @@
+//! Export reachability report formatting
+
+use super::reachability::ReachabilityReport;
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of exports listed by default
+const DEFAULT_TOP_EXPORTS: usize = 20;
+
+/// Number of snip candidates listed by default
+const DEFAULT_TOP_CANDIDATES: usize = 20;
+
+/// Format the reachability report for console output
+pub fn format_console_report(report: &ReachabilityReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the reachability report, limiting the exports and candidates listed
+pub fn format_console_report_with_limit(
+    report: &ReachabilityReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Export Reachability").bold().underlined()
+    )?;
+    writeln!(
+        output,
+        "\n   {} functions ({}) behind {} exported functions",
+        style(report.functions).cyan().bold(),
+        format_bytes(report.code_bytes),
+        style(report.exports.len()).cyan().bold()
+    )?;
+    if report.unreachable_functions > 0 {
+        writeln!(
+            output,
+            "   {}",
+            style(format!(
+                "{} functions ({}) reachable from no export (see --mode dead)",
+                report.unreachable_functions,
+                format_bytes(report.unreachable_bytes)
+            ))
+            .dim()
+        )?;
+    }
+    for name in &report.unknown_live_exports {
+        writeln!(
+            output,
+            "   {} {}",
+            style("⚠").yellow(),
+            style(format!(
+                "Live export '{}' is not exported by the module",
+                name
+            ))
+            .yellow()
+        )?;
+    }
+
+    writeln!(
+        output,
+        "\n  {:>10}  {:>9}  {:<40} Status",
+        "Exclusive", "Functions", "Export"
+    )?;
+    writeln!(output, "{}", style("─".repeat(74)).dim())?;
+    let shown = limit.visible(report.exports.len(), Some(DEFAULT_TOP_EXPORTS));
+    for export in report.exports.iter().take(shown) {
+        let status = if export.live {
+            style("live")
+        } else {
+            style("unused").red().bold()
+        };
+        writeln!(
+            output,
+            "  {:>10}  {:>9}  {:<40} {}",
+            format_bytes(export.exclusive_bytes),
+            export.exclusive_functions,
+            style(truncate_str(&export.name, 40)).bold(),
+            status
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.exports.len(), shown, "exports") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+
+    if !report.live_exports_given {
+        writeln!(
+            output,
+            "\n  {} Pass --live-exports with the exports your JavaScript calls to list removable code",
+            style("💡").dim()
+        )?;
+        return Ok(output);
+    }
+    if report.candidates.is_empty() {
+        writeln!(
+            output,
+            "\n   {} Every exported function is called; nothing to remove",
+            style("✓").green()
+        )?;
+        return Ok(output);
+    }
+
+    writeln!(
+        output,
+        "\n{} {} in {} functions only reachable from unused exports",
+        style("Removal Candidates").bold(),
+        style(format_bytes(report.candidate_bytes)).green().bold(),
+        report.candidates.len()
+    )?;
+    let shown = limit.visible(report.candidates.len(), Some(DEFAULT_TOP_CANDIDATES));
+    for candidate in report.candidates.iter().take(shown) {
+        writeln!(
+            output,
+            "  {:>10}  {:<48} {}",
+            format_bytes(candidate.size_bytes),
+            truncate_str(&candidate.name, 48),
+            style(format!("← {}", candidate.exports.join(", "))).dim()
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.candidates.len(), shown, "candidates") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::reachability::{ExportReachability, SnipCandidate};
+
+    #[test]
+    fn test_format_console_report_lists_unused_exports_and_candidates() {
+        let report = ReachabilityReport {
+            code_bytes: 4096,
+            functions: 40,
+            live_exports_given: true,
+            exports: vec![ExportReachability {
+                name: "render_chart".to_string(),
+                function_index: 7,
+                live: false,
+                exclusive_functions: 3,
+                exclusive_bytes: 1500,
+            }],
+            unknown_live_exports: vec!["greet".to_string()],
+            candidates: vec![SnipCandidate {
+                index: 9,
+                name: "app::chart::draw".to_string(),
+                symbol: None,
+                size_bytes: 1200,
+                exports: vec!["render_chart".to_string()],
+            }],
+            candidate_bytes: 1200,
+            unreachable_functions: 0,
+            unreachable_bytes: 0,
+            metadce_exports: Vec::new(),
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("render_chart"));
+        assert!(text.contains("unused"));
+        assert!(text.contains("Live export 'greet' is not exported"));
+        assert!(text.contains("in 1 functions only reachable from unused exports"));
+        assert!(text.contains("← render_chart"));
+    }
+}
@@ -4,31 +4,15 @@
 //! (asset_report, bloat_report, twiggy_report, feature_report) to ensure consistency
 //! and reduce code duplication.
 
-/// Format bytes with appropriate unit (B, KB, MB, GB)
-///
-/// # Examples
-///
-/// ```
-/// use wasm_slim::analyzer::report_utils::format_bytes;
-///
-/// assert_eq!(format_bytes(512), "512 B");
-/// assert_eq!(format_bytes(2048), "2.00 KB");
-/// assert_eq!(format_bytes(2_097_152), "2.00 MB");
-/// ```
-pub fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
-    }
+pub use crate::fmt::format_bytes;
+
+use super::suppressions::Suppressible;
+use regex::Regex;
+
+/// Format a signed byte delta (`+1.50 KiB`, `-512 B`)
+pub fn format_delta(bytes: i64) -> String {
+    let sign = if bytes < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_bytes(bytes.unsigned_abs()))
 }
 
 /// Truncate string with ellipsis if exceeds max length
@@ -81,6 +65,220 @@ pub fn format_percent(value: f64) -> String {
     format!("{:.2}%", value)
 }
 
+/// How many items each list in a console report shows
+///
+/// Every reporter has its own default per list (e.g. the top 20 symbols);
+/// `--top` (`--max-items` for compare) overrides it and `--all` lifts it.
+///
+/// # Examples
+///
+/// ```
+/// use wasm_slim::analyzer::report_utils::ItemLimit;
+///
+/// assert_eq!(ItemLimit::Default.visible(30, Some(20)), 20);
+/// assert_eq!(ItemLimit::Max(5).visible(30, Some(20)), 5);
+/// assert_eq!(ItemLimit::All.visible(30, Some(20)), 30);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ItemLimit {
+    /// Use the reporter's own default
+    #[default]
+    Default,
+    /// Show at most this many items per list
+    Max(usize),
+    /// Show every item
+    All,
+}
+
+impl ItemLimit {
+    /// Build from the `--top` or `--max-items` and `--all` CLI flags
+    pub fn from_flags(max_items: Option<usize>, all: bool) -> Self {
+        match (all, max_items) {
+            (true, _) => ItemLimit::All,
+            (false, Some(max)) => ItemLimit::Max(max),
+            (false, None) => ItemLimit::Default,
+        }
+    }
+
+    /// Effective limit given the reporter's default (`None` = unlimited)
+    pub fn resolve(self, default: Option<usize>) -> Option<usize> {
+        match self {
+            ItemLimit::Default => default,
+            ItemLimit::Max(max) => Some(max),
+            ItemLimit::All => None,
+        }
+    }
+
+    /// Number of items to show out of `len`
+    pub fn visible(self, len: usize, default: Option<usize>) -> usize {
+        self.resolve(default).map_or(len, |max| len.min(max))
+    }
+}
+
+/// Order of listed items (`--sort`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Largest first
+    Size,
+    /// Largest share first; entries without a share sort by size
+    Percent,
+    /// Alphabetical
+    Name,
+}
+
+impl std::str::FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "size" => Ok(Self::Size),
+            "percent" => Ok(Self::Percent),
+            "name" => Ok(Self::Name),
+            _ => Err(format!(
+                "Unknown sort order: {}. Valid orders: size, percent, name",
+                s
+            )),
+        }
+    }
+}
+
+/// An entry of a result list that [`ItemFilter`] can sort and filter
+pub trait SizedEntry {
+    /// Size in bytes, or `None` for lists without sizes
+    ///
+    /// `--min-size` keeps entries without a size and `--sort size` puts
+    /// them last.
+    fn size_bytes(&self) -> Option<u64>;
+
+    /// Share of the total, if the list reports one
+    fn percentage(&self) -> Option<f64> {
+        None
+    }
+
+    /// Name to sort by
+    fn name(&self) -> &str;
+
+    /// Whether `--filter` keeps the entry (by default, when its name matches)
+    fn matches(&self, pattern: &Regex) -> bool {
+        pattern.is_match(self.name())
+    }
+}
+
+/// Sorting, filtering and truncation of result lists
+///
+/// Unlike [`ItemLimit`], which only shortens console lists, the filter
+/// changes the results themselves, so JSON output is filtered the same way.
+/// Lists keep their analysis order unless a [`SortOrder`] is given.
+///
+/// # Examples
+///
+/// ```
+/// use wasm_slim::analyzer::report_utils::{ItemFilter, SizedEntry, SortOrder};
+///
+/// struct Symbol(&'static str, u64);
+///
+/// impl SizedEntry for Symbol {
+///     fn size_bytes(&self) -> Option<u64> {
+///         Some(self.1)
+///     }
+///
+///     fn name(&self) -> &str {
+///         self.0
+///     }
+/// }
+///
+/// let filter = ItemFilter {
+///     top: Some(2),
+///     sort: Some(SortOrder::Name),
+///     min_size_bytes: 1024,
+///     ..Default::default()
+/// };
+/// let mut symbols = vec![Symbol("c", 4096), Symbol("a", 2048), Symbol("b", 512), Symbol("d", 1024)];
+/// filter.apply(&mut symbols);
+/// let names: Vec<&str> = symbols.iter().map(|s| s.0).collect();
+/// assert_eq!(names, ["a", "c"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ItemFilter {
+    /// Keep only this many of the largest items while analyzing (`--limit`)
+    pub collect_limit: Option<usize>,
+    /// Keep only the first this many items after sorting (`--top`)
+    pub top: Option<usize>,
+    /// Reorder the items (`--sort`)
+    pub sort: Option<SortOrder>,
+    /// Drop items smaller than this (`--min-size`)
+    pub min_size_bytes: u64,
+    /// Keep only items whose name matches (`--filter`)
+    pub pattern: Option<Regex>,
+}
+
+impl ItemFilter {
+    /// Filter, sort and truncate `items` in place
+    pub fn apply<T: SizedEntry>(&self, items: &mut Vec<T>) {
+        if self.min_size_bytes > 0 {
+            items.retain(|item| {
+                item.size_bytes()
+                    .is_none_or(|size| size >= self.min_size_bytes)
+            });
+        }
+        if let Some(pattern) = &self.pattern {
+            items.retain(|item| item.matches(pattern));
+        }
+        match self.sort {
+            Some(SortOrder::Size) => items.sort_by_key(|item| std::cmp::Reverse(item.size_bytes())),
+            Some(SortOrder::Percent) => items.sort_by(|a, b| {
+                let share = |item: &T| {
+                    item.percentage()
+                        .unwrap_or(item.size_bytes().unwrap_or(0) as f64)
+                };
+                share(b).total_cmp(&share(a))
+            }),
+            Some(SortOrder::Name) => items.sort_by(|a, b| a.name().cmp(b.name())),
+            None => {}
+        }
+        if let Some(top) = self.top {
+            items.truncate(top);
+        }
+    }
+
+    /// Keep only the recommendations about a symbol or crate matching
+    /// `--filter`, returning how many were dropped
+    ///
+    /// Recommendations without a subject concern the whole module and are
+    /// dropped too.
+    pub fn retain_recommendations<T: Suppressible>(&self, recommendations: &mut Vec<T>) -> usize {
+        let Some(pattern) = &self.pattern else {
+            return 0;
+        };
+        let before = recommendations.len();
+        recommendations.retain(|r| r.subject().is_some_and(|s| pattern.is_match(s)));
+        before - recommendations.len()
+    }
+}
+
+/// Footer for a truncated list, or `None` if nothing was omitted
+///
+/// # Examples
+///
+/// ```
+/// use wasm_slim::analyzer::report_utils::omitted_footer;
+///
+/// assert_eq!(
+///     omitted_footer(30, 20, "symbols").unwrap(),
+///     "... 10 more symbols omitted, use --all to show everything"
+/// );
+/// assert!(omitted_footer(5, 5, "symbols").is_none());
+/// ```
+pub fn omitted_footer(total: usize, shown: usize, noun: &str) -> Option<String> {
+    let omitted = total.saturating_sub(shown);
+    (omitted > 0).then(|| {
+        format!(
+            "... {} more {} omitted, use --all to show everything",
+            omitted, noun
+        )
+    })
+}
+
 /// Serialize to JSON with pretty printing, fallback to empty object on error
 ///
 /// # Examples
@@ -97,13 +295,123 @@ pub fn format_percent(value: f64) -> String {
 /// assert!(json.contains("\"value\""));
 /// ```
 pub fn to_json_string<T: serde::Serialize>(value: &T) -> String {
-    serde_json::to_string_pretty(value).unwrap_or_else(|_| "{}".to_string())
+    crate::redact::to_json_pretty(value).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Escape text for use in HTML element content and attribute values
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    struct Entry(&'static str, Option<u64>, Option<f64>);
+
+    impl SizedEntry for Entry {
+        fn size_bytes(&self) -> Option<u64> {
+            self.1
+        }
+
+        fn percentage(&self) -> Option<f64> {
+            self.2
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_item_filter_keeps_order_unless_sorted() {
+        let entries = || {
+            vec![
+                Entry("tree", Some(300), Some(10.0)),
+                Entry("leaf", Some(100), Some(40.0)),
+                Entry("root", Some(900), Some(30.0)),
+            ]
+        };
+        let names = |entries: Vec<Entry>| entries.iter().map(|e| e.0).collect::<Vec<_>>();
+
+        let mut unsorted = entries();
+        ItemFilter {
+            min_size_bytes: 200,
+            ..Default::default()
+        }
+        .apply(&mut unsorted);
+        assert_eq!(names(unsorted), ["tree", "root"]);
+
+        let mut by_percent = entries();
+        ItemFilter {
+            sort: Some(SortOrder::Percent),
+            top: Some(2),
+            ..Default::default()
+        }
+        .apply(&mut by_percent);
+        assert_eq!(names(by_percent), ["leaf", "root"]);
+
+        assert_eq!("Size".parse::<SortOrder>(), Ok(SortOrder::Size));
+        assert!("largest".parse::<SortOrder>().is_err());
+    }
+
+    #[test]
+    fn test_item_filter_keeps_unsized_entries_and_sorts_them_last() {
+        let mut entries = vec![
+            Entry("serde", None, None),
+            Entry("small", Some(100), None),
+            Entry("large", Some(900), None),
+        ];
+        ItemFilter {
+            sort: Some(SortOrder::Size),
+            min_size_bytes: 200,
+            ..Default::default()
+        }
+        .apply(&mut entries);
+        let names: Vec<&str> = entries.iter().map(|e| e.0).collect();
+        assert_eq!(names, ["large", "serde"]);
+    }
+
+    #[test]
+    fn test_item_filter_pattern_scopes_items_and_recommendations() {
+        let filter = ItemFilter {
+            pattern: Some(Regex::new("^app::").unwrap()),
+            ..Default::default()
+        };
+        let mut entries = vec![
+            Entry("app::render", Some(300), None),
+            Entry("serde_json::to_string", Some(900), None),
+            Entry("app::parse", Some(100), None),
+        ];
+        filter.apply(&mut entries);
+        let names: Vec<&str> = entries.iter().map(|e| e.0).collect();
+        assert_eq!(names, ["app::render", "app::parse"]);
+
+        let recommendation = |subject: Option<&str>| crate::analyzer::bloat::Recommendation {
+            priority: "P1".to_string(),
+            description: String::new(),
+            estimated_savings_bytes: 0,
+            estimated_savings_percent: 0.0,
+            rule: String::new(),
+            subject: subject.map(str::to_string),
+        };
+        let mut recommendations = vec![
+            recommendation(Some("app::render")),
+            recommendation(Some("serde_json")),
+            recommendation(None),
+        ];
+        assert_eq!(filter.retain_recommendations(&mut recommendations), 2);
+        assert_eq!(recommendations[0].subject.as_deref(), Some("app::render"));
+    }
+
+    #[test]
+    fn test_escape_html_replaces_markup_characters() {
+        assert_eq!(escape_html("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+
     #[test]
     fn test_format_bytes_with_bytes_shows_b_unit() {
         assert_eq!(format_bytes(512), "512 B");
@@ -112,21 +420,21 @@ mod tests {
     }
 
     #[test]
-    fn test_format_bytes_with_kilobytes_shows_kb_unit() {
-        assert_eq!(format_bytes(1024), "1.00 KB");
-        assert_eq!(format_bytes(2048), "2.00 KB");
-        assert_eq!(format_bytes(1024 * 512), "512.00 KB");
+    fn test_format_bytes_with_kilobytes_shows_kib_unit() {
+        assert_eq!(format_bytes(1024), "1.00 KiB");
+        assert_eq!(format_bytes(2048), "2.00 KiB");
+        assert_eq!(format_bytes(1024 * 512), "512.00 KiB");
     }
 
     #[test]
-    fn test_format_bytes_with_megabytes_shows_mb_unit() {
-        assert_eq!(format_bytes(1024 * 1024), "1.00 MB");
-        assert_eq!(format_bytes(2 * 1024 * 1024), "2.00 MB");
+    fn test_format_bytes_with_megabytes_shows_mib_unit() {
+        assert_eq!(format_bytes(1024 * 1024), "1.00 MiB");
+        assert_eq!(format_bytes(2 * 1024 * 1024), "2.00 MiB");
     }
 
     #[test]
-    fn test_format_bytes_with_gigabytes_shows_gb_unit() {
-        assert_eq!(format_bytes(1024 * 1024 * 1024), "1.00 GB");
+    fn test_format_bytes_with_gigabytes_shows_gib_unit() {
+        assert_eq!(format_bytes(1024 * 1024 * 1024), "1.00 GiB");
     }
 
     #[test]
@@ -161,6 +469,32 @@ mod tests {
         assert_eq!(format_percent(0.123), "0.12%");
     }
 
+    #[test]
+    fn test_item_limit_from_flags_prefers_all() {
+        assert_eq!(ItemLimit::from_flags(None, false), ItemLimit::Default);
+        assert_eq!(ItemLimit::from_flags(Some(3), false), ItemLimit::Max(3));
+        assert_eq!(ItemLimit::from_flags(Some(3), true), ItemLimit::All);
+    }
+
+    #[test]
+    fn test_item_limit_visible_respects_reporter_default() {
+        assert_eq!(ItemLimit::Default.visible(5, Some(20)), 5);
+        assert_eq!(ItemLimit::Default.visible(50, None), 50);
+        assert_eq!(ItemLimit::Max(10).visible(50, None), 10);
+        assert_eq!(ItemLimit::Max(0).visible(50, Some(20)), 0);
+        assert_eq!(ItemLimit::All.visible(50, Some(20)), 50);
+    }
+
+    #[test]
+    fn test_omitted_footer_counts_hidden_items() {
+        assert_eq!(
+            omitted_footer(12, 10, "files").unwrap(),
+            "... 2 more files omitted, use --all to show everything"
+        );
+        assert!(omitted_footer(10, 10, "files").is_none());
+        assert!(omitted_footer(0, 0, "files").is_none());
+    }
+
     #[test]
     fn test_to_json_string_serializes_struct() {
         use serde::Serialize;
@@ -0,0 +1,265 @@
+//! Custom analyzer rules for library users
+//!
+//! Organizations often have size policies the built-in analyzers know
+//! nothing about ("no crate over 200KB", "no chrono"). Implement
+//! [`AnalyzerRule`], register it in a [`RuleRegistry`] and pass the registry
+//! to `analyze` through [`AnalyzeOptions::rules`](crate::cmd::AnalyzeOptions::rules).
+//! Registered rules run in `analyze --mode bloat`, where code size per crate is
+//! known, and their recommendations are appended to the bloat report (console
+//! and JSON).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use wasm_slim::analyzer::rules::{BannedCrateRule, MaxCrateSizeRule, RuleRegistry};
+//! use wasm_slim::cmd::{cmd_analyze_with_options, AnalyzeOptions};
+//!
+//! let rules = RuleRegistry::new();
+//! rules.register(Arc::new(MaxCrateSizeRule::new(200 * 1024)));
+//! rules.register(Arc::new(BannedCrateRule::new("chrono", "use js_sys::Date")));
+//!
+//! let options = AnalyzeOptions {
+//!     rules: Arc::new(rules),
+//!     ..Default::default()
+//! };
+//! cmd_analyze_with_options(&None, "bloat", &options)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::bloat::{BloatResults, CrateSize, Recommendation};
+use crate::fmt::{format_bytes, percent_of};
+use parking_lot::Mutex;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+/// What a rule can inspect
+pub struct AnalysisContext<'a> {
+    /// Project root containing Cargo.toml
+    pub project_root: &'a Path,
+    /// Results of the built-in bloat analysis
+    pub bloat: &'a BloatResults,
+    /// Code size per crate (`cargo bloat --crates`)
+    pub crates: &'a [CrateSize],
+    /// Names of every package in the resolved dependency graph
+    pub dependencies: &'a [String],
+}
+
+impl AnalysisContext<'_> {
+    /// Whether `name` is in the dependency graph (dashes and underscores are
+    /// treated alike)
+    pub fn depends_on(&self, name: &str) -> bool {
+        let name = name.replace('-', "_");
+        self.dependencies
+            .iter()
+            .any(|d| d.replace('-', "_") == name)
+    }
+}
+
+/// A custom check run during `analyze`
+pub trait AnalyzerRule: Send + Sync {
+    /// Rule name
+    fn name(&self) -> &str;
+
+    /// Inspect the analysis results and return recommendations
+    fn analyze(&self, ctx: &AnalysisContext) -> Vec<Recommendation>;
+}
+
+/// Registry of custom analyzer rules
+#[derive(Default)]
+pub struct RuleRegistry {
+    rules: Mutex<Vec<Arc<dyn AnalyzerRule>>>,
+}
+
+impl RuleRegistry {
+    /// Create a new empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a rule
+    pub fn register(&self, rule: Arc<dyn AnalyzerRule>) {
+        self.rules.lock().push(rule);
+    }
+
+    /// Run all rules in registration order
+    ///
+    /// Recommendations without a rule id get the name of the rule that
+    /// returned them, so they can be suppressed in `.wasm-slim-ignore`.
+    pub fn run_all(&self, ctx: &AnalysisContext) -> Vec<Recommendation> {
+        self.rules
+            .lock()
+            .iter()
+            .flat_map(|rule| {
+                rule.analyze(ctx).into_iter().map(|mut recommendation| {
+                    if recommendation.rule.is_empty() {
+                        recommendation.rule = rule.name().to_string();
+                    }
+                    recommendation
+                })
+            })
+            .collect()
+    }
+
+    /// Whether no rules are registered
+    pub fn is_empty(&self) -> bool {
+        self.rules.lock().is_empty()
+    }
+
+    /// Get rule count
+    pub fn count(&self) -> usize {
+        self.rules.lock().len()
+    }
+}
+
+impl fmt::Debug for RuleRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules = self.rules.lock();
+        f.debug_list()
+            .entries(rules.iter().map(|rule| rule.name()))
+            .finish()
+    }
+}
+
+/// Flags every crate whose code exceeds a size limit
+#[derive(Debug, Clone)]
+pub struct MaxCrateSizeRule {
+    limit_bytes: u64,
+}
+
+impl MaxCrateSizeRule {
+    /// Create a rule with the given per-crate limit in bytes
+    pub fn new(limit_bytes: u64) -> Self {
+        Self { limit_bytes }
+    }
+}
+
+impl AnalyzerRule for MaxCrateSizeRule {
+    fn name(&self) -> &str {
+        "max-crate-size"
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext) -> Vec<Recommendation> {
+        ctx.crates
+            .iter()
+            .filter(|c| c.size_bytes > self.limit_bytes)
+            .map(|c| Recommendation {
+                priority: "P1".to_string(),
+                description: format!(
+                    "Crate {} is {}, over the {} limit",
+                    c.crate_name,
+                    format_bytes(c.size_bytes),
+                    format_bytes(self.limit_bytes)
+                ),
+                estimated_savings_bytes: c.size_bytes - self.limit_bytes,
+                estimated_savings_percent: percent_of(
+                    c.size_bytes - self.limit_bytes,
+                    ctx.bloat.text_size_bytes,
+                ),
+                rule: self.name().to_string(),
+                subject: Some(c.crate_name.clone()),
+            })
+            .collect()
+    }
+}
+
+/// Flags a crate that must not be in the dependency graph
+#[derive(Debug, Clone)]
+pub struct BannedCrateRule {
+    crate_name: String,
+    advice: String,
+}
+
+impl BannedCrateRule {
+    /// Create a rule banning `crate_name`, with advice on what to use instead
+    pub fn new(crate_name: impl Into<String>, advice: impl Into<String>) -> Self {
+        Self {
+            crate_name: crate_name.into(),
+            advice: advice.into(),
+        }
+    }
+}
+
+impl AnalyzerRule for BannedCrateRule {
+    fn name(&self) -> &str {
+        "banned-crate"
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext) -> Vec<Recommendation> {
+        if !ctx.depends_on(&self.crate_name) {
+            return Vec::new();
+        }
+        let name = self.crate_name.replace('-', "_");
+        let size_bytes = ctx
+            .crates
+            .iter()
+            .find(|c| c.crate_name == name)
+            .map_or(0, |c| c.size_bytes);
+        vec![Recommendation {
+            priority: "P0".to_string(),
+            description: format!(
+                "Crate {} is banned in this project: {}",
+                self.crate_name, self.advice
+            ),
+            estimated_savings_bytes: size_bytes,
+            estimated_savings_percent: percent_of(size_bytes, ctx.bloat.text_size_bytes),
+            rule: self.name().to_string(),
+            subject: Some(name),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crate_size(name: &str, size_bytes: u64) -> CrateSize {
+        CrateSize {
+            crate_name: name.to_string(),
+            size_bytes,
+            percentage: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_registry_runs_rules_in_registration_order() {
+        let bloat = BloatResults {
+            total_size_bytes: 1_000_000,
+            text_size_bytes: 800_000,
+            items: Vec::new(),
+            crates: Vec::new(),
+            recommendations: Vec::new(),
+        };
+        let crates = vec![
+            crate_size("regex", 300 * 1024),
+            crate_size("chrono", 40 * 1024),
+            crate_size("app", 20 * 1024),
+        ];
+        let dependencies = vec!["regex".to_string(), "chrono".to_string()];
+        let ctx = AnalysisContext {
+            project_root: Path::new("."),
+            bloat: &bloat,
+            crates: &crates,
+            dependencies: &dependencies,
+        };
+        let registry = RuleRegistry::new();
+        assert!(registry.run_all(&ctx).is_empty());
+
+        registry.register(Arc::new(MaxCrateSizeRule::new(200 * 1024)));
+        registry.register(Arc::new(BannedCrateRule::new("chrono", "use js_sys::Date")));
+        registry.register(Arc::new(BannedCrateRule::new("time", "use js_sys::Date")));
+
+        let recommendations = registry.run_all(&ctx);
+        assert_eq!(registry.count(), 3);
+        assert_eq!(
+            format!("{:?}", registry),
+            "[\"max-crate-size\", \"banned-crate\", \"banned-crate\"]"
+        );
+        assert_eq!(recommendations.len(), 2);
+        assert!(recommendations[0].description.contains("regex"));
+        assert_eq!(recommendations[0].estimated_savings_bytes, 100 * 1024);
+        assert_eq!(recommendations[1].priority, "P0");
+        assert_eq!(recommendations[1].estimated_savings_bytes, 40 * 1024);
+    }
+}
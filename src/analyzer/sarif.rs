@@ -0,0 +1,448 @@
+//! SARIF output for code scanning
+//!
+//! `analyze --format sarif` turns findings into a SARIF 2.1.0 log that GitHub
+//! Code Scanning (`github/codeql-action/upload-sarif`) accepts, so they show
+//! up as annotations on the pull request. Heavy and banned dependencies point
+//! at their line in Cargo.toml, panic sites at their source line, and
+//! embedded blobs at the `include_*!` that put them in the binary (or the
+//! WASM file when no source matched).
+
+use super::data_segments::DataReport;
+use super::deps_types::{DependencyReport, IssueSeverity, RuleKind};
+use super::panics::{PanicPattern, PanicResults};
+use crate::fmt::format_bytes;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// SARIF schema the log conforms to
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF version
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// Fails the check
+    Error,
+    /// Worth fixing
+    Warning,
+    /// Informational
+    Note,
+}
+
+impl From<IssueSeverity> for Level {
+    fn from(severity: IssueSeverity) -> Self {
+        match severity {
+            IssueSeverity::Critical => Level::Error,
+            IssueSeverity::High | IssueSeverity::Medium => Level::Warning,
+            IssueSeverity::Low => Level::Note,
+        }
+    }
+}
+
+/// One finding, before it is laid out as SARIF
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// Rule id (e.g. `deps.heavy_dependency`)
+    pub rule_id: String,
+    /// One-line description of the rule
+    pub rule_description: String,
+    /// Severity
+    pub level: Level,
+    /// What was found and what to do about it
+    pub message: String,
+    /// File, relative to the project root
+    pub uri: String,
+    /// 1-based line, when known
+    pub line: Option<usize>,
+}
+
+/// SARIF log with a single run
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Message,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: Level,
+    message: Message,
+    locations: Vec<Location>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+}
+
+impl SarifLog {
+    /// Lay out findings as a SARIF log, one rule per distinct rule id
+    pub fn from_findings(findings: Vec<Finding>) -> Self {
+        let mut rule_index: BTreeMap<String, usize> = BTreeMap::new();
+        let mut rules = Vec::new();
+        let results = findings
+            .into_iter()
+            .map(|finding| {
+                let index = *rule_index
+                    .entry(finding.rule_id.clone())
+                    .or_insert_with(|| {
+                        rules.push(Rule {
+                            id: finding.rule_id.clone(),
+                            short_description: Message {
+                                text: finding.rule_description.clone(),
+                            },
+                        });
+                        rules.len() - 1
+                    });
+                SarifResult {
+                    rule_id: finding.rule_id,
+                    rule_index: index,
+                    level: finding.level,
+                    message: Message {
+                        text: finding.message,
+                    },
+                    locations: vec![Location {
+                        physical_location: PhysicalLocation {
+                            artifact_location: ArtifactLocation { uri: finding.uri },
+                            region: finding.line.map(|start_line| Region { start_line }),
+                        },
+                    }],
+                }
+            })
+            .collect();
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: env!("CARGO_PKG_NAME"),
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_HOMEPAGE"),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+    }
+
+    /// Number of results
+    pub fn result_count(&self) -> usize {
+        self.runs.iter().map(|run| run.results.len()).sum()
+    }
+}
+
+/// Heavy dependencies and crate rule violations, located in Cargo.toml
+///
+/// `manifest` is the content of the project's Cargo.toml; transitive
+/// dependencies that it does not mention are reported on its first line.
+pub fn dependency_findings(report: &DependencyReport, manifest: &str) -> Vec<Finding> {
+    let line_of = |package: &str| Some(manifest_line(manifest, package).unwrap_or(1));
+
+    let issues = report.issues.iter().map(|issue| Finding {
+        rule_id: "deps.heavy_dependency".to_string(),
+        rule_description: "Dependency with a large WASM size impact".to_string(),
+        level: issue.severity.into(),
+        message: format!(
+            "{} {}: {} Suggestion: {}",
+            issue.package, issue.version, issue.issue, issue.suggestion
+        ),
+        uri: "Cargo.toml".to_string(),
+        line: line_of(&issue.package),
+    });
+    let violations = report.rule_violations.iter().map(|violation| {
+        let (rule_id, rule_description, level) = match violation.kind {
+            RuleKind::Banned => (
+                "deps.banned_crate",
+                "Crate banned by the project's crate rules",
+                Level::Error,
+            ),
+            RuleKind::Discouraged => (
+                "deps.discouraged_crate",
+                "Crate discouraged by the project's crate rules",
+                Level::Warning,
+            ),
+        };
+        let hint = violation
+            .hint
+            .as_ref()
+            .map(|hint| format!(" {}", hint))
+            .unwrap_or_default();
+        Finding {
+            rule_id: rule_id.to_string(),
+            rule_description: rule_description.to_string(),
+            level,
+            message: format!(
+                "{} {} matches rule `{}`.{}",
+                violation.package, violation.version, violation.rule, hint
+            ),
+            uri: "Cargo.toml".to_string(),
+            line: line_of(&violation.package),
+        }
+    });
+    issues.chain(violations).collect()
+}
+
+/// Panic sites at their source line
+pub fn panic_findings(results: &PanicResults, project_root: &Path) -> Vec<Finding> {
+    results
+        .panic_sites
+        .iter()
+        .map(|site| Finding {
+            rule_id: format!("panics.{}", panic_rule(&site.pattern)),
+            rule_description: format!("Panic site: {}", site.pattern.name()),
+            level: Level::Note,
+            message: format!(
+                "{} links panic machinery (~{}); consider {}",
+                site.pattern.name(),
+                format_bytes(site.pattern.size_per_occurrence()),
+                site.pattern.alternative()
+            ),
+            uri: relative_uri(&site.file, project_root),
+            line: Some(site.line),
+        })
+        .collect()
+}
+
+/// Data segments over `large_segment_bytes` and the blobs recognized in them
+///
+/// Blobs matched to an embedded file are located at the `include_*!` site;
+/// everything else at `wasm_file`.
+pub fn data_segment_findings(
+    report: &DataReport,
+    wasm_file: &Path,
+    project_root: &Path,
+    large_segment_bytes: u64,
+) -> Vec<Finding> {
+    let wasm_uri = relative_uri(wasm_file, project_root);
+    let mut findings = Vec::new();
+    for segment in &report.segments {
+        if segment.size_bytes > large_segment_bytes {
+            findings.push(Finding {
+                rule_id: "data.large_segment".to_string(),
+                rule_description: "Large data segment".to_string(),
+                level: Level::Warning,
+                message: format!(
+                    "data[{}] is {} ({:.1}% of the module)",
+                    segment.index,
+                    format_bytes(segment.size_bytes),
+                    segment.percentage
+                ),
+                uri: wasm_uri.clone(),
+                line: None,
+            });
+        }
+        for blob in &segment.findings {
+            let size = blob
+                .size_bytes
+                .map(|bytes| format!(" of {}", format_bytes(bytes)))
+                .unwrap_or_default();
+            let (uri, line) = blob
+                .source
+                .as_deref()
+                .and_then(include_site)
+                .map(|(file, line)| (relative_uri(Path::new(file), project_root), Some(line)))
+                .unwrap_or_else(|| (wasm_uri.clone(), None));
+            findings.push(Finding {
+                rule_id: "data.embedded_blob".to_string(),
+                rule_description: "File embedded in a data segment".to_string(),
+                level: Level::Note,
+                message: format!(
+                    "{}{} embedded in data[{}] at offset {}; consider loading it at runtime",
+                    blob.kind.label(),
+                    size,
+                    segment.index,
+                    blob.offset
+                ),
+                uri,
+                line,
+            });
+        }
+    }
+    findings
+}
+
+/// Rule id suffix for a panic pattern
+fn panic_rule(pattern: &PanicPattern) -> &'static str {
+    match pattern {
+        PanicPattern::Unwrap => "unwrap",
+        PanicPattern::Expect => "expect",
+        PanicPattern::Index => "index",
+        PanicPattern::Division => "division",
+        PanicPattern::PanicMacro => "panic_macro",
+        PanicPattern::AssertMacro => "assert_macro",
+        PanicPattern::Overflow => "overflow",
+    }
+}
+
+/// Path relative to the project root with forward slashes
+fn relative_uri(path: &Path, project_root: &Path) -> String {
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Line of Cargo.toml that declares `package`
+fn manifest_line(manifest: &str, package: &str) -> Option<usize> {
+    manifest
+        .lines()
+        .position(|line| {
+            let line = line.trim_start();
+            line.strip_prefix(package).is_some_and(|rest| {
+                let rest = rest.trim_start();
+                rest.starts_with('=') || rest.starts_with('.')
+            }) || line == format!("[dependencies.{}]", package)
+        })
+        .map(|index| index + 1)
+}
+
+/// `<source file>:<line>` from an embedded blob's `<asset> (<file>:<line>)` label
+fn include_site(label: &str) -> Option<(&str, usize)> {
+    let location = label.strip_suffix(')')?.rsplit_once(" (")?.1;
+    let (file, line) = location.rsplit_once(':')?;
+    Some((file, line.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::deps_types::{DependencyIssue, RuleViolation};
+
+    #[test]
+    fn test_dependency_findings_point_at_manifest_lines() {
+        let manifest =
+            "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1\"\nregex.version = \"1\"\n";
+        let report = DependencyReport {
+            issues: vec![DependencyIssue {
+                package: "regex".to_string(),
+                version: "1.10.0".to_string(),
+                severity: IssueSeverity::Critical,
+                issue: "Unicode tables add ~500KB.".to_string(),
+                suggestion: "Use regex-lite".to_string(),
+                size_impact_kb: None,
+                savings_percent: None,
+                verify_id: None,
+            }],
+            rule_violations: vec![RuleViolation {
+                package: "chrono".to_string(),
+                version: "0.4.0".to_string(),
+                kind: RuleKind::Discouraged,
+                rule: "chrono".to_string(),
+                hint: Some("Use js_sys::Date".to_string()),
+            }],
+            total_deps: 2,
+            direct_deps: 2,
+            duplicates: Default::default(),
+            duplicate_versions: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        let findings = dependency_findings(&report, manifest);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].level, Level::Error);
+        assert_eq!(findings[0].line, Some(6));
+        assert_eq!(findings[1].rule_id, "deps.discouraged_crate");
+        assert_eq!(findings[1].line, Some(1));
+        assert!(findings[1].message.ends_with("Use js_sys::Date"));
+    }
+
+    #[test]
+    fn test_sarif_log_shares_rules_between_results() {
+        let finding = |rule: &str, line| Finding {
+            rule_id: rule.to_string(),
+            rule_description: "desc".to_string(),
+            level: Level::Note,
+            message: "msg".to_string(),
+            uri: "src/lib.rs".to_string(),
+            line,
+        };
+        let log = SarifLog::from_findings(vec![
+            finding("panics.unwrap", Some(3)),
+            finding("data.large_segment", None),
+            finding("panics.unwrap", Some(9)),
+        ]);
+
+        assert_eq!(log.result_count(), 3);
+        let json = serde_json::to_value(&log).unwrap();
+        assert_eq!(json["version"], "2.1.0");
+        let run = &json["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["results"][2]["ruleIndex"], 0);
+        assert_eq!(
+            run["results"][2]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            9
+        );
+        assert!(run["results"][1]["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+        assert_eq!(
+            include_site("a.bin (src/lib.rs:12)"),
+            Some(("src/lib.rs", 12))
+        );
+    }
+}
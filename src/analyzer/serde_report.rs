@@ -0,0 +1,126 @@
+//! serde usage report formatting
+
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use super::serde_usage::SerdeReport;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of types listed by default
+const DEFAULT_TOP_TYPES: usize = 15;
+
+/// Format the serde report for console output
+pub fn format_console_report(report: &SerdeReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the serde report, limiting the types listed
+pub fn format_console_report_with_limit(
+    report: &SerdeReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("serde Code Size").bold().underlined())?;
+    writeln!(
+        output,
+        "\n   {} types derive serde traits ({} Serialize, {} Deserialize)",
+        style(report.derives.len()).cyan().bold(),
+        report.serialize_derives,
+        report.deserialize_derives
+    )?;
+    if report.serde_bytes > 0 {
+        writeln!(
+            output,
+            "   serde monomorphizations: {} ({} in serde_json)",
+            style(format_bytes(report.serde_bytes)).yellow().bold(),
+            format_bytes(report.serde_json_bytes)
+        )?;
+    }
+    for warning in &report.warnings {
+        writeln!(
+            output,
+            "   {} {}",
+            style("⚠").yellow(),
+            style(warning).yellow()
+        )?;
+    }
+    if report.derives.is_empty() {
+        writeln!(
+            output,
+            "\n   {} No serde derives found in src/",
+            style("✓").green()
+        )?;
+        return Ok(output);
+    }
+
+    if !report.type_costs.is_empty() {
+        writeln!(output, "\n  {:>10} {:>9}  Type", "Size", "Functions")?;
+        writeln!(output, "{}", style("─".repeat(60)).dim())?;
+        let shown = limit.visible(report.type_costs.len(), Some(DEFAULT_TOP_TYPES));
+        for cost in report.type_costs.iter().take(shown) {
+            writeln!(
+                output,
+                "  {:>10} {:>9}  {}",
+                format_bytes(cost.size_bytes),
+                cost.instantiations,
+                style(truncate_str(&cost.type_name, 44)).bold()
+            )?;
+        }
+        if let Some(footer) = omitted_footer(report.type_costs.len(), shown, "types") {
+            writeln!(output, "  {}", style(footer).dim())?;
+        }
+    } else {
+        writeln!(
+            output,
+            "\n  {} Pass the WASM file to attribute serde code to each type",
+            style("💡").dim()
+        )?;
+    }
+
+    if !report.recommendations.is_empty() {
+        writeln!(output, "\n{}", style("Recommendations").bold())?;
+        for rec in &report.recommendations {
+            writeln!(output, "  {} {}", style("→").cyan(), rec)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::serde_usage::{SerdeDerive, SerdeTypeCost};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_console_report_lists_type_costs_and_alternatives() {
+        let report = SerdeReport {
+            derives: vec![SerdeDerive {
+                type_name: "Config".to_string(),
+                file: PathBuf::from("src/lib.rs"),
+                serialize: true,
+                deserialize: true,
+            }],
+            serialize_derives: 1,
+            deserialize_derives: 1,
+            type_costs: vec![SerdeTypeCost {
+                type_name: "Config".to_string(),
+                instantiations: 6,
+                size_bytes: 12_000,
+            }],
+            serde_bytes: 20_000,
+            serde_json_bytes: 15_000,
+            uses_serde_json: true,
+            recommendations: vec!["miniserde: JSON-only derives (~60% smaller)".to_string()],
+            warnings: Vec::new(),
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("1 types derive serde traits (1 Serialize, 1 Deserialize)"));
+        assert!(text.contains("in serde_json"));
+        assert!(text.contains("Config"));
+        assert!(text.contains("miniserde"));
+        assert!(!text.contains("Pass the WASM file"));
+    }
+}
@@ -0,0 +1,413 @@
+//! serde code size analysis
+//!
+//! Every `#[derive(Serialize, Deserialize)]` makes serde generate visitor
+//! code per type, and every format it is used with (usually serde_json)
+//! instantiates that code again. This pass counts the derives in the
+//! project's sources, attributes the serde monomorphizations of a WASM file
+//! to the derived types, and, when serde_json is the heavy path, recommends
+//! the lighter serializers from the heavy-dependency database.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::SerdeAnalyzer;
+//!
+//! let report = SerdeAnalyzer::new(".")
+//!     .with_wasm_file("pkg/app_bg.wasm")
+//!     .analyze()?;
+//! for cost in &report.type_costs {
+//!     println!("{}: {} bytes of serde code", cost.type_name, cost.size_bytes);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::heavy_deps::get_heavy_dependency_info;
+use super::report_utils::SizedEntry;
+use super::twiggy::{AnalysisMode, MonomorphizationGroup, TwiggyAnalyzer};
+use anyhow::{Context, Result};
+use quote::ToTokens;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use syn::visit::{self, Visit};
+
+/// A type deriving serde traits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SerdeDerive {
+    /// Type name
+    pub type_name: String,
+    /// Source file, relative to the project root
+    pub file: PathBuf,
+    /// Derives `Serialize`
+    pub serialize: bool,
+    /// Derives `Deserialize`
+    pub deserialize: bool,
+}
+
+/// serde code attributed to one derived type
+#[derive(Debug, Clone, Serialize)]
+pub struct SerdeTypeCost {
+    /// Type name
+    pub type_name: String,
+    /// Monomorphized serde functions mentioning the type
+    pub instantiations: usize,
+    /// Their total size
+    pub size_bytes: u64,
+}
+
+impl SizedEntry for SerdeTypeCost {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+/// serde usage of a project
+#[derive(Debug, Clone, Serialize)]
+pub struct SerdeReport {
+    /// Types deriving `Serialize` or `Deserialize`
+    pub derives: Vec<SerdeDerive>,
+    /// Number of `Serialize` derives
+    pub serialize_derives: usize,
+    /// Number of `Deserialize` derives
+    pub deserialize_derives: usize,
+    /// Per-type serde code, largest first (empty without a WASM file)
+    pub type_costs: Vec<SerdeTypeCost>,
+    /// Size of all serde monomorphizations
+    pub serde_bytes: u64,
+    /// Size of the serde_json monomorphizations
+    pub serde_json_bytes: u64,
+    /// serde_json is a dependency or shows up in the binary
+    pub uses_serde_json: bool,
+    /// Suggested changes
+    pub recommendations: Vec<String>,
+    /// Problems that limited the analysis
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Counts serde derives and estimates their code size
+pub struct SerdeAnalyzer {
+    project_root: PathBuf,
+    wasm_file: Option<PathBuf>,
+}
+
+impl SerdeAnalyzer {
+    /// Create an analyzer for the project at `project_root`
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+            wasm_file: None,
+        }
+    }
+
+    /// Estimate per-type costs from the monomorphizations of a WASM file
+    pub fn with_wasm_file(mut self, wasm_file: impl Into<PathBuf>) -> Self {
+        self.wasm_file = Some(wasm_file.into());
+        self
+    }
+
+    /// Scan the sources and, with a WASM file, its monomorphizations
+    pub fn analyze(&self) -> Result<SerdeReport> {
+        let mut warnings = Vec::new();
+        let mut files = Vec::new();
+        collect_rust_files(&self.project_root.join("src"), &mut files)?;
+        files.sort();
+
+        let mut derives = Vec::new();
+        for file in &files {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let relative = file.strip_prefix(&self.project_root).unwrap_or(file);
+            match scan_source(&content, relative) {
+                Ok(found) => derives.extend(found),
+                Err(e) => warnings.push(format!("Skipped {}: {}", relative.display(), e)),
+            }
+        }
+
+        let groups = match &self.wasm_file {
+            Some(wasm_file) => TwiggyAnalyzer::new(wasm_file)
+                .analyze(AnalysisMode::Monos)?
+                .mono_groups
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        let mut report = build_report(derives, &groups, self.declares_serde_json());
+        report.warnings = warnings;
+        Ok(report)
+    }
+
+    /// Whether Cargo.toml lists serde_json as a dependency
+    fn declares_serde_json(&self) -> bool {
+        let Ok(manifest) = std::fs::read_to_string(self.project_root.join("Cargo.toml")) else {
+            return false;
+        };
+        let Ok(doc) = manifest.parse::<toml_edit::DocumentMut>() else {
+            return false;
+        };
+        let declares = |table: Option<&toml_edit::Item>| {
+            table
+                .and_then(|t| t.as_table_like())
+                .is_some_and(|t| t.contains_key("serde_json"))
+        };
+        declares(doc.get("dependencies"))
+            || doc
+                .get("target")
+                .and_then(|t| t.as_table_like())
+                .is_some_and(|targets| {
+                    targets
+                        .iter()
+                        .any(|(_, target)| declares(target.get("dependencies")))
+                })
+    }
+}
+
+/// Collect `.rs` files below `dir`
+pub(crate) fn collect_rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_rust_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Types deriving serde traits in one source file
+///
+/// `cfg_attr(..., derive(...))` counts as a derive.
+fn scan_source(content: &str, file: &Path) -> syn::Result<Vec<SerdeDerive>> {
+    let syntax = syn::parse_file(content)?;
+    let mut visitor = DeriveVisitor {
+        file,
+        derives: Vec::new(),
+    };
+    visitor.visit_file(&syntax);
+    Ok(visitor.derives)
+}
+
+struct DeriveVisitor<'a> {
+    file: &'a Path,
+    derives: Vec<SerdeDerive>,
+}
+
+impl DeriveVisitor<'_> {
+    fn record(&mut self, ident: &syn::Ident, attrs: &[syn::Attribute]) {
+        let mut serialize = false;
+        let mut deserialize = false;
+        for attr in attrs {
+            if !(attr.path().is_ident("derive") || attr.path().is_ident("cfg_attr")) {
+                continue;
+            }
+            let tokens = attr.meta.to_token_stream().to_string();
+            if attr.path().is_ident("cfg_attr") && !tokens.contains("derive") {
+                continue;
+            }
+            for word in tokens.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+                serialize |= word == "Serialize";
+                deserialize |= word == "Deserialize";
+            }
+        }
+        if serialize || deserialize {
+            self.derives.push(SerdeDerive {
+                type_name: ident.to_string(),
+                file: self.file.to_path_buf(),
+                serialize,
+                deserialize,
+            });
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for DeriveVisitor<'_> {
+    fn visit_item_struct(&mut self, item: &'ast syn::ItemStruct) {
+        self.record(&item.ident, &item.attrs);
+        visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_enum(&mut self, item: &'ast syn::ItemEnum) {
+        self.record(&item.ident, &item.attrs);
+        visit::visit_item_enum(self, item);
+    }
+}
+
+/// Attribute serde monomorphizations to the derived types
+fn build_report(
+    derives: Vec<SerdeDerive>,
+    groups: &[MonomorphizationGroup],
+    declares_serde_json: bool,
+) -> SerdeReport {
+    let serde_items: Vec<_> = groups
+        .iter()
+        .flat_map(|g| &g.instantiations)
+        .filter(|item| item.name.contains("serde"))
+        .collect();
+    let serde_json_bytes = serde_items
+        .iter()
+        .filter(|item| item.name.contains("serde_json"))
+        .map(|item| item.size_bytes)
+        .sum();
+
+    let mut type_names: Vec<&str> = derives.iter().map(|d| d.type_name.as_str()).collect();
+    type_names.sort_unstable();
+    type_names.dedup();
+    let mut type_costs: Vec<SerdeTypeCost> = type_names
+        .into_iter()
+        .filter_map(|type_name| {
+            let matching: Vec<_> = serde_items
+                .iter()
+                .filter(|item| mentions_type(&item.name, type_name))
+                .collect();
+            (!matching.is_empty()).then(|| SerdeTypeCost {
+                type_name: type_name.to_string(),
+                instantiations: matching.len(),
+                size_bytes: matching.iter().map(|item| item.size_bytes).sum(),
+            })
+        })
+        .collect();
+    type_costs.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.type_name.cmp(&b.type_name))
+    });
+
+    let mut report = SerdeReport {
+        serialize_derives: derives.iter().filter(|d| d.serialize).count(),
+        deserialize_derives: derives.iter().filter(|d| d.deserialize).count(),
+        derives,
+        type_costs,
+        serde_bytes: serde_items.iter().map(|item| item.size_bytes).sum(),
+        serde_json_bytes,
+        uses_serde_json: declares_serde_json || serde_json_bytes > 0,
+        recommendations: Vec::new(),
+        warnings: Vec::new(),
+    };
+    report.recommendations = recommendations(&report);
+    report
+}
+
+/// Whether a symbol names `type_name` as a path segment or generic argument
+fn mentions_type(symbol: &str, type_name: &str) -> bool {
+    symbol.match_indices(type_name).any(|(at, _)| {
+        let before = symbol[..at].chars().next_back();
+        let after = symbol[at + type_name.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// Suggestions for a report
+fn recommendations(report: &SerdeReport) -> Vec<String> {
+    let mut recs = Vec::new();
+    if report.derives.is_empty() || !report.uses_serde_json {
+        return recs;
+    }
+
+    if let Some(largest) = report.type_costs.first() {
+        recs.push(format!(
+            "{} carries the most serde code ({} functions, ~{} KB); a hand-written impl or a smaller wire type can cut it",
+            largest.type_name,
+            largest.instantiations,
+            largest.size_bytes.div_ceil(1024)
+        ));
+    }
+    if let Some(info) = get_heavy_dependency_info("serde_json") {
+        for alternative in &info.alternatives {
+            let name = alternative
+                .crate_name
+                .as_deref()
+                .unwrap_or("Custom implementation");
+            recs.push(format!(
+                "{}: {} (~{}% smaller)",
+                name, alternative.description, alternative.savings_percent
+            ));
+        }
+    }
+    recs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::twiggy::AnalysisItem;
+
+    #[test]
+    fn test_scan_source_counts_serde_derives() {
+        let source = r#"
+            #[derive(Debug, Serialize, Deserialize)]
+            struct Config { name: String }
+
+            #[derive(serde::Serialize)]
+            enum Event { Click }
+
+            #[cfg_attr(feature = "serde", derive(Deserialize))]
+            struct Optional;
+
+            #[derive(Clone)]
+            struct Plain;
+        "#;
+        let derives = scan_source(source, Path::new("src/lib.rs")).unwrap();
+        let summary: Vec<(&str, bool, bool)> = derives
+            .iter()
+            .map(|d| (d.type_name.as_str(), d.serialize, d.deserialize))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Config", true, true),
+                ("Event", true, false),
+                ("Optional", false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_report_attributes_monomorphizations_to_types() {
+        let item = |name: &str, size_bytes| AnalysisItem {
+            size_bytes,
+            percentage: 0.0,
+            name: name.to_string(),
+        };
+        let groups = vec![MonomorphizationGroup {
+            function_name: "serde_json::de::from_str".to_string(),
+            instantiation_count: 3,
+            total_size_bytes: 3500,
+            avg_size_bytes: 1166,
+            instantiations: vec![
+                item("serde_json::de::from_str<app::Config>", 2000),
+                item("serde_json::de::from_str<app::ConfigEntry>", 1000),
+                item("core::fmt::write<app::Config>", 500),
+            ],
+            potential_savings_bytes: 1500,
+            type_arguments: Vec::new(),
+            refactor: None,
+        }];
+        let derives = vec![SerdeDerive {
+            type_name: "Config".to_string(),
+            file: PathBuf::from("src/lib.rs"),
+            serialize: false,
+            deserialize: true,
+        }];
+
+        let report = build_report(derives, &groups, false);
+        assert!(report.uses_serde_json);
+        assert_eq!(report.serde_json_bytes, 3000);
+        assert_eq!(report.type_costs.len(), 1);
+        assert_eq!(report.type_costs[0].size_bytes, 2000);
+        assert!(report.recommendations[0].starts_with("Config carries"));
+        assert!(report
+            .recommendations
+            .iter()
+            .any(|r| r.starts_with("miniserde")));
+    }
+}
@@ -0,0 +1,234 @@
+//! Measured WASM sizes of popular crates
+//!
+//! The database records how much each crate adds to a release
+//! `wasm32-unknown-unknown` build (`opt-level = "z"`, LTO, `wasm-opt -Oz`,
+//! default features). A snapshot ships with wasm-slim; `wasm-slim db update`
+//! downloads the curated copy into `.wasm-slim/size-db.json`, which takes
+//! precedence once present. Dependency analysis prefers these measurements
+//! over the ranges in the heavy-dependency database and over source-size
+//! heuristics.
+
+use crate::config::remote_template::fetch_url;
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Where `db update` fetches the curated database from
+pub const SIZE_DB_URL: &str =
+    "https://raw.githubusercontent.com/vitalratel/wasm-slim/main/src/analyzer/size_db.json";
+
+/// Downloaded database, inside the state directory
+pub const SIZE_DB_FILE: &str = "size-db.json";
+
+/// Schema version this build understands
+pub const SIZE_DB_SCHEMA: u32 = 1;
+
+/// Snapshot bundled at build time
+const BUNDLED_SIZE_DB: &str = include_str!("size_db.json");
+
+static BUNDLED: OnceLock<SizeDatabase> = OnceLock::new();
+
+/// Measured crate sizes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeDatabase {
+    /// Schema version
+    pub schema: u32,
+    /// Date the measurements were taken (YYYY-MM-DD)
+    pub updated: String,
+    /// Measurements by crate name
+    pub crates: BTreeMap<String, CrateMeasurement>,
+}
+
+/// Size a crate adds to an optimized WASM build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrateMeasurement {
+    /// Crate version measured (major.minor)
+    pub version: String,
+    /// Size added to the module in KB
+    pub wasm_kb: u32,
+}
+
+impl Default for SizeDatabase {
+    fn default() -> Self {
+        Self::bundled().clone()
+    }
+}
+
+impl SizeDatabase {
+    /// The snapshot bundled with this build
+    pub fn bundled() -> &'static SizeDatabase {
+        BUNDLED
+            .get_or_init(|| Self::parse(BUNDLED_SIZE_DB).expect("bundled size database is valid"))
+    }
+
+    /// Parse a database, rejecting schemas this build does not understand
+    pub fn parse(json: &str) -> Result<Self> {
+        let database: SizeDatabase = serde_json::from_str(json).context("Invalid size database")?;
+        if database.schema != SIZE_DB_SCHEMA {
+            anyhow::bail!(
+                "Unsupported size database schema {} (expected {}); upgrade wasm-slim",
+                database.schema,
+                SIZE_DB_SCHEMA
+            );
+        }
+        Ok(database)
+    }
+
+    /// The project's downloaded database, or the bundled snapshot
+    ///
+    /// A downloaded copy that cannot be read is ignored with a warning.
+    pub fn load(project_root: &Path) -> Self {
+        Self::load_with_fs(project_root, &RealFileSystem)
+    }
+
+    /// Load the database with a custom filesystem implementation
+    pub fn load_with_fs<FS: FileSystem>(project_root: &Path, fs: &FS) -> Self {
+        let path = crate::state::state_dir(project_root).join(SIZE_DB_FILE);
+        let Ok(contents) = fs.read_to_string(&path) else {
+            return Self::default();
+        };
+        match Self::parse(&contents) {
+            Ok(database) => database,
+            Err(e) => {
+                eprintln!("Warning: Ignoring {}: {:#}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Measured size of a crate in KB
+    pub fn wasm_kb(&self, name: &str) -> Option<u32> {
+        self.crates.get(name).map(|m| m.wasm_kb)
+    }
+}
+
+/// Outcome of `db update`
+#[derive(Debug, Clone, Serialize)]
+pub struct SizeDbUpdate {
+    /// Where the database was written
+    pub path: PathBuf,
+    /// Measurement date of the database in use before the update
+    pub previous_updated: String,
+    /// Measurement date of the downloaded database
+    pub updated: String,
+    /// Crates measured
+    pub crates: usize,
+}
+
+/// Downloads the curated size database
+pub struct SizeDbUpdater<FS: FileSystem = RealFileSystem, CE: CommandExecutor = RealCommandExecutor>
+{
+    project_root: PathBuf,
+    fs: FS,
+    cmd_executor: CE,
+}
+
+impl SizeDbUpdater {
+    /// Create an updater for the given project root
+    pub fn new(project_root: &Path) -> Self {
+        Self::with_executors(project_root, RealFileSystem, RealCommandExecutor)
+    }
+}
+
+impl<FS: FileSystem, CE: CommandExecutor> SizeDbUpdater<FS, CE> {
+    /// Create an updater with custom executors; the database is fetched with `curl`
+    pub fn with_executors(project_root: &Path, fs: FS, cmd_executor: CE) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            fs,
+            cmd_executor,
+        }
+    }
+
+    /// Fetch the database from `url` and save it for later analyses
+    ///
+    /// The download is validated before anything is written, so a failed
+    /// update leaves the previous database in place.
+    pub fn update(&self, url: &str) -> Result<SizeDbUpdate> {
+        let previous = SizeDatabase::load_with_fs(&self.project_root, &self.fs);
+        let contents = fetch_url(&self.cmd_executor, url)?;
+        let database =
+            SizeDatabase::parse(&contents).with_context(|| format!("Rejected {}", url))?;
+
+        let path = crate::state::state_dir(&self.project_root).join(SIZE_DB_FILE);
+        if let Some(parent) = path.parent() {
+            self.fs
+                .create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        self.fs
+            .write(&path, &contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(SizeDbUpdate {
+            path,
+            previous_updated: previous.updated,
+            updated: database.updated,
+            crates: database.crates.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::process::{Command, ExitStatus, Output};
+
+    struct FakeCurl(&'static str);
+
+    impl CommandExecutor for FakeCurl {
+        fn status(&self, _cmd: &mut Command) -> io::Result<ExitStatus> {
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, _cmd: &mut Command) -> io::Result<Output> {
+            Ok(Output {
+                status: crate::infra::mock_exit_status(0),
+                stdout: self.0.as_bytes().to_vec(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_bundled_database_covers_heavy_dependencies() {
+        let database = SizeDatabase::bundled();
+        assert_eq!(database.schema, SIZE_DB_SCHEMA);
+        for name in ["swc_core", "printpdf", "tokio", "regex", "serde_json"] {
+            assert!(database.wasm_kb(name).is_some(), "{} not measured", name);
+        }
+    }
+
+    #[test]
+    fn test_update_saves_download_and_rejects_unknown_schema() {
+        let temp = tempfile::tempdir().unwrap();
+        let updater = SizeDbUpdater::with_executors(
+            temp.path(),
+            RealFileSystem,
+            FakeCurl(
+                r#"{"schema": 1, "updated": "2027-01-01", "crates": {"acme": {"version": "2.0", "wasm_kb": 77}}}"#,
+            ),
+        );
+
+        let update = updater.update(SIZE_DB_URL).unwrap();
+        assert_eq!(update.previous_updated, SizeDatabase::bundled().updated);
+        assert_eq!(update.updated, "2027-01-01");
+        assert_eq!(update.crates, 1);
+        let loaded = SizeDatabase::load(temp.path());
+        assert_eq!(loaded.wasm_kb("acme"), Some(77));
+        assert_eq!(loaded.wasm_kb("regex"), None);
+
+        let newer = SizeDbUpdater::with_executors(
+            temp.path(),
+            RealFileSystem,
+            FakeCurl(r#"{"schema": 2, "updated": "2028-01-01", "crates": {}}"#),
+        );
+        let err = newer.update(SIZE_DB_URL).unwrap_err();
+        assert!(format!("{:#}", err).contains("Unsupported size database schema 2"));
+        assert_eq!(SizeDatabase::load(temp.path()).updated, "2027-01-01");
+    }
+}
@@ -0,0 +1,296 @@
+//! wasm-snip patterns from dead code analysis
+//!
+//! The dead analysis lists functions no root reaches. wasm-snip can replace
+//! such functions with a single `unreachable`, but it matches the names in
+//! the `name` section, which are still mangled, while reports show them
+//! demangled. This module turns the unreachable functions into anchored
+//! wasm-snip patterns over the original symbols, ready to paste into a
+//! build script, and can run wasm-snip on a scratch copy to measure what
+//! snipping them actually saves.
+//!
+//! Functions without a name cannot be targeted by wasm-snip and are only
+//! counted.
+
+use super::twiggy::native::ItemGraph;
+use crate::infra::{CommandExecutor, RealCommandExecutor};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Bytes left of a snipped body (size, locals, `unreachable`, `end`)
+const SNIPPED_BODY_BYTES: u64 = 4;
+
+/// An unreachable function wasm-snip can remove
+#[derive(Debug, Clone, Serialize)]
+pub struct SnipCandidate {
+    /// Demangled name, as shown by the dead analysis
+    pub name: String,
+    /// Symbol in the `name` section, as wasm-snip sees it
+    pub symbol: String,
+    /// Function body size in bytes
+    pub size_bytes: u64,
+}
+
+/// Sizes before and after running wasm-snip
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SnipMeasurement {
+    /// Module size before snipping
+    pub before_bytes: u64,
+    /// Module size after snipping
+    pub after_bytes: u64,
+}
+
+impl SnipMeasurement {
+    /// Bytes saved by snipping
+    pub fn savings_bytes(&self) -> u64 {
+        self.before_bytes.saturating_sub(self.after_bytes)
+    }
+}
+
+/// wasm-snip patterns covering the unreachable functions of a module
+#[derive(Debug, Clone, Serialize)]
+pub struct SnipPlan {
+    /// Analyzed WASM file
+    pub wasm_file: PathBuf,
+    /// Unreachable named functions, largest first
+    pub candidates: Vec<SnipCandidate>,
+    /// One anchored wasm-snip pattern per candidate
+    pub patterns: Vec<String>,
+    /// Unreachable functions without a name (not snippable by name)
+    pub unnamed: usize,
+    /// Estimated bytes saved by snipping every candidate
+    pub estimated_savings_bytes: u64,
+    /// Measured sizes, when wasm-snip was run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub measured: Option<SnipMeasurement>,
+}
+
+impl SnipPlan {
+    /// Shell command snipping every candidate from the module in place
+    pub fn command(&self) -> String {
+        let file = shell_quote(&self.wasm_file.display().to_string());
+        let mut command = format!("wasm-snip {} -o {}", file, file);
+        for pattern in &self.patterns {
+            command.push_str(&format!(" \\\n  -p {}", shell_quote(pattern)));
+        }
+        command
+    }
+}
+
+/// Builds wasm-snip plans and measures them
+pub struct SnipPlanner<CE: CommandExecutor = RealCommandExecutor> {
+    cmd_executor: CE,
+}
+
+impl SnipPlanner {
+    /// Create a planner using the system `wasm-snip`
+    pub fn new() -> Self {
+        Self::with_executor(RealCommandExecutor)
+    }
+}
+
+impl Default for SnipPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CE: CommandExecutor> SnipPlanner<CE> {
+    /// Create a planner with a custom command executor
+    pub fn with_executor(cmd_executor: CE) -> Self {
+        Self { cmd_executor }
+    }
+
+    /// Patterns for the unreachable functions of a WASM file
+    pub fn plan(&self, wasm_file: &Path) -> Result<SnipPlan> {
+        let graph = ItemGraph::read(wasm_file)
+            .with_context(|| format!("Failed to parse {}", wasm_file.display()))?;
+        Ok(plan_graph(&graph, wasm_file))
+    }
+
+    /// Run wasm-snip with the plan's patterns on a scratch copy and record
+    /// the sizes
+    pub fn measure(&self, plan: &mut SnipPlan) -> Result<()> {
+        let before_bytes = std::fs::metadata(&plan.wasm_file)
+            .with_context(|| format!("Failed to read {}", plan.wasm_file.display()))?
+            .len();
+        if plan.patterns.is_empty() {
+            plan.measured = Some(SnipMeasurement {
+                before_bytes,
+                after_bytes: before_bytes,
+            });
+            return Ok(());
+        }
+
+        let output = plan.wasm_file.with_extension("snip.tmp.wasm");
+        let status = self
+            .cmd_executor
+            .run(
+                |cmd| {
+                    cmd.arg(&plan.wasm_file).arg("-o").arg(&output);
+                    for pattern in &plan.patterns {
+                        cmd.arg("-p").arg(pattern);
+                    }
+                    cmd
+                },
+                "wasm-snip",
+            )
+            .context("Failed to run wasm-snip (install with: cargo install wasm-snip)")?;
+        let after = std::fs::metadata(&output).map(|m| m.len());
+        let _ = std::fs::remove_file(&output);
+        if !status.success() {
+            anyhow::bail!("wasm-snip failed with {}", status);
+        }
+
+        plan.measured = Some(SnipMeasurement {
+            before_bytes,
+            after_bytes: after.context("wasm-snip wrote no output")?,
+        });
+        Ok(())
+    }
+}
+
+/// Plan snipping the unreachable functions of a parsed module
+fn plan_graph(graph: &ItemGraph, wasm_file: &Path) -> SnipPlan {
+    let reachable = graph.reachable_avoiding(&[]);
+    let mut unnamed = 0;
+    let mut candidates = Vec::new();
+    for (id, &reached) in reachable.iter().enumerate() {
+        if reached || !graph.is_code(id) || graph.size_bytes(id) == 0 {
+            continue;
+        }
+        match graph.symbol(id) {
+            Some(symbol) => candidates.push(SnipCandidate {
+                name: graph.name(id).to_string(),
+                symbol: symbol.to_string(),
+                size_bytes: graph.size_bytes(id),
+            }),
+            None => unnamed += 1,
+        }
+    }
+    candidates.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.name.cmp(&b.name)));
+
+    // Instantiations can share a symbol; one pattern covers them all
+    let mut patterns: Vec<String> = candidates
+        .iter()
+        .map(|c| format!("^{}$", regex::escape(&c.symbol)))
+        .collect();
+    patterns.sort();
+    patterns.dedup();
+
+    SnipPlan {
+        wasm_file: wasm_file.to_path_buf(),
+        estimated_savings_bytes: candidates
+            .iter()
+            .map(|c| c.size_bytes.saturating_sub(SNIPPED_BODY_BYTES))
+            .sum(),
+        candidates,
+        patterns,
+        unnamed,
+        measured: None,
+    }
+}
+
+/// Quote a word for POSIX shells
+fn shell_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{leb, section, string, HEADER};
+    use std::process::{Command, ExitStatus, Output};
+
+    /// `main` (exported) calls `used`; `dead` and an unnamed function are
+    /// unreachable
+    fn module() -> Vec<u8> {
+        let mut wasm = HEADER.to_vec();
+        wasm.extend(section(1, &[1, 0x60, 0, 0]));
+        wasm.extend(section(3, &[4, 0, 0, 0, 0]));
+
+        let mut exports = vec![1];
+        exports.extend(string("main"));
+        exports.extend([0, 0]);
+        wasm.extend(section(7, &exports));
+
+        let bodies: [&[u8]; 4] = [
+            &[0, 0x10, 1, 0x0b],
+            &[0, 0x0b],
+            &[0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0x0b],
+            &[0, 1, 0x0b],
+        ];
+        let mut code = vec![bodies.len() as u8];
+        for body in bodies {
+            code.extend(leb(body.len()));
+            code.extend(body);
+        }
+        wasm.extend(section(10, &code));
+
+        let names = ["main", "used", "_ZN3app4dead17h0123456789abcdefE"];
+        let mut function_names = leb(names.len());
+        for (index, name) in names.iter().enumerate() {
+            function_names.extend(leb(index));
+            function_names.extend(string(name));
+        }
+        let mut name_section = string("name");
+        name_section.extend(section(1, &function_names));
+        wasm.extend(section(0, &name_section));
+        wasm
+    }
+
+    /// Executor standing in for wasm-snip: writes a 10-byte module to `-o`
+    struct FakeSnip;
+
+    impl CommandExecutor for FakeSnip {
+        fn status(&self, cmd: &mut Command) -> std::io::Result<ExitStatus> {
+            let args: Vec<_> = cmd.get_args().collect();
+            let output = args
+                .iter()
+                .position(|a| *a == "-o")
+                .map(|i| PathBuf::from(args[i + 1]))
+                .unwrap();
+            std::fs::write(output, [0; 10])?;
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, _cmd: &mut Command) -> std::io::Result<Output> {
+            unreachable!("wasm-snip is run for its status")
+        }
+    }
+
+    #[test]
+    fn test_plan_patterns_match_mangled_symbols_of_dead_functions() {
+        let wasm = module();
+        let graph = ItemGraph::parse(&wasm).unwrap();
+
+        let plan = plan_graph(&graph, Path::new("app.wasm"));
+
+        assert_eq!(plan.candidates.len(), 1);
+        assert_eq!(plan.candidates[0].name, "app::dead");
+        assert_eq!(plan.candidates[0].size_bytes, 12);
+        assert_eq!(plan.patterns, ["^_ZN3app4dead17h0123456789abcdefE$"]);
+        assert_eq!(plan.unnamed, 1);
+        assert_eq!(plan.estimated_savings_bytes, 8);
+        assert_eq!(
+            plan.command(),
+            "wasm-snip 'app.wasm' -o 'app.wasm' \\\n  -p '^_ZN3app4dead17h0123456789abcdefE$'"
+        );
+    }
+
+    #[test]
+    fn test_measure_runs_wasm_snip_on_a_scratch_copy() {
+        let temp = tempfile::tempdir().unwrap();
+        let wasm_file = temp.path().join("app.wasm");
+        std::fs::write(&wasm_file, module()).unwrap();
+        let planner = SnipPlanner::with_executor(FakeSnip);
+
+        let mut plan = planner.plan(&wasm_file).unwrap();
+        planner.measure(&mut plan).unwrap();
+
+        let measured = plan.measured.unwrap();
+        assert_eq!(measured.before_bytes, module().len() as u64);
+        assert_eq!(measured.after_bytes, 10);
+        assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,115 @@
+//! wasm-snip plan report formatting
+
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use super::snip::SnipPlan;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of functions listed by default
+const DEFAULT_TOP_FUNCTIONS: usize = 20;
+
+/// Format the snip plan for console output
+pub fn format_console_report(plan: &SnipPlan) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(plan, ItemLimit::Default)
+}
+
+/// Format the snip plan, limiting the functions listed
+///
+/// The build step always lists every pattern so it can be pasted as-is.
+pub fn format_console_report_with_limit(
+    plan: &SnipPlan,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("wasm-snip Plan").bold().underlined())?;
+    if plan.candidates.is_empty() {
+        writeln!(
+            output,
+            "\n{}",
+            style("✨ No unreachable named functions to snip").green()
+        )?;
+    } else {
+        writeln!(
+            output,
+            "\n   {} unreachable functions, ~{} to save",
+            style(plan.candidates.len()).cyan().bold(),
+            style(format_bytes(plan.estimated_savings_bytes)).yellow()
+        )?;
+
+        writeln!(output, "\n{}", style("FUNCTIONS:").bold())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        let shown = limit.visible(plan.candidates.len(), Some(DEFAULT_TOP_FUNCTIONS));
+        for candidate in plan.candidates.iter().take(shown) {
+            writeln!(
+                output,
+                "  {:>12}  {}",
+                format_bytes(candidate.size_bytes),
+                truncate_str(&candidate.name, 80)
+            )?;
+        }
+        if let Some(footer) = omitted_footer(plan.candidates.len(), shown, "functions") {
+            writeln!(output, "\n  {}", style(footer).dim())?;
+        }
+
+        writeln!(output, "\n{}", style("BUILD STEP:").bold())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        writeln!(output, "{}", plan.command())?;
+    }
+
+    if plan.unnamed > 0 {
+        writeln!(
+            output,
+            "\n   {} {} unreachable functions have no name and cannot be snipped by pattern",
+            style("⚠").yellow(),
+            plan.unnamed
+        )?;
+    }
+
+    if let Some(measured) = plan.measured {
+        writeln!(
+            output,
+            "\n   Measured: {} → {} ({} saved)",
+            format_bytes(measured.before_bytes),
+            format_bytes(measured.after_bytes),
+            style(format_bytes(measured.savings_bytes())).green().bold()
+        )?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::snip::{SnipCandidate, SnipMeasurement};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_console_report_lists_functions_and_build_step() {
+        let candidate = |name: &str, size_bytes| SnipCandidate {
+            name: name.to_string(),
+            symbol: format!("_ZN{}", name),
+            size_bytes,
+        };
+        let plan = SnipPlan {
+            wasm_file: PathBuf::from("app.wasm"),
+            candidates: vec![candidate("app::big", 2048), candidate("app::small", 64)],
+            patterns: vec!["^_ZNapp::big$".to_string(), "^_ZNapp::small$".to_string()],
+            unnamed: 3,
+            estimated_savings_bytes: 2104,
+            measured: Some(SnipMeasurement {
+                before_bytes: 4096,
+                after_bytes: 2048,
+            }),
+        };
+
+        let text = format_console_report_with_limit(&plan, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("2 unreachable functions"));
+        assert!(text.contains("app::big"));
+        assert!(!text.contains("  app::small"));
+        assert!(text.contains("-p '^_ZNapp::small$'"));
+        assert!(text.contains("3 unreachable functions have no name"));
+        assert!(text.contains("4.00 KiB → 2.00 KiB (2.00 KiB saved)"));
+    }
+}
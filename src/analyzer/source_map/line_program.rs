@@ -0,0 +1,544 @@
+//! DWARF `.debug_line` decoding
+//!
+//! Runs the line-number program of every unit (DWARF 2 through 5) and
+//! turns consecutive rows into address spans. Addresses in WASM DWARF are
+//! offsets into the code section.
+
+use anyhow::{bail, ensure, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// `DW_LNCT_path`
+const LNCT_PATH: u64 = 0x1;
+/// `DW_LNCT_directory_index`
+const LNCT_DIRECTORY_INDEX: u64 = 0x2;
+
+/// DWARF sections the line program reads
+#[derive(Debug, Default)]
+pub(super) struct Sections<'a> {
+    pub debug_line: &'a [u8],
+    pub debug_str: &'a [u8],
+    pub debug_line_str: &'a [u8],
+}
+
+/// Code range described by one line table row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct LineSpan {
+    /// First code section offset
+    pub start: u64,
+    /// Offset past the end
+    pub end: u64,
+    /// Index into [`LineTable::files`]
+    pub file: usize,
+    /// Source line (0 for compiler-generated code)
+    pub line: u64,
+}
+
+/// Decoded line tables of all units
+#[derive(Debug, Default)]
+pub(super) struct LineTable {
+    /// Source file paths, deduplicated across units
+    pub files: Vec<String>,
+    /// Spans in program order
+    pub spans: Vec<LineSpan>,
+}
+
+/// Decode every unit of `.debug_line`
+pub(super) fn parse(sections: &Sections) -> Result<LineTable> {
+    let mut table = LineTable::default();
+    let mut file_ids = HashMap::new();
+    let mut reader = Reader::new(sections.debug_line);
+    while !reader.is_empty() {
+        parse_unit(&mut reader, sections, &mut table, &mut file_ids)?;
+    }
+    Ok(table)
+}
+
+/// Registers of the line-number state machine
+struct State {
+    address: u64,
+    file: u64,
+    line: u64,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            address: 0,
+            file: 1,
+            line: 1,
+        }
+    }
+}
+
+fn parse_unit(
+    reader: &mut Reader,
+    sections: &Sections,
+    table: &mut LineTable,
+    file_ids: &mut HashMap<String, usize>,
+) -> Result<()> {
+    let mut unit_length = u64::from(reader.u32()?);
+    let is_64 = unit_length == 0xffff_ffff;
+    if is_64 {
+        unit_length = reader.u64()?;
+    }
+    let unit_end = reader.end_of(unit_length)?;
+
+    let version = reader.u16()?;
+    ensure!(
+        (2..=5).contains(&version),
+        "unsupported line table version {}",
+        version
+    );
+    if version >= 5 {
+        reader.u8()?; // address size, also implied by DW_LNE_set_address
+        reader.u8()?; // segment selector size
+    }
+    let header_length = reader.offset(is_64)?;
+    let program_start = reader.end_of(header_length)?;
+
+    let min_instruction_length = u64::from(reader.u8()?);
+    if version >= 4 {
+        reader.u8()?; // maximum operations per instruction
+    }
+    reader.u8()?; // default is_stmt
+    let line_base = i64::from(reader.u8()? as i8);
+    let line_range = reader.u8()?;
+    ensure!(line_range > 0, "line table has a line range of 0");
+    let opcode_base = reader.u8()?;
+    let mut standard_lengths = Vec::new();
+    for _ in 1..opcode_base {
+        standard_lengths.push(reader.u8()?);
+    }
+
+    let strings = Strings { sections, is_64 };
+    let mut files = if version >= 5 {
+        read_v5_files(reader, &strings)?
+    } else {
+        read_legacy_files(reader)?
+    };
+
+    let mut program = Reader::new(&reader.data[..unit_end]);
+    program.seek(program_start);
+    let mut state = State::new();
+    let mut rows: Vec<(u64, u64, u64)> = Vec::new();
+    while !program.is_empty() {
+        let opcode = program.u8()?;
+        if opcode >= opcode_base {
+            let adjusted = opcode - opcode_base;
+            state.address += u64::from(adjusted / line_range) * min_instruction_length;
+            state.line = state
+                .line
+                .wrapping_add_signed(line_base + i64::from(adjusted % line_range));
+            rows.push((state.address, state.file, state.line));
+            continue;
+        }
+        match opcode {
+            0 => {
+                let length = program.uleb()?;
+                let next = program.end_of(length)?;
+                match program.u8()? {
+                    // DW_LNE_end_sequence
+                    1 => {
+                        rows.push((state.address, state.file, state.line));
+                        push_sequence(&rows, &files, table, file_ids);
+                        rows.clear();
+                        state = State::new();
+                    }
+                    // DW_LNE_set_address
+                    2 => state.address = program.uint(u8::try_from(length.saturating_sub(1))?)?,
+                    // DW_LNE_define_file (directory ignored; obsolete since DWARF 5)
+                    3 => files.push(program.cstr()?),
+                    _ => {}
+                }
+                program.seek(next);
+            }
+            // DW_LNS_copy
+            1 => rows.push((state.address, state.file, state.line)),
+            // DW_LNS_advance_pc
+            2 => state.address += program.uleb()? * min_instruction_length,
+            // DW_LNS_advance_line
+            3 => state.line = state.line.wrapping_add_signed(program.sleb()?),
+            // DW_LNS_set_file
+            4 => state.file = program.uleb()?,
+            // DW_LNS_const_add_pc
+            8 => {
+                state.address +=
+                    u64::from((255 - opcode_base) / line_range) * min_instruction_length
+            }
+            // DW_LNS_fixed_advance_pc
+            9 => state.address += u64::from(program.u16()?),
+            _ => {
+                let operands = standard_lengths
+                    .get(usize::from(opcode) - 1)
+                    .copied()
+                    .unwrap_or(0);
+                for _ in 0..operands {
+                    program.uleb()?;
+                }
+            }
+        }
+    }
+
+    reader.seek(unit_end);
+    Ok(())
+}
+
+/// Turn the rows of one sequence into spans
+fn push_sequence(
+    rows: &[(u64, u64, u64)],
+    files: &[String],
+    table: &mut LineTable,
+    file_ids: &mut HashMap<String, usize>,
+) {
+    for pair in rows.windows(2) {
+        let (start, file, line) = pair[0];
+        let end = pair[1].0;
+        if end <= start {
+            continue;
+        }
+        let path = usize::try_from(file).ok().and_then(|i| files.get(i));
+        let Some(path) = path.filter(|p| !p.is_empty()) else {
+            continue;
+        };
+        let file = *file_ids.entry(path.clone()).or_insert_with(|| {
+            table.files.push(path.clone());
+            table.files.len() - 1
+        });
+        table.spans.push(LineSpan {
+            start,
+            end,
+            file,
+            line,
+        });
+    }
+}
+
+/// Directory and file tables of DWARF 2-4 (file indices start at 1)
+fn read_legacy_files(reader: &mut Reader) -> Result<Vec<String>> {
+    let mut dirs = vec![String::new()];
+    loop {
+        let dir = reader.cstr()?;
+        if dir.is_empty() {
+            break;
+        }
+        dirs.push(dir);
+    }
+
+    let mut files = vec![String::new()];
+    loop {
+        let name = reader.cstr()?;
+        if name.is_empty() {
+            break;
+        }
+        let dir = reader.uleb()?;
+        reader.uleb()?; // modification time
+        reader.uleb()?; // length
+        let dir = usize::try_from(dir)
+            .ok()
+            .and_then(|i| dirs.get(i))
+            .map_or("", String::as_str);
+        files.push(join_path(dir, &name));
+    }
+    Ok(files)
+}
+
+/// Directory and file tables of DWARF 5 (file indices start at 0)
+fn read_v5_files(reader: &mut Reader, strings: &Strings) -> Result<Vec<String>> {
+    let dirs: Vec<String> = read_v5_entries(reader, strings)?
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    Ok(read_v5_entries(reader, strings)?
+        .into_iter()
+        .map(|(name, dir)| {
+            let dir = usize::try_from(dir)
+                .ok()
+                .and_then(|i| dirs.get(i))
+                .map_or("", String::as_str);
+            join_path(dir, &name)
+        })
+        .collect())
+}
+
+/// One DWARF 5 entry table as (path, directory index) pairs
+fn read_v5_entries(reader: &mut Reader, strings: &Strings) -> Result<Vec<(String, u64)>> {
+    let format_count = reader.u8()?;
+    let mut formats = Vec::new();
+    for _ in 0..format_count {
+        formats.push((reader.uleb()?, reader.uleb()?));
+    }
+
+    let count = reader.uleb()?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let mut path = String::new();
+        let mut dir = 0;
+        for &(content, form) in &formats {
+            match (content, read_form(reader, form, strings)?) {
+                (LNCT_PATH, FormValue::Str(value)) => path = value,
+                (LNCT_DIRECTORY_INDEX, FormValue::Int(value)) => dir = value,
+                _ => {}
+            }
+        }
+        entries.push((path, dir));
+    }
+    Ok(entries)
+}
+
+enum FormValue {
+    Str(String),
+    Int(u64),
+    Other,
+}
+
+/// String sections referenced by `strp` forms
+struct Strings<'a> {
+    sections: &'a Sections<'a>,
+    is_64: bool,
+}
+
+fn read_form(reader: &mut Reader, form: u64, strings: &Strings) -> Result<FormValue> {
+    Ok(match form {
+        // DW_FORM_string
+        0x08 => FormValue::Str(reader.cstr()?),
+        // DW_FORM_strp
+        0x0e => FormValue::Str(string_at(
+            strings.sections.debug_str,
+            reader.offset(strings.is_64)?,
+        )?),
+        // DW_FORM_line_strp
+        0x1f => FormValue::Str(string_at(
+            strings.sections.debug_line_str,
+            reader.offset(strings.is_64)?,
+        )?),
+        // DW_FORM_data1, data2, data4, data8
+        0x0b => FormValue::Int(u64::from(reader.u8()?)),
+        0x05 => FormValue::Int(u64::from(reader.u16()?)),
+        0x06 => FormValue::Int(u64::from(reader.u32()?)),
+        0x07 => FormValue::Int(reader.u64()?),
+        // DW_FORM_udata
+        0x0f => FormValue::Int(reader.uleb()?),
+        // DW_FORM_data16 (MD5 checksums)
+        0x1e => {
+            reader.skip(16)?;
+            FormValue::Other
+        }
+        // DW_FORM_block
+        0x09 => {
+            let length = reader.uleb()?;
+            reader.skip(length)?;
+            FormValue::Other
+        }
+        _ => bail!("unsupported DWARF form 0x{:x} in line table header", form),
+    })
+}
+
+/// Null-terminated string at `offset` in a string section
+fn string_at(section: &[u8], offset: u64) -> Result<String> {
+    let mut reader = Reader::new(section);
+    reader.seek(usize::try_from(offset)?);
+    reader.cstr()
+}
+
+/// Join a directory and a file name unless the name is already absolute
+fn join_path(dir: &str, name: &str) -> String {
+    if dir.is_empty() || Path::new(name).is_absolute() || name.starts_with('/') {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir.trim_end_matches(['/', '\\']), name)
+    }
+}
+
+/// Little-endian cursor over a DWARF section
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn seek(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    /// Position `length` bytes ahead, checked against the data
+    fn end_of(&self, length: u64) -> Result<usize> {
+        let end = usize::try_from(length)
+            .ok()
+            .and_then(|length| self.pos.checked_add(length))
+            .filter(|&end| end <= self.data.len());
+        match end {
+            Some(end) => Ok(end),
+            None => bail!("truncated .debug_line"),
+        }
+    }
+
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8]> {
+        let end = self.end_of(count as u64)?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, count: u64) -> Result<()> {
+        self.pos = self.end_of(count)?;
+        Ok(())
+    }
+
+    fn uint(&mut self, size: u8) -> Result<u64> {
+        ensure!(size <= 8, "unsupported address size {}", size);
+        Ok(self
+            .bytes(usize::from(size))?
+            .iter()
+            .rev()
+            .fold(0, |value, &byte| (value << 8) | u64::from(byte)))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(self.uint(2)? as u16)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(self.uint(4)? as u32)
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        self.uint(8)
+    }
+
+    /// Section offset, 8 bytes wide in 64-bit DWARF
+    fn offset(&mut self, is_64: bool) -> Result<u64> {
+        self.uint(if is_64 { 8 } else { 4 })
+    }
+
+    fn uleb(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            if shift < 64 {
+                value |= u64::from(byte & 0x7f) << shift;
+            }
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn sleb(&mut self) -> Result<i64> {
+        let mut value = 0i64;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            if shift < 64 {
+                value |= i64::from(byte & 0x7f) << shift;
+            }
+            shift += 7;
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    value |= -1 << shift;
+                }
+                return Ok(value);
+            }
+        }
+    }
+
+    fn cstr(&mut self) -> Result<String> {
+        let rest = &self.data[self.pos.min(self.data.len())..];
+        let Some(len) = rest.iter().position(|&b| b == 0) else {
+            bail!("unterminated string in .debug_line");
+        };
+        self.pos += len + 1;
+        Ok(String::from_utf8_lossy(&rest[..len]).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A DWARF 4 unit with two files and three rows
+    fn debug_line_v4() -> Vec<u8> {
+        let mut header = vec![1, 1, 1, 0xfb, 14, 13];
+        header.extend([0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1]);
+        header.extend(b"/home/me/app/src\0\0");
+        header.extend(b"lib.rs\0\x01\0\0");
+        header.extend(b"/rustc/abc/library/core/src/fmt/mod.rs\0\0\0\0");
+        header.push(0);
+
+        let program = [
+            0x00, 0x05, 0x02, 0x10, 0x00, 0x00, 0x00, // set_address 0x10
+            0x03, 0x09, // advance_line 9 -> 10
+            0x01, // copy
+            132,  // special: address += 8, line += 2
+            0x02, 0x18, // advance_pc 0x18 -> 0x30
+            0x04, 0x02, // set_file 2
+            0x03, 0x08, // advance_line 8 -> 20
+            0x01, // copy
+            0x02, 0x10, // advance_pc 0x10 -> 0x40
+            0x00, 0x01, 0x01, // end_sequence
+        ];
+
+        let mut unit = 4u16.to_le_bytes().to_vec();
+        unit.extend((header.len() as u32).to_le_bytes());
+        unit.extend(header);
+        unit.extend(program);
+        let mut section = (unit.len() as u32).to_le_bytes().to_vec();
+        section.extend(unit);
+        section
+    }
+
+    #[test]
+    fn test_parse_turns_rows_into_spans() {
+        let debug_line = debug_line_v4();
+        let sections = Sections {
+            debug_line: &debug_line,
+            ..Default::default()
+        };
+
+        let table = parse(&sections).unwrap();
+        assert_eq!(
+            table.files,
+            vec![
+                "/home/me/app/src/lib.rs",
+                "/rustc/abc/library/core/src/fmt/mod.rs"
+            ]
+        );
+        let spans: Vec<(u64, u64, usize, u64)> = table
+            .spans
+            .iter()
+            .map(|s| (s.start, s.end, s.file, s.line))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (0x10, 0x18, 0, 10),
+                (0x18, 0x30, 0, 12),
+                (0x30, 0x40, 1, 20)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated_units() {
+        let debug_line = debug_line_v4();
+        let sections = Sections {
+            debug_line: &debug_line[..debug_line.len() - 4],
+            ..Default::default()
+        };
+        assert!(parse(&sections).is_err());
+    }
+}
@@ -0,0 +1,353 @@
+//! Source-file size attribution from DWARF line info
+//!
+//! Reads the `.debug_line` section of a debug build and attributes every
+//! byte of the code section to the source file and line it was compiled
+//! from. Within each function, the lines a file contributes form a line
+//! range, so heavy modules can be narrowed down to the functions (or
+//! inlined code) that make them heavy.
+//!
+//! Line tables only exist in builds with debug info; `wasm-opt` and
+//! `strip = true` drop them, so point this at the module before either runs.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::SourceMapAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = SourceMapAnalyzer::new(".")
+//!     .analyze(Path::new("target/wasm32-unknown-unknown/debug/app.wasm"))?;
+//! for file in &report.files {
+//!     println!("{} {} bytes ({:.1}%)", file.path, file.size_bytes, file.percentage);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+mod line_program;
+
+use super::report_utils::SizedEntry;
+use anyhow::{Context, Result};
+use line_program::{LineTable, Sections};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use wasmparser::{Parser, Payload};
+
+/// Where a source file comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceOrigin {
+    /// The project's own sources
+    Project,
+    /// A crate from the cargo registry or a git checkout
+    Dependency,
+    /// The Rust standard library
+    Toolchain,
+}
+
+/// Lines of one file that a single function's code comes from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LineRange {
+    /// First line
+    pub first_line: u64,
+    /// Last line
+    pub last_line: u64,
+    /// Code bytes generated from these lines
+    pub size_bytes: u64,
+}
+
+/// Code size attributed to one source file
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceFileSize {
+    /// Path relative to the project, registry checkout or `library/`
+    pub path: String,
+    /// Where the file comes from
+    pub origin: SourceOrigin,
+    /// Code bytes generated from the file
+    pub size_bytes: u64,
+    /// Share of the code section
+    pub percentage: f64,
+    /// Line ranges per function, largest first
+    pub ranges: Vec<LineRange>,
+}
+
+impl SizedEntry for SourceFileSize {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.percentage)
+    }
+
+    fn name(&self) -> &str {
+        &self.path
+    }
+}
+
+/// Source-level size breakdown of a module's code
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceMapReport {
+    /// Size of the code section
+    pub code_bytes: u64,
+    /// Files, largest first
+    pub files: Vec<SourceFileSize>,
+    /// Code without line info (compiler-generated or stripped)
+    pub unattributed_bytes: u64,
+    /// Share of the code section without line info
+    pub unattributed_percentage: f64,
+}
+
+impl SourceMapReport {
+    /// Total size of the files from one origin
+    pub fn origin_bytes(&self, origin: SourceOrigin) -> u64 {
+        self.files
+            .iter()
+            .filter(|f| f.origin == origin)
+            .map(|f| f.size_bytes)
+            .sum()
+    }
+}
+
+/// Attributes code size to source files using DWARF line tables
+pub struct SourceMapAnalyzer {
+    project_root: PathBuf,
+}
+
+impl SourceMapAnalyzer {
+    /// Create an analyzer; project paths are shown relative to `project_root`
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Break a module's code size down per source file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<SourceMapReport> {
+        let bytes = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+
+        let mut sections = Sections::default();
+        let mut code_start = 0;
+        let mut code_bytes = 0;
+        let mut functions = Vec::new();
+        for payload in Parser::new(0).parse_all(&bytes) {
+            match payload.with_context(|| format!("Failed to parse {}", wasm_file.display()))? {
+                Payload::CodeSectionStart { range, .. } => {
+                    code_start = range.start;
+                    code_bytes = range.len() as u64;
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let range = body.range();
+                    functions
+                        .push((range.start - code_start) as u64..(range.end - code_start) as u64);
+                }
+                Payload::CustomSection(reader) => match reader.name() {
+                    ".debug_line" => sections.debug_line = reader.data(),
+                    ".debug_str" => sections.debug_str = reader.data(),
+                    ".debug_line_str" => sections.debug_line_str = reader.data(),
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+
+        if sections.debug_line.is_empty() {
+            anyhow::bail!(
+                "{} has no DWARF line info; analyze a debug build (`debug = true`) before it is optimized or stripped",
+                wasm_file.display()
+            );
+        }
+        let table = line_program::parse(&sections).with_context(|| {
+            format!(
+                "Failed to decode DWARF line info in {}",
+                wasm_file.display()
+            )
+        })?;
+
+        Ok(attribute(
+            &table,
+            &functions,
+            code_bytes,
+            &self.project_root,
+        ))
+    }
+}
+
+/// Group line spans per file, and per function within each file
+///
+/// Spans at offset 0 or past the code section belong to functions the
+/// linker discarded and are skipped, as are spans without a line.
+fn attribute(
+    table: &LineTable,
+    functions: &[Range<u64>],
+    code_bytes: u64,
+    project_root: &Path,
+) -> SourceMapReport {
+    // file -> function -> (bytes, first line, last line)
+    let mut by_file: HashMap<usize, HashMap<usize, (u64, u64, u64)>> = HashMap::new();
+    for span in &table.spans {
+        if span.line == 0 || span.start == 0 || span.end > code_bytes {
+            continue;
+        }
+        let function = functions.partition_point(|f| f.end <= span.start);
+        if functions.get(function).is_none_or(|f| f.start > span.start) {
+            continue;
+        }
+        let entry = by_file
+            .entry(span.file)
+            .or_default()
+            .entry(function)
+            .or_insert((0, span.line, span.line));
+        entry.0 += span.end - span.start;
+        entry.1 = entry.1.min(span.line);
+        entry.2 = entry.2.max(span.line);
+    }
+
+    let percentage = |bytes: u64| {
+        if code_bytes == 0 {
+            0.0
+        } else {
+            bytes as f64 / code_bytes as f64 * 100.0
+        }
+    };
+
+    let mut files: Vec<SourceFileSize> = by_file
+        .into_iter()
+        .map(|(file, per_function)| {
+            let mut ranges: Vec<LineRange> = per_function
+                .into_values()
+                .map(|(size_bytes, first_line, last_line)| LineRange {
+                    first_line,
+                    last_line,
+                    size_bytes,
+                })
+                .collect();
+            ranges.sort_by(|a, b| {
+                b.size_bytes
+                    .cmp(&a.size_bytes)
+                    .then_with(|| a.first_line.cmp(&b.first_line))
+            });
+            let size_bytes = ranges.iter().map(|r| r.size_bytes).sum();
+            let (origin, path) = classify(&table.files[file], project_root);
+            SourceFileSize {
+                path,
+                origin,
+                size_bytes,
+                percentage: percentage(size_bytes),
+                ranges,
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let attributed: u64 = files.iter().map(|f| f.size_bytes).sum();
+    let unattributed_bytes = code_bytes.saturating_sub(attributed);
+    SourceMapReport {
+        code_bytes,
+        files,
+        unattributed_bytes,
+        unattributed_percentage: percentage(unattributed_bytes),
+    }
+}
+
+/// Origin of a source path and the path to display for it
+fn classify(path: &str, project_root: &Path) -> (SourceOrigin, String) {
+    let normalized = path.replace('\\', "/");
+    if normalized.starts_with("/rustc/") || normalized.contains("/rustlib/src/rust/") {
+        if let Some(index) = normalized.find("/library/") {
+            let relative = &normalized[index + "/library/".len()..];
+            return (SourceOrigin::Toolchain, relative.to_string());
+        }
+    }
+    if let Some(index) = normalized.find("/.cargo/registry/src/") {
+        // Skip the registry index directory (`index.crates.io-<hash>/`)
+        let rest = &normalized[index + "/.cargo/registry/src/".len()..];
+        let relative = rest.split_once('/').map_or(rest, |(_, path)| path);
+        return (SourceOrigin::Dependency, relative.to_string());
+    }
+    if let Some(index) = normalized.find("/.cargo/git/checkouts/") {
+        let relative = &normalized[index + "/.cargo/git/checkouts/".len()..];
+        return (SourceOrigin::Dependency, relative.to_string());
+    }
+
+    let relative = Path::new(path)
+        .strip_prefix(project_root)
+        .map_or_else(|_| normalized.clone(), |p| p.display().to_string());
+    (SourceOrigin::Project, relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::line_program::LineSpan;
+    use super::*;
+
+    fn span(start: u64, end: u64, file: usize, line: u64) -> LineSpan {
+        LineSpan {
+            start,
+            end,
+            file,
+            line,
+        }
+    }
+
+    #[test]
+    fn test_attribute_groups_spans_per_file_and_function() {
+        let table = LineTable {
+            files: vec![
+                "/home/me/app/src/lib.rs".to_string(),
+                "/rustc/abc/library/core/src/fmt/mod.rs".to_string(),
+                "/home/me/.cargo/registry/src/index.crates.io-6f17/serde-1.0.0/src/de.rs"
+                    .to_string(),
+            ],
+            spans: vec![
+                span(2, 10, 0, 12),
+                span(10, 30, 1, 400),
+                span(30, 40, 0, 15),
+                span(41, 61, 0, 40),
+                span(61, 71, 2, 7),
+                span(71, 80, 0, 0),
+                // Discarded function
+                span(0, 50, 2, 9),
+            ],
+        };
+        let functions = vec![2..40, 41..80];
+
+        let report = attribute(&table, &functions, 100, Path::new("/home/me/app"));
+        let files: Vec<(&str, SourceOrigin, u64)> = report
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.origin, f.size_bytes))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("src/lib.rs", SourceOrigin::Project, 38),
+                ("core/src/fmt/mod.rs", SourceOrigin::Toolchain, 20),
+                ("serde-1.0.0/src/de.rs", SourceOrigin::Dependency, 10),
+            ]
+        );
+        assert_eq!(
+            report.files[0].ranges,
+            vec![
+                LineRange {
+                    first_line: 40,
+                    last_line: 40,
+                    size_bytes: 20
+                },
+                LineRange {
+                    first_line: 12,
+                    last_line: 15,
+                    size_bytes: 18
+                },
+            ]
+        );
+        assert_eq!(report.unattributed_bytes, 32);
+        assert_eq!(report.origin_bytes(SourceOrigin::Project), 38);
+    }
+}
@@ -0,0 +1,130 @@
+//! Source-file size report formatting
+
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use super::source_map::{SourceMapReport, SourceOrigin};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of files listed by default
+const DEFAULT_TOP_FILES: usize = 25;
+/// Line ranges listed under each file
+const RANGES_PER_FILE: usize = 3;
+
+/// Format the source file breakdown for console output
+pub fn format_console_report(report: &SourceMapReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the source file breakdown, limiting the files listed
+pub fn format_console_report_with_limit(
+    report: &SourceMapReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Size by Source File").bold().underlined()
+    )?;
+    writeln!(
+        output,
+        "\n   {} of code in {} files",
+        style(format_bytes(report.code_bytes)).yellow(),
+        style(report.files.len()).cyan().bold()
+    )?;
+    writeln!(
+        output,
+        "   project {}, dependencies {}, toolchain {}",
+        style(format_bytes(report.origin_bytes(SourceOrigin::Project))).green(),
+        style(format_bytes(report.origin_bytes(SourceOrigin::Dependency))).cyan(),
+        style(format_bytes(report.origin_bytes(SourceOrigin::Toolchain))).dim()
+    )?;
+
+    writeln!(output, "\n  {:>10}  {:>6}  File", "Size", "%")?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+
+    let shown = limit.visible(report.files.len(), Some(DEFAULT_TOP_FILES));
+    for file in report.files.iter().take(shown) {
+        let path = truncate_str(&file.path, 56);
+        let path = match file.origin {
+            SourceOrigin::Project => style(path).green().bold(),
+            SourceOrigin::Dependency => style(path).cyan(),
+            SourceOrigin::Toolchain => style(path).dim(),
+        };
+        writeln!(
+            output,
+            "  {:>10}  {:>5.1}%  {}",
+            format_bytes(file.size_bytes),
+            file.percentage,
+            path
+        )?;
+        for range in file.ranges.iter().take(RANGES_PER_FILE) {
+            let lines = if range.first_line == range.last_line {
+                format!("line {}", range.first_line)
+            } else {
+                format!("lines {}-{}", range.first_line, range.last_line)
+            };
+            writeln!(
+                output,
+                "  {:>10}          {}",
+                style(format_bytes(range.size_bytes)).dim(),
+                style(lines).dim()
+            )?;
+        }
+    }
+    if let Some(footer) = omitted_footer(report.files.len(), shown, "files") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+
+    if report.unattributed_bytes > 0 {
+        writeln!(
+            output,
+            "  {:>10}  {:>5.1}%  {}",
+            format_bytes(report.unattributed_bytes),
+            report.unattributed_percentage,
+            style("(code without line info)").dim()
+        )?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::source_map::{LineRange, SourceFileSize};
+
+    #[test]
+    fn test_format_console_report_lists_files_with_line_ranges() {
+        let file = |path: &str, origin, size_bytes| SourceFileSize {
+            path: path.to_string(),
+            origin,
+            size_bytes,
+            percentage: size_bytes as f64 / 40.96,
+            ranges: vec![LineRange {
+                first_line: 10,
+                last_line: 42,
+                size_bytes,
+            }],
+        };
+        let report = SourceMapReport {
+            code_bytes: 4096,
+            files: vec![
+                file("src/render.rs", SourceOrigin::Project, 2048),
+                file("core/src/fmt/mod.rs", SourceOrigin::Toolchain, 1024),
+            ],
+            unattributed_bytes: 1024,
+            unattributed_percentage: 25.0,
+        };
+
+        let text = format_console_report_with_limit(&report, ItemLimit::Max(1)).unwrap();
+        assert!(text.contains("4.00 KiB of code in 2 files"));
+        assert!(text.contains("project 2.00 KiB"));
+        assert!(text.contains("src/render.rs"));
+        assert!(text.contains("lines 10-42"));
+        assert!(!text.contains("core/src/fmt/mod.rs"));
+        assert!(text.contains("1 more files"));
+        assert!(text.contains("(code without line info)"));
+    }
+}
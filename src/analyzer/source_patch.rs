@@ -0,0 +1,93 @@
+//! Unified diffs for source rewrites the analyzers suggest
+//!
+//! Analyzers that can rewrite code (asset externalization, panic fixes)
+//! write a patch for the user to review and `git apply` rather than editing
+//! sources in place.
+
+use std::collections::BTreeMap;
+
+/// Lines of context around each change
+const PATCH_CONTEXT: usize = 3;
+
+/// Unified diff for one file, replacing each line index in `replacements`
+/// with the given lines
+///
+/// `display` is the path written in the `---`/`+++` headers (relative to the
+/// directory the patch is applied from). Returns an empty string when there
+/// is nothing to replace.
+pub fn unified_diff(
+    display: &str,
+    lines: &[&str],
+    replacements: &BTreeMap<usize, Vec<String>>,
+) -> String {
+    if replacements.is_empty() {
+        return String::new();
+    }
+
+    // Group changes whose context would overlap into one hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in replacements.keys() {
+        let start = i.saturating_sub(PATCH_CONTEXT);
+        let end = (i + PATCH_CONTEXT + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut patch = format!("--- a/{}\n+++ b/{}\n", display, display);
+    let mut offset: isize = 0;
+    for (start, end) in hunks {
+        let mut body = String::new();
+        let mut new_len = 0;
+        for (i, line) in lines.iter().enumerate().take(end).skip(start) {
+            match replacements.get(&i) {
+                Some(replacement) => {
+                    body.push_str(&format!("-{}\n", line));
+                    for new_line in replacement {
+                        body.push_str(&format!("+{}\n", new_line));
+                    }
+                    new_len += replacement.len();
+                }
+                None => {
+                    body.push_str(&format!(" {}\n", line));
+                    new_len += 1;
+                }
+            }
+        }
+        let old_len = end - start;
+        patch.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n{}",
+            start + 1,
+            old_len,
+            (start as isize + 1 + offset),
+            new_len,
+            body
+        ));
+        offset += new_len as isize - old_len as isize;
+    }
+    patch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_merges_nearby_changes_and_offsets_hunks() {
+        let lines: Vec<String> = (1..=20).map(|i| format!("line {}", i)).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let replacements = BTreeMap::from([
+            (1, vec!["one".to_string(), "more".to_string()]),
+            (4, vec!["four".to_string()]),
+            (15, vec!["sixteen".to_string()]),
+        ]);
+
+        let patch = unified_diff("src/lib.rs", &lines, &replacements);
+
+        assert!(patch.starts_with("--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,8 +1,9 @@\n"));
+        assert!(patch.contains("@@ -13,7 +14,7 @@\n"));
+        assert!(patch.contains("-line 2\n+one\n+more\n"));
+        assert_eq!(unified_diff("src/lib.rs", &lines, &BTreeMap::new()), "");
+    }
+}
@@ -0,0 +1,391 @@
+//! Instantiation cost analysis
+//!
+//! Before a module can run anything, the engine copies every active data
+//! segment into linear memory and calls the start function; wasm-bindgen
+//! apps also run `__wbindgen_start` (`#[wasm_bindgen(start)]`) from the JS
+//! glue right after instantiation. All of that happens before first paint.
+//! This module measures the code reachable from those entry points through
+//! direct calls, the active and passive data, and the initial memory, and
+//! suggests moving work out of the startup path.
+
+use super::report_utils::SizedEntry;
+use super::twiggy::native::{demangle, function_names};
+use crate::fmt::format_bytes;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use wasmparser::{DataKind, ExternalKind, Operator, Parser, Payload, TypeRef};
+
+/// Exports run by the embedder right after instantiation
+const INIT_EXPORTS: &[&str] = &["__wbindgen_start", "_initialize", "__wasm_call_ctors"];
+
+/// Startup code above this size is worth moving off the startup path
+const LARGE_STARTUP_CODE_BYTES: u64 = 16 * 1024;
+
+/// Active data above this size noticeably delays instantiation
+const LARGE_ACTIVE_DATA_BYTES: u64 = 1024 * 1024;
+
+/// Zero bytes in active segments worth packing away
+const ZERO_DATA_BYTES: u64 = 4 * 1024;
+
+/// Initial memory above this size is worth lowering
+const LARGE_INITIAL_MEMORY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// WASM page size
+const PAGE_BYTES: u64 = 64 * 1024;
+
+/// How an entry point is run at startup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    /// The module's start section
+    StartSection,
+    /// An export the embedder calls after instantiation
+    Export(String),
+}
+
+/// A function run at startup
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupEntry {
+    /// How the function is run
+    pub kind: EntryKind,
+    /// Function index
+    pub function_index: u32,
+    /// Demangled function name, if the module has a `name` section
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Body size of the function itself
+    pub body_bytes: u64,
+    /// Defined functions reachable through direct calls, itself included
+    pub reachable_functions: usize,
+    /// Total body size of those functions
+    pub reachable_bytes: u64,
+}
+
+impl SizedEntry for StartupEntry {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.reachable_bytes)
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+}
+
+/// Data copied into memory at instantiation
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DataInitCost {
+    /// Number of active segments
+    pub active_segments: usize,
+    /// Bytes in active segments
+    pub active_bytes: u64,
+    /// Zero bytes in active segments (memory starts zeroed)
+    pub active_zero_bytes: u64,
+    /// Size of the largest active segment
+    pub largest_active_bytes: u64,
+    /// Number of passive segments
+    pub passive_segments: usize,
+    /// Bytes in passive segments
+    pub passive_bytes: u64,
+}
+
+/// Complete instantiation cost results
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupReport {
+    /// Module size in bytes
+    pub module_bytes: u64,
+    /// Functions run at startup
+    pub entries: Vec<StartupEntry>,
+    /// Data segment initialization
+    pub data: DataInitCost,
+    /// Initial size of the first memory, if the module defines one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_memory_bytes: Option<u64>,
+    /// Suggested changes, most important first
+    pub recommendations: Vec<String>,
+}
+
+/// Measures what a module does at instantiation
+pub struct StartupAnalyzer;
+
+impl StartupAnalyzer {
+    /// Create an analyzer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Measure the instantiation cost of a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<StartupReport> {
+        let module = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        inspect_module(&module).with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+}
+
+impl Default for StartupAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Measure the instantiation cost of a module
+pub fn inspect_module(module: &[u8]) -> Result<StartupReport> {
+    let mut imported_funcs = 0u32;
+    let mut start = None;
+    let mut init_exports = Vec::new();
+    let mut bodies: Vec<(u64, Vec<u32>)> = Vec::new();
+    let mut names = HashMap::new();
+    let mut data = DataInitCost::default();
+    let mut initial_memory_bytes = None;
+
+    for payload in Parser::new(0).parse_all(module) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for entry in reader.into_imports_with_offsets() {
+                    let (_, import) = entry?;
+                    match import.ty {
+                        TypeRef::Func(_) | TypeRef::FuncExact(_) => imported_funcs += 1,
+                        TypeRef::Memory(memory) if initial_memory_bytes.is_none() => {
+                            initial_memory_bytes = Some(memory.initial * PAGE_BYTES);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for memory in reader {
+                    let memory = memory?;
+                    if initial_memory_bytes.is_none() {
+                        initial_memory_bytes = Some(memory.initial * PAGE_BYTES);
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    let is_func =
+                        matches!(export.kind, ExternalKind::Func | ExternalKind::FuncExact);
+                    if is_func && INIT_EXPORTS.contains(&export.name) {
+                        init_exports.push((export.name.to_string(), export.index));
+                    }
+                }
+            }
+            Payload::StartSection { func, .. } => start = Some(func),
+            Payload::CodeSectionEntry(body) => {
+                let mut calls = Vec::new();
+                let mut reader = body.get_operators_reader()?;
+                while !reader.eof() {
+                    if let Operator::Call { function_index }
+                    | Operator::ReturnCall { function_index } = reader.read()?
+                    {
+                        calls.push(function_index);
+                    }
+                }
+                bodies.push((body.range().len() as u64, calls));
+            }
+            Payload::DataSection(reader) => {
+                for segment in reader {
+                    let segment = segment?;
+                    let size = segment.data.len() as u64;
+                    match segment.kind {
+                        DataKind::Active { .. } => {
+                            data.active_segments += 1;
+                            data.active_bytes += size;
+                            data.active_zero_bytes +=
+                                segment.data.iter().filter(|&&b| b == 0).count() as u64;
+                            data.largest_active_bytes = data.largest_active_bytes.max(size);
+                        }
+                        DataKind::Passive => {
+                            data.passive_segments += 1;
+                            data.passive_bytes += size;
+                        }
+                    }
+                }
+            }
+            Payload::CustomSection(reader) => {
+                for (index, name) in function_names(&reader).into_iter().flatten() {
+                    names.insert(index, demangle(name));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let graph = CallGraph {
+        imported_funcs,
+        bodies: &bodies,
+    };
+    let mut entries = Vec::new();
+    if let Some(func) = start {
+        entries.push(graph.entry(EntryKind::StartSection, func, &names));
+    }
+    for (name, func) in init_exports {
+        entries.push(graph.entry(EntryKind::Export(name), func, &names));
+    }
+
+    let mut report = StartupReport {
+        module_bytes: module.len() as u64,
+        entries,
+        data,
+        initial_memory_bytes,
+        recommendations: Vec::new(),
+    };
+    report.recommendations = recommendations(&report);
+    Ok(report)
+}
+
+/// Direct calls between the defined functions of a module
+struct CallGraph<'a> {
+    imported_funcs: u32,
+    /// Body size and direct callees per defined function
+    bodies: &'a [(u64, Vec<u32>)],
+}
+
+impl CallGraph<'_> {
+    fn body(&self, func: u32) -> Option<&(u64, Vec<u32>)> {
+        let index = func.checked_sub(self.imported_funcs)?;
+        self.bodies.get(index as usize)
+    }
+
+    fn entry(&self, kind: EntryKind, func: u32, names: &HashMap<u32, String>) -> StartupEntry {
+        let mut seen = vec![false; self.bodies.len()];
+        let mut stack = vec![func];
+        let mut reachable_functions = 0;
+        let mut reachable_bytes = 0;
+        while let Some(func) = stack.pop() {
+            let Some(index) = func.checked_sub(self.imported_funcs) else {
+                continue;
+            };
+            let Some(&(size, ref calls)) = self.body(func) else {
+                continue;
+            };
+            if std::mem::replace(&mut seen[index as usize], true) {
+                continue;
+            }
+            reachable_functions += 1;
+            reachable_bytes += size;
+            stack.extend(calls);
+        }
+
+        StartupEntry {
+            kind,
+            function_index: func,
+            name: names.get(&func).cloned(),
+            body_bytes: self.body(func).map_or(0, |(size, _)| *size),
+            reachable_functions,
+            reachable_bytes,
+        }
+    }
+}
+
+/// Suggestions for the measured startup work
+fn recommendations(report: &StartupReport) -> Vec<String> {
+    let mut recs = Vec::new();
+    for entry in &report.entries {
+        if entry.reachable_bytes > LARGE_STARTUP_CODE_BYTES {
+            let label = entry.name.as_deref().unwrap_or("start function");
+            recs.push(format!(
+                "[P1] {} reaches {} of code at startup; initialize lazily \
+                 (std::sync::OnceLock, once_cell) or from an explicitly called init function",
+                label,
+                format_bytes(entry.reachable_bytes)
+            ));
+        }
+    }
+    let data = &report.data;
+    if data.active_bytes > LARGE_ACTIVE_DATA_BYTES {
+        recs.push(format!(
+            "[P1] {} of active data is copied into memory at instantiation; \
+             fetch large tables and assets at runtime or keep them in passive segments \
+             loaded with memory.init on first use",
+            format_bytes(data.active_bytes)
+        ));
+    }
+    if data.active_zero_bytes > ZERO_DATA_BYTES {
+        recs.push(format!(
+            "[P2] {} of active data is zeros, which fresh memory already holds; \
+             run wasm-opt with --memory-packing (on at -O levels) to drop them",
+            format_bytes(data.active_zero_bytes)
+        ));
+    }
+    if let Some(initial) = report.initial_memory_bytes {
+        if initial > LARGE_INITIAL_MEMORY_BYTES {
+            recs.push(format!(
+                "[P2] Initial memory is {}; lower it with \
+                 -C link-arg=--initial-memory=<bytes> and let the heap grow on demand",
+                format_bytes(initial)
+            ));
+        }
+    }
+    recs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{leb, section, HEADER};
+
+    /// One import; `start` (index 1) calls `init` (index 2); index 3 is
+    /// unreachable. Two active segments (one all zeros) and one passive
+    fn module(zeros: usize) -> Vec<u8> {
+        let mut wasm = HEADER.to_vec();
+        wasm.extend(section(1, &[1, 0x60, 0, 0]));
+        wasm.extend(section(2, &[1, 1, b'm', 1, b'f', 0, 0]));
+        wasm.extend(section(3, &[3, 0, 0, 0]));
+        wasm.extend(section(5, &[1, 0, 2]));
+        wasm.extend(section(8, &[1]));
+
+        let bodies: [&[u8]; 3] = [
+            &[0, 0x10, 0, 0x10, 2, 0x0b],
+            &[0, 1, 1, 1, 0x0b],
+            &[0, 1, 1, 1, 1, 1, 1, 1, 1, 0x0b],
+        ];
+        let mut code = vec![bodies.len() as u8];
+        for body in bodies {
+            code.extend(leb(body.len()));
+            code.extend(body);
+        }
+        wasm.extend(section(10, &code));
+
+        let mut data = vec![3];
+        data.extend([0, 0x41, 0, 0x0b, 4, b'a', b'b', 0, b'c']);
+        data.extend([0, 0x41, 16, 0x0b]);
+        data.extend(leb(zeros));
+        data.extend(vec![0; zeros]);
+        data.extend([1, 3, b'x', b'y', b'z']);
+        wasm.extend(section(11, &data));
+        wasm
+    }
+
+    #[test]
+    fn test_inspect_module_measures_start_and_data() {
+        let report = inspect_module(&module(8)).unwrap();
+
+        assert_eq!(report.entries.len(), 1);
+        let start = &report.entries[0];
+        assert_eq!(start.kind, EntryKind::StartSection);
+        assert_eq!(start.function_index, 1);
+        assert_eq!(start.body_bytes, 6);
+        assert_eq!(start.reachable_functions, 2);
+        assert_eq!(start.reachable_bytes, 11);
+
+        assert_eq!(report.data.active_segments, 2);
+        assert_eq!(report.data.active_bytes, 12);
+        assert_eq!(report.data.active_zero_bytes, 9);
+        assert_eq!(report.data.largest_active_bytes, 8);
+        assert_eq!(report.data.passive_segments, 1);
+        assert_eq!(report.data.passive_bytes, 3);
+        assert_eq!(report.initial_memory_bytes, Some(2 * PAGE_BYTES));
+        assert!(report.recommendations.is_empty());
+    }
+
+    #[test]
+    fn test_zero_filled_active_data_recommends_memory_packing() {
+        let report = inspect_module(&module(8 * 1024)).unwrap();
+
+        assert_eq!(report.recommendations.len(), 1);
+        assert!(report.recommendations[0].starts_with("[P2]"));
+        assert!(report.recommendations[0].contains("--memory-packing"));
+    }
+}
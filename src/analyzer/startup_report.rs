@@ -0,0 +1,128 @@
+//! Instantiation cost report formatting
+
+use super::report_utils::format_bytes;
+use super::startup::{EntryKind, StartupReport};
+use crate::fmt::percent_of;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Format the instantiation cost report for console output
+pub fn format_console_report(report: &StartupReport) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Instantiation Cost Analysis").bold().underlined()
+    )?;
+
+    writeln!(output, "\n{}", style("STARTUP CODE:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    if report.entries.is_empty() {
+        writeln!(output, "  No start function or init export")?;
+    }
+    for entry in &report.entries {
+        let kind = match &entry.kind {
+            EntryKind::StartSection => "start section".to_string(),
+            EntryKind::Export(name) => format!("export \"{}\"", name),
+        };
+        let name = entry
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("func[{}]", entry.function_index));
+        writeln!(output, "  {} → {}", kind, name)?;
+        writeln!(
+            output,
+            "    body {}, {} reachable through {} functions",
+            format_bytes(entry.body_bytes),
+            style(format_bytes(entry.reachable_bytes)).cyan().bold(),
+            entry.reachable_functions
+        )?;
+    }
+
+    let data = &report.data;
+    writeln!(output, "\n{}", style("DATA INITIALIZATION:").bold())?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+    writeln!(
+        output,
+        "  Active:  {:>12} in {} segments ({:.1}% of module, largest {})",
+        style(format_bytes(data.active_bytes)).cyan().bold(),
+        data.active_segments,
+        percent_of(data.active_bytes, report.module_bytes),
+        format_bytes(data.largest_active_bytes)
+    )?;
+    writeln!(
+        output,
+        "  Zeros:   {:>12} of active data",
+        format_bytes(data.active_zero_bytes)
+    )?;
+    writeln!(
+        output,
+        "  Passive: {:>12} in {} segments",
+        format_bytes(data.passive_bytes),
+        data.passive_segments
+    )?;
+    if let Some(initial) = report.initial_memory_bytes {
+        writeln!(output, "  Initial memory: {}", format_bytes(initial))?;
+    }
+
+    if report.recommendations.is_empty() {
+        writeln!(
+            output,
+            "\n{}",
+            style("✨ Instantiation does little work up front").green()
+        )?;
+    } else {
+        writeln!(output, "\n{}", style("RECOMMENDATIONS:").bold().yellow())?;
+        writeln!(output, "{}", style("─".repeat(70)).dim())?;
+        for rec in &report.recommendations {
+            let styled = if rec.starts_with("[P1]") {
+                style(rec).yellow().bold()
+            } else if rec.starts_with("[P2]") {
+                style(rec).blue()
+            } else {
+                style(rec).green()
+            };
+            writeln!(output, "  {}", styled)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::startup::{DataInitCost, StartupEntry};
+
+    #[test]
+    fn test_format_console_report_shows_entries_data_and_recommendations() {
+        let report = StartupReport {
+            module_bytes: 8192,
+            entries: vec![StartupEntry {
+                kind: EntryKind::Export("__wbindgen_start".to_string()),
+                function_index: 7,
+                name: None,
+                body_bytes: 64,
+                reachable_functions: 3,
+                reachable_bytes: 2048,
+            }],
+            data: DataInitCost {
+                active_segments: 2,
+                active_bytes: 4096,
+                active_zero_bytes: 1024,
+                largest_active_bytes: 3072,
+                passive_segments: 0,
+                passive_bytes: 0,
+            },
+            initial_memory_bytes: Some(1024 * 1024),
+            recommendations: vec!["[P2] run wasm-opt".to_string()],
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("export \"__wbindgen_start\" → func[7]"));
+        assert!(text.contains("2.00 KiB reachable through 3 functions"));
+        assert!(text.contains("in 2 segments (50.0% of module, largest 3.00 KiB)"));
+        assert!(text.contains("[P2] run wasm-opt"));
+    }
+}
@@ -0,0 +1,394 @@
+//! String literal bloat analysis
+//!
+//! rustc merges string constants into the data segments, where size tools
+//! only show them as part of `data[N]`. This pass extracts the runs of text,
+//! sorts them into panic messages, panic source paths, format strings, URLs
+//! and JSON keys, and groups them by prefix (the panic kind, the crate a
+//! source path belongs to, the URL host). Panic messages and source paths
+//! disappear with `panic_immediate_abort` and `-Zlocation-detail=none`, so
+//! their total is reported as the possible saving.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::StringAnalyzer;
+//! use std::path::Path;
+//!
+//! let report = StringAnalyzer::new().analyze(Path::new("pkg/app_bg.wasm"))?;
+//! println!("{} bytes of panic strings could go", report.removable_bytes);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::data_segments::text_runs;
+use super::panic_advisor;
+use super::report_utils::SizedEntry;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use wasmparser::{Parser, Payload};
+
+/// Shortest run of text counted as a string
+const MIN_STRING_BYTES: usize = 8;
+
+/// Strings sampled per group
+const GROUP_SAMPLES: usize = 3;
+
+/// Characters kept from each sampled string
+const SAMPLE_CHARS: usize = 80;
+
+/// Message fragments the standard library panics with
+const PANIC_PHRASES: [&str; 13] = [
+    "called `Option::unwrap()`",
+    "called `Result::unwrap()`",
+    "index out of bounds",
+    "attempt to ",
+    "assertion failed",
+    "explicit panic",
+    "capacity overflow",
+    "already borrowed",
+    "already mutably borrowed",
+    "entered unreachable code",
+    "out of range for slice",
+    "panicked at",
+    "misaligned pointer",
+];
+
+/// What a string constant is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StringCategory {
+    /// Panic message from the standard library or a panic macro
+    PanicMessage,
+    /// Source file path recorded for panic locations
+    SourcePath,
+    /// Format string with `{}` or `%s` placeholders
+    FormatString,
+    /// URL
+    Url,
+    /// Object keys of embedded JSON
+    JsonKey,
+    /// Anything else
+    Other,
+}
+
+impl StringCategory {
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            StringCategory::PanicMessage => "Panic messages",
+            StringCategory::SourcePath => "Panic source paths",
+            StringCategory::FormatString => "Format strings",
+            StringCategory::Url => "URLs",
+            StringCategory::JsonKey => "JSON keys",
+            StringCategory::Other => "Other text",
+        }
+    }
+
+    /// Whether aborting panics without location details removes the strings
+    pub fn removable(self) -> bool {
+        matches!(
+            self,
+            StringCategory::PanicMessage | StringCategory::SourcePath
+        )
+    }
+}
+
+/// Strings of one category sharing a prefix
+#[derive(Debug, Clone, Serialize)]
+pub struct StringGroup {
+    /// What the strings are used for
+    pub category: StringCategory,
+    /// Shared prefix: panic kind, crate of a source path or URL host
+    pub prefix: String,
+    /// Number of strings
+    pub count: usize,
+    /// Sum of their sizes
+    pub total_bytes: u64,
+    /// A few of the strings, truncated
+    pub samples: Vec<String>,
+}
+
+impl SizedEntry for StringGroup {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.total_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.prefix
+    }
+}
+
+/// String constants found in a module's data segments
+#[derive(Debug, Clone, Serialize)]
+pub struct StringReport {
+    /// Module size
+    pub module_bytes: u64,
+    /// Sum of the data segment sizes
+    pub data_bytes: u64,
+    /// Number of strings extracted
+    pub strings: usize,
+    /// Sum of the string sizes
+    pub string_bytes: u64,
+    /// Groups, largest first
+    pub groups: Vec<StringGroup>,
+    /// Bytes of panic messages and source paths
+    pub removable_bytes: u64,
+    /// Panic recommendations based on the removable strings
+    pub recommendations: Vec<String>,
+}
+
+impl StringReport {
+    /// Total size of the strings in `category`
+    pub fn category_bytes(&self, category: StringCategory) -> u64 {
+        self.groups
+            .iter()
+            .filter(|g| g.category == category)
+            .map(|g| g.total_bytes)
+            .sum()
+    }
+}
+
+/// Extracts and classifies the string constants of a module
+#[derive(Debug, Default)]
+pub struct StringAnalyzer;
+
+impl StringAnalyzer {
+    /// Create an analyzer
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Classify the string constants of a WASM file
+    pub fn analyze(&self, wasm_file: &Path) -> Result<StringReport> {
+        let bytes = std::fs::read(wasm_file)
+            .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+        analyze_module(&bytes).with_context(|| format!("Failed to parse {}", wasm_file.display()))
+    }
+}
+
+/// Classify the string constants of a module
+pub fn analyze_module(module: &[u8]) -> Result<StringReport> {
+    let mut data_bytes = 0;
+    let mut strings = 0;
+    let mut groups: HashMap<(StringCategory, String), StringGroup> = HashMap::new();
+    for payload in Parser::new(0).parse_all(module) {
+        let Payload::DataSection(reader) = payload? else {
+            continue;
+        };
+        for data in reader {
+            let data = data?;
+            data_bytes += data.data.len() as u64;
+            for text in text_runs(data.data, MIN_STRING_BYTES) {
+                strings += 1;
+                let (category, prefix) = classify(text);
+                let group =
+                    groups
+                        .entry((category, prefix.clone()))
+                        .or_insert_with(|| StringGroup {
+                            category,
+                            prefix,
+                            count: 0,
+                            total_bytes: 0,
+                            samples: Vec::new(),
+                        });
+                group.count += 1;
+                group.total_bytes += text.len() as u64;
+                if group.samples.len() < GROUP_SAMPLES {
+                    group.samples.push(sample(text));
+                }
+            }
+        }
+    }
+
+    let mut groups: Vec<StringGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| {
+        b.total_bytes
+            .cmp(&a.total_bytes)
+            .then_with(|| a.prefix.cmp(&b.prefix))
+    });
+
+    let mut report = StringReport {
+        module_bytes: module.len() as u64,
+        data_bytes,
+        strings,
+        string_bytes: groups.iter().map(|g| g.total_bytes).sum(),
+        removable_bytes: groups
+            .iter()
+            .filter(|g| g.category.removable())
+            .map(|g| g.total_bytes)
+            .sum(),
+        groups,
+        recommendations: Vec::new(),
+    };
+    report.recommendations = panic_advisor::string_recommendations(
+        report.category_bytes(StringCategory::PanicMessage),
+        report.category_bytes(StringCategory::SourcePath),
+    );
+    Ok(report)
+}
+
+/// Category and group prefix of a string
+///
+/// rustc places literals back to back, so a run of text can hold several;
+/// the first matching category wins.
+fn classify(text: &str) -> (StringCategory, String) {
+    if let Some(phrase) = PANIC_PHRASES.iter().find(|p| text.contains(*p)) {
+        return (StringCategory::PanicMessage, phrase.trim().to_string());
+    }
+    if let Some(group) = source_path_group(text) {
+        return (StringCategory::SourcePath, group);
+    }
+    if let Some(start) = text.find("https://").or_else(|| text.find("http://")) {
+        let url = &text[start..];
+        let host_end = url
+            .find("://")
+            .map(|scheme| scheme + 3)
+            .and_then(|host| {
+                url[host..]
+                    .find(['/', ' ', '"', '\''])
+                    .map(|end| host + end)
+            })
+            .unwrap_or(url.len());
+        return (StringCategory::Url, url[..host_end].to_string());
+    }
+    if ["{}", "{:", "%s", "%d"].iter().any(|p| text.contains(p)) {
+        return (StringCategory::FormatString, String::new());
+    }
+    if is_json_object_text(text) {
+        return (StringCategory::JsonKey, String::new());
+    }
+    (StringCategory::Other, String::new())
+}
+
+/// Group of the first `.rs` path in `text`: the std crate, the registry
+/// crate or the first path component
+fn source_path_group(text: &str) -> Option<String> {
+    let end = text
+        .match_indices(".rs")
+        .map(|(at, _)| at + 3)
+        .find(|&end| {
+            !text[end..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric())
+        })?;
+    let start = text[..end]
+        .rfind(|c: char| c.is_whitespace() || matches!(c, '`' | '\'' | '"'))
+        .map_or(0, |at| at + 1);
+    let path = text[start..end].replace('\\', "/");
+    if !path.contains('/') {
+        return None;
+    }
+
+    let component = |rest: &str, n: usize| rest.split('/').nth(n).unwrap_or("").to_string();
+    let group = if let Some((_, rest)) = path.split_once("/library/") {
+        format!("library/{}", component(rest, 0))
+    } else if let Some((_, rest)) = path.split_once("/registry/src/") {
+        component(rest, 1)
+    } else if let Some((_, rest)) = path.split_once("/git/checkouts/") {
+        component(rest, 0)
+    } else {
+        path.split('/')
+            .find(|c| !c.is_empty())
+            .unwrap_or("")
+            .to_string()
+    };
+    Some(group)
+}
+
+/// Text with at least two `"key":` pairs
+fn is_json_object_text(text: &str) -> bool {
+    text.match_indices("\":")
+        .filter(|&(at, _)| {
+            text[..at].rfind('"').is_some_and(|open| {
+                let key = &text[open + 1..at];
+                !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'))
+            })
+        })
+        .count()
+        >= 2
+}
+
+/// String truncated to [`SAMPLE_CHARS`] characters
+fn sample(text: &str) -> String {
+    if text.chars().count() > SAMPLE_CHARS {
+        format!("{}…", text.chars().take(SAMPLE_CHARS).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Module with one passive data segment per entry
+    fn module(segments: &[&[u8]]) -> Vec<u8> {
+        let mut section = vec![segments.len() as u8];
+        for data in segments {
+            section.push(1);
+            section.push(data.len() as u8);
+            section.extend_from_slice(data);
+        }
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        module.push(11);
+        module.push(section.len() as u8);
+        module.extend(section);
+        module
+    }
+
+    #[test]
+    fn test_classify_groups_by_category_and_prefix() {
+        assert_eq!(
+            classify("called `Option::unwrap()` on a `None` value"),
+            (
+                StringCategory::PanicMessage,
+                "called `Option::unwrap()`".to_string()
+            )
+        );
+        assert_eq!(
+            classify("/rustc/90b35a6/library/core/src/fmt/mod.rs"),
+            (StringCategory::SourcePath, "library/core".to_string())
+        );
+        assert_eq!(
+            classify("/home/ci/.cargo/registry/src/index.crates.io-6f17d/serde-1.0.210/src/de.rs"),
+            (StringCategory::SourcePath, "serde-1.0.210".to_string())
+        );
+        assert_eq!(
+            classify("see https://example.com/docs/errors"),
+            (StringCategory::Url, "https://example.com".to_string())
+        );
+        assert_eq!(
+            classify("user {} logged in").0,
+            StringCategory::FormatString
+        );
+        assert_eq!(
+            classify(r#"{"name":"x","locale":"en"}"#).0,
+            StringCategory::JsonKey
+        );
+        assert_eq!(classify("plain readable words").0, StringCategory::Other);
+    }
+
+    #[test]
+    fn test_analyze_estimates_removable_panic_strings() {
+        let report = analyze_module(&module(&[
+            b"index out of bounds: the len is \0src/parser.rs\0",
+            b"\x01\x02hello plain text\0",
+        ]))
+        .unwrap();
+
+        assert_eq!(report.strings, 3);
+        assert_eq!(report.category_bytes(StringCategory::PanicMessage), 32);
+        assert_eq!(report.category_bytes(StringCategory::SourcePath), 13);
+        assert_eq!(report.removable_bytes, 45);
+        assert_eq!(report.groups[0].prefix, "index out of bounds");
+        assert!(report
+            .recommendations
+            .iter()
+            .any(|r| r.contains("location-detail=none")));
+    }
+}
@@ -0,0 +1,126 @@
+//! String literal report formatting
+
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use super::strings::StringReport;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of groups listed by default
+const DEFAULT_TOP_GROUPS: usize = 15;
+
+/// Format the string literal report for console output
+pub fn format_console_report(report: &StringReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the string literal report, limiting the groups listed
+pub fn format_console_report_with_limit(
+    report: &StringReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{}", style("String Literals").bold().underlined())?;
+    writeln!(
+        output,
+        "\n   {} strings, {} of {} data ({} module)",
+        style(report.strings).cyan().bold(),
+        format_bytes(report.string_bytes),
+        format_bytes(report.data_bytes),
+        format_bytes(report.module_bytes)
+    )?;
+    if report.groups.is_empty() {
+        writeln!(
+            output,
+            "\n   {} No string constants found in the data segments",
+            style("✓").green()
+        )?;
+        return Ok(output);
+    }
+
+    writeln!(
+        output,
+        "\n  {:>10} {:>7}  {:<20} {:<32} Sample",
+        "Size", "Strings", "Category", "Prefix"
+    )?;
+    writeln!(output, "{}", style("─".repeat(100)).dim())?;
+    let shown = limit.visible(report.groups.len(), Some(DEFAULT_TOP_GROUPS));
+    for group in report.groups.iter().take(shown) {
+        let label = if group.category.removable() {
+            style(group.category.label()).yellow()
+        } else {
+            style(group.category.label())
+        };
+        writeln!(
+            output,
+            "  {:>10} {:>7}  {:<20} {:<32} {}",
+            format_bytes(group.total_bytes),
+            group.count,
+            label,
+            truncate_str(&group.prefix, 32),
+            style(truncate_str(
+                group.samples.first().map(String::as_str).unwrap_or(""),
+                40
+            ))
+            .dim()
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.groups.len(), shown, "groups") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+
+    if report.removable_bytes > 0 {
+        writeln!(
+            output,
+            "\n{} {} of panic messages and source paths could be removed",
+            style("Estimated Savings").bold(),
+            style(format_bytes(report.removable_bytes)).green().bold()
+        )?;
+        for rec in &report.recommendations {
+            writeln!(output, "  {}", rec)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::strings::{StringCategory, StringGroup};
+
+    #[test]
+    fn test_format_console_report_lists_groups_and_savings() {
+        let report = StringReport {
+            module_bytes: 50_000,
+            data_bytes: 8_000,
+            strings: 12,
+            string_bytes: 3_000,
+            groups: vec![
+                StringGroup {
+                    category: StringCategory::PanicMessage,
+                    prefix: "index out of bounds".to_string(),
+                    count: 10,
+                    total_bytes: 2_000,
+                    samples: vec!["index out of bounds: the len is ".to_string()],
+                },
+                StringGroup {
+                    category: StringCategory::Url,
+                    prefix: "https://example.com".to_string(),
+                    count: 2,
+                    total_bytes: 1_000,
+                    samples: vec!["https://example.com/docs".to_string()],
+                },
+            ],
+            removable_bytes: 2_000,
+            recommendations: vec!["[P2] ~2 KB of panic messages".to_string()],
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("12 strings"));
+        assert!(text.contains("Panic messages"));
+        assert!(text.contains("https://example.com"));
+        assert!(text.contains("could be removed"));
+        assert!(text.contains("[P2] ~2 KB of panic messages"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Suppression of accepted findings via `.wasm-slim-ignore`
+//!
+//! Once a team has reviewed a recommendation and decided to live with it,
+//! repeating it on every CI run buries the new findings. `.wasm-slim-ignore`
+//! in the project root lists what to drop, one entry per line:
+//!
+//! ```text
+//! # Rule id, then an optional symbol or crate pattern
+//! bloat.heavy_crate regex
+//! twiggy.mono_function core::fmt::*
+//! twiggy.dead_code_*
+//! ```
+//!
+//! Both columns are globs. An entry without a subject pattern suppresses the
+//! rule everywhere; an entry with one only matches recommendations about a
+//! symbol or crate. Blank lines and `#` comments are ignored.
+
+use super::{bloat, twiggy};
+use anyhow::{Context, Result};
+use globset::{Glob, GlobMatcher};
+use std::path::Path;
+
+/// Suppression file name, looked up in the project root
+pub const IGNORE_FILE: &str = ".wasm-slim-ignore";
+
+/// A recommendation that can be matched against `.wasm-slim-ignore`
+pub trait Suppressible {
+    /// Rule id (empty when the producer did not set one)
+    fn rule(&self) -> &str;
+    /// Symbol or crate the recommendation is about
+    fn subject(&self) -> Option<&str>;
+}
+
+impl Suppressible for bloat::Recommendation {
+    fn rule(&self) -> &str {
+        &self.rule
+    }
+
+    fn subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+}
+
+impl Suppressible for twiggy::Recommendation {
+    fn rule(&self) -> &str {
+        &self.rule
+    }
+
+    fn subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+}
+
+/// One `.wasm-slim-ignore` entry
+#[derive(Debug, Clone)]
+struct Suppression {
+    rule: GlobMatcher,
+    subject: Option<GlobMatcher>,
+}
+
+impl Suppression {
+    fn matches(&self, item: &impl Suppressible) -> bool {
+        if item.rule().is_empty() || !self.rule.is_match(item.rule()) {
+            return false;
+        }
+        match &self.subject {
+            None => true,
+            Some(pattern) => item.subject().is_some_and(|s| pattern.is_match(s)),
+        }
+    }
+}
+
+/// Parsed `.wasm-slim-ignore`
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    entries: Vec<Suppression>,
+}
+
+impl Suppressions {
+    /// Load `.wasm-slim-ignore` from the project root (empty when absent)
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = project_root.join(IGNORE_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// Parse suppression entries
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut columns = line.split_whitespace();
+            let rule = columns.next().unwrap_or_default();
+            let subject = columns.next();
+            if columns.next().is_some() {
+                anyhow::bail!(
+                    "line {}: expected `<rule> [<symbol-or-crate>]`, got `{}`",
+                    index + 1,
+                    line
+                );
+            }
+            let glob = |pattern: &str| {
+                Glob::new(pattern)
+                    .map(|g| g.compile_matcher())
+                    .with_context(|| format!("line {}: invalid pattern `{}`", index + 1, pattern))
+            };
+            entries.push(Suppression {
+                rule: glob(rule)?,
+                subject: subject.map(glob).transpose()?,
+            });
+        }
+        Ok(Self { entries })
+    }
+
+    /// Whether there are no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether `item` matches an entry
+    pub fn is_suppressed(&self, item: &impl Suppressible) -> bool {
+        self.entries.iter().any(|entry| entry.matches(item))
+    }
+
+    /// Drop suppressed recommendations, returning how many were dropped
+    pub fn filter<T: Suppressible>(&self, items: &mut Vec<T>) -> usize {
+        let before = items.len();
+        items.retain(|item| !self.is_suppressed(item));
+        before - items.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recommendation(rule: &str, subject: Option<&str>) -> bloat::Recommendation {
+        bloat::Recommendation {
+            priority: "P1".to_string(),
+            description: String::new(),
+            estimated_savings_bytes: 0,
+            estimated_savings_percent: 0.0,
+            rule: rule.to_string(),
+            subject: subject.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_filter_matches_rule_and_subject_globs() {
+        let suppressions = Suppressions::parse(
+            "# accepted\n\nbloat.heavy_crate regex   # needed for parsing\nbloat.formatting_*\n",
+        )
+        .unwrap();
+        let mut items = vec![
+            recommendation("bloat.heavy_crate", Some("regex")),
+            recommendation("bloat.heavy_crate", Some("serde_json")),
+            recommendation("bloat.formatting_code", None),
+            recommendation("bloat.large_function", Some("regex::compile")),
+            recommendation("", Some("regex")),
+        ];
+
+        assert_eq!(suppressions.filter(&mut items), 2);
+        let left: Vec<_> = items.iter().map(|r| r.rule.as_str()).collect();
+        assert_eq!(left, ["bloat.heavy_crate", "bloat.large_function", ""]);
+        assert_eq!(items[0].subject.as_deref(), Some("serde_json"));
+    }
+
+    #[test]
+    fn test_parse_rejects_extra_columns_and_bad_globs() {
+        let err = Suppressions::parse("a b c\n").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+        assert!(Suppressions::parse("ok\nbloat.[\n").is_err());
+        assert!(Suppressions::parse("  # only comments\n")
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,365 @@
+//! Attribute symbol size deltas to recent commits
+//!
+//! Groups the symbol changes of a comparison by the crate each symbol comes
+//! from, then asks git which commits since a base ref touched that crate:
+//! its source directory for workspace members, the lockfile and manifests for
+//! dependencies. The result answers "who should look at this regression"
+//! without claiming line-level precision. Toolchain crates and symbols that
+//! cannot be mapped to a crate are reported as unattributed.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::symbol_blame::SymbolBlamer;
+//! use wasm_slim::analyzer::TwiggyAnalyzer;
+//! use wasm_slim::infra::{RealCommandExecutor, RealFileSystem};
+//! use std::path::Path;
+//!
+//! let results = TwiggyAnalyzer::compare(
+//!     Path::new("main.wasm"),
+//!     Path::new("pkg/app_bg.wasm"),
+//!     &RealFileSystem,
+//!     &RealCommandExecutor,
+//! )?;
+//! let blame = SymbolBlamer::new(Path::new("."), "origin/main").blame(&results)?;
+//! for entry in &blame.crates {
+//!     println!("{} {:+} bytes, {} commits", entry.crate_name, entry.delta_bytes, entry.commits.len());
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::twiggy::ComparisonResults;
+use crate::git::{CommitInfo, GitRepository};
+use crate::infra::{CommandExecutor, RealCommandExecutor};
+use anyhow::{Context, Result};
+use cargo_metadata::MetadataCommand;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Commits kept per crate
+const MAX_COMMITS_PER_CRATE: usize = 3;
+
+/// Size delta of one crate with the commits most likely behind it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateBlame {
+    /// Crate name as it appears in symbol paths
+    pub crate_name: String,
+    /// Sum of symbol deltas in bytes
+    pub delta_bytes: i64,
+    /// Number of changed symbols
+    pub symbols: usize,
+    /// Recent commits touching the crate, newest first
+    pub commits: Vec<CommitInfo>,
+}
+
+/// Symbol deltas attributed to commits since a base ref
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolBlame {
+    /// Base git ref the comparison was made against
+    pub against: String,
+    /// Crates with attributable commits, largest absolute delta first
+    pub crates: Vec<CrateBlame>,
+    /// Delta of symbols without a crate or without commits since the ref
+    pub unattributed_bytes: i64,
+}
+
+impl SymbolBlame {
+    /// Authors ranked by the absolute delta of the crates they touched
+    pub fn suggested_reviewers(&self) -> Vec<(String, i64)> {
+        let mut by_author: HashMap<&str, i64> = HashMap::new();
+        for entry in &self.crates {
+            let mut seen = Vec::new();
+            for commit in &entry.commits {
+                if !seen.contains(&commit.author.as_str()) {
+                    seen.push(commit.author.as_str());
+                    *by_author.entry(commit.author.as_str()).or_insert(0) +=
+                        entry.delta_bytes.abs();
+                }
+            }
+        }
+
+        let mut reviewers: Vec<(String, i64)> = by_author
+            .into_iter()
+            .map(|(author, bytes)| (author.to_string(), bytes))
+            .collect();
+        reviewers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        reviewers
+    }
+}
+
+/// Attributes comparison deltas to commits with git
+pub struct SymbolBlamer<CE: CommandExecutor = RealCommandExecutor> {
+    project_root: PathBuf,
+    against: String,
+    git: GitRepository<CE>,
+}
+
+impl SymbolBlamer {
+    /// Create a blamer for commits after `against` (e.g., `origin/main`)
+    pub fn new(project_root: &Path, against: &str) -> Self {
+        Self::with_executor(project_root, against, RealCommandExecutor)
+    }
+}
+
+impl<CE: CommandExecutor> SymbolBlamer<CE> {
+    /// Create a blamer with a custom command executor for git
+    pub fn with_executor(project_root: &Path, against: &str, cmd_executor: CE) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            against: against.to_string(),
+            git: GitRepository::with_executor(cmd_executor),
+        }
+    }
+
+    /// Attribute the symbol changes of a comparison
+    pub fn blame(&self, results: &ComparisonResults) -> Result<SymbolBlame> {
+        let metadata = MetadataCommand::new()
+            .current_dir(&self.project_root)
+            .no_deps()
+            .exec()
+            .context("Failed to run cargo metadata")?;
+
+        let root = metadata.workspace_root.as_std_path();
+        let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+
+        // Dependency changes show up in the lockfile and member manifests
+        let mut dependency_sources = vec![PathBuf::from("Cargo.lock")];
+        let mut sources = HashMap::new();
+        for package in &metadata.packages {
+            let manifest = relative(package.manifest_path.as_std_path());
+            let dir = manifest.parent().map(Path::to_path_buf).unwrap_or_default();
+            let dir = if dir.as_os_str().is_empty() {
+                PathBuf::from("src")
+            } else {
+                dir
+            };
+            sources.insert(package.name.as_str().replace('-', "_"), vec![dir]);
+            dependency_sources.push(manifest);
+        }
+
+        self.blame_with_sources(results, &sources, &dependency_sources)
+    }
+
+    /// Attribute deltas given each workspace crate's source paths
+    ///
+    /// Crates not in `sources` are treated as dependencies and blamed on
+    /// commits touching `dependency_sources`.
+    pub(crate) fn blame_with_sources(
+        &self,
+        results: &ComparisonResults,
+        sources: &HashMap<String, Vec<PathBuf>>,
+        dependency_sources: &[PathBuf],
+    ) -> Result<SymbolBlame> {
+        let mut deltas: HashMap<String, (i64, usize)> = HashMap::new();
+        let mut unattributed_bytes = 0;
+        for change in &results.top_changes {
+            match crate_of_symbol(&change.name).filter(|c| !is_toolchain_crate(c)) {
+                Some(crate_name) => {
+                    let entry = deltas.entry(crate_name).or_insert((0, 0));
+                    entry.0 += change.delta_bytes;
+                    entry.1 += 1;
+                }
+                None => unattributed_bytes += change.delta_bytes,
+            }
+        }
+
+        let mut dependency_commits: Option<Vec<CommitInfo>> = None;
+        let mut crates = Vec::new();
+        for (crate_name, (delta_bytes, symbols)) in deltas {
+            let commits = match sources.get(&crate_name) {
+                Some(paths) => self.commits_touching(paths)?,
+                None => {
+                    if dependency_commits.is_none() {
+                        dependency_commits = Some(self.commits_touching(dependency_sources)?);
+                    }
+                    dependency_commits.clone().unwrap_or_default()
+                }
+            };
+
+            if commits.is_empty() {
+                unattributed_bytes += delta_bytes;
+                continue;
+            }
+            crates.push(CrateBlame {
+                crate_name,
+                delta_bytes,
+                symbols,
+                commits,
+            });
+        }
+
+        crates.sort_by(|a, b| {
+            b.delta_bytes
+                .abs()
+                .cmp(&a.delta_bytes.abs())
+                .then_with(|| a.crate_name.cmp(&b.crate_name))
+        });
+
+        Ok(SymbolBlame {
+            against: self.against.clone(),
+            crates,
+            unattributed_bytes,
+        })
+    }
+
+    fn commits_touching(&self, paths: &[PathBuf]) -> Result<Vec<CommitInfo>> {
+        let mut commits = self
+            .git
+            .log_since(&self.against, paths)
+            .with_context(|| format!("Failed to read git history since '{}'", self.against))?;
+        commits.truncate(MAX_COMMITS_PER_CRATE);
+        Ok(commits)
+    }
+}
+
+/// Crate a demangled symbol belongs to, if it has a Rust path
+///
+/// For trait impls (`<Type as Trait>::method`) the implementing type's crate
+/// is used, falling back to the trait's crate for generic or primitive types.
+pub fn crate_of_symbol(symbol: &str) -> Option<String> {
+    let symbol = symbol.trim();
+    if let Some(inner) = symbol.strip_prefix('<') {
+        let (self_type, trait_path) = match inner.split_once(" as ") {
+            Some((self_type, rest)) => (self_type, Some(rest)),
+            None => (inner, None),
+        };
+        return leading_crate(self_type).or_else(|| trait_path.and_then(leading_crate));
+    }
+    leading_crate(symbol)
+}
+
+fn leading_crate(path: &str) -> Option<String> {
+    let mut path = path.trim_start();
+    for prefix in ["&mut ", "&", "*const ", "*mut ", "dyn ", "impl ", "<"] {
+        if let Some(rest) = path.strip_prefix(prefix) {
+            path = rest.trim_start();
+        }
+    }
+
+    let (first, _) = path.split_once("::")?;
+    let valid = first
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && first.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then(|| first.to_string())
+}
+
+/// Crates shipped with the Rust toolchain; no commit in the project changes them
+pub(crate) fn is_toolchain_crate(crate_name: &str) -> bool {
+    matches!(
+        crate_name,
+        "std" | "core" | "alloc" | "compiler_builtins" | "panic_abort" | "dlmalloc"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::twiggy::ChangeItem;
+    use std::process::{Command, ExitStatus, Output};
+
+    /// Git that reports one commit per queried path
+    struct FakeGit;
+
+    impl CommandExecutor for FakeGit {
+        fn status(&self, _cmd: &mut Command) -> std::io::Result<ExitStatus> {
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, cmd: &mut Command) -> std::io::Result<Output> {
+            let args: Vec<String> = cmd
+                .get_args()
+                .map(|a| a.to_string_lossy().to_string())
+                .collect();
+            assert!(args.contains(&"main..HEAD".to_string()));
+            let stdout = if args.iter().any(|a| a == "Cargo.lock") {
+                "aaa1111\u{1f}Dep Bumper\u{1f}Add serde_json\n"
+            } else if args.iter().any(|a| a == "crates/ui") {
+                "bbb2222\u{1f}Ui Dev\u{1f}Render tables\nccc3333\u{1f}Dep Bumper\u{1f}Tweak ui\n"
+            } else {
+                ""
+            };
+            Ok(Output {
+                status: crate::infra::mock_exit_status(0),
+                stdout: stdout.as_bytes().to_vec(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    fn change(name: &str, delta_bytes: i64) -> ChangeItem {
+        ChangeItem {
+            delta_bytes,
+            name: name.to_string(),
+            before_bytes: None,
+            after_bytes: None,
+        }
+    }
+
+    #[test]
+    fn test_crate_of_symbol_handles_paths_and_trait_impls() {
+        assert_eq!(
+            crate_of_symbol("serde_json::de::from_str::h0123").as_deref(),
+            Some("serde_json")
+        );
+        assert_eq!(
+            crate_of_symbol("<ui::Table as core::fmt::Display>::fmt").as_deref(),
+            Some("ui")
+        );
+        assert_eq!(
+            crate_of_symbol("<&T as core::fmt::Debug>::fmt").as_deref(),
+            Some("core")
+        );
+        assert_eq!(crate_of_symbol("code[42]"), None);
+        assert_eq!(crate_of_symbol("data[0]"), None);
+    }
+
+    #[test]
+    fn test_blame_groups_by_crate_and_attributes_commits() {
+        let results = ComparisonResults {
+            before_size_bytes: 1000,
+            after_size_bytes: 9000,
+            delta_bytes: 8000,
+            delta_percent: 800.0,
+            top_changes: vec![
+                change("serde_json::de::from_str", 5000),
+                change("serde_json::ser::to_string", 1000),
+                change("<ui::Table as core::fmt::Display>::fmt", 1500),
+                change("legacy::old_path", 200),
+                change("core::fmt::write", 300),
+                change("data[3]", 100),
+            ],
+            symbols: Default::default(),
+            before_compressed: Default::default(),
+            after_compressed: Default::default(),
+            compressed_delta: Default::default(),
+        };
+        let sources = HashMap::from([
+            ("ui".to_string(), vec![PathBuf::from("crates/ui")]),
+            ("legacy".to_string(), vec![PathBuf::from("crates/legacy")]),
+        ]);
+
+        let blame = SymbolBlamer::with_executor(Path::new("."), "main", FakeGit)
+            .blame_with_sources(&results, &sources, &[PathBuf::from("Cargo.lock")])
+            .unwrap();
+
+        let names: Vec<&str> = blame.crates.iter().map(|c| c.crate_name.as_str()).collect();
+        assert_eq!(names, vec!["serde_json", "ui"]);
+        assert_eq!(blame.crates[0].delta_bytes, 6000);
+        assert_eq!(blame.crates[0].symbols, 2);
+        assert_eq!(blame.crates[0].commits[0].summary, "Add serde_json");
+        assert_eq!(blame.crates[1].commits.len(), 2);
+        // core, data[3] and legacy (no commits since main)
+        assert_eq!(blame.unattributed_bytes, 600);
+
+        assert_eq!(
+            blame.suggested_reviewers(),
+            vec![
+                ("Dep Bumper".to_string(), 7500),
+                ("Ui Dev".to_string(), 1500)
+            ]
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Symbol blame and PR comment formatting
+
+use super::markdown_report::{markdown_cell, markdown_code};
+use super::report_utils::{format_bytes, format_delta, omitted_footer, truncate_str, ItemLimit};
+use super::symbol_blame::SymbolBlame;
+use super::twiggy::{ComparisonResults, SizeDelta};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of crates listed by default
+const DEFAULT_TOP_CRATES: usize = 10;
+
+/// Number of symbol changes listed in a PR comment
+const PR_COMMENT_TOP_CHANGES: usize = 10;
+
+/// Format the blame section for console output
+pub fn format_console_report(blame: &SymbolBlame) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(blame, ItemLimit::Default)
+}
+
+/// Format the blame section, limiting the crates listed
+pub fn format_console_report_with_limit(
+    blame: &SymbolBlame,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{} Likely causes since {}",
+        style("🔎").bold(),
+        style(&blame.against).cyan()
+    )?;
+
+    if blame.crates.is_empty() {
+        writeln!(
+            output,
+            "   {}",
+            style("No commits since the base ref touch the changed crates").dim()
+        )?;
+    }
+
+    let shown = limit.visible(blame.crates.len(), Some(DEFAULT_TOP_CRATES));
+    for entry in blame.crates.iter().take(shown) {
+        let delta = format_delta(entry.delta_bytes);
+        writeln!(
+            output,
+            "\n   {} {} ({} symbols)",
+            style(&entry.crate_name).bold(),
+            if entry.delta_bytes < 0 {
+                style(delta).green()
+            } else {
+                style(delta).red()
+            },
+            entry.symbols
+        )?;
+        for commit in &entry.commits {
+            writeln!(
+                output,
+                "     {} {} {}",
+                style(&commit.hash).yellow(),
+                truncate_str(&commit.summary, 50),
+                style(format!("({})", commit.author)).dim()
+            )?;
+        }
+    }
+    if let Some(footer) = omitted_footer(blame.crates.len(), shown, "crates") {
+        writeln!(output, "   {}", style(footer).dim())?;
+    }
+
+    if blame.unattributed_bytes != 0 {
+        writeln!(
+            output,
+            "\n   {} {} not attributed (toolchain, unnamed items or no recent commits)",
+            style("→").dim(),
+            format_delta(blame.unattributed_bytes)
+        )?;
+    }
+
+    let reviewers = blame.suggested_reviewers();
+    if !reviewers.is_empty() {
+        let names: Vec<&str> = reviewers.iter().map(|(name, _)| name.as_str()).collect();
+        writeln!(output, "\n   Suggested reviewers: {}", names.join(", "))?;
+    }
+
+    Ok(output)
+}
+
+/// Format a comparison as a Markdown comment for a pull request
+///
+/// Includes the blame section when `blame` is given.
+pub fn format_pr_comment(results: &ComparisonResults, blame: Option<&SymbolBlame>) -> String {
+    let mut md = String::from("### wasm-slim size report\n\n");
+    md.push_str("| | Size |\n|---|---|\n");
+    md.push_str(&format!(
+        "| Before | {} |\n| After | {} |\n| Change | {} ({:+.1}%) |\n",
+        format_bytes(results.before_size_bytes),
+        format_bytes(results.after_size_bytes),
+        format_delta(results.delta_bytes),
+        results.delta_percent
+    ));
+    for (compression, before, after) in results.compressed_changes() {
+        let delta = SizeDelta::between(before, after);
+        md.push_str(&format!(
+            "| Change ({}) | {} → {} ({}, {:+.1}%) |\n",
+            compression,
+            format_bytes(before),
+            format_bytes(after),
+            format_delta(delta.bytes),
+            delta.percent
+        ));
+    }
+    let symbols = &results.symbols;
+    if !symbols.is_empty() {
+        md.push_str(&format!(
+            "| Symbols | {} added, {} removed, {} grown, {} shrunk |\n",
+            symbols.added.len(),
+            symbols.removed.len(),
+            symbols.grown.len(),
+            symbols.shrunk.len()
+        ));
+    }
+
+    for (compression, delta) in results.diverging_compressions() {
+        md.push_str(&format!(
+            "\n> **Note:** the {} size changes by {} ({:+.1}%) while the raw size changes by {} ({:+.1}%).\n",
+            compression,
+            format_delta(delta.bytes),
+            delta.percent,
+            format_delta(results.delta_bytes),
+            results.delta_percent
+        ));
+    }
+
+    if !results.top_changes.is_empty() {
+        md.push_str("\n<details><summary>Top symbol changes</summary>\n\n");
+        md.push_str("| Delta | Symbol |\n|---:|---|\n");
+        for change in results.top_changes.iter().take(PR_COMMENT_TOP_CHANGES) {
+            md.push_str(&format!(
+                "| {} | `{}` |\n",
+                format_delta(change.delta_bytes),
+                markdown_code(&truncate_str(&change.name, 80))
+            ));
+        }
+        md.push_str("\n</details>\n");
+    }
+
+    if let Some(blame) = blame {
+        md.push_str(&format!(
+            "\n#### Likely causes since `{}`\n\n",
+            markdown_code(&blame.against)
+        ));
+        if blame.crates.is_empty() {
+            md.push_str("No commits since the base ref touch the changed crates.\n");
+        } else {
+            md.push_str("| Crate | Delta | Commits |\n|---|---:|---|\n");
+            for entry in blame.crates.iter().take(DEFAULT_TOP_CRATES) {
+                let commits: Vec<String> = entry
+                    .commits
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            "{} {} ({})",
+                            c.hash,
+                            markdown_cell(&c.summary),
+                            markdown_cell(&c.author)
+                        )
+                    })
+                    .collect();
+                md.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    entry.crate_name,
+                    format_delta(entry.delta_bytes),
+                    commits.join("<br>")
+                ));
+            }
+        }
+
+        let reviewers = blame.suggested_reviewers();
+        if !reviewers.is_empty() {
+            let names: Vec<String> = reviewers
+                .iter()
+                .map(|(name, _)| markdown_cell(name))
+                .collect();
+            md.push_str(&format!("\nSuggested reviewers: {}\n", names.join(", ")));
+        }
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::symbol_blame::CrateBlame;
+    use crate::analyzer::twiggy::{ChangeItem, CompressedDelta, SymbolDiff};
+    use crate::git::CommitInfo;
+    use crate::summary::CompressedSizes;
+
+    fn blame() -> SymbolBlame {
+        SymbolBlame {
+            against: "origin/main".to_string(),
+            crates: vec![CrateBlame {
+                crate_name: "serde_json".to_string(),
+                delta_bytes: 6144,
+                symbols: 2,
+                commits: vec![CommitInfo {
+                    hash: "abc1234".to_string(),
+                    author: "Ana".to_string(),
+                    summary: "Parse config | JSON".to_string(),
+                }],
+            }],
+            unattributed_bytes: 512,
+        }
+    }
+
+    #[test]
+    fn test_format_console_report_lists_commits_and_reviewers() {
+        let text = format_console_report(&blame()).unwrap();
+        assert!(text.contains("origin/main"));
+        assert!(text.contains("serde_json"));
+        assert!(text.contains("+6.00 KiB"));
+        assert!(text.contains("abc1234"));
+        assert!(text.contains("+512 B not attributed"));
+        assert!(text.contains("Suggested reviewers: Ana"));
+    }
+
+    #[test]
+    fn test_format_pr_comment_includes_blame_table() {
+        let top_changes = vec![ChangeItem {
+            delta_bytes: 6144,
+            name: "serde_json::de::from_str".to_string(),
+            before_bytes: Some(0),
+            after_bytes: Some(6144),
+        }];
+        let results = ComparisonResults {
+            before_size_bytes: 10240,
+            after_size_bytes: 16384,
+            delta_bytes: 6144,
+            delta_percent: 60.0,
+            symbols: SymbolDiff::from_changes(&top_changes),
+            top_changes,
+            before_compressed: Default::default(),
+            after_compressed: Default::default(),
+            compressed_delta: Default::default(),
+        };
+
+        let md = format_pr_comment(&results, Some(&blame()));
+        assert!(md.contains("| Change | +6.00 KiB (+60.0%) |"));
+        assert!(md.contains("| Symbols | 1 added, 0 removed, 0 grown, 0 shrunk |"));
+        assert!(md.contains("| +6.00 KiB | `serde_json::de::from_str` |"));
+        assert!(md.contains("#### Likely causes since `origin/main`"));
+        assert!(md.contains("abc1234 Parse config \\| JSON (Ana)"));
+        assert!(md.contains("Suggested reviewers: Ana"));
+
+        let without = format_pr_comment(&results, None);
+        assert!(!without.contains("Likely causes"));
+    }
+
+    #[test]
+    fn test_format_pr_comment_includes_compressed_changes() {
+        let before_compressed = CompressedSizes {
+            gzip_bytes: Some(4096),
+            brotli_bytes: None,
+        };
+        let after_compressed = CompressedSizes {
+            gzip_bytes: Some(5120),
+            brotli_bytes: Some(4000),
+        };
+        let results = ComparisonResults {
+            before_size_bytes: 10240,
+            after_size_bytes: 16384,
+            delta_bytes: 6144,
+            delta_percent: 60.0,
+            top_changes: vec![],
+            symbols: Default::default(),
+            compressed_delta: CompressedDelta::between(&before_compressed, &after_compressed),
+            before_compressed,
+            after_compressed,
+        };
+
+        let md = format_pr_comment(&results, None);
+        assert!(md.contains("| Change (gzip) | 4.00 KiB → 5.00 KiB (+1.00 KiB, +25.0%) |"));
+        // Brotli was only measured for one build
+        assert!(!md.contains("brotli"));
+        assert!(!md.contains("**Note:**"));
+    }
+
+    #[test]
+    fn test_format_pr_comment_notes_compressed_size_moving_against_raw_size() {
+        let before_compressed = CompressedSizes {
+            gzip_bytes: Some(5120),
+            brotli_bytes: None,
+        };
+        let after_compressed = CompressedSizes {
+            gzip_bytes: Some(4096),
+            brotli_bytes: None,
+        };
+        let results = ComparisonResults {
+            before_size_bytes: 10240,
+            after_size_bytes: 11264,
+            delta_bytes: 1024,
+            delta_percent: 10.0,
+            top_changes: vec![],
+            symbols: Default::default(),
+            compressed_delta: CompressedDelta::between(&before_compressed, &after_compressed),
+            before_compressed,
+            after_compressed,
+        };
+
+        let md = format_pr_comment(&results, None);
+        assert!(md.contains("| Change (gzip) | 5.00 KiB → 4.00 KiB (-1.00 KiB, -20.0%) |"));
+        assert!(md.contains(
+            "> **Note:** the gzip size changes by -1.00 KiB (-20.0%) while the raw size changes by +1.00 KiB (+10.0%)."
+        ));
+    }
+}
@@ -0,0 +1,269 @@
+//! Symbol-size baselines and per-symbol growth budgets
+//!
+//! A baseline records the size of every named symbol in a WASM module.
+//! Checking a later build against it flags symbols that grew by more than
+//! the `[symbol_budget]` limits, catching a regression in one function even
+//! when the total size stays within budget.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::SymbolBaseline;
+//! use wasm_slim::config::file::SymbolBudget;
+//! use std::path::Path;
+//!
+//! let baseline = SymbolBaseline::load(Path::new(".wasm-slim/symbol-baseline.json"))?;
+//! let current = SymbolBaseline::capture(Path::new("pkg/app_bg.wasm"))?;
+//! let budget = SymbolBudget {
+//!     max_growth_percent: Some(10.0),
+//!     ..Default::default()
+//! };
+//! for growth in baseline.check(&current, &budget).violations {
+//!     println!("{} grew by {} bytes", growth.name, growth.growth_bytes);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::twiggy::{AnalysisItem, TwiggyAnalyzer};
+use crate::config::file::SymbolBudget;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Baseline format version
+const BASELINE_VERSION: u32 = 1;
+
+/// Symbol sizes of one build
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolBaseline {
+    /// Format version
+    pub version: u32,
+    /// Module the sizes were taken from
+    pub wasm_file: String,
+    /// Sum of all item sizes
+    pub total_bytes: u64,
+    /// Size per symbol name
+    pub symbols: BTreeMap<String, u64>,
+}
+
+/// A symbol that grew past the budget
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolGrowth {
+    /// Symbol name
+    pub name: String,
+    /// Size in the baseline (0 for new symbols)
+    pub baseline_bytes: u64,
+    /// Size in the current build
+    pub current_bytes: u64,
+    /// Growth in bytes
+    pub growth_bytes: u64,
+    /// Growth in percent of the baseline size (`None` for new symbols)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub growth_percent: Option<f64>,
+}
+
+/// Result of checking a build against a baseline
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolBudgetReport {
+    /// Total size in the baseline
+    pub baseline_total_bytes: u64,
+    /// Total size of the current build
+    pub current_total_bytes: u64,
+    /// Symbols present in both builds
+    pub compared: usize,
+    /// Symbols only in the current build
+    pub new_symbols: usize,
+    /// Symbols only in the baseline
+    pub removed_symbols: usize,
+    /// Symbols over budget, largest growth first
+    pub violations: Vec<SymbolGrowth>,
+}
+
+impl SymbolBaseline {
+    /// Build a baseline from a module's sized items
+    ///
+    /// Unnamed functions and data segments (`code[N]`, `data[N]`) are
+    /// skipped: their indices change between builds. Items sharing a name
+    /// are summed.
+    pub fn from_items(wasm_file: &str, items: &[AnalysisItem]) -> Self {
+        let mut symbols = BTreeMap::new();
+        for item in items
+            .iter()
+            .filter(|i| !i.name.starts_with("code[") && !i.name.starts_with("data["))
+        {
+            *symbols.entry(item.name.clone()).or_insert(0) += item.size_bytes;
+        }
+
+        Self {
+            version: BASELINE_VERSION,
+            wasm_file: wasm_file.to_string(),
+            total_bytes: items.iter().map(|i| i.size_bytes).sum(),
+            symbols,
+        }
+    }
+
+    /// Measure the symbols of a WASM file
+    pub fn capture(wasm_file: &Path) -> Result<Self> {
+        let items = TwiggyAnalyzer::new(wasm_file).sized_items()?;
+        Ok(Self::from_items(&wasm_file.display().to_string(), &items))
+    }
+
+    /// Read a baseline saved with [`SymbolBaseline::save`]
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read symbol baseline {}", path.display()))?;
+        let baseline: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse symbol baseline {}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            anyhow::bail!(
+                "Unsupported symbol baseline version {} in {}; save a new baseline",
+                baseline.version,
+                path.display()
+            );
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline as JSON, creating parent directories
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write symbol baseline {}", path.display()))
+    }
+
+    /// Flag symbols of `current` that grew past the budget's limits
+    pub fn check(&self, current: &SymbolBaseline, budget: &SymbolBudget) -> SymbolBudgetReport {
+        let mut violations = Vec::new();
+        let mut compared = 0;
+        let mut new_symbols = 0;
+        for (name, &current_bytes) in &current.symbols {
+            let baseline_bytes = self.symbols.get(name).copied();
+            match baseline_bytes {
+                Some(_) => compared += 1,
+                None => new_symbols += 1,
+            }
+            if current_bytes < budget.min_symbol_bytes {
+                continue;
+            }
+
+            let baseline_bytes = baseline_bytes.unwrap_or(0);
+            let growth_bytes = current_bytes.saturating_sub(baseline_bytes);
+            if growth_bytes == 0 {
+                continue;
+            }
+            let growth_percent =
+                (baseline_bytes > 0).then(|| growth_bytes as f64 / baseline_bytes as f64 * 100.0);
+
+            let over_bytes = budget
+                .max_growth_bytes
+                .is_some_and(|max| growth_bytes > max);
+            let over_percent = match (budget.max_growth_percent, growth_percent) {
+                (Some(max), Some(percent)) => percent > max,
+                _ => false,
+            };
+            if over_bytes || over_percent {
+                violations.push(SymbolGrowth {
+                    name: name.clone(),
+                    baseline_bytes,
+                    current_bytes,
+                    growth_bytes,
+                    growth_percent,
+                });
+            }
+        }
+        violations.sort_by(|a, b| {
+            b.growth_bytes
+                .cmp(&a.growth_bytes)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        SymbolBudgetReport {
+            baseline_total_bytes: self.total_bytes,
+            current_total_bytes: current.total_bytes,
+            compared,
+            new_symbols,
+            removed_symbols: self
+                .symbols
+                .keys()
+                .filter(|name| !current.symbols.contains_key(*name))
+                .count(),
+            violations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, size_bytes: u64) -> AnalysisItem {
+        AnalysisItem {
+            size_bytes,
+            percentage: 0.0,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_check_flags_symbols_over_either_limit() {
+        let baseline = SymbolBaseline::from_items(
+            "before.wasm",
+            &[
+                item("app::render", 1000),
+                item("app::parse", 100),
+                item("app::tiny", 10),
+                item("app::removed", 50),
+                item("code[3]", 400),
+            ],
+        );
+        let current = SymbolBaseline::from_items(
+            "after.wasm",
+            &[
+                item("app::render", 1050),
+                item("app::parse", 150),
+                item("app::tiny", 20),
+                item("app::new_feature", 5000),
+                item("code[3]", 900),
+            ],
+        );
+        let budget = SymbolBudget {
+            max_growth_percent: Some(10.0),
+            max_growth_bytes: Some(4096),
+            min_symbol_bytes: 64,
+            ..Default::default()
+        };
+
+        let report = baseline.check(&current, &budget);
+        let flagged: Vec<&str> = report.violations.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(flagged, vec!["app::new_feature", "app::parse"]);
+        assert_eq!(report.violations[0].growth_percent, None);
+        assert_eq!(report.violations[1].growth_percent, Some(50.0));
+        assert_eq!(
+            (report.compared, report.new_symbols, report.removed_symbols),
+            (3, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_baseline_round_trips_through_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/baseline.json");
+        let baseline = SymbolBaseline::from_items("app.wasm", &[item("app::main", 42)]);
+
+        baseline.save(&path).unwrap();
+        let loaded = SymbolBaseline::load(&path).unwrap();
+        assert_eq!(loaded.symbols, baseline.symbols);
+        assert_eq!(loaded.total_bytes, 42);
+
+        std::fs::write(
+            &path,
+            r#"{"version":99,"wasm_file":"","total_bytes":0,"symbols":{}}"#,
+        )
+        .unwrap();
+        assert!(SymbolBaseline::load(&path).is_err());
+    }
+}
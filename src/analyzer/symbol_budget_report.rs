@@ -0,0 +1,122 @@
+//! Symbol budget report formatting
+
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use super::symbol_budget::SymbolBudgetReport;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of violations listed by default
+const DEFAULT_TOP_VIOLATIONS: usize = 20;
+
+/// Format the baseline comparison for console output
+pub fn format_console_report(report: &SymbolBudgetReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the baseline comparison, limiting the violations listed
+pub fn format_console_report_with_limit(
+    report: &SymbolBudgetReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Symbol Budget vs Baseline").bold().underlined()
+    )?;
+    writeln!(
+        output,
+        "\n   {} → {}, {} symbols compared, {} new, {} removed",
+        format_bytes(report.baseline_total_bytes),
+        format_bytes(report.current_total_bytes),
+        report.compared,
+        report.new_symbols,
+        report.removed_symbols
+    )?;
+
+    if report.violations.is_empty() {
+        writeln!(
+            output,
+            "\n{}",
+            style("✓ No symbol grew past the symbol budget").green()
+        )?;
+        return Ok(output);
+    }
+
+    writeln!(
+        output,
+        "\n{}",
+        style(format!(
+            "⚠ {} symbols grew past the symbol budget:",
+            report.violations.len()
+        ))
+        .yellow()
+        .bold()
+    )?;
+    writeln!(output, "{}", style("─".repeat(70)).dim())?;
+
+    let shown = limit.visible(report.violations.len(), Some(DEFAULT_TOP_VIOLATIONS));
+    for growth in report.violations.iter().take(shown) {
+        let percent = match growth.growth_percent {
+            Some(percent) => format!("+{:.1}%", percent),
+            None => "new".to_string(),
+        };
+        writeln!(
+            output,
+            "  {:>10} → {:>10}  {:>8}  {}",
+            format_bytes(growth.baseline_bytes),
+            format_bytes(growth.current_bytes),
+            style(percent).red(),
+            truncate_str(&growth.name, 50)
+        )?;
+    }
+    if let Some(footer) = omitted_footer(report.violations.len(), shown, "symbols") {
+        writeln!(output, "  {}", style(footer).dim())?;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::symbol_budget::SymbolGrowth;
+
+    #[test]
+    fn test_format_console_report_lists_violations() {
+        let mut report = SymbolBudgetReport {
+            baseline_total_bytes: 2048,
+            current_total_bytes: 4096,
+            compared: 10,
+            new_symbols: 1,
+            removed_symbols: 0,
+            violations: vec![
+                SymbolGrowth {
+                    name: "app::new_feature".to_string(),
+                    baseline_bytes: 0,
+                    current_bytes: 1500,
+                    growth_bytes: 1500,
+                    growth_percent: None,
+                },
+                SymbolGrowth {
+                    name: "app::parse".to_string(),
+                    baseline_bytes: 100,
+                    current_bytes: 150,
+                    growth_bytes: 50,
+                    growth_percent: Some(50.0),
+                },
+            ],
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("10 symbols compared, 1 new, 0 removed"));
+        assert!(text.contains("2 symbols grew past the symbol budget"));
+        assert!(text.contains("+50.0%"));
+        assert!(text.contains("new"));
+
+        report.violations.clear();
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("No symbol grew past the symbol budget"));
+    }
+}
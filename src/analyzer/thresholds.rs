@@ -0,0 +1,300 @@
+//! Recommendation thresholds that scale with bundle size
+//!
+//! Fixed cut-offs misfire at the extremes: a 50 KB data segment is most of a
+//! tiny bundle but noise in a 20 MB one, and a single symbol easily retains
+//! 20% of a small module. Base thresholds apply at a reference size (1 MB by
+//! default) and are scaled by
+//!
+//! ```text
+//! factor = clamp((total / reference) ^ exponent, min_factor, max_factor)
+//! ```
+//!
+//! Byte thresholds are multiplied by `factor`; percentage thresholds are
+//! divided by `sqrt(factor)`. Cut-offs that already pair a byte size with a
+//! share of the bundle (bloat and asset priorities) and the instantiation
+//! count are used as configured. Values come from `[analysis.thresholds]` in
+//! `.wasm-slim.toml` (see [`ThresholdSettings`]) and are shared by
+//! `TwiggyAnalyzer`, `BloatAnalyzer` and `AssetDetector`.
+//!
+//! # Examples
+//!
+//! ```
+//! use wasm_slim::analyzer::thresholds::RecommendationThresholds;
+//!
+//! let base = RecommendationThresholds::default();
+//! let tiny = base.for_bundle(64 * 1024);
+//! assert!(tiny.large_data_segment_bytes < base.large_data_segment_bytes);
+//! assert!(tiny.dominator_percent > base.dominator_percent);
+//! ```
+
+use super::asset_types::AssetPriority;
+use crate::config::file::ThresholdSettings;
+
+/// Scaling curve applied to the base thresholds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScalingCurve {
+    /// Bundle size (bytes) at which base thresholds apply unchanged
+    pub reference_bytes: u64,
+    /// Exponent of the size ratio (0 disables scaling)
+    pub exponent: f64,
+    /// Lower bound of the scale factor
+    pub min_factor: f64,
+    /// Upper bound of the scale factor
+    pub max_factor: f64,
+}
+
+impl Default for ScalingCurve {
+    fn default() -> Self {
+        Self {
+            reference_bytes: 1024 * 1024,
+            exponent: 0.5,
+            min_factor: 0.25,
+            max_factor: 4.0,
+        }
+    }
+}
+
+impl ScalingCurve {
+    /// Scale factor for a bundle of `total_bytes`
+    pub fn factor(&self, total_bytes: u64) -> f64 {
+        if total_bytes == 0 || self.reference_bytes == 0 || self.exponent == 0.0 {
+            return 1.0;
+        }
+        let ratio = total_bytes as f64 / self.reference_bytes as f64;
+        ratio
+            .powf(self.exponent)
+            .clamp(self.min_factor, self.max_factor)
+    }
+}
+
+/// Cut-offs used when generating size recommendations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecommendationThresholds {
+    /// Data segments larger than this are flagged
+    pub large_data_segment_bytes: u64,
+    /// Dominators retaining more than this share are flagged (%)
+    pub dominator_percent: f64,
+    /// Top 20 items above this combined share are flagged (%)
+    pub top_items_percent: f64,
+    /// Dead code above this share is flagged (%)
+    pub dead_code_percent: f64,
+    /// Monomorphization share reported as significant (%)
+    pub mono_significant_percent: f64,
+    /// Monomorphization share reported as moderate (%)
+    pub mono_moderate_percent: f64,
+    /// Generic functions whose instantiations exceed this are listed
+    pub mono_function_bytes: u64,
+    /// Generic functions with at least this many instantiations are listed
+    pub mono_instantiations: usize,
+    /// Functions larger than this are flagged by cargo-bloat analysis
+    pub large_function_bytes: u64,
+    /// Functions above this share are flagged by cargo-bloat analysis (%)
+    pub large_function_percent: f64,
+    /// Crates above this share of the binary are flagged (%)
+    pub heavy_crate_percent: f64,
+    /// Formatting code above this share is flagged (%)
+    pub formatting_percent: f64,
+    /// Assets above this share of the bundle are critical (%)
+    pub asset_critical_percent: f64,
+    /// Assets larger than this are critical
+    pub asset_critical_bytes: u64,
+    /// Assets above this share of the bundle are high priority (%)
+    pub asset_high_percent: f64,
+    /// Assets larger than this are high priority
+    pub asset_high_bytes: u64,
+    /// Assets above this share of the bundle are medium priority (%)
+    pub asset_medium_percent: f64,
+    /// Assets larger than this are medium priority
+    pub asset_medium_bytes: u64,
+    /// How the thresholds scale with bundle size
+    pub curve: ScalingCurve,
+}
+
+impl Default for RecommendationThresholds {
+    fn default() -> Self {
+        Self {
+            large_data_segment_bytes: 50 * 1024,
+            dominator_percent: 20.0,
+            top_items_percent: 30.0,
+            dead_code_percent: 10.0,
+            mono_significant_percent: 15.0,
+            mono_moderate_percent: 5.0,
+            mono_function_bytes: 50 * 1024,
+            mono_instantiations: 10,
+            large_function_bytes: 50 * 1024,
+            large_function_percent: 5.0,
+            heavy_crate_percent: 20.0,
+            formatting_percent: 5.0,
+            asset_critical_percent: AssetPriority::CRITICAL_PERCENTAGE,
+            asset_critical_bytes: AssetPriority::CRITICAL_SIZE_KB * 1024,
+            asset_high_percent: AssetPriority::HIGH_PERCENTAGE,
+            asset_high_bytes: AssetPriority::HIGH_SIZE_KB * 1024,
+            asset_medium_percent: AssetPriority::MEDIUM_PERCENTAGE,
+            asset_medium_bytes: AssetPriority::MEDIUM_SIZE_KB * 1024,
+            curve: ScalingCurve::default(),
+        }
+    }
+}
+
+impl RecommendationThresholds {
+    /// Defaults overridden by `[analysis.thresholds]` settings
+    pub fn from_settings(settings: &ThresholdSettings) -> Self {
+        let defaults = Self::default();
+        let curve = ScalingCurve {
+            reference_bytes: settings
+                .reference_size_kb
+                .map_or(defaults.curve.reference_bytes, |kb| kb * 1024),
+            exponent: settings.exponent.unwrap_or(defaults.curve.exponent),
+            min_factor: settings.min_factor.unwrap_or(defaults.curve.min_factor),
+            max_factor: settings.max_factor.unwrap_or(defaults.curve.max_factor),
+        };
+
+        Self {
+            large_data_segment_bytes: settings
+                .large_data_segment_kb
+                .map_or(defaults.large_data_segment_bytes, |kb| kb * 1024),
+            dominator_percent: settings
+                .dominator_percent
+                .unwrap_or(defaults.dominator_percent),
+            top_items_percent: settings
+                .top_items_percent
+                .unwrap_or(defaults.top_items_percent),
+            dead_code_percent: settings
+                .dead_code_percent
+                .unwrap_or(defaults.dead_code_percent),
+            mono_significant_percent: settings
+                .mono_significant_percent
+                .unwrap_or(defaults.mono_significant_percent),
+            mono_moderate_percent: settings
+                .mono_moderate_percent
+                .unwrap_or(defaults.mono_moderate_percent),
+            mono_function_bytes: settings
+                .mono_function_kb
+                .map_or(defaults.mono_function_bytes, |kb| kb * 1024),
+            mono_instantiations: settings
+                .mono_instantiations
+                .unwrap_or(defaults.mono_instantiations),
+            large_function_bytes: settings
+                .large_function_kb
+                .map_or(defaults.large_function_bytes, |kb| kb * 1024),
+            large_function_percent: settings
+                .large_function_percent
+                .unwrap_or(defaults.large_function_percent),
+            heavy_crate_percent: settings
+                .heavy_crate_percent
+                .unwrap_or(defaults.heavy_crate_percent),
+            formatting_percent: settings
+                .formatting_percent
+                .unwrap_or(defaults.formatting_percent),
+            asset_critical_percent: settings
+                .asset_critical_percent
+                .unwrap_or(defaults.asset_critical_percent),
+            asset_critical_bytes: settings
+                .asset_critical_kb
+                .map_or(defaults.asset_critical_bytes, |kb| kb * 1024),
+            asset_high_percent: settings
+                .asset_high_percent
+                .unwrap_or(defaults.asset_high_percent),
+            asset_high_bytes: settings
+                .asset_high_kb
+                .map_or(defaults.asset_high_bytes, |kb| kb * 1024),
+            asset_medium_percent: settings
+                .asset_medium_percent
+                .unwrap_or(defaults.asset_medium_percent),
+            asset_medium_bytes: settings
+                .asset_medium_kb
+                .map_or(defaults.asset_medium_bytes, |kb| kb * 1024),
+            curve,
+        }
+    }
+
+    /// Thresholds scaled for a bundle of `total_bytes`
+    pub fn for_bundle(&self, total_bytes: u64) -> Self {
+        let factor = self.curve.factor(total_bytes);
+        let bytes = |base: u64| (base as f64 * factor) as u64;
+        let percent = |base: f64| (base / factor.sqrt()).min(100.0);
+
+        Self {
+            large_data_segment_bytes: bytes(self.large_data_segment_bytes),
+            dominator_percent: percent(self.dominator_percent),
+            top_items_percent: percent(self.top_items_percent),
+            dead_code_percent: percent(self.dead_code_percent),
+            mono_significant_percent: percent(self.mono_significant_percent),
+            mono_moderate_percent: percent(self.mono_moderate_percent),
+            mono_function_bytes: bytes(self.mono_function_bytes),
+            curve: self.curve,
+            ..*self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_size_keeps_base_thresholds() {
+        let base = RecommendationThresholds::default();
+        assert_eq!(base.for_bundle(1024 * 1024), base);
+    }
+
+    #[test]
+    fn test_thresholds_scale_with_bundle_size() {
+        let base = RecommendationThresholds::default();
+
+        // 64 KB bundle: factor 0.25 (clamped)
+        let tiny = base.for_bundle(64 * 1024);
+        assert_eq!(tiny.large_data_segment_bytes, 50 * 1024 / 4);
+        assert!((tiny.dominator_percent - 40.0).abs() < 1e-9);
+
+        // 32 MB bundle: factor 4.0 (clamped)
+        let huge = base.for_bundle(32 * 1024 * 1024);
+        assert_eq!(huge.large_data_segment_bytes, 200 * 1024);
+        assert!((huge.dominator_percent - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zero_exponent_disables_scaling() {
+        let settings = ThresholdSettings {
+            exponent: Some(0.0),
+            ..Default::default()
+        };
+        let thresholds = RecommendationThresholds::from_settings(&settings);
+        assert_eq!(thresholds.for_bundle(10 * 1024), thresholds);
+    }
+
+    #[test]
+    fn test_from_settings_overrides_defaults() {
+        let settings = ThresholdSettings {
+            reference_size_kb: Some(256),
+            large_data_segment_kb: Some(8),
+            dominator_percent: Some(35.0),
+            ..Default::default()
+        };
+        let thresholds = RecommendationThresholds::from_settings(&settings);
+
+        assert_eq!(thresholds.curve.reference_bytes, 256 * 1024);
+        assert_eq!(thresholds.large_data_segment_bytes, 8 * 1024);
+        assert_eq!(thresholds.dominator_percent, 35.0);
+        assert_eq!(thresholds.top_items_percent, 30.0);
+        assert_eq!(thresholds.for_bundle(256 * 1024), thresholds);
+    }
+
+    #[test]
+    fn test_bloat_and_asset_thresholds_are_not_scaled() {
+        let settings = ThresholdSettings {
+            mono_instantiations: Some(4),
+            heavy_crate_percent: Some(12.5),
+            asset_critical_kb: Some(128),
+            ..Default::default()
+        };
+        let thresholds = RecommendationThresholds::from_settings(&settings);
+        let tiny = thresholds.for_bundle(64 * 1024);
+
+        assert_eq!(tiny.mono_instantiations, 4);
+        assert_eq!(tiny.heavy_crate_percent, 12.5);
+        assert_eq!(tiny.asset_critical_bytes, 128 * 1024);
+        assert_eq!(tiny.large_function_bytes, 50 * 1024);
+        assert!(tiny.mono_function_bytes < thresholds.mono_function_bytes);
+    }
+}
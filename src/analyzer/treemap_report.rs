@@ -0,0 +1,233 @@
+//! Interactive HTML treemap of a WASM module
+//!
+//! Groups the module's sized items per crate, then per function, and
+//! renders them as a zoomable treemap in the spirit of
+//! webpack-bundle-analyzer. The page is self-contained: the data, styles
+//! and layout script are inlined, so it opens offline and can be attached
+//! to CI artifacts as a single file.
+
+use super::report_utils::{escape_html, format_bytes};
+use super::twiggy::AnalysisItem;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Node of the size treemap
+#[derive(Debug, Clone, Serialize)]
+pub struct TreemapNode {
+    /// Crate, function or item name
+    pub name: String,
+    /// Size of the node including all children
+    pub size_bytes: u64,
+    /// Children, largest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TreemapNode>,
+}
+
+/// Build the crate → function hierarchy for a module's items
+pub fn build_treemap(root_name: &str, items: &[AnalysisItem]) -> TreemapNode {
+    let mut crates: BTreeMap<String, Vec<TreemapNode>> = BTreeMap::new();
+    for item in items.iter().filter(|item| item.size_bytes > 0) {
+        crates
+            .entry(crate_group(&item.name))
+            .or_default()
+            .push(TreemapNode {
+                name: item.name.clone(),
+                size_bytes: item.size_bytes,
+                children: Vec::new(),
+            });
+    }
+
+    let mut children: Vec<TreemapNode> = crates
+        .into_iter()
+        .map(|(name, mut functions)| {
+            // Stable sort: equal sizes stay in name order
+            functions.sort_by_key(|f| std::cmp::Reverse(f.size_bytes));
+            TreemapNode {
+                name,
+                size_bytes: functions.iter().map(|f| f.size_bytes).sum(),
+                children: functions,
+            }
+        })
+        .collect();
+    children.sort_by_key(|c| std::cmp::Reverse(c.size_bytes));
+
+    TreemapNode {
+        name: root_name.to_string(),
+        size_bytes: children.iter().map(|c| c.size_bytes).sum(),
+        children,
+    }
+}
+
+/// Treemap group an item belongs to
+///
+/// Functions group under the first path segment of their demangled name
+/// (`<alloc::vec::Vec<T> as Drop>::drop` belongs to `alloc`); module
+/// structure that is not code gets a group of its own.
+fn crate_group(name: &str) -> String {
+    let group = if name.starts_with("import ") {
+        "(imports)"
+    } else if name.starts_with("export ") {
+        "(exports)"
+    } else if name.starts_with("custom section ") {
+        "(custom sections)"
+    } else if name.starts_with("data[") {
+        "(data)"
+    } else if name.starts_with("code[") {
+        "(unnamed functions)"
+    } else if ["table[", "memory[", "global[", "elem["]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+    {
+        "(module structure)"
+    } else {
+        let path = name.trim_start_matches(['<', '&', '*']);
+        let path = path
+            .strip_prefix("dyn ")
+            .or_else(|| path.strip_prefix("impl "))
+            .or_else(|| path.strip_prefix("mut "))
+            .unwrap_or(path);
+        match path.split_once("::") {
+            Some((krate, _)) if !krate.is_empty() && !krate.contains([' ', '<']) => {
+                return krate.to_string()
+            }
+            _ => "(other functions)",
+        }
+    };
+    group.to_string()
+}
+
+/// Render the treemap as a standalone HTML page
+pub fn format_html_report(root: &TreemapNode) -> Result<String, serde_json::Error> {
+    // `</script>` inside a symbol name must not end the data block
+    let data = serde_json::to_string(root)?.replace("</", "<\\/");
+
+    Ok(format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n  <meta charset=\"utf-8\">\n  <title>wasm-slim treemap: {title}</title>\n  <style>{style}</style>\n</head>\n<body>\n  <h1>{title}</h1>\n  <p>{size} in {crates} groups. Click a crate to zoom in.</p>\n  <nav id=\"crumbs\"></nav>\n  <div id=\"map\"></div>\n  <script>\nconst data = {data};\n{script}  </script>\n</body>\n</html>\n",
+        title = escape_html(&root.name),
+        style = STYLE,
+        size = format_bytes(root.size_bytes),
+        crates = root.children.len(),
+        data = data,
+        script = SCRIPT,
+    ))
+}
+
+const STYLE: &str = "body{font-family:sans-serif;margin:1em}#map{position:relative;height:80vh}#crumbs a{cursor:pointer;color:#06c}.cell{position:absolute;box-sizing:border-box;border:1px solid #fff;overflow:hidden;font-size:11px;padding:1px 3px;white-space:nowrap;text-overflow:ellipsis;cursor:pointer}.group{font-weight:bold}";
+
+const SCRIPT: &str = r#"const map = document.getElementById('map');
+const crumbs = document.getElementById('crumbs');
+// Same units as fmt::format_bytes
+function fmt(b) {
+  return b < 1024 ? b + ' B' : b < 1048576 ? (b / 1024).toFixed(2) + ' KiB' : (b / 1048576).toFixed(2) + ' MiB';
+}
+function worst(row, side) {
+  const sum = row.reduce((a, b) => a + b, 0);
+  return Math.max(side * side * Math.max(...row) / (sum * sum), sum * sum / (side * side * Math.min(...row)));
+}
+// Squarified treemap layout of nodes (largest first) into a rectangle
+function layout(nodes, x, y, w, h) {
+  const total = nodes.reduce((s, n) => s + n.size_bytes, 0);
+  const rects = [];
+  if (!total || w <= 0 || h <= 0) return rects;
+  const areas = nodes.map(n => n.size_bytes * w * h / total);
+  let i = 0;
+  while (i < nodes.length) {
+    const side = Math.min(w, h);
+    const row = [areas[i]];
+    let j = i + 1;
+    while (j < nodes.length && worst(row.concat(areas[j]), side) <= worst(row, side)) row.push(areas[j++]);
+    const thick = row.reduce((a, b) => a + b, 0) / side;
+    let offset = 0;
+    for (let k = i; k < j; k++) {
+      const len = areas[k] / thick;
+      rects.push(w >= h ? { node: nodes[k], x: x, y: y + offset, w: thick, h: len } : { node: nodes[k], x: x + offset, y: y, w: len, h: thick });
+      offset += len;
+    }
+    if (w >= h) { x += thick; w -= thick; } else { y += thick; h -= thick; }
+    i = j;
+  }
+  return rects;
+}
+function draw(node, x, y, w, h, depth, hue, zoom) {
+  layout(node.children || [], x, y, w, h).forEach((r, i) => {
+    const h2 = depth === 0 ? (i * 47) % 360 : hue;
+    const target = depth === 0 && r.node.children ? r.node : zoom;
+    const cell = document.createElement('div');
+    cell.className = r.node.children ? 'cell group' : 'cell';
+    Object.assign(cell.style, { left: r.x + 'px', top: r.y + 'px', width: r.w + 'px', height: r.h + 'px', background: 'hsl(' + h2 + ',55%,' + (r.node.children ? 72 : 86) + '%)' });
+    cell.title = r.node.name + '\n' + fmt(r.node.size_bytes) + ' (' + (r.node.size_bytes / data.size_bytes * 100).toFixed(1) + '%)';
+    if (r.w > 30 && r.h > 12) cell.textContent = r.node.name + ' ' + fmt(r.node.size_bytes);
+    if (target) cell.onclick = () => show(target);
+    map.appendChild(cell);
+    if (r.node.children && depth === 0) draw(r.node, r.x + 2, r.y + 16, r.w - 4, r.h - 18, 1, h2, target);
+  });
+}
+function show(node) {
+  map.innerHTML = '';
+  crumbs.innerHTML = '';
+  const root = document.createElement('a');
+  root.textContent = data.name;
+  root.onclick = () => show(data);
+  crumbs.appendChild(root);
+  if (node !== data) crumbs.appendChild(document.createTextNode(' / ' + node.name + ' (' + fmt(node.size_bytes) + ')'));
+  draw(node, 0, 0, map.clientWidth, map.clientHeight, node === data ? 0 : 1, 0, null);
+}
+window.onresize = () => show(data);
+show(data);
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, size_bytes: u64) -> AnalysisItem {
+        AnalysisItem {
+            size_bytes,
+            percentage: 0.0,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_treemap_groups_functions_by_crate() {
+        let items = vec![
+            item("core::fmt::write", 300),
+            item("<alloc::vec::Vec<T> as core::ops::Drop>::drop", 50),
+            item("core::panicking::panic", 200),
+            item("data[0]", 400),
+            item("import env::log", 10),
+            item("main", 20),
+            item("code[7]", 0),
+        ];
+
+        let root = build_treemap("app.wasm", &items);
+        assert_eq!(root.size_bytes, 980);
+        let groups: Vec<(&str, u64)> = root
+            .children
+            .iter()
+            .map(|c| (c.name.as_str(), c.size_bytes))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("core", 500),
+                ("(data)", 400),
+                ("alloc", 50),
+                ("(other functions)", 20),
+                ("(imports)", 10),
+            ]
+        );
+        assert_eq!(root.children[0].children[0].name, "core::fmt::write");
+    }
+
+    #[test]
+    fn test_format_html_report_inlines_escaped_data() {
+        let root = build_treemap("<app>.wasm", &[item("evil::</script>", 10)]);
+        let html = format_html_report(&root).unwrap();
+
+        assert!(html.contains("<title>wasm-slim treemap: &lt;app&gt;.wasm</title>"));
+        assert!(html.contains("evil::<\\/script>"));
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(!html.contains("src="));
+    }
+}
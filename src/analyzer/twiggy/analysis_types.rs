@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::recommendation::Recommendation;
+use crate::analyzer::report_utils::SizedEntry;
 
 /// Analysis mode for twiggy
 #[derive(Debug, Clone, Copy)]
@@ -17,6 +18,18 @@ pub enum AnalysisMode {
     Monos,
 }
 
+/// Backend computing the analysis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnalysisBackend {
+    /// Parse the module natively, falling back to the twiggy CLI if that fails
+    #[default]
+    Auto,
+    /// Parse the module natively only
+    Native,
+    /// Run the twiggy CLI only
+    Cli,
+}
+
 /// Single analysis item from twiggy output
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisItem {
@@ -28,6 +41,20 @@ pub struct AnalysisItem {
     pub name: String,
 }
 
+impl SizedEntry for AnalysisItem {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.percentage)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
 /// Grouped monomorphization analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonomorphizationGroup {
@@ -43,6 +70,23 @@ pub struct MonomorphizationGroup {
     pub instantiations: Vec<AnalysisItem>,
     /// Estimated savings if using trait objects (keeps 1 copy)
     pub potential_savings_bytes: u64,
+    /// Concrete type arguments of each instantiation, in `instantiations`
+    /// order (empty when the names carry none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub type_arguments: Vec<Vec<String>>,
+    /// Refactor suggested by the type arguments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refactor: Option<String>,
+}
+
+impl SizedEntry for MonomorphizationGroup {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.total_size_bytes)
+    }
+
+    fn name(&self) -> &str {
+        &self.function_name
+    }
 }
 
 /// Complete analysis results
@@ -54,6 +98,9 @@ pub struct AnalysisResults {
     pub mode: String,
     /// Top items
     pub items: Vec<AnalysisItem>,
+    /// Cap on the items kept while collecting (`--limit`), if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
     /// Recommendations based on findings
     pub recommendations: Vec<Recommendation>,
     /// Monomorphization groups (only populated for Monos mode)
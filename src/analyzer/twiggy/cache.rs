@@ -0,0 +1,271 @@
+//! On-disk cache of analysis results
+//!
+//! Analyzing a multi-megabyte module takes a while, and `analyze` is often
+//! run several times against the same artifact. Results are stored as JSON
+//! in `.wasm-slim/cache/`, keyed by the module's SHA-256, the analysis mode,
+//! the wasm-slim version and everything else that shapes the results: the
+//! requested backend, the backend that actually ran and its version (a
+//! fallback to the twiggy CLI is keyed by `twiggy --version`), the
+//! recommendation thresholds, the item limit and the translation catalog the
+//! recommendations were rendered with. A rebuilt module, a changed config, a
+//! twiggy upgrade or another locale misses the cache instead of reusing stale
+//! results. Cache failures are never fatal: an unreadable entry is recomputed
+//! and a failed write is ignored.
+
+use super::{AnalysisBackend, AnalysisMode, AnalysisResults};
+use crate::analyzer::thresholds::RecommendationThresholds;
+use crate::hash::sha256_hex;
+use crate::i18n::Catalog;
+use crate::infra::FileSystem;
+use std::path::{Path, PathBuf};
+
+/// Analysis cache directory, inside the state directory
+pub const ANALYSIS_CACHE_DIR: &str = "cache";
+
+/// Settings besides the module and mode that shape cached results
+pub(crate) struct CacheKey<'a> {
+    /// Backend the analysis was asked to use
+    pub requested: AnalysisBackend,
+    /// Backend that produced the results (`Native` or `Cli`)
+    pub ran: AnalysisBackend,
+    /// Version of the tool behind `ran`
+    pub tool_version: &'a str,
+    /// Thresholds the recommendations were generated with
+    pub thresholds: &'a RecommendationThresholds,
+    /// Item limit applied while analyzing
+    pub limit: Option<usize>,
+    /// Catalog the recommendations were localized with
+    pub catalog: &'a Catalog,
+}
+
+/// Cache entry for a module's bytes analyzed with the given settings
+pub(crate) fn entry_path(
+    cache_dir: &Path,
+    wasm_bytes: &[u8],
+    mode: AnalysisMode,
+    settings: &CacheKey,
+) -> PathBuf {
+    let key = format!(
+        "{}:{}:{:?}:{:?}:{}:{:?}:{:?}:{}",
+        sha256_hex(wasm_bytes),
+        env!("CARGO_PKG_VERSION"),
+        settings.requested,
+        settings.ran,
+        settings.tool_version,
+        settings.thresholds,
+        settings.limit,
+        settings.catalog.fingerprint()
+    );
+    cache_dir.join(format!(
+        "{}-{}.json",
+        format!("{:?}", mode).to_lowercase(),
+        sha256_hex(key.as_bytes())
+    ))
+}
+
+/// Cached results, if the entry exists and parses
+pub(crate) fn load<FS: FileSystem>(fs: &FS, path: &Path) -> Option<AnalysisResults> {
+    let content = fs.read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Store results, ignoring failures
+pub(crate) fn store<FS: FileSystem>(fs: &FS, path: &Path, results: &AnalysisResults) {
+    let Ok(json) = serde_json::to_string(results) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        if fs.create_dir_all(parent).is_err() {
+            return;
+        }
+    }
+    let _ = fs.write(path, json);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::TwiggyAnalyzer;
+    use crate::infra::{CommandExecutor, RealFileSystem};
+    use std::process::{Command, Output};
+    use std::sync::Mutex;
+
+    /// twiggy stand-in answering `--version` with a settable version and
+    /// anything else with a `top` listing
+    struct FakeTwiggy {
+        version: Mutex<&'static str>,
+    }
+
+    impl CommandExecutor for FakeTwiggy {
+        fn status(&self, cmd: &mut Command) -> std::io::Result<std::process::ExitStatus> {
+            self.output(cmd).map(|output| output.status)
+        }
+
+        fn output(&self, cmd: &mut Command) -> std::io::Result<Output> {
+            let stdout = if cmd.get_args().any(|arg| arg == "--version") {
+                format!("twiggy {}\n", self.version.lock().unwrap())
+            } else {
+                " Shallow Bytes │ Shallow % │ Item\n───────────────┼───────────┼─────\n           900 ┊    45.00% ┊ code[0]\n".to_string()
+            };
+            Ok(Output {
+                status: crate::infra::mock_exit_status(0),
+                stdout: stdout.into_bytes(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    fn entry_for(
+        cache_dir: &Path,
+        wasm: &[u8],
+        ran: AnalysisBackend,
+        catalog: &Catalog,
+    ) -> PathBuf {
+        entry_path(
+            cache_dir,
+            wasm,
+            AnalysisMode::Top,
+            &CacheKey {
+                requested: AnalysisBackend::Auto,
+                ran,
+                tool_version: env!("CARGO_PKG_VERSION"),
+                thresholds: &RecommendationThresholds::default(),
+                limit: None,
+                catalog,
+            },
+        )
+    }
+
+    #[test]
+    fn test_entries_are_keyed_by_translation_catalog() {
+        let temp = tempfile::tempdir().unwrap();
+        let wasm_file = temp.path().join("app.wasm");
+        let wasm = b"\0asm\x01\0\0\0".to_vec();
+        std::fs::write(&wasm_file, &wasm).unwrap();
+        let cache_dir = temp.path().join(ANALYSIS_CACHE_DIR);
+        let analyzer = TwiggyAnalyzer::new(&wasm_file).with_cache(&cache_dir);
+
+        // An English run stores its entry under the English catalog
+        let english = Catalog::english();
+        let fresh = analyzer.analyze(AnalysisMode::Top).unwrap();
+        let english_entry = entry_for(&cache_dir, &wasm, AnalysisBackend::Native, &english);
+        assert!(english_entry.exists());
+
+        // A German run keys its own entry and never replays the English one
+        let german = Catalog::from_toml_str(
+            "locale = \"de\"\n[report]\nrecommendations = \"EMPFEHLUNGEN:\"\n",
+        )
+        .unwrap();
+        let german_entry = entry_for(&cache_dir, &wasm, AnalysisBackend::Native, &german);
+        assert_ne!(german_entry, english_entry);
+        assert!(load(&RealFileSystem, &german_entry).is_none());
+        let fresh_size = fresh.total_size_bytes;
+        let mut german_results = fresh;
+        german_results.total_size_bytes = 1;
+        store(&RealFileSystem, &german_entry, &german_results);
+
+        // Switching back to English reads the English entry, not the German one
+        let again = analyzer.analyze(AnalysisMode::Top).unwrap();
+        assert_eq!(again.total_size_bytes, fresh_size);
+
+        // Editing a translation without changing the locale misses too
+        let reworded = Catalog::from_toml_str(
+            "locale = \"de\"\n[report]\nrecommendations = \"VORSCHLÄGE:\"\n",
+        )
+        .unwrap();
+        assert_ne!(
+            entry_for(&cache_dir, &wasm, AnalysisBackend::Native, &reworded),
+            german_entry
+        );
+    }
+
+    #[test]
+    fn test_cli_fallback_is_keyed_by_backend_that_ran_and_twiggy_version() {
+        let temp = tempfile::tempdir().unwrap();
+        let wasm_file = temp.path().join("app.wasm");
+        std::fs::write(&wasm_file, b"not a wasm module").unwrap();
+        let cache_dir = temp.path().join(ANALYSIS_CACHE_DIR);
+        let twiggy = FakeTwiggy {
+            version: Mutex::new("0.7.0"),
+        };
+        let analyzer = TwiggyAnalyzer::with_executors(&wasm_file, RealFileSystem, twiggy)
+            .with_cache(&cache_dir);
+
+        // The native parser rejects the module, so the CLI result is cached
+        let fresh = analyzer.analyze(AnalysisMode::Top).unwrap();
+        assert_eq!(fresh.items.len(), 1);
+        let entries: Vec<_> = std::fs::read_dir(&cache_dir).unwrap().flatten().collect();
+        assert_eq!(entries.len(), 1);
+        assert_ne!(
+            entries[0].path(),
+            entry_for(
+                &cache_dir,
+                b"not a wasm module",
+                AnalysisBackend::Native,
+                &Catalog::english()
+            )
+        );
+
+        let mut cached = fresh;
+        cached.total_size_bytes = 1;
+        std::fs::write(entries[0].path(), serde_json::to_string(&cached).unwrap()).unwrap();
+        assert_eq!(
+            analyzer
+                .analyze(AnalysisMode::Top)
+                .unwrap()
+                .total_size_bytes,
+            1
+        );
+
+        // Upgrading twiggy invalidates the entry
+        *analyzer.cmd_executor.version.lock().unwrap() = "0.8.0";
+        assert_ne!(
+            analyzer
+                .analyze(AnalysisMode::Top)
+                .unwrap()
+                .total_size_bytes,
+            1
+        );
+        assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_analyze_reuses_cached_results_until_settings_change() {
+        let temp = tempfile::tempdir().unwrap();
+        let wasm_file = temp.path().join("app.wasm");
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        wasm.extend([0, 9, 4, b'n', b'o', b't', b'e', 1, 2, 3, 4]);
+        std::fs::write(&wasm_file, &wasm).unwrap();
+        let cache_dir = temp.path().join(ANALYSIS_CACHE_DIR);
+
+        let analyzer = TwiggyAnalyzer::new(&wasm_file).with_cache(&cache_dir);
+        let fresh = analyzer.analyze(AnalysisMode::Top).unwrap();
+        assert_eq!(fresh.total_size_bytes, wasm.len() as u64);
+        let entries: Vec<_> = std::fs::read_dir(&cache_dir).unwrap().flatten().collect();
+        assert_eq!(entries.len(), 1);
+
+        // A doctored entry proves the second run reads the cache
+        let mut cached = fresh;
+        cached.total_size_bytes = 1;
+        std::fs::write(entries[0].path(), serde_json::to_string(&cached).unwrap()).unwrap();
+        assert_eq!(
+            analyzer
+                .analyze(AnalysisMode::Top)
+                .unwrap()
+                .total_size_bytes,
+            1
+        );
+
+        let thresholds = RecommendationThresholds {
+            dead_code_percent: 50.0,
+            ..Default::default()
+        };
+        let recomputed = TwiggyAnalyzer::new(&wasm_file)
+            .with_cache(&cache_dir)
+            .with_thresholds(thresholds)
+            .analyze(AnalysisMode::Top)
+            .unwrap();
+        assert_eq!(recomputed.total_size_bytes, wasm.len() as u64);
+        assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 2);
+    }
+}
@@ -1,5 +1,6 @@
 //! Comparison types for WASM file analysis
 
+use crate::summary::CompressedSizes;
 use serde::{Deserialize, Serialize};
 
 /// Comparison results between two WASM files
@@ -15,6 +16,57 @@ pub struct ComparisonResults {
     pub delta_percent: f64,
     /// Top differences
     pub top_changes: Vec<ChangeItem>,
+    /// Top differences split into added, removed, grown and shrunk symbols
+    #[serde(default)]
+    pub symbols: SymbolDiff,
+    /// Compressed sizes of the before file
+    #[serde(default)]
+    pub before_compressed: CompressedSizes,
+    /// Compressed sizes of the after file
+    #[serde(default)]
+    pub after_compressed: CompressedSizes,
+    /// Change of the compressed sizes, for compressions measured on both files
+    #[serde(default)]
+    pub compressed_delta: CompressedDelta,
+}
+
+impl ComparisonResults {
+    /// Compressed sizes measured for both builds, as `(compression, before, after)`
+    pub fn compressed_changes(&self) -> Vec<(&'static str, u64, u64)> {
+        [
+            (
+                "gzip",
+                self.before_compressed.gzip_bytes,
+                self.after_compressed.gzip_bytes,
+            ),
+            (
+                "brotli",
+                self.before_compressed.brotli_bytes,
+                self.after_compressed.brotli_bytes,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, before, after)| Some((name, before?, after?)))
+        .collect()
+    }
+
+    /// Compressions whose size tells a different story than the raw size:
+    /// moving against it, staying flat under a raw regression, or growing by
+    /// a larger share than the raw size does
+    pub fn diverging_compressions(&self) -> Vec<(&'static str, SizeDelta)> {
+        [
+            ("gzip", self.compressed_delta.gzip),
+            ("brotli", self.compressed_delta.brotli),
+        ]
+        .into_iter()
+        .filter_map(|(name, delta)| Some((name, delta?)))
+        .filter(|(_, delta)| {
+            let opposite = delta.bytes.signum() * self.delta_bytes.signum() < 0;
+            let regression = self.delta_bytes > 0;
+            opposite || (regression && (delta.bytes == 0 || delta.percent > self.delta_percent))
+        })
+        .collect()
+    }
 }
 
 /// A single change between two builds
@@ -24,4 +76,323 @@ pub struct ChangeItem {
     pub delta_bytes: i64,
     /// Symbol name
     pub name: String,
+    /// Size in the before build, when the diff reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before_bytes: Option<u64>,
+    /// Size in the after build, when the diff reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_bytes: Option<u64>,
+}
+
+impl ChangeItem {
+    /// How the symbol changed
+    ///
+    /// `twiggy diff` only reports deltas, so without both sizes a symbol
+    /// counts as grown or shrunk even if it is new or gone.
+    pub fn kind(&self) -> ChangeKind {
+        match (self.before_bytes, self.after_bytes) {
+            (Some(0), _) => ChangeKind::Added,
+            (_, Some(0)) => ChangeKind::Removed,
+            _ if self.delta_bytes > 0 => ChangeKind::Grown,
+            _ => ChangeKind::Shrunk,
+        }
+    }
+}
+
+/// How a symbol changed between two builds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Only in the after build
+    Added,
+    /// Only in the before build
+    Removed,
+    /// Larger in the after build
+    Grown,
+    /// Smaller in the after build
+    Shrunk,
+}
+
+/// Symbol changes grouped by kind, each list largest change first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolDiff {
+    /// Symbols only in the after build
+    pub added: Vec<ChangeItem>,
+    /// Symbols only in the before build
+    pub removed: Vec<ChangeItem>,
+    /// Symbols that got larger
+    pub grown: Vec<ChangeItem>,
+    /// Symbols that got smaller
+    pub shrunk: Vec<ChangeItem>,
+}
+
+impl SymbolDiff {
+    /// Group `changes`, dropping unchanged symbols
+    pub fn from_changes(changes: &[ChangeItem]) -> Self {
+        let mut diff = Self::default();
+        for change in changes.iter().filter(|c| c.delta_bytes != 0) {
+            match change.kind() {
+                ChangeKind::Added => diff.added.push(change.clone()),
+                ChangeKind::Removed => diff.removed.push(change.clone()),
+                ChangeKind::Grown => diff.grown.push(change.clone()),
+                ChangeKind::Shrunk => diff.shrunk.push(change.clone()),
+            }
+        }
+        for list in [
+            &mut diff.added,
+            &mut diff.removed,
+            &mut diff.grown,
+            &mut diff.shrunk,
+        ] {
+            list.sort_by_key(|c| std::cmp::Reverse(c.delta_bytes.unsigned_abs()));
+        }
+        diff
+    }
+
+    /// Lists with their kind, in report order
+    pub fn groups(&self) -> [(ChangeKind, &[ChangeItem]); 4] {
+        [
+            (ChangeKind::Added, &self.added),
+            (ChangeKind::Removed, &self.removed),
+            (ChangeKind::Grown, &self.grown),
+            (ChangeKind::Shrunk, &self.shrunk),
+        ]
+    }
+
+    /// Whether no symbol changed
+    pub fn is_empty(&self) -> bool {
+        self.groups().iter().all(|(_, list)| list.is_empty())
+    }
+}
+
+/// Size change between two builds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SizeDelta {
+    /// Delta in bytes (negative = reduction)
+    pub bytes: i64,
+    /// Delta in percent of the earlier build
+    pub percent: f64,
+}
+
+impl SizeDelta {
+    /// Change from `before` to `after` bytes
+    pub fn between(before: u64, after: u64) -> Self {
+        let bytes = after as i64 - before as i64;
+        let percent = if before == 0 {
+            0.0
+        } else {
+            bytes as f64 / before as f64 * 100.0
+        };
+        Self { bytes, percent }
+    }
+}
+
+/// Change of the transfer sizes between two builds
+///
+/// A raw size regression can vanish after compression, or get worse, and
+/// the compressed size is what users download.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompressedDelta {
+    /// Change after gzip at level 9
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gzip: Option<SizeDelta>,
+    /// Change after brotli at quality 11
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brotli: Option<SizeDelta>,
+}
+
+impl CompressedDelta {
+    /// Change for every compression measured on both builds
+    pub fn between(before: &CompressedSizes, after: &CompressedSizes) -> Self {
+        let delta =
+            |before: Option<u64>, after: Option<u64>| Some(SizeDelta::between(before?, after?));
+        Self {
+            gzip: delta(before.gzip_bytes, after.gzip_bytes),
+            brotli: delta(before.brotli_bytes, after.brotli_bytes),
+        }
+    }
+}
+
+/// One build of an N-way comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildDelta {
+    /// WASM file
+    pub file: String,
+    /// File size
+    pub size_bytes: u64,
+    /// Compressed sizes
+    #[serde(default)]
+    pub compressed: CompressedSizes,
+    /// Change from the previous build (`None` for the first)
+    pub from_previous: Option<SizeDelta>,
+    /// Change from the first build
+    pub from_first: SizeDelta,
+}
+
+/// Comparison of three or more builds, in the order given
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MultiComparison {
+    /// Every build with its deltas
+    pub builds: Vec<BuildDelta>,
+    /// Pairwise comparisons of consecutive builds
+    pub steps: Vec<ComparisonResults>,
+}
+
+impl MultiComparison {
+    /// Chain consecutive comparisons; `steps[i]` compares `files[i]` with `files[i + 1]`
+    pub fn from_steps(files: &[String], steps: Vec<ComparisonResults>) -> Self {
+        let mut sizes: Vec<(u64, CompressedSizes)> = Vec::new();
+        if let Some(first) = steps.first() {
+            sizes.push((first.before_size_bytes, first.before_compressed));
+        }
+        sizes.extend(
+            steps
+                .iter()
+                .map(|step| (step.after_size_bytes, step.after_compressed)),
+        );
+
+        let first_size = sizes.first().map_or(0, |(size, _)| *size);
+        let builds = files
+            .iter()
+            .zip(sizes)
+            .enumerate()
+            .map(|(i, (file, (size_bytes, compressed)))| BuildDelta {
+                file: file.clone(),
+                size_bytes,
+                compressed,
+                from_previous: (i > 0)
+                    .then(|| SizeDelta::between(steps[i - 1].before_size_bytes, size_bytes)),
+                from_first: SizeDelta::between(first_size, size_bytes),
+            })
+            .collect();
+
+        Self { builds, steps }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(before_size_bytes: u64, after_size_bytes: u64) -> ComparisonResults {
+        let delta = SizeDelta::between(before_size_bytes, after_size_bytes);
+        ComparisonResults {
+            before_size_bytes,
+            after_size_bytes,
+            delta_bytes: delta.bytes,
+            delta_percent: delta.percent,
+            top_changes: Vec::new(),
+            symbols: SymbolDiff::default(),
+            before_compressed: CompressedSizes::default(),
+            after_compressed: CompressedSizes::default(),
+            compressed_delta: Default::default(),
+        }
+    }
+
+    fn with_gzip(mut results: ComparisonResults, before: u64, after: u64) -> ComparisonResults {
+        results.before_compressed.gzip_bytes = Some(before);
+        results.after_compressed.gzip_bytes = Some(after);
+        results.compressed_delta =
+            CompressedDelta::between(&results.before_compressed, &results.after_compressed);
+        results
+    }
+
+    fn diverging_bytes(results: &ComparisonResults) -> Vec<(&'static str, i64)> {
+        results
+            .diverging_compressions()
+            .into_iter()
+            .map(|(name, delta)| (name, delta.bytes))
+            .collect()
+    }
+
+    #[test]
+    fn test_diverging_compressions_flags_compressed_size_moving_against_raw_size() {
+        let results = with_gzip(step(10_000, 11_000), 4_000, 3_900);
+        assert_eq!(diverging_bytes(&results), vec![("gzip", -100)]);
+    }
+
+    #[test]
+    fn test_diverging_compressions_flags_raw_regression_with_flat_compressed_size() {
+        let results = with_gzip(step(10_000, 11_000), 4_000, 4_000);
+        assert_eq!(diverging_bytes(&results), vec![("gzip", 0)]);
+    }
+
+    #[test]
+    fn test_diverging_compressions_flags_regression_worse_after_compression() {
+        // +10% raw, +25% gzip
+        let results = with_gzip(step(10_000, 11_000), 4_000, 5_000);
+        assert_eq!(diverging_bytes(&results), vec![("gzip", 1_000)]);
+    }
+
+    #[test]
+    fn test_diverging_compressions_ignores_proportional_changes() {
+        // +10% raw, +5% gzip
+        let regression = with_gzip(step(10_000, 11_000), 4_000, 4_200);
+        assert!(regression.diverging_compressions().is_empty());
+
+        // A reduction that compresses better is good news, not a divergence
+        let reduction = with_gzip(step(11_000, 10_000), 5_000, 4_000);
+        assert!(reduction.diverging_compressions().is_empty());
+
+        let unchanged = with_gzip(step(10_000, 10_000), 4_000, 4_000);
+        assert!(unchanged.diverging_compressions().is_empty());
+    }
+
+    #[test]
+    fn test_from_steps_chains_deltas_to_previous_and_first() {
+        let files: Vec<String> = ["a.wasm", "b.wasm", "c.wasm"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let comparison = MultiComparison::from_steps(&files, vec![step(1000, 800), step(800, 900)]);
+
+        let rows: Vec<(&str, u64, Option<i64>, i64)> = comparison
+            .builds
+            .iter()
+            .map(|b| {
+                (
+                    b.file.as_str(),
+                    b.size_bytes,
+                    b.from_previous.map(|d| d.bytes),
+                    b.from_first.bytes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("a.wasm", 1000, None, 0),
+                ("b.wasm", 800, Some(-200), -200),
+                ("c.wasm", 900, Some(100), -100),
+            ]
+        );
+        assert_eq!(comparison.builds[2].from_previous.unwrap().percent, 12.5);
+        assert_eq!(comparison.builds[2].from_first.percent, -10.0);
+    }
+
+    #[test]
+    fn test_symbol_diff_groups_changes_by_kind_and_size() {
+        let change =
+            |name: &str, before: Option<u64>, after: Option<u64>, delta_bytes: i64| ChangeItem {
+                delta_bytes,
+                name: name.to_string(),
+                before_bytes: before,
+                after_bytes: after,
+            };
+        let diff = SymbolDiff::from_changes(&[
+            change("new_small", Some(0), Some(10), 10),
+            change("gone", Some(500), Some(0), -500),
+            change("new_large", Some(0), Some(300), 300),
+            change("grew", Some(100), Some(150), 50),
+            change("cli_shrunk", None, None, -20),
+            change("same", None, None, 0),
+        ]);
+
+        let names = |list: &[ChangeItem]| list.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&diff.added), ["new_large", "new_small"]);
+        assert_eq!(names(&diff.removed), ["gone"]);
+        assert_eq!(names(&diff.grown), ["grew"]);
+        assert_eq!(names(&diff.shrunk), ["cli_shrunk"]);
+        assert!(SymbolDiff::default().is_empty());
+    }
 }
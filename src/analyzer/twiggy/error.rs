@@ -20,4 +20,12 @@ pub enum TwiggyAnalysisError {
     /// UTF-8 conversion error
     #[error("UTF-8 error: {0}")]
     Utf8(#[from] std::string::FromUtf8Error),
+
+    /// Twiggy output could not be parsed
+    #[error("Invalid twiggy output: {0}")]
+    InvalidOutput(String),
+
+    /// WASM module could not be parsed by the native backend
+    #[error("WASM parsing failed: {0}")]
+    WasmParse(#[from] wasmparser::BinaryReaderError),
 }
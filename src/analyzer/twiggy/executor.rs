@@ -1,78 +1,55 @@
 //! Twiggy tool execution logic
 
+use super::analysis_types::{AnalysisItem, AnalysisMode};
 use super::error::TwiggyAnalysisError;
 use crate::analyzer::TwiggyAnalyzer;
 use crate::infra::{CommandExecutor, FileSystem};
+use std::process::{Command, Stdio};
 
 impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
-    /// Run top contributors analysis
-    pub(super) fn run_top_analysis(&self) -> Result<String, TwiggyAnalysisError> {
-        let output = self.cmd_executor.execute(
-            |cmd| cmd.args(["top", "-n", "50"]).arg(&self.wasm_file),
-            "twiggy",
-        )?;
-
-        if !output.status.success() {
-            return Err(TwiggyAnalysisError::CommandFailed(
-                "top".to_string(),
-                output.status.code().unwrap_or(-1),
-            ));
-        }
-
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    }
-
-    /// Run dominators analysis
-    pub(super) fn run_dominators_analysis(&self) -> Result<String, TwiggyAnalysisError> {
-        let output = self.cmd_executor.execute(
-            |cmd| cmd.args(["dominators"]).arg(&self.wasm_file),
-            "twiggy",
-        )?;
-
-        if !output.status.success() {
-            return Err(TwiggyAnalysisError::CommandFailed(
-                "dominators".to_string(),
-                output.status.code().unwrap_or(-1),
-            ));
-        }
-
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    }
-
-    /// Run dead code analysis
-    pub(super) fn run_dead_code_analysis(&self) -> Result<String, TwiggyAnalysisError> {
-        let output = self.cmd_executor.execute(
-            |cmd| {
-                cmd.args(["garbage", "--max-items", "100"])
-                    .arg(&self.wasm_file)
-            },
-            "twiggy",
-        )?;
-
-        if !output.status.success() {
-            return Err(TwiggyAnalysisError::CommandFailed(
-                "garbage".to_string(),
-                output.status.code().unwrap_or(-1),
-            ));
-        }
-
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    /// `twiggy --version` output, or `None` when twiggy cannot be run
+    pub(super) fn cli_version(&self) -> Option<String> {
+        let output = self
+            .cmd_executor
+            .execute(|cmd| cmd.arg("--version"), "twiggy")
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
-    /// Run monomorphization analysis
-    pub(super) fn run_monos_analysis(&self) -> Result<String, TwiggyAnalysisError> {
-        let output = self
+    /// Run the twiggy subcommand for `mode`, parsing its output as it streams in
+    ///
+    /// The exit status is checked once the output has been read, so a
+    /// listing is never buffered whole just to learn that twiggy failed.
+    pub(super) fn run_cli_analysis(
+        &self,
+        mode: AnalysisMode,
+    ) -> Result<Vec<AnalysisItem>, TwiggyAnalysisError> {
+        let (subcommand, args): (&str, &[&str]) = match mode {
+            AnalysisMode::Top => ("top", &["-n", "50"]),
+            AnalysisMode::Dominators => ("dominators", &[]),
+            AnalysisMode::Dead => ("garbage", &["--max-items", "100"]),
+            AnalysisMode::Monos => ("monos", &[]),
+        };
+
+        let mut cmd = Command::new("twiggy");
+        cmd.arg(subcommand)
+            .args(args)
+            .arg(&self.wasm_file)
+            .stderr(Stdio::null());
+        let (items, status) = self
             .cmd_executor
-            .execute(|cmd| cmd.args(["monos"]).arg(&self.wasm_file), "twiggy")?;
+            .stream_stdout(&mut cmd, |reader| self.parse_reader(reader, self.limit))?;
 
-        if !output.status.success() {
+        if !status.success() {
             return Err(TwiggyAnalysisError::CommandFailed(
-                "monos".to_string(),
-                output.status.code().unwrap_or(-1),
+                subcommand.to_string(),
+                status.code().unwrap_or(-1),
             ));
         }
-
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        items
     }
 }
 
@@ -150,75 +127,49 @@ mod tests {
         }
     }
 
-    fn create_success_output(stdout: &[u8]) -> Output {
+    fn create_output(code: i32, stdout: &[u8]) -> Output {
         Output {
-            status: ExitStatus::default(),
+            status: crate::infra::mock_exit_status(code),
             stdout: stdout.to_vec(),
             stderr: vec![],
         }
     }
 
-    #[test]
-    fn test_run_top_analysis_with_successful_execution() {
-        let mock_executor = MockCommandExecutor::new();
-        mock_executor.set_output(create_success_output(b"Top contributors output"));
-
-        let analyzer = TwiggyAnalyzer::with_executors(
-            PathBuf::from("test.wasm"),
-            MockFileSystem,
-            mock_executor,
-        );
-
-        let result = analyzer.run_top_analysis();
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("Top contributors"));
-    }
-
-    #[test]
-    fn test_run_dominators_analysis_with_successful_execution() {
-        let mock_executor = MockCommandExecutor::new();
-        mock_executor.set_output(create_success_output(b"Dominators output"));
-
-        let analyzer = TwiggyAnalyzer::with_executors(
-            PathBuf::from("test.wasm"),
-            MockFileSystem,
-            mock_executor,
-        );
-
-        let result = analyzer.run_dominators_analysis();
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("Dominators"));
-    }
+    const TOP_OUTPUT: &[u8] = " Shallow Bytes │ Shallow % │ Item\n───────────────┼───────────┼─────\n           900 ┊    45.00% ┊ code[0]\n           100 ┊     5.00% ┊ data[0]\n".as_bytes();
 
     #[test]
-    fn test_run_dead_code_analysis_with_successful_execution() {
-        let mock_executor = MockCommandExecutor::new();
-        mock_executor.set_output(create_success_output(b"Garbage collection output"));
-
-        let analyzer = TwiggyAnalyzer::with_executors(
-            PathBuf::from("test.wasm"),
-            MockFileSystem,
-            mock_executor,
-        );
-
-        let result = analyzer.run_dead_code_analysis();
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("Garbage"));
+    fn test_run_cli_analysis_parses_streamed_output_for_every_mode() {
+        for mode in [
+            AnalysisMode::Top,
+            AnalysisMode::Dominators,
+            AnalysisMode::Dead,
+            AnalysisMode::Monos,
+        ] {
+            let mock_executor = MockCommandExecutor::new();
+            mock_executor.set_output(create_output(0, TOP_OUTPUT));
+            let analyzer = TwiggyAnalyzer::with_executors(
+                PathBuf::from("test.wasm"),
+                MockFileSystem,
+                mock_executor,
+            );
+
+            let items = analyzer.run_cli_analysis(mode).unwrap();
+            let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+            assert_eq!(names, ["code[0]", "data[0]"], "{mode:?}");
+        }
     }
 
     #[test]
-    fn test_run_monos_analysis_with_successful_execution() {
+    fn test_run_cli_analysis_reports_failure_after_reading_output() {
         let mock_executor = MockCommandExecutor::new();
-        mock_executor.set_output(create_success_output(b"Monomorphization output"));
-
+        mock_executor.set_output(create_output(2, TOP_OUTPUT));
         let analyzer = TwiggyAnalyzer::with_executors(
             PathBuf::from("test.wasm"),
             MockFileSystem,
             mock_executor,
         );
 
-        let result = analyzer.run_monos_analysis();
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("Monomorphization"));
+        let err = analyzer.run_cli_analysis(AnalysisMode::Dead).unwrap_err();
+        assert!(matches!(err, TwiggyAnalysisError::CommandFailed(ref cmd, 2) if cmd == "garbage"));
     }
 }
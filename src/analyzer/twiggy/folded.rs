@@ -0,0 +1,287 @@
+//! Collapsed-stack ("folded") export of the dominator tree
+//!
+//! Produces the `frame;frame;frame size` line format read by inferno
+//! (`inferno-flamegraph`) and speedscope, so bundle composition can be
+//! explored as a flamegraph or icicle chart. Each line carries the node's
+//! shallow size; flamegraph tools sum children into their parents, so frame
+//! widths equal retained sizes.
+//!
+//! Comparisons use inferno's differential format (`stack before after`),
+//! which `inferno-flamegraph` renders with growth in red and shrinkage in
+//! blue.
+//!
+//! # Examples
+//!
+//! ```
+//! use wasm_slim::analyzer::twiggy::FoldedStacks;
+//!
+//! let json = r#"{"name": "<root>", "shallow_size": 0, "children": [
+//!     {"name": "main", "shallow_size": 10, "children": [
+//!         {"name": "helper", "shallow_size": 5, "children": []}
+//!     ]}
+//! ]}"#;
+//! let stacks = FoldedStacks::from_dominators_json(json)?;
+//! assert_eq!(stacks.to_collapsed(), "main 10\nmain;helper 5\n");
+//! # Ok::<(), wasm_slim::analyzer::twiggy::TwiggyAnalysisError>(())
+//! ```
+
+use super::analysis_types::AnalysisBackend;
+use super::error::TwiggyAnalysisError;
+use crate::analyzer::TwiggyAnalyzer;
+use crate::infra::{CommandExecutor, FileSystem};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Node of `twiggy dominators --format json`, or of the native dominator tree
+#[derive(Debug, Deserialize)]
+pub(super) struct DominatorNode {
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) shallow_size: u64,
+    #[serde(default)]
+    pub(super) children: Vec<DominatorNode>,
+}
+
+/// Accepted shapes of twiggy's JSON output
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DominatorsJson {
+    Node(DominatorNode),
+    Items { items: Vec<DominatorNode> },
+    List(Vec<DominatorNode>),
+}
+
+/// Dominator tree flattened into collapsed stacks
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FoldedStacks {
+    stacks: BTreeMap<String, u64>,
+}
+
+impl FoldedStacks {
+    /// Build stacks from `twiggy dominators --format json` output
+    pub fn from_dominators_json(json: &str) -> Result<Self, TwiggyAnalysisError> {
+        let parsed: DominatorsJson = serde_json::from_str(json)
+            .map_err(|e| TwiggyAnalysisError::InvalidOutput(e.to_string()))?;
+
+        let roots = match parsed {
+            // twiggy emits a synthetic root node; its children are the real roots
+            DominatorsJson::Node(node) if node.name.starts_with('<') => node.children,
+            DominatorsJson::Node(node) => vec![node],
+            DominatorsJson::Items { items } | DominatorsJson::List(items) => items,
+        };
+
+        Ok(Self::from_roots(&roots))
+    }
+
+    /// Build stacks from the top-level nodes of a dominator tree
+    pub(super) fn from_roots(roots: &[DominatorNode]) -> Self {
+        let mut folded = Self::default();
+        for root in roots {
+            folded.add_node(root, "");
+        }
+        folded
+    }
+
+    fn add_node(&mut self, node: &DominatorNode, parent: &str) {
+        let frame = sanitize_frame(&node.name);
+        let stack = if parent.is_empty() {
+            frame
+        } else {
+            format!("{};{}", parent, frame)
+        };
+
+        if node.shallow_size > 0 {
+            *self.stacks.entry(stack.clone()).or_insert(0) += node.shallow_size;
+        }
+        for child in &node.children {
+            self.add_node(child, &stack);
+        }
+    }
+
+    /// Number of distinct stacks
+    pub fn len(&self) -> usize {
+        self.stacks.len()
+    }
+
+    /// True if no node had a non-zero size
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+
+    /// Sum of all sizes (equals the retained size of the tree)
+    pub fn total_bytes(&self) -> u64 {
+        self.stacks.values().sum()
+    }
+
+    /// Render as `stack size` lines
+    pub fn to_collapsed(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, size)| format!("{} {}\n", stack, size))
+            .collect()
+    }
+
+    /// Render a differential `stack before after` listing
+    ///
+    /// Stacks present in only one build get `0` on the other side.
+    pub fn to_differential(before: &Self, after: &Self) -> String {
+        let stacks: BTreeSet<&String> = before.stacks.keys().chain(after.stacks.keys()).collect();
+        stacks
+            .into_iter()
+            .map(|stack| {
+                format!(
+                    "{} {} {}\n",
+                    stack,
+                    before.stacks.get(stack).copied().unwrap_or(0),
+                    after.stacks.get(stack).copied().unwrap_or(0)
+                )
+            })
+            .collect()
+    }
+}
+
+/// Make a symbol safe for use as a frame (`;` separates frames)
+fn sanitize_frame(name: &str) -> String {
+    name.replace(';', ",")
+        .replace(['\n', '\r'], " ")
+        .trim()
+        .to_string()
+}
+
+impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
+    /// Export the dominator tree as collapsed stacks
+    pub fn dominators_folded(&self) -> Result<FoldedStacks, TwiggyAnalysisError> {
+        if !self.wasm_file.exists() {
+            return Err(TwiggyAnalysisError::WasmFileNotFound(
+                self.wasm_file.display().to_string(),
+            ));
+        }
+        folded_stacks_for(&self.wasm_file, self.backend, &self.cmd_executor)
+    }
+}
+
+impl TwiggyAnalyzer {
+    /// Export both builds' dominator trees as a differential folded listing
+    ///
+    /// Uses FileSystem and CommandExecutor traits for better testability.
+    pub fn compare_folded<FS: FileSystem, CE: CommandExecutor>(
+        before: &Path,
+        after: &Path,
+        fs: &FS,
+        cmd_executor: &CE,
+    ) -> Result<String, TwiggyAnalysisError> {
+        // Fail early with an I/O error if either file is missing
+        fs.metadata(before)?;
+        fs.metadata(after)?;
+
+        let before_stacks = folded_stacks_for(before, AnalysisBackend::Auto, cmd_executor)?;
+        let after_stacks = folded_stacks_for(after, AnalysisBackend::Auto, cmd_executor)?;
+        Ok(FoldedStacks::to_differential(&before_stacks, &after_stacks))
+    }
+}
+
+/// Fold the dominator tree of a module
+fn folded_stacks_for<CE: CommandExecutor>(
+    wasm_file: &Path,
+    backend: AnalysisBackend,
+    cmd_executor: &CE,
+) -> Result<FoldedStacks, TwiggyAnalysisError> {
+    super::run_backend(
+        backend,
+        || {
+            let graph = super::native::ItemGraph::read(wasm_file)?;
+            Ok(FoldedStacks::from_roots(&graph.dominator_nodes()))
+        },
+        || folded_stacks_from_cli(wasm_file, cmd_executor),
+    )
+}
+
+/// Run `twiggy dominators --format json` and fold the result
+fn folded_stacks_from_cli<CE: CommandExecutor>(
+    wasm_file: &Path,
+    cmd_executor: &CE,
+) -> Result<FoldedStacks, TwiggyAnalysisError> {
+    let output = cmd_executor.execute(
+        |cmd| cmd.args(["dominators", "--format", "json"]).arg(wasm_file),
+        "twiggy",
+    )?;
+
+    if !output.status.success() {
+        return Err(TwiggyAnalysisError::CommandFailed(
+            "dominators".to_string(),
+            output.status.code().unwrap_or(-1),
+        ));
+    }
+
+    FoldedStacks::from_dominators_json(&String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TREE: &str = r#"{
+        "name": "<root>",
+        "shallow_size": 0,
+        "shallow_size_percent": 0.0,
+        "retained_size": 180,
+        "retained_size_percent": 100.0,
+        "children": [
+            {"name": "code[0]", "shallow_size": 100, "children": [
+                {"name": "core::fmt::write", "shallow_size": 30, "children": []},
+                {"name": "data[1]", "shallow_size": 20}
+            ]},
+            {"name": "table[0]", "shallow_size": 30, "children": []}
+        ]
+    }"#;
+
+    #[test]
+    fn test_from_dominators_json_builds_stacks_below_root() {
+        let stacks = FoldedStacks::from_dominators_json(TREE).unwrap();
+
+        assert_eq!(stacks.len(), 4);
+        assert_eq!(stacks.total_bytes(), 180);
+        assert_eq!(
+            stacks.to_collapsed(),
+            "code[0] 100\ncode[0];core::fmt::write 30\ncode[0];data[1] 20\ntable[0] 30\n"
+        );
+    }
+
+    #[test]
+    fn test_from_dominators_json_accepts_item_list() {
+        let stacks = FoldedStacks::from_dominators_json(
+            r#"{"items": [{"name": "a", "shallow_size": 1, "children": []}]}"#,
+        )
+        .unwrap();
+        assert_eq!(stacks.to_collapsed(), "a 1\n");
+    }
+
+    #[test]
+    fn test_from_dominators_json_rejects_text_output() {
+        let err = FoldedStacks::from_dominators_json(" Retained Bytes │ ...").unwrap_err();
+        assert!(matches!(err, TwiggyAnalysisError::InvalidOutput(_)));
+    }
+
+    #[test]
+    fn test_sanitize_frame_removes_separators() {
+        assert_eq!(sanitize_frame("a;b\nc"), "a,b c");
+    }
+
+    #[test]
+    fn test_to_differential_includes_added_and_removed_stacks() {
+        let before = FoldedStacks::from_dominators_json(
+            r#"[{"name": "a", "shallow_size": 10}, {"name": "gone", "shallow_size": 5}]"#,
+        )
+        .unwrap();
+        let after = FoldedStacks::from_dominators_json(
+            r#"[{"name": "a", "shallow_size": 12}, {"name": "new", "shallow_size": 7}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            FoldedStacks::to_differential(&before, &after),
+            "a 10 12\ngone 5 0\nnew 0 7\n"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! Twiggy WASM analyzer module
 //!
-//! Provides size analysis using the twiggy tool to identify
-//! the largest contributors to WASM bundle size.
+//! Provides twiggy-style size analysis to identify the largest
+//! contributors to WASM bundle size. Results are computed natively from
+//! the module bytes; the `twiggy` CLI is only used as a fallback for
+//! modules the native parser rejects (see [`AnalysisBackend`]).
 //!
 //! # Examples
 //!
@@ -21,9 +23,13 @@
 //! ```
 
 pub mod analysis_types;
+pub mod cache;
 pub mod comparison;
 pub mod error;
 pub mod executor;
+pub mod folded;
+pub mod mono_types;
+pub mod native;
 pub mod parser;
 pub mod recommendation;
 pub mod recommendations;
@@ -31,9 +37,12 @@ pub mod recommendations;
 pub use analysis_types::*;
 pub use comparison::*;
 pub use error::*;
+pub use folded::FoldedStacks;
 pub use recommendation::*;
 
+use crate::analyzer::thresholds::RecommendationThresholds;
 use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use crate::summary::CompressedSizes;
 use std::path::Path;
 
 /// Main twiggy analyzer for WASM binaries
@@ -44,6 +53,27 @@ pub struct TwiggyAnalyzer<
     wasm_file: std::path::PathBuf,
     fs: FS,
     cmd_executor: CE,
+    thresholds: RecommendationThresholds,
+    backend: AnalysisBackend,
+    cache_dir: Option<std::path::PathBuf>,
+    limit: Option<usize>,
+}
+
+/// Run the native backend, the CLI, or the native backend with CLI fallback
+///
+/// When the fallback fails too, the native error is reported: it explains
+/// what is wrong with the module, while the CLI error usually only says
+/// that twiggy is missing.
+fn run_backend<T>(
+    backend: AnalysisBackend,
+    native: impl FnOnce() -> Result<T, TwiggyAnalysisError>,
+    cli: impl FnOnce() -> Result<T, TwiggyAnalysisError>,
+) -> Result<T, TwiggyAnalysisError> {
+    match backend {
+        AnalysisBackend::Native => native(),
+        AnalysisBackend::Cli => cli(),
+        AnalysisBackend::Auto => native().or_else(|native_err| cli().map_err(|_| native_err)),
+    }
 }
 
 impl TwiggyAnalyzer {
@@ -53,6 +83,9 @@ impl TwiggyAnalyzer {
     }
 
     /// Check if twiggy is installed
+    ///
+    /// Only needed for [`AnalysisBackend::Cli`]; the default backend works
+    /// without it.
     pub fn check_installation() -> Result<bool, TwiggyAnalysisError> {
         Self::check_installation_with_executor(&RealCommandExecutor)
     }
@@ -64,6 +97,7 @@ impl TwiggyAnalyzer {
 
     /// Compare two WASM files
     ///
+    /// Diffs the modules natively, falling back to `twiggy diff`.
     /// Uses FileSystem and CommandExecutor traits for better testability.
     pub fn compare<FS: FileSystem, CE: CommandExecutor>(
         before: &Path,
@@ -78,25 +112,52 @@ impl TwiggyAnalyzer {
         let delta_bytes = after_size_bytes as i64 - before_size_bytes as i64;
         let delta_percent = (delta_bytes as f64 / before_size_bytes as f64) * 100.0;
 
-        // Run twiggy diff
-        let before = before.to_path_buf();
-        let after = after.to_path_buf();
-        let output =
-            cmd_executor.execute(|cmd| cmd.args(["diff"]).arg(&before).arg(&after), "twiggy")?;
-
-        let diff_output = String::from_utf8_lossy(&output.stdout);
-
-        // Parse top changes
-        let top_changes = Self::parse_diff_output(&diff_output)?;
-
+        let top_changes = run_backend(
+            AnalysisBackend::Auto,
+            || {
+                Ok(native::ItemGraph::diff(
+                    &native::ItemGraph::read(before)?,
+                    &native::ItemGraph::read(after)?,
+                ))
+            },
+            || {
+                let output = cmd_executor
+                    .execute(|cmd| cmd.args(["diff"]).arg(before).arg(after), "twiggy")?;
+                Self::parse_diff_output(&String::from_utf8_lossy(&output.stdout))
+            },
+        )?;
+
+        let before_compressed = CompressedSizes::measure(before);
+        let after_compressed = CompressedSizes::measure(after);
         Ok(ComparisonResults {
             before_size_bytes,
             after_size_bytes,
             delta_bytes,
             delta_percent,
+            symbols: SymbolDiff::from_changes(&top_changes),
             top_changes,
+            compressed_delta: CompressedDelta::between(&before_compressed, &after_compressed),
+            before_compressed,
+            after_compressed,
         })
     }
+
+    /// Compare three or more WASM files in order
+    ///
+    /// Each build is diffed against the next one; size deltas against the
+    /// first build are derived from the same measurements.
+    pub fn compare_many<FS: FileSystem, CE: CommandExecutor>(
+        files: &[&Path],
+        fs: &FS,
+        cmd_executor: &CE,
+    ) -> Result<MultiComparison, TwiggyAnalysisError> {
+        let steps = files
+            .windows(2)
+            .map(|pair| Self::compare(pair[0], pair[1], fs, cmd_executor))
+            .collect::<Result<Vec<_>, _>>()?;
+        let names: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+        Ok(MultiComparison::from_steps(&names, steps))
+    }
 }
 
 impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
@@ -110,9 +171,42 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
             wasm_file: wasm_file.into(),
             fs,
             cmd_executor,
+            thresholds: RecommendationThresholds::default(),
+            backend: AnalysisBackend::default(),
+            cache_dir: None,
+            limit: None,
         }
     }
 
+    /// Choose the analysis backend (native with CLI fallback by default)
+    pub fn with_backend(mut self, backend: AnalysisBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Use custom recommendation thresholds (scaled to the analyzed bundle)
+    pub fn with_thresholds(mut self, thresholds: RecommendationThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Keep at most `limit` items, the largest ones, while collecting
+    ///
+    /// Twiggy CLI output is then parsed without ever holding more than
+    /// `limit` items, which keeps memory flat on very large modules.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Reuse results cached in `cache_dir` for a module with the same content
+    ///
+    /// See [`cache`] for how entries are keyed.
+    pub fn with_cache(mut self, cache_dir: impl Into<std::path::PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
     /// Create a new analyzer with a custom command executor (deprecated, use with_executors)
     #[deprecated(since = "0.1.0", note = "Use with_executors instead")]
     pub fn with_executor(wasm_file: impl Into<std::path::PathBuf>, cmd_executor: CE) -> Self
@@ -142,19 +236,56 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
             ));
         }
 
+        let cached_module = self
+            .cache_dir
+            .as_ref()
+            .and_then(|dir| Some((dir, self.fs.read(&self.wasm_file).ok()?)));
+        let cache_entry = |ran: AnalysisBackend| {
+            let (dir, bytes) = cached_module.as_ref()?;
+            let tool_version = match ran {
+                AnalysisBackend::Cli => self.cli_version()?,
+                _ => env!("CARGO_PKG_VERSION").to_string(),
+            };
+            Some(cache::entry_path(
+                dir,
+                bytes,
+                mode,
+                &cache::CacheKey {
+                    requested: self.backend,
+                    ran,
+                    tool_version: &tool_version,
+                    thresholds: &self.thresholds,
+                    limit: self.limit,
+                    catalog: crate::i18n::catalog(),
+                },
+            ))
+        };
+        let candidates: &[AnalysisBackend] = match self.backend {
+            AnalysisBackend::Auto => &[AnalysisBackend::Native, AnalysisBackend::Cli],
+            AnalysisBackend::Native => &[AnalysisBackend::Native],
+            AnalysisBackend::Cli => &[AnalysisBackend::Cli],
+        };
+        if let Some(results) = candidates
+            .iter()
+            .filter_map(|&ran| cache_entry(ran))
+            .find_map(|path| cache::load(&self.fs, &path))
+        {
+            return Ok(results);
+        }
+
         // Get total file size
         let total_size_bytes = self.fs.metadata(&self.wasm_file)?.len();
 
-        // Run twiggy command
-        let output = match mode {
-            AnalysisMode::Top => self.run_top_analysis()?,
-            AnalysisMode::Dominators => self.run_dominators_analysis()?,
-            AnalysisMode::Dead => self.run_dead_code_analysis()?,
-            AnalysisMode::Monos => self.run_monos_analysis()?,
-        };
-
-        // Parse output
-        let items = self.parse_output(&output, mode)?;
+        let (items, ran) = run_backend(
+            self.backend,
+            || {
+                Ok((
+                    self.run_native_analysis(mode, total_size_bytes)?,
+                    AnalysisBackend::Native,
+                ))
+            },
+            || Ok((self.run_cli_analysis(mode)?, AnalysisBackend::Cli)),
+        )?;
 
         // Group monomorphizations if in Monos mode
         let mono_groups = if matches!(mode, AnalysisMode::Monos) {
@@ -170,13 +301,36 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
             self.generate_recommendations(&items, total_size_bytes, mode)
         };
 
-        Ok(AnalysisResults {
+        let results = AnalysisResults {
             total_size_bytes,
             mode: format!("{:?}", mode).to_lowercase(),
             items,
+            limit: self.limit,
             recommendations,
             mono_groups,
-        })
+        };
+        if let Some(path) = cache_entry(ran) {
+            cache::store(&self.fs, &path, &results);
+        }
+        Ok(results)
+    }
+
+    /// Every item of the module with a non-zero size, largest first
+    ///
+    /// The twiggy CLI fallback only lists the largest 50 items.
+    pub fn sized_items(&self) -> Result<Vec<AnalysisItem>, TwiggyAnalysisError> {
+        if !self.wasm_file.exists() {
+            return Err(TwiggyAnalysisError::WasmFileNotFound(
+                self.wasm_file.display().to_string(),
+            ));
+        }
+
+        let total_size_bytes = self.fs.metadata(&self.wasm_file)?.len();
+        run_backend(
+            self.backend,
+            || Ok(native::ItemGraph::read(&self.wasm_file)?.sized_items(total_size_bytes)),
+            || self.run_cli_analysis(AnalysisMode::Top),
+        )
     }
 }
 
@@ -235,12 +389,13 @@ mod tests {
         let analyzer = TwiggyAnalyzer::new(temp_file.path());
         let result = analyzer.analyze(AnalysisMode::Top);
 
-        // Should handle twiggy command failure gracefully
+        // Should handle parse or twiggy command failure gracefully
         assert!(result.is_err());
         let err = result.unwrap_err();
-        // Error should indicate command failure or IO error
+        // Error should indicate a parse failure, command failure or IO error
         assert!(
-            matches!(err, TwiggyAnalysisError::CommandFailed(_, _))
+            matches!(err, TwiggyAnalysisError::WasmParse(_))
+                || matches!(err, TwiggyAnalysisError::CommandFailed(_, _))
                 || matches!(err, TwiggyAnalysisError::Io(_))
         );
     }
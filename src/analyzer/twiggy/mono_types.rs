@@ -0,0 +1,221 @@
+//! Type arguments of generic instantiations and targeted refactors
+//!
+//! Demangled names of instantiations carry their concrete type arguments
+//! (`serialize<alloc::string::String>`, `core::ptr::drop_in_place::<u8>`,
+//! `<app::Cache<u32> as core::ops::Drop>::drop`). Reading them tells why a
+//! function was instantiated many times, which points at a specific
+//! refactor: a string parameter that could be `&str`, a reader that could
+//! be `&mut dyn Read`, closures that could be `&dyn Fn`. Legacy-mangled
+//! symbols drop the type arguments; their groups get the general generic
+//! outlining advice.
+
+/// Concrete type arguments in a demangled instantiation name
+///
+/// For `<Type as Trait>::method` names the self type is the argument;
+/// otherwise the first top-level `<...>` list is split at its commas.
+pub fn type_arguments(name: &str) -> Vec<String> {
+    if let Some(rest) = name.strip_prefix('<') {
+        let self_type = top_level_prefix(rest, &[" as ", ">"]);
+        let mut args = vec![self_type.trim().to_string()];
+        // A generic method on the impl, e.g. `<T as Trait>::method::<U>`
+        if let Some(pos) = name.rfind(">::") {
+            args.extend(angle_list(&name[pos + 3..]));
+        }
+        args.retain(|a| !a.is_empty());
+        return args;
+    }
+    angle_list(name)
+}
+
+/// Arguments of the first `<...>` list in `text`
+fn angle_list(text: &str) -> Vec<String> {
+    let Some(start) = text.find('<') else {
+        return Vec::new();
+    };
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in text[start + 1..].chars() {
+        match c {
+            '<' | '(' | '[' => {
+                depth += 1;
+                current.push(c);
+            }
+            '>' | ')' | ']' if depth > 0 => {
+                depth -= 1;
+                current.push(c);
+            }
+            '>' => break,
+            ',' if depth == 0 => args.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    args.push(current);
+    args.into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+/// Prefix of `text` up to the first top-level occurrence of any `stops`
+fn top_level_prefix<'a>(text: &'a str, stops: &[&str]) -> &'a str {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        if depth == 0 && stops.iter().any(|stop| text[i..].starts_with(stop)) {
+            return &text[..i];
+        }
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Refactor suited to the type arguments a generic function is used with
+///
+/// `type_arguments` holds the arguments of each instantiation. Falls back to
+/// generic outlining when the arguments show no specific pattern.
+pub fn suggest_refactor(function_name: &str, type_arguments: &[Vec<String>]) -> String {
+    let args: Vec<&str> = type_arguments
+        .iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    let all = |pred: fn(&str) -> bool| !args.is_empty() && args.iter().all(|a| pred(a));
+    let any = |pred: fn(&str) -> bool| args.iter().any(|a| pred(a));
+
+    if all(is_string_like) {
+        format!(
+            "Take `&str` in `{}` and convert at the call sites; one copy then serves every string type",
+            function_name
+        )
+    } else if any(is_closure) {
+        format!(
+            "Take `&dyn Fn(..)` (or `&mut dyn FnMut(..)`) in `{}` instead of a generic closure parameter",
+            function_name
+        )
+    } else if all(is_reader) {
+        format!(
+            "Accept `&mut dyn std::io::Read` in `{}`; the reader types differ only in dispatch",
+            function_name
+        )
+    } else if all(is_writer) {
+        format!(
+            "Accept `&mut dyn std::io::Write` (or `&mut dyn core::fmt::Write`) in `{}`",
+            function_name
+        )
+    } else if any(is_iterator) {
+        format!(
+            "Accept `&mut dyn Iterator<Item = ..>` or a collected slice in `{}` instead of each iterator adapter chain",
+            function_name
+        )
+    } else if all(is_integer) {
+        format!(
+            "Convert to one integer type (e.g. u64) before calling `{}`",
+            function_name
+        )
+    } else {
+        format!(
+            "Outline the non-generic body of `{}`: keep a thin generic wrapper that converts its arguments and calls a private non-generic inner function",
+            function_name
+        )
+    }
+}
+
+fn base_type(arg: &str) -> &str {
+    let arg = arg.trim_start_matches('&').trim_start_matches("mut ");
+    let path = arg.split('<').next().unwrap_or(arg);
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+fn is_string_like(arg: &str) -> bool {
+    matches!(base_type(arg), "str" | "String" | "Cow" | "Box" if arg.contains("str") || arg.contains("String"))
+}
+
+fn is_closure(arg: &str) -> bool {
+    arg.contains("{{closure}}") || arg.contains("{closure")
+}
+
+fn is_reader(arg: &str) -> bool {
+    matches!(
+        base_type(arg),
+        "File" | "Cursor" | "BufReader" | "Stdin" | "StdinLock" | "TcpStream"
+    ) || arg.ends_with("[u8]")
+}
+
+fn is_writer(arg: &str) -> bool {
+    matches!(
+        base_type(arg),
+        "BufWriter" | "LineWriter" | "Stdout" | "StdoutLock" | "Stderr" | "Formatter"
+    )
+}
+
+fn is_iterator(arg: &str) -> bool {
+    arg.contains("::iter::adapters::") || arg.contains("::iter::sources::")
+}
+
+fn is_integer(arg: &str) -> bool {
+    matches!(
+        arg,
+        "u8" | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_arguments_reads_plain_turbofish_and_impl_names() {
+        assert_eq!(
+            type_arguments("app::encode<alloc::vec::Vec<u8>, &str>"),
+            ["alloc::vec::Vec<u8>", "&str"]
+        );
+        assert_eq!(
+            type_arguments("core::ptr::drop_in_place::<app::Node>"),
+            ["app::Node"]
+        );
+        assert_eq!(
+            type_arguments("<app::Cache<u32, i64> as core::ops::drop::Drop>::drop"),
+            ["app::Cache<u32, i64>"]
+        );
+        assert!(type_arguments("app::render").is_empty());
+    }
+
+    #[test]
+    fn test_suggest_refactor_matches_argument_patterns() {
+        let args = |lists: &[&[&str]]| -> Vec<Vec<String>> {
+            lists
+                .iter()
+                .map(|l| l.iter().map(|a| a.to_string()).collect())
+                .collect()
+        };
+        assert!(
+            suggest_refactor("greet", &args(&[&["&str"], &["alloc::string::String"]]))
+                .contains("`&str`")
+        );
+        assert!(suggest_refactor(
+            "load",
+            &args(&[&["std::fs::File"], &["std::io::Cursor<&[u8]>"]])
+        )
+        .contains("&mut dyn std::io::Read"));
+        assert!(
+            suggest_refactor("each", &args(&[&["app::main::{{closure}}"], &["u8"]]))
+                .contains("&dyn Fn")
+        );
+        assert!(suggest_refactor("sum", &args(&[&["u8"], &["u64"]])).contains("u64"));
+        assert!(suggest_refactor("parse", &[]).contains("Outline the non-generic body"));
+    }
+}
@@ -0,0 +1,1024 @@
+//! Native WASM size analysis
+//!
+//! Builds twiggy's item graph straight from the module bytes with
+//! `wasmparser`, so top/dominators/dead/monos work without the `twiggy`
+//! binary. Items are named the way twiggy names them (`code[3]`, `data[0]`,
+//! `export "main"`, `import env::log`), with function names taken from the
+//! `name` section and legacy Rust mangling decoded.
+//!
+//! Edges model what keeps an item alive: calls, `ref.func` and global
+//! accesses from code, exports, the start function, tables to their element
+//! segments and memories to their data segments. Memories, passive and
+//! declarative segments and custom sections are roots, since instantiation
+//! keeps them regardless of what the code references; a declarative segment
+//! only declares functions for `ref.func`, so it does not keep them alive.
+
+use super::analysis_types::{AnalysisItem, AnalysisMode};
+use super::comparison::ChangeItem;
+use super::error::TwiggyAnalysisError;
+use super::folded::DominatorNode;
+use crate::analyzer::TwiggyAnalyzer;
+use crate::infra::{CommandExecutor, FileSystem};
+use crate::profiling::{self, ProfilePhase};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use wasmparser::{
+    CustomSectionReader, DataKind, ElementItems, ElementKind, ExternalKind, KnownCustom, Name,
+    Operator, OperatorsReader, Parser, Payload, TableInit, TypeRef,
+};
+
+/// Rows listed by top (matches `twiggy top -n 50`)
+const TOP_ROWS: usize = 50;
+/// Rows listed by dead code analysis (matches `twiggy garbage --max-items 100`)
+const GARBAGE_ROWS: usize = 100;
+/// Rows of the dominator tree listed
+const DOMINATOR_ROWS: usize = 100;
+/// Rows listed by comparisons
+const DIFF_ROWS: usize = 100;
+
+/// Sized items of a module and the references between them
+#[derive(Debug, Default)]
+pub(crate) struct ItemGraph {
+    items: Vec<Item>,
+    edges: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+    /// Item of each function, in function index order (imports first)
+    functions: Vec<usize>,
+    imported_functions: usize,
+    exports: Vec<Export>,
+    code_section_bytes: u64,
+}
+
+/// An export and the item representing it
+#[derive(Debug, Clone)]
+pub(crate) struct Export {
+    /// Export name
+    pub name: String,
+    /// The `export "name"` item, a graph root
+    pub item: usize,
+    /// Function index, for function exports
+    pub function: Option<u32>,
+}
+
+#[derive(Debug)]
+struct Item {
+    name: String,
+    size_bytes: u64,
+    is_code: bool,
+    /// Function name as written in the `name` section (still mangled)
+    symbol: Option<String>,
+}
+
+/// Endpoint of a reference, resolved once all index spaces are known
+#[derive(Debug, Clone, Copy)]
+enum Node {
+    Item(usize),
+    Func(u32),
+    Table(u32),
+    Memory(u32),
+    Global(u32),
+}
+
+/// Dominator tree over the items reachable from the roots
+struct Dominators {
+    /// Shallow size of each item plus everything it dominates
+    retained: Vec<u64>,
+    /// Dominated items per node, largest retained size first; the last
+    /// entry is the synthetic root above all graph roots
+    children: Vec<Vec<usize>>,
+}
+
+impl ItemGraph {
+    /// Read and parse a WASM file
+    pub(crate) fn read(wasm_file: &Path) -> Result<Self, TwiggyAnalysisError> {
+        let bytes = std::fs::read(wasm_file)?;
+        Self::parse(&bytes)
+    }
+
+    /// Parse a WASM module
+    pub(crate) fn parse(bytes: &[u8]) -> Result<Self, TwiggyAnalysisError> {
+        let _span = profiling::span(ProfilePhase::Parsing, "wasm module");
+
+        let mut builder = GraphBuilder::default();
+        for payload in Parser::new(0).parse_all(bytes) {
+            builder.payload(payload?)?;
+        }
+        Ok(builder.finish())
+    }
+
+    /// Largest items by shallow size
+    pub(super) fn top(&self, total_size_bytes: u64) -> Vec<AnalysisItem> {
+        let mut items = self.sized_items(total_size_bytes);
+        items.truncate(TOP_ROWS);
+        items
+    }
+
+    /// Every item with a non-zero shallow size, largest first
+    pub(super) fn sized_items(&self, total_size_bytes: u64) -> Vec<AnalysisItem> {
+        let mut ids: Vec<usize> = (0..self.items.len())
+            .filter(|&id| self.items[id].size_bytes > 0)
+            .collect();
+        self.sort_by_size(&mut ids);
+        ids.into_iter()
+            .map(|id| self.analysis_item(id, self.items[id].size_bytes, total_size_bytes))
+            .collect()
+    }
+
+    /// Items unreachable from any root, largest first
+    pub(super) fn garbage(&self, total_size_bytes: u64) -> Vec<AnalysisItem> {
+        let reachable = self.reachable();
+        let mut ids: Vec<usize> = (0..self.items.len())
+            .filter(|&id| !reachable[id] && self.items[id].size_bytes > 0)
+            .collect();
+        self.sort_by_size(&mut ids);
+        ids.into_iter()
+            .take(GARBAGE_ROWS)
+            .map(|id| self.analysis_item(id, self.items[id].size_bytes, total_size_bytes))
+            .collect()
+    }
+
+    /// Functions that look like generic instantiations, largest first
+    ///
+    /// A function qualifies if its name carries type parameters or if
+    /// several functions share its name (instantiations differ only in the
+    /// symbol hash dropped during demangling).
+    pub(super) fn monos(&self, total_size_bytes: u64) -> Vec<AnalysisItem> {
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
+        for item in self.items.iter().filter(|i| i.is_code) {
+            *name_counts.entry(item.name.as_str()).or_insert(0) += 1;
+        }
+
+        let mut ids: Vec<usize> = (0..self.items.len())
+            .filter(|&id| {
+                let item = &self.items[id];
+                item.is_code && (item.name.contains('<') || name_counts[item.name.as_str()] > 1)
+            })
+            .collect();
+        self.sort_by_size(&mut ids);
+        ids.into_iter()
+            .map(|id| self.analysis_item(id, self.items[id].size_bytes, total_size_bytes))
+            .collect()
+    }
+
+    /// Dominator tree in pre-order with retained sizes, largest subtree first
+    pub(super) fn dominators(&self, total_size_bytes: u64) -> Vec<AnalysisItem> {
+        let dominators = self.dominators_tree();
+        let root = self.items.len();
+
+        let mut items = Vec::new();
+        let mut stack: Vec<usize> = dominators.children[root].iter().rev().copied().collect();
+        while let Some(id) = stack.pop() {
+            if items.len() == DOMINATOR_ROWS {
+                break;
+            }
+            items.push(self.analysis_item(id, dominators.retained[id], total_size_bytes));
+            stack.extend(dominators.children[id].iter().rev());
+        }
+        items
+    }
+
+    /// Dominator tree as nested nodes below the synthetic root
+    pub(super) fn dominator_nodes(&self) -> Vec<DominatorNode> {
+        let dominators = self.dominators_tree();
+        let root = self.items.len();
+
+        // Children come after their dominator in pre-order, so building in
+        // reverse pre-order always finds the child nodes complete
+        let mut order = Vec::new();
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            stack.extend(dominators.children[id].iter().rev());
+        }
+
+        let mut nodes: Vec<Option<DominatorNode>> = (0..=root).map(|_| None).collect();
+        for &id in order.iter().rev().filter(|&&id| id != root) {
+            let children = dominators.children[id]
+                .iter()
+                .filter_map(|&child| nodes[child].take())
+                .collect();
+            nodes[id] = Some(DominatorNode {
+                name: self.items[id].name.clone(),
+                shallow_size: self.items[id].size_bytes,
+                children,
+            });
+        }
+        dominators.children[root]
+            .iter()
+            .filter_map(|&id| nodes[id].take())
+            .collect()
+    }
+
+    /// Number of items
+    pub(crate) fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Name of an item
+    pub(crate) fn name(&self, id: usize) -> &str {
+        &self.items[id].name
+    }
+
+    /// Shallow size of an item
+    pub(crate) fn size_bytes(&self, id: usize) -> u64 {
+        self.items[id].size_bytes
+    }
+
+    /// Whether an item is a function body
+    pub(crate) fn is_code(&self, id: usize) -> bool {
+        self.items[id].is_code
+    }
+
+    /// Name section symbol of a function body, before demangling
+    pub(crate) fn symbol(&self, id: usize) -> Option<&str> {
+        self.items[id].symbol.as_deref()
+    }
+
+    /// Items an item references
+    pub(crate) fn references(&self, id: usize) -> &[usize] {
+        &self.edges[id]
+    }
+
+    /// Item of each function, in function index order (imports first)
+    pub(crate) fn functions(&self) -> &[usize] {
+        &self.functions
+    }
+
+    /// Number of imported functions, which come first in the index space
+    pub(crate) fn imported_functions(&self) -> usize {
+        self.imported_functions
+    }
+
+    /// Exports in export section order
+    pub(crate) fn exports(&self) -> &[Export] {
+        &self.exports
+    }
+
+    /// Size of the code section, including its header
+    pub(crate) fn code_section_bytes(&self) -> u64 {
+        self.code_section_bytes
+    }
+
+    /// Items reachable from the roots without passing through `blocked`
+    /// items (which count as unreachable themselves)
+    pub(crate) fn reachable_avoiding(&self, blocked: &[bool]) -> Vec<bool> {
+        self.reachable_from(self.roots.iter().copied(), blocked)
+    }
+
+    /// Items reachable from `roots` without passing through `blocked` items
+    pub(crate) fn reachable_from(
+        &self,
+        roots: impl IntoIterator<Item = usize>,
+        blocked: &[bool],
+    ) -> Vec<bool> {
+        let is_blocked = |id: usize| blocked.get(id).copied().unwrap_or(false);
+        let mut reachable = vec![false; self.items.len()];
+        let mut stack: Vec<usize> = roots.into_iter().filter(|&id| !is_blocked(id)).collect();
+        while let Some(id) = stack.pop() {
+            if !std::mem::replace(&mut reachable[id], true) {
+                stack.extend(
+                    self.edges[id]
+                        .iter()
+                        .filter(|&&to| !reachable[to] && !is_blocked(to)),
+                );
+            }
+        }
+        reachable
+    }
+
+    /// Items each item immediately dominates; the entry past the last item
+    /// lists the dominator tree roots
+    pub(crate) fn dominator_children(&self) -> Vec<Vec<usize>> {
+        self.dominators_tree().children
+    }
+
+    /// Shallow size changes per item name between two modules
+    pub(super) fn diff(before: &Self, after: &Self) -> Vec<ChangeItem> {
+        let before_sizes = before.sizes_by_name();
+        let after_sizes = after.sizes_by_name();
+
+        let mut changes: Vec<ChangeItem> = before_sizes
+            .keys()
+            .chain(after_sizes.keys())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|name| {
+                let before = before_sizes.get(name).copied().unwrap_or(0);
+                let after = after_sizes.get(name).copied().unwrap_or(0);
+                (before != after).then(|| ChangeItem {
+                    delta_bytes: after as i64 - before as i64,
+                    name: name.to_string(),
+                    before_bytes: Some(before),
+                    after_bytes: Some(after),
+                })
+            })
+            .collect();
+        // Stable sort: equal deltas stay in name order
+        changes.sort_by_key(|c| std::cmp::Reverse(c.delta_bytes.unsigned_abs()));
+        changes.truncate(DIFF_ROWS);
+        changes
+    }
+
+    fn sizes_by_name(&self) -> BTreeMap<&str, u64> {
+        let mut sizes = BTreeMap::new();
+        for item in &self.items {
+            *sizes.entry(item.name.as_str()).or_insert(0) += item.size_bytes;
+        }
+        sizes
+    }
+
+    fn analysis_item(&self, id: usize, size_bytes: u64, total_size_bytes: u64) -> AnalysisItem {
+        AnalysisItem {
+            size_bytes,
+            percentage: if total_size_bytes == 0 {
+                0.0
+            } else {
+                size_bytes as f64 / total_size_bytes as f64 * 100.0
+            },
+            name: self.items[id].name.clone(),
+        }
+    }
+
+    /// Sort by size descending; the stable sort keeps ties in item order
+    fn sort_by_size(&self, ids: &mut [usize]) {
+        ids.sort_by_key(|&id| std::cmp::Reverse(self.items[id].size_bytes));
+    }
+
+    fn reachable(&self) -> Vec<bool> {
+        self.reachable_avoiding(&[])
+    }
+
+    /// Compute immediate dominators with the iterative algorithm of
+    /// Cooper, Harvey and Kennedy ("A Simple, Fast Dominance Algorithm")
+    fn dominators_tree(&self) -> Dominators {
+        let root = self.items.len();
+        let successors = |id: usize| -> &[usize] {
+            if id == root {
+                &self.roots
+            } else {
+                &self.edges[id]
+            }
+        };
+
+        // Reverse postorder from the synthetic root
+        let mut visited = vec![false; root + 1];
+        let mut postorder = Vec::with_capacity(root + 1);
+        let mut stack = vec![(root, 0usize)];
+        visited[root] = true;
+        while let Some((id, next)) = stack.last_mut() {
+            let id = *id;
+            match successors(id).get(*next) {
+                Some(&succ) => {
+                    *next += 1;
+                    if !visited[succ] {
+                        visited[succ] = true;
+                        stack.push((succ, 0));
+                    }
+                }
+                None => {
+                    postorder.push(id);
+                    stack.pop();
+                }
+            }
+        }
+        let rpo: Vec<usize> = postorder.into_iter().rev().collect();
+        let mut order = vec![usize::MAX; root + 1];
+        for (index, &id) in rpo.iter().enumerate() {
+            order[id] = index;
+        }
+
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); root + 1];
+        for &id in &rpo {
+            for &succ in successors(id) {
+                predecessors[succ].push(id);
+            }
+        }
+
+        let mut idom: Vec<Option<usize>> = vec![None; root + 1];
+        idom[root] = Some(root);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &id in rpo.iter().skip(1) {
+                let mut new_idom: Option<usize> = None;
+                for &pred in &predecessors[id] {
+                    if idom[pred].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => pred,
+                        Some(current) => {
+                            let (mut a, mut b) = (pred, current);
+                            while a != b {
+                                while order[a] > order[b] {
+                                    a = idom[a].unwrap_or(root);
+                                }
+                                while order[b] > order[a] {
+                                    b = idom[b].unwrap_or(root);
+                                }
+                            }
+                            a
+                        }
+                    });
+                }
+                if new_idom.is_some() && idom[id] != new_idom {
+                    idom[id] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        // Retained sizes accumulate bottom-up: dominators precede the nodes
+        // they dominate in reverse postorder
+        let mut retained: Vec<u64> = (0..=root)
+            .map(|id| self.items.get(id).map_or(0, |i| i.size_bytes))
+            .collect();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); root + 1];
+        for &id in rpo.iter().skip(1).rev() {
+            if let Some(parent) = idom[id] {
+                retained[parent] += retained[id];
+                children[parent].push(id);
+            }
+        }
+        for list in &mut children {
+            list.sort_by_key(|&id| (std::cmp::Reverse(retained[id]), id));
+        }
+
+        Dominators { retained, children }
+    }
+}
+
+/// Collects items and references while walking the module's payloads
+#[derive(Default)]
+struct GraphBuilder {
+    graph: ItemGraph,
+    edges: Vec<(Node, Node)>,
+    roots: Vec<Node>,
+    funcs: Vec<usize>,
+    tables: Vec<usize>,
+    memories: Vec<usize>,
+    globals: Vec<usize>,
+    /// Function index of the first code section entry
+    code_base: usize,
+    code_seen: usize,
+    function_names: BTreeMap<u32, String>,
+}
+
+impl GraphBuilder {
+    fn add_item(&mut self, name: String, size_bytes: u64) -> usize {
+        self.graph.items.push(Item {
+            name,
+            size_bytes,
+            is_code: false,
+            symbol: None,
+        });
+        self.graph.items.len() - 1
+    }
+
+    fn payload(&mut self, payload: Payload<'_>) -> Result<(), TwiggyAnalysisError> {
+        match payload {
+            Payload::ImportSection(reader) => {
+                let end = reader.range().end;
+                let imports = reader
+                    .into_imports_with_offsets()
+                    .collect::<Result<Vec<_>, _>>()?;
+                let offsets: Vec<usize> = imports.iter().map(|(offset, _)| *offset).collect();
+                for ((_, import), size) in imports.iter().zip(entry_sizes(&offsets, end)) {
+                    let id =
+                        self.add_item(format!("import {}::{}", import.module, import.name), size);
+                    match import.ty {
+                        TypeRef::Func(_) | TypeRef::FuncExact(_) => self.funcs.push(id),
+                        TypeRef::Table(_) => self.tables.push(id),
+                        TypeRef::Memory(_) => {
+                            self.memories.push(id);
+                            self.roots.push(Node::Item(id));
+                        }
+                        TypeRef::Global(_) => self.globals.push(id),
+                        TypeRef::Tag(_) => {}
+                    }
+                }
+            }
+            Payload::FunctionSection(reader) => {
+                self.code_base = self.funcs.len();
+                for index in 0..reader.count() {
+                    let id = self.add_item(format!("code[{}]", index), 0);
+                    self.graph.items[id].is_code = true;
+                    self.funcs.push(id);
+                }
+            }
+            Payload::TableSection(reader) => {
+                let end = reader.range().end;
+                let tables = reader
+                    .into_iter_with_offsets()
+                    .collect::<Result<Vec<_>, _>>()?;
+                let offsets: Vec<usize> = tables.iter().map(|(offset, _)| *offset).collect();
+                for ((_, table), size) in tables.into_iter().zip(entry_sizes(&offsets, end)) {
+                    let id = self.add_item(format!("table[{}]", self.tables.len()), size);
+                    self.tables.push(id);
+                    if let TableInit::Expr(expr) = table.init {
+                        self.scan_operators(id, expr.get_operators_reader())?;
+                    }
+                }
+            }
+            Payload::MemorySection(reader) => {
+                let end = reader.range().end;
+                let offsets = reader
+                    .into_iter_with_offsets()
+                    .map(|entry| entry.map(|(offset, _)| offset))
+                    .collect::<Result<Vec<_>, _>>()?;
+                for size in entry_sizes(&offsets, end) {
+                    let id = self.add_item(format!("memory[{}]", self.memories.len()), size);
+                    self.memories.push(id);
+                    self.roots.push(Node::Item(id));
+                }
+            }
+            Payload::GlobalSection(reader) => {
+                let end = reader.range().end;
+                let globals = reader
+                    .into_iter_with_offsets()
+                    .collect::<Result<Vec<_>, _>>()?;
+                let offsets: Vec<usize> = globals.iter().map(|(offset, _)| *offset).collect();
+                for ((_, global), size) in globals.into_iter().zip(entry_sizes(&offsets, end)) {
+                    let id = self.add_item(format!("global[{}]", self.globals.len()), size);
+                    self.globals.push(id);
+                    self.scan_operators(id, global.init_expr.get_operators_reader())?;
+                }
+            }
+            Payload::ExportSection(reader) => {
+                let end = reader.range().end;
+                let exports = reader
+                    .into_iter_with_offsets()
+                    .collect::<Result<Vec<_>, _>>()?;
+                let offsets: Vec<usize> = exports.iter().map(|(offset, _)| *offset).collect();
+                for ((_, export), size) in exports.into_iter().zip(entry_sizes(&offsets, end)) {
+                    let id = self.add_item(format!("export \"{}\"", export.name), size);
+                    self.roots.push(Node::Item(id));
+                    self.graph.exports.push(Export {
+                        name: export.name.to_string(),
+                        item: id,
+                        function: matches!(
+                            export.kind,
+                            ExternalKind::Func | ExternalKind::FuncExact
+                        )
+                        .then_some(export.index),
+                    });
+                    let target = match export.kind {
+                        ExternalKind::Func | ExternalKind::FuncExact => Node::Func(export.index),
+                        ExternalKind::Table => Node::Table(export.index),
+                        ExternalKind::Memory => Node::Memory(export.index),
+                        ExternalKind::Global => Node::Global(export.index),
+                        ExternalKind::Tag => continue,
+                    };
+                    self.edges.push((Node::Item(id), target));
+                }
+            }
+            Payload::StartSection { func, .. } => self.roots.push(Node::Func(func)),
+            Payload::ElementSection(reader) => {
+                for (index, element) in reader.into_iter().enumerate() {
+                    let element = element?;
+                    let id = self.add_item(format!("elem[{}]", index), element.range.len() as u64);
+                    match element.kind {
+                        ElementKind::Active { table_index, .. } => self
+                            .edges
+                            .push((Node::Table(table_index.unwrap_or(0)), Node::Item(id))),
+                        ElementKind::Passive => self.roots.push(Node::Item(id)),
+                        // Only declares functions for `ref.func`, which adds its own edges
+                        ElementKind::Declared => {
+                            self.roots.push(Node::Item(id));
+                            continue;
+                        }
+                    }
+                    match element.items {
+                        ElementItems::Functions(funcs) => {
+                            for func in funcs {
+                                self.edges.push((Node::Item(id), Node::Func(func?)));
+                            }
+                        }
+                        ElementItems::Expressions(_, exprs) => {
+                            for expr in exprs {
+                                self.scan_operators(id, expr?.get_operators_reader())?;
+                            }
+                        }
+                    }
+                }
+            }
+            Payload::DataSection(reader) => {
+                for (index, data) in reader.into_iter().enumerate() {
+                    let data = data?;
+                    let id = self.add_item(format!("data[{}]", index), data.range.len() as u64);
+                    match data.kind {
+                        DataKind::Active { memory_index, .. } => self
+                            .edges
+                            .push((Node::Memory(memory_index), Node::Item(id))),
+                        DataKind::Passive => self.roots.push(Node::Item(id)),
+                    }
+                }
+            }
+            Payload::CodeSectionStart { range, .. } => {
+                self.graph.code_section_bytes = range.len() as u64;
+            }
+            Payload::CodeSectionEntry(body) => {
+                let Some(&id) = self.funcs.get(self.code_base + self.code_seen) else {
+                    return Ok(());
+                };
+                self.code_seen += 1;
+                self.graph.items[id].size_bytes = body.range().len() as u64;
+                self.scan_operators(id, body.get_operators_reader()?)?;
+            }
+            Payload::CustomSection(reader) => {
+                let id = self.add_item(
+                    format!("custom section '{}'", reader.name()),
+                    reader.range().len() as u64,
+                );
+                self.roots.push(Node::Item(id));
+                for (index, name) in function_names(&reader).into_iter().flatten() {
+                    self.function_names.insert(index, name.to_string());
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Record references made by a function body or constant expression
+    fn scan_operators(
+        &mut self,
+        from: usize,
+        mut reader: OperatorsReader<'_>,
+    ) -> Result<(), TwiggyAnalysisError> {
+        while !reader.eof() {
+            let target = match reader.read()? {
+                Operator::Call { function_index }
+                | Operator::ReturnCall { function_index }
+                | Operator::RefFunc { function_index } => Node::Func(function_index),
+                Operator::GlobalGet { global_index } | Operator::GlobalSet { global_index } => {
+                    Node::Global(global_index)
+                }
+                Operator::CallIndirect { table_index, .. }
+                | Operator::ReturnCallIndirect { table_index, .. } => Node::Table(table_index),
+                _ => continue,
+            };
+            self.edges.push((Node::Item(from), target));
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, node: Node) -> Option<usize> {
+        let index = |space: &[usize], i: u32| space.get(i as usize).copied();
+        match node {
+            Node::Item(id) => Some(id),
+            Node::Func(i) => index(&self.funcs, i),
+            Node::Table(i) => index(&self.tables, i),
+            Node::Memory(i) => index(&self.memories, i),
+            Node::Global(i) => index(&self.globals, i),
+        }
+    }
+
+    fn finish(mut self) -> ItemGraph {
+        // Imported functions keep their `import module::name` item names
+        let names = std::mem::take(&mut self.function_names);
+        for (index, symbol) in names {
+            if let Some(&id) = self.funcs.get(index as usize) {
+                let item = &mut self.graph.items[id];
+                if item.is_code {
+                    item.name = demangle(&symbol);
+                    item.symbol = Some(symbol);
+                }
+            }
+        }
+
+        let mut edges = vec![Vec::new(); self.graph.items.len()];
+        for &(from, to) in &self.edges {
+            if let (Some(from), Some(to)) = (self.resolve(from), self.resolve(to)) {
+                edges[from].push(to);
+            }
+        }
+        for list in &mut edges {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        let mut roots: Vec<usize> = self.roots.iter().filter_map(|&r| self.resolve(r)).collect();
+        roots.sort_unstable();
+        roots.dedup();
+
+        self.graph.edges = edges;
+        self.graph.roots = roots;
+        self.graph.imported_functions = self
+            .funcs
+            .iter()
+            .filter(|&&id| !self.graph.items[id].is_code)
+            .count();
+        self.graph.functions = self.funcs;
+        self.graph
+    }
+}
+
+/// Sizes of consecutive section entries starting at `offsets`
+fn entry_sizes(offsets: &[usize], end: usize) -> impl Iterator<Item = u64> + '_ {
+    offsets.iter().enumerate().map(move |(i, &start)| {
+        let next = offsets.get(i + 1).copied().unwrap_or(end);
+        next.saturating_sub(start) as u64
+    })
+}
+
+/// Function names from a `name` custom section, or `None` for any other section
+///
+/// A malformed name section only costs us the names: iteration stops at the
+/// first error instead of failing the analysis.
+pub(crate) fn function_names<'a>(
+    reader: &CustomSectionReader<'a>,
+) -> Option<impl Iterator<Item = (u32, &'a str)>> {
+    let KnownCustom::Name(section) = reader.as_known() else {
+        return None;
+    };
+    let maps = section
+        .into_iter()
+        .map_while(Result::ok)
+        .filter_map(|name| match name {
+            Name::Function(map) => Some(map),
+            _ => None,
+        });
+    Some(maps.flat_map(|map| {
+        map.into_iter()
+            .map_while(Result::ok)
+            .map(|naming| (naming.index, naming.name))
+    }))
+}
+
+/// Decode a legacy Rust symbol (`_ZN...E`), dropping the trailing hash
+///
+/// Other names (already demangled, v0 symbols, C functions) are returned
+/// unchanged.
+pub(crate) fn demangle(symbol: &str) -> String {
+    let Some(mut rest) = symbol
+        .strip_prefix("_ZN")
+        .or_else(|| symbol.strip_prefix("__ZN"))
+    else {
+        return symbol.to_string();
+    };
+
+    let mut components = Vec::new();
+    while !rest.starts_with('E') {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let Ok(len) = rest[..digits].parse::<usize>() else {
+            return symbol.to_string();
+        };
+        let Some(component) = rest.get(digits..digits + len) else {
+            return symbol.to_string();
+        };
+        components.push(component);
+        rest = &rest[digits + len..];
+    }
+
+    if let Some(last) = components.last() {
+        let is_hash = last.len() == 17
+            && last.starts_with('h')
+            && last[1..].bytes().all(|b| b.is_ascii_hexdigit());
+        if is_hash && components.len() > 1 {
+            components.pop();
+        }
+    }
+
+    components
+        .into_iter()
+        .map(demangle_component)
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Decode the `$...$` escapes and `..` path separators of one component
+fn demangle_component(component: &str) -> String {
+    let component = component
+        .strip_prefix("_$")
+        .map_or(component, |_| &component[1..]);
+
+    let mut out = String::with_capacity(component.len());
+    let mut rest = component;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("..") {
+            out.push_str("::");
+            rest = after;
+        } else if let Some(escape) = rest
+            .strip_prefix('$')
+            .and_then(|after| after.split_once('$'))
+            .and_then(|(code, after)| Some((decode_escape(code)?, after)))
+        {
+            out.push(escape.0);
+            rest = escape.1;
+        } else {
+            let ch = rest.chars().next().unwrap_or_default();
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    out
+}
+
+fn decode_escape(code: &str) -> Option<char> {
+    Some(match code {
+        "SP" => '@',
+        "BP" => '*',
+        "RF" => '&',
+        "LT" => '<',
+        "GT" => '>',
+        "LP" => '(',
+        "RP" => ')',
+        "C" => ',',
+        _ => {
+            let hex = code.strip_prefix('u')?;
+            char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+        }
+    })
+}
+
+impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
+    /// Compute analysis items without the twiggy binary
+    pub(super) fn run_native_analysis(
+        &self,
+        mode: AnalysisMode,
+        total_size_bytes: u64,
+    ) -> Result<Vec<AnalysisItem>, TwiggyAnalysisError> {
+        let graph = ItemGraph::read(&self.wasm_file)?;
+        let mut items = match mode {
+            AnalysisMode::Top => graph.top(total_size_bytes),
+            AnalysisMode::Dominators => graph.dominators(total_size_bytes),
+            AnalysisMode::Dead => graph.garbage(total_size_bytes),
+            AnalysisMode::Monos => graph.monos(total_size_bytes),
+        };
+        if let Some(limit) = self.limit {
+            items.truncate(limit);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{leb, section, string, HEADER};
+
+    /// `main` (exported) calls two instantiations of `demo::parse`, which
+    /// both call `helper`; `unused` is unreachable. One memory holds a
+    /// 20-byte data segment.
+    fn module(unused_nops: usize) -> Vec<u8> {
+        let mut wasm = HEADER.to_vec();
+        wasm.extend(section(1, &[1, 0x60, 0, 0]));
+        wasm.extend(section(3, &[5, 0, 0, 0, 0, 0]));
+        wasm.extend(section(5, &[1, 0, 1]));
+
+        let mut exports = vec![1];
+        exports.extend(string("main"));
+        exports.extend([0, 0]);
+        wasm.extend(section(7, &exports));
+
+        let mut unused = vec![0];
+        unused.extend(std::iter::repeat_n(0x01, unused_nops));
+        unused.push(0x0b);
+        let bodies: [&[u8]; 5] = [
+            &[0, 0x10, 1, 0x10, 2, 0x0b],
+            &[0, 0x10, 3, 0x0b],
+            &[0, 0x10, 3, 0x0b],
+            &[0, 0x0b],
+            &unused,
+        ];
+        let mut code = vec![bodies.len() as u8];
+        for body in bodies {
+            code.extend(leb(body.len()));
+            code.extend(body);
+        }
+        wasm.extend(section(10, &code));
+
+        let mut data = vec![1, 0, 0x41, 0, 0x0b];
+        data.extend(leb(20));
+        data.extend([0xaa; 20]);
+        wasm.extend(section(11, &data));
+
+        let names = [
+            "main",
+            "_ZN4demo5parse17h0123456789abcdefE",
+            "_ZN4demo5parse17hfedcba9876543210E",
+            "helper",
+            "unused",
+        ];
+        let mut function_names = leb(names.len());
+        for (index, name) in names.iter().enumerate() {
+            function_names.extend(leb(index));
+            function_names.extend(string(name));
+        }
+        let mut name_section = string("name");
+        name_section.extend(section(1, &function_names));
+        wasm.extend(section(0, &name_section));
+        wasm
+    }
+
+    fn names(items: &[AnalysisItem]) -> Vec<&str> {
+        items.iter().map(|i| i.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_names_functions_and_sizes_items() {
+        let wasm = module(8);
+        let graph = ItemGraph::parse(&wasm).unwrap();
+        let top = graph.top(wasm.len() as u64);
+
+        assert_eq!(top[0].name, "custom section 'name'");
+        assert!(names(&top).contains(&"data[0]"));
+        assert!(names(&top).contains(&"demo::parse"));
+        let unused = top.iter().find(|i| i.name == "unused").unwrap();
+        assert_eq!(unused.size_bytes, 10);
+        assert!(top.windows(2).all(|w| w[0].size_bytes >= w[1].size_bytes));
+    }
+
+    #[test]
+    fn test_garbage_lists_unreachable_functions_only() {
+        let wasm = module(8);
+        let graph = ItemGraph::parse(&wasm).unwrap();
+        assert_eq!(names(&graph.garbage(wasm.len() as u64)), vec!["unused"]);
+    }
+
+    #[test]
+    fn test_declarative_segment_does_not_keep_functions_alive() {
+        let mut wasm = module(8);
+        let code_start = Parser::new(0)
+            .parse_all(&wasm)
+            .find_map(|payload| match payload.unwrap() {
+                Payload::CodeSectionStart { range, size, .. } => {
+                    Some(range.start - 1 - leb(size as usize).len())
+                }
+                _ => None,
+            })
+            .unwrap();
+        // Declare `unused` (function 4) for `ref.func`
+        let elements = section(9, &[1, 3, 0x00, 1, 4]);
+        wasm.splice(code_start..code_start, elements);
+
+        let graph = ItemGraph::parse(&wasm).unwrap();
+        assert_eq!(names(&graph.garbage(wasm.len() as u64)), vec!["unused"]);
+    }
+
+    #[test]
+    fn test_dominators_attribute_shared_callees_to_common_dominator() {
+        let wasm = module(8);
+        let graph = ItemGraph::parse(&wasm).unwrap();
+
+        let nodes = graph.dominator_nodes();
+        let export = nodes.iter().find(|n| n.name == "export \"main\"").unwrap();
+        let main = &export.children[0];
+        assert_eq!(main.name, "main");
+        let mut children: Vec<&str> = main.children.iter().map(|n| n.name.as_str()).collect();
+        children.sort();
+        assert_eq!(children, vec!["demo::parse", "demo::parse", "helper"]);
+
+        let items = graph.dominators(wasm.len() as u64);
+        let main_item = items.iter().find(|i| i.name == "main").unwrap();
+        let subtree: u64 = main.children.iter().map(|n| n.shallow_size).sum();
+        assert_eq!(main_item.size_bytes, main.shallow_size + subtree);
+        assert!(!names(&items).contains(&"unused"));
+    }
+
+    #[test]
+    fn test_monos_lists_instantiations_sharing_a_name() {
+        let wasm = module(8);
+        let graph = ItemGraph::parse(&wasm).unwrap();
+        assert_eq!(
+            names(&graph.monos(wasm.len() as u64)),
+            vec!["demo::parse", "demo::parse"]
+        );
+    }
+
+    #[test]
+    fn test_diff_reports_size_changes_by_name() {
+        let before = ItemGraph::parse(&module(8)).unwrap();
+        let after = ItemGraph::parse(&module(48)).unwrap();
+
+        let changes = ItemGraph::diff(&before, &after);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].name, "unused");
+        assert_eq!(changes[0].delta_bytes, 40);
+        assert!(ItemGraph::diff(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_module() {
+        let err = ItemGraph::parse(b"NOT_WASM_DATA").unwrap_err();
+        assert!(matches!(err, TwiggyAnalysisError::WasmParse(_)));
+        assert!(err.to_string().contains("magic header"));
+    }
+
+    #[test]
+    fn test_demangle_legacy_symbols() {
+        assert_eq!(
+            demangle("_ZN4core3fmt5write17h0123456789abcdefE"),
+            "core::fmt::write"
+        );
+        assert_eq!(
+            demangle(
+                "_ZN50_$LT$T$u20$as$u20$core..convert..Into$LT$U$GT$$GT$4into17h0123456789abcdefE"
+            ),
+            "<T as core::convert::Into<U>>::into"
+        );
+        assert_eq!(demangle("memcpy"), "memcpy");
+        assert_eq!(demangle("_ZN3bad"), "_ZN3bad");
+    }
+}
@@ -1,37 +1,135 @@
 //! Twiggy output parsing logic
 
-use super::analysis_types::{AnalysisItem, AnalysisMode};
+use super::analysis_types::AnalysisItem;
 use super::comparison::ChangeItem;
 use super::error::TwiggyAnalysisError;
 use crate::analyzer::TwiggyAnalyzer;
 use crate::infra::{CommandExecutor, FileSystem};
+use crate::profiling::{self, ProfilePhase};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+/// Items collected from twiggy output, optionally bounded to the largest N
+enum TopItems {
+    All(Vec<AnalysisItem>),
+    Largest {
+        limit: usize,
+        heap: BinaryHeap<Reverse<Ranked>>,
+    },
+}
+
+/// An item ranked by size; on ties the earlier line ranks higher
+struct Ranked {
+    line: usize,
+    item: AnalysisItem,
+}
+
+impl Ranked {
+    fn key(&self) -> (u64, Reverse<usize>) {
+        (self.item.size_bytes, Reverse(self.line))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl TopItems {
+    fn new(limit: Option<usize>) -> Self {
+        match limit {
+            Some(limit) => TopItems::Largest {
+                limit,
+                heap: BinaryHeap::with_capacity(limit.saturating_add(1)),
+            },
+            None => TopItems::All(Vec::new()),
+        }
+    }
+
+    fn push(&mut self, line: usize, item: AnalysisItem) {
+        match self {
+            TopItems::All(items) => items.push(item),
+            TopItems::Largest { limit, heap } => {
+                heap.push(Reverse(Ranked { line, item }));
+                if heap.len() > *limit {
+                    heap.pop();
+                }
+            }
+        }
+    }
+
+    /// Kept items in output order
+    fn into_items(self) -> Vec<AnalysisItem> {
+        match self {
+            TopItems::All(items) => items,
+            TopItems::Largest { heap, .. } => {
+                let mut ranked: Vec<Ranked> = heap.into_iter().map(|Reverse(r)| r).collect();
+                ranked.sort_by_key(|r| r.line);
+                ranked.into_iter().map(|r| r.item).collect()
+            }
+        }
+    }
+}
 
 impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
-    /// Parse twiggy output into structured data
+    /// Parse buffered twiggy output into structured data
+    #[cfg(test)]
     pub(super) fn parse_output(
         &self,
         output: &str,
-        _mode: AnalysisMode,
+        _mode: super::analysis_types::AnalysisMode,
     ) -> Result<Vec<AnalysisItem>, TwiggyAnalysisError> {
-        // Pre-allocate based on line count estimate (skip 2 header lines)
-        let estimated_capacity = output.lines().count().saturating_sub(2);
-        let mut items = Vec::with_capacity(estimated_capacity);
+        self.parse_reader(output.as_bytes(), self.limit)
+    }
 
-        for line in output.lines().skip(2) {
-            // Skip header lines
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
+    /// Parse twiggy output line by line
+    ///
+    /// With a `limit`, only the largest `limit` items are held at any time
+    /// (in a min-heap), so a listing of millions of rows never materializes.
+    /// Kept items stay in output order, which for dominators is tree order.
+    pub(super) fn parse_reader(
+        &self,
+        mut reader: impl BufRead,
+        limit: Option<usize>,
+    ) -> Result<Vec<AnalysisItem>, TwiggyAnalysisError> {
+        let _span = profiling::span(ProfilePhase::Parsing, "twiggy output");
 
-            // Parse line format: "  Size | % Total | Name"
-            // Example: " 123456 | 12.34% | code[123]"
-            if let Some(item) = self.parse_line(line) {
-                items.push(item);
+        let mut kept = TopItems::new(limit);
+        let mut line = String::new();
+        let mut index = 0;
+        while reader.read_line(&mut line)? > 0 {
+            // Skip header lines
+            if index >= 2 {
+                // Parse line format: "  Size | % Total | Name"
+                // Example: " 123456 | 12.34% | code[123]"
+                let trimmed = line.trim();
+                if !trimmed.is_empty() {
+                    if let Some(item) = self.parse_line(trimmed) {
+                        kept.push(index, item);
+                    }
+                }
             }
+            index += 1;
+            line.clear();
         }
 
-        Ok(items)
+        Ok(kept.into_items())
     }
 
     /// Parse a single line from twiggy output
@@ -81,6 +179,8 @@ impl TwiggyAnalyzer {
                     changes.push(ChangeItem {
                         delta_bytes,
                         name: name.trim().to_string(),
+                        before_bytes: None,
+                        after_bytes: None,
                     });
                 }
             }
@@ -93,6 +193,7 @@ impl TwiggyAnalyzer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyzer::twiggy::AnalysisMode;
 
     #[test]
     fn test_parse_line_valid_format_extracts_all_fields() {
@@ -122,6 +223,25 @@ mod tests {
         assert_eq!(item.name, "data[456]");
     }
 
+    #[test]
+    fn test_parse_reader_with_limit_keeps_largest_in_output_order() {
+        let analyzer = TwiggyAnalyzer::new("dummy.wasm");
+        let output = " Retained Bytes │ Retained % │ Item\n────────────────┼────────────┼─────\n            900 ┊     45.00% ┊ ⤷ main\n            100 ┊      5.00% ┊   ⤷ small\n            500 ┊     25.00% ┊   ⤷ medium\n            500 ┊     25.00% ┊   ⤷ tie\n            700 ┊     35.00% ┊ ⤷ other\n";
+
+        let items = analyzer.parse_reader(output.as_bytes(), Some(3)).unwrap();
+
+        let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["⤷ main", "⤷ medium", "⤷ other"]);
+        assert_eq!(
+            analyzer
+                .with_limit(10)
+                .parse_output(output, AnalysisMode::Dominators)
+                .unwrap()
+                .len(),
+            5
+        );
+    }
+
     #[test]
     fn test_parse_output_malformed_input_returns_empty_items() {
         let analyzer = TwiggyAnalyzer::new("dummy.wasm");
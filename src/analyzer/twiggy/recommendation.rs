@@ -9,8 +9,14 @@ pub struct Recommendation {
     pub priority: String,
     /// Description of the recommendation
     pub description: String,
-    /// Estimated savings in KB
-    pub estimated_savings_kb: u64,
+    /// Estimated savings in bytes
+    pub estimated_savings_bytes: u64,
     /// Estimated savings as percentage
     pub estimated_savings_percent: f64,
+    /// Rule that produced it (e.g. `bloat.heavy_crate`), matched by `.wasm-slim-ignore`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub rule: String,
+    /// Symbol or crate it is about, matched by `.wasm-slim-ignore`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
 }
@@ -1,10 +1,13 @@
 //! Recommendation generation and monomorphization analysis
 
 use super::analysis_types::{AnalysisItem, AnalysisMode, MonomorphizationGroup};
+use super::mono_types;
 use super::recommendation::Recommendation;
 use crate::analyzer::TwiggyAnalyzer;
+use crate::fmt::{format_bytes, percent_of};
+use crate::i18n;
 use crate::infra::{CommandExecutor, FileSystem};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
     /// Generate actionable recommendations based on analysis
@@ -41,17 +44,20 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
         total_size: u64,
         recommendations: &mut Vec<Recommendation>,
     ) {
-        // Check for large data segments (>50KB)
+        let thresholds = self.thresholds.for_bundle(total_size);
+
+        // Check for large data segments
         for item in items {
-            if item.name.starts_with("data[") && item.size_bytes > 50 * 1024 {
+            if item.name.starts_with("data[")
+                && item.size_bytes > thresholds.large_data_segment_bytes
+            {
                 recommendations.push(Recommendation {
                     priority: "P1".to_string(),
-                    description: format!(
-                        "Large data segment '{}' detected. Consider externalizing embedded assets or generating data at runtime.",
-                        item.name
-                    ),
-                    estimated_savings_kb: item.size_bytes / 1024,
+                    description: i18n::tf("twiggy.large_data_segment", &[("name", &item.name)]),
+                    estimated_savings_bytes: item.size_bytes,
                     estimated_savings_percent: item.percentage,
+                    rule: "twiggy.large_data_segment".to_string(),
+                    subject: Some(item.name.clone()),
                 });
             }
         }
@@ -59,17 +65,19 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
         // Check if top 20 items dominate
         if items.len() >= 20 {
             let top_20_size: u64 = items.iter().take(20).map(|i| i.size_bytes).sum();
-            let top_20_percent = (top_20_size as f64 / total_size as f64) * 100.0;
+            let top_20_percent = percent_of(top_20_size, total_size);
 
-            if top_20_percent > 30.0 {
+            if top_20_percent > thresholds.top_items_percent {
                 recommendations.push(Recommendation {
                     priority: "P0".to_string(),
-                    description: format!(
-                        "Top 20 items contribute {:.1}% of bundle. Focus optimization efforts here for maximum impact.",
-                        top_20_percent
+                    description: i18n::tf(
+                        "twiggy.top_items_dominate",
+                        &[("percent", &format!("{:.1}", top_20_percent))],
                     ),
-                    estimated_savings_kb: (top_20_size as f64 * 0.5) as u64 / 1024, // Estimate 50% reduction potential
+                    estimated_savings_bytes: top_20_size / 2, // Estimate 50% reduction potential
                     estimated_savings_percent: top_20_percent * 0.5,
+                    rule: "twiggy.top_items_dominate".to_string(),
+                    subject: None,
                 });
             }
         }
@@ -79,19 +87,26 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
     pub(super) fn generate_dominator_recommendations(
         &self,
         items: &[AnalysisItem],
-        _total_size: u64,
+        total_size: u64,
         recommendations: &mut Vec<Recommendation>,
     ) {
+        let thresholds = self.thresholds.for_bundle(total_size);
+
         for item in items {
-            if item.percentage > 20.0 {
+            if item.percentage > thresholds.dominator_percent {
                 recommendations.push(Recommendation {
                     priority: "P0".to_string(),
-                    description: format!(
-                        "Symbol '{}' dominates {:.1}% of bundle. Making this optional via feature flag could provide significant savings.",
-                        item.name, item.percentage
+                    description: i18n::tf(
+                        "twiggy.symbol_dominates",
+                        &[
+                            ("name", &item.name),
+                            ("percent", &format!("{:.1}", item.percentage)),
+                        ],
                     ),
-                    estimated_savings_kb: item.size_bytes / 1024,
+                    estimated_savings_bytes: item.size_bytes,
                     estimated_savings_percent: item.percentage,
+                    rule: "twiggy.symbol_dominates".to_string(),
+                    subject: Some(item.name.clone()),
                 });
             }
         }
@@ -105,24 +120,29 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
         recommendations: &mut Vec<Recommendation>,
     ) {
         let total_dead: u64 = items.iter().map(|i| i.size_bytes).sum();
-        let dead_percent = (total_dead as f64 / total_size as f64) * 100.0;
+        let dead_percent = percent_of(total_dead, total_size);
+        let thresholds = self.thresholds.for_bundle(total_size);
 
-        if dead_percent > 10.0 {
+        if dead_percent > thresholds.dead_code_percent {
             recommendations.push(Recommendation {
                 priority: "P1".to_string(),
-                description: format!(
-                    "{:.1}% of bundle is potentially removable. Enable lto = 'fat' and strip = true in Cargo.toml.",
-                    dead_percent
+                description: i18n::tf(
+                    "twiggy.dead_code_removable",
+                    &[("percent", &format!("{:.1}", dead_percent))],
                 ),
-                estimated_savings_kb: total_dead / 1024,
+                estimated_savings_bytes: total_dead,
                 estimated_savings_percent: dead_percent,
+                rule: "twiggy.dead_code_removable".to_string(),
+                subject: None,
             });
         } else if dead_percent < 1.0 {
             recommendations.push(Recommendation {
                 priority: "P3".to_string(),
-                description: "Minimal dead code detected (<1%). Bundle is well-optimized. Focus on other areas.".to_string(),
-                estimated_savings_kb: 0,
+                description: i18n::t("twiggy.dead_code_minimal").to_string(),
+                estimated_savings_bytes: 0,
                 estimated_savings_percent: 0.0,
+                rule: "twiggy.dead_code_minimal".to_string(),
+                subject: None,
             });
         }
     }
@@ -140,12 +160,14 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
             if item.name.contains("instantiation") {
                 recommendations.push(Recommendation {
                     priority: "P2".to_string(),
-                    description: format!(
-                        "Excessive monomorphization detected in '{}'. Consider using trait objects or limiting generic types.",
-                        item.name
+                    description: i18n::tf(
+                        "twiggy.excessive_monomorphization",
+                        &[("name", &item.name)],
                     ),
-                    estimated_savings_kb: item.size_bytes / 1024,
+                    estimated_savings_bytes: item.size_bytes,
                     estimated_savings_percent: item.percentage,
+                    rule: "twiggy.excessive_monomorphization".to_string(),
+                    subject: Some(item.name.clone()),
                 });
             }
         }
@@ -159,7 +181,7 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
         &self,
         items: &[AnalysisItem],
     ) -> Vec<MonomorphizationGroup> {
-        let mut groups: HashMap<String, Vec<&AnalysisItem>> = HashMap::new();
+        let mut groups: BTreeMap<String, Vec<&AnalysisItem>> = BTreeMap::new();
 
         // Group items by base function name
         for item in items {
@@ -185,6 +207,18 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
                     .unwrap_or(0);
                 let potential_savings_bytes = total_size_bytes.saturating_sub(max_size);
 
+                let mut type_arguments: Vec<Vec<String>> = instantiations
+                    .iter()
+                    .map(|i| mono_types::type_arguments(&i.name))
+                    .collect();
+                if type_arguments.iter().all(Vec::is_empty) {
+                    type_arguments.clear();
+                }
+                let refactor = Some(mono_types::suggest_refactor(
+                    &function_name,
+                    &type_arguments,
+                ));
+
                 MonomorphizationGroup {
                     function_name,
                     instantiation_count,
@@ -192,12 +226,14 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
                     avg_size_bytes,
                     instantiations: instantiations.into_iter().cloned().collect(),
                     potential_savings_bytes,
+                    type_arguments,
+                    refactor,
                 }
             })
             .collect();
 
         // Sort by potential savings (highest first)
-        result.sort_by(|a, b| b.potential_savings_bytes.cmp(&a.potential_savings_bytes));
+        result.sort_by_key(|g| std::cmp::Reverse(g.potential_savings_bytes));
 
         result
     }
@@ -256,65 +292,98 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
         // Calculate total monomorphization overhead
         let total_mono_size: u64 = groups.iter().map(|g| g.total_size_bytes).sum();
         let total_savings: u64 = groups.iter().map(|g| g.potential_savings_bytes).sum();
-        let mono_percent = (total_mono_size as f64 / total_size_bytes as f64) * 100.0;
+        let mono_percent = percent_of(total_mono_size, total_size_bytes);
+        let thresholds = self.thresholds.for_bundle(total_size_bytes);
 
         // Overall assessment
-        if mono_percent > 15.0 {
+        if mono_percent > thresholds.mono_significant_percent {
             recommendations.push(Recommendation {
                 priority: "P0".to_string(),
-                description: format!(
-                    "Significant monomorphization bloat detected ({:.1}% of bundle). {} KB across {} generic functions.",
-                    mono_percent,
-                    total_mono_size / 1024,
-                    groups.len()
+                description: i18n::tf(
+                    "twiggy.mono_significant",
+                    &[
+                        ("percent", &format!("{:.1}", mono_percent)),
+                        ("size", &format_bytes(total_mono_size)),
+                        ("count", &groups.len()),
+                    ],
                 ),
-                estimated_savings_kb: total_savings / 1024,
-                estimated_savings_percent: (total_savings as f64 / total_size_bytes as f64) * 100.0,
+                estimated_savings_bytes: total_savings,
+                estimated_savings_percent: percent_of(total_savings, total_size_bytes),
+                rule: "twiggy.mono_significant".to_string(),
+                subject: None,
             });
-        } else if mono_percent > 5.0 {
+        } else if mono_percent > thresholds.mono_moderate_percent {
             recommendations.push(Recommendation {
                 priority: "P2".to_string(),
-                description: format!(
-                    "Moderate monomorphization detected ({:.1}% of bundle). Consider optimization for top contributors.",
-                    mono_percent
+                description: i18n::tf(
+                    "twiggy.mono_moderate",
+                    &[("percent", &format!("{:.1}", mono_percent))],
                 ),
-                estimated_savings_kb: total_savings / 1024,
-                estimated_savings_percent: (total_savings as f64 / total_size_bytes as f64) * 100.0,
+                estimated_savings_bytes: total_savings,
+                estimated_savings_percent: percent_of(total_savings, total_size_bytes),
+                rule: "twiggy.mono_moderate".to_string(),
+                subject: None,
             });
         } else {
             recommendations.push(Recommendation {
                 priority: "P3".to_string(),
-                description: format!(
-                    "Minimal monomorphization overhead ({:.1}%). Not a priority optimization target.",
-                    mono_percent
+                description: i18n::tf(
+                    "twiggy.mono_minimal",
+                    &[("percent", &format!("{:.1}", mono_percent))],
                 ),
-                estimated_savings_kb: 0,
+                estimated_savings_bytes: 0,
                 estimated_savings_percent: 0.0,
+                rule: "twiggy.mono_minimal".to_string(),
+                subject: None,
             });
             return recommendations; // No need for per-function recommendations
         }
 
-        // Top offenders (>10 instantiations or >50KB total)
+        // Top offenders (many instantiations or large total); priorities are
+        // relative to the function threshold (100KB / 30KB at the default 50KB)
+        let function_bytes = thresholds.mono_function_bytes;
         for group in groups.iter().take(10) {
-            if group.instantiation_count >= 10 || group.total_size_bytes > 50 * 1024 {
-                let priority = if group.potential_savings_bytes > 100 * 1024 {
+            if group.instantiation_count >= thresholds.mono_instantiations
+                || group.total_size_bytes > function_bytes
+            {
+                let priority = if group.potential_savings_bytes > function_bytes * 2 {
                     "P0"
-                } else if group.potential_savings_bytes > 30 * 1024 {
+                } else if group.potential_savings_bytes > function_bytes * 3 / 5 {
                     "P1"
                 } else {
                     "P2"
                 };
 
+                let size = format_bytes(group.total_size_bytes);
+                let description = match &group.refactor {
+                    Some(refactor) => i18n::tf(
+                        "twiggy.mono_function_refactor",
+                        &[
+                            ("name", &group.function_name),
+                            ("count", &group.instantiation_count),
+                            ("size", &size),
+                            ("refactor", refactor),
+                        ],
+                    ),
+                    None => i18n::tf(
+                        "twiggy.mono_function",
+                        &[
+                            ("name", &group.function_name),
+                            ("count", &group.instantiation_count),
+                            ("size", &size),
+                        ],
+                    ),
+                };
                 recommendations.push(Recommendation {
                     priority: priority.to_string(),
-                    description: format!(
-                        "Function '{}' has {} instantiations ({} KB total). Consider using 'Box<dyn Trait>' or limiting type parameters.",
-                        group.function_name,
-                        group.instantiation_count,
-                        group.total_size_bytes / 1024
+                    description,
+                    estimated_savings_bytes: group.potential_savings_bytes,
+                    estimated_savings_percent: percent_of(
+                        group.potential_savings_bytes,
+                        total_size_bytes,
                     ),
-                    estimated_savings_kb: group.potential_savings_bytes / 1024,
-                    estimated_savings_percent: (group.potential_savings_bytes as f64 / total_size_bytes as f64) * 100.0,
+                    rule: "twiggy.mono_function".to_string(),
+                    subject: Some(group.function_name.clone()),
                 });
             }
         }
@@ -326,6 +395,7 @@ impl<FS: FileSystem, CE: CommandExecutor> TwiggyAnalyzer<FS, CE> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyzer::thresholds::RecommendationThresholds;
 
     #[test]
     fn test_generate_recommendations_empty_items_returns_empty() {
@@ -454,6 +524,48 @@ mod tests {
         assert!(!recs.is_empty());
     }
 
+    #[test]
+    fn test_generate_recommendations_thresholds_scale_with_bundle_size() {
+        let analyzer = TwiggyAnalyzer::new("dummy.wasm");
+        let items = vec![AnalysisItem {
+            size_bytes: 20 * 1024,
+            percentage: 25.0,
+            name: "data[0]".to_string(),
+        }];
+
+        // 80 KB bundle: a 20 KB segment is large, a 25% dominator is normal
+        let mut recs = Vec::new();
+        analyzer.generate_top_recommendations(&items, 80 * 1024, &mut recs);
+        assert!(recs.iter().any(|r| r.description.contains("data segment")));
+
+        let mut recs = Vec::new();
+        analyzer.generate_dominator_recommendations(&items, 80 * 1024, &mut recs);
+        assert!(recs.is_empty());
+
+        // Same items in a 1 MB bundle: the segment is below the threshold
+        let mut recs = Vec::new();
+        analyzer.generate_top_recommendations(&items, 1024 * 1024, &mut recs);
+        assert!(recs.is_empty());
+    }
+
+    #[test]
+    fn test_generate_recommendations_uses_configured_thresholds() {
+        let thresholds = RecommendationThresholds {
+            dominator_percent: 50.0,
+            ..Default::default()
+        };
+        let analyzer = TwiggyAnalyzer::new("dummy.wasm").with_thresholds(thresholds);
+        let items = vec![AnalysisItem {
+            size_bytes: 250 * 1024,
+            percentage: 25.0,
+            name: "dominating_symbol".to_string(),
+        }];
+
+        let mut recs = Vec::new();
+        analyzer.generate_dominator_recommendations(&items, 1024 * 1024, &mut recs);
+        assert!(recs.is_empty());
+    }
+
     #[test]
     fn test_extract_base_function_name_demangled_symbols() {
         let analyzer = TwiggyAnalyzer::new("dummy.wasm");
@@ -546,6 +658,42 @@ mod tests {
         assert_eq!(groups[0].potential_savings_bytes, 3000 - max_size);
     }
 
+    #[test]
+    fn test_group_monomorphizations_lists_type_arguments_and_refactor() {
+        let analyzer = TwiggyAnalyzer::new("dummy.wasm");
+        let item = |name: &str, size_bytes| AnalysisItem {
+            size_bytes,
+            percentage: 1.0,
+            name: name.to_string(),
+        };
+        let items = vec![
+            item("app::greet<alloc::string::String>", 60 * 1024),
+            item("app::greet<&str>", 50 * 1024),
+            item("app::greet<alloc::borrow::Cow<str>>", 40 * 1024),
+        ];
+
+        let groups = analyzer.group_monomorphizations(&items);
+        assert_eq!(
+            groups[0].type_arguments,
+            [
+                vec!["alloc::string::String".to_string()],
+                vec!["&str".to_string()],
+                vec!["alloc::borrow::Cow<str>".to_string()],
+            ]
+        );
+        assert!(groups[0].refactor.as_deref().unwrap().contains("`&str`"));
+
+        let recs = analyzer.generate_monos_recommendations_enhanced(&groups, 500 * 1024);
+        let rec = recs
+            .iter()
+            .find(|r| r.rule == "twiggy.mono_function")
+            .unwrap();
+        assert!(rec
+            .description
+            .starts_with("Function 'app::greet' has 3 instantiations"));
+        assert!(rec.description.contains("Take `&str` in `app::greet`"));
+    }
+
     #[test]
     fn test_group_monomorphizations_filters_single_instantiation() {
         let analyzer = TwiggyAnalyzer::new("dummy.wasm");
@@ -624,6 +772,32 @@ mod tests {
         assert_eq!(groups[1].potential_savings_bytes, 100); // 200 - 100
     }
 
+    #[test]
+    fn test_group_monomorphizations_orders_ties_by_name() {
+        let analyzer = TwiggyAnalyzer::new("dummy.wasm");
+
+        let item = |name: &str| AnalysisItem {
+            size_bytes: 100,
+            percentage: 0.1,
+            name: name.to_string(),
+        };
+        let items = vec![
+            item("zeta<i32>"),
+            item("alpha<i32>"),
+            item("zeta<u32>"),
+            item("mid<i32>"),
+            item("alpha<u32>"),
+            item("mid<u32>"),
+        ];
+
+        let names: Vec<_> = analyzer
+            .group_monomorphizations(&items)
+            .into_iter()
+            .map(|g| g.function_name)
+            .collect();
+        assert_eq!(names, vec!["alpha", "mid", "zeta"]);
+    }
+
     #[test]
     fn test_generate_monos_recommendations_enhanced_significant_bloat() {
         let analyzer = TwiggyAnalyzer::new("dummy.wasm");
@@ -634,7 +808,9 @@ mod tests {
             total_size_bytes: 200_000, // 200 KB
             avg_size_bytes: 10_000,
             instantiations: vec![],
-            potential_savings_bytes: 190_000, // Save 190 KB
+            potential_savings_bytes: 190_000,
+            type_arguments: Vec::new(),
+            refactor: None, // Save 190 KB
         }];
 
         let total_size = 1_000_000; // 1 MB total
@@ -671,6 +847,8 @@ mod tests {
             avg_size_bytes: 10_000,
             instantiations: vec![],
             potential_savings_bytes: 70_000,
+            type_arguments: Vec::new(),
+            refactor: None,
         }];
 
         let total_size = 1_000_000; // 1 MB total (8% monomorphization)
@@ -693,6 +871,8 @@ mod tests {
             avg_size_bytes: 3_333,
             instantiations: vec![],
             potential_savings_bytes: 6_667,
+            type_arguments: Vec::new(),
+            refactor: None,
         }];
 
         let total_size = 1_000_000; // 1 MB total (1% monomorphization)
@@ -702,7 +882,7 @@ mod tests {
         assert_eq!(recommendations.len(), 1); // Only overall assessment
         assert_eq!(recommendations[0].priority, "P3");
         assert!(recommendations[0].description.contains("Minimal"));
-        assert_eq!(recommendations[0].estimated_savings_kb, 0); // Not worth optimizing
+        assert_eq!(recommendations[0].estimated_savings_bytes, 0); // Not worth optimizing
     }
 
     #[test]
@@ -718,7 +898,9 @@ mod tests {
             total_size_bytes: 200_000,
             avg_size_bytes: 4_000,
             instantiations: vec![],
-            potential_savings_bytes: 150_000, // 150 KB
+            potential_savings_bytes: 150_000,
+            type_arguments: Vec::new(),
+            refactor: None, // 150 KB
         }];
         let recs = analyzer.generate_monos_recommendations_enhanced(&groups_p0, total_size);
         let func_rec = recs
@@ -734,7 +916,9 @@ mod tests {
             total_size_bytes: 100_000,
             avg_size_bytes: 6_666,
             instantiations: vec![],
-            potential_savings_bytes: 50_000, // 50 KB
+            potential_savings_bytes: 50_000,
+            type_arguments: Vec::new(),
+            refactor: None, // 50 KB
         }];
         let recs = analyzer.generate_monos_recommendations_enhanced(&groups_p1, total_size);
         let func_rec = recs
@@ -750,7 +934,9 @@ mod tests {
             total_size_bytes: 60_000,
             avg_size_bytes: 6_000,
             instantiations: vec![],
-            potential_savings_bytes: 20_000, // 20 KB
+            potential_savings_bytes: 20_000,
+            type_arguments: Vec::new(),
+            refactor: None, // 20 KB
         }];
         let recs = analyzer.generate_monos_recommendations_enhanced(&groups_p2, total_size);
         let func_rec = recs
@@ -2,58 +2,80 @@
 //!
 //! Provides console output formatters for twiggy analysis results.
 
-use crate::analyzer::twiggy::{AnalysisResults, ComparisonResults};
+use crate::analyzer::report_utils::{
+    format_bytes, format_delta, omitted_footer, truncate_str, ItemLimit,
+};
+use crate::analyzer::twiggy::{
+    AnalysisResults, ChangeKind, ComparisonResults, MultiComparison, SizeDelta, SymbolDiff,
+};
+use crate::fmt::percent_of;
+use crate::i18n::{t, tf};
 use console::style;
 
+/// Number of top contributors listed by default
+const DEFAULT_TOP_ITEMS: usize = 20;
+/// Number of generic functions listed by default
+const DEFAULT_MONO_GROUPS: usize = 10;
+/// Number of instantiations listed with their type arguments per generic function
+const MONO_TYPE_ROWS: usize = 5;
+/// Number of symbol changes listed by default
+const DEFAULT_TOP_CHANGES: usize = 15;
+/// Number of symbol changes listed per step of an N-way comparison by default
+const DEFAULT_STEP_CHANGES: usize = 5;
+
 /// Print twiggy analysis report to console
 pub fn print_analysis_report(results: &AnalysisResults) {
+    print_analysis_report_with_limit(results, ItemLimit::Default);
+}
+
+/// Print twiggy analysis report, limiting the items listed per section
+pub fn print_analysis_report_with_limit(results: &AnalysisResults, limit: ItemLimit) {
     println!();
-    println!("{}", style("WASM Size Analysis").bold().underlined());
+    println!(
+        "{}",
+        style(t("report.wasm_size_analysis")).bold().underlined()
+    );
     println!();
 
     // Summary
-    let total_mb = results.total_size_bytes as f64 / (1024.0 * 1024.0);
     println!(
-        "📦 {} {:.2} MB ({} bytes)",
-        style("Total Size:").bold(),
-        total_mb,
+        "📦 {} {} ({} bytes)",
+        style(t("report.total_size")).bold(),
+        format_bytes(results.total_size_bytes),
         format_number(results.total_size_bytes)
     );
-    println!("🔍 {} {}", style("Analysis Mode:").bold(), results.mode);
+    println!(
+        "🔍 {} {}",
+        style(t("report.analysis_mode")).bold(),
+        results.mode
+    );
     println!();
 
     // Top contributors
     if !results.items.is_empty() {
         println!(
             "{}",
-            style(format!("TOP CONTRIBUTORS ({} items):", results.items.len())).bold()
+            style(tf(
+                "report.top_contributors",
+                &[("count", &results.items.len())]
+            ))
+            .bold()
         );
         println!("{}", style("─".repeat(70)).dim());
 
-        let display_count = results.items.len().min(20);
+        let display_count = limit.visible(results.items.len(), Some(DEFAULT_TOP_ITEMS));
         for (i, item) in results.items.iter().take(display_count).enumerate() {
-            let size_kb = item.size_bytes / 1024;
-            let size_str = if size_kb >= 1024 {
-                format!("{:.2} MB", size_kb as f64 / 1024.0)
-            } else {
-                format!("{} KB", size_kb)
-            };
-
             println!(
                 "  {:2}. {:>10} ({:>5.1}%)  {}",
                 i + 1,
-                style(size_str).cyan().bold(),
+                style(format_bytes(item.size_bytes)).cyan().bold(),
                 item.percentage,
                 style(&item.name).dim()
             );
         }
 
-        if results.items.len() > display_count {
-            println!(
-                "\n      {} {} more items...",
-                style("...").dim(),
-                results.items.len() - display_count
-            );
+        if let Some(footer) = omitted_footer(results.items.len(), display_count, "items") {
+            println!("\n      {}", style(footer).dim());
         }
 
         println!();
@@ -71,8 +93,7 @@ pub fn print_analysis_report(results: &AnalysisResults) {
             let total_instantiations: usize =
                 mono_groups.iter().map(|g| g.instantiation_count).sum();
             let total_savings: u64 = mono_groups.iter().map(|g| g.potential_savings_bytes).sum();
-            let total_savings_kb = total_savings / 1024;
-            let savings_percent = (total_savings as f64 / results.total_size_bytes as f64) * 100.0;
+            let savings_percent = percent_of(total_savings, results.total_size_bytes);
 
             println!(
                 "  {} {} generic functions with {} total instantiations",
@@ -81,15 +102,15 @@ pub fn print_analysis_report(results: &AnalysisResults) {
                 style(total_instantiations).cyan().bold()
             );
             println!(
-                "  {} ~{} KB ({:.1}% of bundle)",
+                "  {} ~{} ({:.1}% of bundle)",
                 style("Potential savings:").bold(),
-                style(format_number(total_savings_kb)).green().bold(),
+                style(format_bytes(total_savings)).green().bold(),
                 savings_percent
             );
             println!();
 
             // Top groups
-            let display_count = mono_groups.len().min(10);
+            let display_count = limit.visible(mono_groups.len(), Some(DEFAULT_MONO_GROUPS));
             println!(
                 "{}",
                 style(format!("TOP {} GENERIC FUNCTIONS:", display_count)).bold()
@@ -97,9 +118,6 @@ pub fn print_analysis_report(results: &AnalysisResults) {
             println!("{}", style("─".repeat(70)).dim());
 
             for (i, group) in mono_groups.iter().take(display_count).enumerate() {
-                let size_kb = group.total_size_bytes / 1024;
-                let savings_kb = group.potential_savings_bytes / 1024;
-
                 println!();
                 println!(
                     "  {:2}. {}",
@@ -107,26 +125,60 @@ pub fn print_analysis_report(results: &AnalysisResults) {
                     style(&group.function_name).yellow().bold()
                 );
                 println!(
-                    "      {} {} instantiations, {} KB total (avg {} KB each)",
+                    "      {} {} instantiations, {} total (avg {} each)",
                     style("→").dim(),
                     style(group.instantiation_count).cyan(),
-                    style(format_number(size_kb)).cyan(),
-                    style(format_number(group.avg_size_bytes / 1024)).dim()
-                );
-                println!(
-                    "      {} ~{} KB using trait objects (Box<dyn Trait>)",
-                    style("→ Savings:").dim(),
-                    style(format_number(savings_kb)).green().bold()
+                    style(format_bytes(group.total_size_bytes)).cyan(),
+                    style(format_bytes(group.avg_size_bytes)).dim()
                 );
+                for (instantiation, args) in group
+                    .instantiations
+                    .iter()
+                    .zip(&group.type_arguments)
+                    .take(MONO_TYPE_ROWS)
+                {
+                    println!(
+                        "        {:>12}  <{}>",
+                        format_bytes(instantiation.size_bytes),
+                        truncate_str(&args.join(", "), 60)
+                    );
+                }
+                if group.type_arguments.len() > MONO_TYPE_ROWS {
+                    println!(
+                        "        {}",
+                        style(format!(
+                            "... {} more instantiations",
+                            group.type_arguments.len() - MONO_TYPE_ROWS
+                        ))
+                        .dim()
+                    );
+                }
+                match &group.refactor {
+                    Some(refactor) => {
+                        println!(
+                            "      {} ~{}",
+                            style("→ Savings:").dim(),
+                            style(format_bytes(group.potential_savings_bytes))
+                                .green()
+                                .bold()
+                        );
+                        println!("      {} {}", style("→ Refactor:").dim(), refactor);
+                    }
+                    None => println!(
+                        "      {} ~{} using trait objects (Box<dyn Trait>)",
+                        style("→ Savings:").dim(),
+                        style(format_bytes(group.potential_savings_bytes))
+                            .green()
+                            .bold()
+                    ),
+                }
             }
 
-            if mono_groups.len() > display_count {
+            if let Some(footer) =
+                omitted_footer(mono_groups.len(), display_count, "generic functions")
+            {
                 println!();
-                println!(
-                    "      {} {} more generic functions...",
-                    style("...").dim(),
-                    mono_groups.len() - display_count
-                );
+                println!("      {}", style(footer).dim());
             }
 
             println!();
@@ -137,7 +189,7 @@ pub fn print_analysis_report(results: &AnalysisResults) {
 
     // Recommendations
     if !results.recommendations.is_empty() {
-        println!("{}", style("RECOMMENDATIONS:").bold().yellow());
+        println!("{}", style(t("report.recommendations")).bold().yellow());
         println!("{}", style("─".repeat(70)).dim());
         println!();
 
@@ -157,11 +209,11 @@ pub fn print_analysis_report(results: &AnalysisResults) {
                 rec.description
             );
 
-            if rec.estimated_savings_kb > 0 {
+            if rec.estimated_savings_bytes > 0 {
                 println!(
-                    "      {} ~{} KB ({:.1}%)",
-                    style("→ Potential savings:").dim(),
-                    format_number(rec.estimated_savings_kb),
+                    "      {} ~{} ({:.1}%)",
+                    style(t("report.potential_savings")).dim(),
+                    format_bytes(rec.estimated_savings_bytes),
                     rec.estimated_savings_percent
                 );
             }
@@ -170,13 +222,13 @@ pub fn print_analysis_report(results: &AnalysisResults) {
         }
 
         // Calculate total potential savings
-        let total_savings_kb: u64 = results
+        let total_savings_bytes: u64 = results
             .recommendations
             .iter()
-            .map(|r| r.estimated_savings_kb)
+            .map(|r| r.estimated_savings_bytes)
             .sum();
 
-        if total_savings_kb > 0 {
+        if total_savings_bytes > 0 {
             let total_savings_percent: f64 = results
                 .recommendations
                 .iter()
@@ -185,19 +237,15 @@ pub fn print_analysis_report(results: &AnalysisResults) {
 
             println!("{}", style("─".repeat(70)).dim());
             println!(
-                "  {} ~{} KB ({:.1}%)",
-                style("Total optimization potential:").bold(),
-                format_number(total_savings_kb),
+                "  {} ~{} ({:.1}%)",
+                style(t("report.total_potential")).bold(),
+                format_bytes(total_savings_bytes),
                 total_savings_percent
             );
             println!();
         }
     } else {
-        println!(
-            "{}",
-            style("✨ No major optimization opportunities detected. Bundle is well-optimized!")
-                .green()
-        );
+        println!("{}", style(t("report.well_optimized")).green());
         println!();
     }
 
@@ -211,16 +259,29 @@ pub fn print_analysis_report(results: &AnalysisResults) {
 
 /// Print comparison report to console
 pub fn print_comparison_report(results: &ComparisonResults) {
+    print_comparison_report_with_limit(results, ItemLimit::Default);
+}
+
+/// Print comparison report, limiting the symbol changes listed
+pub fn print_comparison_report_with_limit(results: &ComparisonResults, limit: ItemLimit) {
     println!();
-    println!("{}", style("WASM Build Comparison").bold().underlined());
+    println!(
+        "{}",
+        style(t("report.comparison_title")).bold().underlined()
+    );
     println!();
 
     // Size comparison
-    let before_mb = results.before_size_bytes as f64 / (1024.0 * 1024.0);
-    let after_mb = results.after_size_bytes as f64 / (1024.0 * 1024.0);
-
-    println!("📊 {} {:.2} MB", style("Before:").bold(), before_mb);
-    println!("📊 {} {:.2} MB", style("After:").bold(), after_mb);
+    println!(
+        "📊 {} {}",
+        style(t("report.before")).bold(),
+        format_bytes(results.before_size_bytes)
+    );
+    println!(
+        "📊 {} {}",
+        style(t("report.after")).bold(),
+        format_bytes(results.after_size_bytes)
+    );
     println!();
 
     // Delta
@@ -231,63 +292,192 @@ pub fn print_comparison_report(results: &ComparisonResults) {
     };
 
     let delta_symbol = if results.delta_bytes < 0 { "-" } else { "+" };
-    let delta_kb = results.delta_bytes.abs() / 1024;
 
     println!(
-        "{}  {} {} KB ({}{:.1}%)",
+        "{}  {} {} ({}{:.1}%)",
         if results.delta_bytes < 0 {
             "📉"
         } else {
             "📈"
         },
-        style("Delta:").bold(),
-        style(format!(
-            "{}{}",
-            delta_symbol,
-            format_number(delta_kb as u64)
-        ))
-        .fg(delta_color)
-        .bold(),
+        style(t("report.delta")).bold(),
+        style(format_delta(results.delta_bytes))
+            .fg(delta_color)
+            .bold(),
         delta_symbol,
         results.delta_percent.abs()
     );
 
+    // Transfer sizes, when both builds could be compressed
+    for (compression, before, after) in results.compressed_changes() {
+        let label = match compression {
+            "gzip" => t("report.gzip"),
+            _ => t("report.brotli"),
+        };
+        let delta = SizeDelta::between(before, after);
+        println!(
+            "🗜️  {} {} → {} ({}, {:+.1}%)",
+            style(label).bold(),
+            format_bytes(before),
+            format_bytes(after),
+            format_delta(delta.bytes),
+            delta.percent
+        );
+    }
+    for (compression, delta) in results.diverging_compressions() {
+        println!(
+            "⚠️  {}",
+            style(format!(
+                "The {} size changes by {} ({:+.1}%) while the raw size changes by {} ({:+.1}%)",
+                compression,
+                format_delta(delta.bytes),
+                delta.percent,
+                format_delta(results.delta_bytes),
+                results.delta_percent
+            ))
+            .yellow()
+        );
+    }
+
     println!();
 
-    // Top changes
-    if !results.top_changes.is_empty() {
-        println!("{}", style("TOP CHANGES:").bold());
-        println!("{}", style("─".repeat(70)).dim());
-        println!();
+    print!("{}", format_symbol_diff(&results.symbols, limit));
+    println!();
+}
+
+/// Added, removed, grown and shrunk symbols, each largest change first
+fn format_symbol_diff(diff: &SymbolDiff, limit: ItemLimit) -> String {
+    let mut output = String::new();
+    if diff.is_empty() {
+        return output;
+    }
+    output.push_str(&format!("{}\n", style(t("report.top_changes")).bold()));
+    output.push_str(&format!("{}\n", style("─".repeat(70)).dim()));
 
-        let display_count = results.top_changes.len().min(15);
-        for change in results.top_changes.iter().take(display_count) {
-            let delta_symbol = if change.delta_bytes < 0 { "-" } else { "+" };
-            let delta_kb = change.delta_bytes.abs() / 1024;
+    for (kind, changes) in diff.groups() {
+        if changes.is_empty() {
+            continue;
+        }
+        let key = match kind {
+            ChangeKind::Added => "report.symbols_added",
+            ChangeKind::Removed => "report.symbols_removed",
+            ChangeKind::Grown => "report.symbols_grown",
+            ChangeKind::Shrunk => "report.symbols_shrunk",
+        };
+        let net: i64 = changes.iter().map(|c| c.delta_bytes).sum();
+        output.push_str(&format!(
+            "\n  {} {}\n",
+            style(tf(key, &[("count", &changes.len())])).bold(),
+            style(format_delta(net)).dim()
+        ));
+
+        let display_count = limit.visible(changes.len(), Some(DEFAULT_TOP_CHANGES));
+        for change in changes.iter().take(display_count) {
             let delta_color = if change.delta_bytes < 0 {
                 console::Color::Green
             } else {
                 console::Color::Red
             };
-
-            println!(
-                "  {}  {:>8} KB  {}",
-                style(delta_symbol).fg(delta_color),
-                style(format_number(delta_kb as u64)).fg(delta_color).bold(),
+            output.push_str(&format!(
+                "    {}  {}\n",
+                style(format!("{:>12}", format_delta(change.delta_bytes)))
+                    .fg(delta_color)
+                    .bold(),
                 style(&change.name).dim()
-            );
+            ));
         }
+        if let Some(footer) = omitted_footer(changes.len(), display_count, "changes") {
+            output.push_str(&format!("      {}\n", style(footer).dim()));
+        }
+    }
+    output
+}
 
-        if results.top_changes.len() > display_count {
-            println!(
-                "\n      {} {} more changes...",
-                style("...").dim(),
-                results.top_changes.len() - display_count
-            );
+/// Print an N-way comparison to console
+pub fn print_multi_comparison_report(results: &MultiComparison) {
+    print_multi_comparison_report_with_limit(results, ItemLimit::Default);
+}
+
+/// Print an N-way comparison, limiting the symbol changes listed per step
+pub fn print_multi_comparison_report_with_limit(results: &MultiComparison, limit: ItemLimit) {
+    print!("{}", format_multi_comparison(results, limit));
+}
+
+/// Size table of every build followed by the top symbol changes of each step
+fn format_multi_comparison(results: &MultiComparison, limit: ItemLimit) -> String {
+    let delta_cell = |delta: Option<SizeDelta>| match delta {
+        Some(delta) => {
+            let text = format!("{} ({:+.1}%)", format_delta(delta.bytes), delta.percent);
+            let color = if delta.bytes <= 0 {
+                console::Color::Green
+            } else {
+                console::Color::Red
+            };
+            // Pad before styling so escape codes do not break the alignment
+            style(format!("{:>22}", text)).fg(color).to_string()
         }
+        None => style(format!("{:>22}", "—")).dim().to_string(),
+    };
+
+    let mut output = String::new();
+    output.push('\n');
+    output.push_str(&format!(
+        "{}\n\n",
+        style(tf(
+            "report.multi_comparison_title",
+            &[("count", &results.builds.len())]
+        ))
+        .bold()
+        .underlined()
+    ));
+    output.push_str(&format!(
+        "  {:<32} {:>10}  {:>22}  {:>22}\n",
+        "Build",
+        "Size",
+        t("report.vs_previous"),
+        t("report.vs_first")
+    ));
+    output.push_str(&format!("{}\n", style("─".repeat(92)).dim()));
+    for build in &results.builds {
+        let from_first = build.from_previous.map(|_| build.from_first);
+        output.push_str(&format!(
+            "  {:<32} {:>10}  {}  {}\n",
+            truncate_str(&build.file, 32),
+            format_bytes(build.size_bytes),
+            delta_cell(build.from_previous),
+            delta_cell(from_first)
+        ));
     }
 
-    println!();
+    for (step, pair) in results.steps.iter().zip(results.builds.windows(2)) {
+        if step.top_changes.is_empty() {
+            continue;
+        }
+        output.push_str(&format!(
+            "\n{} {} → {}\n",
+            style(t("report.top_changes")).bold(),
+            pair[0].file,
+            pair[1].file
+        ));
+        let shown = limit.visible(step.top_changes.len(), Some(DEFAULT_STEP_CHANGES));
+        for change in step.top_changes.iter().take(shown) {
+            let color = if change.delta_bytes < 0 {
+                console::Color::Green
+            } else {
+                console::Color::Red
+            };
+            output.push_str(&format!(
+                "  {}  {}\n",
+                style(format!("{:>12}", format_delta(change.delta_bytes))).fg(color),
+                style(&change.name).dim()
+            ));
+        }
+        if let Some(footer) = omitted_footer(step.top_changes.len(), shown, "changes") {
+            output.push_str(&format!("      {}\n", style(footer).dim()));
+        }
+    }
+    output.push('\n');
+    output
 }
 
 /// Format number with comma separators
@@ -342,6 +532,7 @@ mod tests {
                 },
             ],
             mono_groups: None,
+            limit: None,
             recommendations: vec![],
         };
 
@@ -356,6 +547,7 @@ mod tests {
             mode: "top".to_string(),
             items: vec![],
             mono_groups: None,
+            limit: None,
             recommendations: vec![],
         };
 
@@ -375,6 +567,8 @@ mod tests {
                     total_size_bytes: 10_240,
                     avg_size_bytes: 2_048,
                     potential_savings_bytes: 8_192,
+                    type_arguments: Vec::new(),
+                    refactor: None,
                     instantiations: vec![],
                 },
                 MonomorphizationGroup {
@@ -383,9 +577,12 @@ mod tests {
                     total_size_bytes: 6_144,
                     avg_size_bytes: 2_048,
                     potential_savings_bytes: 4_096,
+                    type_arguments: Vec::new(),
+                    refactor: None,
                     instantiations: vec![],
                 },
             ]),
+            limit: None,
             recommendations: vec![],
         };
 
@@ -408,6 +605,7 @@ mod tests {
             mode: "top".to_string(),
             items,
             mono_groups: None,
+            limit: None,
             recommendations: vec![],
         };
 
@@ -426,6 +624,7 @@ mod tests {
                 percentage: 50.0,
             }],
             mono_groups: None,
+            limit: None,
             recommendations: vec![],
         };
 
@@ -439,6 +638,7 @@ mod tests {
             mode: "garbage".to_string(),
             items: vec![],
             mono_groups: None,
+            limit: None,
             recommendations: vec![],
         };
 
@@ -456,12 +656,20 @@ mod tests {
                 ChangeItem {
                     delta_bytes: -524_288,
                     name: "function_removed".to_string(),
+                    before_bytes: None,
+                    after_bytes: None,
                 },
                 ChangeItem {
                     delta_bytes: -262_144,
                     name: "function_optimized".to_string(),
+                    before_bytes: None,
+                    after_bytes: None,
                 },
             ],
+            symbols: Default::default(),
+            before_compressed: Default::default(),
+            after_compressed: Default::default(),
+            compressed_delta: Default::default(),
         };
 
         print_comparison_report(&results);
@@ -477,7 +685,13 @@ mod tests {
             top_changes: vec![ChangeItem {
                 delta_bytes: 524_288,
                 name: "function_added".to_string(),
+                before_bytes: None,
+                after_bytes: None,
             }],
+            symbols: Default::default(),
+            before_compressed: Default::default(),
+            after_compressed: Default::default(),
+            compressed_delta: Default::default(),
         };
 
         print_comparison_report(&results);
@@ -491,6 +705,10 @@ mod tests {
             delta_bytes: 0,
             delta_percent: 0.0,
             top_changes: vec![],
+            symbols: Default::default(),
+            before_compressed: Default::default(),
+            after_compressed: Default::default(),
+            compressed_delta: Default::default(),
         };
 
         print_comparison_report(&results);
@@ -503,6 +721,8 @@ mod tests {
             changes.push(ChangeItem {
                 delta_bytes: (i as i64 - 10) * 1000,
                 name: format!("change_{}", i),
+                before_bytes: None,
+                after_bytes: None,
             });
         }
 
@@ -512,6 +732,10 @@ mod tests {
             delta_bytes: 100_000,
             delta_percent: 10.0,
             top_changes: changes,
+            symbols: Default::default(),
+            before_compressed: Default::default(),
+            after_compressed: Default::default(),
+            compressed_delta: Default::default(),
         };
 
         // Should show "... X more changes" message
@@ -526,6 +750,10 @@ mod tests {
             delta_bytes: -1_000,
             delta_percent: -0.1,
             top_changes: vec![],
+            symbols: Default::default(),
+            before_compressed: Default::default(),
+            after_compressed: Default::default(),
+            compressed_delta: Default::default(),
         };
 
         print_comparison_report(&results);
@@ -541,6 +769,8 @@ mod tests {
                 total_size_bytes: ((i + 1) * 1024) as u64,
                 avg_size_bytes: 512,
                 potential_savings_bytes: (i * 512) as u64,
+                type_arguments: Vec::new(),
+                refactor: None,
                 instantiations: vec![],
             });
         }
@@ -550,6 +780,7 @@ mod tests {
             mode: "monos".to_string(),
             items: vec![],
             mono_groups: Some(groups),
+            limit: None,
             recommendations: vec![],
         };
 
@@ -567,6 +798,7 @@ mod tests {
                 percentage: 50.0,
             }],
             mono_groups: None,
+            limit: None,
             recommendations: vec![],
         };
 
@@ -584,9 +816,79 @@ mod tests {
                 percentage: 20.0,
             }],
             mono_groups: None,
+            limit: None,
             recommendations: vec![],
         };
 
         print_analysis_report(&results);
     }
+
+    #[test]
+    fn test_format_multi_comparison_lists_deltas_and_step_changes() {
+        let step = |before: u64, after: u64, changes: Vec<ChangeItem>| ComparisonResults {
+            before_size_bytes: before,
+            after_size_bytes: after,
+            delta_bytes: after as i64 - before as i64,
+            delta_percent: 0.0,
+            top_changes: changes,
+            symbols: Default::default(),
+            before_compressed: Default::default(),
+            after_compressed: Default::default(),
+            compressed_delta: Default::default(),
+        };
+        let files: Vec<String> = ["v1.wasm", "v2.wasm", "v3.wasm"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let results = MultiComparison::from_steps(
+            &files,
+            vec![
+                step(
+                    4096,
+                    2048,
+                    vec![ChangeItem {
+                        delta_bytes: -2048,
+                        name: "app::render".to_string(),
+                        before_bytes: None,
+                        after_bytes: None,
+                    }],
+                ),
+                step(2048, 3072, Vec::new()),
+            ],
+        );
+
+        let text = format_multi_comparison(&results, ItemLimit::Default);
+        assert!(text.contains("(3 builds)"));
+        assert!(text.contains("-2.00 KiB (-50.0%)"));
+        assert!(text.contains("+1.00 KiB (+50.0%)"));
+        assert!(text.contains("-1.00 KiB (-25.0%)"));
+        assert!(text.contains("v1.wasm → v2.wasm"));
+        assert!(!text.contains("v2.wasm → v3.wasm"));
+    }
+
+    #[test]
+    fn test_format_symbol_diff_groups_changes_by_kind() {
+        let change = |name: &str, before: u64, after: u64| ChangeItem {
+            delta_bytes: after as i64 - before as i64,
+            name: name.to_string(),
+            before_bytes: Some(before),
+            after_bytes: Some(after),
+        };
+        let diff = SymbolDiff::from_changes(&[
+            change("app::render", 0, 2048),
+            change("app::legacy", 1024, 0),
+            change("app::parse", 512, 1024),
+            change("app::init", 300, 200),
+        ]);
+
+        let text = format_symbol_diff(&diff, ItemLimit::Default);
+        let added = text.find("Added (1):").unwrap();
+        let removed = text.find("Removed (1):").unwrap();
+        let grown = text.find("Grown (1):").unwrap();
+        let shrunk = text.find("Shrunk (1):").unwrap();
+        assert!(added < removed && removed < grown && grown < shrunk);
+        assert!(text.contains("+2.00 KiB"));
+        assert!(text.contains("-100 B"));
+        assert!(format_symbol_diff(&SymbolDiff::default(), ItemLimit::Default).is_empty());
+    }
 }
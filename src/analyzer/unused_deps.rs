@@ -0,0 +1,690 @@
+//! Unused dependency detection
+//!
+//! Cross-references the normal dependencies `cargo metadata` reports for the
+//! root package with the crates its sources actually name: `use` and
+//! `extern crate` items, paths in code and types, and identifiers inside
+//! macro invocations and attributes. A dependency none of these mention can
+//! be removed from `Cargo.toml` entirely.
+//!
+//! Dependencies that only switch on features of a transitive dependency
+//! (`getrandom = { features = ["js"] }` is the usual WASM case), optional
+//! dependencies wired into `[features]` and `links` crates are never
+//! flagged, since removing them changes the build without a source
+//! reference to show for it.
+//!
+//! Dependencies only the build script or the tests, benches and examples
+//! use are not unused but misplaced: they belong in `[build-dependencies]`
+//! or `[dev-dependencies]`, where they stay out of the library build and
+//! the size analysis. Build-time crates (`cc`, `bindgen`, `vergen`, ...)
+//! the library never names are moved to `[build-dependencies]` as well.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::UnusedDepsAnalyzer;
+//!
+//! let report = UnusedDepsAnalyzer::new(".").analyze()?;
+//! for dep in &report.unused {
+//!     println!("{} is never referenced", dep.name);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::report_utils::SizedEntry;
+use super::serde_usage::collect_rust_files;
+use crate::profiling::{self, ProfilePhase};
+use anyhow::{Context, Result};
+use cargo_metadata::{DependencyKind, Metadata, MetadataCommand, Package, TargetKind};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use syn::visit::{self, Visit};
+
+/// A dependency no source file references
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnusedDependency {
+    /// Key in `Cargo.toml` (the rename, if any)
+    pub name: String,
+    /// Name the crate would be referenced by in code
+    pub crate_name: String,
+    /// `[target.<platform>.dependencies]` table the dependency is declared in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Declared with `optional = true`
+    pub optional: bool,
+}
+
+impl SizedEntry for UnusedDependency {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Table a misplaced dependency belongs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependencySection {
+    /// `[build-dependencies]`
+    BuildDependencies,
+    /// `[dev-dependencies]`
+    DevDependencies,
+}
+
+impl DependencySection {
+    /// Table name in `Cargo.toml`
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            DependencySection::BuildDependencies => "build-dependencies",
+            DependencySection::DevDependencies => "dev-dependencies",
+        }
+    }
+}
+
+/// A normal dependency the library never uses but other targets do
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MisplacedDependency {
+    /// Key in `Cargo.toml` (the rename, if any)
+    pub name: String,
+    /// `[target.<platform>.dependencies]` table the dependency is declared in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Table to move it to
+    pub section: DependencySection,
+    /// Why it belongs there
+    pub reason: String,
+}
+
+impl SizedEntry for MisplacedDependency {
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Result of the unused dependency scan
+#[derive(Debug, Clone, Serialize)]
+pub struct UnusedDepsReport {
+    /// Normal dependencies checked
+    pub dependencies_checked: usize,
+    /// Rust files scanned
+    pub files_scanned: usize,
+    /// Dependencies that can be removed
+    pub unused: Vec<UnusedDependency>,
+    /// Dependencies to move out of `[dependencies]`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub misplaced: Vec<MisplacedDependency>,
+    /// Unreferenced dependencies kept on purpose, with the reason
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub kept: Vec<String>,
+    /// Problems that limited the analysis
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Finds dependencies the sources never reference
+pub struct UnusedDepsAnalyzer {
+    project_root: PathBuf,
+}
+
+impl UnusedDepsAnalyzer {
+    /// Create an analyzer for the project at `project_root`
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Scan the root package's sources against its normal dependencies
+    pub fn analyze(&self) -> Result<UnusedDepsReport> {
+        let metadata = {
+            let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+            MetadataCommand::new()
+                .current_dir(&self.project_root)
+                .exec()
+                .context("Failed to run cargo metadata")?
+        };
+        let package = metadata.root_package().context(
+            "unused-deps needs a package manifest; run it in a crate, not a virtual workspace",
+        )?;
+
+        let mut warnings = Vec::new();
+        let mut referenced = HashSet::new();
+        let files = source_files(package)?;
+        for file in &files {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            if let Err(e) = scan_source(&content, &mut referenced) {
+                let relative = file.strip_prefix(&self.project_root).unwrap_or(file);
+                warnings.push(format!(
+                    "Could not parse {} ({}); every identifier in it counts as a reference",
+                    relative.display(),
+                    e
+                ));
+                scan_words(&content, &mut referenced);
+            }
+        }
+
+        let mut build_referenced = HashSet::new();
+        let mut dev_referenced = HashSet::new();
+        for (file, build) in other_target_files(package)? {
+            let Ok(content) = std::fs::read_to_string(&file) else {
+                continue;
+            };
+            let referenced = if build {
+                &mut build_referenced
+            } else {
+                &mut dev_referenced
+            };
+            if scan_source(&content, referenced).is_err() {
+                scan_words(&content, referenced);
+            }
+        }
+
+        let mut report = find_unused(&metadata, package, &referenced);
+        find_misplaced(
+            &mut report,
+            &metadata,
+            package,
+            &build_referenced,
+            &dev_referenced,
+        );
+        report.files_scanned = files.len();
+        report.warnings = warnings;
+        Ok(report)
+    }
+}
+
+/// Rust files below the directories of the package's lib and bin targets
+fn source_files(package: &Package) -> Result<Vec<PathBuf>> {
+    let mut dirs: Vec<PathBuf> = package
+        .targets
+        .iter()
+        .filter(|t| {
+            !t.is_kind(TargetKind::CustomBuild)
+                && !t.is_kind(TargetKind::Test)
+                && !t.is_kind(TargetKind::Bench)
+                && !t.is_kind(TargetKind::Example)
+        })
+        .filter_map(|t| t.src_path.parent().map(|p| p.as_std_path().to_path_buf()))
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+
+    let mut files = BTreeSet::new();
+    for dir in dirs {
+        let mut found = Vec::new();
+        collect_rust_files(&dir, &mut found)?;
+        files.extend(found);
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// Rust files of the build script (`true`) and of the test, bench and
+/// example targets (`false`)
+fn other_target_files(package: &Package) -> Result<Vec<(PathBuf, bool)>> {
+    let mut files = BTreeSet::new();
+    for target in &package.targets {
+        let src_path = target.src_path.as_std_path();
+        if target.is_kind(TargetKind::CustomBuild) {
+            files.insert((src_path.to_path_buf(), true));
+        } else if target.is_kind(TargetKind::Test)
+            || target.is_kind(TargetKind::Bench)
+            || target.is_kind(TargetKind::Example)
+        {
+            // Targets directly in the package root only cover their own file
+            let dir = src_path.parent().filter(|dir| {
+                package
+                    .manifest_path
+                    .parent()
+                    .is_none_or(|root| root.as_std_path() != *dir)
+            });
+            let mut found = Vec::new();
+            match dir {
+                Some(dir) => collect_rust_files(dir, &mut found)?,
+                None => found.push(src_path.to_path_buf()),
+            }
+            files.extend(found.into_iter().map(|file| (file, false)));
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// Move unused dependencies that other targets use, and build-time crates,
+/// from `report.unused` to `report.misplaced`
+fn find_misplaced(
+    report: &mut UnusedDepsReport,
+    metadata: &Metadata,
+    package: &Package,
+    build_referenced: &HashSet<String>,
+    dev_referenced: &HashSet<String>,
+) {
+    let mut unused = Vec::new();
+    for dep in std::mem::take(&mut report.unused) {
+        let is_proc_macro = package
+            .dependencies
+            .iter()
+            .find(|d| d.rename.as_ref().unwrap_or(&d.name) == &dep.name)
+            .and_then(|d| {
+                metadata
+                    .packages
+                    .iter()
+                    .find(|p| p.name.as_str() == d.name && d.req.matches(&p.version))
+            })
+            .is_some_and(|p| p.targets.iter().any(|t| t.is_kind(TargetKind::ProcMacro)));
+        let kind = if is_proc_macro { "proc-macro " } else { "" };
+
+        let (section, reason) = if build_referenced.contains(&dep.crate_name) {
+            (
+                DependencySection::BuildDependencies,
+                format!("{}crate only used by the build script", kind),
+            )
+        } else if dev_referenced.contains(&dep.crate_name) {
+            (
+                DependencySection::DevDependencies,
+                format!("{}crate only used by tests, benches or examples", kind),
+            )
+        } else if BUILD_TOOL_CRATES.contains(&dep.crate_name.as_str()) {
+            (
+                DependencySection::BuildDependencies,
+                "build-time crate".to_string(),
+            )
+        } else {
+            unused.push(dep);
+            continue;
+        };
+        report.misplaced.push(MisplacedDependency {
+            name: dep.name,
+            target: dep.target,
+            section,
+            reason,
+        });
+    }
+    report.unused = unused;
+}
+
+/// Crates that are only ever useful from a build script
+const BUILD_TOOL_CRATES: &[&str] = &[
+    "autocfg",
+    "bindgen",
+    "built",
+    "cbindgen",
+    "cc",
+    "cmake",
+    "embed_resource",
+    "lalrpop",
+    "pkg_config",
+    "prost_build",
+    "rustc_version",
+    "tonic_build",
+    "vcpkg",
+    "version_check",
+    "vergen",
+];
+
+/// Compare the package's normal dependencies with the referenced crate names
+fn find_unused(
+    metadata: &Metadata,
+    package: &Package,
+    referenced: &HashSet<String>,
+) -> UnusedDepsReport {
+    let in_features: HashSet<&str> = package
+        .features
+        .values()
+        .flatten()
+        .map(|f| {
+            let f = f.strip_prefix("dep:").unwrap_or(f);
+            f.split('/').next().unwrap_or(f).trim_end_matches('?')
+        })
+        .collect();
+    // Packages some other package depends on; declaring one directly often
+    // only exists to turn on its features
+    let transitive: HashSet<&str> = metadata
+        .packages
+        .iter()
+        .filter(|p| p.id != package.id)
+        .flat_map(|p| &p.dependencies)
+        .map(|d| d.name.as_str())
+        .collect();
+
+    let mut report = UnusedDepsReport {
+        dependencies_checked: 0,
+        files_scanned: 0,
+        unused: Vec::new(),
+        misplaced: Vec::new(),
+        kept: Vec::new(),
+        warnings: Vec::new(),
+    };
+    for dep in &package.dependencies {
+        if dep.kind != DependencyKind::Normal {
+            continue;
+        }
+        report.dependencies_checked += 1;
+
+        let name = dep.rename.clone().unwrap_or_else(|| dep.name.clone());
+        let resolved = metadata
+            .packages
+            .iter()
+            .find(|p| p.name.as_str() == dep.name && dep.req.matches(&p.version));
+        let crate_name = match (&dep.rename, resolved) {
+            (Some(rename), _) => rename.replace('-', "_"),
+            (None, Some(p)) => lib_name(p),
+            (None, None) => dep.name.replace('-', "_"),
+        };
+        if referenced.contains(&crate_name) {
+            continue;
+        }
+
+        if in_features.contains(name.as_str()) {
+            report
+                .kept
+                .push(format!("{}: enabled through [features]", name));
+        } else if resolved.is_some_and(|p| p.links.is_some()) {
+            report
+                .kept
+                .push(format!("{}: links a native library", name));
+        } else if !dep.features.is_empty() && transitive.contains(dep.name.as_str()) {
+            report.kept.push(format!(
+                "{}: enables features of a transitive dependency",
+                name
+            ));
+        } else {
+            report.unused.push(UnusedDependency {
+                name,
+                crate_name,
+                target: dep.target.as_ref().map(|t| t.to_string()),
+                optional: dep.optional,
+            });
+        }
+    }
+    report.unused.sort_by(|a, b| a.name.cmp(&b.name));
+    report
+}
+
+/// Name of a package's library target as written in code
+fn lib_name(package: &Package) -> String {
+    package
+        .targets
+        .iter()
+        .find(|t| t.is_kind(TargetKind::Lib) || t.is_kind(TargetKind::ProcMacro))
+        .map(|t| t.name.replace('-', "_"))
+        .unwrap_or_else(|| package.name.replace('-', "_"))
+}
+
+/// Collect the crate names one source file may reference
+fn scan_source(content: &str, referenced: &mut HashSet<String>) -> syn::Result<()> {
+    let file = syn::parse_file(content)?;
+    let mut visitor = ReferenceVisitor { referenced };
+    visitor.visit_file(&file);
+    Ok(())
+}
+
+/// Fallback for files syn cannot parse: every identifier-like word counts
+fn scan_words(content: &str, referenced: &mut HashSet<String>) {
+    referenced.extend(
+        content
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|w| !w.is_empty())
+            .map(str::to_string),
+    );
+}
+
+struct ReferenceVisitor<'a> {
+    referenced: &'a mut HashSet<String>,
+}
+
+impl ReferenceVisitor<'_> {
+    fn add_use_roots(&mut self, tree: &syn::UseTree) {
+        match tree {
+            syn::UseTree::Path(p) => self.add(&p.ident),
+            syn::UseTree::Name(n) => self.add(&n.ident),
+            syn::UseTree::Rename(r) => self.add(&r.ident),
+            syn::UseTree::Group(g) => g.items.iter().for_each(|t| self.add_use_roots(t)),
+            syn::UseTree::Glob(_) => {}
+        }
+    }
+
+    fn add(&mut self, ident: &syn::Ident) {
+        let name = ident.to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name);
+        self.referenced.insert(name.to_string());
+    }
+
+    /// Macro and attribute arguments are not parsed, so take every word
+    fn add_tokens(&mut self, tokens: &impl ToString) {
+        scan_words(&tokens.to_string(), self.referenced);
+    }
+}
+
+impl<'ast> Visit<'ast> for ReferenceVisitor<'_> {
+    fn visit_item_use(&mut self, item: &'ast syn::ItemUse) {
+        self.add_use_roots(&item.tree);
+        visit::visit_item_use(self, item);
+    }
+
+    fn visit_item_extern_crate(&mut self, item: &'ast syn::ItemExternCrate) {
+        self.add(&item.ident);
+        visit::visit_item_extern_crate(self, item);
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if let Some(first) = path.segments.first() {
+            self.add(&first.ident);
+        }
+        visit::visit_path(self, path);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        self.add_tokens(&mac.tokens);
+        visit::visit_macro(self, mac);
+    }
+
+    fn visit_meta_list(&mut self, list: &'ast syn::MetaList) {
+        self.add_tokens(&list.tokens);
+        visit::visit_meta_list(self, list);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn references(source: &str) -> HashSet<String> {
+        let mut referenced = HashSet::new();
+        scan_source(source, &mut referenced).unwrap();
+        referenced
+    }
+
+    #[test]
+    fn test_scan_source_finds_uses_paths_macros_and_attributes() {
+        let referenced = references(
+            r#"
+            extern crate alloc_cat;
+            use wasm_bindgen::prelude::*;
+            use {js_sys, web_sys::Window as W};
+
+            #[cfg_attr(feature = "ser", derive(serde::Serialize))]
+            struct Config(hashbrown::HashMap<u8, u8>);
+
+            fn run() -> anyhow::Result<()> {
+                log::info!("{}", serde_json::to_string(&1)?);
+                Ok(())
+            }
+            "#,
+        );
+        for name in [
+            "alloc_cat",
+            "wasm_bindgen",
+            "js_sys",
+            "web_sys",
+            "serde",
+            "hashbrown",
+            "anyhow",
+            "log",
+            "serde_json",
+        ] {
+            assert!(referenced.contains(name), "{} not found", name);
+        }
+        assert!(!referenced.contains("Window"));
+        assert!(!referenced.contains("regex"));
+    }
+
+    #[test]
+    fn test_find_unused_flags_unreferenced_and_keeps_feature_pins() {
+        let dep = |name: &str, features: &[&str], rename: Option<&str>| {
+            serde_json::json!({
+                "name": name, "source": null, "req": "^1", "kind": null,
+                "optional": false, "uses_default_features": true,
+                "features": features, "target": null, "rename": rename,
+                "registry": null, "path": null
+            })
+        };
+        let package = |name: &str, deps: Vec<serde_json::Value>, links: Option<&str>| {
+            serde_json::json!({
+                "name": name, "version": "1.0.0", "id": format!("{} 1.0.0", name),
+                "license": null, "license_file": null, "description": null,
+                "source": null, "dependencies": deps, "targets": [],
+                "features": if name == "app" {
+                    serde_json::json!({"fast": ["dep:ahash"]})
+                } else {
+                    serde_json::json!({})
+                },
+                "manifest_path": format!("/fake/{}/Cargo.toml", name),
+                "metadata": null, "publish": null, "authors": [], "categories": [],
+                "keywords": [], "readme": null, "repository": null, "homepage": null,
+                "documentation": null, "edition": "2021", "links": links,
+                "default_run": null, "rust_version": null
+            })
+        };
+        let metadata: Metadata = serde_json::from_value(serde_json::json!({
+            "packages": [
+                package("app", vec![
+                    dep("serde-json", &[], None),
+                    dep("regex", &[], None),
+                    dep("getrandom", &["js"], None),
+                    dep("ahash", &[], None),
+                    dep("libz-sys", &[], None),
+                    dep("log", &[], Some("logging")),
+                ], None),
+                package("rand", vec![dep("getrandom", &[], None)], None),
+                package("serde-json", vec![], None),
+                package("regex", vec![], None),
+                package("getrandom", vec![], None),
+                package("ahash", vec![], None),
+                package("libz-sys", vec![], Some("z")),
+                package("log", vec![], None),
+            ],
+            "workspace_members": ["app 1.0.0"],
+            "workspace_default_members": ["app 1.0.0"],
+            "resolve": null,
+            "target_directory": "/fake/target",
+            "version": 1,
+            "workspace_root": "/fake",
+            "metadata": null
+        }))
+        .unwrap();
+        let referenced: HashSet<String> = ["serde_json".to_string()].into();
+
+        let report = find_unused(&metadata, &metadata.packages[0], &referenced);
+        assert_eq!(report.dependencies_checked, 6);
+        let unused: Vec<(&str, &str)> = report
+            .unused
+            .iter()
+            .map(|d| (d.name.as_str(), d.crate_name.as_str()))
+            .collect();
+        assert_eq!(unused, vec![("logging", "logging"), ("regex", "regex")]);
+        assert_eq!(report.kept.len(), 3);
+    }
+
+    #[test]
+    fn test_find_misplaced_moves_build_and_test_only_dependencies() {
+        let package = |name: &str, deps: Vec<&str>, kind: &str| {
+            serde_json::json!({
+                "name": name, "version": "1.0.0", "id": format!("{} 1.0.0", name),
+                "license": null, "license_file": null, "description": null,
+                "source": null,
+                "dependencies": deps.iter().map(|dep| serde_json::json!({
+                    "name": dep, "source": null, "req": "^1", "kind": null,
+                    "optional": false, "uses_default_features": true,
+                    "features": [], "target": null, "rename": null,
+                    "registry": null, "path": null
+                })).collect::<Vec<_>>(),
+                "targets": [{
+                    "name": name.replace('-', "_"), "kind": [kind], "crate_types": [kind],
+                    "required-features": [], "src_path": format!("/fake/{}/src/lib.rs", name),
+                    "edition": "2021", "doctest": true, "test": true, "doc": true
+                }],
+                "features": {}, "manifest_path": format!("/fake/{}/Cargo.toml", name),
+                "metadata": null, "publish": null, "authors": [], "categories": [],
+                "keywords": [], "readme": null, "repository": null, "homepage": null,
+                "documentation": null, "edition": "2021", "links": null,
+                "default_run": null, "rust_version": null
+            })
+        };
+        let metadata: Metadata = serde_json::from_value(serde_json::json!({
+            "packages": [
+                package("app", vec!["cc", "test-case", "vergen", "regex"], "lib"),
+                package("cc", vec![], "lib"),
+                package("test-case", vec![], "proc-macro"),
+                package("vergen", vec![], "lib"),
+                package("regex", vec![], "lib"),
+            ],
+            "workspace_members": ["app 1.0.0"],
+            "workspace_default_members": ["app 1.0.0"],
+            "resolve": null,
+            "target_directory": "/fake/target",
+            "version": 1,
+            "workspace_root": "/fake",
+            "metadata": null
+        }))
+        .unwrap();
+        let package = &metadata.packages[0];
+        let mut report = find_unused(&metadata, package, &HashSet::new());
+        let build: HashSet<String> = ["cc".to_string()].into();
+        let dev: HashSet<String> = ["test_case".to_string()].into();
+
+        find_misplaced(&mut report, &metadata, package, &build, &dev);
+
+        let unused: Vec<&str> = report.unused.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(unused, ["regex"]);
+        let misplaced: Vec<(&str, DependencySection, &str)> = report
+            .misplaced
+            .iter()
+            .map(|d| (d.name.as_str(), d.section, d.reason.as_str()))
+            .collect();
+        assert_eq!(
+            misplaced,
+            [
+                (
+                    "cc",
+                    DependencySection::BuildDependencies,
+                    "crate only used by the build script"
+                ),
+                (
+                    "test-case",
+                    DependencySection::DevDependencies,
+                    "proc-macro crate only used by tests, benches or examples"
+                ),
+                (
+                    "vergen",
+                    DependencySection::BuildDependencies,
+                    "build-time crate"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_words_counts_identifiers_in_unparsable_files() {
+        let mut referenced = HashSet::new();
+        assert!(scan_source("fn broken( {", &mut referenced).is_err());
+        scan_words("let x = itoa::Buffer::new(", &mut referenced);
+        assert!(referenced.contains("itoa"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Unused dependency report formatting
+
+use super::report_utils::{omitted_footer, ItemLimit};
+use super::unused_deps::UnusedDepsReport;
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of unused dependencies listed by default
+const DEFAULT_TOP_UNUSED: usize = 30;
+
+/// Format the unused dependency report for console output
+pub fn format_console_report(report: &UnusedDepsReport) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(report, ItemLimit::Default)
+}
+
+/// Format the unused dependency report, limiting the dependencies listed
+pub fn format_console_report_with_limit(
+    report: &UnusedDepsReport,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "\n{}",
+        style("Unused Dependencies").bold().underlined()
+    )?;
+    writeln!(
+        output,
+        "\n   {} dependencies checked against {} source files",
+        style(report.dependencies_checked).cyan().bold(),
+        report.files_scanned
+    )?;
+    for warning in &report.warnings {
+        writeln!(
+            output,
+            "   {} {}",
+            style("⚠").yellow(),
+            style(warning).yellow()
+        )?;
+    }
+
+    if report.unused.is_empty() {
+        writeln!(
+            output,
+            "\n   {} Every dependency is referenced in the sources",
+            style("✓").green()
+        )?;
+    } else {
+        writeln!(output)?;
+        let shown = limit.visible(report.unused.len(), Some(DEFAULT_TOP_UNUSED));
+        for dep in report.unused.iter().take(shown) {
+            let mut notes = Vec::new();
+            if let Some(target) = &dep.target {
+                notes.push(format!("target {}", target));
+            }
+            if dep.optional {
+                notes.push("optional".to_string());
+            }
+            writeln!(
+                output,
+                "  {} {}{}",
+                style("✗").red(),
+                style(&dep.name).bold(),
+                if notes.is_empty() {
+                    String::new()
+                } else {
+                    style(format!(" ({})", notes.join(", "))).dim().to_string()
+                }
+            )?;
+        }
+        if let Some(footer) = omitted_footer(report.unused.len(), shown, "dependencies") {
+            writeln!(output, "  {}", style(footer).dim())?;
+        }
+        writeln!(
+            output,
+            "\n   {} Run with --fix to remove them from Cargo.toml",
+            style("→").cyan()
+        )?;
+    }
+
+    if !report.misplaced.is_empty() {
+        writeln!(output, "\n{}", style("Misplaced").bold())?;
+        for dep in &report.misplaced {
+            let table = match &dep.target {
+                Some(target) => format!("[target.'{}'.{}]", target, dep.section.table_name()),
+                None => format!("[{}]", dep.section.table_name()),
+            };
+            writeln!(
+                output,
+                "  {} {} → {} {}",
+                style("↪").yellow(),
+                style(&dep.name).bold(),
+                table,
+                style(format!("({})", dep.reason)).dim()
+            )?;
+        }
+        writeln!(
+            output,
+            "\n   {} Run with --fix to move them in Cargo.toml",
+            style("→").cyan()
+        )?;
+    }
+
+    if !report.kept.is_empty() {
+        writeln!(output, "\n{}", style("Unreferenced but Kept").bold())?;
+        for kept in &report.kept {
+            writeln!(output, "  {} {}", style("•").dim(), kept)?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::unused_deps::{DependencySection, MisplacedDependency, UnusedDependency};
+
+    #[test]
+    fn test_format_console_report_lists_unused_and_kept() {
+        let report = UnusedDepsReport {
+            dependencies_checked: 5,
+            files_scanned: 12,
+            unused: vec![UnusedDependency {
+                name: "regex".to_string(),
+                crate_name: "regex".to_string(),
+                target: Some("cfg(target_arch = \"wasm32\")".to_string()),
+                optional: false,
+            }],
+            misplaced: vec![MisplacedDependency {
+                name: "cc".to_string(),
+                target: None,
+                section: DependencySection::BuildDependencies,
+                reason: "build-time crate".to_string(),
+            }],
+            kept: vec!["getrandom: enables features of a transitive dependency".to_string()],
+            warnings: Vec::new(),
+        };
+
+        let text = format_console_report(&report).unwrap();
+        assert!(text.contains("5 dependencies checked against 12 source files"));
+        assert!(text.contains("regex"));
+        assert!(text.contains("target cfg(target_arch = \"wasm32\")"));
+        assert!(text.contains("getrandom: enables features"));
+        assert!(text.contains("cc → [build-dependencies] (build-time crate)"));
+    }
+}
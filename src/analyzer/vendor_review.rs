@@ -0,0 +1,381 @@
+//! Combined license and size report for vendor review
+//!
+//! Joins per-crate code size from `cargo bloat --crates` with license
+//! information from `cargo metadata`, so platform teams reviewing third-party
+//! code get both numbers in one table. The table can be exported as CSV.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::vendor_review::VendorReviewer;
+//! use std::path::Path;
+//!
+//! let review = VendorReviewer::new(Path::new(".")).analyze()?;
+//! std::fs::write("vendor-review.csv", review.to_csv())?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use super::bloat::{BloatAnalyzer, CrateSize};
+use super::report_utils::SizedEntry;
+use crate::profiling::{self, ProfilePhase};
+use anyhow::{Context, Result};
+use cargo_metadata::{Metadata, MetadataCommand};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// License of the Rust standard library crates
+const TOOLCHAIN_LICENSE: &str = "MIT OR Apache-2.0";
+
+/// Where a crate's code comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CrateOrigin {
+    /// A member of the project's workspace
+    Workspace,
+    /// A dependency (registry, git or path)
+    ThirdParty,
+    /// Shipped with the Rust toolchain (std, core, alloc, ...)
+    Toolchain,
+    /// Not matched to any package (e.g., `[Unknown]` in cargo-bloat output)
+    Unknown,
+}
+
+impl CrateOrigin {
+    /// Label used in reports and CSV
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CrateOrigin::Workspace => "workspace",
+            CrateOrigin::ThirdParty => "third-party",
+            CrateOrigin::Toolchain => "toolchain",
+            CrateOrigin::Unknown => "unknown",
+        }
+    }
+}
+
+/// One crate in the vendor review table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VendorReviewRow {
+    /// Crate name as it appears in the binary
+    pub crate_name: String,
+    /// Resolved version(s); several if the crate is duplicated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// SPDX license expression(s), or `None` if not declared
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Where the code comes from
+    pub origin: CrateOrigin,
+    /// Code size in bytes
+    pub size_bytes: u64,
+    /// Percentage of the .text section
+    pub percentage: f64,
+}
+
+impl SizedEntry for VendorReviewRow {
+    fn size_bytes(&self) -> Option<u64> {
+        Some(self.size_bytes)
+    }
+
+    fn percentage(&self) -> Option<f64> {
+        Some(self.percentage)
+    }
+
+    fn name(&self) -> &str {
+        &self.crate_name
+    }
+}
+
+/// Per-crate license and size table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VendorReview {
+    /// Rows, largest first
+    pub rows: Vec<VendorReviewRow>,
+    /// Total attributed code size in bytes
+    pub total_size_bytes: u64,
+}
+
+impl VendorReview {
+    /// Third-party crates that do not declare a license
+    pub fn unlicensed(&self) -> impl Iterator<Item = &VendorReviewRow> {
+        self.rows
+            .iter()
+            .filter(|row| row.origin == CrateOrigin::ThirdParty && row.license.is_none())
+    }
+
+    /// Render as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("crate,version,license,origin,size_bytes,percent\n");
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{:.2}\n",
+                csv_field(&row.crate_name),
+                csv_field(row.version.as_deref().unwrap_or("")),
+                csv_field(row.license.as_deref().unwrap_or("")),
+                row.origin.as_str(),
+                row.size_bytes,
+                row.percentage
+            ));
+        }
+        csv
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Builds the vendor review for a project
+pub struct VendorReviewer {
+    project_root: PathBuf,
+}
+
+impl VendorReviewer {
+    /// Create a reviewer for the given project
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Build the project, attribute sizes and join license metadata
+    pub fn analyze(&self) -> Result<VendorReview> {
+        let metadata = {
+            let _span = profiling::span(ProfilePhase::Spawning, "cargo metadata");
+            MetadataCommand::new()
+                .current_dir(&self.project_root)
+                .exec()
+                .context("Failed to run cargo metadata")?
+        };
+        let crate_sizes = BloatAnalyzer::new(&self.project_root).analyze_crates()?;
+
+        Ok(Self::review_with_metadata(&metadata, &crate_sizes))
+    }
+
+    /// Join crate sizes with package metadata
+    pub(crate) fn review_with_metadata(
+        metadata: &Metadata,
+        crate_sizes: &[CrateSize],
+    ) -> VendorReview {
+        let workspace: HashSet<_> = metadata.workspace_members.iter().collect();
+
+        // cargo-bloat reports crate names with underscores
+        let mut packages: HashMap<String, Vec<&cargo_metadata::Package>> = HashMap::new();
+        for package in &metadata.packages {
+            packages
+                .entry(package.name.as_str().replace('-', "_"))
+                .or_default()
+                .push(package);
+        }
+
+        let mut rows: Vec<VendorReviewRow> = crate_sizes
+            .iter()
+            .map(|size| {
+                let (version, license, origin) = match packages.get(&size.crate_name) {
+                    Some(matches) => {
+                        let versions: BTreeSet<String> =
+                            matches.iter().map(|p| p.version.to_string()).collect();
+                        let licenses: BTreeSet<&str> = matches
+                            .iter()
+                            .filter_map(|p| p.license.as_deref())
+                            .collect();
+                        let origin = if matches.iter().any(|p| workspace.contains(&p.id)) {
+                            CrateOrigin::Workspace
+                        } else {
+                            CrateOrigin::ThirdParty
+                        };
+                        (
+                            Some(versions.into_iter().collect::<Vec<_>>().join(", ")),
+                            (!licenses.is_empty())
+                                .then(|| licenses.into_iter().collect::<Vec<_>>().join(" / ")),
+                            origin,
+                        )
+                    }
+                    None if is_toolchain_crate(&size.crate_name) => (
+                        None,
+                        Some(TOOLCHAIN_LICENSE.to_string()),
+                        CrateOrigin::Toolchain,
+                    ),
+                    None => (None, None, CrateOrigin::Unknown),
+                };
+
+                VendorReviewRow {
+                    crate_name: size.crate_name.clone(),
+                    version,
+                    license,
+                    origin,
+                    size_bytes: size.size_bytes,
+                    percentage: size.percentage,
+                }
+            })
+            .collect();
+
+        rows.sort_by(|a, b| {
+            b.size_bytes
+                .cmp(&a.size_bytes)
+                .then_with(|| a.crate_name.cmp(&b.crate_name))
+        });
+
+        VendorReview {
+            total_size_bytes: rows.iter().map(|r| r.size_bytes).sum(),
+            rows,
+        }
+    }
+}
+
+/// Check if a crate is part of the Rust standard library distribution
+fn is_toolchain_crate(crate_name: &str) -> bool {
+    matches!(
+        crate_name,
+        "std"
+            | "core"
+            | "alloc"
+            | "compiler_builtins"
+            | "panic_abort"
+            | "panic_unwind"
+            | "dlmalloc"
+            | "rustc_demangle"
+            | "std_detect"
+            | "hashbrown"
+            | "adler"
+            | "adler2"
+            | "miniz_oxide"
+            | "gimli"
+            | "addr2line"
+            | "object"
+            | "memchr"
+            | "unwind"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, version: &str, license: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "version": version,
+            "id": format!("{} {}", name, version),
+            "license": license,
+            "license_file": null,
+            "description": null,
+            "source": null,
+            "dependencies": [],
+            "targets": [],
+            "features": {},
+            "manifest_path": "/fake/Cargo.toml",
+            "metadata": null,
+            "publish": null,
+            "authors": [],
+            "categories": [],
+            "keywords": [],
+            "readme": null,
+            "repository": null,
+            "homepage": null,
+            "documentation": null,
+            "edition": "2021",
+            "links": null,
+            "default_run": null,
+            "rust_version": null
+        })
+    }
+
+    fn metadata() -> Metadata {
+        serde_json::from_value(serde_json::json!({
+            "packages": [
+                package("app", "0.1.0", None),
+                package("serde-json", "1.0.100", Some("MIT OR Apache-2.0")),
+                package("mystery", "0.3.0", None),
+                package("base64", "0.21.0", Some("MIT")),
+                package("base64", "0.22.1", Some("MIT OR Apache-2.0"))
+            ],
+            "workspace_members": ["app 0.1.0"],
+            "workspace_default_members": ["app 0.1.0"],
+            "resolve": null,
+            "target_directory": "/fake/target",
+            "version": 1,
+            "workspace_root": "/fake",
+            "metadata": null
+        }))
+        .unwrap()
+    }
+
+    fn size(name: &str, size_bytes: u64) -> CrateSize {
+        CrateSize {
+            crate_name: name.to_string(),
+            size_bytes,
+            percentage: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_review_joins_licenses_and_sizes() {
+        let review = VendorReviewer::review_with_metadata(
+            &metadata(),
+            &[
+                size("app", 100),
+                size("serde_json", 500),
+                size("std", 300),
+                size("[Unknown]", 10),
+                size("mystery", 50),
+            ],
+        );
+
+        let names: Vec<&str> = review.rows.iter().map(|r| r.crate_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["serde_json", "std", "app", "mystery", "[Unknown]"]
+        );
+        assert_eq!(review.total_size_bytes, 960);
+
+        let serde = &review.rows[0];
+        assert_eq!(serde.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(serde.version.as_deref(), Some("1.0.100"));
+        assert_eq!(serde.origin, CrateOrigin::ThirdParty);
+
+        assert_eq!(review.rows[1].origin, CrateOrigin::Toolchain);
+        assert_eq!(review.rows[2].origin, CrateOrigin::Workspace);
+        assert_eq!(review.rows[4].origin, CrateOrigin::Unknown);
+
+        let unlicensed: Vec<&str> = review.unlicensed().map(|r| r.crate_name.as_str()).collect();
+        assert_eq!(unlicensed, vec!["mystery"]);
+    }
+
+    #[test]
+    fn test_review_merges_duplicate_versions() {
+        let review = VendorReviewer::review_with_metadata(&metadata(), &[size("base64", 42)]);
+
+        assert_eq!(review.rows[0].version.as_deref(), Some("0.21.0, 0.22.1"));
+        assert_eq!(
+            review.rows[0].license.as_deref(),
+            Some("MIT / MIT OR Apache-2.0")
+        );
+    }
+
+    #[test]
+    fn test_to_csv_quotes_fields() {
+        let review = VendorReviewer::review_with_metadata(
+            &metadata(),
+            &[size("base64", 42), size("std", 7)],
+        );
+
+        assert_eq!(
+            review.to_csv(),
+            "crate,version,license,origin,size_bytes,percent\n\
+             base64,\"0.21.0, 0.22.1\",MIT / MIT OR Apache-2.0,third-party,42,0.00\n\
+             std,,MIT OR Apache-2.0,toolchain,7,0.00\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_field_escapes_quotes() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a \"b\""), "\"a \"\"b\"\"\"");
+    }
+}
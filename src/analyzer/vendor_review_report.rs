@@ -0,0 +1,114 @@
+//! Vendor review report formatting
+
+use super::report_utils::{format_bytes, omitted_footer, truncate_str, ItemLimit};
+use super::vendor_review::{CrateOrigin, VendorReview};
+use console::style;
+use std::fmt::{self, Write as _};
+
+/// Number of crates listed by default
+const DEFAULT_TOP_CRATES: usize = 30;
+
+/// Format the vendor review table for console output
+pub fn format_console_report(review: &VendorReview) -> Result<String, fmt::Error> {
+    format_console_report_with_limit(review, ItemLimit::Default)
+}
+
+/// Format the vendor review table, limiting the crates listed
+pub fn format_console_report_with_limit(
+    review: &VendorReview,
+    limit: ItemLimit,
+) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+
+    writeln!(output, "\n{} Vendor Review", style("📋").bold())?;
+    writeln!(
+        output,
+        "   Attributed code size: {}\n",
+        style(format_bytes(review.total_size_bytes)).cyan()
+    )?;
+
+    writeln!(
+        output,
+        "   {:<24} {:<16} {:<26} {:<12} {:>10} {:>7}",
+        "Crate", "Version", "License", "Origin", "Size", "Percent"
+    )?;
+    writeln!(output, "   {}", "─".repeat(100))?;
+
+    let shown = limit.visible(review.rows.len(), Some(DEFAULT_TOP_CRATES));
+    for row in review.rows.iter().take(shown) {
+        let license = match &row.license {
+            Some(license) => style(truncate_str(license, 26)),
+            None if row.origin == CrateOrigin::ThirdParty => style("(none)".to_string()).red(),
+            None => style("-".to_string()).dim(),
+        };
+        writeln!(
+            output,
+            "   {:<24} {:<16} {:<26} {:<12} {:>10} {:>6.1}%",
+            truncate_str(&row.crate_name, 24),
+            truncate_str(row.version.as_deref().unwrap_or("-"), 16),
+            license,
+            row.origin.as_str(),
+            format_bytes(row.size_bytes),
+            row.percentage
+        )?;
+    }
+    if let Some(footer) = omitted_footer(review.rows.len(), shown, "crates") {
+        writeln!(output, "   {}", style(footer).dim())?;
+    }
+
+    let unlicensed: Vec<&str> = review.unlicensed().map(|r| r.crate_name.as_str()).collect();
+    if !unlicensed.is_empty() {
+        writeln!(
+            output,
+            "\n{} {} third-party crate(s) declare no license: {}",
+            style("⚠️").yellow(),
+            unlicensed.len(),
+            unlicensed.join(", ")
+        )?;
+    }
+
+    writeln!(
+        output,
+        "\n{}",
+        style("💡 Tip: Use --format csv to export this table for review").dim()
+    )?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::vendor_review::VendorReviewRow;
+
+    fn row(name: &str, license: Option<&str>, origin: CrateOrigin) -> VendorReviewRow {
+        VendorReviewRow {
+            crate_name: name.to_string(),
+            version: Some("1.0.0".to_string()),
+            license: license.map(str::to_string),
+            origin,
+            size_bytes: 1024,
+            percentage: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_format_console_report_lists_crates_and_flags_missing_licenses() {
+        let review = VendorReview {
+            rows: vec![
+                row("serde", Some("MIT OR Apache-2.0"), CrateOrigin::ThirdParty),
+                row("mystery", None, CrateOrigin::ThirdParty),
+                row("app", None, CrateOrigin::Workspace),
+            ],
+            total_size_bytes: 3072,
+        };
+
+        let report = format_console_report_with_limit(&review, ItemLimit::Max(2)).unwrap();
+
+        assert!(report.contains("serde"));
+        assert!(report.contains("MIT OR Apache-2.0"));
+        assert!(!report.contains("app "));
+        assert!(report.contains("1 more crates omitted"));
+        assert!(report.contains("1 third-party crate(s) declare no license: mystery"));
+    }
+}
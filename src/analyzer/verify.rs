@@ -0,0 +1,938 @@
+//! Measured verification of size recommendations
+//!
+//! Most analyzer savings are estimates. A recommendation that carries a
+//! verification ID can be checked for real: the experiment is applied to a
+//! copy of the project in a scratch workspace, both the original and the
+//! modified copy are built for wasm32, and the measured size delta is reported.
+//!
+//! The copy's manifest is detached from its original location first: path
+//! dependencies leaving the project point back at the originals, and a
+//! workspace member gets its `workspace = true` fields and dependencies, and
+//! the workspace's profiles and patches, inlined.
+//!
+//! # Verification IDs
+//!
+//! IDs are self-describing so they can be copied straight from a report:
+//!
+//! - `default-features:<crate>` - rebuild with `default-features = false` on a dependency
+//! - `feature:<crate>/<feature>` - rebuild without a feature listed on a dependency
+//! - `profile:<key>=<value>` - rebuild with a `[profile.release]` setting changed
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::analyzer::verify::RecommendationVerifier;
+//!
+//! let verifier = RecommendationVerifier::new(".");
+//! let result = verifier.verify("profile:opt-level=z")?;
+//! println!("Measured delta: {} bytes", result.delta_bytes);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use crate::pipeline::{PipelineConfig, PipelineError, ToolRunner};
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+use toml_edit::{value, DocumentMut, InlineTable, Item, TableLike, Value};
+
+/// Directory (relative to the project root) holding verification workspaces
+pub const VERIFY_DIR: &str = "verify";
+
+/// Errors that can occur while verifying a recommendation
+#[derive(Error, Debug)]
+pub enum VerificationError {
+    /// The ID does not describe a known experiment
+    #[error("Unknown recommendation ID: {0} (expected default-features:<crate>, feature:<crate>/<feature> or profile:<key>=<value>)")]
+    UnknownRecommendation(String),
+
+    /// The dependency targeted by the experiment is not declared
+    #[error("Dependency '{0}' not found in [dependencies]")]
+    DependencyNotFound(String),
+
+    /// The feature targeted by the experiment is not listed on the dependency
+    #[error("Feature '{1}' is not listed in the features of '{0}' in Cargo.toml")]
+    FeatureNotListed(String, String),
+
+    /// Cargo.toml could not be parsed
+    #[error("Failed to parse Cargo.toml: {0}")]
+    Toml(#[from] toml_edit::TomlError),
+
+    /// Building the baseline or the experiment failed
+    #[error("{stage} build failed: {source}")]
+    Build {
+        /// Which build failed ("baseline" or "experiment")
+        stage: &'static str,
+        /// Underlying pipeline error
+        source: PipelineError,
+    },
+
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A change that can be applied to a project to measure its size impact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Experiment {
+    /// Set `default-features = false` on a dependency
+    DisableDefaultFeatures {
+        /// Dependency name as declared in `[dependencies]`
+        package: String,
+    },
+    /// Remove a feature from a dependency's `features` list
+    DisableFeature {
+        /// Dependency name as declared in `[dependencies]`
+        package: String,
+        /// Feature to remove
+        feature: String,
+    },
+    /// Change a `[profile.release]` setting
+    ProfileSetting {
+        /// Profile key (e.g., "opt-level")
+        key: String,
+        /// New value (e.g., "z")
+        value: String,
+    },
+}
+
+impl Experiment {
+    /// Parse an experiment from its verification ID
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_slim::analyzer::verify::Experiment;
+    ///
+    /// let exp = Experiment::parse("default-features:regex").unwrap();
+    /// assert_eq!(exp.id(), "default-features:regex");
+    /// ```
+    pub fn parse(id: &str) -> Result<Self, VerificationError> {
+        let unknown = || VerificationError::UnknownRecommendation(id.to_string());
+        let (kind, rest) = id.split_once(':').ok_or_else(unknown)?;
+        match kind {
+            "default-features" if !rest.is_empty() => Ok(Experiment::DisableDefaultFeatures {
+                package: rest.to_string(),
+            }),
+            "feature" => match rest.split_once('/') {
+                Some((package, feature)) if !package.is_empty() && !feature.is_empty() => {
+                    Ok(Experiment::DisableFeature {
+                        package: package.to_string(),
+                        feature: feature.to_string(),
+                    })
+                }
+                _ => Err(unknown()),
+            },
+            "profile" => {
+                let (key, val) = rest.split_once('=').ok_or_else(unknown)?;
+                if key.is_empty() || val.is_empty() {
+                    return Err(unknown());
+                }
+                Ok(Experiment::ProfileSetting {
+                    key: key.to_string(),
+                    value: val.to_string(),
+                })
+            }
+            _ => Err(unknown()),
+        }
+    }
+
+    /// Verification ID for this experiment
+    pub fn id(&self) -> String {
+        match self {
+            Experiment::DisableDefaultFeatures { package } => {
+                format!("default-features:{}", package)
+            }
+            Experiment::DisableFeature { package, feature } => {
+                format!("feature:{}/{}", package, feature)
+            }
+            Experiment::ProfileSetting { key, value } => format!("profile:{}={}", key, value),
+        }
+    }
+
+    /// Human-readable description of the change
+    pub fn description(&self) -> String {
+        match self {
+            Experiment::DisableDefaultFeatures { package } => {
+                format!("Disable default features of '{}'", package)
+            }
+            Experiment::DisableFeature { package, feature } => {
+                format!("Disable feature '{}' of '{}'", feature, package)
+            }
+            Experiment::ProfileSetting { key, value } => {
+                format!("Set [profile.release] {} = {}", key, value)
+            }
+        }
+    }
+
+    /// Apply the experiment to Cargo.toml content
+    pub fn apply(&self, cargo_toml: &str) -> Result<String, VerificationError> {
+        let mut doc = cargo_toml.parse::<DocumentMut>()?;
+
+        match self {
+            Experiment::DisableDefaultFeatures { package } => {
+                let dep = doc
+                    .get_mut("dependencies")
+                    .and_then(|deps| deps.get_mut(package))
+                    .ok_or_else(|| VerificationError::DependencyNotFound(package.clone()))?;
+
+                match dep {
+                    Item::Value(Value::String(version)) => {
+                        let mut table = InlineTable::new();
+                        table.insert("version", version.value().as_str().into());
+                        table.insert("default-features", false.into());
+                        *dep = Item::Value(Value::InlineTable(table));
+                    }
+                    Item::Value(Value::InlineTable(table)) => {
+                        table.insert("default-features", false.into());
+                    }
+                    Item::Table(table) => {
+                        table.insert("default-features", value(false));
+                    }
+                    _ => return Err(VerificationError::DependencyNotFound(package.clone())),
+                }
+            }
+            Experiment::DisableFeature { package, feature } => {
+                let not_listed =
+                    || VerificationError::FeatureNotListed(package.clone(), feature.clone());
+                let dep = doc
+                    .get_mut("dependencies")
+                    .and_then(|deps| deps.get_mut(package))
+                    .ok_or_else(|| VerificationError::DependencyNotFound(package.clone()))?;
+                let features = dep
+                    .get_mut("features")
+                    .and_then(|f| f.as_array_mut())
+                    .ok_or_else(not_listed)?;
+                let index = features
+                    .iter()
+                    .position(|f| f.as_str() == Some(feature.as_str()))
+                    .ok_or_else(not_listed)?;
+                features.remove(index);
+            }
+            Experiment::ProfileSetting { key, value: val } => {
+                let profile = doc
+                    .entry("profile")
+                    .or_insert(toml_edit::table())
+                    .as_table_mut()
+                    .ok_or_else(|| VerificationError::UnknownRecommendation(self.id()))?;
+                profile.set_implicit(true);
+                let release = profile
+                    .entry("release")
+                    .or_insert(toml_edit::table())
+                    .as_table_mut()
+                    .ok_or_else(|| VerificationError::UnknownRecommendation(self.id()))?;
+                release.insert(key, value(Self::toml_value(val)));
+            }
+        }
+
+        Ok(doc.to_string())
+    }
+
+    /// Interpret a profile value as bool, integer or string
+    fn toml_value(raw: &str) -> Value {
+        if let Ok(b) = raw.parse::<bool>() {
+            b.into()
+        } else if let Ok(n) = raw.parse::<i64>() {
+            n.into()
+        } else {
+            raw.into()
+        }
+    }
+}
+
+/// Measured outcome of a verified recommendation
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationResult {
+    /// Verification ID
+    pub id: String,
+    /// What was changed
+    pub description: String,
+    /// WASM size without the change
+    pub baseline_bytes: u64,
+    /// WASM size with the change applied
+    pub experiment_bytes: u64,
+    /// Size difference (negative = smaller)
+    pub delta_bytes: i64,
+    /// Size difference as percentage of baseline
+    pub delta_percent: f64,
+}
+
+impl VerificationResult {
+    /// Build a result from measured sizes
+    pub fn new(experiment: &Experiment, baseline_bytes: u64, experiment_bytes: u64) -> Self {
+        let delta_bytes = experiment_bytes as i64 - baseline_bytes as i64;
+        let delta_percent = if baseline_bytes > 0 {
+            (delta_bytes as f64 / baseline_bytes as f64) * 100.0
+        } else {
+            0.0
+        };
+        Self {
+            id: experiment.id(),
+            description: experiment.description(),
+            baseline_bytes,
+            experiment_bytes,
+            delta_bytes,
+            delta_percent,
+        }
+    }
+}
+
+/// Runs recommendation experiments in a scratch workspace
+pub struct RecommendationVerifier<
+    FS: FileSystem + Clone = RealFileSystem,
+    CE: CommandExecutor + Clone = RealCommandExecutor,
+> {
+    project_root: PathBuf,
+    fs: FS,
+    cmd_executor: CE,
+}
+
+impl RecommendationVerifier {
+    /// Create a verifier for the given project root
+    pub fn new(project_root: impl AsRef<Path>) -> Self {
+        Self::with_executors(project_root, RealFileSystem, RealCommandExecutor)
+    }
+}
+
+impl<FS: FileSystem + Clone, CE: CommandExecutor + Clone> RecommendationVerifier<FS, CE> {
+    /// Create a verifier with custom filesystem and command executors
+    pub fn with_executors(project_root: impl AsRef<Path>, fs: FS, cmd_executor: CE) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+            fs,
+            cmd_executor,
+        }
+    }
+
+    /// Verify the recommendation with the given ID
+    ///
+    /// The project itself is never modified: the experiment is applied to a
+    /// copy under `.wasm-slim/scratch/verify/`.
+    pub fn verify(&self, id: &str) -> Result<VerificationResult, VerificationError> {
+        let experiment = Experiment::parse(id)?;
+        let workspace = crate::state::scratch_dir(&self.project_root)
+            .join(VERIFY_DIR)
+            .join(Self::workspace_name(&experiment));
+        let copy_root = workspace.join("project");
+
+        // Prepare the modified copy first so malformed experiments fail fast
+        self.copy_project(&copy_root)?;
+        let cargo_toml = copy_root.join("Cargo.toml");
+        let modified = experiment.apply(&self.fs.read_to_string(&cargo_toml)?)?;
+        self.fs.write(&cargo_toml, modified)?;
+
+        let baseline_bytes = self.build_size(
+            &self.project_root,
+            &workspace.join("baseline-target"),
+            "baseline",
+        )?;
+        let experiment_bytes = self.build_size(
+            &copy_root,
+            &workspace.join("experiment-target"),
+            "experiment",
+        )?;
+
+        Ok(VerificationResult::new(
+            &experiment,
+            baseline_bytes,
+            experiment_bytes,
+        ))
+    }
+
+    /// Measure several experiments against a single baseline build
+    ///
+    /// The baseline is built once. The experiments are applied in turn to one
+    /// copy of the project under `scratch_dir` and built into a shared target
+    /// directory, so dependencies unaffected by an experiment are compiled only
+    /// once. A failed baseline fails the whole run; an experiment that cannot
+    /// be applied or built only fails its own entry. Returns the baseline
+    /// size and one result per experiment.
+    pub fn verify_all(
+        &self,
+        experiments: &[Experiment],
+        scratch_dir: &Path,
+    ) -> Result<(u64, Vec<Result<VerificationResult, VerificationError>>), VerificationError> {
+        let copy_root = scratch_dir.join("project");
+        self.copy_project(&copy_root)?;
+        let cargo_toml = copy_root.join("Cargo.toml");
+        let original = self.fs.read_to_string(&cargo_toml)?;
+
+        let baseline_bytes = self.build_size(
+            &self.project_root,
+            &scratch_dir.join("baseline-target"),
+            "baseline",
+        )?;
+
+        let results = experiments
+            .iter()
+            .map(|experiment| {
+                self.fs.write(&cargo_toml, experiment.apply(&original)?)?;
+                let experiment_bytes = self.build_size(
+                    &copy_root,
+                    &scratch_dir.join("experiment-target"),
+                    "experiment",
+                )?;
+                Ok(VerificationResult::new(
+                    experiment,
+                    baseline_bytes,
+                    experiment_bytes,
+                ))
+            })
+            .collect();
+        self.fs.write(&cargo_toml, original)?;
+        Ok((baseline_bytes, results))
+    }
+
+    /// Build a project into the given target dir and return the wasm size
+    pub(crate) fn build_size(
+        &self,
+        root: &Path,
+        target_dir: &Path,
+        stage: &'static str,
+    ) -> Result<u64, VerificationError> {
+        let config = PipelineConfig {
+            target_dir: Some(target_dir.to_path_buf()),
+            ..Default::default()
+        };
+        let runner = ToolRunner::new(
+            root.to_path_buf(),
+            config,
+            self.fs.clone(),
+            self.cmd_executor.clone(),
+        );
+        let wasm = runner
+            .cargo_build()
+            .map_err(|source| VerificationError::Build { stage, source })?;
+        Ok(self.fs.metadata(&wasm)?.len())
+    }
+
+    /// Copy the project to `copy_root` so that it builds there on its own
+    ///
+    /// Build output and hidden dirs are skipped. The copied manifest is
+    /// passed through [`detach_manifest`], and a workspace member also gets
+    /// the workspace's `Cargo.lock` so both builds resolve the same versions.
+    pub(crate) fn copy_project(&self, copy_root: &Path) -> Result<(), VerificationError> {
+        let project_root = std::path::absolute(&self.project_root)?;
+        self.copy_tree(&project_root, copy_root)?;
+
+        let cargo_toml = copy_root.join("Cargo.toml");
+        let manifest = self.fs.read_to_string(&cargo_toml)?;
+        let workspace_root = self.enclosing_workspace(&project_root, &manifest.parse()?);
+        let workspace_manifest = workspace_root
+            .as_ref()
+            .map(|root| self.fs.read_to_string(&root.join("Cargo.toml")))
+            .transpose()?;
+        let workspace = workspace_root.as_deref().zip(workspace_manifest.as_deref());
+        self.fs.write(
+            &cargo_toml,
+            detach_manifest(&manifest, &project_root, workspace)?,
+        )?;
+
+        if let Some(root) = &workspace_root {
+            let lockfile = root.join("Cargo.lock");
+            if self.fs.metadata(&lockfile).is_ok() {
+                self.fs.copy(&lockfile, &copy_root.join("Cargo.lock"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Root of the workspace the manifest at `project_root` is a member of
+    ///
+    /// An explicit `package.workspace` wins, otherwise the nearest ancestor
+    /// whose manifest has a `[workspace]` table. A workspace root has none.
+    fn enclosing_workspace(&self, project_root: &Path, manifest: &DocumentMut) -> Option<PathBuf> {
+        if manifest.contains_key("workspace") {
+            return None;
+        }
+        if let Some(path) = manifest
+            .get("package")
+            .and_then(|package| package.get("workspace"))
+            .and_then(Item::as_str)
+        {
+            return Some(project_root.join(path));
+        }
+        project_root
+            .ancestors()
+            .skip(1)
+            .find(|dir| {
+                self.fs
+                    .read_to_string(&dir.join("Cargo.toml"))
+                    .ok()
+                    .and_then(|contents| contents.parse::<DocumentMut>().ok())
+                    .is_some_and(|doc| doc.contains_key("workspace"))
+            })
+            .map(Path::to_path_buf)
+    }
+
+    /// Recursively copy sources, skipping build output and hidden dirs
+    fn copy_tree(&self, from: &Path, to: &Path) -> Result<(), VerificationError> {
+        self.fs.create_dir_all(to)?;
+        for entry in self.fs.read_dir(from)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if name == "target" || name == "pkg" || name.starts_with('.') {
+                continue;
+            }
+            let dest = to.join(name);
+            if path.is_dir() {
+                self.copy_tree(&path, &dest)?;
+            } else {
+                self.fs.copy(&path, &dest)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Filesystem-safe directory name for an experiment
+    fn workspace_name(experiment: &Experiment) -> String {
+        experiment
+            .id()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect()
+    }
+}
+
+/// Rewrite a manifest copied out of `original_dir` so it builds from the copy
+///
+/// Relative `path` dependencies and patches leaving `original_dir` are made
+/// absolute. With the `workspace` root and manifest of a member, inherited
+/// package fields, dependencies and lints are inlined and the workspace's
+/// `[profile]` and `[patch]` tables carried over, since cargo only reads
+/// those at the root. An empty `[workspace]` keeps cargo from adopting the
+/// copy into a workspace above it.
+pub(crate) fn detach_manifest(
+    manifest: &str,
+    original_dir: &Path,
+    workspace: Option<(&Path, &str)>,
+) -> Result<String, VerificationError> {
+    let mut doc = manifest.parse::<DocumentMut>()?;
+    for_each_dependency(doc.as_table_mut(), &mut |_, dep| {
+        rebase_path(dep, original_dir, true)
+    });
+
+    if let Some((root, workspace_manifest)) = workspace {
+        inherit_from_workspace(&mut doc, root, &workspace_manifest.parse()?);
+    }
+    if let Some(package) = doc.get_mut("package").and_then(Item::as_table_like_mut) {
+        package.remove("workspace");
+    }
+    if !doc.contains_key("workspace") {
+        doc.insert("workspace", toml_edit::table());
+    }
+    Ok(doc.to_string())
+}
+
+/// Inline what a member takes from its workspace manifest `workspace`
+fn inherit_from_workspace(doc: &mut DocumentMut, root: &Path, workspace: &DocumentMut) {
+    let shared = workspace.get("workspace");
+
+    let shared_package = shared.and_then(|w| w.get("package"));
+    if let Some(package) = doc.get_mut("package").and_then(Item::as_table_like_mut) {
+        for (key, field) in package.iter_mut() {
+            let Some(inherited) = shared_package.and_then(|p| p.get(key.get())) else {
+                continue;
+            };
+            if !inherits(field) {
+                continue;
+            }
+            *field = inherited.clone();
+            if matches!(key.get(), "readme" | "license-file") {
+                if let Some(path) = field.as_str().map(Path::new) {
+                    *field = value(root.join(path).display().to_string());
+                }
+            }
+        }
+    }
+
+    let shared_dependencies = shared
+        .and_then(|w| w.get("dependencies"))
+        .and_then(Item::as_table_like);
+    for_each_dependency(doc.as_table_mut(), &mut |name, dep| {
+        if let Some(inherited) = shared_dependencies.and_then(|deps| deps.get(name)) {
+            if inherits(dep) {
+                *dep = merge_dependency(inherited, dep, root);
+            }
+        }
+    });
+
+    if let (Some(lints), Some(shared_lints)) =
+        (doc.get_mut("lints"), shared.and_then(|w| w.get("lints")))
+    {
+        if inherits(lints) {
+            *lints = shared_lints.clone();
+        }
+    }
+
+    for key in ["profile", "patch"] {
+        match workspace.get(key) {
+            Some(item) => doc.insert(key, item.clone()),
+            None => doc.remove(key),
+        };
+    }
+    if let Some(patch) = doc.get_mut("patch").and_then(Item::as_table_like_mut) {
+        for (_, source) in patch.iter_mut() {
+            if let Some(source) = source.as_table_like_mut() {
+                for (_, dep) in source.iter_mut() {
+                    rebase_path(dep, root, false);
+                }
+            }
+        }
+    }
+}
+
+/// Whether a manifest entry is `{ workspace = true, ... }`
+fn inherits(item: &Item) -> bool {
+    item.get("workspace").and_then(Item::as_bool) == Some(true)
+}
+
+/// A workspace dependency with the member's own keys added, features merged
+fn merge_dependency(inherited: &Item, member: &Item, root: &Path) -> Item {
+    let mut merged = InlineTable::new();
+    if let Some(version) = inherited.as_str() {
+        merged.insert("version", version.into());
+    } else if let Some(table) = inherited.as_table_like() {
+        for (key, item) in table.iter() {
+            if let Some(value) = item.as_value() {
+                merged.insert(key, value.clone());
+            }
+        }
+    }
+    for (key, item) in member.as_table_like().into_iter().flat_map(|t| t.iter()) {
+        match (key, item.as_value()) {
+            ("workspace", _) | (_, None) => {}
+            ("features", Some(Value::Array(extra))) => {
+                let mut features = merged
+                    .get("features")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                for feature in extra {
+                    if !features.iter().any(|f| f.as_str() == feature.as_str()) {
+                        features.push(feature.clone());
+                    }
+                }
+                merged.insert("features", Value::Array(features));
+            }
+            (key, Some(value)) => {
+                merged.insert(key, value.clone());
+            }
+        }
+    }
+    merged.fmt();
+
+    let mut merged = Item::Value(Value::InlineTable(merged));
+    rebase_path(&mut merged, root, false);
+    merged
+}
+
+/// Call `visit` with every dependency and `[patch]` entry of a manifest
+fn for_each_dependency(manifest: &mut dyn TableLike, visit: &mut dyn FnMut(&str, &mut Item)) {
+    const KINDS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+    for kind in KINDS {
+        if let Some(deps) = manifest.get_mut(kind).and_then(Item::as_table_like_mut) {
+            for (name, dep) in deps.iter_mut() {
+                visit(name.get(), dep);
+            }
+        }
+    }
+    for section in ["target", "patch"] {
+        let Some(tables) = manifest.get_mut(section).and_then(Item::as_table_like_mut) else {
+            continue;
+        };
+        for (_, table) in tables.iter_mut() {
+            let Some(table) = table.as_table_like_mut() else {
+                continue;
+            };
+            if section == "patch" {
+                for (name, dep) in table.iter_mut() {
+                    visit(name.get(), dep);
+                }
+            } else {
+                for_each_dependency(table, visit);
+            }
+        }
+    }
+}
+
+/// Make a dependency's relative `path` absolute against `base`; with
+/// `outside_only`, only a path that starts by leaving `base`
+fn rebase_path(dep: &mut Item, base: &Path, outside_only: bool) {
+    let Some(table) = dep.as_table_like_mut() else {
+        return;
+    };
+    let Some(path) = table.get("path").and_then(Item::as_str).map(Path::new) else {
+        return;
+    };
+    let leaves_base = matches!(path.components().next(), Some(Component::ParentDir));
+    if path.is_relative() && (leaves_base || !outside_only) {
+        let rebased = base.join(path).display().to_string();
+        table.insert("path", value(rebased));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_experiment_parse_default_features() {
+        let exp = Experiment::parse("default-features:regex").unwrap();
+        assert_eq!(
+            exp,
+            Experiment::DisableDefaultFeatures {
+                package: "regex".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_experiment_parse_profile_setting_roundtrips_id() {
+        let exp = Experiment::parse("profile:opt-level=z").unwrap();
+        assert_eq!(exp.id(), "profile:opt-level=z");
+        assert!(exp.description().contains("opt-level"));
+    }
+
+    #[test]
+    fn test_experiment_parse_rejects_unknown_ids() {
+        for id in [
+            "",
+            "regex",
+            "foo:bar",
+            "profile:opt-level",
+            "profile:=z",
+            "default-features:",
+            "feature:regex",
+            "feature:/unicode",
+        ] {
+            assert!(
+                matches!(
+                    Experiment::parse(id),
+                    Err(VerificationError::UnknownRecommendation(_))
+                ),
+                "expected {id:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_default_features_converts_string_dependency() {
+        let exp = Experiment::parse("default-features:regex").unwrap();
+        let out = exp
+            .apply("[package]\nname = \"x\"\n\n[dependencies]\nregex = \"1.10\"\n")
+            .unwrap();
+        assert!(out.contains("default-features = false"));
+        assert!(out.contains("version = \"1.10\""));
+    }
+
+    #[test]
+    fn test_apply_default_features_updates_table_dependency() {
+        let exp = Experiment::parse("default-features:serde").unwrap();
+        let out = exp
+            .apply("[dependencies.serde]\nversion = \"1\"\nfeatures = [\"derive\"]\n")
+            .unwrap();
+        assert!(out.contains("default-features = false"));
+        assert!(out.contains("features = [\"derive\"]"));
+    }
+
+    #[test]
+    fn test_apply_default_features_missing_dependency_errors() {
+        let exp = Experiment::parse("default-features:chrono").unwrap();
+        let err = exp.apply("[dependencies]\nregex = \"1\"\n").unwrap_err();
+        assert!(matches!(err, VerificationError::DependencyNotFound(_)));
+    }
+
+    #[test]
+    fn test_apply_disable_feature_removes_only_that_feature() {
+        let exp = Experiment::parse("feature:tokio/fs").unwrap();
+        assert_eq!(exp.id(), "feature:tokio/fs");
+        let out = exp
+            .apply("[dependencies]\ntokio = { version = \"1\", features = [\"rt\", \"fs\"] }\n")
+            .unwrap();
+        assert!(out.contains("features = [\"rt\"]"));
+
+        let err = Experiment::parse("feature:tokio/net")
+            .unwrap()
+            .apply(&out)
+            .unwrap_err();
+        assert!(matches!(err, VerificationError::FeatureNotListed(_, _)));
+    }
+
+    #[test]
+    fn test_apply_profile_setting_creates_release_profile() {
+        let out = Experiment::parse("profile:codegen-units=1")
+            .unwrap()
+            .apply("[package]\nname = \"x\"\n")
+            .unwrap();
+        assert!(out.contains("[profile.release]"));
+        assert!(out.contains("codegen-units = 1"));
+
+        let out = Experiment::parse("profile:opt-level=z")
+            .unwrap()
+            .apply(&out)
+            .unwrap();
+        assert!(out.contains("opt-level = \"z\""));
+        assert!(out.contains("codegen-units = 1"));
+    }
+
+    #[test]
+    fn test_verification_result_computes_delta() {
+        let exp = Experiment::parse("profile:lto=true").unwrap();
+        let result = VerificationResult::new(&exp, 1000, 900);
+        assert_eq!(result.delta_bytes, -100);
+        assert!((result.delta_percent + 10.0).abs() < f64::EPSILON);
+        assert_eq!(result.id, "profile:lto=true");
+    }
+
+    #[test]
+    fn test_workspace_name_is_filesystem_safe() {
+        let exp = Experiment::parse("profile:opt-level=z").unwrap();
+        assert_eq!(
+            RecommendationVerifier::<RealFileSystem, RealCommandExecutor>::workspace_name(&exp),
+            "profile-opt-level-z"
+        );
+    }
+
+    #[test]
+    fn test_detach_manifest_rebases_paths_leaving_the_project() {
+        let manifest = "[package]\nname = \"app\"\n\n\
+                        [dependencies]\n\
+                        local = { path = \"crates/local\" }\n\
+                        sibling = { path = \"../sibling\" }\n\n\
+                        [target.'cfg(target_arch = \"wasm32\")'.dependencies]\n\
+                        web = { path = \"../web\" }\n\n\
+                        [patch.crates-io]\n\
+                        serde = { path = \"../serde\" }\n";
+
+        let out = detach_manifest(manifest, Path::new("/work/app"), None).unwrap();
+        let doc = out.parse::<DocumentMut>().unwrap();
+        let path = |item: &Item| item["path"].as_str().unwrap().to_string();
+
+        assert_eq!(path(&doc["dependencies"]["local"]), "crates/local");
+        assert_eq!(
+            path(&doc["dependencies"]["sibling"]),
+            Path::new("/work/app")
+                .join("../sibling")
+                .display()
+                .to_string()
+        );
+        assert!(
+            path(&doc["target"]["cfg(target_arch = \"wasm32\")"]["dependencies"]["web"])
+                .starts_with("/work/app")
+        );
+        assert!(path(&doc["patch"]["crates-io"]["serde"]).starts_with("/work/app"));
+        assert!(doc["workspace"].is_table());
+    }
+
+    #[test]
+    fn test_detach_manifest_inlines_workspace_inheritance() {
+        let workspace = "[workspace]\nmembers = [\"app\"]\n\n\
+                         [workspace.package]\nversion = \"0.3.0\"\nedition = \"2021\"\n\n\
+                         [workspace.dependencies]\n\
+                         serde = { version = \"1\", features = [\"derive\"] }\n\
+                         shared = { path = \"crates/shared\" }\n\n\
+                         [workspace.lints.rust]\nunsafe_code = \"forbid\"\n\n\
+                         [profile.release]\nopt-level = \"z\"\n";
+        let member =
+            "[package]\nname = \"app\"\nversion.workspace = true\nedition.workspace = true\n\n\
+                      [dependencies]\n\
+                      serde = { workspace = true, features = [\"rc\"], optional = true }\n\
+                      shared.workspace = true\n\n\
+                      [lints]\nworkspace = true\n\n\
+                      [profile.release]\nopt-level = 3\n";
+
+        let out = detach_manifest(
+            member,
+            Path::new("/work/app"),
+            Some((Path::new("/work"), workspace)),
+        )
+        .unwrap();
+        let doc = out.parse::<DocumentMut>().unwrap();
+
+        assert_eq!(doc["package"]["version"].as_str(), Some("0.3.0"));
+        assert_eq!(doc["package"]["edition"].as_str(), Some("2021"));
+        let serde = &doc["dependencies"]["serde"];
+        assert_eq!(serde["version"].as_str(), Some("1"));
+        assert_eq!(serde["optional"].as_bool(), Some(true));
+        let features: Vec<_> = serde["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|f| f.as_str())
+            .collect();
+        assert_eq!(features, ["derive", "rc"]);
+        assert!(serde.get("workspace").is_none());
+        assert_eq!(
+            doc["dependencies"]["shared"]["path"].as_str(),
+            Some(
+                Path::new("/work")
+                    .join("crates/shared")
+                    .display()
+                    .to_string()
+                    .as_str()
+            )
+        );
+        assert_eq!(doc["lints"]["rust"]["unsafe_code"].as_str(), Some("forbid"));
+        assert_eq!(doc["profile"]["release"]["opt-level"].as_str(), Some("z"));
+        assert!(doc["workspace"].is_table());
+
+        // Experiments apply to the inlined dependency
+        let out = Experiment::parse("default-features:serde")
+            .unwrap()
+            .apply(&out)
+            .unwrap();
+        assert!(out.contains("default-features = false"));
+    }
+
+    #[test]
+    fn test_copy_project_detaches_workspace_member() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"app\"]\n\n\
+             [workspace.dependencies]\nshared = { path = \"shared\" }\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("Cargo.lock"), "version = 4\n").unwrap();
+        let app = root.join("app");
+        std::fs::create_dir_all(app.join("src")).unwrap();
+        std::fs::write(
+            app.join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[dependencies]\nshared.workspace = true\n",
+        )
+        .unwrap();
+        std::fs::write(app.join("src/lib.rs"), "").unwrap();
+
+        let copy_root = temp.path().join("scratch");
+        RecommendationVerifier::new(&app)
+            .copy_project(&copy_root)
+            .unwrap();
+
+        assert!(copy_root.join("src/lib.rs").exists());
+        assert!(copy_root.join("Cargo.lock").exists());
+        let doc = std::fs::read_to_string(copy_root.join("Cargo.toml"))
+            .unwrap()
+            .parse::<DocumentMut>()
+            .unwrap();
+        assert_eq!(
+            doc["dependencies"]["shared"]["path"].as_str(),
+            Some(root.join("shared").display().to_string().as_str())
+        );
+        assert!(doc["workspace"].is_table());
+    }
+
+    #[test]
+    fn test_verify_unknown_id_fails_before_touching_project() {
+        let verifier = RecommendationVerifier::new("/nonexistent/project");
+        assert!(matches!(
+            verifier.verify("bogus"),
+            Err(VerificationError::UnknownRecommendation(_))
+        ));
+    }
+}
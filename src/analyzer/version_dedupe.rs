@@ -0,0 +1,270 @@
+//! Cost and remediation of duplicate crate versions
+//!
+//! Every version of a crate in the resolved graph is compiled into the
+//! module. For each crate resolved more than once this estimates what the
+//! copies beyond the newest one cost and works out how to unify them on the
+//! newest version:
+//!
+//! - a copy every dependent could replace with the newest version only needs
+//!   the lockfile moved (`cargo update -p name@old --precise new`)
+//! - a copy required by a workspace member needs that member's requirement
+//!   raised in its Cargo.toml
+//! - a copy required by another crate needs that crate upgraded, by hand
+//!
+//! Costs come from measured crate sizes or the heavy-dependency database
+//! when the crate is listed there, otherwise from the size of the crate's
+//! Rust sources.
+
+use cargo_metadata::semver::Version;
+use cargo_metadata::{Metadata, Package, PackageId};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::deps_types::{DedupeFix, DuplicateVersion};
+use super::heavy_deps::HeavyDepsDatabase;
+use super::serde_usage::collect_rust_files;
+
+/// Rough ratio of optimized WASM bytes to Rust source bytes for a crate
+const SOURCE_TO_WASM_RATIO: f64 = 0.15;
+
+/// Duplicated crates in a resolved graph, costliest first
+pub(crate) fn duplicate_versions(
+    metadata: &Metadata,
+    heavy_deps: &HeavyDepsDatabase,
+) -> Vec<DuplicateVersion> {
+    let Some(resolve) = &metadata.resolve else {
+        return Vec::new();
+    };
+    let members: HashSet<&PackageId> = metadata.workspace_members.iter().collect();
+    let packages: HashMap<&PackageId, &Package> =
+        metadata.packages.iter().map(|p| (&p.id, p)).collect();
+
+    let mut dependents: HashMap<&PackageId, Vec<&Package>> = HashMap::new();
+    for node in &resolve.nodes {
+        let Some(parent) = packages.get(&node.id) else {
+            continue;
+        };
+        for dep in &node.deps {
+            dependents.entry(&dep.pkg).or_default().push(parent);
+        }
+    }
+
+    let mut by_name: BTreeMap<&str, Vec<&Package>> = BTreeMap::new();
+    for package in resolve.nodes.iter().filter_map(|n| packages.get(&n.id)) {
+        if !members.contains(&package.id) {
+            by_name.entry(&package.name).or_default().push(package);
+        }
+    }
+
+    let mut duplicates: Vec<DuplicateVersion> = by_name
+        .into_iter()
+        .filter(|(_, copies)| copies.len() > 1)
+        .map(|(name, mut copies)| {
+            copies.sort_by(|a, b| a.version.cmp(&b.version));
+            let newest = copies[copies.len() - 1];
+            let older = &copies[..copies.len() - 1];
+
+            let mut fixes = Vec::new();
+            for copy in older {
+                let copy_dependents = dependents.get(&copy.id).map(Vec::as_slice);
+                fixes.extend(copy_fixes(
+                    name,
+                    copy,
+                    &newest.version,
+                    copy_dependents.unwrap_or_default(),
+                    &members,
+                ));
+            }
+            fixes.dedup();
+
+            DuplicateVersion {
+                package: name.to_string(),
+                versions: copies.iter().map(|p| p.version.to_string()).collect(),
+                estimated_cost_bytes: older
+                    .iter()
+                    .map(|p| estimated_copy_bytes(p, heavy_deps))
+                    .sum(),
+                fixes,
+            }
+        })
+        .collect();
+    duplicates.sort_by(|a, b| {
+        b.estimated_cost_bytes
+            .cmp(&a.estimated_cost_bytes)
+            .then_with(|| a.package.cmp(&b.package))
+    });
+    duplicates
+}
+
+/// Fixes that remove one older copy of `name`
+fn copy_fixes(
+    name: &str,
+    copy: &Package,
+    newest: &Version,
+    dependents: &[&Package],
+    members: &HashSet<&PackageId>,
+) -> Vec<DedupeFix> {
+    // Dependents whose requirement keeps this copy from moving to the newest version
+    let blockers: Vec<_> = dependents
+        .iter()
+        .filter_map(|parent| {
+            let dep = parent
+                .dependencies
+                .iter()
+                .find(|d| d.name == name && d.req.matches(&copy.version))?;
+            (!dep.req.matches(newest)).then_some((*parent, dep))
+        })
+        .collect();
+
+    if blockers.is_empty() {
+        return vec![DedupeFix::CargoUpdate {
+            package: name.to_string(),
+            from: copy.version.to_string(),
+            to: newest.to_string(),
+        }];
+    }
+    blockers
+        .into_iter()
+        .map(|(parent, dep)| {
+            if members.contains(&parent.id) {
+                DedupeFix::UnifyRequirement {
+                    member: parent.name.to_string(),
+                    dependency: dep.rename.clone().unwrap_or_else(|| dep.name.clone()),
+                    from: dep.req.to_string(),
+                    to: format!("{}.{}", newest.major, newest.minor),
+                }
+            } else {
+                DedupeFix::UpgradeDependent {
+                    dependent: parent.name.to_string(),
+                    version: parent.version.to_string(),
+                    package: name.to_string(),
+                    requires: dep.req.to_string(),
+                }
+            }
+        })
+        .collect()
+}
+
+/// Estimated size one copy of a package adds to the module
+fn estimated_copy_bytes(package: &Package, heavy_deps: &HeavyDepsDatabase) -> u64 {
+    if let Some((min_kb, max_kb)) = heavy_deps.size_kb(&package.name) {
+        return u64::from(min_kb + max_kb) / 2 * 1024;
+    }
+    let Some(src) = package.manifest_path.parent().map(|dir| dir.join("src")) else {
+        return 0;
+    };
+    let mut files = Vec::new();
+    if collect_rust_files(src.as_std_path(), &mut files).is_err() {
+        return 0;
+    }
+    let source_bytes: u64 = files
+        .iter()
+        .filter_map(|f| std::fs::metadata(f).ok())
+        .map(|m| m.len())
+        .sum();
+    (source_bytes as f64 * SOURCE_TO_WASM_RATIO) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn dep(name: &str, req: &str) -> Value {
+        json!({
+            "name": name, "source": null, "req": req, "kind": null,
+            "optional": false, "uses_default_features": true,
+            "features": [], "target": null, "rename": null,
+            "registry": null, "path": null
+        })
+    }
+
+    fn package(name: &str, version: &str, deps: Vec<Value>) -> Value {
+        json!({
+            "name": name, "version": version, "id": format!("{} {}", name, version),
+            "license": null, "license_file": null, "description": null,
+            "source": null, "dependencies": deps, "targets": [], "features": {},
+            "manifest_path": format!("/fake/{}-{}/Cargo.toml", name, version),
+            "metadata": null, "publish": null, "authors": [], "categories": [],
+            "keywords": [], "readme": null, "repository": null, "homepage": null,
+            "documentation": null, "edition": "2021", "links": null,
+            "default_run": null, "rust_version": null
+        })
+    }
+
+    fn node(id: &str, deps: &[&str]) -> Value {
+        json!({
+            "id": id,
+            "dependencies": deps,
+            "deps": deps.iter().map(|d| json!({
+                "name": d.split(' ').next().unwrap(), "pkg": d,
+                "dep_kinds": [{"kind": null, "target": null}]
+            })).collect::<Vec<_>>(),
+            "features": []
+        })
+    }
+
+    #[test]
+    fn test_duplicate_versions_picks_update_unify_or_upgrade() {
+        let metadata: Metadata = serde_json::from_value(json!({
+            "packages": [
+                package("app", "0.1.0", vec![dep("syn", "^1.0"), dep("itoa", "^1.0.1"), dep("legacy", "^0.3")]),
+                package("legacy", "0.3.0", vec![dep("itoa", "^1.0"), dep("base64", "^0.13")]),
+                package("modern", "1.0.0", vec![dep("syn", "^2"), dep("base64", "^0.22")]),
+                package("syn", "1.0.109", vec![]),
+                package("syn", "2.0.48", vec![]),
+                package("itoa", "1.0.0", vec![]),
+                package("itoa", "1.0.11", vec![]),
+                package("base64", "0.13.1", vec![]),
+                package("base64", "0.22.1", vec![]),
+            ],
+            "workspace_members": ["app 0.1.0"],
+            "workspace_default_members": ["app 0.1.0"],
+            "resolve": {
+                "nodes": [
+                    node("app 0.1.0", &["syn 1.0.109", "itoa 1.0.11", "legacy 0.3.0", "modern 1.0.0"]),
+                    node("legacy 0.3.0", &["itoa 1.0.0", "base64 0.13.1"]),
+                    node("modern 1.0.0", &["syn 2.0.48", "base64 0.22.1"]),
+                    node("syn 1.0.109", &[]),
+                    node("syn 2.0.48", &[]),
+                    node("itoa 1.0.0", &[]),
+                    node("itoa 1.0.11", &[]),
+                    node("base64 0.13.1", &[]),
+                    node("base64 0.22.1", &[]),
+                ],
+                "root": "app 0.1.0"
+            },
+            "target_directory": "/fake/target",
+            "version": 1,
+            "workspace_root": "/fake",
+            "metadata": null
+        }))
+        .unwrap();
+
+        let duplicates = duplicate_versions(&metadata, &HeavyDepsDatabase::default());
+        let fixes: BTreeMap<&str, Vec<String>> = duplicates
+            .iter()
+            .map(|d| {
+                (
+                    d.package.as_str(),
+                    d.fixes.iter().map(|f| f.to_string()).collect(),
+                )
+            })
+            .collect();
+
+        assert_eq!(duplicates.len(), 3);
+        assert_eq!(
+            fixes["itoa"],
+            vec!["cargo update -p itoa@1.0.0 --precise 1.0.11"]
+        );
+        assert_eq!(
+            fixes["syn"],
+            vec!["set syn = \"2.0\" in app's Cargo.toml (currently ^1.0)"]
+        );
+        assert_eq!(
+            fixes["base64"],
+            vec!["upgrade legacy 0.3.0, which requires base64 ^0.13"]
+        );
+        let syn = duplicates.iter().find(|d| d.package == "syn").unwrap();
+        assert_eq!(syn.versions, vec!["1.0.109", "2.0.48"]);
+    }
+}
@@ -2,7 +2,7 @@
 
 use super::storage::{BenchmarkBaseline, BenchmarkResult};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// Performance budget thresholds
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,7 +61,7 @@ impl BenchmarkComparator {
     /// Compare current results against baseline
     pub fn compare_with_baseline(
         &self,
-        current: &HashMap<String, BenchmarkResult>,
+        current: &BTreeMap<String, BenchmarkResult>,
         baseline: &BenchmarkBaseline,
     ) -> Vec<BenchmarkComparison> {
         let mut comparisons = Vec::new();
@@ -125,7 +125,7 @@ mod tests {
         };
         let comparator = BenchmarkComparator::new(budget);
 
-        let mut baseline_results = HashMap::new();
+        let mut baseline_results = BTreeMap::new();
         baseline_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -147,7 +147,7 @@ mod tests {
         };
 
         // Test at exactly threshold (10% slower = 1,100,000 ns)
-        let mut current_at_threshold = HashMap::new();
+        let mut current_at_threshold = BTreeMap::new();
         current_at_threshold.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -169,7 +169,7 @@ mod tests {
         );
 
         // Test just over threshold (10.1% slower = 1,101,000 ns)
-        let mut current_over_threshold = HashMap::new();
+        let mut current_over_threshold = BTreeMap::new();
         current_over_threshold.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -195,7 +195,7 @@ mod tests {
     fn test_compare_with_baseline_handles_new_benchmarks() {
         let comparator = BenchmarkComparator::with_default_budget();
 
-        let mut baseline_results = HashMap::new();
+        let mut baseline_results = BTreeMap::new();
         baseline_results.insert(
             "old_benchmark".to_string(),
             BenchmarkResult {
@@ -216,7 +216,7 @@ mod tests {
             results: baseline_results,
         };
 
-        let mut current_results = HashMap::new();
+        let mut current_results = BTreeMap::new();
         current_results.insert(
             "old_benchmark".to_string(),
             BenchmarkResult {
@@ -252,7 +252,7 @@ mod tests {
     fn test_compare_with_baseline_detects_improvements() {
         let comparator = BenchmarkComparator::with_default_budget();
 
-        let mut baseline_results = HashMap::new();
+        let mut baseline_results = BTreeMap::new();
         baseline_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -274,7 +274,7 @@ mod tests {
         };
 
         // 20% faster
-        let mut current_results = HashMap::new();
+        let mut current_results = BTreeMap::new();
         current_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -306,7 +306,7 @@ mod tests {
         };
         let comparator = BenchmarkComparator::new(budget);
 
-        let mut baseline_results = HashMap::new();
+        let mut baseline_results = BTreeMap::new();
         baseline_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -327,7 +327,7 @@ mod tests {
             results: baseline_results,
         };
 
-        let mut current_results = HashMap::new();
+        let mut current_results = BTreeMap::new();
         current_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -15,7 +15,7 @@ pub use storage::{BenchmarkBaseline, BenchmarkResult, BenchmarkStorage};
 
 use crate::infra::{FileSystem, RealFileSystem};
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::time::SystemTime;
 
@@ -75,14 +75,14 @@ impl<FS: FileSystem> BenchmarkTracker<FS> {
     pub fn parse_criterion_results(
         &self,
         criterion_dir: &Path,
-    ) -> Result<HashMap<String, BenchmarkResult>> {
+    ) -> Result<BTreeMap<String, BenchmarkResult>> {
         self.parser.parse_criterion_results(criterion_dir)
     }
 
     /// Compare current results against baseline
     pub fn compare_with_baseline(
         &self,
-        current: &HashMap<String, BenchmarkResult>,
+        current: &BTreeMap<String, BenchmarkResult>,
         baseline: &BenchmarkBaseline,
     ) -> Vec<BenchmarkComparison> {
         self.comparator.compare_with_baseline(current, baseline)
@@ -109,7 +109,7 @@ impl<FS: FileSystem> BenchmarkTracker<FS> {
     /// Create a baseline from current results
     pub fn create_baseline(
         &self,
-        results: HashMap<String, BenchmarkResult>,
+        results: BTreeMap<String, BenchmarkResult>,
         version: String,
     ) -> Result<BenchmarkBaseline> {
         Ok(BenchmarkBaseline {
@@ -135,7 +135,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("Failed to create temp directory");
         let tracker = BenchmarkTracker::new(temp_dir.path());
 
-        let mut results = HashMap::new();
+        let mut results = BTreeMap::new();
         results.insert(
             "benchmark_1".to_string(),
             BenchmarkResult {
@@ -195,7 +195,7 @@ mod tests {
         };
         let tracker = BenchmarkTracker::with_budget(temp_dir.path(), budget);
 
-        let mut baseline_results = HashMap::new();
+        let mut baseline_results = BTreeMap::new();
         baseline_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -216,7 +216,7 @@ mod tests {
             results: baseline_results,
         };
 
-        let mut current_at_threshold = HashMap::new();
+        let mut current_at_threshold = BTreeMap::new();
         current_at_threshold.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -237,7 +237,7 @@ mod tests {
             "Exactly at threshold should not be regression"
         );
 
-        let mut current_over_threshold = HashMap::new();
+        let mut current_over_threshold = BTreeMap::new();
         current_over_threshold.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -266,7 +266,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("Failed to create temp directory");
         let tracker = BenchmarkTracker::new(temp_dir.path());
 
-        let mut baseline_results = HashMap::new();
+        let mut baseline_results = BTreeMap::new();
         baseline_results.insert(
             "old_benchmark".to_string(),
             BenchmarkResult {
@@ -287,7 +287,7 @@ mod tests {
             results: baseline_results,
         };
 
-        let mut current_results = HashMap::new();
+        let mut current_results = BTreeMap::new();
         current_results.insert(
             "old_benchmark".to_string(),
             BenchmarkResult {
@@ -345,7 +345,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("Failed to create temp directory");
         let tracker = BenchmarkTracker::new(temp_dir.path());
 
-        let mut baseline_results = HashMap::new();
+        let mut baseline_results = BTreeMap::new();
         baseline_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -366,7 +366,7 @@ mod tests {
             results: baseline_results,
         };
 
-        let mut current_results = HashMap::new();
+        let mut current_results = BTreeMap::new();
         current_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -401,7 +401,7 @@ mod tests {
         };
         let tracker = BenchmarkTracker::with_budget(temp_dir.path(), budget);
 
-        let mut baseline_results = HashMap::new();
+        let mut baseline_results = BTreeMap::new();
         baseline_results.insert(
             "test".to_string(),
             BenchmarkResult {
@@ -422,7 +422,7 @@ mod tests {
             results: baseline_results,
         };
 
-        let mut current_results = HashMap::new();
+        let mut current_results = BTreeMap::new();
         current_results.insert(
             "test".to_string(),
             BenchmarkResult {
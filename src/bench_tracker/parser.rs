@@ -3,7 +3,7 @@
 use super::storage::BenchmarkResult;
 use crate::infra::FileSystem;
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::time::SystemTime;
 
@@ -22,8 +22,8 @@ impl<FS: FileSystem> BenchmarkParser<FS> {
     pub fn parse_criterion_results(
         &self,
         criterion_dir: &Path,
-    ) -> Result<HashMap<String, BenchmarkResult>> {
-        let mut results = HashMap::new();
+    ) -> Result<BTreeMap<String, BenchmarkResult>> {
+        let mut results = BTreeMap::new();
 
         // Criterion stores results in target/criterion/<benchmark_name>/base/estimates.json
         for entry in self
@@ -3,7 +3,7 @@
 use crate::infra::FileSystem;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// Collection of benchmark results
@@ -16,7 +16,7 @@ pub struct BenchmarkBaseline {
     /// Git commit hash if available
     pub git_commit: Option<String>,
     /// Map of benchmark name to result
-    pub results: HashMap<String, BenchmarkResult>,
+    pub results: BTreeMap<String, BenchmarkResult>,
 }
 
 /// A single benchmark measurement
@@ -47,7 +47,7 @@ pub struct BenchmarkStorage<FS: FileSystem> {
 impl<FS: FileSystem> BenchmarkStorage<FS> {
     /// Create a new benchmark storage
     pub fn new(project_root: &Path, fs: FS) -> Self {
-        let baseline_dir = project_root.join(".wasm-slim").join("benchmarks");
+        let baseline_dir = crate::state::state_dir(project_root).join("benchmarks");
         Self { baseline_dir, fs }
     }
 
@@ -107,7 +107,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("Failed to create temp directory");
         let storage = BenchmarkStorage::new(temp_dir.path(), RealFileSystem);
 
-        let mut results = HashMap::new();
+        let mut results = BTreeMap::new();
         results.insert(
             "benchmark_1".to_string(),
             BenchmarkResult {
@@ -277,8 +277,7 @@ fn show_baseline(project_root: &Path) -> Result<()> {
 
 /// Reset/delete current baseline
 fn reset_baseline(project_root: &Path) -> Result<()> {
-    let baseline_path = project_root
-        .join(".wasm-slim")
+    let baseline_path = wasm_slim::state::state_dir(project_root)
         .join("benchmarks")
         .join("baseline.json");
 
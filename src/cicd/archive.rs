@@ -0,0 +1,462 @@
+//! Archived size reports
+//!
+//! `wasm-slim report archive` bundles everything needed to explain a shipped
+//! bundle's size into one timestamped `.tar.zst` under
+//! `.wasm-slim/reports/`:
+//!
+//! | Entry           | Contents                                                |
+//! |-----------------|---------------------------------------------------------|
+//! | `report.json`   | Size, budget and regression results (same as `--json`)  |
+//! | `report.html`   | The same report as a standalone HTML page               |
+//! | `manifest.json` | wasm-slim version, git commit/branch, archived files    |
+//! | `config.lock`   | Effective `.wasm-slim.toml` settings at archive time    |
+//! | `Cargo.lock`    | Resolved dependency versions (if present)               |
+//!
+//! The tarball is written and zstd-compressed in process, so no `tar` or
+//! `zstd` install is needed. Only the newest archives are kept; older ones are
+//! pruned after each new archive is written.
+
+use crate::analyzer::report_utils::escape_html;
+use crate::cicd::{BudgetChecker, BuildHistory, DeployProfile, JsonOutput};
+use crate::config::ConfigLoader;
+use crate::git::GitRepository;
+use crate::summary::CompressedSizes;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of archives kept by default
+pub const DEFAULT_KEEP: usize = 20;
+
+const ARCHIVE_PREFIX: &str = "report-";
+const ARCHIVE_EXTENSION: &str = ".tar.zst";
+
+/// zstd compression level for archives
+const ZSTD_LEVEL: i32 = 19;
+
+/// One file inside an archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// File name inside the archive
+    pub name: String,
+    /// Size in bytes
+    pub size_bytes: u64,
+}
+
+/// Description of an archive, stored as `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    /// wasm-slim version that wrote the archive
+    pub wasm_slim_version: String,
+    /// Creation time (seconds since the Unix epoch)
+    pub created_unix: u64,
+    /// Archived WASM file
+    pub wasm_file: String,
+    /// Size of the WASM file in bytes
+    pub wasm_size_bytes: u64,
+    /// Git commit hash (if available)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_hash: Option<String>,
+    /// Git branch (if available)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Files in the archive, excluding the manifest itself
+    pub files: Vec<ManifestEntry>,
+}
+
+/// Writes and prunes archived size reports
+pub struct ReportArchiver {
+    project_root: PathBuf,
+    reports_dir: PathBuf,
+}
+
+impl ReportArchiver {
+    /// Create an archiver for the given project
+    pub fn new(project_root: &Path) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            reports_dir: crate::state::state_dir(project_root).join("reports"),
+        }
+    }
+
+    /// Directory archives are written to
+    pub fn reports_dir(&self) -> &Path {
+        &self.reports_dir
+    }
+
+    /// Archive the report for `wasm_file` and return the archive path
+    pub fn create(&self, wasm_file: &Path) -> Result<PathBuf> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("System time before UNIX_EPOCH")?;
+        let stamp = format_archive_stamp(now.as_secs());
+
+        std::fs::create_dir_all(&self.reports_dir)
+            .context("Failed to create .wasm-slim/reports directory")?;
+
+        let mut archive = self
+            .reports_dir
+            .join(format!("{}{}{}", ARCHIVE_PREFIX, stamp, ARCHIVE_EXTENSION));
+        // Two archives within the same second get a numeric suffix
+        let mut suffix = 1;
+        while archive.exists() {
+            suffix += 1;
+            archive = self.reports_dir.join(format!(
+                "{}{}-{}{}",
+                ARCHIVE_PREFIX, stamp, suffix, ARCHIVE_EXTENSION
+            ));
+        }
+
+        let staging = self.reports_dir.join(format!(".staging-{}", stamp));
+        let result = self
+            .stage(wasm_file, &staging, now.as_secs())
+            .and_then(|manifest| self.compress(&staging, &manifest, &archive));
+        let _ = std::fs::remove_dir_all(&staging);
+        result?;
+
+        Ok(archive)
+    }
+
+    /// Write the archive contents into `staging`
+    pub fn stage(
+        &self,
+        wasm_file: &Path,
+        staging: &Path,
+        created_unix: u64,
+    ) -> Result<ArchiveManifest> {
+        let wasm_size_bytes = std::fs::metadata(wasm_file)
+            .with_context(|| format!("WASM file not found: {}", wasm_file.display()))?
+            .len();
+
+        std::fs::create_dir_all(staging).context("Failed to create staging directory")?;
+
+        let config = ConfigLoader::load(&self.project_root)?;
+        let mut report = JsonOutput::new(wasm_size_bytes);
+        if let Some(path) = ConfigLoader::override_path(&self.project_root) {
+            report = report.with_config(&path);
+        }
+        let profile = config.deploy.as_ref().map(DeployProfile::from_settings);
+        let compressed = || CompressedSizes::measure(wasm_file);
+        if let Some(profile) = profile.filter(|p| p.limit.is_some()) {
+            let budget = config.size_budget.clone().unwrap_or_default();
+            let (size, metric) = profile.measure(Some(wasm_file), wasm_size_bytes);
+            let checker = BudgetChecker::new(budget)
+                .with_deploy_profile(&profile)
+                .with_metric(metric);
+            report = report.with_budget(&checker.check(size)?.with_compressed(compressed()));
+        } else if let Some(budget) = config.size_budget.clone() {
+            let result = BudgetChecker::new(budget).check(wasm_size_bytes)?;
+            report = report.with_budget(&result.with_compressed(compressed()));
+        }
+        if let Some(regression) =
+            BuildHistory::load(&self.project_root)?.check_regression(wasm_size_bytes)
+        {
+            report = report.with_regression(&regression);
+        }
+
+        let git = GitRepository::new();
+        let mut manifest = ArchiveManifest {
+            wasm_slim_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_unix,
+            wasm_file: wasm_file.display().to_string(),
+            wasm_size_bytes,
+            commit_hash: git.get_commit_hash().ok().flatten(),
+            branch: git.get_branch_name().ok().flatten(),
+            files: Vec::new(),
+        };
+
+        let html = render_html(&report, &manifest);
+        let mut write = |name: &str, contents: String| -> Result<()> {
+            std::fs::write(staging.join(name), &contents)
+                .with_context(|| format!("Failed to write {}", name))?;
+            manifest.files.push(ManifestEntry {
+                name: name.to_string(),
+                size_bytes: contents.len() as u64,
+            });
+            Ok(())
+        };
+
+        write("report.json", report.to_json()?)?;
+        write("report.html", html)?;
+        write(
+            "config.lock",
+            toml_edit::ser::to_string_pretty(&config).context("Failed to serialize config")?,
+        )?;
+        if let Ok(lockfile) = std::fs::read_to_string(self.project_root.join("Cargo.lock")) {
+            write("Cargo.lock", lockfile)?;
+        }
+
+        std::fs::write(
+            staging.join("manifest.json"),
+            crate::redact::to_json_pretty(&manifest).context("Failed to serialize manifest")?,
+        )
+        .context("Failed to write manifest.json")?;
+
+        Ok(manifest)
+    }
+
+    /// Pack the staged files into a zstd-compressed tarball
+    fn compress(&self, staging: &Path, manifest: &ArchiveManifest, archive: &Path) -> Result<()> {
+        let result = write_tarball(staging, manifest, archive);
+        if result.is_err() {
+            let _ = std::fs::remove_file(archive);
+        }
+        result.with_context(|| format!("Failed to write {}", archive.display()))
+    }
+
+    /// Archives in the reports directory, newest first
+    pub fn list(&self) -> Result<Vec<PathBuf>> {
+        let entries = match std::fs::read_dir(&self.reports_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("Failed to read .wasm-slim/reports"),
+        };
+
+        let mut archives: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+                    n.starts_with(ARCHIVE_PREFIX) && n.ends_with(ARCHIVE_EXTENSION)
+                })
+            })
+            .collect();
+        // Timestamped names sort chronologically once the extension is
+        // dropped (so `-2` suffixes sort after their base name)
+        let stem = |path: &PathBuf| {
+            path.to_string_lossy()
+                .trim_end_matches(ARCHIVE_EXTENSION)
+                .to_string()
+        };
+        archives.sort_by_key(|path| std::cmp::Reverse(stem(path)));
+        Ok(archives)
+    }
+
+    /// Delete all but the newest `keep` archives (0 keeps everything)
+    ///
+    /// Returns the deleted paths.
+    pub fn prune(&self, keep: usize) -> Result<Vec<PathBuf>> {
+        if keep == 0 {
+            return Ok(Vec::new());
+        }
+
+        let stale: Vec<PathBuf> = self.list()?.into_iter().skip(keep).collect();
+        for path in &stale {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Ok(stale)
+    }
+}
+
+/// Format Unix seconds as `YYYYMMDD_HHMMSS` (UTC)
+fn format_archive_stamp(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+
+    // Civil-from-days (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}_{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        (secs_of_day % 3_600) / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Tar the manifest and staged files into `archive`, compressed with zstd
+fn write_tarball(staging: &Path, manifest: &ArchiveManifest, archive: &Path) -> Result<()> {
+    let file = std::fs::File::create(archive)?;
+    let encoder = zstd::Encoder::new(file, ZSTD_LEVEL)?;
+    let mut tarball = tar::Builder::new(encoder);
+    for name in
+        std::iter::once("manifest.json").chain(manifest.files.iter().map(|f| f.name.as_str()))
+    {
+        tarball
+            .append_path_with_name(staging.join(name), name)
+            .with_context(|| format!("Failed to add {}", name))?;
+    }
+    tarball.into_inner()?.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// Standalone HTML rendering of the archived report
+fn render_html(report: &JsonOutput, manifest: &ArchiveManifest) -> String {
+    let mut rows = vec![
+        ("WASM file", manifest.wasm_file.clone()),
+        ("Size", report.size.formatted.clone()),
+        ("Bytes", report.size.bytes.to_string()),
+    ];
+    if let Some(budget) = &report.budget {
+        rows.push(("Budget", format!("{} ({})", budget.status, budget.message)));
+    }
+    if let Some(regression) = &report.regression {
+        rows.push((
+            "Change since last build",
+            format!("{:+.2}%", regression.percent_change),
+        ));
+    }
+    if let Some(commit) = &manifest.commit_hash {
+        rows.push(("Commit", commit.clone()));
+    }
+    if let Some(branch) = &manifest.branch {
+        rows.push(("Branch", branch.clone()));
+    }
+    rows.push(("wasm-slim", manifest.wasm_slim_version.clone()));
+
+    let body: String = rows
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "    <tr><th>{}</th><td>{}</td></tr>\n",
+                escape_html(key),
+                escape_html(value)
+            )
+        })
+        .collect();
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n  <meta charset=\"utf-8\">\n  <title>wasm-slim size report</title>\n  <style>body{{font-family:sans-serif}}th{{text-align:left;padding-right:1em}}</style>\n</head>\n<body>\n  <h1>wasm-slim size report</h1>\n  <table>\n{}  </table>\n</body>\n</html>\n",
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project_with_wasm() -> (TempDir, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let wasm = temp.path().join("app_bg.wasm");
+        std::fs::write(&wasm, vec![0u8; 2048]).unwrap();
+        std::fs::write(
+            temp.path().join(".wasm-slim.toml"),
+            "template = \"balanced\"\n\n[size_budget]\nmax-size-kb = 1\n",
+        )
+        .unwrap();
+        (temp, wasm)
+    }
+
+    #[test]
+    fn test_stage_writes_reports_manifest_and_config_lock() {
+        let (temp, wasm) = project_with_wasm();
+        let archiver = ReportArchiver::new(temp.path());
+        let staging = temp.path().join("staging");
+
+        let manifest = archiver.stage(&wasm, &staging, 0).unwrap();
+
+        assert_eq!(manifest.wasm_size_bytes, 2048);
+        let names: Vec<&str> = manifest.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["report.json", "report.html", "config.lock"]);
+
+        let report = std::fs::read_to_string(staging.join("report.json")).unwrap();
+        assert!(report.contains("over_budget"));
+        let html = std::fs::read_to_string(staging.join("report.html")).unwrap();
+        assert!(html.contains("<td>2.00 KB</td>"));
+        let lock = std::fs::read_to_string(staging.join("config.lock")).unwrap();
+        assert!(lock.contains("balanced"));
+        assert!(staging.join("manifest.json").exists());
+    }
+
+    #[test]
+    fn test_create_writes_archive_and_removes_staging() {
+        let (temp, wasm) = project_with_wasm();
+        let archiver = ReportArchiver::new(temp.path());
+
+        let first = archiver.create(&wasm).unwrap();
+        let second = archiver.create(&wasm).unwrap();
+
+        assert_ne!(first, second);
+        assert!(first.exists() && second.exists());
+        let name = first.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("report-") && name.ends_with(".tar.zst"));
+        assert_eq!(
+            std::fs::read_dir(archiver.reports_dir()).unwrap().count(),
+            2
+        );
+
+        // A zstd-compressed tarball with the manifest first
+        let decoder = zstd::Decoder::new(std::fs::File::open(&first).unwrap()).unwrap();
+        let mut tarball = tar::Archive::new(decoder);
+        let mut names = Vec::new();
+        for entry in tarball.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let name = entry.path().unwrap().to_string_lossy().into_owned();
+            if name == "manifest.json" {
+                let mut manifest = String::new();
+                std::io::Read::read_to_string(&mut entry, &mut manifest).unwrap();
+                assert!(manifest.contains("\"wasm_size_bytes\": 2048"));
+            }
+            names.push(name);
+        }
+        assert_eq!(
+            names,
+            vec!["manifest.json", "report.json", "report.html", "config.lock"]
+        );
+    }
+
+    #[test]
+    fn test_compress_failure_leaves_no_partial_archive() {
+        let (temp, wasm) = project_with_wasm();
+        let archiver = ReportArchiver::new(temp.path());
+        let staging = temp.path().join("staging");
+        let mut manifest = archiver.stage(&wasm, &staging, 0).unwrap();
+        manifest.files.push(ManifestEntry {
+            name: "missing.txt".to_string(),
+            size_bytes: 0,
+        });
+        let archive = temp.path().join("report.tar.zst");
+
+        let err = archiver
+            .compress(&staging, &manifest, &archive)
+            .unwrap_err();
+
+        assert!(format!("{:#}", err).contains("Failed to add missing.txt"));
+        assert!(!archive.exists());
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_archives() {
+        let temp = TempDir::new().unwrap();
+        let archiver = ReportArchiver::new(temp.path());
+        std::fs::create_dir_all(archiver.reports_dir()).unwrap();
+        for stamp in ["20250101_000000", "20250102_000000", "20250103_000000"] {
+            std::fs::write(
+                archiver
+                    .reports_dir()
+                    .join(format!("report-{}.tar.zst", stamp)),
+                b"",
+            )
+            .unwrap();
+        }
+        std::fs::write(archiver.reports_dir().join("notes.txt"), b"").unwrap();
+
+        assert!(archiver.prune(0).unwrap().is_empty());
+        let removed = archiver.prune(2).unwrap();
+
+        assert_eq!(removed.len(), 1);
+        assert!(removed[0].ends_with("report-20250101_000000.tar.zst"));
+        assert_eq!(archiver.list().unwrap().len(), 2);
+        assert!(archiver.reports_dir().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_format_archive_stamp_is_utc_calendar_date() {
+        assert_eq!(format_archive_stamp(0), "19700101_000000");
+        // 2024-02-29 12:34:56 UTC (leap day)
+        assert_eq!(format_archive_stamp(1_709_210_096), "20240229_123456");
+    }
+}
@@ -4,8 +4,15 @@
 //! - Target: Ideal size goal (informational)
 //! - Warning: Threshold for warnings (exit 0 with warning)
 //! - Max: Hard limit (exit 1 if exceeded)
+//!
+//! With a deployment profile, budgets apply to the profile's size metric and
+//! the platform's own limit is enforced like a second max.
 
+use super::deploy::{DeployProfile, PlatformLimit, SizeMetric};
+use super::output::{BudgetInfo, SizeInfo};
 use crate::config::file::SizeBudget;
+use crate::fmt::format_bytes;
+use crate::summary::CompressedSizes;
 use anyhow::Result;
 use console::style;
 
@@ -27,6 +34,8 @@ pub enum BudgetStatus {
 pub struct BudgetResult {
     /// Budget status
     pub status: BudgetStatus,
+    /// Actual size in bytes
+    pub size_bytes: u64,
     /// Actual size in KB
     pub size_kb: f64,
     /// Target size if set
@@ -35,11 +44,23 @@ pub struct BudgetResult {
     pub warn_threshold_kb: Option<u64>,
     /// Maximum allowed size if set
     pub max_size_kb: Option<u64>,
+    /// Metric the size was measured with
+    pub metric: SizeMetric,
+    /// Deployment platform limit, if any
+    pub platform_limit: Option<PlatformLimit>,
+    /// Compressed transfer sizes of the checked bundle, if measured
+    pub compressed: CompressedSizes,
     /// Human-readable message
     pub message: String,
 }
 
 impl BudgetResult {
+    /// Report the bundle's compressed sizes alongside the checked size
+    pub fn with_compressed(mut self, compressed: CompressedSizes) -> Self {
+        self.compressed = compressed;
+        self
+    }
+
     /// Get exit code for CI/CD (0 = pass, 1 = fail, 2 = warning)
     pub fn exit_code(&self) -> i32 {
         match self.status {
@@ -58,17 +79,30 @@ impl BudgetResult {
             BudgetStatus::OverBudget => style("❌").red(),
         };
 
+        let size_text = match self.metric {
+            SizeMetric::Raw => format_bytes(self.size_bytes),
+            metric => format!("{} ({})", format_bytes(self.size_bytes), metric),
+        };
         let size_str = match self.status {
-            BudgetStatus::UnderTarget | BudgetStatus::AboveTarget => {
-                style(format!("{:.2} KB", self.size_kb)).green()
-            }
-            BudgetStatus::Warning => style(format!("{:.2} KB", self.size_kb)).yellow(),
-            BudgetStatus::OverBudget => style(format!("{:.2} KB", self.size_kb)).red(),
+            BudgetStatus::UnderTarget | BudgetStatus::AboveTarget => style(size_text).green(),
+            BudgetStatus::Warning => style(size_text).yellow(),
+            BudgetStatus::OverBudget => style(size_text).red(),
         };
 
         println!("\n{} Size Budget Check: {}", status_icon, size_str);
         println!("   {}", self.message);
 
+        let compressed: Vec<String> = [
+            ("gzip", self.compressed.gzip_bytes),
+            ("brotli", self.compressed.brotli_bytes),
+        ]
+        .into_iter()
+        .filter_map(|(name, bytes)| bytes.map(|b| format!("{}: {}", name, format_bytes(b))))
+        .collect();
+        if !compressed.is_empty() {
+            println!("   {}", style(compressed.join(", ")).dim());
+        }
+
         // Show budget thresholds
         if let Some(target) = self.target_kb {
             let target_str = if self.size_kb <= target as f64 {
@@ -98,24 +132,114 @@ impl BudgetResult {
             };
             println!("   {}", max_str);
         }
+
+        if let Some(limit) = &self.platform_limit {
+            let limit_text = format!(
+                "{} limit: {} KB ({})",
+                limit.platform, limit.limit_kb, limit.metric
+            );
+            let limit_str = if self.size_kb > limit.limit_kb as f64 {
+                style(format!("{} (EXCEEDED)", limit_text)).red()
+            } else {
+                style(limit_text).dim()
+            };
+            println!("   {}", limit_str);
+        }
+    }
+}
+
+/// Outcome of evaluating a size against a budget
+///
+/// Bundles the result the CLI prints with its JSON form and exit code.
+#[derive(Debug, Clone)]
+pub struct BudgetOutcome {
+    /// Status, thresholds and message
+    pub result: BudgetResult,
+    /// The result as reported in `--json` output
+    pub info: BudgetInfo,
+    /// Exit code the CLI exits with (see [`BudgetResult::exit_code`])
+    pub exit_code: i32,
+}
+
+impl BudgetOutcome {
+    /// Budget status
+    pub fn status(&self) -> BudgetStatus {
+        self.result.status
+    }
+
+    /// Whether the size passes (is not over budget)
+    pub fn passed(&self) -> bool {
+        self.info.passed
+    }
+
+    /// Human-readable message, as printed by the CLI
+    pub fn message(&self) -> &str {
+        &self.result.message
+    }
+}
+
+impl From<BudgetResult> for BudgetOutcome {
+    fn from(result: BudgetResult) -> Self {
+        Self {
+            info: BudgetInfo::from_result(&result),
+            exit_code: result.exit_code(),
+            result,
+        }
     }
 }
 
 /// Size budget checker
 pub struct BudgetChecker {
     budget: SizeBudget,
+    metric: SizeMetric,
+    platform_limit: Option<PlatformLimit>,
 }
 
 impl BudgetChecker {
     /// Create a new budget checker
     pub fn new(budget: SizeBudget) -> Self {
-        Self { budget }
+        Self {
+            budget,
+            metric: SizeMetric::Raw,
+            platform_limit: None,
+        }
+    }
+
+    /// Apply a deployment profile: its metric and platform limit
+    ///
+    /// Sizes passed to [`check`](Self::check) must be measured with the
+    /// profile's metric.
+    pub fn with_deploy_profile(mut self, profile: &DeployProfile) -> Self {
+        self.metric = profile.metric;
+        self.platform_limit = profile.limit;
+        self
+    }
+
+    /// Set the metric checked sizes were measured with
+    ///
+    /// Used when the profile's estimator could not measure the bundle; a raw
+    /// size is still checked against compressed platform limits as an upper
+    /// bound.
+    pub fn with_metric(mut self, metric: SizeMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Platform limit exceeded by `size_kb`, if any
+    fn exceeded_platform_limit(&self, size_kb: f64) -> Option<&PlatformLimit> {
+        self.platform_limit
+            .as_ref()
+            .filter(|limit| size_kb > limit.limit_kb as f64)
     }
 
     /// Determine budget status based on size and thresholds
     ///
-    /// Priority order: max > warn > target
+    /// Priority order: platform limit > max > warn > target
     fn determine_status(&self, size_kb: f64) -> BudgetStatus {
+        if self.exceeded_platform_limit(size_kb).is_some() {
+            return BudgetStatus::OverBudget;
+        }
+
         // Check max threshold first (highest priority)
         if let Some(max) = self.budget.max_size_kb {
             if size_kb > max as f64 {
@@ -160,6 +284,7 @@ impl BudgetChecker {
     ///     target_size_kb: Some(500),
     ///     warn_threshold_kb: Some(750),
     ///     max_size_kb: Some(1000),
+    ///     preopt_factor: None,
     /// };
     /// let checker = BudgetChecker::new(budget);
     /// let result = checker.check(600 * 1024)?; // 600 KB
@@ -167,6 +292,37 @@ impl BudgetChecker {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn check(&self, size_bytes: u64) -> Result<BudgetResult> {
+        Ok(self.result_for(size_bytes))
+    }
+
+    /// Evaluate an externally measured size against a budget
+    ///
+    /// Applies the same rules and messages as `wasm-slim build` without
+    /// building or reading a config, for dashboards and custom CI scripts.
+    /// The budget is not validated; call [`SizeBudget::validate`] first for
+    /// user-supplied budgets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_slim::cicd::{BudgetChecker, BudgetStatus, SizeInfo};
+    /// use wasm_slim::config::file::SizeBudget;
+    ///
+    /// let budget = SizeBudget {
+    ///     max_size_kb: Some(1000),
+    ///     ..Default::default()
+    /// };
+    /// let outcome = BudgetChecker::evaluate(&SizeInfo::new(1200 * 1024), &budget);
+    /// assert_eq!(outcome.status(), BudgetStatus::OverBudget);
+    /// assert_eq!(outcome.exit_code, 1);
+    /// assert_eq!(outcome.message(), "FAILED: 200 KB over budget (optimization required)");
+    /// ```
+    pub fn evaluate(size_info: &SizeInfo, budget: &SizeBudget) -> BudgetOutcome {
+        Self::new(budget.clone()).result_for(size_info.bytes).into()
+    }
+
+    /// Budget result for a size in bytes
+    fn result_for(&self, size_bytes: u64) -> BudgetResult {
         let size_kb = size_bytes as f64 / 1024.0;
 
         // Determine status using priority order: max > warn > target
@@ -202,7 +358,15 @@ impl BudgetChecker {
                 }
             }
             BudgetStatus::OverBudget => {
-                if let Some(max) = self.budget.max_size_kb {
+                if let Some(limit) = self.exceeded_platform_limit(size_kb) {
+                    format!(
+                        "FAILED: {} KB over the {} limit of {} KB ({})",
+                        (size_kb - limit.limit_kb as f64) as i64,
+                        limit.platform,
+                        limit.limit_kb,
+                        limit.metric
+                    )
+                } else if let Some(max) = self.budget.max_size_kb {
                     let over_by = size_kb - max as f64;
                     format!(
                         "FAILED: {} KB over budget (optimization required)",
@@ -214,14 +378,18 @@ impl BudgetChecker {
             }
         };
 
-        Ok(BudgetResult {
+        BudgetResult {
             status,
+            size_bytes,
             size_kb,
             target_kb: self.budget.target_size_kb,
             warn_threshold_kb: self.budget.warn_threshold_kb,
             max_size_kb: self.budget.max_size_kb,
+            metric: self.metric,
+            platform_limit: self.platform_limit,
+            compressed: CompressedSizes::default(),
             message,
-        })
+        }
     }
 }
 
@@ -235,6 +403,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(400 * 1024).unwrap(); // 400 KB
@@ -248,6 +417,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(600 * 1024).unwrap(); // 600 KB
@@ -261,6 +431,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(900 * 1024).unwrap(); // 900 KB
@@ -274,6 +445,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(1100 * 1024).unwrap(); // 1100 KB
@@ -287,6 +459,7 @@ mod tests {
             max_size_kb: None,
             warn_threshold_kb: None,
             target_size_kb: None,
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(5000 * 1024).unwrap(); // 5 MB
@@ -300,6 +473,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: None,
             target_size_kb: None,
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
 
@@ -320,6 +494,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(400 * 1024).unwrap();
@@ -336,6 +511,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(600 * 1024).unwrap();
@@ -351,6 +527,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(900 * 1024).unwrap();
@@ -367,6 +544,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(1100 * 1024).unwrap();
@@ -383,6 +561,7 @@ mod tests {
             max_size_kb: None,
             warn_threshold_kb: None,
             target_size_kb: None,
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(5000 * 1024).unwrap();
@@ -396,6 +575,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(600 * 1024).unwrap();
@@ -408,12 +588,39 @@ mod tests {
         assert!(!result.message.is_empty());
     }
 
+    #[test]
+    fn test_check_budget_enforces_platform_limit_from_deploy_profile() {
+        use crate::config::file::{DeployPlan, DeploySettings, DeployTarget};
+
+        let profile = DeployProfile::from_settings(&DeploySettings {
+            target: DeployTarget::CloudflareWorkers,
+            plan: DeployPlan::Free,
+        });
+        let budget = SizeBudget {
+            warn_threshold_kb: Some(2048),
+            ..Default::default()
+        };
+        let checker = BudgetChecker::new(budget).with_deploy_profile(&profile);
+
+        let within = checker.check(2500 * 1024).unwrap();
+        assert_eq!(within.status, BudgetStatus::Warning);
+        assert_eq!(within.metric, SizeMetric::Gzip);
+
+        let over = checker.check(3100 * 1024).unwrap();
+        assert_eq!(over.status, BudgetStatus::OverBudget);
+        assert_eq!(over.exit_code(), 1);
+        assert!(over
+            .message
+            .contains("Cloudflare Workers (free) limit of 3072 KB (gzip)"));
+    }
+
     #[test]
     fn test_check_budget_exactly_at_target_returns_success() {
         let budget = SizeBudget {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(500 * 1024).unwrap();
@@ -429,6 +636,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(800 * 1024).unwrap();
@@ -443,6 +651,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(1000 * 1024).unwrap();
@@ -458,6 +667,7 @@ mod tests {
             max_size_kb: None,
             warn_threshold_kb: None,
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
 
@@ -472,6 +682,27 @@ mod tests {
         assert!(result_over.message.contains("Above target"));
     }
 
+    #[test]
+    fn test_evaluate_matches_check_and_json_info() {
+        let budget = SizeBudget {
+            max_size_kb: Some(1000),
+            warn_threshold_kb: Some(800),
+            target_size_kb: Some(500),
+            preopt_factor: None,
+        };
+        let checked = BudgetChecker::new(budget.clone())
+            .check(900 * 1024)
+            .unwrap();
+
+        let outcome = BudgetChecker::evaluate(&SizeInfo::new(900 * 1024), &budget);
+        assert_eq!(outcome.status(), BudgetStatus::Warning);
+        assert_eq!(outcome.message(), checked.message);
+        assert_eq!(outcome.exit_code, 0);
+        assert!(outcome.passed());
+        assert_eq!(outcome.info.status, "warning");
+        assert_eq!(outcome.info.delta_kb, Some(-100.0));
+    }
+
     // Property-based tests using proptest
     use proptest::prelude::*;
 
@@ -491,9 +722,10 @@ mod tests {
                 target_size_kb: Some(target),
                 warn_threshold_kb: Some(warn),
                 max_size_kb: Some(max),
+                preopt_factor: None,
             };
 
-            let checker = BudgetChecker { budget };
+            let checker = BudgetChecker::new(budget);
             let size_bytes = size_kb * 1024;
 
             let result = checker.check(size_bytes).unwrap();
@@ -524,9 +756,10 @@ mod tests {
                 target_size_kb: Some(max / 2),
                 warn_threshold_kb: Some(max * 3 / 4),
                 max_size_kb: Some(max),
+                preopt_factor: None,
             };
 
-            let checker = BudgetChecker { budget };
+            let checker = BudgetChecker::new(budget);
             let result = checker.check(size_kb * 1024).unwrap();
 
             // Exit code rules
@@ -548,9 +781,10 @@ mod tests {
                 target_size_kb: Some(1000),
                 warn_threshold_kb: Some(2000),
                 max_size_kb: Some(3000),
+                preopt_factor: None,
             };
 
-            let checker = BudgetChecker { budget };
+            let checker = BudgetChecker::new(budget);
             let result = checker.check(size_bytes).unwrap();
 
             let expected_kb = size_bytes as f64 / 1024.0;
@@ -571,9 +805,10 @@ mod tests {
                 target_size_kb: if has_target { Some(500) } else { None },
                 warn_threshold_kb: if has_warn { Some(1000) } else { None },
                 max_size_kb: if has_max { Some(2000) } else { None },
+                preopt_factor: None,
             };
 
-            let checker = BudgetChecker { budget };
+            let checker = BudgetChecker::new(budget);
             let result = checker.check(size_kb * 1024);
 
             // Should never panic, always return Ok
@@ -594,9 +829,10 @@ mod tests {
                 target_size_kb: Some(target),
                 warn_threshold_kb: Some(warn),
                 max_size_kb: Some(max),
+                preopt_factor: None,
             };
 
-            let checker = BudgetChecker { budget };
+            let checker = BudgetChecker::new(budget);
             let result = checker.check(size_kb * 1024).unwrap();
 
             // Verify reported thresholds match input
@@ -615,9 +851,10 @@ mod tests {
                 target_size_kb: Some(max / 2),
                 warn_threshold_kb: Some(max * 3 / 4),
                 max_size_kb: Some(max),
+                preopt_factor: None,
             };
 
-            let checker = BudgetChecker { budget };
+            let checker = BudgetChecker::new(budget);
             let result = checker.check(size_kb * 1024).unwrap();
 
             prop_assert!(!result.message.is_empty(), "Message should never be empty");
@@ -637,9 +874,10 @@ mod tests {
                 target_size_kb: Some(target),
                 warn_threshold_kb: Some(warn),
                 max_size_kb: Some(max),
+                preopt_factor: None,
             };
 
-            let checker = BudgetChecker { budget };
+            let checker = BudgetChecker::new(budget);
             let result = checker.check(0).unwrap();
 
             prop_assert_eq!(result.status, BudgetStatus::UnderTarget,
@@ -0,0 +1,137 @@
+//! Accidental debug build detection for budget checks
+//!
+//! A debug artifact (or a release build with `debug = true`) is several times
+//! larger than usual because of its DWARF sections. Failing it with a plain
+//! "over budget" error sends users hunting for a dependency that grew; this
+//! check recognizes the case and points at the build profile instead.
+
+use crate::fmt::format_bytes;
+use anyhow::{Context, Result};
+use std::path::Path;
+use wasmparser::{Parser, Payload};
+
+/// Size, relative to the historical median, above which a build is suspect
+pub const DEBUG_BUILD_RATIO: f64 = 3.0;
+
+/// An artifact that looks like a debug build
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugBuildSuspicion {
+    /// Artifact size
+    pub size_bytes: u64,
+    /// Median size of the builds in history
+    pub median_bytes: u64,
+    /// DWARF sections and their sizes
+    pub debug_sections: Vec<(String, u64)>,
+}
+
+impl DebugBuildSuspicion {
+    /// How many times larger than the median the artifact is
+    pub fn ratio(&self) -> f64 {
+        self.size_bytes as f64 / self.median_bytes.max(1) as f64
+    }
+
+    /// Total size of the DWARF sections
+    pub fn debug_bytes(&self) -> u64 {
+        self.debug_sections.iter().map(|(_, size)| size).sum()
+    }
+
+    /// Error message with remediation steps
+    pub fn message(&self, wasm_file: &Path) -> String {
+        let names: Vec<&str> = self
+            .debug_sections
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        format!(
+            "{} looks like a debug build, not a budget regression: it is {} ({:.1}× the \
+             historical median of {}) and {} of it is DWARF debug info ({})\n\n\
+             To fix:\n  \
+             1. Build in release mode (`cargo build --release`, `wasm-pack build --release`)\n  \
+             2. Remove `debug = true` / `strip = false` from [profile.release] in Cargo.toml\n  \
+             3. Unset CARGO_PROFILE_RELEASE_DEBUG if your CI environment sets it\n  \
+             4. To keep debug info for DevTools, use `wasm-slim build --split-debug`",
+            wasm_file.display(),
+            format_bytes(self.size_bytes),
+            self.ratio(),
+            format_bytes(self.median_bytes),
+            format_bytes(self.debug_bytes()),
+            names.join(", ")
+        )
+    }
+}
+
+/// DWARF custom sections of a module and their sizes
+pub fn debug_sections(module: &[u8]) -> Result<Vec<(String, u64)>> {
+    let mut sections = Vec::new();
+    for payload in Parser::new(0).parse_all(module) {
+        if let Payload::CustomSection(reader) = payload? {
+            if reader.name().starts_with(".debug_") {
+                sections.push((reader.name().to_string(), reader.range().len() as u64));
+            }
+        }
+    }
+    Ok(sections)
+}
+
+/// Flag an artifact over [`DEBUG_BUILD_RATIO`] × `median_bytes` that carries DWARF
+pub fn detect(module: &[u8], median_bytes: Option<u64>) -> Result<Option<DebugBuildSuspicion>> {
+    let size_bytes = module.len() as u64;
+    let Some(median_bytes) = median_bytes.filter(|&m| m > 0) else {
+        return Ok(None);
+    };
+    if (size_bytes as f64) <= median_bytes as f64 * DEBUG_BUILD_RATIO {
+        return Ok(None);
+    }
+
+    let debug_sections = debug_sections(module)?;
+    if debug_sections.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(DebugBuildSuspicion {
+        size_bytes,
+        median_bytes,
+        debug_sections,
+    }))
+}
+
+/// Read `wasm_file` and flag it if it looks like a debug build
+pub fn detect_file(
+    wasm_file: &Path,
+    median_bytes: Option<u64>,
+) -> Result<Option<DebugBuildSuspicion>> {
+    let module = std::fs::read(wasm_file)
+        .with_context(|| format!("Failed to read {}", wasm_file.display()))?;
+    detect(&module, median_bytes)
+        .with_context(|| format!("Failed to parse {}", wasm_file.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::{custom_section, HEADER};
+
+    fn module_with_custom(name: &str, payload_len: usize) -> Vec<u8> {
+        let mut module = HEADER.to_vec();
+        module.extend(custom_section(name, &vec![0; payload_len]));
+        module
+    }
+
+    #[test]
+    fn test_detect_requires_both_size_jump_and_dwarf() {
+        let debug = module_with_custom(".debug_info", 4000);
+        let suspicion = detect(&debug, Some(1000)).unwrap().unwrap();
+        assert_eq!(suspicion.debug_sections[0].0, ".debug_info");
+        assert!(suspicion.ratio() > DEBUG_BUILD_RATIO);
+        assert!(suspicion
+            .message(Path::new("app_bg.wasm"))
+            .contains("looks like a debug build"));
+
+        // Within the usual size range
+        assert!(detect(&debug, Some(2000)).unwrap().is_none());
+        // Large, but no DWARF: a genuine regression
+        let big = module_with_custom("producers", 4000);
+        assert!(detect(&big, Some(1000)).unwrap().is_none());
+        // No history to compare against
+        assert!(detect(&debug, None).unwrap().is_none());
+    }
+}
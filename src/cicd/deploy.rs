@@ -0,0 +1,213 @@
+//! Deployment target profiles for size budgets
+//!
+//! Platforms account for bundle size differently: Cloudflare Workers limits
+//! the compressed script, Fastly Compute the compressed package, and browsers
+//! have no hard limit at all. A profile picks the size metric budgets apply
+//! to and the platform limit the [`BudgetChecker`](super::BudgetChecker)
+//! enforces on top of the configured budget.
+
+use crate::config::file::{DeployPlan, DeploySettings, DeployTarget};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Size metric a budget applies to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeMetric {
+    /// Uncompressed WASM size
+    #[default]
+    Raw,
+    /// gzip compressed size (level 9)
+    Gzip,
+    /// brotli compressed size (quality 11)
+    Brotli,
+}
+
+impl fmt::Display for SizeMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeMetric::Raw => write!(f, "raw"),
+            SizeMetric::Gzip => write!(f, "gzip"),
+            SizeMetric::Brotli => write!(f, "brotli"),
+        }
+    }
+}
+
+/// Estimates the size of a WASM file under one metric
+pub trait CompressionEstimator {
+    /// Metric this estimator produces
+    fn metric(&self) -> SizeMetric;
+
+    /// Size of the file under this metric, or `None` if it cannot be measured
+    fn estimate(&self, wasm_file: &Path) -> Option<u64>;
+}
+
+/// Uncompressed file size
+pub struct RawSizeEstimator;
+
+impl CompressionEstimator for RawSizeEstimator {
+    fn metric(&self) -> SizeMetric {
+        SizeMetric::Raw
+    }
+
+    fn estimate(&self, wasm_file: &Path) -> Option<u64> {
+        std::fs::metadata(wasm_file).ok().map(|m| m.len())
+    }
+}
+
+/// Compressed size under gzip at level 9
+pub struct GzipEstimator;
+
+impl CompressionEstimator for GzipEstimator {
+    fn metric(&self) -> SizeMetric {
+        SizeMetric::Gzip
+    }
+
+    fn estimate(&self, wasm_file: &Path) -> Option<u64> {
+        crate::summary::gzip_size(wasm_file)
+    }
+}
+
+/// Compressed size under brotli at quality 11
+pub struct BrotliEstimator;
+
+impl CompressionEstimator for BrotliEstimator {
+    fn metric(&self) -> SizeMetric {
+        SizeMetric::Brotli
+    }
+
+    fn estimate(&self, wasm_file: &Path) -> Option<u64> {
+        crate::summary::brotli_size(wasm_file)
+    }
+}
+
+/// Size limit enforced by a deployment platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PlatformLimit {
+    /// Platform name for messages
+    pub platform: &'static str,
+    /// Limit in KB
+    pub limit_kb: u64,
+    /// Metric the limit applies to
+    pub metric: SizeMetric,
+}
+
+/// Size accounting of a deployment target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeployProfile {
+    /// Platform the bundle is deployed to
+    pub target: DeployTarget,
+    /// Metric budgets apply to
+    pub metric: SizeMetric,
+    /// Platform size limit, if any
+    pub limit: Option<PlatformLimit>,
+}
+
+impl DeployProfile {
+    /// Resolve the profile for a `[deploy]` section
+    pub fn from_settings(settings: &DeploySettings) -> Self {
+        let (metric, limit) = match (settings.target, settings.plan) {
+            // Workers limit the compressed script size
+            (DeployTarget::CloudflareWorkers, DeployPlan::Free) => (
+                SizeMetric::Gzip,
+                Some(("Cloudflare Workers (free)", 3 * 1024)),
+            ),
+            (DeployTarget::CloudflareWorkers, DeployPlan::Paid) => (
+                SizeMetric::Gzip,
+                Some(("Cloudflare Workers (paid)", 10 * 1024)),
+            ),
+            // Compute limits the compressed package, which the module dominates
+            (DeployTarget::Fastly, _) => (SizeMetric::Gzip, Some(("Fastly Compute", 100 * 1024))),
+            (DeployTarget::Browser, _) => (SizeMetric::Raw, None),
+        };
+
+        Self {
+            target: settings.target,
+            metric,
+            limit: limit.map(|(platform, limit_kb)| PlatformLimit {
+                platform,
+                limit_kb,
+                metric,
+            }),
+        }
+    }
+
+    /// Estimator for this profile's metric
+    pub fn estimator(&self) -> Box<dyn CompressionEstimator> {
+        match self.metric {
+            SizeMetric::Raw => Box::new(RawSizeEstimator),
+            SizeMetric::Gzip => Box::new(GzipEstimator),
+            SizeMetric::Brotli => Box::new(BrotliEstimator),
+        }
+    }
+
+    /// Size of the bundle under this profile's metric
+    pub fn measure(&self, wasm_file: Option<&Path>, raw_bytes: u64) -> (u64, SizeMetric) {
+        measure_size(self.estimator().as_ref(), wasm_file, raw_bytes)
+    }
+}
+
+/// Size of a bundle as measured by `estimator`
+///
+/// Falls back to the raw size (an upper bound for compressed sizes) when
+/// the estimator cannot measure the file, e.g. because gzip is missing.
+pub fn measure_size(
+    estimator: &dyn CompressionEstimator,
+    wasm_file: Option<&Path>,
+    raw_bytes: u64,
+) -> (u64, SizeMetric) {
+    match wasm_file.and_then(|file| estimator.estimate(file)) {
+        Some(bytes) => (bytes, estimator.metric()),
+        None => (raw_bytes, SizeMetric::Raw),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_pick_metric_and_platform_limit() {
+        let workers = DeployProfile::from_settings(&DeploySettings {
+            target: DeployTarget::CloudflareWorkers,
+            plan: DeployPlan::Free,
+        });
+        assert_eq!(workers.metric, SizeMetric::Gzip);
+        assert_eq!(workers.limit.unwrap().limit_kb, 3 * 1024);
+
+        let paid = DeployProfile::from_settings(&DeploySettings {
+            target: DeployTarget::CloudflareWorkers,
+            plan: DeployPlan::Paid,
+        });
+        assert_eq!(paid.limit.unwrap().limit_kb, 10 * 1024);
+
+        let browser = DeployProfile::from_settings(&DeploySettings {
+            target: DeployTarget::Browser,
+            plan: DeployPlan::Free,
+        });
+        assert_eq!(browser.metric, SizeMetric::Raw);
+        assert!(browser.limit.is_none());
+    }
+
+    #[test]
+    fn test_measure_falls_back_to_raw_size() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("app_bg.wasm");
+        std::fs::write(&file, vec![0u8; 1000]).unwrap();
+
+        let (gzip_bytes, metric) = measure_size(&GzipEstimator, Some(&file), 1000);
+        assert_eq!(metric, SizeMetric::Gzip);
+        assert!(gzip_bytes < 1000);
+
+        let missing = temp_dir.path().join("missing.wasm");
+        assert_eq!(
+            measure_size(&GzipEstimator, Some(&missing), 1000),
+            (1000, SizeMetric::Raw)
+        );
+        assert_eq!(
+            measure_size(&GzipEstimator, None, 1000),
+            (1000, SizeMetric::Raw)
+        );
+    }
+}
@@ -1,35 +1,37 @@
 //! Display formatting for CI/CD results
 
+use super::features::FeatureDrift;
 use super::history::RegressionResult;
+use crate::fmt::format_bytes;
 use console::style;
 
 /// Print regression result to console with formatted output
 pub fn print_regression(result: &RegressionResult) {
     if result.is_regression {
         println!("\n{} Size Regression Detected!", style("⚠️").red());
+        println!("   Previous: {}", format_bytes(result.previous_size));
         println!(
-            "   Previous: {:.2} KB",
-            result.previous_size as f64 / 1024.0
+            "   Current:  {} ({})",
+            format_bytes(result.current_size),
+            style(format!("+{:.1}%", result.percent_change)).red()
         );
         println!(
-            "   Current:  {:.2} KB ({})",
-            result.current_size as f64 / 1024.0,
-            style(format!("+{:.1}%", result.percent_change)).red()
+            "   Increase: {}",
+            format_bytes(result.size_diff.unsigned_abs())
         );
-        println!("   Increase: {:.2} KB", result.size_diff as f64 / 1024.0);
     } else if result.percent_change < -1.0 {
         // Size reduction
         println!("\n{} Size Improvement!", style("✨").green());
+        println!("   Previous: {}", format_bytes(result.previous_size));
         println!(
-            "   Previous: {:.2} KB",
-            result.previous_size as f64 / 1024.0
+            "   Current:  {} ({})",
+            format_bytes(result.current_size),
+            style(format!("{:.1}%", result.percent_change)).green()
         );
         println!(
-            "   Current:  {:.2} KB ({})",
-            result.current_size as f64 / 1024.0,
-            style(format!("{:.1}%", result.percent_change)).green()
+            "   Reduction: {}",
+            format_bytes(result.size_diff.unsigned_abs())
         );
-        println!("   Reduction: {:.2} KB", -result.size_diff as f64 / 1024.0);
     } else {
         // No significant change
         println!(
@@ -38,6 +40,23 @@ pub fn print_regression(result: &RegressionResult) {
             style(format!("{:+.1}%", result.percent_change)).dim()
         );
     }
+
+    if let Some(drift) = &result.feature_drift {
+        print_feature_drift(drift);
+    }
+}
+
+/// Warn that a size difference may come from features rather than code
+pub fn print_feature_drift(drift: &FeatureDrift) {
+    println!(
+        "\n{} Resolved features differ between the builds: {}",
+        style("⚠️").yellow(),
+        drift
+    );
+    println!(
+        "   {}",
+        style("The size difference may come from features rather than code changes").dim()
+    );
 }
 
 #[cfg(test)]
@@ -52,6 +71,7 @@ mod tests {
             current_size: 110_000,
             size_diff: 10_000,
             percent_change: 10.0,
+            feature_drift: None,
         };
 
         // Should not panic
@@ -66,6 +86,7 @@ mod tests {
             current_size: 90_000,
             size_diff: -10_000,
             percent_change: -10.0,
+            feature_drift: None,
         };
 
         // Should not panic
@@ -80,6 +101,7 @@ mod tests {
             current_size: 100_500,
             size_diff: 500,
             percent_change: 0.5,
+            feature_drift: None,
         };
 
         // Should not panic
@@ -95,6 +117,7 @@ mod tests {
             current_size: 99_000,
             size_diff: -1_000,
             percent_change: -1.0,
+            feature_drift: None,
         };
 
         print_regression(&result);
@@ -109,6 +132,7 @@ mod tests {
             current_size: 98_900,
             size_diff: -1_100,
             percent_change: -1.1,
+            feature_drift: None,
         };
 
         print_regression(&result);
@@ -122,6 +146,7 @@ mod tests {
             current_size: 100_000,
             size_diff: 0,
             percent_change: 0.0,
+            feature_drift: None,
         };
 
         print_regression(&result);
@@ -135,6 +160,7 @@ mod tests {
             current_size: 11_000_000,
             size_diff: 1_000_000,
             percent_change: 10.0,
+            feature_drift: None,
         };
 
         print_regression(&result);
@@ -148,6 +174,7 @@ mod tests {
             current_size: 900,
             size_diff: -100,
             percent_change: -10.0,
+            feature_drift: None,
         };
 
         print_regression(&result);
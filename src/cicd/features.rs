@@ -0,0 +1,163 @@
+//! Resolved feature sets for feature-flag drift detection
+//!
+//! Local builds and CI often resolve different features (a developer enables
+//! `debug-ui`, CI builds with `--no-default-features`, a lockfile update pulls
+//! a dependency with new default features). Each build record stores the
+//! feature set cargo resolved for the WASM target, so size differences that
+//! coincide with feature differences can be flagged instead of being blamed
+//! on code changes.
+
+use anyhow::{Context, Result};
+use cargo_metadata::{Metadata, MetadataCommand};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+/// Target the feature set is resolved for
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Enabled features per package, as resolved by cargo
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FeatureSet {
+    packages: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl FeatureSet {
+    /// Resolve the feature set of a project for the WASM target
+    pub fn resolve(project_root: &Path) -> Result<Self> {
+        let metadata = MetadataCommand::new()
+            .current_dir(project_root)
+            .other_options(vec![
+                "--filter-platform".to_string(),
+                WASM_TARGET.to_string(),
+            ])
+            .exec()
+            .context("Failed to run cargo metadata")?;
+        Ok(Self::from_metadata(&metadata))
+    }
+
+    /// Collect enabled features from a resolved dependency graph
+    ///
+    /// Packages without enabled features are omitted.
+    pub(crate) fn from_metadata(metadata: &Metadata) -> Self {
+        let names: BTreeMap<_, _> = metadata
+            .packages
+            .iter()
+            .map(|p| (&p.id, p.name.as_str()))
+            .collect();
+
+        let mut packages: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for node in metadata.resolve.iter().flat_map(|r| &r.nodes) {
+            if node.features.is_empty() {
+                continue;
+            }
+            let Some(name) = names.get(&node.id) else {
+                continue;
+            };
+            packages
+                .entry(name.to_string())
+                .or_default()
+                .extend(node.features.iter().map(|f| f.to_string()));
+        }
+        Self { packages }
+    }
+
+    /// Enable a feature (mainly for tests and tooling)
+    pub fn insert(&mut self, package: &str, feature: &str) {
+        self.packages
+            .entry(package.to_string())
+            .or_default()
+            .insert(feature.to_string());
+    }
+
+    /// True if no package has enabled features
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    /// Features enabled in `other` but not here, and the reverse
+    pub fn drift_to(&self, other: &FeatureSet) -> FeatureDrift {
+        FeatureDrift {
+            added: Self::missing_from(&other.packages, &self.packages),
+            removed: Self::missing_from(&self.packages, &other.packages),
+        }
+    }
+
+    /// `package/feature` entries of `a` that are not in `b`
+    fn missing_from(
+        a: &BTreeMap<String, BTreeSet<String>>,
+        b: &BTreeMap<String, BTreeSet<String>>,
+    ) -> Vec<String> {
+        a.iter()
+            .flat_map(|(package, features)| {
+                features
+                    .iter()
+                    .filter(move |f| !b.get(package).is_some_and(|set| set.contains(*f)))
+                    .map(move |f| format!("{}/{}", package, f))
+            })
+            .collect()
+    }
+}
+
+/// Difference between two resolved feature sets
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureDrift {
+    /// `package/feature` entries enabled only in the newer build
+    pub added: Vec<String>,
+    /// `package/feature` entries enabled only in the older build
+    pub removed: Vec<String>,
+}
+
+impl FeatureDrift {
+    /// True if both builds resolved the same features
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl fmt::Display for FeatureDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<String> = self
+            .added
+            .iter()
+            .map(|e| format!("+{}", e))
+            .chain(self.removed.iter().map(|e| format!("-{}", e)))
+            .collect();
+        write!(f, "{}", entries.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(entries: &[(&str, &str)]) -> FeatureSet {
+        let mut set = FeatureSet::default();
+        for (package, feature) in entries {
+            set.insert(package, feature);
+        }
+        set
+    }
+
+    #[test]
+    fn test_drift_to_lists_added_and_removed_features() {
+        let local = features(&[("app", "debug-ui"), ("serde", "std"), ("regex", "unicode")]);
+        let ci = features(&[("serde", "std"), ("serde", "derive"), ("regex", "unicode")]);
+
+        let drift = local.drift_to(&ci);
+        assert_eq!(drift.added, vec!["serde/derive"]);
+        assert_eq!(drift.removed, vec!["app/debug-ui"]);
+        assert_eq!(drift.to_string(), "+serde/derive, -app/debug-ui");
+        assert!(local.drift_to(&local.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_feature_set_serializes_as_map() {
+        let set = features(&[("serde", "std"), ("serde", "derive")]);
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(json, r#"{"serde":["derive","std"]}"#);
+        assert_eq!(serde_json::from_str::<FeatureSet>(&json).unwrap(), set);
+    }
+}
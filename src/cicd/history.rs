@@ -4,13 +4,33 @@
 //! - Detect size regressions (>5% increase)
 //! - Track optimization progress over time
 //! - Provide historical context for CI/CD
+//! - Flag size changes that coincide with feature-set drift
+//!
+//! Builds older than the retention window are compacted into one rollup
+//! record per day. Automatic compaction runs at most once a day and rolls up
+//! a bounded number of days per run; each run is idempotent, so an
+//! interrupted or partial compaction simply continues on the next run.
 
+use super::features::{FeatureDrift, FeatureSet};
+use crate::config::HistorySettings;
 use crate::git::GitRepository;
 use crate::infra::{FileSystem, RealFileSystem};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Default number of days kept at full resolution
+pub const DEFAULT_KEEP_DAYS: u64 = 90;
+
+/// Default number of days rolled up by one automatic compaction
+pub const DEFAULT_DAYS_PER_RUN: usize = 30;
+
+/// Minimum time between two automatic compactions
+pub const AUTO_COMPACT_INTERVAL_SECS: u64 = SECS_PER_DAY;
+
 /// Single build record in history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildRecord {
@@ -24,6 +44,25 @@ pub struct BuildRecord {
     /// Git branch (if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branch: Option<String>,
+    /// Features cargo resolved for the WASM target (if recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<FeatureSet>,
+    /// Builds of the day this record summarizes (compacted records only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollup: Option<DailyRollup>,
+}
+
+/// Summary of the builds of one day, kept on a compacted record
+///
+/// The record itself keeps the day's last build; the rollup keeps the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyRollup {
+    /// Number of builds rolled up
+    pub builds: u32,
+    /// Smallest build of the day in bytes
+    pub min_bytes: u64,
+    /// Largest build of the day in bytes
+    pub max_bytes: u64,
 }
 
 impl BuildRecord {
@@ -34,8 +73,92 @@ impl BuildRecord {
             size_bytes,
             commit_hash: GitRepository::new().get_commit_hash().ok().flatten(),
             branch: GitRepository::new().get_branch_name().ok().flatten(),
+            features: None,
+            rollup: None,
+        })
+    }
+
+    /// Attach the resolved feature set of the build
+    pub fn with_features(mut self, features: FeatureSet) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    /// Day number (days since the UNIX epoch) of the build
+    fn day(&self) -> Option<u64> {
+        parse_timestamp(&self.timestamp).map(|secs| secs / SECS_PER_DAY)
+    }
+
+    /// Builds summarized by this record, as a rollup
+    fn as_rollup(&self) -> DailyRollup {
+        self.rollup.unwrap_or(DailyRollup {
+            builds: 1,
+            min_bytes: self.size_bytes,
+            max_bytes: self.size_bytes,
         })
     }
+
+    /// Fold an older build of the same day into this record
+    fn absorb(&mut self, older: &BuildRecord) {
+        let (newer, older) = (self.as_rollup(), older.as_rollup());
+        self.rollup = Some(DailyRollup {
+            builds: newer.builds.saturating_add(older.builds),
+            min_bytes: newer.min_bytes.min(older.min_bytes),
+            max_bytes: newer.max_bytes.max(older.max_bytes),
+        });
+    }
+}
+
+/// Retention policy for history compaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionPolicy {
+    /// Days of builds kept individually
+    pub keep_days: u64,
+    /// Whether builds trigger compaction automatically
+    pub auto_compact: bool,
+    /// Days rolled up per automatic compaction
+    pub days_per_run: usize,
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_days: DEFAULT_KEEP_DAYS,
+            auto_compact: true,
+            days_per_run: DEFAULT_DAYS_PER_RUN,
+        }
+    }
+}
+
+impl CompactionPolicy {
+    /// Policy from the `[history]` section of `.wasm-slim.toml`
+    pub fn from_settings(settings: Option<&HistorySettings>) -> Self {
+        let defaults = Self::default();
+        let Some(settings) = settings else {
+            return defaults;
+        };
+        Self {
+            keep_days: settings.keep_days.unwrap_or(defaults.keep_days),
+            auto_compact: settings.auto_compact.unwrap_or(defaults.auto_compact),
+            days_per_run: settings
+                .days_per_run
+                .unwrap_or(defaults.days_per_run)
+                .max(1),
+        }
+    }
+}
+
+/// Outcome of a history compaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionSummary {
+    /// Records before compaction
+    pub records_before: usize,
+    /// Records after compaction
+    pub records_after: usize,
+    /// Days rolled up in this run
+    pub days_compacted: usize,
+    /// Days still awaiting compaction (left for the next run)
+    pub days_pending: usize,
 }
 
 /// Build history manager
@@ -43,17 +166,20 @@ impl BuildRecord {
 pub struct BuildHistory {
     /// List of build records (newest first)
     pub records: Vec<BuildRecord>,
+    /// Timestamp of the last compaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_compacted: Option<String>,
 }
 
 impl BuildHistory {
-    const HISTORY_DIR: &'static str = ".wasm-slim";
     const HISTORY_FILE: &'static str = "history.json";
-    const MAX_RECORDS: usize = 100;
+    const MAX_RECORDS: usize = 5000;
 
     /// Create a new empty history
     pub fn new() -> Self {
         Self {
             records: Vec::new(),
+            last_compacted: None,
         }
     }
 
@@ -87,7 +213,7 @@ impl BuildHistory {
 
     /// Save history with a custom filesystem implementation
     pub fn save_with_fs<FS: FileSystem>(&self, project_root: &Path, fs: &FS) -> Result<()> {
-        let history_dir = project_root.join(Self::HISTORY_DIR);
+        let history_dir = crate::state::state_dir(project_root);
         let history_path = Self::history_path(project_root);
 
         // Create directory if it doesn't exist
@@ -95,7 +221,7 @@ impl BuildHistory {
             .context("Failed to create .wasm-slim directory")?;
 
         let contents =
-            serde_json::to_string_pretty(self).context("Failed to serialize build history")?;
+            crate::redact::to_json_pretty(self).context("Failed to serialize build history")?;
 
         fs.write(&history_path, contents)
             .context("Failed to write build history")?;
@@ -119,9 +245,49 @@ impl BuildHistory {
         self.records.first()
     }
 
+    /// Most recent build with exactly this size
+    ///
+    /// Used to recover the metadata of an artifact produced by a recorded build.
+    pub fn find_by_size(&self, size_bytes: u64) -> Option<&BuildRecord> {
+        self.records.iter().find(|r| r.size_bytes == size_bytes)
+    }
+
+    /// Feature drift between the recorded builds that produced two artifact sizes
+    ///
+    /// Returns `None` unless both sizes match a recorded build with a feature set
+    /// and those feature sets differ.
+    pub fn feature_drift_between(&self, before_size: u64, after_size: u64) -> Option<FeatureDrift> {
+        let before = self.find_by_size(before_size)?.features.as_ref()?;
+        let after = self.find_by_size(after_size)?.features.as_ref()?;
+        Some(before.drift_to(after)).filter(|d| !d.is_empty())
+    }
+
+    /// Median size of the recorded builds
+    pub fn median_size(&self) -> Option<u64> {
+        let mut sizes: Vec<u64> = self.records.iter().map(|r| r.size_bytes).collect();
+        sizes.sort_unstable();
+        let mid = sizes.len() / 2;
+        match sizes.len() {
+            0 => None,
+            n if n % 2 == 0 => Some((sizes[mid - 1] + sizes[mid]) / 2),
+            _ => Some(sizes[mid]),
+        }
+    }
+
     /// Check for size regression (>5% increase from previous)
     /// Compares current_size against the most recent build in history
     pub fn check_regression(&self, current_size: u64) -> Option<RegressionResult> {
+        self.check_regression_with_features(current_size, None)
+    }
+
+    /// Check for size regression, also reporting feature drift from the previous build
+    ///
+    /// Drift is only reported when both builds recorded their feature set.
+    pub fn check_regression_with_features(
+        &self,
+        current_size: u64,
+        current_features: Option<&FeatureSet>,
+    ) -> Option<RegressionResult> {
         // Compare against the latest build (most recent)
         let previous = self.latest()?;
 
@@ -131,20 +297,99 @@ impl BuildHistory {
         // Regression if >5% increase
         let is_regression = percent_change > 5.0;
 
+        let feature_drift = match (&previous.features, current_features) {
+            (Some(before), Some(after)) => Some(before.drift_to(after)).filter(|d| !d.is_empty()),
+            _ => None,
+        };
+
         Some(RegressionResult {
             is_regression,
             previous_size: previous.size_bytes,
             current_size,
             size_diff,
             percent_change,
+            feature_drift,
         })
     }
 
+    /// Roll up builds older than the policy's retention window, one record per day
+    ///
+    /// Each compacted day keeps its last build, with the day's build count and
+    /// size range in [`BuildRecord::rollup`]. Oldest days are compacted first;
+    /// `max_days` bounds the work of one run and the rest is reported as
+    /// pending. Records with unparseable timestamps are left untouched.
+    pub fn compact(
+        &mut self,
+        policy: &CompactionPolicy,
+        now_secs: u64,
+        max_days: Option<usize>,
+    ) -> CompactionSummary {
+        let records_before = self.records.len();
+        let cutoff_day =
+            now_secs.saturating_sub(policy.keep_days.saturating_mul(SECS_PER_DAY)) / SECS_PER_DAY;
+
+        let mut per_day: BTreeMap<u64, usize> = BTreeMap::new();
+        for day in self.records.iter().filter_map(BuildRecord::day) {
+            if day < cutoff_day {
+                *per_day.entry(day).or_default() += 1;
+            }
+        }
+        let due: Vec<u64> = per_day
+            .into_iter()
+            .filter(|&(_, count)| count > 1)
+            .map(|(day, _)| day)
+            .collect();
+        let take = max_days.map_or(due.len(), |max| max.min(due.len()));
+        let (selected, pending) = due.split_at(take);
+
+        // Newest first, so the first record seen for a day is its last build
+        let mut kept: Vec<BuildRecord> = Vec::with_capacity(self.records.len());
+        let mut day_slot: BTreeMap<u64, usize> = BTreeMap::new();
+        for record in self.records.drain(..) {
+            match record.day().filter(|day| selected.contains(day)) {
+                Some(day) => match day_slot.get(&day) {
+                    Some(&slot) => kept[slot].absorb(&record),
+                    None => {
+                        day_slot.insert(day, kept.len());
+                        kept.push(record);
+                    }
+                },
+                None => kept.push(record),
+            }
+        }
+        self.records = kept;
+        self.last_compacted = Some(format_timestamp(now_secs));
+
+        CompactionSummary {
+            records_before,
+            records_after: self.records.len(),
+            days_compacted: selected.len(),
+            days_pending: pending.len(),
+        }
+    }
+
+    /// Compact if the policy enables it and the last compaction is over a day old
+    ///
+    /// Rolls up at most `policy.days_per_run` days, so a large backlog is
+    /// worked off across several builds instead of slowing one down.
+    pub fn auto_compact(
+        &mut self,
+        policy: &CompactionPolicy,
+        now_secs: u64,
+    ) -> Option<CompactionSummary> {
+        if !policy.auto_compact {
+            return None;
+        }
+        let last = self.last_compacted.as_deref().and_then(parse_timestamp);
+        if last.is_some_and(|last| now_secs.saturating_sub(last) < AUTO_COMPACT_INTERVAL_SECS) {
+            return None;
+        }
+        Some(self.compact(policy, now_secs, Some(policy.days_per_run)))
+    }
+
     /// Get history file path
     fn history_path(project_root: &Path) -> PathBuf {
-        project_root
-            .join(Self::HISTORY_DIR)
-            .join(Self::HISTORY_FILE)
+        crate::state::state_dir(project_root).join(Self::HISTORY_FILE)
     }
 }
 
@@ -167,6 +412,8 @@ pub struct RegressionResult {
     pub size_diff: i64,
     /// Percent change (positive = increase)
     pub percent_change: f64,
+    /// Features that differ from the previous build, if any
+    pub feature_drift: Option<FeatureDrift>,
 }
 
 impl RegressionResult {
@@ -180,17 +427,28 @@ impl RegressionResult {
 
 /// Get current ISO 8601 timestamp
 fn current_timestamp() -> Result<String> {
+    Ok(format_timestamp(current_unix_secs()?))
+}
+
+/// Seconds since the UNIX epoch
+pub fn current_unix_secs() -> Result<u64> {
     use std::time::{SystemTime, UNIX_EPOCH};
 
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .context("System time before UNIX_EPOCH")?;
+    Ok(now.as_secs())
+}
 
-    // Format as ISO 8601: YYYY-MM-DDTHH:MM:SSZ
-    let secs = now.as_secs();
-
+/// Format a record timestamp
+fn format_timestamp(secs: u64) -> String {
     // Simple ISO 8601 formatting without external dependencies
-    Ok(format!("{:019}", secs))
+    format!("{:019}", secs)
+}
+
+/// Parse a record timestamp back to seconds since the UNIX epoch
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    timestamp.parse().ok()
 }
 
 #[cfg(test)]
@@ -221,6 +479,18 @@ mod tests {
         assert_eq!(history.records[1].size_bytes, 500 * 1024);
     }
 
+    #[test]
+    fn test_build_history_median_size() {
+        let mut history = BuildHistory::new();
+        assert_eq!(history.median_size(), None);
+        for size in [300, 100, 200] {
+            history.add_record(BuildRecord::new(size).unwrap());
+        }
+        assert_eq!(history.median_size(), Some(200));
+        history.add_record(BuildRecord::new(1000).unwrap());
+        assert_eq!(history.median_size(), Some(250));
+    }
+
     #[test]
     fn test_build_history_check_regression_detects_size_increases() {
         let mut history = BuildHistory::new();
@@ -272,15 +542,16 @@ mod tests {
         let mut history = BuildHistory::new();
 
         // Add more than MAX_RECORDS
-        for i in 0..150 {
+        let total = BuildHistory::MAX_RECORDS as u64 + 50;
+        for i in 0..total {
             let record = BuildRecord::new((i + 1) * 1024).unwrap();
             history.add_record(record);
         }
 
         // Should be truncated to MAX_RECORDS
         assert_eq!(history.records.len(), BuildHistory::MAX_RECORDS);
-        // Most recent should be 150 KB
-        assert_eq!(history.latest().unwrap().size_bytes, 150 * 1024);
+        // Most recent should be the last one added
+        assert_eq!(history.latest().unwrap().size_bytes, total * 1024);
     }
 
     // P1-TEST-COV-005: History tracking edge cases
@@ -449,6 +720,75 @@ mod tests {
         assert_eq!(loaded_record.branch, None);
     }
 
+    #[test]
+    fn test_check_regression_with_features_reports_drift() {
+        let mut before = FeatureSet::default();
+        before.insert("serde", "std");
+        let mut after = before.clone();
+        after.insert("app", "debug-ui");
+
+        let mut history = BuildHistory::new();
+        history.add_record(
+            BuildRecord::new(500 * 1024)
+                .unwrap()
+                .with_features(before.clone()),
+        );
+
+        let result = history
+            .check_regression_with_features(600 * 1024, Some(&after))
+            .unwrap();
+        assert!(result.is_regression);
+        assert_eq!(result.feature_drift.unwrap().added, vec!["app/debug-ui"]);
+
+        let same = history
+            .check_regression_with_features(600 * 1024, Some(&before))
+            .unwrap();
+        assert!(same.feature_drift.is_none());
+        assert!(history
+            .check_regression(600 * 1024)
+            .unwrap()
+            .feature_drift
+            .is_none());
+    }
+
+    #[test]
+    fn test_build_history_loads_records_without_features() {
+        let history: BuildHistory = serde_json::from_str(
+            r#"{"records": [{"timestamp": "0000000000000000001", "size_bytes": 1024}]}"#,
+        )
+        .unwrap();
+        assert!(history.latest().unwrap().features.is_none());
+        assert_eq!(
+            history.find_by_size(1024).unwrap().timestamp,
+            "0000000000000000001"
+        );
+        assert!(history.find_by_size(2048).is_none());
+    }
+
+    #[test]
+    fn test_feature_drift_between_recorded_builds() {
+        let mut local = FeatureSet::default();
+        local.insert("app", "debug-ui");
+        let ci = FeatureSet::default();
+
+        let mut history = BuildHistory::new();
+        history.add_record(BuildRecord::new(600 * 1024).unwrap().with_features(local));
+        history.add_record(BuildRecord::new(500 * 1024).unwrap().with_features(ci));
+        history.add_record(BuildRecord::new(400 * 1024).unwrap());
+
+        let drift = history
+            .feature_drift_between(500 * 1024, 600 * 1024)
+            .unwrap();
+        assert_eq!(drift.added, vec!["app/debug-ui"]);
+        assert!(history
+            .feature_drift_between(500 * 1024, 500 * 1024)
+            .is_none());
+        assert!(history
+            .feature_drift_between(400 * 1024, 600 * 1024)
+            .is_none());
+        assert!(history.feature_drift_between(500 * 1024, 123).is_none());
+    }
+
     #[test]
     fn test_build_history_check_regression_with_empty_history_returns_none() {
         // Test regression check with no history
@@ -515,4 +855,89 @@ mod tests {
         assert_eq!(final_history.records[1].size_bytes, 200 * 1024);
         assert_eq!(final_history.records[2].size_bytes, 100 * 1024);
     }
+
+    fn record_at(secs: u64, size_bytes: u64) -> BuildRecord {
+        BuildRecord {
+            timestamp: format_timestamp(secs),
+            size_bytes,
+            commit_hash: None,
+            branch: None,
+            features: None,
+            rollup: None,
+        }
+    }
+
+    #[test]
+    fn test_compact_rolls_up_old_days_and_keeps_recent_builds() {
+        let now = 200 * SECS_PER_DAY;
+        let old_day = 10 * SECS_PER_DAY;
+        let mut history = BuildHistory::new();
+        for (secs, size) in [
+            (old_day + 60, 300),
+            (old_day + 120, 100),
+            (old_day + 180, 200),
+            (now - 120, 500),
+            (now - 60, 400),
+        ] {
+            history.add_record(record_at(secs, size));
+        }
+
+        let summary = history.compact(&CompactionPolicy::default(), now, None);
+        assert_eq!(summary.records_before, 5);
+        assert_eq!(summary.records_after, 3);
+        assert_eq!(summary.days_compacted, 1);
+        assert_eq!(summary.days_pending, 0);
+
+        // Recent builds untouched, old day reduced to its last build
+        assert_eq!(history.records[0].size_bytes, 400);
+        assert_eq!(history.records[1].size_bytes, 500);
+        assert_eq!(history.records[2].size_bytes, 200);
+        assert_eq!(
+            history.records[2].rollup,
+            Some(DailyRollup {
+                builds: 3,
+                min_bytes: 100,
+                max_bytes: 300
+            })
+        );
+
+        // Idempotent
+        let again = history.compact(&CompactionPolicy::default(), now, None);
+        assert_eq!(again.records_after, 3);
+        assert_eq!(again.days_compacted, 0);
+    }
+
+    #[test]
+    fn test_auto_compact_is_rate_limited_and_resumes_pending_days() {
+        let policy = CompactionPolicy {
+            days_per_run: 1,
+            ..CompactionPolicy::default()
+        };
+        let mut history = BuildHistory::new();
+        for day in [1, 2] {
+            history.add_record(record_at(day * SECS_PER_DAY, 100));
+            history.add_record(record_at(day * SECS_PER_DAY + 60, 110));
+        }
+        let now = 200 * SECS_PER_DAY;
+
+        let first = history.auto_compact(&policy, now).unwrap();
+        assert_eq!((first.days_compacted, first.days_pending), (1, 1));
+        // Oldest day first
+        assert!(history.records[2].rollup.is_some());
+
+        // Within a day of the last run: skipped
+        assert!(history.auto_compact(&policy, now + 60).is_none());
+
+        let second = history
+            .auto_compact(&policy, now + AUTO_COMPACT_INTERVAL_SECS)
+            .unwrap();
+        assert_eq!((second.days_compacted, second.days_pending), (1, 0));
+        assert_eq!(history.records.len(), 2);
+
+        let disabled = CompactionPolicy {
+            auto_compact: false,
+            ..policy
+        };
+        assert!(history.auto_compact(&disabled, now * 2).is_none());
+    }
 }
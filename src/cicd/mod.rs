@@ -2,18 +2,29 @@
 //!
 //! Provides:
 //! - Size budget enforcement with configurable thresholds
-//! - Build history tracking for regression detection
+//! - Build history tracking for regression detection, with daily rollups
+//! - Accidental debug build detection in budget checks
 //! - JSON output for CI/CD tool integration
 //! - Exit codes for automated workflows
+//! - Archived size reports for auditing shipped builds
+//! - Feature-set drift detection between builds
+//! - Deployment target profiles with platform size limits
 
+pub mod archive;
 pub mod budget;
+pub mod debug_build;
+pub mod deploy;
 pub mod display;
+pub mod features;
 pub mod history;
 pub mod output;
 
-pub use budget::BudgetChecker;
-pub use history::{BuildHistory, BuildRecord};
-pub use output::JsonOutput;
+pub use archive::ReportArchiver;
+pub use budget::{BudgetChecker, BudgetOutcome, BudgetResult, BudgetStatus};
+pub use deploy::{DeployProfile, SizeMetric};
+pub use features::{FeatureDrift, FeatureSet};
+pub use history::{BuildHistory, BuildRecord, CompactionPolicy};
+pub use output::{BudgetInfo, JsonOutput, SizeInfo};
 
 #[cfg(test)]
 mod tests {
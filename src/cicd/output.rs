@@ -1,7 +1,10 @@
 //! JSON output formatting for CI/CD integration
 
 use crate::cicd::budget::{BudgetResult, BudgetStatus};
+use crate::cicd::deploy::SizeMetric;
+use crate::cicd::features::FeatureDrift;
 use crate::cicd::history::RegressionResult;
+use crate::summary::CompressedSizes;
 use serde::{Deserialize, Serialize};
 
 /// JSON output structure for CI/CD tools
@@ -17,6 +20,9 @@ pub struct JsonOutput {
     /// Regression check result (if applicable)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub regression: Option<RegressionInfo>,
+    /// Config file used, when not the project's `.wasm-slim.toml`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<String>,
 }
 
 /// Size information in multiple units
@@ -71,6 +77,15 @@ pub struct BudgetInfo {
     /// Amount over/under budget in KB
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delta_kb: Option<f64>,
+    /// Size metric the budget was checked against
+    #[serde(default)]
+    pub metric: SizeMetric,
+    /// Deployment platform limit in KB (if a deploy target is configured)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform_limit_kb: Option<u64>,
+    /// Compressed transfer sizes
+    #[serde(flatten)]
+    pub compressed: CompressedSizes,
     /// Human-readable message
     pub message: String,
 }
@@ -105,6 +120,9 @@ impl BudgetInfo {
             warn_threshold_kb: result.warn_threshold_kb,
             max_size_kb: result.max_size_kb,
             delta_kb,
+            metric: result.metric,
+            platform_limit_kb: result.platform_limit.map(|l| l.limit_kb),
+            compressed: result.compressed,
             message: result.message.clone(),
         }
     }
@@ -125,6 +143,9 @@ pub struct RegressionInfo {
     pub diff_kb: f64,
     /// Percent change (negative = reduction)
     pub percent_change: f64,
+    /// Features that differ from the previous build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature_drift: Option<FeatureDrift>,
 }
 
 impl RegressionInfo {
@@ -137,6 +158,7 @@ impl RegressionInfo {
             diff_bytes: result.size_diff,
             diff_kb: result.size_diff as f64 / 1024.0,
             percent_change: result.percent_change,
+            feature_drift: result.feature_drift.clone(),
         }
     }
 }
@@ -149,6 +171,7 @@ impl JsonOutput {
             size: SizeInfo::new(size_bytes),
             budget: None,
             regression: None,
+            config: None,
         }
     }
 
@@ -165,9 +188,15 @@ impl JsonOutput {
         self
     }
 
+    /// Record the config file the build used
+    pub fn with_config(mut self, path: &std::path::Path) -> Self {
+        self.config = Some(path.display().to_string());
+        self
+    }
+
     /// Convert to JSON string
     pub fn to_json(&self) -> anyhow::Result<String> {
-        serde_json::to_string_pretty(self)
+        crate::redact::to_json_pretty(self)
             .map_err(|e| anyhow::anyhow!("Failed to serialize JSON output: {}", e))
     }
 
@@ -210,6 +239,7 @@ mod tests {
             max_size_kb: Some(1000),
             warn_threshold_kb: Some(800),
             target_size_kb: Some(500),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let budget_result = checker.check(600 * 1024).unwrap();
@@ -229,6 +259,7 @@ mod tests {
             max_size_kb: Some(500),
             warn_threshold_kb: None,
             target_size_kb: None,
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let budget_result = checker.check(600 * 1024).unwrap();
@@ -265,6 +296,7 @@ mod tests {
             target_size_kb: Some(100),
             warn_threshold_kb: None,
             max_size_kb: Some(150),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(200 * 1024).unwrap(); // 200 KB - over budget
@@ -280,6 +312,7 @@ mod tests {
             target_size_kb: Some(100),
             warn_threshold_kb: Some(120),
             max_size_kb: Some(150),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(130 * 1024).unwrap(); // 130 KB - warning
@@ -295,6 +328,7 @@ mod tests {
             target_size_kb: Some(100),
             warn_threshold_kb: None,
             max_size_kb: Some(150),
+            preopt_factor: None,
         };
         let checker = BudgetChecker::new(budget);
         let result = checker.check(110 * 1024).unwrap(); // 110 KB - above target but under max
@@ -304,6 +338,30 @@ mod tests {
         assert!(info.passed);
     }
 
+    #[test]
+    fn test_budget_info_reports_compressed_sizes() {
+        let budget = SizeBudget {
+            target_size_kb: None,
+            warn_threshold_kb: None,
+            max_size_kb: Some(150),
+            preopt_factor: None,
+        };
+        let result = BudgetChecker::new(budget)
+            .check(110 * 1024)
+            .unwrap()
+            .with_compressed(CompressedSizes {
+                gzip_bytes: Some(40 * 1024),
+                brotli_bytes: Some(32 * 1024),
+            });
+
+        let json = JsonOutput::new(110 * 1024)
+            .with_budget(&result)
+            .to_json()
+            .unwrap();
+        assert!(json.contains("\"gzip_bytes\": 40960"));
+        assert!(json.contains("\"brotli_bytes\": 32768"));
+    }
+
     #[test]
     fn test_regression_info_from_result() {
         use crate::cicd::history::RegressionResult;
@@ -314,6 +372,7 @@ mod tests {
             current_size: 110 * 1024,
             size_diff: 10 * 1024,
             percent_change: 10.0,
+            feature_drift: None,
         };
 
         let info = RegressionInfo::from_result(&result);
@@ -6,14 +6,97 @@
 //! - bloat: Binary size analysis
 //! - features: Feature flag analysis
 //! - panics: Panic pattern detection (unwrap, indexing, division)
-//! - top/dominators/dead/monos: WASM binary analysis with twiggy
+//! - serde: serde derives, their code size per type, and lighter serializers
+//! - allocator: Allocator recommendation backed by measured allocation counts
+//! - vendor-review: Per-crate license and size table (exportable as CSV)
+//! - --batch: Many artifacts from a list file, analyzed on a worker pool
+//! - config-check: Artifact cross-checked against the active .wasm-slim.toml
+//! - crates: Size of a WASM file broken down per owning crate
+//! - frameworks: Two reactive frameworks (or two versions of one) in the WASM graph
+//! - sources: Code size of a debug build attributed to source files via DWARF
+//! - duplicates: Byte-identical and near-identical function bodies in a WASM file
+//! - histogram: Distribution of function sizes in a WASM file, with outliers
+//! - data: What a WASM file's data segments contain, matched to embedded assets
+//! - data-structures: Costly lookup tables, lazy statics, B-tree code and Debug impls
+//! - strings: String constants grouped by kind, with removable panic strings
+//! - custom-sections: Custom section sizes, stripped in place with --fix
+//! - debug-info: DWARF, name section and symbol tables left in, stripped with --fix
+//! - reachability: Code only reachable from exports the JavaScript never calls
+//! - --format html (or --output html): Per-crate/per-function treemap of a WASM file as HTML
+//! - top/dominators/dead/monos: twiggy-style WASM binary analysis
 
 use anyhow::{Context, Result};
 use console::style;
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use crate::analyzer;
+use crate::analyzer::{ItemFilter, ItemLimit};
+use crate::cmd::OutputFormat;
 use crate::fmt::{MICROSCOPE, WARNING, WRENCH};
+use crate::redact;
+
+/// Options for `wasm-slim analyze`
+#[derive(Debug, Clone, Default)]
+pub struct AnalyzeOptions {
+    /// Apply optimization suggestions to Cargo.toml (deps), or strip custom sections
+    /// (custom-sections) or leftover debug info (debug-info)
+    pub fix: bool,
+    /// Show what would be changed without modifying files
+    pub dry_run: bool,
+    /// Show the externalization guide (assets mode)
+    pub guide: bool,
+    /// Run cargo metadata without network access (deps mode)
+    pub offline: bool,
+    /// How many items each list in the console report shows
+    pub limit: ItemLimit,
+    /// Write the dominator tree as collapsed stacks here (`-` for stdout)
+    pub folded: Option<PathBuf>,
+    /// Analyze every artifact listed in this file instead of a single one
+    pub batch: Option<PathBuf>,
+    /// Worker threads for batch analysis (defaults to available parallelism)
+    pub jobs: Option<usize>,
+    /// Also write the batch report as HTML here
+    pub html: Option<PathBuf>,
+    /// Save the WASM file's symbol sizes as a baseline here
+    pub save_baseline: Option<PathBuf>,
+    /// Flag symbols of the WASM file that grew past `[symbol_budget]` since this baseline
+    pub baseline: Option<PathBuf>,
+    /// Exports the JavaScript calls, or a file listing them (reachability mode)
+    pub live_exports: Option<Vec<String>>,
+    /// Measure each flagged feature with a trial build (features mode), or
+    /// run wasm-snip to measure the snip plan (dead mode with `snip`)
+    pub measure: bool,
+    /// Copy large assets to `public/assets` and write a patch for their embedding sites (assets mode)
+    pub apply: bool,
+    /// Convert fonts, images and JSON to more compact formats next to the originals (assets mode)
+    pub compress: bool,
+    /// List every panic site grouped by file (panics mode)
+    pub detailed: bool,
+    /// Write a patch rewriting fixable unwrap() calls (panics mode)
+    pub patch: bool,
+    /// Build with each supported allocator and benchmark them (allocator mode)
+    pub compare: bool,
+    /// Custom rules run alongside the built-in checks (bloat mode)
+    pub rules: Arc<analyzer::rules::RuleRegistry>,
+    /// Print this format instead of a console report (`json`: every mode; `csv`: vendor-review;
+    /// `sarif`: deps, panics and data; `markdown`: bloat, top, dominators, dead and monos;
+    /// `html`: the treemap of a WASM file, or the batch report)
+    pub format: Option<OutputFormat>,
+    /// Recompute instead of reusing cached results (top, dominators, dead, monos, all and score modes)
+    pub no_cache: bool,
+    /// Print wasm-snip patterns for the unreachable functions (dead mode)
+    pub snip: bool,
+    /// Fail when the size health score is below this (score mode)
+    pub min_score: Option<u8>,
+    /// Write the size-annotated dependency graph here, as DOT or `.json` (deps mode)
+    pub graph: Option<PathBuf>,
+    /// Sorting, filtering and truncation of the result lists (`--top`,
+    /// `--sort`, `--min-size`, `--filter`), plus the `--limit` collection cap (top,
+    /// dominators, dead and monos modes)
+    pub filter: ItemFilter,
+}
 
 /// Main analyze command dispatcher
 ///
@@ -42,15 +125,211 @@ pub fn cmd_analyze(
     guide: bool,
     json: bool,
 ) -> Result<()> {
+    let options = AnalyzeOptions {
+        fix,
+        dry_run,
+        guide,
+        offline: false,
+        limit: ItemLimit::Default,
+        folded: None,
+        batch: None,
+        jobs: None,
+        html: None,
+        save_baseline: None,
+        baseline: None,
+        live_exports: None,
+        measure: false,
+        apply: false,
+        compress: false,
+        detailed: false,
+        patch: false,
+        compare: false,
+        rules: Arc::default(),
+        format: json.then_some(OutputFormat::Json),
+        no_cache: false,
+        snip: false,
+        min_score: None,
+        graph: None,
+        filter: ItemFilter::default(),
+    };
+    cmd_analyze_with_options(file, mode, &options)
+}
+
+/// Analyze command dispatcher with the full set of options
+pub fn cmd_analyze_with_options(
+    file: &Option<String>,
+    mode: &str,
+    options: &AnalyzeOptions,
+) -> Result<()> {
+    let AnalyzeOptions {
+        fix,
+        dry_run,
+        guide,
+        offline,
+        limit,
+        ref folded,
+        ref batch,
+        jobs,
+        ref html,
+        ref save_baseline,
+        ref baseline,
+        ref live_exports,
+        measure,
+        apply,
+        compress,
+        detailed,
+        patch,
+        compare,
+        ref rules,
+        format,
+        no_cache,
+        snip,
+        min_score,
+        ref graph,
+        ref filter,
+    } = *options;
+
+    let json = format == Some(OutputFormat::Json);
+    if let Some(list) = batch {
+        return match format {
+            None | Some(OutputFormat::Json | OutputFormat::Html) => {
+                analyze_batch(list, jobs, format, html.as_deref(), limit)
+            }
+            Some(other) => anyhow::bail!("--format {} is not supported with --batch", other),
+        };
+    }
+    if html.is_some() {
+        anyhow::bail!("--html is only supported with --batch");
+    }
+    let file = &file
+        .as_deref()
+        .map(crate::download::resolve_input)
+        .transpose()?;
+    // A directory with the default mode is a wasm-pack package (`analyze pkg/`)
+    let mode = match file.as_deref() {
+        Some(f) if mode == "deps" && Path::new(f).is_dir() => "pkg",
+        _ => mode,
+    };
+
+    if format == Some(OutputFormat::Html) {
+        return export_treemap_html(file);
+    }
+
+    if let Some(path) = save_baseline {
+        return save_symbol_baseline(file, path);
+    }
+    if let Some(path) = baseline {
+        return check_symbol_baseline(file, path, json, limit);
+    }
+
+    if let Some(output) = folded {
+        if mode != "dominators" {
+            anyhow::bail!("--folded is only supported with --mode dominators");
+        }
+        return export_dominators_folded(file, output);
+    }
+    if let Some(output) = graph {
+        if mode != "deps" {
+            anyhow::bail!("--graph is only supported with --mode deps");
+        }
+        return export_dependency_graph(file, output, offline);
+    }
+    if live_exports.is_some() && mode != "reachability" {
+        anyhow::bail!("--live-exports is only supported with --mode reachability");
+    }
+    if min_score.is_some() && mode != "score" {
+        anyhow::bail!("--min-score is only supported with --mode score");
+    }
+    if snip && mode != "dead" {
+        anyhow::bail!("--snip is only supported with --mode dead");
+    }
+    match filter.collect_limit {
+        Some(0) => anyhow::bail!("--limit must be at least 1"),
+        Some(_) if !matches!(mode, "top" | "dominators" | "dead" | "monos") => {
+            anyhow::bail!("--limit is only supported with --mode top, dominators, dead or monos")
+        }
+        _ => {}
+    }
+    if measure && mode != "features" && !snip {
+        anyhow::bail!("--measure is only supported with --mode features or --mode dead --snip");
+    }
+    if apply && mode != "assets" {
+        anyhow::bail!("--apply is only supported with --mode assets");
+    }
+    if compress && mode != "assets" {
+        anyhow::bail!("--compress is only supported with --mode assets");
+    }
+    if detailed && mode != "panics" {
+        anyhow::bail!("--detailed is only supported with --mode panics");
+    }
+    if patch && mode != "panics" {
+        anyhow::bail!("--patch is only supported with --mode panics");
+    }
+    if compare && mode != "allocator" {
+        anyhow::bail!("--compare is only supported with --mode allocator");
+    }
+    if no_cache
+        && !matches!(
+            mode,
+            "top" | "dominators" | "dead" | "monos" | "all" | "score"
+        )
+    {
+        anyhow::bail!(
+            "--no-cache is only supported with --mode top, dominators, dead, monos, all or score"
+        );
+    }
+    let csv = format_in_mode(format, OutputFormat::Csv, mode, &["vendor-review"])?;
+    let sarif = format_in_mode(
+        format,
+        OutputFormat::Sarif,
+        mode,
+        &["deps", "panics", "data"],
+    )?;
+    let markdown = format_in_mode(
+        format,
+        OutputFormat::Markdown,
+        mode,
+        &["bloat", "top", "dominators", "dead", "monos"],
+    )?;
+
     match mode {
-        "assets" => analyze_assets(guide, json),
-        "deps" => analyze_dependencies(fix, dry_run, json),
-        "bloat" => analyze_bloat(json),
-        "features" => analyze_features(json),
-        "panics" => analyze_panics(json),
-        "top" | "dominators" | "dead" | "monos" => analyze_wasm_binary(file, mode, json),
+        "assets" => analyze_assets(guide, json, apply, compress, dry_run, filter, limit),
+        "deps" => analyze_dependencies(&env::current_dir()?, json, sarif, options),
+        "unused-deps" => analyze_unused_deps(fix, dry_run, json, filter, limit),
+        "no-std" => analyze_no_std(file, json, filter, limit),
+        "bloat" => analyze_bloat(file, json, markdown, rules, filter, limit),
+        "fmt" => analyze_fmt_bloat(file, json, filter, limit),
+        "features" => analyze_features(json, measure, filter, limit),
+        "panics" => analyze_panics(file, json, sarif, options),
+        "serde" => analyze_serde(file, json, filter, limit),
+        "llvm-lines" => analyze_llvm_lines(file, json, filter, limit),
+        "allocator" => analyze_allocator(json, fix, dry_run, compare, filter),
+        "vendor-review" => analyze_vendor_review(json, csv, filter, limit),
+        "wasm-bindgen-abi" => analyze_bindgen_abi(json, filter, limit),
+        "js-glue" => analyze_js_glue(file, json, filter, limit),
+        "pkg" => analyze_package(file, json, filter, limit),
+        "config-check" => analyze_config_check(file, json, filter),
+        "crates" => analyze_crate_sizes(file, json, filter, limit),
+        "sources" => analyze_source_files(file, json, filter, limit),
+        "frameworks" => analyze_frameworks(file, json, filter, limit),
+        "duplicates" => analyze_duplicates(file, json, filter, limit),
+        "histogram" => analyze_function_sizes(file, json, filter, limit),
+        "data" => analyze_data_segments(file, json, sarif, filter, limit),
+        "data-structures" => analyze_data_structures(file, json, filter, limit),
+        "startup" => analyze_startup(file, json, filter),
+        "memory" => analyze_memory(file, json, filter),
+        "strings" => analyze_strings(file, json, filter, limit),
+        "custom-sections" => analyze_custom_sections(file, fix, dry_run, json, filter, limit),
+        "debug-info" => analyze_debug_info(file, fix, dry_run, json, filter),
+        "reachability" => analyze_reachability(file, live_exports.as_deref(), json, filter, limit),
+        "dead" if snip => analyze_snip(file, json, measure, no_cache, filter, limit),
+        "top" | "dominators" | "dead" | "monos" => {
+            analyze_wasm_binary(file, mode, json, markdown, no_cache, filter, limit)
+        }
+        "all" => analyze_all(file, json, offline, no_cache, filter, limit),
+        "score" => analyze_score(file, json, offline, no_cache, min_score, filter),
         _ => {
-            anyhow::bail!("Unknown analysis mode: {}. Valid modes: assets, deps, features, bloat, panics, top, dominators, dead, monos", mode);
+            anyhow::bail!("Unknown analysis mode: {}. Valid modes: assets, deps, unused-deps, no-std, features, bloat, fmt, panics, serde, llvm-lines, allocator, vendor-review, wasm-bindgen-abi, js-glue, pkg, config-check, crates, sources, frameworks, duplicates, histogram, data, data-structures, startup, memory, strings, custom-sections, debug-info, reachability, top, dominators, dead, monos, all, score", mode);
         }
     }
 }
@@ -58,8 +337,20 @@ pub fn cmd_analyze(
 /// Analyze embedded assets (Phase 5)
 ///
 /// Scans the project for embedded assets (include_bytes!, include_str!, fonts, images)
-/// and provides externalization recommendations
-pub fn analyze_assets(guide: bool, json: bool) -> Result<()> {
+/// and provides externalization recommendations. With `apply`, critical and
+/// high priority assets are copied to `public/assets/` and a patch rewriting
+/// their embedding sites is written (printed instead with `dry_run`). With
+/// `compress`, fonts, images and JSON are converted to more compact formats
+/// next to the originals (listed only with `dry_run`).
+pub fn analyze_assets(
+    guide: bool,
+    json: bool,
+    apply: bool,
+    compress: bool,
+    dry_run: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
     if !json {
         println!(
             "{} {} Asset Detection",
@@ -69,21 +360,85 @@ pub fn analyze_assets(guide: bool, json: bool) -> Result<()> {
     }
 
     let project_root = env::current_dir()?;
-    let detector = analyzer::AssetDetector::new(&project_root);
-    let results = detector.scan_project()?;
+    let mut results = asset_detector(&project_root)?.scan_project()?;
+    filter.apply(&mut results.assets);
+    filter.apply(&mut results.compression);
 
     if json {
         analyzer::print_json_output(&results)?;
     } else {
-        analyzer::print_asset_report(&results);
+        analyzer::print_asset_report_with_limit(&results, limit);
         if guide {
             analyzer::show_externalization_guide(&results);
         }
     }
 
+    if apply {
+        let externalizer = analyzer::asset_externalize::AssetExternalizer::new(&project_root);
+        let plan = externalizer.plan(&results)?;
+        let patch_path = if dry_run || plan.moves.is_empty() {
+            None
+        } else {
+            Some(externalizer.apply(&plan)?)
+        };
+        analyzer::print_externalization(&plan, patch_path.as_deref());
+    }
+
+    if compress {
+        let compressor = analyzer::asset_compression::AssetCompressor::new();
+        let mut outcomes = Vec::new();
+        for opportunity in &results.compression {
+            let outcome = if dry_run || !opportunity.kind.tool_available() {
+                None
+            } else {
+                Some(
+                    compressor
+                        .compress(opportunity)
+                        .map_err(|e| format!("{:#}", e)),
+                )
+            };
+            outcomes.push((opportunity, outcome));
+        }
+        analyzer::print_compression(&outcomes, dry_run);
+    }
+
     Ok(())
 }
 
+/// Asset detector with the globs and thresholds from `[analysis]`
+fn asset_detector(project_root: &Path) -> Result<analyzer::AssetDetector> {
+    let analysis = crate::config::ConfigLoader::load(project_root)?
+        .analysis
+        .unwrap_or_default();
+    let thresholds = analysis
+        .thresholds
+        .as_ref()
+        .map(analyzer::thresholds::RecommendationThresholds::from_settings)
+        .unwrap_or_default();
+    Ok(analyzer::AssetDetector::new(project_root)
+        .with_globs(analysis.include, analysis.exclude)
+        .with_thresholds(thresholds))
+}
+
+/// Dependency analyzer with the project's crate rules and heavy dependency
+/// database (also returned, for the fixes)
+fn dependency_analyzer(
+    project_root: &Path,
+    offline: bool,
+) -> Result<(
+    analyzer::DependencyAnalyzer,
+    analyzer::heavy_deps::HeavyDepsDatabase,
+)> {
+    let config = crate::config::ConfigLoader::load(project_root).unwrap_or_default();
+    let policy = crate::config::Policy::for_project(project_root, &config)?;
+    let heavy_deps = analyzer::heavy_deps::HeavyDepsDatabase::for_project(project_root, &config)?;
+    let analyzer = analyzer::DependencyAnalyzer::new(project_root)
+        .with_offline(offline)
+        .with_crate_rules(crate::config::policy::crate_rules(policy.as_ref(), &config))
+        .with_heavy_deps(heavy_deps.clone());
+    Ok((analyzer, heavy_deps))
+}
+
 /// Analyze dependencies with optional auto-fix (Phase 4/4.5)
 ///
 /// Analyzes dependency tree for:
@@ -91,9 +446,25 @@ pub fn analyze_assets(guide: bool, json: bool) -> Result<()> {
 /// - Unnecessary feature flags
 /// - Optimization opportunities
 ///
-/// Can automatically apply fixes with --fix flag
-pub fn analyze_dependencies(fix: bool, dry_run: bool, json: bool) -> Result<()> {
-    if !json {
+/// Can automatically apply fixes with --fix flag. With `offline`, cargo
+/// metadata runs with `--offline` and falls back to declared dependencies
+/// when the registry index is cold. `--top`, `--filter` and the other list
+/// options only narrow what is printed; fixes cover every issue.
+pub fn analyze_dependencies(
+    project_root: &Path,
+    json: bool,
+    sarif: bool,
+    options: &AnalyzeOptions,
+) -> Result<()> {
+    let AnalyzeOptions {
+        fix,
+        dry_run,
+        offline,
+        ref filter,
+        limit,
+        ..
+    } = *options;
+    if !json && !sarif {
         println!(
             "{} {} Dependency Analysis",
             MICROSCOPE,
@@ -101,19 +472,40 @@ pub fn analyze_dependencies(fix: bool, dry_run: bool, json: bool) -> Result<()>
         );
     }
 
-    let project_root = env::current_dir()?;
-    let analyzer = analyzer::DependencyAnalyzer::new(&project_root);
+    let (analyzer, heavy_deps) = dependency_analyzer(project_root, offline)?;
     let report = analyzer.analyze()?;
+    let mut shown = report.clone();
+    filter.apply(&mut shown.issues);
+    filter.apply(&mut shown.duplicate_versions);
 
-    if json {
+    if sarif {
+        let manifest = std::fs::read_to_string(project_root.join("Cargo.toml"))
+            .context("Failed to read Cargo.toml")?;
+        print_sarif(analyzer::sarif::dependency_findings(&shown, &manifest))?;
+    } else if json {
         // Output JSON format
-        let json_output = serde_json::to_string_pretty(&report)
+        let json_output = redact::to_json_pretty(&shown)
             .context("Failed to serialize dependency report to JSON")?;
         println!("{}", json_output);
-        return Ok(());
+    } else {
+        shown.print_report_with_limit(limit);
     }
 
-    report.print_report();
+    if report.has_banned() {
+        anyhow::bail!(
+            "Banned crates in the WASM dependency graph: {}",
+            report
+                .rule_violations
+                .iter()
+                .filter(|v| v.kind == analyzer::deps::RuleKind::Banned)
+                .map(|v| v.package.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    if json || sarif {
+        return Ok(());
+    }
 
     // Apply fixes if requested
     if fix || dry_run {
@@ -131,7 +523,8 @@ pub fn analyze_dependencies(fix: bool, dry_run: bool, json: bool) -> Result<()>
             );
         }
 
-        let applicator = analyzer::SuggestionApplicator::new(&project_root);
+        let applicator =
+            analyzer::SuggestionApplicator::new(project_root).with_heavy_deps(heavy_deps);
         let fixes_applied = applicator.apply_suggestions(&report, dry_run)?;
 
         if fixes_applied > 0 {
@@ -174,9 +567,18 @@ pub fn analyze_dependencies(fix: bool, dry_run: bool, json: bool) -> Result<()>
 /// Analyze binary bloat (Phase 4.5)
 ///
 /// Uses cargo-bloat to identify the largest code contributors
-/// in the compiled binary
-pub fn analyze_bloat(json: bool) -> Result<()> {
-    if !json {
+/// in the compiled binary. Custom `rules` add their recommendations to the
+/// report. Without cargo-bloat, the function bodies of `file` (or of the
+/// release artifact) are sized from the WASM name section instead.
+pub fn analyze_bloat(
+    file: &Option<String>,
+    json: bool,
+    markdown: bool,
+    rules: &analyzer::rules::RuleRegistry,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    if !json && !markdown {
         println!(
             "{} {} Binary Size Analysis",
             MICROSCOPE,
@@ -184,21 +586,71 @@ pub fn analyze_bloat(json: bool) -> Result<()> {
         );
     }
 
-    // Check if cargo-bloat is installed
-    if !analyzer::BloatAnalyzer::check_installation()? {
-        anyhow::bail!("cargo-bloat is not installed. Install with: cargo install cargo-bloat");
+    let builtin = !analyzer::BloatAnalyzer::check_installation()?;
+    if builtin && !json && !markdown {
+        println!(
+            "   {} {}",
+            WARNING,
+            style("cargo-bloat is not installed; sizing functions from the WASM name section. Install with: cargo install cargo-bloat").yellow()
+        );
     }
 
     let project_root = env::current_dir()?;
-    let bloat_analyzer = analyzer::BloatAnalyzer::new(&project_root);
-    let results = bloat_analyzer.analyze()?;
+    let config = crate::config::ConfigLoader::load(&project_root)?;
+    let thresholds = config
+        .analysis
+        .and_then(|a| a.thresholds)
+        .map(|t| analyzer::thresholds::RecommendationThresholds::from_settings(&t))
+        .unwrap_or_default();
+    let bloat_analyzer = analyzer::BloatAnalyzer::new(&project_root).with_thresholds(thresholds);
+    let mut results = if builtin {
+        bloat_analyzer.analyze_builtin(file.as_deref().map(Path::new))?
+    } else {
+        bloat_analyzer.analyze()?
+    };
 
-    if json {
+    if !rules.is_empty() {
+        let crates = if builtin {
+            results.crates.clone()
+        } else {
+            bloat_analyzer.analyze_crates()?
+        };
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .current_dir(&project_root)
+            .exec()
+            .context("Failed to run cargo metadata for custom rules")?;
+        let dependencies: Vec<String> = metadata
+            .packages
+            .iter()
+            .map(|p| p.name.to_string())
+            .collect();
+        let custom = rules.run_all(&analyzer::rules::AnalysisContext {
+            project_root: &project_root,
+            bloat: &results,
+            crates: &crates,
+            dependencies: &dependencies,
+        });
+        results.recommendations.extend(custom);
+    }
+
+    let suppressions = analyzer::suppressions::Suppressions::load(&project_root)?;
+    let suppressed = suppressions.filter(&mut results.recommendations);
+    filter.retain_recommendations(&mut results.recommendations);
+    filter.apply(&mut results.items);
+    filter.apply(&mut results.crates);
+
+    if markdown {
+        print!(
+            "{}",
+            analyzer::markdown_report::format_bloat_markdown(&results, limit)
+        );
+    } else if json {
         let json_output = analyzer::format_bloat_json(&results)?;
         println!("{}", json_output);
     } else {
-        let report = analyzer::format_bloat_console(&results)?;
+        let report = analyzer::format_bloat_console_with_limit(&results, limit)?;
         print!("{}", report);
+        print_suppressed(suppressed);
     }
 
     Ok(())
@@ -210,7 +662,15 @@ pub fn analyze_bloat(json: bool) -> Result<()> {
 /// - Unused features
 /// - Feature flag combinations
 /// - Optimization opportunities
-pub fn analyze_features(json: bool) -> Result<()> {
+///
+/// With `measure`, each flagged feature is also turned off in a trial build
+/// and the measured size difference is reported.
+pub fn analyze_features(
+    json: bool,
+    measure: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
     if !json {
         println!(
             "{} {} Feature Flag Analysis",
@@ -221,13 +681,28 @@ pub fn analyze_features(json: bool) -> Result<()> {
 
     let project_root = env::current_dir()?;
     let feature_analyzer = analyzer::FeatureAnalyzer::new(&project_root);
-    let results = feature_analyzer.analyze()?;
+    let mut results = feature_analyzer.analyze()?;
+    filter.apply(&mut results.unused_features);
+    filter.apply(&mut results.unified_features);
+
+    if measure && !results.unused_features.is_empty() {
+        if !json {
+            println!(
+                "   {} Building once per flagged feature (this may take a while)...",
+                style("→").dim()
+            );
+        }
+        let measurements = analyzer::feature_measure::FeatureMeasurer::new(&project_root)
+            .measure(&results.unused_features)
+            .context("Failed to measure features")?;
+        results.measurements = Some(measurements);
+    }
 
     if json {
         let json_output = analyzer::format_feature_json(&results)?;
         println!("{}", json_output);
     } else {
-        let report = analyzer::format_feature_console(&results)?;
+        let report = analyzer::format_feature_console_with_limit(&results, limit)?;
         print!("{}", report);
     }
 
@@ -242,9 +717,26 @@ pub fn analyze_features(json: bool) -> Result<()> {
 /// - Division operators / and % (vs .checked_div())
 /// - panic!() and assert!() macros
 ///
-/// Each panic site adds 500-2000 bytes to the WASM binary.
-pub fn analyze_panics(json: bool) -> Result<()> {
-    if !json {
+/// Each panic site adds 500-2000 bytes to the WASM binary. With a WASM
+/// file, the panic strings found in its data segments add recommendations.
+/// With `options.detailed`, every site is listed with its line, grouped by file.
+/// With `options.patch`, the `unwrap()` calls whose rewrite can be inferred are
+/// turned into a patch in the state directory (printed with `options.dry_run`).
+pub fn analyze_panics(
+    file: &Option<String>,
+    json: bool,
+    sarif: bool,
+    options: &AnalyzeOptions,
+) -> Result<()> {
+    let AnalyzeOptions {
+        detailed,
+        patch,
+        dry_run,
+        ref filter,
+        limit,
+        ..
+    } = *options;
+    if !json && !sarif {
         println!(
             "{} {} Panic Pattern Analysis",
             MICROSCOPE,
@@ -255,139 +747,2213 @@ pub fn analyze_panics(json: bool) -> Result<()> {
 
     let project_root = env::current_dir()?;
     let detector = analyzer::PanicDetector::new(&project_root);
-    let results = detector.scan_project()?;
+    let mut results = detector.scan_project()?;
+    filter.apply(&mut results.panic_sites);
+    filter.apply(&mut results.by_file);
+    if let Some(f) = file {
+        let strings = analyzer::StringAnalyzer::new().analyze(Path::new(f))?;
+        results.recommendations.extend(strings.recommendations);
+    }
 
-    if json {
+    if sarif {
+        print_sarif(analyzer::sarif::panic_findings(&results, &project_root))?;
+    } else if json {
         analyzer::print_panic_json(&results)?;
     } else {
-        analyzer::print_panic_report(&results);
+        analyzer::print_panic_report_with_limit(&results, limit);
+        if detailed {
+            analyzer::print_panic_sites(&results, limit);
+        }
+    }
+
+    if patch {
+        let fixer = analyzer::panic_fixes::PanicFixer::new(&project_root);
+        let plan = fixer.plan(&results)?;
+        let patch_path = if dry_run || plan.fixes.is_empty() {
+            None
+        } else {
+            Some(fixer.write(&plan)?)
+        };
+        analyzer::print_panic_fixes(&plan, patch_path.as_deref());
     }
 
     Ok(())
 }
 
-/// Analyze WASM binary with twiggy (Phase 6)
+/// Attribute `core::fmt` code to the impls and call sites that pull it in
 ///
-/// Uses the twiggy profiler to analyze WASM binaries with different modes:
-/// - top: Show largest code contributors
-/// - dominators: Show dominator tree analysis
-/// - dead: Identify dead code
-/// - monos: Analyze monomorphizations
-pub fn analyze_wasm_binary(file: &Option<String>, mode: &str, json: bool) -> Result<()> {
-    let f = file.as_ref().ok_or_else(|| {
-        anyhow::anyhow!("WASM file required for binary analysis mode (top/dominators/dead/monos)")
-    })?;
-
-    // Check if the WASM file exists first (before checking for twiggy)
-    let wasm_path = std::path::Path::new(f);
+/// Unlike bloat mode, which matches symbol names, this walks the module's
+/// item graph, so code only formatting needs is counted too.
+pub fn analyze_fmt_bloat(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for fmt mode"))?;
+    let wasm_path = Path::new(f);
     if !wasm_path.exists() {
         anyhow::bail!("WASM file not found: {}", f);
     }
 
-    // Check if twiggy is installed
-    if !analyzer::TwiggyAnalyzer::check_installation()? {
-        eprintln!(
-            "{}",
-            style(analyzer::TwiggyAnalyzer::installation_instructions()).yellow()
-        );
-        anyhow::bail!("twiggy not installed");
-    }
+    let mut report = analyzer::FmtBloatAnalyzer::new().analyze(wasm_path)?;
+    filter.apply(&mut report.items);
 
-    if !json {
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
         println!(
-            "{} {} WASM Binary Analysis",
+            "{} {} Formatting Bloat Analysis",
             MICROSCOPE,
             style("wasm-slim").bold()
         );
-        println!("   File: {}", f);
-        println!("   Mode: {}", mode);
-        println!();
+        print!(
+            "{}",
+            analyzer::format_fmt_bloat_console_with_limit(&report, limit)?
+        );
     }
 
-    let analysis_mode = match mode {
-        "top" => analyzer::AnalysisMode::Top,
-        "dominators" => analyzer::AnalysisMode::Dominators,
-        "dead" => analyzer::AnalysisMode::Dead,
-        "monos" => analyzer::AnalysisMode::Monos,
-        _ => unreachable!(),
-    };
+    Ok(())
+}
 
-    let wasm_analyzer = analyzer::TwiggyAnalyzer::new(f);
-    let results = wasm_analyzer.analyze(analysis_mode)?;
+/// Find dependencies no source file references
+///
+/// With `fix`, they are removed from Cargo.toml after a backup; `dry_run`
+/// lists the removals without writing.
+pub fn analyze_unused_deps(
+    fix: bool,
+    dry_run: bool,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let report = analyzer::UnusedDepsAnalyzer::new(&project_root).analyze()?;
+    // Only the listing is filtered; fixes cover every finding
+    let mut shown = report.clone();
+    filter.apply(&mut shown.unused);
+    filter.apply(&mut shown.misplaced);
 
     if json {
-        let json_output = serde_json::to_string_pretty(&results)?;
-        println!("{}", json_output);
-    } else {
-        analyzer::print_analysis_report(&results);
+        println!("{}", redact::to_json_pretty(&shown)?);
+        return Ok(());
+    }
+    println!(
+        "{} {} Unused Dependency Analysis",
+        MICROSCOPE,
+        style("wasm-slim").bold()
+    );
+    print!(
+        "{}",
+        analyzer::format_unused_deps_console_with_limit(&shown, limit)?
+    );
+
+    if (fix || dry_run) && !(report.unused.is_empty() && report.misplaced.is_empty()) {
+        let cargo_toml = project_root.join("Cargo.toml");
+        if !dry_run {
+            let backup = crate::optimizer::BackupManager::new(&project_root)
+                .create_backup(&cargo_toml)
+                .context("Failed to create backup")?;
+            println!(
+                "\n   {} Backup created: {}",
+                style("💾").dim(),
+                backup.display()
+            );
+        }
+        let removals: Vec<(String, Option<String>)> = report
+            .unused
+            .iter()
+            .map(|dep| (dep.name.clone(), dep.target.clone()))
+            .collect();
+        let moves: Vec<(String, Option<String>, String)> = report
+            .misplaced
+            .iter()
+            .map(|dep| {
+                (
+                    dep.name.clone(),
+                    dep.target.clone(),
+                    dep.section.table_name().to_string(),
+                )
+            })
+            .collect();
+        let editor = crate::optimizer::CargoTomlEditor::new();
+        let mut changes = editor
+            .remove_dependencies(&cargo_toml, &removals, dry_run)
+            .context("Failed to update Cargo.toml")?;
+        changes.extend(
+            editor
+                .move_dependencies(&cargo_toml, &moves, dry_run)
+                .context("Failed to update Cargo.toml")?,
+        );
+        for change in &changes {
+            if dry_run {
+                println!("   {} Would apply: {}", style("•").yellow(), change);
+            } else {
+                println!("   {} {}", style("✓").green(), change);
+            }
+        }
+        if !dry_run && !changes.is_empty() {
+            println!(
+                "   {} Run `cargo check` to verify changes",
+                style("💡").bold()
+            );
+        }
     }
 
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Check whether the current crate can build as `no_std + alloc`
+///
+/// With a WASM file, the code attributed to `std` is reported as the
+/// expected savings.
+pub fn analyze_no_std(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let wasm_path = match file {
+        Some(f) => {
+            let path = Path::new(f);
+            if !path.exists() {
+                anyhow::bail!("WASM file not found: {}", f);
+            }
+            Some(path)
+        }
+        None => None,
+    };
 
-    #[test]
-    fn test_cmd_analyze_unknown_mode() {
-        let result = cmd_analyze(&None, "unknown_mode", false, false, false, false);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Unknown analysis mode"));
+    let project_root = env::current_dir()?;
+    let mut report = analyzer::NoStdAnalyzer::new(&project_root).analyze(wasm_path)?;
+    filter.apply(&mut report.source_blockers);
+    filter.apply(&mut report.dependency_blockers);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} no_std Feasibility Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_no_std_console_with_limit(&report, limit)?
+        );
     }
 
-    #[test]
-    fn test_cmd_analyze_valid_modes() {
-        let modes = vec![
-            "assets",
-            "deps",
-            "bloat",
-            "features",
-            "panics",
-            "top",
-            "dominators",
-            "dead",
-            "monos",
-        ];
+    Ok(())
+}
 
-        for mode in modes {
-            // These will fail due to missing tools or project setup, but should route correctly
-            let result = cmd_analyze(&None, mode, false, false, false, false);
-            // Just verify the error is not "Unknown analysis mode"
-            if let Err(e) = result {
-                assert!(!e.to_string().contains("Unknown analysis mode"));
-            }
+/// Count serde derives and estimate their code size
+///
+/// With a WASM file, serde monomorphizations are attributed to the derived
+/// types; lighter serializers are suggested when serde_json is in use.
+pub fn analyze_serde(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let mut serde_analyzer = analyzer::SerdeAnalyzer::new(&project_root);
+    if let Some(f) = file {
+        let wasm_path = Path::new(f);
+        if !wasm_path.exists() {
+            anyhow::bail!("WASM file not found: {}", f);
         }
+        serde_analyzer = serde_analyzer.with_wasm_file(wasm_path);
     }
+    let mut report = serde_analyzer.analyze()?;
+    filter.apply(&mut report.type_costs);
 
-    #[test]
-    fn test_cmd_analyze_wasm_binary_modes_require_file() {
-        let wasm_modes = vec!["top", "dominators", "dead", "monos"];
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} serde Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_serde_console_with_limit(&report, limit)?
+        );
+    }
 
-        for mode in wasm_modes {
-            let result = cmd_analyze(&None, mode, false, false, false, false);
-            assert!(result.is_err());
-            let error_msg = result.unwrap_err().to_string();
-            assert!(error_msg.contains("WASM file required") || error_msg.contains("twiggy"));
+    Ok(())
+}
+
+/// Count LLVM IR lines per generic function with `cargo llvm-lines`
+///
+/// Needs only a debug build for the WASM target; with a WASM file, each
+/// function is matched to its monomorphized copies in the binary.
+pub fn analyze_llvm_lines(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let mut llvm_lines = analyzer::LlvmLinesAnalyzer::new(&project_root);
+    if let Some(f) = file {
+        let wasm_path = Path::new(f);
+        if !wasm_path.exists() {
+            anyhow::bail!("WASM file not found: {}", f);
         }
+        llvm_lines = llvm_lines.with_wasm_file(wasm_path);
+    }
+    if !json {
+        println!(
+            "{} {} LLVM Lines Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        println!("   Running cargo llvm-lines for wasm32-unknown-unknown...");
     }
+    let mut report = llvm_lines.analyze()?;
+    filter.apply(&mut report.functions);
 
-    #[test]
-    fn test_cmd_analyze_with_file_parameter() {
-        let file = Some("test.wasm".to_string());
-        let result = cmd_analyze(&file, "top", false, false, false, false);
-        // Should fail on twiggy check or file not found, not on missing file parameter
-        if let Err(e) = result {
-            assert!(!e.to_string().contains("WASM file required"));
-        }
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        print!(
+            "{}",
+            analyzer::format_llvm_lines_console_with_limit(&report, limit)?
+        );
     }
 
-    #[test]
-    fn test_analyze_wasm_binary_requires_file() {
-        let result = analyze_wasm_binary(&None, "top", false);
+    Ok(())
+}
+
+/// Analyze allocator usage
+///
+/// Combines the dependency-name heuristic with actual allocation counts.
+/// When `[allocator] smoke-entrypoints` is configured, the entrypoints are
+/// run in a debug build under a counting allocator so the allocator
+/// recommendation is based on measured heap usage. With `compare`, the
+/// project is also built with each supported allocator and their measured
+/// sizes (and micro-benchmark times) replace the estimate. With `fix`, a
+/// recommendation is applied to Cargo.toml and the library root (shown
+/// only with `dry_run`): the smallest measured allocator with `compare`,
+/// lol_alloc otherwise.
+pub fn analyze_allocator(
+    json: bool,
+    fix: bool,
+    dry_run: bool,
+    compare: bool,
+    filter: &ItemFilter,
+) -> Result<()> {
+    if !json {
+        println!(
+            "{} {} Allocator Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+    }
+
+    let project_root = env::current_dir()?;
+    let heuristic = analyzer::AllocatorDetector::new(&project_root)
+        .check_allocator_optimization()
+        .context("Failed to analyze allocator usage")?;
+
+    let config = crate::config::ConfigLoader::load(&project_root)?;
+    let entrypoints = config
+        .allocator
+        .map(|a| a.smoke_entrypoints)
+        .unwrap_or_default();
+
+    let profile = if entrypoints.is_empty() {
+        None
+    } else {
+        if !json {
+            println!(
+                "   {} Running {} smoke entrypoint(s) under a counting allocator...",
+                style("→").dim(),
+                entrypoints.len()
+            );
+        }
+        Some(
+            analyzer::AllocationProfiler::new(&project_root)
+                .profile(&entrypoints)
+                .context("Failed to profile allocations")?,
+        )
+    };
+
+    let comparison = if compare {
+        if !json {
+            println!(
+                "   {} Building once per allocator (this may take a while)...",
+                style("→").dim()
+            );
+        }
+        Some(
+            analyzer::allocator_trials::AllocatorComparer::new(&project_root)
+                .compare()
+                .context("Failed to compare allocators")?,
+        )
+    } else {
+        None
+    };
+    // Only the listing is filtered; --fix picks from every trial
+    let shown = comparison.clone().map(|mut comparison| {
+        filter.apply(&mut comparison.trials);
+        comparison
+    });
+
+    if json {
+        let report = serde_json::json!({
+            "heuristic": heuristic,
+            "profile": profile,
+            "assessment": profile.as_ref().map(|p| p.assess()),
+            "comparison": shown,
+        });
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        analyzer::print_allocator_report(heuristic.as_ref(), profile.as_ref());
+        if let Some(comparison) = &shown {
+            analyzer::print_allocator_comparison(comparison);
+        }
+    }
+
+    if (fix || dry_run) && !json {
+        println!(
+            "\n{} {} Applying Allocator Recommendation",
+            WRENCH,
+            style("Auto-Fix").bold()
+        );
+        if dry_run {
+            println!(
+                "   {} {}",
+                WARNING,
+                style("[DRY RUN] No changes will be made").yellow()
+            );
+        }
+
+        // A measured profile overrides the dependency-name heuristic
+        let recommended = match profile.as_ref().map(|p| p.assess()) {
+            Some(assessment) => {
+                matches!(
+                    assessment,
+                    analyzer::alloc_profile::AllocatorAssessment::Recommended(_)
+                )
+            }
+            None => heuristic.is_some(),
+        };
+        // ...and a measured build picks the allocator, if any beats the current one
+        let allocator = match &comparison {
+            Some(comparison) => comparison.smallest().map(|trial| trial.allocator),
+            None => Some(analyzer::allocator_trials::AllocatorCandidate::RECOMMENDED),
+        };
+        let Some(allocator) = allocator.filter(|_| recommended) else {
+            println!(
+                "   {} No allocator change recommended; nothing to apply",
+                style("→").dim()
+            );
+            return Ok(());
+        };
+
+        let applicator = analyzer::SuggestionApplicator::new(&project_root);
+        let changed = applicator.apply_allocator(allocator, dry_run)?;
+        if changed == 0 {
+            println!(
+                "   {} {} is already configured",
+                style("✓").green(),
+                allocator.crate_name()
+            );
+        } else if dry_run {
+            println!(
+                "   {} Run without --dry-run to apply changes",
+                style("→").dim()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Combined license and size report for vendor review
+///
+/// Attributes code size to crates with cargo-bloat and joins the license
+/// declared in each crate's manifest. `csv` prints the table as CSV.
+pub fn analyze_vendor_review(
+    json: bool,
+    csv: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let machine_readable = json || csv;
+    if !machine_readable {
+        println!("{} {} Vendor Review", MICROSCOPE, style("wasm-slim").bold());
+    }
+
+    if !analyzer::BloatAnalyzer::check_installation()? {
+        anyhow::bail!("cargo-bloat is not installed. Install with: cargo install cargo-bloat");
+    }
+
+    let project_root = env::current_dir()?;
+    let mut review = analyzer::VendorReviewer::new(&project_root).analyze()?;
+    filter.apply(&mut review.rows);
+
+    if csv {
+        print!("{}", review.to_csv());
+    } else if json {
+        println!("{}", redact::to_json_pretty(&review)?);
+    } else {
+        print!(
+            "{}",
+            analyzer::format_vendor_review_console_with_limit(&review, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Analyze the cost of wasm-bindgen boundary signatures
+///
+/// Inspects `#[wasm_bindgen]` exports and the generated glue in `pkg/` for
+/// struct-by-value, String round-trip, Vec copy and serde `JsValue` patterns,
+/// with estimated savings in size and copies per call.
+pub fn analyze_bindgen_abi(json: bool, filter: &ItemFilter, limit: ItemLimit) -> Result<()> {
+    if !json {
+        println!(
+            "{} {} wasm-bindgen ABI Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+    }
+
+    let project_root = env::current_dir()?;
+    let mut results = analyzer::BindgenAbiAnalyzer::new(&project_root).analyze()?;
+    filter.apply(&mut results.issues);
+    filter.apply(&mut results.glue);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&results)?);
+    } else {
+        print!(
+            "{}",
+            analyzer::format_bindgen_abi_console_with_limit(&results, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Measure the wasm-bindgen JS glue and count its shims
+///
+/// `file` may be a glue file, a directory or the WASM module next to the
+/// glue; without it the project's `pkg/` directory is scanned.
+pub fn analyze_js_glue(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    if !json {
+        println!(
+            "{} {} wasm-bindgen JS Glue Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+    }
+
+    let analyzer = match file {
+        Some(path) => analyzer::JsGlueAnalyzer::new(path),
+        None => analyzer::JsGlueAnalyzer::for_project(&env::current_dir()?),
+    };
+    let mut results = analyzer.analyze()?;
+    filter.apply(&mut results.files);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&results)?);
+    } else {
+        print!(
+            "{}",
+            analyzer::format_js_glue_console_with_limit(&results, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Total the deliverable size of a wasm-pack package directory
+///
+/// Sums the `.wasm`, JS glue, `.d.ts` and snippets, and checks the total
+/// against `[package_budget]` when one is configured.
+pub fn analyze_package(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let dir = file
+        .as_deref()
+        .map(Path::new)
+        .filter(|p| p.is_dir())
+        .ok_or_else(|| anyhow::anyhow!("Package directory required for --mode pkg (e.g. pkg/)"))?;
+    let mut report = analyzer::PackageAnalyzer::new(dir).analyze()?;
+    let budget = crate::config::ConfigLoader::load(&env::current_dir()?)?
+        .package_budget
+        .map(|budget| {
+            crate::cicd::BudgetChecker::evaluate(
+                &crate::cicd::SizeInfo::new(report.total_size_bytes),
+                &budget,
+            )
+        });
+    filter.apply(&mut report.files);
+
+    if json {
+        let mut output = serde_json::to_value(&report)?;
+        if let Some(budget) = &budget {
+            output["budget"] = serde_json::to_value(&budget.info)?;
+        }
+        println!("{}", redact::to_json_pretty(&output)?);
+    } else {
+        println!(
+            "{} {} Package Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_package_console_with_limit(&report, limit)?
+        );
+        if let Some(budget) = &budget {
+            budget.result.print();
+        }
+    }
+
+    if let Some(budget) = budget.filter(|b| !b.passed()) {
+        anyhow::bail!("Package exceeds [package_budget]: {}", budget.message());
+    }
+    Ok(())
+}
+
+/// Analyze every artifact in a list file on a bounded worker pool
+///
+/// Each artifact gets its size, gzip size and top contributors; artifacts
+/// that fail are reported with their error instead of aborting the batch.
+pub fn analyze_batch(
+    list: &Path,
+    jobs: Option<usize>,
+    format: Option<OutputFormat>,
+    html_out: Option<&Path>,
+    limit: ItemLimit,
+) -> Result<()> {
+    let json = format == Some(OutputFormat::Json);
+    let html = format == Some(OutputFormat::Html);
+    let contents = std::fs::read_to_string(list)
+        .with_context(|| format!("Failed to read artifact list {}", list.display()))?;
+    let base_dir = list.parent().unwrap_or(Path::new("."));
+    let artifacts = analyzer::batch::parse_artifact_list(&contents, base_dir);
+    if artifacts.is_empty() {
+        anyhow::bail!("No artifacts listed in {}", list.display());
+    }
+
+    let workers = jobs.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+    if !json && !html {
+        println!(
+            "{} {} Batch Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        println!(
+            "   Analyzing {} artifacts with {} workers...",
+            artifacts.len(),
+            workers.min(artifacts.len())
+        );
+    }
+
+    let config = crate::config::ConfigLoader::load(&env::current_dir()?)?;
+    let thresholds = config
+        .analysis
+        .and_then(|a| a.thresholds)
+        .map(|t| analyzer::thresholds::RecommendationThresholds::from_settings(&t))
+        .unwrap_or_default();
+
+    let report = analyzer::BatchAnalyzer::new(workers)
+        .with_thresholds(thresholds)
+        .analyze(&artifacts);
+
+    crate::summary::record("artifacts", report.artifacts.len().to_string());
+    crate::summary::record("failed", report.failed.to_string());
+    crate::summary::record("size", crate::summary::format_kb(report.total_size_bytes));
+
+    if let Some(path) = html_out {
+        std::fs::write(path, report.to_html())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    if html {
+        print!("{}", report.to_html());
+    } else if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        print!(
+            "{}",
+            analyzer::format_batch_console_with_limit(&report, limit)?
+        );
+        if let Some(path) = html_out {
+            println!("   HTML report written to {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Analyze WASM binary with twiggy (Phase 6)
+///
+/// Analyzes WASM binaries natively (falling back to the twiggy CLI for
+/// modules the native parser rejects) with different modes:
+/// - top: Show largest code contributors
+/// - dominators: Show dominator tree analysis
+/// - dead: Identify dead code
+/// - monos: Analyze monomorphizations
+///
+/// `filter` sorts, filters and truncates the results, and its
+/// `collect_limit` keeps only that many of the largest items while
+/// analyzing; `limit` only truncates the printed lists.
+pub fn analyze_wasm_binary(
+    file: &Option<String>,
+    mode: &str,
+    json: bool,
+    markdown: bool,
+    no_cache: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file.as_ref().ok_or_else(|| {
+        anyhow::anyhow!("WASM file required for binary analysis mode (top/dominators/dead/monos)")
+    })?;
+
+    let wasm_path = std::path::Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    if !json && !markdown {
+        println!(
+            "{} {} WASM Binary Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        println!("   File: {}", f);
+        println!("   Mode: {}", mode);
+        println!();
+    }
+
+    let analysis_mode = match mode {
+        "top" => analyzer::AnalysisMode::Top,
+        "dominators" => analyzer::AnalysisMode::Dominators,
+        "dead" => analyzer::AnalysisMode::Dead,
+        "monos" => analyzer::AnalysisMode::Monos,
+        _ => unreachable!(),
+    };
+
+    let project_root = env::current_dir()?;
+    let mut results =
+        wasm_analyzer(f, &project_root, no_cache, filter.collect_limit)?.analyze(analysis_mode)?;
+    filter.apply(&mut results.items);
+    if let Some(groups) = results.mono_groups.as_mut() {
+        filter.apply(groups);
+    }
+    let suppressions = analyzer::suppressions::Suppressions::load(&project_root)?;
+    let suppressed = suppressions.filter(&mut results.recommendations);
+    filter.retain_recommendations(&mut results.recommendations);
+
+    if markdown {
+        let previous = crate::cicd::BuildHistory::load(&project_root)
+            .ok()
+            .and_then(|history| history.latest().map(|record| record.size_bytes));
+        print!(
+            "{}",
+            analyzer::markdown_report::format_analysis_markdown(&results, previous, limit)
+        );
+    } else if json {
+        let json_output = redact::to_json_pretty(&results)?;
+        println!("{}", json_output);
+    } else {
+        analyzer::print_analysis_report_with_limit(&results, limit);
+        print_suppressed(suppressed);
+
+        // Sizes of a build that ignored the config are misleading; say so
+        if let Some(checker) = config_checker()? {
+            if let Ok(check) = checker.check(wasm_path) {
+                if !check.mismatches.is_empty() {
+                    print!("{}", analyzer::format_config_check_console(&check)?);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Dead code analysis followed by wasm-snip patterns for the unreachable
+/// functions
+///
+/// With `measure`, wasm-snip runs on a scratch copy to report the actual
+/// savings. JSON output holds the plan only.
+pub fn analyze_snip(
+    file: &Option<String>,
+    json: bool,
+    measure: bool,
+    no_cache: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    if !json {
+        analyze_wasm_binary(file, "dead", false, false, no_cache, filter, limit)?;
+    }
+    let wasm_path = existing_wasm_file(file, "--snip")?;
+
+    let planner = analyzer::SnipPlanner::new();
+    let mut plan = planner.plan(wasm_path)?;
+    if measure {
+        planner.measure(&mut plan)?;
+    }
+
+    if json {
+        println!("{}", redact::to_json_pretty(&plan)?);
+    } else {
+        print!(
+            "{}",
+            analyzer::format_snip_console_with_limit(&plan, limit)?
+        );
+    }
+    Ok(())
+}
+
+/// Whether `--format` asks for `wanted`, failing unless `mode` is one of the `modes` that print it
+fn format_in_mode(
+    format: Option<OutputFormat>,
+    wanted: OutputFormat,
+    mode: &str,
+    modes: &[&str],
+) -> Result<bool> {
+    if format != Some(wanted) {
+        return Ok(false);
+    }
+    if !modes.contains(&mode) {
+        let listed = match modes {
+            [.., last] if modes.len() > 1 => {
+                format!("{} or {}", modes[..modes.len() - 1].join(", "), last)
+            }
+            _ => modes.join(", "),
+        };
+        anyhow::bail!(
+            "--format {} is only supported with --mode {}",
+            wanted,
+            listed
+        );
+    }
+    Ok(true)
+}
+
+/// Print findings as a SARIF log for code scanning
+fn print_sarif(findings: Vec<analyzer::sarif::Finding>) -> Result<()> {
+    let log = analyzer::sarif::SarifLog::from_findings(findings);
+    println!("{}", redact::to_json_pretty(&log)?);
+    Ok(())
+}
+
+/// Twiggy analyzer with the configured thresholds, caching results unless
+/// `no_cache` and keeping at most `collect_limit` items
+fn wasm_analyzer(
+    wasm_file: &str,
+    project_root: &Path,
+    no_cache: bool,
+    collect_limit: Option<usize>,
+) -> Result<analyzer::TwiggyAnalyzer> {
+    let thresholds = crate::config::ConfigLoader::load(project_root)?
+        .analysis
+        .and_then(|a| a.thresholds)
+        .map(|t| analyzer::thresholds::RecommendationThresholds::from_settings(&t))
+        .unwrap_or_default();
+    let mut wasm_analyzer = analyzer::TwiggyAnalyzer::new(wasm_file).with_thresholds(thresholds);
+    if let Some(collect_limit) = collect_limit {
+        wasm_analyzer = wasm_analyzer.with_limit(collect_limit);
+    }
+    if no_cache {
+        return Ok(wasm_analyzer);
+    }
+    Ok(wasm_analyzer.with_cache(
+        crate::state::state_dir(project_root).join(analyzer::twiggy::cache::ANALYSIS_CACHE_DIR),
+    ))
+}
+
+/// Run the top, dominators, dead, monos, assets and deps analyses and merge
+/// their findings into one action plan
+///
+/// The WASM file is required. The assets and deps analyses need a Cargo
+/// project; when they fail, the plan notes it and lists the rest.
+pub fn analyze_all(
+    file: &Option<String>,
+    json: bool,
+    offline: bool,
+    no_cache: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for --mode all"))?;
+    if !Path::new(f).exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    if !json {
+        println!(
+            "{} {} Combined Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        println!("   File: {}", f);
+    }
+
+    let project_root = env::current_dir()?;
+    let wasm_analyzer = wasm_analyzer(f, &project_root, no_cache, None)?;
+    let suppressions = analyzer::suppressions::Suppressions::load(&project_root)?;
+    let mut suppressed = 0;
+    let mut builder = analyzer::action_plan::ActionPlanBuilder::new();
+    for mode in [
+        analyzer::AnalysisMode::Top,
+        analyzer::AnalysisMode::Dominators,
+        analyzer::AnalysisMode::Dead,
+        analyzer::AnalysisMode::Monos,
+    ] {
+        let mut results = wasm_analyzer.analyze(mode)?;
+        suppressed += suppressions.filter(&mut results.recommendations);
+        builder.add_analysis(&results);
+    }
+    match asset_detector(&project_root).and_then(|d| Ok(d.scan_project()?)) {
+        Ok(results) => builder.add_assets(&results),
+        Err(e) => builder.add_warning(format!("assets: {:#}", e)),
+    }
+    match dependency_analyzer(&project_root, offline).and_then(|(a, _)| Ok(a.analyze()?)) {
+        Ok(report) => builder.add_dependencies(&report),
+        Err(e) => builder.add_warning(format!("deps: {:#}", e)),
+    }
+    let mut plan = builder.build();
+    filter.apply(&mut plan.actions);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&plan)?);
+    } else {
+        print!(
+            "{}",
+            analyzer::action_plan_report::format_console_report_with_limit(&plan, limit)?
+        );
+        print_suppressed(suppressed);
+    }
+
+    Ok(())
+}
+
+/// Size health score from the twiggy, assets, deps and fmt analyses
+///
+/// The WASM file is required. Categories whose analysis fails are left out
+/// of the score with a warning. With `min_score`, fails after printing when
+/// the score is lower, so CI can gate on it.
+pub fn analyze_score(
+    file: &Option<String>,
+    json: bool,
+    offline: bool,
+    no_cache: bool,
+    min_score: Option<u8>,
+    filter: &ItemFilter,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for --mode score"))?;
+    if !Path::new(f).exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    if !json {
+        println!(
+            "{} {} Size Health Score",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        println!("   File: {}", f);
+    }
+
+    let project_root = env::current_dir()?;
+    let wasm_analyzer = wasm_analyzer(f, &project_root, no_cache, None)?;
+    let mut builder = analyzer::health_score::HealthScoreBuilder::new();
+    for mode in [analyzer::AnalysisMode::Dead, analyzer::AnalysisMode::Monos] {
+        builder.add_analysis(&wasm_analyzer.analyze(mode)?);
+    }
+    match asset_detector(&project_root).and_then(|d| Ok(d.scan_project()?)) {
+        Ok(results) => builder.add_assets(&results),
+        Err(e) => builder.add_warning(format!("assets: {:#}", e)),
+    }
+    match dependency_analyzer(&project_root, offline).and_then(|(a, _)| Ok(a.analyze()?)) {
+        Ok(report) => builder.add_dependencies(&report),
+        Err(e) => builder.add_warning(format!("deps: {:#}", e)),
+    }
+    match analyzer::FmtBloatAnalyzer::new().analyze(Path::new(f)) {
+        Ok(report) => builder.add_fmt(&report),
+        Err(e) => builder.add_warning(format!("fmt: {:#}", e)),
+    }
+    let mut health = builder.build();
+    filter.apply(&mut health.categories);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&health)?);
+    } else {
+        print!(
+            "{}",
+            analyzer::health_score_report::format_console_report(&health)?
+        );
+    }
+
+    if let Some(min) = min_score {
+        if health.score < min {
+            anyhow::bail!(
+                "Size health score {} is below --min-score {}",
+                health.score,
+                min
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Note how many recommendations `.wasm-slim-ignore` hid
+fn print_suppressed(count: usize) {
+    if count > 0 {
+        println!(
+            "   {} {} recommendation(s) suppressed by {}",
+            style("→").dim(),
+            count,
+            analyzer::suppressions::IGNORE_FILE
+        );
+    }
+}
+
+/// Cross-check a WASM artifact against the active `.wasm-slim.toml`
+///
+/// Flags settings the build silently failed to apply: a `name` section or
+/// DWARF despite `strip = true`, sections wasm-opt should have removed,
+/// wasm-opt features the code was not compiled with, or a debug artifact.
+pub fn analyze_config_check(file: &Option<String>, json: bool, filter: &ItemFilter) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for config-check mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let checker = config_checker()?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No {} found; run `wasm-slim init` to create one",
+            crate::config::CONFIG_FILE_NAME
+        )
+    })?;
+    let mut results = checker.check(wasm_path)?;
+    filter.apply(&mut results.mismatches);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&results)?);
+    } else {
+        println!(
+            "{} {} Config Cross-Check",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!("{}", analyzer::format_config_check_console(&results)?);
+    }
+
+    Ok(())
+}
+
+/// Break a WASM file's size down per crate
+///
+/// Symbols are mapped to crates by their demangled path and matched to
+/// packages in the project's WASM dependency graph.
+pub fn analyze_crate_sizes(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for crates mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut report = analyzer::CrateSizeAnalyzer::new(env::current_dir()?).analyze(wasm_path)?;
+    filter.apply(&mut report.crates);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Crate Size Attribution",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_crate_sizes_console_with_limit(&report, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Attribute a debug build's code size to source files and line ranges
+///
+/// Reads the DWARF line tables, so the module must be built with debug info
+/// and not yet optimized or stripped.
+pub fn analyze_source_files(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for sources mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut report = analyzer::SourceMapAnalyzer::new(env::current_dir()?).analyze(wasm_path)?;
+    filter.apply(&mut report.files);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Source File Attribution",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_source_map_console_with_limit(&report, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Detect reactive frameworks included more than once in the WASM graph
+///
+/// With a WASM file, each framework's size is measured from its symbols.
+pub fn analyze_frameworks(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let wasm_path = file.as_deref().map(Path::new);
+    if let Some(path) = wasm_path.filter(|p| !p.exists()) {
+        anyhow::bail!("WASM file not found: {}", path.display());
+    }
+
+    let mut report = analyzer::FrameworkAnalyzer::new(env::current_dir()?).analyze(wasm_path)?;
+    filter.apply(&mut report.frameworks);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Framework Inclusion Check",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_frameworks_console_with_limit(&report, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Find function bodies that are byte-identical or differ only in immediates
+///
+/// Each group comes with the bytes merging it would save.
+pub fn analyze_duplicates(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for duplicates mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut report = analyzer::DuplicateFunctionAnalyzer::new().analyze(wasm_path)?;
+    filter.apply(&mut report.groups);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Duplicate Function Detection",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_duplicates_console_with_limit(&report, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Bucket a WASM file's function sizes and list the outliers
+///
+/// Shows whether the code size is spread over many small functions or sits
+/// in a few large ones.
+pub fn analyze_function_sizes(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for histogram mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut report = analyzer::FunctionSizeAnalyzer::new().analyze(wasm_path)?;
+    filter.apply(&mut report.outliers);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Function Size Histogram",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_histogram_console_with_limit(&report, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Decode a WASM file's data segments and name what they contain
+///
+/// Blobs are matched against the files embedded with `include_bytes!` and
+/// `include_str!` in the current project.
+pub fn analyze_data_segments(
+    file: &Option<String>,
+    json: bool,
+    sarif: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for data mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let project_root = env::current_dir()?;
+    let mut report = analyzer::DataSegmentAnalyzer::new(&project_root).analyze(wasm_path)?;
+    filter.apply(&mut report.segments);
+
+    if sarif {
+        let thresholds = crate::config::ConfigLoader::load(&project_root)?
+            .analysis
+            .and_then(|a| a.thresholds)
+            .map(|t| analyzer::thresholds::RecommendationThresholds::from_settings(&t))
+            .unwrap_or_default()
+            .for_bundle(report.module_bytes);
+        let wasm_path = wasm_path.canonicalize().unwrap_or(wasm_path.to_path_buf());
+        print_sarif(analyzer::sarif::data_segment_findings(
+            &report,
+            &wasm_path,
+            &project_root,
+            thresholds.large_data_segment_bytes,
+        ))?;
+    } else if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Data Segment Inspection",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_data_segments_console_with_limit(&report, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Measure what a WASM file does at instantiation
+///
+/// Covers the start function and init exports, data segment copying and
+/// the initial memory size.
+pub fn analyze_startup(file: &Option<String>, json: bool, filter: &ItemFilter) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for startup mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut report = analyzer::StartupAnalyzer::new().analyze(wasm_path)?;
+    filter.apply(&mut report.entries);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Instantiation Cost Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!("{}", analyzer::format_startup_console(&report)?);
+    }
+
+    Ok(())
+}
+
+/// Check the declared memory and table sizes of a WASM file
+///
+/// Compares them with the static layout and measures the zeros shipped in
+/// active data.
+pub fn analyze_memory(file: &Option<String>, json: bool, filter: &ItemFilter) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for memory mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut report = analyzer::MemoryLayoutAnalyzer::new().analyze(wasm_path)?;
+    filter.apply(&mut report.memories);
+    filter.apply(&mut report.tables);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Memory and Table Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!("{}", analyzer::format_memory_layout_console(&report)?);
+    }
+
+    Ok(())
+}
+
+/// Find size-costly data structure patterns in a WASM file
+///
+/// Each pattern found comes with targeted advice.
+pub fn analyze_data_structures(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for data-structures mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut report = analyzer::DataStructureAnalyzer::new().analyze(wasm_path)?;
+    filter.apply(&mut report.findings);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Data Structure Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_data_structures_console_with_limit(&report, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// Classify the string constants of a WASM file
+///
+/// Panic messages and source paths are totalled as the possible saving.
+pub fn analyze_strings(
+    file: &Option<String>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for strings mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut report = analyzer::StringAnalyzer::new().analyze(wasm_path)?;
+    filter.apply(&mut report.groups);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} String Literal Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_strings_console_with_limit(&report, limit)?
+        );
+    }
+
+    Ok(())
+}
+
+/// List a WASM file's custom sections, optionally stripping the removable ones
+///
+/// With `fix`, the recommended sections are stripped in place with wasm-opt;
+/// `dry_run` prints the command instead.
+pub fn analyze_custom_sections(
+    file: &Option<String>,
+    fix: bool,
+    dry_run: bool,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for custom-sections mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let analyzer = analyzer::CustomSectionAnalyzer::new();
+    let mut report = analyzer.analyze(wasm_path)?;
+    // Only the listing is filtered; stripping follows the recommendations
+    filter.apply(&mut report.sections);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Custom Section Analysis",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_custom_sections_console_with_limit(&report, limit)?
+        );
+    }
+
+    if (fix || dry_run) && !report.recommendations.is_empty() {
+        let outcome = analyzer.strip(wasm_path, &report, dry_run)?;
+        match outcome.after_bytes {
+            _ if json => {}
+            None => println!(
+                "\n   {} Would run: {} {}",
+                WRENCH,
+                outcome.tool,
+                outcome.args.join(" ")
+            ),
+            Some(after) => println!(
+                "\n   {} Stripped with {}: {} → {}",
+                WRENCH,
+                outcome.tool,
+                crate::fmt::format_bytes(outcome.before_bytes),
+                style(crate::fmt::format_bytes(after)).green().bold()
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a WASM file for leftover DWARF, names and symbol tables
+///
+/// With `fix`, the DWARF and name sections are stripped in place; `dry_run`
+/// prints the command instead.
+pub fn analyze_debug_info(
+    file: &Option<String>,
+    fix: bool,
+    dry_run: bool,
+    json: bool,
+    filter: &ItemFilter,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for debug-info mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let checker = analyzer::DebugInfoChecker::new();
+    let mut report = checker.check(wasm_path)?;
+    filter.apply(&mut report.sections);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Debug Info Check",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!("{}", analyzer::format_debug_info_console(&report)?);
+    }
+
+    if (fix || dry_run) && report.strippable_bytes > 0 {
+        let outcome = checker.strip(wasm_path, &report, dry_run)?;
+        match outcome.after_bytes {
+            _ if json => {}
+            None => println!(
+                "\n   {} Would run: {} {}",
+                WRENCH,
+                outcome.tool,
+                outcome.args.join(" ")
+            ),
+            Some(after) => println!(
+                "\n   {} Stripped with {}: {} → {}",
+                WRENCH,
+                outcome.tool,
+                crate::fmt::format_bytes(outcome.before_bytes),
+                style(crate::fmt::format_bytes(after)).green().bold()
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// wasm-metadce graph for the live exports, inside the state directory
+const METADCE_GRAPH: &str = "metadce-graph.json";
+
+/// Report code only reachable from exports the JavaScript never calls
+///
+/// `live_exports` lists the exports that are called, either as names or as
+/// a single file with one name per line. Without it, each export's
+/// exclusive code is listed. When exports are unused, a wasm-metadce graph
+/// keeping only the live ones is written to `.wasm-slim/metadce-graph.json`.
+pub fn analyze_reachability(
+    file: &Option<String>,
+    live_exports: Option<&[String]>,
+    json: bool,
+    filter: &ItemFilter,
+    limit: ItemLimit,
+) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for reachability mode"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let mut analyzer = analyzer::ReachabilityAnalyzer::new();
+    if let Some(live) = live_exports {
+        analyzer = analyzer.with_live_exports(read_live_exports(live)?);
+    }
+    let mut report = analyzer.analyze(wasm_path)?;
+    filter.apply(&mut report.exports);
+    filter.apply(&mut report.candidates);
+
+    let cwd = env::current_dir()?;
+    let graph_path = crate::state::state_dir(&cwd).join(METADCE_GRAPH);
+    let graph_path = graph_path.strip_prefix(&cwd).unwrap_or(&graph_path);
+    if report.dead_exports().next().is_some() {
+        if let Some(dir) = graph_path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(graph_path, redact::to_json_pretty(&report.metadce_graph())?)
+            .with_context(|| format!("Failed to write {}", graph_path.display()))?;
+    }
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} {} Export Reachability Analysis",
+        MICROSCOPE,
+        style("wasm-slim").bold()
+    );
+    print!(
+        "{}",
+        analyzer::format_reachability_console_with_limit(&report, limit)?
+    );
+
+    if !report.candidates.is_empty() {
+        println!(
+            "
+{}",
+            style("To remove them:").bold()
+        );
+        println!(
+            "   {} wasm-metadce --graph-file {} {} -o {}",
+            WRENCH,
+            graph_path.display(),
+            f,
+            f
+        );
+        let snip_args = report.wasm_snip_args(f, f);
+        if snip_args.len() > 3 {
+            println!(
+                "   {} or, keeping the exports: wasm-snip {} && wasm-opt -Oz {} -o {}",
+                WRENCH,
+                snip_args.join(" "),
+                f,
+                f
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Live export names, read from a file when a single existing path is given
+fn read_live_exports(live: &[String]) -> Result<Vec<String>> {
+    match live {
+        [path] if Path::new(path).is_file() => {
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path))?;
+            Ok(contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect())
+        }
+        _ => Ok(live.to_vec()),
+    }
+}
+
+/// Checker for the project's config, if the project has a config file
+fn config_checker() -> Result<Option<analyzer::ConfigChecker>> {
+    let project_root = env::current_dir()?;
+    if !crate::config::ConfigLoader::exists(&project_root) {
+        return Ok(None);
+    }
+    let config = crate::config::ConfigLoader::load(&project_root)?;
+    let template = crate::config::TemplateResolver::resolve_in(&config, &project_root)?;
+    Ok(Some(analyzer::ConfigChecker::new(template)))
+}
+
+/// Growth limit for `--baseline` when `[symbol_budget]` is not configured
+const DEFAULT_MAX_SYMBOL_GROWTH_PERCENT: f64 = 10.0;
+
+/// Save a WASM file's symbol sizes as a baseline for `--baseline` and `build --check`
+pub fn save_symbol_baseline(file: &Option<String>, path: &Path) -> Result<()> {
+    let wasm_path = existing_wasm_file(file, "--save-baseline")?;
+    let baseline = analyzer::SymbolBaseline::capture(wasm_path)?;
+    baseline.save(path)?;
+    println!(
+        "{} Saved {} symbols to {}",
+        style("✓").green(),
+        baseline.symbols.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Compare a WASM file's symbols against a saved baseline
+///
+/// Uses the `[symbol_budget]` limits, or a 10% growth limit when the
+/// section is missing. Symbols over the limits are reported, not failed;
+/// `build --check` enforces the budget.
+pub fn check_symbol_baseline(
+    file: &Option<String>,
+    path: &Path,
+    json: bool,
+    limit: ItemLimit,
+) -> Result<()> {
+    let wasm_path = existing_wasm_file(file, "--baseline")?;
+    let budget = crate::config::ConfigLoader::load(&env::current_dir()?)?
+        .symbol_budget
+        .unwrap_or(crate::config::file::SymbolBudget {
+            max_growth_percent: Some(DEFAULT_MAX_SYMBOL_GROWTH_PERCENT),
+            ..Default::default()
+        });
+
+    let baseline = analyzer::SymbolBaseline::load(path)?;
+    let current = analyzer::SymbolBaseline::capture(wasm_path)?;
+    let report = baseline.check(&current, &budget);
+
+    if json {
+        println!("{}", redact::to_json_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} Symbol Baseline Comparison",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        print!(
+            "{}",
+            analyzer::format_symbol_budget_console_with_limit(&report, limit)?
+        );
+    }
+    Ok(())
+}
+
+/// The WASM file an option needs, checked to exist
+fn existing_wasm_file<'a>(file: &'a Option<String>, option: &str) -> Result<&'a Path> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for {}", option))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+    Ok(wasm_path)
+}
+
+/// Render a WASM file as a self-contained HTML treemap on stdout
+///
+/// Items are grouped per crate, then per function; redirect the output to
+/// a file and open it in a browser.
+pub fn export_treemap_html(file: &Option<String>) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for --format html"))?;
+    let wasm_path = Path::new(f);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let items = analyzer::TwiggyAnalyzer::new(f).sized_items()?;
+    let name = wasm_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| f.clone());
+    let root = analyzer::build_treemap(&name, &items);
+    print!("{}", analyzer::format_treemap_html(&root)?);
+    Ok(())
+}
+
+/// Export the dominator tree in collapsed-stack format
+///
+/// The output can be rendered with `inferno-flamegraph` or loaded into
+/// speedscope.
+pub fn export_dominators_folded(file: &Option<String>, output: &Path) -> Result<()> {
+    let f = file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("WASM file required for --folded export"))?;
+    if !Path::new(f).exists() {
+        anyhow::bail!("WASM file not found: {}", f);
+    }
+
+    let stacks = analyzer::TwiggyAnalyzer::new(f).dominators_folded()?;
+    write_folded_output(output, &stacks.to_collapsed())
+}
+
+/// Write the dependency graph annotated with per-crate sizes
+///
+/// Sizes are measured in the WASM file when one is given and estimated
+/// otherwise. A `.json` path gets JSON, anything else Graphviz DOT; `-`
+/// prints DOT to stdout.
+pub fn export_dependency_graph(file: &Option<String>, output: &Path, offline: bool) -> Result<()> {
+    let wasm_file = file.as_deref().map(Path::new);
+    if let Some(f) = wasm_file.filter(|f| !f.exists()) {
+        anyhow::bail!("WASM file not found: {}", f.display());
+    }
+
+    let project_root = env::current_dir()?;
+    let (_, heavy_deps) = dependency_analyzer(&project_root, offline)?;
+    let graph = analyzer::DepGraphAnalyzer::new(&project_root)
+        .with_heavy_deps(heavy_deps)
+        .with_offline(offline)
+        .analyze(wasm_file)?;
+
+    let is_json = output.extension().is_some_and(|ext| ext == "json");
+    let contents = if is_json {
+        redact::to_json_pretty(&graph)?
+    } else {
+        graph.to_dot()
+    };
+    if output == Path::new("-") {
+        print!("{}", contents);
+        return Ok(());
+    }
+
+    std::fs::write(output, contents)
+        .with_context(|| format!("Failed to write dependency graph to {}", output.display()))?;
+    println!(
+        "{} Wrote {} packages and {} edges to {}",
+        style("✓").green(),
+        graph.nodes.len(),
+        graph.edges.len(),
+        output.display()
+    );
+    for warning in &graph.warnings {
+        println!("   {} {}", style("⚠").yellow(), warning);
+    }
+    if !is_json {
+        println!(
+            "   {} Render with: dot -Tsvg {} -o deps.svg",
+            style("→").dim(),
+            output.display()
+        );
+    }
+    Ok(())
+}
+
+/// Write collapsed stacks to a file, or to stdout when the path is `-`
+pub(crate) fn write_folded_output(output: &Path, contents: &str) -> Result<()> {
+    if output == Path::new("-") {
+        print!("{}", contents);
+        return Ok(());
+    }
+
+    std::fs::write(output, contents)
+        .with_context(|| format!("Failed to write folded stacks to {}", output.display()))?;
+    println!(
+        "{} Wrote {} stacks to {}",
+        style("✓").green(),
+        contents.lines().count(),
+        output.display()
+    );
+    println!(
+        "   {} Render with: inferno-flamegraph < {} > flamegraph.svg",
+        style("→").dim(),
+        output.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmd_analyze_unknown_mode() {
+        let result = cmd_analyze(&None, "unknown_mode", false, false, false, false);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown analysis mode"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_valid_modes() {
+        let modes = vec![
+            "assets",
+            "deps",
+            "unused-deps",
+            "no-std",
+            "bloat",
+            "fmt",
+            "features",
+            "panics",
+            "serde",
+            "llvm-lines",
+            "allocator",
+            "vendor-review",
+            "wasm-bindgen-abi",
+            "js-glue",
+            "pkg",
+            "config-check",
+            "crates",
+            "sources",
+            "frameworks",
+            "duplicates",
+            "histogram",
+            "data",
+            "data-structures",
+            "startup",
+            "memory",
+            "strings",
+            "custom-sections",
+            "debug-info",
+            "reachability",
+            "top",
+            "dominators",
+            "dead",
+            "monos",
+            "score",
+        ];
+
+        for mode in modes {
+            // These will fail due to missing tools or project setup, but should route correctly
+            let result = cmd_analyze(&None, mode, false, false, false, false);
+            // Just verify the error is not "Unknown analysis mode"
+            if let Err(e) = result {
+                assert!(!e.to_string().contains("Unknown analysis mode"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmd_analyze_wasm_binary_modes_require_file() {
+        let wasm_modes = vec![
+            "crates",
+            "sources",
+            "duplicates",
+            "histogram",
+            "data",
+            "data-structures",
+            "startup",
+            "memory",
+            "strings",
+            "custom-sections",
+            "debug-info",
+            "reachability",
+            "top",
+            "dominators",
+            "dead",
+            "monos",
+            "all",
+            "score",
+        ];
+
+        for mode in wasm_modes {
+            let result = cmd_analyze(&None, mode, false, false, false, false);
+            assert!(result.is_err());
+            let error_msg = result.unwrap_err().to_string();
+            assert!(error_msg.contains("WASM file required") || error_msg.contains("twiggy"));
+        }
+    }
+
+    #[test]
+    fn test_cmd_analyze_folded_requires_dominators_mode() {
+        let options = AnalyzeOptions {
+            folded: Some(PathBuf::from("-")),
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "top", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--folded is only supported with --mode dominators"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_graph_requires_deps_mode() {
+        let options = AnalyzeOptions {
+            graph: Some(PathBuf::from("deps.dot")),
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "top", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--graph is only supported with --mode deps"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_min_score_requires_score_mode() {
+        let options = AnalyzeOptions {
+            min_score: Some(80),
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "all", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--min-score is only supported with --mode score"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_limit_requires_binary_mode() {
+        let options = AnalyzeOptions {
+            filter: ItemFilter {
+                collect_limit: Some(100),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "bloat", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--limit is only supported with --mode top, dominators, dead or monos"));
+
+        let options = AnalyzeOptions {
+            filter: ItemFilter {
+                collect_limit: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "top", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--limit must be at least 1"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_snip_requires_dead_mode() {
+        let options = AnalyzeOptions {
+            snip: true,
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "top", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--snip is only supported with --mode dead"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_csv_requires_vendor_review_mode() {
+        let options = AnalyzeOptions {
+            format: Some(OutputFormat::Csv),
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "deps", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--format csv is only supported with --mode vendor-review"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_live_exports_requires_reachability_mode() {
+        let options = AnalyzeOptions {
+            live_exports: Some(vec!["greet".to_string()]),
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "dead", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--live-exports is only supported with --mode reachability"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_measure_requires_features_mode() {
+        let options = AnalyzeOptions {
+            measure: true,
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "deps", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--measure is only supported with --mode features"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_apply_requires_assets_mode() {
+        let options = AnalyzeOptions {
+            apply: true,
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "deps", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--apply is only supported with --mode assets"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_compress_requires_assets_mode() {
+        let options = AnalyzeOptions {
+            compress: true,
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "bloat", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--compress is only supported with --mode assets"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_detailed_requires_panics_mode() {
+        let options = AnalyzeOptions {
+            detailed: true,
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "assets", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--detailed is only supported with --mode panics"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_format_html_requires_file() {
+        let options = AnalyzeOptions {
+            format: Some(OutputFormat::Html),
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "top", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("WASM file required for --format html"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_batch_rejects_unsupported_format() {
+        let options = AnalyzeOptions {
+            batch: Some(PathBuf::from("artifacts.txt")),
+            format: Some(OutputFormat::Sarif),
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "deps", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("--format sarif is not supported with --batch"));
+    }
+
+    #[test]
+    fn test_cmd_analyze_baselines_require_file() {
+        let mut options = AnalyzeOptions {
+            save_baseline: Some(PathBuf::from("baseline.json")),
+            ..Default::default()
+        };
+        let result = cmd_analyze_with_options(&None, "top", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("WASM file required for --save-baseline"));
+
+        options.save_baseline = None;
+        options.baseline = Some(PathBuf::from("baseline.json"));
+        let result = cmd_analyze_with_options(&Some("missing.wasm".to_string()), "top", &options);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("WASM file not found: missing.wasm"));
+    }
+
+    #[test]
+    fn test_analyze_dependencies_top_limits_listing_but_fixes_every_issue() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+        std::fs::write(
+            &cargo_toml,
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nchrono = \"0.4\"\nregex = \"1\"\n",
+        )
+        .unwrap();
+        let options = AnalyzeOptions {
+            fix: true,
+            offline: true,
+            filter: ItemFilter {
+                top: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        analyze_dependencies(temp_dir.path(), false, false, &options).unwrap();
+
+        // --top 1 lists only the chrono issue, yet regex is fixed too
+        let manifest = std::fs::read_to_string(&cargo_toml).unwrap();
+        let doc: toml_edit::DocumentMut = manifest.parse().unwrap();
+        assert!(!doc["dependencies"]["chrono"].is_str(), "{}", manifest);
+        assert_eq!(
+            doc["dependencies"]["regex"]["default-features"].as_bool(),
+            Some(false),
+            "{}",
+            manifest
+        );
+    }
+
+    #[test]
+    fn test_analyze_batch_with_empty_list_fails() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let list = temp_dir.path().join("artifacts.txt");
+        std::fs::write(&list, "# nothing yet\n\n").unwrap();
+
+        let result = analyze_batch(
+            &list,
+            Some(2),
+            Some(OutputFormat::Json),
+            None,
+            ItemLimit::Default,
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("No artifacts listed"));
+    }
+
+    #[test]
+    fn test_write_folded_output_writes_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let output = temp_dir.path().join("stacks.folded");
+
+        write_folded_output(&output, "a 1\na;b 2\n").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "a 1\na;b 2\n");
+    }
+
+    #[test]
+    fn test_cmd_analyze_with_file_parameter() {
+        let file = Some("test.wasm".to_string());
+        let result = cmd_analyze(&file, "top", false, false, false, false);
+        // Should fail on twiggy check or file not found, not on missing file parameter
+        if let Err(e) = result {
+            assert!(!e.to_string().contains("WASM file required"));
+        }
+    }
+
+    #[test]
+    fn test_analyze_wasm_binary_requires_file() {
+        let result = analyze_wasm_binary(
+            &None,
+            "top",
+            false,
+            false,
+            true,
+            &ItemFilter::default(),
+            ItemLimit::Default,
+        );
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -398,7 +2964,15 @@ mod tests {
     #[test]
     fn test_analyze_wasm_binary_with_file() {
         let file = Some("/path/to/test.wasm".to_string());
-        let result = analyze_wasm_binary(&file, "top", false);
+        let result = analyze_wasm_binary(
+            &file,
+            "top",
+            false,
+            false,
+            true,
+            &ItemFilter::default(),
+            ItemLimit::Default,
+        );
         // Should fail on twiggy check or file existence, not on missing file
         if let Err(e) = result {
             assert!(!e.to_string().contains("WASM file required"));
@@ -411,7 +2985,15 @@ mod tests {
         let file = Some("test.wasm".to_string());
 
         for mode in modes {
-            let result = analyze_wasm_binary(&file, mode, false);
+            let result = analyze_wasm_binary(
+                &file,
+                mode,
+                false,
+                false,
+                true,
+                &ItemFilter::default(),
+                ItemLimit::Default,
+            );
             // These will fail but should parse the mode correctly
             if let Err(e) = result {
                 assert!(!e.to_string().contains("WASM file required"));
@@ -429,6 +3011,7 @@ mod tests {
         assert!(error.contains("bloat"));
         assert!(error.contains("features"));
         assert!(error.contains("panics"));
+        assert!(error.contains("allocator"));
         assert!(error.contains("top"));
         assert!(error.contains("dominators"));
         assert!(error.contains("dead"));
@@ -8,6 +8,7 @@ use console::style;
 use std::env;
 
 use crate::cmd::workflow::BuildWorkflow;
+use crate::config::ConfigLoader;
 use crate::fmt::{format_bytes, CHECKMARK, ROCKET};
 
 /// Main build command handler (presentation layer)
@@ -21,26 +22,36 @@ use crate::fmt::{format_bytes, CHECKMARK, ROCKET};
 /// use wasm_slim::cmd::build::cmd_build;
 ///
 /// // Build with default settings
-/// cmd_build(false, false, false, None)?;
+/// cmd_build(false, false, false, false, false, None)?;
 ///
 /// // Dry-run to preview changes
-/// cmd_build(true, false, false, None)?;
+/// cmd_build(true, false, false, false, false, None)?;
 ///
 /// // Build with JSON output for CI/CD
-/// cmd_build(false, false, true, None)?;
+/// cmd_build(false, false, true, false, false, None)?;
+///
+/// // Continue a failed build from the stage that failed
+/// cmd_build(false, false, false, true, false, None)?;
+///
+/// // Ship a small binary with its DWARF in a `.debug.wasm` sidecar
+/// cmd_build(false, false, false, false, true, None)?;
 /// # Ok::<(), anyhow::Error>(())
 /// ```
 pub fn cmd_build(
     dry_run: bool,
     check: bool,
     json_output: bool,
+    resume: bool,
+    split_debug: bool,
     target_dir: Option<&str>,
 ) -> Result<()> {
     println!("{} {} Build Pipeline", ROCKET, style("wasm-slim").bold());
     println!();
 
     let project_root = env::current_dir()?;
-    let workflow = BuildWorkflow::new(&project_root);
+    let workflow = BuildWorkflow::new(&project_root)
+        .with_resume(resume)
+        .with_split_debug(split_debug);
 
     // Execute workflow
     let result = workflow.execute(dry_run, check, target_dir)?;
@@ -52,11 +63,22 @@ pub fn cmd_build(
         present_cargo_changes(&result.cargo_changes);
     }
     present_build_results(&result.metrics);
+    if let Some(regression) = &result.regression {
+        regression.print();
+    }
+    if let (Some(report), Some(output)) = (&result.debug_info, &result.metrics.output) {
+        present_debug_info(report, output);
+    }
     present_budget_check(result.budget_check_passed, result.budget_threshold);
 
+    let config = ConfigLoader::override_path(&project_root);
+    if let Some(path) = &config {
+        println!("   Config: {}", style(path.display()).dim());
+    }
+
     // JSON output for CI/CD
     if json_output {
-        present_json_report(&result.metrics)?;
+        present_json_report(&result.metrics, config.as_deref())?;
     }
 
     Ok(())
@@ -104,6 +126,28 @@ fn present_build_results(metrics: &crate::pipeline::SizeMetrics) {
     println!();
 }
 
+/// Present debug info left in the artifact, with the command that strips it
+fn present_debug_info(report: &crate::analyzer::DebugInfoReport, output: &std::path::Path) {
+    let kinds: Vec<String> = report
+        .bytes_by_kind()
+        .iter()
+        .map(|(kind, bytes)| format!("{} {}", kind.label(), format_bytes(*bytes)))
+        .collect();
+    println!(
+        "   {} {} of debug info left in the artifact ({})",
+        style("⚠").yellow(),
+        style(format_bytes(report.leftover_bytes)).yellow().bold(),
+        kinds.join(", ")
+    );
+    if report.strippable_bytes > 0 {
+        println!(
+            "     Strip it: wasm-slim analyze {} --mode debug-info --fix",
+            output.display()
+        );
+    }
+    println!();
+}
+
 /// Present budget check results
 fn present_budget_check(passed: Option<bool>, threshold: Option<u64>) {
     if let (Some(passed), Some(threshold)) = (passed, threshold) {
@@ -118,8 +162,11 @@ fn present_budget_check(passed: Option<bool>, threshold: Option<u64>) {
 }
 
 /// Present JSON report for CI/CD systems
-fn present_json_report(metrics: &crate::pipeline::SizeMetrics) -> Result<()> {
-    let report = serde_json::json!({
+fn present_json_report(
+    metrics: &crate::pipeline::SizeMetrics,
+    config: Option<&std::path::Path>,
+) -> Result<()> {
+    let mut report = serde_json::json!({
         "final_size": metrics.after_bytes,
         "original_size": metrics.before_bytes,
         "reduction_bytes": metrics.before_bytes.saturating_sub(metrics.after_bytes),
@@ -129,9 +176,12 @@ fn present_json_report(metrics: &crate::pipeline::SizeMetrics) -> Result<()> {
             .unwrap_or_default()
             .as_secs(),
     });
+    if let Some(path) = config {
+        report["config"] = serde_json::json!(path.display().to_string());
+    }
 
     println!();
-    println!("{}", serde_json::to_string_pretty(&report)?);
+    println!("{}", crate::redact::to_json_pretty(&report)?);
     println!();
 
     Ok(())
@@ -175,6 +225,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 2000,
             after_bytes: 1000,
+            ..Default::default()
         };
         present_build_results(&metrics);
     }
@@ -184,10 +235,20 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 1000,
             after_bytes: 1000,
+            ..Default::default()
         };
         present_build_results(&metrics);
     }
 
+    #[test]
+    fn test_present_debug_info_with_dwarf() {
+        let mut module = b"\0asm\x01\0\0\0".to_vec();
+        module.extend([0, 8, 7]);
+        module.extend(b".debug_");
+        let report = crate::analyzer::debug_info::check_module(&module).unwrap();
+        present_debug_info(&report, std::path::Path::new("pkg/app_bg.wasm"));
+    }
+
     #[test]
     fn test_present_budget_check_with_passed() {
         present_budget_check(Some(true), Some(2000));
@@ -203,8 +264,9 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 2000,
             after_bytes: 1500,
+            ..Default::default()
         };
-        let result = present_json_report(&metrics);
+        let result = present_json_report(&metrics, None);
         assert!(result.is_ok());
     }
 
@@ -212,9 +274,9 @@ mod tests {
     fn test_format_bytes_via_shared_module() {
         use crate::fmt::format_bytes;
         assert_eq!(format_bytes(512), "512 B");
-        assert_eq!(format_bytes(1024), "1.00 KB");
-        assert_eq!(format_bytes(1536), "1.50 KB");
-        assert_eq!(format_bytes(1_048_576), "1.00 MB");
-        assert_eq!(format_bytes(2_621_440), "2.50 MB");
+        assert_eq!(format_bytes(1024), "1.00 KiB");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(1_048_576), "1.00 MiB");
+        assert_eq!(format_bytes(2_621_440), "2.50 MiB");
     }
 }
@@ -0,0 +1,322 @@
+//! Clean command implementation
+//!
+//! Handles `wasm-slim clean`, which removes the state wasm-slim leaves in a
+//! project: the remote template, downloaded WASM and analysis caches, Cargo.toml
+//! backups beyond the newest few, interrupted report staging directories and
+//! the scratch builds and checkpoints under `.wasm-slim/scratch/`. Build
+//! history, symbol baselines, benchmarks and archived reports are records, not
+//! caches, and are never touched.
+
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::fmt::{format_bytes, CHECKMARK};
+
+/// Backups kept per file by default
+pub const DEFAULT_KEEP_BACKUPS: usize = 3;
+
+/// Options for `wasm-slim clean`
+#[derive(Debug, Clone)]
+pub struct CleanOptions {
+    /// List what would be removed without removing it
+    pub dry_run: bool,
+    /// Also remove cargo's `target/wasm32-*` build directories
+    pub targets: bool,
+    /// Newest backups to keep per file (0 removes all)
+    pub keep_backups: usize,
+}
+
+impl Default for CleanOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            targets: false,
+            keep_backups: DEFAULT_KEEP_BACKUPS,
+        }
+    }
+}
+
+/// A file or directory `clean` removes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CleanEntry {
+    /// Path to remove
+    pub path: PathBuf,
+    /// What the path holds
+    pub kind: &'static str,
+    /// Bytes reclaimed by removing it
+    pub size_bytes: u64,
+}
+
+/// Collect everything `clean` would remove from `project_root`
+pub fn plan_clean(project_root: &Path, options: &CleanOptions) -> Result<Vec<CleanEntry>> {
+    let state_dir = crate::state::state_dir(project_root);
+    let target_dir = project_root.join("target");
+    let mut entries = Vec::new();
+
+    let mut add = |path: PathBuf, kind: &'static str| -> Result<()> {
+        if path.exists() {
+            let size_bytes = disk_usage(&path)?;
+            entries.push(CleanEntry {
+                path,
+                kind,
+                size_bytes,
+            });
+        }
+        Ok(())
+    };
+
+    add(state_dir.join("templates"), "remote template cache")?;
+    add(state_dir.join("downloads"), "downloaded WASM cache")?;
+    add(
+        state_dir.join(crate::analyzer::twiggy::cache::ANALYSIS_CACHE_DIR),
+        "analysis result cache",
+    )?;
+    for backup in stale_backups(&state_dir.join("backups"), options.keep_backups)? {
+        add(backup, "Cargo.toml backup")?;
+    }
+    for staging in children(&state_dir.join("reports"))?
+        .into_iter()
+        .filter(|path| file_name(path).starts_with(".staging-"))
+    {
+        add(staging, "interrupted report staging")?;
+    }
+    add(
+        state_dir.join(crate::state::SCRATCH_DIR_NAME),
+        "scratch builds and checkpoints",
+    )?;
+    if options.targets {
+        for dir in children(&target_dir)?
+            .into_iter()
+            .filter(|path| path.is_dir() && file_name(path).starts_with("wasm32-"))
+        {
+            add(dir, "cargo WASM target directory")?;
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Remove wasm-slim caches and stale state from the current project
+///
+/// # Examples
+///
+/// ```no_run
+/// use wasm_slim::cmd::clean::{cmd_clean, CleanOptions};
+///
+/// // Preview what would be removed
+/// cmd_clean(&CleanOptions {
+///     dry_run: true,
+///     ..Default::default()
+/// })?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn cmd_clean(options: &CleanOptions) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let entries = plan_clean(&project_root, options)?;
+
+    if entries.is_empty() {
+        println!("{} Nothing to clean", CHECKMARK);
+        return Ok(());
+    }
+
+    for entry in &entries {
+        let display = entry
+            .path
+            .strip_prefix(&project_root)
+            .unwrap_or(&entry.path);
+        println!(
+            "  {:>10}  {}  {}",
+            format_bytes(entry.size_bytes),
+            style(display.display()).cyan(),
+            style(format!("({})", entry.kind)).dim()
+        );
+        if !options.dry_run {
+            remove(&entry.path)?;
+        }
+    }
+
+    let total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+    if options.dry_run {
+        println!(
+            "\n{} Would remove {} path(s), reclaiming {}",
+            style("→").dim(),
+            entries.len(),
+            style(format_bytes(total)).yellow().bold()
+        );
+    } else {
+        println!(
+            "\n{} Removed {} path(s), reclaimed {}",
+            CHECKMARK,
+            entries.len(),
+            style(format_bytes(total)).green().bold()
+        );
+    }
+
+    Ok(())
+}
+
+/// Backups beyond the newest `keep` per original file name
+///
+/// Backup names are `<file>.<YYYYMMDD_HHMMSS.mmm>.<uuid>.backup`, so the
+/// timestamp orders them. Files that do not follow the pattern are left alone.
+fn stale_backups(backup_dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let mut by_file: BTreeMap<String, Vec<(String, PathBuf)>> = BTreeMap::new();
+    for path in children(backup_dir)? {
+        let name = file_name(&path);
+        let Some(stem) = name.strip_suffix(".backup") else {
+            continue;
+        };
+        let parts: Vec<&str> = stem.rsplitn(4, '.').collect();
+        if let [_uuid, millis, seconds, file] = parts[..] {
+            by_file
+                .entry(file.to_string())
+                .or_default()
+                .push((format!("{}.{}", seconds, millis), path));
+        }
+    }
+
+    let mut stale = Vec::new();
+    for mut backups in by_file.into_values() {
+        backups.sort_by(|a, b| b.0.cmp(&a.0));
+        stale.extend(backups.into_iter().skip(keep).map(|(_, path)| path));
+    }
+    stale.sort();
+    Ok(stale)
+}
+
+/// Entries of a directory, sorted; empty when it does not exist
+fn children(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Size of a file, or of everything below a directory (symlinks not followed)
+fn disk_usage(path: &Path) -> Result<u64> {
+    let metadata = std::fs::symlink_metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+    let mut total = 0;
+    for child in children(path)? {
+        total += disk_usage(&child)?;
+    }
+    Ok(total)
+}
+
+fn remove(path: &Path) -> Result<()> {
+    let result = if std::fs::symlink_metadata(path)?.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    };
+    result.with_context(|| format!("Failed to remove {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, bytes: usize) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0; bytes]).unwrap();
+    }
+
+    #[test]
+    fn test_plan_clean_lists_caches_and_stale_backups_only() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, ".wasm-slim/templates/abc.toml", 100);
+        write(
+            root,
+            ".wasm-slim/downloads/app_bg-0123456789abcdef.wasm",
+            70,
+        );
+        for stamp in [
+            "20260101_100000.001",
+            "20260102_100000.001",
+            "20260103_100000.001",
+        ] {
+            write(
+                root,
+                &format!(".wasm-slim/backups/Cargo.toml.{}.ff.backup", stamp),
+                10,
+            );
+        }
+        write(root, ".wasm-slim/backups/notes.txt", 10);
+        write(
+            root,
+            ".wasm-slim/reports/.staging-20260101_100000/report.json",
+            20,
+        );
+        write(root, ".wasm-slim/reports/report-20260101_100000.tar.gz", 30);
+        write(root, ".wasm-slim/history.json", 40);
+        write(root, ".wasm-slim/scratch/checkpoint/stages.json", 50);
+        write(root, "target/wasm32-unknown-unknown/release/app.wasm", 60);
+
+        let options = CleanOptions {
+            keep_backups: 2,
+            ..Default::default()
+        };
+        let entries = plan_clean(root, &options).unwrap();
+        let paths: Vec<(String, u64)> = entries
+            .iter()
+            .map(|e| {
+                let relative = e.path.strip_prefix(root).unwrap();
+                (relative.display().to_string(), e.size_bytes)
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (".wasm-slim/templates".to_string(), 100),
+                (".wasm-slim/downloads".to_string(), 70),
+                (
+                    ".wasm-slim/backups/Cargo.toml.20260101_100000.001.ff.backup".to_string(),
+                    10
+                ),
+                (
+                    ".wasm-slim/reports/.staging-20260101_100000".to_string(),
+                    20
+                ),
+                (".wasm-slim/scratch".to_string(), 50),
+            ]
+        );
+
+        let with_targets = CleanOptions {
+            targets: true,
+            ..options
+        };
+        let entries = plan_clean(root, &with_targets).unwrap();
+        assert_eq!(
+            entries.last().unwrap().path,
+            root.join("target/wasm32-unknown-unknown")
+        );
+    }
+
+    #[test]
+    fn test_plan_clean_on_fresh_project_is_empty() {
+        let temp = TempDir::new().unwrap();
+        assert!(plan_clean(temp.path(), &CleanOptions::default())
+            .unwrap()
+            .is_empty());
+    }
+}
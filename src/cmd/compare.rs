@@ -1,17 +1,22 @@
 //! Compare command implementation
 //!
-//! Handles the `wasm-slim compare` command which compares two WASM builds
-//! to show size differences and optimization impact
+//! Handles the `wasm-slim compare` command which compares two or more WASM
+//! builds to show size differences and optimization impact
 
 use anyhow::Result;
 use console::style;
 use std::path::Path;
 
 use crate::analyzer;
+use crate::analyzer::ItemLimit;
+use crate::cicd;
+use crate::cmd::OutputFormat;
+use crate::download::resolve_input;
+use crate::redact;
 
 /// Compare two WASM builds to show optimization impact
 ///
-/// Uses twiggy to analyze both files and show:
+/// Diffs both modules (natively, falling back to twiggy) and shows:
 /// - Size differences
 /// - Symbol-level changes
 /// - Optimization effectiveness
@@ -30,18 +35,68 @@ use crate::analyzer;
 ///
 /// Returns an error if:
 /// - Either file doesn't exist
-/// - twiggy is not installed
 /// - Files are not valid WASM binaries
 pub fn cmd_compare(before: &str, after: &str) -> Result<()> {
-    cmd_compare_impl(before, after, true)
+    cmd_compare_with_limit(before, after, ItemLimit::Default)
 }
 
-/// Internal implementation that allows skipping twiggy check for testing
-fn cmd_compare_impl(before: &str, after: &str, check_twiggy: bool) -> Result<()> {
+/// Options for `wasm-slim compare`
+#[derive(Debug, Clone, Default)]
+pub struct CompareOptions {
+    /// How many symbol changes the console report lists
+    pub limit: ItemLimit,
+    /// Git ref the baseline was built from; attributes deltas to commits since it
+    pub against: Option<String>,
+    /// Print this format instead of the console report: `json` for the comparison with
+    /// its symbol changes, `markdown` for a pull request comment with the recommendations
+    /// for the after build
+    pub format: Option<OutputFormat>,
+}
+
+/// Whether `options` ask for JSON and for Markdown, rejecting formats compare cannot print
+fn requested_format(options: &CompareOptions) -> Result<(bool, bool)> {
+    match options.format {
+        None => Ok((false, false)),
+        Some(OutputFormat::Json) if options.against.is_some() => {
+            anyhow::bail!("--against is not supported with --format json")
+        }
+        Some(OutputFormat::Json) => Ok((true, false)),
+        Some(OutputFormat::Markdown) => Ok((false, true)),
+        Some(other) => anyhow::bail!(
+            "--format {} is not supported by compare. Valid formats: json, markdown",
+            other
+        ),
+    }
+}
+
+/// Compare two WASM builds, limiting the symbol changes listed
+pub fn cmd_compare_with_limit(before: &str, after: &str, limit: ItemLimit) -> Result<()> {
+    let options = CompareOptions {
+        limit,
+        ..Default::default()
+    };
+    cmd_compare_with_options(before, after, &options)
+}
+
+/// Compare two WASM builds with the full set of options
+///
+/// With `against`, symbol deltas are grouped by crate and attributed to the
+/// commits since that ref which touched each crate.
+pub fn cmd_compare_with_options(before: &str, after: &str, options: &CompareOptions) -> Result<()> {
+    let before = resolve_input(before)?;
+    let after = resolve_input(after)?;
+    cmd_compare_impl(&before, &after, options)
+}
+
+/// Export both builds' dominator trees as a differential folded listing
+///
+/// Each line is `stack before_bytes after_bytes`, the format produced by
+/// `inferno-diff-folded`, so `inferno-flamegraph` colors growth red and
+/// shrinkage blue. `output` may be `-` for stdout.
+pub fn cmd_compare_folded(before: &str, after: &str, output: &Path) -> Result<()> {
+    let (before, after) = (&resolve_input(before)?, &resolve_input(after)?);
     let before_path = Path::new(before);
     let after_path = Path::new(after);
-
-    // Verify files exist first (before checking for twiggy)
     if !before_path.exists() {
         anyhow::bail!(
             "Baseline file not found: {}. Run a build first to create a baseline.",
@@ -52,17 +107,97 @@ fn cmd_compare_impl(before: &str, after: &str, check_twiggy: bool) -> Result<()>
         anyhow::bail!("Comparison file not found: {}", after);
     }
 
-    // Check if twiggy is installed (unless we're in test mode)
-    if check_twiggy && !analyzer::TwiggyAnalyzer::check_installation()? {
-        eprintln!(
+    use crate::infra::{RealCommandExecutor, RealFileSystem};
+    let folded = analyzer::TwiggyAnalyzer::compare_folded(
+        before_path,
+        after_path,
+        &RealFileSystem,
+        &RealCommandExecutor,
+    )?;
+    super::analyze::write_folded_output(output, &folded)
+}
+
+/// Compare three or more builds in order
+///
+/// Prints a table with each build's delta from the previous build and from
+/// the first, followed by the top symbol changes of every step. With
+/// `--format markdown` the same table is printed as Markdown.
+/// `--folded` and `--against` describe a single pair of builds and are
+/// rejected here.
+pub fn cmd_compare_many(
+    files: &[String],
+    folded: Option<&Path>,
+    options: &CompareOptions,
+) -> Result<()> {
+    if folded.is_some() || options.against.is_some() {
+        anyhow::bail!(
+            "--folded and --against compare exactly two builds; got {}",
+            files.len()
+        );
+    }
+
+    let (json, markdown) = requested_format(options)?;
+
+    let files = files
+        .iter()
+        .map(|f| resolve_input(f))
+        .collect::<Result<Vec<_>>>()?;
+    let paths: Vec<&Path> = files.iter().map(Path::new).collect();
+    for (i, path) in paths.iter().enumerate() {
+        if path.exists() {
+            continue;
+        }
+        if i == 0 {
+            anyhow::bail!(
+                "Baseline file not found: {}. Run a build first to create a baseline.",
+                path.display()
+            );
+        }
+        anyhow::bail!("Comparison file not found: {}", path.display());
+    }
+
+    if !json && !markdown {
+        println!("📊 {} WASM Build Comparison", style("wasm-slim").bold());
+    }
+
+    use crate::infra::{RealCommandExecutor, RealFileSystem};
+    let results =
+        analyzer::TwiggyAnalyzer::compare_many(&paths, &RealFileSystem, &RealCommandExecutor)?;
+    if json {
+        println!("{}", redact::to_json_pretty(&results)?);
+    } else if markdown {
+        print!(
             "{}",
-            style(analyzer::TwiggyAnalyzer::installation_instructions()).yellow()
+            analyzer::markdown_report::format_multi_comparison_markdown(&results, options.limit)
         );
-        anyhow::bail!("twiggy not installed");
+    } else {
+        analyzer::print_multi_comparison_report_with_limit(&results, options.limit);
     }
 
-    println!("📊 {} WASM Build Comparison", style("wasm-slim").bold());
-    println!();
+    Ok(())
+}
+
+/// Internal implementation shared by the public entry points
+fn cmd_compare_impl(before: &str, after: &str, options: &CompareOptions) -> Result<()> {
+    let (json, markdown) = requested_format(options)?;
+    let before_path = Path::new(before);
+    let after_path = Path::new(after);
+
+    // Verify files exist first
+    if !before_path.exists() {
+        anyhow::bail!(
+            "Baseline file not found: {}. Run a build first to create a baseline.",
+            before
+        );
+    }
+    if !after_path.exists() {
+        anyhow::bail!("Comparison file not found: {}", after);
+    }
+
+    if !markdown && !json {
+        println!("📊 {} WASM Build Comparison", style("wasm-slim").bold());
+        println!();
+    }
 
     // Run comparison
     use crate::infra::{RealCommandExecutor, RealFileSystem};
@@ -73,12 +208,76 @@ fn cmd_compare_impl(before: &str, after: &str, check_twiggy: bool) -> Result<()>
         &RealCommandExecutor,
     )?;
 
+    let blame = match &options.against {
+        Some(against) => {
+            let project_root = std::env::current_dir()?;
+            Some(analyzer::SymbolBlamer::new(&project_root, against).blame(&results)?)
+        }
+        None => None,
+    };
+
+    // Both artifacts may come from recorded builds with different features
+    let feature_drift = std::env::current_dir()
+        .ok()
+        .and_then(|root| cicd::BuildHistory::load(&root).ok())
+        .and_then(|history| {
+            history.feature_drift_between(results.before_size_bytes, results.after_size_bytes)
+        });
+
     // Print report
-    analyzer::print_comparison_report(&results);
+    if json {
+        println!("{}", redact::to_json_pretty(&results)?);
+        return Ok(());
+    }
+    if markdown {
+        print!("{}", analyzer::format_pr_comment(&results, blame.as_ref()));
+        print!(
+            "{}",
+            analyzer::markdown_report::format_recommendations_markdown(
+                &after_recommendations(after_path)?,
+                options.limit
+            )
+        );
+        if let Some(drift) = &feature_drift {
+            println!(
+                "\n> **Warning:** resolved features differ between the builds ({}); \
+                 the size difference may come from features rather than code changes.",
+                drift
+            );
+        }
+        return Ok(());
+    }
+
+    analyzer::print_comparison_report_with_limit(&results, options.limit);
+    if let Some(drift) = &feature_drift {
+        cicd::display::print_feature_drift(drift);
+    }
+    if let Some(blame) = &blame {
+        print!(
+            "{}",
+            analyzer::format_symbol_blame_console_with_limit(blame, options.limit)?
+        );
+    }
 
     Ok(())
 }
 
+/// Recommendations for the after build, minus those `.wasm-slim-ignore` suppresses
+fn after_recommendations(after_path: &Path) -> Result<Vec<analyzer::twiggy::Recommendation>> {
+    let project_root = std::env::current_dir()?;
+    let thresholds = crate::config::ConfigLoader::load(&project_root)?
+        .analysis
+        .and_then(|a| a.thresholds)
+        .map(|t| analyzer::thresholds::RecommendationThresholds::from_settings(&t))
+        .unwrap_or_default();
+    let mut recommendations = analyzer::TwiggyAnalyzer::new(after_path)
+        .with_thresholds(thresholds)
+        .analyze(analyzer::AnalysisMode::Top)?
+        .recommendations;
+    analyzer::suppressions::Suppressions::load(&project_root)?.filter(&mut recommendations);
+    Ok(recommendations)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,7 +293,11 @@ mod tests {
         // Create after file but not baseline
         fs::write(&after, b"dummy wasm content").unwrap();
 
-        let result = cmd_compare_impl(baseline.to_str().unwrap(), after.to_str().unwrap(), false);
+        let result = cmd_compare_impl(
+            baseline.to_str().unwrap(),
+            after.to_str().unwrap(),
+            &CompareOptions::default(),
+        );
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -111,7 +314,11 @@ mod tests {
         // Create baseline file but not comparison
         fs::write(&baseline, b"dummy wasm content").unwrap();
 
-        let result = cmd_compare_impl(baseline.to_str().unwrap(), after.to_str().unwrap(), false);
+        let result = cmd_compare_impl(
+            baseline.to_str().unwrap(),
+            after.to_str().unwrap(),
+            &CompareOptions::default(),
+        );
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -125,7 +332,11 @@ mod tests {
         let baseline = temp_dir.path().join("nonexistent1.wasm");
         let after = temp_dir.path().join("nonexistent2.wasm");
 
-        let result = cmd_compare_impl(baseline.to_str().unwrap(), after.to_str().unwrap(), false);
+        let result = cmd_compare_impl(
+            baseline.to_str().unwrap(),
+            after.to_str().unwrap(),
+            &CompareOptions::default(),
+        );
 
         assert!(result.is_err());
         // Should fail on baseline first
@@ -140,7 +351,11 @@ mod tests {
         let after = temp_dir.path().join("missing_after.wasm");
 
         // Neither file exists, but baseline should be checked first
-        let result = cmd_compare_impl(baseline.to_str().unwrap(), after.to_str().unwrap(), false);
+        let result = cmd_compare_impl(
+            baseline.to_str().unwrap(),
+            after.to_str().unwrap(),
+            &CompareOptions::default(),
+        );
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -154,10 +369,54 @@ mod tests {
         let baseline_path = "/some/path/to/baseline.wasm";
         let after_path = "/some/path/to/after.wasm";
 
-        let result = cmd_compare_impl(baseline_path, after_path, false);
+        let result = cmd_compare_impl(baseline_path, after_path, &CompareOptions::default());
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains(baseline_path));
     }
+
+    #[test]
+    fn test_compare_many_checks_every_file_and_rejects_pair_options() {
+        let temp_dir = TempDir::new().unwrap();
+        let files: Vec<String> = ["a.wasm", "b.wasm", "c.wasm"]
+            .iter()
+            .map(|f| temp_dir.path().join(f).display().to_string())
+            .collect();
+        fs::write(&files[0], b"dummy wasm content").unwrap();
+        fs::write(&files[1], b"dummy wasm content").unwrap();
+
+        let err = cmd_compare_many(&files, None, &CompareOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("Comparison file not found"));
+        assert!(err.to_string().contains("c.wasm"));
+
+        let options = CompareOptions {
+            against: Some("origin/main".to_string()),
+            ..Default::default()
+        };
+        let err = cmd_compare_many(&files, None, &options).unwrap_err();
+        assert!(err.to_string().contains("exactly two builds"));
+    }
+
+    #[test]
+    fn test_compare_rejects_formats_it_cannot_print() {
+        let options = CompareOptions {
+            format: Some(OutputFormat::Sarif),
+            ..Default::default()
+        };
+        let err = cmd_compare_impl("a.wasm", "b.wasm", &options).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("--format sarif is not supported by compare"));
+
+        let options = CompareOptions {
+            format: Some(OutputFormat::Json),
+            against: Some("origin/main".to_string()),
+            ..Default::default()
+        };
+        let err = cmd_compare_impl("a.wasm", "b.wasm", &options).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("--against is not supported with --format json"));
+    }
 }
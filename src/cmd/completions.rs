@@ -40,7 +40,7 @@ pub fn cmd_completions(shell: Shell) {
         .subcommand(Command::new("build").about("Build and optimize WASM binary"))
         .subcommand(Command::new("analyze").about("Analyze WASM bundle or dependencies"))
         .subcommand(Command::new("init").about("Initialize wasm-slim configuration"))
-        .subcommand(Command::new("compare").about("Compare two WASM builds"))
+        .subcommand(Command::new("compare").about("Compare two or more WASM builds"))
         .subcommand(Command::new("completions").about("Generate shell completions"));
 
     let bin_name = "wasm-slim".to_string();
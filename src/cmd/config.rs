@@ -0,0 +1,155 @@
+//! Config command implementations
+//!
+//! Handles `wasm-slim config validate`, which checks `.wasm-slim.toml`
+//! against the shared team policy, when it references one, and the WASM
+//! dependency graph against banned and discouraged crates.
+
+use anyhow::Result;
+use console::style;
+use std::env;
+use std::path::Path;
+
+use crate::analyzer::{deps::RuleKind, DependencyAnalyzer};
+use crate::config::{self, ConfigFile, ValidationIssue, ValidationResult, ValidationSeverity};
+use crate::fmt::{CHECKMARK, CROSSMARK, MICROSCOPE};
+
+/// Validate the project config and check it against the team policy
+///
+/// Fails when the config cannot be loaded, violates the policy or a banned
+/// crate is in the dependency graph.
+pub fn cmd_config_validate() -> Result<()> {
+    let project_root = env::current_dir()?;
+    println!(
+        "{} {} Validating {}",
+        MICROSCOPE,
+        style("wasm-slim").bold(),
+        style(config::ConfigLoader::display_name(&project_root)).cyan()
+    );
+
+    let result = validate_project(&project_root)?;
+
+    match &result {
+        Some(result) => print_policy_result(result),
+        None => println!(
+            "\n{} Config is valid (no policy or dependency rules configured)",
+            CHECKMARK
+        ),
+    }
+
+    if result.is_some_and(|r| r.has_errors()) {
+        anyhow::bail!("Config violates the team policy or dependency rules");
+    }
+    Ok(())
+}
+
+/// Load the config, resolve its template and check the policy, if any
+fn validate_project(project_root: &Path) -> Result<Option<ValidationResult>> {
+    let config = config::ConfigLoader::load(project_root)?;
+    config::TemplateResolver::resolve_in(&config, project_root)?;
+    check_project(project_root, &config)
+}
+
+/// Check the config against its policy and the dependency graph against
+/// the `[crates]` rules of both
+///
+/// Returns `None` when there is neither a policy nor any rules.
+pub(crate) fn check_project(
+    project_root: &Path,
+    config: &ConfigFile,
+) -> Result<Option<ValidationResult>> {
+    let policy = config::Policy::for_project(project_root, config)?;
+    let rules = config::policy::crate_rules(policy.as_ref(), config);
+    if rules.is_empty() {
+        return Ok(policy.map(|policy| policy.check(config)));
+    }
+
+    let mut result = policy.map_or_else(ValidationResult::success, |p| p.check(config));
+    let report = DependencyAnalyzer::new(project_root)
+        .with_crate_rules(rules)
+        .analyze()?;
+    for violation in report.rule_violations {
+        let issue = match violation.kind {
+            RuleKind::Banned => ValidationIssue::error(
+                "dependencies",
+                format!(
+                    "Crate '{}' {} matches banned rule \"{}\"",
+                    violation.package, violation.version, violation.rule
+                ),
+            )
+            .with_suggestion("Remove it from the WASM dependency graph"),
+            RuleKind::Discouraged => ValidationIssue::warning(
+                "dependencies",
+                format!(
+                    "Crate '{}' {} is discouraged",
+                    violation.package, violation.version
+                ),
+            )
+            .with_suggestion(violation.hint.unwrap_or_default()),
+        };
+        result.add_issue(issue);
+    }
+    Ok(Some(result))
+}
+
+/// Print policy and dependency rule violations, errors first
+pub(crate) fn print_policy_result(result: &ValidationResult) {
+    if result.issues.is_empty() {
+        println!(
+            "\n{} Config satisfies the team policy and dependency rules",
+            CHECKMARK
+        );
+        return;
+    }
+
+    let mut issues: Vec<_> = result.issues.iter().collect();
+    issues.sort_by_key(|i| std::cmp::Reverse(i.severity));
+    println!();
+    for issue in issues {
+        let severity = match issue.severity {
+            ValidationSeverity::Error => style(issue.severity.as_str()).red().bold(),
+            ValidationSeverity::Warning => style(issue.severity.as_str()).yellow().bold(),
+            ValidationSeverity::Info => style(issue.severity.as_str()).dim(),
+        };
+        println!(
+            "{} [{}] {}: {}",
+            CROSSMARK, severity, issue.field, issue.message
+        );
+        if let Some(suggestion) = &issue.suggestion {
+            println!("     {} {}", style("→").dim(), style(suggestion).green());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_project_checks_referenced_policy() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join(config::CONFIG_FILE_NAME),
+            "template = \"minimal\"\npolicy = \"policy.toml\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("policy.toml"),
+            "[templates]\nallowed = [\"aggressive\"]\n",
+        )
+        .unwrap();
+
+        let result = validate_project(temp.path()).unwrap().unwrap();
+        assert!(result.has_errors());
+        assert_eq!(result.errors()[0].field, "template");
+
+        std::fs::remove_file(temp.path().join("policy.toml")).unwrap();
+        assert!(validate_project(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_validate_project_without_policy() {
+        let temp = TempDir::new().unwrap();
+        assert!(validate_project(temp.path()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,63 @@
+//! Size database command implementations
+//!
+//! Handles `wasm-slim db update`, which downloads the curated database of
+//! measured crate sizes used by dependency analysis.
+
+use anyhow::Result;
+use console::style;
+use std::env;
+
+use crate::analyzer::size_db::{SizeDbUpdate, SizeDbUpdater, SIZE_DB_URL};
+use crate::fmt::CHECKMARK;
+
+/// Download the crate size database into `.wasm-slim/size-db.json`
+///
+/// `url` defaults to the curated database published with wasm-slim. Until
+/// the first update, analyses use the snapshot bundled with the binary.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wasm_slim::cmd::db::cmd_db_update;
+///
+/// cmd_db_update(None)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn cmd_db_update(url: Option<&str>) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let url = url.unwrap_or(SIZE_DB_URL);
+    let update = SizeDbUpdater::new(&project_root).update(url)?;
+    println!("{}", format_update(&update));
+    Ok(())
+}
+
+/// One-line description of an update
+fn format_update(update: &SizeDbUpdate) -> String {
+    format!(
+        "{} Size database {} ({} crates, was {}) saved to {}",
+        CHECKMARK,
+        style(&update.updated).cyan().bold(),
+        update.crates,
+        update.previous_updated,
+        update.path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_update_names_both_dates() {
+        let update = SizeDbUpdate {
+            path: PathBuf::from(".wasm-slim/size-db.json"),
+            previous_updated: "2026-10-01".to_string(),
+            updated: "2026-11-15".to_string(),
+            crates: 42,
+        };
+        let text = format_update(&update);
+        assert!(text.contains("2026-11-15"));
+        assert!(text.contains("42 crates, was 2026-10-01"));
+    }
+}
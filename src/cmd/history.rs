@@ -0,0 +1,93 @@
+//! History command implementations
+//!
+//! Handles `wasm-slim history compact`, which rolls up builds older than the
+//! `[history]` retention window in `.wasm-slim/history.json`.
+
+use anyhow::{Context, Result};
+use console::style;
+use std::env;
+
+use crate::cicd::history::{current_unix_secs, CompactionSummary};
+use crate::cicd::{BuildHistory, CompactionPolicy};
+use crate::config::ConfigLoader;
+use crate::fmt::CHECKMARK;
+
+/// Compact the build history, rolling up every day past the retention window
+///
+/// Unlike the automatic compaction after each build, this is not limited to
+/// a few days per run.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wasm_slim::cmd::history::cmd_history_compact;
+///
+/// // Show what would be rolled up without rewriting history.json
+/// cmd_history_compact(true)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn cmd_history_compact(dry_run: bool) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let settings = ConfigLoader::load(&project_root)
+        .ok()
+        .and_then(|config| config.history);
+    let policy = CompactionPolicy::from_settings(settings.as_ref());
+
+    let mut history = BuildHistory::load(&project_root).context("Failed to load build history")?;
+    let summary = history.compact(&policy, current_unix_secs()?, None);
+    if !dry_run {
+        history.save(&project_root)?;
+    }
+
+    println!("{}", format_summary(&summary, &policy, dry_run));
+    Ok(())
+}
+
+/// One-line description of a compaction
+fn format_summary(summary: &CompactionSummary, policy: &CompactionPolicy, dry_run: bool) -> String {
+    if summary.days_compacted == 0 {
+        return format!(
+            "{} History is compact: {} records, none older than {} days to roll up",
+            CHECKMARK, summary.records_after, policy.keep_days
+        );
+    }
+    format!(
+        "{} {} {} day(s) older than {} days: {} → {} records",
+        CHECKMARK,
+        if dry_run {
+            "Would roll up"
+        } else {
+            "Rolled up"
+        },
+        style(summary.days_compacted).cyan().bold(),
+        policy.keep_days,
+        summary.records_before,
+        style(summary.records_after).green()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_summary_reports_rolled_up_days() {
+        let policy = CompactionPolicy::default();
+        let summary = CompactionSummary {
+            records_before: 120,
+            records_after: 40,
+            days_compacted: 12,
+            days_pending: 0,
+        };
+        let text = format_summary(&summary, &policy, true);
+        assert!(text.contains("Would roll up"));
+        assert!(text.contains("older than 90 days"));
+        assert!(text.contains("120 → "));
+
+        let idle = CompactionSummary {
+            days_compacted: 0,
+            ..summary
+        };
+        assert!(format_summary(&idle, &policy, false).contains("History is compact"));
+    }
+}
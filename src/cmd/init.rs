@@ -39,13 +39,14 @@ pub fn cmd_init(template: &str) -> Result<()> {
     println!();
 
     let project_root = env::current_dir()?;
+    let config_name = config::ConfigLoader::display_name(&project_root);
 
     // Check if config file already exists
     if config::ConfigLoader::exists(&project_root) {
         println!(
             "{} Config file already exists: {}",
             style("⚠️").yellow(),
-            style(config::CONFIG_FILE_NAME).cyan()
+            style(&config_name).cyan()
         );
         println!("   Delete it first or edit manually to update.");
         return Ok(());
@@ -116,14 +117,11 @@ pub fn cmd_init(template: &str) -> Result<()> {
     println!(
         "{} Created {}",
         CHECKMARK,
-        style(config::CONFIG_FILE_NAME).cyan().bold()
+        style(&config_name).cyan().bold()
     );
     println!();
     println!("{}  Next Steps:", style("💡").bold());
-    println!(
-        "   1. Review and customize {} if needed",
-        config::CONFIG_FILE_NAME
-    );
+    println!("   1. Review and customize {} if needed", config_name);
     println!(
         "   2. Run {} to build with optimizations",
         style("wasm-slim build").cyan()
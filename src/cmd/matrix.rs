@@ -0,0 +1,577 @@
+//! Matrix command implementation
+//!
+//! Handles the `wasm-slim matrix` command which builds every combination of
+//! templates and wasm-bindgen targets and compares them side by side.
+//!
+//! Templates only differ from each other in `[profile.release]` settings and
+//! wasm-opt flags, so cargo runs once per distinct profile (passed as
+//! `CARGO_PROFILE_RELEASE_*` variables, leaving Cargo.toml untouched) and
+//! every target reuses that build for wasm-bindgen and wasm-opt. All output
+//! goes under `.wasm-slim/scratch/matrix/`.
+
+use anyhow::{bail, Context, Result};
+use console::style;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::config::{self, ProfileConfig, Template};
+use crate::fmt::{format_bytes, percent_of, CHART, ROCKET};
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use crate::pipeline::{BindgenTarget, PipelineConfig, StageEnv, ToolRunner, WasmOptLevel};
+
+/// Directory (relative to the project root) holding matrix builds
+pub const MATRIX_DIR: &str = "matrix";
+
+/// Flags the tool runner already passes to every wasm-opt invocation
+const RUNNER_WASM_OPT_FLAGS: [&str; 4] = [
+    "--enable-mutable-globals",
+    "--enable-bulk-memory",
+    "--enable-sign-ext",
+    "--enable-nontrapping-float-to-int",
+];
+
+/// One template and wasm-bindgen target pairing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combination {
+    /// Template name
+    pub template: String,
+    /// wasm-bindgen target
+    pub target: BindgenTarget,
+}
+
+impl Combination {
+    /// Parse a `template:target` pair such as `balanced:web`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_slim::cmd::matrix::Combination;
+    ///
+    /// let combo = Combination::parse("balanced:bundler").unwrap();
+    /// assert_eq!(combo.label(), "balanced:bundler");
+    /// ```
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (template, target) = spec
+            .split_once(':')
+            .with_context(|| format!("Expected TEMPLATE:TARGET, got '{}'", spec))?;
+        let template = Template::get(template)
+            .with_context(|| format!("Unknown template: {}", template))?
+            .name;
+        let target = target
+            .parse::<BindgenTarget>()
+            .map_err(anyhow::Error::msg)?;
+        Ok(Self { template, target })
+    }
+
+    /// `template:target` label
+    pub fn label(&self) -> String {
+        format!("{}:{}", self.template, self.target.as_str())
+    }
+}
+
+/// Cargo build of one template
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateBuild {
+    /// Template name
+    pub template: String,
+    /// Time spent in `cargo build` (0 when reused)
+    pub compile_ms: u64,
+    /// Earlier template whose build was reused because the profiles match
+    pub reused_from: Option<String>,
+}
+
+/// Result of one template and target combination
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixCell {
+    /// Template name
+    pub template: String,
+    /// wasm-bindgen target
+    pub target: String,
+    /// Optimized module size
+    pub size_bytes: u64,
+    /// Time spent in wasm-bindgen and wasm-opt
+    pub postprocess_ms: u64,
+}
+
+/// Sizes and timings of every combination
+#[derive(Debug, Clone, Serialize)]
+pub struct MatrixReport {
+    /// Cargo builds, in template order
+    pub builds: Vec<TemplateBuild>,
+    /// wasm-bindgen targets, in column order
+    pub targets: Vec<String>,
+    /// One cell per template and target
+    pub cells: Vec<MatrixCell>,
+}
+
+impl MatrixReport {
+    /// Cell for a template and target
+    pub fn cell(&self, template: &str, target: &str) -> Option<&MatrixCell> {
+        self.cells
+            .iter()
+            .find(|c| c.template == template && c.target == target)
+    }
+
+    /// Smallest combination (the first one on ties)
+    pub fn best(&self) -> Option<&MatrixCell> {
+        self.cells.iter().min_by_key(|c| c.size_bytes)
+    }
+
+    /// Fail unless `production` is within `margin_percent` of the smallest combination
+    ///
+    /// The margin is relative to the production size: with a 2% margin, a
+    /// 100 KiB production build fails only if another combination is below 98 KiB.
+    pub fn check_production(&self, production: &Combination, margin_percent: f64) -> Result<()> {
+        let Some(chosen) = self.cell(&production.template, production.target.as_str()) else {
+            bail!(
+                "Production combination {} is not part of the matrix",
+                production.label()
+            );
+        };
+        let Some(best) = self.best() else {
+            return Ok(());
+        };
+
+        let saved = chosen.size_bytes.saturating_sub(best.size_bytes);
+        let saved_percent = percent_of(saved, chosen.size_bytes);
+        if saved > 0 && saved_percent > margin_percent {
+            bail!(
+                "Production combination {} ({}) is not the best: {}:{} is {} smaller ({:.1}%, margin {:.1}%)",
+                production.label(),
+                format_bytes(chosen.size_bytes),
+                best.template,
+                best.target,
+                format_bytes(saved),
+                saved_percent,
+                margin_percent
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Builds every template and target combination
+pub struct BuildMatrix<CE: CommandExecutor + Clone = RealCommandExecutor> {
+    project_root: PathBuf,
+    templates: Vec<Template>,
+    targets: Vec<BindgenTarget>,
+    env: StageEnv,
+    cmd_executor: CE,
+}
+
+impl BuildMatrix {
+    /// Create a matrix over the given templates and targets
+    pub fn new(
+        project_root: impl AsRef<Path>,
+        templates: Vec<Template>,
+        targets: Vec<BindgenTarget>,
+    ) -> Self {
+        Self::with_executor(project_root, templates, targets, RealCommandExecutor)
+    }
+}
+
+impl<CE: CommandExecutor + Clone> BuildMatrix<CE> {
+    /// Create a matrix with a custom command executor
+    pub fn with_executor(
+        project_root: impl AsRef<Path>,
+        templates: Vec<Template>,
+        targets: Vec<BindgenTarget>,
+        cmd_executor: CE,
+    ) -> Self {
+        Self {
+            project_root: project_root.as_ref().to_path_buf(),
+            templates,
+            targets,
+            env: StageEnv::default(),
+            cmd_executor,
+        }
+    }
+
+    /// Environment variables applied to every tool invocation
+    pub fn with_env(mut self, env: StageEnv) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Number of cargo builds the matrix needs
+    pub fn compile_count(&self) -> usize {
+        reused_builds(&self.templates)
+            .iter()
+            .filter(|r| r.is_none())
+            .count()
+    }
+
+    /// Build every combination
+    pub fn run(&self) -> Result<MatrixReport> {
+        let matrix_dir = crate::state::scratch_dir(&self.project_root).join(MATRIX_DIR);
+        let mut builds = Vec::with_capacity(self.templates.len());
+        let mut wasm_files: Vec<PathBuf> = Vec::with_capacity(self.templates.len());
+        let mut cells = Vec::new();
+
+        for (template, reused) in self.templates.iter().zip(reused_builds(&self.templates)) {
+            let wasm = match reused {
+                Some(index) => {
+                    builds.push(TemplateBuild {
+                        template: template.name.clone(),
+                        compile_ms: 0,
+                        reused_from: Some(self.templates[index].name.clone()),
+                    });
+                    wasm_files[index].clone()
+                }
+                None => {
+                    let started = Instant::now();
+                    let wasm = self
+                        .runner(template, BindgenTarget::default(), &matrix_dir)
+                        .cargo_build()
+                        .with_context(|| format!("cargo build failed for '{}'", template.name))?;
+                    builds.push(TemplateBuild {
+                        template: template.name.clone(),
+                        compile_ms: started.elapsed().as_millis() as u64,
+                        reused_from: None,
+                    });
+                    wasm
+                }
+            };
+
+            for &target in &self.targets {
+                let runner = self.runner(template, target, &matrix_dir);
+                let out_dir = combination_dir(&matrix_dir, &template.name, target);
+                RealFileSystem.create_dir_all(&out_dir)?;
+
+                let started = Instant::now();
+                let output = runner.run_wasm_bindgen(&wasm).with_context(|| {
+                    format!(
+                        "wasm-bindgen failed for {}:{}",
+                        template.name,
+                        target.as_str()
+                    )
+                })?;
+                runner.run_wasm_opt(&output).with_context(|| {
+                    format!("wasm-opt failed for {}:{}", template.name, target.as_str())
+                })?;
+                cells.push(MatrixCell {
+                    template: template.name.clone(),
+                    target: target.as_str().to_string(),
+                    size_bytes: RealFileSystem.metadata(&output)?.len(),
+                    postprocess_ms: started.elapsed().as_millis() as u64,
+                });
+            }
+            wasm_files.push(wasm);
+        }
+
+        Ok(MatrixReport {
+            builds,
+            targets: self
+                .targets
+                .iter()
+                .map(|t| t.as_str().to_string())
+                .collect(),
+            cells,
+        })
+    }
+
+    /// Tool runner configured for one template and target
+    fn runner(
+        &self,
+        template: &Template,
+        target: BindgenTarget,
+        matrix_dir: &Path,
+    ) -> ToolRunner<RealFileSystem, CE> {
+        let (opt_level, wasm_opt_args) = wasm_opt_settings(template);
+        let mut env = self.env.clone();
+        env.cargo.extend(profile_env(&template.profile));
+
+        let config = PipelineConfig {
+            target_dir: Some(matrix_dir.join(&template.name).join("target")),
+            bindgen_target: target,
+            out_dir: Some(combination_dir(matrix_dir, &template.name, target)),
+            opt_level,
+            wasm_opt_args,
+            env,
+            ..Default::default()
+        };
+        ToolRunner::new(
+            self.project_root.clone(),
+            config,
+            RealFileSystem,
+            self.cmd_executor.clone(),
+        )
+    }
+}
+
+/// Output directory of one combination
+fn combination_dir(matrix_dir: &Path, template: &str, target: BindgenTarget) -> PathBuf {
+    matrix_dir.join(template).join(target.as_str())
+}
+
+/// `CARGO_PROFILE_RELEASE_*` overrides applying a template's profile
+fn profile_env(profile: &ProfileConfig) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        (
+            "CARGO_PROFILE_RELEASE_OPT_LEVEL".to_string(),
+            profile.opt_level.clone(),
+        ),
+        ("CARGO_PROFILE_RELEASE_LTO".to_string(), profile.lto.clone()),
+        (
+            "CARGO_PROFILE_RELEASE_STRIP".to_string(),
+            profile.strip.to_string(),
+        ),
+        (
+            "CARGO_PROFILE_RELEASE_CODEGEN_UNITS".to_string(),
+            profile.codegen_units.to_string(),
+        ),
+        (
+            "CARGO_PROFILE_RELEASE_PANIC".to_string(),
+            profile.panic.clone(),
+        ),
+    ])
+}
+
+/// For each template, the earlier template with an identical profile, if any
+fn reused_builds(templates: &[Template]) -> Vec<Option<usize>> {
+    let profiles: Vec<_> = templates.iter().map(|t| profile_env(&t.profile)).collect();
+    profiles
+        .iter()
+        .enumerate()
+        .map(|(i, profile)| profiles[..i].iter().position(|p| p == profile))
+        .collect()
+}
+
+/// wasm-opt level and extra arguments from a template's flags
+fn wasm_opt_settings(template: &Template) -> (WasmOptLevel, Vec<String>) {
+    let mut level = WasmOptLevel::Oz;
+    let mut args = Vec::new();
+    for flag in &template.wasm_opt.flags {
+        match WasmOptLevel::from_arg(flag) {
+            Some(parsed) => level = parsed,
+            None if RUNNER_WASM_OPT_FLAGS.contains(&flag.as_str()) => {}
+            None => args.push(flag.clone()),
+        }
+    }
+    args.extend(template.wasm_opt.tuning_args());
+    (level, args)
+}
+
+/// Format the comparison grid: one row per template, one size column per target
+pub fn format_grid(report: &MatrixReport) -> Result<String, fmt::Error> {
+    let mut output = String::new();
+    let best = report.best();
+
+    writeln!(
+        output,
+        "\n{} {}",
+        CHART,
+        style("Build Matrix").bold().underlined()
+    )?;
+    write!(output, "\n  {:<12}", "Template")?;
+    for target in &report.targets {
+        write!(output, " {:>12}", target)?;
+    }
+    writeln!(output, "  Compile")?;
+    writeln!(
+        output,
+        "{}",
+        style("─".repeat(24 + 13 * report.targets.len())).dim()
+    )?;
+
+    for build in &report.builds {
+        write!(output, "  {:<12}", build.template)?;
+        for target in &report.targets {
+            let text = match report.cell(&build.template, target) {
+                Some(cell) => format_bytes(cell.size_bytes),
+                None => "-".to_string(),
+            };
+            let is_best = best.is_some_and(|b| b.template == build.template && &b.target == target);
+            if is_best {
+                write!(output, " {}", style(format!("{:>12}", text)).green().bold())?;
+            } else {
+                write!(output, " {:>12}", text)?;
+            }
+        }
+        match &build.reused_from {
+            Some(from) => writeln!(output, "  {}", style(format!("reused {}", from)).dim())?,
+            None => writeln!(output, "  {:.1}s", build.compile_ms as f64 / 1000.0)?,
+        }
+    }
+
+    if let Some(best) = best {
+        writeln!(
+            output,
+            "\n   Smallest: {} ({})",
+            style(format!("{}:{}", best.template, best.target))
+                .green()
+                .bold(),
+            format_bytes(best.size_bytes)
+        )?;
+    }
+    Ok(output)
+}
+
+/// Options for `wasm-slim matrix`
+#[derive(Debug, Clone, Default)]
+pub struct MatrixOptions {
+    /// Template names (rows)
+    pub templates: Vec<String>,
+    /// wasm-bindgen targets (columns)
+    pub targets: Vec<String>,
+    /// Combination that must be the smallest, as `template:target`
+    pub production: Option<String>,
+    /// Percentage another combination may beat production by before failing
+    pub margin_percent: f64,
+    /// Output as JSON
+    pub json: bool,
+}
+
+/// Build every template and target combination and compare them
+///
+/// # Examples
+///
+/// ```no_run
+/// use wasm_slim::cmd::matrix::{cmd_matrix, MatrixOptions};
+///
+/// // Fail if balanced:web is more than 2% larger than the best combination
+/// cmd_matrix(&MatrixOptions {
+///     templates: vec!["balanced".into(), "aggressive".into()],
+///     targets: vec!["web".into(), "bundler".into()],
+///     production: Some("balanced:web".into()),
+///     margin_percent: 2.0,
+///     json: false,
+/// })?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn cmd_matrix(options: &MatrixOptions) -> Result<()> {
+    let templates = options
+        .templates
+        .iter()
+        .map(|name| Template::get(name).with_context(|| format!("Unknown template: {}", name)))
+        .collect::<Result<Vec<_>>>()?;
+    let targets = options
+        .targets
+        .iter()
+        .map(|name| name.parse::<BindgenTarget>().map_err(anyhow::Error::msg))
+        .collect::<Result<Vec<_>>>()?;
+    // Validate before spending minutes on builds
+    let production = options
+        .production
+        .as_deref()
+        .map(Combination::parse)
+        .transpose()?;
+
+    let project_root = env::current_dir()?;
+    let config = config::ConfigLoader::load(&project_root).unwrap_or_default();
+    let matrix = BuildMatrix::new(&project_root, templates, targets).with_env(
+        config
+            .env
+            .as_ref()
+            .map(super::workflow::stage_env)
+            .unwrap_or_default(),
+    );
+
+    if !options.json {
+        println!("{} {} Build Matrix", ROCKET, style("wasm-slim").bold());
+        println!(
+            "   {} Building {} combinations with {} cargo builds (this may take a while)...",
+            style("→").dim(),
+            options.templates.len() * options.targets.len(),
+            matrix.compile_count()
+        );
+    }
+    let report = matrix.run()?;
+
+    if options.json {
+        println!("{}", crate::redact::to_json_pretty(&report)?);
+    } else {
+        print!("{}", format_grid(&report)?);
+    }
+
+    if let Some(production) = &production {
+        report.check_production(production, options.margin_percent)?;
+        if !options.json {
+            println!(
+                "   {} {} is within {:.1}% of the smallest combination",
+                style("✓").green(),
+                production.label(),
+                options.margin_percent
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> MatrixReport {
+        let cell = |template: &str, target: &str, size_bytes| MatrixCell {
+            template: template.to_string(),
+            target: target.to_string(),
+            size_bytes,
+            postprocess_ms: 800,
+        };
+        MatrixReport {
+            builds: vec![
+                TemplateBuild {
+                    template: "balanced".to_string(),
+                    compile_ms: 41_200,
+                    reused_from: None,
+                },
+                TemplateBuild {
+                    template: "aggressive".to_string(),
+                    compile_ms: 0,
+                    reused_from: Some("balanced".to_string()),
+                },
+            ],
+            targets: vec!["web".to_string(), "bundler".to_string()],
+            cells: vec![
+                cell("balanced", "web", 100_000),
+                cell("balanced", "bundler", 99_500),
+                cell("aggressive", "web", 97_000),
+                cell("aggressive", "bundler", 96_800),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_check_production_applies_margin() {
+        let report = report();
+        let production = Combination::parse("balanced:web").unwrap();
+
+        assert!(report.check_production(&production, 5.0).is_ok());
+        let err = report.check_production(&production, 2.0).unwrap_err();
+        assert!(err.to_string().contains("aggressive:bundler"));
+
+        let best = Combination::parse("aggressive:bundler").unwrap();
+        assert!(report.check_production(&best, 0.0).is_ok());
+        let missing = Combination::parse("minimal:deno").unwrap();
+        assert!(report.check_production(&missing, 5.0).is_err());
+    }
+
+    #[test]
+    fn test_reused_builds_shares_identical_profiles() {
+        let templates: Vec<Template> = ["minimal", "balanced", "aggressive"]
+            .iter()
+            .map(|name| Template::get(name).unwrap())
+            .collect();
+        // minimal and aggressive both build with opt-level = "z"
+        assert_eq!(reused_builds(&templates), vec![None, None, Some(0)]);
+
+        let (level, args) = wasm_opt_settings(&templates[2]);
+        assert_eq!(level, WasmOptLevel::Oz);
+        assert!(args.contains(&"--gufa-optimizing".to_string()));
+        assert!(!args.contains(&"--enable-bulk-memory".to_string()));
+    }
+
+    #[test]
+    fn test_format_grid_shows_sizes_and_compile_times() {
+        let text = format_grid(&report()).unwrap();
+        assert!(text.contains("97.66 KiB"));
+        assert!(text.contains("41.2s"));
+        assert!(text.contains("reused balanced"));
+        assert!(text.contains("Smallest: aggressive:bundler"));
+    }
+}
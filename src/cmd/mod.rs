@@ -5,18 +5,40 @@
 
 pub mod analyze;
 pub mod build;
+pub mod clean;
 pub mod compare;
 pub mod completions;
+pub mod config;
+pub mod db;
+pub mod history;
 pub mod init;
+pub mod matrix;
+pub mod output_format;
+pub mod pager;
+pub mod report;
+pub mod tutor;
+pub mod verify;
 pub mod workflow;
 
 // Re-export command functions for convenient access
 pub use analyze::{
     analyze_assets, analyze_bloat, analyze_dependencies, analyze_features, analyze_wasm_binary,
-    cmd_analyze,
+    cmd_analyze, cmd_analyze_with_options, AnalyzeOptions,
 };
 pub use build::cmd_build;
-pub use compare::cmd_compare;
+pub use clean::{cmd_clean, CleanOptions};
+pub use compare::{
+    cmd_compare, cmd_compare_folded, cmd_compare_many, cmd_compare_with_limit,
+    cmd_compare_with_options, CompareOptions,
+};
 pub use completions::cmd_completions;
+pub use config::cmd_config_validate;
+pub use db::cmd_db_update;
+pub use history::cmd_history_compact;
 pub use init::cmd_init;
+pub use matrix::{cmd_matrix, MatrixOptions};
+pub use output_format::OutputFormat;
+pub use report::cmd_report_archive;
+pub use tutor::{cmd_tutor, TutorOptions};
+pub use verify::cmd_verify_recommendation;
 pub use workflow::BuildWorkflow;
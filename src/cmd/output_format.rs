@@ -0,0 +1,66 @@
+//! Machine-readable output formats shared by `analyze` and `compare`
+
+use std::fmt;
+
+/// Format to print instead of the console report (`--format`)
+///
+/// Each command and mode accepts a subset and rejects the others with the
+/// formats it does support.
+///
+/// # Examples
+///
+/// ```
+/// use wasm_slim::cmd::OutputFormat;
+///
+/// assert_eq!("SARIF".parse::<OutputFormat>(), Ok(OutputFormat::Sarif));
+/// assert_eq!(OutputFormat::Markdown.to_string(), "markdown");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON
+    Json,
+    /// Comma-separated table
+    Csv,
+    /// SARIF log for code scanning upload
+    Sarif,
+    /// Markdown for a pull request comment
+    Markdown,
+    /// Self-contained HTML page
+    Html,
+}
+
+impl OutputFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Sarif => "sarif",
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "sarif" => Ok(Self::Sarif),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(format!(
+                "Unknown format: {}. Valid formats: json, csv, sarif, markdown, html",
+                s
+            )),
+        }
+    }
+}
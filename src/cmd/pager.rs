@@ -0,0 +1,108 @@
+//! Pager integration for long console reports
+//!
+//! When stdout is an interactive terminal, report commands re-run themselves
+//! with stdout piped into a pager (`$PAGER`, or `less -FRX`). `less -F` exits
+//! immediately when the output fits on one screen, so short reports behave
+//! as before.
+//!
+//! Paging is skipped when:
+//! - stdout is not a terminal (CI logs, redirects, pipes)
+//! - `--no-pager` or `--json` is given
+//! - `PAGER` is set to an empty string or `cat`
+//! - the pager cannot be started (output is written directly instead)
+
+use anyhow::{Context, Result};
+use std::ffi::OsString;
+use std::process::{Command, Stdio};
+
+/// Set on the re-spawned child so it writes directly to the pager
+pub const NO_PAGER_ENV: &str = "WASM_SLIM_NO_PAGER";
+
+/// Pager used when `$PAGER` is not set
+const DEFAULT_PAGER: &str = "less -FRX";
+
+/// Resolve the pager program and its arguments from the `$PAGER` value
+///
+/// Returns `None` when paging is disabled via `PAGER=""` or `PAGER=cat`.
+pub fn resolve_pager(pager_env: Option<&str>) -> Option<(String, Vec<String>)> {
+    let spec = pager_env.unwrap_or(DEFAULT_PAGER);
+    let mut parts = spec.split_whitespace().map(str::to_string);
+    let program = parts.next()?;
+    if program == "cat" {
+        return None;
+    }
+    Some((program, parts.collect()))
+}
+
+/// Decide whether output should go through a pager
+///
+/// # Arguments
+///
+/// * `disabled` - `--no-pager` was given or the command emits JSON
+/// * `is_child` - this process was already spawned behind a pager
+/// * `stdout_is_term` - stdout is an interactive terminal
+pub fn should_page(disabled: bool, is_child: bool, stdout_is_term: bool) -> bool {
+    !disabled && !is_child && stdout_is_term
+}
+
+/// Re-run the current command with stdout piped into the pager
+///
+/// Returns the child's exit code. Fails without side effects if the pager
+/// cannot be started, so the caller can fall back to direct output.
+pub fn run_through_pager(program: &str, args: &[String]) -> Result<i32> {
+    let exe = std::env::current_exe().context("Failed to locate wasm-slim executable")?;
+    let cli_args: Vec<OsString> = std::env::args_os().skip(1).collect();
+
+    let mut pager = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start pager '{}'", program))?;
+    let pager_stdin = pager.stdin.take().context("Failed to open pager input")?;
+
+    // Colors are decided per process; keep them for the piped child
+    let status = Command::new(exe)
+        .args(cli_args)
+        .env(NO_PAGER_ENV, "1")
+        .env("CLICOLOR_FORCE", "1")
+        .stdout(Stdio::from(pager_stdin))
+        .status()
+        .context("Failed to run wasm-slim behind the pager")?;
+
+    pager.wait().context("Pager exited abnormally")?;
+    Ok(status.code().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_pager_defaults_to_less() {
+        let (program, args) = resolve_pager(None).unwrap();
+        assert_eq!(program, "less");
+        assert_eq!(args, vec!["-FRX".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_pager_splits_custom_pager() {
+        let (program, args) = resolve_pager(Some("most -s")).unwrap();
+        assert_eq!(program, "most");
+        assert_eq!(args, vec!["-s".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_pager_disabled_by_empty_or_cat() {
+        assert!(resolve_pager(Some("")).is_none());
+        assert!(resolve_pager(Some("   ")).is_none());
+        assert!(resolve_pager(Some("cat")).is_none());
+    }
+
+    #[test]
+    fn test_should_page_only_for_interactive_parent() {
+        assert!(should_page(false, false, true));
+        assert!(!should_page(true, false, true));
+        assert!(!should_page(false, true, true));
+        assert!(!should_page(false, false, false));
+    }
+}
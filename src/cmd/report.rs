@@ -0,0 +1,70 @@
+//! Report command implementations
+//!
+//! Handles `wasm-slim report archive`, which stores an auditable record of a
+//! shipped build's size in `.wasm-slim/reports/`.
+
+use anyhow::{Context, Result};
+use console::style;
+use std::env;
+use std::path::Path;
+
+use crate::cicd::ReportArchiver;
+use crate::fmt::{format_bytes, CHECKMARK};
+
+/// Archive the size report for a WASM file and prune old archives
+///
+/// # Examples
+///
+/// ```no_run
+/// use wasm_slim::cmd::report::cmd_report_archive;
+///
+/// // Archive the shipped bundle, keeping the 20 newest archives
+/// cmd_report_archive("pkg/app_bg.wasm", 20)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn cmd_report_archive(wasm_file: &str, keep: usize) -> Result<()> {
+    let wasm_path = Path::new(wasm_file);
+    if !wasm_path.exists() {
+        anyhow::bail!("WASM file not found: {}", wasm_file);
+    }
+
+    let project_root = env::current_dir()?;
+    let archiver = ReportArchiver::new(&project_root);
+    let archive = archiver
+        .create(wasm_path)
+        .context("Failed to archive size report")?;
+
+    let archive_size = std::fs::metadata(&archive).map(|m| m.len()).unwrap_or(0);
+    println!(
+        "{} Archived report to {} ({})",
+        CHECKMARK,
+        style(archive.display()).cyan(),
+        format_bytes(archive_size)
+    );
+
+    let removed = archiver.prune(keep)?;
+    if !removed.is_empty() {
+        println!(
+            "   {} Removed {} old archive(s) (keeping {})",
+            style("→").dim(),
+            removed.len(),
+            keep
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmd_report_archive_missing_file() {
+        let result = cmd_report_archive("/nonexistent/app_bg.wasm", 20);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("WASM file not found"));
+    }
+}
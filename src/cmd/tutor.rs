@@ -0,0 +1,492 @@
+//! Tutor command implementation
+//!
+//! Handles `wasm-slim tutor`, which walks through optimizing one of the
+//! bundled example projects: build it as it is, see what takes the space,
+//! let wasm-slim apply its fixes and compare the two builds. Every step runs
+//! the real command in a scratch copy of the example and explains what its
+//! output shows, so a run also checks cargo, the wasm32 target, wasm-bindgen
+//! and wasm-opt end to end.
+
+use anyhow::{Context, Result};
+use console::style;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::fmt::{CHECKMARK, CROSSMARK, INFO, SPARKLES};
+use crate::infra::{CommandExecutor, RealCommandExecutor};
+
+/// Target the examples are built for
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Cargo target directory of the baseline build, kept apart from the one
+/// `wasm-slim build` rebuilds into
+const BASELINE_TARGET_DIR: &str = "target/tutor-baseline";
+
+/// Placeholder for the baseline artifact in [`Example::focus_args`]
+const BASELINE_PLACEHOLDER: &str = "{baseline}";
+
+/// An example project bundled with wasm-slim
+#[derive(Debug)]
+pub struct Example {
+    /// Name given to `--example`
+    pub name: &'static str,
+    /// What the example demonstrates
+    pub summary: &'static str,
+    /// Library name of the crate (`-` replaced by `_`)
+    lib_name: &'static str,
+    /// `analyze` arguments for the analysis the example is about
+    focus_args: &'static [&'static str],
+    /// What that analysis shows
+    focus_explanation: &'static str,
+    /// Project files, relative to the project root
+    files: &'static [(&'static str, &'static str)],
+}
+
+/// Examples `wasm-slim tutor` can walk through
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "basic-usage",
+        summary: "a small wasm-bindgen library with default build settings",
+        lib_name: "basic_usage_example",
+        focus_args: &["--mode", "dead", BASELINE_PLACEHOLDER],
+        focus_explanation:
+            "Code and data nothing can reach from the exports. A release build \
+            without LTO keeps whole functions the module never calls; LTO and wasm-opt remove them.",
+        files: &[
+            (
+                "Cargo.toml",
+                include_str!("../../examples/basic-usage/Cargo.toml"),
+            ),
+            (
+                "src/lib.rs",
+                include_str!("../../examples/basic-usage/src/lib.rs"),
+            ),
+        ],
+    },
+    Example {
+        name: "with-assets",
+        summary: "a library that embeds files with include_bytes! and include_str!",
+        lib_name: "with_assets_example",
+        focus_args: &["--mode", "assets"],
+        focus_explanation: "Files compiled into the module. Each one is downloaded with the \
+            code on every load; the larger ones are better fetched at runtime and cached.",
+        files: &[
+            (
+                "Cargo.toml",
+                include_str!("../../examples/with-assets/Cargo.toml"),
+            ),
+            (
+                ".wasm-slim.toml",
+                include_str!("../../examples/with-assets/.wasm-slim.toml"),
+            ),
+            (
+                "src/lib.rs",
+                include_str!("../../examples/with-assets/src/lib.rs"),
+            ),
+            (
+                "assets/data.json",
+                include_str!("../../examples/with-assets/assets/data.json"),
+            ),
+            (
+                "assets/font.txt",
+                include_str!("../../examples/with-assets/assets/font.txt"),
+            ),
+        ],
+    },
+    Example {
+        name: "ci-integration",
+        summary: "a library with a size budget, set up for CI",
+        lib_name: "ci_integration_example",
+        focus_args: &["--mode", "deps"],
+        focus_explanation: "Dependencies known to weigh on WASM size, with lighter \
+            alternatives or feature flags to turn off.",
+        files: &[
+            (
+                "Cargo.toml",
+                include_str!("../../examples/ci-integration/Cargo.toml"),
+            ),
+            (
+                ".wasm-slim.toml",
+                include_str!("../../examples/ci-integration/.wasm-slim.toml"),
+            ),
+            (
+                "src/lib.rs",
+                include_str!("../../examples/ci-integration/src/lib.rs"),
+            ),
+        ],
+    },
+];
+
+impl Example {
+    /// Find a bundled example by name
+    pub fn find(name: &str) -> Result<&'static Example> {
+        EXAMPLES.iter().find(|e| e.name == name).ok_or_else(|| {
+            let names: Vec<&str> = EXAMPLES.iter().map(|e| e.name).collect();
+            anyhow::anyhow!(
+                "Unknown example: {}. Valid examples: {}",
+                name,
+                names.join(", ")
+            )
+        })
+    }
+
+    /// Write the project files into `dir`
+    pub fn write_to(&self, dir: &Path) -> Result<()> {
+        for (path, content) in self.files {
+            let path = dir.join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Artifact of the plain release build, relative to the project root
+    pub fn baseline_wasm(&self) -> PathBuf {
+        Path::new(BASELINE_TARGET_DIR)
+            .join(WASM_TARGET)
+            .join("release")
+            .join(format!("{}.wasm", self.lib_name))
+    }
+
+    /// Artifact `wasm-slim build` leaves, relative to the project root
+    pub fn optimized_wasm(&self) -> PathBuf {
+        Path::new("pkg").join(format!("{}_bg.wasm", self.lib_name))
+    }
+}
+
+/// Program a tutorial step runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepProgram {
+    /// `cargo`
+    Cargo,
+    /// This wasm-slim executable
+    WasmSlim,
+}
+
+/// One step of the walkthrough
+#[derive(Debug, Clone)]
+pub struct TutorStep {
+    /// Short title
+    pub title: &'static str,
+    /// What the command does and how to read its output
+    pub explanation: String,
+    /// Program to run
+    pub program: StepProgram,
+    /// Arguments, run from the project root
+    pub args: Vec<String>,
+    /// File the step must produce, relative to the project root
+    pub produces: Option<PathBuf>,
+}
+
+impl TutorStep {
+    /// The command as a user would type it
+    pub fn command_line(&self) -> String {
+        let program = match self.program {
+            StepProgram::Cargo => "cargo",
+            StepProgram::WasmSlim => "wasm-slim",
+        };
+        std::iter::once(program)
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Steps of the walkthrough for `example`
+pub fn plan_steps(example: &Example) -> Vec<TutorStep> {
+    let baseline = example.baseline_wasm().display().to_string();
+    let optimized = example.optimized_wasm().display().to_string();
+    let strings = |args: &[&str]| -> Vec<String> {
+        args.iter()
+            .map(|arg| arg.replace(BASELINE_PLACEHOLDER, &baseline))
+            .collect()
+    };
+
+    vec![
+        TutorStep {
+            title: "Build the example as it is",
+            explanation: "A plain release build: no LTO, speed-oriented opt-level 3, \
+                symbols kept. Its size is the baseline the next steps improve on."
+                .to_string(),
+            program: StepProgram::Cargo,
+            args: strings(&[
+                "build",
+                "--lib",
+                "--release",
+                "--target",
+                WASM_TARGET,
+                "--target-dir",
+                BASELINE_TARGET_DIR,
+            ]),
+            produces: Some(example.baseline_wasm()),
+        },
+        TutorStep {
+            title: "See what takes the space",
+            explanation: "The largest items of the module. `code[N]` rows are functions and \
+                `data[N]` rows are data segments such as string literals; the percent column \
+                is each item's share of the file."
+                .to_string(),
+            program: StepProgram::WasmSlim,
+            args: strings(&["analyze", &baseline, "--mode", "top", "--max-items", "10"]),
+            produces: None,
+        },
+        TutorStep {
+            title: "Look closer at what this example is about",
+            explanation: example.focus_explanation.to_string(),
+            program: StepProgram::WasmSlim,
+            args: strings(&[&["analyze"], example.focus_args].concat()),
+            produces: None,
+        },
+        TutorStep {
+            title: "Apply the fixes",
+            explanation: "wasm-slim writes a size-oriented release profile (LTO, opt-level, \
+                strip, one codegen unit) into Cargo.toml, keeping a backup, then rebuilds and \
+                runs wasm-bindgen and wasm-opt. The optimized module lands in pkg/."
+                .to_string(),
+            program: StepProgram::WasmSlim,
+            args: strings(&["build"]),
+            produces: Some(example.optimized_wasm()),
+        },
+        TutorStep {
+            title: "Compare the two builds",
+            explanation: "The size change, the change after gzip and brotli (what users \
+                download), and the symbols whose size changed the most."
+                .to_string(),
+            program: StepProgram::WasmSlim,
+            args: strings(&["compare", &baseline, &optimized]),
+            produces: None,
+        },
+    ]
+}
+
+/// Options for `wasm-slim tutor`
+#[derive(Debug, Clone)]
+pub struct TutorOptions {
+    /// Bundled example to optimize
+    pub example: String,
+    /// Keep the scratch copy instead of removing it at the end
+    pub keep: bool,
+    /// Wait for Enter before each step when run in a terminal
+    pub pause: bool,
+}
+
+impl Default for TutorOptions {
+    fn default() -> Self {
+        Self {
+            example: EXAMPLES[0].name.to_string(),
+            keep: false,
+            pause: true,
+        }
+    }
+}
+
+/// Runs the walkthrough steps in a project directory
+pub struct Tutor<CE: CommandExecutor = RealCommandExecutor> {
+    project_dir: PathBuf,
+    cmd_executor: CE,
+}
+
+impl Tutor {
+    /// Create a tutor that runs real commands in `project_dir`
+    pub fn new(project_dir: impl Into<PathBuf>) -> Self {
+        Self::with_executor(project_dir, RealCommandExecutor)
+    }
+}
+
+impl<CE: CommandExecutor> Tutor<CE> {
+    /// Create a tutor with a custom command executor
+    pub fn with_executor(project_dir: impl Into<PathBuf>, cmd_executor: CE) -> Self {
+        Self {
+            project_dir: project_dir.into(),
+            cmd_executor,
+        }
+    }
+
+    /// Run one step with its output going straight to the terminal
+    pub fn run_step(&self, step: &TutorStep) -> Result<()> {
+        let mut cmd = match step.program {
+            StepProgram::Cargo => Command::new("cargo"),
+            StepProgram::WasmSlim => {
+                let exe =
+                    std::env::current_exe().context("Failed to locate wasm-slim executable")?;
+                let mut cmd = Command::new(exe);
+                cmd.arg("--no-pager");
+                cmd
+            }
+        };
+        cmd.args(&step.args).current_dir(&self.project_dir);
+
+        let status = self
+            .cmd_executor
+            .status(&mut cmd)
+            .with_context(|| format!("Failed to run `{}`", step.command_line()))?;
+        if !status.success() {
+            anyhow::bail!("`{}` failed ({})", step.command_line(), status);
+        }
+        if let Some(artifact) = &step.produces {
+            if !self.project_dir.join(artifact).exists() {
+                anyhow::bail!(
+                    "`{}` succeeded but did not produce {}",
+                    step.command_line(),
+                    artifact.display()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Walk through optimizing a bundled example project
+///
+/// Stops at the first failing step, which points at the part of the
+/// toolchain that needs attention.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wasm_slim::cmd::tutor::{cmd_tutor, TutorOptions};
+///
+/// cmd_tutor(&TutorOptions {
+///     example: "with-assets".to_string(),
+///     pause: false,
+///     ..Default::default()
+/// })?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn cmd_tutor(options: &TutorOptions) -> Result<()> {
+    let example = Example::find(&options.example)?;
+    println!(
+        "{} {} Tutorial: {}",
+        SPARKLES,
+        style("wasm-slim").bold(),
+        style(example.name).cyan().bold()
+    );
+    println!("   {}", example.summary);
+
+    let project_dir = std::env::temp_dir().join(format!(
+        "wasm-slim-tutor-{}-{}",
+        example.name,
+        std::process::id()
+    ));
+    if project_dir.exists() {
+        std::fs::remove_dir_all(&project_dir)
+            .with_context(|| format!("Failed to clear {}", project_dir.display()))?;
+    }
+    example.write_to(&project_dir)?;
+    println!(
+        "   Working in a scratch copy: {}",
+        style(project_dir.display()).dim()
+    );
+
+    let tutor = Tutor::new(&project_dir);
+    let steps = plan_steps(example);
+    let pause = options.pause && console::Term::stdout().is_term();
+    let mut outcome = Ok(());
+    for (index, step) in steps.iter().enumerate() {
+        println!(
+            "\n{} {}",
+            style(format!("Step {}/{}:", index + 1, steps.len())).bold(),
+            style(step.title).bold()
+        );
+        println!("   {} {}", INFO, step.explanation);
+        println!("   {}", style(format!("$ {}", step.command_line())).dim());
+        if pause {
+            println!("   {}", style("Press Enter to run it").dim());
+            console::Term::stdout().read_line()?;
+        }
+        println!();
+
+        if let Err(e) = tutor.run_step(step) {
+            println!("\n{} Step {} failed: {:#}", CROSSMARK, index + 1, e);
+            outcome = Err(e.context(format!("Tutorial step {} failed", index + 1)));
+            break;
+        }
+        println!("\n{} {}", CHECKMARK, step.title);
+    }
+
+    if options.keep || outcome.is_err() {
+        println!(
+            "\n   Scratch copy kept at {}",
+            style(project_dir.display()).cyan()
+        );
+    } else {
+        std::fs::remove_dir_all(&project_dir)
+            .with_context(|| format!("Failed to remove {}", project_dir.display()))?;
+    }
+
+    outcome?;
+    println!(
+        "\n{} Tutorial complete: every step ran, so the toolchain works end to end.",
+        CHECKMARK
+    );
+    println!("   Run the same steps in your own project, starting with `wasm-slim analyze`.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::mock_exit_status;
+    use std::process::{ExitStatus, Output};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_examples_write_their_files_and_plan_steps_on_their_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let example = Example::find("with-assets").unwrap();
+        example.write_to(temp_dir.path()).unwrap();
+        assert!(temp_dir.path().join("Cargo.toml").exists());
+        assert!(temp_dir.path().join("assets/font.txt").exists());
+
+        let steps = plan_steps(example);
+        let commands: Vec<String> = steps.iter().map(TutorStep::command_line).collect();
+        assert_eq!(
+            commands[0],
+            "cargo build --lib --release --target wasm32-unknown-unknown --target-dir target/tutor-baseline"
+        );
+        assert_eq!(commands[2], "wasm-slim analyze --mode assets");
+        assert_eq!(
+            commands[4],
+            "wasm-slim compare target/tutor-baseline/wasm32-unknown-unknown/release/with_assets_example.wasm pkg/with_assets_example_bg.wasm"
+        );
+        assert!(Example::find("missing")
+            .unwrap_err()
+            .to_string()
+            .contains("basic-usage, with-assets, ci-integration"));
+    }
+
+    /// Reports success for every command without running it
+    struct Succeeds;
+
+    impl CommandExecutor for Succeeds {
+        fn status(&self, _cmd: &mut Command) -> std::io::Result<ExitStatus> {
+            Ok(mock_exit_status(0))
+        }
+
+        fn output(&self, _cmd: &mut Command) -> std::io::Result<Output> {
+            Ok(Output {
+                status: mock_exit_status(0),
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_run_step_fails_when_the_artifact_is_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let example = Example::find("basic-usage").unwrap();
+        let steps = plan_steps(example);
+        let tutor = Tutor::with_executor(temp_dir.path(), Succeeds);
+
+        let err = tutor.run_step(&steps[0]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("did not produce target/tutor-baseline"));
+        tutor.run_step(&steps[1]).unwrap();
+    }
+}
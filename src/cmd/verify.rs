@@ -0,0 +1,101 @@
+//! Verify-recommendation command implementation
+//!
+//! Handles the `wasm-slim verify-recommendation <id>` command which turns an
+//! estimated saving into a measured one by rebuilding the project with the
+//! recommended change applied in a scratch workspace.
+
+use anyhow::{Context, Result};
+use console::style;
+use std::env;
+
+use crate::analyzer::verify::{RecommendationVerifier, VerificationResult};
+use crate::fmt::{format_bytes, CHART, MICROSCOPE};
+
+/// Verify a recommendation by measuring its actual size impact
+///
+/// # Examples
+///
+/// ```no_run
+/// use wasm_slim::cmd::verify::cmd_verify_recommendation;
+///
+/// // Measure what disabling regex's default features really saves
+/// cmd_verify_recommendation("default-features:regex", false)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn cmd_verify_recommendation(id: &str, json: bool) -> Result<()> {
+    if !json {
+        println!(
+            "{} {} Verifying Recommendation",
+            MICROSCOPE,
+            style("wasm-slim").bold()
+        );
+        println!("   ID: {}", style(id).cyan());
+        println!(
+            "   {} Building baseline and experiment (this may take a while)...",
+            style("→").dim()
+        );
+    }
+
+    let project_root = env::current_dir()?;
+    let result = RecommendationVerifier::new(&project_root)
+        .verify(id)
+        .with_context(|| format!("Failed to verify recommendation '{}'", id))?;
+
+    if json {
+        println!("{}", crate::redact::to_json_pretty(&result)?);
+    } else {
+        print_verification_result(&result);
+    }
+
+    Ok(())
+}
+
+/// Print measured verification outcome
+fn print_verification_result(result: &VerificationResult) {
+    println!();
+    println!("{} {}", CHART, style(&result.description).bold());
+    println!(
+        "   Baseline:   {}",
+        style(format_bytes(result.baseline_bytes)).dim()
+    );
+    println!(
+        "   Experiment: {}",
+        style(format_bytes(result.experiment_bytes)).bold()
+    );
+
+    let delta = format!(
+        "{}{} ({:+.1}%)",
+        if result.delta_bytes < 0 { "-" } else { "+" },
+        format_bytes(result.delta_bytes.unsigned_abs()),
+        result.delta_percent
+    );
+    if result.delta_bytes < 0 {
+        println!("   Measured:   {}", style(delta).green().bold());
+    } else {
+        println!("   Measured:   {}", style(delta).yellow().bold());
+        println!(
+            "   {} The change did not reduce size for this project",
+            style("→").dim()
+        );
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::verify::Experiment;
+
+    #[test]
+    fn test_print_verification_result_handles_growth_and_shrink() {
+        let exp = Experiment::parse("profile:opt-level=z").unwrap();
+        print_verification_result(&VerificationResult::new(&exp, 2000, 1500));
+        print_verification_result(&VerificationResult::new(&exp, 1500, 2000));
+    }
+
+    #[test]
+    fn test_cmd_verify_recommendation_rejects_unknown_id() {
+        let err = cmd_verify_recommendation("not-a-recommendation", true).unwrap_err();
+        assert!(format!("{:#}", err).contains("Unknown recommendation ID"));
+    }
+}
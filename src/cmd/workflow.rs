@@ -16,7 +16,9 @@
 //!    runs wasm-opt for further size reduction, and tracks build metrics.
 //!
 //! 3. **CI/CD Metrics Validation**: Validates build outputs against configured size budgets
-//!    and records historical build data for regression detection.
+//!    and records historical build data for regression detection. An artifact far
+//!    above the historical median that carries DWARF sections fails early as a
+//!    likely debug build rather than as a budget overrun.
 //!
 //! # Examples
 //!
@@ -59,7 +61,7 @@
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
-use crate::{config, optimizer, pipeline};
+use crate::{analyzer, cicd, config, optimizer, pipeline};
 
 /// Result of the complete build workflow
 #[derive(Debug)]
@@ -76,6 +78,10 @@ pub struct BuildResult {
     pub dry_run: bool,
     /// Files that would be optimized in dry-run mode
     pub dry_run_files: Vec<String>,
+    /// Size change since the previous recorded build (not in dry-run mode)
+    pub regression: Option<cicd::history::RegressionResult>,
+    /// DWARF, names or symbol tables left in the artifact (not in dry-run mode)
+    pub debug_info: Option<analyzer::DebugInfoReport>,
 }
 
 /// Result type for Cargo.toml optimization with backup information
@@ -91,6 +97,8 @@ type OptimizationResult = (Vec<String>, Vec<String>, Vec<(PathBuf, String)>);
 /// 3. CI/CD metrics validation
 pub struct BuildWorkflow {
     project_root: PathBuf,
+    resume: bool,
+    split_debug: bool,
 }
 
 impl BuildWorkflow {
@@ -98,9 +106,23 @@ impl BuildWorkflow {
     pub fn new(project_root: &Path) -> Self {
         Self {
             project_root: project_root.to_path_buf(),
+            resume: false,
+            split_debug: false,
         }
     }
 
+    /// Resume from the last failed build's completed stages when inputs are unchanged
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Keep DWARF through the pipeline and move it into a `.debug.wasm` sidecar
+    pub fn with_split_debug(mut self, split_debug: bool) -> Self {
+        self.split_debug = split_debug;
+        self
+    }
+
     /// Execute the complete build workflow
     pub fn execute(
         &self,
@@ -108,6 +130,9 @@ impl BuildWorkflow {
         check_budget: bool,
         _target_dir: Option<&str>,
     ) -> Result<BuildResult> {
+        // Phase 0: Refuse to build a config that violates the team policy
+        self.enforce_policy()?;
+
         // Phase 1: Optimize Cargo.toml files and save backups
         let (cargo_changes, dry_run_files, backups) =
             self.optimize_cargo_tomls_with_backup(dry_run)?;
@@ -124,9 +149,31 @@ impl BuildWorkflow {
             }
         };
 
-        // Phase 3: Check CI/CD metrics
+        // Phase 2b: A debug artifact fails with its own error, before it
+        // skews the history it is compared against
+        if check_budget && !dry_run {
+            self.check_debug_build(&metrics)?;
+        }
+
+        // Phase 2c: Note debug info the pipeline left in (never fails the build)
+        let debug_info = if dry_run {
+            None
+        } else {
+            self.check_debug_info(&metrics)
+        };
+
+        // Phase 3: Record the build in history (a failure here never fails the build)
+        let regression = if dry_run {
+            None
+        } else {
+            self.record_history(&metrics).ok().flatten()
+        };
+
+        // Phase 4: Check CI/CD metrics
         let (budget_check_passed, budget_threshold) = if check_budget {
-            self.check_budget(&metrics)?
+            let budget = self.check_budget(&metrics)?;
+            self.check_symbol_budget(&metrics)?;
+            budget
         } else {
             (None, None)
         };
@@ -138,9 +185,29 @@ impl BuildWorkflow {
             budget_threshold,
             dry_run,
             dry_run_files,
+            regression,
+            debug_info,
         })
     }
 
+    /// Phase 0: Check the config against the team policy and dependency rules
+    fn enforce_policy(&self) -> Result<()> {
+        let config = config::ConfigLoader::load(&self.project_root)
+            .unwrap_or_else(|_| config::ConfigFile::default());
+        let Some(result) = super::config::check_project(&self.project_root, &config)? else {
+            return Ok(());
+        };
+
+        if result.has_errors() {
+            super::config::print_policy_result(&result);
+            anyhow::bail!(
+                "Config violates the team policy or dependency rules ({} violations); run `wasm-slim config validate` for details",
+                result.errors().len()
+            );
+        }
+        Ok(())
+    }
+
     /// Phase 1: Optimize Cargo.toml files with backup support
     fn optimize_cargo_tomls_with_backup(&self, dry_run: bool) -> Result<OptimizationResult> {
         let _config = config::ConfigLoader::load(&self.project_root)
@@ -244,44 +311,235 @@ impl BuildWorkflow {
     fn run_build_pipeline(&self) -> Result<pipeline::SizeMetrics> {
         use crate::error::WasmSlimError;
 
-        let _config = config::ConfigLoader::load(&self.project_root)
+        let config = config::ConfigLoader::load(&self.project_root)
             .unwrap_or_else(|_| config::ConfigFile::default());
 
-        let pipeline_config = pipeline::PipelineConfig {
+        let mut pipeline_config = pipeline::PipelineConfig {
             opt_level: pipeline::WasmOptLevel::Oz,
             run_wasm_snip: true,
+            checkpoint: true,
+            resume: self.resume,
+            split_debug_info: self.split_debug,
+            preopt_max_bytes: config
+                .size_budget
+                .as_ref()
+                .and_then(|budget| budget.preopt_limit_bytes()),
+            env: config.env.as_ref().map(stage_env).unwrap_or_default(),
             ..Default::default()
         };
 
-        let build_pipeline = pipeline::BuildPipeline::new(&self.project_root, pipeline_config);
+        // Unknown built-in names fall back to the defaults; a shared template
+        // that cannot be fetched or verified must not be silently ignored
+        let template = match config::TemplateResolver::resolve_in(&config, &self.project_root) {
+            Ok(template) => Some(template),
+            Err(e) if config::RemoteTemplateRef::is_remote(&config.template) => return Err(e),
+            Err(_) => None,
+        };
+        if let Some(template) = template {
+            let wasm_opt = &template.wasm_opt;
+            // Only probe wasm-opt when an option needs a version check
+            let binaryen_version = if wasm_opt.low_memory_unused {
+                crate::tools::ToolChain::default()
+                    .wasm_opt
+                    .version()
+                    .ok()
+                    .and_then(|v| config::wasm_config::parse_binaryen_version(&v))
+            } else {
+                None
+            };
+            let available = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            apply_wasm_opt_tuning(wasm_opt, binaryen_version, available, &mut pipeline_config)?;
+        }
+
+        let mut build_pipeline = pipeline::BuildPipeline::new(&self.project_root, pipeline_config);
+        if let Some(profiler) = crate::profiling::global() {
+            build_pipeline.add_collector(profiler);
+        }
         build_pipeline
             .build()
             .map_err(|e| anyhow::Error::from(WasmSlimError::from(e)))
     }
 
-    /// Phase 3: Check CI/CD budget
+    /// Phase 2b: Refuse an artifact that looks like an accidental debug build
+    ///
+    /// Without history, or when the history cannot be read, nothing is checked.
+    fn check_debug_build(&self, metrics: &pipeline::SizeMetrics) -> Result<()> {
+        let Some(output) = &metrics.output else {
+            return Ok(());
+        };
+        let median = cicd::BuildHistory::load(&self.project_root)
+            .ok()
+            .and_then(|history| history.median_size());
+        if let Some(suspicion) = cicd::debug_build::detect_file(output, median)? {
+            anyhow::bail!("{}", suspicion.message(output));
+        }
+        Ok(())
+    }
+
+    /// Phase 2c: Look for DWARF, names or symbol tables in the artifact
+    fn check_debug_info(
+        &self,
+        metrics: &pipeline::SizeMetrics,
+    ) -> Option<analyzer::DebugInfoReport> {
+        let output = metrics.output.as_ref()?;
+        analyzer::DebugInfoChecker::new()
+            .check(output)
+            .ok()
+            .filter(|report| !report.is_clean())
+    }
+
+    /// Phase 3: Append the build to history with its resolved feature set
+    ///
+    /// Returns the size change since the previous build, including feature
+    /// drift when both builds recorded their features. Old builds are rolled
+    /// up per the `[history]` policy (at most once a day).
+    fn record_history(
+        &self,
+        metrics: &pipeline::SizeMetrics,
+    ) -> Result<Option<cicd::history::RegressionResult>> {
+        let features = cicd::FeatureSet::resolve(&self.project_root).ok();
+        let mut history = cicd::BuildHistory::load(&self.project_root)?;
+        let regression =
+            history.check_regression_with_features(metrics.after_bytes, features.as_ref());
+
+        let mut record = cicd::BuildRecord::new(metrics.after_bytes)?;
+        if let Some(features) = features {
+            record = record.with_features(features);
+        }
+        history.add_record(record);
+
+        let settings = config::ConfigLoader::load(&self.project_root)
+            .ok()
+            .and_then(|config| config.history);
+        let policy = cicd::CompactionPolicy::from_settings(settings.as_ref());
+        history.auto_compact(&policy, cicd::history::current_unix_secs()?);
+        history.save(&self.project_root)?;
+
+        Ok(regression)
+    }
+
+    /// Phase 4: Check CI/CD budget
+    ///
+    /// With a `[deploy]` target, the budget applies to the target's size
+    /// metric and the platform limit is checked as well.
     fn check_budget(&self, metrics: &pipeline::SizeMetrics) -> Result<(Option<bool>, Option<u64>)> {
         let config = config::ConfigLoader::load(&self.project_root)
             .unwrap_or_else(|_| config::ConfigFile::default());
+        let profile = config
+            .deploy
+            .as_ref()
+            .map(cicd::DeployProfile::from_settings);
+        let budget = config.size_budget.clone().unwrap_or_default();
+
+        // The tighter of the configured max and the platform limit
+        let max_size_kb = budget
+            .max_size_kb
+            .into_iter()
+            .chain(profile.and_then(|p| p.limit).map(|l| l.limit_kb))
+            .min();
+        let Some(max_size_kb) = max_size_kb else {
+            return Ok((None, None));
+        };
+        let max_size = max_size_kb * 1024;
 
-        if let Some(budget) = &config.size_budget {
-            if let Some(max_size_kb) = budget.max_size_kb {
-                let max_size = max_size_kb * 1024;
-                let passed = metrics.after_bytes <= max_size;
-
-                if !passed {
-                    anyhow::bail!(
-                        "WASM bundle size ({} bytes) exceeds maximum ({} bytes)",
-                        metrics.after_bytes,
-                        max_size
-                    );
-                }
+        let (size, metric) = match &profile {
+            Some(profile) => profile.measure(metrics.output.as_deref(), metrics.after_bytes),
+            None => (metrics.after_bytes, cicd::SizeMetric::Raw),
+        };
 
-                return Ok((Some(passed), Some(max_size)));
-            }
+        let mut checker = cicd::BudgetChecker::new(budget);
+        if let Some(profile) = &profile {
+            checker = checker.with_deploy_profile(profile);
+        }
+        let result = checker.with_metric(metric).check(size)?;
+        let passed = result.status != cicd::budget::BudgetStatus::OverBudget;
+        crate::summary::record("budget", crate::summary::format_budget(size, max_size));
+
+        if !passed {
+            let measured = match metric {
+                cicd::SizeMetric::Raw => "size".to_string(),
+                metric => format!("{} size", metric),
+            };
+            anyhow::bail!(
+                "WASM bundle {} ({} bytes) exceeds maximum ({} bytes)",
+                measured,
+                size,
+                max_size
+            );
+        }
+
+        Ok((Some(passed), Some(max_size)))
+    }
+
+    /// Phase 4b: Check per-symbol growth against the saved baseline, if configured
+    fn check_symbol_budget(&self, metrics: &pipeline::SizeMetrics) -> Result<()> {
+        let config = config::ConfigLoader::load(&self.project_root)
+            .unwrap_or_else(|_| config::ConfigFile::default());
+        let (Some(budget), Some(output)) = (&config.symbol_budget, &metrics.output) else {
+            return Ok(());
+        };
+
+        let baseline_path = self.project_root.join(budget.baseline_path());
+        if !baseline_path.exists() {
+            anyhow::bail!(
+                "No symbol baseline at {}; save one with `wasm-slim analyze {} --save-baseline {}`",
+                baseline_path.display(),
+                output.display(),
+                budget.baseline_path().display()
+            );
         }
+        let baseline = crate::analyzer::SymbolBaseline::load(&baseline_path)?;
+        let current = crate::analyzer::SymbolBaseline::capture(output)?;
+        let report = baseline.check(&current, budget);
+
+        if !report.violations.is_empty() {
+            print!(
+                "{}",
+                crate::analyzer::format_symbol_budget_console(&report)?
+            );
+            anyhow::bail!(
+                "{} symbols grew past the symbol budget since the baseline",
+                report.violations.len()
+            );
+        }
+        Ok(())
+    }
+}
 
-        Ok((None, None))
+/// Apply validated wasm-opt tuning options to the pipeline configuration
+///
+/// Thread count is passed as `BINARYEN_CORES` unless `[env.wasm-opt]` already
+/// sets it explicitly.
+fn apply_wasm_opt_tuning(
+    wasm_opt: &config::WasmOptConfig,
+    binaryen_version: Option<u32>,
+    available_cores: usize,
+    pipeline_config: &mut pipeline::PipelineConfig,
+) -> Result<()> {
+    wasm_opt.validate(binaryen_version)?;
+
+    if let Some(threads) = wasm_opt.effective_threads(available_cores) {
+        pipeline_config
+            .env
+            .wasm_opt
+            .entry("BINARYEN_CORES".to_string())
+            .or_insert_with(|| threads.to_string());
+    }
+    pipeline_config.wasm_opt_args = wasm_opt.tuning_args();
+
+    Ok(())
+}
+
+/// Map `[env]` config settings onto the pipeline's per-stage environment
+pub(crate) fn stage_env(settings: &config::file::EnvSettings) -> pipeline::StageEnv {
+    pipeline::StageEnv {
+        global: settings.global.clone(),
+        cargo: settings.cargo.clone(),
+        wasm_bindgen: settings.wasm_bindgen.clone(),
+        wasm_opt: settings.wasm_opt.clone(),
+        wasm_snip: settings.wasm_snip.clone(),
     }
 }
 
@@ -305,6 +563,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_workflow_with_resume() {
+        let workflow = BuildWorkflow::new(Path::new("/test/project"));
+        assert!(!workflow.resume);
+        assert!(workflow.with_resume(true).resume);
+    }
+
+    #[test]
+    fn test_build_workflow_with_split_debug() {
+        let workflow = BuildWorkflow::new(Path::new("/test/project"));
+        assert!(!workflow.split_debug);
+        assert!(workflow.with_split_debug(true).split_debug);
+    }
+
+    #[test]
+    fn test_apply_wasm_opt_tuning_caps_threads_and_sets_args() {
+        let wasm_opt = config::WasmOptConfig {
+            threads: Some(16),
+            converge: true,
+            ..Default::default()
+        };
+        let mut pipeline_config = pipeline::PipelineConfig::default();
+
+        apply_wasm_opt_tuning(&wasm_opt, None, 2, &mut pipeline_config).unwrap();
+        assert_eq!(pipeline_config.env.wasm_opt["BINARYEN_CORES"], "2");
+        assert_eq!(pipeline_config.wasm_opt_args, vec!["--converge"]);
+    }
+
+    #[test]
+    fn test_apply_wasm_opt_tuning_keeps_explicit_env_and_rejects_old_binaryen() {
+        let mut pipeline_config = pipeline::PipelineConfig::default();
+        pipeline_config
+            .env
+            .wasm_opt
+            .insert("BINARYEN_CORES".into(), "1".into());
+        let threads = config::WasmOptConfig {
+            threads: Some(4),
+            ..Default::default()
+        };
+        apply_wasm_opt_tuning(&threads, None, 8, &mut pipeline_config).unwrap();
+        assert_eq!(pipeline_config.env.wasm_opt["BINARYEN_CORES"], "1");
+
+        let low_memory = config::WasmOptConfig {
+            low_memory_unused: true,
+            ..Default::default()
+        };
+        assert!(apply_wasm_opt_tuning(&low_memory, Some(80), 8, &mut pipeline_config).is_err());
+    }
+
+    #[test]
+    fn test_stage_env_maps_all_sections() {
+        let mut settings = config::file::EnvSettings::default();
+        settings.global.insert("A".into(), "1".into());
+        settings.wasm_bindgen.insert("B".into(), "2".into());
+
+        let env = stage_env(&settings);
+        assert_eq!(env.global["A"], "1");
+        assert_eq!(env.wasm_bindgen["B"], "2");
+        assert!(env.cargo.is_empty());
+    }
+
     #[test]
     fn test_build_workflow_stores_project_root() {
         let root = Path::new("/test/project");
@@ -321,11 +640,14 @@ mod tests {
             metrics: SizeMetrics {
                 before_bytes: 1000,
                 after_bytes: 800,
+                ..Default::default()
             },
             budget_check_passed: None,
             budget_threshold: None,
             dry_run: false,
             dry_run_files: vec![],
+            regression: None,
+            debug_info: None,
         };
         assert_eq!(result.cargo_changes.len(), 1);
         assert_eq!(result.metrics.before_bytes, 1000);
@@ -341,11 +663,14 @@ mod tests {
             metrics: SizeMetrics {
                 before_bytes: 2000,
                 after_bytes: 1500,
+                ..Default::default()
             },
             budget_check_passed: Some(true),
             budget_threshold: Some(2000),
             dry_run: false,
             dry_run_files: vec![],
+            regression: None,
+            debug_info: None,
         };
         assert_eq!(result.budget_check_passed, Some(true));
         assert_eq!(result.budget_threshold, Some(2000));
@@ -360,11 +685,14 @@ mod tests {
             metrics: SizeMetrics {
                 before_bytes: 1000,
                 after_bytes: 2500,
+                ..Default::default()
             },
             budget_check_passed: Some(false),
             budget_threshold: Some(2000),
             dry_run: false,
             dry_run_files: vec![],
+            regression: None,
+            debug_info: None,
         };
         assert_eq!(result.budget_check_passed, Some(false));
         assert!(result.metrics.after_bytes > result.budget_threshold.unwrap());
@@ -379,11 +707,14 @@ mod tests {
             metrics: SizeMetrics {
                 before_bytes: 1000,
                 after_bytes: 1000,
+                ..Default::default()
             },
             budget_check_passed: None,
             budget_threshold: None,
             dry_run: true,
             dry_run_files: vec!["file1.toml".to_string(), "file2.toml".to_string()],
+            regression: None,
+            debug_info: None,
         };
         assert!(result.dry_run);
         assert_eq!(result.dry_run_files.len(), 2);
@@ -402,11 +733,14 @@ mod tests {
             metrics: SizeMetrics {
                 before_bytes: 2000,
                 after_bytes: 1000,
+                ..Default::default()
             },
             budget_check_passed: None,
             budget_threshold: None,
             dry_run: false,
             dry_run_files: vec![],
+            regression: None,
+            debug_info: None,
         };
         assert_eq!(result.cargo_changes.len(), 3);
         assert!(result.metrics.before_bytes > result.metrics.after_bytes);
@@ -538,11 +872,14 @@ mod tests {
             metrics: SizeMetrics {
                 before_bytes: 1000,
                 after_bytes: 800,
+                ..Default::default()
             },
             budget_check_passed: None,
             budget_threshold: None,
             dry_run: false,
             dry_run_files: vec![],
+            regression: None,
+            debug_info: None,
         };
 
         // Verify Debug trait is implemented
@@ -559,11 +896,14 @@ mod tests {
             metrics: SizeMetrics {
                 before_bytes: 5000,
                 after_bytes: 3000,
+                ..Default::default()
             },
             budget_check_passed: Some(true),
             budget_threshold: Some(4000),
             dry_run: true,
             dry_run_files: vec!["Cargo.toml".to_string()],
+            regression: None,
+            debug_info: None,
         };
 
         assert_eq!(result.cargo_changes.len(), 2);
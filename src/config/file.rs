@@ -1,7 +1,10 @@
 //! Configuration file data structures
 
+use crate::analyzer::heavy_deps::AlternativeType;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 /// Configuration file name
 pub const CONFIG_FILE_NAME: &str = ".wasm-slim.toml";
@@ -13,6 +16,10 @@ pub struct ConfigFile {
     #[serde(default = "default_template")]
     pub template: String,
 
+    /// Expected SHA-256 of a remote (`github:`) template file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_sha256: Option<String>,
+
     /// Custom profile settings (overrides template)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub profile: Option<ProfileSettings>,
@@ -24,6 +31,59 @@ pub struct ConfigFile {
     /// Size budget settings
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size_budget: Option<SizeBudget>,
+
+    /// Size budget for a whole wasm-pack package (`analyze pkg/`): the
+    /// `.wasm`, JS glue, `.d.ts` and snippets together
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_budget: Option<SizeBudget>,
+
+    /// Per-symbol growth limits against a saved baseline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_budget: Option<SymbolBudget>,
+
+    /// Allocator profiling settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocator: Option<AllocatorSettings>,
+
+    /// Environment variables injected into build tools
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<EnvSettings>,
+
+    /// Report localization settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub i18n: Option<I18nSettings>,
+
+    /// Analysis tuning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<AnalysisSettings>,
+
+    /// Deployment target profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy: Option<DeploySettings>,
+
+    /// Build history retention
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<HistorySettings>,
+
+    /// Secrets redacted from reports and logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redaction: Option<RedactionSettings>,
+
+    /// Shared team policy (path relative to the project, or http(s) URL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+
+    /// Banned and discouraged dependencies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crates: Option<CrateRules>,
+
+    /// Project entries for the heavy-dependency database
+    #[serde(
+        rename = "heavy-deps",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub heavy_deps: BTreeMap<String, HeavyDepSettings>,
 }
 
 fn default_template() -> String {
@@ -55,11 +115,27 @@ pub struct ProfileSettings {
 }
 
 /// wasm-opt settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WasmOptSettings {
     /// wasm-opt flags (e.g., ["-Oz", "--strip-debug"])
     #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<Vec<String>>,
+
+    /// Worker thread count (capped at available cores)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u32>,
+
+    /// Pass `--low-memory-unused` (requires binaryen 90+)
+    #[serde(
+        rename = "low-memory-unused",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub low_memory_unused: Option<bool>,
+
+    /// Pass `--converge`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converge: Option<bool>,
 }
 
 /// Size budget configuration (Phase 8)
@@ -76,6 +152,534 @@ pub struct SizeBudget {
     /// Target size in KB (ideal target)
     #[serde(rename = "target-size-kb", skip_serializing_if = "Option::is_none")]
     pub target_size_kb: Option<u64>,
+
+    /// Fail before wasm-opt if the pre-optimization wasm exceeds
+    /// `max-size-kb` times this factor (e.g., 10.0)
+    #[serde(rename = "preopt-factor", skip_serializing_if = "Option::is_none")]
+    pub preopt_factor: Option<f64>,
+}
+
+/// Default symbol baseline location, relative to the project root
+pub const DEFAULT_SYMBOL_BASELINE: &str = ".wasm-slim/symbol-baseline.json";
+
+/// Per-symbol size budget (`[symbol_budget]`), enforced by `build --check`
+///
+/// Symbols are compared against a baseline saved with
+/// `wasm-slim analyze <FILE> --save-baseline`. A symbol is flagged when it
+/// grew by more than either limit:
+///
+/// ```toml
+/// [symbol_budget]
+/// max-growth-percent = 10.0
+/// max-growth-bytes = 2048
+/// min-symbol-bytes = 256
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SymbolBudget {
+    /// Baseline JSON, relative to the project root
+    /// (default: `.wasm-slim/symbol-baseline.json`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<PathBuf>,
+
+    /// Largest allowed growth of a symbol, in percent of its baseline size
+    #[serde(rename = "max-growth-percent", skip_serializing_if = "Option::is_none")]
+    pub max_growth_percent: Option<f64>,
+
+    /// Largest allowed growth of a symbol in bytes; also limits new symbols
+    #[serde(rename = "max-growth-bytes", skip_serializing_if = "Option::is_none")]
+    pub max_growth_bytes: Option<u64>,
+
+    /// Symbols smaller than this after the build are not checked
+    #[serde(rename = "min-symbol-bytes", default)]
+    pub min_symbol_bytes: u64,
+}
+
+/// Allocator profiling settings used by `analyze --mode allocator`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AllocatorSettings {
+    /// Zero-argument functions to run under a counting allocator
+    /// (e.g., ["my_crate::smoke::render"])
+    #[serde(rename = "smoke-entrypoints", default)]
+    pub smoke_entrypoints: Vec<String>,
+}
+
+/// Report localization settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct I18nSettings {
+    /// Translation TOML file, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translations: Option<PathBuf>,
+}
+
+/// Analysis settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalysisSettings {
+    /// Recommendation thresholds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thresholds: Option<ThresholdSettings>,
+    /// Gitignore-style globs of the source files the asset scan reads
+    /// (default: every `.rs` file not ignored by `.gitignore`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Gitignore-style globs of source files the asset scan skips
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+/// Recommendation thresholds and how they scale with bundle size
+///
+/// Base values apply to a bundle of `reference-size-kb`. For other sizes they
+/// are multiplied by `(size / reference) ^ exponent`, clamped to
+/// `[min-factor, max-factor]`: byte thresholds grow with that factor and
+/// percentage thresholds shrink with its square root. The bloat, asset and
+/// instantiation-count keys are used as configured. Unset keys keep the
+/// built-in defaults.
+///
+/// ```toml
+/// [analysis.thresholds]
+/// reference-size-kb = 1024
+/// exponent = 0.5
+/// large-data-segment-kb = 50
+/// dominator-percent = 20.0
+/// mono-instantiations = 10
+/// heavy-crate-percent = 20.0
+/// asset-critical-kb = 500
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ThresholdSettings {
+    /// Bundle size at which base thresholds apply unchanged (KB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_size_kb: Option<u64>,
+
+    /// Scaling exponent (0 disables scaling)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exponent: Option<f64>,
+
+    /// Lower bound of the scale factor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_factor: Option<f64>,
+
+    /// Upper bound of the scale factor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_factor: Option<f64>,
+
+    /// Data segments larger than this are flagged (KB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub large_data_segment_kb: Option<u64>,
+
+    /// Dominators retaining more than this share are flagged (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominator_percent: Option<f64>,
+
+    /// Top 20 items above this combined share are flagged (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_items_percent: Option<f64>,
+
+    /// Dead code above this share is flagged (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_code_percent: Option<f64>,
+
+    /// Monomorphization share reported as significant (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mono_significant_percent: Option<f64>,
+
+    /// Monomorphization share reported as moderate (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mono_moderate_percent: Option<f64>,
+
+    /// Generic functions whose instantiations exceed this are listed (KB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mono_function_kb: Option<u64>,
+
+    /// Generic functions with at least this many instantiations are listed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mono_instantiations: Option<usize>,
+
+    /// Functions larger than this are flagged by the bloat analysis (KB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub large_function_kb: Option<u64>,
+
+    /// Functions above this share are flagged by the bloat analysis (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub large_function_percent: Option<f64>,
+
+    /// Crates above this share of the binary are flagged (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heavy_crate_percent: Option<f64>,
+
+    /// Formatting code above this share is flagged (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatting_percent: Option<f64>,
+
+    /// Assets above this share of the bundle are critical (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_critical_percent: Option<f64>,
+
+    /// Assets larger than this are critical (KB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_critical_kb: Option<u64>,
+
+    /// Assets above this share of the bundle are high priority (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_high_percent: Option<f64>,
+
+    /// Assets larger than this are high priority (KB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_high_kb: Option<u64>,
+
+    /// Assets above this share of the bundle are medium priority (%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_medium_percent: Option<f64>,
+
+    /// Assets larger than this are medium priority (KB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_medium_kb: Option<u64>,
+}
+
+/// Deployment target settings
+///
+/// The target decides which size metric the budget applies to and adds the
+/// platform's own size limit to the budget check:
+///
+/// ```toml
+/// [deploy]
+/// target = "cloudflare-workers"
+/// plan = "paid"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploySettings {
+    /// Platform the bundle is deployed to
+    pub target: DeployTarget,
+
+    /// Account plan, for platforms whose limits depend on it
+    #[serde(default)]
+    pub plan: DeployPlan,
+}
+
+/// Platform a bundle is deployed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeployTarget {
+    /// Cloudflare Workers (limit on the compressed script)
+    CloudflareWorkers,
+    /// Fastly Compute (limit on the compressed package)
+    Fastly,
+    /// Served to browsers (no platform limit)
+    Browser,
+}
+
+/// Account plan of the deployment platform
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeployPlan {
+    /// Free tier
+    #[default]
+    Free,
+    /// Paid tier
+    Paid,
+}
+
+/// Build history retention (`[history]`)
+///
+/// Builds newer than `keep-days` are kept individually; older builds are
+/// rolled up to one record per day:
+///
+/// ```toml
+/// [history]
+/// keep-days = 90
+/// auto-compact = true
+/// days-per-run = 30
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HistorySettings {
+    /// Days of builds kept individually (default: 90)
+    #[serde(rename = "keep-days", skip_serializing_if = "Option::is_none")]
+    pub keep_days: Option<u64>,
+
+    /// Compact automatically after recording a build (default: true)
+    #[serde(rename = "auto-compact", skip_serializing_if = "Option::is_none")]
+    pub auto_compact: Option<bool>,
+
+    /// Days rolled up per automatic compaction (default: 30)
+    #[serde(rename = "days-per-run", skip_serializing_if = "Option::is_none")]
+    pub days_per_run: Option<usize>,
+}
+
+/// Redaction of secrets in reports and logs (`[redaction]`)
+///
+/// Token-like strings and the values of environment variables named like
+/// secrets are always redacted; these settings add to them:
+///
+/// ```toml
+/// [redaction]
+/// patterns = ["internal-[0-9a-f]{32}"]
+/// env-vars = ["REGISTRY_URL"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedactionSettings {
+    /// Extra regular expressions whose matches are redacted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+
+    /// Extra environment variables whose values are redacted
+    #[serde(rename = "env-vars", default, skip_serializing_if = "Vec::is_empty")]
+    pub env_vars: Vec<String>,
+}
+
+impl RedactionSettings {
+    /// Check that every pattern is a valid regular expression
+    pub fn validate(&self) -> Result<()> {
+        for pattern in &self.patterns {
+            regex::Regex::new(pattern)
+                .map_err(|e| anyhow::anyhow!("Invalid pattern '{}': {}", pattern, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Environment variables for build tools
+///
+/// Top-level keys apply to every stage; the `cargo`, `wasm-bindgen`,
+/// `wasm-opt` and `wasm-snip` tables apply to a single stage and override
+/// top-level values:
+///
+/// ```toml
+/// [env]
+/// RUST_LOG = "warn"
+///
+/// [env.cargo]
+/// RUSTFLAGS = "-C target-feature=+bulk-memory"
+///
+/// [env.wasm-opt]
+/// BINARYEN_CORES = "4"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvSettings {
+    /// Variables for `cargo build`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cargo: BTreeMap<String, String>,
+
+    /// Variables for wasm-bindgen
+    #[serde(
+        rename = "wasm-bindgen",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub wasm_bindgen: BTreeMap<String, String>,
+
+    /// Variables for wasm-opt
+    #[serde(
+        rename = "wasm-opt",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub wasm_opt: BTreeMap<String, String>,
+
+    /// Variables for wasm-snip
+    #[serde(
+        rename = "wasm-snip",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub wasm_snip: BTreeMap<String, String>,
+
+    /// Variables for every stage
+    #[serde(flatten)]
+    pub global: BTreeMap<String, String>,
+}
+
+/// Banned and discouraged dependencies (`[crates]`)
+///
+/// Rules apply to the dependency graph cargo resolves for the WASM target:
+///
+/// ```toml
+/// [crates]
+/// # `name(default-features)` bans the crate only with default features on
+/// banned = ["openssl", "reqwest(default-features)"]
+///
+/// [crates.discouraged]
+/// chrono = "Use `time` or js-sys Date instead"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CrateRules {
+    /// Crates that fail dependency analysis and builds
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub banned: Vec<String>,
+
+    /// Crates that produce a warning, with a replacement hint
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub discouraged: BTreeMap<String, String>,
+}
+
+/// A parsed `banned` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BannedCrate {
+    /// Crate name
+    pub name: String,
+    /// Only banned while its default features are enabled
+    pub default_features_only: bool,
+}
+
+impl CrateRules {
+    /// Parse the `banned` entries
+    pub fn banned_crates(&self) -> Result<Vec<BannedCrate>> {
+        self.banned
+            .iter()
+            .map(|rule| {
+                let (name, default_features_only) = match rule.split_once('(') {
+                    Some((name, "default-features)")) => (name, true),
+                    Some(_) => anyhow::bail!(
+                        "Invalid banned crate '{}': only `name(default-features)` is supported",
+                        rule
+                    ),
+                    None => (rule.as_str(), false),
+                };
+                Ok(BannedCrate {
+                    name: name.trim().to_string(),
+                    default_features_only,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether no crates are banned or discouraged
+    pub fn is_empty(&self) -> bool {
+        self.banned.is_empty() && self.discouraged.is_empty()
+    }
+
+    /// Validate the rule syntax
+    pub fn validate(&self) -> Result<()> {
+        self.banned_crates().map(|_| ())
+    }
+
+    /// Rules of both sets; `self` wins for crates discouraged by both
+    pub fn merged(&self, other: &CrateRules) -> CrateRules {
+        let mut merged = self.clone();
+        for rule in &other.banned {
+            if !merged.banned.contains(rule) {
+                merged.banned.push(rule.clone());
+            }
+        }
+        for (name, hint) in &other.discouraged {
+            merged
+                .discouraged
+                .entry(name.clone())
+                .or_insert_with(|| hint.clone());
+        }
+        merged
+    }
+}
+
+/// A project entry in the heavy-dependency database (`[heavy-deps.<crate>]`)
+///
+/// Entries extend the built-in database, or replace its entry for the same
+/// crate, so internal crates get recommendations too. The same tables may
+/// live in a `heavy-deps.toml` next to `.wasm-slim.toml`, without the
+/// `heavy-deps.` prefix:
+///
+/// ```toml
+/// [heavy-deps.acme-telemetry]
+/// size-kb = [300, 450]
+/// bundle-percent = [10, 15]
+/// reason = "Bundles the full protobuf runtime"
+///
+/// [[heavy-deps.acme-telemetry.alternatives]]
+/// type = "replacement"
+/// crate = "acme-telemetry-lite"
+/// size-kb = [40, 60]
+/// savings-percent = 85
+/// description = "JSON-only client for browsers"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct HeavyDepSettings {
+    /// Typical size range in KB
+    pub size_kb: (u32, u32),
+
+    /// Percentage of a typical bundle
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_percent: Option<(u8, u8)>,
+
+    /// Why the crate is heavy
+    pub reason: String,
+
+    /// Recommended alternatives
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<HeavyDepAlternativeSettings>,
+}
+
+/// A recommended alternative in a `[heavy-deps.<crate>]` entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct HeavyDepAlternativeSettings {
+    /// Kind of recommendation
+    #[serde(rename = "type")]
+    pub alt_type: AlternativeType,
+
+    /// Replacement crate, for `replacement` and `split`
+    #[serde(rename = "crate", default, skip_serializing_if = "Option::is_none")]
+    pub crate_name: Option<String>,
+
+    /// Expected size in KB after following the recommendation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_kb: Option<(u32, u32)>,
+
+    /// Estimated savings percentage
+    pub savings_percent: u8,
+
+    /// What to do
+    pub description: String,
+}
+
+impl HeavyDepSettings {
+    /// Check that ranges are ordered and percentages at most 100
+    pub fn validate(&self) -> Result<()> {
+        let ordered = |(min, max): (u32, u32)| min <= max;
+        if !ordered(self.size_kb) {
+            anyhow::bail!("size-kb must be [min, max]");
+        }
+        if let Some((min, max)) = self.bundle_percent {
+            if min > max || max > 100 {
+                anyhow::bail!("bundle-percent must be [min, max] within 0-100");
+            }
+        }
+        for alternative in &self.alternatives {
+            if alternative.size_kb.is_some_and(|range| !ordered(range)) {
+                anyhow::bail!("alternative size-kb must be [min, max]");
+            }
+            if alternative.savings_percent > 100 {
+                anyhow::bail!("savings-percent must be at most 100");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SymbolBudget {
+    /// Baseline location, relative to the project root
+    pub fn baseline_path(&self) -> PathBuf {
+        self.baseline
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_SYMBOL_BASELINE))
+    }
+
+    /// Validate that at least one non-negative growth limit is set
+    pub fn validate(&self) -> Result<()> {
+        if self.max_growth_percent.is_none() && self.max_growth_bytes.is_none() {
+            anyhow::bail!("Set max-growth-percent, max-growth-bytes or both");
+        }
+        if let Some(percent) = self.max_growth_percent {
+            if percent.is_nan() || percent < 0.0 {
+                anyhow::bail!("max-growth-percent ({}) must not be negative", percent);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl SizeBudget {
@@ -113,6 +717,69 @@ impl SizeBudget {
             }
         }
 
+        if let Some(factor) = self.preopt_factor {
+            if factor.is_nan() || factor < 1.0 {
+                anyhow::bail!("Pre-optimization factor ({}) must be at least 1.0", factor);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Largest pre-optimization size (in bytes) allowed before wasm-opt runs
+    ///
+    /// Returns `None` unless both `max-size-kb` and `preopt-factor` are set.
+    pub fn preopt_limit_bytes(&self) -> Option<u64> {
+        let max_bytes = self.max_size_kb? * 1024;
+        Some((max_bytes as f64 * self.preopt_factor?) as u64)
+    }
+}
+
+impl ThresholdSettings {
+    /// Validate the scaling curve and threshold ranges
+    pub fn validate(&self) -> Result<()> {
+        if self.reference_size_kb == Some(0) {
+            anyhow::bail!("reference-size-kb must be greater than 0");
+        }
+        if let Some(exponent) = self.exponent {
+            if !exponent.is_finite() || exponent < 0.0 {
+                anyhow::bail!("exponent ({}) must be a non-negative number", exponent);
+            }
+        }
+        for (key, factor) in [
+            ("min-factor", self.min_factor),
+            ("max-factor", self.max_factor),
+        ] {
+            if let Some(factor) = factor {
+                if !factor.is_finite() || factor <= 0.0 {
+                    anyhow::bail!("{} ({}) must be greater than 0", key, factor);
+                }
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_factor, self.max_factor) {
+            if min > max {
+                anyhow::bail!("min-factor ({}) cannot exceed max-factor ({})", min, max);
+            }
+        }
+        for (key, percent) in [
+            ("dominator-percent", self.dominator_percent),
+            ("top-items-percent", self.top_items_percent),
+            ("dead-code-percent", self.dead_code_percent),
+            ("mono-significant-percent", self.mono_significant_percent),
+            ("mono-moderate-percent", self.mono_moderate_percent),
+            ("large-function-percent", self.large_function_percent),
+            ("heavy-crate-percent", self.heavy_crate_percent),
+            ("formatting-percent", self.formatting_percent),
+            ("asset-critical-percent", self.asset_critical_percent),
+            ("asset-high-percent", self.asset_high_percent),
+            ("asset-medium-percent", self.asset_medium_percent),
+        ] {
+            if let Some(percent) = percent {
+                if !(0.0..=100.0).contains(&percent) {
+                    anyhow::bail!("{} ({}) must be between 0 and 100", key, percent);
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -121,9 +788,22 @@ impl Default for ConfigFile {
     fn default() -> Self {
         Self {
             template: "balanced".to_string(),
+            template_sha256: None,
             profile: None,
             wasm_opt: None,
             size_budget: None,
+            package_budget: None,
+            symbol_budget: None,
+            allocator: None,
+            env: None,
+            i18n: None,
+            analysis: None,
+            deploy: None,
+            history: None,
+            redaction: None,
+            policy: None,
+            crates: None,
+            heavy_deps: BTreeMap::new(),
         }
     }
 }
@@ -161,6 +841,7 @@ mod tests {
             }),
             wasm_opt: None,
             size_budget: None,
+            ..Default::default()
         };
 
         ConfigLoader::save(&config, project_root).unwrap();
@@ -200,6 +881,7 @@ mod tests {
             }),
             wasm_opt: None,
             size_budget: None,
+            ..Default::default()
         };
 
         let resolved = TemplateResolver::resolve(&config).unwrap();
@@ -227,6 +909,7 @@ mod tests {
             target_size_kb: Some(100),
             warn_threshold_kb: Some(150),
             max_size_kb: Some(200),
+            preopt_factor: None,
         };
         assert!(budget.validate().is_ok());
     }
@@ -237,6 +920,7 @@ mod tests {
             target_size_kb: Some(200),
             warn_threshold_kb: Some(100),
             max_size_kb: None,
+            preopt_factor: None,
         };
         assert!(budget.validate().is_err());
     }
@@ -247,6 +931,7 @@ mod tests {
             target_size_kb: None,
             warn_threshold_kb: Some(300),
             max_size_kb: Some(200),
+            preopt_factor: None,
         };
         assert!(budget.validate().is_err());
     }
@@ -257,10 +942,158 @@ mod tests {
             target_size_kb: Some(300),
             warn_threshold_kb: None,
             max_size_kb: Some(200),
+            preopt_factor: None,
+        };
+        assert!(budget.validate().is_err());
+    }
+
+    #[test]
+    fn test_size_budget_validate_with_preopt_factor_below_one_returns_error() {
+        let budget = SizeBudget {
+            target_size_kb: None,
+            warn_threshold_kb: None,
+            max_size_kb: Some(200),
+            preopt_factor: Some(0.5),
         };
         assert!(budget.validate().is_err());
     }
 
+    #[test]
+    fn test_size_budget_preopt_limit_requires_max_and_factor() {
+        let mut budget = SizeBudget {
+            max_size_kb: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(budget.preopt_limit_bytes(), None);
+
+        budget.preopt_factor = Some(10.0);
+        assert_eq!(budget.preopt_limit_bytes(), Some(1000 * 1024));
+
+        budget.max_size_kb = None;
+        assert_eq!(budget.preopt_limit_bytes(), None);
+    }
+
+    #[test]
+    fn test_size_budget_parses_preopt_factor() {
+        let config: ConfigFile =
+            toml_edit::de::from_str("[size_budget]\nmax-size-kb = 500\npreopt-factor = 8.0\n")
+                .unwrap();
+        assert_eq!(config.size_budget.unwrap().preopt_factor, Some(8.0));
+    }
+
+    #[test]
+    fn test_wasm_opt_settings_parse_tuning_options() {
+        let config: ConfigFile = toml_edit::de::from_str(
+            "[wasm_opt]\nthreads = 2\nlow-memory-unused = true\nconverge = true\n",
+        )
+        .unwrap();
+
+        let wasm_opt = config.wasm_opt.unwrap();
+        assert_eq!(wasm_opt.threads, Some(2));
+        assert_eq!(wasm_opt.low_memory_unused, Some(true));
+        assert_eq!(wasm_opt.converge, Some(true));
+        assert!(wasm_opt.flags.is_none());
+    }
+
+    #[test]
+    fn test_env_settings_parse_global_and_stage_tables() {
+        let config: ConfigFile = toml_edit::de::from_str(
+            r#"
+[env]
+RUST_LOG = "warn"
+
+[env.cargo]
+RUSTFLAGS = "-C target-feature=+bulk-memory"
+
+[env.wasm-opt]
+BINARYEN_CORES = "4"
+"#,
+        )
+        .unwrap();
+
+        let env = config.env.unwrap();
+        assert_eq!(env.global["RUST_LOG"], "warn");
+        assert_eq!(env.cargo["RUSTFLAGS"], "-C target-feature=+bulk-memory");
+        assert_eq!(env.wasm_opt["BINARYEN_CORES"], "4");
+        assert!(env.wasm_bindgen.is_empty());
+        assert!(!env.global.contains_key("cargo"));
+    }
+
+    #[test]
+    fn test_env_settings_round_trip() {
+        let mut env = EnvSettings::default();
+        env.global.insert("RUST_LOG".into(), "warn".into());
+        env.wasm_snip.insert("FOO".into(), "bar".into());
+        let config = ConfigFile {
+            env: Some(env.clone()),
+            ..Default::default()
+        };
+
+        let serialized = toml_edit::ser::to_string(&config).unwrap();
+        let deserialized: ConfigFile = toml_edit::de::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.env, Some(env));
+    }
+
+    #[test]
+    fn test_i18n_settings_parse_translations_path() {
+        let config: ConfigFile =
+            toml_edit::de::from_str("[i18n]\ntranslations = \"i18n/de.toml\"\n").unwrap();
+        assert_eq!(
+            config.i18n.unwrap().translations,
+            Some(PathBuf::from("i18n/de.toml"))
+        );
+    }
+
+    #[test]
+    fn test_analysis_thresholds_parse_kebab_case_keys() {
+        let config: ConfigFile = toml_edit::de::from_str(
+            "[analysis.thresholds]\nreference-size-kb = 512\nexponent = 0.3\ndominator-percent = 25.0\n",
+        )
+        .unwrap();
+        let thresholds = config.analysis.unwrap().thresholds.unwrap();
+        assert_eq!(thresholds.reference_size_kb, Some(512));
+        assert_eq!(thresholds.exponent, Some(0.3));
+        assert_eq!(thresholds.dominator_percent, Some(25.0));
+        assert!(thresholds.validate().is_ok());
+    }
+
+    #[test]
+    fn test_deploy_settings_parse_target_and_default_plan() {
+        let config: ConfigFile =
+            toml_edit::de::from_str("[deploy]\ntarget = \"cloudflare-workers\"\n").unwrap();
+        let deploy = config.deploy.unwrap();
+        assert_eq!(deploy.target, DeployTarget::CloudflareWorkers);
+        assert_eq!(deploy.plan, DeployPlan::Free);
+
+        let paid: ConfigFile =
+            toml_edit::de::from_str("[deploy]\ntarget = \"fastly\"\nplan = \"paid\"\n").unwrap();
+        assert_eq!(paid.deploy.unwrap().plan, DeployPlan::Paid);
+
+        assert!(toml_edit::de::from_str::<ConfigFile>("[deploy]\ntarget = \"lambda\"\n").is_err());
+    }
+
+    #[test]
+    fn test_threshold_settings_validate_rejects_bad_curve() {
+        let inverted = ThresholdSettings {
+            min_factor: Some(2.0),
+            max_factor: Some(1.0),
+            ..Default::default()
+        };
+        assert!(inverted.validate().is_err());
+
+        let negative = ThresholdSettings {
+            exponent: Some(-1.0),
+            ..Default::default()
+        };
+        assert!(negative.validate().is_err());
+
+        let percent = ThresholdSettings {
+            dominator_percent: Some(120.0),
+            ..Default::default()
+        };
+        assert!(percent.validate().is_err());
+    }
+
     // P0-TEST-COV-002: File permission error tests
 
     #[test]
@@ -392,6 +1225,7 @@ profile = "not a table"
             profile: None,
             wasm_opt: None,
             size_budget: None,
+            ..Default::default()
         };
 
         let result = TemplateResolver::resolve(&config);
@@ -429,12 +1263,15 @@ profile = "not a table"
             }),
             wasm_opt: Some(WasmOptSettings {
                 flags: Some(vec!["-Oz".to_string()]),
+                ..Default::default()
             }),
             size_budget: Some(SizeBudget {
                 max_size_kb: Some(500),
                 warn_threshold_kb: Some(400),
                 target_size_kb: Some(300),
+                preopt_factor: None,
             }),
+            ..Default::default()
         };
 
         // Serialize
@@ -577,6 +1414,43 @@ lto = "thin"
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_config_file_load_validates_heavy_deps_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        let config_path = project_root.join(CONFIG_FILE_NAME);
+
+        fs::write(
+            &config_path,
+            r#"
+[heavy-deps.acme-telemetry]
+size-kb = [300, 450]
+reason = "Bundles the full protobuf runtime"
+
+[[heavy-deps.acme-telemetry.alternatives]]
+type = "feature-minimization"
+savings-percent = 40
+description = "Disable the grpc feature"
+"#,
+        )
+        .unwrap();
+        let config = ConfigLoader::load(project_root).unwrap();
+        let entry = &config.heavy_deps["acme-telemetry"];
+        assert_eq!(entry.size_kb, (300, 450));
+        assert_eq!(
+            entry.alternatives[0].alt_type,
+            AlternativeType::FeatureMinimization
+        );
+
+        fs::write(
+            &config_path,
+            "[heavy-deps.acme-telemetry]\nsize-kb = [450, 300]\nreason = \"x\"\n",
+        )
+        .unwrap();
+        let err = ConfigLoader::load(project_root).unwrap_err();
+        assert!(format!("{:#}", err).contains("[heavy-deps.acme-telemetry]"));
+    }
+
     #[test]
     fn test_config_file_load_with_only_whitespace_returns_default() {
         let temp_dir = TempDir::new().unwrap();
@@ -2,13 +2,53 @@
 
 use super::file::{ConfigFile, CONFIG_FILE_NAME};
 use crate::infra::{FileSystem, RealFileSystem};
+use crate::profiling::{self, ProfilePhase};
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable naming an alternative config file
+pub const CONFIG_ENV_VAR: &str = "WASM_SLIM_CONFIG";
+
+/// Config file given with `--config`, installed once from `main`
+static CONFIG_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
 /// Handles loading and saving configuration files
 pub struct ConfigLoader;
 
 impl ConfigLoader {
+    /// Use `path` instead of `.wasm-slim.toml` for the rest of the process
+    ///
+    /// Returns `false` if an override was already installed.
+    pub fn install_override(path: PathBuf) -> bool {
+        CONFIG_OVERRIDE.set(path).is_ok()
+    }
+
+    /// Path of the config file used for `project_root`
+    ///
+    /// Precedence: `--config`, then `WASM_SLIM_CONFIG` (relative to the
+    /// project root), then `.wasm-slim.toml` in the project root.
+    pub fn config_path(project_root: &Path) -> PathBuf {
+        let env_path = std::env::var_os(CONFIG_ENV_VAR).filter(|v| !v.is_empty());
+        resolve_config_path(
+            project_root,
+            CONFIG_OVERRIDE.get().map(PathBuf::as_path),
+            env_path.as_deref().map(Path::new),
+        )
+    }
+
+    /// The alternative config file in use, if any
+    pub fn override_path(project_root: &Path) -> Option<PathBuf> {
+        let path = Self::config_path(project_root);
+        (path != project_root.join(CONFIG_FILE_NAME)).then_some(path)
+    }
+
+    /// Config file name for messages: the override path or `.wasm-slim.toml`
+    pub fn display_name(project_root: &Path) -> String {
+        Self::override_path(project_root)
+            .map_or_else(|| CONFIG_FILE_NAME.to_string(), |p| p.display().to_string())
+    }
+
     /// Load config from .wasm-slim.toml in the given directory
     ///
     /// # Examples
@@ -27,22 +67,26 @@ impl ConfigLoader {
 
     /// Load config with a custom filesystem implementation
     pub fn load_with_fs<FS: FileSystem>(project_root: &Path, fs: &FS) -> Result<ConfigFile> {
-        let config_path = project_root.join(CONFIG_FILE_NAME);
+        let config_path = Self::config_path(project_root);
+        let explicit = Self::override_path(project_root).is_some();
 
         // Read file atomically - no TOCTOU race window
         let contents = match fs.read_to_string(&config_path) {
             Ok(c) => c,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !explicit => {
                 // Return default config if file doesn't exist
                 return Ok(ConfigFile::default());
             }
             Err(e) => {
-                return Err(e).context("Failed to read .wasm-slim.toml");
+                return Err(e).with_context(|| format!("Failed to read {}", config_path.display()));
             }
         };
 
-        let config: ConfigFile =
-            toml_edit::de::from_str(&contents).context("Failed to parse .wasm-slim.toml")?;
+        let config: ConfigFile = {
+            let _span = profiling::span(ProfilePhase::Parsing, "config");
+            toml_edit::de::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", config_path.display()))?
+        };
 
         // Validate size budget constraints
         if let Some(ref budget) = config.size_budget {
@@ -51,6 +95,42 @@ impl ConfigLoader {
                 .context("Invalid size budget configuration")?;
         }
 
+        if let Some(budget) = &config.package_budget {
+            budget
+                .validate()
+                .context("Invalid [package_budget] configuration")?;
+        }
+
+        if let Some(symbol_budget) = &config.symbol_budget {
+            symbol_budget
+                .validate()
+                .context("Invalid [symbol_budget] configuration")?;
+        }
+
+        if let Some(crates) = &config.crates {
+            crates
+                .validate()
+                .context("Invalid [crates] configuration")?;
+        }
+
+        for (name, entry) in &config.heavy_deps {
+            entry
+                .validate()
+                .with_context(|| format!("Invalid [heavy-deps.{}] configuration", name))?;
+        }
+
+        if let Some(redaction) = &config.redaction {
+            redaction
+                .validate()
+                .context("Invalid [redaction] configuration")?;
+        }
+
+        if let Some(thresholds) = config.analysis.as_ref().and_then(|a| a.thresholds.as_ref()) {
+            thresholds
+                .validate()
+                .context("Invalid [analysis.thresholds] configuration")?;
+        }
+
         Ok(config)
     }
 
@@ -77,20 +157,29 @@ impl ConfigLoader {
         project_root: &Path,
         fs: &FS,
     ) -> Result<()> {
-        let config_path = project_root.join(CONFIG_FILE_NAME);
+        let config_path = Self::config_path(project_root);
 
         let contents =
             toml_edit::ser::to_string_pretty(config).context("Failed to serialize config")?;
 
         fs.write(&config_path, contents)
-            .context("Failed to write .wasm-slim.toml")?;
+            .with_context(|| format!("Failed to write {}", config_path.display()))?;
 
         Ok(())
     }
 
     /// Check if config file exists in project
     pub fn exists(project_root: &Path) -> bool {
-        project_root.join(CONFIG_FILE_NAME).exists()
+        Self::config_path(project_root).exists()
+    }
+}
+
+/// Pick the config file from the flag, the environment and the default name
+fn resolve_config_path(project_root: &Path, flag: Option<&Path>, env: Option<&Path>) -> PathBuf {
+    match (flag, env) {
+        (Some(path), _) => path.to_path_buf(),
+        (None, Some(path)) => project_root.join(path),
+        (None, None) => project_root.join(CONFIG_FILE_NAME),
     }
 }
 
@@ -356,4 +445,25 @@ opt-level = "z"
         // Other fields should have default values (None)
         assert!(config.profile.is_none());
     }
+
+    #[test]
+    fn test_resolve_config_path_precedence() {
+        let root = Path::new("/project");
+        assert_eq!(
+            resolve_config_path(root, None, None),
+            root.join(CONFIG_FILE_NAME)
+        );
+        assert_eq!(
+            resolve_config_path(root, None, Some(Path::new("ci/size.toml"))),
+            root.join("ci/size.toml")
+        );
+        assert_eq!(
+            resolve_config_path(
+                root,
+                Some(Path::new("/alt/experiment.toml")),
+                Some(Path::new("ci/size.toml"))
+            ),
+            PathBuf::from("/alt/experiment.toml")
+        );
+    }
 }
@@ -3,19 +3,27 @@
 //! This module provides:
 //! - Template definitions (minimal, balanced, aggressive, framework-specific)
 //! - .wasm-slim.toml config file support
+//! - Shared team policy files that project configs may only tighten
 //! - Template application logic
 
 pub mod file;
 pub mod loader;
+pub mod policy;
 pub mod profile_config;
+pub mod remote_template;
 pub mod resolver;
 pub mod template;
 pub mod validator;
 pub mod wasm_config;
 
-pub use file::{ConfigFile, CONFIG_FILE_NAME};
-pub use loader::ConfigLoader;
+pub use file::{
+    BannedCrate, ConfigFile, CrateRules, DeployPlan, DeploySettings, DeployTarget,
+    HeavyDepSettings, HistorySettings, RedactionSettings, CONFIG_FILE_NAME,
+};
+pub use loader::{ConfigLoader, CONFIG_ENV_VAR};
+pub use policy::Policy;
 pub use profile_config::ProfileConfig;
+pub use remote_template::{RemoteTemplateFetcher, RemoteTemplateRef};
 pub use resolver::TemplateResolver;
 pub use template::{Template, TemplateBuilder, TemplateType};
 pub use validator::{
@@ -0,0 +1,296 @@
+//! Shared team policy files
+//!
+//! A policy pins organization-wide rules for every project that references
+//! it from `.wasm-slim.toml`, by path (relative to the project) or URL:
+//!
+//! ```toml
+//! policy = "../platform/wasm-slim-policy.toml"
+//! ```
+//!
+//! wasm-slim only ever reads the policy. Project configs may be stricter
+//! than the policy but never looser; `config validate` reports violations
+//! and `build` refuses to run while there are any.
+//!
+//! ```toml
+//! # Projects must set budgets at or below these
+//! [size-budget]
+//! max-size-kb = 2048
+//! warn-threshold-kb = 1536
+//!
+//! # Merged with the project's own `[crates]` rules
+//! [crates]
+//! banned = ["openssl", "reqwest(default-features)"]
+//!
+//! [crates.discouraged]
+//! chrono = "Use `time` instead"
+//!
+//! # Templates projects may use
+//! [templates]
+//! allowed = ["balanced", "aggressive"]
+//! ```
+
+use super::file::{ConfigFile, CrateRules};
+use super::remote_template::fetch_url;
+use super::validator::{ValidationIssue, ValidationResult};
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Organization-wide rules a project config must respect
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    /// Loosest budgets projects may configure
+    #[serde(rename = "size-budget", default)]
+    pub size_budget: PolicyBudget,
+
+    /// Dependency rules
+    #[serde(default)]
+    pub crates: CrateRules,
+
+    /// Template rules
+    #[serde(default)]
+    pub templates: PolicyTemplates,
+}
+
+/// Loosest budgets a project may configure
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyBudget {
+    /// Projects must set `max-size-kb` at or below this
+    #[serde(rename = "max-size-kb", skip_serializing_if = "Option::is_none")]
+    pub max_size_kb: Option<u64>,
+
+    /// Projects must set `warn-threshold-kb` at or below this
+    #[serde(rename = "warn-threshold-kb", skip_serializing_if = "Option::is_none")]
+    pub warn_threshold_kb: Option<u64>,
+}
+
+/// Template rules
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyTemplates {
+    /// Templates projects may use (empty allows any)
+    #[serde(default)]
+    pub allowed: Vec<String>,
+}
+
+impl Policy {
+    /// Load a policy from a path (relative to `project_root`) or an http(s) URL
+    pub fn load(source: &str, project_root: &Path) -> Result<Self> {
+        Self::load_with_executors(source, project_root, &RealFileSystem, &RealCommandExecutor)
+    }
+
+    /// Load a policy with custom executors; URLs are fetched with `curl`
+    pub fn load_with_executors<FS: FileSystem, CE: CommandExecutor>(
+        source: &str,
+        project_root: &Path,
+        fs: &FS,
+        cmd_executor: &CE,
+    ) -> Result<Self> {
+        let contents = if source.starts_with("https://") || source.starts_with("http://") {
+            fetch_url(cmd_executor, source).context("Failed to fetch policy")?
+        } else {
+            fs.read_to_string(&project_root.join(source))
+                .with_context(|| format!("Failed to read policy {}", source))?
+        };
+
+        let policy: Self = toml_edit::de::from_str(&contents)
+            .with_context(|| format!("Failed to parse policy {}", source))?;
+        policy
+            .crates
+            .validate()
+            .with_context(|| format!("Invalid [crates] rules in policy {}", source))?;
+        Ok(policy)
+    }
+
+    /// Check a project config's budgets and template against the policy
+    ///
+    /// Dependency rules are enforced by the dependency analyzer instead,
+    /// on the graph cargo resolves for the WASM target.
+    pub fn check(&self, config: &ConfigFile) -> ValidationResult {
+        let mut result = ValidationResult::success();
+        let budget = config.size_budget.as_ref();
+
+        let limits = [
+            (
+                "size_budget.max-size-kb",
+                self.size_budget.max_size_kb,
+                budget.and_then(|b| b.max_size_kb),
+            ),
+            (
+                "size_budget.warn-threshold-kb",
+                self.size_budget.warn_threshold_kb,
+                budget.and_then(|b| b.warn_threshold_kb),
+            ),
+        ];
+        for (field, ceiling, configured) in limits {
+            let Some(ceiling) = ceiling else { continue };
+            match configured {
+                Some(kb) if kb <= ceiling => {}
+                Some(kb) => result.add_issue(
+                    ValidationIssue::error(
+                        field,
+                        format!(
+                            "{} KB is looser than the policy limit of {} KB",
+                            kb, ceiling
+                        ),
+                    )
+                    .with_suggestion(format!("Set it to {} or less", ceiling)),
+                ),
+                None => result.add_issue(
+                    ValidationIssue::error(field, "Required by the policy but not set")
+                        .with_suggestion(format!("Set it to {} or less", ceiling)),
+                ),
+            }
+        }
+
+        let allowed = &self.templates.allowed;
+        if !allowed.is_empty() && !allowed.contains(&config.template) {
+            result.add_issue(
+                ValidationIssue::error(
+                    "template",
+                    format!(
+                        "Template '{}' is not allowed by the policy",
+                        config.template
+                    ),
+                )
+                .with_suggestion(format!("Use one of: {}", allowed.join(", "))),
+            );
+        }
+
+        result
+    }
+
+    /// Load the policy the project's config references, if any
+    pub fn for_project(project_root: &Path, config: &ConfigFile) -> Result<Option<Self>> {
+        config
+            .policy
+            .as_deref()
+            .map(|source| Self::load(source, project_root))
+            .transpose()
+    }
+}
+
+/// Dependency rules in effect for a project: its own `[crates]` plus the policy's
+///
+/// Policy hints win for crates discouraged by both.
+pub fn crate_rules(policy: Option<&Policy>, config: &ConfigFile) -> CrateRules {
+    let project = config.crates.clone().unwrap_or_default();
+    match policy {
+        Some(policy) => policy.crates.merged(&project),
+        None => project,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::file::SizeBudget;
+    use std::io;
+    use std::process::{Command, ExitStatus, Output};
+
+    struct FakeCurl;
+
+    impl CommandExecutor for FakeCurl {
+        fn status(&self, _cmd: &mut Command) -> io::Result<ExitStatus> {
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, cmd: &mut Command) -> io::Result<Output> {
+            assert_eq!(cmd.get_program(), "curl");
+            Ok(Output {
+                status: crate::infra::mock_exit_status(0),
+                stdout: b"[templates]\nallowed = [\"aggressive\"]\n".to_vec(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    fn policy() -> Policy {
+        toml_edit::de::from_str(
+            "[size-budget]\nmax-size-kb = 2048\n\n[crates]\nbanned = [\"openssl\"]\n\n[templates]\nallowed = [\"balanced\", \"aggressive\"]\n",
+        )
+        .unwrap()
+    }
+
+    fn config(template: &str, max_size_kb: Option<u64>) -> ConfigFile {
+        ConfigFile {
+            template: template.to_string(),
+            size_budget: Some(SizeBudget {
+                max_size_kb,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_allows_tighter_configs() {
+        let result = policy().check(&config("aggressive", Some(1024)));
+        assert!(result.valid);
+        assert!(result.issues.is_empty());
+    }
+
+    #[test]
+    fn test_check_rejects_looser_configs() {
+        let result = policy().check(&config("minimal", Some(4096)));
+
+        assert!(!result.valid);
+        let fields: Vec<&str> = result.errors().iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(fields, vec!["size_budget.max-size-kb", "template"]);
+        assert!(result.issues[0]
+            .message
+            .contains("looser than the policy limit of 2048 KB"));
+
+        let unset = policy().check(&config("balanced", None));
+        assert!(unset.errors()[0].message.contains("not set"));
+    }
+
+    #[test]
+    fn test_crate_rules_merge_policy_and_project() {
+        let mut project = config("balanced", None);
+        project.crates = Some(CrateRules {
+            banned: vec!["openssl".to_string(), "ring".to_string()],
+            discouraged: [("chrono".to_string(), "project hint".to_string())].into(),
+        });
+        let mut policy = policy();
+        policy
+            .crates
+            .discouraged
+            .insert("chrono".to_string(), "policy hint".to_string());
+
+        let rules = crate_rules(Some(&policy), &project);
+        assert_eq!(rules.banned, vec!["openssl", "ring"]);
+        assert_eq!(rules.discouraged["chrono"], "policy hint");
+        assert_eq!(
+            crate_rules(None, &project).discouraged["chrono"],
+            "project hint"
+        );
+    }
+
+    #[test]
+    fn test_load_fetches_urls_and_rejects_unknown_rules() {
+        let policy = Policy::load_with_executors(
+            "https://example.com/policy.toml",
+            Path::new("."),
+            &RealFileSystem,
+            &FakeCurl,
+        )
+        .unwrap();
+        assert_eq!(policy.templates.allowed, vec!["aggressive"]);
+
+        let unknown: Result<Policy, _> = toml_edit::de::from_str("[crates]\nbaned = [\"x\"]\n");
+        assert!(unknown.is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("policy.toml"),
+            "[crates]\nbanned = [\"reqwest(no-default)\"]\n",
+        )
+        .unwrap();
+        let invalid = Policy::load("policy.toml", dir.path()).unwrap_err();
+        assert!(format!("{:#}", invalid).contains("only `name(default-features)`"));
+    }
+}
@@ -0,0 +1,324 @@
+//! Remote template registry
+//!
+//! Platform teams publish a template as a TOML file in a GitHub repository
+//! and projects reference it instead of copying settings around:
+//!
+//! ```toml
+//! template = "github:my-org/wasm-slim-templates#strict-v2"
+//! template_sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+//! ```
+//!
+//! The reference is `github:<owner>/<repo>[/<path>]#<ref>`, where `<ref>` is
+//! a tag, branch or commit and `<path>` defaults to
+//! `wasm-slim-template.toml`. The published file uses the `.wasm-slim.toml`
+//! format: a built-in base `template` plus `[profile]` and `[wasm_opt]`
+//! overrides. Project overrides apply on top.
+//!
+//! Fetched files are cached in `.wasm-slim/templates/`, so builds keep
+//! working offline. When `template_sha256` is set, both cached and fetched
+//! files must match it and a matching cached copy is used as is; without it
+//! the reference is fetched again on every load, since a branch can move.
+
+use super::file::ConfigFile;
+use crate::hash::sha256_hex;
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Prefix of GitHub template references
+pub const GITHUB_PREFIX: &str = "github:";
+
+/// Template file looked up when the reference has no path
+pub const DEFAULT_TEMPLATE_FILE: &str = "wasm-slim-template.toml";
+
+/// Cache directory, relative to the project root
+const CACHE_DIR: &str = "templates";
+
+/// A parsed `github:` template reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteTemplateRef {
+    /// Repository owner
+    pub owner: String,
+    /// Repository name
+    pub repo: String,
+    /// Template file inside the repository
+    pub path: String,
+    /// Tag, branch or commit
+    pub reference: String,
+}
+
+impl RemoteTemplateRef {
+    /// Whether a `template` value refers to a remote template
+    pub fn is_remote(template: &str) -> bool {
+        template.starts_with(GITHUB_PREFIX)
+    }
+
+    /// Parse `github:<owner>/<repo>[/<path>]#<ref>`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasm_slim::config::RemoteTemplateRef;
+    ///
+    /// let r = RemoteTemplateRef::parse("github:my-org/templates#strict-v2")?;
+    /// assert_eq!(r.reference, "strict-v2");
+    /// assert_eq!(r.path, "wasm-slim-template.toml");
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse(template: &str) -> Result<Self> {
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid remote template '{}': expected github:<owner>/<repo>[/<path>]#<ref>",
+                template
+            )
+        };
+        let location = template.strip_prefix(GITHUB_PREFIX).ok_or_else(invalid)?;
+        let (location, reference) = location.split_once('#').ok_or_else(invalid)?;
+        let mut parts = location.splitn(3, '/');
+        let owner = parts.next().filter(|s| !s.is_empty()).ok_or_else(invalid)?;
+        let repo = parts.next().filter(|s| !s.is_empty()).ok_or_else(invalid)?;
+        let path = parts.next().unwrap_or(DEFAULT_TEMPLATE_FILE);
+        if reference.is_empty() || path.is_empty() || path.split('/').any(|c| c == "..") {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            path: path.to_string(),
+            reference: reference.to_string(),
+        })
+    }
+
+    /// URL of the raw template file
+    pub fn url(&self) -> String {
+        format!(
+            "https://raw.githubusercontent.com/{}/{}/{}/{}",
+            self.owner, self.repo, self.reference, self.path
+        )
+    }
+
+    /// File name of the cached copy
+    fn cache_file_name(&self) -> String {
+        let key = format!(
+            "{}-{}-{}-{}",
+            self.owner, self.repo, self.reference, self.path
+        );
+        let key: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}.toml", key)
+    }
+}
+
+/// Fetches, caches and verifies remote templates
+pub struct RemoteTemplateFetcher<
+    FS: FileSystem = RealFileSystem,
+    CE: CommandExecutor = RealCommandExecutor,
+> {
+    cache_dir: PathBuf,
+    fs: FS,
+    cmd_executor: CE,
+}
+
+impl RemoteTemplateFetcher {
+    /// Create a fetcher caching in the project's `.wasm-slim/templates/`
+    pub fn new(project_root: &Path) -> Self {
+        Self::with_executors(project_root, RealFileSystem, RealCommandExecutor)
+    }
+}
+
+impl<FS: FileSystem, CE: CommandExecutor> RemoteTemplateFetcher<FS, CE> {
+    /// Create a fetcher with custom executors; files are fetched with `curl`
+    pub fn with_executors(project_root: &Path, fs: FS, cmd_executor: CE) -> Self {
+        Self {
+            cache_dir: crate::state::state_dir(project_root).join(CACHE_DIR),
+            fs,
+            cmd_executor,
+        }
+    }
+
+    /// Load a published template
+    ///
+    /// `sha256` is the expected hex digest of the file. A pinned template is
+    /// served from the cache when the cached copy matches it. An unpinned
+    /// reference may be a branch that moves, so it is fetched every time and
+    /// the cached copy is only used when the fetch fails (e.g. offline).
+    pub fn fetch(&self, reference: &RemoteTemplateRef, sha256: Option<&str>) -> Result<ConfigFile> {
+        let expected = sha256.map(|s| s.trim().to_ascii_lowercase());
+        let cache_path = self.cache_dir.join(reference.cache_file_name());
+
+        let cached = self.fs.read_to_string(&cache_path).ok();
+        let contents = match (&expected, cached) {
+            (Some(expected), Some(cached)) if sha256_hex(cached.as_bytes()) == *expected => cached,
+            (_, cached) => match fetch_url(&self.cmd_executor, &reference.url()) {
+                Ok(contents) => {
+                    if let Some(expected) = &expected {
+                        let actual = sha256_hex(contents.as_bytes());
+                        if &actual != expected {
+                            anyhow::bail!(
+                                "Checksum mismatch for template {}: expected {}, got {}",
+                                reference.url(),
+                                expected,
+                                actual
+                            );
+                        }
+                    }
+                    self.fs
+                        .create_dir_all(&self.cache_dir)
+                        .and_then(|_| self.fs.write(&cache_path, &contents))
+                        .with_context(|| {
+                            format!("Failed to cache template in {}", self.cache_dir.display())
+                        })?;
+                    contents
+                }
+                Err(err) => match cached.filter(|_| expected.is_none()) {
+                    Some(cached) => {
+                        eprintln!(
+                            "Note: could not refresh template {} ({:#}); using the cached copy",
+                            reference.url(),
+                            err
+                        );
+                        cached
+                    }
+                    None => return Err(err.context("Failed to fetch remote template")),
+                },
+            },
+        };
+
+        let published: ConfigFile = toml_edit::de::from_str(&contents)
+            .with_context(|| format!("Failed to parse template {}", reference.url()))?;
+        if RemoteTemplateRef::is_remote(&published.template) {
+            anyhow::bail!(
+                "Template {} must extend a built-in template, not '{}'",
+                reference.url(),
+                published.template
+            );
+        }
+        Ok(published)
+    }
+}
+
+/// Fetch a URL with `curl`
+pub(crate) fn fetch_url<CE: CommandExecutor>(cmd_executor: &CE, url: &str) -> Result<String> {
+    let output = cmd_executor
+        .execute(|cmd| cmd.arg("-fsSL").arg(url), "curl")
+        .with_context(|| format!("Failed to run curl to fetch {}", url))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to fetch {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout).with_context(|| format!("{} is not valid UTF-8", url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::io;
+    use std::process::{Command, ExitStatus, Output};
+
+    const PUBLISHED: &str = "template = \"aggressive\"\n\n[profile]\nopt-level = \"z\"\n";
+
+    /// Serves `PUBLISHED` and counts fetches, or fails when offline
+    #[derive(Default)]
+    struct FakeCurl {
+        fetches: Cell<usize>,
+        offline: Cell<bool>,
+    }
+
+    impl CommandExecutor for FakeCurl {
+        fn status(&self, _cmd: &mut Command) -> io::Result<ExitStatus> {
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, cmd: &mut Command) -> io::Result<Output> {
+            let args: Vec<_> = cmd.get_args().collect();
+            assert_eq!(
+                args[1],
+                "https://raw.githubusercontent.com/org/templates/v2/wasm-slim-template.toml"
+            );
+            self.fetches.set(self.fetches.get() + 1);
+            if self.offline.get() {
+                return Ok(Output {
+                    status: crate::infra::mock_exit_status(6),
+                    stdout: Vec::new(),
+                    stderr: b"curl: (6) Could not resolve host".to_vec(),
+                });
+            }
+            Ok(Output {
+                status: crate::infra::mock_exit_status(0),
+                stdout: PUBLISHED.as_bytes().to_vec(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_remote_template_references() {
+        let r = RemoteTemplateRef::parse("github:org/templates/wasm/strict.toml#abc123").unwrap();
+        assert_eq!(
+            r.url(),
+            "https://raw.githubusercontent.com/org/templates/abc123/wasm/strict.toml"
+        );
+
+        for invalid in [
+            "github:org/templates",
+            "github:org#v1",
+            "github:org/templates#",
+            "github:org/templates/../x.toml#v1",
+        ] {
+            assert!(RemoteTemplateRef::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_fetch_verifies_checksum_and_caches() {
+        let temp = tempfile::tempdir().unwrap();
+        let reference = RemoteTemplateRef::parse("github:org/templates#v2").unwrap();
+        let fetcher =
+            RemoteTemplateFetcher::with_executors(temp.path(), RealFileSystem, FakeCurl::default());
+
+        let mismatch = fetcher.fetch(&reference, Some("00")).unwrap_err();
+        assert!(mismatch.to_string().contains("Checksum mismatch"));
+
+        let checksum = sha256_hex(PUBLISHED.as_bytes());
+        let published = fetcher.fetch(&reference, Some(&checksum)).unwrap();
+        assert_eq!(published.template, "aggressive");
+        // Served from the cache the second time
+        fetcher.fetch(&reference, Some(&checksum)).unwrap();
+        assert_eq!(fetcher.cmd_executor.fetches.get(), 2);
+    }
+
+    #[test]
+    fn test_fetch_unpinned_refetches_and_falls_back_to_cache_offline() {
+        let temp = tempfile::tempdir().unwrap();
+        let reference = RemoteTemplateRef::parse("github:org/templates#v2").unwrap();
+        let fetcher =
+            RemoteTemplateFetcher::with_executors(temp.path(), RealFileSystem, FakeCurl::default());
+
+        fetcher.fetch(&reference, None).unwrap();
+        fetcher.fetch(&reference, None).unwrap();
+        assert_eq!(fetcher.cmd_executor.fetches.get(), 2);
+
+        fetcher.cmd_executor.offline.set(true);
+        let published = fetcher.fetch(&reference, None).unwrap();
+        assert_eq!(published.template, "aggressive");
+        assert!(fetcher
+            .fetch(
+                &RemoteTemplateRef::parse("github:org/templates#v2").unwrap(),
+                Some("00")
+            )
+            .is_err());
+    }
+}
@@ -1,12 +1,16 @@
 //! Template resolution logic
 
 use super::file::ConfigFile;
-use super::template::Template;
-use anyhow::Result;
+use super::remote_template::{RemoteTemplateFetcher, RemoteTemplateRef};
+use super::template::{Template, TemplateType};
+use anyhow::{Context, Result};
+use std::path::Path;
 
 /// Handles template resolution and merging
 ///
 /// Merges base template settings with user overrides from `.wasm-slim.toml`.
+/// Remote (`github:`) templates are fetched and cached via
+/// [`RemoteTemplateFetcher`].
 ///
 /// # Examples
 ///
@@ -38,10 +42,40 @@ impl TemplateResolver {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn resolve(config: &ConfigFile) -> Result<Template> {
-        // Get base template
-        let mut template = super::template::Template::get(&config.template)
+        Self::resolve_in(config, Path::new("."))
+    }
+
+    /// Merge template settings with config overrides for a project
+    ///
+    /// Remote templates are cached in the project's `.wasm-slim/templates/`.
+    pub fn resolve_in(config: &ConfigFile, project_root: &Path) -> Result<Template> {
+        if !RemoteTemplateRef::is_remote(&config.template) {
+            return Self::resolve_builtin(config);
+        }
+
+        let reference = RemoteTemplateRef::parse(&config.template)?;
+        let published = RemoteTemplateFetcher::new(project_root)
+            .fetch(&reference, config.template_sha256.as_deref())
+            .with_context(|| format!("Failed to resolve template '{}'", config.template))?;
+
+        let mut template = Self::resolve_builtin(&published)?;
+        template.template_type = TemplateType::Custom;
+        template.name = config.template.clone();
+        template.description = format!("Shared template based on '{}'", published.template);
+        Self::apply_overrides(&mut template, config);
+        Ok(template)
+    }
+
+    /// Resolve a built-in template with the config's overrides
+    fn resolve_builtin(config: &ConfigFile) -> Result<Template> {
+        let mut template = Template::get(&config.template)
             .ok_or_else(|| anyhow::anyhow!("Template '{}' not found", config.template))?;
+        Self::apply_overrides(&mut template, config);
+        Ok(template)
+    }
 
+    /// Apply the config's `[profile]` and `[wasm_opt]` overrides
+    fn apply_overrides(template: &mut Template, config: &ConfigFile) {
         // Apply profile overrides
         if let Some(ref profile) = config.profile {
             if let Some(ref opt_level) = profile.opt_level {
@@ -66,9 +100,16 @@ impl TemplateResolver {
             if let Some(ref flags) = wasm_opt.flags {
                 template.wasm_opt.flags = flags.clone();
             }
+            if let Some(threads) = wasm_opt.threads {
+                template.wasm_opt.threads = Some(threads);
+            }
+            if let Some(low_memory_unused) = wasm_opt.low_memory_unused {
+                template.wasm_opt.low_memory_unused = low_memory_unused;
+            }
+            if let Some(converge) = wasm_opt.converge {
+                template.wasm_opt.converge = converge;
+            }
         }
-
-        Ok(template)
     }
 
     /// Create a config from a template
@@ -98,8 +139,12 @@ impl TemplateResolver {
             }),
             wasm_opt: Some(WasmOptSettings {
                 flags: Some(template.wasm_opt.flags.clone()),
+                threads: template.wasm_opt.threads,
+                low_memory_unused: Some(template.wasm_opt.low_memory_unused),
+                converge: Some(template.wasm_opt.converge),
             }),
             size_budget: None,
+            ..Default::default()
         }
     }
 }
@@ -236,12 +281,34 @@ mod tests {
         };
         config.wasm_opt = Some(WasmOptSettings {
             flags: Some(vec!["--enable-simd".to_string()]),
+            ..Default::default()
         });
 
         let template = TemplateResolver::resolve(&config).unwrap();
         assert_eq!(template.wasm_opt.flags, vec!["--enable-simd"]);
     }
 
+    #[test]
+    fn test_resolver_merges_wasm_opt_tuning_without_touching_flags() {
+        use super::super::file::WasmOptSettings;
+
+        let config = ConfigFile {
+            template: "balanced".to_string(),
+            wasm_opt: Some(WasmOptSettings {
+                threads: Some(2),
+                converge: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let template = TemplateResolver::resolve(&config).unwrap();
+        assert_eq!(template.wasm_opt.threads, Some(2));
+        assert!(template.wasm_opt.converge);
+        assert!(!template.wasm_opt.low_memory_unused);
+        assert!(template.wasm_opt.flags.contains(&"-Oz".to_string()));
+    }
+
     #[test]
     fn test_resolver_preserves_template_values_without_overrides() {
         let config = ConfigFile {
@@ -308,6 +375,7 @@ mod tests {
         });
         config.wasm_opt = Some(WasmOptSettings {
             flags: Some(vec!["--custom-flag".to_string()]),
+            ..Default::default()
         });
 
         let template = TemplateResolver::resolve(&config).unwrap();
@@ -319,4 +387,34 @@ mod tests {
         assert_eq!(template.profile.panic, "abort");
         assert_eq!(template.wasm_opt.flags, vec!["--custom-flag"]);
     }
+
+    #[test]
+    fn test_resolve_in_layers_project_overrides_on_cached_remote_template() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache = temp.path().join(".wasm-slim/templates");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(
+            cache.join("org-templates-v2-wasm-slim-template.toml.toml"),
+            "template = \"aggressive\"\n\n[profile]\nopt-level = \"z\"\nlto = \"thin\"\n",
+        )
+        .unwrap();
+
+        let config = ConfigFile {
+            template: "github:org/templates#v2".to_string(),
+            profile: Some(ProfileSettings {
+                opt_level: None,
+                lto: Some("fat".to_string()),
+                strip: None,
+                codegen_units: None,
+                panic: None,
+            }),
+            ..Default::default()
+        };
+        let template = TemplateResolver::resolve_in(&config, temp.path()).unwrap();
+
+        assert_eq!(template.name, "github:org/templates#v2");
+        assert_eq!(template.template_type, TemplateType::Custom);
+        assert_eq!(template.profile.opt_level, "z");
+        assert_eq!(template.profile.lto, "fat");
+    }
 }
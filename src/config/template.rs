@@ -190,6 +190,9 @@ impl Template {
                     "--strip-dwarf".to_string(),
                     "--strip-producers".to_string(),
                 ],
+                threads: None,
+                low_memory_unused: false,
+                converge: false,
             },
             wasm_bindgen: WasmBindgenConfig {
                 debug: false,
@@ -231,6 +234,9 @@ impl Template {
                     "--strip-dwarf".to_string(),
                     "--strip-producers".to_string(),
                 ],
+                threads: None,
+                low_memory_unused: false,
+                converge: false,
             },
             wasm_bindgen: WasmBindgenConfig {
                 debug: false,
@@ -276,6 +282,9 @@ impl Template {
                     "--closed-world".to_string(),
                     "--gufa-optimizing".to_string(),
                 ],
+                threads: None,
+                low_memory_unused: false,
+                converge: false,
             },
             wasm_bindgen: WasmBindgenConfig {
                 debug: false,
@@ -375,6 +384,9 @@ impl Template {
             },
             wasm_opt: WasmOptConfig {
                 flags: vec!["-Oz".to_string()],
+                threads: None,
+                low_memory_unused: false,
+                converge: false,
             },
             wasm_bindgen: WasmBindgenConfig {
                 debug: false,
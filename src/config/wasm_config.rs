@@ -1,12 +1,87 @@
 //! WASM tooling configuration (wasm-opt, wasm-bindgen)
 
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+/// First binaryen release that accepts `--low-memory-unused`
+pub const LOW_MEMORY_UNUSED_MIN_VERSION: u32 = 90;
+
 /// wasm-opt configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WasmOptConfig {
     /// Optimization flags for wasm-opt
     pub flags: Vec<String>,
+    /// Worker threads for wasm-opt (passed as `BINARYEN_CORES`, capped at
+    /// the machine's available parallelism; `None` uses all cores)
+    #[serde(default)]
+    pub threads: Option<u32>,
+    /// Pass `--low-memory-unused` (assumes addresses below 1024 are unused)
+    #[serde(default)]
+    pub low_memory_unused: bool,
+    /// Pass `--converge` (rerun passes until the binary stops shrinking)
+    #[serde(default)]
+    pub converge: bool,
+}
+
+impl WasmOptConfig {
+    /// Validate tuning options against the detected binaryen version
+    ///
+    /// `binaryen_version` is the release number reported by
+    /// `wasm-opt --version` (see [`parse_binaryen_version`]); version checks are
+    /// skipped when it is unknown.
+    pub fn validate(&self, binaryen_version: Option<u32>) -> Result<()> {
+        if self.threads == Some(0) {
+            anyhow::bail!("wasm-opt threads must be at least 1");
+        }
+
+        if let Some(version) = binaryen_version {
+            if self.low_memory_unused && version < LOW_MEMORY_UNUSED_MIN_VERSION {
+                anyhow::bail!(
+                    "low-memory-unused requires binaryen {} or newer (detected version {})",
+                    LOW_MEMORY_UNUSED_MIN_VERSION,
+                    version
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pass arguments derived from the tuning options
+    pub fn tuning_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.low_memory_unused {
+            args.push("--low-memory-unused".to_string());
+        }
+        if self.converge {
+            args.push("--converge".to_string());
+        }
+        args
+    }
+
+    /// Thread count to use on a machine with `available` cores
+    pub fn effective_threads(&self, available: usize) -> Option<u32> {
+        let available = u32::try_from(available.max(1)).unwrap_or(u32::MAX);
+        self.threads.map(|threads| threads.min(available))
+    }
+}
+
+/// Extract the binaryen release number from `wasm-opt --version` output
+///
+/// # Examples
+///
+/// ```
+/// use wasm_slim::config::wasm_config::parse_binaryen_version;
+///
+/// assert_eq!(parse_binaryen_version("wasm-opt version 116 (version_116)"), Some(116));
+/// assert_eq!(parse_binaryen_version("wasm-opt"), None);
+/// ```
+pub fn parse_binaryen_version(output: &str) -> Option<u32> {
+    let rest = &output[output.find("version")? + "version".len()..];
+    rest.split(|c: char| !c.is_ascii_digit())
+        .find(|part| !part.is_empty())?
+        .parse()
+        .ok()
 }
 
 impl Default for WasmOptConfig {
@@ -23,6 +98,9 @@ impl Default for WasmOptConfig {
                 "--strip-dwarf".to_string(),
                 "--strip-producers".to_string(),
             ],
+            threads: None,
+            low_memory_unused: false,
+            converge: false,
         }
     }
 }
@@ -73,6 +151,60 @@ mod tests {
         assert!(config.flags.contains(&"--enable-sign-ext".to_string()));
     }
 
+    #[test]
+    fn test_wasm_opt_config_default_has_no_tuning() {
+        let config = WasmOptConfig::default();
+        assert!(config.tuning_args().is_empty());
+        assert_eq!(config.effective_threads(8), None);
+    }
+
+    #[test]
+    fn test_wasm_opt_tuning_args_and_thread_cap() {
+        let config = WasmOptConfig {
+            threads: Some(16),
+            low_memory_unused: true,
+            converge: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.tuning_args(),
+            vec!["--low-memory-unused", "--converge"]
+        );
+        assert_eq!(config.effective_threads(2), Some(2));
+        assert_eq!(config.effective_threads(32), Some(16));
+    }
+
+    #[test]
+    fn test_wasm_opt_validate_rejects_zero_threads() {
+        let config = WasmOptConfig {
+            threads: Some(0),
+            ..Default::default()
+        };
+        assert!(config.validate(None).is_err());
+    }
+
+    #[test]
+    fn test_wasm_opt_validate_checks_binaryen_version() {
+        let config = WasmOptConfig {
+            low_memory_unused: true,
+            ..Default::default()
+        };
+        assert!(config.validate(Some(89)).is_err());
+        assert!(config.validate(Some(LOW_MEMORY_UNUSED_MIN_VERSION)).is_ok());
+        assert!(config.validate(None).is_ok());
+    }
+
+    #[test]
+    fn test_parse_binaryen_version_formats() {
+        assert_eq!(
+            parse_binaryen_version("wasm-opt version 116 (version_116)"),
+            Some(116)
+        );
+        assert_eq!(parse_binaryen_version("wasm-opt version_89"), Some(89));
+        assert_eq!(parse_binaryen_version("mock-version 1.0.0"), Some(1));
+        assert_eq!(parse_binaryen_version(""), None);
+    }
+
     #[test]
     fn test_wasm_bindgen_config_default_disables_debug() {
         let config = WasmBindgenConfig::default();
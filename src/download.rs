@@ -0,0 +1,351 @@
+//! Downloading WASM modules from URLs
+//!
+//! `analyze` and `compare` accept `http://` and `https://` URLs in place of
+//! file paths, so deployed bundles can be audited without a checkout. Modules
+//! are fetched with `curl` into `.wasm-slim/downloads/`; later runs send a
+//! conditional request and reuse that copy unless the module changed. The
+//! SHA-256 of every module is reported so the artifact that was analyzed can
+//! be identified.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use wasm_slim::download::WasmDownloader;
+//!
+//! let module = WasmDownloader::new(".".as_ref()).fetch("https://example.com/app_bg.wasm")?;
+//! println!("{} -> {} (sha256 {})", module.url, module.path.display(), module.sha256);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::fmt::format_bytes;
+use crate::hash::sha256_hex;
+use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Download cache directory, inside the state directory
+pub const CACHE_DIR: &str = "downloads";
+
+/// Magic number every WASM module starts with
+const WASM_MAGIC: [u8; 4] = [0x00, 0x61, 0x73, 0x6d];
+
+/// Whether a command-line argument is a URL rather than a path
+pub fn is_url(arg: &str) -> bool {
+    arg.starts_with("https://") || arg.starts_with("http://")
+}
+
+/// A module fetched from a URL
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadedWasm {
+    /// URL the module was fetched from
+    pub url: String,
+    /// Local copy
+    pub path: PathBuf,
+    /// Lowercase hex SHA-256 of the module
+    pub sha256: String,
+    /// Module size
+    pub size_bytes: u64,
+    /// Whether the local copy came from an earlier run (unchanged on the server)
+    pub cached: bool,
+}
+
+/// Fetches and caches WASM modules
+pub struct WasmDownloader<
+    FS: FileSystem = RealFileSystem,
+    CE: CommandExecutor = RealCommandExecutor,
+> {
+    cache_dir: PathBuf,
+    fs: FS,
+    cmd_executor: CE,
+}
+
+impl WasmDownloader {
+    /// Create a downloader caching in the project's `.wasm-slim/downloads/`
+    pub fn new(project_root: &Path) -> Self {
+        Self::with_executors(project_root, RealFileSystem, RealCommandExecutor)
+    }
+}
+
+impl<FS: FileSystem, CE: CommandExecutor> WasmDownloader<FS, CE> {
+    /// Create a downloader with custom executors; modules are fetched with `curl`
+    pub fn with_executors(project_root: &Path, fs: FS, cmd_executor: CE) -> Self {
+        Self {
+            cache_dir: crate::state::state_dir(project_root).join(CACHE_DIR),
+            fs,
+            cmd_executor,
+        }
+    }
+
+    /// Fetch the module at `url`, revalidating a copy downloaded before
+    ///
+    /// A cached module is only downloaded again when the server reports it
+    /// changed (`If-None-Match` with the saved ETag, or `If-Modified-Since`).
+    /// When the server cannot be reached the cached copy is used. A response
+    /// that is not a WASM module (an HTML error page, say) is rejected and
+    /// not cached.
+    pub fn fetch(&self, url: &str) -> Result<DownloadedWasm> {
+        let path = self.cache_dir.join(cache_file_name(url));
+        let etag = path.with_extension("etag");
+        let partial = path.with_extension("wasm.part");
+        let cached = self.fs.metadata(&path).is_ok();
+        let has_etag = cached && self.fs.metadata(&etag).is_ok();
+
+        self.fs
+            .create_dir_all(&self.cache_dir)
+            .with_context(|| format!("Failed to create {}", self.cache_dir.display()))?;
+        let output = self
+            .cmd_executor
+            .execute(
+                |cmd| {
+                    cmd.args(["-fsSL", "-w", "%{http_code}", "--etag-save"])
+                        .arg(&etag);
+                    if has_etag {
+                        cmd.arg("--etag-compare").arg(&etag);
+                    }
+                    if cached {
+                        cmd.arg("-z").arg(&path);
+                    }
+                    cmd.arg("-o").arg(&partial).arg(url)
+                },
+                "curl",
+            )
+            .with_context(|| format!("Failed to run curl to fetch {}", url))?;
+        let not_modified = String::from_utf8_lossy(&output.stdout).trim() == "304";
+
+        let refreshed = if !output.status.success() {
+            let _ = self.fs.remove_file(&partial);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if !cached {
+                anyhow::bail!("Failed to fetch {}: {}", url, stderr.trim());
+            }
+            eprintln!(
+                "Note: could not revalidate {} ({}); using the cached copy",
+                url,
+                stderr.trim()
+            );
+            false
+        } else if cached && not_modified {
+            let _ = self.fs.remove_file(&partial);
+            false
+        } else {
+            let bytes = self
+                .fs
+                .read(&partial)
+                .with_context(|| format!("Failed to read download of {}", url))?;
+            let _ = self.fs.remove_file(&partial);
+            if !bytes.starts_with(&WASM_MAGIC) {
+                let _ = self.fs.remove_file(&etag);
+                anyhow::bail!("{} did not return a WASM module", url);
+            }
+            self.fs.write(&path, &bytes).with_context(|| {
+                format!("Failed to cache {} in {}", url, self.cache_dir.display())
+            })?;
+            true
+        };
+
+        let bytes = self
+            .fs
+            .read(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(DownloadedWasm {
+            url: url.to_string(),
+            path,
+            sha256: sha256_hex(&bytes),
+            size_bytes: bytes.len() as u64,
+            cached: !refreshed,
+        })
+    }
+}
+
+/// Resolve a command-line WASM argument to a local path
+///
+/// Paths are returned unchanged. URLs are fetched into the current project's
+/// download cache, with the size and SHA-256 printed to stderr so JSON output
+/// on stdout stays machine-readable.
+pub fn resolve_input(arg: &str) -> Result<String> {
+    if !is_url(arg) {
+        return Ok(arg.to_string());
+    }
+    let module = WasmDownloader::new(&std::env::current_dir()?).fetch(arg)?;
+    eprintln!(
+        "{} {} ({}{})\n   sha256 {}",
+        if module.cached {
+            "Using cached"
+        } else {
+            "Downloaded"
+        },
+        module.url,
+        format_bytes(module.size_bytes),
+        if module.cached {
+            String::new()
+        } else {
+            format!(", saved to {}", module.path.display())
+        },
+        module.sha256
+    );
+    Ok(module.path.to_string_lossy().into_owned())
+}
+
+/// Cache file name: a digest of the URL plus its last path segment
+fn cache_file_name(url: &str) -> String {
+    let without_query = url.split(['?', '#']).next().unwrap_or(url);
+    let base: String = without_query
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(".wasm")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let digest = &sha256_hex(url.as_bytes())[..16];
+    if base.is_empty() {
+        format!("{}.wasm", digest)
+    } else {
+        format!("{}-{}.wasm", base, digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::io;
+    use std::process::{Command, ExitStatus, Output};
+
+    /// A server holding `body` under ETag `"v1"`; counts fetches
+    ///
+    /// A request carrying the saved ETag gets a 304 unless `changed` is set.
+    struct FakeCurl {
+        body: &'static [u8],
+        fetches: Cell<usize>,
+        changed: bool,
+        offline: bool,
+    }
+
+    impl FakeCurl {
+        fn serving(body: &'static [u8]) -> Self {
+            Self {
+                body,
+                fetches: Cell::new(0),
+                changed: false,
+                offline: false,
+            }
+        }
+    }
+
+    impl CommandExecutor for FakeCurl {
+        fn status(&self, _cmd: &mut Command) -> io::Result<ExitStatus> {
+            Ok(crate::infra::mock_exit_status(0))
+        }
+
+        fn output(&self, cmd: &mut Command) -> io::Result<Output> {
+            let args: Vec<_> = cmd.get_args().collect();
+            let after = |flag: &str| {
+                args.iter()
+                    .position(|a| *a == flag)
+                    .map(|i| Path::new(args[i + 1]))
+            };
+            self.fetches.set(self.fetches.get() + 1);
+            if self.offline {
+                return Ok(Output {
+                    status: crate::infra::mock_exit_status(6),
+                    stdout: Vec::new(),
+                    stderr: b"curl: (6) Could not resolve host".to_vec(),
+                });
+            }
+            let revalidating = after("--etag-compare").is_some();
+            let http_code = if revalidating && !self.changed {
+                "304"
+            } else {
+                std::fs::write(after("-o").unwrap(), self.body)?;
+                "200"
+            };
+            std::fs::write(after("--etag-save").unwrap(), "\"v1\"")?;
+            Ok(Output {
+                status: crate::infra::mock_exit_status(0),
+                stdout: http_code.as_bytes().to_vec(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_fetch_caches_module_and_reports_checksum() {
+        let temp = tempfile::tempdir().unwrap();
+        let curl = FakeCurl::serving(b"\0asm\x01\0\0\0");
+        let downloader = WasmDownloader::with_executors(temp.path(), RealFileSystem, curl);
+        let url = "https://example.com/assets/app_bg.wasm?v=3";
+
+        let first = downloader.fetch(url).unwrap();
+        assert!(!first.cached);
+        assert_eq!(first.size_bytes, 8);
+        assert_eq!(first.sha256, sha256_hex(b"\0asm\x01\0\0\0"));
+        assert!(first
+            .path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("app_bg-"));
+
+        // Revalidated with the saved ETag and answered with 304
+        let second = downloader.fetch(url).unwrap();
+        assert!(second.cached);
+        assert_eq!(second.path, first.path);
+        assert_eq!(downloader.cmd_executor.fetches.get(), 2);
+    }
+
+    #[test]
+    fn test_fetch_replaces_changed_module_and_falls_back_offline() {
+        let temp = tempfile::tempdir().unwrap();
+        let url = "https://example.com/app.wasm";
+        WasmDownloader::with_executors(temp.path(), RealFileSystem, FakeCurl::serving(b"\0asm"))
+            .fetch(url)
+            .unwrap();
+
+        let changed = FakeCurl {
+            changed: true,
+            ..FakeCurl::serving(b"\0asm\x01\0\0\0")
+        };
+        let refreshed = WasmDownloader::with_executors(temp.path(), RealFileSystem, changed)
+            .fetch(url)
+            .unwrap();
+        assert!(!refreshed.cached);
+        assert_eq!(refreshed.size_bytes, 8);
+
+        let offline = FakeCurl {
+            offline: true,
+            ..FakeCurl::serving(b"")
+        };
+        let fallback = WasmDownloader::with_executors(temp.path(), RealFileSystem, offline)
+            .fetch(url)
+            .unwrap();
+        assert!(fallback.cached);
+        assert_eq!(fallback.sha256, refreshed.sha256);
+    }
+
+    #[test]
+    fn test_fetch_rejects_non_wasm_responses() {
+        let temp = tempfile::tempdir().unwrap();
+        let curl = FakeCurl::serving(b"<html>Not Found</html>");
+        let downloader = WasmDownloader::with_executors(temp.path(), RealFileSystem, curl);
+
+        let err = downloader
+            .fetch("https://example.com/app.wasm")
+            .unwrap_err();
+        assert!(err.to_string().contains("did not return a WASM module"));
+        assert!(
+            std::fs::read_dir(temp.path().join(".wasm-slim").join(CACHE_DIR))
+                .unwrap()
+                .next()
+                .is_none()
+        );
+        assert!(is_url("http://localhost/x.wasm") && !is_url("pkg/app_bg.wasm"));
+    }
+}
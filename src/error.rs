@@ -188,6 +188,9 @@ impl WasmSlimError {
                 "Check file permissions and that {} is accessible",
                 context
             )),
+            Self::Pipeline(PipelineError::PreOptSizeExceeded { .. }) => Some(
+                "The problem is upstream of wasm-opt: check for a debug build (profile, debug = true) or large embedded assets (wasm-slim analyze --mode assets)".to_string(),
+            ),
             Self::Pipeline(e) => {
                 let msg = e.to_string();
                 if msg.contains("wasm32-unknown-unknown") {
@@ -649,6 +652,20 @@ mod tests {
         assert!(suggestion.contains("wasm32-unknown-unknown"));
     }
 
+    #[test]
+    fn test_preopt_size_exceeded_points_upstream() {
+        let err = WasmSlimError::Pipeline(crate::pipeline::PipelineError::PreOptSizeExceeded {
+            size_bytes: 10 * 1024 * 1024,
+            limit_bytes: 1024 * 1024,
+        });
+
+        let suggestion = err
+            .suggestion()
+            .expect("Pipeline error should have suggestion");
+        assert!(suggestion.contains("debug build"));
+        assert!(!suggestion.contains("cargo install"));
+    }
+
     #[test]
     fn test_pipeline_error_accessor() {
         let pipeline_err = crate::pipeline::PipelineError::BuildFailed("test".to_string());
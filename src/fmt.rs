@@ -34,28 +34,53 @@ pub const WARNING: Emoji = Emoji("⚠️", "!");
 
 /// Format bytes as human-readable size string
 ///
+/// Units are binary (1 KiB = 1024 bytes) and labeled as such. Sizes are
+/// kept in bytes everywhere else and only converted here, so small items
+/// are never lost to rounding before they are summed.
+///
 /// # Examples
 ///
 /// ```
 /// use wasm_slim::fmt::format_bytes;
 ///
 /// assert_eq!(format_bytes(512), "512 B");
-/// assert_eq!(format_bytes(1024), "1.00 KB");
-/// assert_eq!(format_bytes(1_048_576), "1.00 MB");
+/// assert_eq!(format_bytes(1024), "1.00 KiB");
+/// assert_eq!(format_bytes(1_048_576), "1.00 MiB");
 /// ```
 pub fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
+    const KIB: u64 = 1024;
+    const MIB: u64 = KIB * 1024;
+    const GIB: u64 = MIB * 1024;
 
-    if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
+    if bytes >= GIB {
+        format!("{:.2} GiB", bytes as f64 / GIB as f64)
+    } else if bytes >= MIB {
+        format!("{:.2} MiB", bytes as f64 / MIB as f64)
+    } else if bytes >= KIB {
+        format!("{:.2} KiB", bytes as f64 / KIB as f64)
     } else {
         format!("{} B", bytes)
     }
 }
 
+/// Percentage of `part` in `total`, 0 for an empty total
+///
+/// # Examples
+///
+/// ```
+/// use wasm_slim::fmt::percent_of;
+///
+/// assert_eq!(percent_of(512, 2048), 25.0);
+/// assert_eq!(percent_of(10, 0), 0.0);
+/// ```
+pub fn percent_of(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,9 +88,10 @@ mod tests {
     #[test]
     fn test_format_bytes_various_sizes() {
         assert_eq!(format_bytes(512), "512 B");
-        assert_eq!(format_bytes(1024), "1.00 KB");
-        assert_eq!(format_bytes(1536), "1.50 KB");
-        assert_eq!(format_bytes(1_048_576), "1.00 MB");
-        assert_eq!(format_bytes(2_621_440), "2.50 MB");
+        assert_eq!(format_bytes(1024), "1.00 KiB");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(1_048_576), "1.00 MiB");
+        assert_eq!(format_bytes(2_621_440), "2.50 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.00 GiB");
     }
 }
@@ -1,6 +1,8 @@
 //! Git metadata utilities for build tracking
 
 use crate::infra::{CommandExecutor, RealCommandExecutor};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Git operation errors
@@ -23,6 +25,17 @@ pub enum GitError {
     Io(#[from] std::io::Error),
 }
 
+/// A commit from `git log`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitInfo {
+    /// Abbreviated commit hash
+    pub hash: String,
+    /// Author name
+    pub author: String,
+    /// First line of the commit message
+    pub summary: String,
+}
+
 /// Git repository interface with dependency injection for testability
 pub struct GitRepository<CE: CommandExecutor = RealCommandExecutor> {
     cmd_executor: CE,
@@ -118,6 +131,47 @@ impl<CE: CommandExecutor> GitRepository<CE> {
 
         Ok(Some(branch))
     }
+
+    /// Commits after `since` (up to HEAD) that touch any of `paths`, newest first
+    ///
+    /// Fails if `since` is not a valid revision.
+    pub fn log_since(&self, since: &str, paths: &[PathBuf]) -> Result<Vec<CommitInfo>, GitError> {
+        let range = format!("{}..HEAD", since);
+        let output = self.cmd_executor.execute(
+            |cmd| {
+                cmd.args([
+                    "log",
+                    "--no-merges",
+                    "--format=%h%x1f%an%x1f%s",
+                    &range,
+                    "--",
+                ])
+                .args(paths)
+            },
+            "git",
+        )?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("not a git repository") {
+                return Err(GitError::NotARepository);
+            }
+            return Err(GitError::CommandFailed(stderr.trim().to_string()));
+        }
+
+        let stdout = String::from_utf8(output.stdout).map_err(|_| GitError::InvalidUtf8)?;
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\u{1f}');
+                Some(CommitInfo {
+                    hash: fields.next()?.to_string(),
+                    author: fields.next()?.to_string(),
+                    summary: fields.next().unwrap_or("").to_string(),
+                })
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -178,6 +232,37 @@ mod tests {
         assert_eq!(result, Some("main".to_string()));
     }
 
+    #[test]
+    fn test_log_since_parses_commits() {
+        let mock = MockCommandExecutor {
+            stdout: "abc1234\u{1f}Ana\u{1f}Add serde_json\ndef5678\u{1f}Li\u{1f}Bump regex\n"
+                .as_bytes()
+                .to_vec(),
+            stderr: vec![],
+            success: true,
+        };
+        let repo = GitRepository::with_executor(mock);
+
+        let commits = repo
+            .log_since("main", &[PathBuf::from("Cargo.lock")])
+            .unwrap();
+        assert_eq!(
+            commits,
+            vec![
+                CommitInfo {
+                    hash: "abc1234".to_string(),
+                    author: "Ana".to_string(),
+                    summary: "Add serde_json".to_string(),
+                },
+                CommitInfo {
+                    hash: "def5678".to_string(),
+                    author: "Li".to_string(),
+                    summary: "Bump regex".to_string(),
+                },
+            ]
+        );
+    }
+
     // Integration tests with real git
     #[test]
     fn test_get_commit_hash_returns_option() {
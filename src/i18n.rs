@@ -0,0 +1,420 @@
+//! Localization of user-facing report strings
+//!
+//! Report headings, summaries and recommendation descriptions are looked up
+//! by key in a [`Catalog`]. English is built in; a translation file replaces
+//! any subset of the messages and the rest fall back to English.
+//!
+//! # Translation files
+//!
+//! Translations are TOML files. Nested tables are flattened into dotted keys,
+//! and `{name}` placeholders are filled in at runtime:
+//!
+//! ```toml
+//! locale = "de"
+//!
+//! [report]
+//! recommendations = "EMPFEHLUNGEN:"
+//!
+//! [twiggy]
+//! symbol_dominates = "Symbol '{name}' belegt {percent}% des Bundles."
+//! ```
+//!
+//! Load one with `--translations <FILE>` or in `.wasm-slim.toml`:
+//!
+//! ```toml
+//! [i18n]
+//! translations = "i18n/de.toml"
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use wasm_slim::i18n::Catalog;
+//!
+//! let catalog = Catalog::from_toml_str("locale = \"de\"\n[report]\nrecommendations = \"EMPFEHLUNGEN:\"\n")?;
+//! assert_eq!(catalog.locale(), "de");
+//! assert_eq!(catalog.text("report.recommendations"), "EMPFEHLUNGEN:");
+//! // Untranslated keys fall back to English
+//! assert_eq!(catalog.text("report.total_size"), "Total Size:");
+//! # Ok::<(), wasm_slim::i18n::I18nError>(())
+//! ```
+
+use crate::infra::{FileSystem, RealFileSystem};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Built-in English messages
+pub const ENGLISH: &[(&str, &str)] = &[
+    // Report headings and summaries
+    ("report.wasm_size_analysis", "WASM Size Analysis"),
+    ("report.total_size", "Total Size:"),
+    ("report.analysis_mode", "Analysis Mode:"),
+    ("report.top_contributors", "TOP CONTRIBUTORS ({count} items):"),
+    ("report.recommendations", "RECOMMENDATIONS:"),
+    ("report.potential_savings", "→ Potential savings:"),
+    ("report.total_potential", "Total optimization potential:"),
+    (
+        "report.well_optimized",
+        "✨ No major optimization opportunities detected. Bundle is well-optimized!",
+    ),
+    ("report.comparison_title", "WASM Build Comparison"),
+    ("report.before", "Before:"),
+    ("report.after", "After:"),
+    ("report.delta", "Delta:"),
+    ("report.gzip", "Gzip:"),
+    ("report.brotli", "Brotli:"),
+    ("report.top_changes", "TOP CHANGES:"),
+    ("report.symbols_added", "Added ({count}):"),
+    ("report.symbols_removed", "Removed ({count}):"),
+    ("report.symbols_grown", "Grown ({count}):"),
+    ("report.symbols_shrunk", "Shrunk ({count}):"),
+    ("report.multi_comparison_title", "WASM Build Comparison ({count} builds)"),
+    ("report.vs_previous", "vs previous"),
+    ("report.vs_first", "vs first"),
+    ("deps.report_title", "Dependency Analysis Report"),
+    ("deps.total", "Total dependencies:"),
+    ("deps.direct", "Direct dependencies:"),
+    ("deps.opportunities_found", "Optimization Opportunities Found"),
+    ("deps.estimated_savings", "Estimated savings:"),
+    ("deps.all_optimized", "All dependencies optimized!"),
+    ("deps.duplicates", "Duplicate Versions Detected"),
+    ("deps.rule_violations", "Dependency Rule Violations"),
+    ("bloat.title", "Binary Size Analysis"),
+    ("bloat.total_size", "Total Size:"),
+    ("bloat.code_size", "Code (.text):"),
+    ("bloat.top_contributors", "Top Contributors by Size"),
+    ("bloat.top_crates", "Code Size by Crate"),
+    ("bloat.opportunities", "Optimization Opportunities"),
+    // Recommendation descriptions
+    (
+        "twiggy.large_data_segment",
+        "Large data segment '{name}' detected. Consider externalizing embedded assets or generating data at runtime.",
+    ),
+    (
+        "twiggy.top_items_dominate",
+        "Top 20 items contribute {percent}% of bundle. Focus optimization efforts here for maximum impact.",
+    ),
+    (
+        "twiggy.symbol_dominates",
+        "Symbol '{name}' dominates {percent}% of bundle. Making this optional via feature flag could provide significant savings.",
+    ),
+    (
+        "twiggy.dead_code_removable",
+        "{percent}% of bundle is potentially removable. Enable lto = 'fat' and strip = true in Cargo.toml.",
+    ),
+    (
+        "twiggy.dead_code_minimal",
+        "Minimal dead code detected (<1%). Bundle is well-optimized. Focus on other areas.",
+    ),
+    (
+        "twiggy.excessive_monomorphization",
+        "Excessive monomorphization detected in '{name}'. Consider using trait objects or limiting generic types.",
+    ),
+    (
+        "twiggy.mono_significant",
+        "Significant monomorphization bloat detected ({percent}% of bundle). {size} across {count} generic functions.",
+    ),
+    (
+        "twiggy.mono_moderate",
+        "Moderate monomorphization detected ({percent}% of bundle). Consider optimization for top contributors.",
+    ),
+    (
+        "twiggy.mono_minimal",
+        "Minimal monomorphization overhead ({percent}%). Not a priority optimization target.",
+    ),
+    (
+        "twiggy.mono_function",
+        "Function '{name}' has {count} instantiations ({size} total). Consider using 'Box<dyn Trait>' or limiting type parameters.",
+    ),
+    (
+        "twiggy.mono_function_refactor",
+        "Function '{name}' has {count} instantiations ({size} total). {refactor}.",
+    ),
+    (
+        "bloat.large_function",
+        "Large function '{name}' ({percent}% of binary). Consider splitting or optimizing.",
+    ),
+    (
+        "bloat.heavy_crate",
+        "Crate '{name}' contributes {percent}% of binary. Consider lighter alternatives or feature minimization.",
+    ),
+    (
+        "bloat.formatting_code",
+        "Formatting code takes up {percent}% of binary. Consider using panic='abort' or removing Debug derives.",
+    ),
+];
+
+/// Process-wide catalog, installed by [`install`]
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Errors that can occur while loading a translation file
+#[derive(Error, Debug)]
+pub enum I18nError {
+    /// Translation file could not be read
+    #[error("Failed to read translation file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    /// Translation file is not valid TOML
+    #[error("Failed to parse translation file: {0}")]
+    Parse(String),
+
+    /// A message is not a string
+    #[error("Translation for '{0}' must be a string")]
+    NotAString(String),
+}
+
+/// Message catalog for one locale
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    locale: String,
+    messages: HashMap<String, String>,
+    unknown_keys: Vec<String>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl Catalog {
+    /// The built-in English catalog
+    pub fn english() -> Self {
+        Self {
+            locale: "en".to_string(),
+            messages: HashMap::new(),
+            unknown_keys: Vec::new(),
+        }
+    }
+
+    /// Parse a translation file's contents
+    pub fn from_toml_str(content: &str) -> Result<Self, I18nError> {
+        let doc: toml_edit::DocumentMut = content
+            .parse()
+            .map_err(|e: toml_edit::TomlError| I18nError::Parse(e.to_string()))?;
+
+        let mut catalog = Self::english();
+        if let Some(locale) = doc.get("locale") {
+            catalog.locale = locale
+                .as_str()
+                .ok_or_else(|| I18nError::NotAString("locale".to_string()))?
+                .to_string();
+        }
+        catalog.collect(doc.as_table(), "")?;
+        catalog.unknown_keys.sort();
+        Ok(catalog)
+    }
+
+    /// Load a translation file from disk
+    pub fn load(path: &Path) -> Result<Self, I18nError> {
+        Self::load_with_fs(path, &RealFileSystem)
+    }
+
+    /// Load a translation file with a custom filesystem implementation
+    pub fn load_with_fs<FS: FileSystem>(path: &Path, fs: &FS) -> Result<Self, I18nError> {
+        let content = fs
+            .read_to_string(path)
+            .map_err(|e| I18nError::Io(path.to_path_buf(), e))?;
+        Self::from_toml_str(&content)
+    }
+
+    /// Flatten nested tables into dotted keys
+    fn collect(&mut self, table: &dyn toml_edit::TableLike, prefix: &str) -> Result<(), I18nError> {
+        for (key, item) in table.iter() {
+            if prefix.is_empty() && key == "locale" {
+                continue;
+            }
+            let full_key = if prefix.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+
+            if let Some(nested) = item.as_table_like() {
+                self.collect(nested, &full_key)?;
+                continue;
+            }
+
+            let text = item
+                .as_str()
+                .ok_or_else(|| I18nError::NotAString(full_key.clone()))?;
+            if english_text(&full_key).is_none() {
+                self.unknown_keys.push(full_key.clone());
+            }
+            self.messages.insert(full_key, text.to_string());
+        }
+        Ok(())
+    }
+
+    /// Locale code declared by the translation file (`en` for built-in)
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Digest of the locale and every translation
+    ///
+    /// Two catalogs with the same fingerprint render the same text, so it
+    /// keys anything cached after localization.
+    pub fn fingerprint(&self) -> String {
+        let mut messages: Vec<_> = self.messages.iter().collect();
+        messages.sort();
+        let mut bytes = Vec::new();
+        for text in
+            std::iter::once(&self.locale).chain(messages.into_iter().flat_map(|(k, v)| [k, v]))
+        {
+            bytes.extend((text.len() as u64).to_le_bytes());
+            bytes.extend(text.as_bytes());
+        }
+        crate::hash::sha256_hex(&bytes)
+    }
+
+    /// Keys in the translation file that wasm-slim does not use
+    ///
+    /// Usually typos or messages removed in a newer release.
+    pub fn unknown_keys(&self) -> &[String] {
+        &self.unknown_keys
+    }
+
+    /// Message for `key`: translation, then English, then the key itself
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages
+            .get(key)
+            .map(String::as_str)
+            .or_else(|| english_text(key))
+            .unwrap_or(key)
+    }
+
+    /// Message for `key` with `{name}` placeholders filled in
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = self.text(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        text
+    }
+}
+
+/// Built-in English text for `key`
+fn english_text(key: &str) -> Option<&'static str> {
+    ENGLISH.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+/// Install the process-wide catalog
+///
+/// Returns `false` if a catalog was already installed.
+pub fn install(catalog: Catalog) -> bool {
+    CATALOG.set(catalog).is_ok()
+}
+
+/// The process-wide catalog (English unless one was installed)
+pub fn catalog() -> &'static Catalog {
+    static ENGLISH_CATALOG: OnceLock<Catalog> = OnceLock::new();
+    CATALOG
+        .get()
+        .unwrap_or_else(|| ENGLISH_CATALOG.get_or_init(Catalog::english))
+}
+
+/// Look up `key` in the process-wide catalog
+pub fn t(key: &'static str) -> &'static str {
+    catalog().text(key)
+}
+
+/// Look up `key` in the process-wide catalog and fill in placeholders
+pub fn tf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    catalog().format(key, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_catalog_returns_builtin_text() {
+        let catalog = Catalog::english();
+        assert_eq!(catalog.locale(), "en");
+        assert_eq!(catalog.text("report.recommendations"), "RECOMMENDATIONS:");
+    }
+
+    #[test]
+    fn test_unknown_key_falls_back_to_key() {
+        assert_eq!(Catalog::english().text("no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn test_english_keys_are_unique() {
+        let mut keys: Vec<_> = ENGLISH.iter().map(|(k, _)| *k).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), ENGLISH.len());
+    }
+
+    #[test]
+    fn test_from_toml_str_flattens_nested_tables() {
+        let catalog = Catalog::from_toml_str(
+            r#"
+locale = "fr"
+
+[twiggy]
+symbol_dominates = "Le symbole '{name}' occupe {percent}% du bundle."
+
+[report]
+recommendations = "RECOMMANDATIONS :"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(catalog.locale(), "fr");
+        assert_eq!(catalog.text("report.recommendations"), "RECOMMANDATIONS :");
+        assert_eq!(
+            catalog.format(
+                "twiggy.symbol_dominates",
+                &[("name", &"core::fmt"), ("percent", &"25.0")]
+            ),
+            "Le symbole 'core::fmt' occupe 25.0% du bundle."
+        );
+        assert!(catalog.unknown_keys().is_empty());
+    }
+
+    #[test]
+    fn test_from_toml_str_accepts_dotted_keys() {
+        let catalog =
+            Catalog::from_toml_str("\"report.recommendations\" = \"EMPFEHLUNGEN:\"\n").unwrap();
+        assert_eq!(catalog.text("report.recommendations"), "EMPFEHLUNGEN:");
+    }
+
+    #[test]
+    fn test_from_toml_str_reports_unknown_keys() {
+        let catalog = Catalog::from_toml_str("[report]\nrecomendations = \"typo\"\n").unwrap();
+        assert_eq!(catalog.unknown_keys(), ["report.recomendations"]);
+    }
+
+    #[test]
+    fn test_from_toml_str_rejects_non_string_messages() {
+        let err = Catalog::from_toml_str("[report]\nrecommendations = 42\n").unwrap_err();
+        assert!(matches!(err, I18nError::NotAString(key) if key == "report.recommendations"));
+    }
+
+    #[test]
+    fn test_from_toml_str_rejects_invalid_toml() {
+        assert!(matches!(
+            Catalog::from_toml_str("[report\n"),
+            Err(I18nError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_leaves_missing_placeholders_intact() {
+        let text = Catalog::english().format("twiggy.symbol_dominates", &[("name", &"foo")]);
+        assert!(text.contains("'foo'"));
+        assert!(text.contains("{percent}"));
+    }
+
+    #[test]
+    fn test_load_with_missing_file_returns_io_error() {
+        let err = Catalog::load(Path::new("/nonexistent/de.toml")).unwrap_err();
+        assert!(matches!(err, I18nError::Io(..)));
+    }
+}
@@ -4,9 +4,9 @@
 //! enabling better testability and adherence to the Dependency Inversion Principle.
 
 use std::fs::{Metadata, ReadDir};
-use std::io;
-use std::path::Path;
-use std::process::{Command, ExitStatus, Output};
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Output, Stdio};
 
 /// Trait for abstracting filesystem operations.
 ///
@@ -29,8 +29,105 @@ pub trait FileSystem {
     /// Read the entire contents of a file into a string.
     fn read_to_string(&self, path: &Path) -> io::Result<String>;
 
+    /// Read the entire contents of a file as bytes.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.read_to_string(path).map(String::into_bytes)
+    }
+
+    /// Read a file into a string, replacing invalid UTF-8 sequences.
+    ///
+    /// The flag is `true` when the file was not valid UTF-8. Used by the
+    /// source scanners, which should not give up on a file because of a
+    /// stray binary fixture or Latin-1 comment.
+    fn read_to_string_lossy(&self, path: &Path) -> io::Result<(String, bool)> {
+        let bytes = self.read(path)?;
+        match String::from_utf8(bytes) {
+            Ok(text) => Ok((text, false)),
+            Err(e) => Ok((String::from_utf8_lossy(e.as_bytes()).into_owned(), true)),
+        }
+    }
+
     /// Write a slice of bytes to a file.
     fn write(&self, path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()>;
+
+    /// Remove a file.
+    ///
+    /// Unsupported unless overridden; callers treat removal as best effort.
+    fn remove_file(&self, _path: &Path) -> io::Result<()> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    /// List the files under `base` selected by `options`.
+    ///
+    /// Honors `.gitignore` (also outside a git checkout), skips hidden
+    /// entries and `target/` directories, and follows symlinks without
+    /// looping: a symlink back to an ancestor is returned as an error, like
+    /// any directory that cannot be read, and the walk carries on.
+    fn walk_files(&self, base: &Path, options: &WalkOptions) -> Vec<io::Result<PathBuf>> {
+        walk_files(base, options)
+    }
+}
+
+/// Which files [`FileSystem::walk_files`] returns
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WalkOptions {
+    /// Directories to walk (default: the base itself)
+    pub roots: Vec<PathBuf>,
+    /// Only files with this extension (e.g. `"rs"`)
+    pub extension: Option<String>,
+    /// Gitignore-style globs relative to the base; when set, only matching
+    /// files are returned, even if `.gitignore` excludes them
+    pub include: Vec<String>,
+    /// Gitignore-style globs relative to the base to skip
+    pub exclude: Vec<String>,
+}
+
+fn walk_files(base: &Path, options: &WalkOptions) -> Vec<io::Result<PathBuf>> {
+    let mut overrides = ignore::overrides::OverrideBuilder::new(base);
+    let globs = options
+        .include
+        .iter()
+        .cloned()
+        .chain(options.exclude.iter().map(|glob| format!("!{}", glob)));
+    for glob in globs {
+        if let Err(e) = overrides.add(&glob) {
+            return vec![Err(io::Error::new(io::ErrorKind::InvalidInput, e))];
+        }
+    }
+    let overrides = match overrides.build() {
+        Ok(overrides) => overrides,
+        Err(e) => return vec![Err(io::Error::new(io::ErrorKind::InvalidInput, e))],
+    };
+
+    let mut roots = options.roots.iter();
+    let mut walker = ignore::WalkBuilder::new(roots.next().map_or(base, PathBuf::as_path));
+    for root in roots {
+        walker.add(root);
+    }
+    walker
+        .follow_links(true)
+        .require_git(false)
+        .overrides(overrides)
+        .filter_entry(|entry| {
+            !(entry.file_name() == "target" && entry.file_type().is_some_and(|t| t.is_dir()))
+        });
+
+    let mut files = Vec::new();
+    for entry in walker.build() {
+        match entry {
+            Ok(entry) => {
+                let is_file = entry.file_type().is_some_and(|t| t.is_file());
+                let wanted = options.extension.as_deref().is_none_or(|ext| {
+                    entry.path().extension().and_then(|e| e.to_str()) == Some(ext)
+                });
+                if is_file && wanted {
+                    files.push(Ok(entry.into_path()));
+                }
+            }
+            Err(e) => files.push(Err(io::Error::other(e))),
+        }
+    }
+    files
 }
 
 /// Real filesystem implementation that delegates to std::fs.
@@ -58,9 +155,17 @@ impl FileSystem for RealFileSystem {
         std::fs::read_to_string(path)
     }
 
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
     fn write(&self, path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
         std::fs::write(path, contents)
     }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        std::fs::remove_file(path)
+    }
 }
 
 /// Trait for abstracting command execution.
@@ -103,6 +208,20 @@ pub trait CommandExecutor {
         self.output(&mut cmd)
     }
 
+    /// Execute a command and hand its stdout to `consume` as a reader.
+    ///
+    /// The exit status is returned alongside what `consume` produced; it is
+    /// only known once the output has been read. The default buffers the
+    /// whole output via [`CommandExecutor::output`]; the real executor
+    /// streams it from a pipe.
+    fn stream_stdout<T, F>(&self, cmd: &mut Command, consume: F) -> io::Result<(T, ExitStatus)>
+    where
+        F: FnOnce(&mut dyn BufRead) -> T,
+    {
+        let output = self.output(cmd)?;
+        Ok((consume(&mut output.stdout.as_slice()), output.status))
+    }
+
     /// Execute a command built with a closure and return its exit status.
     ///
     /// Similar to `execute()` but only returns the exit status without capturing output.
@@ -136,12 +255,40 @@ pub struct RealCommandExecutor;
 
 impl CommandExecutor for RealCommandExecutor {
     fn status(&self, cmd: &mut Command) -> io::Result<ExitStatus> {
+        let _span = spawn_span(cmd);
         cmd.status()
     }
 
     fn output(&self, cmd: &mut Command) -> io::Result<Output> {
+        let _span = spawn_span(cmd);
         cmd.output()
     }
+
+    fn stream_stdout<T, F>(&self, cmd: &mut Command, consume: F) -> io::Result<(T, ExitStatus)>
+    where
+        F: FnOnce(&mut dyn BufRead) -> T,
+    {
+        let _span = spawn_span(cmd);
+        let mut child = cmd.stdout(Stdio::piped()).spawn()?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| io::Error::other("child stdout was not captured"))?;
+        let value = consume(&mut io::BufReader::new(stdout));
+        // The reader is dropped by now, so a child still writing gets EPIPE
+        let status = child.wait()?;
+        Ok((value, status))
+    }
+}
+
+/// Self-profiling span for a spawned process, labelled by program name
+fn spawn_span(cmd: &Command) -> crate::profiling::ProfileSpan {
+    let program = Path::new(cmd.get_program())
+        .file_name()
+        .unwrap_or(cmd.get_program())
+        .to_string_lossy()
+        .into_owned();
+    crate::profiling::span(crate::profiling::ProfilePhase::Spawning, program)
 }
 
 /// Create an ExitStatus with the given exit code for use in test mocks.
@@ -183,6 +330,84 @@ mod tests {
         assert_eq!(read_content, "Hello, World!");
     }
 
+    #[test]
+    fn test_walk_files_honors_gitignore_globs_and_symlink_loops() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path();
+        let fs = RealFileSystem;
+        for file in [
+            "src/lib.rs",
+            "src/gen/out.rs",
+            "src/notes.md",
+            "vendor/dep.rs",
+            "target/debug/build.rs",
+            ".hidden/x.rs",
+        ] {
+            fs.create_dir_all(base.join(file).parent().unwrap())
+                .unwrap();
+            fs.write(&base.join(file), "").unwrap();
+        }
+        fs.write(&base.join(".gitignore"), "/vendor\n").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(base.join("src"), base.join("src/gen/loop")).unwrap();
+
+        let options = WalkOptions {
+            extension: Some("rs".to_string()),
+            exclude: vec!["src/gen/".to_string()],
+            ..Default::default()
+        };
+        let files: Vec<PathBuf> = fs
+            .walk_files(base, &options)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(files, vec![base.join("src/lib.rs")]);
+
+        let options = WalkOptions {
+            extension: Some("rs".to_string()),
+            ..Default::default()
+        };
+        let results = fs.walk_files(base, &options);
+        let mut files: Vec<PathBuf> = results.iter().flatten().cloned().collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![base.join("src/gen/out.rs"), base.join("src/lib.rs")]
+        );
+        #[cfg(unix)]
+        assert!(results.iter().any(|r| r.is_err()));
+    }
+
+    #[test]
+    fn test_read_to_string_lossy_flags_invalid_utf8() {
+        let temp_dir = TempDir::new().unwrap();
+        let fs = RealFileSystem;
+        let valid = temp_dir.path().join("valid.rs");
+        let latin1 = temp_dir.path().join("latin1.rs");
+        fs.write(&valid, "// café").unwrap();
+        fs.write(&latin1, b"// caf\xe9\nfn main() {}").unwrap();
+
+        assert_eq!(
+            fs.read_to_string_lossy(&valid).unwrap(),
+            ("// café".to_string(), false)
+        );
+        let (text, lossy) = fs.read_to_string_lossy(&latin1).unwrap();
+        assert!(lossy);
+        assert_eq!(text, "// caf\u{FFFD}\nfn main() {}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_real_executor_streams_stdout_and_reports_status_after() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "printf 'a\\nb\\n'; exit 3"]);
+        let (lines, status) = RealCommandExecutor
+            .stream_stdout(&mut cmd, |reader| reader.lines().count())
+            .unwrap();
+        assert_eq!(lines, 2);
+        assert_eq!(status.code(), Some(3));
+    }
+
     #[test]
     fn test_real_filesystem_copy() {
         let temp_dir = TempDir::new().unwrap();
@@ -26,6 +26,7 @@
 //!     }),
 //!     wasm_opt: None,
 //!     size_budget: None,
+//!     ..Default::default()
 //! };
 //!
 //! assert_eq!(config.template, "balanced");
@@ -43,6 +44,7 @@
 //!     target_size_kb: Some(300),
 //!     warn_threshold_kb: Some(400),
 //!     max_size_kb: Some(500),
+//!     preopt_factor: None,
 //! };
 //!
 //! // Validate budget constraints
@@ -54,6 +56,7 @@
 //!     target_size_kb: Some(600),
 //!     warn_threshold_kb: Some(400),
 //!     max_size_kb: Some(500),
+//!     preopt_factor: None,
 //! };
 //! assert!(bad_budget.validate().is_err());
 //! ```
@@ -98,19 +101,36 @@ pub mod cicd;
 pub mod cmd;
 /// Configuration file and template management
 pub mod config;
+/// Fetching WASM modules from URLs for analysis
+pub mod download;
 /// Enhanced error types with contextual suggestions
 pub mod error;
 /// Shared formatting utilities
 pub mod fmt;
 /// Git metadata utilities
 pub mod git;
+/// SHA-256 digests for cache keys and checksums
+pub mod hash;
+/// Localization of user-facing report strings
+pub mod i18n;
 /// Infrastructure traits for filesystem and command execution
 pub mod infra;
 /// Cargo.toml optimization and backup management
 pub mod optimizer;
 /// Build pipeline orchestration
 pub mod pipeline;
+/// Self-profiling of wasm-slim's own runtime
+pub mod profiling;
+/// Redaction of secrets in reports and logs
+pub mod redact;
+/// Location of the per-project state directory
+pub mod state;
+/// One-line exit summary for CI logs
+pub mod summary;
 /// Rust toolchain detection and management
 pub mod toolchain;
 /// Tool detection and version checking
 pub mod tools;
+
+#[cfg(test)]
+mod test_wasm;
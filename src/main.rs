@@ -1,6 +1,7 @@
 use clap::{Parser, Subcommand};
 use clap_complete::Shell;
 use std::process;
+use wasm_slim::analyzer::{ItemFilter, ItemLimit, SortOrder};
 use wasm_slim::cmd;
 
 /// WASM bundle size optimizer
@@ -15,10 +16,32 @@ pub struct Cli {
     #[arg(long, global = true)]
     no_emoji: bool,
 
+    /// Record where wasm-slim spends its own time and print a breakdown
+    #[arg(long, global = true)]
+    self_profile: bool,
+
+    /// Translation TOML file for report text (overrides [i18n] translations)
+    #[arg(long, global = true, value_name = "FILE")]
+    translations: Option<std::path::PathBuf>,
+
+    /// Write long reports directly instead of piping them through a pager
+    #[arg(long, global = true)]
+    no_pager: bool,
+
+    /// Config file to use instead of .wasm-slim.toml (overrides WASM_SLIM_CONFIG)
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<std::path::PathBuf>,
+
+    /// Directory for caches, backups and history instead of .wasm-slim (overrides WASM_SLIM_STATE_DIR)
+    #[arg(long, global = true, value_name = "DIR")]
+    state_dir: Option<std::path::PathBuf>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
+// Parsed once per run, so the size of the `Analyze` variant does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Build and optimize WASM binary
@@ -35,6 +58,14 @@ enum Commands {
         #[arg(long)]
         json: bool,
 
+        /// Continue from the stage that failed last time (if inputs are unchanged)
+        #[arg(long)]
+        resume: bool,
+
+        /// Build with DWARF and move it into a `.debug.wasm` sidecar referenced via `external_debug_info`
+        #[arg(long)]
+        split_debug: bool,
+
         /// Target directory for output
         #[arg(short, long)]
         target_dir: Option<String>,
@@ -42,15 +73,15 @@ enum Commands {
 
     /// Analyze WASM bundle or dependencies
     Analyze {
-        /// WASM file to analyze (optional, omit for dependency analysis)
+        /// WASM file, http(s) URL or wasm-pack package directory to analyze (optional, omit for dependency analysis)
         #[arg(value_name = "FILE")]
         file: Option<String>,
 
-        /// Analysis mode: assets, deps, top, dominators, dead
+        /// Analysis mode: assets, deps, unused-deps, no-std, features, bloat, fmt, panics, serde, llvm-lines, allocator, vendor-review, wasm-bindgen-abi, js-glue, pkg (default for a directory FILE), config-check, crates, sources, frameworks, duplicates, histogram, data, data-structures, startup, memory, strings, custom-sections, debug-info, reachability, top, dominators, dead, monos, all (top, dominators, dead, monos, assets and deps as one action plan), score (0-100 size health score with a per-category breakdown)
         #[arg(short, long, default_value = "deps")]
         mode: String,
 
-        /// Automatically apply optimization suggestions to Cargo.toml
+        /// Apply optimization suggestions to Cargo.toml (deps), remove unused dependencies and move build- or test-only ones (unused-deps), or strip custom sections (custom-sections) or leftover DWARF and names (debug-info) in place
         #[arg(long)]
         fix: bool,
 
@@ -62,9 +93,127 @@ enum Commands {
         #[arg(long)]
         guide: bool,
 
-        /// Output as JSON
-        #[arg(long)]
+        /// Output as JSON (same as --format json)
+        #[arg(long, conflicts_with = "format")]
         json: bool,
+
+        /// Analyze dependencies without network access (deps mode)
+        #[arg(long)]
+        offline: bool,
+
+        /// Output as CSV (vendor-review mode; same as --format csv)
+        #[arg(long, conflicts_with_all = ["json", "format"])]
+        csv: bool,
+
+        /// Show every item instead of truncating long lists
+        #[arg(long)]
+        all: bool,
+
+        /// Export the dominator tree as collapsed stacks for flamegraph tools ('-' for stdout)
+        #[arg(long, value_name = "FILE")]
+        folded: Option<std::path::PathBuf>,
+
+        /// Export the dependency graph with per-crate WASM sizes (measured in FILE, else estimated) as DOT, or JSON for a .json path ('-' for stdout)
+        #[arg(long, value_name = "OUT", conflicts_with_all = ["folded", "batch", "format", "output"])]
+        graph: Option<std::path::PathBuf>,
+
+        /// Analyze every WASM file listed in FILE (one per line) into one combined report
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["file", "folded", "csv"])]
+        batch: Option<std::path::PathBuf>,
+
+        /// Worker threads for --batch (default: number of CPUs)
+        #[arg(long, value_name = "N", requires = "batch")]
+        jobs: Option<usize>,
+
+        /// Write the --batch report as HTML
+        #[arg(long, value_name = "FILE", requires = "batch")]
+        html: Option<std::path::PathBuf>,
+
+        /// Render FILE as a self-contained report on stdout instead (html: per-crate treemap; same as --format html)
+        #[arg(long, value_name = "FORMAT", value_parser = ["html"], requires = "file", conflicts_with_all = ["json", "csv", "format", "folded", "batch"])]
+        output: Option<String>,
+
+        /// Save FILE's symbol sizes as a baseline JSON
+        #[arg(long, value_name = "PATH", requires = "file", conflicts_with_all = ["baseline", "format", "output", "batch"])]
+        save_baseline: Option<std::path::PathBuf>,
+
+        /// Flag symbols of FILE that grew past [symbol_budget] since this baseline
+        #[arg(long, value_name = "PATH", requires = "file", conflicts_with_all = ["format", "output", "batch"])]
+        baseline: Option<std::path::PathBuf>,
+
+        /// Exports your JavaScript calls, comma-separated or a file with one per line (reachability mode)
+        #[arg(
+            long,
+            value_name = "NAMES|FILE",
+            value_delimiter = ',',
+            requires = "file"
+        )]
+        live_exports: Option<Vec<String>>,
+
+        /// Measure each flagged feature by rebuilding without it (features mode), or run wasm-snip on a scratch copy to measure the savings (dead mode with --snip)
+        #[arg(long, conflicts_with = "batch")]
+        measure: bool,
+
+        /// Copy critical and high priority assets to public/assets and write a patch replacing their include_bytes!/include_str! sites (assets mode)
+        #[arg(long, conflicts_with_all = ["json", "format", "batch"])]
+        apply: bool,
+
+        /// Convert TTF/OTF fonts to WOFF2, PNG/JPEG to WebP and minify JSON next to the originals, using woff2_compress and cwebp when installed (assets mode)
+        #[arg(long, conflicts_with_all = ["json", "format", "batch"])]
+        compress: bool,
+
+        /// List every unwrap/expect/panic!/indexing/overflow site with its line, grouped by file (panics mode)
+        #[arg(long, conflicts_with_all = ["json", "format", "batch"])]
+        detailed: bool,
+
+        /// Write a patch turning unwrap() into ? or unwrap_or_default() where the surrounding code allows (panics mode)
+        #[arg(long, conflicts_with_all = ["json", "format", "batch"])]
+        patch: bool,
+
+        /// Build with wee_alloc (measured only, never applied), lol_alloc, talc and dlmalloc to measure their size, and benchmark allocation speed under wasmtime when installed (allocator mode)
+        #[arg(long, conflicts_with = "batch")]
+        compare: bool,
+
+        /// Print this format instead of the console report: json (every mode), csv (vendor-review), sarif (deps, panics and data, for code scanning upload), markdown (bloat, top, dominators, dead and monos, for a PR comment), html (per-crate treemap of FILE, or the --batch report)
+        #[arg(long, value_name = "FORMAT", conflicts_with = "folded")]
+        format: Option<cmd::OutputFormat>,
+
+        /// Recompute the analysis instead of reusing results cached in .wasm-slim/cache (top, dominators, dead, monos, all and score modes)
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Print wasm-snip patterns and a build step covering the unreachable functions (dead mode)
+        #[arg(long, conflicts_with_all = ["format", "batch"])]
+        snip: bool,
+
+        /// Exit with an error when the size health score is below this (score mode)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(0..=100))]
+        min_score: Option<u8>,
+
+        /// Keep only the N largest items while analyzing, bounding memory on very large modules (top, dominators, dead and monos modes)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), conflicts_with = "top")]
+        limit: Option<u32>,
+
+        /// Keep only the first N items of each result list, console and JSON alike
+        #[arg(
+            long,
+            visible_alias = "max-items",
+            value_name = "N",
+            conflicts_with = "all"
+        )]
+        top: Option<usize>,
+
+        /// Sort result lists by size, percent or name instead of the analysis order
+        #[arg(long, value_name = "ORDER")]
+        sort: Option<SortOrder>,
+
+        /// Drop items smaller than this many KiB from result lists (items without a size are kept)
+        #[arg(long, value_name = "KB")]
+        min_size: Option<f64>,
+
+        /// Keep only items and recommendations about symbols matching this regex, e.g. `^my_crate::`
+        #[arg(long, value_name = "REGEX")]
+        filter: Option<regex::Regex>,
     },
 
     /// Initialize wasm-slim configuration
@@ -74,13 +223,135 @@ enum Commands {
         template: String,
     },
 
-    /// Compare two WASM builds
+    /// Compare two or more WASM builds
     Compare {
-        /// Before file
-        before: String,
+        /// WASM builds (paths or http(s) URLs), oldest first; three or more print a table of deltas
+        #[arg(value_name = "FILE", num_args = 2.., required = true)]
+        files: Vec<String>,
+
+        /// Maximum number of symbol changes to show
+        #[arg(long, value_name = "N", conflicts_with = "all")]
+        max_items: Option<usize>,
+
+        /// Show every symbol change instead of truncating the list
+        #[arg(long)]
+        all: bool,
+
+        /// Export a differential collapsed-stack listing instead of the report ('-' for stdout)
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["max_items", "all"])]
+        folded: Option<std::path::PathBuf>,
+
+        /// Git ref the before file was built from; attributes symbol deltas to commits since it
+        #[arg(long, value_name = "REF", conflicts_with = "folded")]
+        against: Option<String>,
+
+        /// Print a Markdown summary for a pull request comment (same as --format markdown)
+        #[arg(long, conflicts_with = "folded")]
+        pr_comment: bool,
+
+        /// Print this format instead of the report: json (added, removed, grown and shrunk symbols), markdown (a PR comment)
+        #[arg(long, value_name = "FORMAT", conflicts_with_all = ["folded", "pr_comment"])]
+        format: Option<cmd::OutputFormat>,
+
+        /// Print the comparison as JSON, with added, removed, grown and shrunk symbols (same as --format json)
+        #[arg(long, conflicts_with_all = ["folded", "pr_comment", "format", "markdown", "against"])]
+        json: bool,
+
+        /// Print the comparison as Markdown for a bot to post (same as --format markdown)
+        #[arg(long, conflicts_with_all = ["folded", "pr_comment", "format"])]
+        markdown: bool,
+    },
+
+    /// Build every template and wasm-bindgen target combination and compare them
+    Matrix {
+        /// Templates to build (rows)
+        #[arg(long, value_delimiter = ',', default_value = "balanced,aggressive")]
+        templates: Vec<String>,
+
+        /// wasm-bindgen targets to build (columns)
+        #[arg(long, value_delimiter = ',', default_value = "web")]
+        targets: Vec<String>,
+
+        /// Combination shipped to production; fails if another one is smaller by more than --margin
+        #[arg(long, value_name = "TEMPLATE:TARGET")]
+        production: Option<String>,
+
+        /// Percentage another combination may beat the production one by
+        #[arg(
+            long,
+            value_name = "PERCENT",
+            default_value_t = 0.0,
+            requires = "production"
+        )]
+        margin: f64,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Remove wasm-slim caches, stale backups and scratch builds
+    Clean {
+        /// List what would be removed and the space reclaimed, without removing anything
+        #[arg(short, long)]
+        dry_run: bool,
+
+        /// Also remove cargo's target/wasm32-* build directories
+        #[arg(long)]
+        targets: bool,
+
+        /// Newest Cargo.toml backups to keep per file (0 removes all)
+        #[arg(long, value_name = "N", default_value_t = cmd::clean::DEFAULT_KEEP_BACKUPS)]
+        keep_backups: usize,
+    },
+
+    /// Manage the build history in .wasm-slim/history.json
+    History {
+        #[command(subcommand)]
+        command: HistoryCommands,
+    },
+
+    /// Manage archived size reports
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+
+    /// Inspect the wasm-slim configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Manage the database of measured crate sizes
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+
+    /// Measure the real size impact of a recommendation
+    VerifyRecommendation {
+        /// Recommendation ID (e.g., default-features:regex, feature:tokio/fs, profile:opt-level=z)
+        id: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Walk through optimizing a bundled example project, step by step
+    Tutor {
+        /// Example project to optimize
+        #[arg(long, default_value = "basic-usage", value_parser = ["basic-usage", "with-assets", "ci-integration"])]
+        example: String,
+
+        /// Keep the scratch copy of the example when done
+        #[arg(long)]
+        keep: bool,
 
-        /// After file
-        after: String,
+        /// Run every step without waiting for Enter
+        #[arg(long)]
+        no_pause: bool,
     },
 
     /// Generate shell completions
@@ -91,10 +362,51 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Validate .wasm-slim.toml and check it against the team policy
+    Validate,
+}
+
+#[derive(Subcommand)]
+enum DbCommands {
+    /// Download the curated crate size database into .wasm-slim/size-db.json
+    Update {
+        /// Fetch the database from this URL instead of the published one
+        #[arg(long)]
+        url: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommands {
+    /// Roll up builds older than the [history] keep-days into one record per day
+    Compact {
+        /// Show what would be rolled up without rewriting the history
+        #[arg(short, long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommands {
+    /// Bundle the size report, manifest and config into a timestamped .tar.zst
+    Archive {
+        /// Shipped WASM file to report on
+        #[arg(value_name = "FILE")]
+        wasm: String,
+
+        /// Number of archives to keep in .wasm-slim/reports (0 keeps all)
+        #[arg(long, value_name = "N", default_value_t = wasm_slim::cicd::archive::DEFAULT_KEEP)]
+        keep: usize,
+    },
+}
+
 fn main() {
     // Initialize logger (use RUST_LOG env var to control verbosity)
     env_logger::init();
 
+    let started = std::time::Instant::now();
     let cli = Cli::parse();
 
     // Set console emoji mode based on CLI flag
@@ -102,23 +414,189 @@ fn main() {
         std::env::set_var("NO_EMOJI", "1");
     }
 
+    if wants_pager(&cli) {
+        if let Some((program, args)) =
+            cmd::pager::resolve_pager(std::env::var("PAGER").ok().as_deref())
+        {
+            // Fall back to direct output if the pager cannot be started
+            if let Ok(code) = cmd::pager::run_through_pager(&program, &args) {
+                process::exit(code);
+            }
+        }
+    }
+
+    if let Some(path) = &cli.config {
+        // Commands resolve the config against the project root, not the cwd
+        let path = std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.clone());
+        wasm_slim::config::ConfigLoader::install_override(path);
+    }
+    if let Some(path) = &cli.state_dir {
+        let path = std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.clone());
+        wasm_slim::state::install_override(path);
+    }
+
+    install_translations(cli.translations.as_deref());
+    install_redaction();
+
+    let profiler = cli.self_profile.then(wasm_slim::profiling::enable);
+
     let result = match &cli.command {
         Some(Commands::Build {
             dry_run,
             check,
             json,
+            resume,
+            split_debug,
             target_dir,
-        }) => cmd::cmd_build(*dry_run, *check, *json, target_dir.as_deref()),
-        Some(Commands::Analyze {
-            file,
-            mode,
-            fix,
-            dry_run,
-            guide,
-            json,
-        }) => cmd::cmd_analyze(file, mode, *fix, *dry_run, *guide, *json),
+        }) => cmd::cmd_build(
+            *dry_run,
+            *check,
+            *json,
+            *resume,
+            *split_debug,
+            target_dir.as_deref(),
+        ),
+        Some(
+            command @ Commands::Analyze {
+                file,
+                mode,
+                fix,
+                dry_run,
+                guide,
+                offline,
+                all,
+                folded,
+                batch,
+                jobs,
+                html,
+                save_baseline,
+                baseline,
+                live_exports,
+                measure,
+                apply,
+                compress,
+                detailed,
+                patch,
+                compare,
+                no_cache,
+                snip,
+                min_score,
+                graph,
+                limit,
+                top,
+                sort,
+                min_size,
+                filter,
+                ..
+            },
+        ) => {
+            let options = cmd::AnalyzeOptions {
+                fix: *fix,
+                dry_run: *dry_run,
+                guide: *guide,
+                offline: *offline,
+                limit: ItemLimit::from_flags(*top, *all),
+                folded: folded.clone(),
+                batch: batch.clone(),
+                jobs: *jobs,
+                html: html.clone(),
+                save_baseline: save_baseline.clone(),
+                baseline: baseline.clone(),
+                live_exports: live_exports.clone(),
+                measure: *measure,
+                apply: *apply,
+                compress: *compress,
+                detailed: *detailed,
+                patch: *patch,
+                compare: *compare,
+                rules: Default::default(),
+                format: command.output_format(),
+                no_cache: *no_cache,
+                snip: *snip,
+                min_score: *min_score,
+                graph: graph.clone(),
+                filter: ItemFilter {
+                    collect_limit: limit.map(|n| n as usize),
+                    top: *top,
+                    sort: *sort,
+                    min_size_bytes: min_size.map_or(0, |kb| (kb * 1024.0).round() as u64),
+                    pattern: filter.clone(),
+                },
+            };
+            cmd::cmd_analyze_with_options(file, mode, &options)
+        }
         Some(Commands::Init { template }) => cmd::cmd_init(template),
-        Some(Commands::Compare { before, after }) => cmd::cmd_compare(before, after),
+        Some(
+            command @ Commands::Compare {
+                files,
+                max_items,
+                all,
+                folded,
+                against,
+                ..
+            },
+        ) => {
+            let options = cmd::CompareOptions {
+                limit: ItemLimit::from_flags(*max_items, *all),
+                against: against.clone(),
+                format: command.output_format(),
+            };
+            match (files.as_slice(), folded) {
+                ([before, after], Some(output)) => cmd::cmd_compare_folded(before, after, output),
+                ([before, after], None) => cmd::cmd_compare_with_options(before, after, &options),
+                (_, _) => cmd::cmd_compare_many(files, folded.as_deref(), &options),
+            }
+        }
+        Some(Commands::Matrix {
+            templates,
+            targets,
+            production,
+            margin,
+            json,
+        }) => cmd::cmd_matrix(&cmd::MatrixOptions {
+            templates: templates.clone(),
+            targets: targets.clone(),
+            production: production.clone(),
+            margin_percent: *margin,
+            json: *json,
+        }),
+        Some(Commands::Clean {
+            dry_run,
+            targets,
+            keep_backups,
+        }) => cmd::cmd_clean(&cmd::CleanOptions {
+            dry_run: *dry_run,
+            targets: *targets,
+            keep_backups: *keep_backups,
+        }),
+        Some(Commands::History {
+            command: HistoryCommands::Compact { dry_run },
+        }) => cmd::cmd_history_compact(*dry_run),
+        Some(Commands::Report {
+            command: ReportCommands::Archive { wasm, keep },
+        }) => cmd::cmd_report_archive(wasm, *keep),
+        Some(Commands::Config {
+            command: ConfigCommands::Validate,
+        }) => cmd::cmd_config_validate(),
+        Some(Commands::Db {
+            command: DbCommands::Update { url },
+        }) => cmd::cmd_db_update(url.as_deref()),
+        Some(Commands::VerifyRecommendation { id, json }) => {
+            cmd::cmd_verify_recommendation(id, *json)
+        }
+        Some(Commands::Tutor {
+            example,
+            keep,
+            no_pause,
+        }) => cmd::cmd_tutor(&cmd::TutorOptions {
+            example: example.clone(),
+            keep: *keep,
+            pause: !*no_pause,
+        }),
         Some(Commands::Completions { shell }) => {
             cmd::cmd_completions(*shell);
             Ok(())
@@ -132,27 +610,262 @@ fn main() {
             println!("  build    Build and optimize WASM binary");
             println!("  analyze  Analyze WASM bundle size");
             println!("  init     Initialize wasm-slim configuration");
-            println!("  compare  Compare two WASM builds");
+            println!("  compare  Compare two or more WASM builds");
+            println!("  matrix   Compare builds across templates and targets");
+            println!("  config   Validate configuration against the team policy");
+            println!("  clean    Remove caches, stale backups and scratch builds");
+            println!("  history  Compact the build history");
+            println!("  report   Archive size reports");
+            println!("  db       Update the crate size database");
+            println!("  verify-recommendation  Measure the real impact of a recommendation");
+            println!("  tutor    Walk through optimizing a bundled example project");
             println!("\nRun 'wasm-slim <COMMAND> --help' for more information on a command.");
             Ok(())
         }
     };
 
-    if let Err(e) = result {
-        use wasm_slim::error::ErrorFormatter;
-        eprintln!("{}", ErrorFormatter::format(&e));
-        let exit_code = ErrorFormatter::exit_code(&e);
+    // Printed to stderr so JSON output on stdout stays machine-readable
+    if let Some(profiler) = profiler {
+        eprintln!("\n{}", profiler.render());
+    }
+
+    let exit_code = match &result {
+        Ok(()) => 0,
+        Err(e) => {
+            use wasm_slim::error::ErrorFormatter;
+            eprintln!("{}", ErrorFormatter::format(e));
+            ErrorFormatter::exit_code(e)
+        }
+    };
+
+    // Always the last line, so CI logs can be grepped for it
+    if let Some(command) = command_name(&cli) {
+        eprintln!(
+            "{}",
+            wasm_slim::summary::ExitSummary::new(command, result.is_ok())
+                .with_fields(wasm_slim::summary::take())
+                .with_elapsed(started.elapsed())
+                .render()
+        );
+    }
+
+    if result.is_err() {
         process::exit(exit_code);
     }
 }
 
+/// Load report translations from the CLI flag or `[i18n]` config
+///
+/// Problems are reported as warnings; reports fall back to English.
+fn install_translations(cli_path: Option<&std::path::Path>) {
+    use wasm_slim::i18n::{self, Catalog};
+
+    let path = match cli_path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let Ok(project_root) = std::env::current_dir() else {
+                return;
+            };
+            let configured = wasm_slim::config::ConfigLoader::load(&project_root)
+                .ok()
+                .and_then(|config| config.i18n)
+                .and_then(|i18n| i18n.translations);
+            match configured {
+                Some(path) => project_root.join(path),
+                None => return,
+            }
+        }
+    };
+
+    match Catalog::load(&path) {
+        Ok(catalog) => {
+            for key in catalog.unknown_keys() {
+                eprintln!(
+                    "{} Unknown translation key '{}' in {}",
+                    console::style("⚠️").yellow(),
+                    key,
+                    path.display()
+                );
+            }
+            i18n::install(catalog);
+        }
+        Err(e) => eprintln!("{} {}", console::style("⚠️").yellow(), e),
+    }
+}
+
+/// Install the redactor with the `[redaction]` config patterns
+///
+/// Without a config the built-in token patterns still apply.
+fn install_redaction() {
+    let settings = std::env::current_dir()
+        .ok()
+        .and_then(|root| wasm_slim::config::ConfigLoader::load(&root).ok())
+        .and_then(|config| config.redaction);
+    match wasm_slim::redact::Redactor::from_settings(settings.as_ref()) {
+        Ok(redactor) => {
+            wasm_slim::redact::install(redactor);
+        }
+        Err(e) => eprintln!("{} {:#}", console::style("⚠️").yellow(), e),
+    }
+}
+
+/// Name of the subcommand shown in the exit summary line
+fn command_name(cli: &Cli) -> Option<&'static str> {
+    let name = match cli.command.as_ref()? {
+        Commands::Build { .. } => "build",
+        Commands::Analyze { .. } => "analyze",
+        Commands::Init { .. } => "init",
+        Commands::Compare { .. } => "compare",
+        Commands::Matrix { .. } => "matrix",
+        Commands::Clean { .. } => "clean",
+        Commands::History { .. } => "history",
+        Commands::Report { .. } => "report",
+        Commands::Config { .. } => "config",
+        Commands::Db { .. } => "db",
+        Commands::VerifyRecommendation { .. } => "verify-recommendation",
+        Commands::Tutor { .. } => "tutor",
+        Commands::Completions { .. } => "completions",
+    };
+    Some(name)
+}
+
+/// Report commands page their console output on interactive terminals
+fn wants_pager(cli: &Cli) -> bool {
+    let disabled = cli.no_pager
+        || match &cli.command {
+            Some(
+                command @ Commands::Analyze {
+                    folded,
+                    save_baseline,
+                    ..
+                },
+            ) => folded.is_some() || save_baseline.is_some() || command.output_format().is_some(),
+            Some(command @ Commands::Compare { folded, .. }) => {
+                folded.is_some() || command.output_format().is_some()
+            }
+            _ => true,
+        };
+    cmd::pager::should_page(
+        disabled,
+        std::env::var_os(cmd::pager::NO_PAGER_ENV).is_some(),
+        console::Term::stdout().is_term(),
+    )
+}
+
+impl Commands {
+    /// The `--format` value, or the format a shorthand flag such as `--json` stands for
+    fn output_format(&self) -> Option<cmd::OutputFormat> {
+        match self {
+            Commands::Analyze {
+                format,
+                json,
+                csv,
+                output,
+                ..
+            } => format
+                .or(json.then_some(cmd::OutputFormat::Json))
+                .or(csv.then_some(cmd::OutputFormat::Csv))
+                .or(output.as_ref().map(|_| cmd::OutputFormat::Html)),
+            Commands::Compare {
+                format,
+                json,
+                markdown,
+                pr_comment,
+                ..
+            } => format
+                .or(json.then_some(cmd::OutputFormat::Json))
+                .or((*markdown || *pr_comment).then_some(cmd::OutputFormat::Markdown)),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn output_format(args: &[&str]) -> Option<cmd::OutputFormat> {
+        let cli =
+            Cli::try_parse_from(std::iter::once("wasm-slim").chain(args.iter().copied())).unwrap();
+        cli.command.unwrap().output_format()
+    }
+
     #[test]
     fn verify_cli() {
         use clap::CommandFactory;
         Cli::command().debug_assert()
     }
+
+    #[test]
+    fn test_analyze_output_html_maps_onto_format_html() {
+        assert_eq!(
+            output_format(&["analyze", "app.wasm", "--output", "html"]),
+            Some(cmd::OutputFormat::Html)
+        );
+        assert_eq!(
+            output_format(&["analyze", "app.wasm", "--format", "html"]),
+            Some(cmd::OutputFormat::Html)
+        );
+        assert!(
+            Cli::try_parse_from(["wasm-slim", "analyze", "app.wasm", "--output", "csv"]).is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "wasm-slim",
+            "analyze",
+            "app.wasm",
+            "--output",
+            "html",
+            "--format",
+            "html"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_analyze_format_shorthands_map_onto_format() {
+        assert_eq!(
+            output_format(&["analyze", "--mode", "vendor-review", "--csv"]),
+            Some(cmd::OutputFormat::Csv)
+        );
+        assert_eq!(
+            output_format(&["analyze", "--json"]),
+            Some(cmd::OutputFormat::Json)
+        );
+        assert_eq!(
+            output_format(&["analyze", "--batch", "list.txt", "--html", "out.html"]),
+            None
+        );
+        assert!(
+            Cli::try_parse_from(["wasm-slim", "analyze", "--csv", "--format", "json"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["wasm-slim", "analyze", "--html", "out.html"]).is_err());
+    }
+
+    #[test]
+    fn test_compare_format_shorthands_map_onto_format() {
+        let compare = ["compare", "a.wasm", "b.wasm"];
+        for (flag, expected) in [
+            ("--json", cmd::OutputFormat::Json),
+            ("--markdown", cmd::OutputFormat::Markdown),
+            ("--pr-comment", cmd::OutputFormat::Markdown),
+        ] {
+            let args: Vec<&str> = compare.iter().copied().chain([flag]).collect();
+            assert_eq!(output_format(&args), Some(expected), "{}", flag);
+        }
+        assert_eq!(
+            output_format(&["compare", "a.wasm", "b.wasm", "--format", "json"]),
+            Some(cmd::OutputFormat::Json)
+        );
+        assert_eq!(output_format(&compare), None);
+        assert!(Cli::try_parse_from([
+            "wasm-slim",
+            "compare",
+            "a",
+            "b",
+            "--json",
+            "--format",
+            "json"
+        ])
+        .is_err());
+    }
 }
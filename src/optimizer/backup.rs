@@ -103,7 +103,7 @@ impl<FS: FileSystem> BackupManager<FS> {
     /// Create a new BackupManager with a custom filesystem implementation
     pub fn with_fs(project_root: &Path, fs: FS) -> Self {
         Self {
-            backup_dir: project_root.join(".wasm-slim").join("backups"),
+            backup_dir: crate::state::state_dir(project_root).join("backups"),
             fs,
         }
     }
@@ -156,6 +156,131 @@ impl<FS: FileSystem> CargoTomlEditor<FS> {
         Ok(changes)
     }
 
+    /// Remove dependencies from a Cargo.toml file
+    ///
+    /// Each entry is the dependency's key and, for dependencies declared under
+    /// `[target.<platform>.dependencies]`, the platform. Keys that are not
+    /// found are skipped.
+    ///
+    /// # Returns
+    ///
+    /// A vector of strings describing the removals
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or written, or the TOML
+    /// content is malformed
+    pub fn remove_dependencies(
+        &self,
+        cargo_toml_path: &Path,
+        dependencies: &[(String, Option<String>)],
+        dry_run: bool,
+    ) -> Result<Vec<String>, TomlEditError> {
+        let content = self.fs.read_to_string(cargo_toml_path)?;
+        let mut doc = content.parse::<DocumentMut>()?;
+
+        let mut changes = Vec::new();
+        for (name, platform) in dependencies {
+            let table = match platform {
+                Some(platform) => doc
+                    .get_mut("target")
+                    .and_then(|t| t.get_mut(platform.as_str()))
+                    .and_then(|t| t.get_mut("dependencies")),
+                None => doc.get_mut("dependencies"),
+            };
+            let removed = table
+                .and_then(|t| t.as_table_like_mut())
+                .and_then(|t| t.remove(name))
+                .is_some();
+            if removed {
+                changes.push(match platform {
+                    Some(platform) => {
+                        format!("Removed {} from [target.'{}'.dependencies]", name, platform)
+                    }
+                    None => format!("Removed {} from [dependencies]", name),
+                });
+            }
+        }
+
+        if !dry_run && !changes.is_empty() {
+            self.fs.write(cargo_toml_path, doc.to_string())?;
+        }
+
+        Ok(changes)
+    }
+
+    /// Move dependencies out of `[dependencies]` into another table
+    ///
+    /// Each entry is the dependency's key, the platform for dependencies
+    /// declared under `[target.<platform>.dependencies]`, and the destination
+    /// table (e.g. `build-dependencies`), created under the same platform if
+    /// missing. The declaration is moved as written; an existing entry in the
+    /// destination is kept. Keys that are not found are skipped.
+    ///
+    /// # Returns
+    ///
+    /// A vector of strings describing the moves
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or written, or the TOML
+    /// content is malformed
+    pub fn move_dependencies(
+        &self,
+        cargo_toml_path: &Path,
+        moves: &[(String, Option<String>, String)],
+        dry_run: bool,
+    ) -> Result<Vec<String>, TomlEditError> {
+        let content = self.fs.read_to_string(cargo_toml_path)?;
+        let mut doc = content.parse::<DocumentMut>()?;
+
+        let mut changes = Vec::new();
+        for (name, platform, destination) in moves {
+            let parent = match platform {
+                Some(platform) => {
+                    let Some(parent) = doc
+                        .get_mut("target")
+                        .and_then(|t| t.get_mut(platform.as_str()))
+                        .and_then(|t| t.as_table_like_mut())
+                    else {
+                        continue;
+                    };
+                    parent
+                }
+                None => doc.as_table_mut() as &mut dyn toml_edit::TableLike,
+            };
+            let Some(item) = parent
+                .get_mut("dependencies")
+                .and_then(|t| t.as_table_like_mut())
+                .and_then(|t| t.remove(name))
+            else {
+                continue;
+            };
+            let target = parent
+                .entry(destination)
+                .or_insert(Item::Table(Table::new()))
+                .as_table_like_mut()
+                .ok_or_else(|| {
+                    TomlEditError::InvalidStructure(format!("{} is not a table", destination))
+                })?;
+            if !target.contains_key(name) {
+                target.insert(name, item);
+            }
+            changes.push(match platform {
+                Some(platform) => {
+                    format!("Moved {} to [target.'{}'.{}]", name, platform, destination)
+                }
+                None => format!("Moved {} to [{}]", name, destination),
+            });
+        }
+
+        if !dry_run && !changes.is_empty() {
+            self.fs.write(cargo_toml_path, doc.to_string())?;
+        }
+
+        Ok(changes)
+    }
+
     /// Apply [profile.release] optimizations
     fn apply_profile_optimizations(
         &self,
@@ -338,6 +463,92 @@ mod tests {
         assert!(changes.iter().any(|c| c.contains("opt-level")));
     }
 
+    #[test]
+    fn test_remove_dependencies_edits_plain_and_target_tables() {
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+        std::fs::write(
+            &cargo_toml,
+            "[package]\nname = \"test\"\n\n[dependencies]\nlog = \"0.4\" # logging\nregex = \"1\"\n\n[target.'cfg(target_arch = \"wasm32\")'.dependencies]\njs-sys = \"0.3\"\n",
+        )
+        .unwrap();
+
+        let editor = CargoTomlEditor::new();
+        let changes = editor
+            .remove_dependencies(
+                &cargo_toml,
+                &[
+                    ("regex".to_string(), None),
+                    (
+                        "js-sys".to_string(),
+                        Some("cfg(target_arch = \"wasm32\")".to_string()),
+                    ),
+                    ("missing".to_string(), None),
+                ],
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(changes.len(), 2);
+        let content = std::fs::read_to_string(&cargo_toml).unwrap();
+        assert!(content.contains("log = \"0.4\" # logging"));
+        assert!(!content.contains("regex"));
+        assert!(!content.contains("js-sys"));
+    }
+
+    #[test]
+    fn test_move_dependencies_creates_destination_tables() {
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_toml = temp_dir.path().join("Cargo.toml");
+        std::fs::write(
+            &cargo_toml,
+            "[package]\nname = \"test\"\n\n[dependencies]\nlog = \"0.4\"\ncc = { version = \"1\", features = [\"parallel\"] }\n\n[dev-dependencies]\ntempfile = \"3\"\n\n[target.'cfg(unix)'.dependencies]\nvergen = \"8\"\n",
+        )
+        .unwrap();
+
+        let editor = CargoTomlEditor::new();
+        let changes = editor
+            .move_dependencies(
+                &cargo_toml,
+                &[
+                    ("cc".to_string(), None, "build-dependencies".to_string()),
+                    ("tempfile".to_string(), None, "dev-dependencies".to_string()),
+                    (
+                        "vergen".to_string(),
+                        Some("cfg(unix)".to_string()),
+                        "build-dependencies".to_string(),
+                    ),
+                ],
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(
+            changes,
+            [
+                "Moved cc to [build-dependencies]",
+                "Moved vergen to [target.'cfg(unix)'.build-dependencies]"
+            ]
+        );
+        let doc = std::fs::read_to_string(&cargo_toml)
+            .unwrap()
+            .parse::<DocumentMut>()
+            .unwrap();
+        assert!(doc["dependencies"].get("cc").is_none());
+        assert_eq!(
+            doc["build-dependencies"]["cc"]["features"][0].as_str(),
+            Some("parallel")
+        );
+        assert_eq!(doc["dev-dependencies"]["tempfile"].as_str(), Some("3"));
+        assert!(doc["target"]["cfg(unix)"]["dependencies"]
+            .get("vergen")
+            .is_none());
+        assert_eq!(
+            doc["target"]["cfg(unix)"]["build-dependencies"]["vergen"].as_str(),
+            Some("8")
+        );
+    }
+
     #[test]
     fn test_dry_run_does_not_modify_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -729,6 +940,9 @@ wasm-opt = ["-O3"]
         let config = OptimizationConfig::default();
         let wasm_config = WasmOptConfig {
             flags: vec!["-Oz".to_string()],
+            threads: None,
+            low_memory_unused: false,
+            converge: false,
         };
         let result = editor.optimize_cargo_toml(&cargo_toml, &config, Some(&wasm_config), false);
 
@@ -830,6 +1044,9 @@ wasm-opt = ["-O"]
         let config = OptimizationConfig::default();
         let wasm_config = WasmOptConfig {
             flags: vec!["-Oz".to_string(), "--strip-debug".to_string()],
+            threads: None,
+            low_memory_unused: false,
+            converge: false,
         };
         let result = editor.optimize_cargo_toml(&cargo_toml, &config, Some(&wasm_config), false);
 
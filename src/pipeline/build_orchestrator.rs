@@ -3,17 +3,24 @@
 //! Coordinates the build workflow across multiple tools
 
 use console::style;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::fmt::{format_bytes, CHECKMARK, HAMMER, SPARKLES};
 use crate::infra::{CommandExecutor, FileSystem};
+use crate::summary;
 use crate::tools::ToolChain;
 
+use super::checkpoint::{BuildCheckpoint, Stage, StageRecord};
 use super::config::PipelineConfig;
 use super::error::PipelineError;
 use super::metrics::SizeMetrics;
 use super::result_formatter::ResultFormatter;
+use super::telemetry::{BuildEvent, FanOutCollector, MetricsCollector};
 use super::tool_runner::ToolRunner;
+use super::transform::{apply_transforms, WasmTransform};
 
 /// Orchestrates the complete build workflow
 pub struct BuildOrchestrator<FS: FileSystem, CE: CommandExecutor> {
@@ -22,6 +29,8 @@ pub struct BuildOrchestrator<FS: FileSystem, CE: CommandExecutor> {
     toolchain: ToolChain<CE>,
     tool_runner: ToolRunner<FS, CE>,
     fs: FS,
+    collectors: FanOutCollector,
+    transforms: Vec<Arc<dyn WasmTransform>>,
 }
 
 impl<FS: FileSystem + Clone, CE: CommandExecutor + Clone> BuildOrchestrator<FS, CE> {
@@ -45,11 +54,170 @@ impl<FS: FileSystem + Clone, CE: CommandExecutor + Clone> BuildOrchestrator<FS,
             toolchain,
             tool_runner,
             fs,
+            collectors: FanOutCollector::new(),
+            transforms: Vec::new(),
         }
     }
 
+    /// Register a metrics collector that receives pipeline events
+    ///
+    /// Any number of collectors can be registered; every event and metric is
+    /// forwarded to each of them in registration order.
+    pub fn add_collector(&mut self, collector: Arc<dyn MetricsCollector>) {
+        self.collectors.register(collector);
+    }
+
+    /// Register a transform run after wasm-opt and wasm-snip
+    ///
+    /// Transforms run in registration order.
+    pub fn add_transform(&mut self, transform: Arc<dyn WasmTransform>) {
+        self.transforms.push(transform);
+    }
+
     /// Execute the complete build pipeline
+    ///
+    /// Emits `BuildStarted` before the first stage and either `BuildCompleted`
+    /// or `BuildFailed` at the end, then flushes all registered collectors.
     pub fn execute(&self) -> Result<SizeMetrics, PipelineError> {
+        self.collectors.record_event(
+            BuildEvent::BuildStarted,
+            HashMap::from([
+                (
+                    "target".to_string(),
+                    self.config.target.as_str().to_string(),
+                ),
+                ("profile".to_string(), self.config.profile.clone()),
+            ]),
+        );
+
+        let result = self.run_stages();
+
+        match &result {
+            Ok(metrics) => {
+                self.collectors.record_size("final", metrics.after_bytes);
+                self.collectors.record_event(
+                    BuildEvent::BuildCompleted,
+                    HashMap::from([
+                        ("before_bytes".to_string(), metrics.before_bytes.to_string()),
+                        ("after_bytes".to_string(), metrics.after_bytes.to_string()),
+                    ]),
+                );
+            }
+            Err(e) => {
+                self.collectors.record_event(
+                    BuildEvent::BuildFailed,
+                    HashMap::from([(
+                        "error".to_string(),
+                        crate::redact::redactor()
+                            .redact(&e.to_string())
+                            .into_owned(),
+                    )]),
+                );
+            }
+        }
+        self.collectors.flush();
+
+        result
+    }
+
+    /// Load the previous checkpoint when resuming and the inputs are unchanged
+    fn load_resumable(&self, fingerprint: &str) -> Option<BuildCheckpoint> {
+        if !self.config.resume {
+            return None;
+        }
+        match BuildCheckpoint::load_with_fs(&self.project_root, &self.fs) {
+            Some(checkpoint) if checkpoint.fingerprint == fingerprint => {
+                println!(
+                    "   {} Resuming from checkpoint ({} completed stage(s))",
+                    style("↻").cyan(),
+                    checkpoint.stages.len()
+                );
+                Some(checkpoint)
+            }
+            Some(_) => {
+                println!(
+                    "   {} Inputs changed since the last attempt, starting from scratch",
+                    style("ℹ️")
+                );
+                None
+            }
+            None => {
+                println!(
+                    "   {} No checkpoint found, starting from scratch",
+                    style("ℹ️")
+                );
+                None
+            }
+        }
+    }
+
+    /// Reuse a stage recorded in the resumable checkpoint, restoring its snapshot
+    fn resume_stage(
+        &self,
+        resumable: Option<&BuildCheckpoint>,
+        stage: Stage,
+    ) -> Option<StageRecord> {
+        let record = resumable?.completed(stage, &self.fs)?.clone();
+        BuildCheckpoint::restore(&record, &self.fs).ok()?;
+        println!(
+            "   {} Reusing {} from previous attempt ({})",
+            CHECKMARK,
+            style(record.output.display()).cyan(),
+            style(format_bytes(record.size_bytes)).yellow()
+        );
+        Some(record)
+    }
+
+    /// Write a completion marker; checkpointing is disabled if it cannot be saved
+    fn mark_completed(
+        &self,
+        checkpoint: &mut Option<BuildCheckpoint>,
+        stage: Stage,
+        output: &std::path::Path,
+        size_bytes: u64,
+        take_snapshot: bool,
+    ) {
+        let Some(current) = checkpoint.as_mut() else {
+            return;
+        };
+        if let Err(e) = current.record(
+            &self.project_root,
+            &self.fs,
+            stage,
+            output,
+            size_bytes,
+            take_snapshot,
+        ) {
+            println!("   {} Could not write build checkpoint: {}", style("⚠️"), e);
+            *checkpoint = None;
+        }
+    }
+
+    /// Apply the registered transforms to `wasm_file` in place
+    fn run_transforms(&self, wasm_file: &std::path::Path) -> Result<u64, PipelineError> {
+        let module = std::fs::read(wasm_file).map_err(PipelineError::Io)?;
+        let (module, outcomes) = apply_transforms(&self.transforms, module, &self.collectors)?;
+        self.fs
+            .write(wasm_file, &module)
+            .map_err(PipelineError::Io)?;
+        for outcome in &outcomes {
+            println!(
+                "   {} {}: {} → {}",
+                CHECKMARK,
+                style(&outcome.name).cyan(),
+                format_bytes(outcome.before_bytes),
+                style(format_bytes(outcome.after_bytes)).yellow()
+            );
+        }
+        Ok(module.len() as u64)
+    }
+
+    /// Run the pipeline stages, reporting per-stage durations and sizes
+    ///
+    /// With `checkpoint` enabled each completed stage is recorded; with
+    /// `resume` enabled the leading stages of a matching checkpoint are
+    /// reused instead of rerun. Once any stage runs, all later stages run too.
+    fn run_stages(&self) -> Result<SizeMetrics, PipelineError> {
         println!(
             "\n{} {} WASM Build Pipeline",
             HAMMER,
@@ -68,32 +236,89 @@ impl<FS: FileSystem + Clone, CE: CommandExecutor + Clone> BuildOrchestrator<FS,
         // Step 1: Check required tools are available
         self.toolchain.check_required()?;
 
+        let fingerprint = (self.config.checkpoint || self.config.resume)
+            .then(|| BuildCheckpoint::fingerprint(&self.project_root, &self.config, &self.fs));
+        let mut resumable = fingerprint.as_deref().and_then(|f| self.load_resumable(f));
+        let mut checkpoint = if self.config.checkpoint {
+            resumable
+                .clone()
+                .or_else(|| fingerprint.clone().map(BuildCheckpoint::new))
+        } else {
+            None
+        };
+
         // Step 2: Build with cargo
         println!("\n{} Step 1: Building with cargo...", SPARKLES);
-        let wasm_file = self.tool_runner.cargo_build()?;
-        let before_size = self
-            .fs
-            .metadata(&wasm_file)
-            .map_err(PipelineError::Io)?
-            .len();
-        println!(
-            "   {} Built: {} ({})",
-            CHECKMARK,
-            style(wasm_file.display()).cyan(),
-            style(format_bytes(before_size)).yellow()
-        );
+        let (wasm_file, before_size) =
+            if let Some(record) = self.resume_stage(resumable.as_ref(), Stage::CargoBuild) {
+                (record.output, record.size_bytes)
+            } else {
+                resumable = None;
+                let started = Instant::now();
+                let wasm_file = self.tool_runner.cargo_build()?;
+                self.collectors
+                    .record_duration("cargo_build", started.elapsed());
+                let before_size = self
+                    .fs
+                    .metadata(&wasm_file)
+                    .map_err(PipelineError::Io)?
+                    .len();
+                self.mark_completed(
+                    &mut checkpoint,
+                    Stage::CargoBuild,
+                    &wasm_file,
+                    before_size,
+                    false,
+                );
+                println!(
+                    "   {} Built: {} ({})",
+                    CHECKMARK,
+                    style(wasm_file.display()).cyan(),
+                    style(format_bytes(before_size)).yellow()
+                );
+                (wasm_file, before_size)
+            };
+        self.collectors.record_size("cargo_build", before_size);
 
         // Step 3: Run wasm-bindgen
         println!("\n{} Step 2: Running wasm-bindgen...", SPARKLES);
-        let bindgen_output = self.tool_runner.run_wasm_bindgen(&wasm_file)?;
-        println!("   {} wasm-bindgen complete", CHECKMARK);
-
-        // Get the size after wasm-bindgen
-        let mut current_size = self
-            .fs
-            .metadata(&bindgen_output)
-            .map_err(PipelineError::Io)?
-            .len();
+        let (bindgen_output, mut current_size) =
+            if let Some(record) = self.resume_stage(resumable.as_ref(), Stage::WasmBindgen) {
+                (record.output, record.size_bytes)
+            } else {
+                resumable = None;
+                let started = Instant::now();
+                let bindgen_output = self.tool_runner.run_wasm_bindgen(&wasm_file)?;
+                self.collectors
+                    .record_duration("wasm_bindgen", started.elapsed());
+                println!("   {} wasm-bindgen complete", CHECKMARK);
+
+                // Get the size after wasm-bindgen
+                let size = self
+                    .fs
+                    .metadata(&bindgen_output)
+                    .map_err(PipelineError::Io)?
+                    .len();
+                self.mark_completed(
+                    &mut checkpoint,
+                    Stage::WasmBindgen,
+                    &bindgen_output,
+                    size,
+                    true,
+                );
+                (bindgen_output, size)
+            };
+        self.collectors.record_size("wasm_bindgen", current_size);
+
+        // Fail fast: wasm-opt cannot rescue an artifact that is wildly over budget
+        if let Some(limit_bytes) = self.config.preopt_max_bytes {
+            if current_size > limit_bytes {
+                return Err(PipelineError::PreOptSizeExceeded {
+                    size_bytes: current_size,
+                    limit_bytes,
+                });
+            }
+        }
 
         // Step 4: Run wasm-opt if available
         if self.config.run_wasm_opt && self.toolchain.wasm_opt.is_installed() {
@@ -102,13 +327,43 @@ impl<FS: FileSystem + Clone, CE: CommandExecutor + Clone> BuildOrchestrator<FS,
                 SPARKLES,
                 self.config.opt_level.as_arg()
             );
-            self.tool_runner.run_wasm_opt(&bindgen_output)?;
-            current_size = self
-                .fs
-                .metadata(&bindgen_output)
-                .map_err(PipelineError::Io)?
-                .len();
-            println!("   {} wasm-opt complete", CHECKMARK);
+            if let Some(record) = self.resume_stage(resumable.as_ref(), Stage::WasmOpt) {
+                current_size = record.size_bytes;
+            } else {
+                resumable = None;
+                self.collectors.record_event(
+                    BuildEvent::OptimizationStarted,
+                    HashMap::from([
+                        ("tool".to_string(), "wasm-opt".to_string()),
+                        (
+                            "level".to_string(),
+                            self.config.opt_level.as_arg().to_string(),
+                        ),
+                    ]),
+                );
+                let started = Instant::now();
+                self.tool_runner.run_wasm_opt(&bindgen_output)?;
+                self.collectors
+                    .record_duration("wasm_opt", started.elapsed());
+                current_size = self
+                    .fs
+                    .metadata(&bindgen_output)
+                    .map_err(PipelineError::Io)?
+                    .len();
+                self.mark_completed(
+                    &mut checkpoint,
+                    Stage::WasmOpt,
+                    &bindgen_output,
+                    current_size,
+                    true,
+                );
+                self.collectors.record_event(
+                    BuildEvent::OptimizationCompleted,
+                    HashMap::from([("tool".to_string(), "wasm-opt".to_string())]),
+                );
+                println!("   {} wasm-opt complete", CHECKMARK);
+            }
+            self.collectors.record_size("wasm_opt", current_size);
         } else if self.config.run_wasm_opt {
             println!(
                 "\n{} Step 3: Skipping wasm-opt (not installed)",
@@ -119,24 +374,96 @@ impl<FS: FileSystem + Clone, CE: CommandExecutor + Clone> BuildOrchestrator<FS,
         // Step 5: Run wasm-snip if requested and available
         if self.config.run_wasm_snip && self.toolchain.wasm_snip.is_installed() {
             println!("\n{} Step 4: Running wasm-snip...", SPARKLES);
-            self.tool_runner.run_wasm_snip(&bindgen_output)?;
-            current_size = self
-                .fs
-                .metadata(&bindgen_output)
-                .map_err(PipelineError::Io)?
-                .len();
-            println!("   {} wasm-snip complete", CHECKMARK);
+            if let Some(record) = self.resume_stage(resumable.as_ref(), Stage::WasmSnip) {
+                current_size = record.size_bytes;
+            } else {
+                self.collectors.record_event(
+                    BuildEvent::OptimizationStarted,
+                    HashMap::from([("tool".to_string(), "wasm-snip".to_string())]),
+                );
+                let started = Instant::now();
+                self.tool_runner.run_wasm_snip(&bindgen_output)?;
+                self.collectors
+                    .record_duration("wasm_snip", started.elapsed());
+                current_size = self
+                    .fs
+                    .metadata(&bindgen_output)
+                    .map_err(PipelineError::Io)?
+                    .len();
+                self.mark_completed(
+                    &mut checkpoint,
+                    Stage::WasmSnip,
+                    &bindgen_output,
+                    current_size,
+                    true,
+                );
+                self.collectors.record_event(
+                    BuildEvent::OptimizationCompleted,
+                    HashMap::from([("tool".to_string(), "wasm-snip".to_string())]),
+                );
+                println!("   {} wasm-snip complete", CHECKMARK);
+            }
+            self.collectors.record_size("wasm_snip", current_size);
+        }
+
+        // Step 6: Custom transforms see the optimized module before the
+        // debug split, so their output is what ships
+        if !self.transforms.is_empty() {
+            println!("\n{} Step 5: Running custom transforms...", SPARKLES);
+            current_size = self.run_transforms(&bindgen_output)?;
+        }
+
+        // Step 7: Move DWARF into a sidecar so the shipped module stays small
+        if self.config.split_debug_info {
+            println!("\n{} Step 6: Splitting debug info...", SPARKLES);
+            match self.tool_runner.split_debug_info(&bindgen_output)? {
+                Some((sidecar, debug_bytes)) => {
+                    current_size = self
+                        .fs
+                        .metadata(&bindgen_output)
+                        .map_err(PipelineError::Io)?
+                        .len();
+                    println!(
+                        "   {} Moved {} of DWARF to {}",
+                        CHECKMARK,
+                        style(format_bytes(debug_bytes)).yellow(),
+                        style(sidecar.display()).cyan()
+                    );
+                }
+                None => println!(
+                    "   {} No DWARF sections in the output; nothing to split",
+                    style("⚠️")
+                ),
+            }
+            self.collectors
+                .record_size("split_debug_info", current_size);
+        }
+
+        // The build finished, so there is nothing left to resume
+        if self.config.checkpoint {
+            BuildCheckpoint::clear(&self.project_root);
         }
 
         // Calculate final metrics
         let metrics = SizeMetrics {
             before_bytes: before_size,
             after_bytes: current_size,
+            output: Some(bindgen_output.clone()),
+            before_compressed: self.tool_runner.compressed_sizes(&wasm_file),
+            after_compressed: self.tool_runner.compressed_sizes(&bindgen_output),
         };
 
         // Print summary
         ResultFormatter::print_summary(&metrics);
 
+        summary::record("size", summary::format_kb(current_size));
+        if let Some(gzip_bytes) = metrics.after_compressed.gzip_bytes {
+            summary::record("gzip", summary::format_kb(gzip_bytes));
+        }
+        if let Some(brotli_bytes) = metrics.after_compressed.brotli_bytes {
+            summary::record("brotli", summary::format_kb(brotli_bytes));
+        }
+
         Ok(metrics)
     }
 }
@@ -438,6 +765,126 @@ mod integration_tests {
         );
     }
 
+    #[test]
+    fn test_orchestrator_resume_skips_completed_stages() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        let build_dir = root.join("target/wasm32-unknown-unknown/release");
+        std::fs::create_dir_all(&build_dir).unwrap();
+        std::fs::write(build_dir.join("app.wasm"), [0u8; 128]).unwrap();
+
+        let config = PipelineConfig {
+            run_wasm_opt: false,
+            run_wasm_snip: false,
+            checkpoint: true,
+            ..Default::default()
+        };
+        let orchestrator = |config: PipelineConfig, executor: MockCommandExecutor| {
+            BuildOrchestrator::new(
+                root.to_path_buf(),
+                config,
+                ToolChain::with_executor(executor.clone()),
+                RealFileSystem,
+                executor,
+            )
+        };
+
+        // First attempt: cargo succeeds, wasm-bindgen produces no output
+        let first = MockCommandExecutor::new();
+        assert!(orchestrator(config.clone(), first.clone())
+            .execute()
+            .is_err());
+        assert!(first.operations().contains(&"execute: cargo".to_string()));
+
+        // Second attempt resumes after the completed cargo stage
+        std::fs::create_dir_all(root.join("pkg")).unwrap();
+        std::fs::write(root.join("pkg/app_bg.wasm"), [0u8; 64]).unwrap();
+        let second = MockCommandExecutor::new();
+        let resumed = PipelineConfig {
+            resume: true,
+            ..config
+        };
+        let metrics = orchestrator(resumed, second.clone()).execute().unwrap();
+
+        let ops = second.operations();
+        assert!(!ops.contains(&"execute: cargo".to_string()), "{:?}", ops);
+        assert!(ops.contains(&"execute: wasm-bindgen".to_string()));
+        assert_eq!(metrics.before_bytes, 128);
+        assert_eq!(metrics.after_bytes, 64);
+        assert!(BuildCheckpoint::load_with_fs(root, &RealFileSystem).is_none());
+    }
+
+    #[test]
+    fn test_orchestrator_fails_fast_when_preopt_size_exceeds_limit() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        let build_dir = root.join("target/wasm32-unknown-unknown/release");
+        std::fs::create_dir_all(&build_dir).unwrap();
+        std::fs::write(build_dir.join("app.wasm"), [0u8; 4096]).unwrap();
+        std::fs::create_dir_all(root.join("pkg")).unwrap();
+        std::fs::write(root.join("pkg/app_bg.wasm"), [0u8; 4096]).unwrap();
+
+        let executor = MockCommandExecutor::new();
+        let config = PipelineConfig {
+            preopt_max_bytes: Some(1024),
+            ..Default::default()
+        };
+        let orchestrator = BuildOrchestrator::new(
+            root.to_path_buf(),
+            config,
+            ToolChain::with_executor(executor.clone()),
+            RealFileSystem,
+            executor.clone(),
+        );
+
+        let err = orchestrator.execute().unwrap_err();
+        assert!(matches!(
+            err,
+            PipelineError::PreOptSizeExceeded {
+                size_bytes: 4096,
+                limit_bytes: 1024
+            }
+        ));
+        let ops = executor.operations();
+        assert!(!ops.contains(&"execute: wasm-opt".to_string()));
+        assert!(!ops.contains(&"execute: wasm-snip".to_string()));
+    }
+
+    #[test]
+    fn test_orchestrator_fans_out_events_to_all_collectors() {
+        use crate::pipeline::telemetry::MemoryCollector;
+
+        let fs = MockFileSystem::new(1000);
+        let cmd_executor = MockCommandExecutor::new();
+        cmd_executor.set_fail_at_step("cargo");
+
+        let mut orchestrator = BuildOrchestrator::new(
+            PathBuf::from("/test"),
+            PipelineConfig::default(),
+            ToolChain::with_executor(cmd_executor.clone()),
+            fs,
+            cmd_executor,
+        );
+        let first = Arc::new(MemoryCollector::new());
+        let second = Arc::new(MemoryCollector::new());
+        orchestrator.add_collector(first.clone());
+        orchestrator.add_collector(second.clone());
+
+        assert!(orchestrator.execute().is_err());
+
+        for collector in [&first, &second] {
+            let events: Vec<_> = collector.events().into_iter().map(|(e, _)| e).collect();
+            assert_eq!(
+                events,
+                vec![BuildEvent::BuildStarted, BuildEvent::BuildFailed]
+            );
+        }
+        let (_, failure) = &first.events()[1];
+        assert!(failure.contains_key("error"));
+    }
+
     #[test]
     fn test_orchestrator_tracks_size_changes_through_pipeline() {
         let config = PipelineConfig {
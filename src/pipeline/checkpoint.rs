@@ -0,0 +1,566 @@
+//! Per-stage build checkpoints for resuming failed builds
+//!
+//! After each pipeline stage completes, its output is recorded in
+//! `.wasm-slim/scratch/checkpoint/stages.json`. Stages that rewrite the
+//! wasm-bindgen output in place (wasm-bindgen, wasm-opt, wasm-snip) also keep a
+//! snapshot of their result, so `build --resume` can restore the exact
+//! intermediate artifact and continue from the stage that failed.
+//!
+//! A checkpoint is only reused when the input fingerprint (Cargo.toml,
+//! Cargo.lock, build.rs and `src/` of the project and of every local path or
+//! workspace dependency, `.cargo/config.toml` and `rust-toolchain.toml` in the
+//! project and its ancestors, the pipeline settings and injected environment
+//! variables) is unchanged.
+
+use crate::infra::FileSystem;
+use cargo_metadata::MetadataCommand;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::config::PipelineConfig;
+
+/// Directory (inside the scratch directory) holding checkpoint state
+pub const CHECKPOINT_DIR: &str = "checkpoint";
+
+const CHECKPOINT_FILE: &str = "stages.json";
+
+/// Pipeline stage that can be checkpointed (ordered as the pipeline runs them)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// `cargo build` producing the raw .wasm
+    CargoBuild,
+    /// wasm-bindgen producing the pkg/ output
+    WasmBindgen,
+    /// wasm-opt rewriting the bindgen output
+    WasmOpt,
+    /// wasm-snip rewriting the bindgen output
+    WasmSnip,
+}
+
+impl Stage {
+    /// Stage name as used in markers and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CargoBuild => "cargo_build",
+            Self::WasmBindgen => "wasm_bindgen",
+            Self::WasmOpt => "wasm_opt",
+            Self::WasmSnip => "wasm_snip",
+        }
+    }
+}
+
+/// Completion marker for a single stage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageRecord {
+    /// Completed stage
+    pub stage: Stage,
+    /// Artifact the stage produced (consumed by the next stage)
+    pub output: PathBuf,
+    /// Copy of the output taken right after the stage completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<PathBuf>,
+    /// Output size in bytes
+    pub size_bytes: u64,
+}
+
+/// Completed stages of the most recent build attempt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildCheckpoint {
+    /// Fingerprint of the inputs the stages were built from
+    pub fingerprint: String,
+    /// Completed stages in pipeline order
+    pub stages: Vec<StageRecord>,
+}
+
+impl BuildCheckpoint {
+    /// Create an empty checkpoint for the given input fingerprint
+    pub fn new(fingerprint: impl Into<String>) -> Self {
+        Self {
+            fingerprint: fingerprint.into(),
+            stages: Vec::new(),
+        }
+    }
+
+    /// Path of the checkpoint directory for a project
+    pub fn dir(project_root: &Path) -> PathBuf {
+        crate::state::scratch_dir(project_root).join(CHECKPOINT_DIR)
+    }
+
+    /// Load the checkpoint for a project, if one exists and parses
+    pub fn load_with_fs<FS: FileSystem>(project_root: &Path, fs: &FS) -> Option<Self> {
+        let contents = fs
+            .read_to_string(&Self::dir(project_root).join(CHECKPOINT_FILE))
+            .ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Persist the checkpoint
+    pub fn save_with_fs<FS: FileSystem>(&self, project_root: &Path, fs: &FS) -> io::Result<()> {
+        let dir = Self::dir(project_root);
+        fs.create_dir_all(&dir)?;
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs.write(&dir.join(CHECKPOINT_FILE), contents)
+    }
+
+    /// Remove all checkpoint state for a project (best effort)
+    pub fn clear(project_root: &Path) {
+        let _ = std::fs::remove_dir_all(Self::dir(project_root));
+    }
+
+    /// Record a completed stage, snapshotting its output when requested
+    ///
+    /// Markers for this stage and every later stage are replaced, since they
+    /// were derived from the previous output.
+    pub fn record<FS: FileSystem>(
+        &mut self,
+        project_root: &Path,
+        fs: &FS,
+        stage: Stage,
+        output: &Path,
+        size_bytes: u64,
+        take_snapshot: bool,
+    ) -> io::Result<()> {
+        let snapshot = if take_snapshot {
+            let dir = Self::dir(project_root);
+            fs.create_dir_all(&dir)?;
+            let snapshot = dir.join(format!("{}.wasm", stage.as_str()));
+            fs.copy(output, &snapshot)?;
+            Some(snapshot)
+        } else {
+            None
+        };
+
+        self.stages.retain(|r| r.stage < stage);
+        self.stages.push(StageRecord {
+            stage,
+            output: output.to_path_buf(),
+            snapshot,
+            size_bytes,
+        });
+        self.save_with_fs(project_root, fs)
+    }
+
+    /// Marker for a stage, if it completed and its artifact is still intact
+    ///
+    /// The artifact checked is the snapshot when one was taken, otherwise the
+    /// stage output itself; its size must match the recorded size.
+    pub fn completed<FS: FileSystem>(&self, stage: Stage, fs: &FS) -> Option<&StageRecord> {
+        let record = self.stages.iter().find(|r| r.stage == stage)?;
+        let artifact = record.snapshot.as_ref().unwrap_or(&record.output);
+        let len = fs.metadata(artifact).ok()?.len();
+        (len == record.size_bytes).then_some(record)
+    }
+
+    /// Restore a stage's snapshot over its output path
+    pub fn restore<FS: FileSystem>(record: &StageRecord, fs: &FS) -> io::Result<()> {
+        if let Some(snapshot) = &record.snapshot {
+            fs.copy(snapshot, &record.output)?;
+        }
+        Ok(())
+    }
+
+    /// Fingerprint the build inputs of a project
+    ///
+    /// Covers Cargo.toml, Cargo.lock, build.rs and every file under `src/`
+    /// (raw bytes, so binary files count in full) for the project and each
+    /// local package `cargo metadata` resolves, Cargo and rustup settings
+    /// (`.cargo/config.toml` and `rust-toolchain.toml`, legacy names
+    /// included) in the project and every ancestor directory, and the
+    /// pipeline settings that affect stage outputs. Every value is written
+    /// as explicit length-prefixed bytes, so the digest does not depend on
+    /// how a Rust release lays out `Hash` input.
+    ///
+    /// Not covered: `$CARGO_HOME/config.toml`, `RUSTFLAGS` and other
+    /// variables in the calling environment, and the installed toolchain
+    /// itself (a `rustup update` of the same channel); build without
+    /// `--resume` after changing those.
+    pub fn fingerprint<FS: FileSystem>(
+        project_root: &Path,
+        config: &PipelineConfig,
+        fs: &FS,
+    ) -> String {
+        let mut digest = FingerprintDigest::default();
+
+        digest.str(config.target.as_str());
+        digest.str(&config.profile);
+        digest.optional_path(config.target_dir.as_deref());
+        digest.str(config.bindgen_target.as_str());
+        digest.optional_path(config.out_dir.as_deref());
+        digest.flag(config.run_wasm_opt);
+        digest.flag(config.run_wasm_snip);
+        digest.str(config.opt_level.as_arg());
+        for vars in [
+            &config.env.global,
+            &config.env.cargo,
+            &config.env.wasm_bindgen,
+            &config.env.wasm_opt,
+            &config.env.wasm_snip,
+        ] {
+            digest.map(vars);
+        }
+        digest.count(config.wasm_opt_args.len());
+        for arg in &config.wasm_opt_args {
+            digest.str(arg);
+        }
+        digest.flag(config.split_debug_info);
+
+        let mut files = Vec::new();
+        for dir in source_dirs(project_root) {
+            files.extend(["Cargo.toml", "Cargo.lock", "build.rs"].map(|f| dir.join(f)));
+            collect_files(&dir.join("src"), fs, &mut files);
+        }
+        for dir in project_root.ancestors() {
+            files.extend(
+                [
+                    ".cargo/config.toml",
+                    ".cargo/config",
+                    "rust-toolchain.toml",
+                    "rust-toolchain",
+                ]
+                .map(|f| dir.join(f)),
+            );
+        }
+        files.sort();
+        files.dedup();
+
+        for file in &files {
+            digest.path(file);
+            // Missing files (no build.rs, say) are recorded as absent
+            digest.optional(fs.read(file).ok().as_deref());
+        }
+
+        digest.hex_digest()
+    }
+}
+
+/// SHA-256 over explicitly encoded fields
+///
+/// Each field is its byte length as a little-endian `u64` followed by the
+/// bytes, so adjacent fields cannot run into each other and the encoding
+/// is fixed here rather than by std's `Hash` implementations.
+#[derive(Default)]
+struct FingerprintDigest(Sha256);
+
+impl FingerprintDigest {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.count(bytes.len());
+        self.0.update(bytes);
+    }
+
+    fn count(&mut self, count: usize) {
+        self.0.update((count as u64).to_le_bytes());
+    }
+
+    fn str(&mut self, text: &str) {
+        self.bytes(text.as_bytes());
+    }
+
+    fn flag(&mut self, flag: bool) {
+        self.bytes(&[u8::from(flag)]);
+    }
+
+    fn path(&mut self, path: &Path) {
+        self.bytes(path.as_os_str().as_encoded_bytes());
+    }
+
+    fn optional(&mut self, bytes: Option<&[u8]>) {
+        self.flag(bytes.is_some());
+        if let Some(bytes) = bytes {
+            self.bytes(bytes);
+        }
+    }
+
+    fn optional_path(&mut self, path: Option<&Path>) {
+        self.optional(path.map(|path| path.as_os_str().as_encoded_bytes()));
+    }
+
+    fn map(&mut self, map: &BTreeMap<String, String>) {
+        self.count(map.len());
+        for (key, value) in map {
+            self.str(key);
+            self.str(value);
+        }
+    }
+
+    fn hex_digest(self) -> String {
+        crate::hash::hex(&self.0.finalize())
+    }
+}
+
+/// Directories whose sources feed the build: the project, its workspace
+/// root and every local (path or workspace) package in the dependency graph
+///
+/// Falls back to the project alone when `cargo metadata` fails.
+fn source_dirs(project_root: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![project_root.to_path_buf()];
+    let Ok(metadata) = MetadataCommand::new().current_dir(project_root).exec() else {
+        return dirs;
+    };
+    dirs.push(metadata.workspace_root.into_std_path_buf());
+    dirs.extend(
+        metadata
+            .packages
+            .iter()
+            .filter(|package| package.source.is_none())
+            .filter_map(|package| package.manifest_path.parent())
+            .map(|dir| dir.as_std_path().to_path_buf()),
+    );
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// Recursively collect files below `dir`
+fn collect_files<FS: FileSystem>(dir: &Path, fs: &FS, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs.read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, fs, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::RealFileSystem;
+    use tempfile::TempDir;
+
+    fn project() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(temp.path().join("src")).unwrap();
+        std::fs::write(temp.path().join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_for_unchanged_inputs() {
+        let temp = project();
+        let config = PipelineConfig::default();
+        assert_eq!(
+            BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem),
+            BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_sources_and_config() {
+        let temp = project();
+        let config = PipelineConfig::default();
+        let original = BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem);
+
+        std::fs::write(temp.path().join("src/lib.rs"), "pub fn g() {}\n").unwrap();
+        let edited = BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem);
+        assert_ne!(original, edited);
+
+        let no_opt = PipelineConfig {
+            run_wasm_opt: false,
+            ..Default::default()
+        };
+        assert_ne!(
+            edited,
+            BuildCheckpoint::fingerprint(temp.path(), &no_opt, &RealFileSystem)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_covers_binary_files_and_path_dependencies() {
+        let temp = project();
+        let helper = temp.path().join("helper");
+        std::fs::create_dir_all(helper.join("src")).unwrap();
+        std::fs::write(
+            helper.join("Cargo.toml"),
+            "[package]\nname = \"helper\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        std::fs::write(helper.join("src/lib.rs"), "pub fn h() {}\n").unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n\n[dependencies]\nhelper = { path = \"helper\" }\n",
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("src/table.bin"), [0xff, 0x00]).unwrap();
+        let config = PipelineConfig::default();
+        let original = BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem);
+
+        // Same size, different bytes, not UTF-8
+        std::fs::write(temp.path().join("src/table.bin"), [0xfe, 0x00]).unwrap();
+        let binary_edit = BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem);
+        assert_ne!(original, binary_edit);
+
+        std::fs::write(helper.join("src/lib.rs"), "pub fn h2() {}\n").unwrap();
+        assert_ne!(
+            binary_edit,
+            BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_covers_cargo_config_and_toolchain_files() {
+        let temp = project();
+        let config = PipelineConfig::default();
+        let original = BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem);
+
+        std::fs::create_dir_all(temp.path().join(".cargo")).unwrap();
+        std::fs::write(
+            temp.path().join(".cargo/config.toml"),
+            "[build]\nrustflags = [\"-Ctarget-feature=+simd128\"]\n",
+        )
+        .unwrap();
+        let cargo_config = BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem);
+        assert_ne!(original, cargo_config);
+
+        std::fs::write(
+            temp.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.88\"\n",
+        )
+        .unwrap();
+        assert_ne!(
+            cargo_config,
+            BuildCheckpoint::fingerprint(temp.path(), &config, &RealFileSystem)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_digest_encoding_is_length_prefixed_and_fixed() {
+        let digest = |fields: &[&[u8]]| {
+            let mut digest = FingerprintDigest::default();
+            for field in fields {
+                digest.bytes(field);
+            }
+            digest.hex_digest()
+        };
+
+        // Pinned, so a change to the encoding cannot slip in unnoticed
+        assert_eq!(
+            digest(&[b"ab", b""]),
+            "e38fddf799dfc9884ea023573aeb35487342dc2b75837ecc5b72fd9386050ccb"
+        );
+        assert_ne!(digest(&[b"ab", b""]), digest(&[b"a", b"b"]));
+    }
+
+    #[test]
+    fn test_record_save_and_load_round_trip() {
+        let temp = project();
+        let output = temp.path().join("app.wasm");
+        std::fs::write(&output, [0u8; 64]).unwrap();
+
+        let mut checkpoint = BuildCheckpoint::new("abc");
+        checkpoint
+            .record(
+                temp.path(),
+                &RealFileSystem,
+                Stage::WasmBindgen,
+                &output,
+                64,
+                true,
+            )
+            .unwrap();
+
+        let loaded = BuildCheckpoint::load_with_fs(temp.path(), &RealFileSystem).unwrap();
+        assert_eq!(loaded, checkpoint);
+        let record = loaded
+            .completed(Stage::WasmBindgen, &RealFileSystem)
+            .unwrap();
+        assert!(record.snapshot.as_ref().unwrap().exists());
+        assert!(loaded.completed(Stage::WasmOpt, &RealFileSystem).is_none());
+    }
+
+    #[test]
+    fn test_record_drops_markers_of_later_stages() {
+        let temp = project();
+        let output = temp.path().join("app.wasm");
+        std::fs::write(&output, [0u8; 16]).unwrap();
+
+        let mut checkpoint = BuildCheckpoint::new("abc");
+        for stage in [Stage::CargoBuild, Stage::WasmBindgen, Stage::WasmOpt] {
+            checkpoint
+                .record(temp.path(), &RealFileSystem, stage, &output, 16, false)
+                .unwrap();
+        }
+        checkpoint
+            .record(
+                temp.path(),
+                &RealFileSystem,
+                Stage::WasmBindgen,
+                &output,
+                16,
+                false,
+            )
+            .unwrap();
+
+        let stages: Vec<_> = checkpoint.stages.iter().map(|r| r.stage).collect();
+        assert_eq!(stages, vec![Stage::CargoBuild, Stage::WasmBindgen]);
+    }
+
+    #[test]
+    fn test_completed_rejects_modified_artifact() {
+        let temp = project();
+        let output = temp.path().join("app.wasm");
+        std::fs::write(&output, [0u8; 64]).unwrap();
+
+        let mut checkpoint = BuildCheckpoint::new("abc");
+        checkpoint
+            .record(
+                temp.path(),
+                &RealFileSystem,
+                Stage::CargoBuild,
+                &output,
+                64,
+                false,
+            )
+            .unwrap();
+
+        std::fs::write(&output, [0u8; 10]).unwrap();
+        assert!(checkpoint
+            .completed(Stage::CargoBuild, &RealFileSystem)
+            .is_none());
+    }
+
+    #[test]
+    fn test_restore_copies_snapshot_over_output() {
+        let temp = project();
+        let output = temp.path().join("app.wasm");
+        std::fs::write(&output, [1u8; 32]).unwrap();
+
+        let mut checkpoint = BuildCheckpoint::new("abc");
+        checkpoint
+            .record(
+                temp.path(),
+                &RealFileSystem,
+                Stage::WasmOpt,
+                &output,
+                32,
+                true,
+            )
+            .unwrap();
+
+        std::fs::write(&output, [2u8; 8]).unwrap();
+        BuildCheckpoint::restore(&checkpoint.stages[0], &RealFileSystem).unwrap();
+        assert_eq!(std::fs::read(&output).unwrap(), vec![1u8; 32]);
+    }
+
+    #[test]
+    fn test_clear_removes_checkpoint() {
+        let temp = project();
+        BuildCheckpoint::new("abc")
+            .save_with_fs(temp.path(), &RealFileSystem)
+            .unwrap();
+        BuildCheckpoint::clear(temp.path());
+        assert!(BuildCheckpoint::load_with_fs(temp.path(), &RealFileSystem).is_none());
+    }
+}
@@ -1,6 +1,10 @@
 //! Pipeline configuration types
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::checkpoint::Stage;
 
 /// WebAssembly compilation target
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -54,6 +58,21 @@ impl BindgenTarget {
     }
 }
 
+impl FromStr for BindgenTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "web" => Ok(Self::Web),
+            "nodejs" => Ok(Self::NodeJs),
+            "bundler" => Ok(Self::Bundler),
+            "deno" => Ok(Self::Deno),
+            "no-modules" => Ok(Self::NoModules),
+            _ => Err(format!("Unknown wasm-bindgen target: {}", s)),
+        }
+    }
+}
+
 /// wasm-opt optimization levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WasmOptLevel {
@@ -79,6 +98,63 @@ impl WasmOptLevel {
             WasmOptLevel::Oz => "-Oz",
         }
     }
+
+    /// Parse a wasm-opt level flag such as `-Oz`
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "-O1" => Some(WasmOptLevel::O1),
+            "-O2" => Some(WasmOptLevel::O2),
+            "-O3" => Some(WasmOptLevel::O3),
+            "-O4" => Some(WasmOptLevel::O4),
+            "-Oz" => Some(WasmOptLevel::Oz),
+            _ => None,
+        }
+    }
+}
+
+/// Environment variables injected into tool invocations
+///
+/// Global variables apply to every stage; per-stage variables override them.
+///
+/// # Examples
+///
+/// ```
+/// use wasm_slim::pipeline::{Stage, StageEnv};
+///
+/// let mut env = StageEnv::default();
+/// env.global.insert("RUST_LOG".into(), "warn".into());
+/// env.wasm_opt.insert("BINARYEN_CORES".into(), "4".into());
+///
+/// assert_eq!(env.vars_for(Stage::WasmOpt).len(), 2);
+/// assert_eq!(env.vars_for(Stage::CargoBuild).len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct StageEnv {
+    /// Variables for every stage
+    pub global: BTreeMap<String, String>,
+    /// Variables for `cargo build`
+    pub cargo: BTreeMap<String, String>,
+    /// Variables for wasm-bindgen
+    pub wasm_bindgen: BTreeMap<String, String>,
+    /// Variables for wasm-opt
+    pub wasm_opt: BTreeMap<String, String>,
+    /// Variables for wasm-snip
+    pub wasm_snip: BTreeMap<String, String>,
+}
+
+impl StageEnv {
+    /// Effective variables for a stage (stage-specific values win)
+    pub fn vars_for(&self, stage: Stage) -> BTreeMap<String, String> {
+        let stage_vars = match stage {
+            Stage::CargoBuild => &self.cargo,
+            Stage::WasmBindgen => &self.wasm_bindgen,
+            Stage::WasmOpt => &self.wasm_opt,
+            Stage::WasmSnip => &self.wasm_snip,
+        };
+        let mut vars = self.global.clone();
+        vars.extend(stage_vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+        vars
+    }
 }
 
 /// Configuration for the build pipeline
@@ -115,12 +191,26 @@ pub struct PipelineConfig {
     pub target_dir: Option<PathBuf>,
     /// wasm-bindgen target (web, nodejs, bundler, deno)
     pub bindgen_target: BindgenTarget,
+    /// wasm-bindgen output directory (default: pkg in the project root)
+    pub out_dir: Option<PathBuf>,
     /// Whether to run wasm-opt
     pub run_wasm_opt: bool,
     /// Whether to run wasm-snip
     pub run_wasm_snip: bool,
     /// wasm-opt optimization level
     pub opt_level: WasmOptLevel,
+    /// Persist per-stage completion markers so a failed build can be resumed
+    pub checkpoint: bool,
+    /// Skip stages recorded in an existing checkpoint when inputs are unchanged
+    pub resume: bool,
+    /// Fail before wasm-opt when the wasm-bindgen output exceeds this size
+    pub preopt_max_bytes: Option<u64>,
+    /// Environment variables applied to each tool invocation
+    pub env: StageEnv,
+    /// Extra arguments appended to the wasm-opt invocation
+    pub wasm_opt_args: Vec<String>,
+    /// Build with DWARF, keep it through every tool, then move it into a sidecar
+    pub split_debug_info: bool,
 }
 
 impl Default for PipelineConfig {
@@ -130,9 +220,16 @@ impl Default for PipelineConfig {
             profile: "release".to_string(),
             target_dir: None,
             bindgen_target: BindgenTarget::default(),
+            out_dir: None,
             run_wasm_opt: true,
             run_wasm_snip: false,
             opt_level: WasmOptLevel::Oz,
+            checkpoint: false,
+            resume: false,
+            preopt_max_bytes: None,
+            env: StageEnv::default(),
+            wasm_opt_args: Vec::new(),
+            split_debug_info: false,
         }
     }
 }
@@ -171,6 +268,19 @@ mod tests {
         assert_ne!(target1, target3);
     }
 
+    #[test]
+    fn test_stage_env_stage_values_override_global() {
+        let mut env = StageEnv::default();
+        env.global.insert("RUSTFLAGS".into(), "-C a".into());
+        env.global.insert("RUST_LOG".into(), "warn".into());
+        env.cargo.insert("RUSTFLAGS".into(), "-C b".into());
+
+        let cargo = env.vars_for(Stage::CargoBuild);
+        assert_eq!(cargo["RUSTFLAGS"], "-C b");
+        assert_eq!(cargo["RUST_LOG"], "warn");
+        assert_eq!(env.vars_for(Stage::WasmSnip)["RUSTFLAGS"], "-C a");
+    }
+
     #[test]
     fn test_bindgen_target_default() {
         let target = BindgenTarget::default();
@@ -187,6 +297,20 @@ mod tests {
         assert_eq!(BindgenTarget::NoModules.as_str(), "no-modules");
     }
 
+    #[test]
+    fn test_bindgen_target_from_str_roundtrips_as_str() {
+        for target in [
+            BindgenTarget::Web,
+            BindgenTarget::NodeJs,
+            BindgenTarget::Bundler,
+            BindgenTarget::Deno,
+            BindgenTarget::NoModules,
+        ] {
+            assert_eq!(target.as_str().parse::<BindgenTarget>(), Ok(target));
+        }
+        assert!("browser".parse::<BindgenTarget>().is_err());
+    }
+
     #[test]
     fn test_bindgen_target_equality() {
         let target1 = BindgenTarget::NodeJs;
@@ -204,6 +328,8 @@ mod tests {
         assert_eq!(WasmOptLevel::O3.as_arg(), "-O3");
         assert_eq!(WasmOptLevel::O4.as_arg(), "-O4");
         assert_eq!(WasmOptLevel::Oz.as_arg(), "-Oz");
+        assert_eq!(WasmOptLevel::from_arg("-O3"), Some(WasmOptLevel::O3));
+        assert_eq!(WasmOptLevel::from_arg("--vacuum"), None);
     }
 
     #[test]
@@ -0,0 +1,153 @@
+//! Split DWARF debug info into a sidecar module
+//!
+//! Moves the `.debug_*` custom sections of a module into a sidecar module
+//! that holds nothing else, and points the stripped module at it with an
+//! `external_debug_info` custom section. Chrome DevTools (with the C/C++
+//! DWARF support extension) follows that section to load the debug info on
+//! demand, so the production binary ships without it while stack traces and
+//! breakpoints still resolve to source lines.
+
+use std::path::{Path, PathBuf};
+
+use wasmparser::{Parser, Payload};
+
+use super::error::PipelineError;
+
+/// Custom section naming the sidecar's URL, relative to the module's URL
+pub const EXTERNAL_DEBUG_INFO_SECTION: &str = "external_debug_info";
+
+/// WASM magic number and version 1
+const HEADER: [u8; 8] = *b"\0asm\x01\0\0\0";
+
+/// A module with its debug info moved out
+#[derive(Debug)]
+pub struct DebugSplit {
+    /// The module without DWARF, referencing the sidecar
+    pub stripped: Vec<u8>,
+    /// Header plus the DWARF sections
+    pub sidecar: Vec<u8>,
+    /// Size of the DWARF sections moved
+    pub debug_bytes: u64,
+}
+
+/// Sidecar path for a module: `app_bg.wasm` → `app_bg.debug.wasm`
+pub fn sidecar_path(wasm_file: &Path) -> PathBuf {
+    wasm_file.with_extension("debug.wasm")
+}
+
+/// Move the DWARF sections of `module` into a sidecar module
+///
+/// Returns `None` when the module has no DWARF sections. An existing
+/// `external_debug_info` section is replaced.
+pub fn split_debug_info(
+    module: &[u8],
+    sidecar_url: &str,
+) -> Result<Option<DebugSplit>, PipelineError> {
+    if module.get(..HEADER.len()) != Some(&HEADER[..]) {
+        return Err(PipelineError::DebugSplit(
+            "not a WASM module (bad magic number or version)".to_string(),
+        ));
+    }
+
+    let mut stripped = HEADER.to_vec();
+    let mut sidecar = HEADER.to_vec();
+    let mut debug_bytes = 0;
+    // Each section's header starts where the previous section ended
+    let mut start = HEADER.len();
+    for payload in Parser::new(0).parse_all(module) {
+        let payload = payload.map_err(|e| PipelineError::DebugSplit(e.message().to_string()))?;
+        let Some((_, range)) = payload.as_section() else {
+            continue;
+        };
+        let section = &module[start..range.end];
+        start = range.end;
+        let Payload::CustomSection(reader) = payload else {
+            stripped.extend_from_slice(section);
+            continue;
+        };
+        if reader.name().starts_with(".debug_") {
+            sidecar.extend_from_slice(section);
+            debug_bytes += section.len() as u64;
+        } else if reader.name() != EXTERNAL_DEBUG_INFO_SECTION {
+            stripped.extend_from_slice(section);
+        }
+    }
+
+    if debug_bytes == 0 {
+        return Ok(None);
+    }
+
+    let mut content = Vec::new();
+    write_u32(&mut content, EXTERNAL_DEBUG_INFO_SECTION.len() as u32);
+    content.extend_from_slice(EXTERNAL_DEBUG_INFO_SECTION.as_bytes());
+    write_u32(&mut content, sidecar_url.len() as u32);
+    content.extend_from_slice(sidecar_url.as_bytes());
+    stripped.push(0);
+    write_u32(&mut stripped, content.len() as u32);
+    stripped.extend(content);
+
+    Ok(Some(DebugSplit {
+        stripped,
+        sidecar,
+        debug_bytes,
+    }))
+}
+
+fn write_u32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_wasm::custom_section;
+
+    #[test]
+    fn test_split_moves_dwarf_into_sidecar() {
+        let type_section = [1, 4, 1, 0x60, 0, 0];
+        let mut module = HEADER.to_vec();
+        module.extend(type_section);
+        module.extend(custom_section(".debug_info", &[1; 200]));
+        module.extend(custom_section("name", b"names"));
+        module.extend(custom_section(".debug_line", &[2; 100]));
+
+        let split = split_debug_info(&module, "app_bg.debug.wasm")
+            .unwrap()
+            .unwrap();
+
+        let mut expected = HEADER.to_vec();
+        expected.extend(type_section);
+        expected.extend(custom_section("name", b"names"));
+        expected.extend(custom_section(
+            EXTERNAL_DEBUG_INFO_SECTION,
+            b"\x11app_bg.debug.wasm",
+        ));
+        assert_eq!(split.stripped, expected);
+
+        let mut sidecar = HEADER.to_vec();
+        sidecar.extend(custom_section(".debug_info", &[1; 200]));
+        sidecar.extend(custom_section(".debug_line", &[2; 100]));
+        assert_eq!(split.sidecar, sidecar);
+        assert_eq!(split.debug_bytes, (sidecar.len() - HEADER.len()) as u64);
+
+        // Already split: nothing left to move
+        assert!(split_debug_info(&split.stripped, "x").unwrap().is_none());
+        assert!(split_debug_info(b"not wasm", "x").is_err());
+    }
+
+    #[test]
+    fn test_sidecar_path_keeps_stem() {
+        assert_eq!(
+            sidecar_path(Path::new("pkg/app_bg.wasm")),
+            PathBuf::from("pkg/app_bg.debug.wasm")
+        );
+    }
+}
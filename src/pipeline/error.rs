@@ -1,5 +1,6 @@
 //! Error types for the build pipeline
 
+use crate::fmt::format_bytes;
 use thiserror::Error;
 
 /// Errors that can occur during pipeline execution
@@ -24,4 +25,30 @@ pub enum PipelineError {
     /// File not found
     #[error("File not found: {0}")]
     FileNotFound(String),
+
+    /// Debug info could not be split from the module
+    #[error("Failed to split debug info: {0}")]
+    DebugSplit(String),
+
+    /// A custom post-processing transform failed
+    #[error("Transform '{name}' failed: {message}")]
+    Transform {
+        /// Name of the transform
+        name: String,
+        /// What went wrong
+        message: String,
+    },
+
+    /// Pre-optimization artifact is far beyond the size budget
+    #[error(
+        "Pre-optimization size {} exceeds the fail-fast limit of {} (size budget × preopt-factor); skipping wasm-opt",
+        format_bytes(*.size_bytes),
+        format_bytes(*.limit_bytes)
+    )]
+    PreOptSizeExceeded {
+        /// Size of the wasm-bindgen output
+        size_bytes: u64,
+        /// Configured fail-fast limit
+        limit_bytes: u64,
+    },
 }
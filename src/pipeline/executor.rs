@@ -8,6 +8,7 @@
 
 use crate::infra::{CommandExecutor, FileSystem, RealCommandExecutor, RealFileSystem};
 use std::path::Path;
+use std::sync::Arc;
 
 use crate::tools::ToolChain;
 
@@ -15,6 +16,8 @@ use super::build_orchestrator::BuildOrchestrator;
 use super::config::PipelineConfig;
 use super::error::PipelineError;
 use super::metrics::SizeMetrics;
+use super::telemetry::MetricsCollector;
+use super::transform::WasmTransform;
 
 /// Main build pipeline orchestrator
 ///
@@ -87,9 +90,36 @@ impl<FS: FileSystem + Clone, CE: CommandExecutor + Clone> BuildPipeline<FS, CE>
         Self { orchestrator }
     }
 
+    /// Register a metrics collector to receive build events and metrics
+    ///
+    /// Multiple collectors may be registered; each receives every emission.
+    /// See [`crate::pipeline::telemetry`] for the event schema.
+    pub fn with_collector(mut self, collector: Arc<dyn MetricsCollector>) -> Self {
+        self.add_collector(collector);
+        self
+    }
+
+    /// Register a metrics collector on an existing pipeline
+    pub fn add_collector(&mut self, collector: Arc<dyn MetricsCollector>) {
+        self.orchestrator.add_collector(collector);
+    }
+
+    /// Register a custom transform applied after wasm-opt and wasm-snip
+    ///
+    /// Transforms run in registration order; see [`crate::pipeline::transform`].
+    pub fn with_transform(mut self, transform: Arc<dyn WasmTransform>) -> Self {
+        self.add_transform(transform);
+        self
+    }
+
+    /// Register a custom transform on an existing pipeline
+    pub fn add_transform(&mut self, transform: Arc<dyn WasmTransform>) {
+        self.orchestrator.add_transform(transform);
+    }
+
     /// Run the complete build pipeline
     ///
-    /// Executes: cargo build → wasm-bindgen → wasm-opt → (optional) wasm-snip
+    /// Executes: cargo build → wasm-bindgen → wasm-opt → (optional) wasm-snip → registered transforms
     ///
     /// # Returns
     /// Size metrics showing before/after optimization results
@@ -223,6 +253,16 @@ mod tests {
         // Pipeline successfully created with orchestrator
     }
 
+    #[test]
+    fn test_pipeline_accepts_multiple_collectors() {
+        use crate::pipeline::telemetry::{MemoryCollector, StdoutCollector};
+
+        let memory = Arc::new(MemoryCollector::new());
+        let _pipeline = BuildPipeline::new("/test/project", PipelineConfig::default())
+            .with_collector(Arc::new(StdoutCollector))
+            .with_collector(memory);
+    }
+
     #[test]
     fn test_new_pipeline_with_custom_config() {
         let config = PipelineConfig {
@@ -1,14 +1,23 @@
 //! Size metrics for build pipeline optimization tracking
 
+use crate::summary::CompressedSizes;
+use std::path::PathBuf;
+
 /// Size metrics for before/after comparison
 ///
 /// Tracks binary size reduction through the optimization pipeline.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SizeMetrics {
     /// Binary size before optimization (bytes)
     pub before_bytes: u64,
     /// Binary size after optimization (bytes)
     pub after_bytes: u64,
+    /// Optimized WASM file, when the pipeline produced one
+    pub output: Option<PathBuf>,
+    /// Compressed sizes before optimization
+    pub before_compressed: CompressedSizes,
+    /// Compressed sizes after optimization
+    pub after_compressed: CompressedSizes,
 }
 
 impl SizeMetrics {
@@ -5,26 +5,35 @@
 //! 2. wasm-bindgen with optimized flags
 //! 3. wasm-opt -Oz for aggressive size optimization
 //! 4. wasm-snip to remove panic infrastructure (optional)
+//! 5. Custom [`WasmTransform`]s registered by library users (optional)
+//! 6. DWARF moved into a `.debug.wasm` sidecar (optional)
 
 pub mod build_orchestrator;
+pub mod checkpoint;
 pub mod config;
+pub mod debug_split;
 pub mod error;
 pub mod executor;
 pub mod metrics;
 pub mod result_formatter;
 pub mod telemetry;
 pub mod tool_runner;
+pub mod transform;
 
 pub use build_orchestrator::BuildOrchestrator;
-pub use config::{BindgenTarget, PipelineConfig, WasmOptLevel, WasmTarget};
+pub use checkpoint::{BuildCheckpoint, Stage, StageRecord};
+pub use config::{BindgenTarget, PipelineConfig, StageEnv, WasmOptLevel, WasmTarget};
+pub use debug_split::{split_debug_info, DebugSplit};
 pub use error::PipelineError;
 pub use executor::BuildPipeline;
 pub use metrics::SizeMetrics;
 pub use result_formatter::ResultFormatter;
 pub use telemetry::{
-    BuildEvent, MemoryCollector, MetricData, MetricsCollector, NoOpCollector, StdoutCollector,
+    BuildEvent, FanOutCollector, MemoryCollector, MetricData, MetricsCollector, NoOpCollector,
+    StdoutCollector,
 };
 pub use tool_runner::ToolRunner;
+pub use transform::{TransformOutcome, WasmTransform};
 
 #[cfg(test)]
 mod tests {
@@ -36,6 +45,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 1024 * 1024, // 1 MB
             after_bytes: 512 * 1024,   // 512 KB
+            ..Default::default()
         };
 
         assert_eq!(metrics.reduction_bytes(), 512 * 1024);
@@ -46,8 +56,8 @@ mod tests {
     fn test_format_bytes_converts_to_readable_units() {
         use crate::fmt::format_bytes;
         assert_eq!(format_bytes(512), "512 B");
-        assert_eq!(format_bytes(1024), "1.00 KB");
-        assert_eq!(format_bytes(1024 * 1024), "1.00 MB");
+        assert_eq!(format_bytes(1024), "1.00 KiB");
+        assert_eq!(format_bytes(1024 * 1024), "1.00 MiB");
     }
 
     #[test]
@@ -62,6 +72,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 0,
             after_bytes: 100,
+            ..Default::default()
         };
 
         assert_eq!(metrics.reduction_bytes(), -100);
@@ -74,6 +85,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 512 * 1024,
             after_bytes: 1024 * 1024,
+            ..Default::default()
         };
 
         assert_eq!(metrics.reduction_bytes(), -(512 * 1024));
@@ -86,6 +98,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 1024,
             after_bytes: 1024,
+            ..Default::default()
         };
 
         assert_eq!(metrics.reduction_bytes(), 0);
@@ -99,10 +112,10 @@ mod tests {
         assert_eq!(format_bytes(0), "0 B");
         assert_eq!(format_bytes(1), "1 B");
         assert_eq!(format_bytes(1023), "1023 B");
-        assert_eq!(format_bytes(1024), "1.00 KB");
-        assert_eq!(format_bytes(1024 * 1023), "1023.00 KB");
-        assert_eq!(format_bytes(1024 * 1024), "1.00 MB");
-        assert_eq!(format_bytes(1024 * 1024 * 10), "10.00 MB");
+        assert_eq!(format_bytes(1024), "1.00 KiB");
+        assert_eq!(format_bytes(1024 * 1023), "1023.00 KiB");
+        assert_eq!(format_bytes(1024 * 1024), "1.00 MiB");
+        assert_eq!(format_bytes(1024 * 1024 * 10), "10.00 MiB");
     }
 
     #[test]
@@ -136,9 +149,16 @@ mod tests {
             profile: "dev".to_string(),
             target_dir: Some(PathBuf::from("/custom/target")),
             bindgen_target: BindgenTarget::NodeJs,
+            out_dir: None,
             run_wasm_opt: false,
             run_wasm_snip: true,
             opt_level: WasmOptLevel::O3,
+            checkpoint: false,
+            resume: false,
+            split_debug_info: false,
+            preopt_max_bytes: None,
+            env: StageEnv::default(),
+            wasm_opt_args: Vec::new(),
         };
 
         assert_eq!(config.target, WasmTarget::Wasm32Wasi);
@@ -156,6 +176,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 2 * 1024 * 1024 * 1024, // 2 GB
             after_bytes: 1024 * 1024 * 1024,      // 1 GB
+            ..Default::default()
         };
 
         assert_eq!(metrics.reduction_bytes(), 1024 * 1024 * 1024);
@@ -163,12 +184,12 @@ mod tests {
     }
 
     #[test]
-    fn test_format_bytes_with_gigabyte_sizes_formats_as_megabytes() {
+    fn test_format_bytes_with_gigabyte_sizes_formats_as_gibibytes() {
         use crate::fmt::format_bytes;
         // Test formatting of large sizes
         let gb = 1024 * 1024 * 1024;
-        assert_eq!(format_bytes(gb), "1024.00 MB");
-        assert_eq!(format_bytes(gb * 2), "2048.00 MB");
+        assert_eq!(format_bytes(gb), "1.00 GiB");
+        assert_eq!(format_bytes(gb * 2), "2.00 GiB");
     }
 
     #[test]
@@ -179,9 +200,16 @@ mod tests {
             profile: String::new(),
             target_dir: None,
             bindgen_target: BindgenTarget::Web,
+            out_dir: None,
             run_wasm_opt: false,
             run_wasm_snip: false,
             opt_level: WasmOptLevel::Oz,
+            checkpoint: false,
+            resume: false,
+            split_debug_info: false,
+            preopt_max_bytes: None,
+            env: StageEnv::default(),
+            wasm_opt_args: Vec::new(),
         };
 
         assert_eq!(config.target, WasmTarget::Wasm32UnknownUnknown);
@@ -195,6 +223,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 5_000_000_000, // ~5GB before
             after_bytes: 2_000_000_000,  // ~2GB after
+            ..Default::default()
         };
 
         assert_eq!(metrics.reduction_bytes(), 3_000_000_000);
@@ -204,6 +233,7 @@ mod tests {
         let regression_metrics = SizeMetrics {
             before_bytes: 1_000_000,
             after_bytes: 2_000_000,
+            ..Default::default()
         };
 
         assert_eq!(regression_metrics.reduction_bytes(), -1_000_000);
@@ -218,9 +248,16 @@ mod tests {
             profile: "release".to_string(),
             target_dir: Some(PathBuf::from("/path/with spaces/and-dashes")),
             bindgen_target: BindgenTarget::Web,
+            out_dir: None,
             run_wasm_opt: true,
             run_wasm_snip: false,
             opt_level: WasmOptLevel::Oz,
+            checkpoint: false,
+            resume: false,
+            split_debug_info: false,
+            preopt_max_bytes: None,
+            env: StageEnv::default(),
+            wasm_opt_args: Vec::new(),
         };
 
         assert!(config.target_dir.is_some());
@@ -234,6 +271,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 1000,
             after_bytes: 333,
+            ..Default::default()
         };
 
         let percent = metrics.reduction_percent();
@@ -250,6 +288,6 @@ mod tests {
         let formatted = format_bytes(bytes_1_5_kb);
 
         assert!(formatted.contains("1.50") || formatted.contains("1.5"));
-        assert!(formatted.contains("KB"));
+        assert!(formatted.contains("KiB"));
     }
 }
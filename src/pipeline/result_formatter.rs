@@ -37,6 +37,33 @@ impl ResultFormatter {
             println!("   {} No size reduction", style("→").dim());
         }
 
+        // Transfer sizes, for the compressors that are installed
+        let compressed = [
+            (
+                "Gzip:  ",
+                metrics.before_compressed.gzip_bytes,
+                metrics.after_compressed.gzip_bytes,
+            ),
+            (
+                "Brotli:",
+                metrics.before_compressed.brotli_bytes,
+                metrics.after_compressed.brotli_bytes,
+            ),
+        ];
+        for (label, before, after) in compressed {
+            let Some(after) = after else { continue };
+            let before = before
+                .map(|b| format!("{} → ", format_bytes(b)))
+                .unwrap_or_default();
+            println!(
+                "   {} {} {}{}",
+                style("→").dim(),
+                label,
+                before,
+                style(format_bytes(after)).green()
+            );
+        }
+
         println!(
             "\n{} {} Build complete!",
             SPARKLES,
@@ -48,6 +75,7 @@ impl ResultFormatter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::summary::CompressedSizes;
 
     #[test]
     fn test_result_formatter_exists() {
@@ -55,15 +83,36 @@ mod tests {
         ResultFormatter::print_summary(&SizeMetrics {
             before_bytes: 1000,
             after_bytes: 500,
+            ..Default::default()
         });
     }
 
+    #[test]
+    fn test_format_with_compressed_sizes() {
+        // Brotli missing before optimization: only the after size is shown
+        let metrics = SizeMetrics {
+            before_bytes: 500_000,
+            after_bytes: 250_000,
+            before_compressed: CompressedSizes {
+                gzip_bytes: Some(180_000),
+                brotli_bytes: None,
+            },
+            after_compressed: CompressedSizes {
+                gzip_bytes: Some(90_000),
+                brotli_bytes: Some(75_000),
+            },
+            ..Default::default()
+        };
+        ResultFormatter::print_summary(&metrics);
+    }
+
     #[test]
     fn test_format_with_unicode_content() {
         // Test that formatter handles emojis and unicode properly
         let metrics = SizeMetrics {
             before_bytes: 500_000,
             after_bytes: 250_000,
+            ..Default::default()
         };
         // Should not panic with unicode emojis in output
         ResultFormatter::print_summary(&metrics);
@@ -75,6 +124,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 5_000_000_000, // ~5GB
             after_bytes: 2_500_000_000,  // ~2.5GB
+            ..Default::default()
         };
         // Should handle large numbers without overflow
         ResultFormatter::print_summary(&metrics);
@@ -86,6 +136,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 1000,
             after_bytes: 1000,
+            ..Default::default()
         };
         // Should display "No size reduction" message
         ResultFormatter::print_summary(&metrics);
@@ -97,6 +148,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 1000,
             after_bytes: 1500,
+            ..Default::default()
         };
         // Should handle negative reduction gracefully
         ResultFormatter::print_summary(&metrics);
@@ -108,6 +160,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 100,
             after_bytes: 50,
+            ..Default::default()
         };
         ResultFormatter::print_summary(&metrics);
     }
@@ -118,6 +171,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 0,
             after_bytes: 0,
+            ..Default::default()
         };
         // Should not panic with division by zero
         ResultFormatter::print_summary(&metrics);
@@ -129,6 +183,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 1,
             after_bytes: 0,
+            ..Default::default()
         };
         ResultFormatter::print_summary(&metrics);
     }
@@ -139,6 +194,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 2000,
             after_bytes: 1000,
+            ..Default::default()
         };
         ResultFormatter::print_summary(&metrics);
         assert_eq!(metrics.reduction_percent(), 50.0);
@@ -150,6 +206,7 @@ mod tests {
         let metrics = SizeMetrics {
             before_bytes: 100_000,
             after_bytes: 1_000,
+            ..Default::default()
         };
         ResultFormatter::print_summary(&metrics);
         assert!(metrics.reduction_percent() > 90.0);
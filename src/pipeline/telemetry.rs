@@ -2,6 +2,47 @@
 //!
 //! Provides pluggable metrics collection for integration with monitoring systems
 //! like Prometheus, DataDog, or custom dashboards.
+//!
+//! [`MetricsCollector`] is object-safe, so embedders can register any number of
+//! their own sinks on a pipeline as `Arc<dyn MetricsCollector>`; a
+//! [`FanOutCollector`] forwards every event and metric to each of them.
+//!
+//! # Event schema
+//!
+//! A pipeline run emits, in order:
+//!
+//! | Emission | Name | Metadata / tags |
+//! |----------|------|-----------------|
+//! | event | `BuildStarted` | `target`, `profile` |
+//! | metric | `cargo_build_duration_ms`, `cargo_build_size_bytes` | `stage` / `label` |
+//! | metric | `wasm_bindgen_duration_ms`, `wasm_bindgen_size_bytes` | `stage` / `label` |
+//! | event | `OptimizationStarted` | `tool` (`wasm-opt` or `wasm-snip`), `level` (wasm-opt only) |
+//! | metric | `wasm_opt_duration_ms`, `wasm_opt_size_bytes` | `stage` / `label` |
+//! | event | `OptimizationCompleted` | `tool` |
+//! | metric | `wasm_snip_duration_ms`, `wasm_snip_size_bytes` | `stage` / `label` |
+//! | event | `OptimizationStarted`, `OptimizationCompleted` per custom transform | `tool` (`transform`), `transform` |
+//! | metric | `transform_duration_ms`, `transform_size_bytes` per custom transform | `stage` / `label`, `transform` |
+//! | metric | `final_size_bytes` | `label` |
+//! | event | `BuildCompleted` | `before_bytes`, `after_bytes` |
+//! | event | `BuildFailed` (instead of `BuildCompleted`) | `error` |
+//!
+//! Optimization events and metrics are only emitted for stages that run.
+//! `flush()` is called once on every collector after the final event.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use wasm_slim::pipeline::{BuildPipeline, MemoryCollector, PipelineConfig, StdoutCollector};
+//!
+//! let memory = Arc::new(MemoryCollector::new());
+//! let pipeline = BuildPipeline::new(".", PipelineConfig::default())
+//!     .with_collector(Arc::new(StdoutCollector))
+//!     .with_collector(memory.clone());
+//!
+//! let _ = pipeline.build();
+//! println!("{} events recorded", memory.events().len());
+//! ```
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -128,6 +169,79 @@ impl MetricsCollector for StdoutCollector {
     }
 }
 
+/// Collector that forwards to any number of registered collectors
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use wasm_slim::pipeline::{FanOutCollector, MemoryCollector, MetricData, MetricsCollector};
+///
+/// let a = Arc::new(MemoryCollector::new());
+/// let b = Arc::new(MemoryCollector::new());
+/// let fan_out = FanOutCollector::new().with(a.clone()).with(b.clone());
+///
+/// fan_out.record_metric(MetricData::new("size", 1.0));
+/// assert_eq!(a.metrics().len(), 1);
+/// assert_eq!(b.metrics().len(), 1);
+/// ```
+#[derive(Default, Clone)]
+pub struct FanOutCollector {
+    collectors: Vec<Arc<dyn MetricsCollector>>,
+}
+
+impl FanOutCollector {
+    /// Create an empty fan-out collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a collector (builder style)
+    pub fn with(mut self, collector: Arc<dyn MetricsCollector>) -> Self {
+        self.register(collector);
+        self
+    }
+
+    /// Register a collector
+    pub fn register(&mut self, collector: Arc<dyn MetricsCollector>) {
+        self.collectors.push(collector);
+    }
+
+    /// Number of registered collectors
+    pub fn len(&self) -> usize {
+        self.collectors.len()
+    }
+
+    /// Whether no collectors are registered
+    pub fn is_empty(&self) -> bool {
+        self.collectors.is_empty()
+    }
+}
+
+impl MetricsCollector for FanOutCollector {
+    fn name(&self) -> &str {
+        "fan-out"
+    }
+
+    fn record_event(&self, event: BuildEvent, metadata: HashMap<String, String>) {
+        for collector in &self.collectors {
+            collector.record_event(event, metadata.clone());
+        }
+    }
+
+    fn record_metric(&self, metric: MetricData) {
+        for collector in &self.collectors {
+            collector.record_metric(metric.clone());
+        }
+    }
+
+    fn flush(&self) {
+        for collector in &self.collectors {
+            collector.flush();
+        }
+    }
+}
+
 /// Event with metadata
 type EventRecord = (BuildEvent, HashMap<String, String>);
 
@@ -232,6 +346,45 @@ mod tests {
         assert_eq!(metric.tags.get("region"), Some(&"us-west".to_string()));
     }
 
+    #[test]
+    fn test_fan_out_collector_forwards_to_every_collector() {
+        let first = Arc::new(MemoryCollector::new());
+        let second = Arc::new(MemoryCollector::new());
+        let fan_out = FanOutCollector::new()
+            .with(first.clone())
+            .with(second.clone() as Arc<dyn MetricsCollector>);
+        assert_eq!(fan_out.len(), 2);
+
+        fan_out.record_event(BuildEvent::BuildStarted, HashMap::new());
+        fan_out.record_size("wasm", 2048);
+        fan_out.flush();
+
+        for collector in [&first, &second] {
+            assert_eq!(collector.events().len(), 1);
+            assert_eq!(collector.metrics()[0].name, "wasm_size_bytes");
+        }
+    }
+
+    #[test]
+    fn test_empty_fan_out_collector_is_noop() {
+        let fan_out = FanOutCollector::new();
+        assert!(fan_out.is_empty());
+        fan_out.record_event(BuildEvent::BuildFailed, HashMap::new());
+        fan_out.record_metric(MetricData::new("x", 1.0));
+    }
+
+    #[test]
+    fn test_metrics_collector_is_object_safe() {
+        let collectors: Vec<Box<dyn MetricsCollector>> = vec![
+            Box::new(NoOpCollector),
+            Box::new(StdoutCollector),
+            Box::new(MemoryCollector::new()),
+            Box::new(FanOutCollector::new()),
+        ];
+        let names: Vec<_> = collectors.iter().map(|c| c.name()).collect();
+        assert_eq!(names, vec!["noop", "stdout", "memory", "fan-out"]);
+    }
+
     #[test]
     fn test_noop_collector_does_nothing() {
         let collector = NoOpCollector;
@@ -3,10 +3,13 @@
 //! Handles execution of individual tools: cargo, wasm-bindgen, wasm-opt, wasm-snip
 
 use crate::infra::{CommandExecutor, FileSystem};
+use crate::summary::CompressedSizes;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use super::checkpoint::Stage;
 use super::config::PipelineConfig;
+use super::debug_split;
 use super::error::PipelineError;
 
 /// Executes individual build tools
@@ -28,11 +31,18 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
         }
     }
 
+    /// Sizes of a file after gzip and brotli, where those are available
+    pub fn compressed_sizes(&self, wasm_file: &Path) -> CompressedSizes {
+        CompressedSizes::measure(wasm_file)
+    }
+
     /// Execute cargo build for wasm32-unknown-unknown target
     pub fn cargo_build(&self) -> Result<PathBuf, PipelineError> {
         let project_root = self.project_root.clone();
         let target = self.config.target.as_str();
         let target_dir = self.config.target_dir.clone();
+        let cargo_env = self.config.env.vars_for(Stage::CargoBuild);
+        let split_debug_info = self.config.split_debug_info;
 
         let status = self.cmd_executor.run(
             |cmd| {
@@ -57,6 +67,15 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
                     .env_remove("CARGO_LLVM_COV")
                     .env_remove("CARGO_LLVM_COV_TARGET_DIR");
 
+                // Emit DWARF regardless of the profile; it is split off at the end
+                if split_debug_info {
+                    cmd.env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+                        .env("CARGO_PROFILE_RELEASE_STRIP", "none");
+                }
+
+                // User-configured variables are applied last so they win
+                cmd.envs(&cargo_env);
+
                 cmd
             },
             "cargo",
@@ -106,9 +125,15 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
 
     /// Run wasm-bindgen on the WASM file
     pub fn run_wasm_bindgen(&self, wasm_file: &Path) -> Result<PathBuf, PipelineError> {
-        let out_dir = self.project_root.join("pkg");
+        let out_dir = self
+            .config
+            .out_dir
+            .clone()
+            .unwrap_or_else(|| self.project_root.join("pkg"));
         let wasm_file = wasm_file.to_path_buf();
         let bindgen_target = self.config.bindgen_target.as_str();
+        let env = self.config.env.vars_for(Stage::WasmBindgen);
+        let keep_debug = self.config.split_debug_info;
 
         let status = self.cmd_executor.run(
             |cmd| {
@@ -116,7 +141,11 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
                     .arg("--out-dir")
                     .arg(&out_dir)
                     .arg("--target")
-                    .arg(bindgen_target)
+                    .arg(bindgen_target);
+                if keep_debug {
+                    cmd.arg("--keep-debug");
+                }
+                cmd.envs(&env)
             },
             "wasm-bindgen",
         )?;
@@ -148,6 +177,14 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
     pub fn run_wasm_opt(&self, wasm_file: &Path) -> Result<(), PipelineError> {
         let wasm_file = wasm_file.to_path_buf();
         let opt_level_arg = self.config.opt_level.as_arg().to_string();
+        let env = self.config.env.vars_for(Stage::WasmOpt);
+        let extra_args = self.config.wasm_opt_args.clone();
+        // `-g` keeps (and updates) DWARF through the optimization passes
+        let debug_args: &[&str] = if self.config.split_debug_info {
+            &["-g"]
+        } else {
+            &[]
+        };
 
         let status = self.cmd_executor.run(
             |cmd| {
@@ -160,6 +197,9 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
                     .arg("--enable-bulk-memory")
                     .arg("--enable-sign-ext")
                     .arg("--enable-nontrapping-float-to-int")
+                    .args(debug_args)
+                    .args(&extra_args)
+                    .envs(&env)
             },
             "wasm-opt",
         )?;
@@ -176,6 +216,7 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
         // Create a temporary file for output
         let temp_file = wasm_file.with_extension("wasm.tmp");
         let wasm_file = wasm_file.to_path_buf();
+        let env = self.config.env.vars_for(Stage::WasmSnip);
 
         let status = self.cmd_executor.run(
             |cmd| {
@@ -183,6 +224,7 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
                     .arg("-o")
                     .arg(&temp_file)
                     .arg("--snip-rust-panicking-code")
+                    .envs(&env)
             },
             "wasm-snip",
         )?;
@@ -201,6 +243,34 @@ impl<FS: FileSystem, CE: CommandExecutor> ToolRunner<FS, CE> {
 
         Ok(())
     }
+
+    /// Move the module's DWARF into a `.debug.wasm` sidecar next to it
+    ///
+    /// Returns the sidecar path and the bytes moved, or `None` when the
+    /// module carries no DWARF.
+    pub fn split_debug_info(
+        &self,
+        wasm_file: &Path,
+    ) -> Result<Option<(PathBuf, u64)>, PipelineError> {
+        let module = fs::read(wasm_file).map_err(PipelineError::Io)?;
+        let sidecar = debug_split::sidecar_path(wasm_file);
+        // Resolved relative to the module's URL, so the pair can be served together
+        let sidecar_url = sidecar
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let Some(split) = debug_split::split_debug_info(&module, &sidecar_url)? else {
+            return Ok(None);
+        };
+        self.fs
+            .write(&sidecar, &split.sidecar)
+            .map_err(PipelineError::Io)?;
+        self.fs
+            .write(wasm_file, &split.stripped)
+            .map_err(PipelineError::Io)?;
+        Ok(Some((sidecar, split.debug_bytes)))
+    }
 }
 
 #[cfg(test)]
@@ -262,11 +332,16 @@ mod tests {
         }
     }
 
+    /// Environment variables captured from the last executed command
+    type CapturedEnv = Vec<(String, Option<String>)>;
+
     // Mock CommandExecutor for testing
     #[derive(Clone)]
     struct MockCommandExecutor {
         exit_code: Arc<Mutex<i32>>,
         should_fail: Arc<Mutex<bool>>,
+        envs: Arc<Mutex<CapturedEnv>>,
+        args: Arc<Mutex<Vec<String>>>,
     }
 
     impl MockCommandExecutor {
@@ -274,6 +349,8 @@ mod tests {
             Self {
                 exit_code: Arc::new(Mutex::new(0)),
                 should_fail: Arc::new(Mutex::new(false)),
+                envs: Arc::new(Mutex::new(Vec::new())),
+                args: Arc::new(Mutex::new(Vec::new())),
             }
         }
 
@@ -284,6 +361,20 @@ mod tests {
                 .expect("MockCommandExecutor lock should never be poisoned in tests") = code;
         }
 
+        fn envs(&self) -> CapturedEnv {
+            self.envs
+                .lock()
+                .expect("MockCommandExecutor lock should never be poisoned in tests")
+                .clone()
+        }
+
+        fn args(&self) -> Vec<String> {
+            self.args
+                .lock()
+                .expect("MockCommandExecutor lock should never be poisoned in tests")
+                .clone()
+        }
+
         fn set_should_fail(&self, fail: bool) {
             *self
                 .should_fail
@@ -293,7 +384,27 @@ mod tests {
     }
 
     impl CommandExecutor for MockCommandExecutor {
-        fn status(&self, _cmd: &mut Command) -> io::Result<std::process::ExitStatus> {
+        fn status(&self, cmd: &mut Command) -> io::Result<std::process::ExitStatus> {
+            *self
+                .envs
+                .lock()
+                .expect("MockCommandExecutor lock should never be poisoned in tests") = cmd
+                .get_envs()
+                .map(|(k, v)| {
+                    (
+                        k.to_string_lossy().to_string(),
+                        v.map(|v| v.to_string_lossy().to_string()),
+                    )
+                })
+                .collect();
+            *self
+                .args
+                .lock()
+                .expect("MockCommandExecutor lock should never be poisoned in tests") = cmd
+                .get_args()
+                .map(|a| a.to_string_lossy().to_string())
+                .collect();
+
             if *self
                 .should_fail
                 .lock()
@@ -326,6 +437,109 @@ mod tests {
         assert_eq!(runner.config.target.as_str(), config.target.as_str());
     }
 
+    #[test]
+    fn test_cargo_build_applies_configured_env_after_stripping_coverage_vars() {
+        let mut config = PipelineConfig::default();
+        config
+            .env
+            .cargo
+            .insert("RUSTFLAGS".to_string(), "-C opt-level=z".to_string());
+        config
+            .env
+            .wasm_opt
+            .insert("BINARYEN_CORES".to_string(), "2".to_string());
+        let cmd_executor = MockCommandExecutor::new();
+        cmd_executor.set_exit_code(1);
+
+        let runner = ToolRunner::new(
+            PathBuf::from("/test"),
+            config,
+            MockFileSystem::new(),
+            cmd_executor.clone(),
+        );
+        let _ = runner.cargo_build();
+
+        let envs = cmd_executor.envs();
+        assert!(envs.contains(&("RUSTFLAGS".to_string(), Some("-C opt-level=z".to_string()))));
+        assert!(!envs.iter().any(|(k, _)| k == "BINARYEN_CORES"));
+    }
+
+    #[test]
+    fn test_run_wasm_opt_appends_tuning_args() {
+        let config = PipelineConfig {
+            wasm_opt_args: vec!["--converge".to_string()],
+            ..Default::default()
+        };
+        let cmd_executor = MockCommandExecutor::new();
+
+        let runner = ToolRunner::new(
+            PathBuf::from("/test"),
+            config,
+            MockFileSystem::new(),
+            cmd_executor.clone(),
+        );
+        runner.run_wasm_opt(Path::new("/test/input.wasm")).unwrap();
+
+        assert_eq!(
+            cmd_executor.args().last().map(String::as_str),
+            Some("--converge")
+        );
+    }
+
+    #[test]
+    fn test_split_debug_info_keeps_dwarf_through_every_tool() {
+        let config = PipelineConfig {
+            split_debug_info: true,
+            ..Default::default()
+        };
+        let cmd_executor = MockCommandExecutor::new();
+        let runner = ToolRunner::new(
+            PathBuf::from("/test"),
+            config,
+            MockFileSystem::new(),
+            cmd_executor.clone(),
+        );
+
+        cmd_executor.set_exit_code(1);
+        let _ = runner.cargo_build();
+        assert!(cmd_executor.envs().contains(&(
+            "CARGO_PROFILE_RELEASE_DEBUG".to_string(),
+            Some("true".to_string())
+        )));
+        let _ = runner.run_wasm_bindgen(Path::new("/test/input.wasm"));
+        assert!(cmd_executor.args().contains(&"--keep-debug".to_string()));
+
+        cmd_executor.set_exit_code(0);
+        runner.run_wasm_opt(Path::new("/test/input.wasm")).unwrap();
+        assert!(cmd_executor.args().contains(&"-g".to_string()));
+    }
+
+    #[test]
+    fn test_run_wasm_opt_applies_stage_env() {
+        let mut config = PipelineConfig::default();
+        config
+            .env
+            .global
+            .insert("RUST_LOG".to_string(), "warn".to_string());
+        config
+            .env
+            .wasm_opt
+            .insert("BINARYEN_CORES".to_string(), "2".to_string());
+        let cmd_executor = MockCommandExecutor::new();
+
+        let runner = ToolRunner::new(
+            PathBuf::from("/test"),
+            config,
+            MockFileSystem::new(),
+            cmd_executor.clone(),
+        );
+        runner.run_wasm_opt(Path::new("/test/input.wasm")).unwrap();
+
+        let envs = cmd_executor.envs();
+        assert!(envs.contains(&("BINARYEN_CORES".to_string(), Some("2".to_string()))));
+        assert!(envs.contains(&("RUST_LOG".to_string(), Some("warn".to_string()))));
+    }
+
     #[test]
     fn test_cargo_build_with_nonexistent_tool_returns_error() {
         let config = PipelineConfig::default();
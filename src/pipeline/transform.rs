@@ -0,0 +1,211 @@
+//! Custom post-processing transforms
+//!
+//! Library users can register [`WasmTransform`]s on a [`BuildPipeline`] to
+//! rewrite the module after wasm-opt and wasm-snip, before debug info is
+//! split and the output is final: obfuscation, watermarking or injecting
+//! custom sections without forking the pipeline.
+//!
+//! Transforms run in registration order, each receiving the previous one's
+//! output. A transform error, or output that is not a WASM module, fails the
+//! build with [`PipelineError::Transform`] naming the transform.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use wasm_slim::pipeline::{BuildPipeline, PipelineConfig, WasmTransform};
+//!
+//! /// Appends a `watermark` custom section
+//! struct Watermark;
+//!
+//! impl WasmTransform for Watermark {
+//!     fn name(&self) -> &str {
+//!         "watermark"
+//!     }
+//!
+//!     fn transform(&self, wasm: &[u8]) -> anyhow::Result<Vec<u8>> {
+//!         let mut out = wasm.to_vec();
+//!         let payload = b"\x09watermarkbuild-42";
+//!         out.push(0);
+//!         out.push(payload.len() as u8);
+//!         out.extend_from_slice(payload);
+//!         Ok(out)
+//!     }
+//! }
+//!
+//! let pipeline = BuildPipeline::new(".", PipelineConfig::default())
+//!     .with_transform(Arc::new(Watermark));
+//! let metrics = pipeline.build()?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! [`BuildPipeline`]: super::BuildPipeline
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::error::PipelineError;
+use super::telemetry::{BuildEvent, MetricData, MetricsCollector};
+
+/// Magic number and version 1 every WASM module starts with
+const WASM_HEADER: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+/// A post-processing step turning one WASM module into another
+pub trait WasmTransform: Send + Sync {
+    /// Name used in output, errors and telemetry tags
+    fn name(&self) -> &str;
+
+    /// Transform the module bytes
+    fn transform(&self, wasm: &[u8]) -> anyhow::Result<Vec<u8>>;
+}
+
+/// Size change of one transform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformOutcome {
+    /// Transform name
+    pub name: String,
+    /// Module size before the transform
+    pub before_bytes: u64,
+    /// Module size after the transform
+    pub after_bytes: u64,
+}
+
+/// Run `transforms` in order over `module`
+///
+/// Emits `OptimizationStarted`/`OptimizationCompleted` events and
+/// `transform_duration_ms`/`transform_size_bytes` metrics tagged with the
+/// transform name.
+pub(crate) fn apply_transforms(
+    transforms: &[Arc<dyn WasmTransform>],
+    mut module: Vec<u8>,
+    collector: &dyn MetricsCollector,
+) -> Result<(Vec<u8>, Vec<TransformOutcome>), PipelineError> {
+    let mut outcomes = Vec::with_capacity(transforms.len());
+    for transform in transforms {
+        let name = transform.name().to_string();
+        let tags = HashMap::from([
+            ("tool".to_string(), "transform".to_string()),
+            ("transform".to_string(), name.clone()),
+        ]);
+        collector.record_event(BuildEvent::OptimizationStarted, tags.clone());
+
+        let started = Instant::now();
+        let output = transform
+            .transform(&module)
+            .map_err(|e| PipelineError::Transform {
+                name: name.clone(),
+                message: format!("{:#}", e),
+            })?;
+        if !output.starts_with(&WASM_HEADER) {
+            return Err(PipelineError::Transform {
+                name,
+                message: "output is not a WASM module".to_string(),
+            });
+        }
+
+        collector.record_metric(
+            MetricData::new(
+                "transform_duration_ms",
+                started.elapsed().as_millis() as f64,
+            )
+            .with_tag("stage", "transform")
+            .with_tag("transform", name.clone()),
+        );
+        collector.record_metric(
+            MetricData::new("transform_size_bytes", output.len() as f64)
+                .with_tag("label", "transform")
+                .with_tag("transform", name.clone()),
+        );
+        collector.record_event(BuildEvent::OptimizationCompleted, tags);
+
+        outcomes.push(TransformOutcome {
+            name,
+            before_bytes: module.len() as u64,
+            after_bytes: output.len() as u64,
+        });
+        module = output;
+    }
+    Ok((module, outcomes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::telemetry::MemoryCollector;
+
+    /// Appends `suffix` as the payload of an empty-named custom section
+    struct Append(&'static str, &'static [u8]);
+
+    impl WasmTransform for Append {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn transform(&self, wasm: &[u8]) -> anyhow::Result<Vec<u8>> {
+            let mut out = wasm.to_vec();
+            out.extend_from_slice(&[0, self.1.len() as u8 + 1, 0]);
+            out.extend_from_slice(self.1);
+            Ok(out)
+        }
+    }
+
+    struct Failing;
+
+    impl WasmTransform for Failing {
+        fn name(&self) -> &str {
+            "obfuscate"
+        }
+
+        fn transform(&self, _wasm: &[u8]) -> anyhow::Result<Vec<u8>> {
+            anyhow::bail!("key not configured")
+        }
+    }
+
+    #[test]
+    fn test_apply_transforms_runs_in_order_and_records_sizes() {
+        let collector = MemoryCollector::new();
+        let transforms: Vec<Arc<dyn WasmTransform>> = vec![
+            Arc::new(Append("first", b"ab")),
+            Arc::new(Append("second", b"cdef")),
+        ];
+
+        let (module, outcomes) =
+            apply_transforms(&transforms, WASM_HEADER.to_vec(), &collector).unwrap();
+
+        assert!(module.ends_with(b"ab\x00\x05\x00cdef"));
+        assert_eq!(
+            outcomes
+                .iter()
+                .map(|o| (o.name.as_str(), o.before_bytes, o.after_bytes))
+                .collect::<Vec<_>>(),
+            vec![("first", 8, 13), ("second", 13, 20)]
+        );
+        let sizes: Vec<(String, f64)> = collector
+            .metrics()
+            .into_iter()
+            .filter(|m| m.name == "transform_size_bytes")
+            .map(|m| (m.tags["transform"].clone(), m.value))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![("first".to_string(), 13.0), ("second".to_string(), 20.0)]
+        );
+        assert_eq!(collector.events().len(), 4);
+    }
+
+    #[test]
+    fn test_apply_transforms_names_the_failing_transform() {
+        let collector = MemoryCollector::new();
+        let transforms: Vec<Arc<dyn WasmTransform>> = vec![Arc::new(Failing)];
+        let err = apply_transforms(&transforms, WASM_HEADER.to_vec(), &collector).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Transform 'obfuscate' failed: key not configured"
+        );
+
+        let transforms: Vec<Arc<dyn WasmTransform>> = vec![Arc::new(Append("garble", b""))];
+        let err = apply_transforms(&transforms, b"not wasm".to_vec(), &collector).unwrap_err();
+        assert!(err.to_string().contains("output is not a WASM module"));
+    }
+}
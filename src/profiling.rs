@@ -0,0 +1,362 @@
+//! Self-profiling of wasm-slim's own runtime
+//!
+//! Enabled with the global `--self-profile` flag. Timings are recorded through
+//! the telemetry layer ([`MetricsCollector`]) and grouped into phases:
+//!
+//! | Phase      | What is timed                                            |
+//! |------------|----------------------------------------------------------|
+//! | `scanning` | Walking the source tree (asset and panic detection)      |
+//! | `parsing`  | Config loading and parsing tool output                   |
+//! | `spawning` | External processes (cargo, cargo metadata, twiggy, ...)  |
+//! | `pipeline` | Build pipeline stages reported by the orchestrator       |
+//!
+//! Phases may nest (a pipeline stage includes the tool it spawns), so shares
+//! of wall time need not add up to 100%.
+//!
+//! # Examples
+//!
+//! ```
+//! use wasm_slim::profiling::{ProfilePhase, SelfProfiler};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let profiler = Arc::new(SelfProfiler::new());
+//! profiler.record(ProfilePhase::Spawning, "cargo", Duration::from_millis(120));
+//! {
+//!     let _span = profiler.span(ProfilePhase::Scanning, "asset scan");
+//!     // ... work ...
+//! }
+//! println!("{}", profiler.render());
+//! ```
+
+use crate::pipeline::telemetry::{BuildEvent, MemoryCollector, MetricData, MetricsCollector};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Process-wide profiler, installed by [`enable`]
+static PROFILER: OnceLock<Arc<SelfProfiler>> = OnceLock::new();
+
+/// Phase of wasm-slim's own work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProfilePhase {
+    /// Walking the source tree
+    Scanning,
+    /// Parsing config files and tool output
+    Parsing,
+    /// Running external processes
+    Spawning,
+    /// Build pipeline stages
+    Pipeline,
+}
+
+impl ProfilePhase {
+    /// Tag value used in recorded metrics
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProfilePhase::Scanning => "scanning",
+            ProfilePhase::Parsing => "parsing",
+            ProfilePhase::Spawning => "spawning",
+            ProfilePhase::Pipeline => "pipeline",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "scanning" => Some(ProfilePhase::Scanning),
+            "parsing" => Some(ProfilePhase::Parsing),
+            "spawning" => Some(ProfilePhase::Spawning),
+            "pipeline" => Some(ProfilePhase::Pipeline),
+            _ => None,
+        }
+    }
+}
+
+/// Aggregated timing for one label within a phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelTiming {
+    /// What was timed (e.g., `cargo`, `asset scan`)
+    pub label: String,
+    /// Number of recorded spans
+    pub count: usize,
+    /// Total time in milliseconds
+    pub total_ms: f64,
+}
+
+/// Aggregated timing for one phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    /// Phase
+    pub phase: ProfilePhase,
+    /// Total time in milliseconds
+    pub total_ms: f64,
+    /// Per-label breakdown, slowest first
+    pub labels: Vec<LabelTiming>,
+}
+
+/// Collects timings of wasm-slim's own work
+///
+/// Also usable as a pipeline [`MetricsCollector`]: stage durations reported
+/// by the orchestrator are filed under [`ProfilePhase::Pipeline`].
+pub struct SelfProfiler {
+    metrics: MemoryCollector,
+    started: Instant,
+}
+
+impl Default for SelfProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfProfiler {
+    /// Create a profiler; wall time is measured from this point
+    pub fn new() -> Self {
+        Self {
+            metrics: MemoryCollector::new(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Record a finished span
+    pub fn record(&self, phase: ProfilePhase, label: &str, duration: Duration) {
+        self.metrics.record_metric(
+            MetricData::new(
+                format!("{}_duration_ms", label),
+                duration.as_secs_f64() * 1000.0,
+            )
+            .with_tag("phase", phase.as_str())
+            .with_tag("label", label),
+        );
+    }
+
+    /// Start a span that is recorded when dropped
+    pub fn span(self: &Arc<Self>, phase: ProfilePhase, label: impl Into<String>) -> ProfileSpan {
+        ProfileSpan {
+            active: Some((Arc::clone(self), Instant::now())),
+            phase,
+            label: label.into(),
+        }
+    }
+
+    /// Time elapsed since the profiler was created
+    pub fn wall_time(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Timings grouped by phase, then by label (slowest first)
+    pub fn breakdown(&self) -> Vec<PhaseTiming> {
+        let mut grouped: BTreeMap<ProfilePhase, BTreeMap<String, (usize, f64)>> = BTreeMap::new();
+        for metric in self.metrics.metrics() {
+            let Some(phase) = metric
+                .tags
+                .get("phase")
+                .and_then(|t| ProfilePhase::from_tag(t))
+            else {
+                continue;
+            };
+            let label = metric.tags.get("label").cloned().unwrap_or(metric.name);
+            let entry = grouped
+                .entry(phase)
+                .or_default()
+                .entry(label)
+                .or_insert((0, 0.0));
+            entry.0 += 1;
+            entry.1 += metric.value;
+        }
+
+        grouped
+            .into_iter()
+            .map(|(phase, labels)| {
+                let mut labels: Vec<LabelTiming> = labels
+                    .into_iter()
+                    .map(|(label, (count, total_ms))| LabelTiming {
+                        label,
+                        count,
+                        total_ms,
+                    })
+                    .collect();
+                labels.sort_by(|a, b| {
+                    b.total_ms
+                        .total_cmp(&a.total_ms)
+                        .then_with(|| a.label.cmp(&b.label))
+                });
+                PhaseTiming {
+                    phase,
+                    total_ms: labels.iter().map(|l| l.total_ms).sum(),
+                    labels,
+                }
+            })
+            .collect()
+    }
+
+    /// Human-readable breakdown table
+    pub fn render(&self) -> String {
+        let wall_ms = self.wall_time().as_secs_f64() * 1000.0;
+        let mut out = format!("Self-profile (wall time {:.1} ms)\n", wall_ms);
+
+        let breakdown = self.breakdown();
+        if breakdown.is_empty() {
+            out.push_str("  No spans recorded\n");
+            return out;
+        }
+
+        for phase in breakdown {
+            out.push_str(&format!(
+                "  {:<10} {:>10.1} ms {:>6.1}%\n",
+                phase.phase.as_str(),
+                phase.total_ms,
+                share(phase.total_ms, wall_ms)
+            ));
+            for label in phase.labels {
+                out.push_str(&format!(
+                    "    {:<30} {:>10.1} ms  x{}\n",
+                    label.label, label.total_ms, label.count
+                ));
+            }
+        }
+        out
+    }
+}
+
+fn share(part_ms: f64, wall_ms: f64) -> f64 {
+    if wall_ms > 0.0 {
+        (part_ms / wall_ms * 100.0).min(100.0)
+    } else {
+        0.0
+    }
+}
+
+impl MetricsCollector for SelfProfiler {
+    fn name(&self) -> &str {
+        "self-profile"
+    }
+
+    fn record_event(&self, _event: BuildEvent, _metadata: HashMap<String, String>) {}
+
+    fn record_metric(&self, metric: MetricData) {
+        if metric.tags.contains_key("phase") {
+            self.metrics.record_metric(metric);
+        } else if let Some(stage) = metric.tags.get("stage").cloned() {
+            // Stage durations from the pipeline; sizes are not timings
+            self.metrics.record_metric(
+                metric
+                    .with_tag("phase", ProfilePhase::Pipeline.as_str())
+                    .with_tag("label", stage),
+            );
+        }
+    }
+}
+
+/// Timing span; records its duration when dropped
+#[must_use = "the span is recorded when dropped"]
+pub struct ProfileSpan {
+    active: Option<(Arc<SelfProfiler>, Instant)>,
+    phase: ProfilePhase,
+    label: String,
+}
+
+impl Drop for ProfileSpan {
+    fn drop(&mut self) {
+        if let Some((profiler, start)) = self.active.take() {
+            profiler.record(self.phase, &self.label, start.elapsed());
+        }
+    }
+}
+
+/// Install the process-wide profiler (idempotent)
+pub fn enable() -> Arc<SelfProfiler> {
+    Arc::clone(PROFILER.get_or_init(|| Arc::new(SelfProfiler::new())))
+}
+
+/// The process-wide profiler, if `--self-profile` is active
+pub fn global() -> Option<Arc<SelfProfiler>> {
+    PROFILER.get().cloned()
+}
+
+/// Start a span on the process-wide profiler (no-op when disabled)
+pub fn span(phase: ProfilePhase, label: impl Into<String>) -> ProfileSpan {
+    match PROFILER.get() {
+        Some(profiler) => profiler.span(phase, label),
+        None => ProfileSpan {
+            active: None,
+            phase,
+            label: String::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_groups_by_phase_and_sorts_labels() {
+        let profiler = SelfProfiler::new();
+        profiler.record(ProfilePhase::Spawning, "twiggy", Duration::from_millis(10));
+        profiler.record(ProfilePhase::Spawning, "cargo", Duration::from_millis(50));
+        profiler.record(ProfilePhase::Spawning, "twiggy", Duration::from_millis(15));
+        profiler.record(
+            ProfilePhase::Scanning,
+            "asset scan",
+            Duration::from_millis(5),
+        );
+
+        let breakdown = profiler.breakdown();
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown[0].phase, ProfilePhase::Scanning);
+
+        let spawning = &breakdown[1];
+        assert_eq!(spawning.phase, ProfilePhase::Spawning);
+        assert!((spawning.total_ms - 75.0).abs() < 0.01);
+        assert_eq!(spawning.labels[0].label, "cargo");
+        assert_eq!(spawning.labels[1].label, "twiggy");
+        assert_eq!(spawning.labels[1].count, 2);
+        assert!((spawning.labels[1].total_ms - 25.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_pipeline_stage_durations_are_filed_under_pipeline() {
+        let profiler = SelfProfiler::new();
+        profiler.record_duration("wasm_opt", Duration::from_millis(40));
+        profiler.record_size("wasm_opt", 1024);
+
+        let breakdown = profiler.breakdown();
+        assert_eq!(breakdown.len(), 1);
+        assert_eq!(breakdown[0].phase, ProfilePhase::Pipeline);
+        assert_eq!(breakdown[0].labels.len(), 1);
+        assert_eq!(breakdown[0].labels[0].label, "wasm_opt");
+    }
+
+    #[test]
+    fn test_span_records_on_drop() {
+        let profiler = Arc::new(SelfProfiler::new());
+        {
+            let _span = profiler.span(ProfilePhase::Parsing, "config");
+        }
+
+        let breakdown = profiler.breakdown();
+        assert_eq!(breakdown[0].phase, ProfilePhase::Parsing);
+        assert_eq!(breakdown[0].labels[0].label, "config");
+        assert_eq!(breakdown[0].labels[0].count, 1);
+    }
+
+    #[test]
+    fn test_render_lists_phases_and_labels() {
+        let profiler = SelfProfiler::new();
+        assert!(profiler.render().contains("No spans recorded"));
+
+        profiler.record(ProfilePhase::Spawning, "cargo", Duration::from_millis(3));
+        let rendered = profiler.render();
+        assert!(rendered.starts_with("Self-profile (wall time"));
+        assert!(rendered.contains("spawning"));
+        assert!(rendered.contains("cargo"));
+        assert!(rendered.contains("x1"));
+    }
+
+    #[test]
+    fn test_share_handles_zero_wall_time() {
+        assert_eq!(share(10.0, 0.0), 0.0);
+        assert_eq!(share(50.0, 200.0), 25.0);
+    }
+}
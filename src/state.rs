@@ -0,0 +1,224 @@
+//! Location of wasm-slim's per-project state directory
+//!
+//! Caches, backups, build history and archived reports live in
+//! `.wasm-slim/` in the project root. When the project is checked out
+//! read-only (a Nix store path, a restored CI cache), that directory cannot
+//! be created, so the state moves to the user's cache directory (e.g.
+//! `~/.cache/wasm-slim/`), one per project. Only when there is no cache
+//! directory does it fall back to the system temp directory, in a directory
+//! created private (0700) and checked to be ours before use. `--state-dir`
+//! or `WASM_SLIM_STATE_DIR` pick the location explicitly.
+//!
+//! Throwaway builds (recommendation verification, feature and allocator
+//! trials, the allocation profiling harness, matrix builds and build
+//! checkpoints) go to [`scratch_dir`] inside the state directory, so they
+//! follow it when the project is read-only.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! let backups = wasm_slim::state::state_dir(Path::new(".")).join("backups");
+//! println!("Backups are kept in {}", backups.display());
+//! ```
+
+use crate::hash::sha256_hex;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// State directory name in the project root
+pub const STATE_DIR_NAME: &str = ".wasm-slim";
+
+/// Scratch directory name inside the state directory
+pub const SCRATCH_DIR_NAME: &str = "scratch";
+
+/// Environment variable naming the state directory
+pub const STATE_DIR_ENV_VAR: &str = "WASM_SLIM_STATE_DIR";
+
+/// State directory given with `--state-dir`, installed once from `main`
+static STATE_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// State directory already resolved for each project root
+static RESOLVED: OnceLock<Mutex<HashMap<PathBuf, PathBuf>>> = OnceLock::new();
+
+/// Use `path` as the state directory for the rest of the process
+///
+/// Returns `false` if an override was already installed.
+pub fn install_override(path: PathBuf) -> bool {
+    STATE_DIR_OVERRIDE.set(path).is_ok()
+}
+
+/// State directory for `project_root`
+///
+/// Precedence: `--state-dir`, then `WASM_SLIM_STATE_DIR` (relative to the
+/// project root), then `.wasm-slim/` in the project root when it is
+/// writable, then [`relocated_state_dir`]. The first time a project's state
+/// is relocated a note is printed to stderr.
+pub fn state_dir(project_root: &Path) -> PathBuf {
+    let env_path = std::env::var_os(STATE_DIR_ENV_VAR).filter(|v| !v.is_empty());
+    if let Some(path) = STATE_DIR_OVERRIDE
+        .get()
+        .cloned()
+        .or_else(|| env_path.map(PathBuf::from))
+    {
+        return project_root.join(path);
+    }
+
+    let resolved = RESOLVED.get_or_init(Default::default);
+    let mut resolved = resolved.lock().unwrap_or_else(|e| e.into_inner());
+    resolved
+        .entry(project_root.to_path_buf())
+        .or_insert_with(|| {
+            let local = project_root.join(STATE_DIR_NAME);
+            let probed = if local.is_dir() { &local } else { project_root };
+            if !probed.is_dir() || is_writable(probed) {
+                return local;
+            }
+            let relocated = relocated_state_dir(project_root);
+            if let Err(e) = ensure_private_dir(&relocated) {
+                eprintln!(
+                    "Warning: {} is read-only and {} cannot be used safely ({}); set {} to choose a state directory",
+                    project_root.display(),
+                    relocated.display(),
+                    e,
+                    STATE_DIR_ENV_VAR
+                );
+                return local;
+            }
+            eprintln!(
+                "Note: {} is read-only; wasm-slim state is kept in {} (set {} to choose)",
+                project_root.display(),
+                relocated.display(),
+                STATE_DIR_ENV_VAR
+            );
+            relocated
+        })
+        .clone()
+}
+
+/// Scratch directory for throwaway builds of `project_root`
+pub fn scratch_dir(project_root: &Path) -> PathBuf {
+    state_dir(project_root).join(SCRATCH_DIR_NAME)
+}
+
+/// Work directory used for a read-only project
+///
+/// `wasm-slim/<digest>` in the user's cache directory, or
+/// `wasm-slim-<digest>` in the system temp directory when there is none.
+pub fn relocated_state_dir(project_root: &Path) -> PathBuf {
+    let root = project_root
+        .canonicalize()
+        .unwrap_or_else(|_| project_root.to_path_buf());
+    let digest = sha256_hex(root.to_string_lossy().as_bytes());
+    match dirs::cache_dir() {
+        Some(cache) => cache.join("wasm-slim").join(&digest[..16]),
+        None => std::env::temp_dir().join(format!("wasm-slim-{}", &digest[..16])),
+    }
+}
+
+/// Make sure a relocated state directory is private to the current user
+///
+/// Directories in the user's cache directory are created as usual. In the
+/// shared temp directory, where another user could have created the path
+/// first, the directory is created with mode 0700; an existing one must be
+/// a real directory (not a symlink), grant nothing to group or others, and
+/// be writable by us, which for a 0700 directory means we own it.
+fn ensure_private_dir(dir: &Path) -> io::Result<()> {
+    let shared = dirs::cache_dir().is_none_or(|cache| !dir.starts_with(cache));
+    if !shared {
+        return std::fs::create_dir_all(dir);
+    }
+    create_private_dir(dir)
+}
+
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    let metadata = std::fs::symlink_metadata(dir)?;
+    if !metadata.is_dir() {
+        return Err(io::Error::other("not a directory"));
+    }
+    if metadata.permissions().mode() & 0o077 != 0 {
+        return Err(io::Error::other("accessible to other users"));
+    }
+    if !is_writable(dir) {
+        return Err(io::Error::other("owned by another user"));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(dir)
+}
+
+/// Whether files can be created in `dir`
+///
+/// Checked by creating and removing a probe file, since permission bits say
+/// nothing about read-only mounts and are ignored for root.
+pub fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".wasm-slim-probe-{}", std::process::id()));
+    match OpenOptions::new().write(true).create_new(true).open(&probe) {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_dir_stays_in_writable_project() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(is_writable(temp.path()));
+        assert_eq!(state_dir(temp.path()), temp.path().join(STATE_DIR_NAME));
+        assert!(!is_writable(&temp.path().join("missing")));
+    }
+
+    #[test]
+    fn test_relocated_state_dir_is_stable_per_project() {
+        let a = tempfile::tempdir().unwrap();
+        let b = tempfile::tempdir().unwrap();
+        assert_eq!(relocated_state_dir(a.path()), relocated_state_dir(a.path()));
+        assert_ne!(relocated_state_dir(a.path()), relocated_state_dir(b.path()));
+        let base = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+        assert!(relocated_state_dir(a.path()).starts_with(base));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_private_dir_is_created_0700_and_rejects_shared_ones() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let private = temp.path().join("private");
+        create_private_dir(&private).unwrap();
+        let mode = std::fs::metadata(&private).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        // Reusing our own private directory is fine
+        create_private_dir(&private).unwrap();
+
+        let shared = temp.path().join("shared");
+        std::fs::create_dir(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(create_private_dir(&shared).is_err());
+
+        let link = temp.path().join("link");
+        std::os::unix::fs::symlink(&private, &link).unwrap();
+        assert!(create_private_dir(&link).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! One-line exit summary for CI logs
+//!
+//! Every command ends with a single line on stderr, printed regardless of
+//! `--json`, `--no-pager` or `RUST_LOG`:
+//!
+//! ```text
+//! wasm-slim: build OK size=412KB gzip=148KB budget=OK(-12%) time=93s
+//! ```
+//!
+//! The line always starts with `wasm-slim: <command> <OK|FAILED>` and ends
+//! with `time=<seconds>s`. Commands add `key=value` fields in between with
+//! [`record`]; fields keep the order they were first recorded in, and values
+//! never contain spaces, so the line can be split on whitespace.
+//!
+//! # Examples
+//!
+//! ```
+//! use wasm_slim::summary::{self, ExitSummary};
+//! use std::time::Duration;
+//!
+//! summary::record("size", summary::format_kb(421_888));
+//! let line = ExitSummary::new("build", true)
+//!     .with_fields(summary::take())
+//!     .with_elapsed(Duration::from_secs(93))
+//!     .render();
+//! assert_eq!(line, "wasm-slim: build OK size=412KB time=93s");
+//! ```
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// Fields recorded by the running command
+static FIELDS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+/// Record a summary field, replacing an earlier value for the same key
+///
+/// Whitespace in the value is replaced with `_` to keep the line splittable.
+pub fn record(key: &str, value: impl Into<String>) {
+    record_into(&mut FIELDS.lock(), key, value.into());
+}
+
+/// [`record`] into `fields` instead of the process-wide list
+fn record_into(fields: &mut Vec<(String, String)>, key: &str, value: String) {
+    let value: String = value
+        .chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect();
+
+    match fields.iter_mut().find(|(k, _)| k == key) {
+        Some(field) => field.1 = value,
+        None => fields.push((key.to_string(), value)),
+    }
+}
+
+/// Take all recorded fields, leaving none behind
+pub fn take() -> Vec<(String, String)> {
+    std::mem::take(&mut *FIELDS.lock())
+}
+
+/// Format a size as whole kilobytes (`412KB`)
+pub fn format_kb(bytes: u64) -> String {
+    format!("{}KB", (bytes + 512) / 1024)
+}
+
+/// Format a budget check as `OK(-12%)` or `FAIL(+8%)`, relative to the limit
+pub fn format_budget(size_bytes: u64, limit_bytes: u64) -> String {
+    let status = if size_bytes <= limit_bytes {
+        "OK"
+    } else {
+        "FAIL"
+    };
+    if limit_bytes == 0 {
+        return status.to_string();
+    }
+    let delta = (size_bytes as f64 - limit_bytes as f64) / limit_bytes as f64 * 100.0;
+    format!("{}({:+.0}%)", status, delta)
+}
+
+/// Size of a file after gzip at level 9, or `None` if it cannot be read
+///
+/// Compressed in process, so the size does not depend on a `gzip` install.
+pub fn gzip_size(path: &Path) -> Option<u64> {
+    let bytes = std::fs::read(path).ok()?;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&bytes).ok()?;
+    Some(encoder.finish().ok()?.len() as u64)
+}
+
+/// Size of a file after brotli at quality 11, or `None` if it cannot be read
+///
+/// Compressed in process, so the size does not depend on a `brotli` install.
+pub fn brotli_size(path: &Path) -> Option<u64> {
+    let bytes = std::fs::read(path).ok()?;
+    let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+    encoder.write_all(&bytes).ok()?;
+    encoder.flush().ok()?;
+    Some(encoder.into_inner().len() as u64)
+}
+
+/// Transfer sizes of a file under the compressions browsers negotiate
+///
+/// Both sizes are `None` when the file cannot be read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressedSizes {
+    /// Size after gzip at level 9
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gzip_bytes: Option<u64>,
+    /// Size after brotli at quality 11
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brotli_bytes: Option<u64>,
+}
+
+impl CompressedSizes {
+    /// Measure a file with both compressions
+    pub fn measure(path: &Path) -> Self {
+        Self {
+            gzip_bytes: gzip_size(path),
+            brotli_bytes: brotli_size(path),
+        }
+    }
+
+    /// Whether the file could not be measured
+    pub fn is_empty(&self) -> bool {
+        self.gzip_bytes.is_none() && self.brotli_bytes.is_none()
+    }
+}
+
+/// Final status line of a command
+#[derive(Debug, Clone)]
+pub struct ExitSummary {
+    command: String,
+    success: bool,
+    fields: Vec<(String, String)>,
+    elapsed: Duration,
+}
+
+impl ExitSummary {
+    /// Create a summary for a finished command
+    pub fn new(command: &str, success: bool) -> Self {
+        Self {
+            command: command.to_string(),
+            success,
+            fields: Vec::new(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Attach recorded fields
+    pub fn with_fields(mut self, fields: Vec<(String, String)>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Set the wall time of the command
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Render the summary line
+    pub fn render(&self) -> String {
+        let mut line = format!(
+            "wasm-slim: {} {}",
+            self.command,
+            if self.success { "OK" } else { "FAILED" }
+        );
+        for (key, value) in &self.fields {
+            line.push_str(&format!(" {}={}", key, value));
+        }
+        line.push_str(&format!(" time={}s", self.elapsed.as_secs_f64().round()));
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_status_fields_and_time() {
+        let line = ExitSummary::new("build", true)
+            .with_fields(vec![
+                ("size".to_string(), "412KB".to_string()),
+                ("gzip".to_string(), "148KB".to_string()),
+                ("budget".to_string(), "OK(-12%)".to_string()),
+            ])
+            .with_elapsed(Duration::from_millis(92_600))
+            .render();
+        assert_eq!(
+            line,
+            "wasm-slim: build OK size=412KB gzip=148KB budget=OK(-12%) time=93s"
+        );
+
+        let failed = ExitSummary::new("analyze", false).render();
+        assert_eq!(failed, "wasm-slim: analyze FAILED time=0s");
+    }
+
+    #[test]
+    fn test_format_budget_reports_signed_delta() {
+        assert_eq!(format_budget(88, 100), "OK(-12%)");
+        assert_eq!(format_budget(108, 100), "FAIL(+8%)");
+        assert_eq!(format_budget(100, 100), "OK(+0%)");
+    }
+
+    #[test]
+    fn test_format_kb_rounds_to_nearest() {
+        assert_eq!(format_kb(421_888), "412KB");
+        assert_eq!(format_kb(511), "0KB");
+        assert_eq!(format_kb(512), "1KB");
+    }
+
+    #[test]
+    fn test_compressed_sizes_are_measured_in_process() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wasm = temp_dir.path().join("app.wasm");
+        std::fs::write(&wasm, vec![0u8; 64 * 1024]).unwrap();
+
+        let sizes = CompressedSizes::measure(&wasm);
+        let gzip = sizes.gzip_bytes.unwrap();
+        let brotli = sizes.brotli_bytes.unwrap();
+        assert!(gzip > 0 && gzip < 1024, "gzip size {}", gzip);
+        assert!(brotli > 0 && brotli < 1024, "brotli size {}", brotli);
+        assert!(!sizes.is_empty());
+    }
+
+    #[test]
+    fn test_compressed_sizes_of_missing_file_are_empty() {
+        let sizes = CompressedSizes::measure(Path::new("missing.wasm"));
+        assert!(sizes.is_empty());
+        assert_eq!(serde_json::to_string(&sizes).unwrap(), "{}");
+        assert_eq!(
+            serde_json::to_string(&CompressedSizes {
+                gzip_bytes: Some(148),
+                brotli_bytes: None,
+            })
+            .unwrap(),
+            r#"{"gzip_bytes":148}"#
+        );
+    }
+
+    #[test]
+    fn test_record_replaces_value_and_keeps_order() {
+        // A local list: the process-wide one is shared by tests running in parallel
+        let mut fields = Vec::new();
+        record_into(&mut fields, "a", "1".to_string());
+        record_into(&mut fields, "b", "two words".to_string());
+        record_into(&mut fields, "a", "3".to_string());
+
+        assert_eq!(
+            fields,
+            vec![
+                ("a".to_string(), "3".to_string()),
+                ("b".to_string(), "two_words".to_string()),
+            ]
+        );
+    }
+}
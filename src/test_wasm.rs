@@ -0,0 +1,52 @@
+//! Hand-assembled wasm modules for unit tests
+
+/// Magic number and version of a wasm module
+pub(crate) const HEADER: &[u8] = b"\0asm\x01\0\0\0";
+
+/// Unsigned LEB128 encoding
+pub(crate) fn leb(mut value: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return out;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Length-prefixed UTF-8 name
+pub(crate) fn string(s: &str) -> Vec<u8> {
+    let mut out = leb(s.len());
+    out.extend_from_slice(s.as_bytes());
+    out
+}
+
+/// Section with the given id and contents
+pub(crate) fn section(id: u8, contents: &[u8]) -> Vec<u8> {
+    let mut out = vec![id];
+    out.extend(leb(contents.len()));
+    out.extend_from_slice(contents);
+    out
+}
+
+/// Custom section with the given name and payload
+pub(crate) fn custom_section(name: &str, payload: &[u8]) -> Vec<u8> {
+    let mut contents = string(name);
+    contents.extend_from_slice(payload);
+    section(0, &contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leb_encodes_multi_byte_values() {
+        assert_eq!(leb(0), vec![0]);
+        assert_eq!(leb(624_485), vec![0xe5, 0x8e, 0x26]);
+        assert_eq!(custom_section("a", &[7]), vec![0, 3, 1, b'a', 7]);
+    }
+}
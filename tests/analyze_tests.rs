@@ -88,7 +88,7 @@ fn main() {
         .assert()
         .success()
         .stdout(predicate::str::contains("total_assets"))
-        .stdout(predicate::str::contains("total_size_kb"));
+        .stdout(predicate::str::contains("total_size_bytes"));
 }
 
 #[test]
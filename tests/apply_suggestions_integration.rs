@@ -4,7 +4,7 @@
 //! These tests verify that the full optimization pipeline works correctly with
 //! real Cargo.toml files and actual dependency reports.
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
@@ -73,7 +73,10 @@ fn create_empty_report() -> DependencyReport {
         issues: vec![],
         total_deps: 0,
         direct_deps: 0,
-        duplicates: HashMap::new(),
+        duplicates: BTreeMap::new(),
+        duplicate_versions: Vec::new(),
+        warnings: Vec::new(),
+        rule_violations: Vec::new(),
     }
 }
 
@@ -88,10 +91,14 @@ fn create_swc_issue_report() -> DependencyReport {
             suggestion: "Consider using minimal features or alternatives".to_string(),
             size_impact_kb: Some((2500, 500)),
             savings_percent: Some(80),
+            verify_id: None,
         }],
         total_deps: 1,
         direct_deps: 1,
-        duplicates: HashMap::new(),
+        duplicates: BTreeMap::new(),
+        duplicate_versions: Vec::new(),
+        warnings: Vec::new(),
+        rule_violations: Vec::new(),
     }
 }
 
@@ -106,10 +113,14 @@ fn create_getrandom_issue_report() -> DependencyReport {
             suggestion: "Add 'js' feature for WASM compatibility".to_string(),
             size_impact_kb: Some((50, 20)),
             savings_percent: Some(60),
+            verify_id: None,
         }],
         total_deps: 1,
         direct_deps: 1,
-        duplicates: HashMap::new(),
+        duplicates: BTreeMap::new(),
+        duplicate_versions: Vec::new(),
+        warnings: Vec::new(),
+        rule_violations: Vec::new(),
     }
 }
 
@@ -125,6 +136,7 @@ fn create_multiple_issues_report() -> DependencyReport {
                 suggestion: "Use minimal features".to_string(),
                 size_impact_kb: Some((2500, 500)),
                 savings_percent: Some(80),
+                verify_id: None,
             },
             DependencyIssue {
                 package: "getrandom".to_string(),
@@ -134,11 +146,15 @@ fn create_multiple_issues_report() -> DependencyReport {
                 suggestion: "Add 'js' feature".to_string(),
                 size_impact_kb: Some((50, 20)),
                 savings_percent: Some(60),
+                verify_id: None,
             },
         ],
         total_deps: 2,
         direct_deps: 2,
-        duplicates: HashMap::new(),
+        duplicates: BTreeMap::new(),
+        duplicate_versions: Vec::new(),
+        warnings: Vec::new(),
+        rule_violations: Vec::new(),
     }
 }
 
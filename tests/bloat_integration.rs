@@ -63,6 +63,7 @@ fn test_bloat_results_structure() {
             name: "large_function".to_string(),
             crate_name: Some("my_crate".to_string()),
         }],
+        crates: Vec::new(),
         recommendations: vec![],
     };
 
@@ -154,6 +155,7 @@ fn test_bloat_results_multiple_items() {
                 crate_name: Some("core".to_string()),
             },
         ],
+        crates: Vec::new(),
         recommendations: vec![],
     };
 
@@ -168,13 +170,15 @@ fn test_recommendation_structure() {
     let rec = Recommendation {
         priority: "P2".to_string(),
         description: "Large function detected - consider refactoring".to_string(),
-        estimated_savings_kb: 100,
+        estimated_savings_bytes: 100 * 1024,
         estimated_savings_percent: 5.0,
+        rule: String::new(),
+        subject: None,
     };
 
     assert!(!rec.description.is_empty());
     assert_eq!(rec.priority, "P2");
-    assert_eq!(rec.estimated_savings_kb, 100);
+    assert_eq!(rec.estimated_savings_bytes, 100 * 1024);
     assert_eq!(rec.estimated_savings_percent, 5.0);
 }
 
@@ -202,6 +206,7 @@ fn test_bloat_results_empty_items() {
         total_size_bytes: 1000000,
         text_size_bytes: 800000,
         items: vec![],
+        crates: Vec::new(),
         recommendations: vec![],
     };
 
@@ -302,17 +307,23 @@ fn test_bloat_results_with_recommendations() {
             name: "regex::Regex::new".to_string(),
             crate_name: Some("regex".to_string()),
         }],
+        crates: Vec::new(),
         recommendations: vec![Recommendation {
             priority: "P1".to_string(),
             description: "Large regex function detected - consider using simpler string operations"
                 .to_string(),
-            estimated_savings_kb: 50,
+            estimated_savings_bytes: 50 * 1024,
             estimated_savings_percent: 10.0,
+            rule: String::new(),
+            subject: None,
         }],
     };
 
     assert_eq!(results.recommendations.len(), 1);
     assert!(!results.recommendations[0].description.is_empty());
     assert_eq!(results.recommendations[0].priority, "P1");
-    assert_eq!(results.recommendations[0].estimated_savings_kb, 50);
+    assert_eq!(
+        results.recommendations[0].estimated_savings_bytes,
+        50 * 1024
+    );
 }